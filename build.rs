@@ -108,9 +108,17 @@ fn main() {
         println!(
             "cargo:rerun-if-changed=proto/opentelemetry/proto/collector/logs/v1/logs_service.proto"
         );
+        println!(
+            "cargo:rerun-if-changed=proto/opentelemetry/proto/collector/metrics/v1/metrics_service.proto"
+        );
+        println!(
+            "cargo:rerun-if-changed=proto/opentelemetry/proto/collector/trace/v1/trace_service.proto"
+        );
         println!("cargo:rerun-if-changed=proto/opentelemetry/proto/common/v1/common.proto");
         println!("cargo:rerun-if-changed=proto/opentelemetry/proto/logs/v1/logs.proto");
+        println!("cargo:rerun-if-changed=proto/opentelemetry/proto/metrics/v1/metrics.proto");
         println!("cargo:rerun-if-changed=proto/opentelemetry/proto/resource/v1/resource.proto");
+        println!("cargo:rerun-if-changed=proto/opentelemetry/proto/trace/v1/trace.proto");
 
         let mut prost_build = prost_build::Config::new();
         prost_build.btree_map(&["."]);
@@ -127,12 +135,28 @@ fn main() {
                     "proto/google/pubsub/v1/pubsub.proto",
                     "proto/vector.proto",
                     "proto/opentelemetry/proto/collector/logs/v1/logs_service.proto",
+                    "proto/opentelemetry/proto/collector/metrics/v1/metrics_service.proto",
+                    "proto/opentelemetry/proto/collector/trace/v1/trace_service.proto",
                 ],
                 &["proto/", "lib/vector-core/proto/"],
             )
             .unwrap();
     }
 
+    // The eBPF programs are only built for Linux, which is the only target the source supports.
+    #[cfg(feature = "sources-ebpf")]
+    if env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("linux") {
+        println!("cargo:rerun-if-changed=src/sources/ebpf/bpf/telemetry.bpf.c");
+        println!("cargo:rerun-if-changed=src/sources/ebpf/bpf/telemetry.h");
+
+        let out_dir =
+            env::var("OUT_DIR").expect("Cargo-provided environment variables should always exist!");
+        libbpf_cargo::SkeletonBuilder::new()
+            .source("src/sources/ebpf/bpf/telemetry.bpf.c")
+            .build_and_generate(Path::new(&out_dir).join("telemetry.skel.rs"))
+            .unwrap();
+    }
+
     // We keep track of which environment variables we slurp in, and then emit stanzas at the end to
     // inform Cargo when it needs to rerun this build script.  This allows us to avoid rerunning it
     // every single time unless something _actually_ changes.
@@ -0,0 +1,46 @@
+//! Contains common definitions for CEF codec support
+
+/// CEF header fields, in the order they appear in a message. Definitions from
+/// https://www.microfocus.com/documentation/arcsight/arcsight-smartconnectors/pdfdoc/common-event-format-v25/common-event-format-v25.pdf
+pub mod cef_fields {
+
+    /// <not a field> The prefix every CEF message starts with, possibly after a syslog header.
+    pub const CEF_PREFIX: &str = "CEF:";
+
+    /// <not a field> The CEF format version emitted when the event doesn't provide one.
+    pub const CEF_VERSION: &str = "0";
+
+    /// (required) The version of the CEF format.
+    pub const VERSION: &str = "cefVersion";
+
+    /// (required) The vendor of the sending device.
+    pub const DEVICE_VENDOR: &str = "deviceVendor";
+
+    /// (required) The product name of the sending device.
+    pub const DEVICE_PRODUCT: &str = "deviceProduct";
+
+    /// (required) The version of the sending device.
+    pub const DEVICE_VERSION: &str = "deviceVersion";
+
+    /// (required) A unique identifier of the type of event reported, also known as Signature ID.
+    pub const DEVICE_EVENT_CLASS_ID: &str = "deviceEventClassId";
+
+    /// (required) A human-readable description of the event.
+    pub const NAME: &str = "name";
+
+    /// (required) The importance of the event, either 0-10 or Unknown, Low, Medium, High or Very-High.
+    pub const SEVERITY: &str = "severity";
+
+    /// All of the header fields, in order.
+    pub const HEADER_FIELDS: [&str; 7] = [
+        VERSION,
+        DEVICE_VENDOR,
+        DEVICE_PRODUCT,
+        DEVICE_VERSION,
+        DEVICE_EVENT_CLASS_ID,
+        NAME,
+        SEVERITY,
+    ];
+
+    // < Every other field is an extension, encoded as `key=value` pairs separated by spaces. >
+}
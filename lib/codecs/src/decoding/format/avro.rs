@@ -0,0 +1,660 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    future::Future,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use avro_rs::{types::Value as AvroValue, Schema};
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use futures::future::{BoxFuture, FutureExt, Shared};
+use ordered_float::NotNan;
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+use value::Kind;
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, DataType, LogNamespace},
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+use super::Deserializer;
+
+/// The first byte of a message in the Confluent wire format.
+const CONFLUENT_MAGIC_BYTE: u8 = 0;
+
+/// The length of the Confluent wire format header: the magic byte followed by the big-endian
+/// schema ID.
+const CONFLUENT_HEADER_LENGTH: usize = 5;
+
+/// How long a request to the Schema Registry may take.
+const REGISTRY_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a schema which failed to be resolved isn't requested again, doubled after each
+/// consecutive failure up to `MAX_RETRY_BACKOFF`.
+const INITIAL_RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+/// The longest time a schema which failed to be resolved isn't requested again.
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Config used to build an `AvroDeserializer`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AvroDeserializerConfig {
+    /// Options for the Avro deserializer.
+    pub avro: AvroDeserializerOptions,
+}
+
+impl AvroDeserializerConfig {
+    /// Creates a new `AvroDeserializerConfig`.
+    pub const fn new(schema_registry_url: String) -> Self {
+        Self {
+            avro: AvroDeserializerOptions {
+                schema_registry_url,
+            },
+        }
+    }
+
+    /// Build the `AvroDeserializer` from this configuration.
+    pub fn build(&self) -> AvroDeserializer {
+        AvroDeserializer::new(self.avro.schema_registry_url.clone())
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        // TODO: Convert the Avro schemas to a vector schema definition. The schemas are only
+        // known once the messages referencing them are received, though.
+        match log_namespace {
+            LogNamespace::Legacy => schema::Definition::empty_legacy_namespace()
+                .with_field(
+                    log_schema().timestamp_key(),
+                    // The timestamp is only inserted if the record doesn't contain this field.
+                    Kind::any(),
+                    Some("timestamp"),
+                )
+                .unknown_fields(Kind::any()),
+            LogNamespace::Vector => schema::Definition::new(Kind::any(), [log_namespace]),
+        }
+    }
+}
+
+/// Apache Avro deserializer options.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct AvroDeserializerOptions {
+    /// The URL of the Confluent Schema Registry to resolve the writer schemas from.
+    ///
+    /// Messages are expected to be in the Confluent wire format, that is prefixed with a zero
+    /// byte and the big-endian ID of the schema they were written with. Schemas are fetched
+    /// once per ID and cached for the lifetime of the component.
+    ///
+    /// The `kafka` source fetches the schemas before decoding the messages. Other sources fetch
+    /// them in the background, and fail to decode the messages referencing a schema until it is
+    /// fetched.
+    ///
+    /// Requests time out after 10 seconds. When a schema fails to be fetched, the messages
+    /// referencing it fail to be decoded without requesting it again for an exponential backoff
+    /// of one second up to one minute.
+    pub schema_registry_url: String,
+}
+
+/// The response of the Schema Registry for `GET /schemas/ids/{id}`.
+#[derive(Deserialize)]
+struct RegistrySchema {
+    schema: String,
+}
+
+/// A request to the Schema Registry for a schema, shared by the concurrent lookups of the schema.
+type SchemaRequest = Shared<BoxFuture<'static, Result<Arc<Schema>, String>>>;
+
+/// A schema of the cache of an `AvroDeserializer`.
+enum CachedSchema {
+    Resolved(Arc<Schema>),
+    /// The schema is being fetched from the Schema Registry.
+    Pending(SchemaRequest),
+    /// The schema failed to be resolved, and isn't requested again until `retry_at`.
+    Failed {
+        error: String,
+        retry_at: Instant,
+        backoff: Duration,
+    },
+}
+
+impl fmt::Debug for CachedSchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Resolved(schema) => f.debug_tuple("Resolved").field(schema).finish(),
+            Self::Pending(_) => f.write_str("Pending"),
+            Self::Failed {
+                error,
+                retry_at,
+                backoff,
+            } => f
+                .debug_struct("Failed")
+                .field("error", error)
+                .field("retry_at", retry_at)
+                .field("backoff", backoff)
+                .finish(),
+        }
+    }
+}
+
+/// The result of looking a schema up in the cache of an `AvroDeserializer`.
+enum Lookup {
+    Resolved(Arc<Schema>),
+    Pending(SchemaRequest),
+    Failed(String),
+    /// The schema isn't cached, and no request was started for it.
+    Missing,
+}
+
+/// Deserializer that builds `Event`s from a byte frame containing a Confluent wire format Avro
+/// message.
+///
+/// Parsing a message never waits for the Schema Registry: the schema it references is fetched
+/// in the background if it isn't cached yet, and the message fails to be parsed in the meantime.
+/// Sources can resolve the schemas before parsing the messages with [`Self::prefetch`].
+#[derive(Debug, Clone)]
+pub struct AvroDeserializer {
+    schema_registry_url: String,
+    client: reqwest::Client,
+    /// Schemas resolved so far, requests in flight, and failed lookups, by ID. Shared between
+    /// clones of the deserializer.
+    schemas: Arc<RwLock<HashMap<u32, CachedSchema>>>,
+}
+
+impl AvroDeserializer {
+    /// Creates a new `AvroDeserializer`.
+    pub fn new(schema_registry_url: String) -> Self {
+        Self {
+            schema_registry_url,
+            client: reqwest::Client::new(),
+            schemas: Default::default(),
+        }
+    }
+
+    /// Resolves the schema referenced by the message, so that parsing it doesn't fail while the
+    /// schema is fetched.
+    ///
+    /// Errors are reported when the message is parsed.
+    pub async fn prefetch(&self, message: &[u8]) {
+        if let Some(id) = schema_id(message) {
+            let _ = self.resolve_schema(id).await;
+        }
+    }
+
+    /// Returns the schema with the given ID, fetching it from the Schema Registry on first use.
+    ///
+    /// Concurrent lookups of a schema wait for the same request. Failed lookups are cached, and
+    /// the schema is only requested again once their backoff elapsed.
+    pub async fn resolve_schema(&self, id: u32) -> Result<Arc<Schema>, String> {
+        match self.lookup(id, true) {
+            Lookup::Resolved(schema) => Ok(schema),
+            Lookup::Pending(request) => request.await,
+            Lookup::Failed(error) => Err(error),
+            Lookup::Missing => unreachable!("the schema is requested when it is missing"),
+        }
+    }
+
+    /// Looks the schema with the given ID up in the cache.
+    ///
+    /// If `request` is set, and the schema isn't cached or its backoff elapsed, a request for it
+    /// is started. The request is spawned on the current runtime, if any, so that it completes
+    /// even if no lookup waits for it.
+    fn lookup(&self, id: u32, request: bool) -> Lookup {
+        if let Some(CachedSchema::Resolved(schema)) =
+            self.schemas.read().expect("lock poisoned").get(&id)
+        {
+            return Lookup::Resolved(Arc::clone(schema));
+        }
+
+        let mut schemas = self.schemas.write().expect("lock poisoned");
+        let previous_backoff = match schemas.get(&id) {
+            Some(CachedSchema::Resolved(schema)) => return Lookup::Resolved(Arc::clone(schema)),
+            Some(CachedSchema::Pending(request)) => return Lookup::Pending(request.clone()),
+            Some(CachedSchema::Failed {
+                error,
+                retry_at,
+                backoff,
+            }) => {
+                if Instant::now() < *retry_at {
+                    return Lookup::Failed(error.clone());
+                }
+                Some(*backoff)
+            }
+            None => None,
+        };
+        if !request {
+            return Lookup::Missing;
+        }
+
+        let request = self.request_schema(id, previous_backoff).boxed().shared();
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            handle.spawn(request.clone());
+        }
+        schemas.insert(id, CachedSchema::Pending(request.clone()));
+        Lookup::Pending(request)
+    }
+
+    /// Fetches the schema with the given ID from the Schema Registry, and caches the result.
+    fn request_schema(
+        &self,
+        id: u32,
+        previous_backoff: Option<Duration>,
+    ) -> impl Future<Output = Result<Arc<Schema>, String>> + Send + 'static {
+        let url = format!(
+            "{}/schemas/ids/{}",
+            self.schema_registry_url.trim_end_matches('/'),
+            id
+        );
+        let request = self
+            .client
+            .get(url)
+            .timeout(REGISTRY_REQUEST_TIMEOUT)
+            .send();
+        let schemas = Arc::clone(&self.schemas);
+
+        async move {
+            let response = async {
+                request
+                    .await?
+                    .error_for_status()?
+                    .json::<RegistrySchema>()
+                    .await
+            };
+            let result = response
+                .await
+                .map_err(|error| format!("Failed fetching Avro schema {}: {}", id, error))
+                .and_then(|response| {
+                    Schema::parse_str(&response.schema)
+                        .map(Arc::new)
+                        .map_err(|error| format!("Failed parsing Avro schema {}: {}", id, error))
+                });
+
+            let cached = match &result {
+                Ok(schema) => CachedSchema::Resolved(Arc::clone(schema)),
+                Err(error) => {
+                    let backoff = previous_backoff.map_or(INITIAL_RETRY_BACKOFF, |backoff| {
+                        (backoff * 2).min(MAX_RETRY_BACKOFF)
+                    });
+                    CachedSchema::Failed {
+                        error: error.clone(),
+                        retry_at: Instant::now() + backoff,
+                        backoff,
+                    }
+                }
+            };
+            schemas.write().expect("lock poisoned").insert(id, cached);
+
+            result
+        }
+    }
+}
+
+/// Returns the ID of the schema of a message in the Confluent wire format.
+fn schema_id(message: &[u8]) -> Option<u32> {
+    match message {
+        [CONFLUENT_MAGIC_BYTE, a, b, c, d, ..] => Some(u32::from_be_bytes([*a, *b, *c, *d])),
+        _ => None,
+    }
+}
+
+impl Deserializer for AvroDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_core::Result<SmallVec<[Event; 1]>> {
+        // Tombstones and other empty messages don't carry any event.
+        if bytes.is_empty() {
+            return Ok(smallvec![]);
+        }
+
+        let schema_id = schema_id(&bytes).ok_or("Message is not in the Confluent wire format")?;
+        // Schemas can only be fetched in the background from within a runtime.
+        let request = tokio::runtime::Handle::try_current().is_ok();
+        let schema = match self.lookup(schema_id, request) {
+            Lookup::Resolved(schema) => schema,
+            Lookup::Pending(_) => {
+                return Err(format!(
+                    "Avro schema {} is being fetched from the Schema Registry",
+                    schema_id
+                )
+                .into())
+            }
+            Lookup::Failed(error) => return Err(error.into()),
+            Lookup::Missing => {
+                return Err(format!(
+                    "Avro schema {} isn't resolved, and can't be fetched outside of a runtime",
+                    schema_id
+                )
+                .into())
+            }
+        };
+
+        let mut datum = &bytes[CONFLUENT_HEADER_LENGTH..];
+        let value = avro_rs::from_avro_datum(&schema, &mut datum, None)
+            .map_err(|error| format!("Error parsing Avro: {}", error))?;
+        let value = avro_to_value(value)?;
+
+        let log = match log_namespace {
+            LogNamespace::Vector => LogEvent::from(value),
+            LogNamespace::Legacy => {
+                let mut log = match value {
+                    value @ Value::Object(_) => LogEvent::from(value),
+                    value => {
+                        let mut log = LogEvent::from(Value::Object(BTreeMap::new()));
+                        log.insert(log_schema().message_key(), value);
+                        log
+                    }
+                };
+                let timestamp_key = log_schema().timestamp_key();
+                if !log.contains(timestamp_key) {
+                    log.insert(timestamp_key, Utc::now());
+                }
+                log
+            }
+        };
+
+        Ok(smallvec![Event::Log(log)])
+    }
+}
+
+/// Converts a decoded Avro value to a Vector value.
+fn avro_to_value(value: AvroValue) -> vector_core::Result<Value> {
+    fn float(value: f64) -> vector_core::Result<Value> {
+        NotNan::new(value)
+            .map(Value::Float)
+            .map_err(|_| "NaN float values are not supported".into())
+    }
+
+    Ok(match value {
+        AvroValue::Null => Value::Null,
+        AvroValue::Boolean(value) => Value::Boolean(value),
+        AvroValue::Int(value) | AvroValue::Date(value) | AvroValue::TimeMillis(value) => {
+            Value::Integer(i64::from(value))
+        }
+        AvroValue::Long(value) | AvroValue::TimeMicros(value) => Value::Integer(value),
+        AvroValue::Float(value) => float(f64::from(value))?,
+        AvroValue::Double(value) => float(value)?,
+        AvroValue::Bytes(value) | AvroValue::Fixed(_, value) => Value::Bytes(value.into()),
+        AvroValue::String(value) | AvroValue::Enum(_, value) => Value::Bytes(value.into()),
+        AvroValue::Uuid(value) => Value::Bytes(value.to_string().into()),
+        AvroValue::TimestampMillis(value) => Value::Timestamp(Utc.timestamp_millis(value)),
+        AvroValue::TimestampMicros(value) => Value::Timestamp(Utc.timestamp_nanos(value * 1_000)),
+        AvroValue::Union(value) => avro_to_value(*value)?,
+        AvroValue::Array(values) => Value::Array(
+            values
+                .into_iter()
+                .map(avro_to_value)
+                .collect::<vector_core::Result<_>>()?,
+        ),
+        AvroValue::Map(values) => Value::Object(
+            values
+                .into_iter()
+                .map(|(key, value)| Ok((key, avro_to_value(value)?)))
+                .collect::<vector_core::Result<_>>()?,
+        ),
+        AvroValue::Record(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| Ok((key, avro_to_value(value)?)))
+                .collect::<vector_core::Result<_>>()?,
+        ),
+        value => return Err(format!("Unsupported Avro value: {:?}", value).into()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use indoc::indoc;
+
+    use super::*;
+
+    const SCHEMA_ID: u32 = 42;
+
+    fn deserializer() -> AvroDeserializer {
+        let schema = Schema::parse_str(indoc! {r#"
+            {
+                "type": "record",
+                "name": "Log",
+                "fields": [
+                    { "name": "message", "type": "string" },
+                    { "name": "count", "type": ["null", "long"] }
+                ]
+            }
+        "#})
+        .unwrap();
+
+        // Avoids hitting the Schema Registry by seeding the cache.
+        let deserializer = AvroDeserializer::new("http://localhost:8081".to_owned());
+        deserializer
+            .schemas
+            .write()
+            .unwrap()
+            .insert(SCHEMA_ID, CachedSchema::Resolved(Arc::new(schema)));
+        deserializer
+    }
+
+    fn message(deserializer: &AvroDeserializer) -> Bytes {
+        let schema = match deserializer.lookup(SCHEMA_ID, false) {
+            Lookup::Resolved(schema) => schema,
+            _ => panic!("the schema isn't cached"),
+        };
+        let record = AvroValue::Record(vec![
+            ("message".to_owned(), AvroValue::String("foo".to_owned())),
+            (
+                "count".to_owned(),
+                AvroValue::Union(Box::new(AvroValue::Long(3))),
+            ),
+        ]);
+
+        let mut bytes = vec![CONFLUENT_MAGIC_BYTE];
+        bytes.extend_from_slice(&SCHEMA_ID.to_be_bytes());
+        bytes.extend(avro_rs::to_avro_datum(&schema, record).unwrap());
+        bytes.into()
+    }
+
+    #[test]
+    fn deserialize_avro_legacy_namespace() {
+        let deserializer = deserializer();
+        let events = deserializer
+            .parse(message(&deserializer), LogNamespace::Legacy)
+            .unwrap();
+        assert_eq!(events.len(), 1);
+
+        let log = events[0].as_log();
+        assert_eq!(log["message"], "foo".into());
+        assert_eq!(log["count"], 3.into());
+        assert!(log[log_schema().timestamp_key()].is_timestamp());
+    }
+
+    #[test]
+    fn deserialize_avro_vector_namespace() {
+        let deserializer = deserializer();
+        let events = deserializer
+            .parse(message(&deserializer), LogNamespace::Vector)
+            .unwrap();
+        assert_eq!(events.len(), 1);
+
+        let log = events[0].as_log();
+        assert_eq!(log["message"], "foo".into());
+        assert_eq!(log["count"], 3.into());
+        assert!(!log.contains(log_schema().timestamp_key()));
+    }
+
+    #[test]
+    fn deserialize_avro_skips_empty_messages() {
+        let events = deserializer()
+            .parse(Bytes::new(), LogNamespace::Legacy)
+            .unwrap();
+        assert!(events.is_empty());
+    }
+
+    /// Serves the schema of the given ID to every request, and returns the address of the server
+    /// and the number of requests it received.
+    fn schema_registry(schema: &'static str) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+
+        let counter = Arc::clone(&requests);
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                counter.fetch_add(1, Ordering::SeqCst);
+                let mut request = [0; 1024];
+                let _ = stream.read(&mut request).unwrap();
+                // Lets the concurrent lookups start before the schema is served.
+                std::thread::sleep(Duration::from_millis(100));
+
+                let body = serde_json::json!({ "schema": schema }).to_string();
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        (address, requests)
+    }
+
+    fn header() -> Bytes {
+        let mut bytes = vec![CONFLUENT_MAGIC_BYTE];
+        bytes.extend_from_slice(&SCHEMA_ID.to_be_bytes());
+        bytes.into()
+    }
+
+    #[tokio::test]
+    async fn deserialize_avro_shares_schema_requests() {
+        let (address, requests) = schema_registry(r#"{ "type": "string" }"#);
+        let deserializer = AvroDeserializer::new(address);
+
+        // Parsing doesn't wait for the schema, which is fetched in the background.
+        let error = deserializer
+            .parse(header(), LogNamespace::Legacy)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            error,
+            "Avro schema 42 is being fetched from the Schema Registry"
+        );
+
+        let (first, second) = futures::future::join(
+            deserializer.resolve_schema(SCHEMA_ID),
+            deserializer.clone().resolve_schema(SCHEMA_ID),
+        )
+        .await;
+        assert_eq!(first.unwrap(), second.unwrap());
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        let mut message = header().to_vec();
+        message.extend(
+            avro_rs::to_avro_datum(&Schema::String, AvroValue::String("foo".to_owned())).unwrap(),
+        );
+        let events = deserializer
+            .parse(message.into(), LogNamespace::Legacy)
+            .unwrap();
+        assert_eq!(events[0].as_log()["message"], "foo".into());
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn deserialize_avro_prefetches_schemas() {
+        let (address, requests) = schema_registry(r#"{ "type": "string" }"#);
+        let deserializer = AvroDeserializer::new(address);
+
+        let mut message = header().to_vec();
+        message.extend(
+            avro_rs::to_avro_datum(&Schema::String, AvroValue::String("foo".to_owned())).unwrap(),
+        );
+        deserializer.prefetch(&message).await;
+
+        let events = deserializer
+            .parse(message.into(), LogNamespace::Legacy)
+            .unwrap();
+        assert_eq!(events[0].as_log()["message"], "foo".into());
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn deserialize_avro_outside_of_a_runtime() {
+        let deserializer = AvroDeserializer::new("http://127.0.0.1:1".to_owned());
+        let error = deserializer
+            .parse(header(), LogNamespace::Legacy)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            error,
+            "Avro schema 42 isn't resolved, and can't be fetched outside of a runtime"
+        );
+        assert!(deserializer.schemas.read().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn deserialize_avro_caches_failed_lookups() {
+        // Nothing listens on the port, so the lookup fails right away.
+        let deserializer = AvroDeserializer::new("http://127.0.0.1:1".to_owned());
+
+        let error = deserializer.resolve_schema(SCHEMA_ID).await.unwrap_err();
+        assert!(
+            error.starts_with("Failed fetching Avro schema 42"),
+            "{}",
+            error
+        );
+        let retry_at = match deserializer.schemas.read().unwrap().get(&SCHEMA_ID) {
+            Some(CachedSchema::Failed {
+                retry_at, backoff, ..
+            }) => {
+                assert_eq!(*backoff, INITIAL_RETRY_BACKOFF);
+                *retry_at
+            }
+            cached => panic!("unexpected cached schema {:?}", cached),
+        };
+
+        // The schema isn't requested again during the backoff.
+        let cached_error = deserializer
+            .parse(header(), LogNamespace::Legacy)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(cached_error, error);
+        assert!(matches!(
+            deserializer.schemas.read().unwrap().get(&SCHEMA_ID),
+            Some(CachedSchema::Failed { retry_at: cached_retry_at, .. }) if *cached_retry_at == retry_at
+        ));
+
+        // Once the backoff elapsed, the schema is requested again, doubling the backoff.
+        if let Some(CachedSchema::Failed { retry_at, .. }) =
+            deserializer.schemas.write().unwrap().get_mut(&SCHEMA_ID)
+        {
+            *retry_at = Instant::now();
+        }
+        assert!(deserializer.resolve_schema(SCHEMA_ID).await.is_err());
+        assert!(matches!(
+            deserializer.schemas.read().unwrap().get(&SCHEMA_ID),
+            Some(CachedSchema::Failed { backoff, .. }) if *backoff == INITIAL_RETRY_BACKOFF * 2
+        ));
+    }
+
+    #[test]
+    fn deserialize_avro_rejects_missing_header() {
+        let result = deserializer().parse(Bytes::from_static(b"{}"), LogNamespace::Legacy);
+        assert!(result.is_err());
+    }
+}
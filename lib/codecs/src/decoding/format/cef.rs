@@ -0,0 +1,290 @@
+use bytes::Bytes;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+use std::collections::BTreeMap;
+use value::{kind::Collection, Kind};
+use vector_core::{
+    config::{log_schema, DataType, LogNamespace},
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+use super::Deserializer;
+use crate::cef_fields::*;
+
+/// Config used to build a `CefDeserializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct CefDeserializerConfig;
+
+impl CefDeserializerConfig {
+    /// Creates a new `CefDeserializerConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `CefDeserializer` from this configuration.
+    pub const fn build(&self) -> CefDeserializer {
+        CefDeserializer
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        let definition = match log_namespace {
+            LogNamespace::Legacy => schema::Definition::empty_legacy_namespace().with_field(
+                log_schema().timestamp_key(),
+                // The timestamp is only inserted if the message has no extension of that name.
+                Kind::bytes().or_timestamp(),
+                Some("timestamp"),
+            ),
+            LogNamespace::Vector => {
+                schema::Definition::new(Kind::object(Collection::empty()), [log_namespace])
+            }
+        };
+
+        HEADER_FIELDS
+            .iter()
+            .fold(definition, |definition, field| {
+                definition.with_field(*field, Kind::bytes(), None)
+            })
+            // Extensions are placed at the root, next to the header fields.
+            .unknown_fields(Kind::bytes())
+    }
+}
+
+/// Deserializer that builds an `Event` from a byte frame containing a CEF message.
+#[derive(Debug, Clone, Default)]
+pub struct CefDeserializer;
+
+impl CefDeserializer {
+    /// Creates a new `CefDeserializer`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Deserializer for CefDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_core::Result<SmallVec<[Event; 1]>> {
+        let line = std::str::from_utf8(&bytes)?;
+        let line = line.trim_end_matches(&['\r', '\n'][..]);
+
+        let fields = parse_message(line)?;
+        let mut log = LogEvent::from(Value::Object(fields));
+
+        if log_namespace == LogNamespace::Legacy {
+            let timestamp_key = log_schema().timestamp_key();
+            if !log.contains(timestamp_key) {
+                log.insert(timestamp_key, Utc::now());
+            }
+        }
+
+        Ok(smallvec![Event::Log(log)])
+    }
+}
+
+/// Parses a CEF message, possibly preceded by a syslog header, into its header fields and
+/// extensions.
+fn parse_message(line: &str) -> vector_core::Result<BTreeMap<String, Value>> {
+    let start = line
+        .find(CEF_PREFIX)
+        .ok_or("Message does not contain a CEF header")?;
+    let message = &line[start + CEF_PREFIX.len()..];
+
+    let mut fields = BTreeMap::new();
+    let (header, extension) = split_header(message)?;
+    for (key, value) in parse_extension(extension)? {
+        fields.insert(key, Value::from(value));
+    }
+    // Header fields take precedence over extensions of the same name.
+    for (key, value) in HEADER_FIELDS.iter().zip(header) {
+        fields.insert((*key).to_owned(), Value::from(value));
+    }
+
+    Ok(fields)
+}
+
+/// Splits the header fields, unescaped, from the extension.
+fn split_header(message: &str) -> vector_core::Result<(Vec<String>, &str)> {
+    let mut header = Vec::with_capacity(HEADER_FIELDS.len());
+    let mut field = String::new();
+    let mut chars = message.char_indices();
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some((_, escaped @ ('\\' | '|'))) => field.push(escaped),
+                Some((_, other)) => {
+                    field.push('\\');
+                    field.push(other);
+                }
+                None => field.push('\\'),
+            },
+            '|' => {
+                header.push(std::mem::take(&mut field));
+                if header.len() == HEADER_FIELDS.len() {
+                    return Ok((header, &message[index + 1..]));
+                }
+            }
+            c => field.push(c),
+        }
+    }
+
+    Err(format!(
+        "CEF header must have {} fields separated by `|`, found {}",
+        HEADER_FIELDS.len(),
+        header.len()
+    )
+    .into())
+}
+
+/// Parses the `key=value` pairs of the extension. Values may contain spaces, so a value ends
+/// where the key of the next pair starts.
+fn parse_extension(extension: &str) -> vector_core::Result<Vec<(String, String)>> {
+    let mut separators = Vec::new();
+    let mut escaped = false;
+    for (index, byte) in extension.bytes().enumerate() {
+        match byte {
+            _ if escaped => escaped = false,
+            b'\\' => escaped = true,
+            b'=' => separators.push(index),
+            _ => {}
+        }
+    }
+
+    if separators.is_empty() {
+        return if extension.trim().is_empty() {
+            Ok(Vec::new())
+        } else {
+            Err("CEF extension must consist of `key=value` pairs".into())
+        };
+    }
+
+    let mut pairs = Vec::with_capacity(separators.len());
+    let mut key_start = 0;
+    for (n, &separator) in separators.iter().enumerate() {
+        let key = extension[key_start..separator].trim_start();
+        if key.is_empty() || key.contains(' ') {
+            return Err(format!("Invalid CEF extension key: {:?}", key).into());
+        }
+
+        let value_end = match separators.get(n + 1) {
+            Some(&next) => extension[..next]
+                .rfind(' ')
+                .filter(|&space| space > separator)
+                .ok_or_else(|| format!("Unescaped `=` in the value of CEF extension {}", key))?,
+            None => extension.len(),
+        };
+
+        pairs.push((
+            key.to_owned(),
+            unescape_extension_value(&extension[separator + 1..value_end]),
+        ));
+        key_start = value_end + 1;
+    }
+
+    Ok(pairs)
+}
+
+fn unescape_extension_value(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(escaped @ ('\\' | '=')) => unescaped.push(escaped),
+            Some(other) => {
+                unescaped.push('\\');
+                unescaped.push(other);
+            }
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deserialize(input: &'static str, log_namespace: LogNamespace) -> LogEvent {
+        let events = CefDeserializer
+            .parse(Bytes::from_static(input.as_bytes()), log_namespace)
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        events[0].as_log().clone()
+    }
+
+    #[test]
+    fn deserialize_cef_header_and_extensions() {
+        let log = deserialize(
+            r#"CEF:0|Security|threatmanager|1.0|100|worm successfully stopped|10|src=10.0.0.1 dst=2.1.2.2 spt=1232 msg=Detected a threat. No action needed"#,
+            LogNamespace::Vector,
+        );
+
+        assert_eq!(log[VERSION], "0".into());
+        assert_eq!(log[DEVICE_VENDOR], "Security".into());
+        assert_eq!(log[DEVICE_PRODUCT], "threatmanager".into());
+        assert_eq!(log[DEVICE_VERSION], "1.0".into());
+        assert_eq!(log[DEVICE_EVENT_CLASS_ID], "100".into());
+        assert_eq!(log[NAME], "worm successfully stopped".into());
+        assert_eq!(log[SEVERITY], "10".into());
+        assert_eq!(log["src"], "10.0.0.1".into());
+        assert_eq!(log["dst"], "2.1.2.2".into());
+        assert_eq!(log["spt"], "1232".into());
+        assert_eq!(log["msg"], "Detected a threat. No action needed".into());
+        assert!(!log.contains(log_schema().timestamp_key()));
+    }
+
+    #[test]
+    fn deserialize_cef_escapes() {
+        let log = deserialize(
+            r#"CEF:0|security\|vendor|product\\name|1.0|100|name|5|path=C:\\Windows eq=a\=b msg=line\nbreak"#,
+            LogNamespace::Vector,
+        );
+
+        assert_eq!(log[DEVICE_VENDOR], "security|vendor".into());
+        assert_eq!(log[DEVICE_PRODUCT], r#"product\name"#.into());
+        assert_eq!(log["path"], r#"C:\Windows"#.into());
+        assert_eq!(log["eq"], "a=b".into());
+        assert_eq!(log["msg"], "line\nbreak".into());
+    }
+
+    #[test]
+    fn deserialize_cef_with_syslog_prefix_and_no_extensions() {
+        let log = deserialize(
+            "Sep 19 08:26:10 host CEF:0|Vendor|Product|1.0|100|name|Low|",
+            LogNamespace::Legacy,
+        );
+
+        assert_eq!(log[DEVICE_VENDOR], "Vendor".into());
+        assert_eq!(log[SEVERITY], "Low".into());
+        assert!(log[log_schema().timestamp_key()].is_timestamp());
+    }
+
+    #[test]
+    fn deserialize_cef_errors() {
+        for input in [
+            "not a CEF message",
+            "CEF:0|Vendor|Product|1.0|100|name",
+            "CEF:0|Vendor|Product|1.0|100|name|5|no pairs",
+            "CEF:0|Vendor|Product|1.0|100|name|5|a=b=c",
+        ] {
+            let result = CefDeserializer.parse(Bytes::from(input), LogNamespace::Legacy);
+            assert!(result.is_err(), "{} should fail to parse", input);
+        }
+    }
+}
@@ -0,0 +1,271 @@
+use bytes::Bytes;
+use chrono::Utc;
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+use value::{kind::Collection, Kind};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, DataType, LogNamespace},
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+use super::Deserializer;
+
+const fn default_delimiter() -> u8 {
+    b','
+}
+
+const fn default_quote() -> u8 {
+    b'"'
+}
+
+/// Config used to build a `CsvDeserializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct CsvDeserializerConfig {
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    /// Options for the CSV deserializer.
+    pub csv: CsvDeserializerOptions,
+}
+
+impl CsvDeserializerConfig {
+    /// Creates a new `CsvDeserializerConfig`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Build the `CsvDeserializer` from this configuration.
+    pub fn build(&self) -> CsvDeserializer {
+        CsvDeserializer::new(self.csv.delimiter, self.csv.quote, self.csv.headers.clone())
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        let definition = match log_namespace {
+            LogNamespace::Legacy => schema::Definition::empty_legacy_namespace().with_field(
+                log_schema().timestamp_key(),
+                // The timestamp is only inserted if there is no column of that name.
+                Kind::bytes().or_timestamp(),
+                Some("timestamp"),
+            ),
+            LogNamespace::Vector => {
+                schema::Definition::new(Kind::object(Collection::empty()), [log_namespace])
+            }
+        };
+
+        match &self.csv.headers {
+            Some(headers) => headers.iter().fold(definition, |definition, header| {
+                definition.with_field(header.as_str(), Kind::bytes(), None)
+            }),
+            None => definition.unknown_fields(Kind::bytes()),
+        }
+    }
+}
+
+/// CSV deserializer options.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative, PartialEq)]
+#[derivative(Default)]
+pub struct CsvDeserializerOptions {
+    /// The character separating the fields of a record.
+    #[serde(default = "default_delimiter", with = "vector_core::serde::ascii_char")]
+    #[derivative(Default(value = "default_delimiter()"))]
+    pub delimiter: u8,
+
+    /// The character used to quote fields containing the delimiter.
+    ///
+    /// Quotes within a quoted field are escaped by doubling them.
+    #[serde(default = "default_quote", with = "vector_core::serde::ascii_char")]
+    #[derivative(Default(value = "default_quote()"))]
+    pub quote: u8,
+
+    /// The names of the columns, used as the field names of the decoded events.
+    ///
+    /// If not set, the first record of each stream, such as a file or the body of a request, is
+    /// read as the header and doesn't produce an event.
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub headers: Option<Vec<String>>,
+}
+
+/// Deserializer that builds an `Event` from a byte frame containing a CSV record.
+#[derive(Debug)]
+pub struct CsvDeserializer {
+    delimiter: u8,
+    quote: u8,
+    /// The configured headers, or the ones read from the first record once it was decoded.
+    headers: Mutex<Option<Arc<[String]>>>,
+}
+
+impl CsvDeserializer {
+    /// Creates a new `CsvDeserializer`.
+    pub fn new(delimiter: u8, quote: u8, headers: Option<Vec<String>>) -> Self {
+        Self {
+            delimiter,
+            quote,
+            headers: Mutex::new(headers.map(Into::into)),
+        }
+    }
+}
+
+impl Clone for CsvDeserializer {
+    /// Clones start out with the headers known at that time, rather than sharing them, so each
+    /// stream decoded with its own clone reads its own header record.
+    fn clone(&self) -> Self {
+        Self {
+            delimiter: self.delimiter,
+            quote: self.quote,
+            headers: Mutex::new(self.headers.lock().expect("lock poisoned").clone()),
+        }
+    }
+}
+
+impl Deserializer for CsvDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_core::Result<SmallVec<[Event; 1]>> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .delimiter(self.delimiter)
+            .quote(self.quote)
+            .from_reader(bytes.as_ref());
+        let mut record = csv::StringRecord::new();
+        // Empty frames, such as blank lines, don't hold a record.
+        if !reader.read_record(&mut record)? {
+            return Ok(smallvec![]);
+        }
+
+        let headers = {
+            let mut headers = self.headers.lock().expect("lock poisoned");
+            match &*headers {
+                Some(headers) => Arc::clone(headers),
+                None => {
+                    *headers = Some(record.iter().map(ToOwned::to_owned).collect());
+                    return Ok(smallvec![]);
+                }
+            }
+        };
+
+        if record.len() != headers.len() {
+            return Err(format!(
+                "CSV record has {} fields, but there are {} headers",
+                record.len(),
+                headers.len()
+            )
+            .into());
+        }
+
+        let fields = headers
+            .iter()
+            .zip(record.iter())
+            .map(|(header, field)| (header.clone(), Value::from(field)))
+            .collect::<BTreeMap<_, _>>();
+        let mut log = LogEvent::from(Value::Object(fields));
+
+        if log_namespace == LogNamespace::Legacy {
+            let timestamp_key = log_schema().timestamp_key();
+            if !log.contains(timestamp_key) {
+                log.insert(timestamp_key, Utc::now());
+            }
+        }
+
+        Ok(smallvec![Event::Log(log)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(
+        deserializer: &CsvDeserializer,
+        input: &'static str,
+    ) -> vector_core::Result<SmallVec<[Event; 1]>> {
+        deserializer.parse(Bytes::from_static(input.as_bytes()), LogNamespace::Vector)
+    }
+
+    #[test]
+    fn deserialize_csv_with_configured_headers() {
+        let deserializer = CsvDeserializer::new(
+            b',',
+            b'"',
+            Some(vec!["name".to_owned(), "comment".to_owned()]),
+        );
+
+        let events = parse(&deserializer, r#"foo,"hello, ""world""""#).unwrap();
+        assert_eq!(events.len(), 1);
+
+        let log = events[0].as_log();
+        assert_eq!(log["name"], "foo".into());
+        assert_eq!(log["comment"], r#"hello, "world""#.into());
+        assert!(!log.contains(log_schema().timestamp_key()));
+    }
+
+    #[test]
+    fn deserialize_csv_with_headers_from_first_record() {
+        let deserializer = CsvDeserializerConfig::new().build();
+        let clone = deserializer.clone();
+
+        assert!(parse(&deserializer, "name,size").unwrap().is_empty());
+        let events = parse(&deserializer, "foo,42").unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].as_log()["name"], "foo".into());
+        assert_eq!(events[0].as_log()["size"], "42".into());
+
+        // Clones don't share the header read by the deserializer they were cloned from.
+        assert!(parse(&clone, "other,columns").unwrap().is_empty());
+        assert_eq!(
+            parse(&clone, "a,b").unwrap()[0].as_log()["other"],
+            "a".into()
+        );
+    }
+
+    #[test]
+    fn deserialize_csv_custom_delimiter_and_quote() {
+        let deserializer =
+            CsvDeserializer::new(b';', b'\'', Some(vec!["a".to_owned(), "b".to_owned()]));
+
+        let events = parse(&deserializer, "'x;y';z").unwrap();
+        assert_eq!(events[0].as_log()["a"], "x;y".into());
+        assert_eq!(events[0].as_log()["b"], "z".into());
+    }
+
+    #[test]
+    fn deserialize_csv_legacy_namespace_adds_timestamp() {
+        let deserializer = CsvDeserializer::new(b',', b'"', Some(vec!["a".to_owned()]));
+        let events = deserializer
+            .parse(Bytes::from_static(b"x"), LogNamespace::Legacy)
+            .unwrap();
+        assert!(events[0].as_log()[log_schema().timestamp_key()].is_timestamp());
+    }
+
+    #[test]
+    fn deserialize_csv_skips_empty_frames() {
+        let deserializer = CsvDeserializer::new(b',', b'"', Some(vec!["a".to_owned()]));
+        assert!(parse(&deserializer, "").unwrap().is_empty());
+    }
+
+    #[test]
+    fn deserialize_csv_field_count_mismatch() {
+        let deserializer =
+            CsvDeserializer::new(b',', b'"', Some(vec!["a".to_owned(), "b".to_owned()]));
+        assert!(parse(&deserializer, "x,y,z").is_err());
+    }
+}
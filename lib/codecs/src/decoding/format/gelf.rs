@@ -1,11 +1,20 @@
 use bytes::Bytes;
 use chrono::{DateTime, NaiveDateTime, Utc};
+use derivative::Derivative;
+#[cfg(feature = "gelf-compression")]
+use flate2::read::{GzDecoder, ZlibDecoder};
 use lookup::path;
 use serde::{Deserialize, Serialize};
 use smallvec::{smallvec, SmallVec};
-use std::collections::HashMap;
+#[cfg(feature = "gelf-compression")]
+use std::io::Read;
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+};
 use value::kind::Collection;
 use value::Kind;
+use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 use vector_core::{
     config::{log_schema, DataType},
@@ -23,14 +32,50 @@ use crate::{gelf_fields::*, VALID_FIELD_REGEX};
 ///   in the event that we need to change the behavior to be more relaxed, so that prior versions
 ///   of vector will still work with the new relaxed decoding.
 
+/// The magic bytes of a gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+const fn default_max_decompressed_length() -> usize {
+    8 * 1024 * 1024
+}
+
 /// Config used to build a `GelfDeserializer`.
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
-pub struct GelfDeserializerConfig;
+pub struct GelfDeserializerConfig {
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    /// Options for the GELF deserializer.
+    pub gelf: GelfDeserializerOptions,
+}
+
+/// Options for building a `GelfDeserializer`.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative, PartialEq)]
+#[derivative(Default)]
+pub struct GelfDeserializerOptions {
+    /// The maximum length, in bytes, of a message once decompressed.
+    ///
+    /// Messages compressed with gzip or zlib are detected and decompressed before being parsed.
+    /// Messages exceeding this length once decompressed are discarded, which guards against
+    /// decompression bombs.
+    #[serde(default = "default_max_decompressed_length")]
+    #[derivative(Default(value = "default_max_decompressed_length()"))]
+    pub max_decompressed_length: usize,
+}
 
 impl GelfDeserializerConfig {
+    /// Creates a new `GelfDeserializerConfig`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
     /// Build the `GelfDeserializer` from this configuration.
     pub fn build(&self) -> GelfDeserializer {
-        GelfDeserializer::default()
+        GelfDeserializer {
+            max_decompressed_length: self.gelf.max_decompressed_length,
+        }
     }
 
     /// Return the type of event built by this deserializer.
@@ -40,15 +85,29 @@ impl GelfDeserializerConfig {
 
     /// The schema produced by the deserializer.
     pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
-        schema::Definition::new(Kind::object(Collection::empty()), [log_namespace])
+        let definition = match log_namespace {
+            LogNamespace::Legacy => schema::Definition::empty_legacy_namespace()
+                .with_field(log_schema().message_key(), Kind::bytes(), Some("message"))
+                // The timestamp is set to the current time if the message doesn't provide one.
+                .with_field(
+                    log_schema().timestamp_key(),
+                    Kind::timestamp(),
+                    Some("timestamp"),
+                ),
+            LogNamespace::Vector => {
+                schema::Definition::new(Kind::object(Collection::empty()), [log_namespace])
+                    .with_field(SHORT_MESSAGE, Kind::bytes(), Some("message"))
+                    .optional_field(TIMESTAMP, Kind::timestamp(), Some("timestamp"))
+            }
+        };
+
+        definition
             .with_field(VERSION, Kind::bytes(), None)
             .with_field(HOST, Kind::bytes(), None)
-            .with_field(SHORT_MESSAGE, Kind::bytes(), None)
             .optional_field(FULL_MESSAGE, Kind::bytes(), None)
-            .optional_field(TIMESTAMP, Kind::timestamp(), None)
             .optional_field(LEVEL, Kind::integer(), None)
             .optional_field(FACILITY, Kind::bytes(), None)
-            .optional_field(LINE, Kind::integer(), None)
+            .optional_field(LINE, Kind::float(), None)
             .optional_field(FILE, Kind::bytes(), None)
             // Every field with an underscore (_) prefix will be treated as an additional field.
             // Allowed characters in field names are any word character (letter, number, underscore), dashes and dots.
@@ -60,7 +119,10 @@ impl GelfDeserializerConfig {
 /// Deserializer that builds an `Event` from a byte frame containing a GELF log
 /// message.
 #[derive(Debug, Clone)]
-pub struct GelfDeserializer;
+pub struct GelfDeserializer {
+    #[cfg_attr(not(feature = "gelf-compression"), allow(dead_code))]
+    max_decompressed_length: usize,
+}
 
 impl Default for GelfDeserializer {
     fn default() -> Self {
@@ -71,14 +133,57 @@ impl Default for GelfDeserializer {
 impl GelfDeserializer {
     /// Create a new GelfDeserializer
     pub fn new() -> GelfDeserializer {
-        GelfDeserializer
+        GelfDeserializer {
+            max_decompressed_length: default_max_decompressed_length(),
+        }
+    }
+
+    /// Inflates the message if it is compressed with gzip or zlib, as is common for GELF sent
+    /// over UDP. Uncompressed messages are returned as-is.
+    #[cfg(feature = "gelf-compression")]
+    fn decompress<'a>(&self, bytes: &'a [u8]) -> vector_core::Result<Cow<'a, [u8]>> {
+        let reader: Box<dyn Read + 'a> = if bytes.starts_with(&GZIP_MAGIC) {
+            Box::new(GzDecoder::new(bytes))
+        } else if is_zlib(bytes) {
+            Box::new(ZlibDecoder::new(bytes))
+        } else {
+            return Ok(Cow::Borrowed(bytes));
+        };
+
+        // Read one byte past the limit to tell messages which are exactly at the limit apart from
+        // the ones exceeding it.
+        let mut decompressed = Vec::new();
+        reader
+            .take(self.max_decompressed_length as u64 + 1)
+            .read_to_end(&mut decompressed)?;
+        if decompressed.len() > self.max_decompressed_length {
+            return Err(format!(
+                "Decompressed message exceeds the maximum length of {} bytes",
+                self.max_decompressed_length
+            )
+            .into());
+        }
+
+        Ok(Cow::Owned(decompressed))
+    }
+
+    /// Rejects compressed messages, which can't be inflated without the `gelf-compression`
+    /// feature. Uncompressed messages are returned as-is.
+    #[cfg(not(feature = "gelf-compression"))]
+    fn decompress<'a>(&self, bytes: &'a [u8]) -> vector_core::Result<Cow<'a, [u8]>> {
+        if bytes.starts_with(&GZIP_MAGIC) || is_zlib(bytes) {
+            return Err("Compressed messages require the `gelf-compression` feature".into());
+        }
+        Ok(Cow::Borrowed(bytes))
     }
 
     /// Builds a LogEvent from the parsed GelfMessage.
     /// The logic follows strictly the documented GELF standard.
-    fn message_to_event(&self, parsed: &GelfMessage) -> vector_core::Result<Event> {
-        let mut log = LogEvent::from_str_legacy(parsed.short_message.to_string());
-
+    fn message_to_event(
+        &self,
+        parsed: &GelfMessage,
+        log_namespace: LogNamespace,
+    ) -> vector_core::Result<Event> {
         // GELF spec defines the version as 1.1 which has not changed since 2013
         if parsed.version != GELF_VERSION {
             return Err(format!(
@@ -88,6 +193,16 @@ impl GelfDeserializer {
             .into());
         }
 
+        let mut log = LogEvent::from(value::Value::Object(BTreeMap::new()));
+        match log_namespace {
+            LogNamespace::Legacy => {
+                log.insert(log_schema().message_key(), parsed.short_message.to_string());
+            }
+            LogNamespace::Vector => {
+                log.insert(SHORT_MESSAGE, parsed.short_message.to_string());
+            }
+        }
+
         log.insert(VERSION, parsed.version.to_string());
         log.insert(HOST, parsed.host.to_string());
 
@@ -95,18 +210,28 @@ impl GelfDeserializer {
             log.insert(FULL_MESSAGE, full_message.to_string());
         }
 
-        if let Some(timestamp) = parsed.timestamp {
+        let timestamp = parsed.timestamp.map(|timestamp| {
             let naive = NaiveDateTime::from_timestamp(
                 f64::trunc(timestamp) as i64,
                 f64::fract(timestamp) as u32,
             );
-            log.insert(
-                log_schema().timestamp_key(),
-                DateTime::<Utc>::from_utc(naive, Utc),
-            );
-        // per GELF spec- add timestamp if not provided
-        } else {
-            log.insert(log_schema().timestamp_key(), Utc::now());
+            DateTime::<Utc>::from_utc(naive, Utc)
+        });
+        match log_namespace {
+            // per GELF spec- add timestamp if not provided
+            LogNamespace::Legacy => {
+                log.insert(
+                    log_schema().timestamp_key(),
+                    timestamp.unwrap_or_else(Utc::now),
+                );
+            }
+            // The source records when the event was ingested in the event metadata, so only the
+            // timestamp sent by the client is kept.
+            LogNamespace::Vector => {
+                if let Some(timestamp) = timestamp {
+                    log.insert(TIMESTAMP, timestamp);
+                }
+            }
         }
 
         if let Some(level) = parsed.level {
@@ -170,6 +295,15 @@ impl GelfDeserializer {
     }
 }
 
+/// Whether the bytes start with a zlib header using the deflate method, which GELF messages
+/// starting with `{` can never be mistaken for.
+fn is_zlib(bytes: &[u8]) -> bool {
+    match bytes {
+        [cmf, flg, ..] => cmf & 0x0f == 8 && (u16::from(*cmf) << 8 | u16::from(*flg)) % 31 == 0,
+        _ => false,
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 struct GelfMessage {
     version: String,
@@ -189,13 +323,14 @@ impl Deserializer for GelfDeserializer {
     fn parse(
         &self,
         bytes: Bytes,
-        _log_namespace: LogNamespace,
+        log_namespace: LogNamespace,
     ) -> vector_core::Result<SmallVec<[Event; 1]>> {
+        let bytes = self.decompress(&bytes)?;
         let line = std::str::from_utf8(&bytes)?;
         let line = line.trim();
 
         let parsed: GelfMessage = serde_json::from_str(line)?;
-        let event = self.message_to_event(&parsed)?;
+        let event = self.message_to_event(&parsed, log_namespace)?;
 
         Ok(smallvec![event])
     }
@@ -216,7 +351,7 @@ mod tests {
     fn deserialize_gelf_input(
         input: &serde_json::Value,
     ) -> vector_core::Result<SmallVec<[Event; 1]>> {
-        let config = GelfDeserializerConfig;
+        let config = GelfDeserializerConfig::new();
         let deserializer = config.build();
         let buffer = Bytes::from(serde_json::to_vec(&input).unwrap());
         deserializer.parse(buffer, LogNamespace::Legacy)
@@ -299,6 +434,30 @@ mod tests {
         );
     }
 
+    /// Validates the GELF fields are kept under their own names in the Vector namespace.
+    #[test]
+    fn gelf_deserialize_vector_namespace() {
+        let input = json!({
+            VERSION: "1.1",
+            HOST: "example.org",
+            SHORT_MESSAGE: "foobar",
+            "_custom": "value",
+        });
+
+        let deserializer = GelfDeserializerConfig::new().build();
+        let buffer = Bytes::from(serde_json::to_vec(&input).unwrap());
+        let events = deserializer.parse(buffer, LogNamespace::Vector).unwrap();
+        assert_eq!(events.len(), 1);
+
+        let log = events[0].as_log();
+        assert_eq!(log.get(SHORT_MESSAGE), Some(&Value::from("foobar")));
+        assert_eq!(log.get(HOST), Some(&Value::from("example.org")));
+        assert_eq!(log.get("_custom"), Some(&Value::from("value")));
+        assert!(!log.contains(log_schema().message_key()));
+        // The timestamp is only set when the message provides one.
+        assert!(!log.contains(TIMESTAMP));
+    }
+
     /// Validates deserializiation succeeds for edge case inputs.
     #[test]
     fn gelf_deserializing_edge_cases() {
@@ -379,4 +538,66 @@ mod tests {
             LEVEL: "baz",
         }));
     }
+
+    #[cfg(feature = "gelf-compression")]
+    fn compressed_input(compression: &str) -> Bytes {
+        use flate2::{
+            write::{GzEncoder, ZlibEncoder},
+            Compression,
+        };
+        use std::io::Write;
+
+        let input = serde_json::to_vec(&json!({
+            VERSION: "1.1",
+            HOST: "example.org",
+            SHORT_MESSAGE: "foobar",
+        }))
+        .unwrap();
+
+        let compressed = match compression {
+            "gzip" => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(&input).unwrap();
+                encoder.finish().unwrap()
+            }
+            "zlib" => {
+                let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(&input).unwrap();
+                encoder.finish().unwrap()
+            }
+            _ => unreachable!(),
+        };
+        Bytes::from(compressed)
+    }
+
+    #[cfg(feature = "gelf-compression")]
+    #[test]
+    fn gelf_deserializes_compressed_messages() {
+        let deserializer = GelfDeserializerConfig::new().build();
+
+        for compression in ["gzip", "zlib"] {
+            let events = deserializer
+                .parse(compressed_input(compression), LogNamespace::Legacy)
+                .unwrap();
+            assert_eq!(events.len(), 1);
+            assert_eq!(
+                events[0].as_log().get(log_schema().message_key()),
+                Some(&Value::Bytes(Bytes::from_static(b"foobar")))
+            );
+        }
+    }
+
+    #[cfg(feature = "gelf-compression")]
+    #[test]
+    fn gelf_rejects_oversized_decompressed_messages() {
+        let config: GelfDeserializerConfig =
+            serde_json::from_value(json!({ "gelf": { "max_decompressed_length": 16 } })).unwrap();
+        let deserializer = config.build();
+
+        for compression in ["gzip", "zlib"] {
+            assert!(deserializer
+                .parse(compressed_input(compression), LogNamespace::Legacy)
+                .is_err());
+        }
+    }
 }
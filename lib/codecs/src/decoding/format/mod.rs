@@ -3,20 +3,38 @@
 
 #![deny(missing_docs)]
 
+#[cfg(feature = "avro-registry")]
+mod avro;
 mod bytes;
+mod cef;
+#[cfg(feature = "csv")]
+mod csv;
 mod gelf;
 mod json;
+#[cfg(feature = "msgpack")]
+mod msgpack;
 mod native;
 mod native_json;
+#[cfg(feature = "protobuf-dynamic")]
+mod protobuf;
 #[cfg(feature = "syslog")]
 mod syslog;
 
 use ::bytes::Bytes;
+#[cfg(feature = "avro-registry")]
+pub use avro::{AvroDeserializer, AvroDeserializerConfig, AvroDeserializerOptions};
+pub use cef::{CefDeserializer, CefDeserializerConfig};
+#[cfg(feature = "csv")]
+pub use csv::{CsvDeserializer, CsvDeserializerConfig, CsvDeserializerOptions};
 use dyn_clone::DynClone;
-pub use gelf::{GelfDeserializer, GelfDeserializerConfig};
+pub use gelf::{GelfDeserializer, GelfDeserializerConfig, GelfDeserializerOptions};
 pub use json::{JsonDeserializer, JsonDeserializerConfig};
+#[cfg(feature = "msgpack")]
+pub use msgpack::{MsgpackDeserializer, MsgpackDeserializerConfig};
 pub use native::{NativeDeserializer, NativeDeserializerConfig};
 pub use native_json::{NativeJsonDeserializer, NativeJsonDeserializerConfig};
+#[cfg(feature = "protobuf-dynamic")]
+pub use protobuf::{ProtobufDeserializer, ProtobufDeserializerConfig, ProtobufDeserializerOptions};
 use smallvec::SmallVec;
 use std::fmt::Debug;
 use vector_core::config::LogNamespace;
@@ -0,0 +1,244 @@
+use std::collections::BTreeMap;
+
+use bytes::Bytes;
+use chrono::Utc;
+use ordered_float::NotNan;
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+use value::{kind::Collection, Kind};
+use vector_core::{
+    config::{log_schema, DataType, LogNamespace},
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+use super::Deserializer;
+use crate::msgpack::decode_timestamp;
+
+/// Config used to build a `MsgpackDeserializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct MsgpackDeserializerConfig;
+
+impl MsgpackDeserializerConfig {
+    /// Creates a new `MsgpackDeserializerConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `MsgpackDeserializer` from this configuration.
+    pub const fn build(&self) -> MsgpackDeserializer {
+        MsgpackDeserializer
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        match log_namespace {
+            LogNamespace::Legacy => schema::Definition::empty_legacy_namespace()
+                .with_field(
+                    log_schema().timestamp_key(),
+                    // The timestamp is only inserted if the message doesn't contain this field.
+                    Kind::any(),
+                    Some("timestamp"),
+                )
+                .unknown_fields(Kind::any()),
+            LogNamespace::Vector => {
+                schema::Definition::new(Kind::object(Collection::any()), [log_namespace])
+            }
+        }
+    }
+}
+
+/// Deserializer that builds `Event`s from a byte frame containing MessagePack maps.
+///
+/// A frame may hold several concatenated maps, each of which is decoded into its own event.
+#[derive(Debug, Clone, Default)]
+pub struct MsgpackDeserializer;
+
+impl MsgpackDeserializer {
+    /// Creates a new `MsgpackDeserializer`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Deserializer for MsgpackDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_core::Result<SmallVec<[Event; 1]>> {
+        let mut events = smallvec![];
+        let mut reader = bytes.as_ref();
+
+        while !reader.is_empty() {
+            let value = rmpv::decode::read_value(&mut reader)
+                .map_err(|error| format!("Error parsing MessagePack: {}", error))?;
+            let value = to_value(value)?;
+            if !value.is_object() {
+                return Err(format!(
+                    "MessagePack value must be a map, found {}",
+                    value.kind_str()
+                )
+                .into());
+            }
+
+            let mut log = LogEvent::from(value);
+            if log_namespace == LogNamespace::Legacy {
+                let timestamp_key = log_schema().timestamp_key();
+                if !log.contains(timestamp_key) {
+                    log.insert(timestamp_key, Utc::now());
+                }
+            }
+            events.push(Event::Log(log));
+        }
+
+        Ok(events)
+    }
+}
+
+/// Converts a decoded MessagePack value to a Vector value. Timestamp extensions are converted to
+/// timestamps, while other extensions are kept as their raw payload.
+fn to_value(value: rmpv::Value) -> vector_core::Result<Value> {
+    use rmpv::Value as MsgpackValue;
+
+    fn float(value: f64) -> vector_core::Result<Value> {
+        NotNan::new(value)
+            .map(Value::Float)
+            .map_err(|_| "NaN float values are not supported".into())
+    }
+
+    Ok(match value {
+        MsgpackValue::Nil => Value::Null,
+        MsgpackValue::Boolean(value) => Value::Boolean(value),
+        MsgpackValue::Integer(value) => {
+            Value::Integer(value.as_i64().ok_or_else(|| {
+                format!("Integer value {} exceeds the maximum integer value", value)
+            })?)
+        }
+        MsgpackValue::F32(value) => float(f64::from(value))?,
+        MsgpackValue::F64(value) => float(value)?,
+        MsgpackValue::String(value) => Value::Bytes(value.into_bytes().into()),
+        MsgpackValue::Binary(value) => Value::Bytes(value.into()),
+        MsgpackValue::Array(values) => Value::Array(
+            values
+                .into_iter()
+                .map(to_value)
+                .collect::<vector_core::Result<_>>()?,
+        ),
+        MsgpackValue::Map(entries) => Value::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| Ok((to_key(key)?, to_value(value)?)))
+                .collect::<vector_core::Result<BTreeMap<_, _>>>()?,
+        ),
+        MsgpackValue::Ext(ext_type, payload) => match decode_timestamp(ext_type, &payload) {
+            Some(timestamp) => Value::Timestamp(timestamp),
+            None => Value::Bytes(payload.into()),
+        },
+    })
+}
+
+fn to_key(key: rmpv::Value) -> vector_core::Result<String> {
+    match key {
+        rmpv::Value::String(key) => Ok(String::from_utf8_lossy(key.as_bytes()).into_owned()),
+        rmpv::Value::Integer(key) => Ok(key.to_string()),
+        key => Err(format!("Unsupported MessagePack map key: {}", key).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use rmpv::Value as MsgpackValue;
+
+    use super::*;
+    use crate::msgpack::{FLUENTD_EVENT_TIME_EXT_TYPE, TIMESTAMP_EXT_TYPE};
+
+    fn encode(values: &[MsgpackValue]) -> Bytes {
+        let mut buffer = Vec::new();
+        for value in values {
+            rmpv::encode::write_value(&mut buffer, value).unwrap();
+        }
+        buffer.into()
+    }
+
+    fn record() -> MsgpackValue {
+        MsgpackValue::Map(vec![
+            ("message".into(), "foo".into()),
+            ("count".into(), 3.into()),
+            (
+                "nested".into(),
+                MsgpackValue::Map(vec![(
+                    "values".into(),
+                    MsgpackValue::Array(vec![true.into(), MsgpackValue::Nil]),
+                )]),
+            ),
+            (
+                "time".into(),
+                MsgpackValue::Ext(TIMESTAMP_EXT_TYPE, 1_600_000_000u32.to_be_bytes().to_vec()),
+            ),
+        ])
+    }
+
+    #[test]
+    fn deserialize_msgpack_legacy_namespace() {
+        let events = MsgpackDeserializer
+            .parse(encode(&[record()]), LogNamespace::Legacy)
+            .unwrap();
+        assert_eq!(events.len(), 1);
+
+        let log = events[0].as_log();
+        assert_eq!(log["message"], "foo".into());
+        assert_eq!(log["count"], 3.into());
+        assert_eq!(log["nested.values[0]"], true.into());
+        assert_eq!(log["nested.values[1]"], Value::Null);
+        assert_eq!(log["time"], Utc.timestamp(1_600_000_000, 0).into());
+        assert!(log[log_schema().timestamp_key()].is_timestamp());
+    }
+
+    #[test]
+    fn deserialize_msgpack_concatenated_maps() {
+        let events = MsgpackDeserializer
+            .parse(encode(&[record(), record()]), LogNamespace::Vector)
+            .unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(!events[1].as_log().contains(log_schema().timestamp_key()));
+    }
+
+    #[test]
+    fn deserialize_msgpack_fluentd_event_time() {
+        let input = encode(&[MsgpackValue::Map(vec![(
+            "time".into(),
+            MsgpackValue::Ext(
+                FLUENTD_EVENT_TIME_EXT_TYPE,
+                vec![0x5f, 0x5e, 0x10, 0x00, 0, 0, 0, 1],
+            ),
+        )])]);
+        let events = MsgpackDeserializer
+            .parse(input, LogNamespace::Vector)
+            .unwrap();
+        assert_eq!(
+            events[0].as_log()["time"],
+            Utc.timestamp(1_600_000_000, 1).into()
+        );
+    }
+
+    #[test]
+    fn deserialize_msgpack_errors() {
+        for input in [
+            encode(&["not a map".into()]),
+            encode(&[MsgpackValue::Map(vec![(true.into(), 1.into())])]),
+            encode(&[MsgpackValue::Map(vec![("big".into(), u64::MAX.into())])]),
+            Bytes::from_static(&[0x81, 0xa1]),
+        ] {
+            assert!(MsgpackDeserializer
+                .parse(input, LogNamespace::Legacy)
+                .is_err());
+        }
+    }
+}
@@ -0,0 +1,352 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use bytes::Bytes;
+use chrono::Utc;
+use ordered_float::NotNan;
+use prost_reflect::{DescriptorPool, DynamicMessage, FieldDescriptor, MapKey, MessageDescriptor};
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+use value::{kind::Collection, Kind};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, DataType, LogNamespace},
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+use super::Deserializer;
+use crate::decoding::BuildError;
+
+/// Config used to build a `ProtobufDeserializer`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProtobufDeserializerConfig {
+    /// Options for the Protobuf deserializer.
+    pub protobuf: ProtobufDeserializerOptions,
+}
+
+impl ProtobufDeserializerConfig {
+    /// Creates a new `ProtobufDeserializerConfig`.
+    pub const fn new(desc_file: PathBuf, message_type: String) -> Self {
+        Self {
+            protobuf: ProtobufDeserializerOptions {
+                desc_file,
+                message_type,
+            },
+        }
+    }
+
+    /// Build the `ProtobufDeserializer` from this configuration.
+    pub fn build(&self) -> Result<ProtobufDeserializer, BuildError> {
+        let desc_file = &self.protobuf.desc_file;
+        let bytes = std::fs::read(desc_file).map_err(|error| {
+            format!(
+                "Failed reading descriptor set {}: {}",
+                desc_file.display(),
+                error
+            )
+        })?;
+        let pool = DescriptorPool::decode(bytes.as_slice()).map_err(|error| {
+            format!(
+                "Failed parsing descriptor set {}: {}",
+                desc_file.display(),
+                error
+            )
+        })?;
+        let message_descriptor = pool
+            .get_message_by_name(&self.protobuf.message_type)
+            .ok_or_else(|| {
+                format!(
+                    "Message type {} not found in descriptor set {}",
+                    self.protobuf.message_type,
+                    desc_file.display()
+                )
+            })?;
+
+        Ok(ProtobufDeserializer::new(message_descriptor))
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        // TODO: Convert the message descriptor to a vector schema definition.
+        match log_namespace {
+            LogNamespace::Legacy => schema::Definition::empty_legacy_namespace()
+                .with_field(
+                    log_schema().timestamp_key(),
+                    // The timestamp is only inserted if the message doesn't contain this field.
+                    Kind::any(),
+                    Some("timestamp"),
+                )
+                .unknown_fields(Kind::any()),
+            LogNamespace::Vector => {
+                schema::Definition::new(Kind::object(Collection::any()), [log_namespace])
+            }
+        }
+    }
+}
+
+/// Protobuf deserializer options.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct ProtobufDeserializerOptions {
+    /// The path to the compiled file descriptor set, as produced by
+    /// `protoc --include_imports --descriptor_set_out`.
+    pub desc_file: PathBuf,
+
+    /// The fully qualified name of the message type to decode, e.g. `package.Message`.
+    pub message_type: String,
+}
+
+/// Deserializer that builds `Event`s from a byte frame containing a Protobuf message.
+#[derive(Debug, Clone)]
+pub struct ProtobufDeserializer {
+    message_descriptor: MessageDescriptor,
+}
+
+impl ProtobufDeserializer {
+    /// Creates a new `ProtobufDeserializer`.
+    pub const fn new(message_descriptor: MessageDescriptor) -> Self {
+        Self { message_descriptor }
+    }
+}
+
+impl Deserializer for ProtobufDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_core::Result<SmallVec<[Event; 1]>> {
+        let message = DynamicMessage::decode(self.message_descriptor.clone(), bytes)
+            .map_err(|error| format!("Error parsing Protobuf: {}", error))?;
+        let value = message_to_value(&message)?;
+
+        let mut log = LogEvent::from(value);
+        if log_namespace == LogNamespace::Legacy {
+            let timestamp_key = log_schema().timestamp_key();
+            if !log.contains(timestamp_key) {
+                log.insert(timestamp_key, Utc::now());
+            }
+        }
+
+        Ok(smallvec![Event::Log(log)])
+    }
+}
+
+/// Converts a decoded message to a Vector object, including the fields set to their defaults.
+fn message_to_value(message: &DynamicMessage) -> vector_core::Result<Value> {
+    let mut fields = BTreeMap::new();
+    for field in message.descriptor().fields() {
+        let value = message.get_field(&field);
+        fields.insert(field.name().to_owned(), to_value(&value, &field)?);
+    }
+    Ok(Value::Object(fields))
+}
+
+/// Converts a decoded field value to a Vector value. The field descriptor is used to resolve
+/// enum values to their names.
+fn to_value(value: &prost_reflect::Value, field: &FieldDescriptor) -> vector_core::Result<Value> {
+    use prost_reflect::Value as ProtobufValue;
+
+    fn float(value: f64) -> vector_core::Result<Value> {
+        NotNan::new(value)
+            .map(Value::Float)
+            .map_err(|_| "NaN float values are not supported".into())
+    }
+
+    Ok(match value {
+        ProtobufValue::Bool(value) => Value::Boolean(*value),
+        ProtobufValue::I32(value) => Value::Integer(i64::from(*value)),
+        ProtobufValue::I64(value) => Value::Integer(*value),
+        ProtobufValue::U32(value) => Value::Integer(i64::from(*value)),
+        ProtobufValue::U64(value) => Value::Integer(i64::try_from(*value).map_err(|_| {
+            format!(
+                "Field {} value {} exceeds the maximum integer value",
+                field.name(),
+                value
+            )
+        })?),
+        ProtobufValue::F32(value) => float(f64::from(*value))?,
+        ProtobufValue::F64(value) => float(*value)?,
+        ProtobufValue::String(value) => Value::Bytes(value.clone().into()),
+        ProtobufValue::Bytes(value) => Value::Bytes(value.clone()),
+        ProtobufValue::EnumNumber(number) => {
+            let name = field
+                .kind()
+                .as_enum()
+                .and_then(|descriptor| descriptor.get_value(*number))
+                .map(|value| value.name().to_owned())
+                // Unknown values are kept, since newer writers may have added them.
+                .unwrap_or_else(|| number.to_string());
+            Value::Bytes(name.into())
+        }
+        ProtobufValue::Message(message) => message_to_value(message)?,
+        ProtobufValue::List(values) => Value::Array(
+            values
+                .iter()
+                .map(|value| to_value(value, field))
+                .collect::<vector_core::Result<_>>()?,
+        ),
+        ProtobufValue::Map(values) => {
+            let value_field = field
+                .kind()
+                .as_message()
+                .map(MessageDescriptor::map_entry_value_field)
+                .ok_or_else(|| format!("Field {} is not a map", field.name()))?;
+            Value::Object(
+                values
+                    .iter()
+                    .map(|(key, value)| Ok((map_key(key), to_value(value, &value_field)?)))
+                    .collect::<vector_core::Result<_>>()?,
+            )
+        }
+    })
+}
+
+fn map_key(key: &MapKey) -> String {
+    match key {
+        MapKey::Bool(key) => key.to_string(),
+        MapKey::I32(key) => key.to_string(),
+        MapKey::I64(key) => key.to_string(),
+        MapKey::U32(key) => key.to_string(),
+        MapKey::U64(key) => key.to_string(),
+        MapKey::String(key) => key.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prost::Message;
+    use prost_types::{
+        field_descriptor_proto::{Label, Type},
+        DescriptorProto, EnumDescriptorProto, EnumValueDescriptorProto, FieldDescriptorProto,
+        FileDescriptorProto, FileDescriptorSet,
+    };
+
+    use super::*;
+
+    fn field(
+        name: &str,
+        number: i32,
+        r#type: Type,
+        type_name: Option<&str>,
+    ) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.to_owned()),
+            number: Some(number),
+            label: Some(Label::Optional as i32),
+            r#type: Some(r#type as i32),
+            type_name: type_name.map(ToOwned::to_owned),
+            ..Default::default()
+        }
+    }
+
+    /// Writes a descriptor set equivalent to:
+    ///
+    /// ```protobuf
+    /// package test;
+    /// enum Level { INFO = 0; ERROR = 1; }
+    /// message Log { string message = 1; int64 count = 2; Level level = 3; }
+    /// ```
+    fn write_descriptor_set(path: &std::path::Path) {
+        let file = FileDescriptorProto {
+            name: Some("test.proto".to_owned()),
+            package: Some("test".to_owned()),
+            syntax: Some("proto3".to_owned()),
+            message_type: vec![DescriptorProto {
+                name: Some("Log".to_owned()),
+                field: vec![
+                    field("message", 1, Type::String, None),
+                    field("count", 2, Type::Int64, None),
+                    field("level", 3, Type::Enum, Some(".test.Level")),
+                ],
+                ..Default::default()
+            }],
+            enum_type: vec![EnumDescriptorProto {
+                name: Some("Level".to_owned()),
+                value: vec![
+                    EnumValueDescriptorProto {
+                        name: Some("INFO".to_owned()),
+                        number: Some(0),
+                        ..Default::default()
+                    },
+                    EnumValueDescriptorProto {
+                        name: Some("ERROR".to_owned()),
+                        number: Some(1),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let set = FileDescriptorSet { file: vec![file] };
+        std::fs::write(path, set.encode_to_vec()).unwrap();
+    }
+
+    fn deserializer() -> ProtobufDeserializer {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.desc");
+        write_descriptor_set(&path);
+
+        ProtobufDeserializerConfig::new(path, "test.Log".to_owned())
+            .build()
+            .unwrap()
+    }
+
+    fn message(deserializer: &ProtobufDeserializer) -> Bytes {
+        let mut message = DynamicMessage::new(deserializer.message_descriptor.clone());
+        message.set_field_by_name("message", prost_reflect::Value::String("foo".to_owned()));
+        message.set_field_by_name("count", prost_reflect::Value::I64(3));
+        message.set_field_by_name("level", prost_reflect::Value::EnumNumber(1));
+        message.encode_to_vec().into()
+    }
+
+    #[test]
+    fn deserialize_protobuf_legacy_namespace() {
+        let deserializer = deserializer();
+        let events = deserializer
+            .parse(message(&deserializer), LogNamespace::Legacy)
+            .unwrap();
+        assert_eq!(events.len(), 1);
+
+        let log = events[0].as_log();
+        assert_eq!(log["message"], "foo".into());
+        assert_eq!(log["count"], 3.into());
+        assert_eq!(log["level"], "ERROR".into());
+        assert!(log[log_schema().timestamp_key()].is_timestamp());
+    }
+
+    #[test]
+    fn deserialize_protobuf_vector_namespace() {
+        let deserializer = deserializer();
+        let events = deserializer
+            .parse(message(&deserializer), LogNamespace::Vector)
+            .unwrap();
+        assert_eq!(events.len(), 1);
+
+        let log = events[0].as_log();
+        assert_eq!(log["message"], "foo".into());
+        assert_eq!(log["level"], "ERROR".into());
+        assert!(!log.contains(log_schema().timestamp_key()));
+    }
+
+    #[test]
+    fn deserialize_protobuf_invalid_message() {
+        let result = deserializer().parse(Bytes::from_static(b"\xff\xff"), LogNamespace::Legacy);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn build_fails_for_unknown_message_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.desc");
+        write_descriptor_set(&path);
+
+        let result = ProtobufDeserializerConfig::new(path, "test.Missing".to_owned()).build();
+        assert!(result.is_err());
+    }
+}
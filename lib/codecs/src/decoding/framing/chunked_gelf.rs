@@ -0,0 +1,375 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use bytes::{Buf, Bytes, BytesMut};
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use tokio_util::codec::Decoder;
+use tracing::{trace, warn};
+use vector_config::configurable_component;
+
+use super::{BoxedFramingError, FramingError};
+use crate::{
+    decoding::StreamDecodingError,
+    gelf::{GELF_CHUNK_HEADER_LENGTH, GELF_MAGIC, GELF_MAX_CHUNKS},
+};
+
+const fn default_timeout_secs() -> f64 {
+    5.0
+}
+
+const fn default_pending_messages_limit() -> usize {
+    1000
+}
+
+/// Config used to build a `ChunkedGelfDecoder`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct ChunkedGelfDecoderConfig {
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    /// Options for the chunked GELF decoder.
+    pub chunked_gelf: ChunkedGelfDecoderOptions,
+}
+
+impl ChunkedGelfDecoderConfig {
+    /// Creates a new `ChunkedGelfDecoderConfig`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Build the `ChunkedGelfDecoder` from this configuration.
+    pub fn build(&self) -> ChunkedGelfDecoder {
+        ChunkedGelfDecoder::new(
+            Duration::from_secs_f64(self.chunked_gelf.timeout_secs),
+            self.chunked_gelf.pending_messages_limit,
+        )
+    }
+}
+
+/// Options for building a `ChunkedGelfDecoder`.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative, PartialEq)]
+#[derivative(Default)]
+pub struct ChunkedGelfDecoderOptions {
+    /// The time, in seconds, to wait for all the chunks of a message to arrive.
+    ///
+    /// Messages that are still incomplete after this time are discarded, as mandated by the GELF
+    /// specification.
+    #[serde(default = "default_timeout_secs")]
+    #[derivative(Default(value = "default_timeout_secs()"))]
+    timeout_secs: f64,
+
+    /// The maximum number of messages which can be reassembled at the same time.
+    ///
+    /// Chunks starting a new message are discarded while this limit is reached.
+    #[serde(default = "default_pending_messages_limit")]
+    #[derivative(Default(value = "default_pending_messages_limit()"))]
+    pending_messages_limit: usize,
+}
+
+/// An error that occurred while reassembling a chunked GELF message.
+#[derive(Debug, Snafu)]
+pub enum ChunkedGelfDecoderError {
+    /// The chunk announced an invalid number of chunks for its message.
+    #[snafu(display(
+        "Chunk of message {:#x} has an invalid sequence count {}, expected 1 to {}",
+        message_id,
+        count,
+        GELF_MAX_CHUNKS
+    ))]
+    InvalidSequenceCount {
+        /// The ID of the message the chunk belongs to.
+        message_id: u64,
+        /// The announced number of chunks.
+        count: u8,
+    },
+    /// The chunk's position lies outside of its message.
+    #[snafu(display(
+        "Chunk of message {:#x} has an invalid sequence number {} for a sequence count of {}",
+        message_id,
+        number,
+        count
+    ))]
+    InvalidSequenceNumber {
+        /// The ID of the message the chunk belongs to.
+        message_id: u64,
+        /// The position of the chunk.
+        number: u8,
+        /// The announced number of chunks.
+        count: u8,
+    },
+    /// Too many messages are being reassembled to start a new one.
+    #[snafu(display(
+        "Discarding chunk of message {:#x}, as {} messages are already being reassembled",
+        message_id,
+        limit
+    ))]
+    PendingMessagesLimitReached {
+        /// The ID of the message the chunk belongs to.
+        message_id: u64,
+        /// The configured limit of messages being reassembled.
+        limit: usize,
+    },
+}
+
+impl StreamDecodingError for ChunkedGelfDecoderError {
+    fn can_continue(&self) -> bool {
+        // Each datagram is self-contained, so an invalid chunk never affects the following ones.
+        true
+    }
+}
+
+impl FramingError for ChunkedGelfDecoderError {}
+
+impl From<ChunkedGelfDecoderError> for BoxedFramingError {
+    fn from(error: ChunkedGelfDecoderError) -> Self {
+        Box::new(error)
+    }
+}
+
+#[derive(Debug)]
+struct PendingMessage {
+    chunks: Vec<Option<Bytes>>,
+    received: usize,
+    first_seen: Instant,
+}
+
+/// A decoder for reassembling GELF messages chunked over multiple UDP datagrams.
+///
+/// Each input is expected to be a single datagram. Datagrams that aren't chunks are passed through
+/// as-is, so unchunked GELF messages can be sent to the same socket. The reassembly state is shared
+/// between clones of the decoder, as message based sources clone their decoder per datagram.
+#[derive(Debug, Clone)]
+pub struct ChunkedGelfDecoder {
+    timeout: Duration,
+    pending_messages_limit: usize,
+    pending: Arc<Mutex<HashMap<u64, PendingMessage>>>,
+}
+
+impl ChunkedGelfDecoder {
+    /// Creates a new `ChunkedGelfDecoder`.
+    pub fn new(timeout: Duration, pending_messages_limit: usize) -> Self {
+        Self {
+            timeout,
+            pending_messages_limit,
+            pending: Default::default(),
+        }
+    }
+
+    fn decode_chunk(&self, mut chunk: BytesMut) -> Result<Option<Bytes>, ChunkedGelfDecoderError> {
+        chunk.advance(GELF_MAGIC.len());
+        let message_id = chunk.get_u64();
+        let number = chunk.get_u8();
+        let count = chunk.get_u8();
+
+        if count == 0 || count > GELF_MAX_CHUNKS {
+            return Err(ChunkedGelfDecoderError::InvalidSequenceCount { message_id, count });
+        }
+        if number >= count {
+            return Err(ChunkedGelfDecoderError::InvalidSequenceNumber {
+                message_id,
+                number,
+                count,
+            });
+        }
+
+        let mut pending = self.pending.lock().expect("poisoned lock");
+
+        let now = Instant::now();
+        let timeout = self.timeout;
+        pending.retain(|message_id, message| {
+            let expired = now.duration_since(message.first_seen) > timeout;
+            if expired {
+                warn!(
+                    message = "Discarding incomplete chunked GELF message after timeout.",
+                    message_id = %format!("{:#x}", message_id),
+                    received_chunks = message.received,
+                    expected_chunks = message.chunks.len(),
+                    internal_log_rate_secs = 30
+                );
+            }
+            !expired
+        });
+
+        if !pending.contains_key(&message_id) && pending.len() >= self.pending_messages_limit {
+            return Err(ChunkedGelfDecoderError::PendingMessagesLimitReached {
+                message_id,
+                limit: self.pending_messages_limit,
+            });
+        }
+
+        let message = pending.entry(message_id).or_insert_with(|| PendingMessage {
+            chunks: vec![None; count as usize],
+            received: 0,
+            first_seen: now,
+        });
+
+        if message.chunks.len() != count as usize {
+            // The chunk doesn't agree with the previous ones on the size of the message, so none
+            // of them can be trusted.
+            pending.remove(&message_id);
+            return Err(ChunkedGelfDecoderError::InvalidSequenceCount { message_id, count });
+        }
+
+        let slot = &mut message.chunks[number as usize];
+        if slot.is_none() {
+            message.received += 1;
+        }
+        *slot = Some(chunk.freeze());
+
+        if message.received < message.chunks.len() {
+            trace!(
+                message = "Buffered chunk of GELF message.",
+                message_id = %format!("{:#x}", message_id),
+                received_chunks = message.received,
+                expected_chunks = message.chunks.len(),
+            );
+            return Ok(None);
+        }
+
+        let message = pending.remove(&message_id).expect("message is pending");
+        let mut frame = BytesMut::new();
+        for chunk in message.chunks.into_iter().flatten() {
+            frame.extend_from_slice(&chunk);
+        }
+        Ok(Some(frame.freeze()))
+    }
+}
+
+impl Default for ChunkedGelfDecoder {
+    fn default() -> Self {
+        Self::new(
+            Duration::from_secs_f64(default_timeout_secs()),
+            default_pending_messages_limit(),
+        )
+    }
+}
+
+impl Decoder for ChunkedGelfDecoder {
+    type Item = Bytes;
+    type Error = BoxedFramingError;
+
+    fn decode(&mut self, _src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        // A datagram can only be handled once it has been read entirely.
+        Ok(None)
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if src.is_empty() {
+            return Ok(None);
+        }
+
+        let datagram = src.split();
+        if datagram.len() < GELF_CHUNK_HEADER_LENGTH || datagram[..2] != GELF_MAGIC {
+            return Ok(Some(datagram.freeze()));
+        }
+
+        Ok(self.decode_chunk(datagram)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BufMut;
+
+    use super::*;
+
+    fn chunk(message_id: u64, number: u8, count: u8, payload: &str) -> BytesMut {
+        let mut chunk = BytesMut::new();
+        chunk.extend_from_slice(&GELF_MAGIC);
+        chunk.put_u64(message_id);
+        chunk.put_u8(number);
+        chunk.put_u8(count);
+        chunk.extend_from_slice(payload.as_bytes());
+        chunk
+    }
+
+    fn decode(decoder: &ChunkedGelfDecoder, mut datagram: BytesMut) -> Option<Bytes> {
+        // Mimic message based sources, which clone the decoder for each datagram.
+        let mut decoder = decoder.clone();
+        assert_eq!(decoder.decode(&mut datagram).unwrap(), None);
+        let frame = decoder.decode_eof(&mut datagram).unwrap();
+        assert_eq!(decoder.decode_eof(&mut datagram).unwrap(), None);
+        frame
+    }
+
+    #[test]
+    fn passes_through_unchunked_messages() {
+        let decoder = ChunkedGelfDecoder::default();
+        let message = r#"{"version":"1.1","host":"example.org","short_message":"foo"}"#;
+
+        assert_eq!(
+            decode(&decoder, BytesMut::from(message)),
+            Some(Bytes::from(message))
+        );
+    }
+
+    #[test]
+    fn reassembles_chunks_in_any_order() {
+        let decoder = ChunkedGelfDecoder::default();
+
+        assert_eq!(decode(&decoder, chunk(1, 2, 3, "baz")), None);
+        assert_eq!(decode(&decoder, chunk(2, 0, 2, "other ")), None);
+        assert_eq!(decode(&decoder, chunk(1, 0, 3, "foo")), None);
+        // Duplicated chunks replace the previous ones.
+        assert_eq!(decode(&decoder, chunk(1, 0, 3, "foo")), None);
+        assert_eq!(
+            decode(&decoder, chunk(1, 1, 3, "bar")),
+            Some(Bytes::from("foobarbaz"))
+        );
+        assert_eq!(
+            decode(&decoder, chunk(2, 1, 2, "message")),
+            Some(Bytes::from("other message"))
+        );
+        assert!(decoder.pending.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_sequences() {
+        let mut decoder = ChunkedGelfDecoder::default();
+
+        for datagram in [
+            chunk(1, 0, 0, "foo"),
+            chunk(1, 0, 129, "foo"),
+            chunk(1, 3, 3, "foo"),
+        ] {
+            let error = decoder.decode_eof(&mut datagram.clone()).unwrap_err();
+            assert!(error.can_continue());
+        }
+
+        assert_eq!(decode(&decoder, chunk(2, 0, 2, "foo")), None);
+        assert!(decoder.decode_eof(&mut chunk(2, 1, 3, "bar")).is_err());
+        assert!(decoder.pending.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn discards_expired_messages() {
+        let decoder = ChunkedGelfDecoder::new(Duration::ZERO, 10);
+
+        assert_eq!(decode(&decoder, chunk(1, 0, 2, "foo")), None);
+        std::thread::sleep(Duration::from_millis(1));
+        assert_eq!(decode(&decoder, chunk(2, 0, 2, "bar")), None);
+
+        let pending = decoder.pending.lock().unwrap();
+        assert_eq!(pending.keys().collect::<Vec<_>>(), vec![&2]);
+    }
+
+    #[test]
+    fn limits_pending_messages() {
+        let mut decoder = ChunkedGelfDecoder::new(Duration::from_secs(5), 1);
+
+        assert_eq!(decode(&decoder, chunk(1, 0, 2, "foo")), None);
+        assert!(decoder.decode_eof(&mut chunk(2, 0, 2, "bar")).is_err());
+        assert_eq!(
+            decode(&decoder, chunk(1, 1, 2, "bar")),
+            Some(Bytes::from("foobar"))
+        );
+        assert_eq!(decode(&decoder, chunk(2, 0, 2, "bar")), None);
+    }
+}
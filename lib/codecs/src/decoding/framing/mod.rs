@@ -5,7 +5,9 @@
 
 mod bytes;
 mod character_delimited;
+mod chunked_gelf;
 mod length_delimited;
+mod multiline;
 mod newline_delimited;
 mod octet_counting;
 
@@ -15,8 +17,15 @@ use ::bytes::Bytes;
 pub use character_delimited::{
     CharacterDelimitedDecoder, CharacterDelimitedDecoderConfig, CharacterDelimitedDecoderOptions,
 };
+pub use chunked_gelf::{
+    ChunkedGelfDecoder, ChunkedGelfDecoderConfig, ChunkedGelfDecoderError,
+    ChunkedGelfDecoderOptions,
+};
 use dyn_clone::DynClone;
 pub use length_delimited::{LengthDelimitedDecoder, LengthDelimitedDecoderConfig};
+pub use multiline::{
+    MultilineDecoder, MultilineDecoderConfig, MultilineDecoderOptions, MultilineMode,
+};
 pub use newline_delimited::{
     NewlineDelimitedDecoder, NewlineDelimitedDecoderConfig, NewlineDelimitedDecoderOptions,
 };
@@ -0,0 +1,363 @@
+use std::time::{Duration, Instant};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use regex::bytes::Regex;
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Decoder;
+use vector_config::configurable_component;
+
+use super::{BoxedFramingError, NewlineDelimitedDecoder};
+use crate::decoding::BuildError;
+
+/// Mode of operation of the line aggregator.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Hash, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MultilineMode {
+    /// All consecutive lines matching this pattern are included in the group.
+    ///
+    /// The first line (the line that matched the start pattern) does not need to match the `ContinueThrough` pattern.
+    ///
+    /// This is useful in cases such as a Java stack trace, where some indicator in the line (such as leading
+    /// whitespace) indicates that it is an extension of the proceeding line.
+    ContinueThrough,
+
+    /// All consecutive lines matching this pattern, plus one additional line, are included in the group.
+    ///
+    /// This is useful in cases where a log message ends with a continuation marker, such as a backslash, indicating
+    /// that the following line is part of the same message.
+    ContinuePast,
+
+    /// All consecutive lines not matching this pattern are included in the group.
+    ///
+    /// This is useful where a log line contains a marker indicating that it begins a new message.
+    HaltBefore,
+
+    /// All consecutive lines, up to and including the first line matching this pattern, are included in the group.
+    ///
+    /// This is useful where a log line ends with a termination marker, such as a semicolon.
+    HaltWith,
+}
+
+/// Config used to build a `MultilineDecoder`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct MultilineDecoderConfig {
+    /// Options for the multiline decoder.
+    pub multiline: MultilineDecoderOptions,
+}
+
+impl MultilineDecoderConfig {
+    /// Build the `MultilineDecoder` from this configuration.
+    pub fn build(&self) -> Result<MultilineDecoder, BuildError> {
+        let start_pattern = Regex::new(&self.multiline.start_pattern).map_err(|error| {
+            format!(
+                "Unable to parse multiline start pattern from {:?}: {}",
+                self.multiline.start_pattern, error
+            )
+        })?;
+        let condition_pattern = Regex::new(&self.multiline.condition_pattern).map_err(|error| {
+            format!(
+                "Unable to parse multiline condition pattern from {:?}: {}",
+                self.multiline.condition_pattern, error
+            )
+        })?;
+
+        Ok(MultilineDecoder::new(
+            start_pattern,
+            condition_pattern,
+            self.multiline.mode,
+            Duration::from_millis(self.multiline.timeout_ms),
+        ))
+    }
+}
+
+/// Options for building a `MultilineDecoder`.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultilineDecoderOptions {
+    /// Regular expression pattern that is used to match the start of a new message.
+    pub start_pattern: String,
+
+    /// Regular expression pattern that is used to determine whether or not more lines should be read.
+    ///
+    /// This setting must be configured in conjunction with `mode`.
+    pub condition_pattern: String,
+
+    /// Aggregation mode.
+    ///
+    /// This setting must be configured in conjunction with `condition_pattern`.
+    pub mode: MultilineMode,
+
+    /// The maximum amount of time to wait for the next additional line, in milliseconds.
+    ///
+    /// Since frames are only decoded as data arrives, a message is flushed once a line is received
+    /// after this timeout elapsed, or when the stream ends.
+    pub timeout_ms: u64,
+}
+
+/// A codec for aggregating newline delimited lines into multiline messages, such as stack traces.
+///
+/// The aggregated lines are joined by a newline.
+#[derive(Debug, Clone)]
+pub struct MultilineDecoder {
+    lines: NewlineDelimitedDecoder,
+    start_pattern: Regex,
+    condition_pattern: Regex,
+    mode: MultilineMode,
+    timeout: Duration,
+    /// The message being aggregated, and when its last line was received.
+    aggregate: Option<(BytesMut, Instant)>,
+    /// A line to return as-is before decoding any further lines.
+    pending: Option<Bytes>,
+}
+
+impl MultilineDecoder {
+    /// Creates a new `MultilineDecoder`.
+    pub const fn new(
+        start_pattern: Regex,
+        condition_pattern: Regex,
+        mode: MultilineMode,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            lines: NewlineDelimitedDecoder::new(),
+            start_pattern,
+            condition_pattern,
+            mode,
+            timeout,
+            aggregate: None,
+            pending: None,
+        }
+    }
+
+    /// Handles a line, returning a frame if one is complete.
+    fn handle_line(&mut self, line: Bytes) -> Option<Bytes> {
+        let (mut buffer, _) = match self.aggregate.take() {
+            Some(aggregate) => aggregate,
+            None => return self.start(line),
+        };
+
+        let condition_matched = self.condition_pattern.is_match(&line);
+        match (self.mode, condition_matched) {
+            // The line continues the message.
+            (MultilineMode::ContinueThrough, true)
+            | (MultilineMode::ContinuePast, true)
+            | (MultilineMode::HaltBefore, false)
+            | (MultilineMode::HaltWith, false) => {
+                buffer.put_u8(b'\n');
+                buffer.extend_from_slice(&line);
+                self.aggregate = Some((buffer, Instant::now()));
+                None
+            }
+            // The line ends the message.
+            (MultilineMode::ContinuePast, false) | (MultilineMode::HaltWith, true) => {
+                buffer.put_u8(b'\n');
+                buffer.extend_from_slice(&line);
+                Some(buffer.freeze())
+            }
+            // The message ended before this line, which may start the next one.
+            (MultilineMode::ContinueThrough, false) | (MultilineMode::HaltBefore, true) => {
+                self.pending = self.start(line);
+                Some(buffer.freeze())
+            }
+        }
+    }
+
+    /// Starts aggregating a message if the line matches the start pattern, otherwise returns the
+    /// line as-is.
+    fn start(&mut self, line: Bytes) -> Option<Bytes> {
+        if self.start_pattern.is_match(&line) {
+            self.aggregate = Some((BytesMut::from(&line[..]), Instant::now()));
+            None
+        } else {
+            Some(line)
+        }
+    }
+
+    /// Returns the message being aggregated if no line was received within the timeout.
+    fn flush_expired(&mut self) -> Option<Bytes> {
+        let expired = matches!(
+            &self.aggregate,
+            Some((_, last_line)) if last_line.elapsed() >= self.timeout
+        );
+        if expired {
+            self.aggregate.take().map(|(buffer, _)| buffer.freeze())
+        } else {
+            None
+        }
+    }
+}
+
+impl Decoder for MultilineDecoder {
+    type Item = Bytes;
+    type Error = BoxedFramingError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if let Some(frame) = self.pending.take() {
+            return Ok(Some(frame));
+        }
+
+        loop {
+            if let Some(frame) = self.flush_expired() {
+                return Ok(Some(frame));
+            }
+
+            let line = match self.lines.decode(src)? {
+                Some(line) => line,
+                None => return Ok(None),
+            };
+            if let Some(frame) = self.handle_line(line) {
+                return Ok(Some(frame));
+            }
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if let Some(frame) = self.decode(src)? {
+            return Ok(Some(frame));
+        }
+
+        // The last line may not be terminated by a newline.
+        if let Some(line) = self.lines.decode_eof(src)? {
+            if let Some(frame) = self.handle_line(line) {
+                return Ok(Some(frame));
+            }
+        }
+
+        Ok(self.aggregate.take().map(|(buffer, _)| buffer.freeze()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decoder(
+        start_pattern: &str,
+        condition_pattern: &str,
+        mode: MultilineMode,
+    ) -> MultilineDecoder {
+        MultilineDecoderConfig {
+            multiline: MultilineDecoderOptions {
+                start_pattern: start_pattern.to_owned(),
+                condition_pattern: condition_pattern.to_owned(),
+                mode,
+                timeout_ms: 60_000,
+            },
+        }
+        .build()
+        .unwrap()
+    }
+
+    fn decode_all(decoder: &mut MultilineDecoder, input: &str) -> Vec<Bytes> {
+        let mut buffer = BytesMut::from(input);
+        let mut frames = Vec::new();
+        while let Some(frame) = decoder.decode_eof(&mut buffer).unwrap() {
+            frames.push(frame);
+        }
+        frames
+    }
+
+    #[test]
+    fn decode_continue_through() {
+        let mut decoder = decoder(r"^[^\s]", r"^[\s]+", MultilineMode::ContinueThrough);
+        let input = "some usual line\nfirst part\n second part\n last part\nanother message\n trailing part";
+
+        assert_eq!(
+            decode_all(&mut decoder, input),
+            vec![
+                "some usual line",
+                "first part\n second part\n last part",
+                "another message\n trailing part",
+            ]
+        );
+    }
+
+    #[test]
+    fn decode_continue_past() {
+        let mut decoder = decoder(r"\\$", r"\\$", MultilineMode::ContinuePast);
+        let input = "usual line\nfirst part \\\nsecond part \\\nlast part\nanother line\n";
+
+        assert_eq!(
+            decode_all(&mut decoder, input),
+            vec![
+                "usual line",
+                "first part \\\nsecond part \\\nlast part",
+                "another line",
+            ]
+        );
+    }
+
+    #[test]
+    fn decode_halt_before() {
+        let mut decoder = decoder("", r"^(INFO|ERROR) ", MultilineMode::HaltBefore);
+        let input = "INFO first\nERROR second\n  at foo\n  at bar\nINFO third\n";
+
+        assert_eq!(
+            decode_all(&mut decoder, input),
+            vec![
+                "INFO first",
+                "ERROR second\n  at foo\n  at bar",
+                "INFO third"
+            ]
+        );
+    }
+
+    #[test]
+    fn decode_halt_with() {
+        let mut decoder = decoder("[^;]$", ";$", MultilineMode::HaltWith);
+        let input = "single;\nfirst\nsecond\nthird;\n";
+
+        assert_eq!(
+            decode_all(&mut decoder, input),
+            vec!["single;", "first\nsecond\nthird;"]
+        );
+    }
+
+    #[test]
+    fn decode_waits_for_complete_message() {
+        let mut decoder = decoder(r"^[^\s]", r"^[\s]+", MultilineMode::ContinueThrough);
+        let mut buffer = BytesMut::from("first part\n second part\n");
+
+        assert_eq!(decoder.decode(&mut buffer).unwrap(), None);
+
+        buffer.extend_from_slice(b"next\n");
+        assert_eq!(
+            decoder.decode(&mut buffer).unwrap().unwrap(),
+            "first part\n second part"
+        );
+        assert_eq!(decoder.decode(&mut buffer).unwrap(), None);
+    }
+
+    #[test]
+    fn decode_flushes_after_timeout() {
+        let mut decoder = MultilineDecoder::new(
+            Regex::new(r"^[^\s]").unwrap(),
+            Regex::new(r"^[\s]+").unwrap(),
+            MultilineMode::ContinueThrough,
+            Duration::from_millis(0),
+        );
+        let mut buffer = BytesMut::from("first part\n second part\n");
+
+        // The message is flushed before the next line is read, which is then no longer part of it.
+        assert_eq!(decoder.decode(&mut buffer).unwrap().unwrap(), "first part");
+        assert_eq!(
+            decoder.decode(&mut buffer).unwrap().unwrap(),
+            " second part"
+        );
+        assert_eq!(decoder.decode(&mut buffer).unwrap(), None);
+    }
+
+    #[test]
+    fn build_fails_for_invalid_pattern() {
+        let config = MultilineDecoderConfig {
+            multiline: MultilineDecoderOptions {
+                start_pattern: "(".to_owned(),
+                condition_pattern: "".to_owned(),
+                mode: MultilineMode::HaltBefore,
+                timeout_ms: 1000,
+            },
+        };
+        assert!(config.build().is_err());
+    }
+}
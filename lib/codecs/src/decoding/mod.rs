@@ -6,20 +6,32 @@ pub mod format;
 pub mod framing;
 
 use bytes::{Bytes, BytesMut};
+use derivative::Derivative;
 pub use error::StreamDecodingError;
+#[cfg(feature = "avro-registry")]
+pub use format::{AvroDeserializer, AvroDeserializerConfig, AvroDeserializerOptions};
 pub use format::{
-    BoxedDeserializer, BytesDeserializer, BytesDeserializerConfig, GelfDeserializer,
-    GelfDeserializerConfig, JsonDeserializer, JsonDeserializerConfig, NativeDeserializer,
-    NativeDeserializerConfig, NativeJsonDeserializer, NativeJsonDeserializerConfig,
+    BoxedDeserializer, BytesDeserializer, BytesDeserializerConfig, CefDeserializer,
+    CefDeserializerConfig, GelfDeserializer, GelfDeserializerConfig, GelfDeserializerOptions,
+    JsonDeserializer, JsonDeserializerConfig, NativeDeserializer, NativeDeserializerConfig,
+    NativeJsonDeserializer, NativeJsonDeserializerConfig,
 };
+#[cfg(feature = "csv")]
+pub use format::{CsvDeserializer, CsvDeserializerConfig, CsvDeserializerOptions};
+#[cfg(feature = "msgpack")]
+pub use format::{MsgpackDeserializer, MsgpackDeserializerConfig};
+#[cfg(feature = "protobuf-dynamic")]
+pub use format::{ProtobufDeserializer, ProtobufDeserializerConfig, ProtobufDeserializerOptions};
 #[cfg(feature = "syslog")]
 pub use format::{SyslogDeserializer, SyslogDeserializerConfig};
 pub use framing::{
     BoxedFramer, BoxedFramingError, BytesDecoder, BytesDecoderConfig, CharacterDelimitedDecoder,
-    CharacterDelimitedDecoderConfig, CharacterDelimitedDecoderOptions, FramingError,
-    LengthDelimitedDecoder, LengthDelimitedDecoderConfig, NewlineDelimitedDecoder,
-    NewlineDelimitedDecoderConfig, NewlineDelimitedDecoderOptions, OctetCountingDecoder,
-    OctetCountingDecoderConfig, OctetCountingDecoderOptions,
+    CharacterDelimitedDecoderConfig, CharacterDelimitedDecoderOptions, ChunkedGelfDecoder,
+    ChunkedGelfDecoderConfig, ChunkedGelfDecoderError, ChunkedGelfDecoderOptions, FramingError,
+    LengthDelimitedDecoder, LengthDelimitedDecoderConfig, MultilineDecoder, MultilineDecoderConfig,
+    MultilineDecoderOptions, MultilineMode, NewlineDelimitedDecoder, NewlineDelimitedDecoderConfig,
+    NewlineDelimitedDecoderOptions, OctetCountingDecoder, OctetCountingDecoderConfig,
+    OctetCountingDecoderOptions,
 };
 use smallvec::SmallVec;
 use std::fmt::Debug;
@@ -30,6 +42,9 @@ use vector_core::{
     schema,
 };
 
+/// An error that occurred while building a decoder.
+pub type BuildError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
 /// An error that occurred while decoding structured events from a byte stream /
 /// byte messages.
 #[derive(Debug)]
@@ -67,6 +82,36 @@ impl StreamDecodingError for Error {
     }
 }
 
+/// How frames that fail to be deserialized are handled.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, PartialEq, Eq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DecodingErrorMode {
+    /// The frame is dropped, and the error is logged.
+    #[derivative(Default)]
+    Drop,
+
+    /// The frame is emitted as a raw text event, as if it was decoded with the `bytes` codec.
+    Raw,
+
+    /// The frame is emitted as a raw text event, tagged with the error in the `decoding_error`
+    /// field.
+    ///
+    /// This allows routing malformed frames to a dead-letter sink.
+    Route,
+}
+
+impl DecodingErrorMode {
+    /// The type of events emitted by a decoder whose deserializer outputs the given type.
+    pub fn output_type(&self, output_type: DataType) -> DataType {
+        match self {
+            Self::Drop => output_type,
+            Self::Raw | Self::Route => output_type | DataType::Log,
+        }
+    }
+}
+
 /// Configuration for building a `Framer`.
 // Unfortunately, copying options of the nested enum variants is necessary
 // since `serde` doesn't allow `flatten`ing these:
@@ -82,8 +127,22 @@ pub enum FramingConfig {
         /// Options for the character delimited decoder.
         character_delimited: CharacterDelimitedDecoderOptions,
     },
+    /// Configures the `ChunkedGelfDecoder`.
+    ChunkedGelf {
+        #[serde(
+            default,
+            skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+        )]
+        /// Options for the chunked GELF decoder.
+        chunked_gelf: ChunkedGelfDecoderOptions,
+    },
     /// Configures the `LengthDelimitedDecoder`.
     LengthDelimited,
+    /// Configures the `MultilineDecoder`.
+    Multiline {
+        /// Options for the multiline decoder.
+        multiline: MultilineDecoderOptions,
+    },
     /// Configures the `NewlineDelimitedDecoder`.
     NewlineDelimited {
         #[serde(
@@ -118,12 +177,28 @@ impl From<CharacterDelimitedDecoderConfig> for FramingConfig {
     }
 }
 
+impl From<ChunkedGelfDecoderConfig> for FramingConfig {
+    fn from(config: ChunkedGelfDecoderConfig) -> Self {
+        Self::ChunkedGelf {
+            chunked_gelf: config.chunked_gelf,
+        }
+    }
+}
+
 impl From<LengthDelimitedDecoderConfig> for FramingConfig {
     fn from(_: LengthDelimitedDecoderConfig) -> Self {
         Self::LengthDelimited
     }
 }
 
+impl From<MultilineDecoderConfig> for FramingConfig {
+    fn from(config: MultilineDecoderConfig) -> Self {
+        Self::Multiline {
+            multiline: config.multiline,
+        }
+    }
+}
+
 impl From<NewlineDelimitedDecoderConfig> for FramingConfig {
     fn from(config: NewlineDelimitedDecoderConfig) -> Self {
         Self::NewlineDelimited {
@@ -142,32 +217,44 @@ impl From<OctetCountingDecoderConfig> for FramingConfig {
 
 impl FramingConfig {
     /// Build the `Framer` from this configuration.
-    pub fn build(&self) -> Framer {
+    pub fn build(&self) -> Result<Framer, BuildError> {
         match self {
-            FramingConfig::Bytes => Framer::Bytes(BytesDecoderConfig.build()),
+            FramingConfig::Bytes => Ok(Framer::Bytes(BytesDecoderConfig.build())),
             FramingConfig::CharacterDelimited {
                 character_delimited,
-            } => Framer::CharacterDelimited(
+            } => Ok(Framer::CharacterDelimited(
                 CharacterDelimitedDecoderConfig {
                     character_delimited: character_delimited.clone(),
                 }
                 .build(),
-            ),
-            FramingConfig::LengthDelimited => {
-                Framer::LengthDelimited(LengthDelimitedDecoderConfig.build())
-            }
-            FramingConfig::NewlineDelimited { newline_delimited } => Framer::NewlineDelimited(
+            )),
+            FramingConfig::ChunkedGelf { chunked_gelf } => Ok(Framer::ChunkedGelf(
+                ChunkedGelfDecoderConfig {
+                    chunked_gelf: chunked_gelf.clone(),
+                }
+                .build(),
+            )),
+            FramingConfig::LengthDelimited => Ok(Framer::LengthDelimited(
+                LengthDelimitedDecoderConfig.build(),
+            )),
+            FramingConfig::Multiline { multiline } => Ok(Framer::Multiline(
+                MultilineDecoderConfig {
+                    multiline: multiline.clone(),
+                }
+                .build()?,
+            )),
+            FramingConfig::NewlineDelimited { newline_delimited } => Ok(Framer::NewlineDelimited(
                 NewlineDelimitedDecoderConfig {
                     newline_delimited: newline_delimited.clone(),
                 }
                 .build(),
-            ),
-            FramingConfig::OctetCounting { octet_counting } => Framer::OctetCounting(
+            )),
+            FramingConfig::OctetCounting { octet_counting } => Ok(Framer::OctetCounting(
                 OctetCountingDecoderConfig {
                     octet_counting: octet_counting.clone(),
                 }
                 .build(),
-            ),
+            )),
         }
     }
 }
@@ -179,8 +266,12 @@ pub enum Framer {
     Bytes(BytesDecoder),
     /// Uses a `CharacterDelimitedDecoder` for framing.
     CharacterDelimited(CharacterDelimitedDecoder),
+    /// Uses a `ChunkedGelfDecoder` for framing.
+    ChunkedGelf(ChunkedGelfDecoder),
     /// Uses a `LengthDelimitedDecoder` for framing.
     LengthDelimited(LengthDelimitedDecoder),
+    /// Uses a `MultilineDecoder` for framing.
+    Multiline(MultilineDecoder),
     /// Uses a `NewlineDelimitedDecoder` for framing.
     NewlineDelimited(NewlineDelimitedDecoder),
     /// Uses a `OctetCountingDecoder` for framing.
@@ -197,7 +288,9 @@ impl tokio_util::codec::Decoder for Framer {
         match self {
             Framer::Bytes(framer) => framer.decode(src),
             Framer::CharacterDelimited(framer) => framer.decode(src),
+            Framer::ChunkedGelf(framer) => framer.decode(src),
             Framer::LengthDelimited(framer) => framer.decode(src),
+            Framer::Multiline(framer) => framer.decode(src),
             Framer::NewlineDelimited(framer) => framer.decode(src),
             Framer::OctetCounting(framer) => framer.decode(src),
             Framer::Boxed(framer) => framer.decode(src),
@@ -208,7 +301,9 @@ impl tokio_util::codec::Decoder for Framer {
         match self {
             Framer::Bytes(framer) => framer.decode_eof(src),
             Framer::CharacterDelimited(framer) => framer.decode_eof(src),
+            Framer::ChunkedGelf(framer) => framer.decode_eof(src),
             Framer::LengthDelimited(framer) => framer.decode_eof(src),
+            Framer::Multiline(framer) => framer.decode_eof(src),
             Framer::NewlineDelimited(framer) => framer.decode_eof(src),
             Framer::OctetCounting(framer) => framer.decode_eof(src),
             Framer::Boxed(framer) => framer.decode_eof(src),
@@ -224,10 +319,31 @@ impl tokio_util::codec::Decoder for Framer {
 #[derive(Clone, Debug)]
 #[serde(tag = "codec", rename_all = "snake_case")]
 pub enum DeserializerConfig {
+    #[cfg(feature = "avro-registry")]
+    /// Configures the `AvroDeserializer`.
+    Avro {
+        /// Options for the Avro deserializer.
+        avro: AvroDeserializerOptions,
+    },
     /// Configures the `BytesDeserializer`.
     Bytes,
+    /// Configures the `CefDeserializer`.
+    Cef,
+    #[cfg(feature = "csv")]
+    /// Configures the `CsvDeserializer`.
+    Csv {
+        #[serde(
+            default,
+            skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+        )]
+        /// Options for the CSV deserializer.
+        csv: CsvDeserializerOptions,
+    },
     /// Configures the `JsonDeserializer`.
     Json,
+    #[cfg(feature = "msgpack")]
+    /// Configures the `MsgpackDeserializer`.
+    Msgpack,
     #[cfg(feature = "syslog")]
     /// Configures the `SyslogDeserializer`.
     Syslog,
@@ -236,7 +352,27 @@ pub enum DeserializerConfig {
     /// Configures the `NativeJsonDeserializer`.
     NativeJson,
     /// Configures the `GelfDeserializer`.
-    Gelf,
+    Gelf {
+        #[serde(
+            default,
+            skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+        )]
+        /// Options for the GELF deserializer.
+        gelf: GelfDeserializerOptions,
+    },
+    #[cfg(feature = "protobuf-dynamic")]
+    /// Configures the `ProtobufDeserializer`.
+    Protobuf {
+        /// Options for the Protobuf deserializer.
+        protobuf: ProtobufDeserializerOptions,
+    },
+}
+
+#[cfg(feature = "avro-registry")]
+impl From<AvroDeserializerConfig> for DeserializerConfig {
+    fn from(config: AvroDeserializerConfig) -> Self {
+        Self::Avro { avro: config.avro }
+    }
 }
 
 impl From<BytesDeserializerConfig> for DeserializerConfig {
@@ -245,12 +381,32 @@ impl From<BytesDeserializerConfig> for DeserializerConfig {
     }
 }
 
+impl From<CefDeserializerConfig> for DeserializerConfig {
+    fn from(_: CefDeserializerConfig) -> Self {
+        Self::Cef
+    }
+}
+
+#[cfg(feature = "csv")]
+impl From<CsvDeserializerConfig> for DeserializerConfig {
+    fn from(config: CsvDeserializerConfig) -> Self {
+        Self::Csv { csv: config.csv }
+    }
+}
+
 impl From<JsonDeserializerConfig> for DeserializerConfig {
     fn from(_: JsonDeserializerConfig) -> Self {
         Self::Json
     }
 }
 
+#[cfg(feature = "msgpack")]
+impl From<MsgpackDeserializerConfig> for DeserializerConfig {
+    fn from(_: MsgpackDeserializerConfig) -> Self {
+        Self::Msgpack
+    }
+}
+
 #[cfg(feature = "syslog")]
 impl From<SyslogDeserializerConfig> for DeserializerConfig {
     fn from(_: SyslogDeserializerConfig) -> Self {
@@ -259,34 +415,82 @@ impl From<SyslogDeserializerConfig> for DeserializerConfig {
 }
 
 impl From<GelfDeserializerConfig> for DeserializerConfig {
-    fn from(_: GelfDeserializerConfig) -> Self {
-        Self::Gelf
+    fn from(config: GelfDeserializerConfig) -> Self {
+        Self::Gelf { gelf: config.gelf }
+    }
+}
+
+#[cfg(feature = "protobuf-dynamic")]
+impl From<ProtobufDeserializerConfig> for DeserializerConfig {
+    fn from(config: ProtobufDeserializerConfig) -> Self {
+        Self::Protobuf {
+            protobuf: config.protobuf,
+        }
     }
 }
 
 impl DeserializerConfig {
     /// Build the `Deserializer` from this configuration.
-    pub fn build(&self) -> Deserializer {
+    pub fn build(&self) -> Result<Deserializer, BuildError> {
         match self {
-            DeserializerConfig::Bytes => Deserializer::Bytes(BytesDeserializerConfig.build()),
-            DeserializerConfig::Json => Deserializer::Json(JsonDeserializerConfig.build()),
+            #[cfg(feature = "avro-registry")]
+            DeserializerConfig::Avro { avro } => Ok(Deserializer::Avro(
+                AvroDeserializerConfig::new(avro.schema_registry_url.clone()).build(),
+            )),
+            DeserializerConfig::Bytes => Ok(Deserializer::Bytes(BytesDeserializerConfig.build())),
+            DeserializerConfig::Cef => Ok(Deserializer::Cef(CefDeserializerConfig.build())),
+            #[cfg(feature = "csv")]
+            DeserializerConfig::Csv { csv } => Ok(Deserializer::Csv(
+                CsvDeserializerConfig { csv: csv.clone() }.build(),
+            )),
+            DeserializerConfig::Json => Ok(Deserializer::Json(JsonDeserializerConfig.build())),
+            #[cfg(feature = "msgpack")]
+            DeserializerConfig::Msgpack => {
+                Ok(Deserializer::Msgpack(MsgpackDeserializerConfig.build()))
+            }
             #[cfg(feature = "syslog")]
-            DeserializerConfig::Syslog => Deserializer::Syslog(SyslogDeserializerConfig.build()),
-            DeserializerConfig::Native => Deserializer::Native(NativeDeserializerConfig.build()),
-            DeserializerConfig::NativeJson => {
-                Deserializer::NativeJson(NativeJsonDeserializerConfig.build())
+            DeserializerConfig::Syslog => {
+                Ok(Deserializer::Syslog(SyslogDeserializerConfig.build()))
+            }
+            DeserializerConfig::Native => {
+                Ok(Deserializer::Native(NativeDeserializerConfig.build()))
             }
-            DeserializerConfig::Gelf => Deserializer::Gelf(GelfDeserializerConfig.build()),
+            DeserializerConfig::NativeJson => Ok(Deserializer::NativeJson(
+                NativeJsonDeserializerConfig.build(),
+            )),
+            DeserializerConfig::Gelf { gelf } => Ok(Deserializer::Gelf(
+                GelfDeserializerConfig { gelf: gelf.clone() }.build(),
+            )),
+            #[cfg(feature = "protobuf-dynamic")]
+            DeserializerConfig::Protobuf { protobuf } => Ok(Deserializer::Protobuf(
+                ProtobufDeserializerConfig {
+                    protobuf: protobuf.clone(),
+                }
+                .build()?,
+            )),
         }
     }
 
     /// Return an appropriate default framer for the given deserializer
     pub fn default_stream_framing(&self) -> FramingConfig {
         match self {
+            // Avro and Protobuf are binary formats, so each message is expected to be framed by
+            // the transport. MessagePack values are self-delimiting, so a frame may hold several.
+            #[cfg(feature = "avro-registry")]
+            DeserializerConfig::Avro { .. } => FramingConfig::Bytes,
+            #[cfg(feature = "msgpack")]
+            DeserializerConfig::Msgpack => FramingConfig::Bytes,
+            #[cfg(feature = "protobuf-dynamic")]
+            DeserializerConfig::Protobuf { .. } => FramingConfig::Bytes,
             DeserializerConfig::Native => FramingConfig::LengthDelimited,
+            #[cfg(feature = "csv")]
+            DeserializerConfig::Csv { .. } => FramingConfig::NewlineDelimited {
+                newline_delimited: Default::default(),
+            },
             DeserializerConfig::Bytes
+            | DeserializerConfig::Cef
             | DeserializerConfig::Json
-            | DeserializerConfig::Gelf
+            | DeserializerConfig::Gelf { .. }
             | DeserializerConfig::NativeJson => FramingConfig::NewlineDelimited {
                 newline_delimited: Default::default(),
             },
@@ -300,28 +504,63 @@ impl DeserializerConfig {
     /// Return the type of event build by this deserializer.
     pub fn output_type(&self) -> DataType {
         match self {
+            #[cfg(feature = "avro-registry")]
+            DeserializerConfig::Avro { avro } => {
+                AvroDeserializerConfig::new(avro.schema_registry_url.clone()).output_type()
+            }
             DeserializerConfig::Bytes => BytesDeserializerConfig.output_type(),
+            DeserializerConfig::Cef => CefDeserializerConfig.output_type(),
+            #[cfg(feature = "csv")]
+            DeserializerConfig::Csv { .. } => CsvDeserializerConfig::new().output_type(),
             DeserializerConfig::Json => JsonDeserializerConfig.output_type(),
+            #[cfg(feature = "msgpack")]
+            DeserializerConfig::Msgpack => MsgpackDeserializerConfig.output_type(),
             #[cfg(feature = "syslog")]
             DeserializerConfig::Syslog => SyslogDeserializerConfig.output_type(),
             DeserializerConfig::Native => NativeDeserializerConfig.output_type(),
             DeserializerConfig::NativeJson => NativeJsonDeserializerConfig.output_type(),
-            DeserializerConfig::Gelf => GelfDeserializerConfig.output_type(),
+            DeserializerConfig::Gelf { .. } => GelfDeserializerConfig::new().output_type(),
+            #[cfg(feature = "protobuf-dynamic")]
+            DeserializerConfig::Protobuf { protobuf } => ProtobufDeserializerConfig {
+                protobuf: protobuf.clone(),
+            }
+            .output_type(),
         }
     }
 
     /// The schema produced by the deserializer.
     pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
         match self {
+            #[cfg(feature = "avro-registry")]
+            DeserializerConfig::Avro { avro } => {
+                AvroDeserializerConfig::new(avro.schema_registry_url.clone())
+                    .schema_definition(log_namespace)
+            }
             DeserializerConfig::Bytes => BytesDeserializerConfig.schema_definition(log_namespace),
+            DeserializerConfig::Cef => CefDeserializerConfig.schema_definition(log_namespace),
+            #[cfg(feature = "csv")]
+            DeserializerConfig::Csv { csv } => {
+                CsvDeserializerConfig { csv: csv.clone() }.schema_definition(log_namespace)
+            }
             DeserializerConfig::Json => JsonDeserializerConfig.schema_definition(log_namespace),
+            #[cfg(feature = "msgpack")]
+            DeserializerConfig::Msgpack => {
+                MsgpackDeserializerConfig.schema_definition(log_namespace)
+            }
             #[cfg(feature = "syslog")]
             DeserializerConfig::Syslog => SyslogDeserializerConfig.schema_definition(log_namespace),
             DeserializerConfig::Native => NativeDeserializerConfig.schema_definition(log_namespace),
             DeserializerConfig::NativeJson => {
                 NativeJsonDeserializerConfig.schema_definition(log_namespace)
             }
-            DeserializerConfig::Gelf => GelfDeserializerConfig.schema_definition(log_namespace),
+            DeserializerConfig::Gelf { .. } => {
+                GelfDeserializerConfig::new().schema_definition(log_namespace)
+            }
+            #[cfg(feature = "protobuf-dynamic")]
+            DeserializerConfig::Protobuf { protobuf } => ProtobufDeserializerConfig {
+                protobuf: protobuf.clone(),
+            }
+            .schema_definition(log_namespace),
         }
     }
 }
@@ -329,10 +568,21 @@ impl DeserializerConfig {
 /// Parse structured events from bytes.
 #[derive(Debug, Clone)]
 pub enum Deserializer {
+    #[cfg(feature = "avro-registry")]
+    /// Uses an `AvroDeserializer` for deserialization.
+    Avro(AvroDeserializer),
     /// Uses a `BytesDeserializer` for deserialization.
     Bytes(BytesDeserializer),
+    /// Uses a `CefDeserializer` for deserialization.
+    Cef(CefDeserializer),
+    #[cfg(feature = "csv")]
+    /// Uses a `CsvDeserializer` for deserialization.
+    Csv(CsvDeserializer),
     /// Uses a `JsonDeserializer` for deserialization.
     Json(JsonDeserializer),
+    #[cfg(feature = "msgpack")]
+    /// Uses a `MsgpackDeserializer` for deserialization.
+    Msgpack(MsgpackDeserializer),
     #[cfg(feature = "syslog")]
     /// Uses a `SyslogDeserializer` for deserialization.
     Syslog(SyslogDeserializer),
@@ -344,6 +594,31 @@ pub enum Deserializer {
     Boxed(BoxedDeserializer),
     /// Uses a `GelfDeserializer` for deserialization.
     Gelf(GelfDeserializer),
+    #[cfg(feature = "protobuf-dynamic")]
+    /// Uses a `ProtobufDeserializer` for deserialization.
+    Protobuf(ProtobufDeserializer),
+}
+
+impl Deserializer {
+    /// Whether the deserializer resolves data from external services to parse frames, which
+    /// should then be prefetched with [`Self::prefetch`].
+    pub const fn needs_prefetch(&self) -> bool {
+        #[cfg(feature = "avro-registry")]
+        if let Deserializer::Avro(_) = self {
+            return true;
+        }
+        false
+    }
+
+    /// Resolves the data from external services needed to parse the frame, such as the Avro
+    /// schema it references, so that parsing it doesn't wait for them.
+    #[cfg_attr(not(feature = "avro-registry"), allow(unused_variables))]
+    pub async fn prefetch(&self, frame: &[u8]) {
+        #[cfg(feature = "avro-registry")]
+        if let Deserializer::Avro(deserializer) = self {
+            deserializer.prefetch(frame).await;
+        }
+    }
 }
 
 impl format::Deserializer for Deserializer {
@@ -353,14 +628,23 @@ impl format::Deserializer for Deserializer {
         log_namespace: LogNamespace,
     ) -> vector_core::Result<SmallVec<[Event; 1]>> {
         match self {
+            #[cfg(feature = "avro-registry")]
+            Deserializer::Avro(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Bytes(deserializer) => deserializer.parse(bytes, log_namespace),
+            Deserializer::Cef(deserializer) => deserializer.parse(bytes, log_namespace),
+            #[cfg(feature = "csv")]
+            Deserializer::Csv(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Json(deserializer) => deserializer.parse(bytes, log_namespace),
+            #[cfg(feature = "msgpack")]
+            Deserializer::Msgpack(deserializer) => deserializer.parse(bytes, log_namespace),
             #[cfg(feature = "syslog")]
             Deserializer::Syslog(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Native(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::NativeJson(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Boxed(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Gelf(deserializer) => deserializer.parse(bytes, log_namespace),
+            #[cfg(feature = "protobuf-dynamic")]
+            Deserializer::Protobuf(deserializer) => deserializer.parse(bytes, log_namespace),
         }
     }
 }
@@ -0,0 +1,266 @@
+//! Splitting of encoded messages which exceed the size of a datagram.
+
+#[cfg(feature = "gelf-compression")]
+use std::io::Write;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use derivative::Derivative;
+#[cfg(feature = "gelf-compression")]
+use flate2::{
+    write::{GzEncoder, ZlibEncoder},
+    Compression,
+};
+use serde::{Deserialize, Serialize};
+use vector_config::configurable_component;
+
+use super::BuildError;
+use crate::gelf::{GELF_CHUNK_HEADER_LENGTH, GELF_MAGIC, GELF_MAX_CHUNKS};
+
+const fn default_max_chunk_size() -> usize {
+    8192
+}
+
+/// Config used to build a `ChunkedGelfEncoder`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct ChunkedGelfEncoderConfig {
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    /// Options for the chunked GELF encoder.
+    pub chunked_gelf: ChunkedGelfEncoderOptions,
+}
+
+impl ChunkedGelfEncoderConfig {
+    /// Creates a new `ChunkedGelfEncoderConfig`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Build the `ChunkedGelfEncoder` from this configuration.
+    pub fn build(&self) -> Result<ChunkedGelfEncoder, BuildError> {
+        let max_chunk_size = self.chunked_gelf.max_chunk_size;
+        if max_chunk_size <= GELF_CHUNK_HEADER_LENGTH {
+            return Err(format!(
+                "The maximum chunk size must exceed the {} bytes of the chunk header, got {}",
+                GELF_CHUNK_HEADER_LENGTH, max_chunk_size
+            )
+            .into());
+        }
+
+        Ok(ChunkedGelfEncoder::new(
+            max_chunk_size,
+            self.chunked_gelf.compression,
+        ))
+    }
+}
+
+/// Options for building a `ChunkedGelfEncoder`.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative, PartialEq)]
+#[derivative(Default)]
+pub struct ChunkedGelfEncoderOptions {
+    /// The maximum size of a datagram, including the chunk header.
+    ///
+    /// Messages exceeding this size are split into up to 128 chunks, as defined by the GELF
+    /// specification. Graylog recommends 8192 bytes, or 1420 bytes when sending through a network
+    /// with a smaller MTU.
+    #[serde(default = "default_max_chunk_size")]
+    #[derivative(Default(value = "default_max_chunk_size()"))]
+    pub max_chunk_size: usize,
+
+    /// The compression applied to messages before they are chunked.
+    #[serde(default)]
+    pub compression: GelfCompression,
+}
+
+/// The compression algorithm applied to GELF messages sent as datagrams.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum GelfCompression {
+    /// Messages are sent uncompressed.
+    #[derivative(Default)]
+    None,
+
+    #[cfg(feature = "gelf-compression")]
+    /// Messages are compressed with gzip.
+    Gzip,
+
+    #[cfg(feature = "gelf-compression")]
+    /// Messages are compressed with zlib.
+    Zlib,
+}
+
+/// Splits encoded GELF messages exceeding the maximum chunk size into chunks, as defined by the
+/// GELF specification for messages sent over UDP.
+#[derive(Debug, Clone)]
+pub struct ChunkedGelfEncoder {
+    max_chunk_size: usize,
+    compression: GelfCompression,
+}
+
+impl ChunkedGelfEncoder {
+    /// Creates a new `ChunkedGelfEncoder`.
+    pub const fn new(max_chunk_size: usize, compression: GelfCompression) -> Self {
+        Self {
+            max_chunk_size,
+            compression,
+        }
+    }
+
+    /// Compresses the message and splits it into the datagrams to send.
+    ///
+    /// Messages fitting in a single datagram are sent as-is, without a chunk header.
+    pub fn encode(&self, message: &[u8]) -> vector_core::Result<Vec<Bytes>> {
+        let message = self.compress(message)?;
+        if message.len() <= self.max_chunk_size {
+            return Ok(vec![message.into()]);
+        }
+
+        let payload_size = self.max_chunk_size - GELF_CHUNK_HEADER_LENGTH;
+        let count = (message.len() + payload_size - 1) / payload_size;
+        if count > usize::from(GELF_MAX_CHUNKS) {
+            return Err(format!(
+                "Message of {} bytes exceeds the maximum of {} chunks of {} bytes",
+                message.len(),
+                GELF_MAX_CHUNKS,
+                self.max_chunk_size
+            )
+            .into());
+        }
+
+        let message_id = rand::random::<u64>();
+        Ok(message
+            .chunks(payload_size)
+            .enumerate()
+            .map(|(number, payload)| {
+                let mut chunk = BytesMut::with_capacity(GELF_CHUNK_HEADER_LENGTH + payload.len());
+                chunk.extend_from_slice(&GELF_MAGIC);
+                chunk.put_u64(message_id);
+                chunk.put_u8(number as u8);
+                chunk.put_u8(count as u8);
+                chunk.extend_from_slice(payload);
+                chunk.freeze()
+            })
+            .collect())
+    }
+
+    fn compress(&self, message: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self.compression {
+            GelfCompression::None => Ok(message.to_vec()),
+            #[cfg(feature = "gelf-compression")]
+            GelfCompression::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(message)?;
+                encoder.finish()
+            }
+            #[cfg(feature = "gelf-compression")]
+            GelfCompression::Zlib => {
+                let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(message)?;
+                encoder.finish()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio_util::codec::Decoder;
+    use vector_core::config::LogNamespace;
+
+    use super::*;
+    use crate::decoding::{
+        format::{Deserializer, GelfDeserializer},
+        ChunkedGelfDecoder,
+    };
+
+    fn encoder(max_chunk_size: usize, compression: GelfCompression) -> ChunkedGelfEncoder {
+        ChunkedGelfEncoderConfig {
+            chunked_gelf: ChunkedGelfEncoderOptions {
+                max_chunk_size,
+                compression,
+            },
+        }
+        .build()
+        .unwrap()
+    }
+
+    fn reassemble(datagrams: Vec<Bytes>) -> Option<Bytes> {
+        let decoder = ChunkedGelfDecoder::default();
+        datagrams.into_iter().fold(None, |_, datagram| {
+            decoder
+                .clone()
+                .decode_eof(&mut BytesMut::from(&datagram[..]))
+                .unwrap()
+        })
+    }
+
+    #[test]
+    fn small_messages_are_not_chunked() {
+        let message = br#"{"version":"1.1","host":"example.org","short_message":"foo"}"#;
+        let datagrams = encoder(8192, GelfCompression::None)
+            .encode(message)
+            .unwrap();
+
+        assert_eq!(datagrams, vec![Bytes::from_static(message)]);
+    }
+
+    #[test]
+    fn large_messages_are_chunked() {
+        let message = format!(
+            r#"{{"version":"1.1","host":"example.org","short_message":"{}"}}"#,
+            "a".repeat(100)
+        );
+        let datagrams = encoder(32, GelfCompression::None)
+            .encode(message.as_bytes())
+            .unwrap();
+
+        assert_eq!(datagrams.len(), (message.len() + 19) / 20);
+        assert!(datagrams.iter().all(|datagram| datagram.len() <= 32));
+        assert_eq!(reassemble(datagrams).unwrap(), message.as_bytes());
+    }
+
+    #[cfg(feature = "gelf-compression")]
+    #[test]
+    fn compressed_chunks_round_trip() {
+        let message = format!(
+            r#"{{"version":"1.1","host":"example.org","short_message":"{}"}}"#,
+            "a".repeat(10_000)
+        );
+
+        for compression in [GelfCompression::Gzip, GelfCompression::Zlib] {
+            let datagrams = encoder(20, compression).encode(message.as_bytes()).unwrap();
+            assert!(datagrams.len() > 1);
+
+            let events = GelfDeserializer::new()
+                .parse(reassemble(datagrams).unwrap(), LogNamespace::Vector)
+                .unwrap();
+            assert_eq!(
+                events[0].as_log()["short_message"],
+                "a".repeat(10_000).into()
+            );
+        }
+    }
+
+    #[test]
+    fn too_many_chunks() {
+        let message = "a".repeat(129 * 20);
+        assert!(encoder(32, GelfCompression::None)
+            .encode(message.as_bytes())
+            .is_err());
+    }
+
+    #[test]
+    fn chunk_size_must_exceed_header() {
+        let config = ChunkedGelfEncoderConfig {
+            chunked_gelf: ChunkedGelfEncoderOptions {
+                max_chunk_size: GELF_CHUNK_HEADER_LENGTH,
+                compression: GelfCompression::None,
+            },
+        };
+        assert!(config.build().is_err());
+    }
+}
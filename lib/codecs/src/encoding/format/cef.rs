@@ -0,0 +1,185 @@
+use crate::cef_fields::*;
+use bytes::{BufMut, BytesMut};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use value::Value;
+use vector_core::{config::DataType, event::Event, schema};
+
+/// Config used to build a `CefSerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct CefSerializerConfig;
+
+impl CefSerializerConfig {
+    /// Creates a new `CefSerializerConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `CefSerializer` from this configuration.
+    pub const fn build(&self) -> CefSerializer {
+        CefSerializer
+    }
+
+    /// The data type of events that are accepted by `CefSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        // The header fields are checked when encoding, since they are usually added by a `remap`
+        // transform that we don't want to require schema support from.
+        schema::Requirement::empty()
+    }
+}
+
+/// Serializer that converts an `Event` to bytes using the CEF format.
+///
+/// The header is built from the `deviceVendor`, `deviceProduct`, `deviceVersion`,
+/// `deviceEventClassId`, `name` and `severity` fields, and `cefVersion` if present. Every other
+/// top-level field is encoded as an extension.
+#[derive(Debug, Clone)]
+pub struct CefSerializer;
+
+impl CefSerializer {
+    /// Creates a new `CefSerializer`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Encoder<Event> for CefSerializer {
+    type Error = vector_core::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = event.into_log();
+
+        let mut header = Vec::with_capacity(HEADER_FIELDS.len());
+        for field in HEADER_FIELDS {
+            let value = match log.get(field) {
+                Some(value) => to_string(field, value)?,
+                None if field == VERSION => CEF_VERSION.to_owned(),
+                None => {
+                    return Err(format!(
+                        r#"LogEvent does not contain required CEF header field: "{}""#,
+                        field
+                    )
+                    .into())
+                }
+            };
+            header.push(escape_header(&value));
+        }
+
+        let mut extension = Vec::new();
+        if let Some(fields) = log.as_map() {
+            for (key, value) in fields {
+                if HEADER_FIELDS.contains(&key.as_str()) || matches!(value, Value::Null) {
+                    continue;
+                }
+                let value = to_string(key, value)?;
+                extension.push(format!("{}={}", key, escape_extension_value(&value)));
+            }
+        }
+
+        buffer.put_slice(CEF_PREFIX.as_bytes());
+        buffer.put_slice(header.join("|").as_bytes());
+        buffer.put_u8(b'|');
+        buffer.put_slice(extension.join(" ").as_bytes());
+
+        Ok(())
+    }
+}
+
+/// Converts a scalar value to the string written in the message. Timestamps are written as
+/// milliseconds since the UNIX epoch, as CEF expects for its date extensions.
+fn to_string(field: &str, value: &Value) -> vector_core::Result<String> {
+    match value {
+        Value::Bytes(bytes) => Ok(String::from_utf8_lossy(bytes).into_owned()),
+        Value::Integer(value) => Ok(value.to_string()),
+        Value::Float(value) => Ok(value.to_string()),
+        Value::Boolean(value) => Ok(value.to_string()),
+        Value::Timestamp(value) => Ok(value.timestamp_millis().to_string()),
+        value => Err(format!(
+            r#"LogEvent contains a value with an invalid type. field = "{}" type = "{}" expected type = "string, number, boolean or timestamp""#,
+            field,
+            value.kind_str()
+        )
+        .into()),
+    }
+}
+
+fn escape_header(value: &str) -> String {
+    value.replace('\\', r"\\").replace('|', r"\|")
+}
+
+fn escape_extension_value(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace('=', r"\=")
+        .replace('\n', r"\n")
+        .replace('\r', r"\r")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vector_common::btreemap;
+    use vector_core::event::LogEvent;
+
+    fn serialize(event: LogEvent) -> Result<String, vector_core::Error> {
+        let mut buffer = BytesMut::new();
+        CefSerializer::new().encode(event.into(), &mut buffer)?;
+        Ok(String::from_utf8(buffer.to_vec()).unwrap())
+    }
+
+    fn header() -> LogEvent {
+        LogEvent::from(btreemap! {
+            DEVICE_VENDOR => "Security",
+            DEVICE_PRODUCT => "threat|manager",
+            DEVICE_VERSION => "1.0",
+            DEVICE_EVENT_CLASS_ID => "100",
+            NAME => "worm successfully stopped",
+            SEVERITY => 10,
+        })
+    }
+
+    #[test]
+    fn serialize_cef() {
+        let mut log = header();
+        log.insert("dst", "2.1.2.2");
+        log.insert("msg", "a=b\nc");
+        log.insert("path", r"C:\Windows");
+
+        assert_eq!(
+            serialize(log).unwrap(),
+            r"CEF:0|Security|threat\|manager|1.0|100|worm successfully stopped|10|dst=2.1.2.2 msg=a\=b\nc path=C:\\Windows"
+        );
+    }
+
+    #[test]
+    fn serialize_cef_without_extensions() {
+        let mut log = header();
+        log.insert(VERSION, "1");
+
+        assert_eq!(
+            serialize(log).unwrap(),
+            r"CEF:1|Security|threat\|manager|1.0|100|worm successfully stopped|10|"
+        );
+    }
+
+    #[test]
+    fn serialize_cef_missing_header_field() {
+        let mut log = header();
+        log.remove(SEVERITY);
+
+        assert!(serialize(log).is_err());
+    }
+
+    #[test]
+    fn serialize_cef_nested_extension() {
+        let mut log = header();
+        log.insert("nested", btreemap! { "foo" => "bar" });
+
+        assert!(serialize(log).is_err());
+    }
+}
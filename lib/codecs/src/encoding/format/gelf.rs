@@ -1,9 +1,11 @@
 use crate::{gelf_fields::*, VALID_FIELD_REGEX};
 use bytes::{BufMut, BytesMut};
 use lookup::path;
+use ordered_float::NotNan;
 use serde::{Deserialize, Serialize};
 use snafu::Snafu;
 use tokio_util::codec::Encoder;
+use value::Value;
 use vector_core::{
     config::{log_schema, DataType},
     event::Event,
@@ -165,6 +167,14 @@ fn coerce_field_names_and_values(
                     if !(value.is_timestamp() || value.is_integer()) {
                         err_invalid_type(field, "timestamp or integer", value.kind_str())?;
                     }
+
+                    // GELF expects seconds since the UNIX epoch, with the fractional part
+                    // carrying sub-second precision, rather than an RFC 3339 string.
+                    if let Value::Timestamp(ts) = value {
+                        let secs = ts.timestamp() as f64
+                            + f64::from(ts.timestamp_subsec_nanos()) / 1_000_000_000.0;
+                        *value = Value::Float(NotNan::new(secs).expect("timestamp is not NaN"));
+                    }
                 }
                 LEVEL => {
                     if !value.is_integer() {
@@ -225,7 +235,7 @@ mod tests {
     use crate::encoding::SerializerConfig;
 
     use super::*;
-    use value::Value;
+    use chrono::{TimeZone, Utc};
     use vector_common::btreemap;
     use vector_core::event::{Event, EventMetadata};
 
@@ -283,7 +293,7 @@ mod tests {
             FULL_MESSAGE => "Even more message",
             FACILITY => "",
             FILE => "/tmp/foobar",
-            LINE => Value::Float(ordered_float::NotNan::new(1.5).unwrap()),
+            LINE => Value::Float(NotNan::new(1.5).unwrap()),
             LEVEL => 5,
         };
 
@@ -322,6 +332,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn gelf_serializing_timestamp() {
+        let event_fields = btreemap! {
+            HOST => "example.org",
+            SHORT_MESSAGE => "Some message",
+            TIMESTAMP => Utc.ymd(2022, 8, 1).and_hms_milli(12, 30, 0, 250),
+        };
+
+        let jsn = do_serialize(true, event_fields).unwrap();
+        assert_eq!(jsn.get(VERSION).unwrap(), GELF_VERSION);
+        assert_eq!(jsn.get(TIMESTAMP).unwrap().as_f64(), Some(1659357000.25));
+    }
+
     #[test]
     fn gelf_serializing_invalid_error() {
         // no host
@@ -4,21 +4,33 @@
 #![deny(missing_docs)]
 
 mod avro;
+mod cef;
 mod gelf;
 mod json;
 mod logfmt;
+#[cfg(feature = "msgpack")]
+mod msgpack;
 mod native;
 mod native_json;
+#[cfg(feature = "parquet")]
+mod parquet;
 mod raw_message;
 mod text;
 
 use std::fmt::Debug;
 
+#[cfg(feature = "parquet")]
+pub use self::parquet::{
+    ParquetCompression, ParquetSerializer, ParquetSerializerConfig, ParquetSerializerOptions,
+};
 pub use avro::{AvroSerializer, AvroSerializerConfig, AvroSerializerOptions};
+pub use cef::{CefSerializer, CefSerializerConfig};
 use dyn_clone::DynClone;
 pub use gelf::{GelfSerializer, GelfSerializerConfig};
 pub use json::{JsonSerializer, JsonSerializerConfig};
 pub use logfmt::{LogfmtSerializer, LogfmtSerializerConfig};
+#[cfg(feature = "msgpack")]
+pub use msgpack::{MsgpackSerializer, MsgpackSerializerConfig};
 pub use native::{NativeSerializer, NativeSerializerConfig};
 pub use native_json::{NativeJsonSerializer, NativeJsonSerializerConfig};
 pub use raw_message::{RawMessageSerializer, RawMessageSerializerConfig};
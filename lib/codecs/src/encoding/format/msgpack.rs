@@ -0,0 +1,146 @@
+use bytes::{BufMut, BytesMut};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use vector_core::{
+    config::DataType,
+    event::{Event, Value},
+    schema,
+};
+
+use crate::msgpack::{encode_timestamp, TIMESTAMP_EXT_TYPE};
+
+/// Config used to build a `MsgpackSerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct MsgpackSerializerConfig;
+
+impl MsgpackSerializerConfig {
+    /// Creates a new `MsgpackSerializerConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `MsgpackSerializer` from this configuration.
+    pub const fn build(&self) -> MsgpackSerializer {
+        MsgpackSerializer
+    }
+
+    /// The data type of events that are accepted by `MsgpackSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Serializer that converts an `Event` to bytes using the MessagePack format.
+///
+/// Each event is encoded as a map. Timestamps are encoded using the timestamp extension type
+/// defined by the MessagePack specification.
+#[derive(Debug, Clone)]
+pub struct MsgpackSerializer;
+
+impl MsgpackSerializer {
+    /// Creates a new `MsgpackSerializer`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Encoder<Event> for MsgpackSerializer {
+    type Error = vector_core::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = event.into_log();
+        let value = log.as_map().map_or_else(
+            || rmpv::Value::Map(Vec::new()),
+            |fields| {
+                rmpv::Value::Map(
+                    fields
+                        .iter()
+                        .map(|(key, value)| (key.as_str().into(), to_msgpack(value)))
+                        .collect(),
+                )
+            },
+        );
+
+        rmpv::encode::write_value(&mut buffer.writer(), &value)?;
+        Ok(())
+    }
+}
+
+fn to_msgpack(value: &Value) -> rmpv::Value {
+    match value {
+        Value::Bytes(bytes) => match std::str::from_utf8(bytes) {
+            Ok(string) => string.into(),
+            Err(_) => rmpv::Value::Binary(bytes.to_vec()),
+        },
+        Value::Regex(regex) => regex.as_str().into(),
+        Value::Integer(value) => (*value).into(),
+        Value::Float(value) => value.into_inner().into(),
+        Value::Boolean(value) => (*value).into(),
+        Value::Timestamp(timestamp) => {
+            rmpv::Value::Ext(TIMESTAMP_EXT_TYPE, encode_timestamp(timestamp))
+        }
+        Value::Object(fields) => rmpv::Value::Map(
+            fields
+                .iter()
+                .map(|(key, value)| (key.as_str().into(), to_msgpack(value)))
+                .collect(),
+        ),
+        Value::Array(values) => rmpv::Value::Array(values.iter().map(to_msgpack).collect()),
+        Value::Null => rmpv::Value::Nil,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use chrono::{TimeZone, Utc};
+    use vector_common::btreemap;
+    use vector_core::{config::LogNamespace, event::LogEvent};
+
+    use super::*;
+    use crate::decoding::format::{Deserializer, MsgpackDeserializer};
+
+    #[test]
+    fn serialize_msgpack() {
+        let event = Event::from(LogEvent::from(btreemap! {
+            "message" => "foo",
+            "count" => 3,
+        }));
+        let mut buffer = BytesMut::new();
+        MsgpackSerializer.encode(event, &mut buffer).unwrap();
+
+        // fixmap(2) "count" 3 "message" "foo"
+        assert_eq!(
+            buffer.freeze(),
+            Bytes::from_static(b"\x82\xa5count\x03\xa7message\xa3foo")
+        );
+    }
+
+    #[test]
+    fn msgpack_round_trip() {
+        let log = LogEvent::from(btreemap! {
+            "message" => "foo",
+            "binary" => Bytes::from_static(b"\xff\x00"),
+            "float" => 1.5,
+            "nested" => btreemap! {
+                "values" => vec![Value::from(true), Value::Null],
+            },
+            "timestamp" => Utc.timestamp(1_600_000_000, 123_456_789),
+        });
+        let mut buffer = BytesMut::new();
+        MsgpackSerializer
+            .encode(Event::from(log.clone()), &mut buffer)
+            .unwrap();
+
+        let events = MsgpackDeserializer
+            .parse(buffer.freeze(), LogNamespace::Vector)
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].as_log(), &log);
+    }
+}
@@ -0,0 +1,777 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use bytes::Bytes;
+use chrono::{DateTime, Datelike, Utc};
+use derivative::Derivative;
+use once_cell::sync::OnceCell;
+use parquet::{
+    basic::{Compression, ConvertedType, Repetition, Type as PhysicalType},
+    column::writer::ColumnWriter,
+    data_type::ByteArray,
+    errors::{ParquetError, Result as ParquetResult},
+    file::{properties::WriterProperties, writer::SerializedFileWriter},
+    schema::{
+        parser::parse_message_type,
+        types::{ColumnDescriptor, SchemaDescriptor, Type, TypePtr},
+    },
+};
+use serde::{Deserialize, Serialize};
+use vector_config::configurable_component;
+use vector_core::{
+    config::DataType,
+    event::{Event, Value},
+    schema,
+};
+
+use crate::encoding::BuildError;
+
+const fn default_row_group_size() -> usize {
+    10_000
+}
+
+/// The number of days between the Common Era and the Unix epoch.
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+/// Config used to build a `ParquetSerializer`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ParquetSerializerConfig {
+    /// Options for the Parquet serializer.
+    pub parquet: ParquetSerializerOptions,
+}
+
+impl ParquetSerializerConfig {
+    /// Creates a new `ParquetSerializerConfig`.
+    pub const fn new(parquet: ParquetSerializerOptions) -> Self {
+        Self { parquet }
+    }
+
+    /// Build the `ParquetSerializer` from this configuration.
+    pub fn build(&self) -> Result<ParquetSerializer, BuildError> {
+        if self.parquet.row_group_size == 0 {
+            return Err("The row group size of the Parquet serializer must be positive.".into());
+        }
+        let schema = match &self.parquet.schema {
+            Some(schema) => {
+                let schema = parse_message_type(schema)
+                    .map_err(|error| format!("Failed building Parquet serializer: {}", error))?;
+                Some(schema)
+            }
+            None => None,
+        };
+        ParquetSerializer::new(
+            schema,
+            self.parquet.compression,
+            self.parquet.row_group_size,
+        )
+    }
+
+    /// The data type of events that are accepted by `ParquetSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Apache Parquet serializer options.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+pub struct ParquetSerializerOptions {
+    /// The schema of the files, as a Parquet message type.
+    ///
+    /// When it isn't set, the schema is inferred from the fields of the first batch of events,
+    /// all of them being optional, and the fields of the later events that aren't part of it are
+    /// dropped.
+    #[serde(default)]
+    pub schema: Option<String>,
+
+    /// The compression codec of the pages of the files.
+    #[serde(default)]
+    pub compression: ParquetCompression,
+
+    /// The maximum number of rows of the row groups of the files.
+    #[serde(default = "default_row_group_size")]
+    #[derivative(Default(value = "default_row_group_size()"))]
+    pub row_group_size: usize,
+}
+
+/// The compression codec of the pages of Parquet files.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ParquetCompression {
+    /// The pages are not compressed.
+    None,
+
+    /// The pages are compressed with Snappy.
+    #[derivative(Default)]
+    Snappy,
+
+    /// The pages are compressed with gzip.
+    Gzip,
+}
+
+impl From<ParquetCompression> for Compression {
+    fn from(compression: ParquetCompression) -> Self {
+        match compression {
+            ParquetCompression::None => Self::UNCOMPRESSED,
+            ParquetCompression::Snappy => Self::SNAPPY,
+            ParquetCompression::Gzip => Self::GZIP,
+        }
+    }
+}
+
+/// Serializer that converts batches of events to Apache Parquet files.
+///
+/// Unlike the other serializers, it doesn't encode the events one by one, as a Parquet file holds
+/// the values of all of its rows column by column, so it can only be used by the sinks writing
+/// whole batches as objects.
+///
+/// The fields of the logs are written to the columns of the same path. The values that can't be
+/// converted to the type of their column are written as nulls, or as the zero value of the type
+/// for the required columns. The objects written to `MAP` groups have their entries written as
+/// the keys and values of the maps.
+#[derive(Debug, Clone)]
+pub struct ParquetSerializer {
+    schema: Arc<OnceCell<Arc<Schema>>>,
+    properties: Arc<WriterProperties>,
+    row_group_size: usize,
+}
+
+#[derive(Debug)]
+struct Schema {
+    root: TypePtr,
+    descriptor: SchemaDescriptor,
+}
+
+impl ParquetSerializer {
+    /// Creates a new `ParquetSerializer`, which infers the schema from the first batch when none
+    /// is given.
+    pub fn new(
+        schema: Option<Type>,
+        compression: ParquetCompression,
+        row_group_size: usize,
+    ) -> Result<Self, BuildError> {
+        let cell = OnceCell::new();
+        if let Some(schema) = schema {
+            validate_group(&schema)
+                .map_err(|error| format!("Failed building Parquet serializer: {}", error))?;
+            let root = Arc::new(schema);
+            let descriptor = SchemaDescriptor::new(Arc::clone(&root));
+            cell.set(Arc::new(Schema { root, descriptor }))
+                .expect("the schema is only set once");
+        }
+        let properties = WriterProperties::builder()
+            .set_compression(compression.into())
+            .set_max_row_group_size(row_group_size)
+            .build();
+        Ok(Self {
+            schema: Arc::new(cell),
+            properties: Arc::new(properties),
+            row_group_size,
+        })
+    }
+
+    /// The schema of the files, once it is given or inferred.
+    pub fn schema(&self) -> Option<&SchemaDescriptor> {
+        self.schema.get().map(|schema| &schema.descriptor)
+    }
+
+    /// Serializes the logs of the batch as a Parquet file.
+    pub fn serialize(&self, events: Vec<Event>) -> Result<Bytes, vector_core::Error> {
+        let values = events
+            .into_iter()
+            .filter_map(|event| event.try_into_log())
+            .map(|log| log.into_parts().0)
+            .collect::<Vec<_>>();
+        let schema = self
+            .schema
+            .get_or_try_init(|| infer_schema(&values).map(Arc::new))?;
+        let file = write_file(
+            schema,
+            Arc::clone(&self.properties),
+            &values,
+            self.row_group_size,
+        )?;
+        Ok(file.into())
+    }
+
+    /// Returns whether the value can be written to the column, without being converted to null
+    /// or to the zero value of its type.
+    pub fn is_convertible(column: &ColumnDescriptor, value: &Value) -> bool {
+        convert(column, value).is_some()
+    }
+}
+
+fn write_file(
+    schema: &Schema,
+    properties: Arc<WriterProperties>,
+    values: &[Value],
+    row_group_size: usize,
+) -> ParquetResult<Vec<u8>> {
+    let mut buffer = Vec::new();
+    let mut writer = SerializedFileWriter::new(&mut buffer, Arc::clone(&schema.root), properties)?;
+    for rows in values.chunks(row_group_size) {
+        let mut row_group = writer.next_row_group()?;
+        let mut columns = schema.descriptor.columns().iter();
+        while let Some(mut column_writer) = row_group.next_column()? {
+            let column = columns.next().expect("the columns match the schema");
+            let mut data = ColumnData::default();
+            for row in rows {
+                shred_row(&schema.root, column, row, &mut data);
+            }
+            data.write(column, column_writer.untyped())?;
+            column_writer.close()?;
+        }
+        row_group.close()?;
+    }
+    writer.close()?;
+    Ok(buffer)
+}
+
+/// A value of a column.
+#[derive(Clone, Debug, PartialEq)]
+enum Cell {
+    Boolean(bool),
+    Int32(i32),
+    Int64(i64),
+    Float(f32),
+    Double(f64),
+    Bytes(ByteArray),
+}
+
+/// The non-null values of a column of a row group, with their definition and repetition levels.
+#[derive(Debug, Default)]
+struct ColumnData {
+    cells: Vec<Cell>,
+    def_levels: Vec<i16>,
+    rep_levels: Vec<i16>,
+}
+
+impl ColumnData {
+    fn push(&mut self, cell: Option<Cell>, def_level: i16, rep_level: i16) {
+        self.cells.extend(cell);
+        self.def_levels.push(def_level);
+        self.rep_levels.push(rep_level);
+    }
+
+    fn write(&self, column: &ColumnDescriptor, writer: &mut ColumnWriter) -> ParquetResult<()> {
+        let def_levels = (column.max_def_level() > 0).then(|| self.def_levels.as_slice());
+        let rep_levels = (column.max_rep_level() > 0).then(|| self.rep_levels.as_slice());
+        let written = match writer {
+            ColumnWriter::BoolColumnWriter(writer) => writer.write_batch(
+                &self.values(|cell| match cell {
+                    Cell::Boolean(value) => Some(*value),
+                    _ => None,
+                }),
+                def_levels,
+                rep_levels,
+            ),
+            ColumnWriter::Int32ColumnWriter(writer) => writer.write_batch(
+                &self.values(|cell| match cell {
+                    Cell::Int32(value) => Some(*value),
+                    _ => None,
+                }),
+                def_levels,
+                rep_levels,
+            ),
+            ColumnWriter::Int64ColumnWriter(writer) => writer.write_batch(
+                &self.values(|cell| match cell {
+                    Cell::Int64(value) => Some(*value),
+                    _ => None,
+                }),
+                def_levels,
+                rep_levels,
+            ),
+            ColumnWriter::FloatColumnWriter(writer) => writer.write_batch(
+                &self.values(|cell| match cell {
+                    Cell::Float(value) => Some(*value),
+                    _ => None,
+                }),
+                def_levels,
+                rep_levels,
+            ),
+            ColumnWriter::DoubleColumnWriter(writer) => writer.write_batch(
+                &self.values(|cell| match cell {
+                    Cell::Double(value) => Some(*value),
+                    _ => None,
+                }),
+                def_levels,
+                rep_levels,
+            ),
+            ColumnWriter::ByteArrayColumnWriter(writer) => writer.write_batch(
+                &self.values(|cell| match cell {
+                    Cell::Bytes(value) => Some(value.clone()),
+                    _ => None,
+                }),
+                def_levels,
+                rep_levels,
+            ),
+            _ => Err(ParquetError::General(format!(
+                "Unsupported type of column {}",
+                column.path()
+            ))),
+        };
+        written.map(|_| ())
+    }
+
+    fn values<T>(&self, value: impl Fn(&Cell) -> Option<T>) -> Vec<T> {
+        self.cells.iter().filter_map(value).collect()
+    }
+}
+
+/// Appends the value of the column in the row, and its levels, to the data of the column.
+fn shred_row(root: &Type, column: &ColumnDescriptor, row: &Value, data: &mut ColumnData) {
+    let path = column.path().parts();
+    let node = field(root, &path[0]);
+    let value = get(Some(row), &path[0]);
+    shred(node, &path[1..], value, 0, 0, column, data);
+}
+
+/// Appends the values of the column under the node, whose value is given, to the data of the
+/// column, the levels of the node's ancestors being given.
+fn shred(
+    node: &Type,
+    path: &[String],
+    value: Option<&Value>,
+    def_level: i16,
+    rep_level: i16,
+    column: &ColumnDescriptor,
+    data: &mut ColumnData,
+) {
+    let value = value.filter(|value| !matches!(value, Value::Null));
+    let optional = node.get_basic_info().repetition() == Repetition::OPTIONAL;
+
+    if node.is_primitive() {
+        match value.and_then(|value| convert(column, value)) {
+            Some(cell) => data.push(Some(cell), def_level + i16::from(optional), rep_level),
+            None if optional => data.push(None, def_level, rep_level),
+            None => data.push(Some(zero(column)), def_level, rep_level),
+        }
+        return;
+    }
+
+    if optional && value.is_none() {
+        data.push(None, def_level, rep_level);
+        return;
+    }
+    let def_level = def_level + i16::from(optional);
+
+    if is_map(node) {
+        let entries = match value {
+            Some(Value::Object(entries)) if !entries.is_empty() => entries,
+            _ => {
+                data.push(None, def_level, rep_level);
+                return;
+            }
+        };
+        // The path goes through the repeated group of the entries, then to their key or value.
+        let key_value = &node.get_fields()[0];
+        let is_key = key_value.get_fields()[0].name() == path[1];
+        let leaf = field(key_value, &path[1]);
+        for (index, (key, value)) in entries.iter().enumerate() {
+            let rep_level = if index == 0 {
+                rep_level
+            } else {
+                column.max_rep_level()
+            };
+            if is_key {
+                let key = Cell::Bytes(ByteArray::from(key.as_str()));
+                data.push(Some(key), def_level + 1, rep_level);
+            } else {
+                shred(
+                    leaf,
+                    &[],
+                    Some(value),
+                    def_level + 1,
+                    rep_level,
+                    column,
+                    data,
+                );
+            }
+        }
+        return;
+    }
+
+    let child = field(node, &path[0]);
+    let value = get(value, &path[0]);
+    shred(child, &path[1..], value, def_level, rep_level, column, data);
+}
+
+fn field<'a>(node: &'a Type, name: &str) -> &'a Type {
+    node.get_fields()
+        .iter()
+        .find(|field| field.name() == name)
+        .expect("the path of the column is part of the schema")
+}
+
+fn get<'a>(value: Option<&'a Value>, name: &str) -> Option<&'a Value> {
+    match value {
+        Some(Value::Object(fields)) => fields.get(name),
+        _ => None,
+    }
+}
+
+fn is_map(node: &Type) -> bool {
+    !node.is_primitive() && node.get_basic_info().converted_type() == ConvertedType::MAP
+}
+
+/// Converts the value to the type of the column, if it can be without losing information.
+fn convert(column: &ColumnDescriptor, value: &Value) -> Option<Cell> {
+    let converted_type = column.converted_type();
+    match (column.physical_type(), value) {
+        (PhysicalType::BOOLEAN, Value::Boolean(value)) => Some(Cell::Boolean(*value)),
+        (PhysicalType::INT32, Value::Integer(value)) => i32::try_from(*value).ok().map(Cell::Int32),
+        (PhysicalType::INT32, Value::Timestamp(timestamp))
+            if converted_type == ConvertedType::DATE =>
+        {
+            Some(Cell::Int32(
+                timestamp.num_days_from_ce() - UNIX_EPOCH_DAYS_FROM_CE,
+            ))
+        }
+        (PhysicalType::INT64, Value::Integer(value)) => Some(Cell::Int64(*value)),
+        (PhysicalType::INT64, Value::Timestamp(timestamp)) => Some(Cell::Int64(
+            if converted_type == ConvertedType::TIMESTAMP_MICROS {
+                timestamp_micros(timestamp)
+            } else {
+                timestamp.timestamp_millis()
+            },
+        )),
+        (PhysicalType::FLOAT, Value::Float(value)) => Some(Cell::Float(value.into_inner() as f32)),
+        (PhysicalType::FLOAT, Value::Integer(value)) => Some(Cell::Float(*value as f32)),
+        (PhysicalType::DOUBLE, Value::Float(value)) => Some(Cell::Double(value.into_inner())),
+        (PhysicalType::DOUBLE, Value::Integer(value)) => Some(Cell::Double(*value as f64)),
+        (PhysicalType::BYTE_ARRAY, Value::Bytes(bytes)) => {
+            Some(Cell::Bytes(ByteArray::from(bytes.to_vec())))
+        }
+        (PhysicalType::BYTE_ARRAY, Value::Timestamp(timestamp)) => Some(Cell::Bytes(
+            ByteArray::from(timestamp.to_rfc3339().as_str()),
+        )),
+        (PhysicalType::BYTE_ARRAY, value) if !matches!(value, Value::Null) => {
+            serde_json::to_string(value)
+                .ok()
+                .map(|json| Cell::Bytes(ByteArray::from(json.as_str())))
+        }
+        _ => None,
+    }
+}
+
+fn timestamp_micros(timestamp: &DateTime<Utc>) -> i64 {
+    timestamp.timestamp() * 1_000_000 + i64::from(timestamp.timestamp_subsec_micros())
+}
+
+/// The value written to the required columns whose value is missing.
+fn zero(column: &ColumnDescriptor) -> Cell {
+    match column.physical_type() {
+        PhysicalType::BOOLEAN => Cell::Boolean(false),
+        PhysicalType::INT32 => Cell::Int32(0),
+        PhysicalType::INT64 => Cell::Int64(0),
+        PhysicalType::FLOAT => Cell::Float(0.0),
+        PhysicalType::DOUBLE => Cell::Double(0.0),
+        _ => Cell::Bytes(ByteArray::from("")),
+    }
+}
+
+/// Checks that the fields of the group only use the types the serializer can write.
+fn validate_group(group: &Type) -> Result<(), String> {
+    for field in group.get_fields() {
+        if field.get_basic_info().repetition() == Repetition::REPEATED {
+            return Err(format!(
+                "Field {:?} is repeated, which is only supported for the entries of MAP groups",
+                field.name()
+            ));
+        }
+        if field.is_primitive() {
+            validate_primitive(field)?;
+        } else if is_map(field) {
+            validate_map(field)?;
+        } else {
+            validate_group(field)?;
+        }
+    }
+    Ok(())
+}
+
+fn validate_primitive(field: &Type) -> Result<(), String> {
+    match field.get_physical_type() {
+        PhysicalType::BOOLEAN
+        | PhysicalType::INT32
+        | PhysicalType::INT64
+        | PhysicalType::FLOAT
+        | PhysicalType::DOUBLE
+        | PhysicalType::BYTE_ARRAY => Ok(()),
+        physical_type => Err(format!(
+            "Field {:?} has the unsupported type {}",
+            field.name(),
+            physical_type
+        )),
+    }
+}
+
+fn validate_map(field: &Type) -> Result<(), String> {
+    let entries = match field.get_fields() {
+        [entries] if entries.get_basic_info().repetition() == Repetition::REPEATED => entries,
+        _ => {
+            return Err(format!(
+                "MAP group {:?} must hold a single repeated group of entries",
+                field.name()
+            ))
+        }
+    };
+    match entries.get_fields() {
+        [key, value]
+            if key.is_primitive()
+                && key.get_physical_type() == PhysicalType::BYTE_ARRAY
+                && key.get_basic_info().repetition() == Repetition::REQUIRED
+                && value.is_primitive() =>
+        {
+            validate_primitive(value)
+        }
+        _ => Err(format!(
+            "The entries of MAP group {:?} must have a required binary key and a primitive value",
+            field.name()
+        )),
+    }
+}
+
+/// The type of a field inferred from the values of a batch.
+#[derive(Debug)]
+enum InferredType {
+    Primitive(PhysicalType, ConvertedType),
+    Group(BTreeMap<String, InferredType>),
+}
+
+/// Infers the schema from the fields of the logs, the first type seen for each field winning.
+fn infer_schema(values: &[Value]) -> ParquetResult<Schema> {
+    let mut fields = BTreeMap::new();
+    for value in values {
+        if let Value::Object(object) = value {
+            infer_fields(&mut fields, object);
+        }
+    }
+
+    let mut fields = build_fields(fields)?;
+    if fields.is_empty() {
+        return Err(ParquetError::General(
+            "No fields to infer the Parquet schema from".into(),
+        ));
+    }
+    let root = Arc::new(
+        Type::group_type_builder("vector")
+            .with_fields(&mut fields)
+            .build()?,
+    );
+    let descriptor = SchemaDescriptor::new(Arc::clone(&root));
+    Ok(Schema { root, descriptor })
+}
+
+fn infer_fields(fields: &mut BTreeMap<String, InferredType>, object: &BTreeMap<String, Value>) {
+    for (name, value) in object {
+        let (physical_type, converted_type) = match value {
+            Value::Object(object) => {
+                if let InferredType::Group(fields) = fields
+                    .entry(name.clone())
+                    .or_insert_with(|| InferredType::Group(BTreeMap::new()))
+                {
+                    infer_fields(fields, object);
+                }
+                continue;
+            }
+            Value::Null => continue,
+            Value::Boolean(_) => (PhysicalType::BOOLEAN, ConvertedType::NONE),
+            Value::Integer(_) => (PhysicalType::INT64, ConvertedType::NONE),
+            Value::Float(_) => (PhysicalType::DOUBLE, ConvertedType::NONE),
+            Value::Timestamp(_) => (PhysicalType::INT64, ConvertedType::TIMESTAMP_MICROS),
+            // Arrays are written as JSON, as are regexes as strings.
+            _ => (PhysicalType::BYTE_ARRAY, ConvertedType::UTF8),
+        };
+        fields
+            .entry(name.clone())
+            .or_insert(InferredType::Primitive(physical_type, converted_type));
+    }
+}
+
+fn build_fields(fields: BTreeMap<String, InferredType>) -> ParquetResult<Vec<TypePtr>> {
+    let mut types = Vec::new();
+    for (name, field) in fields {
+        let field = match field {
+            InferredType::Primitive(physical_type, converted_type) => {
+                Type::primitive_type_builder(&name, physical_type)
+                    .with_repetition(Repetition::OPTIONAL)
+                    .with_converted_type(converted_type)
+                    .build()?
+            }
+            InferredType::Group(fields) => {
+                let mut fields = build_fields(fields)?;
+                // The groups of objects without any values are left out, as they can't be empty.
+                if fields.is_empty() {
+                    continue;
+                }
+                Type::group_type_builder(&name)
+                    .with_repetition(Repetition::OPTIONAL)
+                    .with_fields(&mut fields)
+                    .build()?
+            }
+        };
+        types.push(Arc::new(field));
+    }
+    Ok(types)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use indoc::indoc;
+    use parquet::{
+        file::reader::{FileReader, SerializedFileReader},
+        record::{Field, RowAccessor},
+    };
+    use vector_common::btreemap;
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    fn serializer(schema: Option<&str>, row_group_size: usize) -> ParquetSerializer {
+        ParquetSerializerConfig::new(ParquetSerializerOptions {
+            schema: schema.map(Into::into),
+            compression: ParquetCompression::Snappy,
+            row_group_size,
+        })
+        .build()
+        .unwrap()
+    }
+
+    fn read(file: Bytes) -> (SerializedFileReader<Bytes>, Vec<parquet::record::Row>) {
+        let reader = SerializedFileReader::new(file).unwrap();
+        let rows = reader.get_row_iter(None).unwrap().collect();
+        (reader, rows)
+    }
+
+    #[test]
+    fn serialize_with_schema() {
+        let serializer = serializer(
+            Some(indoc! {"
+                message logs {
+                    required binary message (UTF8);
+                    optional int64 timestamp (TIMESTAMP_MILLIS);
+                    optional group http {
+                        optional int32 status;
+                        required double duration;
+                    }
+                    optional group labels (MAP) {
+                        repeated group key_value {
+                            required binary key (UTF8);
+                            optional binary value (UTF8);
+                        }
+                    }
+                }
+            "}),
+            10,
+        );
+        let first = LogEvent::from(btreemap! {
+            "message" => "first",
+            "timestamp" => Utc.ymd(2022, 10, 1).and_hms_milli(12, 30, 0, 250),
+            "http" => btreemap! {
+                "status" => 200,
+                "duration" => 1.5,
+            },
+            "labels" => btreemap! {
+                "env" => "prod",
+                "region" => "eu",
+            },
+            "ignored" => true,
+        });
+        let second = LogEvent::from(btreemap! {
+            "http" => btreemap! {
+                "status" => "unknown",
+            },
+        });
+
+        let file = serializer
+            .serialize(vec![first.into(), second.into()])
+            .unwrap();
+        let (_, rows) = read(file);
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].get_string(0).unwrap(), "first");
+        assert_eq!(rows[0].get_timestamp_millis(1).unwrap(), 1664627400250);
+        let http = rows[0].get_group(2).unwrap();
+        assert_eq!(http.get_int(0).unwrap(), 200);
+        assert_eq!(http.get_double(1).unwrap(), 1.5);
+        assert_eq!(rows[0].get_map(3).unwrap().len(), 2);
+
+        assert_eq!(rows[1].get_string(0).unwrap(), "");
+        let http = rows[1].get_group(2).unwrap();
+        assert!(matches!(
+            http.get_column_iter().next().unwrap().1,
+            Field::Null
+        ));
+        assert_eq!(http.get_double(1).unwrap(), 0.0);
+        assert!(rows[1].get_map(3).is_err());
+    }
+
+    #[test]
+    fn serialize_with_inferred_schema() {
+        let serializer = serializer(None, 1);
+        let first = LogEvent::from(btreemap! {
+            "message" => "first",
+            "count" => 1,
+            "tags" => vec!["a", "b"],
+            "host" => btreemap! { "name" => "a" },
+        });
+        let second = LogEvent::from(btreemap! {
+            "message" => "second",
+            "count" => "two",
+            "extra" => 2,
+        });
+
+        let file = serializer
+            .serialize(vec![first.into(), second.into()])
+            .unwrap();
+        let (reader, rows) = read(file);
+
+        let columns = serializer
+            .schema()
+            .unwrap()
+            .columns()
+            .iter()
+            .map(|column| column.path().string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            columns,
+            vec!["count", "extra", "host.name", "message", "tags"]
+        );
+        assert_eq!(reader.num_row_groups(), 2);
+        assert_eq!(rows[0].get_long(0).unwrap(), 1);
+        assert_eq!(rows[0].get_string(4).unwrap(), r#"["a","b"]"#);
+        assert_eq!(rows[1].get_string(3).unwrap(), "second");
+        assert_eq!(rows[1].get_long(1).unwrap(), 2);
+        assert!(rows[1].get_long(0).is_err());
+
+        // The schema inferred from the first batch is kept for the next ones.
+        let third = LogEvent::from(btreemap! { "other" => true });
+        serializer.serialize(vec![third.into()]).unwrap();
+        assert_eq!(serializer.schema().unwrap().num_columns(), 5);
+    }
+
+    #[test]
+    fn reject_unsupported_schemas() {
+        for schema in [
+            "message logs { repeated binary tags (UTF8); }",
+            "message logs { required int96 time; }",
+            "message logs { optional group labels (MAP) { repeated group key_value { required int32 key; } } }",
+        ] {
+            assert!(ParquetSerializerConfig::new(ParquetSerializerOptions {
+                schema: Some(schema.into()),
+                ..Default::default()
+            })
+            .build()
+            .is_err());
+        }
+    }
+}
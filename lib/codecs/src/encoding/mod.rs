@@ -1,18 +1,28 @@
 //! A collection of support structures that are used in the process of encoding
 //! events into bytes.
 
+pub mod chunking;
 pub mod format;
 pub mod framing;
 
 use std::fmt::Debug;
 
 use bytes::BytesMut;
+pub use chunking::{
+    ChunkedGelfEncoder, ChunkedGelfEncoderConfig, ChunkedGelfEncoderOptions, GelfCompression,
+};
+pub use format::{
+    AvroSerializer, AvroSerializerConfig, AvroSerializerOptions, CefSerializer,
+    CefSerializerConfig, GelfSerializer, GelfSerializerConfig, JsonSerializer,
+    JsonSerializerConfig, LogfmtSerializer, LogfmtSerializerConfig, NativeJsonSerializer,
+    NativeJsonSerializerConfig, NativeSerializer, NativeSerializerConfig, RawMessageSerializer,
+    RawMessageSerializerConfig, TextSerializer, TextSerializerConfig,
+};
+#[cfg(feature = "msgpack")]
+pub use format::{MsgpackSerializer, MsgpackSerializerConfig};
+#[cfg(feature = "parquet")]
 pub use format::{
-    AvroSerializer, AvroSerializerConfig, AvroSerializerOptions, GelfSerializer,
-    GelfSerializerConfig, JsonSerializer, JsonSerializerConfig, LogfmtSerializer,
-    LogfmtSerializerConfig, NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer,
-    NativeSerializerConfig, RawMessageSerializer, RawMessageSerializerConfig, TextSerializer,
-    TextSerializerConfig,
+    ParquetCompression, ParquetSerializer, ParquetSerializerConfig, ParquetSerializerOptions,
 };
 pub use framing::{
     BoxedFramer, BoxedFramingError, BytesEncoder, BytesEncoderConfig, CharacterDelimitedEncoder,
@@ -196,6 +206,9 @@ pub enum SerializerConfig {
         avro: AvroSerializerOptions,
     },
 
+    /// CEF (ArcSight Common Event Format) serialization.
+    Cef,
+
     /// GELF serialization.
     Gelf,
 
@@ -205,12 +218,28 @@ pub enum SerializerConfig {
     /// Logfmt serialization.
     Logfmt,
 
+    #[cfg(feature = "msgpack")]
+    /// MessagePack serialization.
+    Msgpack,
+
     /// Native Vector serialization based on Protocol Buffers.
     Native,
 
     /// Native Vector serialization based on JSON.
     NativeJson,
 
+    #[cfg(feature = "parquet")]
+    /// Apache Parquet serialization.
+    ///
+    /// As Parquet files hold the values of their rows column by column, each batch is written as
+    /// a whole file, which is only supported by the sinks writing batches as objects: `aws_s3`,
+    /// `azure_blob` and `gcp_cloud_storage`.
+    Parquet {
+        /// Apache Parquet serializer options.
+        #[serde(default)]
+        parquet: ParquetSerializerOptions,
+    },
+
     /// No serialization.
     ///
     /// This encoding, specifically, will only encode the `message` field of a log event. Users should take care if
@@ -234,6 +263,12 @@ impl From<AvroSerializerConfig> for SerializerConfig {
     }
 }
 
+impl From<CefSerializerConfig> for SerializerConfig {
+    fn from(_: CefSerializerConfig) -> Self {
+        Self::Cef
+    }
+}
+
 impl From<GelfSerializerConfig> for SerializerConfig {
     fn from(_: GelfSerializerConfig) -> Self {
         Self::Gelf
@@ -252,6 +287,13 @@ impl From<LogfmtSerializerConfig> for SerializerConfig {
     }
 }
 
+#[cfg(feature = "msgpack")]
+impl From<MsgpackSerializerConfig> for SerializerConfig {
+    fn from(_: MsgpackSerializerConfig) -> Self {
+        Self::Msgpack
+    }
+}
+
 impl From<NativeSerializerConfig> for SerializerConfig {
     fn from(_: NativeSerializerConfig) -> Self {
         Self::Native
@@ -264,6 +306,15 @@ impl From<NativeJsonSerializerConfig> for SerializerConfig {
     }
 }
 
+#[cfg(feature = "parquet")]
+impl From<ParquetSerializerConfig> for SerializerConfig {
+    fn from(config: ParquetSerializerConfig) -> Self {
+        Self::Parquet {
+            parquet: config.parquet,
+        }
+    }
+}
+
 impl From<RawMessageSerializerConfig> for SerializerConfig {
     fn from(_: RawMessageSerializerConfig) -> Self {
         Self::RawMessage
@@ -283,13 +334,21 @@ impl SerializerConfig {
             SerializerConfig::Avro { avro } => Ok(Serializer::Avro(
                 AvroSerializerConfig::new(avro.schema.clone()).build()?,
             )),
+            SerializerConfig::Cef => Ok(Serializer::Cef(CefSerializerConfig.build())),
             SerializerConfig::Gelf => Ok(Serializer::Gelf(GelfSerializerConfig::new().build())),
             SerializerConfig::Json => Ok(Serializer::Json(JsonSerializerConfig.build())),
             SerializerConfig::Logfmt => Ok(Serializer::Logfmt(LogfmtSerializerConfig.build())),
+            #[cfg(feature = "msgpack")]
+            SerializerConfig::Msgpack => Ok(Serializer::Msgpack(MsgpackSerializerConfig.build())),
             SerializerConfig::Native => Ok(Serializer::Native(NativeSerializerConfig.build())),
             SerializerConfig::NativeJson => {
                 Ok(Serializer::NativeJson(NativeJsonSerializerConfig.build()))
             }
+            #[cfg(feature = "parquet")]
+            SerializerConfig::Parquet { .. } => Err("Parquet serialization writes batches as \
+                whole files, which is only supported by the `aws_s3`, `azure_blob` and \
+                `gcp_cloud_storage` sinks."
+                .into()),
             SerializerConfig::RawMessage => {
                 Ok(Serializer::RawMessage(RawMessageSerializerConfig.build()))
             }
@@ -303,11 +362,18 @@ impl SerializerConfig {
             SerializerConfig::Avro { avro } => {
                 AvroSerializerConfig::new(avro.schema.clone()).input_type()
             }
+            SerializerConfig::Cef => CefSerializerConfig.input_type(),
             SerializerConfig::Gelf { .. } => GelfSerializerConfig::input_type(),
             SerializerConfig::Json => JsonSerializerConfig.input_type(),
             SerializerConfig::Logfmt => LogfmtSerializerConfig.input_type(),
+            #[cfg(feature = "msgpack")]
+            SerializerConfig::Msgpack => MsgpackSerializerConfig.input_type(),
             SerializerConfig::Native => NativeSerializerConfig.input_type(),
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.input_type(),
+            #[cfg(feature = "parquet")]
+            SerializerConfig::Parquet { parquet } => {
+                ParquetSerializerConfig::new(parquet.clone()).input_type()
+            }
             SerializerConfig::RawMessage => RawMessageSerializerConfig.input_type(),
             SerializerConfig::Text => TextSerializerConfig.input_type(),
         }
@@ -319,11 +385,18 @@ impl SerializerConfig {
             SerializerConfig::Avro { avro } => {
                 AvroSerializerConfig::new(avro.schema.clone()).schema_requirement()
             }
+            SerializerConfig::Cef => CefSerializerConfig.schema_requirement(),
             SerializerConfig::Gelf { .. } => GelfSerializerConfig::schema_requirement(),
             SerializerConfig::Json => JsonSerializerConfig.schema_requirement(),
             SerializerConfig::Logfmt => LogfmtSerializerConfig.schema_requirement(),
+            #[cfg(feature = "msgpack")]
+            SerializerConfig::Msgpack => MsgpackSerializerConfig.schema_requirement(),
             SerializerConfig::Native => NativeSerializerConfig.schema_requirement(),
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.schema_requirement(),
+            #[cfg(feature = "parquet")]
+            SerializerConfig::Parquet { parquet } => {
+                ParquetSerializerConfig::new(parquet.clone()).schema_requirement()
+            }
             SerializerConfig::RawMessage => RawMessageSerializerConfig.schema_requirement(),
             SerializerConfig::Text => TextSerializerConfig.schema_requirement(),
         }
@@ -335,12 +408,17 @@ impl SerializerConfig {
 pub enum Serializer {
     /// Uses an `AvroSerializer` for serialization.
     Avro(AvroSerializer),
+    /// Uses a `CefSerializer` for serialization.
+    Cef(CefSerializer),
     /// Uses a `GelfSerializer` for serialization.
     Gelf(GelfSerializer),
     /// Uses a `JsonSerializer` for serialization.
     Json(JsonSerializer),
     /// Uses a `LogfmtSerializer` for serialization.
     Logfmt(LogfmtSerializer),
+    #[cfg(feature = "msgpack")]
+    /// Uses a `MsgpackSerializer` for serialization.
+    Msgpack(MsgpackSerializer),
     /// Uses a `NativeSerializer` for serialization.
     Native(NativeSerializer),
     /// Uses a `NativeJsonSerializer` for serialization.
@@ -356,7 +434,10 @@ impl Serializer {
     pub fn supports_json(&self) -> bool {
         match self {
             Serializer::Json(_) | Serializer::NativeJson(_) | Serializer::Gelf(_) => true,
+            #[cfg(feature = "msgpack")]
+            Serializer::Msgpack(_) => false,
             Serializer::Avro(_)
+            | Serializer::Cef(_)
             | Serializer::Logfmt(_)
             | Serializer::Text(_)
             | Serializer::Native(_)
@@ -375,7 +456,10 @@ impl Serializer {
             Serializer::Gelf(serializer) => serializer.to_json_value(event),
             Serializer::Json(serializer) => serializer.to_json_value(event),
             Serializer::NativeJson(serializer) => serializer.to_json_value(event),
+            #[cfg(feature = "msgpack")]
+            Serializer::Msgpack(_) => panic!("Serializer does not support JSON"),
             Serializer::Avro(_)
+            | Serializer::Cef(_)
             | Serializer::Logfmt(_)
             | Serializer::Text(_)
             | Serializer::Native(_)
@@ -392,6 +476,12 @@ impl From<AvroSerializer> for Serializer {
     }
 }
 
+impl From<CefSerializer> for Serializer {
+    fn from(serializer: CefSerializer) -> Self {
+        Self::Cef(serializer)
+    }
+}
+
 impl From<GelfSerializer> for Serializer {
     fn from(serializer: GelfSerializer) -> Self {
         Self::Gelf(serializer)
@@ -410,6 +500,13 @@ impl From<LogfmtSerializer> for Serializer {
     }
 }
 
+#[cfg(feature = "msgpack")]
+impl From<MsgpackSerializer> for Serializer {
+    fn from(serializer: MsgpackSerializer) -> Self {
+        Self::Msgpack(serializer)
+    }
+}
+
 impl From<NativeSerializer> for Serializer {
     fn from(serializer: NativeSerializer) -> Self {
         Self::Native(serializer)
@@ -440,9 +537,12 @@ impl tokio_util::codec::Encoder<Event> for Serializer {
     fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
         match self {
             Serializer::Avro(serializer) => serializer.encode(event, buffer),
+            Serializer::Cef(serializer) => serializer.encode(event, buffer),
             Serializer::Gelf(serializer) => serializer.encode(event, buffer),
             Serializer::Json(serializer) => serializer.encode(event, buffer),
             Serializer::Logfmt(serializer) => serializer.encode(event, buffer),
+            #[cfg(feature = "msgpack")]
+            Serializer::Msgpack(serializer) => serializer.encode(event, buffer),
             Serializer::Native(serializer) => serializer.encode(event, buffer),
             Serializer::NativeJson(serializer) => serializer.encode(event, buffer),
             Serializer::RawMessage(serializer) => serializer.encode(event, buffer),
@@ -40,6 +40,15 @@ pub mod gelf_fields {
     // < Every field with an underscore (_) prefix will be treated as an additional field. >
 }
 
+/// The magic bytes prefixing every chunk of a chunked GELF message.
+pub const GELF_MAGIC: [u8; 2] = [0x1e, 0x0f];
+
+/// Magic bytes, 8 bytes of message ID, 1 byte of sequence number and 1 byte of sequence count.
+pub const GELF_CHUNK_HEADER_LENGTH: usize = 12;
+
+/// The GELF specification caps the number of chunks a single message can be split into.
+pub const GELF_MAX_CHUNKS: u8 = 128;
+
 /// Regex for matching valid field names. Must contain only word chars, periods and dashes.
 /// Additional field names must also be prefixed with an `_` , however that is intentionally
 /// omitted from this regex to be checked separately to create a specific error message.
@@ -4,27 +4,42 @@
 #![deny(missing_docs)]
 #![deny(warnings)]
 
+pub mod cef;
 pub mod decoding;
 pub mod encoding;
 pub mod gelf;
+pub mod msgpack;
 
+pub use cef::cef_fields;
+#[cfg(feature = "avro-registry")]
+pub use decoding::{AvroDeserializer, AvroDeserializerConfig};
 pub use decoding::{
-    BytesDecoder, BytesDecoderConfig, BytesDeserializer, BytesDeserializerConfig,
-    CharacterDelimitedDecoder, CharacterDelimitedDecoderConfig, GelfDeserializer,
-    GelfDeserializerConfig, JsonDeserializer, JsonDeserializerConfig, LengthDelimitedDecoder,
-    LengthDelimitedDecoderConfig, NativeDeserializer, NativeDeserializerConfig,
+    BytesDecoder, BytesDecoderConfig, BytesDeserializer, BytesDeserializerConfig, CefDeserializer,
+    CefDeserializerConfig, CharacterDelimitedDecoder, CharacterDelimitedDecoderConfig,
+    ChunkedGelfDecoder, ChunkedGelfDecoderConfig, GelfDeserializer, GelfDeserializerConfig,
+    JsonDeserializer, JsonDeserializerConfig, LengthDelimitedDecoder, LengthDelimitedDecoderConfig,
+    MultilineDecoder, MultilineDecoderConfig, NativeDeserializer, NativeDeserializerConfig,
     NativeJsonDeserializer, NativeJsonDeserializerConfig, NewlineDelimitedDecoder,
     NewlineDelimitedDecoderConfig, OctetCountingDecoder, OctetCountingDecoderConfig,
     StreamDecodingError,
 };
+#[cfg(feature = "csv")]
+pub use decoding::{CsvDeserializer, CsvDeserializerConfig};
+#[cfg(feature = "msgpack")]
+pub use decoding::{MsgpackDeserializer, MsgpackDeserializerConfig};
+#[cfg(feature = "protobuf-dynamic")]
+pub use decoding::{ProtobufDeserializer, ProtobufDeserializerConfig};
 #[cfg(feature = "syslog")]
 pub use decoding::{SyslogDeserializer, SyslogDeserializerConfig};
 pub use encoding::{
-    BytesEncoder, BytesEncoderConfig, CharacterDelimitedEncoder, CharacterDelimitedEncoderConfig,
-    GelfSerializer, GelfSerializerConfig, JsonSerializer, JsonSerializerConfig,
-    LengthDelimitedEncoder, LengthDelimitedEncoderConfig, LogfmtSerializer, LogfmtSerializerConfig,
-    NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer, NativeSerializerConfig,
-    NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig, RawMessageSerializer,
-    RawMessageSerializerConfig, TextSerializer, TextSerializerConfig,
+    BytesEncoder, BytesEncoderConfig, CefSerializer, CefSerializerConfig,
+    CharacterDelimitedEncoder, CharacterDelimitedEncoderConfig, GelfSerializer,
+    GelfSerializerConfig, JsonSerializer, JsonSerializerConfig, LengthDelimitedEncoder,
+    LengthDelimitedEncoderConfig, LogfmtSerializer, LogfmtSerializerConfig, NativeJsonSerializer,
+    NativeJsonSerializerConfig, NativeSerializer, NativeSerializerConfig, NewlineDelimitedEncoder,
+    NewlineDelimitedEncoderConfig, RawMessageSerializer, RawMessageSerializerConfig,
+    TextSerializer, TextSerializerConfig,
 };
+#[cfg(feature = "msgpack")]
+pub use encoding::{MsgpackSerializer, MsgpackSerializerConfig};
 pub use gelf::{gelf_fields, VALID_FIELD_REGEX};
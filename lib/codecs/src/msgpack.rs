@@ -0,0 +1,84 @@
+//! Contains common definitions for MessagePack codec support
+
+use chrono::{DateTime, TimeZone, Utc};
+
+/// The extension type of the timestamps defined by the MessagePack specification.
+pub const TIMESTAMP_EXT_TYPE: i8 = -1;
+
+/// The extension type Fluentd uses for its `EventTime` timestamps.
+pub const FLUENTD_EVENT_TIME_EXT_TYPE: i8 = 0;
+
+/// Encodes a timestamp as the payload of a timestamp extension, using the smallest of the 32, 64
+/// and 96 bit formats able to hold it.
+pub fn encode_timestamp(timestamp: &DateTime<Utc>) -> Vec<u8> {
+    let seconds = timestamp.timestamp();
+    let nanoseconds = timestamp.timestamp_subsec_nanos();
+
+    if seconds >> 34 == 0 {
+        let data = (u64::from(nanoseconds) << 34) | seconds as u64;
+        if data & 0xffff_ffff_0000_0000 == 0 {
+            (data as u32).to_be_bytes().to_vec()
+        } else {
+            data.to_be_bytes().to_vec()
+        }
+    } else {
+        let mut payload = Vec::with_capacity(12);
+        payload.extend_from_slice(&nanoseconds.to_be_bytes());
+        payload.extend_from_slice(&seconds.to_be_bytes());
+        payload
+    }
+}
+
+/// Decodes the payload of a timestamp extension, or of a Fluentd `EventTime`. Returns `None` if
+/// the payload isn't a valid timestamp.
+pub fn decode_timestamp(ext_type: i8, payload: &[u8]) -> Option<DateTime<Utc>> {
+    let (seconds, nanoseconds) = match (ext_type, payload.len()) {
+        (TIMESTAMP_EXT_TYPE, 4) => (i64::from(u32::from_be_bytes(payload.try_into().ok()?)), 0),
+        (TIMESTAMP_EXT_TYPE, 8) => {
+            let data = u64::from_be_bytes(payload.try_into().ok()?);
+            ((data & 0x0000_0003_ffff_ffff) as i64, (data >> 34) as u32)
+        }
+        (TIMESTAMP_EXT_TYPE, 12) => (
+            i64::from_be_bytes(payload[4..].try_into().ok()?),
+            u32::from_be_bytes(payload[..4].try_into().ok()?),
+        ),
+        (FLUENTD_EVENT_TIME_EXT_TYPE, 8) => (
+            i64::from(u32::from_be_bytes(payload[..4].try_into().ok()?)),
+            u32::from_be_bytes(payload[4..].try_into().ok()?),
+        ),
+        _ => return None,
+    };
+
+    Utc.timestamp_opt(seconds, nanoseconds).single()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamp_formats_round_trip() {
+        for (timestamp, len) in [
+            (Utc.timestamp(1_600_000_000, 0), 4),
+            (Utc.timestamp(1_600_000_000, 123_456_789), 8),
+            (Utc.timestamp(-1, 500), 12),
+            (Utc.timestamp(20_000_000_000, 1), 12),
+        ] {
+            let payload = encode_timestamp(&timestamp);
+            assert_eq!(payload.len(), len);
+            assert_eq!(
+                decode_timestamp(TIMESTAMP_EXT_TYPE, &payload),
+                Some(timestamp)
+            );
+        }
+    }
+
+    #[test]
+    fn decode_fluentd_event_time() {
+        let payload = [0x5f, 0x5e, 0x10, 0x00, 0x07, 0x5b, 0xcd, 0x15];
+        assert_eq!(
+            decode_timestamp(FLUENTD_EVENT_TIME_EXT_TYPE, &payload),
+            Some(Utc.timestamp(1_600_000_000, 123_456_789))
+        );
+    }
+}
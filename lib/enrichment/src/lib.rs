@@ -73,6 +73,11 @@ pub trait Table: DynClone {
 
     /// Returns true if the underlying data has changed and the table needs reloading.
     fn needs_reload(&self) -> bool;
+
+    /// Returns the number of rows loaded, if the table holds its data in memory.
+    fn rows(&self) -> Option<usize> {
+        None
+    }
 }
 
 dyn_clone::clone_trait_object!(Table);
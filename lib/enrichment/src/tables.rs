@@ -111,6 +111,42 @@ impl TableRegistry {
         self.tables.swap(Arc::new(tables));
     }
 
+    /// Replace a table that is being read with its reloaded data, without reloading the other
+    /// tables.
+    ///
+    /// As with [`load`](Self::load), the tables being read cannot be mutated, so the other tables
+    /// are cloned into a new list of tables, which is then swapped into the `ArcSwap`. Searches
+    /// in progress complete against the previous data.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the tables are being loaded, or if the table isn't loaded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex is poisoned.
+    pub fn swap_table(
+        &self,
+        name: &str,
+        table: Box<dyn Table + Send + Sync>,
+    ) -> Result<(), String> {
+        // Holding the lock keeps the tables from being loaded whilst they are swapped.
+        let loading = self.loading.lock().unwrap();
+        if loading.is_some() {
+            return Err("tables are being loaded".to_string());
+        }
+
+        match &**self.tables.load() {
+            Some(tables) if tables.contains_key(name) => {
+                let mut tables = tables.clone();
+                tables.insert(name.to_string(), table);
+                self.tables.store(Arc::new(Some(tables)));
+                Ok(())
+            }
+            _ => Err(format!("table '{}' not loaded", name)),
+        }
+    }
+
     /// Return a list of the available tables that we can write to.
     ///
     /// This only works in the writing stage and will acquire a lock to retrieve
@@ -451,4 +487,40 @@ mod tests {
                 .unwrap()
         );
     }
+
+    #[test]
+    fn can_swap_table_while_reading() {
+        let mut tables: TableMap = HashMap::new();
+        tables.insert("dummy1".to_string(), Box::new(DummyEnrichmentTable::new()));
+        tables.insert("dummy2".to_string(), Box::new(DummyEnrichmentTable::new()));
+
+        let registry = super::TableRegistry::default();
+        registry.load(tables);
+
+        let table = || -> Box<dyn Table + Send + Sync> {
+            Box::new(DummyEnrichmentTable::new_with_data(BTreeMap::from([(
+                "field".to_string(),
+                Value::from("reloaded"),
+            )])))
+        };
+
+        // Tables being loaded can't be swapped.
+        assert!(registry.swap_table("dummy1", table()).is_err());
+
+        registry.finish_load();
+        let tables_search = registry.as_readonly();
+
+        assert_eq!(Ok(()), registry.swap_table("dummy1", table()));
+        assert!(registry.swap_table("dummy3", table()).is_err());
+
+        let field = |name| {
+            tables_search
+                .find_table_row(name, Case::Sensitive, &[], None, None)
+                .unwrap()
+                .remove("field")
+                .unwrap()
+        };
+        assert_eq!(Value::from("reloaded"), field("dummy1"));
+        assert_eq!(Value::from("result"), field("dummy2"));
+    }
 }
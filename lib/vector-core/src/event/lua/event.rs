@@ -1,6 +1,6 @@
 use mlua::prelude::*;
 
-use crate::event::{Event, LogEvent, Metric};
+use crate::event::{Event, LogEvent, Metric, TraceEvent};
 
 impl<'a> ToLua<'a> for Event {
     #![allow(clippy::wrong_self_convention)] // this trait is defined by mlua
@@ -9,12 +9,10 @@ impl<'a> ToLua<'a> for Event {
         match self {
             Event::Log(log) => table.raw_set("log", log.to_lua(lua)?)?,
             Event::Metric(metric) => table.raw_set("metric", metric.to_lua(lua)?)?,
-            Event::Trace(_) => {
-                return Err(LuaError::ToLuaConversionError {
-                    from: "Event",
-                    to: "table",
-                    message: Some("Trace are not supported".to_string()),
-                })
+            Event::Trace(trace) => {
+                let (fields, metadata) = trace.into_parts();
+                let log = LogEvent::from_map(fields, metadata);
+                table.raw_set("trace", log.to_lua(lua)?)?;
             }
         }
         Ok(LuaValue::Table(table))
@@ -33,19 +31,25 @@ impl<'a> FromLua<'a> for Event {
                 })
             }
         };
-        match (table.raw_get("log")?, table.raw_get("metric")?) {
-            (LuaValue::Table(log), LuaValue::Nil) => {
+        match (
+            table.raw_get("log")?,
+            table.raw_get("metric")?,
+            table.raw_get("trace")?,
+        ) {
+            (LuaValue::Table(log), LuaValue::Nil, LuaValue::Nil) => {
                 Ok(Event::Log(LogEvent::from_lua(LuaValue::Table(log), lua)?))
             }
-            (LuaValue::Nil, LuaValue::Table(metric)) => Ok(Event::Metric(Metric::from_lua(
-                LuaValue::Table(metric),
-                lua,
-            )?)),
+            (LuaValue::Nil, LuaValue::Table(metric), LuaValue::Nil) => Ok(Event::Metric(
+                Metric::from_lua(LuaValue::Table(metric), lua)?,
+            )),
+            (LuaValue::Nil, LuaValue::Nil, LuaValue::Table(trace)) => Ok(Event::Trace(
+                TraceEvent::from(LogEvent::from_lua(LuaValue::Table(trace), lua)?),
+            )),
             _ => Err(LuaError::FromLuaConversionError {
                 from: value.type_name(),
                 to: "Event",
                 message: Some(
-                    "Event should contain either \"log\" or \"metric\" key at the top level"
+                    "Event should contain exactly one of \"log\", \"metric\" or \"trace\" keys at the top level"
                         .to_string(),
                 ),
             }),
@@ -109,6 +113,21 @@ mod test {
         assert_event(event, assertions);
     }
 
+    #[test]
+    fn to_lua_trace() {
+        let mut event = LogEvent::default();
+        event.insert("trace_id", 123);
+
+        let assertions = vec![
+            "type(event) == 'table'",
+            "event.log == nil",
+            "type(event.trace) == 'table'",
+            "event.trace.trace_id == 123",
+        ];
+
+        assert_event(Event::Trace(event.into()), assertions);
+    }
+
     #[test]
     fn from_lua_log() {
         let lua_event = r#"
@@ -150,6 +169,19 @@ mod test {
         vector_common::assert_event_data_eq!(event, expected);
     }
 
+    #[test]
+    fn from_lua_trace() {
+        let lua_event = r#"
+        {
+            trace = {
+                trace_id = 123
+            }
+        }"#;
+
+        let event = Lua::new().load(lua_event).eval::<Event>().unwrap();
+        assert_eq!(event.as_trace().get("trace_id"), Some(&Value::from(123)));
+    }
+
     #[test]
     #[should_panic]
     fn from_lua_missing_log_and_metric() {
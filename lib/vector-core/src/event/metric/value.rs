@@ -126,6 +126,27 @@ impl MetricValue {
         }
     }
 
+    /// Converts a sketch to an aggregated histogram.
+    ///
+    /// Histogram bucket bounds are based on `buckets`, where the value is the upper bound of the bucket, as with
+    /// [`MetricValue::distribution_to_agg_histogram`].  The samples of the sketch are counted towards the buckets
+    /// within the relative accuracy of the sketch, while the count and sum are exact.
+    ///
+    /// If this value is not a sketch, then `None` is returned.  Otherwise, `Some(MetricValue::AggregatedHistogram)` is
+    /// returned.
+    pub fn sketch_to_agg_histogram(&self, buckets: &[f64]) -> Option<MetricValue> {
+        match self {
+            MetricValue::Sketch { sketch } => match sketch {
+                MetricSketch::AgentDDSketch(ddsketch) => Some(MetricValue::AggregatedHistogram {
+                    buckets: ddsketch.to_buckets(buckets),
+                    count: u64::from(ddsketch.count()),
+                    sum: ddsketch.sum().unwrap_or(0.0),
+                }),
+            },
+            _ => None,
+        }
+    }
+
     /// Zeroes out all the values contained in this value.
     ///
     /// This keeps all the bucket/value vectors for the histogram and summary metric types intact while zeroing the
@@ -681,6 +681,36 @@ impl AgentDDSketch {
             .or(Some(f64::NAN))
     }
 
+    /// Converts this sketch into histogram buckets with the given upper bounds.
+    ///
+    /// The samples of each bin are counted towards the first bucket whose upper bound is greater than or equal to the
+    /// value of the bin, so the bucket counts are accurate within the relative accuracy of the sketch.  As with
+    /// distributions, the buckets are not cumulative, and samples greater than the highest upper bound are not counted
+    /// towards any bucket.
+    pub fn to_buckets(&self, bounds: &[f64]) -> Vec<Bucket> {
+        let mut buckets = bounds
+            .iter()
+            .map(|upper_limit| Bucket {
+                upper_limit: *upper_limit,
+                count: 0,
+            })
+            .collect::<Vec<_>>();
+
+        for bin in &self.bins {
+            // Bins are only present when samples were inserted, so the minimum and maximum are
+            // always set here.
+            let value = self.config.bin_lower_bound(bin.k).clamp(self.min, self.max);
+            if let Some(bucket) = buckets
+                .iter_mut()
+                .find(|bucket| bucket.upper_limit >= value)
+            {
+                bucket.count += u64::from(bin.n);
+            }
+        }
+
+        buckets
+    }
+
     /// Merges another sketch into this sketch, without a loss of accuracy.
     ///
     /// All samples present in the other sketch will be correctly represented in this sketch, and
@@ -1204,6 +1234,62 @@ mod tests {
         assert_eq!(sketch, AgentDDSketch::with_agent_defaults());
     }
 
+    #[test]
+    fn test_to_buckets() {
+        let mut sketch = AgentDDSketch::with_agent_defaults();
+        sketch.insert_many(&[0.0, 0.5, 1.0, 1.5, 2.0, 3.0, 10.0]);
+        sketch.insert_n(-1.0, 2);
+
+        let buckets = sketch.to_buckets(&[0.0, 1.0, 2.5, 5.0]);
+        let counts = buckets
+            .iter()
+            .map(|bucket| (bucket.upper_limit, bucket.count))
+            .collect::<Vec<_>>();
+        assert_eq!(counts, vec![(0.0, 3), (1.0, 2), (2.5, 2), (5.0, 1)]);
+
+        // Samples greater than the highest bound only count towards the sketch itself.
+        assert_eq!(sketch.count(), 9);
+    }
+
+    #[test]
+    fn test_to_buckets_round_trip() {
+        let mut sketch = AgentDDSketch::with_agent_defaults();
+        for v in 1..=1000 {
+            sketch.insert(f64::from(v));
+        }
+
+        // The quantiles of 1..=1000 are known, so the cumulative count of the bucket of each
+        // quantile is its rank, within the relative accuracy of the sketch on the bucket bounds.
+        let quantiles = [0.1, 0.25, 0.5, 0.75, 0.9, 0.99, 1.0];
+        let bounds = quantiles.iter().map(|q| q * 1000.0).collect::<Vec<_>>();
+        let buckets = sketch.to_buckets(&bounds);
+        assert_eq!(buckets.len(), bounds.len());
+
+        let tolerance = 1.0 + 2.0 * AGENT_DEFAULT_EPS;
+        let mut cumulative = 0;
+        for (bucket, bound) in buckets.iter().zip(&bounds) {
+            assert_eq!(bucket.upper_limit, *bound);
+            cumulative += bucket.count;
+
+            #[allow(clippy::cast_possible_truncation)]
+            let (min_rank, max_rank) = (
+                (bound / tolerance).floor() as u64,
+                (bound * tolerance).floor() as u64,
+            );
+            assert!(
+                (min_rank..=max_rank).contains(&cumulative),
+                "bucket {} holds {} samples, expected between {} and {}",
+                bound,
+                cumulative,
+                min_rank,
+                max_rank
+            );
+        }
+
+        // The highest bound is the maximum, so every sample is counted.
+        assert_eq!(cumulative, 1000);
+    }
+
     #[test]
     fn test_merge() {
         let mut all_values = AgentDDSketch::with_agent_defaults();
@@ -0,0 +1,66 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+use crate::state_store::StateStore;
+
+fn get_state(store: &StateStore, key: Value) -> Resolved {
+    let key = key.try_bytes_utf8_lossy()?;
+    Ok(store.get(&key).unwrap_or(Value::Null))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GetState;
+
+impl Function for GetState {
+    fn identifier(&self) -> &'static str {
+        "get_state"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "key",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "Get a missing value",
+            source: r#"get_state("last_sequence")"#,
+            result: Ok("null"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let key = arguments.required("key");
+        let store = ctx
+            .get_external_context::<StateStore>()
+            .cloned()
+            .unwrap_or_default();
+
+        Ok(Box::new(GetStateFn { store, key }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct GetStateFn {
+    store: StateStore,
+    key: Box<dyn Expression>,
+}
+
+impl Expression for GetStateFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let key = self.key.resolve(ctx)?;
+        get_state(&self.store, key)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::any().infallible()
+    }
+}
@@ -0,0 +1,114 @@
+use std::ops::Deref;
+
+use ::value::Value;
+use lookup::LookupBuf;
+use vrl::{diagnostic::Label, prelude::*};
+
+/// The metadata field the output of the event is stored in, until the transform sends the event
+/// to that output.
+pub const ROUTE_METADATA_KEY: &str = "vector_route";
+
+/// The named outputs of the transform, which events can be routed to.
+///
+/// When the context is missing, as in the REPL, events can be routed to any output.
+#[derive(Debug, Default, Clone)]
+pub struct RouteOutputs(pub Vec<String>);
+
+impl Deref for RouteOutputs {
+    type Target = Vec<String>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+fn route(ctx: &mut Context, outputs: Option<&RouteOutputs>, output: Value) -> Resolved {
+    let output = output.try_bytes_utf8_lossy()?.into_owned();
+    if let Some(outputs) = outputs {
+        if !outputs.contains(&output) {
+            return Err(format!("unknown output \"{}\"", output).into());
+        }
+    }
+
+    ctx.target_mut()
+        .set_metadata(&LookupBuf::from(ROUTE_METADATA_KEY), output.into())?;
+    Ok(Value::Null)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Route;
+
+impl Function for Route {
+    fn identifier(&self) -> &'static str {
+        "route"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "output",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "Route the event to a named output",
+            source: r#"route("errors")"#,
+            result: Ok("null"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let span = ctx.span();
+        let output = arguments.required("output");
+        let outputs = ctx.get_external_context::<RouteOutputs>().cloned();
+
+        // Outputs known when the program is compiled are checked then, instead of failing for
+        // every event.
+        if let (Some(outputs), Some(literal)) = (&outputs, output.as_value()) {
+            let literal = literal
+                .try_bytes_utf8_lossy()
+                .expect("output not bytes")
+                .into_owned();
+            if !outputs.contains(&literal) {
+                let error = ExpressionError::Error {
+                    message: format!("unknown output \"{}\"", literal),
+                    labels: vec![Label::primary(
+                        "the output must be one of the `named_outputs` of the transform",
+                        span,
+                    )],
+                    notes: vec![],
+                };
+
+                return Err(Box::new(error) as Box<dyn DiagnosticMessage>);
+            }
+        }
+
+        Ok(Box::new(RouteFn { output, outputs }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RouteFn {
+    output: Box<dyn Expression>,
+    outputs: Option<RouteOutputs>,
+}
+
+impl Expression for RouteFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let output = self.output.resolve(ctx)?;
+        route(ctx, self.outputs.as_ref(), output)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        // Outputs that are only known at runtime may not be one of the named outputs.
+        let fallible = self.outputs.is_some() && self.output.as_value().is_none();
+        TypeDef::null().with_fallibility(fallible)
+    }
+}
@@ -0,0 +1,146 @@
+use std::time::Duration;
+
+use ::value::Value;
+use vrl::prelude::*;
+
+use crate::state_store::StateStore;
+
+const TTL_NOT_POSITIVE_ERR: &str = "ttl must be a positive number of seconds";
+
+fn set_state(store: &StateStore, key: Value, value: Value, ttl: Option<Value>) -> Resolved {
+    let key = key.try_bytes_utf8_lossy()?.into_owned();
+    let ttl = ttl.map(get_ttl).transpose()?;
+    store.set(key, value, ttl);
+    Ok(Value::Null)
+}
+
+fn get_ttl(value: Value) -> std::result::Result<Duration, &'static str> {
+    match value.try_integer().expect("ttl must be an integer") {
+        ttl if ttl > 0 => Ok(Duration::from_secs(ttl as u64)),
+        _ => Err(TTL_NOT_POSITIVE_ERR),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SetState;
+
+impl Function for SetState {
+    fn identifier(&self) -> &'static str {
+        "set_state"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "key",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "value",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "ttl",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "Set a value",
+                source: r#"set_state("last_sequence", 42)"#,
+                result: Ok("null"),
+            },
+            Example {
+                title: "Set a value expiring after an hour",
+                source: r#"set_state("last_sequence", 42, ttl: 3600)"#,
+                result: Ok("null"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let key = arguments.required("key");
+        let value = arguments.required("value");
+        let ttl = arguments.optional("ttl");
+
+        if let Some(literal) = ttl.as_ref().and_then(|ttl| ttl.as_value()) {
+            let _ =
+                get_ttl(literal.clone()).map_err(|err| vrl::function::Error::InvalidArgument {
+                    keyword: "ttl",
+                    value: literal,
+                    error: err,
+                })?;
+        }
+
+        let store = ctx
+            .get_external_context::<StateStore>()
+            .cloned()
+            .unwrap_or_default();
+
+        Ok(Box::new(SetStateFn {
+            store,
+            key,
+            value,
+            ttl,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SetStateFn {
+    store: StateStore,
+    key: Box<dyn Expression>,
+    value: Box<dyn Expression>,
+    ttl: Option<Box<dyn Expression>>,
+}
+
+impl Expression for SetStateFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let key = self.key.resolve(ctx)?;
+        let value = self.value.resolve(ctx)?;
+        let ttl = self.ttl.as_ref().map(|ttl| ttl.resolve(ctx)).transpose()?;
+
+        set_state(&self.store, key, value, ttl)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        // Literal time to lives are checked when the program is compiled.
+        let fallible = self
+            .ttl
+            .as_ref()
+            .map_or(false, |ttl| ttl.as_value().is_none());
+        TypeDef::null().with_fallibility(fallible)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sets_and_gets_state() {
+        let store = StateStore::default();
+        assert_eq!(
+            set_state(&store, "count".into(), 1.into(), None),
+            Ok(Value::Null)
+        );
+        assert_eq!(store.get("count"), Some(1.into()));
+        assert_eq!(
+            set_state(&store, "count".into(), 2.into(), Some(0.into()))
+                .unwrap_err()
+                .to_string(),
+            TTL_NOT_POSITIVE_ERR
+        );
+    }
+}
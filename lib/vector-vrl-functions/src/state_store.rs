@@ -0,0 +1,256 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, Weak},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use ::value::Value;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+/// How long changes may stay in memory before they are written to the state file.
+const PERSIST_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The stores that are open, so that a transform rebuilt on a configuration reload shares the
+/// store of the transform it replaces instead of reading a state file that is about to change.
+static OPEN_STORES: Lazy<Mutex<HashMap<PathBuf, Weak<Mutex<Inner>>>>> = Lazy::new(Default::default);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Entry {
+    value: Value,
+    /// The Unix timestamp in seconds after which the entry is expired.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expires_at: Option<i64>,
+}
+
+impl Entry {
+    fn is_expired(&self, now: i64) -> bool {
+        matches!(self.expires_at, Some(expires_at) if expires_at <= now)
+    }
+}
+
+#[derive(Debug)]
+struct Inner {
+    path: Option<PathBuf>,
+    entries: BTreeMap<String, Entry>,
+    dirty: bool,
+}
+
+impl Inner {
+    /// Drops the expired entries and returns the ones to write to the state file, if they changed
+    /// since they were last written.
+    fn take_changes(&mut self) -> Option<(PathBuf, BTreeMap<String, Entry>)> {
+        if !self.dirty {
+            return None;
+        }
+        self.dirty = false;
+        let path = self.path.clone()?;
+        let now = unix_now();
+        self.entries.retain(|_, entry| !entry.is_expired(now));
+        Some((path, self.entries.clone()))
+    }
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        if let Some((path, entries)) = self.take_changes() {
+            persist(&path, &entries);
+        }
+    }
+}
+
+/// The key/value store read and written by the `get_state` and `set_state` functions.
+///
+/// Stores opened with a path load the entries from that file and periodically write them back to
+/// it, so that they survive restarts. Setting a value doesn't write the file: a background thread
+/// writes the whole file, and syncs it to disk, at most once every second while the entries
+/// change, and the changes left are written when the last handle on the store is dropped. Changes
+/// made less than a second before a crash are lost.
+#[derive(Debug, Clone)]
+pub struct StateStore(Arc<Mutex<Inner>>);
+
+impl Default for StateStore {
+    /// Returns a store that only keeps its entries in memory.
+    fn default() -> Self {
+        Self::with_entries(None, BTreeMap::new())
+    }
+}
+
+impl StateStore {
+    /// Opens the store persisted at the given path, which is created on the first write.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the existing state file can't be read or parsed.
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let mut stores = OPEN_STORES.lock().expect("poisoned lock");
+        stores.retain(|_, store| store.strong_count() > 0);
+        if let Some(store) = stores.get(&path).and_then(Weak::upgrade) {
+            return Ok(Self(store));
+        }
+
+        let entries = match fs::read(&path) {
+            Ok(contents) => serde_json::from_slice(&contents)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(error) => return Err(error),
+        };
+        let store = Self::with_entries(Some(path.clone()), entries);
+        stores.insert(path, Arc::downgrade(&store.0));
+        spawn_persister(Arc::downgrade(&store.0))?;
+        Ok(store)
+    }
+
+    fn with_entries(path: Option<PathBuf>, entries: BTreeMap<String, Entry>) -> Self {
+        Self(Arc::new(Mutex::new(Inner {
+            path,
+            entries,
+            dirty: false,
+        })))
+    }
+
+    /// Returns the value of the key, unless it is missing or expired.
+    pub fn get(&self, key: &str) -> Option<Value> {
+        let inner = self.0.lock().expect("poisoned lock");
+        inner
+            .entries
+            .get(key)
+            .filter(|entry| !entry.is_expired(unix_now()))
+            .map(|entry| entry.value.clone())
+    }
+
+    /// Sets the value of the key, which expires after the time to live if there is one.
+    pub fn set(&self, key: String, value: Value, ttl: Option<Duration>) {
+        let expires_at = ttl.map(|ttl| unix_now().saturating_add(ttl.as_secs() as i64));
+        let mut inner = self.0.lock().expect("poisoned lock");
+        inner.entries.insert(key, Entry { value, expires_at });
+        inner.dirty = true;
+    }
+}
+
+/// Spawns the thread writing the changes of the store to its state file, until the store is
+/// dropped. The entries are copied out of the store, so that events aren't held up while the file
+/// is written.
+fn spawn_persister(store: Weak<Mutex<Inner>>) -> io::Result<()> {
+    thread::Builder::new()
+        .name("vrl-state-persister".to_owned())
+        .spawn(move || loop {
+            thread::sleep(PERSIST_INTERVAL);
+            // The store is kept alive while it's written, so that it isn't also written on drop.
+            let store = match store.upgrade() {
+                Some(store) => store,
+                None => break,
+            };
+            let changes = store.lock().expect("poisoned lock").take_changes();
+            if let Some((path, entries)) = changes {
+                persist(&path, &entries);
+            }
+        })
+        .map(|_| ())
+}
+
+fn persist(path: &Path, entries: &BTreeMap<String, Entry>) {
+    if let Err(error) = write_atomically(path, entries) {
+        tracing::error!(
+            message = "Failed to persist VRL state.",
+            path = ?path,
+            %error,
+            internal_log_rate_secs = 10
+        );
+    }
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |now| now.as_secs() as i64)
+}
+
+/// Writes the entries to a temporary file which replaces the state file, so that a crash while
+/// writing doesn't leave a truncated state file behind.
+fn write_atomically(path: &Path, entries: &BTreeMap<String, Entry>) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let contents = serde_json::to_vec(entries)?;
+    let temporary = path.with_extension("tmp");
+    let mut file = fs::File::create(&temporary)?;
+    file.write_all(&contents)?;
+    file.sync_all()?;
+    fs::rename(&temporary, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path() -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        std::env::temp_dir()
+            .join(format!("vrl-state-{}", nanos))
+            .join("state.json")
+    }
+
+    #[test]
+    fn gets_and_sets() {
+        let store = StateStore::default();
+        assert_eq!(store.get("count"), None);
+
+        store.set("count".to_owned(), 1.into(), None);
+        assert_eq!(store.get("count"), Some(1.into()));
+    }
+
+    #[test]
+    fn expires_entries() {
+        let store = StateStore::default();
+        store.set("count".to_owned(), 1.into(), Some(Duration::ZERO));
+        assert_eq!(store.get("count"), None);
+    }
+
+    #[test]
+    fn persists_entries() {
+        let path = temp_path();
+        let store = StateStore::open(&path).unwrap();
+        store.set("count".to_owned(), 1.into(), None);
+        store.set("expired".to_owned(), 1.into(), Some(Duration::ZERO));
+
+        // Another transform using the same file shares the store while it's open.
+        let shared = StateStore::open(&path).unwrap();
+        assert_eq!(shared.get("count"), Some(1.into()));
+
+        drop(store);
+        drop(shared);
+        let entries: BTreeMap<String, Entry> =
+            serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        assert_eq!(entries.keys().collect::<Vec<_>>(), vec!["count"]);
+
+        let reopened = StateStore::open(&path).unwrap();
+        assert_eq!(reopened.get("count"), Some(1.into()));
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn persists_entries_in_background() {
+        let path = temp_path();
+        let store = StateStore::open(&path).unwrap();
+        store.set("count".to_owned(), 1.into(), None);
+        assert!(!path.exists());
+
+        thread::sleep(PERSIST_INTERVAL * 3);
+        let entries: BTreeMap<String, Entry> =
+            serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        assert_eq!(entries.keys().collect::<Vec<_>>(), vec!["count"]);
+
+        drop(store);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
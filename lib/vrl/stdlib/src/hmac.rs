@@ -0,0 +1,184 @@
+use ::hmac::Mac;
+use ::value::Value;
+use sha_2::{Sha224, Sha256, Sha384, Sha512};
+use vrl::prelude::*;
+
+macro_rules! hmac {
+    ($hash:ty, $value:expr, $key:expr) => {{
+        let mut mac =
+            ::hmac::Hmac::<$hash>::new_from_slice($key).expect("HMAC takes keys of any size");
+        mac.update($value);
+        mac.finalize().into_bytes().to_vec()
+    }};
+}
+
+/// Returns the HMAC of the value, the algorithm being the name of the SHA-2 variant.
+pub(crate) fn hmac_digest(value: &[u8], key: &[u8], algorithm: &[u8]) -> Vec<u8> {
+    match algorithm {
+        b"SHA-224" => hmac!(Sha224, value, key),
+        b"SHA-256" => hmac!(Sha256, value, key),
+        b"SHA-384" => hmac!(Sha384, value, key),
+        b"SHA-512" => hmac!(Sha512, value, key),
+        _ => unreachable!("enum invariant"),
+    }
+}
+
+/// Compares the signatures in a time independent of their contents, so that comparing them
+/// doesn't reveal how much of a forged signature is valid.
+pub(crate) fn signatures_eq(left: &[u8], right: &[u8]) -> bool {
+    left.len() == right.len()
+        && left
+            .iter()
+            .zip(right)
+            .fold(0, |difference, (left, right)| difference | (left ^ right))
+            == 0
+}
+
+fn hmac(value: Value, key: Value, algorithm: &Bytes) -> Resolved {
+    let value = value.try_bytes()?;
+    let key = key.try_bytes()?;
+    Ok(hex::encode(hmac_digest(&value, &key, algorithm)).into())
+}
+
+pub(crate) fn algorithms() -> Vec<Value> {
+    vec![
+        value!("SHA-224"),
+        value!("SHA-256"),
+        value!("SHA-384"),
+        value!("SHA-512"),
+    ]
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Hmac;
+
+impl Function for Hmac {
+    fn identifier(&self) -> &'static str {
+        "hmac"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "key",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "algorithm",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "default algorithm",
+                source: r#"hmac("foo", "secret")"#,
+                result: Ok("773ba44693c7553d6ee20f61ea5d2757a9a4f4a44d2841ae4e95b52e4cd62db4"),
+            },
+            Example {
+                title: "custom algorithm",
+                source: r#"hmac("foo", "secret", "SHA-224")"#,
+                result: Ok("21f62f59e04ee0d50b3546230207af9d2bf36ce2075eaa2dc50c0b37"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let key = arguments.required("key");
+        let algorithm = arguments
+            .optional_enum("algorithm", &algorithms())?
+            .unwrap_or_else(|| value!("SHA-256"))
+            .try_bytes()
+            .expect("algorithm not bytes");
+
+        Ok(Box::new(HmacFn {
+            value,
+            key,
+            algorithm,
+        }))
+    }
+
+    fn compile_argument(
+        &self,
+        _args: &[(&'static str, Option<FunctionArgument>)],
+        _ctx: &mut FunctionCompileContext,
+        name: &str,
+        expr: Option<&expression::Expr>,
+    ) -> CompiledArgument {
+        match (name, expr) {
+            ("algorithm", Some(expr)) => {
+                let algorithm = expr
+                    .as_enum("algorithm", algorithms())?
+                    .try_bytes()
+                    .expect("algorithm not bytes");
+
+                Ok(Some(Box::new(algorithm) as _))
+            }
+            ("algorithm", None) => Ok(Some(Box::new(Bytes::from("SHA-256")) as _)),
+            _ => Ok(None),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct HmacFn {
+    value: Box<dyn Expression>,
+    key: Box<dyn Expression>,
+    algorithm: Bytes,
+}
+
+impl Expression for HmacFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let key = self.key.resolve(ctx)?;
+
+        hmac(value, key, &self.algorithm)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        hmac => Hmac;
+
+        hmac {
+            args: func_args![value: "foo", key: "secret"],
+            want: Ok("773ba44693c7553d6ee20f61ea5d2757a9a4f4a44d2841ae4e95b52e4cd62db4"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        hmac_512 {
+            args: func_args![value: "foo", key: "secret", algorithm: "SHA-512"],
+            want: Ok("82df7103de8d82de45e01c45fe642b5d13c6c2b47decafebc009431c665c6fa5f3d1af4e978ea1bde91426622073ebeac61a3461efd467e0971c788bc8ebdbbe"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+
+    #[test]
+    fn compares_signatures() {
+        assert!(signatures_eq(b"signature", b"signature"));
+        assert!(!signatures_eq(b"signature", b"signaturf"));
+        assert!(!signatures_eq(b"signature", b"sig"));
+    }
+}
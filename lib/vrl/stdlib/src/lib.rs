@@ -94,6 +94,8 @@ mod get;
 mod get_env_var;
 #[cfg(feature = "get_hostname")]
 mod get_hostname;
+#[cfg(feature = "hmac")]
+mod hmac;
 #[cfg(feature = "includes")]
 mod includes;
 #[cfg(feature = "integer")]
@@ -196,6 +198,8 @@ mod parse_groks;
 mod parse_int;
 #[cfg(feature = "parse_json")]
 mod parse_json;
+#[cfg(feature = "parse_jwt")]
+mod parse_jwt;
 #[cfg(feature = "parse_key_value")]
 mod parse_key_value;
 #[cfg(feature = "parse_klog")]
@@ -248,6 +252,8 @@ mod sha1;
 mod sha2;
 #[cfg(feature = "sha3")]
 mod sha3;
+#[cfg(feature = "sign_jwt")]
+mod sign_jwt;
 #[cfg(feature = "slice")]
 mod slice;
 #[cfg(feature = "split")]
@@ -302,6 +308,8 @@ mod unnest;
 mod upcase;
 #[cfg(feature = "uuid_v4")]
 mod uuid_v4;
+#[cfg(feature = "verify_hmac")]
+mod verify_hmac;
 
 // -----------------------------------------------------------------------------
 
@@ -461,6 +469,8 @@ pub use parse_groks::ParseGroks;
 pub use parse_int::ParseInt;
 #[cfg(feature = "parse_json")]
 pub use parse_json::ParseJson;
+#[cfg(feature = "parse_jwt")]
+pub use parse_jwt::ParseJwt;
 #[cfg(feature = "parse_key_value")]
 pub use parse_key_value::ParseKeyValue;
 #[cfg(feature = "parse_klog")]
@@ -513,6 +523,8 @@ pub use set::Set;
 pub use sha2::Sha2;
 #[cfg(feature = "sha3")]
 pub use sha3::Sha3;
+#[cfg(feature = "sign_jwt")]
+pub use sign_jwt::SignJwt;
 #[cfg(feature = "slice")]
 pub use slice::Slice;
 #[cfg(feature = "split")]
@@ -567,9 +579,13 @@ pub use unnest::Unnest;
 pub use upcase::Upcase;
 #[cfg(feature = "uuid_v4")]
 pub use uuid_v4::UuidV4;
+#[cfg(feature = "verify_hmac")]
+pub use verify_hmac::VerifyHmac;
 
 #[cfg(feature = "array")]
 pub use crate::array::Array;
+#[cfg(feature = "hmac")]
+pub use crate::hmac::Hmac;
 #[cfg(feature = "md5")]
 pub use crate::md5::Md5;
 #[cfg(feature = "sha1")]
@@ -644,6 +660,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(GetEnvVar),
         #[cfg(feature = "get_hostname")]
         Box::new(GetHostname),
+        #[cfg(feature = "hmac")]
+        Box::new(Hmac),
         #[cfg(feature = "includes")]
         Box::new(Includes),
         #[cfg(feature = "integer")]
@@ -742,6 +760,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ParseInt),
         #[cfg(feature = "parse_json")]
         Box::new(ParseJson),
+        #[cfg(feature = "parse_jwt")]
+        Box::new(ParseJwt),
         #[cfg(feature = "parse_key_value")]
         Box::new(ParseKeyValue),
         #[cfg(feature = "parse_klog")]
@@ -794,6 +814,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Sha2),
         #[cfg(feature = "sha3")]
         Box::new(Sha3),
+        #[cfg(feature = "sign_jwt")]
+        Box::new(SignJwt),
         #[cfg(feature = "slice")]
         Box::new(Slice),
         #[cfg(feature = "split")]
@@ -848,5 +870,7 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Upcase),
         #[cfg(feature = "uuid_v4")]
         Box::new(UuidV4),
+        #[cfg(feature = "verify_hmac")]
+        Box::new(VerifyHmac),
     ]
 }
@@ -0,0 +1,165 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+use crate::hmac::{algorithms, hmac_digest, signatures_eq};
+
+fn verify_hmac(value: Value, key: Value, signature: Value, algorithm: &Bytes) -> Resolved {
+    let value = value.try_bytes()?;
+    let key = key.try_bytes()?;
+    let signature = signature.try_bytes()?;
+    let valid = match hex::decode(&signature) {
+        Ok(signature) => signatures_eq(&hmac_digest(&value, &key, algorithm), &signature),
+        Err(_) => false,
+    };
+    Ok(valid.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct VerifyHmac;
+
+impl Function for VerifyHmac {
+    fn identifier(&self) -> &'static str {
+        "verify_hmac"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "key",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "signature",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "algorithm",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "valid signature",
+                source: r#"verify_hmac("foo", "secret", "773ba44693c7553d6ee20f61ea5d2757a9a4f4a44d2841ae4e95b52e4cd62db4")"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "invalid signature",
+                source: r#"verify_hmac("foo", "secret", "773ba44693c7553d6ee20f61ea5d2757a9a4f4a44d2841ae4e95b52e4cd62db4", "SHA-512")"#,
+                result: Ok("false"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let key = arguments.required("key");
+        let signature = arguments.required("signature");
+        let algorithm = arguments
+            .optional_enum("algorithm", &algorithms())?
+            .unwrap_or_else(|| value!("SHA-256"))
+            .try_bytes()
+            .expect("algorithm not bytes");
+
+        Ok(Box::new(VerifyHmacFn {
+            value,
+            key,
+            signature,
+            algorithm,
+        }))
+    }
+
+    fn compile_argument(
+        &self,
+        _args: &[(&'static str, Option<FunctionArgument>)],
+        _ctx: &mut FunctionCompileContext,
+        name: &str,
+        expr: Option<&expression::Expr>,
+    ) -> CompiledArgument {
+        match (name, expr) {
+            ("algorithm", Some(expr)) => {
+                let algorithm = expr
+                    .as_enum("algorithm", algorithms())?
+                    .try_bytes()
+                    .expect("algorithm not bytes");
+
+                Ok(Some(Box::new(algorithm) as _))
+            }
+            ("algorithm", None) => Ok(Some(Box::new(Bytes::from("SHA-256")) as _)),
+            _ => Ok(None),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct VerifyHmacFn {
+    value: Box<dyn Expression>,
+    key: Box<dyn Expression>,
+    signature: Box<dyn Expression>,
+    algorithm: Bytes,
+}
+
+impl Expression for VerifyHmacFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let key = self.key.resolve(ctx)?;
+        let signature = self.signature.resolve(ctx)?;
+
+        verify_hmac(value, key, signature, &self.algorithm)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::boolean().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        verify_hmac => VerifyHmac;
+
+        valid {
+            args: func_args![
+                value: "foo",
+                key: "secret",
+                signature: "773BA44693C7553D6EE20F61EA5D2757A9A4F4A44D2841AE4E95B52E4CD62DB4"
+            ],
+            want: Ok(true),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        wrong_key {
+            args: func_args![
+                value: "foo",
+                key: "other",
+                signature: "773ba44693c7553d6ee20f61ea5d2757a9a4f4a44d2841ae4e95b52e4cd62db4"
+            ],
+            want: Ok(false),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        not_hex {
+            args: func_args![value: "foo", key: "secret", signature: "not a signature"],
+            want: Ok(false),
+            tdef: TypeDef::boolean().infallible(),
+        }
+    ];
+}
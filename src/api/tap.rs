@@ -624,7 +624,11 @@ mod tests {
                     name: None,
                     namespace: None,
                     tags: None,
+                    value_expression: None,
+                    tags_expression: None,
                 })],
+                all_metrics: false,
+                host_tag: None,
             },
         );
         config.add_sink(
@@ -183,7 +183,11 @@ async fn integration_test_source_metric() {
                 name: None,
                 namespace: None,
                 tags: None,
+                value_expression: None,
+                tags_expression: None,
             })],
+            all_metrics: false,
+            host_tag: None,
         },
     );
     config.add_sink(
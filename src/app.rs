@@ -168,15 +168,6 @@ impl Application {
 
                 let config_paths = config::process_paths(&config_paths).ok_or(exitcode::CONFIG)?;
 
-                if watch_config {
-                    // Start listening for config changes immediately.
-                    config::watcher::spawn_thread(config_paths.iter().map(Into::into), None)
-                        .map_err(|error| {
-                            error!(message = "Unable to start config watcher.", %error);
-                            exitcode::CONFIG
-                        })?;
-                }
-
                 info!(
                     message = "Loading configs.",
                     paths = ?config_paths.iter().map(<&PathBuf>::from).collect::<Vec<_>>()
@@ -192,6 +183,23 @@ impl Application {
                 .await
                 .map_err(handle_config_errors)?;
 
+                if watch_config {
+                    // Start listening for changes to the config and to the files of the
+                    // enrichment tables, which are reloaded along with the config.
+                    let table_paths = config.enrichment_table_files();
+                    config::watcher::spawn_thread(
+                        config_paths
+                            .iter()
+                            .map(<&PathBuf>::from)
+                            .chain(&table_paths),
+                        None,
+                    )
+                    .map_err(|error| {
+                        error!(message = "Unable to start config watcher.", %error);
+                        exitcode::CONFIG
+                    })?;
+                }
+
                 if !config.healthchecks.enabled {
                     info!("Health checks are disabled.");
                 }
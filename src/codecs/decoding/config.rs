@@ -1,4 +1,4 @@
-use codecs::decoding::{DeserializerConfig, FramingConfig};
+use codecs::decoding::{DecodingErrorMode, DeserializerConfig, FramingConfig};
 use serde::{Deserialize, Serialize};
 use vector_core::config::LogNamespace;
 
@@ -13,6 +13,8 @@ pub struct DecodingConfig {
     decoding: DeserializerConfig,
     /// The namespace used when decoding.
     log_namespace: LogNamespace,
+    /// How frames that fail to be deserialized are handled.
+    on_error: DecodingErrorMode,
 }
 
 impl DecodingConfig {
@@ -27,17 +29,26 @@ impl DecodingConfig {
             framing,
             decoding,
             log_namespace,
+            on_error: DecodingErrorMode::Drop,
         }
     }
 
+    /// Sets how frames that fail to be deserialized are handled.
+    pub const fn with_error_mode(mut self, on_error: DecodingErrorMode) -> Self {
+        self.on_error = on_error;
+        self
+    }
+
     /// Builds a `Decoder` from the provided configuration.
-    pub fn build(&self) -> Decoder {
+    pub fn build(&self) -> crate::Result<Decoder> {
         // Build the framer.
-        let framer = self.framing.build();
+        let framer = self.framing.build()?;
 
         // Build the deserializer.
-        let deserializer = self.decoding.build();
+        let deserializer = self.decoding.build()?;
 
-        Decoder::new(framer, deserializer).with_log_namespace(self.log_namespace)
+        Ok(Decoder::new(framer, deserializer)
+            .with_log_namespace(self.log_namespace)
+            .with_error_mode(self.on_error))
     }
 }
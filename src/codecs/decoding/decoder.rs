@@ -1,13 +1,15 @@
 use bytes::{Bytes, BytesMut};
 use codecs::decoding::{
-    format::Deserializer as _, BoxedFramingError, BytesDeserializer, Deserializer, Error, Framer,
-    NewlineDelimitedDecoder,
+    format::Deserializer as _, BoxedFramingError, BytesDeserializer, DecodingErrorMode,
+    Deserializer, Error, Framer, NewlineDelimitedDecoder,
 };
-use smallvec::SmallVec;
+use lookup::path;
+use smallvec::{smallvec, SmallVec};
 use vector_core::config::LogNamespace;
 
 use crate::{
-    event::Event,
+    config::log_schema,
+    event::{Event, LogEvent},
     internal_events::{DecoderDeserializeFailed, DecoderFramingFailed},
 };
 
@@ -18,6 +20,7 @@ pub struct Decoder {
     framer: Framer,
     deserializer: Deserializer,
     log_namespace: LogNamespace,
+    error_mode: DecodingErrorMode,
 }
 
 impl Default for Decoder {
@@ -26,6 +29,7 @@ impl Default for Decoder {
             framer: Framer::NewlineDelimited(NewlineDelimitedDecoder::new()),
             deserializer: Deserializer::Bytes(BytesDeserializer::new()),
             log_namespace: LogNamespace::Legacy,
+            error_mode: DecodingErrorMode::Drop,
         }
     }
 }
@@ -39,6 +43,7 @@ impl Decoder {
             framer,
             deserializer,
             log_namespace: LogNamespace::Legacy,
+            error_mode: DecodingErrorMode::Drop,
         }
     }
 
@@ -48,10 +53,31 @@ impl Decoder {
         self
     }
 
+    /// Sets how frames that fail to be deserialized are handled.
+    pub const fn with_error_mode(mut self, error_mode: DecodingErrorMode) -> Self {
+        self.error_mode = error_mode;
+        self
+    }
+
+    /// Resolves the data from external services needed to parse the frames of the message, such
+    /// as the Avro schemas they reference, so that decoding the message doesn't wait for them.
+    pub async fn prefetch(&self, message: &[u8]) {
+        if !self.deserializer.needs_prefetch() {
+            return;
+        }
+
+        let mut framer = self.framer.clone();
+        let mut buf = BytesMut::from(message);
+        while let Ok(Some(frame)) = tokio_util::codec::Decoder::decode_eof(&mut framer, &mut buf) {
+            self.deserializer.prefetch(&frame).await;
+        }
+    }
+
     /// Handles the framing result and parses it into a structured event, if
     /// possible.
     ///
-    /// Emits logs if either framing or parsing failed.
+    /// Emits logs if either framing or parsing failed. Frames that failed to be parsed are
+    /// emitted as raw events if configured by the error mode.
     fn handle_framing_result(
         &mut self,
         frame: Result<Option<Bytes>, BoxedFramingError>,
@@ -68,13 +94,43 @@ impl Decoder {
 
         let byte_size = frame.len();
         // Parse structured events from the byte frame.
-        self.deserializer
-            .parse(frame, self.log_namespace)
-            .map(|events| Some((events, byte_size)))
-            .map_err(|error| {
+        match self.deserializer.parse(frame.clone(), self.log_namespace) {
+            Ok(events) => Ok(Some((events, byte_size))),
+            Err(error) => {
                 emit!(DecoderDeserializeFailed { error: &error });
-                Error::ParsingError(error)
-            })
+                match self.error_mode {
+                    DecodingErrorMode::Drop => Err(Error::ParsingError(error)),
+                    DecodingErrorMode::Raw => Ok(Some((self.raw_event(frame, None), byte_size))),
+                    DecodingErrorMode::Route => {
+                        Ok(Some((self.raw_event(frame, Some(&error)), byte_size)))
+                    }
+                }
+            }
+        }
+    }
+
+    /// Creates a raw text event from a frame that failed to be parsed, optionally tagged with the
+    /// parsing error.
+    fn raw_event(&self, frame: Bytes, error: Option<&crate::Error>) -> SmallVec<[Event; 1]> {
+        let mut log = match self.log_namespace {
+            LogNamespace::Vector => self.log_namespace.new_log_from_data(frame),
+            LogNamespace::Legacy => {
+                let mut log = LogEvent::default();
+                log.insert(log_schema().message_key(), frame);
+                log
+            }
+        };
+
+        if let Some(error) = error {
+            self.log_namespace.insert_vector_metadata(
+                &mut log,
+                path!("decoding_error"),
+                path!("decoding_error"),
+                error.to_string(),
+            );
+        }
+
+        smallvec![log.into()]
     }
 }
 
@@ -1,7 +1,7 @@
 use crate::codecs::Transformer;
 use codecs::{
     encoding::{Framer, FramingConfig, Serializer, SerializerConfig},
-    CharacterDelimitedEncoder, LengthDelimitedEncoder, NewlineDelimitedEncoder,
+    BytesEncoder, CharacterDelimitedEncoder, LengthDelimitedEncoder, NewlineDelimitedEncoder,
 };
 use serde::{Deserialize, Serialize};
 use vector_config::configurable_component;
@@ -105,9 +105,17 @@ impl EncodingConfigWithFraming {
             (None, Serializer::Avro(_) | Serializer::Native(_)) => {
                 LengthDelimitedEncoder::new().into()
             }
+            // MessagePack values are self-delimiting, so they can be concatenated as-is.
+            #[cfg(feature = "codecs-msgpack")]
+            (None, Serializer::Msgpack(_)) => BytesEncoder::new().into(),
+            (None, Serializer::Gelf(_)) => match sink_type {
+                // Graylog's TCP GELF inputs expect each message to be terminated by a null byte.
+                SinkType::StreamBased => CharacterDelimitedEncoder::new(0).into(),
+                SinkType::MessageBased => NewlineDelimitedEncoder::new().into(),
+            },
             (
                 None,
-                Serializer::Gelf(_)
+                Serializer::Cef(_)
                 | Serializer::Logfmt(_)
                 | Serializer::NativeJson(_)
                 | Serializer::RawMessage(_)
@@ -233,4 +241,17 @@ mod test {
         );
         assert_eq!(transformer.timestamp_format(), &Some(TimestampFormat::Unix));
     }
+
+    #[test]
+    fn gelf_defaults_to_null_delimited_framing_for_stream_sinks() {
+        let encoding =
+            EncodingConfigWithFraming::new(None, SerializerConfig::Gelf, Default::default());
+        let (framer, _) = encoding.build(SinkType::StreamBased).unwrap();
+        assert!(
+            matches!(framer, Framer::CharacterDelimited(ref encoder) if encoder.delimiter == 0)
+        );
+
+        let (framer, _) = encoding.build(SinkType::MessageBased).unwrap();
+        assert!(matches!(framer, Framer::NewlineDelimited(_)));
+    }
 }
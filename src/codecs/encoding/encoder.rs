@@ -114,8 +114,11 @@ impl Encoder<Framer> {
                 Framer::CharacterDelimited(CharacterDelimitedEncoder { delimiter: b',' }),
             ) => "application/json",
             (Serializer::Native(_), _) => "application/octet-stream",
+            #[cfg(feature = "codecs-msgpack")]
+            (Serializer::Msgpack(_), _) => "application/msgpack",
             (
                 Serializer::Avro(_)
+                | Serializer::Cef(_)
                 | Serializer::Gelf(_)
                 | Serializer::Json(_)
                 | Serializer::Logfmt(_)
@@ -18,3 +18,15 @@ pub(crate) mod sqs;
 
 #[cfg(any(feature = "sources-aws_s3", feature = "sinks-aws_s3"))]
 pub(crate) mod s3;
+
+#[cfg(feature = "sources-aws_kinesis_streams")]
+pub(crate) mod dynamodb;
+
+#[cfg(any(
+    feature = "sources-aws_kinesis_streams",
+    feature = "sinks-aws_kinesis_streams"
+))]
+pub(crate) mod kinesis;
+
+#[cfg(any(feature = "sources-websocket_client", feature = "sinks-websocket"))]
+pub(crate) mod websocket;
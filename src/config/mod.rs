@@ -3,6 +3,7 @@ use std::{
     fmt::{self, Display, Formatter},
     hash::Hash,
     net::SocketAddr,
+    num::NonZeroU64,
     path::PathBuf,
 };
 
@@ -116,6 +117,17 @@ impl Config {
         Default::default()
     }
 
+    /// Returns the files holding the data of the enrichment tables.
+    ///
+    /// These are only watched along with the configuration files when running with
+    /// `--watch-config`, so changes to them don't trigger a reload otherwise.
+    pub fn enrichment_table_files(&self) -> Vec<PathBuf> {
+        self.enrichment_tables
+            .values()
+            .flat_map(|table| table.inner.files_to_watch())
+            .collect()
+    }
+
     pub fn sources(&self) -> impl Iterator<Item = (&ComponentKey, &SourceOuter)> {
         self.sources.iter()
     }
@@ -260,13 +272,21 @@ macro_rules! impl_generate_config_from_default {
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct EnrichmentTableOuter {
+    /// The interval at which the table is checked for changes to its data, and reloaded without
+    /// reloading the configuration if it [needs to](enrichment::Table::needs_reload).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reload_interval_secs: Option<NonZeroU64>,
+
     #[serde(flatten)]
     pub inner: Box<dyn EnrichmentTableConfig>,
 }
 
 impl EnrichmentTableOuter {
     pub fn new(inner: Box<dyn EnrichmentTableConfig>) -> Self {
-        EnrichmentTableOuter { inner }
+        EnrichmentTableOuter {
+            reload_interval_secs: None,
+            inner,
+        }
     }
 }
 
@@ -277,6 +297,15 @@ pub trait EnrichmentTableConfig: core::fmt::Debug + Send + Sync + dyn_clone::Dyn
         &self,
         globals: &GlobalOptions,
     ) -> crate::Result<Box<dyn enrichment::Table + Send + Sync>>;
+
+    /// Returns the files holding the data of the table.
+    ///
+    /// When watching the configuration with `--watch-config`, changes to these files trigger a
+    /// reload, which reloads the table if it [needs to](enrichment::Table::needs_reload). Without
+    /// it, the table is only reloaded along with the configuration, such as on `SIGHUP`.
+    fn files_to_watch(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
 }
 
 pub type EnrichmentTableDescription = ComponentDescription<Box<dyn EnrichmentTableConfig>>;
@@ -174,6 +174,37 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "enrichment-tables-file")]
+    #[tokio::test]
+    async fn enrichment_table_file_update() {
+        trace_init();
+
+        let delay = Duration::from_secs(3);
+        let table_path = temp_file();
+        let mut file = File::create(&table_path).unwrap();
+
+        let table: crate::enrichment_tables::file::FileConfig = toml::from_str(&format!(
+            r#"
+            file.path = {:?}
+            file.encoding.type = "csv"
+            "#,
+            table_path
+        ))
+        .unwrap();
+        let mut builder = crate::config::ConfigBuilder::default();
+        builder.add_source("in", crate::test_util::mock::basic_source().1);
+        builder.add_sink("out", &["in"], crate::test_util::mock::basic_sink(1).1);
+        builder.add_enrichment_table("table", table);
+        let config = builder.build().unwrap();
+
+        // Only the file of the table is watched, so the reload is triggered by the table.
+        spawn_thread(&config.enrichment_table_files(), delay).unwrap();
+
+        if !test(&mut file, delay * 5).await {
+            panic!("Test timed out");
+        }
+    }
+
     #[tokio::test]
     async fn sym_file_update() {
         trace_init();
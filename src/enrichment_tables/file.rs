@@ -173,6 +173,10 @@ impl EnrichmentTableConfig for FileConfig {
 
         Ok(Box::new(File::new(self.clone(), modified, data, headers)))
     }
+
+    fn files_to_watch(&self) -> Vec<PathBuf> {
+        vec![self.file.path.clone()]
+    }
 }
 
 inventory::submit! {
@@ -505,6 +509,10 @@ impl Table for File {
             .and_then(|metadata| metadata.modified()),
             Ok(modified) if modified > self.last_modified)
     }
+
+    fn rows(&self) -> Option<usize> {
+        Some(self.data.len())
+    }
 }
 
 impl std::fmt::Debug for File {
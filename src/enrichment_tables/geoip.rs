@@ -1,4 +1,4 @@
-use std::{collections::BTreeMap, fs, net::IpAddr, sync::Arc, time::SystemTime};
+use std::{collections::BTreeMap, fs, net::IpAddr, path::PathBuf, sync::Arc, time::SystemTime};
 
 use enrichment::{Case, Condition, IndexHandle, Table};
 use maxminddb::{
@@ -55,6 +55,10 @@ impl EnrichmentTableConfig for GeoipConfig {
     ) -> crate::Result<Box<dyn Table + Send + Sync>> {
         Ok(Box::new(Geoip::new(self.clone())?))
     }
+
+    fn files_to_watch(&self) -> Vec<PathBuf> {
+        vec![PathBuf::from(&self.path)]
+    }
 }
 
 #[derive(Clone)]
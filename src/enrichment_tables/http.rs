@@ -0,0 +1,342 @@
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use enrichment::{Case, Condition, IndexHandle, Table};
+use http::{Request, StatusCode};
+use hyper::Body;
+use indexmap::IndexMap;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use serde::{Deserialize, Serialize};
+use value::Value;
+
+use super::remote::{
+    default_timeout_secs, json_row, select_fields, single_row, CacheConfig, KeyTemplate,
+    LookupCache, LookupRuntime, Row,
+};
+use crate::{
+    config::{EnrichmentTableConfig, EnrichmentTableDescription, GenerateConfig, ProxyConfig},
+    http::{Auth, HttpClient},
+    tls::{TlsConfig, TlsSettings},
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct HttpTableConfig {
+    /// The URL of the records, with the searched fields as `{{ field }}` placeholders.
+    pub url: String,
+    #[serde(default)]
+    pub headers: IndexMap<String, String>,
+    pub auth: Option<Auth>,
+    pub tls: Option<TlsConfig>,
+    #[serde(default)]
+    pub proxy: ProxyConfig,
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    #[serde(default)]
+    pub cache: CacheConfig,
+}
+
+impl GenerateConfig for HttpTableConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            url: "http://inventory.local/hosts/{{ host }}".to_string(),
+            headers: IndexMap::new(),
+            auth: None,
+            tls: None,
+            proxy: ProxyConfig::default(),
+            timeout_secs: default_timeout_secs(),
+            cache: CacheConfig::default(),
+        })
+        .unwrap()
+    }
+}
+
+inventory::submit! {
+    EnrichmentTableDescription::new::<HttpTableConfig>("http")
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "http")]
+impl EnrichmentTableConfig for HttpTableConfig {
+    async fn build(
+        &self,
+        globals: &crate::config::GlobalOptions,
+    ) -> crate::Result<Box<dyn Table + Send + Sync>> {
+        let proxy = ProxyConfig::merge_with_env(&globals.proxy, &self.proxy);
+        Ok(Box::new(HttpTable::new(self, &proxy)?))
+    }
+}
+
+#[derive(Clone)]
+pub struct HttpTable {
+    config: HttpTableConfig,
+    url: KeyTemplate,
+    client: HttpClient,
+    runtime: Arc<LookupRuntime>,
+    cache: LookupCache,
+}
+
+impl HttpTable {
+    pub fn new(config: &HttpTableConfig, proxy: &ProxyConfig) -> crate::Result<Self> {
+        let tls = TlsSettings::from_options(&config.tls)?;
+
+        Ok(Self {
+            config: config.clone(),
+            url: KeyTemplate::parse(&config.url)?,
+            client: HttpClient::new(tls, proxy)?,
+            runtime: Arc::new(LookupRuntime::new("http-enrichment-table")?),
+            cache: LookupCache::new(&config.cache),
+        })
+    }
+
+    fn request(&self, url: &str) -> Result<Request<Body>, String> {
+        let mut request = Request::get(url);
+        for (name, value) in &self.config.headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        if let Some(auth) = &self.config.auth {
+            request = auth.apply_builder(request);
+        }
+        request
+            .body(Body::empty())
+            .map_err(|error| format!("Invalid request: {}", error))
+    }
+
+    /// Requests the records, blocking the calling thread until the response is received.
+    fn fetch(&self, url: &str) -> Result<Vec<Row>, String> {
+        let request = self.request(url)?;
+        let client = self.client.clone();
+        let timeout = Duration::from_secs(self.config.timeout_secs);
+        let body = self.runtime.block_on(timeout, async move {
+            let response = client
+                .send(request)
+                .await
+                .map_err(|error| format!("Request failed: {}", error))?;
+            match response.status() {
+                StatusCode::NOT_FOUND => Ok(None),
+                status if status.is_success() => hyper::body::to_bytes(response.into_body())
+                    .await
+                    .map(Some)
+                    .map_err(|error| format!("Failed to read response: {}", error)),
+                status => Err(format!("Unexpected response status: {}", status)),
+            }
+        })?;
+
+        match body {
+            None => Ok(Vec::new()),
+            Some(body) => match serde_json::from_slice(&body) {
+                Ok(serde_json::Value::Null) => Ok(Vec::new()),
+                Ok(serde_json::Value::Object(record)) => Ok(vec![json_row(record)]),
+                Ok(serde_json::Value::Array(records)) => records
+                    .into_iter()
+                    .map(|record| match record {
+                        serde_json::Value::Object(record) => Ok(json_row(record)),
+                        _ => Err("Records must be JSON objects".to_string()),
+                    })
+                    .collect(),
+                Ok(_) => Err("Records must be JSON objects".to_string()),
+                Err(error) => Err(format!("Invalid JSON response: {}", error)),
+            },
+        }
+    }
+}
+
+impl Table for HttpTable {
+    /// Search the enrichment table data with the given condition.
+    /// All conditions must match (AND).
+    ///
+    /// # Errors
+    /// Errors if no rows, or more than 1 row is found.
+    fn find_table_row<'a>(
+        &self,
+        case: Case,
+        condition: &'a [Condition<'a>],
+        select: Option<&[String]>,
+        index: Option<IndexHandle>,
+    ) -> Result<BTreeMap<String, Value>, String> {
+        single_row(self.find_table_rows(case, condition, select, index)?)
+    }
+
+    /// Search the enrichment table data with the given condition.
+    /// All conditions must match (AND).
+    /// Can return multiple matched records
+    fn find_table_rows<'a>(
+        &self,
+        _: Case,
+        condition: &'a [Condition<'a>],
+        select: Option<&[String]>,
+        _: Option<IndexHandle>,
+    ) -> Result<Vec<BTreeMap<String, Value>>, String> {
+        let url = self.url.render(condition, |value| {
+            utf8_percent_encode(value, NON_ALPHANUMERIC).into()
+        })?;
+        let rows = self.cache.get_or_fetch(url, |url| self.fetch(url))?;
+        Ok(select_fields(&rows, select))
+    }
+
+    /// Hints to the enrichment table what data is going to be searched to allow it to index the
+    /// data in advance.
+    ///
+    /// # Errors
+    /// Errors if the fields are not in the table.
+    fn add_index(&mut self, _: Case, fields: &[&str]) -> Result<IndexHandle, String> {
+        self.url.check_fields(fields).map(|_| IndexHandle(0))
+    }
+
+    /// Returns a list of the field names that are in each index
+    fn index_fields(&self) -> Vec<(Case, Vec<String>)> {
+        Vec::new()
+    }
+
+    /// Returns true if the underlying data has changed and the table needs reloading.
+    fn needs_reload(&self) -> bool {
+        false
+    }
+}
+
+impl std::fmt::Debug for HttpTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "HttpTable {}", self.config.url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use serde_json::json;
+    use warp::Filter;
+
+    use super::*;
+    use crate::test_util::{next_addr, wait_for_tcp};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<HttpTableConfig>();
+    }
+
+    fn find(table: &HttpTable, host: &str) -> Result<BTreeMap<String, Value>, String> {
+        table.find_table_row(
+            Case::Sensitive,
+            &[Condition::Equals {
+                field: "host",
+                value: host.into(),
+            }],
+            Some(&["owner".to_string()][..]),
+            None,
+        )
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn looks_up_and_caches_records() {
+        let address = next_addr();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&requests);
+        let hosts = warp::path!("hosts" / String).map(move |host: String| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            match host.as_str() {
+                "web-1" => warp::reply::with_status(
+                    warp::reply::json(&json!({"owner": "web", "rack": 4})),
+                    warp::http::StatusCode::OK,
+                ),
+                _ => warp::reply::with_status(
+                    warp::reply::json(&json!({})),
+                    warp::http::StatusCode::NOT_FOUND,
+                ),
+            }
+        });
+        tokio::spawn(warp::serve(hosts).run(address));
+        wait_for_tcp(address).await;
+
+        let config: HttpTableConfig = toml::from_str(&format!(
+            r#"url = "http://{}/hosts/{{{{ host }}}}""#,
+            address
+        ))
+        .unwrap();
+        let table = HttpTable::new(&config, &ProxyConfig::default()).unwrap();
+
+        for _ in 0..2 {
+            assert_eq!(
+                find(&table, "web-1"),
+                Ok(BTreeMap::from([("owner".to_string(), Value::from("web"))]))
+            );
+            assert_eq!(find(&table, "db-1"), Err("Row not found".to_string()));
+        }
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn does_not_cache_errors() {
+        let address = next_addr();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&requests);
+        let hosts = warp::any().map(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            warp::reply::with_status("unavailable", warp::http::StatusCode::SERVICE_UNAVAILABLE)
+        });
+        tokio::spawn(warp::serve(hosts).run(address));
+        wait_for_tcp(address).await;
+
+        let config: HttpTableConfig = toml::from_str(&format!(
+            r#"url = "http://{}/hosts/{{{{ host }}}}""#,
+            address
+        ))
+        .unwrap();
+        let table = HttpTable::new(&config, &ProxyConfig::default()).unwrap();
+
+        assert!(find(&table, "web-1").is_err());
+        assert!(find(&table, "web-1").is_err());
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn looks_up_records_concurrently() {
+        let address = next_addr();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&requests);
+        // The service runs on the runtime of the lookups, so it only responds if the blocked
+        // lookups leave the runtime free to run it.
+        let hosts = warp::path!("hosts" / String).and_then(move |host: String| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async move {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                Ok::<_, warp::Rejection>(warp::reply::json(&json!({ "owner": host })))
+            }
+        });
+        tokio::spawn(warp::serve(hosts).run(address));
+        wait_for_tcp(address).await;
+
+        let config: HttpTableConfig = toml::from_str(&format!(
+            r#"url = "http://{}/hosts/{{{{ host }}}}"
+            timeout_secs = 2"#,
+            address
+        ))
+        .unwrap();
+        let table = HttpTable::new(&config, &ProxyConfig::default()).unwrap();
+
+        let lookups = (0..8)
+            .map(|id| {
+                let table = table.clone();
+                tokio::spawn(async move { find(&table, &format!("web-{}", id)) })
+            })
+            .collect::<Vec<_>>();
+        for (id, lookup) in lookups.into_iter().enumerate() {
+            assert_eq!(
+                lookup.await.unwrap(),
+                Ok(BTreeMap::from([(
+                    "owner".to_string(),
+                    Value::from(format!("web-{}", id))
+                )]))
+            );
+        }
+        assert_eq!(requests.load(Ordering::SeqCst), 8);
+    }
+
+    #[test]
+    fn add_index_requires_url_fields() {
+        let config: HttpTableConfig =
+            toml::from_str(r#"url = "http://localhost/hosts/{{ host }}""#).unwrap();
+        let mut table = HttpTable::new(&config, &ProxyConfig::default()).unwrap();
+        assert!(table.add_index(Case::Sensitive, &["host"]).is_ok());
+        assert!(table.add_index(Case::Sensitive, &["ip"]).is_err());
+    }
+}
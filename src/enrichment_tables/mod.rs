@@ -5,3 +5,12 @@ pub mod file;
 
 #[cfg(feature = "enrichment-tables-geoip")]
 pub mod geoip;
+
+#[cfg(feature = "enrichment-tables-http")]
+pub mod http;
+
+#[cfg(feature = "enrichment-tables-redis")]
+pub mod redis;
+
+#[cfg(any(feature = "enrichment-tables-http", feature = "enrichment-tables-redis"))]
+mod remote;
@@ -0,0 +1,326 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use bytes::Bytes;
+use enrichment::{Case, Condition, IndexHandle, Table};
+use redis::aio::ConnectionManager;
+use serde::{Deserialize, Serialize};
+use value::Value;
+
+use super::remote::{
+    default_timeout_secs, json_row, select_fields, single_row, CacheConfig, KeyTemplate,
+    LookupCache, LookupRuntime, Row,
+};
+use crate::config::{EnrichmentTableConfig, EnrichmentTableDescription, GenerateConfig};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct RedisTableConfig {
+    pub url: String,
+    /// The key of the records, with the searched fields as `{{ field }}` placeholders.
+    pub key: String,
+    #[serde(default)]
+    pub data_type: DataType,
+    #[serde(default = "default_connections")]
+    pub connections: usize,
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    #[serde(default)]
+    pub cache: CacheConfig,
+}
+
+/// The type of the values of the records.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Derivative, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DataType {
+    /// Hashes, whose fields are the fields of the records.
+    #[derivative(Default)]
+    Hash,
+    /// Strings, which are the fields of JSON objects, or the `value` field of the records.
+    String,
+}
+
+const fn default_connections() -> usize {
+    4
+}
+
+impl GenerateConfig for RedisTableConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            url: "redis://127.0.0.1:6379/0".to_string(),
+            key: "users:{{ user_id }}".to_string(),
+            data_type: DataType::default(),
+            connections: default_connections(),
+            timeout_secs: default_timeout_secs(),
+            cache: CacheConfig::default(),
+        })
+        .unwrap()
+    }
+}
+
+inventory::submit! {
+    EnrichmentTableDescription::new::<RedisTableConfig>("redis")
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "redis")]
+impl EnrichmentTableConfig for RedisTableConfig {
+    async fn build(
+        &self,
+        _: &crate::config::GlobalOptions,
+    ) -> crate::Result<Box<dyn Table + Send + Sync>> {
+        Ok(Box::new(RedisTable::new(self).await?))
+    }
+}
+
+#[derive(Clone)]
+pub struct RedisTable {
+    config: RedisTableConfig,
+    key: KeyTemplate,
+    connections: Arc<Vec<ConnectionManager>>,
+    next_connection: Arc<AtomicUsize>,
+    runtime: Arc<LookupRuntime>,
+    cache: LookupCache,
+}
+
+impl RedisTable {
+    pub async fn new(config: &RedisTableConfig) -> crate::Result<Self> {
+        if config.connections == 0 {
+            return Err("`connections` must be greater than zero".into());
+        }
+        let key = KeyTemplate::parse(&config.key)?;
+        let runtime = LookupRuntime::new("redis-enrichment-table")?;
+
+        // The connections are driven by the runtime they are opened on.
+        let client = redis::Client::open(config.url.as_str())?;
+        let count = config.connections;
+        let connections = runtime
+            .spawn(async move {
+                let mut connections = Vec::with_capacity(count);
+                for _ in 0..count {
+                    connections.push(client.get_tokio_connection_manager().await?);
+                }
+                Ok::<_, redis::RedisError>(connections)
+            })
+            .await??;
+
+        Ok(Self {
+            config: config.clone(),
+            key,
+            connections: Arc::new(connections),
+            next_connection: Arc::new(AtomicUsize::new(0)),
+            runtime: Arc::new(runtime),
+            cache: LookupCache::new(&config.cache),
+        })
+    }
+
+    /// Reads the records, blocking the calling thread until the response is received.
+    fn fetch(&self, key: &str) -> Result<Vec<Row>, String> {
+        let index = self.next_connection.fetch_add(1, Ordering::Relaxed) % self.connections.len();
+        let mut connection = self.connections[index].clone();
+        let data_type = self.config.data_type;
+        let key = key.to_owned();
+        let timeout = Duration::from_secs(self.config.timeout_secs);
+
+        self.runtime.block_on(timeout, async move {
+            let row = match data_type {
+                DataType::Hash => {
+                    let fields: BTreeMap<String, Vec<u8>> = redis::cmd("HGETALL")
+                        .arg(&key)
+                        .query_async(&mut connection)
+                        .await
+                        .map_err(|error| format!("Lookup failed: {}", error))?;
+                    (!fields.is_empty()).then(|| {
+                        fields
+                            .into_iter()
+                            .map(|(field, value)| (field, Value::from(Bytes::from(value))))
+                            .collect()
+                    })
+                }
+                DataType::String => {
+                    let value: Option<Vec<u8>> = redis::cmd("GET")
+                        .arg(&key)
+                        .query_async(&mut connection)
+                        .await
+                        .map_err(|error| format!("Lookup failed: {}", error))?;
+                    value.map(string_row)
+                }
+            };
+            Ok(row.into_iter().collect())
+        })
+    }
+}
+
+/// Returns the fields of the JSON object in the string, or the string as the `value` field.
+fn string_row(value: Vec<u8>) -> Row {
+    match serde_json::from_slice(&value) {
+        Ok(serde_json::Value::Object(record)) => json_row(record),
+        _ => Row::from([("value".to_string(), Value::from(Bytes::from(value)))]),
+    }
+}
+
+impl Table for RedisTable {
+    /// Search the enrichment table data with the given condition.
+    /// All conditions must match (AND).
+    ///
+    /// # Errors
+    /// Errors if no rows, or more than 1 row is found.
+    fn find_table_row<'a>(
+        &self,
+        case: Case,
+        condition: &'a [Condition<'a>],
+        select: Option<&[String]>,
+        index: Option<IndexHandle>,
+    ) -> Result<BTreeMap<String, Value>, String> {
+        single_row(self.find_table_rows(case, condition, select, index)?)
+    }
+
+    /// Search the enrichment table data with the given condition.
+    /// All conditions must match (AND).
+    /// Can return multiple matched records
+    fn find_table_rows<'a>(
+        &self,
+        _: Case,
+        condition: &'a [Condition<'a>],
+        select: Option<&[String]>,
+        _: Option<IndexHandle>,
+    ) -> Result<Vec<BTreeMap<String, Value>>, String> {
+        let key = self.key.render(condition, |value| value.into())?;
+        let rows = self.cache.get_or_fetch(key, |key| self.fetch(key))?;
+        Ok(select_fields(&rows, select))
+    }
+
+    /// Hints to the enrichment table what data is going to be searched to allow it to index the
+    /// data in advance.
+    ///
+    /// # Errors
+    /// Errors if the fields are not in the table.
+    fn add_index(&mut self, _: Case, fields: &[&str]) -> Result<IndexHandle, String> {
+        self.key.check_fields(fields).map(|_| IndexHandle(0))
+    }
+
+    /// Returns a list of the field names that are in each index
+    fn index_fields(&self) -> Vec<(Case, Vec<String>)> {
+        Vec::new()
+    }
+
+    /// Returns true if the underlying data has changed and the table needs reloading.
+    fn needs_reload(&self) -> bool {
+        false
+    }
+}
+
+impl std::fmt::Debug for RedisTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RedisTable {}", self.config.key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<RedisTableConfig>();
+    }
+
+    #[test]
+    fn string_rows() {
+        assert_eq!(
+            string_row(br#"{"name": "Jane", "admin": true}"#.to_vec()),
+            Row::from([
+                ("admin".to_string(), Value::from(true)),
+                ("name".to_string(), Value::from("Jane")),
+            ])
+        );
+        assert_eq!(
+            string_row(b"Jane".to_vec()),
+            Row::from([("value".to_string(), Value::from("Jane"))])
+        );
+    }
+}
+
+#[cfg(feature = "redis-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use redis::AsyncCommands;
+
+    use super::*;
+    use crate::test_util::random_string;
+
+    fn redis_server() -> String {
+        std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379/0".to_owned())
+    }
+
+    fn find(table: &RedisTable, user_id: &str) -> Result<BTreeMap<String, Value>, String> {
+        table.find_table_row(
+            Case::Sensitive,
+            &[Condition::Equals {
+                field: "user_id",
+                value: user_id.into(),
+            }],
+            None,
+            None,
+        )
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn redis_table_lookups() {
+        let prefix = random_string(10);
+        let client = redis::Client::open(redis_server()).unwrap();
+        let mut conn = client.get_tokio_connection_manager().await.unwrap();
+        let _: () = conn
+            .hset_multiple(
+                format!("{}:hash:jane", prefix),
+                &[("name", "Jane"), ("team", "core")],
+            )
+            .await
+            .unwrap();
+        let _: () = conn
+            .set(format!("{}:string:jane", prefix), r#"{"name": "Jane"}"#)
+            .await
+            .unwrap();
+
+        let hash = RedisTable::new(
+            &toml::from_str(&format!(
+                "url = {:?}\nkey = \"{}:hash:{{{{ user_id }}}}\"",
+                redis_server(),
+                prefix
+            ))
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            find(&hash, "jane"),
+            Ok(BTreeMap::from([
+                ("name".to_string(), Value::from("Jane")),
+                ("team".to_string(), Value::from("core")),
+            ]))
+        );
+        assert_eq!(find(&hash, "john"), Err("Row not found".to_string()));
+
+        let string = RedisTable::new(
+            &toml::from_str(&format!(
+                "url = {:?}\nkey = \"{}:string:{{{{ user_id }}}}\"\ndata_type = \"string\"",
+                redis_server(),
+                prefix
+            ))
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            find(&string, "jane"),
+            Ok(BTreeMap::from([("name".to_string(), Value::from("Jane"))]))
+        );
+    }
+}
@@ -0,0 +1,350 @@
+//! Shared support for the enrichment tables looking up their records in remote services.
+//!
+//! The records are looked up by rendering a key template, such as a URL, with the values of the
+//! searched fields. The lookups of the tables are synchronous, so the requests are made on a
+//! runtime owned by the table, and the records are cached to limit the lookups waiting on the
+//! service.
+
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use enrichment::Condition;
+use lru::LruCache;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tokio::{runtime::Runtime, task::JoinHandle};
+use value::Value;
+
+pub(super) type Row = BTreeMap<String, Value>;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct CacheConfig {
+    /// The maximum number of lookups cached.
+    #[serde(default = "default_max_entries")]
+    pub max_entries: usize,
+    /// The time the records found are cached for.
+    #[serde(default = "default_ttl_secs")]
+    pub ttl_secs: u64,
+    /// The time the absence of records is cached for.
+    #[serde(default = "default_negative_ttl_secs")]
+    pub negative_ttl_secs: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: default_max_entries(),
+            ttl_secs: default_ttl_secs(),
+            negative_ttl_secs: default_negative_ttl_secs(),
+        }
+    }
+}
+
+const fn default_max_entries() -> usize {
+    10000
+}
+
+const fn default_ttl_secs() -> u64 {
+    300
+}
+
+const fn default_negative_ttl_secs() -> u64 {
+    60
+}
+
+pub(super) const fn default_timeout_secs() -> u64 {
+    5
+}
+
+static PLACEHOLDER: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{\{\s*([^}\s]+)\s*\}\}").unwrap());
+
+#[derive(Clone, Debug)]
+enum KeyPart {
+    Literal(String),
+    Field(String),
+}
+
+/// The key of the records, with the searched fields as `{{ field }}` placeholders.
+#[derive(Clone, Debug)]
+pub(super) struct KeyTemplate(Vec<KeyPart>);
+
+impl KeyTemplate {
+    pub(super) fn parse(template: &str) -> crate::Result<Self> {
+        let mut parts = Vec::new();
+        let mut last = 0;
+        for captures in PLACEHOLDER.captures_iter(template) {
+            let placeholder = captures.get(0).unwrap();
+            if placeholder.start() > last {
+                parts.push(KeyPart::Literal(
+                    template[last..placeholder.start()].to_owned(),
+                ));
+            }
+            parts.push(KeyPart::Field(captures[1].to_owned()));
+            last = placeholder.end();
+        }
+        if last < template.len() {
+            parts.push(KeyPart::Literal(template[last..].to_owned()));
+        }
+
+        let template = Self(parts);
+        if template.fields().next().is_none() {
+            return Err("The key must have at least one `{{ field }}` placeholder".into());
+        }
+        Ok(template)
+    }
+
+    fn fields(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().filter_map(|part| match part {
+            KeyPart::Field(field) => Some(field.as_str()),
+            KeyPart::Literal(_) => None,
+        })
+    }
+
+    /// Checks that the fields can be searched.
+    pub(super) fn check_fields(&self, fields: &[&str]) -> Result<(), String> {
+        match fields
+            .iter()
+            .find(|field| !self.fields().any(|name| name == **field))
+        {
+            Some(field) => Err(format!("Field {:?} is not in the key", field)),
+            None => Ok(()),
+        }
+    }
+
+    /// Renders the key with the values of the searched fields, encoded with `encode`.
+    pub(super) fn render<'a>(
+        &self,
+        condition: &'a [Condition<'a>],
+        encode: impl Fn(&str) -> Cow<'_, str>,
+    ) -> Result<String, String> {
+        let mut values = BTreeMap::new();
+        for condition in condition {
+            match condition {
+                Condition::Equals { field, value } if self.fields().any(|name| name == *field) => {
+                    values.insert(*field, value.to_string_lossy());
+                }
+                Condition::Equals { field, .. } => {
+                    return Err(format!("Field {:?} is not in the key", field))
+                }
+                _ => return Err("Only equality conditions are allowed".to_string()),
+            }
+        }
+
+        let mut key = String::new();
+        for part in &self.0 {
+            match part {
+                KeyPart::Literal(literal) => key.push_str(literal),
+                KeyPart::Field(field) => {
+                    let value = values
+                        .get(field.as_str())
+                        .ok_or_else(|| format!("Field {:?} must be specified", field))?;
+                    key.push_str(&encode(value));
+                }
+            }
+        }
+        Ok(key)
+    }
+}
+
+/// The runtime driving the requests of a table.
+pub(super) struct LookupRuntime(Option<Runtime>);
+
+impl LookupRuntime {
+    pub(super) fn new(name: &str) -> crate::Result<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name(name)
+            .enable_all()
+            .build()?;
+        Ok(Self(Some(runtime)))
+    }
+
+    pub(super) fn spawn<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        self.0
+            .as_ref()
+            .expect("runtime is only taken on drop")
+            .spawn(future)
+    }
+
+    /// Runs the future on the runtime, blocking the calling thread until it completes, or until
+    /// the timeout.
+    ///
+    /// When called from a worker of a multi-threaded runtime, such as the one running the
+    /// transforms, the worker hands its other tasks off to another thread while blocked. Calling
+    /// it from a current-thread runtime panics.
+    pub(super) fn block_on<F, T>(&self, timeout: Duration, future: F) -> Result<T, String>
+    where
+        F: Future<Output = Result<T, String>> + Send + 'static,
+        T: Send + 'static,
+    {
+        let handle = self.spawn(async move {
+            tokio::time::timeout(timeout, future)
+                .await
+                .map_err(|_| "Lookup timed out".to_string())?
+        });
+        tokio::task::block_in_place(|| futures::executor::block_on(handle))
+            .map_err(|error| format!("Lookup failed: {}", error))?
+    }
+}
+
+impl Drop for LookupRuntime {
+    fn drop(&mut self) {
+        // The tables can be dropped from an asynchronous context, where dropping a runtime panics.
+        if let Some(runtime) = self.0.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+struct CacheEntry {
+    rows: Arc<Vec<Row>>,
+    expires: Instant,
+}
+
+/// The records looked up recently, including the absence of records.
+#[derive(Clone)]
+pub(super) struct LookupCache {
+    entries: Arc<Mutex<LruCache<String, CacheEntry>>>,
+    ttl: Duration,
+    negative_ttl: Duration,
+}
+
+impl LookupCache {
+    pub(super) fn new(config: &CacheConfig) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(LruCache::new(config.max_entries))),
+            ttl: Duration::from_secs(config.ttl_secs),
+            negative_ttl: Duration::from_secs(config.negative_ttl_secs),
+        }
+    }
+
+    /// Returns the cached records of the key, or fetches them.
+    ///
+    /// Errors aren't cached, so that the records are fetched again on the next lookup.
+    pub(super) fn get_or_fetch(
+        &self,
+        key: String,
+        fetch: impl FnOnce(&str) -> Result<Vec<Row>, String>,
+    ) -> Result<Arc<Vec<Row>>, String> {
+        let now = Instant::now();
+        if let Some(entry) = self.entries.lock().unwrap().get(&key) {
+            if entry.expires > now {
+                return Ok(Arc::clone(&entry.rows));
+            }
+        }
+
+        let rows = Arc::new(fetch(&key)?);
+        let ttl = if rows.is_empty() {
+            self.negative_ttl
+        } else {
+            self.ttl
+        };
+        self.entries.lock().unwrap().put(
+            key,
+            CacheEntry {
+                rows: Arc::clone(&rows),
+                expires: now + ttl,
+            },
+        );
+        Ok(rows)
+    }
+}
+
+pub(super) fn select_fields(rows: &[Row], select: Option<&[String]>) -> Vec<Row> {
+    rows.iter()
+        .map(|row| match select {
+            Some(fields) => row
+                .iter()
+                .filter(|(key, _)| fields.contains(key))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+            None => row.clone(),
+        })
+        .collect()
+}
+
+pub(super) fn json_row(record: serde_json::Map<String, serde_json::Value>) -> Row {
+    record
+        .into_iter()
+        .map(|(key, value)| (key, Value::from(value)))
+        .collect()
+}
+
+/// Returns the single row found.
+pub(super) fn single_row(mut rows: Vec<Row>) -> Result<Row, String> {
+    match rows.pop() {
+        Some(row) if rows.is_empty() => Ok(row),
+        Some(_) => Err("More than 1 row found".to_string()),
+        None => Err("Row not found".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn equals<'a>(field: &'a str, value: &str) -> Condition<'a> {
+        Condition::Equals {
+            field,
+            value: value.into(),
+        }
+    }
+
+    #[test]
+    fn renders_key() {
+        let template = KeyTemplate::parse("hosts/{{ kind }}/{{id}}?full=true").unwrap();
+        let render = |condition: &[Condition]| template.render(condition, |value| value.into());
+
+        assert_eq!(
+            render(&[equals("id", "a b"), equals("kind", "web")]),
+            Ok("hosts/web/a b?full=true".to_string())
+        );
+        assert!(render(&[equals("kind", "web")]).is_err());
+        assert!(render(&[equals("other", "web")]).is_err());
+        assert!(template.check_fields(&["id", "kind"]).is_ok());
+        assert!(template.check_fields(&["other"]).is_err());
+        assert!(KeyTemplate::parse("hosts").is_err());
+    }
+
+    #[test]
+    fn caches_rows_and_absence() {
+        let cache = LookupCache::new(&CacheConfig::default());
+        let row = Row::from([("owner".to_string(), Value::from("web"))]);
+
+        let mut fetches = 0;
+        for _ in 0..2 {
+            let rows = cache
+                .get_or_fetch("web".to_string(), |_| {
+                    fetches += 1;
+                    Ok(vec![row.clone()])
+                })
+                .unwrap();
+            assert_eq!(*rows, vec![row.clone()]);
+            let rows = cache
+                .get_or_fetch("db".to_string(), |_| {
+                    fetches += 1;
+                    Ok(Vec::new())
+                })
+                .unwrap();
+            assert!(rows.is_empty());
+            assert!(cache
+                .get_or_fetch("error".to_string(), |_| {
+                    fetches += 1;
+                    Err("unavailable".to_string())
+                })
+                .is_err());
+        }
+        assert_eq!(fetches, 4);
+    }
+}
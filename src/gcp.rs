@@ -1,4 +1,7 @@
-use std::sync::{Arc, RwLock};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, RwLock,
+};
 use std::time::Duration;
 
 pub use goauth::scopes::Scope;
@@ -12,14 +15,40 @@ use hyper::header::AUTHORIZATION;
 use once_cell::sync::Lazy;
 use smpl_jwt::Jwt;
 use snafu::{ResultExt, Snafu};
-use tokio::{sync::watch, time::Instant};
+use tokio::{
+    sync::{watch, Notify},
+    time::Instant,
+};
 use vector_config::configurable_component;
 
-use crate::{config::ProxyConfig, http::HttpClient, http::HttpError};
+use crate::{
+    config::ProxyConfig,
+    http::HttpClient,
+    http::HttpError,
+    internal_events::{GcpTokenRefreshAttempt, GcpTokenRefreshFailed},
+};
+
+mod external_account;
+
+use external_account::ExternalAccount;
 
 const SERVICE_ACCOUNT_TOKEN_URL: &str =
     "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
 
+/// Number of consecutive token refresh failures after which the authenticator is reported as
+/// unhealthy.
+const MAX_TOKEN_REFRESH_FAILURES: usize = 3;
+
+/// Delay before retrying a failed token refresh, doubled on each consecutive failure.
+const TOKEN_REFRESH_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Upper bound of the delay between two token refresh retries.
+const MAX_TOKEN_REFRESH_RETRY_DELAY: Duration = Duration::from_secs(300);
+
+/// Minimum delay between two forced token refreshes, so a burst of rejected requests only
+/// triggers a single refresh.
+const MIN_FORCED_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
 pub const PUBSUB_URL: &str = "https://pubsub.googleapis.com";
 
 pub static PUBSUB_ADDRESS: Lazy<String> = Lazy::new(|| {
@@ -51,6 +80,19 @@ pub enum GcpError {
     TokenJsonFromStr { source: serde_json::Error },
     #[snafu(display("Failed to build HTTP client: {}", source))]
     BuildHttpClient { source: HttpError },
+    #[snafu(display("Failed to read GCP credentials file {:?}: {}", path, source))]
+    ReadCredentials {
+        path: String,
+        source: std::io::Error,
+    },
+    #[snafu(display("Invalid GCP external account credentials: {}", source))]
+    InvalidExternalAccount { source: serde_json::Error },
+    #[snafu(display(
+        "GCP authentication token refresh failed {} times in a row, last error: {}",
+        failures,
+        error
+    ))]
+    TokenRefreshFailing { failures: usize, error: String },
 }
 
 /// Configuration of the authentication strategy for interacting with GCP services.
@@ -77,6 +119,10 @@ pub struct GcpAuthConfig {
     ///
     /// Either an API key, or a path to a service account credentials JSON file can be specified.
     ///
+    /// External account credentials files, as used by [workload identity federation](https://cloud.google.com/iam/docs/workload-identity-federation),
+    /// are also supported, allowing Vector running on AWS, Azure, or any OIDC-capable platform to authenticate without a
+    /// service account key.
+    ///
     /// If both are unset, Vector checks the `GOOGLE_APPLICATION_CREDENTIALS` environment variable for a filename. If no
     /// filename is named, Vector will attempt to fetch an instance service account for the compute instance the program is
     /// running on. If Vector is not running on a GCE instance, then you must define eith an API key or service account
@@ -113,22 +159,57 @@ pub enum GcpAuthenticator {
 
 #[derive(Debug)]
 pub struct InnerCreds {
-    creds: Option<(Credentials, Scope)>,
+    creds: Option<(CredentialsSource, Scope)>,
     token: RwLock<Token>,
+    refresh_requested: Notify,
+    refresh_failures: AtomicUsize,
+    last_refresh_error: RwLock<Option<String>>,
+}
+
+#[derive(Debug)]
+enum CredentialsSource {
+    ServiceAccount(Credentials),
+    ExternalAccount(ExternalAccount),
+}
+
+impl CredentialsSource {
+    fn from_file(path: &str) -> crate::Result<Self> {
+        let contents = std::fs::read_to_string(path).context(ReadCredentialsSnafu { path })?;
+        let kind = serde_json::from_str::<serde_json::Value>(&contents)
+            .ok()
+            .and_then(|value| value.get("type")?.as_str().map(ToOwned::to_owned));
+
+        Ok(match kind.as_deref() {
+            Some("external_account") => Self::ExternalAccount(
+                serde_json::from_str(&contents).context(InvalidExternalAccountSnafu)?,
+            ),
+            _ => Self::ServiceAccount(
+                Credentials::from_file(path).context(InvalidCredentialsSnafu)?,
+            ),
+        })
+    }
+
+    async fn fetch_token(&self, scope: &Scope) -> crate::Result<Token> {
+        match self {
+            Self::ServiceAccount(creds) => fetch_token(creds, scope).await,
+            Self::ExternalAccount(account) => account.fetch_token(scope).await,
+        }
+    }
 }
 
 impl GcpAuthenticator {
     async fn from_file(path: &str, scope: Scope) -> crate::Result<Self> {
-        let creds = Credentials::from_file(path).context(InvalidCredentialsSnafu)?;
-        let token = RwLock::new(fetch_token(&creds, &scope).await?);
-        let creds = Some((creds, scope));
-        Ok(Self::Credentials(Arc::new(InnerCreds { creds, token })))
+        let creds = CredentialsSource::from_file(path)?;
+        let token = creds.fetch_token(&scope).await?;
+        Ok(Self::Credentials(Arc::new(InnerCreds::new(
+            Some((creds, scope)),
+            token,
+        ))))
     }
 
     async fn new_implicit() -> crate::Result<Self> {
-        let token = RwLock::new(get_token_implicit().await?);
-        let creds = None;
-        Ok(Self::Credentials(Arc::new(InnerCreds { creds, token })))
+        let token = get_token_implicit().await?;
+        Ok(Self::Credentials(Arc::new(InnerCreds::new(None, token))))
     }
 
     fn from_api_key(api_key: &str) -> crate::Result<Self> {
@@ -173,6 +254,23 @@ impl GcpAuthenticator {
         }
     }
 
+    /// Requests an immediate renewal of the token, typically after a request was rejected as
+    /// unauthorized. This is a no-op unless a token regeneration task is running.
+    pub fn force_refresh(&self) {
+        if let Self::Credentials(inner) = self {
+            inner.refresh_requested.notify_waiters();
+        }
+    }
+
+    /// Fails if the token could not be renewed several times in a row, meaning requests are
+    /// likely to be rejected until it is.
+    pub fn token_health(&self) -> Result<(), GcpError> {
+        match self {
+            Self::Credentials(inner) => inner.token_health(),
+            Self::ApiKey(_) | Self::None => Ok(()),
+        }
+    }
+
     pub fn spawn_regenerate_token(&self) -> watch::Receiver<()> {
         let (sender, receiver) = watch::channel(());
         tokio::spawn(self.clone().token_regenerator(sender));
@@ -182,19 +280,35 @@ impl GcpAuthenticator {
     async fn token_regenerator(self, sender: watch::Sender<()>) {
         match self {
             Self::Credentials(inner) => {
-                let period =
-                    Duration::from_secs(inner.token.read().unwrap().expires_in() as u64 / 2);
-                let mut interval = tokio::time::interval_at(Instant::now() + period, period);
+                let mut deadline = Instant::now() + inner.renewal_period();
+                let mut last_attempt = Instant::now();
                 loop {
-                    interval.tick().await;
+                    tokio::select! {
+                        _ = tokio::time::sleep_until(deadline) => (),
+                        _ = inner.refresh_requested.notified() => {
+                            if last_attempt.elapsed() < MIN_FORCED_REFRESH_INTERVAL {
+                                continue;
+                            }
+                            debug!("Forcing renewal of GCP authentication token.");
+                        }
+                    }
+
                     debug!("Renewing GCP authentication token.");
+                    emit!(GcpTokenRefreshAttempt);
+                    last_attempt = Instant::now();
                     match inner.regenerate_token().await {
-                        Ok(()) => sender.send_replace(()),
+                        Ok(()) => {
+                            inner.record_refresh_success();
+                            sender.send_replace(());
+                            deadline = Instant::now() + inner.renewal_period();
+                        }
                         Err(error) => {
-                            error!(
-                                message = "Failed to update GCP authentication token.",
-                                %error
-                            );
+                            let failures = inner.record_refresh_failure(&error);
+                            emit!(GcpTokenRefreshFailed {
+                                error: &error,
+                                failures
+                            });
+                            deadline = Instant::now() + refresh_retry_delay(failures);
                         }
                     }
                 }
@@ -210,9 +324,47 @@ impl GcpAuthenticator {
 }
 
 impl InnerCreds {
+    fn new(creds: Option<(CredentialsSource, Scope)>, token: Token) -> Self {
+        Self {
+            creds,
+            token: RwLock::new(token),
+            refresh_requested: Notify::new(),
+            refresh_failures: AtomicUsize::new(0),
+            last_refresh_error: RwLock::new(None),
+        }
+    }
+
+    fn renewal_period(&self) -> Duration {
+        Duration::from_secs(self.token.read().unwrap().expires_in() as u64 / 2)
+    }
+
+    fn record_refresh_success(&self) {
+        self.refresh_failures.store(0, Ordering::Relaxed);
+        *self.last_refresh_error.write().unwrap() = None;
+    }
+
+    fn record_refresh_failure(&self, error: &crate::Error) -> usize {
+        *self.last_refresh_error.write().unwrap() = Some(error.to_string());
+        self.refresh_failures.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    fn token_health(&self) -> Result<(), GcpError> {
+        let failures = self.refresh_failures.load(Ordering::Relaxed);
+        if failures < MAX_TOKEN_REFRESH_FAILURES {
+            return Ok(());
+        }
+        let error = self
+            .last_refresh_error
+            .read()
+            .unwrap()
+            .clone()
+            .unwrap_or_default();
+        Err(GcpError::TokenRefreshFailing { failures, error })
+    }
+
     async fn regenerate_token(&self) -> crate::Result<()> {
         let token = match &self.creds {
-            Some((creds, scope)) => fetch_token(creds, scope).await?,
+            Some((creds, scope)) => creds.fetch_token(scope).await?,
             None => get_token_implicit().await?,
         };
         *self.token.write().unwrap() = token;
@@ -225,6 +377,11 @@ impl InnerCreds {
     }
 }
 
+fn refresh_retry_delay(failures: usize) -> Duration {
+    let factor = 1u32 << failures.saturating_sub(1).min(16);
+    (TOKEN_REFRESH_RETRY_DELAY * factor).min(MAX_TOKEN_REFRESH_RETRY_DELAY)
+}
+
 async fn fetch_token(creds: &Credentials, scope: &Scope) -> crate::Result<Token> {
     let claims = JwtClaims::new(creds.iss(), scope, creds.token_uri(), None, None);
     let rsa_key = creds.rsa_key().context(InvalidRsaKeySnafu)?;
@@ -273,6 +430,8 @@ async fn get_token_implicit() -> Result<Token, GcpError> {
 
 #[cfg(test)]
 mod tests {
+    use std::io::Write;
+
     use super::*;
     use crate::assert_downcast_matches;
 
@@ -331,6 +490,71 @@ mod tests {
         assert_downcast_matches!(error, GcpError, GcpError::InvalidApiKey { .. });
     }
 
+    #[test]
+    fn detects_external_account_credentials() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(
+            file,
+            r#"{{
+                "type": "external_account",
+                "audience": "audience",
+                "subject_token_type": "urn:ietf:params:oauth:token-type:jwt",
+                "token_url": "https://sts.googleapis.com/v1/token",
+                "credential_source": {{ "file": "/var/run/token" }}
+            }}"#
+        )
+        .unwrap();
+
+        let creds = CredentialsSource::from_file(file.path().to_str().unwrap()).unwrap();
+        assert!(matches!(creds, CredentialsSource::ExternalAccount(_)));
+    }
+
+    #[test]
+    fn token_health_reports_repeated_refresh_failures() {
+        let inner = InnerCreds::new(None, test_token());
+        let error: crate::Error = "token endpoint unreachable".into();
+
+        for _ in 1..MAX_TOKEN_REFRESH_FAILURES {
+            inner.record_refresh_failure(&error);
+            assert!(inner.token_health().is_ok());
+        }
+        assert_eq!(
+            inner.record_refresh_failure(&error),
+            MAX_TOKEN_REFRESH_FAILURES
+        );
+        match inner.token_health() {
+            Err(GcpError::TokenRefreshFailing { failures, .. }) => {
+                assert_eq!(failures, MAX_TOKEN_REFRESH_FAILURES)
+            }
+            other => panic!("unexpected token health: {:?}", other),
+        }
+
+        inner.record_refresh_success();
+        assert!(inner.token_health().is_ok());
+    }
+
+    #[test]
+    fn refresh_retry_delay_backs_off() {
+        assert_eq!(refresh_retry_delay(1), TOKEN_REFRESH_RETRY_DELAY);
+        assert_eq!(refresh_retry_delay(2), TOKEN_REFRESH_RETRY_DELAY * 2);
+        assert_eq!(refresh_retry_delay(3), TOKEN_REFRESH_RETRY_DELAY * 4);
+        assert_eq!(refresh_retry_delay(100), MAX_TOKEN_REFRESH_RETRY_DELAY);
+    }
+
+    #[tokio::test]
+    async fn forced_refresh_is_ignored_without_credentials() {
+        let auth = GcpAuthenticator::None;
+        auth.force_refresh();
+        assert!(auth.token_health().is_ok());
+    }
+
+    fn test_token() -> Token {
+        serde_json::from_str(
+            r#"{"access_token": "token", "token_type": "Bearer", "expires_in": 3600}"#,
+        )
+        .unwrap()
+    }
+
     fn apply_uri(auth: &GcpAuthenticator, uri: &str) -> String {
         let mut uri: Uri = uri.parse().unwrap();
         auth.apply_uri(&mut uri);
@@ -0,0 +1,480 @@
+//! Support for external account credentials, as used by workload identity federation.
+//!
+//! The credentials file describes where to find a token issued by a third party identity provider
+//! (a file, a URL, or the AWS environment). That token is exchanged against a GCP access token
+//! through the Security Token Service, optionally impersonating a service account.
+use std::{collections::HashMap, time::SystemTime};
+
+use aws_sigv4::{
+    http_request::{SignableRequest, SigningSettings},
+    SigningParams,
+};
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use goauth::auth::Token;
+use http::{header::HeaderValue, Request};
+use hyper::Body;
+use serde::Deserialize;
+use serde_json::json;
+use snafu::{ResultExt, Snafu};
+use url::form_urlencoded;
+
+use super::Scope;
+use crate::{config::ProxyConfig, http::HttpClient};
+
+const TOKEN_EXCHANGE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:token-exchange";
+const ACCESS_TOKEN_TYPE: &str = "urn:ietf:params:oauth:token-type:access_token";
+const AWS_STS_SERVICE: &str = "sts";
+const AWS_METADATA_TOKEN_TTL: &str = "300";
+const AWS_CRED_VERIFICATION_URL: &str =
+    "https://sts.{region}.amazonaws.com?Action=GetCallerIdentity&Version=2011-06-15";
+
+#[derive(Debug, Snafu)]
+pub enum ExternalAccountError {
+    #[snafu(display("Failed to read the subject token file {:?}: {}", path, source))]
+    ReadSubjectToken {
+        path: String,
+        source: std::io::Error,
+    },
+    #[snafu(display("Subject token field {:?} is missing from the response", field))]
+    MissingSubjectTokenField { field: String },
+    #[snafu(display("Credential source must define one of `file`, `url` or `environment_id`"))]
+    MissingCredentialSource,
+    #[snafu(display("Unsupported credential source environment {:?}", environment_id))]
+    UnsupportedEnvironment { environment_id: String },
+    #[snafu(display("Could not determine the AWS region"))]
+    MissingAwsRegion,
+    #[snafu(display("Could not find AWS security credentials"))]
+    MissingAwsCredentials,
+    #[snafu(display("Request to {:?} failed with status {}: {}", url, status, body))]
+    UnexpectedResponse {
+        url: String,
+        status: http::StatusCode,
+        body: String,
+    },
+    #[snafu(display("Invalid impersonated token expiry time: {}", source))]
+    InvalidExpireTime { source: chrono::ParseError },
+}
+
+/// An external account credentials file, as generated by
+/// `gcloud iam workload-identity-pools create-cred-config`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ExternalAccount {
+    audience: String,
+    subject_token_type: String,
+    token_url: String,
+    service_account_impersonation_url: Option<String>,
+    credential_source: CredentialSource,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct CredentialSource {
+    file: Option<String>,
+    url: Option<String>,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    format: Option<CredentialFormat>,
+    environment_id: Option<String>,
+    region_url: Option<String>,
+    regional_cred_verification_url: Option<String>,
+    imdsv2_session_token_url: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum CredentialFormat {
+    Text,
+    Json { subject_token_field_name: String },
+}
+
+#[derive(Debug, Deserialize)]
+struct StsResponse {
+    access_token: String,
+    token_type: String,
+    expires_in: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ImpersonationResponse {
+    access_token: String,
+    expire_time: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct AwsSecurityCredentials {
+    access_key_id: String,
+    secret_access_key: String,
+    token: Option<String>,
+}
+
+impl ExternalAccount {
+    pub async fn fetch_token(&self, scope: &Scope) -> crate::Result<Token> {
+        let client = HttpClient::new(None, &ProxyConfig::from_env())?;
+        let subject_token = self.credential_source.subject_token(&client, self).await?;
+
+        let body = form_urlencoded::Serializer::new(String::new())
+            .append_pair("grant_type", TOKEN_EXCHANGE_GRANT_TYPE)
+            .append_pair("audience", &self.audience)
+            .append_pair("scope", &scope.url())
+            .append_pair("requested_token_type", ACCESS_TOKEN_TYPE)
+            .append_pair("subject_token_type", &self.subject_token_type)
+            .append_pair("subject_token", &subject_token)
+            .finish();
+        let request = Request::post(&self.token_url)
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body(Body::from(body))?;
+
+        debug!(
+            message = "Exchanging external account token.",
+            token_url = %self.token_url,
+        );
+        let response: StsResponse = send_json(&client, request).await?;
+
+        match &self.service_account_impersonation_url {
+            None => make_token(
+                response.access_token,
+                response.token_type,
+                response.expires_in.unwrap_or(3600),
+            ),
+            Some(url) => impersonate(&client, url, &response.access_token, scope).await,
+        }
+    }
+}
+
+impl CredentialSource {
+    async fn subject_token(
+        &self,
+        client: &HttpClient,
+        account: &ExternalAccount,
+    ) -> crate::Result<String> {
+        let raw = match (&self.environment_id, &self.file, &self.url) {
+            (Some(environment_id), _, _) => {
+                if !environment_id.starts_with("aws") {
+                    return Err(ExternalAccountError::UnsupportedEnvironment {
+                        environment_id: environment_id.clone(),
+                    }
+                    .into());
+                }
+                return self.aws_subject_token(client, &account.audience).await;
+            }
+            (None, Some(path), _) => tokio::fs::read_to_string(path)
+                .await
+                .context(ReadSubjectTokenSnafu { path })?,
+            (None, None, Some(url)) => {
+                let mut request = Request::get(url);
+                for (name, value) in &self.headers {
+                    request = request.header(name.as_str(), value.as_str());
+                }
+                send_text(client, request.body(Body::empty())?).await?
+            }
+            (None, None, None) => {
+                return Err(ExternalAccountError::MissingCredentialSource.into())
+            }
+        };
+
+        match &self.format {
+            None | Some(CredentialFormat::Text) => Ok(raw.trim().to_owned()),
+            Some(CredentialFormat::Json {
+                subject_token_field_name,
+            }) => {
+                let value: serde_json::Value = serde_json::from_str(&raw)?;
+                value
+                    .get(subject_token_field_name)
+                    .and_then(|token| token.as_str())
+                    .map(Into::into)
+                    .ok_or_else(|| {
+                        ExternalAccountError::MissingSubjectTokenField {
+                            field: subject_token_field_name.clone(),
+                        }
+                        .into()
+                    })
+            }
+        }
+    }
+
+    /// Builds the serialized, signed `GetCallerIdentity` request that GCP uses to verify the
+    /// identity of an AWS workload.
+    async fn aws_subject_token(
+        &self,
+        client: &HttpClient,
+        audience: &str,
+    ) -> crate::Result<String> {
+        let session_token = match &self.imdsv2_session_token_url {
+            Some(url) => {
+                let request = Request::put(url)
+                    .header("x-aws-ec2-metadata-token-ttl-seconds", AWS_METADATA_TOKEN_TTL)
+                    .body(Body::empty())?;
+                Some(send_text(client, request).await?)
+            }
+            None => None,
+        };
+        let metadata_request = |url: &str| {
+            let mut request = Request::get(url);
+            if let Some(token) = &session_token {
+                request = request.header("x-aws-ec2-metadata-token", token.as_str());
+            }
+            request.body(Body::empty())
+        };
+
+        let region = std::env::var("AWS_REGION").or_else(|_| std::env::var("AWS_DEFAULT_REGION"));
+        let region = match region {
+            Ok(region) => region,
+            Err(_) => {
+                let url = self
+                    .region_url
+                    .as_deref()
+                    .ok_or(ExternalAccountError::MissingAwsRegion)?;
+                // The metadata server returns the availability zone, e.g. `us-east-1b`.
+                let mut zone = send_text(client, metadata_request(url)?).await?;
+                zone.pop();
+                zone
+            }
+        };
+
+        let credentials = match (
+            std::env::var("AWS_ACCESS_KEY_ID"),
+            std::env::var("AWS_SECRET_ACCESS_KEY"),
+        ) {
+            (Ok(access_key_id), Ok(secret_access_key)) => AwsSecurityCredentials {
+                access_key_id,
+                secret_access_key,
+                token: std::env::var("AWS_SESSION_TOKEN").ok(),
+            },
+            _ => {
+                let url = self
+                    .url
+                    .as_deref()
+                    .ok_or(ExternalAccountError::MissingAwsCredentials)?;
+                let role = send_text(client, metadata_request(url)?).await?;
+                let url = format!("{}/{}", url.trim_end_matches('/'), role.trim());
+                send_json(client, metadata_request(&url)?).await?
+            }
+        };
+
+        let url = self
+            .regional_cred_verification_url
+            .as_deref()
+            .unwrap_or(AWS_CRED_VERIFICATION_URL)
+            .replace("{region}", &region);
+        let mut request = Request::post(&url)
+            .header("x-goog-cloud-target-resource", audience)
+            .body(Bytes::new())?;
+        sign_aws_request(&mut request, &credentials, &region)?;
+
+        let headers = request
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                Ok::<_, crate::Error>(json!({
+                    "key": name.as_str(),
+                    "value": value.to_str()?,
+                }))
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+        let token = json!({
+            "url": url,
+            "method": "POST",
+            "headers": headers,
+        });
+
+        Ok(form_urlencoded::byte_serialize(token.to_string().as_bytes()).collect())
+    }
+}
+
+fn sign_aws_request(
+    request: &mut Request<Bytes>,
+    credentials: &AwsSecurityCredentials,
+    region: &str,
+) -> crate::Result<()> {
+    let host = request
+        .uri()
+        .host()
+        .map(HeaderValue::from_str)
+        .transpose()?;
+    if let Some(host) = host {
+        request.headers_mut().insert("host", host);
+    }
+
+    let signable_request = SignableRequest::from(&*request);
+    let mut signing_params_builder = SigningParams::builder()
+        .access_key(&credentials.access_key_id)
+        .secret_key(&credentials.secret_access_key)
+        .region(region)
+        .service_name(AWS_STS_SERVICE)
+        .time(SystemTime::now())
+        .settings(SigningSettings::default());
+    signing_params_builder.set_security_token(credentials.token.as_deref());
+
+    let (signing_instructions, _signature) =
+        aws_sigv4::http_request::sign(signable_request, &signing_params_builder.build()?)?
+            .into_parts();
+    signing_instructions.apply_to_request(request);
+
+    Ok(())
+}
+
+async fn impersonate(
+    client: &HttpClient,
+    url: &str,
+    access_token: &str,
+    scope: &Scope,
+) -> crate::Result<Token> {
+    let body = json!({ "scope": [scope.url()] }).to_string();
+    let request = Request::post(url)
+        .header("content-type", "application/json")
+        .header("authorization", format!("Bearer {}", access_token))
+        .body(Body::from(body))?;
+
+    debug!(message = "Impersonating service account.", %url);
+    let response: ImpersonationResponse = send_json(client, request).await?;
+
+    let expire_time = DateTime::parse_from_rfc3339(&response.expire_time)
+        .context(InvalidExpireTimeSnafu)?
+        .with_timezone(&Utc);
+    let expires_in = (expire_time - Utc::now()).num_seconds().max(0) as u64;
+
+    make_token(response.access_token, "Bearer".into(), expires_in)
+}
+
+fn make_token(access_token: String, token_type: String, expires_in: u64) -> crate::Result<Token> {
+    // `Token` can only be built through deserialization.
+    Ok(serde_json::from_value(json!({
+        "access_token": access_token,
+        "token_type": token_type,
+        "expires_in": expires_in,
+    }))?)
+}
+
+async fn send_text(client: &HttpClient, request: Request<Body>) -> crate::Result<String> {
+    let url = request.uri().to_string();
+    let response = client.send(request).await?;
+    let status = response.status();
+    let body = hyper::body::to_bytes(response.into_body()).await?;
+    let body = String::from_utf8_lossy(&body).into_owned();
+
+    if status.is_success() {
+        Ok(body)
+    } else {
+        Err(ExternalAccountError::UnexpectedResponse { url, status, body }.into())
+    }
+}
+
+async fn send_json<T: serde::de::DeserializeOwned>(
+    client: &HttpClient,
+    request: Request<Body>,
+) -> crate::Result<T> {
+    let body = send_text(client, request).await?;
+    Ok(serde_json::from_str(&body)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    fn parse(json: &str) -> ExternalAccount {
+        serde_json::from_str(json).expect("Invalid external account")
+    }
+
+    #[test]
+    fn parses_aws_credentials_file() {
+        let account = parse(
+            r#"{
+                "type": "external_account",
+                "audience": "//iam.googleapis.com/projects/1/locations/global/workloadIdentityPools/pool/providers/aws",
+                "subject_token_type": "urn:ietf:params:aws:token-type:aws4_request",
+                "service_account_impersonation_url": "https://iamcredentials.googleapis.com/v1/projects/-/serviceAccounts/vector@project.iam.gserviceaccount.com:generateAccessToken",
+                "token_url": "https://sts.googleapis.com/v1/token",
+                "credential_source": {
+                    "environment_id": "aws1",
+                    "region_url": "http://169.254.169.254/latest/meta-data/placement/availability-zone",
+                    "url": "http://169.254.169.254/latest/meta-data/iam/security-credentials",
+                    "regional_cred_verification_url": "https://sts.{region}.amazonaws.com?Action=GetCallerIdentity&Version=2011-06-15"
+                }
+            }"#,
+        );
+
+        assert_eq!(
+            account.credential_source.environment_id.as_deref(),
+            Some("aws1")
+        );
+        assert!(account.service_account_impersonation_url.is_some());
+    }
+
+    #[tokio::test]
+    async fn reads_json_subject_token_from_file() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(file, r#"{{"access_token": "subject-token"}}"#).unwrap();
+
+        let account = parse(&format!(
+            r#"{{
+                "type": "external_account",
+                "audience": "audience",
+                "subject_token_type": "urn:ietf:params:oauth:token-type:jwt",
+                "token_url": "https://sts.googleapis.com/v1/token",
+                "credential_source": {{
+                    "file": {:?},
+                    "format": {{ "type": "json", "subject_token_field_name": "access_token" }}
+                }}
+            }}"#,
+            file.path().to_str().unwrap()
+        ));
+
+        let client = HttpClient::new(None, &Default::default()).unwrap();
+        let token = account
+            .credential_source
+            .subject_token(&client, &account)
+            .await
+            .unwrap();
+        assert_eq!(token, "subject-token");
+    }
+
+    #[tokio::test]
+    async fn rejects_missing_credential_source() {
+        let account = parse(
+            r#"{
+                "type": "external_account",
+                "audience": "audience",
+                "subject_token_type": "urn:ietf:params:oauth:token-type:jwt",
+                "token_url": "https://sts.googleapis.com/v1/token",
+                "credential_source": {}
+            }"#,
+        );
+
+        let client = HttpClient::new(None, &Default::default()).unwrap();
+        assert!(account
+            .credential_source
+            .subject_token(&client, &account)
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn signs_get_caller_identity_request() {
+        let mut request = Request::post(
+            "https://sts.us-east-1.amazonaws.com?Action=GetCallerIdentity&Version=2011-06-15",
+        )
+        .header("x-goog-cloud-target-resource", "audience")
+        .body(Bytes::new())
+        .unwrap();
+        let credentials = AwsSecurityCredentials {
+            access_key_id: "AKIDEXAMPLE".into(),
+            secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".into(),
+            token: Some("session".into()),
+        };
+
+        sign_aws_request(&mut request, &credentials, "us-east-1").unwrap();
+
+        let headers = request.headers();
+        assert_eq!(headers["host"], "sts.us-east-1.amazonaws.com");
+        assert_eq!(headers["x-amz-security-token"], "session");
+        assert!(headers.contains_key("x-amz-date"));
+        assert!(headers["authorization"]
+            .to_str()
+            .unwrap()
+            .starts_with("AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/"));
+    }
+}
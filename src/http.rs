@@ -5,7 +5,12 @@ use std::{
 
 use futures::future::BoxFuture;
 use headers::{Authorization, HeaderMapExt};
-use http::{header::HeaderValue, request::Builder, uri::InvalidUri, HeaderMap, Request, Uri};
+use http::{
+    header::{HeaderValue, AUTHORIZATION},
+    request::Builder,
+    uri::InvalidUri,
+    HeaderMap, Request, Uri,
+};
 use hyper::{
     body::{Body, HttpBody},
     client,
@@ -246,6 +251,14 @@ pub enum Auth {
         /// The bearer token to send.
         token: String,
     },
+
+    /// API key authentication.
+    ///
+    /// The API key is passed as-is with the `ApiKey` scheme, as expected by Elasticsearch and Kibana.
+    ApiKey {
+        /// The API key to send, encoded as base64.
+        api_key: String,
+    },
 }
 
 pub trait MaybeAuth: Sized {
@@ -284,6 +297,14 @@ impl Auth {
                 Ok(auth) => map.typed_insert(auth),
                 Err(error) => error!(message = "Invalid bearer token.", token = %token, %error),
             },
+            Auth::ApiKey { api_key } => {
+                match HeaderValue::from_str(&format!("ApiKey {}", api_key)) {
+                    Ok(value) => {
+                        map.insert(AUTHORIZATION, value);
+                    }
+                    Err(error) => error!(message = "Invalid API key.", %error),
+                }
+            }
         }
     }
 }
@@ -335,4 +356,19 @@ mod tests {
             Some(&HeaderValue::from_static("foo"))
         );
     }
+
+    #[test]
+    fn test_api_key_auth() {
+        let mut request = Request::post("http://example.com").body(()).unwrap();
+        Auth::ApiKey {
+            api_key: "VnVhQ2ZHY0JDZGJrUW0tZTVhT3g6dWkybHAyYXhUTm1zeWFrdzl0dk5udw==".into(),
+        }
+        .apply(&mut request);
+        assert_eq!(
+            request.headers().get(AUTHORIZATION),
+            Some(&HeaderValue::from_static(
+                "ApiKey VnVhQ2ZHY0JDZGJrUW0tZTVhT3g6dWkybHAyYXhUTm1zeWFrdzl0dk5udw=="
+            ))
+        );
+    }
 }
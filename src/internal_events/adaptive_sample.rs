@@ -0,0 +1,11 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct AdaptiveSampleEventDiscarded;
+
+impl InternalEvent for AdaptiveSampleEventDiscarded {
+    fn emit(self) {
+        counter!("events_discarded_total", 1);
+    }
+}
@@ -0,0 +1,14 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct AggregateLogsFlushed {
+    pub count: usize,
+}
+
+impl InternalEvent for AggregateLogsFlushed {
+    fn emit(self) {
+        trace!(message = "Flushed window summaries.", count = %self.count);
+        counter!("aggregate_logs_flushes_total", 1);
+    }
+}
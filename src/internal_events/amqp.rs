@@ -0,0 +1,73 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct AmqpConnectError {
+    pub error: lapin::Error,
+}
+
+impl InternalEvent for AmqpConnectError {
+    fn emit(self) {
+        error!(
+            message = "Failed to connect to the broker.",
+            error = %self.error,
+            error_code = "failed_connecting",
+            error_type = error_type::CONNECTION_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_connecting",
+            "error_type" => error_type::CONNECTION_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AmqpReceiveError {
+    pub error: lapin::Error,
+}
+
+impl InternalEvent for AmqpReceiveError {
+    fn emit(self) {
+        error!(
+            message = "Failed to receive message.",
+            error = %self.error,
+            error_code = "failed_receiving",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_receiving",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AmqpAcknowledgementError {
+    pub error: lapin::Error,
+}
+
+impl InternalEvent for AmqpAcknowledgementError {
+    fn emit(self) {
+        error!(
+            message = "Failed to acknowledge message.",
+            error = %self.error,
+            error_code = "failed_acknowledging",
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_acknowledging",
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct AwsKinesisStreamsLeaseError<'a, E> {
+    pub error: &'a E,
+}
+
+impl<'a, E: std::fmt::Display> InternalEvent for AwsKinesisStreamsLeaseError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to access the lease table.",
+            error = %self.error,
+            error_code = "failed_accessing_lease_table",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_accessing_lease_table",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AwsKinesisStreamsShardReadError<'a, E> {
+    pub shard_id: &'a str,
+    pub error: &'a E,
+}
+
+impl<'a, E: std::fmt::Display> InternalEvent for AwsKinesisStreamsShardReadError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to read the records of shard.",
+            shard_id = %self.shard_id,
+            error = %self.error,
+            error_code = "failed_reading_shard",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_reading_shard",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
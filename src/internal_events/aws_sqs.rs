@@ -145,6 +145,79 @@ mod s3 {
     }
 }
 
+#[derive(Debug)]
+pub struct S3ObjectListError<'a, E> {
+    pub bucket: &'a str,
+    pub error: &'a E,
+}
+
+impl<'a, E: std::fmt::Display> InternalEvent for S3ObjectListError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to list S3 objects.",
+            bucket = %self.bucket,
+            error = %self.error,
+            error_code = "failed_listing_s3_objects",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_listing_s3_objects",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct S3ObjectProcessingError<'a> {
+    pub error: &'a ProcessingError,
+}
+
+impl<'a> InternalEvent for S3ObjectProcessingError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to process S3 object.",
+            error = %self.error,
+            error_code = "failed_processing_s3_object",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::PROCESSING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_processing_s3_object",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct S3CheckpointWriteError<'a> {
+    pub path: &'a std::path::Path,
+    pub error: &'a std::io::Error,
+}
+
+impl<'a> InternalEvent for S3CheckpointWriteError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to write S3 listing checkpoint.",
+            path = ?self.path,
+            error = %self.error,
+            error_code = "failed_writing_checkpoint",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_writing_checkpoint",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
 #[derive(Debug)]
 pub struct SqsMessageReceiveSucceeded {
     pub count: usize,
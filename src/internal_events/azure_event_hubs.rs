@@ -0,0 +1,55 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct AzureEventHubsCheckpointLoadError {
+    pub error: crate::Error,
+    pub partition: i32,
+}
+
+impl InternalEvent for AzureEventHubsCheckpointLoadError {
+    fn emit(self) {
+        error!(
+            message = "Failed to load checkpoint.",
+            error = %self.error,
+            partition = %self.partition,
+            error_code = "checkpoint_load",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "checkpoint_load",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AzureEventHubsCheckpointStoreError {
+    pub error: crate::Error,
+    pub partition: i32,
+}
+
+impl InternalEvent for AzureEventHubsCheckpointStoreError {
+    fn emit(self) {
+        error!(
+            message = "Failed to store checkpoint.",
+            error = %self.error,
+            partition = %self.partition,
+            error_code = "checkpoint_store",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "checkpoint_store",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
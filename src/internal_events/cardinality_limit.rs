@@ -0,0 +1,40 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+pub struct CardinalityLimitSeriesLimitReached<'a> {
+    pub metric_name: &'a str,
+}
+
+impl<'a> InternalEvent for CardinalityLimitSeriesLimitReached<'a> {
+    fn emit(self) {
+        debug!(
+            message =
+                "Series_limit reached for metric. New series for this metric will be limited.",
+            metric_name = self.metric_name,
+        );
+        counter!("series_limit_reached_total", 1);
+    }
+}
+
+pub struct CardinalityLimitLimitingSeries<'a> {
+    pub metric_name: &'a str,
+    pub action: &'static str,
+}
+
+impl<'a> InternalEvent for CardinalityLimitLimitingSeries<'a> {
+    fn emit(self) {
+        debug!(
+            message = "Limiting new series after hitting configured 'series_limit'.",
+            metric_name = self.metric_name,
+            action = self.action,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "limited_series_events_total", 1,
+            "action" => self.action,
+        );
+        if self.action == "drop_series" {
+            counter!("events_discarded_total", 1);
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use std::fmt::Display;
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct EbpfReadError<'a, E> {
+    pub error: &'a E,
+}
+
+impl<E: Display> InternalEvent for EbpfReadError<'_, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to read the events of the eBPF programs.",
+            error = %self.error,
+            error_code = "failed_reading_ring_buffer",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_reading_ring_buffer",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct EbpfDecodeError<'a, E> {
+    pub error: &'a E,
+}
+
+impl<E: Display> InternalEvent for EbpfDecodeError<'_, E> {
+    fn emit(self) {
+        error!(
+            message = "Invalid event sent by the eBPF programs, discarding.",
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        counter!(
+            "component_discarded_events_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
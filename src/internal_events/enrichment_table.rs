@@ -0,0 +1,53 @@
+use metrics::{counter, gauge};
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct EnrichmentTableLoaded<'a> {
+    pub table: &'a str,
+    pub rows: Option<usize>,
+}
+
+impl<'a> InternalEvent for EnrichmentTableLoaded<'a> {
+    fn emit(self) {
+        debug!(
+            message = "Enrichment table loaded.",
+            table = %self.table,
+            rows = ?self.rows,
+        );
+        if let Some(rows) = self.rows {
+            gauge!("enrichment_table_rows", rows as f64, "table" => self.table.to_owned());
+        }
+        gauge!(
+            "enrichment_table_last_reload_timestamp_seconds",
+            chrono::Utc::now().timestamp_millis() as f64 / 1000.0,
+            "table" => self.table.to_owned(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct EnrichmentTableReloadError<'a> {
+    pub table: &'a str,
+    pub error: String,
+}
+
+impl<'a> InternalEvent for EnrichmentTableReloadError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to reload enrichment table; the previously loaded data is still used.",
+            table = %self.table,
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "enrichment_table_reload_errors_total", 1,
+            "table" => self.table.to_owned(),
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
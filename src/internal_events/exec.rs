@@ -141,3 +141,45 @@ impl InternalEvent for ExecCommandExecuted<'_> {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct ExecProcessingError<'a> {
+    pub command: &'a str,
+    pub error: std::io::Error,
+    pub dropped_events: usize,
+}
+
+impl InternalEvent for ExecProcessingError<'_> {
+    fn emit(self) {
+        let error_type = if self.error.kind() == std::io::ErrorKind::TimedOut {
+            error_type::TIMED_OUT
+        } else {
+            error_type::COMMAND_FAILED
+        };
+        error!(
+            message = "Unable to process events with command.",
+            command = %self.command,
+            error = ?self.error,
+            error_type,
+            error_code = %io_error_code(&self.error),
+            dropped_events = %self.dropped_events,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "command" => self.command.to_owned(),
+            "error_type" => error_type,
+            "error_code" => io_error_code(&self.error),
+            "stage" => error_stage::PROCESSING,
+        );
+        if self.dropped_events > 0 {
+            counter!(
+                "component_discarded_events_total", self.dropped_events as u64,
+                "command" => self.command.to_owned(),
+                "error_type" => error_type,
+                "stage" => error_stage::PROCESSING,
+            );
+        }
+    }
+}
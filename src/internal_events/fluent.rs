@@ -17,6 +17,30 @@ impl InternalEvent for FluentMessageReceived {
     }
 }
 
+#[derive(Debug)]
+pub struct FluentHandshakeError<'a> {
+    pub error: &'a DecodeError,
+}
+
+impl<'a> InternalEvent for FluentHandshakeError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Error during fluent handshake, closing connection.",
+            error = %self.error,
+            error_code = "handshake_failed",
+            error_type = error_type::CONNECTION_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "handshake_failed",
+            "error_type" => error_type::CONNECTION_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
 #[derive(Debug)]
 pub struct FluentMessageDecodeError<'a> {
     pub error: &'a DecodeError,
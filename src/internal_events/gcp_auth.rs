@@ -0,0 +1,40 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use crate::internal_events::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct GcpTokenRefreshAttempt;
+
+impl InternalEvent for GcpTokenRefreshAttempt {
+    fn emit(self) {
+        trace!(message = "Refreshing GCP authentication token.");
+        counter!("gcp_token_refresh_attempts_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct GcpTokenRefreshFailed<'a> {
+    pub error: &'a crate::Error,
+    pub failures: usize,
+}
+
+impl<'a> InternalEvent for GcpTokenRefreshFailed<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to update GCP authentication token.",
+            error = %self.error,
+            consecutive_failures = self.failures,
+            error_code = "token_refresh_failed",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+        );
+        counter!("gcp_token_refresh_failures_total", 1);
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "token_refresh_failed",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use crate::{
+    internal_events::prelude::{error_stage, error_type},
+    sinks::gcp::chronicle_udm::UdmValidationError,
+};
+
+#[derive(Debug)]
+pub struct ChronicleUdmInvalidEvent {
+    pub error: UdmValidationError,
+}
+
+impl InternalEvent for ChronicleUdmInvalidEvent {
+    fn emit(self) {
+        error!(
+            message = "Event is not a valid UDM event; discarding event.",
+            error = %self.error,
+            field = self.error.field(),
+            error_type = error_type::CONDITION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONDITION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        counter!(
+            "component_discarded_events_total", 1,
+            "error_type" => error_type::CONDITION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ChronicleForwarderHeartbeatError {
+    pub error: crate::Error,
+}
+
+impl InternalEvent for ChronicleForwarderHeartbeatError {
+    fn emit(self) {
+        warn!(
+            message = "Failed to send forwarder heartbeat to Chronicle.",
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_secs = 60,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ChronicleFallbackLogType<'a> {
+    pub error: crate::template::TemplateRenderingError,
+    pub fallback_log_type: &'a str,
+}
+
+impl<'a> InternalEvent for ChronicleFallbackLogType<'a> {
+    fn emit(self) {
+        warn!(
+            message = "Failed to render template for \"log_type\"; using the fallback log type.",
+            error = %self.error,
+            fallback_log_type = %self.fallback_log_type,
+            internal_log_rate_secs = 30,
+        );
+        counter!("chronicle_fallback_log_type_events_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct ChronicleLogTypeSent<'a> {
+    pub log_type: &'a str,
+    pub count: usize,
+    pub byte_size: usize,
+}
+
+impl<'a> InternalEvent for ChronicleLogTypeSent<'a> {
+    fn emit(self) {
+        trace!(
+            message = "Requests sent.",
+            log_type = %self.log_type,
+            count = %self.count,
+            byte_size = %self.byte_size,
+        );
+        let log_type = self.log_type.to_owned();
+        counter!("chronicle_requests_sent_total", 1, "log_type" => log_type.clone());
+        counter!(
+            "chronicle_events_sent_total", self.count as u64,
+            "log_type" => log_type.clone(),
+        );
+        counter!(
+            "chronicle_bytes_sent_total", self.byte_size as u64,
+            "log_type" => log_type,
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use crate::internal_events::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct GcsNotificationPullError<'a> {
+    pub error: &'a crate::Error,
+}
+
+impl<'a> InternalEvent for GcsNotificationPullError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to pull bucket notifications from Pub/Sub.",
+            error = %self.error,
+            error_code = "failed_pulling_notifications",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_pulling_notifications",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct GcsNotificationAcknowledgeError<'a> {
+    pub error: &'a crate::Error,
+    pub count: usize,
+}
+
+impl<'a> InternalEvent for GcsNotificationAcknowledgeError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to acknowledge bucket notifications; they will be redelivered.",
+            error = %self.error,
+            count = %self.count,
+            error_code = "failed_acknowledging_notifications",
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::PROCESSING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_acknowledging_notifications",
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct GcsObjectProcessingError<'a> {
+    pub message_id: &'a str,
+    pub error: &'a crate::Error,
+}
+
+impl<'a> InternalEvent for GcsObjectProcessingError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to process bucket notification.",
+            message_id = %self.message_id,
+            error = %self.error,
+            error_code = "failed_processing_gcs_object",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::PROCESSING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_processing_gcs_object",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct GcsNotificationIgnored<'a> {
+    pub bucket: &'a str,
+    pub object: &'a str,
+    pub event_type: &'a str,
+}
+
+impl<'a> InternalEvent for GcsNotificationIgnored<'a> {
+    fn emit(self) {
+        debug!(
+            message = "Ignored bucket notification for an event that was not OBJECT_FINALIZE.",
+            bucket = %self.bucket,
+            object = %self.object,
+            event_type = %self.event_type,
+        );
+        counter!("gcs_notification_ignored_total", 1, "event_type" => self.event_type.to_owned());
+    }
+}
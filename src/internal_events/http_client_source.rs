@@ -0,0 +1,80 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type, http_error_code};
+
+#[derive(Debug)]
+pub struct HttpClientHttpError<'a> {
+    pub error: crate::Error,
+    pub url: &'a str,
+}
+
+impl<'a> InternalEvent for HttpClientHttpError<'a> {
+    fn emit(self) {
+        error!(
+            message = "HTTP request processing error.",
+            url = %self.url,
+            error = ?self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "url" => self.url.to_owned(),
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct HttpClientHttpResponseError<'a> {
+    pub code: hyper::StatusCode,
+    pub url: &'a str,
+}
+
+impl<'a> InternalEvent for HttpClientHttpResponseError<'a> {
+    fn emit(self) {
+        error!(
+            message = "HTTP error response.",
+            url = %self.url,
+            stage = error_stage::RECEIVING,
+            error_type = error_type::REQUEST_FAILED,
+            error_code = %http_error_code(self.code.as_u16()),
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "url" => self.url.to_owned(),
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::REQUEST_FAILED,
+            "error_code" => http_error_code(self.code.as_u16()),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct HttpClientParseError<'a> {
+    pub error: serde_json::Error,
+    pub url: &'a str,
+}
+
+impl<'a> InternalEvent for HttpClientParseError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to parse the response as JSON.",
+            url = %self.url,
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "url" => self.url.to_owned(),
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -199,3 +199,30 @@ impl InternalEvent for KafkaNegativeAcknowledgmentError<'_> {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct KafkaTransactionError {
+    pub error: rdkafka::error::KafkaError,
+    pub operation: &'static str,
+    pub count: usize,
+}
+
+impl InternalEvent for KafkaTransactionError {
+    fn emit(self) {
+        error!(
+            message = "Transaction failed; events of the transaction are not delivered.",
+            error = %self.error,
+            error_code = "kafka_transaction",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            operation = self.operation,
+            count = self.count,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "kafka_transaction",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use std::fmt::Display;
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct KubernetesEventsWatchError<'a, E> {
+    pub namespace: &'a str,
+    pub error: &'a E,
+}
+
+impl<E: Display> InternalEvent for KubernetesEventsWatchError<'_, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to watch the events of namespace, retrying.",
+            namespace = %self.namespace,
+            error = %self.error,
+            error_code = "failed_watching_events",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_watching_events",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct KubernetesEventsConversionError {
+    pub error: serde_json::Error,
+}
+
+impl InternalEvent for KubernetesEventsConversionError {
+    fn emit(self) {
+        error!(
+            message = "Failed to convert the event, discarding.",
+            error = %self.error,
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct KubernetesEventsCheckpointError {
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for KubernetesEventsCheckpointError {
+    fn emit(self) {
+        error!(
+            message = "Failed to write the resource versions of the watches.",
+            error = %self.error,
+            error_code = "failed_writing_checkpoints",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_writing_checkpoints",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
@@ -93,3 +93,49 @@ impl InternalEvent for LogToMetricTemplateParseError {
         );
     }
 }
+
+pub struct LogToMetricExpressionError<'a> {
+    pub error: &'a str,
+}
+
+impl<'a> InternalEvent for LogToMetricExpressionError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to evaluate VRL expression.",
+            error = %self.error,
+            error_code = "failed_evaluating_expression",
+            error_type = error_type::SCRIPT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 30,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_evaluating_expression",
+            "error_type" => error_type::SCRIPT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+pub struct LogToMetricNativeConversionError {
+    pub error: serde_json::Error,
+}
+
+impl InternalEvent for LogToMetricNativeConversionError {
+    fn emit(self) {
+        error!(
+            message = "Failed to convert log event to metric.",
+            error = %self.error,
+            error_code = "failed_converting_to_metric",
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 30,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_converting_to_metric",
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
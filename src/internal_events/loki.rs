@@ -49,3 +49,19 @@ impl InternalEvent for LokiOutOfOrderEventRewritten {
         counter!("rewritten_timestamp_events_total", self.count as u64);
     }
 }
+
+#[derive(Debug)]
+pub struct LokiOutOfOrderEventSharded {
+    pub count: usize,
+}
+
+impl InternalEvent for LokiOutOfOrderEventSharded {
+    fn emit(self) {
+        debug!(
+            message = "Received out-of-order events, sending them to shards of their stream.",
+            count = %self.count,
+            internal_log_rate_secs = 10,
+        );
+        counter!("sharded_stream_events_total", self.count as u64);
+    }
+}
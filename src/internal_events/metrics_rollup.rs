@@ -0,0 +1,32 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct MetricsRollupEventRecorded;
+
+impl InternalEvent for MetricsRollupEventRecorded {
+    fn emit(self) {
+        counter!("metrics_rollup_events_recorded_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct MetricsRollupFlushed {
+    pub series: usize,
+}
+
+impl InternalEvent for MetricsRollupFlushed {
+    fn emit(self) {
+        counter!("metrics_rollup_flushes_total", 1);
+        counter!("metrics_rollup_series_flushed_total", self.series as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct MetricsRollupUpdateFailed;
+
+impl InternalEvent for MetricsRollupUpdateFailed {
+    fn emit(self) {
+        counter!("metrics_rollup_failed_updates_total", 1);
+    }
+}
@@ -1,7 +1,13 @@
 pub mod prelude;
 
 mod adaptive_concurrency;
+#[cfg(feature = "transforms-adaptive_sample")]
+mod adaptive_sample;
 mod aggregate;
+#[cfg(feature = "transforms-aggregate_logs")]
+mod aggregate_logs;
+#[cfg(feature = "sources-amqp")]
+mod amqp;
 #[cfg(feature = "sources-apache_metrics")]
 mod apache_metrics;
 #[cfg(feature = "api")]
@@ -16,10 +22,18 @@ mod aws_ec2_metadata;
 mod aws_ecs_metrics;
 #[cfg(feature = "sources-aws_kinesis_firehose")]
 mod aws_kinesis_firehose;
+#[cfg(feature = "sources-aws_kinesis_streams")]
+mod aws_kinesis_streams;
 #[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs",))]
 mod aws_sqs;
-#[cfg(any(feature = "sinks-azure_blob", feature = "sinks-datadog_archives"))]
+#[cfg(any(
+    feature = "sinks-azure_blob",
+    feature = "sinks-datadog_archives",
+    feature = "sources-azure_event_hubs"
+))]
 pub(crate) mod azure_blob;
+#[cfg(feature = "sources-azure_event_hubs")]
+mod azure_event_hubs;
 mod batch;
 mod common;
 mod conditions;
@@ -28,6 +42,8 @@ mod datadog_metrics;
 #[cfg(feature = "sinks-datadog_traces")]
 mod datadog_traces;
 mod decoder;
+#[cfg(feature = "transforms-cardinality_limit")]
+mod cardinality_limit;
 #[cfg(feature = "transforms-dedupe")]
 mod dedupe;
 #[cfg(feature = "sources-demo_logs")]
@@ -36,16 +52,25 @@ mod demo_logs;
 mod dnstap;
 #[cfg(feature = "sources-docker_logs")]
 mod docker_logs;
+#[cfg(all(target_os = "linux", feature = "sources-ebpf"))]
+mod ebpf;
 mod elasticsearch;
 mod encoding_transcode;
+mod enrichment_table;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 mod eventstoredb_metrics;
-#[cfg(feature = "sources-exec")]
+#[cfg(any(feature = "sources-exec", feature = "transforms-exec"))]
 mod exec;
 #[cfg(feature = "transforms-filter")]
 mod filter;
 #[cfg(feature = "sources-fluent")]
 mod fluent;
+#[cfg(feature = "gcp")]
+mod gcp_auth;
+#[cfg(feature = "sinks-gcp")]
+mod gcp_chronicle;
+#[cfg(feature = "sources-gcp_cloud_storage")]
+mod gcp_cloud_storage;
 #[cfg(feature = "sources-gcp_pubsub")]
 mod gcp_pubsub;
 #[cfg(feature = "transforms-geoip")]
@@ -53,12 +78,16 @@ mod geoip;
 mod heartbeat;
 mod http;
 pub mod http_client;
+#[cfg(feature = "sources-http_client")]
+mod http_client_source;
 #[cfg(feature = "sources-internal_logs")]
 mod internal_logs;
 #[cfg(all(unix, feature = "sources-journald"))]
 mod journald;
 #[cfg(any(feature = "sources-kafka", feature = "sinks-kafka"))]
 mod kafka;
+#[cfg(feature = "sources-kubernetes_events")]
+mod kubernetes_events;
 #[cfg(feature = "sources-kubernetes_logs")]
 mod kubernetes_logs;
 #[cfg(feature = "transforms-log_to_metric")]
@@ -70,9 +99,13 @@ mod loki;
 mod lua;
 #[cfg(feature = "transforms-metric_to_log")]
 mod metric_to_log;
+#[cfg(feature = "transforms-metrics_rollup")]
+mod metrics_rollup;
 #[cfg(feature = "sources-mongodb_metrics")]
 mod mongodb_metrics;
-#[cfg(feature = "sinks-nats")]
+#[cfg(feature = "sinks-mqtt")]
+mod mqtt;
+#[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
 mod nats;
 #[cfg(feature = "sources-nginx_metrics")]
 mod nginx_metrics;
@@ -89,17 +122,27 @@ mod postgresql_metrics;
 mod process;
 #[cfg(any(feature = "sources-prometheus", feature = "sinks-prometheus"))]
 mod prometheus;
+#[cfg(feature = "sinks-pulsar")]
+mod pulsar;
+#[cfg(feature = "sinks-questdb")]
+mod questdb;
+#[cfg(feature = "transforms-redact_pii")]
+mod redact_pii;
 #[cfg(any(feature = "sources-redis", feature = "sinks-redis"))]
 mod redis;
 #[cfg(feature = "transforms-reduce")]
 mod reduce;
 mod remap;
 mod sample;
+#[cfg(feature = "transforms-schema_guard")]
+mod schema_guard;
 #[cfg(feature = "sinks-sematext")]
 mod sematext_metrics;
 mod socket;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 mod splunk_hec;
+#[cfg(feature = "sources-sql_query")]
+mod sql_query;
 #[cfg(feature = "sinks-statsd")]
 mod statsd_sink;
 #[cfg(feature = "sources-statsd")]
@@ -108,15 +151,21 @@ mod statsd_source;
 mod syslog;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 mod tag_cardinality_limit;
+#[cfg(feature = "transforms-tail_sample")]
+mod tail_sample;
 mod tcp;
 mod template;
 #[cfg(feature = "transforms-throttle")]
 mod throttle;
+#[cfg(feature = "transforms-trace_sampling")]
+mod trace_sampling;
 mod udp;
 mod unix;
 mod vector;
-#[cfg(feature = "sinks-websocket")]
+#[cfg(any(feature = "sources-websocket_client", feature = "sinks-websocket"))]
 mod websocket;
+#[cfg(all(windows, feature = "sources-windows_eventlog"))]
+mod windows_eventlog;
 
 #[cfg(any(
     feature = "sources-file",
@@ -129,8 +178,14 @@ mod windows;
 #[cfg(feature = "sources-mongodb_metrics")]
 pub(crate) use mongodb_metrics::*;
 
+#[cfg(feature = "transforms-adaptive_sample")]
+pub(crate) use self::adaptive_sample::*;
 #[cfg(feature = "transforms-aggregate")]
 pub(crate) use self::aggregate::*;
+#[cfg(feature = "transforms-aggregate_logs")]
+pub(crate) use self::aggregate_logs::*;
+#[cfg(feature = "sources-amqp")]
+pub(crate) use self::amqp::*;
 #[cfg(feature = "sources-apache_metrics")]
 pub(crate) use self::apache_metrics::*;
 #[cfg(feature = "api")]
@@ -145,13 +200,19 @@ pub(crate) use self::aws_ec2_metadata::*;
 pub(crate) use self::aws_ecs_metrics::*;
 #[cfg(feature = "sources-aws_kinesis_firehose")]
 pub(crate) use self::aws_kinesis_firehose::*;
+#[cfg(feature = "sources-aws_kinesis_streams")]
+pub(crate) use self::aws_kinesis_streams::*;
 #[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs",))]
 pub(crate) use self::aws_sqs::*;
+#[cfg(feature = "sources-azure_event_hubs")]
+pub(crate) use self::azure_event_hubs::*;
 #[cfg(feature = "sinks-datadog_metrics")]
 pub(crate) use self::datadog_metrics::*;
 #[cfg(feature = "sinks-datadog_traces")]
 pub(crate) use self::datadog_traces::*;
 pub(crate) use self::decoder::*;
+#[cfg(feature = "transforms-cardinality_limit")]
+pub(crate) use self::cardinality_limit::*;
 #[cfg(feature = "transforms-dedupe")]
 pub(crate) use self::dedupe::*;
 #[cfg(feature = "sources-demo_logs")]
@@ -160,11 +221,13 @@ pub(crate) use self::demo_logs::*;
 pub(crate) use self::dnstap::*;
 #[cfg(feature = "sources-docker_logs")]
 pub(crate) use self::docker_logs::*;
+#[cfg(all(target_os = "linux", feature = "sources-ebpf"))]
+pub(crate) use self::ebpf::*;
 #[cfg(feature = "sinks-elasticsearch")]
 pub(crate) use self::elasticsearch::*;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 pub(crate) use self::eventstoredb_metrics::*;
-#[cfg(feature = "sources-exec")]
+#[cfg(any(feature = "sources-exec", feature = "transforms-exec"))]
 pub(crate) use self::exec::*;
 #[cfg(any(
     feature = "sources-file",
@@ -176,6 +239,12 @@ pub(crate) use self::file::*;
 pub(crate) use self::filter::*;
 #[cfg(feature = "sources-fluent")]
 pub(crate) use self::fluent::*;
+#[cfg(feature = "gcp")]
+pub(crate) use self::gcp_auth::*;
+#[cfg(feature = "sinks-gcp")]
+pub(crate) use self::gcp_chronicle::*;
+#[cfg(feature = "sources-gcp_cloud_storage")]
+pub(crate) use self::gcp_cloud_storage::*;
 #[cfg(feature = "sources-gcp_pubsub")]
 pub(crate) use self::gcp_pubsub::*;
 #[cfg(feature = "transforms-geoip")]
@@ -187,12 +256,16 @@ pub(crate) use self::geoip::*;
     feature = "sources-splunk_hec",
 ))]
 pub(crate) use self::http::*;
+#[cfg(feature = "sources-http_client")]
+pub(crate) use self::http_client_source::*;
 #[cfg(feature = "sources-internal_logs")]
 pub(crate) use self::internal_logs::*;
 #[cfg(all(unix, feature = "sources-journald"))]
 pub(crate) use self::journald::*;
 #[cfg(any(feature = "sources-kafka", feature = "sinks-kafka"))]
 pub(crate) use self::kafka::*;
+#[cfg(feature = "sources-kubernetes_events")]
+pub(crate) use self::kubernetes_events::*;
 #[cfg(feature = "sources-kubernetes_logs")]
 pub(crate) use self::kubernetes_logs::*;
 #[cfg(feature = "transforms-log_to_metric")]
@@ -205,7 +278,11 @@ pub(crate) use self::loki::*;
 pub(crate) use self::lua::*;
 #[cfg(feature = "transforms-metric_to_log")]
 pub(crate) use self::metric_to_log::*;
-#[cfg(feature = "sinks-nats")]
+#[cfg(feature = "transforms-metrics_rollup")]
+pub(crate) use self::metrics_rollup::*;
+#[cfg(feature = "sinks-mqtt")]
+pub(crate) use self::mqtt::*;
+#[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
 pub(crate) use self::nats::*;
 #[cfg(feature = "sources-nginx_metrics")]
 pub(crate) use self::nginx_metrics::*;
@@ -220,6 +297,12 @@ pub(crate) use self::parser::*;
 pub(crate) use self::postgresql_metrics::*;
 #[cfg(any(feature = "sources-prometheus", feature = "sinks-prometheus"))]
 pub(crate) use self::prometheus::*;
+#[cfg(feature = "sinks-pulsar")]
+pub(crate) use self::pulsar::*;
+#[cfg(feature = "sinks-questdb")]
+pub(crate) use self::questdb::*;
+#[cfg(feature = "transforms-redact_pii")]
+pub(crate) use self::redact_pii::*;
 #[cfg(any(feature = "sources-redis", feature = "sinks-redis"))]
 pub(crate) use self::redis::*;
 #[cfg(feature = "transforms-reduce")]
@@ -228,10 +311,14 @@ pub(crate) use self::reduce::*;
 pub(crate) use self::remap::*;
 #[cfg(feature = "transforms-sample")]
 pub(crate) use self::sample::*;
+#[cfg(feature = "transforms-schema_guard")]
+pub(crate) use self::schema_guard::*;
 #[cfg(feature = "sinks-sematext")]
 pub(crate) use self::sematext_metrics::*;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 pub(crate) use self::splunk_hec::*;
+#[cfg(feature = "sources-sql_query")]
+pub(crate) use self::sql_query::*;
 #[cfg(feature = "sinks-statsd")]
 pub(crate) use self::statsd_sink::*;
 #[cfg(feature = "sources-statsd")]
@@ -240,8 +327,12 @@ pub(crate) use self::statsd_source::*;
 pub(crate) use self::syslog::*;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 pub(crate) use self::tag_cardinality_limit::*;
+#[cfg(feature = "transforms-tail_sample")]
+pub(crate) use self::tail_sample::*;
 #[cfg(feature = "transforms-throttle")]
 pub(crate) use self::throttle::*;
+#[cfg(feature = "transforms-trace_sampling")]
+pub(crate) use self::trace_sampling::*;
 #[cfg(all(
     any(
         feature = "sinks-socket",
@@ -250,20 +341,23 @@ pub(crate) use self::throttle::*;
         feature = "sources-metrics",
         feature = "sources-statsd",
         feature = "sources-syslog",
-        feature = "sources-socket"
+        feature = "sources-socket",
+        feature = "sources-vector"
     ),
     unix
 ))]
 pub(crate) use self::unix::*;
 #[cfg(feature = "sources-vector")]
 pub(crate) use self::vector::*;
-#[cfg(feature = "sinks-websocket")]
+#[cfg(any(feature = "sources-websocket_client", feature = "sinks-websocket"))]
 pub(crate) use self::websocket::*;
 #[cfg(windows)]
 pub(crate) use self::windows::*;
+#[cfg(all(windows, feature = "sources-windows_eventlog"))]
+pub(crate) use self::windows_eventlog::*;
 pub(crate) use self::{
     adaptive_concurrency::*, batch::*, common::*, conditions::*, encoding_transcode::*,
-    heartbeat::*, open::*, process::*, socket::*, tcp::*, template::*, udp::*,
+    enrichment_table::*, heartbeat::*, open::*, process::*, socket::*, tcp::*, template::*, udp::*,
 };
 
 // this version won't be needed once all `InternalEvent`s implement `name()`
@@ -0,0 +1,53 @@
+use metrics::counter;
+use rumqttc::{ClientError, ConnectionError};
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct MqttConnectionError {
+    pub error: ConnectionError,
+}
+
+impl InternalEvent for MqttConnectionError {
+    fn emit(self) {
+        error!(
+            message = "MQTT connection error.",
+            error = %self.error,
+            error_code = "mqtt_connection_error",
+            error_type = error_type::CONNECTION_FAILED,
+            stage = error_stage::SENDING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "mqtt_connection_error",
+            "error_type" => error_type::CONNECTION_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct MqttClientError {
+    pub error: ClientError,
+}
+
+impl InternalEvent for MqttClientError {
+    fn emit(self) {
+        error!(
+            message = "Failed to send message.",
+            error = %self.error,
+            error_code = "mqtt_client_error",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::SENDING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "mqtt_client_error",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        // deprecated
+        counter!("send_errors_total", 1);
+    }
+}
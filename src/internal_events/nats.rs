@@ -4,6 +4,7 @@ use metrics::counter;
 use vector_core::internal_event::InternalEvent;
 
 use super::prelude::{error_stage, error_type, io_error_code};
+use crate::nats::JetStreamError;
 
 #[derive(Debug)]
 pub struct NatsEventSendError {
@@ -29,3 +30,75 @@ impl InternalEvent for NatsEventSendError {
         counter!("send_errors_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct NatsPullError {
+    pub error: Error,
+}
+
+impl InternalEvent for NatsPullError {
+    fn emit(self) {
+        error!(
+            message = "Failed to pull messages from JetStream consumer.",
+            error = %self.error,
+            error_code = io_error_code(&self.error),
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => io_error_code(&self.error),
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct NatsAcknowledgementError {
+    pub error: Error,
+}
+
+impl InternalEvent for NatsAcknowledgementError {
+    fn emit(self) {
+        error!(
+            message = "Failed to acknowledge message.",
+            error = %self.error,
+            error_code = "failed_acknowledging",
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_acknowledging",
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+#[derive(Debug)]
+pub struct NatsPublishAckError {
+    pub error: JetStreamError,
+    pub count: usize,
+}
+
+impl InternalEvent for NatsPublishAckError {
+    fn emit(self) {
+        error!(
+            message = "Message not acknowledged by JetStream.",
+            error = %self.error,
+            error_code = "publish_ack",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            count = %self.count,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "publish_ack",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        // deprecated
+        counter!("send_errors_total", 1);
+    }
+}
@@ -170,3 +170,19 @@ impl InternalEvent for PrometheusServerRequestComplete {
         counter!("requests_received_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct PrometheusRemoteWriteOutOfOrderSampleDropped<'a> {
+    pub metric_name: &'a str,
+}
+
+impl<'a> InternalEvent for PrometheusRemoteWriteOutOfOrderSampleDropped<'a> {
+    fn emit(self) {
+        debug!(
+            message = "Dropping sample older than the latest sample sent for its series.",
+            metric_name = %self.metric_name,
+            internal_log_rate_secs = 10,
+        );
+        counter!("events_discarded_total", 1, "reason" => "out_of_order");
+    }
+}
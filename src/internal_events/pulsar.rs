@@ -0,0 +1,32 @@
+use metrics::counter;
+use pulsar::Error as PulsarError;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct PulsarSendingError {
+    pub error: PulsarError,
+    pub count: usize,
+}
+
+impl InternalEvent for PulsarSendingError {
+    fn emit(self) {
+        error!(
+            message = "Failed to send messages.",
+            error = %self.error,
+            error_code = "pulsar_sending",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            count = %self.count,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "pulsar_sending",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        // deprecated
+        counter!("send_errors_total", 1);
+    }
+}
@@ -0,0 +1,34 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct QuestDbEncodingError {
+    pub error: crate::Error,
+}
+
+impl InternalEvent for QuestDbEncodingError {
+    fn emit(self) {
+        error!(
+            message = "Failed to encode event; dropping event.",
+            error = %self.error,
+            error_code = "questdb_encoding",
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "questdb_encoding",
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        counter!(
+            "component_discarded_events_total", 1,
+            "error_code" => "questdb_encoding",
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -71,3 +71,35 @@ impl InternalEvent for RedisReceiveEventError {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct RedisAcknowledgementError {
+    error: redis::RedisError,
+    error_code: String,
+}
+
+impl From<redis::RedisError> for RedisAcknowledgementError {
+    fn from(error: redis::RedisError) -> Self {
+        let error_code = error.code().unwrap_or("UNKNOWN").to_string();
+        Self { error, error_code }
+    }
+}
+
+impl InternalEvent for RedisAcknowledgementError {
+    fn emit(self) {
+        error!(
+            message = "Failed to acknowledge stream entry.",
+            error = %self.error,
+            error_code = %self.error_code,
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::RECEIVING,
+            rate_limit_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => self.error_code,
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
@@ -1,6 +1,8 @@
 use metrics::counter;
 use vector_core::internal_event::InternalEvent;
 
+use super::prelude::{error_stage, error_type};
+
 #[derive(Debug)]
 pub struct ReduceStaleEventFlushed;
 
@@ -9,3 +11,30 @@ impl InternalEvent for ReduceStaleEventFlushed {
         counter!("stale_events_flushed_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct ReduceMergeError {
+    pub error: String,
+}
+
+impl InternalEvent for ReduceMergeError {
+    fn emit(self) {
+        error!(
+            message = "Merge program failed; discarding event.",
+            error = ?self.error,
+            error_type = error_type::SCRIPT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::SCRIPT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        counter!(
+            "component_discarded_events_total", 1,
+            "error_type" => error_type::SCRIPT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -0,0 +1,26 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct SchemaGuardValidationFailed<'a> {
+    pub error: &'a str,
+}
+
+impl<'a> InternalEvent for SchemaGuardValidationFailed<'a> {
+    fn emit(self) {
+        error!(
+            message = "Event failed schema validation; routing to invalid output.",
+            error = ?self.error,
+            error_type = error_type::CONDITION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONDITION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct SqlQueryExecuteError {
+    pub error: crate::Error,
+}
+
+impl InternalEvent for SqlQueryExecuteError {
+    fn emit(self) {
+        error!(
+            message = "Failed to run the query.",
+            error = %self.error,
+            error_code = "failed_executing_query",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_executing_query",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct SqlQueryCheckpointError {
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for SqlQueryCheckpointError {
+    fn emit(self) {
+        error!(
+            message = "Failed to write the checkpoint.",
+            error = %self.error,
+            error_code = "failed_writing_checkpoint",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_writing_checkpoint",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
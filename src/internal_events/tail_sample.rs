@@ -0,0 +1,26 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct TailSampleEventsDiscarded {
+    pub count: usize,
+}
+
+impl InternalEvent for TailSampleEventsDiscarded {
+    fn emit(self) {
+        counter!("events_discarded_total", self.count as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct TailSampleTraceEvicted;
+
+impl InternalEvent for TailSampleTraceEvicted {
+    fn emit(self) {
+        debug!(
+            message = "Maximum number of pending traces reached; deciding on the oldest one.",
+            internal_log_rate_secs = 10
+        );
+        counter!("tail_sample_traces_evicted_total", 1);
+    }
+}
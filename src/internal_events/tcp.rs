@@ -105,6 +105,33 @@ impl InternalEvent for TcpSocketTlsConnectionError {
     }
 }
 
+#[derive(Debug)]
+pub struct TcpSocketTlsClientUnauthorized {
+    pub peer_addr: SocketAddr,
+    pub subject: Option<String>,
+}
+
+impl InternalEvent for TcpSocketTlsClientUnauthorized {
+    fn emit(self) {
+        error!(
+            message = "Client certificate not allowed, closing connection.",
+            peer_addr = %self.peer_addr,
+            subject = ?self.subject,
+            error_code = "client_unauthorized",
+            error_type = error_type::CONNECTION_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "client_unauthorized",
+            "error_type" => error_type::CONNECTION_FAILED,
+            "stage" => error_stage::RECEIVING,
+            "mode" => "tcp",
+        );
+    }
+}
+
 #[derive(Debug)]
 pub struct TcpSocketError {
     pub error: std::io::Error,
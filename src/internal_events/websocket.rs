@@ -87,3 +87,26 @@ impl InternalEvent for WsConnectionError {
         Some("WsConnectionError")
     }
 }
+
+#[derive(Debug)]
+pub struct WsClientEventsDropped {
+    pub count: usize,
+}
+
+impl InternalEvent for WsClientEventsDropped {
+    fn emit(self) {
+        warn!(
+            message = "Client queue is full; dropping events.",
+            count = %self.count,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_discarded_events_total", self.count as u64,
+            "reason" => "client_queue_full",
+        );
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("WsClientEventsDropped")
+    }
+}
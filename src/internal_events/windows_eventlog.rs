@@ -0,0 +1,98 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct WindowsEventLogReadError<'a> {
+    pub channel: &'a str,
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for WindowsEventLogReadError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to read the events of channel.",
+            channel = %self.channel,
+            error = %self.error,
+            error_code = "failed_reading_channel",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_reading_channel",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct WindowsEventLogParseError<'a> {
+    pub channel: &'a str,
+    pub error: roxmltree::Error,
+}
+
+impl InternalEvent for WindowsEventLogParseError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Invalid event XML, discarding.",
+            channel = %self.channel,
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct WindowsEventLogCheckpointError {
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for WindowsEventLogCheckpointError {
+    fn emit(self) {
+        error!(
+            message = "Failed to write the bookmarks of the channels.",
+            error = %self.error,
+            error_code = "failed_writing_bookmarks",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_writing_bookmarks",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct WindowsEventLogNegativeAcknowledgmentError<'a> {
+    pub channel: &'a str,
+}
+
+impl InternalEvent for WindowsEventLogNegativeAcknowledgmentError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Event received a negative acknowledgment, channel has been stopped.",
+            channel = %self.channel,
+            error_code = "negative_acknowledgement",
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::SENDING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "negative_acknowledgment",
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
@@ -1,4 +1,7 @@
+use std::time::Duration;
+
 use nkeys::error::Error as NKeysError;
+use serde::de::DeserializeOwned;
 use snafu::{ResultExt, Snafu};
 use vector_config::configurable_component;
 
@@ -172,10 +175,71 @@ pub(crate) fn from_tls_auth_config(
     }
 }
 
+/// How long a JetStream API request waits for its response.
+const JETSTREAM_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Snafu)]
+pub enum JetStreamError {
+    #[snafu(display("JetStream request failed: {}", source))]
+    Request { source: std::io::Error },
+    #[snafu(display("JetStream request timed out"))]
+    Timeout,
+    #[snafu(display("invalid JetStream response: {}", source))]
+    Response { source: serde_json::Error },
+    #[snafu(display("JetStream API error {}: {}", code, description))]
+    Api { code: u16, description: String },
+}
+
+/// Parses the response of the JetStream API, which holds an `error` object on failure.
+pub(crate) fn parse_jetstream_response<T: DeserializeOwned>(
+    data: &[u8],
+) -> Result<T, JetStreamError> {
+    let mut response: serde_json::Value = serde_json::from_slice(data).context(ResponseSnafu)?;
+    if let Some(error) = response.get_mut("error").map(serde_json::Value::take) {
+        return Err(JetStreamError::Api {
+            code: error["code"].as_u64().unwrap_or_default() as u16,
+            description: error["description"]
+                .as_str()
+                .unwrap_or("unknown error")
+                .to_owned(),
+        });
+    }
+    serde_json::from_value(response).context(ResponseSnafu)
+}
+
+/// Sends a request to the JetStream API, such as `$JS.API.STREAM.INFO.<stream>`.
+pub(crate) async fn jetstream_request<T: DeserializeOwned>(
+    connection: &nats::asynk::Connection,
+    subject: &str,
+    body: &serde_json::Value,
+) -> Result<T, JetStreamError> {
+    let response = tokio::time::timeout(
+        JETSTREAM_REQUEST_TIMEOUT,
+        connection.request(subject, body.to_string()),
+    )
+    .await
+    .map_err(|_| JetStreamError::Timeout)?
+    .context(RequestSnafu)?;
+    parse_jetstream_response(&response.data)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn parses_jetstream_api_error() {
+        let error = parse_jetstream_response::<serde_json::Value>(
+            br#"{"type":"io.nats.jetstream.api.v1.consumer_create_response","error":{"code":404,"err_code":10059,"description":"stream not found"}}"#,
+        )
+        .unwrap_err();
+
+        assert!(matches!(
+            error,
+            JetStreamError::Api { code: 404, ref description } if description == "stream not found"
+        ));
+    }
+
     fn parse_auth(s: &str) -> Result<nats::asynk::Options, crate::Error> {
         toml::from_str(s)
             .map_err(Into::into)
@@ -1,27 +1,54 @@
 use super::{
-    Common::{any_value::Value as PBValue, KeyValue},
+    Common::{any_value::Value as PBValue, InstrumentationScope, KeyValue},
     Logs::{LogRecord, ResourceLogs, SeverityNumber},
+    Metrics::{
+        exponential_histogram_data_point::Buckets, metric::Data, number_data_point,
+        AggregationTemporality, DataPointFlags, ExponentialHistogramDataPoint, HistogramDataPoint,
+        Metric as OtelMetric, NumberDataPoint, ResourceMetrics, SummaryDataPoint,
+    },
     Resource as OtelResource,
+    Trace::{
+        span::{Event as SpanEvent, Link},
+        ResourceSpans, Span, Status,
+    },
 };
 use bytes::Bytes;
-use chrono::{TimeZone, Utc};
+use chrono::{DateTime, TimeZone, Utc};
 use ordered_float::NotNan;
 use std::collections::BTreeMap;
 use value::Value;
 use vector_core::{
     config::log_schema,
-    event::{Event, LogEvent},
+    event::{
+        metric::{Bucket, MetricTags, Quantile},
+        Event, LogEvent, Metric, MetricKind, MetricValue, TraceEvent,
+    },
 };
 
-const RESOURCE_KEY: &str = "resources";
-const ATTRIBUTES_KEY: &str = "attributes";
-const TRACE_ID_KEY: &str = "trace_id";
-const SPAN_ID_KEY: &str = "span_id";
-const SEVERITY_TEXT_KEY: &str = "severity_text";
-const SEVERITY_NUMBER_KEY: &str = "severity_number";
-const OBSERVED_TIMESTAMP_KEY: &str = "observed_timestamp";
-const DROPPED_ATTRIBUTES_COUNT_KEY: &str = "dropped_attributes_count";
-const FLAGS_KEY: &str = "flags";
+pub(super) const RESOURCE_KEY: &str = "resources";
+pub(super) const SCOPE_KEY: &str = "scope";
+pub(super) const ATTRIBUTES_KEY: &str = "attributes";
+pub(super) const TRACE_ID_KEY: &str = "trace_id";
+pub(super) const SPAN_ID_KEY: &str = "span_id";
+pub(super) const SEVERITY_TEXT_KEY: &str = "severity_text";
+pub(super) const SEVERITY_NUMBER_KEY: &str = "severity_number";
+pub(super) const OBSERVED_TIMESTAMP_KEY: &str = "observed_timestamp";
+pub(super) const DROPPED_ATTRIBUTES_COUNT_KEY: &str = "dropped_attributes_count";
+pub(super) const FLAGS_KEY: &str = "flags";
+pub(super) const PARENT_SPAN_ID_KEY: &str = "parent_span_id";
+pub(super) const TRACE_STATE_KEY: &str = "trace_state";
+pub(super) const NAME_KEY: &str = "name";
+pub(super) const VERSION_KEY: &str = "version";
+pub(super) const KIND_KEY: &str = "kind";
+pub(super) const START_TIMESTAMP_KEY: &str = "start_timestamp";
+pub(super) const END_TIMESTAMP_KEY: &str = "end_timestamp";
+pub(super) const EVENTS_KEY: &str = "events";
+pub(super) const DROPPED_EVENTS_COUNT_KEY: &str = "dropped_events_count";
+pub(super) const LINKS_KEY: &str = "links";
+pub(super) const DROPPED_LINKS_COUNT_KEY: &str = "dropped_links_count";
+pub(super) const STATUS_KEY: &str = "status";
+pub(super) const STATUS_MESSAGE_KEY: &str = "message";
+pub(super) const STATUS_CODE_KEY: &str = "code";
 
 impl IntoIterator for ResourceLogs {
     type Item = Event;
@@ -30,19 +57,80 @@ impl IntoIterator for ResourceLogs {
         let resource = self.resource;
         self.scope_logs
             .into_iter()
-            .flat_map(|scope_log| scope_log.log_records)
-            .map(|log_record| {
-                ResourceLog {
+            .flat_map(|scope_log| {
+                let scope = scope_log.scope;
+                let resource = resource.clone();
+                scope_log
+                    .log_records
+                    .into_iter()
+                    .map(move |log_record| ResourceLog {
+                        resource: resource.clone(),
+                        scope: scope.clone(),
+                        log_record,
+                    })
+            })
+            .map(Into::into)
+            .collect::<Vec<Self::Item>>()
+            .into_iter()
+    }
+}
+
+impl IntoIterator for ResourceSpans {
+    type Item = Event;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+    fn into_iter(self) -> Self::IntoIter {
+        let resource = self.resource;
+        self.scope_spans
+            .into_iter()
+            .flat_map(|scope_span| {
+                let scope = scope_span.scope;
+                let resource = resource.clone();
+                scope_span.spans.into_iter().map(move |span| ResourceSpan {
                     resource: resource.clone(),
-                    log_record,
-                }
-                .into()
+                    scope: scope.clone(),
+                    span,
+                })
             })
+            .map(Into::into)
             .collect::<Vec<Self::Item>>()
             .into_iter()
     }
 }
 
+impl IntoIterator for ResourceMetrics {
+    type Item = Event;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+    fn into_iter(self) -> Self::IntoIter {
+        // The resource and scope are added as tags named after their fields in logs and traces.
+        let mut resource_tags = MetricTags::new();
+        if let Some(resource) = self.resource {
+            insert_attribute_tags(&mut resource_tags, RESOURCE_KEY, resource.attributes);
+        }
+
+        let mut events = Vec::new();
+        for scope_metrics in self.scope_metrics {
+            let mut tags = resource_tags.clone();
+            if let Some(scope) = scope_metrics.scope {
+                if !scope.name.is_empty() {
+                    tags.insert(format!("{}.{}", SCOPE_KEY, NAME_KEY), scope.name);
+                }
+                if !scope.version.is_empty() {
+                    tags.insert(format!("{}.{}", SCOPE_KEY, VERSION_KEY), scope.version);
+                }
+                insert_attribute_tags(
+                    &mut tags,
+                    &format!("{}.{}", SCOPE_KEY, ATTRIBUTES_KEY),
+                    scope.attributes,
+                );
+            }
+            for metric in scope_metrics.metrics {
+                events.extend(metric_into_events(metric, &tags));
+            }
+        }
+        events.into_iter()
+    }
+}
+
 impl From<PBValue> for Value {
     fn from(av: PBValue) -> Self {
         match av {
@@ -64,9 +152,16 @@ impl From<PBValue> for Value {
 
 struct ResourceLog {
     resource: Option<OtelResource>,
+    scope: Option<InstrumentationScope>,
     log_record: LogRecord,
 }
 
+struct ResourceSpan {
+    resource: Option<OtelResource>,
+    scope: Option<InstrumentationScope>,
+    span: Span,
+}
+
 fn kv_list_into_value(arr: Vec<KeyValue>) -> Value {
     Value::Object(
         arr.into_iter()
@@ -78,6 +173,32 @@ fn kv_list_into_value(arr: Vec<KeyValue>) -> Value {
     )
 }
 
+fn scope_into_value(scope: InstrumentationScope) -> Option<Value> {
+    let mut fields = BTreeMap::new();
+    if !scope.name.is_empty() {
+        fields.insert(NAME_KEY.to_owned(), scope.name.into());
+    }
+    if !scope.version.is_empty() {
+        fields.insert(VERSION_KEY.to_owned(), scope.version.into());
+    }
+    if !scope.attributes.is_empty() {
+        fields.insert(
+            ATTRIBUTES_KEY.to_owned(),
+            kv_list_into_value(scope.attributes),
+        );
+    }
+    (!fields.is_empty()).then(|| Value::Object(fields))
+}
+
+fn id_into_value(id: Vec<u8>) -> Value {
+    Value::Bytes(Bytes::from(hex::encode(id)))
+}
+
+fn timestamp_from_nanos(nanos: u64) -> Option<DateTime<Utc>> {
+    // 0 represents a missing or unknown timestamp.
+    (nanos > 0).then(|| Utc.timestamp_nanos(nanos as i64))
+}
+
 impl From<ResourceLog> for Event {
     fn from(rl: ResourceLog) -> Self {
         let mut le = LogEvent::default();
@@ -88,6 +209,9 @@ impl From<ResourceLog> for Event {
                 le.insert(RESOURCE_KEY, kv_list_into_value(resource.attributes));
             }
         }
+        if let Some(scope) = rl.scope.and_then(scope_into_value) {
+            le.insert(SCOPE_KEY, scope);
+        }
         if !rl.log_record.attributes.is_empty() {
             le.insert(ATTRIBUTES_KEY, kv_list_into_value(rl.log_record.attributes));
         }
@@ -142,3 +266,317 @@ impl From<ResourceLog> for Event {
         le.into()
     }
 }
+
+impl From<ResourceSpan> for Event {
+    fn from(rs: ResourceSpan) -> Self {
+        let span = rs.span;
+        let mut trace = BTreeMap::new();
+
+        trace.insert(TRACE_ID_KEY.to_owned(), id_into_value(span.trace_id));
+        trace.insert(SPAN_ID_KEY.to_owned(), id_into_value(span.span_id));
+        trace.insert(NAME_KEY.to_owned(), span.name.into());
+        trace.insert(KIND_KEY.to_owned(), span.kind.into());
+
+        // optional fields
+        if let Some(resource) = rs.resource {
+            if !resource.attributes.is_empty() {
+                trace.insert(
+                    RESOURCE_KEY.to_owned(),
+                    kv_list_into_value(resource.attributes),
+                );
+            }
+        }
+        if let Some(scope) = rs.scope.and_then(scope_into_value) {
+            trace.insert(SCOPE_KEY.to_owned(), scope);
+        }
+        if !span.parent_span_id.is_empty() {
+            trace.insert(
+                PARENT_SPAN_ID_KEY.to_owned(),
+                id_into_value(span.parent_span_id),
+            );
+        }
+        if !span.trace_state.is_empty() {
+            trace.insert(TRACE_STATE_KEY.to_owned(), span.trace_state.into());
+        }
+        if !span.attributes.is_empty() {
+            trace.insert(
+                ATTRIBUTES_KEY.to_owned(),
+                kv_list_into_value(span.attributes),
+            );
+        }
+        if let Some(timestamp) = timestamp_from_nanos(span.start_time_unix_nano) {
+            trace.insert(START_TIMESTAMP_KEY.to_owned(), timestamp.into());
+        }
+        if let Some(timestamp) = timestamp_from_nanos(span.end_time_unix_nano) {
+            trace.insert(END_TIMESTAMP_KEY.to_owned(), timestamp.into());
+        }
+        if !span.events.is_empty() {
+            trace.insert(
+                EVENTS_KEY.to_owned(),
+                Value::Array(span.events.into_iter().map(span_event_into_value).collect()),
+            );
+        }
+        if !span.links.is_empty() {
+            trace.insert(
+                LINKS_KEY.to_owned(),
+                Value::Array(span.links.into_iter().map(link_into_value).collect()),
+            );
+        }
+        if let Some(status) = span.status {
+            trace.insert(STATUS_KEY.to_owned(), status_into_value(status));
+        }
+
+        trace.insert(
+            DROPPED_ATTRIBUTES_COUNT_KEY.to_owned(),
+            span.dropped_attributes_count.into(),
+        );
+        trace.insert(
+            DROPPED_EVENTS_COUNT_KEY.to_owned(),
+            span.dropped_events_count.into(),
+        );
+        trace.insert(
+            DROPPED_LINKS_COUNT_KEY.to_owned(),
+            span.dropped_links_count.into(),
+        );
+
+        TraceEvent::from(trace).into()
+    }
+}
+
+fn span_event_into_value(event: SpanEvent) -> Value {
+    let mut fields = BTreeMap::new();
+    fields.insert(NAME_KEY.to_owned(), event.name.into());
+    if let Some(timestamp) = timestamp_from_nanos(event.time_unix_nano) {
+        fields.insert(log_schema().timestamp_key().to_owned(), timestamp.into());
+    }
+    if !event.attributes.is_empty() {
+        fields.insert(
+            ATTRIBUTES_KEY.to_owned(),
+            kv_list_into_value(event.attributes),
+        );
+    }
+    fields.insert(
+        DROPPED_ATTRIBUTES_COUNT_KEY.to_owned(),
+        event.dropped_attributes_count.into(),
+    );
+    Value::Object(fields)
+}
+
+fn link_into_value(link: Link) -> Value {
+    let mut fields = BTreeMap::new();
+    fields.insert(TRACE_ID_KEY.to_owned(), id_into_value(link.trace_id));
+    fields.insert(SPAN_ID_KEY.to_owned(), id_into_value(link.span_id));
+    if !link.trace_state.is_empty() {
+        fields.insert(TRACE_STATE_KEY.to_owned(), link.trace_state.into());
+    }
+    if !link.attributes.is_empty() {
+        fields.insert(
+            ATTRIBUTES_KEY.to_owned(),
+            kv_list_into_value(link.attributes),
+        );
+    }
+    fields.insert(
+        DROPPED_ATTRIBUTES_COUNT_KEY.to_owned(),
+        link.dropped_attributes_count.into(),
+    );
+    Value::Object(fields)
+}
+
+fn status_into_value(status: Status) -> Value {
+    let mut fields = BTreeMap::new();
+    if !status.message.is_empty() {
+        fields.insert(STATUS_MESSAGE_KEY.to_owned(), status.message.into());
+    }
+    fields.insert(STATUS_CODE_KEY.to_owned(), status.code.into());
+    Value::Object(fields)
+}
+
+/// Inserts the attributes as tags prefixed with the given key, converting their values to strings.
+fn insert_attribute_tags(tags: &mut MetricTags, prefix: &str, attributes: Vec<KeyValue>) {
+    for kv in attributes {
+        if let Some(value) = kv.value.and_then(|av| av.value) {
+            tags.insert(
+                format!("{}.{}", prefix, kv.key),
+                Value::from(value).to_string_lossy(),
+            );
+        }
+    }
+}
+
+fn data_point_tags(tags: &MetricTags, attributes: Vec<KeyValue>) -> Option<MetricTags> {
+    let mut tags = tags.clone();
+    for kv in attributes {
+        if let Some(value) = kv.value.and_then(|av| av.value) {
+            tags.insert(kv.key, Value::from(value).to_string_lossy());
+        }
+    }
+    (!tags.is_empty()).then(|| tags)
+}
+
+fn is_recorded(flags: u32) -> bool {
+    flags & DataPointFlags::FlagNoRecordedValue as u32 == 0
+}
+
+fn temporality_kind(aggregation_temporality: i32) -> MetricKind {
+    if aggregation_temporality == AggregationTemporality::Delta as i32 {
+        MetricKind::Incremental
+    } else {
+        MetricKind::Absolute
+    }
+}
+
+/// Converts each data point of the metric into a metric event, skipping those without a value.
+fn metric_into_events(metric: OtelMetric, tags: &MetricTags) -> Vec<Event> {
+    let name = metric.name;
+    let event = |kind, value, time_unix_nano, attributes| -> Event {
+        Metric::new(name.clone(), kind, value)
+            .with_timestamp(timestamp_from_nanos(time_unix_nano))
+            .with_tags(data_point_tags(tags, attributes))
+            .into()
+    };
+
+    match metric.data {
+        Some(Data::Gauge(gauge)) => gauge
+            .data_points
+            .into_iter()
+            .filter_map(|point| {
+                let value = number_value(&point)?;
+                Some(event(
+                    MetricKind::Absolute,
+                    MetricValue::Gauge { value },
+                    point.time_unix_nano,
+                    point.attributes,
+                ))
+            })
+            .collect(),
+        Some(Data::Sum(sum)) => {
+            let kind = temporality_kind(sum.aggregation_temporality);
+            sum.data_points
+                .into_iter()
+                .filter_map(|point| {
+                    let value = number_value(&point)?;
+                    let value = if sum.is_monotonic {
+                        MetricValue::Counter { value }
+                    } else {
+                        MetricValue::Gauge { value }
+                    };
+                    Some(event(kind, value, point.time_unix_nano, point.attributes))
+                })
+                .collect()
+        }
+        Some(Data::Histogram(histogram)) => {
+            let kind = temporality_kind(histogram.aggregation_temporality);
+            histogram
+                .data_points
+                .into_iter()
+                .filter(|point| is_recorded(point.flags))
+                .map(|point| {
+                    let value = histogram_value(&point);
+                    event(kind, value, point.time_unix_nano, point.attributes)
+                })
+                .collect()
+        }
+        Some(Data::ExponentialHistogram(histogram)) => {
+            let kind = temporality_kind(histogram.aggregation_temporality);
+            histogram
+                .data_points
+                .into_iter()
+                .filter(|point| is_recorded(point.flags))
+                .map(|point| {
+                    let value = exponential_histogram_value(&point);
+                    event(kind, value, point.time_unix_nano, point.attributes)
+                })
+                .collect()
+        }
+        Some(Data::Summary(summary)) => summary
+            .data_points
+            .into_iter()
+            .filter(|point| is_recorded(point.flags))
+            .map(|point| {
+                let value = summary_value(&point);
+                event(
+                    MetricKind::Absolute,
+                    value,
+                    point.time_unix_nano,
+                    point.attributes,
+                )
+            })
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+fn number_value(point: &NumberDataPoint) -> Option<f64> {
+    if !is_recorded(point.flags) {
+        return None;
+    }
+    match point.value.as_ref()? {
+        number_data_point::Value::AsDouble(value) => Some(*value),
+        number_data_point::Value::AsInt(value) => Some(*value as f64),
+    }
+}
+
+fn histogram_value(point: &HistogramDataPoint) -> MetricValue {
+    // The count of the overflow bucket, above the last bound, is only part of the total count.
+    let buckets = point
+        .explicit_bounds
+        .iter()
+        .zip(&point.bucket_counts)
+        .map(|(&upper_limit, &count)| Bucket { upper_limit, count })
+        .collect();
+    MetricValue::AggregatedHistogram {
+        buckets,
+        count: point.count,
+        sum: point.sum.unwrap_or_default(),
+    }
+}
+
+fn exponential_histogram_value(point: &ExponentialHistogramDataPoint) -> MetricValue {
+    // The bucket at `index` holds the values in (base^index, base^(index + 1)], with negative
+    // values mapped into the negative buckets by their absolute value.
+    let base = 2f64.powf(2f64.powi(-point.scale));
+    let upper_limits = |buckets: &Buckets, sign: f64, shift: i32| {
+        buckets
+            .bucket_counts
+            .iter()
+            .enumerate()
+            .map(move |(i, &count)| Bucket {
+                upper_limit: sign * base.powi(buckets.offset + i as i32 + shift),
+                count,
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut buckets = Vec::new();
+    if let Some(negative) = &point.negative {
+        buckets.extend(upper_limits(negative, -1.0, 0).into_iter().rev());
+    }
+    buckets.push(Bucket {
+        upper_limit: 0.0,
+        count: point.zero_count,
+    });
+    if let Some(positive) = &point.positive {
+        buckets.extend(upper_limits(positive, 1.0, 1));
+    }
+
+    MetricValue::AggregatedHistogram {
+        buckets,
+        count: point.count,
+        sum: point.sum.unwrap_or_default(),
+    }
+}
+
+fn summary_value(point: &SummaryDataPoint) -> MetricValue {
+    MetricValue::AggregatedSummary {
+        quantiles: point
+            .quantile_values
+            .iter()
+            .map(|quantile| Quantile {
+                quantile: quantile.quantile,
+                value: quantile.value,
+            })
+            .collect(),
+        count: point.count,
+        sum: point.sum,
+    }
+}
@@ -0,0 +1,716 @@
+//! Conversion of Vector events into OTLP export requests, the inverse of the conversions in
+//! [`super::convert`].
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use value::Value;
+use vector_core::{
+    config::log_schema,
+    event::{
+        metric::{Bucket, MetricSketch, Quantile, Sample},
+        LogEvent, Metric, MetricKind, MetricValue, TraceEvent,
+    },
+};
+
+use super::{
+    convert::{
+        ATTRIBUTES_KEY, DROPPED_ATTRIBUTES_COUNT_KEY, DROPPED_EVENTS_COUNT_KEY,
+        DROPPED_LINKS_COUNT_KEY, END_TIMESTAMP_KEY, EVENTS_KEY, FLAGS_KEY, KIND_KEY, LINKS_KEY,
+        NAME_KEY, OBSERVED_TIMESTAMP_KEY, PARENT_SPAN_ID_KEY, RESOURCE_KEY, SCOPE_KEY,
+        SEVERITY_NUMBER_KEY, SEVERITY_TEXT_KEY, SPAN_ID_KEY, START_TIMESTAMP_KEY, STATUS_CODE_KEY,
+        STATUS_KEY, STATUS_MESSAGE_KEY, TRACE_ID_KEY, TRACE_STATE_KEY, VERSION_KEY,
+    },
+    Common::{
+        any_value::Value as PBValue, AnyValue, ArrayValue, InstrumentationScope, KeyValue,
+        KeyValueList,
+    },
+    LogService::ExportLogsServiceRequest,
+    Logs::{LogRecord, ResourceLogs, ScopeLogs},
+    Metrics::{
+        metric::Data, number_data_point, summary_data_point::ValueAtQuantile,
+        AggregationTemporality, Gauge, Histogram, HistogramDataPoint, Metric as OtelMetric,
+        NumberDataPoint, ResourceMetrics, ScopeMetrics, Sum, Summary, SummaryDataPoint,
+    },
+    MetricsService::ExportMetricsServiceRequest,
+    Resource as OtelResource,
+    Trace::{
+        span::{Event as SpanEvent, Link},
+        ResourceSpans, ScopeSpans, Span, Status,
+    },
+    TraceService::ExportTraceServiceRequest,
+};
+
+/// The quantiles sketches are summarized with, as OTLP has no equivalent of sketches.
+const SKETCH_QUANTILES: [f64; 4] = [0.5, 0.75, 0.9, 0.99];
+
+type Fields = BTreeMap<String, Value>;
+
+/// The items of a request, grouped by resource and then by instrumentation scope.
+type Groups<T> = Vec<(
+    Option<OtelResource>,
+    Vec<(Option<InstrumentationScope>, Vec<T>)>,
+)>;
+
+/// Log fields other than those of a log record are sent as attributes, so that no data is lost when
+/// sending logs that weren't received from OpenTelemetry.
+impl FromIterator<LogEvent> for ExportLogsServiceRequest {
+    fn from_iter<I: IntoIterator<Item = LogEvent>>(iter: I) -> Self {
+        let records = iter.into_iter().map(|mut log| {
+            let body = log.remove(log_schema().message_key());
+            let timestamp = log.remove(log_schema().timestamp_key());
+            let mut fields = match log.into_parts().0 {
+                Value::Object(fields) => fields,
+                _ => Fields::new(),
+            };
+
+            let resource = take_resource(&mut fields);
+            let scope = take_scope(&mut fields);
+            let record = LogRecord {
+                time_unix_nano: timestamp.as_ref().map_or(0, value_into_nanos),
+                observed_time_unix_nano: take_nanos(&mut fields, OBSERVED_TIMESTAMP_KEY),
+                severity_number: take_integer(&mut fields, SEVERITY_NUMBER_KEY),
+                severity_text: take_string(&mut fields, SEVERITY_TEXT_KEY),
+                body: body.map(value_into_any_value),
+                dropped_attributes_count: take_integer(&mut fields, DROPPED_ATTRIBUTES_COUNT_KEY),
+                flags: take_integer(&mut fields, FLAGS_KEY),
+                trace_id: take_id(&mut fields, TRACE_ID_KEY),
+                span_id: take_id(&mut fields, SPAN_ID_KEY),
+                attributes: take_attributes_and_rest(fields),
+            };
+            (resource, scope, record)
+        });
+
+        Self {
+            resource_logs: group_by_resource_and_scope(records)
+                .into_iter()
+                .map(|(resource, scopes)| ResourceLogs {
+                    resource,
+                    scope_logs: scopes
+                        .into_iter()
+                        .map(|(scope, log_records)| ScopeLogs {
+                            scope,
+                            log_records,
+                            schema_url: String::new(),
+                        })
+                        .collect(),
+                    schema_url: String::new(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Trace fields other than those of a span are sent as attributes, as done for logs.
+impl FromIterator<TraceEvent> for ExportTraceServiceRequest {
+    fn from_iter<I: IntoIterator<Item = TraceEvent>>(iter: I) -> Self {
+        let spans = iter.into_iter().map(|trace| {
+            let mut fields = trace.into_parts().0;
+
+            let resource = take_resource(&mut fields);
+            let scope = take_scope(&mut fields);
+            let span = Span {
+                trace_id: take_id(&mut fields, TRACE_ID_KEY),
+                span_id: take_id(&mut fields, SPAN_ID_KEY),
+                trace_state: take_string(&mut fields, TRACE_STATE_KEY),
+                parent_span_id: take_id(&mut fields, PARENT_SPAN_ID_KEY),
+                name: take_string(&mut fields, NAME_KEY),
+                kind: take_integer(&mut fields, KIND_KEY),
+                start_time_unix_nano: take_nanos(&mut fields, START_TIMESTAMP_KEY),
+                end_time_unix_nano: take_nanos(&mut fields, END_TIMESTAMP_KEY),
+                dropped_attributes_count: take_integer(&mut fields, DROPPED_ATTRIBUTES_COUNT_KEY),
+                events: take_objects(&mut fields, EVENTS_KEY)
+                    .map(span_event_from_fields)
+                    .collect(),
+                dropped_events_count: take_integer(&mut fields, DROPPED_EVENTS_COUNT_KEY),
+                links: take_objects(&mut fields, LINKS_KEY)
+                    .map(link_from_fields)
+                    .collect(),
+                dropped_links_count: take_integer(&mut fields, DROPPED_LINKS_COUNT_KEY),
+                status: match fields.remove(STATUS_KEY) {
+                    Some(Value::Object(mut status)) => Some(Status {
+                        message: take_string(&mut status, STATUS_MESSAGE_KEY),
+                        code: take_integer(&mut status, STATUS_CODE_KEY),
+                    }),
+                    _ => None,
+                },
+                attributes: take_attributes_and_rest(fields),
+            };
+            (resource, scope, span)
+        });
+
+        Self {
+            resource_spans: group_by_resource_and_scope(spans)
+                .into_iter()
+                .map(|(resource, scopes)| ResourceSpans {
+                    resource,
+                    scope_spans: scopes
+                        .into_iter()
+                        .map(|(scope, spans)| ScopeSpans {
+                            scope,
+                            spans,
+                            schema_url: String::new(),
+                        })
+                        .collect(),
+                    schema_url: String::new(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The resource and scope are taken from the tags named after their fields, the remaining tags
+/// being the attributes of the data point.
+impl FromIterator<Metric> for ExportMetricsServiceRequest {
+    fn from_iter<I: IntoIterator<Item = Metric>>(iter: I) -> Self {
+        let metrics = iter.into_iter().map(|metric| {
+            let resource_prefix = format!("{}.", RESOURCE_KEY);
+            let scope_name = format!("{}.{}", SCOPE_KEY, NAME_KEY);
+            let scope_version = format!("{}.{}", SCOPE_KEY, VERSION_KEY);
+            let scope_prefix = format!("{}.{}.", SCOPE_KEY, ATTRIBUTES_KEY);
+
+            let mut resource_attributes = Vec::new();
+            let mut scope = InstrumentationScope::default();
+            let mut attributes = Vec::new();
+            for (key, value) in metric.tags().into_iter().flatten() {
+                if let Some(key) = key.strip_prefix(&resource_prefix) {
+                    resource_attributes.push(string_attribute(key, value));
+                } else if *key == scope_name {
+                    scope.name = value.clone();
+                } else if *key == scope_version {
+                    scope.version = value.clone();
+                } else if let Some(key) = key.strip_prefix(&scope_prefix) {
+                    scope.attributes.push(string_attribute(key, value));
+                } else {
+                    attributes.push(string_attribute(key, value));
+                }
+            }
+
+            let resource = (!resource_attributes.is_empty()).then(|| OtelResource {
+                attributes: resource_attributes,
+                dropped_attributes_count: 0,
+            });
+            let scope = (scope != InstrumentationScope::default()).then(|| scope);
+            let name = match metric.namespace() {
+                Some(namespace) => format!("{}.{}", namespace, metric.name()),
+                None => metric.name().to_owned(),
+            };
+            let time_unix_nano = datetime_into_nanos(&metric.timestamp().unwrap_or_else(Utc::now));
+            let otel_metric = OtelMetric {
+                name,
+                description: String::new(),
+                unit: String::new(),
+                data: Some(metric_data(
+                    metric.kind(),
+                    metric.value(),
+                    attributes,
+                    time_unix_nano,
+                )),
+            };
+            (resource, scope, otel_metric)
+        });
+
+        Self {
+            resource_metrics: group_by_resource_and_scope(metrics)
+                .into_iter()
+                .map(|(resource, scopes)| ResourceMetrics {
+                    resource,
+                    scope_metrics: scopes
+                        .into_iter()
+                        .map(|(scope, metrics)| ScopeMetrics {
+                            scope,
+                            metrics,
+                            schema_url: String::new(),
+                        })
+                        .collect(),
+                    schema_url: String::new(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Converts the metric value into a single data point.
+///
+/// Incremental values are sent with the delta temporality, and absolute values with the cumulative
+/// one. Non-monotonic values, such as incremental gauges, are sent as non-monotonic sums.
+fn metric_data(
+    kind: MetricKind,
+    value: &MetricValue,
+    attributes: Vec<KeyValue>,
+    time_unix_nano: u64,
+) -> Data {
+    let aggregation_temporality = match kind {
+        MetricKind::Incremental => AggregationTemporality::Delta,
+        MetricKind::Absolute => AggregationTemporality::Cumulative,
+    } as i32;
+    let number = |value: f64| NumberDataPoint {
+        attributes: attributes.clone(),
+        start_time_unix_nano: 0,
+        time_unix_nano,
+        value: Some(number_data_point::Value::AsDouble(value)),
+        exemplars: Vec::new(),
+        flags: 0,
+    };
+    let gauge = |value: f64| match kind {
+        MetricKind::Absolute => Data::Gauge(Gauge {
+            data_points: vec![number(value)],
+        }),
+        MetricKind::Incremental => Data::Sum(Sum {
+            data_points: vec![number(value)],
+            aggregation_temporality,
+            is_monotonic: false,
+        }),
+    };
+    let histogram = |point: HistogramDataPoint| {
+        Data::Histogram(Histogram {
+            data_points: vec![HistogramDataPoint {
+                attributes: attributes.clone(),
+                time_unix_nano,
+                ..point
+            }],
+            aggregation_temporality,
+        })
+    };
+    let summary = |quantile_values: Vec<ValueAtQuantile>, count: u64, sum: f64| {
+        Data::Summary(Summary {
+            data_points: vec![SummaryDataPoint {
+                attributes: attributes.clone(),
+                start_time_unix_nano: 0,
+                time_unix_nano,
+                count,
+                sum,
+                quantile_values,
+                flags: 0,
+            }],
+        })
+    };
+
+    match value {
+        MetricValue::Counter { value } => Data::Sum(Sum {
+            data_points: vec![number(*value)],
+            aggregation_temporality,
+            is_monotonic: true,
+        }),
+        MetricValue::Gauge { value } => gauge(*value),
+        MetricValue::Set { values } => gauge(values.len() as f64),
+        MetricValue::Distribution { samples, .. } => histogram(distribution_point(samples)),
+        MetricValue::AggregatedHistogram {
+            buckets,
+            count,
+            sum,
+        } => histogram(aggregated_histogram_point(buckets, *count, *sum)),
+        MetricValue::AggregatedSummary {
+            quantiles,
+            count,
+            sum,
+        } => summary(
+            quantiles.iter().map(value_at_quantile).collect(),
+            *count,
+            *sum,
+        ),
+        MetricValue::Sketch { sketch } => match sketch {
+            MetricSketch::AgentDDSketch(ddsketch) => summary(
+                SKETCH_QUANTILES
+                    .iter()
+                    .map(|&quantile| ValueAtQuantile {
+                        quantile,
+                        value: ddsketch.quantile(quantile).unwrap_or(0.0),
+                    })
+                    .collect(),
+                u64::from(ddsketch.count()),
+                ddsketch.sum().unwrap_or(0.0),
+            ),
+        },
+    }
+}
+
+/// Distributions have no buckets, so they are sent as histograms with a single bucket holding all
+/// of the samples.
+fn distribution_point(samples: &[Sample]) -> HistogramDataPoint {
+    let count = samples.iter().map(|sample| u64::from(sample.rate)).sum();
+    let values = || samples.iter().map(|sample| sample.value);
+    HistogramDataPoint {
+        count,
+        sum: Some(
+            samples
+                .iter()
+                .map(|sample| sample.value * f64::from(sample.rate))
+                .sum(),
+        ),
+        bucket_counts: vec![count],
+        min: values().reduce(f64::min),
+        max: values().reduce(f64::max),
+        ..Default::default()
+    }
+}
+
+/// The count of the overflow bucket, above the last bound, is the part of the total count not
+/// counted in any other bucket. A last bucket without upper limit is that bucket.
+fn aggregated_histogram_point(buckets: &[Bucket], count: u64, sum: f64) -> HistogramDataPoint {
+    let (explicit_bounds, mut bucket_counts): (Vec<_>, Vec<_>) = buckets
+        .iter()
+        .filter(|bucket| bucket.upper_limit.is_finite())
+        .map(|bucket| (bucket.upper_limit, bucket.count))
+        .unzip();
+    bucket_counts.push(count.saturating_sub(bucket_counts.iter().sum()));
+    HistogramDataPoint {
+        count,
+        sum: Some(sum),
+        bucket_counts,
+        explicit_bounds,
+        ..Default::default()
+    }
+}
+
+const fn value_at_quantile(quantile: &Quantile) -> ValueAtQuantile {
+    ValueAtQuantile {
+        quantile: quantile.quantile,
+        value: quantile.value,
+    }
+}
+
+/// Groups the items by resource and scope, keeping them in order within each group.
+fn group_by_resource_and_scope<T>(
+    items: impl IntoIterator<Item = (Option<OtelResource>, Option<InstrumentationScope>, T)>,
+) -> Groups<T> {
+    let mut groups: Groups<T> = Vec::new();
+    for (resource, scope, item) in items {
+        let index = match groups.iter().position(|(r, _)| *r == resource) {
+            Some(index) => index,
+            None => {
+                groups.push((resource, Vec::new()));
+                groups.len() - 1
+            }
+        };
+        let scopes = &mut groups[index].1;
+        match scopes.iter_mut().find(|(s, _)| *s == scope) {
+            Some((_, items)) => items.push(item),
+            None => scopes.push((scope, vec![item])),
+        }
+    }
+    groups
+}
+
+fn span_event_from_fields(mut fields: Fields) -> SpanEvent {
+    SpanEvent {
+        time_unix_nano: take_nanos(&mut fields, log_schema().timestamp_key()),
+        name: take_string(&mut fields, NAME_KEY),
+        attributes: take_attributes(&mut fields, ATTRIBUTES_KEY),
+        dropped_attributes_count: take_integer(&mut fields, DROPPED_ATTRIBUTES_COUNT_KEY),
+    }
+}
+
+fn link_from_fields(mut fields: Fields) -> Link {
+    Link {
+        trace_id: take_id(&mut fields, TRACE_ID_KEY),
+        span_id: take_id(&mut fields, SPAN_ID_KEY),
+        trace_state: take_string(&mut fields, TRACE_STATE_KEY),
+        attributes: take_attributes(&mut fields, ATTRIBUTES_KEY),
+        dropped_attributes_count: take_integer(&mut fields, DROPPED_ATTRIBUTES_COUNT_KEY),
+    }
+}
+
+fn take_resource(fields: &mut Fields) -> Option<OtelResource> {
+    let attributes = take_attributes(fields, RESOURCE_KEY);
+    (!attributes.is_empty()).then(|| OtelResource {
+        attributes,
+        dropped_attributes_count: 0,
+    })
+}
+
+fn take_scope(fields: &mut Fields) -> Option<InstrumentationScope> {
+    match fields.remove(SCOPE_KEY) {
+        Some(Value::Object(mut scope)) => Some(InstrumentationScope {
+            name: take_string(&mut scope, NAME_KEY),
+            version: take_string(&mut scope, VERSION_KEY),
+            attributes: take_attributes(&mut scope, ATTRIBUTES_KEY),
+            dropped_attributes_count: 0,
+        }),
+        _ => None,
+    }
+}
+
+/// Takes the attributes, adding the remaining fields to them.
+fn take_attributes_and_rest(mut fields: Fields) -> Vec<KeyValue> {
+    let mut attributes = take_attributes(&mut fields, ATTRIBUTES_KEY);
+    attributes.extend(object_into_kv_list(fields));
+    attributes
+}
+
+fn take_attributes(fields: &mut Fields, key: &str) -> Vec<KeyValue> {
+    match fields.remove(key) {
+        Some(Value::Object(attributes)) => object_into_kv_list(attributes),
+        _ => Vec::new(),
+    }
+}
+
+fn take_objects(fields: &mut Fields, key: &str) -> impl Iterator<Item = Fields> {
+    let values = match fields.remove(key) {
+        Some(Value::Array(values)) => values,
+        _ => Vec::new(),
+    };
+    values.into_iter().filter_map(|value| match value {
+        Value::Object(fields) => Some(fields),
+        _ => None,
+    })
+}
+
+fn take_string(fields: &mut Fields, key: &str) -> String {
+    fields
+        .remove(key)
+        .map(|value| value.to_string_lossy())
+        .unwrap_or_default()
+}
+
+/// Takes an integer field, defaulting to zero if it's missing or out of range.
+fn take_integer<T: TryFrom<i64> + Default>(fields: &mut Fields, key: &str) -> T {
+    match fields.remove(key) {
+        Some(Value::Integer(value)) => T::try_from(value).unwrap_or_default(),
+        _ => T::default(),
+    }
+}
+
+fn take_nanos(fields: &mut Fields, key: &str) -> u64 {
+    fields.remove(key).as_ref().map_or(0, value_into_nanos)
+}
+
+/// Takes a hex encoded id, as trace and span ids are encoded when received.
+fn take_id(fields: &mut Fields, key: &str) -> Vec<u8> {
+    fields
+        .remove(key)
+        .and_then(|value| hex::decode(value.to_string_lossy()).ok())
+        .unwrap_or_default()
+}
+
+/// Converts a timestamp into nanoseconds since the epoch, 0 representing a missing timestamp.
+fn value_into_nanos(value: &Value) -> u64 {
+    match value {
+        Value::Timestamp(timestamp) => datetime_into_nanos(timestamp),
+        _ => 0,
+    }
+}
+
+fn datetime_into_nanos(timestamp: &DateTime<Utc>) -> u64 {
+    u64::try_from(timestamp.timestamp_nanos()).unwrap_or(0)
+}
+
+fn string_attribute(key: &str, value: &str) -> KeyValue {
+    KeyValue {
+        key: key.to_owned(),
+        value: Some(AnyValue {
+            value: Some(PBValue::StringValue(value.to_owned())),
+        }),
+    }
+}
+
+fn object_into_kv_list(object: Fields) -> Vec<KeyValue> {
+    object
+        .into_iter()
+        .map(|(key, value)| KeyValue {
+            key,
+            value: Some(value_into_any_value(value)),
+        })
+        .collect()
+}
+
+fn value_into_any_value(value: Value) -> AnyValue {
+    let value = match value {
+        Value::Bytes(bytes) => Some(PBValue::StringValue(
+            String::from_utf8_lossy(&bytes).into_owned(),
+        )),
+        Value::Regex(regex) => Some(PBValue::StringValue(regex.as_str().to_owned())),
+        Value::Integer(value) => Some(PBValue::IntValue(value)),
+        Value::Float(value) => Some(PBValue::DoubleValue(value.into_inner())),
+        Value::Boolean(value) => Some(PBValue::BoolValue(value)),
+        Value::Timestamp(timestamp) => Some(PBValue::StringValue(
+            timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+        )),
+        Value::Object(object) => Some(PBValue::KvlistValue(KeyValueList {
+            values: object_into_kv_list(object),
+        })),
+        Value::Array(values) => Some(PBValue::ArrayValue(ArrayValue {
+            values: values.into_iter().map(value_into_any_value).collect(),
+        })),
+        Value::Null => None,
+    };
+    AnyValue { value }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use vector_core::event::{metric::MetricTags, Event};
+
+    use super::*;
+
+    fn roundtrip_logs(log: LogEvent) -> Vec<Event> {
+        let request = ExportLogsServiceRequest::from_iter([log]);
+        request
+            .resource_logs
+            .into_iter()
+            .flat_map(IntoIterator::into_iter)
+            .collect()
+    }
+
+    #[test]
+    fn encodes_logs() {
+        let mut log = LogEvent::from("hello");
+        log.insert("resources.service", "api");
+        log.insert("scope.name", "library");
+        log.insert("attributes.key", "value");
+        log.insert("trace_id", "4ac52aadf321c2e531db005df08792f5");
+        log.insert("severity_number", 9);
+        log.insert("host", "localhost");
+        log.insert(log_schema().timestamp_key(), Utc.timestamp_nanos(1));
+
+        let request = ExportLogsServiceRequest::from_iter([log]);
+        let resource_logs = &request.resource_logs[0];
+        assert_eq!(
+            resource_logs.resource.as_ref().unwrap().attributes,
+            vec![string_attribute("service", "api")]
+        );
+        let scope_logs = &resource_logs.scope_logs[0];
+        assert_eq!(scope_logs.scope.as_ref().unwrap().name, "library");
+        let record = &scope_logs.log_records[0];
+        assert_eq!(
+            record.body.clone().and_then(|body| body.value),
+            Some(PBValue::StringValue("hello".to_owned()))
+        );
+        assert_eq!(record.time_unix_nano, 1);
+        assert_eq!(record.severity_number, 9);
+        assert_eq!(
+            hex::encode(&record.trace_id),
+            "4ac52aadf321c2e531db005df08792f5"
+        );
+        assert_eq!(
+            record.attributes,
+            vec![
+                string_attribute("key", "value"),
+                string_attribute("host", "localhost"),
+            ]
+        );
+    }
+
+    #[test]
+    fn roundtrips_logs() {
+        let mut log = LogEvent::from("hello");
+        log.insert("resources.service", "api");
+        log.insert("attributes.key", "value");
+        log.insert("span_id", "0b9e4bda2a55530d");
+        log.insert("severity_text", "info");
+        log.insert("dropped_attributes_count", 0);
+        log.insert(log_schema().timestamp_key(), Utc.timestamp_nanos(1));
+        log.insert("observed_timestamp", Utc.timestamp_nanos(2));
+
+        assert_eq!(roundtrip_logs(log.clone()), vec![Event::from(log)]);
+    }
+
+    #[test]
+    fn groups_by_resource_and_scope() {
+        let logs = ["a", "b", "a"].into_iter().map(|service| {
+            let mut log = LogEvent::from("hello");
+            log.insert("resources.service", service);
+            log
+        });
+
+        let request = ExportLogsServiceRequest::from_iter(logs);
+        assert_eq!(request.resource_logs.len(), 2);
+        assert_eq!(request.resource_logs[0].scope_logs[0].log_records.len(), 2);
+        assert_eq!(request.resource_logs[1].scope_logs[0].log_records.len(), 1);
+    }
+
+    #[test]
+    fn roundtrips_traces() {
+        let mut trace = TraceEvent::from(BTreeMap::new());
+        trace.insert("trace_id", "4ac52aadf321c2e531db005df08792f5");
+        trace.insert("span_id", "0b9e4bda2a55530d");
+        trace.insert("name", "span");
+        trace.insert("kind", 2);
+        trace.insert("scope.name", "library");
+        trace.insert("start_timestamp", Utc.timestamp_nanos(1));
+        trace.insert("status.code", 2);
+        trace.insert("dropped_attributes_count", 0);
+        trace.insert("dropped_events_count", 0);
+        trace.insert("dropped_links_count", 0);
+
+        let request = ExportTraceServiceRequest::from_iter([trace.clone()]);
+        let events = request
+            .resource_spans
+            .into_iter()
+            .flat_map(IntoIterator::into_iter)
+            .collect::<Vec<_>>();
+        assert_eq!(events, vec![Event::from(trace)]);
+    }
+
+    #[test]
+    fn encodes_metrics() {
+        let tags = MetricTags::from([
+            ("resources.service".to_owned(), "api".to_owned()),
+            ("scope.name".to_owned(), "library".to_owned()),
+            ("host".to_owned(), "localhost".to_owned()),
+        ]);
+        let metric = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 3.0 },
+        )
+        .with_namespace(Some("app"))
+        .with_tags(Some(tags))
+        .with_timestamp(Some(Utc.timestamp_nanos(1)));
+
+        let request = ExportMetricsServiceRequest::from_iter([metric]);
+        let resource_metrics = &request.resource_metrics[0];
+        assert_eq!(
+            resource_metrics.resource.as_ref().unwrap().attributes,
+            vec![string_attribute("service", "api")]
+        );
+        let scope_metrics = &resource_metrics.scope_metrics[0];
+        assert_eq!(scope_metrics.scope.as_ref().unwrap().name, "library");
+        let metric = &scope_metrics.metrics[0];
+        assert_eq!(metric.name, "app.requests");
+        match &metric.data {
+            Some(Data::Sum(sum)) => {
+                assert!(sum.is_monotonic);
+                assert_eq!(
+                    sum.aggregation_temporality,
+                    AggregationTemporality::Delta as i32
+                );
+                let point = &sum.data_points[0];
+                assert_eq!(point.time_unix_nano, 1);
+                assert_eq!(point.value, Some(number_data_point::Value::AsDouble(3.0)));
+                assert_eq!(
+                    point.attributes,
+                    vec![string_attribute("host", "localhost")]
+                );
+            }
+            data => panic!("unexpected data: {:?}", data),
+        }
+    }
+
+    #[test]
+    fn encodes_aggregated_histograms() {
+        let point = aggregated_histogram_point(
+            &[
+                Bucket {
+                    upper_limit: 1.0,
+                    count: 2,
+                },
+                Bucket {
+                    upper_limit: 2.0,
+                    count: 3,
+                },
+                Bucket {
+                    upper_limit: f64::INFINITY,
+                    count: 1,
+                },
+            ],
+            6,
+            10.0,
+        );
+        assert_eq!(point.explicit_bounds, vec![1.0, 2.0]);
+        assert_eq!(point.bucket_counts, vec![2, 3, 1]);
+        assert_eq!(point.count, 6);
+        assert_eq!(point.sum, Some(10.0));
+    }
+}
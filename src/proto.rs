@@ -3,3 +3,6 @@ use crate::event::proto as event;
 
 #[cfg(any(feature = "sources-vector", feature = "sinks-vector"))]
 pub mod vector;
+
+#[cfg(any(feature = "sources-vector", feature = "sinks-vector"))]
+pub mod v1;
@@ -0,0 +1,145 @@
+//! Acknowledgements and compression of version one of the `vector` protocol.
+//!
+//! When acknowledgements are enabled, the `vector` source answers each length-delimited event
+//! frame with a single byte holding the delivery status of the event.
+//!
+//! When compression is enabled, the `vector` sink starts each connection with a handshake frame
+//! holding `HANDSHAKE_MARKER` followed by the requested `FrameCompression`. The source answers with
+//! a single byte holding the compression it accepts, after which every event frame starts with a
+//! byte holding the compression applied to the rest of the frame.
+
+use std::io::{self, Read, Write};
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+
+use crate::event::EventStatus;
+
+/// The first byte of the handshake frame.
+///
+/// This is not a valid Protocol Buffers field tag, so sources not supporting compression reject the
+/// handshake instead of decoding it as an event.
+pub const HANDSHAKE_MARKER: u8 = 0;
+
+/// The delivery status sent back by the `vector` source for each event.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum AckStatus {
+    /// The event was delivered.
+    Delivered = 0,
+
+    /// The event could not be delivered, but may be retried.
+    Errored = 1,
+
+    /// The event was rejected by a sink.
+    Rejected = 2,
+}
+
+impl AckStatus {
+    /// Parses the status from the byte sent by the `vector` source.
+    pub const fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Self::Delivered),
+            1 => Some(Self::Errored),
+            2 => Some(Self::Rejected),
+            _ => None,
+        }
+    }
+
+    /// The byte sent by the `vector` source for this status.
+    pub const fn as_byte(self) -> u8 {
+        self as u8
+    }
+}
+
+impl From<AckStatus> for EventStatus {
+    fn from(status: AckStatus) -> Self {
+        match status {
+            AckStatus::Delivered => Self::Delivered,
+            AckStatus::Errored => Self::Errored,
+            AckStatus::Rejected => Self::Rejected,
+        }
+    }
+}
+
+/// The compression applied to an event frame.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum FrameCompression {
+    /// The frame is not compressed.
+    None = 0,
+
+    /// The frame is compressed with gzip.
+    Gzip = 1,
+
+    /// The frame is compressed with zstd.
+    Zstd = 2,
+}
+
+impl FrameCompression {
+    /// Parses the compression from the byte preceding a frame, or ending the handshake.
+    pub const fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Self::None),
+            1 => Some(Self::Gzip),
+            2 => Some(Self::Zstd),
+            _ => None,
+        }
+    }
+
+    /// The byte sent for this compression.
+    pub const fn as_byte(self) -> u8 {
+        self as u8
+    }
+
+    /// Compresses the payload of a frame.
+    pub fn compress(self, payload: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            Self::None => Ok(payload.to_vec()),
+            Self::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(payload)?;
+                encoder.finish()
+            }
+            Self::Zstd => zstd::stream::encode_all(payload, 0),
+        }
+    }
+
+    /// Decompresses the payload of a frame.
+    pub fn decompress(self, payload: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            Self::None => Ok(payload.to_vec()),
+            Self::Gzip => {
+                let mut decompressed = Vec::new();
+                GzDecoder::new(payload).read_to_end(&mut decompressed)?;
+                Ok(decompressed)
+            }
+            Self::Zstd => zstd::stream::decode_all(payload),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_compression_round_trip() {
+        let payload = b"event payload ".repeat(100);
+        for compression in [
+            FrameCompression::None,
+            FrameCompression::Gzip,
+            FrameCompression::Zstd,
+        ] {
+            assert_eq!(
+                FrameCompression::from_byte(compression.as_byte()),
+                Some(compression)
+            );
+
+            let compressed = compression.compress(&payload).unwrap();
+            if compression != FrameCompression::None {
+                assert!(compressed.len() < payload.len());
+            }
+            assert_eq!(compression.decompress(&compressed).unwrap(), payload);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use std::convert::TryFrom;
+
+use futures::FutureExt;
+use lapin::options::ConfirmSelectOptions;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    request_builder::AmqpRequestBuilder,
+    service::{AmqpRetryLogic, AmqpService},
+    sink::AmqpSink,
+};
+use crate::{
+    amqp::AmqpConfig,
+    codecs::{Encoder, EncodingConfig},
+    config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
+    sinks::{util::TowerRequestConfig, Healthcheck, VectorSink},
+    template::Template,
+    tls::MaybeTlsSettings,
+};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AmqpSinkConfig {
+    #[serde(flatten)]
+    pub(super) connection: AmqpConfig,
+    /// The exchange to publish messages to, where an empty string is the default exchange.
+    pub(super) exchange: String,
+    /// The routing key of the messages, which defaults to an empty string.
+    pub(super) routing_key: Option<String>,
+    pub(super) encoding: EncodingConfig,
+    #[serde(default)]
+    pub(super) request: TowerRequestConfig,
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub(super) acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for AmqpSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"connection_string = "amqp://localhost:5672/%2f"
+            exchange = "vector"
+            routing_key = "{{ host }}"
+            encoding.codec = "json""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "amqp")]
+impl SinkConfig for AmqpSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let tls = self.connection.tls_settings()?;
+        let (connection, channel) = self.connection.connect(&tls).await?;
+        // Have the broker confirm each message, so that events are only delivered once their
+        // message is.
+        channel
+            .confirm_select(ConfirmSelectOptions::default())
+            .await?;
+
+        let transformer = self.encoding.transformer();
+        let serializer = self.encoding.build()?;
+        let request_builder = AmqpRequestBuilder {
+            exchange: Template::try_from(self.exchange.as_str())?,
+            routing_key: self
+                .routing_key
+                .as_deref()
+                .map(Template::try_from)
+                .transpose()?,
+            transformer,
+            encoder: Encoder::<()>::new(serializer),
+        };
+        let sink = AmqpSink {
+            request_builder,
+            service: AmqpService::new(connection, channel),
+            request: self.request,
+            retry_logic: AmqpRetryLogic,
+        };
+
+        let healthcheck = healthcheck(self.connection.clone(), tls).boxed();
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(self.encoding.config().input_type() & (DataType::Log | DataType::Metric))
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "amqp"
+    }
+
+    fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
+        Some(&self.acknowledgements)
+    }
+}
+
+async fn healthcheck(config: AmqpConfig, tls: MaybeTlsSettings) -> crate::Result<()> {
+    let (connection, _channel) = config.connect(&tls).await?;
+    connection.close(200, "Healthcheck done").await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AmqpSinkConfig>();
+    }
+}
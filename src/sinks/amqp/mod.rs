@@ -0,0 +1,27 @@
+use snafu::Snafu;
+
+use crate::config::SinkDescription;
+
+mod config;
+mod request_builder;
+mod service;
+mod sink;
+
+#[cfg(feature = "amqp-integration-tests")]
+#[cfg(test)]
+mod integration_tests;
+
+pub use self::config::AmqpSinkConfig;
+
+inventory::submit! {
+    SinkDescription::new::<AmqpSinkConfig>("amqp")
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum AmqpError {
+    #[snafu(display("Failed to publish message: {}", source))]
+    Publish { source: lapin::Error },
+
+    #[snafu(display("The broker did not confirm the message"))]
+    Nack,
+}
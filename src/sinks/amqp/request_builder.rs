@@ -0,0 +1,121 @@
+use bytes::{Bytes, BytesMut};
+use tokio_util::codec::Encoder as _;
+use vector_core::ByteSizeOf;
+
+use super::service::AmqpRequest;
+use crate::{
+    codecs::{Encoder, Transformer},
+    event::{Event, EventStatus, Finalizable},
+    internal_events::TemplateRenderingError,
+    template::Template,
+};
+
+pub(super) struct AmqpRequestBuilder {
+    pub(super) exchange: Template,
+    pub(super) routing_key: Option<Template>,
+    pub(super) transformer: Transformer,
+    pub(super) encoder: Encoder<()>,
+}
+
+impl AmqpRequestBuilder {
+    /// Builds the message of the event, or drops the event if its exchange or routing key can't
+    /// be rendered or it can't be encoded.
+    pub(super) fn build_request(&mut self, mut event: Event) -> Option<AmqpRequest> {
+        let finalizers = event.take_finalizers();
+
+        let exchange = render(&self.exchange, &event, "exchange");
+        let routing_key = match &self.routing_key {
+            Some(routing_key) => render(routing_key, &event, "routing_key"),
+            None => Some(String::new()),
+        };
+        let (exchange, routing_key) = match (exchange, routing_key) {
+            (Some(exchange), Some(routing_key)) => (exchange, routing_key),
+            _ => {
+                finalizers.update_status(EventStatus::Errored);
+                return None;
+            }
+        };
+
+        let event_byte_size = event.size_of();
+        self.transformer.transform(&mut event);
+        let mut body = BytesMut::new();
+        if self.encoder.encode(event, &mut body).is_err() {
+            // Error is handled by `Encoder`.
+            finalizers.update_status(EventStatus::Errored);
+            return None;
+        }
+
+        Some(AmqpRequest {
+            body: Bytes::from(body),
+            exchange,
+            routing_key,
+            finalizers,
+            event_byte_size,
+        })
+    }
+}
+
+fn render(template: &Template, event: &Event, field: &'static str) -> Option<String> {
+    template
+        .render_string(event)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some(field),
+                drop_event: true,
+            });
+        })
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use codecs::TextSerializer;
+    use vector_core::event::{BatchNotifier, BatchStatus, LogEvent};
+
+    use super::*;
+
+    fn request_builder(exchange: &str, routing_key: Option<&str>) -> AmqpRequestBuilder {
+        AmqpRequestBuilder {
+            exchange: Template::try_from(exchange).unwrap(),
+            routing_key: routing_key.map(|routing_key| Template::try_from(routing_key).unwrap()),
+            transformer: Default::default(),
+            encoder: Encoder::<()>::new(TextSerializer::new().into()),
+        }
+    }
+
+    #[test]
+    fn renders_exchange_and_routing_key() {
+        let mut event = LogEvent::from("hello");
+        event.insert("app", "billing");
+        let request = request_builder("logs", Some("apps.{{ app }}"))
+            .build_request(event.into())
+            .unwrap();
+
+        assert_eq!(request.exchange, "logs");
+        assert_eq!(request.routing_key, "apps.billing");
+        assert_eq!(request.body, Bytes::from("hello"));
+    }
+
+    #[test]
+    fn defaults_to_empty_routing_key() {
+        let request = request_builder("", None)
+            .build_request(LogEvent::from("hello").into())
+            .unwrap();
+
+        assert_eq!(request.exchange, "");
+        assert_eq!(request.routing_key, "");
+    }
+
+    #[test]
+    fn drops_event_without_routing_key() {
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let event = LogEvent::from("hello").with_batch_notifier(&batch);
+        drop(batch);
+
+        let request = request_builder("logs", Some("apps.{{ app }}")).build_request(event.into());
+
+        assert!(request.is_none());
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Errored));
+    }
+}
@@ -0,0 +1,131 @@
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use lapin::{
+    options::BasicPublishOptions, publisher_confirm::Confirmation, BasicProperties, Channel,
+    Connection,
+};
+use snafu::ResultExt;
+use tower::Service;
+use vector_core::{
+    internal_event::{BytesSent, EventsSent},
+    stream::DriverResponse,
+};
+
+use super::{AmqpError, PublishSnafu};
+use crate::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    sinks::util::retries::RetryLogic,
+};
+
+#[derive(Clone)]
+pub(super) struct AmqpRequest {
+    pub(super) body: Bytes,
+    pub(super) exchange: String,
+    pub(super) routing_key: String,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) event_byte_size: usize,
+}
+
+impl Finalizable for AmqpRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+pub(super) struct AmqpResponse {
+    byte_size: usize,
+    event_byte_size: usize,
+}
+
+impl DriverResponse for AmqpResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> EventsSent {
+        EventsSent {
+            count: 1,
+            byte_size: self.event_byte_size,
+            output: None,
+        }
+    }
+
+    fn bytes_sent(&self) -> Option<BytesSent> {
+        Some(BytesSent {
+            byte_size: self.byte_size,
+            protocol: "amqp_0_9_1",
+        })
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct AmqpService {
+    // Held so the connection stays open as long as the channel is used.
+    _connection: Arc<Connection>,
+    channel: Channel,
+}
+
+impl AmqpService {
+    pub(super) fn new(connection: Connection, channel: Channel) -> Self {
+        Self {
+            _connection: Arc::new(connection),
+            channel,
+        }
+    }
+}
+
+impl Service<AmqpRequest> for AmqpService {
+    type Response = AmqpResponse;
+    type Error = AmqpError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: AmqpRequest) -> Self::Future {
+        let channel = self.channel.clone();
+
+        Box::pin(async move {
+            let confirmation = channel
+                .basic_publish(
+                    &request.exchange,
+                    &request.routing_key,
+                    BasicPublishOptions::default(),
+                    &request.body,
+                    BasicProperties::default(),
+                )
+                .await
+                .context(PublishSnafu)?
+                .await
+                .context(PublishSnafu)?;
+
+            match confirmation {
+                Confirmation::Nack(_) => Err(AmqpError::Nack),
+                Confirmation::Ack(_) | Confirmation::NotRequested => Ok(AmqpResponse {
+                    byte_size: request.body.len(),
+                    event_byte_size: request.event_byte_size,
+                }),
+            }
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct AmqpRetryLogic;
+
+impl RetryLogic for AmqpRetryLogic {
+    type Error = AmqpError;
+    type Response = AmqpResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        // Publishing fails once the channel is closed, such as when the exchange doesn't exist,
+        // which retrying doesn't recover from.
+        matches!(error, AmqpError::Nack)
+    }
+}
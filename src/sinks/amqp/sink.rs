@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+use futures::{future, stream::BoxStream, StreamExt};
+use tower::ServiceBuilder;
+use vector_core::sink::StreamSink;
+
+use super::{
+    request_builder::AmqpRequestBuilder,
+    service::{AmqpRetryLogic, AmqpService},
+};
+use crate::{
+    event::Event,
+    sinks::util::{builder::SinkBuilderExt, ServiceBuilderExt, TowerRequestConfig},
+};
+
+pub(super) struct AmqpSink {
+    pub(super) request_builder: AmqpRequestBuilder,
+    pub(super) service: AmqpService,
+    pub(super) request: TowerRequestConfig,
+    pub(super) retry_logic: AmqpRetryLogic,
+}
+
+impl AmqpSink {
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let request = self.request.unwrap_with(&TowerRequestConfig::default());
+        let service = ServiceBuilder::new()
+            .settings(request, self.retry_logic)
+            .service(self.service);
+
+        // Each event is published as its own message, with the messages in flight awaiting the
+        // confirmation of the broker concurrently.
+        let mut request_builder = self.request_builder;
+        input
+            .filter_map(|event| future::ready(request_builder.build_request(event)))
+            .into_driver(service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for AmqpSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
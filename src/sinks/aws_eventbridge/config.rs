@@ -0,0 +1,188 @@
+use aws_sdk_eventbridge::Client as EventBridgeClient;
+use futures::FutureExt;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    request_builder::{EventBridgeEncoder, EventBridgeRequestBuilder},
+    service::EventBridgeService,
+    sink::EventBridgeSink,
+};
+use crate::{
+    aws::{create_client, AwsAuthentication, ClientBuilder, RegionOrEndpoint},
+    codecs::Transformer,
+    config::{AcknowledgementsConfig, GenerateConfig, Input, ProxyConfig, SinkConfig, SinkContext},
+    sinks::{
+        util::{BatchConfig, SinkBatchSettings, TowerRequestConfig},
+        Healthcheck, VectorSink,
+    },
+    template::Template,
+    tls::TlsConfig,
+};
+
+/// The maximum number of entries of a `PutEvents` request.
+pub(super) const MAX_ENTRIES: usize = 10;
+
+/// The maximum total size of the entries of a `PutEvents` request.
+pub(super) const MAX_REQUEST_SIZE: usize = 256_000;
+
+pub(super) struct EventBridgeClientBuilder;
+
+impl ClientBuilder for EventBridgeClientBuilder {
+    type Config = aws_sdk_eventbridge::config::Config;
+    type Client = aws_sdk_eventbridge::client::Client;
+    type DefaultMiddleware = aws_sdk_eventbridge::middleware::DefaultMiddleware;
+
+    fn default_middleware() -> Self::DefaultMiddleware {
+        aws_sdk_eventbridge::middleware::DefaultMiddleware::new()
+    }
+
+    fn build(client: aws_smithy_client::Client, config: &aws_types::SdkConfig) -> Self::Client {
+        aws_sdk_eventbridge::client::Client::with_config(client, config.into())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EventBridgeDefaultBatchSettings;
+
+impl SinkBatchSettings for EventBridgeDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(MAX_ENTRIES);
+    const MAX_BYTES: Option<usize> = Some(MAX_REQUEST_SIZE);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AwsEventBridgeSinkConfig {
+    /// The name or ARN of the event bus the events are published to, such as `my-bus`.
+    ///
+    /// By default, the events are published to the default event bus of the account.
+    pub(super) event_bus: Option<Template>,
+    /// The source of the EventBridge events, such as `com.example.orders`.
+    pub(super) source: Template,
+    /// The detail type of the EventBridge events, such as `Order Created`.
+    pub(super) detail_type: Template,
+    #[serde(flatten)]
+    pub(super) region: RegionOrEndpoint,
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub(super) encoding: Transformer,
+    #[serde(default)]
+    pub(super) batch: BatchConfig<EventBridgeDefaultBatchSettings>,
+    #[serde(default)]
+    pub(super) request: TowerRequestConfig,
+    pub(super) tls: Option<TlsConfig>,
+    #[serde(default)]
+    pub(super) auth: AwsAuthentication,
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub(super) acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for AwsEventBridgeSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"region = "us-east-1"
+            source = "com.example.vector"
+            detail_type = "log""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "aws_eventbridge")]
+impl SinkConfig for AwsEventBridgeSinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let batch_settings = self
+            .batch
+            .validate()?
+            .limit_max_events(MAX_ENTRIES)?
+            .limit_max_bytes(MAX_REQUEST_SIZE)?
+            .into_batcher_settings()?;
+
+        let client = self.create_client(&cx.proxy).await?;
+        let healthcheck = healthcheck(client.clone(), self.event_bus.clone()).boxed();
+
+        let sink = EventBridgeSink {
+            service: EventBridgeService::new(client),
+            request: self.request,
+            request_builder: EventBridgeRequestBuilder {
+                encoder: EventBridgeEncoder {
+                    transformer: self.encoding.clone(),
+                },
+            },
+            event_bus: self.event_bus.clone(),
+            source: self.source.clone(),
+            detail_type: self.detail_type.clone(),
+            batch_settings,
+        };
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "aws_eventbridge"
+    }
+
+    fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
+        Some(&self.acknowledgements)
+    }
+}
+
+impl AwsEventBridgeSinkConfig {
+    async fn create_client(&self, proxy: &ProxyConfig) -> crate::Result<EventBridgeClient> {
+        create_client::<EventBridgeClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region.endpoint()?,
+            proxy,
+            &self.tls,
+            true,
+        )
+        .await
+    }
+}
+
+/// Checks that the event bus exists, when it isn't rendered from the events.
+async fn healthcheck(client: EventBridgeClient, event_bus: Option<Template>) -> crate::Result<()> {
+    let name = match &event_bus {
+        Some(template) if template.is_dynamic() => return Ok(()),
+        Some(template) => template.get_ref().to_owned(),
+        None => "default".to_owned(),
+    };
+    client.describe_event_bus().name(name).send().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AwsEventBridgeSinkConfig>();
+    }
+
+    #[tokio::test]
+    async fn rejects_batches_over_api_limits() {
+        let config: AwsEventBridgeSinkConfig = toml::from_str(
+            r#"
+            region = "us-east-1"
+            source = "com.example.vector"
+            detail_type = "log"
+            batch.max_events = 20
+            "#,
+        )
+        .unwrap();
+
+        assert!(config.build(SinkContext::new_test()).await.is_err());
+    }
+}
@@ -0,0 +1,19 @@
+//! The `aws_eventbridge` sink.
+//!
+//! Events are published to Amazon EventBridge event buses with `PutEvents`, as the `detail` of
+//! the EventBridge events, whose source, detail type and event bus are rendered from templates.
+//! EventBridge reports the outcome of each entry of a request separately, so the entries that
+//! failed with a transient error are retried on their own, without publishing the others again.
+
+use crate::config::SinkDescription;
+
+mod config;
+mod request_builder;
+mod service;
+mod sink;
+
+pub use self::config::AwsEventBridgeSinkConfig;
+
+inventory::submit! {
+    SinkDescription::new::<AwsEventBridgeSinkConfig>("aws_eventbridge")
+}
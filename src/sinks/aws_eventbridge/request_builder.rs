@@ -0,0 +1,180 @@
+use std::io;
+
+use aws_sdk_eventbridge::model::PutEventsRequestEntry;
+use aws_smithy_types::DateTime;
+use bytes::Bytes;
+use vector_core::ByteSizeOf;
+
+use crate::{
+    codecs::Transformer,
+    event::{Event, EventFinalizers, Finalizable, LogEvent},
+    sinks::util::{
+        encoding::Encoder, processed_event::ProcessedEvent, request_builder::EncodeResult,
+        Compression, RequestBuilder,
+    },
+};
+
+/// The size EventBridge counts for the time of an entry, whatever its value.
+const TIME_SIZE: usize = 14;
+
+pub(super) type EventBridgeProcessedEvent = ProcessedEvent<LogEvent, EventBridgeMetadata>;
+
+/// The attributes of the EventBridge event, rendered from the log.
+pub(super) struct EventBridgeMetadata {
+    pub(super) event_bus: Option<String>,
+    pub(super) source: String,
+    pub(super) detail_type: String,
+    pub(super) time: Option<DateTime>,
+}
+
+/// Encodes the logs as JSON objects, which is the only format accepted for the detail of the
+/// EventBridge events.
+#[derive(Clone)]
+pub(super) struct EventBridgeEncoder {
+    pub(super) transformer: Transformer,
+}
+
+impl Encoder<Event> for EventBridgeEncoder {
+    fn encode_input(&self, mut event: Event, writer: &mut dyn io::Write) -> io::Result<usize> {
+        self.transformer.transform(&mut event);
+        let detail = serde_json::to_vec(event.as_log())?;
+        writer.write_all(&detail).map(|()| detail.len())
+    }
+}
+
+pub(super) struct Metadata {
+    attributes: EventBridgeMetadata,
+    finalizers: EventFinalizers,
+    event_byte_size: usize,
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct EventBridgeRequest {
+    pub(super) entry: PutEventsRequestEntry,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) event_byte_size: usize,
+}
+
+impl Finalizable for EventBridgeRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl EventBridgeRequest {
+    /// The size of the entry as counted by EventBridge towards the limit of the requests.
+    fn entry_size(&self) -> usize {
+        let len = |value: &Option<String>| value.as_ref().map_or(0, String::len);
+        len(&self.entry.source)
+            + len(&self.entry.detail_type)
+            + len(&self.entry.detail)
+            + self
+                .entry
+                .resources
+                .as_ref()
+                .map_or(0, |resources| resources.iter().map(String::len).sum())
+            + if self.entry.time.is_some() {
+                TIME_SIZE
+            } else {
+                0
+            }
+    }
+}
+
+impl ByteSizeOf for EventBridgeRequest {
+    fn size_of(&self) -> usize {
+        // As for the `aws_kinesis_streams` sink, the batcher is given the size of the entries as
+        // EventBridge counts it, so that the batches don't exceed the limit of the requests.
+        self.entry_size()
+    }
+
+    fn allocated_bytes(&self) -> usize {
+        0
+    }
+}
+
+pub(super) struct EventBridgeRequestBuilder {
+    pub(super) encoder: EventBridgeEncoder,
+}
+
+impl RequestBuilder<EventBridgeProcessedEvent> for EventBridgeRequestBuilder {
+    type Metadata = Metadata;
+    type Events = Event;
+    type Encoder = EventBridgeEncoder;
+    type Payload = Bytes;
+    type Request = EventBridgeRequest;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        Compression::None
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(&self, mut input: EventBridgeProcessedEvent) -> (Self::Metadata, Self::Events) {
+        let metadata = Metadata {
+            attributes: input.metadata,
+            finalizers: input.event.take_finalizers(),
+            event_byte_size: input.event.size_of(),
+        };
+        (metadata, Event::from(input.event))
+    }
+
+    fn build_request(
+        &self,
+        metadata: Self::Metadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        let payload = payload.into_payload();
+        let attributes = metadata.attributes;
+        EventBridgeRequest {
+            entry: PutEventsRequestEntry::builder()
+                .set_event_bus_name(attributes.event_bus)
+                .source(attributes.source)
+                .detail_type(attributes.detail_type)
+                .detail(String::from_utf8_lossy(&payload))
+                .set_time(attributes.time)
+                .build(),
+            finalizers: metadata.finalizers,
+            event_byte_size: metadata.event_byte_size,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_entry_size_as_eventbridge() {
+        let request = EventBridgeRequest {
+            entry: PutEventsRequestEntry::builder()
+                .event_bus_name("my-bus")
+                .source("com.example")
+                .detail_type("Order")
+                .detail(r#"{"id":1}"#)
+                .time(DateTime::from_secs(1_664_627_400))
+                .build(),
+            finalizers: Default::default(),
+            event_byte_size: 0,
+        };
+
+        assert_eq!(request.size_of(), 11 + 5 + 8 + 14);
+    }
+
+    #[test]
+    fn encodes_logs_as_json_objects() {
+        let encoder = EventBridgeEncoder {
+            transformer: Default::default(),
+        };
+        let mut log = LogEvent::default();
+        log.insert("id", 1);
+
+        let mut detail = Vec::new();
+        encoder.encode_input(Event::from(log), &mut detail).unwrap();
+
+        assert_eq!(detail, br#"{"id":1}"#);
+    }
+}
@@ -0,0 +1,262 @@
+use std::{
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use aws_sdk_eventbridge::{
+    error::PutEventsError, model::PutEventsResultEntry, types::SdkError,
+    Client as EventBridgeClient,
+};
+use futures::future::BoxFuture;
+use tower::Service;
+use tracing::Instrument;
+use vector_core::{internal_event::EventsSent, stream::DriverResponse};
+
+use super::request_builder::EventBridgeRequest;
+use crate::{
+    aws::is_retriable_error,
+    event::{EventFinalizers, EventStatus, Finalizable},
+    sinks::util::retries::{RetryAction, RetryLogic},
+};
+
+/// The error codes of the entries that may succeed when published again.
+const RETRIABLE_ENTRY_ERRORS: &[&str] = &["InternalFailure", "ThrottlingException"];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum EntryStatus {
+    Pending,
+    Published,
+    Rejected,
+}
+
+/// The entries of a `PutEvents` request.
+///
+/// The status of the entries is shared by the retries of the batch, which only publish the
+/// entries that are still pending.
+#[derive(Clone, Debug)]
+pub(super) struct PutEventsBatch {
+    requests: Vec<EventBridgeRequest>,
+    statuses: Arc<Mutex<Vec<EntryStatus>>>,
+}
+
+impl PutEventsBatch {
+    pub(super) fn new(requests: Vec<EventBridgeRequest>) -> Self {
+        let statuses = vec![EntryStatus::Pending; requests.len()];
+        Self {
+            requests,
+            statuses: Arc::new(Mutex::new(statuses)),
+        }
+    }
+
+    fn pending(&self) -> Vec<usize> {
+        let statuses = self.statuses.lock().expect("batch lock poisoned");
+        (0..statuses.len())
+            .filter(|index| statuses[*index] == EntryStatus::Pending)
+            .collect()
+    }
+
+    /// Records the results of the entries published, which are in the same order as them.
+    fn record(&self, published: &[usize], results: &[PutEventsResultEntry]) -> EventBridgeResponse {
+        let mut statuses = self.statuses.lock().expect("batch lock poisoned");
+        for (index, result) in published.iter().zip(results) {
+            statuses[*index] = match result.error_code.as_deref() {
+                None => EntryStatus::Published,
+                Some(code) if RETRIABLE_ENTRY_ERRORS.contains(&code) => EntryStatus::Pending,
+                Some(code) => {
+                    error!(
+                        message = "EventBridge rejected an event.",
+                        error_code = %code,
+                        error_message = %result.error_message.as_deref().unwrap_or_default(),
+                        internal_log_rate_secs = 10,
+                    );
+                    EntryStatus::Rejected
+                }
+            };
+        }
+
+        let count = |status| statuses.iter().filter(|s| **s == status).count();
+        EventBridgeResponse {
+            count: count(EntryStatus::Published),
+            events_byte_size: self
+                .requests
+                .iter()
+                .zip(statuses.iter())
+                .filter(|(_, status)| **status == EntryStatus::Published)
+                .map(|(request, _)| request.event_byte_size)
+                .sum(),
+            pending: count(EntryStatus::Pending),
+            rejected: count(EntryStatus::Rejected),
+        }
+    }
+}
+
+impl Finalizable for PutEventsBatch {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.requests.take_finalizers()
+    }
+}
+
+pub(super) struct EventBridgeResponse {
+    count: usize,
+    events_byte_size: usize,
+    /// The entries that failed with a transient error, and are published again on retry.
+    pending: usize,
+    rejected: usize,
+}
+
+impl DriverResponse for EventBridgeResponse {
+    fn event_status(&self) -> EventStatus {
+        if self.pending > 0 {
+            EventStatus::Errored
+        } else if self.rejected > 0 {
+            EventStatus::Rejected
+        } else {
+            EventStatus::Delivered
+        }
+    }
+
+    fn events_sent(&self) -> EventsSent {
+        EventsSent {
+            count: self.count,
+            byte_size: self.events_byte_size,
+            output: None,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct EventBridgeService {
+    client: EventBridgeClient,
+}
+
+impl EventBridgeService {
+    pub(super) const fn new(client: EventBridgeClient) -> Self {
+        Self { client }
+    }
+}
+
+impl Service<PutEventsBatch> for EventBridgeService {
+    type Response = EventBridgeResponse;
+    type Error = SdkError<PutEventsError>;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, batch: PutEventsBatch) -> Self::Future {
+        let client = self.client.clone();
+
+        Box::pin(async move {
+            let pending = batch.pending();
+            debug!(message = "Publishing events.", events = %pending.len());
+
+            let entries = pending
+                .iter()
+                .map(|index| batch.requests[*index].entry.clone())
+                .collect();
+            let output = client
+                .put_events()
+                .set_entries(Some(entries))
+                .send()
+                .instrument(info_span!("request").or_current())
+                .await?;
+
+            Ok(batch.record(&pending, &output.entries.unwrap_or_default()))
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct EventBridgeRetryLogic;
+
+impl RetryLogic for EventBridgeRetryLogic {
+    type Error = SdkError<PutEventsError>;
+    type Response = EventBridgeResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        is_retriable_error(error)
+    }
+
+    fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
+        if response.pending > 0 {
+            RetryAction::Retry(
+                format!("{} entries failed to be published", response.pending).into(),
+            )
+        } else {
+            RetryAction::Successful
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_eventbridge::model::PutEventsRequestEntry;
+
+    use super::*;
+
+    fn batch(size: usize) -> PutEventsBatch {
+        PutEventsBatch::new(
+            (0..size)
+                .map(|index| EventBridgeRequest {
+                    entry: PutEventsRequestEntry::builder()
+                        .detail(format!(r#"{{"index":{}}}"#, index))
+                        .build(),
+                    finalizers: Default::default(),
+                    event_byte_size: 10,
+                })
+                .collect(),
+        )
+    }
+
+    fn result(error_code: Option<&str>) -> PutEventsResultEntry {
+        PutEventsResultEntry::builder()
+            .set_error_code(error_code.map(Into::into))
+            .build()
+    }
+
+    #[test]
+    fn retries_only_failed_entries() {
+        let batch = batch(3);
+        let retry = batch.clone();
+
+        let published = batch.pending();
+        let response = batch.record(
+            &published,
+            &[
+                result(None),
+                result(Some("ThrottlingException")),
+                result(None),
+            ],
+        );
+        assert_eq!(response.count, 2);
+        assert_eq!(response.events_byte_size, 20);
+        assert_eq!(response.pending, 1);
+        assert!(matches!(
+            EventBridgeRetryLogic.should_retry_response(&response),
+            RetryAction::Retry(_)
+        ));
+
+        assert_eq!(retry.pending(), [1]);
+        let response = retry.record(&[1], &[result(None)]);
+        assert_eq!(response.count, 3);
+        assert_eq!(response.event_status(), EventStatus::Delivered);
+    }
+
+    #[test]
+    fn rejects_invalid_entries() {
+        let batch = batch(2);
+
+        let published = batch.pending();
+        let response = batch.record(&published, &[result(Some("MalformedDetail")), result(None)]);
+
+        assert!(batch.pending().is_empty());
+        assert_eq!(response.count, 1);
+        assert_eq!(response.rejected, 1);
+        assert_eq!(response.event_status(), EventStatus::Rejected);
+        assert!(matches!(
+            EventBridgeRetryLogic.should_retry_response(&response),
+            RetryAction::Successful
+        ));
+    }
+}
@@ -0,0 +1,165 @@
+use std::num::NonZeroUsize;
+
+use async_trait::async_trait;
+use aws_smithy_types::DateTime;
+use futures::{future, stream::BoxStream, StreamExt};
+use tower::ServiceBuilder;
+use vector_core::{sink::StreamSink, stream::BatcherSettings};
+
+use super::{
+    request_builder::{EventBridgeMetadata, EventBridgeProcessedEvent, EventBridgeRequestBuilder},
+    service::{EventBridgeRetryLogic, EventBridgeService, PutEventsBatch},
+};
+use crate::{
+    config::log_schema,
+    event::{Event, LogEvent, Value},
+    internal_events::TemplateRenderingError,
+    sinks::util::{ServiceBuilderExt, SinkBuilderExt, TowerRequestConfig},
+    template::Template,
+};
+
+pub(super) struct EventBridgeSink {
+    pub(super) service: EventBridgeService,
+    pub(super) request: TowerRequestConfig,
+    pub(super) request_builder: EventBridgeRequestBuilder,
+    pub(super) event_bus: Option<Template>,
+    pub(super) source: Template,
+    pub(super) detail_type: Template,
+    pub(super) batch_settings: BatcherSettings,
+}
+
+impl EventBridgeSink {
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let request = self.request.unwrap_with(&TowerRequestConfig::default());
+        let service = ServiceBuilder::new()
+            .settings(request, EventBridgeRetryLogic)
+            .service(self.service);
+        let builder_limit = NonZeroUsize::new(64);
+
+        let event_bus = self.event_bus;
+        let source = self.source;
+        let detail_type = self.detail_type;
+        input
+            .filter_map(move |event| {
+                future::ready(process_log(
+                    event.into_log(),
+                    event_bus.as_ref(),
+                    &source,
+                    &detail_type,
+                ))
+            })
+            .request_builder(builder_limit, self.request_builder)
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        error!("Failed to build EventBridge request: {:?}.", error);
+                        None
+                    }
+                    Ok(request) => Some(request),
+                }
+            })
+            .batched(self.batch_settings.into_byte_size_config())
+            .map(PutEventsBatch::new)
+            .into_driver(service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for EventBridgeSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+/// Renders the attributes of the EventBridge event of the log, dropping the log when one of its
+/// templates fails to render.
+fn process_log(
+    log: LogEvent,
+    event_bus: Option<&Template>,
+    source: &Template,
+    detail_type: &Template,
+) -> Option<EventBridgeProcessedEvent> {
+    let render = |template: &Template, field| {
+        template
+            .render_string(&log)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some(field),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    };
+    let event_bus = match event_bus {
+        Some(template) => Some(render(template, "event_bus")?),
+        None => None,
+    };
+    let source = render(source, "source")?;
+    let detail_type = render(detail_type, "detail_type")?;
+    let time = match log.get(log_schema().timestamp_key()) {
+        Some(Value::Timestamp(timestamp)) => {
+            Some(DateTime::from_millis(timestamp.timestamp_millis()))
+        }
+        _ => None,
+    };
+
+    Some(EventBridgeProcessedEvent {
+        event: log,
+        metadata: EventBridgeMetadata {
+            event_bus,
+            source,
+            detail_type,
+            time,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    #[test]
+    fn renders_event_attributes() {
+        let mut log = LogEvent::from("hello");
+        log.insert("service", "orders");
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.ymd(2022, 10, 1).and_hms_milli(12, 30, 0, 250),
+        );
+
+        let processed = process_log(
+            log,
+            Some(&Template::try_from("bus-{{ service }}").unwrap()),
+            &Template::try_from("com.example.{{ service }}").unwrap(),
+            &Template::try_from("Log").unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(processed.metadata.event_bus.as_deref(), Some("bus-orders"));
+        assert_eq!(processed.metadata.source, "com.example.orders");
+        assert_eq!(processed.metadata.detail_type, "Log");
+        assert_eq!(
+            processed.metadata.time,
+            Some(DateTime::from_millis(1_664_627_400_250))
+        );
+    }
+
+    #[test]
+    fn drops_logs_failing_to_render() {
+        let processed = process_log(
+            LogEvent::from("hello"),
+            None,
+            &Template::try_from("com.example.{{ service }}").unwrap(),
+            &Template::try_from("Log").unwrap(),
+        );
+
+        assert!(processed.is_none());
+    }
+}
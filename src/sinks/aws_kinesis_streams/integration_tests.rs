@@ -9,11 +9,9 @@ use tokio::time::{sleep, Duration};
 use super::*;
 use crate::{
     aws::{create_client, AwsAuthentication, RegionOrEndpoint},
+    common::kinesis::KinesisClientBuilder,
     config::{ProxyConfig, SinkConfig, SinkContext},
-    sinks::{
-        aws_kinesis_streams::config::KinesisClientBuilder,
-        util::{BatchConfig, Compression},
-    },
+    sinks::util::{BatchConfig, Compression},
     test_util::{
         components::{run_and_assert_sink_compliance, AWS_SINK_TAGS},
         random_lines_with_stream, random_string,
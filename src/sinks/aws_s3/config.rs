@@ -1,15 +1,18 @@
 use std::convert::TryInto;
 
 use aws_sdk_s3::Client as S3Client;
-use codecs::encoding::{Framer, FramingConfig};
+use codecs::encoding::FramingConfig;
 use codecs::TextSerializerConfig;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
 use serde::{Deserialize, Serialize};
 use tower::ServiceBuilder;
 use vector_core::sink::VectorSink;
 
+use super::manifest::{ManifestConfig, ManifestService, ManifestSink, ManifestTracker};
 use crate::{
     aws::{AwsAuthentication, RegionOrEndpoint},
-    codecs::{Encoder, EncodingConfigWithFraming, SinkType},
+    codecs::EncodingConfigWithFraming,
     config::{
         AcknowledgementsConfig, DataType, GenerateConfig, Input, ProxyConfig, SinkConfig,
         SinkContext,
@@ -23,8 +26,8 @@ use crate::{
             sink::S3Sink,
         },
         util::{
-            partitioner::KeyPartitioner, BatchConfig, BulkSizeBasedDefaultBatchSettings,
-            Compression, ServiceBuilderExt, TowerRequestConfig,
+            encoding::ObjectEncoder, partitioner::KeyPartitioner, BatchConfig,
+            BulkSizeBasedDefaultBatchSettings, Compression, ServiceBuilderExt, TowerRequestConfig,
         },
         Healthcheck,
     },
@@ -35,6 +38,19 @@ const DEFAULT_KEY_PREFIX: &str = "date=%F/";
 const DEFAULT_FILENAME_TIME_FORMAT: &str = "%s";
 const DEFAULT_FILENAME_APPEND_UUID: bool = true;
 
+/// The Hive-style partitions `key_prefix` can use as `{{ partition_<name> }}`, by name, with the
+/// `strftime` specifiers of the timestamp of the events they're rendered with.
+const PARTITION_HELPERS: &[(&str, &str)] = &[
+    ("date", "date=%F"),
+    ("year", "year=%Y"),
+    ("month", "month=%m"),
+    ("day", "day=%d"),
+    ("hour", "hour=%H"),
+];
+
+static PARTITION_HELPER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{\s*partition_(\w+)\s*\}\}").unwrap());
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct S3SinkConfig {
@@ -43,6 +59,7 @@ pub struct S3SinkConfig {
     pub filename_time_format: Option<String>,
     pub filename_append_uuid: Option<bool>,
     pub filename_extension: Option<String>,
+    pub manifest: Option<ManifestConfig>,
     #[serde(flatten)]
     pub options: S3Options,
     #[serde(flatten)]
@@ -74,6 +91,7 @@ impl GenerateConfig for S3SinkConfig {
             filename_time_format: None,
             filename_append_uuid: None,
             filename_extension: None,
+            manifest: None,
             options: S3Options::default(),
             region: RegionOrEndpoint::default(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::new()).into(),
@@ -118,6 +136,7 @@ impl S3SinkConfig {
         // order to configure the client/service with retries, concurrency
         // limits, rate limits, and whatever else the client should have.
         let request_limits = self.request.unwrap_with(&Default::default());
+        let manifest_service = service.clone();
         let service = ServiceBuilder::new()
             .settings(request_limits, S3RetryLogic)
             .service(service);
@@ -126,8 +145,8 @@ impl S3SinkConfig {
         let batch_settings = self.batch.into_batcher_settings()?;
         let key_prefix = self
             .key_prefix
-            .as_ref()
-            .cloned()
+            .as_deref()
+            .map(expand_partition_helpers)
             .unwrap_or_else(|| DEFAULT_KEY_PREFIX.into())
             .try_into()?;
         let partitioner = KeyPartitioner::new(key_prefix);
@@ -142,23 +161,52 @@ impl S3SinkConfig {
             .filename_append_uuid
             .unwrap_or(DEFAULT_FILENAME_APPEND_UUID);
 
-        let transformer = self.encoding.transformer();
-        let (framer, serializer) = self.encoding.build(SinkType::MessageBased)?;
-        let encoder = Encoder::<Framer>::new(framer, serializer);
+        let encoder = ObjectEncoder::build(&self.encoding)?;
+        let mut api_options = self.options.clone();
+        if encoder.is_parquet() {
+            api_options
+                .content_type
+                .get_or_insert_with(|| encoder.content_type().to_owned());
+        }
 
         let request_options = S3RequestOptions {
             bucket: self.bucket.clone(),
-            api_options: self.options.clone(),
+            api_options: api_options.clone(),
             filename_extension: self.filename_extension.clone(),
             filename_time_format,
             filename_append_uuid,
-            encoder: (transformer, encoder),
-            compression: self.compression,
+            compression: encoder.compression(self.compression),
+            encoder,
+            manifests: None,
         };
 
-        let sink = S3Sink::new(service, request_options, partitioner, batch_settings);
+        let sink = match &self.manifest {
+            Some(manifest) => {
+                let tracker = ManifestTracker::default();
+                let request_options = S3RequestOptions {
+                    manifests: Some(tracker.clone()),
+                    ..request_options
+                };
+                let service = ManifestService::new(service, tracker.clone());
+                let sink = S3Sink::new(service, request_options, partitioner, batch_settings);
+                VectorSink::from_event_streamsink(ManifestSink::new(
+                    sink,
+                    manifest,
+                    manifest_service,
+                    self.bucket.clone(),
+                    api_options,
+                    tracker,
+                ))
+            }
+            None => VectorSink::from_event_streamsink(S3Sink::new(
+                service,
+                request_options,
+                partitioner,
+                batch_settings,
+            )),
+        };
 
-        Ok(VectorSink::from_event_streamsink(sink))
+        Ok(sink)
     }
 
     pub fn build_healthcheck(&self, client: S3Client) -> crate::Result<Healthcheck> {
@@ -170,12 +218,44 @@ impl S3SinkConfig {
     }
 }
 
+/// Replaces the `{{ partition_<name> }}` helpers of the key prefix with their Hive-style
+/// partition, leaving the other templates to be rendered with the fields of the events.
+fn expand_partition_helpers(key_prefix: &str) -> String {
+    PARTITION_HELPER
+        .replace_all(key_prefix, |captures: &Captures| {
+            PARTITION_HELPERS
+                .iter()
+                .find(|(name, _)| *name == &captures[1])
+                .map(|(_, partition)| (*partition).to_owned())
+                .unwrap_or_else(|| captures[0].to_owned())
+        })
+        .into_owned()
+}
+
 #[cfg(test)]
 mod tests {
-    use super::S3SinkConfig;
+    use super::{expand_partition_helpers, S3SinkConfig};
 
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<S3SinkConfig>();
     }
+
+    #[test]
+    fn expands_partition_helpers() {
+        assert_eq!(
+            expand_partition_helpers("logs/{{ partition_date }}/{{partition_hour}}/"),
+            "logs/date=%F/hour=%H/"
+        );
+        assert_eq!(
+            expand_partition_helpers(
+                "{{ partition_year }}/{{ partition_month }}/{{ partition_day }}/"
+            ),
+            "year=%Y/month=%m/day=%d/"
+        );
+        assert_eq!(
+            expand_partition_helpers("{{ host }}/{{ partition_unknown }}/"),
+            "{{ host }}/{{ partition_unknown }}/"
+        );
+    }
 }
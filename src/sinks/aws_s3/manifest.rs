@@ -0,0 +1,365 @@
+//! Markers and manifests of the partitions of the objects.
+//!
+//! The objects of each partition, the key prefix rendered for their events, are tracked from the
+//! building of their request until their upload. A partition is considered complete once it has
+//! no upload in flight and no new object for the configured settle period, or when the sink shuts
+//! down, at which point a `_SUCCESS` marker or a `_manifest.json` object listing its objects is
+//! written under its prefix. As their names start with an underscore, query engines such as
+//! Athena and Spark skip these objects when reading the partition.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::{future::BoxFuture, stream::BoxStream, FutureExt};
+use serde::{Deserialize, Serialize};
+use tokio::sync::oneshot;
+use tower::{Service, ServiceExt};
+use vector_core::{
+    event::{Event, EventFinalizers},
+    sink::StreamSink,
+};
+
+use crate::sinks::s3_common::{
+    config::S3Options,
+    service::{S3Metadata, S3Request, S3Response, S3Service},
+};
+
+const fn default_settle_secs() -> u64 {
+    60
+}
+
+/// The objects written once all the objects of a partition have been uploaded.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestConfig {
+    /// The kind of object written for each complete partition.
+    #[serde(default)]
+    pub mode: ManifestMode,
+    /// How long a partition must go without new objects before it is considered complete.
+    ///
+    /// This should be longer than the timeout of the batches, so that the events still being
+    /// batched for the partition are flushed first.
+    #[serde(default = "default_settle_secs")]
+    pub settle_secs: u64,
+}
+
+/// The kind of object written for each complete partition.
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ManifestMode {
+    /// An empty `_SUCCESS` object, as written by Hadoop and Spark jobs.
+    #[derivative(Default)]
+    SuccessMarker,
+    /// A `_manifest.json` object listing the objects of the partition.
+    Manifest,
+}
+
+#[derive(Debug, Serialize)]
+struct ManifestObject {
+    key: String,
+    events: usize,
+    bytes: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct Manifest<'a> {
+    prefix: &'a str,
+    events: usize,
+    bytes: usize,
+    objects: &'a [ManifestObject],
+}
+
+#[derive(Debug, Default)]
+struct Partition {
+    in_flight: usize,
+    failed: bool,
+    objects: Vec<ManifestObject>,
+    updated: Option<Instant>,
+}
+
+#[derive(Debug, Default)]
+struct TrackerState {
+    partitions: HashMap<String, Partition>,
+    /// The partitions of the objects being uploaded, by key.
+    in_flight: HashMap<String, String>,
+}
+
+/// Tracks the objects of each partition, from the building of their request to their upload.
+#[derive(Clone, Debug, Default)]
+pub struct ManifestTracker(Arc<Mutex<TrackerState>>);
+
+impl ManifestTracker {
+    /// Records that the object with the given key is about to be uploaded for the partition.
+    pub fn started(&self, prefix: &str, key: &str) {
+        let mut state = self.0.lock().expect("manifest tracker lock poisoned");
+        let partition = state.partitions.entry(prefix.to_owned()).or_default();
+        partition.in_flight += 1;
+        partition.updated = Some(Instant::now());
+        state.in_flight.insert(key.to_owned(), prefix.to_owned());
+    }
+
+    /// Records the outcome of the upload of the object, with its number of events and size when
+    /// it succeeded.
+    fn finished(&self, key: &str, uploaded: Option<(usize, usize)>) {
+        let mut state = self.0.lock().expect("manifest tracker lock poisoned");
+        let partition = match state.in_flight.remove(key) {
+            Some(prefix) => state.partitions.get_mut(&prefix),
+            None => None,
+        };
+        if let Some(partition) = partition {
+            partition.in_flight -= 1;
+            partition.updated = Some(Instant::now());
+            match uploaded {
+                Some((events, bytes)) => partition.objects.push(ManifestObject {
+                    key: key.to_owned(),
+                    events,
+                    bytes,
+                }),
+                None => partition.failed = true,
+            }
+        }
+    }
+
+    /// Removes the partitions without uploads in flight that haven't been updated for `settle`,
+    /// or all of them regardless of their last update when it is `None`.
+    fn take_complete(&self, settle: Option<Duration>) -> Vec<(String, Partition)> {
+        let mut state = self.0.lock().expect("manifest tracker lock poisoned");
+        let complete = state
+            .partitions
+            .iter()
+            .filter(|(_, partition)| {
+                partition.in_flight == 0
+                    && settle.map_or(true, |settle| {
+                        partition
+                            .updated
+                            .map_or(true, |updated| updated.elapsed() >= settle)
+                    })
+            })
+            .map(|(prefix, _)| prefix.clone())
+            .collect::<Vec<_>>();
+
+        complete
+            .into_iter()
+            .filter_map(|prefix| state.partitions.remove_entry(&prefix))
+            .collect()
+    }
+}
+
+/// Records the outcome of the uploads of the objects in the tracker.
+#[derive(Clone)]
+pub struct ManifestService<S> {
+    inner: S,
+    tracker: ManifestTracker,
+}
+
+impl<S> ManifestService<S> {
+    pub const fn new(inner: S, tracker: ManifestTracker) -> Self {
+        Self { inner, tracker }
+    }
+}
+
+impl<S> Service<S3Request> for ManifestService<S>
+where
+    S: Service<S3Request, Response = S3Response>,
+    S::Future: Send + 'static,
+{
+    type Response = S3Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: S3Request) -> Self::Future {
+        let key = request.metadata.partition_key.clone();
+        let events = request.metadata.count;
+        let bytes = request.body.len();
+        let tracker = self.tracker.clone();
+
+        self.inner
+            .call(request)
+            .map(move |result| {
+                tracker.finished(&key, result.is_ok().then(|| (events, bytes)));
+                result
+            })
+            .boxed()
+    }
+}
+
+/// Writes the marker or manifest of the complete partitions.
+#[derive(Clone)]
+struct ManifestWriter {
+    service: S3Service,
+    bucket: String,
+    options: S3Options,
+    mode: ManifestMode,
+    tracker: ManifestTracker,
+}
+
+impl ManifestWriter {
+    async fn flush(&self, settle: Option<Duration>) {
+        for (prefix, partition) in self.tracker.take_complete(settle) {
+            self.write(&prefix, &partition).await;
+        }
+    }
+
+    async fn write(&self, prefix: &str, partition: &Partition) {
+        if partition.failed {
+            error!(
+                message = "Not writing the manifest of a partition whose objects failed to be uploaded.",
+                %prefix,
+            );
+            return;
+        }
+
+        let (name, body, content_type) = match self.mode {
+            ManifestMode::SuccessMarker => ("_SUCCESS", Bytes::new(), "application/octet-stream"),
+            ManifestMode::Manifest => {
+                let manifest = Manifest {
+                    prefix,
+                    events: partition.objects.iter().map(|object| object.events).sum(),
+                    bytes: partition.objects.iter().map(|object| object.bytes).sum(),
+                    objects: &partition.objects,
+                };
+                let body = serde_json::to_vec(&manifest).expect("manifests are valid JSON");
+                ("_manifest.json", body.into(), "application/json")
+            }
+        };
+
+        let key = format!("{}{}", prefix, name);
+        let request = S3Request {
+            body,
+            bucket: self.bucket.clone(),
+            metadata: S3Metadata {
+                partition_key: key.clone(),
+                count: 0,
+                byte_size: 0,
+                finalizers: EventFinalizers::default(),
+            },
+            content_encoding: None,
+            options: S3Options {
+                content_encoding: None,
+                content_type: Some(content_type.into()),
+                ..self.options.clone()
+            },
+        };
+        if let Err(error) = self.service.clone().oneshot(request).await {
+            error!(message = "Failed to write the manifest of a partition.", %key, ?error);
+        }
+    }
+}
+
+/// Runs the sink while writing the marker or manifest of its partitions once they're complete.
+pub struct ManifestSink<S> {
+    inner: S,
+    writer: ManifestWriter,
+    settle: Duration,
+}
+
+impl<S> ManifestSink<S> {
+    pub fn new(
+        inner: S,
+        config: &ManifestConfig,
+        service: S3Service,
+        bucket: String,
+        options: S3Options,
+        tracker: ManifestTracker,
+    ) -> Self {
+        Self {
+            inner,
+            writer: ManifestWriter {
+                service,
+                bucket,
+                options,
+                mode: config.mode,
+                tracker,
+            },
+            settle: Duration::from_secs(config.settle_secs),
+        }
+    }
+}
+
+#[async_trait]
+impl<S> StreamSink<Event> for ManifestSink<S>
+where
+    S: StreamSink<Event> + Send + 'static,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let Self {
+            inner,
+            writer,
+            settle,
+        } = *self;
+
+        let (shutdown, mut done) = oneshot::channel::<()>();
+        let flusher = {
+            let writer = writer.clone();
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(Duration::from_secs(1));
+                loop {
+                    tokio::select! {
+                        _ = interval.tick() => writer.flush(Some(settle)).await,
+                        _ = &mut done => break,
+                    }
+                }
+            })
+        };
+
+        let result = Box::new(inner).run(input).await;
+
+        // All the batches have been uploaded, so the remaining partitions are complete.
+        drop(shutdown);
+        _ = flusher.await;
+        writer.flush(None).await;
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn completes_partitions_without_uploads_in_flight() {
+        let tracker = ManifestTracker::default();
+        tracker.started("date=2022-08-01/", "date=2022-08-01/a.log");
+        tracker.started("date=2022-08-01/", "date=2022-08-01/b.log");
+        tracker.started("date=2022-08-02/", "date=2022-08-02/c.log");
+
+        tracker.finished("date=2022-08-01/a.log", Some((10, 100)));
+        tracker.finished("date=2022-08-02/c.log", Some((5, 50)));
+        let complete = tracker.take_complete(None);
+        assert_eq!(complete.len(), 1);
+        assert_eq!(complete[0].0, "date=2022-08-02/");
+        assert_eq!(complete[0].1.objects[0].key, "date=2022-08-02/c.log");
+
+        tracker.finished("date=2022-08-01/b.log", Some((20, 200)));
+        assert!(tracker
+            .take_complete(Some(Duration::from_secs(60)))
+            .is_empty());
+        let complete = tracker.take_complete(None);
+        assert_eq!(complete.len(), 1);
+        assert_eq!(complete[0].1.objects.len(), 2);
+        assert!(!complete[0].1.failed);
+    }
+
+    #[test]
+    fn marks_failed_partitions() {
+        let tracker = ManifestTracker::default();
+        tracker.started("date=2022-08-01/", "date=2022-08-01/a.log");
+        tracker.finished("date=2022-08-01/a.log", None);
+
+        let complete = tracker.take_complete(None);
+        assert!(complete[0].1.failed);
+        assert!(complete[0].1.objects.is_empty());
+    }
+}
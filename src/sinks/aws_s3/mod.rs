@@ -1,6 +1,7 @@
 use crate::config::SinkDescription;
 
 mod config;
+mod manifest;
 mod sink;
 
 #[cfg(test)]
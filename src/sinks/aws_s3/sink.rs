@@ -2,19 +2,20 @@ use std::io;
 
 use bytes::Bytes;
 use chrono::Utc;
-use codecs::encoding::Framer;
 use uuid::Uuid;
 use vector_core::{event::Finalizable, ByteSizeOf};
 
+use super::manifest::ManifestTracker;
 use crate::{
-    codecs::{Encoder, Transformer},
     event::Event,
     sinks::{
         s3_common::{
             config::S3Options,
             service::{S3Metadata, S3Request},
         },
-        util::{request_builder::EncodeResult, Compression, RequestBuilder},
+        util::{
+            encoding::ObjectEncoder, request_builder::EncodeResult, Compression, RequestBuilder,
+        },
     },
 };
 
@@ -25,14 +26,15 @@ pub struct S3RequestOptions {
     pub filename_append_uuid: bool,
     pub filename_extension: Option<String>,
     pub api_options: S3Options,
-    pub encoder: (Transformer, Encoder<Framer>),
+    pub encoder: ObjectEncoder,
     pub compression: Compression,
+    pub manifests: Option<ManifestTracker>,
 }
 
 impl RequestBuilder<(String, Vec<Event>)> for S3RequestOptions {
     type Metadata = S3Metadata;
     type Events = Vec<Event>;
-    type Encoder = (Transformer, Encoder<Framer>);
+    type Encoder = ObjectEncoder;
     type Payload = Bytes;
     type Request = S3Request;
     type Error = io::Error; // TODO: this is ugly.
@@ -75,8 +77,12 @@ impl RequestBuilder<(String, Vec<Event>)> for S3RequestOptions {
             .filename_extension
             .as_ref()
             .cloned()
-            .unwrap_or_else(|| self.compression.extension().into());
-        metadata.partition_key = format!("{}{}.{}", metadata.partition_key, filename, extension);
+            .unwrap_or_else(|| self.encoder.extension(self.compression).into());
+        let key = format!("{}{}.{}", metadata.partition_key, filename, extension);
+        if let Some(manifests) = &self.manifests {
+            manifests.started(&metadata.partition_key, &key);
+        }
+        metadata.partition_key = key;
 
         S3Request {
             body: payload.into_payload(),
@@ -0,0 +1,198 @@
+use std::convert::TryFrom;
+
+use codecs::encoding::ParquetCompression;
+use serde::{Deserialize, Serialize};
+use tower::ServiceBuilder;
+
+use super::{
+    ocsf::{OcsfDefaults, OcsfWriter},
+    request_builder::{SecurityLakeEncoder, SecurityLakeRequestBuilder},
+};
+use crate::{
+    aws::{AwsAuthentication, RegionOrEndpoint},
+    codecs::Transformer,
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    sinks::{
+        s3_common::{
+            self,
+            config::{S3Options, S3RetryLogic},
+            sink::S3Sink,
+        },
+        util::{
+            partitioner::KeyPartitioner, BatchConfig, ServiceBuilderExt, SinkBatchSettings,
+            TowerRequestConfig,
+        },
+        Healthcheck, VectorSink,
+    },
+    template::Template,
+    tls::TlsConfig,
+};
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SecurityLakeDefaultBatchSettings;
+
+impl SinkBatchSettings for SecurityLakeDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(100_000_000);
+    // Security Lake expects the objects of custom sources to be written at most every 5 minutes.
+    const TIMEOUT_SECS: f64 = 300.0;
+}
+
+fn default_product_name() -> String {
+    "Vector".into()
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AwsSecurityLakeSinkConfig {
+    /// The S3 bucket of the data lake, such as `aws-security-data-lake-us-east-1-abcdefghij`.
+    pub(super) bucket: String,
+    /// The name of the custom source the events are written as, under which their objects are
+    /// stored in the `ext/` prefix of the bucket.
+    pub(super) source_name: String,
+    /// The ID of the AWS account the events come from, which partitions the objects with the
+    /// region.
+    pub(super) account_id: String,
+    /// The OCSF event class of the records, used for the logs that don't set their `class_uid`
+    /// attribute.
+    pub(super) class_uid: i32,
+    /// The product reported in the metadata of the records that don't set their own.
+    #[serde(default = "default_product_name")]
+    pub(super) product_name: String,
+    /// The vendor of the product reported in the metadata of the records that don't set their own.
+    #[serde(default = "default_product_name")]
+    pub(super) vendor_name: String,
+    #[serde(flatten)]
+    pub(super) region: RegionOrEndpoint,
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub(super) encoding: Transformer,
+    #[serde(default)]
+    pub(super) compression: ParquetCompression,
+    #[serde(default)]
+    pub(super) batch: BatchConfig<SecurityLakeDefaultBatchSettings>,
+    #[serde(default)]
+    pub(super) request: TowerRequestConfig,
+    pub(super) tls: Option<TlsConfig>,
+    #[serde(default)]
+    pub(super) auth: AwsAuthentication,
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub(super) acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for AwsSecurityLakeSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"bucket = "aws-security-data-lake-us-east-1-abcdefghij"
+            source_name = "vector"
+            account_id = "123456789012"
+            class_uid = 1001
+            region = "us-east-1""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "aws_security_lake")]
+impl SinkConfig for AwsSecurityLakeSinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let region = self
+            .region
+            .region
+            .clone()
+            .ok_or("The region of the data lake must be set with `region`.")?;
+
+        let service =
+            s3_common::config::create_service(&self.region, &self.auth, &cx.proxy, &self.tls)
+                .await?;
+        let healthcheck =
+            s3_common::config::build_healthcheck(self.bucket.clone(), service.client())?;
+
+        let request_limits = self.request.unwrap_with(&Default::default());
+        let service = ServiceBuilder::new()
+            .settings(request_limits, S3RetryLogic)
+            .service(service);
+
+        let partitioner = KeyPartitioner::new(Template::try_from(self.key_prefix(&region))?);
+        let writer = OcsfWriter::new(
+            OcsfDefaults {
+                class_uid: self.class_uid,
+                product_name: self.product_name.clone(),
+                vendor_name: self.vendor_name.clone(),
+                region,
+                account_id: self.account_id.clone(),
+            },
+            self.compression,
+        );
+        let request_builder = SecurityLakeRequestBuilder {
+            bucket: self.bucket.clone(),
+            options: S3Options {
+                content_type: Some("application/octet-stream".into()),
+                ..Default::default()
+            },
+            encoder: SecurityLakeEncoder {
+                transformer: self.encoding.clone(),
+                writer,
+            },
+        };
+
+        let sink = S3Sink::new(
+            service,
+            request_builder,
+            partitioner,
+            self.batch.into_batcher_settings()?,
+        );
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "aws_security_lake"
+    }
+
+    fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
+        Some(&self.acknowledgements)
+    }
+}
+
+impl AwsSecurityLakeSinkConfig {
+    /// The prefix of the objects of the custom source, partitioned by region, account and day of
+    /// the events as Security Lake expects.
+    fn key_prefix(&self, region: &str) -> String {
+        format!(
+            "ext/{}/region={}/accountId={}/eventDay=%Y%m%d/",
+            self.source_name, region, self.account_id
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AwsSecurityLakeSinkConfig>();
+    }
+
+    #[test]
+    fn partitions_by_region_account_and_day() {
+        let config: AwsSecurityLakeSinkConfig =
+            toml::from_str(&AwsSecurityLakeSinkConfig::generate_config().to_string()).unwrap();
+
+        assert_eq!(
+            config.key_prefix("us-east-1"),
+            "ext/vector/region=us-east-1/accountId=123456789012/eventDay=%Y%m%d/"
+        );
+    }
+}
@@ -0,0 +1,18 @@
+//! The `aws_security_lake` sink.
+//!
+//! Events are written to the S3 bucket of Amazon Security Lake as a custom source: the logs are
+//! converted to records of the Open Cybersecurity Schema Framework (OCSF), which are written as
+//! Parquet objects under the `ext/{source}/region={region}/accountId={account}/eventDay={day}/`
+//! partitions Security Lake and its AWS Glue tables expect.
+
+use crate::config::SinkDescription;
+
+mod config;
+mod ocsf;
+mod request_builder;
+
+pub use self::config::AwsSecurityLakeSinkConfig;
+
+inventory::submit! {
+    SinkDescription::new::<AwsSecurityLakeSinkConfig>("aws_security_lake")
+}
@@ -0,0 +1,474 @@
+//! The conversion of logs to OCSF records, written as Parquet files.
+//!
+//! The records follow the attributes of the OCSF base event, with the same nested structures as
+//! the event classes, so that the tables Security Lake registers in the AWS Glue Data Catalog for
+//! custom sources can query them. The fields of the logs that aren't part of the schema are kept
+//! in the `unmapped` attribute, as a map of strings.
+
+use std::{io, sync::Arc};
+
+use codecs::encoding::ParquetCompression;
+use parquet::{
+    basic::Type as PhysicalType,
+    data_type::{ByteArray, ByteArrayType, Int32Type, Int64Type},
+    file::{properties::WriterProperties, writer::SerializedFileWriter},
+    schema::{
+        parser::parse_message_type,
+        types::{ColumnDescriptor, SchemaDescriptor, Type},
+    },
+};
+
+use crate::{
+    config::log_schema,
+    event::{LogEvent, Value},
+};
+
+/// The version of the OCSF schema the records are written with.
+const OCSF_VERSION: &str = "1.0.0-rc.2";
+
+/// The Parquet schema of the records.
+///
+/// The column names are lowercase and the logical types are limited to the ones Glue maps to its
+/// own types, as the tables of the custom sources are created from it.
+const SCHEMA: &str = "
+message ocsf {
+    required int32 activity_id;
+    optional binary activity_name (UTF8);
+    required int32 category_uid;
+    optional binary category_name (UTF8);
+    required int32 class_uid;
+    optional binary class_name (UTF8);
+    required group cloud {
+        required binary provider (UTF8);
+        required binary region (UTF8);
+        required group account {
+            required binary uid (UTF8);
+        }
+    }
+    optional int32 count;
+    optional binary message (UTF8);
+    required group metadata {
+        required binary version (UTF8);
+        required group product {
+            required binary name (UTF8);
+            required binary vendor_name (UTF8);
+            optional binary version (UTF8);
+        }
+        optional binary uid (UTF8);
+        optional binary log_name (UTF8);
+        optional binary original_time (UTF8);
+    }
+    optional binary raw_data (UTF8);
+    required int32 severity_id;
+    optional binary severity (UTF8);
+    optional binary status (UTF8);
+    optional int32 status_id;
+    optional binary status_detail (UTF8);
+    required int64 time (TIMESTAMP_MILLIS);
+    required int64 type_uid;
+    optional binary type_name (UTF8);
+    optional group unmapped (MAP) {
+        repeated group key_value (MAP_KEY_VALUE) {
+            required binary key (UTF8);
+            optional binary value (UTF8);
+        }
+    }
+}
+";
+
+/// The values of the attributes the logs don't set themselves.
+#[derive(Clone, Debug)]
+pub(super) struct OcsfDefaults {
+    pub(super) class_uid: i32,
+    pub(super) product_name: String,
+    pub(super) vendor_name: String,
+    pub(super) region: String,
+    pub(super) account_id: String,
+}
+
+impl OcsfDefaults {
+    /// Sets the required attributes the log is missing, deriving the category and type of the
+    /// event from its class and activity as OCSF does.
+    fn apply(&self, log: &mut LogEvent) {
+        let class_uid = get_integer(log, "class_uid").unwrap_or(self.class_uid as i64);
+        let activity_id = get_integer(log, "activity_id").unwrap_or(0);
+        let defaults: [(&str, Value); 10] = [
+            ("class_uid", class_uid.into()),
+            ("category_uid", (class_uid / 1000).into()),
+            ("activity_id", activity_id.into()),
+            ("type_uid", (class_uid * 100 + activity_id).into()),
+            ("severity_id", Value::Integer(0)),
+            ("metadata.version", OCSF_VERSION.into()),
+            ("metadata.product.name", self.product_name.clone().into()),
+            (
+                "metadata.product.vendor_name",
+                self.vendor_name.clone().into(),
+            ),
+            ("cloud.region", self.region.clone().into()),
+            ("cloud.account.uid", self.account_id.clone().into()),
+        ];
+        for (path, value) in defaults {
+            if !log.contains(path) {
+                log.insert(path, value);
+            }
+        }
+        if !log.contains("cloud.provider") {
+            log.insert("cloud.provider", "AWS");
+        }
+
+        let timestamp_key = log_schema().timestamp_key();
+        if timestamp_key != "time" {
+            if let Some(timestamp) = log.remove(timestamp_key) {
+                log.try_insert("time", timestamp);
+            }
+        }
+        if !log.contains("time") {
+            log.insert("time", chrono::Utc::now());
+        }
+    }
+}
+
+fn get_integer(log: &LogEvent, path: &str) -> Option<i64> {
+    log.get(path).and_then(Value::as_integer)
+}
+
+/// A value of a column of the records.
+#[derive(Clone, Debug, PartialEq)]
+enum Cell {
+    Int32(i32),
+    Int64(i64),
+    Bytes(ByteArray),
+}
+
+/// A log converted to the columns of the schema.
+#[derive(Debug, Default)]
+struct Record {
+    /// The values of the columns of the attributes, `None` being null.
+    cells: Vec<Option<Cell>>,
+    /// The fields that aren't part of the schema, with their values as strings.
+    unmapped: Vec<(String, String)>,
+}
+
+/// Writes logs as OCSF records in Parquet files.
+#[derive(Clone, Debug)]
+pub(super) struct OcsfWriter {
+    schema: Arc<Type>,
+    descriptor: Arc<SchemaDescriptor>,
+    properties: Arc<WriterProperties>,
+    defaults: OcsfDefaults,
+}
+
+impl OcsfWriter {
+    pub(super) fn new(defaults: OcsfDefaults, compression: ParquetCompression) -> Self {
+        let schema = Arc::new(parse_message_type(SCHEMA).expect("the OCSF schema is valid"));
+        let descriptor = Arc::new(SchemaDescriptor::new(Arc::clone(&schema)));
+        let properties = Arc::new(
+            WriterProperties::builder()
+                .set_compression(compression.into())
+                .build(),
+        );
+        Self {
+            schema,
+            descriptor,
+            properties,
+            defaults,
+        }
+    }
+
+    /// Converts the log to the columns of the schema.
+    ///
+    /// The attributes whose value can't be converted to the type of their column are kept in the
+    /// unmapped fields instead, leaving the default value or null in the column.
+    fn record(&self, mut log: LogEvent) -> Record {
+        self.defaults.apply(&mut log);
+
+        let mut record = Record::default();
+        let mut unconverted = Vec::new();
+        for column in self.descriptor.columns() {
+            if column.max_rep_level() > 0 {
+                continue;
+            }
+            let path = column.path().string();
+            let cell = match log.remove_prune(path.as_str(), true) {
+                Some(value) => match convert(column, &value) {
+                    Some(cell) => Some(cell),
+                    None => {
+                        unconverted.push((path, value));
+                        None
+                    }
+                },
+                None => None,
+            };
+            record.cells.push(match cell {
+                None if column.max_def_level() == 0 => Some(default_cell(column)),
+                cell => cell,
+            });
+        }
+
+        if let Some(fields) = log.all_fields() {
+            record.unmapped = fields
+                .filter(|(_, value)| !matches!(value, Value::Object(map) if map.is_empty()))
+                .map(|(path, value)| (path, stringify(value)))
+                .collect();
+        }
+        record.unmapped.extend(
+            unconverted
+                .into_iter()
+                .map(|(path, value)| (path, stringify(&value))),
+        );
+        record
+    }
+
+    /// Writes the logs as a Parquet file holding a single row group.
+    pub(super) fn write(&self, logs: Vec<LogEvent>) -> io::Result<Vec<u8>> {
+        let records = logs
+            .into_iter()
+            .map(|log| self.record(log))
+            .collect::<Vec<_>>();
+        self.write_records(&records).map_err(|error| {
+            io::Error::new(io::ErrorKind::Other, format!("Parquet error: {}", error))
+        })
+    }
+
+    fn write_records(&self, records: &[Record]) -> parquet::errors::Result<Vec<u8>> {
+        let mut buffer = Vec::new();
+        let mut writer = SerializedFileWriter::new(
+            &mut buffer,
+            Arc::clone(&self.schema),
+            Arc::clone(&self.properties),
+        )?;
+
+        let mut row_group = writer.next_row_group()?;
+        let mut columns = self.descriptor.columns().iter();
+        let mut index = 0;
+        while let Some(mut column_writer) = row_group.next_column()? {
+            let column = columns.next().expect("the columns match the schema");
+            if column.max_rep_level() > 0 {
+                let (values, def_levels, rep_levels) = unmapped_column(column, records);
+                column_writer.typed::<ByteArrayType>().write_batch(
+                    &values,
+                    Some(&def_levels),
+                    Some(&rep_levels),
+                )?;
+            } else {
+                let cells = records.iter().map(|record| record.cells[index].as_ref());
+                let def_levels = cells
+                    .clone()
+                    .map(|cell| {
+                        if cell.is_some() {
+                            column.max_def_level()
+                        } else {
+                            0
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                let def_levels = (column.max_def_level() > 0).then(|| def_levels.as_slice());
+                let cells = cells.flatten();
+                match column.physical_type() {
+                    PhysicalType::INT32 => {
+                        let values = cells
+                            .filter_map(|cell| match cell {
+                                Cell::Int32(value) => Some(*value),
+                                _ => None,
+                            })
+                            .collect::<Vec<_>>();
+                        column_writer
+                            .typed::<Int32Type>()
+                            .write_batch(&values, def_levels, None)?;
+                    }
+                    PhysicalType::INT64 => {
+                        let values = cells
+                            .filter_map(|cell| match cell {
+                                Cell::Int64(value) => Some(*value),
+                                _ => None,
+                            })
+                            .collect::<Vec<_>>();
+                        column_writer
+                            .typed::<Int64Type>()
+                            .write_batch(&values, def_levels, None)?;
+                    }
+                    _ => {
+                        let values = cells
+                            .filter_map(|cell| match cell {
+                                Cell::Bytes(value) => Some(value.clone()),
+                                _ => None,
+                            })
+                            .collect::<Vec<_>>();
+                        column_writer
+                            .typed::<ByteArrayType>()
+                            .write_batch(&values, def_levels, None)?;
+                    }
+                }
+                index += 1;
+            }
+            column_writer.close()?;
+        }
+        row_group.close()?;
+        writer.close()?;
+
+        Ok(buffer)
+    }
+}
+
+/// Builds the values and levels of the key or value column of the unmapped fields, which are
+/// null for the records without any.
+fn unmapped_column(
+    column: &ColumnDescriptor,
+    records: &[Record],
+) -> (Vec<ByteArray>, Vec<i16>, Vec<i16>) {
+    let is_key = column.path().parts().last().map(String::as_str) == Some("key");
+    let mut values = Vec::new();
+    let mut def_levels = Vec::new();
+    let mut rep_levels = Vec::new();
+    for record in records {
+        if record.unmapped.is_empty() {
+            def_levels.push(0);
+            rep_levels.push(0);
+            continue;
+        }
+        for (index, (key, value)) in record.unmapped.iter().enumerate() {
+            let value = if is_key { key } else { value };
+            values.push(ByteArray::from(value.as_str()));
+            def_levels.push(column.max_def_level());
+            rep_levels.push(if index == 0 { 0 } else { 1 });
+        }
+    }
+    (values, def_levels, rep_levels)
+}
+
+/// Converts the value to the type of the column, if it can be without losing information.
+fn convert(column: &ColumnDescriptor, value: &Value) -> Option<Cell> {
+    match (column.physical_type(), value) {
+        (PhysicalType::INT32, Value::Integer(value)) => i32::try_from(*value).ok().map(Cell::Int32),
+        (PhysicalType::INT64, Value::Integer(value)) => Some(Cell::Int64(*value)),
+        (PhysicalType::INT64, Value::Timestamp(timestamp)) => {
+            Some(Cell::Int64(timestamp.timestamp_millis()))
+        }
+        (PhysicalType::BYTE_ARRAY, Value::Bytes(bytes)) => Some(Cell::Bytes(ByteArray::from(
+            String::from_utf8_lossy(bytes).as_ref(),
+        ))),
+        (PhysicalType::BYTE_ARRAY, Value::Timestamp(timestamp)) => Some(Cell::Bytes(
+            ByteArray::from(timestamp.to_rfc3339().as_str()),
+        )),
+        (
+            PhysicalType::BYTE_ARRAY,
+            value @ (Value::Integer(_) | Value::Float(_) | Value::Boolean(_)),
+        ) => Some(Cell::Bytes(ByteArray::from(stringify(value).as_str()))),
+        _ => None,
+    }
+}
+
+/// The value of the required columns left without one, which only happens when the value of the
+/// log can't be converted.
+fn default_cell(column: &ColumnDescriptor) -> Cell {
+    match column.physical_type() {
+        PhysicalType::INT32 => Cell::Int32(0),
+        PhysicalType::INT64 => Cell::Int64(0),
+        _ => Cell::Bytes(ByteArray::from("")),
+    }
+}
+
+/// Formats the value as a string, the strings being kept as they are and the other values being
+/// encoded as JSON.
+fn stringify(value: &Value) -> String {
+    match value {
+        Value::Bytes(bytes) => String::from_utf8_lossy(bytes).into_owned(),
+        value => serde_json::to_string(value).unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use chrono::{TimeZone, Utc};
+    use parquet::{
+        file::reader::{FileReader, SerializedFileReader},
+        record::RowAccessor,
+    };
+
+    use super::*;
+
+    fn writer() -> OcsfWriter {
+        OcsfWriter::new(
+            OcsfDefaults {
+                class_uid: 1001,
+                product_name: "Vector".into(),
+                vendor_name: "Vector".into(),
+                region: "us-east-1".into(),
+                account_id: "123456789012".into(),
+            },
+            ParquetCompression::default(),
+        )
+    }
+
+    #[test]
+    fn fills_required_attributes() {
+        let mut log = LogEvent::from("hello");
+        log.insert("activity_id", 2);
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.ymd(2022, 10, 1).and_hms_milli(12, 30, 0, 250),
+        );
+        let record = writer().record(log);
+        let columns = writer().descriptor;
+        let cell = |path: &str| {
+            let index = columns
+                .columns()
+                .iter()
+                .filter(|column| column.max_rep_level() == 0)
+                .position(|column| column.path().string() == path)
+                .unwrap();
+            record.cells[index].clone()
+        };
+
+        assert_eq!(cell("class_uid"), Some(Cell::Int32(1001)));
+        assert_eq!(cell("category_uid"), Some(Cell::Int32(1)));
+        assert_eq!(cell("activity_id"), Some(Cell::Int32(2)));
+        assert_eq!(cell("type_uid"), Some(Cell::Int64(100102)));
+        assert_eq!(cell("time"), Some(Cell::Int64(1664627400250)));
+        assert_eq!(
+            cell("cloud.account.uid"),
+            Some(Cell::Bytes(ByteArray::from("123456789012")))
+        );
+        assert_eq!(cell("message"), Some(Cell::Bytes(ByteArray::from("hello"))));
+        assert_eq!(cell("status"), None);
+        assert!(record.unmapped.is_empty());
+    }
+
+    #[test]
+    fn keeps_unmapped_fields() {
+        let mut log = LogEvent::from("hello");
+        log.insert("user.name", "alice");
+        log.insert("severity_id", "high");
+        log.insert("metadata.product.feature", 3);
+        let record = writer().record(log);
+
+        assert_eq!(
+            record.unmapped,
+            vec![
+                ("metadata.product.feature".to_owned(), "3".to_owned()),
+                ("user.name".to_owned(), "alice".to_owned()),
+                ("severity_id".to_owned(), "high".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn writes_parquet_files() {
+        let mut first = LogEvent::from("first");
+        first.insert("user", "alice");
+        first.insert("group", "admins");
+        let second = LogEvent::from("second");
+
+        let file = writer().write(vec![first, second]).unwrap();
+        let reader = SerializedFileReader::new(Bytes::from(file)).unwrap();
+        assert_eq!(reader.metadata().file_metadata().num_rows(), 2);
+
+        let rows = reader.get_row_iter(None).unwrap().collect::<Vec<_>>();
+        assert_eq!(rows[0].get_string(8).unwrap(), "first");
+        assert_eq!(rows[1].get_string(8).unwrap(), "second");
+        assert_eq!(rows[0].get_int(4).unwrap(), 1001);
+        assert_eq!(rows[0].get_map(19).unwrap().len(), 2);
+        assert!(rows[1].get_map(19).is_err());
+    }
+}
@@ -0,0 +1,98 @@
+use std::io;
+
+use bytes::Bytes;
+use uuid::Uuid;
+use vector_core::ByteSizeOf;
+
+use super::ocsf::OcsfWriter;
+use crate::{
+    codecs::Transformer,
+    event::{Event, Finalizable},
+    sinks::{
+        s3_common::{
+            config::S3Options,
+            service::{S3Metadata, S3Request},
+        },
+        util::{encoding::Encoder, request_builder::EncodeResult, Compression, RequestBuilder},
+    },
+};
+
+/// Encodes the batches as Parquet files of OCSF records.
+#[derive(Clone)]
+pub(super) struct SecurityLakeEncoder {
+    pub(super) transformer: Transformer,
+    pub(super) writer: OcsfWriter,
+}
+
+impl Encoder<Vec<Event>> for SecurityLakeEncoder {
+    fn encode_input(&self, events: Vec<Event>, writer: &mut dyn io::Write) -> io::Result<usize> {
+        let logs = events
+            .into_iter()
+            .filter_map(|mut event| {
+                self.transformer.transform(&mut event);
+                event.try_into_log()
+            })
+            .collect();
+
+        let file = self.writer.write(logs)?;
+        writer.write_all(&file).map(|()| file.len())
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct SecurityLakeRequestBuilder {
+    pub(super) bucket: String,
+    pub(super) options: S3Options,
+    pub(super) encoder: SecurityLakeEncoder,
+}
+
+impl RequestBuilder<(String, Vec<Event>)> for SecurityLakeRequestBuilder {
+    type Metadata = S3Metadata;
+    type Events = Vec<Event>;
+    type Encoder = SecurityLakeEncoder;
+    type Payload = Bytes;
+    type Request = S3Request;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        // The pages of the Parquet files are compressed by the encoder itself.
+        Compression::None
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(&self, input: (String, Vec<Event>)) -> (Self::Metadata, Self::Events) {
+        let (partition_key, mut events) = input;
+        let finalizers = events.take_finalizers();
+        let metadata = S3Metadata {
+            partition_key,
+            count: events.len(),
+            byte_size: events.size_of(),
+            finalizers,
+        };
+
+        (metadata, events)
+    }
+
+    fn build_request(
+        &self,
+        mut metadata: Self::Metadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        metadata.partition_key = format!(
+            "{}{}.parquet",
+            metadata.partition_key,
+            Uuid::new_v4().hyphenated()
+        );
+
+        S3Request {
+            body: payload.into_payload(),
+            bucket: self.bucket.clone(),
+            metadata,
+            content_encoding: None,
+            options: self.options.clone(),
+        }
+    }
+}
@@ -1,15 +1,17 @@
 use bytes::Bytes;
 use chrono::Utc;
-use codecs::encoding::Framer;
 use uuid::Uuid;
 use vector_core::ByteSizeOf;
 
 use crate::{
-    codecs::{Encoder, Transformer},
     event::{Event, Finalizable},
     sinks::{
         azure_common::config::{AzureBlobMetadata, AzureBlobRequest},
-        util::{request_builder::EncodeResult, Compression, RequestBuilder},
+        util::{
+            encoding::{ObjectEncoder, PARQUET_CONTENT_TYPE},
+            request_builder::EncodeResult,
+            Compression, RequestBuilder,
+        },
     },
 };
 
@@ -18,14 +20,14 @@ pub struct AzureBlobRequestOptions {
     pub container_name: String,
     pub blob_time_format: String,
     pub blob_append_uuid: bool,
-    pub encoder: (Transformer, Encoder<Framer>),
+    pub encoder: ObjectEncoder,
     pub compression: Compression,
 }
 
 impl RequestBuilder<(String, Vec<Event>)> for AzureBlobRequestOptions {
     type Metadata = AzureBlobMetadata;
     type Events = Vec<Event>;
-    type Encoder = (Transformer, Encoder<Framer>);
+    type Encoder = ObjectEncoder;
     type Payload = Bytes;
     type Request = AzureBlobRequest;
     type Error = std::io::Error;
@@ -64,7 +66,7 @@ impl RequestBuilder<(String, Vec<Event>)> for AzureBlobRequestOptions {
                 .unwrap_or_else(|| formatted_ts.to_string())
         };
 
-        let extension = self.compression.extension();
+        let extension = self.encoder.extension(self.compression);
         metadata.partition_key = format!("{}{}.{}", metadata.partition_key, blob_name, extension);
 
         let payload = payload.into_payload();
@@ -80,7 +82,11 @@ impl RequestBuilder<(String, Vec<Event>)> for AzureBlobRequestOptions {
         AzureBlobRequest {
             blob_data: payload,
             content_encoding: self.compression.content_encoding(),
-            content_type: self.compression.content_type(),
+            content_type: if self.encoder.is_parquet() {
+                PARQUET_CONTENT_TYPE
+            } else {
+                self.compression.content_type()
+            },
             metadata,
         }
     }
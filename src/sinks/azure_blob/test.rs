@@ -10,7 +10,7 @@ use super::config::AzureBlobSinkConfig;
 use super::request_builder::AzureBlobRequestOptions;
 use crate::codecs::EncodingConfigWithFraming;
 use crate::event::{Event, LogEvent};
-use crate::sinks::util::{request_builder::RequestBuilder, Compression};
+use crate::sinks::util::{encoding::ObjectEncoder, request_builder::RequestBuilder, Compression};
 use crate::{codecs::Encoder, sinks::util::request_builder::EncodeResult};
 
 fn default_config(encoding: EncodingConfigWithFraming) -> AzureBlobSinkConfig {
@@ -57,13 +57,13 @@ fn azure_blob_build_request_without_compression() {
         container_name,
         blob_time_format,
         blob_append_uuid,
-        encoder: (
+        encoder: ObjectEncoder::Framed((
             Default::default(),
             Encoder::<Framer>::new(
                 NewlineDelimitedEncoder::new().into(),
                 TextSerializer::new().into(),
             ),
-        ),
+        )),
         compression,
     };
 
@@ -98,13 +98,13 @@ fn azure_blob_build_request_with_compression() {
         container_name,
         blob_time_format,
         blob_append_uuid,
-        encoder: (
+        encoder: ObjectEncoder::Framed((
             Default::default(),
             Encoder::<Framer>::new(
                 NewlineDelimitedEncoder::new().into(),
                 TextSerializer::new().into(),
             ),
-        ),
+        )),
         compression,
     };
 
@@ -139,13 +139,13 @@ fn azure_blob_build_request_with_time_format() {
         container_name,
         blob_time_format,
         blob_append_uuid,
-        encoder: (
+        encoder: ObjectEncoder::Framed((
             Default::default(),
             Encoder::<Framer>::new(
                 NewlineDelimitedEncoder::new().into(),
                 TextSerializer::new().into(),
             ),
-        ),
+        )),
         compression,
     };
 
@@ -183,13 +183,13 @@ fn azure_blob_build_request_with_uuid() {
         container_name,
         blob_time_format,
         blob_append_uuid,
-        encoder: (
+        encoder: ObjectEncoder::Framed((
             Default::default(),
             Encoder::<Framer>::new(
                 NewlineDelimitedEncoder::new().into(),
                 TextSerializer::new().into(),
             ),
-        ),
+        )),
         compression,
     };
 
@@ -0,0 +1,215 @@
+use std::{convert::TryFrom, sync::Arc};
+
+use azure_core::auth::TokenCredential;
+use azure_identity::{
+    AutoRefreshingTokenCredential, ClientSecretCredential, DefaultAzureCredential,
+    TokenCredentialOptions,
+};
+use codecs::{encoding::Framer, NewlineDelimitedEncoder};
+use futures::FutureExt;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    ingest::IngestClient,
+    request_builder::AzureDataExplorerRequestBuilder,
+    service::AzureDataExplorerService,
+    sink::{AzureDataExplorerSink, TablePartitioner},
+};
+use crate::{
+    codecs::{Encoder, EncodingConfig},
+    config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
+    http::HttpClient,
+    sinks::{
+        util::{BatchConfig, BulkSizeBasedDefaultBatchSettings, Compression, TowerRequestConfig},
+        Healthcheck, VectorSink,
+    },
+    template::Template,
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// The format Azure Data Explorer parses the ingested data as.
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Eq, PartialEq, Serialize)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AzureDataExplorerFormat {
+    /// JSON objects, such as the newline-delimited ones of the `json` codec.
+    #[derivative(Default)]
+    Json,
+
+    /// CSV records, one per line, such as the ones of the `text` codec for messages formatted as
+    /// CSV.
+    Csv,
+}
+
+impl AzureDataExplorerFormat {
+    pub(super) const fn as_str(self) -> &'static str {
+        match self {
+            Self::Json => "multijson",
+            Self::Csv => "csv",
+        }
+    }
+
+    pub(super) const fn mapping_kind(self) -> &'static str {
+        match self {
+            Self::Json => "Json",
+            Self::Csv => "Csv",
+        }
+    }
+}
+
+/// The credentials of an Azure AD application, used instead of Azure's default credentials.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AzureDataExplorerAuth {
+    pub(super) tenant_id: String,
+    pub(super) client_id: String,
+    pub(super) client_secret: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AzureDataExplorerSinkConfig {
+    /// The ingestion endpoint of the cluster, such as
+    /// `https://ingest-mycluster.westeurope.kusto.windows.net`.
+    pub(super) ingestion_endpoint: String,
+    /// The database the events are ingested into.
+    pub(super) database: String,
+    /// The table the events are ingested into.
+    pub(super) table: String,
+    #[serde(default)]
+    pub(super) format: AzureDataExplorerFormat,
+    /// The name of the ingestion mapping of the table to ingest the events with.
+    pub(super) ingestion_mapping: Option<String>,
+    pub(super) auth: Option<AzureDataExplorerAuth>,
+    pub(super) encoding: EncodingConfig,
+    #[serde(default = "Compression::gzip_default")]
+    pub(super) compression: Compression,
+    #[serde(default)]
+    pub(super) batch: BatchConfig<BulkSizeBasedDefaultBatchSettings>,
+    #[serde(default)]
+    pub(super) request: TowerRequestConfig,
+    pub(super) tls: Option<TlsConfig>,
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub(super) acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for AzureDataExplorerSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"ingestion_endpoint = "https://ingest-mycluster.westeurope.kusto.windows.net"
+            database = "logs"
+            table = "{{ application }}"
+            encoding.codec = "json""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "azure_data_explorer")]
+impl SinkConfig for AzureDataExplorerSinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls_settings, cx.proxy())?;
+        let ingest = Arc::new(IngestClient::new(
+            client,
+            self.ingestion_endpoint.clone(),
+            self.credential(),
+        ));
+
+        let transformer = self.encoding.transformer();
+        let serializer = self.encoding.build()?;
+        let encoder = Encoder::<Framer>::new(NewlineDelimitedEncoder::new().into(), serializer);
+        let request_builder = AzureDataExplorerRequestBuilder {
+            format: self.format,
+            mapping: self.ingestion_mapping.clone(),
+            encoder: (transformer, encoder),
+            compression: self.compression,
+        };
+        let partitioner = TablePartitioner {
+            database: Template::try_from(self.database.as_str())?,
+            table: Template::try_from(self.table.as_str())?,
+        };
+
+        let sink = AzureDataExplorerSink {
+            service: AzureDataExplorerService::new(Arc::clone(&ingest)),
+            request: self.request,
+            request_builder,
+            partitioner,
+            batch_settings: self.batch.into_batcher_settings()?,
+        };
+
+        let healthcheck = healthcheck(ingest).boxed();
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(self.encoding.config().input_type() & DataType::Log)
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "azure_data_explorer"
+    }
+
+    fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
+        Some(&self.acknowledgements)
+    }
+}
+
+impl AzureDataExplorerSinkConfig {
+    fn credential(&self) -> Arc<dyn TokenCredential> {
+        let credential: Arc<dyn TokenCredential> = match &self.auth {
+            Some(auth) => Arc::new(ClientSecretCredential::new(
+                auth.tenant_id.clone(),
+                auth.client_id.clone(),
+                auth.client_secret.clone(),
+                TokenCredentialOptions::default(),
+            )),
+            None => Arc::new(DefaultAzureCredential::default()),
+        };
+        Arc::new(AutoRefreshingTokenCredential::new(credential))
+    }
+}
+
+/// Checks that the cluster provides its ingestion resources to Vector.
+async fn healthcheck(ingest: Arc<IngestClient>) -> crate::Result<()> {
+    ingest.resources().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AzureDataExplorerSinkConfig>();
+    }
+
+    #[test]
+    fn parses_format() {
+        let config: AzureDataExplorerSinkConfig = toml::from_str(
+            r#"
+            ingestion_endpoint = "https://ingest-mycluster.westeurope.kusto.windows.net"
+            database = "logs"
+            table = "events"
+            format = "csv"
+            ingestion_mapping = "events_csv_mapping"
+            encoding.codec = "text"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.format, AzureDataExplorerFormat::Csv);
+        assert_eq!(config.format.as_str(), "csv");
+        assert_eq!(
+            config.ingestion_mapping.as_deref(),
+            Some("events_csv_mapping")
+        );
+        assert_eq!(config.compression, Compression::gzip_default());
+    }
+}
@@ -0,0 +1,239 @@
+use std::{sync::Arc, time::Duration};
+
+use azure_core::auth::TokenCredential;
+use bytes::Bytes;
+use http::{header, Request, Uri};
+use hyper::Body;
+use rand::seq::SliceRandom;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use snafu::ResultExt;
+use tokio::{sync::Mutex, time::Instant};
+
+use super::{
+    AzureDataExplorerError, InvalidResourcesSnafu, ReadResponseSnafu, RequestSnafu, ServerSnafu,
+};
+use crate::http::HttpClient;
+
+/// The Azure AD resource of Azure Data Explorer clusters.
+const KUSTO_RESOURCE: &str = "https://kusto.kusto.windows.net";
+
+/// How long the ingestion resources are used before they are fetched again.
+const RESOURCES_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// The storage resources and authorization context the cluster provides for queued ingestion.
+#[derive(Debug)]
+pub(super) struct IngestionResources {
+    /// The URIs, with SAS tokens, of the queues ingestion messages are posted to.
+    queues: Vec<String>,
+    /// The URIs, with SAS tokens, of the containers the data is uploaded to.
+    containers: Vec<String>,
+    /// The token authorizing the ingestion of the data on behalf of the caller.
+    pub(super) authorization_context: String,
+}
+
+impl IngestionResources {
+    pub(super) fn queue(&self) -> &str {
+        self.queues
+            .choose(&mut rand::thread_rng())
+            .expect("resources have queues")
+    }
+
+    pub(super) fn container(&self) -> &str {
+        self.containers
+            .choose(&mut rand::thread_rng())
+            .expect("resources have containers")
+    }
+}
+
+/// A management command response, which is made of tables of rows.
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CommandResponse {
+    tables: Vec<CommandTable>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CommandTable {
+    rows: Vec<Vec<Value>>,
+}
+
+/// Runs the management commands of the ingestion endpoint of a cluster.
+pub(super) struct IngestClient {
+    client: HttpClient,
+    endpoint: String,
+    credential: Arc<dyn TokenCredential>,
+    resources: Mutex<Option<(Instant, Arc<IngestionResources>)>>,
+}
+
+impl IngestClient {
+    pub(super) fn new(
+        client: HttpClient,
+        endpoint: String,
+        credential: Arc<dyn TokenCredential>,
+    ) -> Self {
+        Self {
+            client,
+            endpoint: endpoint.trim_end_matches('/').to_owned(),
+            credential,
+            resources: Mutex::new(None),
+        }
+    }
+
+    pub(super) const fn http_client(&self) -> &HttpClient {
+        &self.client
+    }
+
+    /// Returns the ingestion resources, fetching them again once they are an hour old.
+    pub(super) async fn resources(
+        &self,
+    ) -> Result<Arc<IngestionResources>, AzureDataExplorerError> {
+        let mut cached = self.resources.lock().await;
+        if let Some((fetched_at, resources)) = &*cached {
+            if fetched_at.elapsed() < RESOURCES_TTL {
+                return Ok(Arc::clone(resources));
+            }
+        }
+
+        let resources = Arc::new(self.fetch_resources().await?);
+        *cached = Some((Instant::now(), Arc::clone(&resources)));
+        Ok(resources)
+    }
+
+    async fn fetch_resources(&self) -> Result<IngestionResources, AzureDataExplorerError> {
+        let mut queues = Vec::new();
+        let mut containers = Vec::new();
+        for row in self.command(".get ingestion resources").await? {
+            match (
+                row.get(0).and_then(Value::as_str),
+                row.get(1).and_then(Value::as_str),
+            ) {
+                (Some("SecuredReadyForAggregationQueue"), Some(uri)) => queues.push(uri.to_owned()),
+                (Some("TempStorage"), Some(uri)) => containers.push(uri.to_owned()),
+                _ => {}
+            }
+        }
+        if queues.is_empty() || containers.is_empty() {
+            return InvalidResourcesSnafu {
+                message: "the cluster provided no ingestion queues or temporary storage",
+            }
+            .fail();
+        }
+
+        let authorization_context = self
+            .command(".get kusto identity token")
+            .await?
+            .into_iter()
+            .next()
+            .and_then(|row| row.into_iter().next())
+            .and_then(|token| token.as_str().map(ToOwned::to_owned))
+            .ok_or_else(|| AzureDataExplorerError::InvalidResources {
+                message: "the cluster provided no identity token".into(),
+            })?;
+
+        Ok(IngestionResources {
+            queues,
+            containers,
+            authorization_context,
+        })
+    }
+
+    /// Runs the management command, returning the rows of its first table.
+    async fn command(&self, command: &str) -> Result<Vec<Vec<Value>>, AzureDataExplorerError> {
+        let token = self
+            .credential
+            .get_token(KUSTO_RESOURCE)
+            .await
+            .map_err(|error| AzureDataExplorerError::Token {
+                source: error.into(),
+            })?;
+        let body = json!({ "csl": command, "db": "NetDefaultDB" });
+        let request = Request::post(format!("{}/v1/rest/mgmt", self.endpoint))
+            .header(
+                header::AUTHORIZATION,
+                format!("Bearer {}", token.token.secret()),
+            )
+            .header(header::ACCEPT, "application/json")
+            .header(header::CONTENT_TYPE, "application/json; charset=utf-8")
+            .body(Body::from(body.to_string()))
+            .expect("management request is valid");
+
+        let body = send(&self.client, request).await?;
+        let response: CommandResponse = serde_json::from_slice(&body).map_err(|error| {
+            AzureDataExplorerError::InvalidResources {
+                message: error.to_string(),
+            }
+        })?;
+        Ok(response
+            .tables
+            .into_iter()
+            .next()
+            .map(|table| table.rows)
+            .unwrap_or_default())
+    }
+}
+
+/// Sends the request, returning the body of its successful response.
+pub(super) async fn send(
+    client: &HttpClient,
+    request: Request<Body>,
+) -> Result<Bytes, AzureDataExplorerError> {
+    let response = client.send(request).await.context(RequestSnafu)?;
+    let (parts, body) = response.into_parts();
+    let body = hyper::body::to_bytes(body)
+        .await
+        .context(ReadResponseSnafu)?;
+    if parts.status.is_success() {
+        Ok(body)
+    } else {
+        ServerSnafu {
+            status: parts.status,
+            body: String::from_utf8_lossy(&body),
+        }
+        .fail()
+    }
+}
+
+/// Inserts the path into the URI of a storage resource, ahead of its SAS token.
+pub(super) fn resource_uri(resource: &str, path: &str) -> Result<Uri, AzureDataExplorerError> {
+    let uri = match resource.split_once('?') {
+        Some((base, sas)) => format!("{}/{}?{}", base.trim_end_matches('/'), path, sas),
+        None => format!("{}/{}", resource.trim_end_matches('/'), path),
+    };
+    uri.parse().map_err(
+        |error: http::uri::InvalidUri| AzureDataExplorerError::InvalidResources {
+            message: error.to_string(),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inserts_path_ahead_of_sas_token() {
+        let uri = resource_uri(
+            "https://account.blob.core.windows.net/container?sv=2018-03-28&sig=c2ln",
+            "database__table.multijson.gz",
+        )
+        .unwrap();
+
+        assert_eq!(
+            uri.to_string(),
+            "https://account.blob.core.windows.net/container/database__table.multijson.gz?sv=2018-03-28&sig=c2ln"
+        );
+    }
+
+    #[test]
+    fn parses_command_response() {
+        let response: CommandResponse = serde_json::from_str(
+            r#"{"Tables":[{"TableName":"Table_0","Columns":[{"ColumnName":"ResourceTypeName","DataType":"String"},{"ColumnName":"StorageRoot","DataType":"String"}],"Rows":[["SecuredReadyForAggregationQueue","https://account.queue.core.windows.net/queue?sig=c2ln"],["TempStorage","https://account.blob.core.windows.net/container?sig=c2ln"]]}]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(response.tables[0].rows.len(), 2);
+        assert_eq!(response.tables[0].rows[1][0], "TempStorage");
+    }
+}
@@ -0,0 +1,52 @@
+//! The `azure_data_explorer` sink.
+//!
+//! Events are ingested with the queued ingestion of Azure Data Explorer (Kusto): each batch is
+//! uploaded to one of the temporary storage containers of the cluster, and an ingestion message
+//! referencing the blob is posted to one of its ingestion queues.
+
+use http::StatusCode;
+use snafu::Snafu;
+
+use crate::{config::SinkDescription, http::HttpError};
+
+mod config;
+mod ingest;
+mod request_builder;
+mod service;
+mod sink;
+
+pub use self::config::AzureDataExplorerSinkConfig;
+
+inventory::submit! {
+    SinkDescription::new::<AzureDataExplorerSinkConfig>("azure_data_explorer")
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum AzureDataExplorerError {
+    #[snafu(display("Failed to get an Azure AD token: {}", source))]
+    Token { source: crate::Error },
+
+    #[snafu(display("Failed to make request: {}", source))]
+    Request { source: HttpError },
+
+    #[snafu(display("Failed to read response: {}", source))]
+    ReadResponse { source: hyper::Error },
+
+    #[snafu(display("Server responded with status {}: {}", status, body))]
+    ServerError { status: StatusCode, body: String },
+
+    #[snafu(display("Invalid ingestion resources: {}", message))]
+    InvalidResources { message: String },
+}
+
+impl AzureDataExplorerError {
+    pub(super) fn is_retriable(&self) -> bool {
+        match self {
+            Self::Token { .. } | Self::Request { .. } | Self::ReadResponse { .. } => true,
+            Self::ServerError { status, .. } => {
+                status.is_server_error() || *status == StatusCode::TOO_MANY_REQUESTS
+            }
+            Self::InvalidResources { .. } => false,
+        }
+    }
+}
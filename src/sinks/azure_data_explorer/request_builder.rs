@@ -0,0 +1,137 @@
+use bytes::Bytes;
+use codecs::encoding::Framer;
+use uuid::Uuid;
+use vector_core::ByteSizeOf;
+
+use super::{
+    config::AzureDataExplorerFormat,
+    service::{AzureDataExplorerMetadata, AzureDataExplorerRequest},
+};
+use crate::{
+    codecs::{Encoder, Transformer},
+    event::{Event, Finalizable},
+    sinks::util::{request_builder::EncodeResult, Compression, RequestBuilder},
+};
+
+#[derive(Clone)]
+pub(super) struct AzureDataExplorerRequestBuilder {
+    pub(super) format: AzureDataExplorerFormat,
+    pub(super) mapping: Option<String>,
+    pub(super) encoder: (Transformer, Encoder<Framer>),
+    pub(super) compression: Compression,
+}
+
+impl RequestBuilder<((String, String), Vec<Event>)> for AzureDataExplorerRequestBuilder {
+    type Metadata = AzureDataExplorerMetadata;
+    type Events = Vec<Event>;
+    type Encoder = (Transformer, Encoder<Framer>);
+    type Payload = Bytes;
+    type Request = AzureDataExplorerRequest;
+    type Error = std::io::Error;
+
+    fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(&self, input: ((String, String), Vec<Event>)) -> (Self::Metadata, Self::Events) {
+        let ((database, table), mut events) = input;
+        let finalizers = events.take_finalizers();
+        let metadata = AzureDataExplorerMetadata {
+            database,
+            table,
+            count: events.len(),
+            byte_size: events.size_of(),
+            finalizers,
+        };
+
+        (metadata, events)
+    }
+
+    fn build_request(
+        &self,
+        metadata: Self::Metadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        // Azure Data Explorer detects the compression of the data from the extension of the blob.
+        let mut blob_name = format!(
+            "{}__{}__{}.{}",
+            metadata.database,
+            metadata.table,
+            Uuid::new_v4().hyphenated(),
+            self.format.as_str(),
+        );
+        if self.compression.is_compressed() {
+            blob_name.push('.');
+            blob_name.push_str(self.compression.extension());
+        }
+
+        AzureDataExplorerRequest {
+            raw_data_size: payload.uncompressed_byte_size,
+            body: payload.into_payload(),
+            blob_name,
+            format: self.format.as_str(),
+            mapping_kind: self.format.mapping_kind(),
+            mapping: self.mapping.clone(),
+            metadata,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codecs::{JsonSerializer, NewlineDelimitedEncoder};
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    fn request_builder(compression: Compression) -> AzureDataExplorerRequestBuilder {
+        AzureDataExplorerRequestBuilder {
+            format: AzureDataExplorerFormat::Json,
+            mapping: Some("events_mapping".into()),
+            encoder: (
+                Default::default(),
+                Encoder::<Framer>::new(
+                    NewlineDelimitedEncoder::new().into(),
+                    JsonSerializer::new().into(),
+                ),
+            ),
+            compression,
+        }
+    }
+
+    #[test]
+    fn names_blob_after_table_and_format() {
+        let builder = request_builder(Compression::gzip_default());
+        let (metadata, events) = builder.split_input((
+            ("logs".into(), "events".into()),
+            vec![Event::from(LogEvent::from("hello"))],
+        ));
+        let payload = EncodeResult {
+            payload: Bytes::from("{}"),
+            uncompressed_byte_size: 20,
+            compressed_byte_size: Some(2),
+        };
+        let request = builder.build_request(metadata, payload);
+
+        assert_eq!(events.len(), 1);
+        assert!(request.blob_name.starts_with("logs__events__"));
+        assert!(request.blob_name.ends_with(".multijson.gz"));
+        assert_eq!(request.raw_data_size, 20);
+        assert_eq!(request.format, "multijson");
+        assert_eq!(request.mapping.as_deref(), Some("events_mapping"));
+        assert_eq!(request.metadata.count, 1);
+    }
+
+    #[test]
+    fn omits_extension_of_uncompressed_blobs() {
+        let builder = request_builder(Compression::None);
+        let (metadata, _) = builder.split_input((("logs".into(), "events".into()), vec![]));
+        let request = builder.build_request(metadata, EncodeResult::uncompressed(Bytes::new()));
+
+        assert!(request.blob_name.ends_with(".multijson"));
+    }
+}
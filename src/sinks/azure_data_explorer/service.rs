@@ -0,0 +1,234 @@
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use http::{header, Request};
+use hyper::Body;
+use serde_json::json;
+use tower::Service;
+use uuid::Uuid;
+use vector_core::{
+    internal_event::{BytesSent, EventsSent},
+    stream::DriverResponse,
+};
+
+use super::{
+    ingest::{resource_uri, send, IngestClient},
+    AzureDataExplorerError,
+};
+use crate::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    sinks::util::retries::RetryLogic,
+};
+
+/// The version of the Azure Storage REST API the blobs and queue messages are sent with.
+const STORAGE_API_VERSION: &str = "2019-12-12";
+
+#[derive(Clone, Debug)]
+pub(super) struct AzureDataExplorerRequest {
+    pub(super) body: Bytes,
+    pub(super) blob_name: String,
+    pub(super) raw_data_size: usize,
+    pub(super) format: &'static str,
+    pub(super) mapping_kind: &'static str,
+    pub(super) mapping: Option<String>,
+    pub(super) metadata: AzureDataExplorerMetadata,
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct AzureDataExplorerMetadata {
+    pub(super) database: String,
+    pub(super) table: String,
+    pub(super) count: usize,
+    pub(super) byte_size: usize,
+    pub(super) finalizers: EventFinalizers,
+}
+
+impl Finalizable for AzureDataExplorerRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.metadata.finalizers)
+    }
+}
+
+impl AzureDataExplorerRequest {
+    /// Builds the ingestion message of the request, which references the blob the data was
+    /// uploaded to.
+    fn ingestion_message(&self, blob_path: String, authorization_context: &str) -> String {
+        let mut properties = json!({
+            "authorizationContext": authorization_context,
+            "format": self.format,
+        });
+        if let Some(mapping) = &self.mapping {
+            properties["ingestionMappingReference"] = mapping.as_str().into();
+            properties["ingestionMappingType"] = self.mapping_kind.into();
+        }
+
+        json!({
+            "Id": Uuid::new_v4().to_string(),
+            "BlobPath": blob_path,
+            "RawDataSize": self.raw_data_size,
+            "DatabaseName": self.metadata.database,
+            "TableName": self.metadata.table,
+            "RetainBlobOnSuccess": false,
+            "FlushImmediately": false,
+            // Report failures only, to the failure queue of the cluster.
+            "ReportLevel": 0,
+            "ReportMethod": 0,
+            "AdditionalProperties": properties,
+        })
+        .to_string()
+    }
+}
+
+pub(super) struct AzureDataExplorerResponse {
+    count: usize,
+    events_byte_size: usize,
+    byte_size: usize,
+}
+
+impl DriverResponse for AzureDataExplorerResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> EventsSent {
+        EventsSent {
+            count: self.count,
+            byte_size: self.events_byte_size,
+            output: None,
+        }
+    }
+
+    fn bytes_sent(&self) -> Option<BytesSent> {
+        Some(BytesSent {
+            byte_size: self.byte_size,
+            protocol: "https",
+        })
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct AzureDataExplorerService {
+    ingest: Arc<IngestClient>,
+}
+
+impl AzureDataExplorerService {
+    pub(super) const fn new(ingest: Arc<IngestClient>) -> Self {
+        Self { ingest }
+    }
+}
+
+impl Service<AzureDataExplorerRequest> for AzureDataExplorerService {
+    type Response = AzureDataExplorerResponse;
+    type Error = AzureDataExplorerError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: AzureDataExplorerRequest) -> Self::Future {
+        let ingest = Arc::clone(&self.ingest);
+
+        Box::pin(async move {
+            let resources = ingest.resources().await?;
+            let client = ingest.http_client();
+
+            // Upload the data to the temporary storage of the cluster.
+            let blob_uri = resource_uri(resources.container(), &request.blob_name)?;
+            let upload = Request::put(blob_uri.clone())
+                .header("x-ms-blob-type", "BlockBlob")
+                .header("x-ms-version", STORAGE_API_VERSION)
+                .header(header::CONTENT_LENGTH, request.body.len())
+                .body(Body::from(request.body.clone()))
+                .expect("upload request is valid");
+            send(client, upload).await?;
+
+            // Then queue its ingestion.
+            let message =
+                request.ingestion_message(blob_uri.to_string(), &resources.authorization_context);
+            let enqueue = Request::post(resource_uri(resources.queue(), "messages")?)
+                .header("x-ms-version", STORAGE_API_VERSION)
+                .header(header::CONTENT_TYPE, "application/xml")
+                .body(Body::from(format!(
+                    "<QueueMessage><MessageText>{}</MessageText></QueueMessage>",
+                    base64::encode(message)
+                )))
+                .expect("enqueue request is valid");
+            send(client, enqueue).await?;
+
+            Ok(AzureDataExplorerResponse {
+                count: request.metadata.count,
+                events_byte_size: request.metadata.byte_size,
+                byte_size: request.body.len(),
+            })
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct AzureDataExplorerRetryLogic;
+
+impl RetryLogic for AzureDataExplorerRetryLogic {
+    type Error = AzureDataExplorerError;
+    type Response = AzureDataExplorerResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        error.is_retriable()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+
+    fn request(mapping: Option<&str>) -> AzureDataExplorerRequest {
+        AzureDataExplorerRequest {
+            body: Bytes::from("{}"),
+            blob_name: "logs__events__id.multijson.gz".into(),
+            raw_data_size: 2,
+            format: "multijson",
+            mapping_kind: "Json",
+            mapping: mapping.map(Into::into),
+            metadata: AzureDataExplorerMetadata {
+                database: "logs".into(),
+                table: "events".into(),
+                count: 1,
+                byte_size: 2,
+                finalizers: Default::default(),
+            },
+        }
+    }
+
+    #[test]
+    fn builds_ingestion_message() {
+        let message = request(Some("events_mapping"))
+            .ingestion_message("https://blob/path?sig=c2ln".into(), "token");
+        let message: Value = serde_json::from_str(&message).unwrap();
+
+        assert_eq!(message["BlobPath"], "https://blob/path?sig=c2ln");
+        assert_eq!(message["RawDataSize"], 2);
+        assert_eq!(message["DatabaseName"], "logs");
+        assert_eq!(message["TableName"], "events");
+        let properties = &message["AdditionalProperties"];
+        assert_eq!(properties["authorizationContext"], "token");
+        assert_eq!(properties["format"], "multijson");
+        assert_eq!(properties["ingestionMappingReference"], "events_mapping");
+        assert_eq!(properties["ingestionMappingType"], "Json");
+    }
+
+    #[test]
+    fn omits_mapping_when_unset() {
+        let message = request(None).ingestion_message("https://blob/path".into(), "token");
+        let message: Value = serde_json::from_str(&message).unwrap();
+
+        assert!(message["AdditionalProperties"]
+            .get("ingestionMappingReference")
+            .is_none());
+    }
+}
@@ -0,0 +1,91 @@
+use std::num::NonZeroUsize;
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use tower::ServiceBuilder;
+use vector_core::{partition::Partitioner, sink::StreamSink, stream::BatcherSettings};
+
+use super::{
+    request_builder::AzureDataExplorerRequestBuilder,
+    service::{AzureDataExplorerRetryLogic, AzureDataExplorerService},
+};
+use crate::{
+    event::Event,
+    internal_events::TemplateRenderingError,
+    sinks::util::{ServiceBuilderExt, SinkBuilderExt, TowerRequestConfig},
+    template::Template,
+};
+
+/// Partitions the events by the database and table they are ingested into.
+pub(super) struct TablePartitioner {
+    pub(super) database: Template,
+    pub(super) table: Template,
+}
+
+impl TablePartitioner {
+    fn render(template: &Template, event: &Event, field: &'static str) -> Option<String> {
+        template
+            .render_string(event)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some(field),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+}
+
+impl Partitioner for TablePartitioner {
+    type Item = Event;
+    type Key = Option<(String, String)>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        let database = Self::render(&self.database, item, "database")?;
+        let table = Self::render(&self.table, item, "table")?;
+        Some((database, table))
+    }
+}
+
+pub(super) struct AzureDataExplorerSink {
+    pub(super) service: AzureDataExplorerService,
+    pub(super) request: TowerRequestConfig,
+    pub(super) request_builder: AzureDataExplorerRequestBuilder,
+    pub(super) partitioner: TablePartitioner,
+    pub(super) batch_settings: BatcherSettings,
+}
+
+impl AzureDataExplorerSink {
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let request = self.request.unwrap_with(&TowerRequestConfig::default());
+        let service = ServiceBuilder::new()
+            .settings(request, AzureDataExplorerRetryLogic)
+            .service(self.service);
+        let builder_limit = NonZeroUsize::new(64);
+
+        input
+            .batched_partitioned(self.partitioner, self.batch_settings)
+            .filter_map(|(key, batch)| async move { key.map(move |key| (key, batch)) })
+            .request_builder(builder_limit, self.request_builder)
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        error!("Failed to build Azure Data Explorer request: {:?}.", error);
+                        None
+                    }
+                    Ok(request) => Some(request),
+                }
+            })
+            .into_driver(service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for AzureDataExplorerSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
@@ -0,0 +1,200 @@
+use std::{convert::TryFrom, sync::Arc};
+
+use azure_core::auth::TokenCredential;
+use azure_identity::{
+    AutoRefreshingTokenCredential, ClientSecretCredential, DefaultAzureCredential,
+    TokenCredentialOptions,
+};
+use futures::FutureExt;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    request_builder::{AzureMonitorLogsDcrEncoder, AzureMonitorLogsDcrRequestBuilder},
+    service::{AzureMonitorLogsDcrService, IngestionClient},
+    sink::{AzureMonitorLogsDcrSink, StreamPartitioner},
+};
+use crate::{
+    codecs::Transformer,
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    http::HttpClient,
+    sinks::{
+        util::{BatchConfig, Compression, SinkBatchSettings, TowerRequestConfig},
+        Healthcheck, VectorSink,
+    },
+    template::Template,
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// The maximum size of the body of the requests accepted by the Logs Ingestion API.
+const MAX_BATCH_SIZE: usize = 1_000_000;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AzureMonitorLogsDcrDefaultBatchSettings;
+
+impl SinkBatchSettings for AzureMonitorLogsDcrDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(MAX_BATCH_SIZE);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// The credentials of an Azure AD application, used instead of Azure's default credentials.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AzureMonitorLogsDcrAuth {
+    pub(super) tenant_id: String,
+    pub(super) client_id: String,
+    pub(super) client_secret: String,
+}
+
+fn default_time_generated_key() -> String {
+    "TimeGenerated".into()
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AzureMonitorLogsDcrSinkConfig {
+    /// The logs ingestion endpoint of the data collection endpoint, such as
+    /// `https://my-dce-a1b2.westeurope-1.ingest.monitor.azure.com`.
+    pub(super) endpoint: String,
+    /// The immutable ID of the data collection rule, such as
+    /// `dcr-00000000000000000000000000000000`.
+    pub(super) dcr_immutable_id: String,
+    /// The stream of the data collection rule the events are sent to, such as
+    /// `Custom-VectorLogs_CL`.
+    pub(super) stream_name: String,
+    pub(super) auth: Option<AzureMonitorLogsDcrAuth>,
+    /// The field the timestamp of the events is sent as.
+    #[serde(default = "default_time_generated_key")]
+    pub(super) time_generated_key: String,
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub(super) encoding: Transformer,
+    #[serde(default = "Compression::gzip_default")]
+    pub(super) compression: Compression,
+    #[serde(default)]
+    pub(super) batch: BatchConfig<AzureMonitorLogsDcrDefaultBatchSettings>,
+    #[serde(default)]
+    pub(super) request: TowerRequestConfig,
+    pub(super) tls: Option<TlsConfig>,
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub(super) acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for AzureMonitorLogsDcrSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"endpoint = "https://my-dce-a1b2.westeurope-1.ingest.monitor.azure.com"
+            dcr_immutable_id = "dcr-00000000000000000000000000000000"
+            stream_name = "Custom-VectorLogs_CL""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "azure_monitor_logs_dcr")]
+impl SinkConfig for AzureMonitorLogsDcrSinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        if matches!(self.compression, Compression::Zlib(_)) {
+            return Err("The Logs Ingestion API only supports gzip compression.".into());
+        }
+
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls_settings, cx.proxy())?;
+        let ingestion = Arc::new(IngestionClient::new(
+            client,
+            &self.endpoint,
+            self.dcr_immutable_id.clone(),
+            self.credential(),
+        ));
+
+        let request_builder = AzureMonitorLogsDcrRequestBuilder {
+            encoder: AzureMonitorLogsDcrEncoder {
+                transformer: self.encoding.clone(),
+                time_generated_key: self.time_generated_key.clone(),
+            },
+            compression: self.compression,
+        };
+        let batch_settings = self
+            .batch
+            .validate()?
+            .limit_max_bytes(MAX_BATCH_SIZE)?
+            .into_batcher_settings()?;
+
+        let sink = AzureMonitorLogsDcrSink {
+            service: AzureMonitorLogsDcrService::new(Arc::clone(&ingestion)),
+            request: self.request,
+            request_builder,
+            partitioner: StreamPartitioner(Template::try_from(self.stream_name.as_str())?),
+            batch_settings,
+        };
+
+        let healthcheck = healthcheck(ingestion).boxed();
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "azure_monitor_logs_dcr"
+    }
+
+    fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
+        Some(&self.acknowledgements)
+    }
+}
+
+impl AzureMonitorLogsDcrSinkConfig {
+    fn credential(&self) -> Arc<dyn TokenCredential> {
+        let credential: Arc<dyn TokenCredential> = match &self.auth {
+            Some(auth) => Arc::new(ClientSecretCredential::new(
+                auth.tenant_id.clone(),
+                auth.client_id.clone(),
+                auth.client_secret.clone(),
+                TokenCredentialOptions::default(),
+            )),
+            None => Arc::new(DefaultAzureCredential::default()),
+        };
+        Arc::new(AutoRefreshingTokenCredential::new(credential))
+    }
+}
+
+/// Checks that a token for the Logs Ingestion API can be acquired, as the API has no endpoint to
+/// check that logs can be sent without sending any.
+async fn healthcheck(ingestion: Arc<IngestionClient>) -> crate::Result<()> {
+    ingestion.token().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AzureMonitorLogsDcrSinkConfig>();
+    }
+
+    #[tokio::test]
+    async fn rejects_zlib_compression() {
+        let config: AzureMonitorLogsDcrSinkConfig = toml::from_str(
+            r#"
+            endpoint = "https://my-dce-a1b2.westeurope-1.ingest.monitor.azure.com"
+            dcr_immutable_id = "dcr-00000000000000000000000000000000"
+            stream_name = "Custom-VectorLogs_CL"
+            compression = "zlib"
+            "#,
+        )
+        .unwrap();
+
+        assert!(config.build(SinkContext::new_test()).await.is_err());
+    }
+}
@@ -0,0 +1,48 @@
+//! The `azure_monitor_logs_dcr` sink.
+//!
+//! Events are sent to the Logs Ingestion API of Azure Monitor, which replaces the deprecated HTTP
+//! Data Collector API used by the `azure_monitor_logs` sink: each batch is posted as a JSON array
+//! to a stream of a data collection rule (DCR), through a data collection endpoint (DCE), which
+//! routes it to Log Analytics tables, including the ones of Microsoft Sentinel.
+
+use http::StatusCode;
+use snafu::Snafu;
+
+use crate::{config::SinkDescription, http::HttpError};
+
+mod config;
+mod request_builder;
+mod service;
+mod sink;
+
+pub use self::config::AzureMonitorLogsDcrSinkConfig;
+
+inventory::submit! {
+    SinkDescription::new::<AzureMonitorLogsDcrSinkConfig>("azure_monitor_logs_dcr")
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum AzureMonitorLogsDcrError {
+    #[snafu(display("Failed to get an Azure AD token: {}", source))]
+    Token { source: crate::Error },
+
+    #[snafu(display("Failed to make request: {}", source))]
+    Request { source: HttpError },
+
+    #[snafu(display("Failed to read response: {}", source))]
+    ReadResponse { source: hyper::Error },
+
+    #[snafu(display("Server responded with status {}: {}", status, body))]
+    ServerError { status: StatusCode, body: String },
+}
+
+impl AzureMonitorLogsDcrError {
+    pub(super) fn is_retriable(&self) -> bool {
+        match self {
+            Self::Token { .. } | Self::Request { .. } | Self::ReadResponse { .. } => true,
+            Self::ServerError { status, .. } => {
+                status.is_server_error() || *status == StatusCode::TOO_MANY_REQUESTS
+            }
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use std::io;
+
+use bytes::Bytes;
+use serde_json::Value as JsonValue;
+use vector_core::ByteSizeOf;
+
+use super::service::{AzureMonitorLogsDcrMetadata, AzureMonitorLogsDcrRequest};
+use crate::{
+    codecs::Transformer,
+    config::log_schema,
+    event::{Event, Finalizable, Value},
+    sinks::util::{encoding::Encoder, request_builder::EncodeResult, Compression, RequestBuilder},
+};
+
+/// Encodes the batches as JSON arrays of the logs, as expected by the Logs Ingestion API.
+#[derive(Clone)]
+pub(super) struct AzureMonitorLogsDcrEncoder {
+    pub(super) transformer: Transformer,
+    /// The field the timestamp of the logs is moved to, as the `TimeGenerated` column of Log
+    /// Analytics tables is usually read from the field of the same name.
+    pub(super) time_generated_key: String,
+}
+
+impl Encoder<Vec<Event>> for AzureMonitorLogsDcrEncoder {
+    fn encode_input(&self, events: Vec<Event>, writer: &mut dyn io::Write) -> io::Result<usize> {
+        let timestamp_key = log_schema().timestamp_key();
+        let logs = events
+            .into_iter()
+            .filter_map(|mut event| {
+                self.transformer.transform(&mut event);
+                let mut log = event.try_into_log()?;
+                if let Some(Value::Timestamp(timestamp)) = log.remove(timestamp_key) {
+                    log.insert(
+                        self.time_generated_key.as_str(),
+                        timestamp.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+                    );
+                }
+                serde_json::to_value(log).ok()
+            })
+            .collect::<Vec<_>>();
+
+        let body = serde_json::to_vec(&JsonValue::Array(logs))?;
+        writer.write_all(&body).map(|()| body.len())
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct AzureMonitorLogsDcrRequestBuilder {
+    pub(super) encoder: AzureMonitorLogsDcrEncoder,
+    pub(super) compression: Compression,
+}
+
+impl RequestBuilder<(String, Vec<Event>)> for AzureMonitorLogsDcrRequestBuilder {
+    type Metadata = AzureMonitorLogsDcrMetadata;
+    type Events = Vec<Event>;
+    type Encoder = AzureMonitorLogsDcrEncoder;
+    type Payload = Bytes;
+    type Request = AzureMonitorLogsDcrRequest;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(&self, input: (String, Vec<Event>)) -> (Self::Metadata, Self::Events) {
+        let (stream, mut events) = input;
+        let finalizers = events.take_finalizers();
+        let metadata = AzureMonitorLogsDcrMetadata {
+            stream,
+            count: events.len(),
+            byte_size: events.size_of(),
+            finalizers,
+        };
+
+        (metadata, events)
+    }
+
+    fn build_request(
+        &self,
+        metadata: Self::Metadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        AzureMonitorLogsDcrRequest {
+            body: payload.into_payload(),
+            compression: self.compression,
+            metadata,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    #[test]
+    fn encodes_logs_as_json_array() {
+        let encoder = AzureMonitorLogsDcrEncoder {
+            transformer: Default::default(),
+            time_generated_key: "TimeGenerated".into(),
+        };
+        let mut log = LogEvent::from("hello");
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.ymd(2022, 10, 1).and_hms_milli(12, 30, 0, 250),
+        );
+
+        let mut body = Vec::new();
+        encoder
+            .encode_input(vec![Event::from(log)], &mut body)
+            .unwrap();
+        let body: JsonValue = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(
+            body,
+            serde_json::json!([{
+                "message": "hello",
+                "TimeGenerated": "2022-10-01T12:30:00.250Z",
+            }])
+        );
+    }
+}
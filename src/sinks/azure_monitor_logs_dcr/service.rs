@@ -0,0 +1,232 @@
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use azure_core::auth::TokenCredential;
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use http::{header, Request};
+use hyper::Body;
+use snafu::ResultExt;
+use tower::Service;
+use vector_core::{
+    internal_event::{BytesSent, EventsSent},
+    stream::DriverResponse,
+};
+
+use super::{AzureMonitorLogsDcrError, ReadResponseSnafu, RequestSnafu, ServerSnafu};
+use crate::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    http::HttpClient,
+    sinks::util::{retries::RetryLogic, Compression},
+};
+
+/// The Azure AD resource of the Logs Ingestion API.
+const MONITOR_RESOURCE: &str = "https://monitor.azure.com";
+
+/// The version of the Logs Ingestion API the logs are sent with.
+const API_VERSION: &str = "2023-01-01";
+
+#[derive(Clone, Debug)]
+pub(super) struct AzureMonitorLogsDcrRequest {
+    pub(super) body: Bytes,
+    pub(super) compression: Compression,
+    pub(super) metadata: AzureMonitorLogsDcrMetadata,
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct AzureMonitorLogsDcrMetadata {
+    pub(super) stream: String,
+    pub(super) count: usize,
+    pub(super) byte_size: usize,
+    pub(super) finalizers: EventFinalizers,
+}
+
+impl Finalizable for AzureMonitorLogsDcrRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.metadata.finalizers)
+    }
+}
+
+pub(super) struct AzureMonitorLogsDcrResponse {
+    count: usize,
+    events_byte_size: usize,
+    byte_size: usize,
+}
+
+impl DriverResponse for AzureMonitorLogsDcrResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> EventsSent {
+        EventsSent {
+            count: self.count,
+            byte_size: self.events_byte_size,
+            output: None,
+        }
+    }
+
+    fn bytes_sent(&self) -> Option<BytesSent> {
+        Some(BytesSent {
+            byte_size: self.byte_size,
+            protocol: "https",
+        })
+    }
+}
+
+/// Sends the logs to the streams of a data collection rule, through a data collection endpoint.
+pub(super) struct IngestionClient {
+    client: HttpClient,
+    endpoint: String,
+    dcr_immutable_id: String,
+    credential: Arc<dyn TokenCredential>,
+}
+
+impl IngestionClient {
+    pub(super) fn new(
+        client: HttpClient,
+        endpoint: &str,
+        dcr_immutable_id: String,
+        credential: Arc<dyn TokenCredential>,
+    ) -> Self {
+        Self {
+            client,
+            endpoint: endpoint.trim_end_matches('/').to_owned(),
+            dcr_immutable_id,
+            credential,
+        }
+    }
+
+    pub(super) async fn token(&self) -> Result<String, AzureMonitorLogsDcrError> {
+        let token = self
+            .credential
+            .get_token(MONITOR_RESOURCE)
+            .await
+            .map_err(|error| AzureMonitorLogsDcrError::Token {
+                source: error.into(),
+            })?;
+        Ok(token.token.secret().to_owned())
+    }
+
+    async fn send(
+        &self,
+        request: &AzureMonitorLogsDcrRequest,
+    ) -> Result<(), AzureMonitorLogsDcrError> {
+        let token = self.token().await?;
+        let mut builder = Request::post(stream_uri(
+            &self.endpoint,
+            &self.dcr_immutable_id,
+            &request.metadata.stream,
+        ))
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .header(header::CONTENT_TYPE, "application/json");
+        if let Some(encoding) = request.compression.content_encoding() {
+            builder = builder.header(header::CONTENT_ENCODING, encoding);
+        }
+        let http_request = builder
+            .body(Body::from(request.body.clone()))
+            .expect("ingestion request is valid");
+
+        let response = self.client.send(http_request).await.context(RequestSnafu)?;
+        let (parts, body) = response.into_parts();
+        if parts.status.is_success() {
+            return Ok(());
+        }
+        let body = hyper::body::to_bytes(body)
+            .await
+            .context(ReadResponseSnafu)?;
+        ServerSnafu {
+            status: parts.status,
+            body: String::from_utf8_lossy(&body),
+        }
+        .fail()
+    }
+}
+
+/// Builds the URI of the stream of the data collection rule the logs are sent to.
+fn stream_uri(endpoint: &str, dcr_immutable_id: &str, stream: &str) -> String {
+    format!(
+        "{}/dataCollectionRules/{}/streams/{}?api-version={}",
+        endpoint, dcr_immutable_id, stream, API_VERSION
+    )
+}
+
+#[derive(Clone)]
+pub(super) struct AzureMonitorLogsDcrService {
+    client: Arc<IngestionClient>,
+}
+
+impl AzureMonitorLogsDcrService {
+    pub(super) const fn new(client: Arc<IngestionClient>) -> Self {
+        Self { client }
+    }
+}
+
+impl Service<AzureMonitorLogsDcrRequest> for AzureMonitorLogsDcrService {
+    type Response = AzureMonitorLogsDcrResponse;
+    type Error = AzureMonitorLogsDcrError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: AzureMonitorLogsDcrRequest) -> Self::Future {
+        let client = Arc::clone(&self.client);
+
+        Box::pin(async move {
+            client.send(&request).await?;
+
+            Ok(AzureMonitorLogsDcrResponse {
+                count: request.metadata.count,
+                events_byte_size: request.metadata.byte_size,
+                byte_size: request.body.len(),
+            })
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct AzureMonitorLogsDcrRetryLogic;
+
+impl RetryLogic for AzureMonitorLogsDcrRetryLogic {
+    type Error = AzureMonitorLogsDcrError;
+    type Response = AzureMonitorLogsDcrResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        error.is_retriable()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+
+    use super::*;
+
+    #[test]
+    fn builds_stream_uri() {
+        assert_eq!(
+            stream_uri(
+                "https://my-dce-a1b2.westeurope-1.ingest.monitor.azure.com",
+                "dcr-00000000000000000000000000000000",
+                "Custom-VectorLogs_CL",
+            ),
+            "https://my-dce-a1b2.westeurope-1.ingest.monitor.azure.com/dataCollectionRules/dcr-00000000000000000000000000000000/streams/Custom-VectorLogs_CL?api-version=2023-01-01"
+        );
+    }
+
+    #[test]
+    fn retries_throttled_and_server_errors() {
+        let error = |status| AzureMonitorLogsDcrError::ServerError {
+            status,
+            body: String::new(),
+        };
+        assert!(error(StatusCode::TOO_MANY_REQUESTS).is_retriable());
+        assert!(error(StatusCode::SERVICE_UNAVAILABLE).is_retriable());
+        assert!(!error(StatusCode::FORBIDDEN).is_retriable());
+        assert!(!error(StatusCode::REQUEST_ENTITY_TOO_LARGE).is_retriable());
+    }
+}
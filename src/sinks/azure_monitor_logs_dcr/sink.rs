@@ -0,0 +1,80 @@
+use std::num::NonZeroUsize;
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use tower::ServiceBuilder;
+use vector_core::{partition::Partitioner, sink::StreamSink, stream::BatcherSettings};
+
+use super::{
+    request_builder::AzureMonitorLogsDcrRequestBuilder,
+    service::{AzureMonitorLogsDcrRetryLogic, AzureMonitorLogsDcrService},
+};
+use crate::{
+    event::Event,
+    internal_events::TemplateRenderingError,
+    sinks::util::{ServiceBuilderExt, SinkBuilderExt, TowerRequestConfig},
+    template::Template,
+};
+
+/// Partitions the events by the stream of the data collection rule they are sent to.
+pub(super) struct StreamPartitioner(pub(super) Template);
+
+impl Partitioner for StreamPartitioner {
+    type Item = Event;
+    type Key = Option<String>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        self.0
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("stream_name"),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+}
+
+pub(super) struct AzureMonitorLogsDcrSink {
+    pub(super) service: AzureMonitorLogsDcrService,
+    pub(super) request: TowerRequestConfig,
+    pub(super) request_builder: AzureMonitorLogsDcrRequestBuilder,
+    pub(super) partitioner: StreamPartitioner,
+    pub(super) batch_settings: BatcherSettings,
+}
+
+impl AzureMonitorLogsDcrSink {
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let request = self.request.unwrap_with(&TowerRequestConfig::default());
+        let service = ServiceBuilder::new()
+            .settings(request, AzureMonitorLogsDcrRetryLogic)
+            .service(self.service);
+        let builder_limit = NonZeroUsize::new(64);
+
+        input
+            .batched_partitioned(self.partitioner, self.batch_settings)
+            .filter_map(|(key, batch)| async move { key.map(move |key| (key, batch)) })
+            .request_builder(builder_limit, self.request_builder)
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        error!("Failed to build Azure Monitor Logs request: {:?}.", error);
+                        None
+                    }
+                    Ok(request) => Some(request),
+                }
+            })
+            .into_driver(service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for AzureMonitorLogsDcrSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
@@ -0,0 +1,391 @@
+use std::{convert::TryFrom, fmt, str::FromStr};
+
+use bytes::{BufMut, BytesMut};
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::native::put_string;
+use crate::event::Value;
+
+/// The ClickHouse type of a column, which determines how the values of the events are encoded
+/// in the blocks sent with the native protocol.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum ClickhouseColumnType {
+    String,
+    Bool,
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+    Float32,
+    Float64,
+    /// A timestamp with a precision of a second, with the time zone it is displayed in.
+    DateTime(Option<String>),
+    /// A timestamp with the given number of decimal places for the fractions of a second.
+    DateTime64(u32, Option<String>),
+    Nullable(Box<Self>),
+    Array(Box<Self>),
+}
+
+impl ClickhouseColumnType {
+    /// Whether the values of the column can be inserted into a column of the table of the given
+    /// type, which is the case when they are encoded the same way.
+    pub(super) fn is_compatible(&self, table_type: &Self) -> bool {
+        match (self, table_type) {
+            (Self::DateTime(_), Self::DateTime(_)) => true,
+            (Self::DateTime64(precision, _), Self::DateTime64(table_precision, _)) => {
+                precision == table_precision
+            }
+            (Self::Nullable(inner), Self::Nullable(table_inner))
+            | (Self::Array(inner), Self::Array(table_inner)) => inner.is_compatible(table_inner),
+            (column_type, table_type) => column_type == table_type,
+        }
+    }
+
+    /// Encodes the values of the column.
+    ///
+    /// The values that are missing, or can't be converted to the type of the column, are encoded
+    /// as null for nullable columns, and as the default value of the type otherwise.
+    pub(super) fn encode(&self, values: &[Option<&Value>], buf: &mut BytesMut) {
+        match self {
+            Self::Nullable(inner) => {
+                let mut data = BytesMut::new();
+                for value in values {
+                    let converted = inner.encode_value(*value, &mut data);
+                    buf.put_u8(u8::from(!converted));
+                }
+                buf.extend_from_slice(&data);
+            }
+            Self::Array(inner) => {
+                let mut offset = 0;
+                let mut items = Vec::new();
+                for value in values {
+                    if let Some(Value::Array(array)) = value {
+                        offset += array.len() as u64;
+                        items.extend(array.iter().map(Some));
+                    }
+                    buf.put_u64_le(offset);
+                }
+                inner.encode(&items, buf);
+            }
+            _ => {
+                for value in values {
+                    self.encode_value(*value, buf);
+                }
+            }
+        }
+    }
+
+    /// Encodes the value of a scalar column, returning whether it was converted to its type.
+    fn encode_value(&self, value: Option<&Value>, buf: &mut BytesMut) -> bool {
+        match self {
+            Self::String => {
+                let bytes = value
+                    .filter(|value| !value.is_null())
+                    .map(Value::coerce_to_bytes);
+                put_string(buf, bytes.as_deref().unwrap_or_default());
+                bytes.is_some()
+            }
+            Self::Bool => put(buf, value.and_then(to_bool).map(u8::from), BytesMut::put_u8),
+            Self::Int8 => put(buf, to_integer(value), BytesMut::put_i8),
+            Self::Int16 => put(buf, to_integer(value), BytesMut::put_i16_le),
+            Self::Int32 => put(buf, to_integer(value), BytesMut::put_i32_le),
+            Self::Int64 => put(buf, to_integer(value), BytesMut::put_i64_le),
+            Self::UInt8 => put(buf, to_integer(value), BytesMut::put_u8),
+            Self::UInt16 => put(buf, to_integer(value), BytesMut::put_u16_le),
+            Self::UInt32 => put(buf, to_integer(value), BytesMut::put_u32_le),
+            Self::UInt64 => put(buf, to_integer(value), BytesMut::put_u64_le),
+            Self::Float32 => put(
+                buf,
+                value.and_then(to_float).map(|float| float as f32),
+                BytesMut::put_f32_le,
+            ),
+            Self::Float64 => put(buf, value.and_then(to_float), BytesMut::put_f64_le),
+            Self::DateTime(_) => put(
+                buf,
+                value
+                    .and_then(to_timestamp)
+                    .and_then(|timestamp| u32::try_from(timestamp.timestamp()).ok()),
+                BytesMut::put_u32_le,
+            ),
+            Self::DateTime64(precision, _) => put(
+                buf,
+                value
+                    .and_then(to_timestamp)
+                    .and_then(|timestamp| to_ticks(timestamp, *precision)),
+                BytesMut::put_i64_le,
+            ),
+            Self::Nullable(_) | Self::Array(_) => {
+                unreachable!("nullable and array columns are not scalar")
+            }
+        }
+    }
+}
+
+impl fmt::Display for ClickhouseColumnType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::String => write!(f, "String"),
+            Self::Bool => write!(f, "Bool"),
+            Self::Int8 => write!(f, "Int8"),
+            Self::Int16 => write!(f, "Int16"),
+            Self::Int32 => write!(f, "Int32"),
+            Self::Int64 => write!(f, "Int64"),
+            Self::UInt8 => write!(f, "UInt8"),
+            Self::UInt16 => write!(f, "UInt16"),
+            Self::UInt32 => write!(f, "UInt32"),
+            Self::UInt64 => write!(f, "UInt64"),
+            Self::Float32 => write!(f, "Float32"),
+            Self::Float64 => write!(f, "Float64"),
+            Self::DateTime(None) => write!(f, "DateTime"),
+            Self::DateTime(Some(timezone)) => write!(f, "DateTime('{}')", timezone),
+            Self::DateTime64(precision, None) => write!(f, "DateTime64({})", precision),
+            Self::DateTime64(precision, Some(timezone)) => {
+                write!(f, "DateTime64({}, '{}')", precision, timezone)
+            }
+            Self::Nullable(inner) => write!(f, "Nullable({})", inner),
+            Self::Array(inner) => write!(f, "Array({})", inner),
+        }
+    }
+}
+
+impl FromStr for ClickhouseColumnType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(inner) = arguments(s, "Nullable") {
+            let inner: Self = inner.parse()?;
+            return match inner {
+                Self::Nullable(_) | Self::Array(_) => {
+                    Err(format!("{} can't be inside a Nullable type", inner))
+                }
+                inner => Ok(Self::Nullable(Box::new(inner))),
+            };
+        }
+        if let Some(inner) = arguments(s, "Array") {
+            return Ok(Self::Array(Box::new(inner.parse()?)));
+        }
+        if let Some(arguments) = arguments(s, "DateTime64") {
+            let (precision, timezone) = match arguments.split_once(',') {
+                Some((precision, timezone)) => (precision, Some(parse_timezone(timezone)?)),
+                None => (arguments, None),
+            };
+            let precision = precision
+                .trim()
+                .parse()
+                .ok()
+                .filter(|precision| *precision <= 9)
+                .ok_or_else(|| format!("invalid DateTime64 precision {:?}", precision.trim()))?;
+            return Ok(Self::DateTime64(precision, timezone));
+        }
+        if let Some(timezone) = arguments(s, "DateTime") {
+            return Ok(Self::DateTime(Some(parse_timezone(timezone)?)));
+        }
+
+        match s {
+            "String" => Ok(Self::String),
+            "Bool" => Ok(Self::Bool),
+            "Int8" => Ok(Self::Int8),
+            "Int16" => Ok(Self::Int16),
+            "Int32" => Ok(Self::Int32),
+            "Int64" => Ok(Self::Int64),
+            "UInt8" => Ok(Self::UInt8),
+            "UInt16" => Ok(Self::UInt16),
+            "UInt32" => Ok(Self::UInt32),
+            "UInt64" => Ok(Self::UInt64),
+            "Float32" => Ok(Self::Float32),
+            "Float64" => Ok(Self::Float64),
+            "DateTime" => Ok(Self::DateTime(None)),
+            _ => Err(format!("unsupported ClickHouse type {:?}", s)),
+        }
+    }
+}
+
+impl TryFrom<String> for ClickhouseColumnType {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<ClickhouseColumnType> for String {
+    fn from(column_type: ClickhouseColumnType) -> Self {
+        column_type.to_string()
+    }
+}
+
+/// Returns the arguments of the parametric type with the given name.
+fn arguments<'a>(s: &'a str, name: &str) -> Option<&'a str> {
+    s.strip_prefix(name)?.strip_prefix('(')?.strip_suffix(')')
+}
+
+fn parse_timezone(s: &str) -> Result<String, String> {
+    s.trim()
+        .strip_prefix('\'')
+        .and_then(|timezone| timezone.strip_suffix('\''))
+        .map(ToOwned::to_owned)
+        .ok_or_else(|| format!("invalid time zone {:?}", s.trim()))
+}
+
+/// Puts the value, or the default value of its type when it is missing, returning whether it
+/// was present.
+fn put<T: Default>(buf: &mut BytesMut, value: Option<T>, put: fn(&mut BytesMut, T)) -> bool {
+    let present = value.is_some();
+    put(buf, value.unwrap_or_default());
+    present
+}
+
+fn to_integer<T: TryFrom<i64>>(value: Option<&Value>) -> Option<T> {
+    let integer = match value? {
+        Value::Integer(integer) => *integer,
+        Value::Float(float) if float.fract() == 0.0 => float.into_inner() as i64,
+        Value::Boolean(boolean) => i64::from(*boolean),
+        Value::Bytes(bytes) => std::str::from_utf8(bytes).ok()?.trim().parse().ok()?,
+        _ => return None,
+    };
+    T::try_from(integer).ok()
+}
+
+fn to_float(value: &Value) -> Option<f64> {
+    match value {
+        Value::Float(float) => Some(float.into_inner()),
+        Value::Integer(integer) => Some(*integer as f64),
+        Value::Bytes(bytes) => std::str::from_utf8(bytes).ok()?.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn to_bool(value: &Value) -> Option<bool> {
+    match value {
+        Value::Boolean(boolean) => Some(*boolean),
+        Value::Integer(0) => Some(false),
+        Value::Integer(1) => Some(true),
+        Value::Bytes(bytes) => std::str::from_utf8(bytes).ok()?.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn to_timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::Timestamp(timestamp) => Some(*timestamp),
+        Value::Integer(seconds) => Utc.timestamp_opt(*seconds, 0).single(),
+        Value::Bytes(bytes) => DateTime::parse_from_rfc3339(std::str::from_utf8(bytes).ok()?)
+            .ok()
+            .map(|timestamp| timestamp.with_timezone(&Utc)),
+        _ => None,
+    }
+}
+
+/// Converts the timestamp to the number of ticks of the given precision since the epoch.
+fn to_ticks(timestamp: DateTime<Utc>, precision: u32) -> Option<i64> {
+    let fraction = i64::from(timestamp.timestamp_subsec_nanos()) / 10_i64.pow(9 - precision);
+    timestamp
+        .timestamp()
+        .checked_mul(10_i64.pow(precision))?
+        .checked_add(fraction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_types() {
+        for (s, column_type) in [
+            ("String", ClickhouseColumnType::String),
+            ("UInt16", ClickhouseColumnType::UInt16),
+            (
+                "Nullable(String)",
+                ClickhouseColumnType::Nullable(Box::new(ClickhouseColumnType::String)),
+            ),
+            (
+                "Array(Nullable(Int64))",
+                ClickhouseColumnType::Array(Box::new(ClickhouseColumnType::Nullable(Box::new(
+                    ClickhouseColumnType::Int64,
+                )))),
+            ),
+            ("DateTime", ClickhouseColumnType::DateTime(None)),
+            (
+                "DateTime('UTC')",
+                ClickhouseColumnType::DateTime(Some("UTC".into())),
+            ),
+            ("DateTime64(3)", ClickhouseColumnType::DateTime64(3, None)),
+            (
+                "DateTime64(6, 'Europe/Paris')",
+                ClickhouseColumnType::DateTime64(6, Some("Europe/Paris".into())),
+            ),
+        ] {
+            assert_eq!(s.parse::<ClickhouseColumnType>(), Ok(column_type.clone()));
+            assert_eq!(column_type.to_string(), s);
+        }
+    }
+
+    #[test]
+    fn rejects_unsupported_types() {
+        for s in [
+            "LowCardinality(String)",
+            "Nullable(Array(String))",
+            "DateTime64(10)",
+            "DateTime(UTC)",
+        ] {
+            assert!(s.parse::<ClickhouseColumnType>().is_err(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn ignores_time_zones_for_compatibility() {
+        let column_type: ClickhouseColumnType = "Nullable(DateTime)".parse().unwrap();
+
+        assert!(column_type.is_compatible(&"Nullable(DateTime('UTC'))".parse().unwrap()));
+        assert!(!column_type.is_compatible(&"DateTime('UTC')".parse().unwrap()));
+    }
+
+    #[test]
+    fn encodes_nullable_column() {
+        let values = [Value::from(3), Value::from("4"), Value::from("four")];
+        let mut buf = BytesMut::new();
+        ClickhouseColumnType::Nullable(Box::new(ClickhouseColumnType::UInt16)).encode(
+            &[Some(&values[0]), None, Some(&values[1]), Some(&values[2])],
+            &mut buf,
+        );
+
+        assert_eq!(&buf[..], &[0, 1, 0, 1, 3, 0, 0, 0, 4, 0, 0, 0][..]);
+    }
+
+    #[test]
+    fn encodes_array_column() {
+        let values = [
+            Value::from(vec![Value::from("a"), Value::from("bc")]),
+            Value::from("not an array"),
+        ];
+        let mut buf = BytesMut::new();
+        ClickhouseColumnType::Array(Box::new(ClickhouseColumnType::String))
+            .encode(&[Some(&values[0]), Some(&values[1])], &mut buf);
+
+        let mut expected = BytesMut::new();
+        expected.put_u64_le(2);
+        expected.put_u64_le(2);
+        expected.extend_from_slice(b"\x01a\x02bc");
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn encodes_timestamps() {
+        let timestamp = Value::from(Utc.timestamp(1_600_000_000, 123_456_789));
+        let mut buf = BytesMut::new();
+        ClickhouseColumnType::DateTime(None).encode(&[Some(&timestamp)], &mut buf);
+        ClickhouseColumnType::DateTime64(3, None).encode(&[Some(&timestamp)], &mut buf);
+
+        let mut expected = BytesMut::new();
+        expected.put_u32_le(1_600_000_000);
+        expected.put_i64_le(1_600_000_000_123);
+        assert_eq!(buf, expected);
+    }
+}
@@ -2,6 +2,7 @@ use bytes::{BufMut, Bytes, BytesMut};
 use futures::{FutureExt, SinkExt};
 use http::{Request, StatusCode, Uri};
 use hyper::Body;
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use snafu::ResultExt;
 
@@ -19,6 +20,24 @@ use crate::{
     tls::{TlsConfig, TlsSettings},
 };
 
+mod columns;
+mod native;
+
+pub use columns::ClickhouseColumnType;
+
+/// The protocol the events are inserted with.
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Eq, PartialEq, Serialize)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ClickhouseProtocol {
+    /// The HTTP interface, the events being inserted as JSON objects.
+    #[derivative(Default)]
+    Http,
+
+    /// The native TCP protocol, the events being inserted as blocks of columns.
+    Native,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, Default)]
 #[serde(deny_unknown_fields)]
 pub struct ClickhouseConfig {
@@ -28,6 +47,12 @@ pub struct ClickhouseConfig {
     pub table: String,
     pub database: Option<String>,
     #[serde(default)]
+    pub protocol: ClickhouseProtocol,
+    /// The columns the events are inserted into with the native protocol, along with their
+    /// types, the values of the columns being the ones of the fields of the same name.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub columns: IndexMap<String, ClickhouseColumnType>,
+    #[serde(default)]
     pub skip_unknown_fields: bool,
     #[serde(default = "Compression::gzip_default")]
     pub compression: Compression,
@@ -63,6 +88,10 @@ impl SinkConfig for ClickhouseConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        if self.protocol == ClickhouseProtocol::Native {
+            return self.build_native();
+        }
+
         let batch = self.batch.into_batch_settings()?;
         let request = self.request.unwrap_with(&TowerRequestConfig::default());
         let tls_settings = TlsSettings::from_options(&self.tls)?;
@@ -253,6 +282,52 @@ mod tests {
         crate::test_util::test_generate_config::<ClickhouseConfig>();
     }
 
+    #[test]
+    fn parses_native_columns() {
+        let config: ClickhouseConfig = toml::from_str(
+            r#"
+            endpoint = "tcp://localhost:9000"
+            table = "logs"
+            protocol = "native"
+            columns.timestamp = "DateTime64(3, 'UTC')"
+            columns.message = "String"
+            columns.status = "Nullable(UInt16)"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.protocol, ClickhouseProtocol::Native);
+        assert_eq!(
+            config.columns.into_iter().collect::<Vec<_>>(),
+            vec![
+                (
+                    "timestamp".to_owned(),
+                    ClickhouseColumnType::DateTime64(3, Some("UTC".to_owned()))
+                ),
+                ("message".to_owned(), ClickhouseColumnType::String),
+                (
+                    "status".to_owned(),
+                    ClickhouseColumnType::Nullable(Box::new(ClickhouseColumnType::UInt16))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_column_types() {
+        let error = toml::from_str::<ClickhouseConfig>(
+            r#"
+            endpoint = "tcp://localhost:9000"
+            table = "logs"
+            protocol = "native"
+            columns.message = "LowCardinality(String)"
+            "#,
+        )
+        .unwrap_err();
+
+        assert!(error.to_string().contains("unsupported ClickHouse type"));
+    }
+
     #[test]
     fn encode_valid() {
         let uri = set_uri_query(
@@ -312,7 +387,7 @@ mod integration_tests {
         codecs::TimestampFormat,
         config::{log_schema, SinkConfig, SinkContext},
         test_util::{
-            components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+            components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS, SINK_TAGS},
             random_string, trace_init,
         },
     };
@@ -321,6 +396,10 @@ mod integration_tests {
         std::env::var("CLICKHOUSE_ADDRESS").unwrap_or_else(|_| "http://localhost:8123".into())
     }
 
+    fn clickhouse_native_address() -> String {
+        std::env::var("CLICKHOUSE_NATIVE_ADDRESS").unwrap_or_else(|_| "tcp://localhost:9000".into())
+    }
+
     #[tokio::test]
     async fn insert_events() {
         trace_init();
@@ -630,6 +709,118 @@ timestamp_format = "unix""#,
         assert_eq!(receiver.try_recv(), Ok(BatchStatus::Errored));
     }
 
+    #[tokio::test]
+    async fn insert_events_native() {
+        trace_init();
+
+        let table = gen_table();
+        let host = clickhouse_address();
+
+        let mut batch = BatchConfig::default();
+        batch.max_events = Some(2);
+
+        let config: ClickhouseConfig = toml::from_str(&format!(
+            r#"
+            endpoint = "{}"
+            table = "{}"
+            protocol = "native"
+            columns.host = "String"
+            columns.timestamp = "DateTime"
+            columns.message = "String"
+            columns.status = "Nullable(UInt16)"
+            columns.items = "Array(String)"
+            "#,
+            clickhouse_native_address(),
+            table
+        ))
+        .unwrap();
+        let config = ClickhouseConfig { batch, ..config };
+
+        let client = ClickhouseClient::new(host);
+        client
+            .create_table(
+                &table,
+                "host String, timestamp DateTime('UTC'), message String, status Nullable(UInt16), items Array(String)",
+            )
+            .await;
+
+        let (sink, healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+        healthcheck.await.unwrap();
+
+        let (mut first, mut receiver) = make_event();
+        first.as_mut_log().insert("status", 200);
+        first.as_mut_log().insert("items", vec!["item1", "item2"]);
+        let (second, _) = make_event();
+
+        run_and_assert_sink_compliance(sink, stream::iter(vec![first.clone(), second]), &SINK_TAGS)
+            .await;
+
+        let output = client.select_all(&table).await;
+        assert_eq!(2, output.rows);
+
+        let timestamp = first
+            .as_log()
+            .get(log_schema().timestamp_key())
+            .unwrap()
+            .as_timestamp()
+            .unwrap()
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+        let (with_status, without_status): (Vec<_>, Vec<_>) = output
+            .data
+            .into_iter()
+            .partition(|row| !row["status"].is_null());
+        assert_eq!(
+            with_status,
+            vec![serde_json::json!({
+                "host": "example.com",
+                "timestamp": timestamp,
+                "message": "raw log line",
+                "status": 200,
+                "items": ["item1", "item2"],
+            })]
+        );
+        assert_eq!(without_status.len(), 1);
+        assert_eq!(without_status[0]["items"], serde_json::json!([]));
+
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+    }
+
+    #[tokio::test]
+    async fn native_rejects_incompatible_columns() {
+        trace_init();
+
+        let table = gen_table();
+        let client = ClickhouseClient::new(clickhouse_address());
+        client
+            .create_table(&table, "host String, timestamp String")
+            .await;
+
+        let config: ClickhouseConfig = toml::from_str(&format!(
+            r#"
+            endpoint = "{}"
+            table = "{}"
+            protocol = "native"
+            columns.host = "UInt64"
+            columns.timestamp = "String"
+            batch.max_events = 1
+            "#,
+            clickhouse_native_address(),
+            table
+        ))
+        .unwrap();
+        let (sink, _hc) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (input_event, mut receiver) = make_event();
+
+        timeout(Duration::from_secs(5), sink.run_events(vec![input_event]))
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+    }
+
     fn make_event() -> (Event, BatchStatusReceiver) {
         let (batch, receiver) = BatchNotifier::new_with_receiver();
         let mut event = LogEvent::from("raw log line").with_batch_notifier(&batch);
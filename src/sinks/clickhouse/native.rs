@@ -0,0 +1,929 @@
+use std::{
+    io,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use async_trait::async_trait;
+use bytes::{BufMut, Bytes, BytesMut};
+use futures::{future::BoxFuture, stream::BoxStream, FutureExt, StreamExt};
+use snafu::{ResultExt, Snafu};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWriteExt, BufStream},
+    net::TcpStream,
+};
+use tower::{Service, ServiceBuilder};
+use vector_core::{
+    internal_event::{BytesSent, EventsSent},
+    stream::{BatcherSettings, DriverResponse},
+    ByteSizeOf,
+};
+
+use super::{columns::ClickhouseColumnType, ClickhouseConfig};
+use crate::{
+    codecs::Transformer,
+    dns,
+    event::{Event, EventFinalizers, EventStatus, Finalizable, LogEvent},
+    http::{Auth, MaybeAuth},
+    sinks::{
+        util::{
+            retries::RetryLogic, Compression, ServiceBuilderExt, SinkBuilderExt, StreamSink,
+            TowerRequestConfig,
+        },
+        Healthcheck, VectorSink,
+    },
+    tls::{MaybeTlsSettings, MaybeTlsStream, TlsError},
+};
+
+const CLIENT_NAME: &str = "Vector";
+
+/// The revision of the protocol spoken with the servers, which keep supporting it for older
+/// clients. The fields added to the packets by later revisions are not exchanged.
+const PROTOCOL_REVISION: u64 = 54213;
+
+/// The revision from which the servers send their time zone in their hello packet.
+const REVISION_WITH_SERVER_TIMEZONE: u64 = 54058;
+
+const DEFAULT_PORT: u16 = 9000;
+const DEFAULT_TLS_PORT: u16 = 9440;
+
+const QUERY_KIND_INITIAL: u8 = 1;
+const INTERFACE_TCP: u8 = 1;
+const QUERY_STAGE_COMPLETE: u64 = 2;
+
+const COMPRESSION_METHOD_NONE: u8 = 0x02;
+const COMPRESSION_METHOD_LZ4: u8 = 0x82;
+
+/// The size of the checksum of the compressed frames.
+const CHECKSUM_SIZE: usize = 16;
+
+/// The size of the header of the compressed frames, made of their compression method, their
+/// compressed size, including the header, and their uncompressed size.
+const FRAME_HEADER_SIZE: usize = 9;
+
+/// The maximum size of the data compressed in a single frame.
+const MAX_FRAME_SIZE: usize = 1 << 20;
+
+/// The maximum size of the strings and compressed frames read from the server.
+const MAX_READ_SIZE: usize = 1 << 30;
+
+/// The codes of the server errors caused by the events or the configuration, which retrying the
+/// insert doesn't resolve.
+const NON_RETRIABLE_ERROR_CODES: [i32; 6] = [
+    16,  // NO_SUCH_COLUMN_IN_TABLE
+    53,  // TYPE_MISMATCH
+    60,  // UNKNOWN_TABLE
+    81,  // UNKNOWN_DATABASE
+    117, // INCORRECT_DATA
+    516, // AUTHENTICATION_FAILED
+];
+
+mod client_packet {
+    pub(super) const HELLO: u64 = 0;
+    pub(super) const QUERY: u64 = 1;
+    pub(super) const DATA: u64 = 2;
+    pub(super) const PING: u64 = 4;
+}
+
+mod server_packet {
+    pub(super) const HELLO: u64 = 0;
+    pub(super) const DATA: u64 = 1;
+    pub(super) const EXCEPTION: u64 = 2;
+    pub(super) const PROGRESS: u64 = 3;
+    pub(super) const PONG: u64 = 4;
+    pub(super) const END_OF_STREAM: u64 = 5;
+    pub(super) const PROFILE_INFO: u64 = 6;
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`columns` must be set to use the native protocol"))]
+    NoColumns,
+    #[snafu(display("The endpoint has no host"))]
+    NoHost,
+    #[snafu(display("Only basic authentication is supported by the native protocol"))]
+    UnsupportedAuth,
+}
+
+#[derive(Debug, Snafu)]
+enum NativeError {
+    #[snafu(display("Failed to resolve {}: {}", host, source))]
+    Resolve { host: String, source: dns::DnsError },
+    #[snafu(display("No addresses found for {}", host))]
+    NoAddresses { host: String },
+    #[snafu(display("Failed to connect: {}", source))]
+    Connect { source: TlsError },
+    #[snafu(display("Connection error: {}", source))]
+    Io { source: io::Error },
+    #[snafu(display("Server error {} ({}): {}", code, name, message))]
+    Server {
+        code: i32,
+        name: String,
+        message: String,
+    },
+    #[snafu(display("Unexpected packet {} from the server", packet))]
+    UnexpectedPacket { packet: u64 },
+    #[snafu(display("Invalid data from the server: {}", message))]
+    InvalidData { message: String },
+    #[snafu(display(
+        "Column {:?} is of type {} in the table, which isn't compatible with {}",
+        column,
+        table_type,
+        column_type
+    ))]
+    ColumnType {
+        column: String,
+        table_type: String,
+        column_type: ClickhouseColumnType,
+    },
+}
+
+impl NativeError {
+    fn is_retriable(&self) -> bool {
+        match self {
+            Self::Resolve { .. } | Self::NoAddresses { .. } | Self::Connect { .. } => true,
+            Self::Io { .. } => true,
+            Self::Server { code, .. } => !NON_RETRIABLE_ERROR_CODES.contains(code),
+            Self::UnexpectedPacket { .. } | Self::InvalidData { .. } | Self::ColumnType { .. } => {
+                false
+            }
+        }
+    }
+}
+
+impl ClickhouseConfig {
+    pub(super) fn build_native(&self) -> crate::Result<(VectorSink, Healthcheck)> {
+        if self.columns.is_empty() {
+            return Err(BuildError::NoColumns.into());
+        }
+        let (user, password) = match self.auth.choose_one(&self.endpoint.auth)? {
+            None => ("default".to_owned(), String::new()),
+            Some(Auth::Basic { user, password }) => (user, password),
+            Some(Auth::Bearer { .. } | Auth::ApiKey { .. }) => {
+                return Err(BuildError::UnsupportedAuth.into())
+            }
+        };
+        let tls = match &self.tls {
+            Some(_) => MaybeTlsSettings::tls_client(&self.tls)?,
+            None => MaybeTlsSettings::Raw(()),
+        };
+        let host = self
+            .endpoint
+            .uri
+            .host()
+            .ok_or(BuildError::NoHost)?
+            .to_owned();
+        let port = self.endpoint.uri.port_u16().unwrap_or(if tls.is_tls() {
+            DEFAULT_TLS_PORT
+        } else {
+            DEFAULT_PORT
+        });
+        let database = self
+            .database
+            .clone()
+            .unwrap_or_else(|| "default".to_owned());
+        let columns: Vec<_> = self.columns.clone().into_iter().collect();
+        let compression = self.compression != Compression::None;
+
+        let connector = Arc::new(NativeConnector {
+            host,
+            port,
+            tls,
+            database: database.clone(),
+            user,
+            password,
+            compression,
+        });
+        let service = NativeService {
+            connector: Arc::clone(&connector),
+            query: insert_query(&database, &self.table, &columns).into(),
+            columns: Arc::new(columns.clone()),
+            idle: Default::default(),
+        };
+        let sink = NativeSink {
+            service,
+            request: self.request,
+            encoder: NativeEncoder {
+                columns,
+                transformer: self.encoding.clone(),
+                compression,
+            },
+            batch_settings: self.batch.into_batcher_settings()?,
+        };
+
+        let healthcheck = healthcheck(connector).boxed();
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+}
+
+/// Checks that the server accepts the connections of the sink.
+async fn healthcheck(connector: Arc<NativeConnector>) -> crate::Result<()> {
+    connector.connect().await?.ping().await?;
+    Ok(())
+}
+
+fn insert_query(database: &str, table: &str, columns: &[(String, ClickhouseColumnType)]) -> String {
+    let columns = columns
+        .iter()
+        .map(|(name, _)| quote_identifier(name))
+        .collect::<Vec<_>>();
+    format!(
+        "INSERT INTO {}.{} ({}) VALUES",
+        quote_identifier(database),
+        quote_identifier(table),
+        columns.join(", ")
+    )
+}
+
+fn quote_identifier(identifier: &str) -> String {
+    format!(
+        "\"{}\"",
+        identifier.replace('\\', "\\\\").replace('\"', "\\\"")
+    )
+}
+
+/// Encodes the batches of events as the blocks inserted into the table.
+struct NativeEncoder {
+    columns: Vec<(String, ClickhouseColumnType)>,
+    transformer: Transformer,
+    compression: bool,
+}
+
+impl NativeEncoder {
+    fn encode(&self, mut events: Vec<Event>) -> NativeRequest {
+        let finalizers = events.take_finalizers();
+        let events_byte_size = events.size_of();
+        let logs = events
+            .into_iter()
+            .map(|mut event| {
+                self.transformer.transform(&mut event);
+                event.into_log()
+            })
+            .collect::<Vec<_>>();
+
+        NativeRequest {
+            block: encode_block(&self.columns, &logs, self.compression),
+            count: logs.len(),
+            events_byte_size,
+            finalizers,
+        }
+    }
+}
+
+/// Encodes the block of the events, holding the values of their fields in the columns of the
+/// same name, ready to be sent in a data packet.
+fn encode_block(
+    columns: &[(String, ClickhouseColumnType)],
+    logs: &[LogEvent],
+    compression: bool,
+) -> Bytes {
+    let mut block = BytesMut::new();
+    // The block info: the block is not an overflow one, and is not part of a bucket.
+    put_varint(&mut block, 1);
+    block.put_u8(0);
+    put_varint(&mut block, 2);
+    block.put_i32_le(-1);
+    put_varint(&mut block, 0);
+
+    put_varint(&mut block, columns.len() as u64);
+    put_varint(&mut block, logs.len() as u64);
+    for (name, column_type) in columns {
+        put_string(&mut block, name.as_bytes());
+        put_string(&mut block, column_type.to_string().as_bytes());
+        let values = logs
+            .iter()
+            .map(|log| log.get(name.as_str()))
+            .collect::<Vec<_>>();
+        column_type.encode(&values, &mut block);
+    }
+
+    if compression {
+        compress(&block)
+    } else {
+        block.freeze()
+    }
+}
+
+/// Compresses the data in LZ4 frames, each of them checksummed.
+fn compress(data: &[u8]) -> Bytes {
+    let mut buf = BytesMut::new();
+    for chunk in data.chunks(MAX_FRAME_SIZE) {
+        let compressed = lz4_flex::block::compress(chunk);
+        let mut frame = BytesMut::with_capacity(FRAME_HEADER_SIZE + compressed.len());
+        frame.put_u8(COMPRESSION_METHOD_LZ4);
+        frame.put_u32_le((FRAME_HEADER_SIZE + compressed.len()) as u32);
+        frame.put_u32_le(chunk.len() as u32);
+        frame.extend_from_slice(&compressed);
+
+        buf.extend_from_slice(&checksum(&frame));
+        buf.extend_from_slice(&frame);
+    }
+    buf.freeze()
+}
+
+/// Computes the checksum of a compressed frame, which is the CityHash v1.0.2 hash of its header
+/// and data, written as its two halves, the high one first.
+fn checksum(frame: &[u8]) -> [u8; CHECKSUM_SIZE] {
+    let hash = cityhash_rs::cityhash_102_128(frame);
+    let mut checksum = [0; CHECKSUM_SIZE];
+    checksum[..8].copy_from_slice(&((hash >> 64) as u64).to_le_bytes());
+    checksum[8..].copy_from_slice(&(hash as u64).to_le_bytes());
+    checksum
+}
+
+/// Reads a compressed frame, returning its decompressed data.
+async fn read_frame<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Vec<u8>, NativeError> {
+    let mut expected_checksum = [0; CHECKSUM_SIZE];
+    reader
+        .read_exact(&mut expected_checksum)
+        .await
+        .context(IoSnafu)?;
+    let mut frame = vec![0; FRAME_HEADER_SIZE];
+    reader.read_exact(&mut frame).await.context(IoSnafu)?;
+
+    let method = frame[0];
+    let compressed_size = u32::from_le_bytes([frame[1], frame[2], frame[3], frame[4]]) as usize;
+    let size = u32::from_le_bytes([frame[5], frame[6], frame[7], frame[8]]) as usize;
+    if compressed_size < FRAME_HEADER_SIZE || compressed_size > MAX_READ_SIZE {
+        return InvalidDataSnafu {
+            message: format!("invalid compressed frame size {}", compressed_size),
+        }
+        .fail();
+    }
+    frame.resize(compressed_size, 0);
+    reader
+        .read_exact(&mut frame[FRAME_HEADER_SIZE..])
+        .await
+        .context(IoSnafu)?;
+    if checksum(&frame) != expected_checksum {
+        return InvalidDataSnafu {
+            message: "invalid compressed frame checksum",
+        }
+        .fail();
+    }
+
+    let data = &frame[FRAME_HEADER_SIZE..];
+    match method {
+        COMPRESSION_METHOD_LZ4 => {
+            lz4_flex::block::decompress(data, size).map_err(|error| NativeError::InvalidData {
+                message: error.to_string(),
+            })
+        }
+        COMPRESSION_METHOD_NONE => Ok(data.to_vec()),
+        method => InvalidDataSnafu {
+            message: format!("unsupported compression method {:#x}", method),
+        }
+        .fail(),
+    }
+}
+
+pub(super) fn put_varint(buf: &mut BytesMut, mut value: u64) {
+    while value >= 0x80 {
+        buf.put_u8(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buf.put_u8(value as u8);
+}
+
+pub(super) fn put_string(buf: &mut BytesMut, s: &[u8]) {
+    put_varint(buf, s.len() as u64);
+    buf.extend_from_slice(s);
+}
+
+async fn read_varint<R: AsyncRead + Unpin>(reader: &mut R) -> Result<u64, NativeError> {
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+        let byte = reader.read_u8().await.context(IoSnafu)?;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    InvalidDataSnafu {
+        message: "invalid variable-length integer",
+    }
+    .fail()
+}
+
+async fn read_string<R: AsyncRead + Unpin>(reader: &mut R) -> Result<String, NativeError> {
+    let size = read_varint(reader).await? as usize;
+    if size > MAX_READ_SIZE {
+        return InvalidDataSnafu {
+            message: format!("invalid string size {}", size),
+        }
+        .fail();
+    }
+    let mut s = vec![0; size];
+    reader.read_exact(&mut s).await.context(IoSnafu)?;
+    Ok(String::from_utf8_lossy(&s).into_owned())
+}
+
+/// Reads the names and types of the columns of a block. The blocks sent by the server during
+/// inserts have no rows.
+async fn read_block_header<R: AsyncRead + Unpin>(
+    reader: &mut R,
+) -> Result<Vec<(String, String)>, NativeError> {
+    // The fields of the block info, ended by 0.
+    loop {
+        match read_varint(reader).await? {
+            0 => break,
+            1 => {
+                reader.read_u8().await.context(IoSnafu)?;
+            }
+            2 => {
+                reader.read_i32_le().await.context(IoSnafu)?;
+            }
+            field => {
+                return InvalidDataSnafu {
+                    message: format!("unknown block info field {}", field),
+                }
+                .fail()
+            }
+        }
+    }
+
+    let columns = read_varint(reader).await?;
+    let rows = read_varint(reader).await?;
+    if rows != 0 {
+        return InvalidDataSnafu {
+            message: format!("unexpected block of {} rows", rows),
+        }
+        .fail();
+    }
+    let mut header = Vec::new();
+    for _ in 0..columns {
+        let name = read_string(reader).await?;
+        let column_type = read_string(reader).await?;
+        header.push((name, column_type));
+    }
+    Ok(header)
+}
+
+/// Checks that the columns of the block to insert are the ones of the header of the table.
+fn check_header(
+    columns: &[(String, ClickhouseColumnType)],
+    header: &[(String, String)],
+) -> Result<(), NativeError> {
+    if columns.len() != header.len() {
+        return InvalidDataSnafu {
+            message: format!(
+                "the table has {} of the {} columns to insert",
+                header.len(),
+                columns.len()
+            ),
+        }
+        .fail();
+    }
+    for ((name, column_type), (table_name, table_type)) in columns.iter().zip(header) {
+        if name != table_name {
+            return InvalidDataSnafu {
+                message: format!("unexpected column {:?} in the table", table_name),
+            }
+            .fail();
+        }
+        let compatible = table_type
+            .parse::<ClickhouseColumnType>()
+            .map_or(false, |table_type| column_type.is_compatible(&table_type));
+        if !compatible {
+            return ColumnTypeSnafu {
+                column: name,
+                table_type,
+                column_type: column_type.clone(),
+            }
+            .fail();
+        }
+    }
+    Ok(())
+}
+
+/// Opens the connections to the server.
+struct NativeConnector {
+    host: String,
+    port: u16,
+    tls: MaybeTlsSettings,
+    database: String,
+    user: String,
+    password: String,
+    compression: bool,
+}
+
+impl NativeConnector {
+    async fn connect(&self) -> Result<Connection, NativeError> {
+        let ip = dns::Resolver
+            .lookup_ip(self.host.clone())
+            .await
+            .context(ResolveSnafu { host: &self.host })?
+            .next()
+            .ok_or_else(|| NativeError::NoAddresses {
+                host: self.host.clone(),
+            })?;
+        let stream = self
+            .tls
+            .connect(&self.host, &SocketAddr::new(ip, self.port))
+            .await
+            .context(ConnectSnafu)?;
+        let mut connection = Connection {
+            stream: BufStream::new(stream),
+            compression: self.compression,
+        };
+
+        let mut buf = BytesMut::new();
+        put_varint(&mut buf, client_packet::HELLO);
+        put_client_version(&mut buf);
+        put_string(&mut buf, self.database.as_bytes());
+        put_string(&mut buf, self.user.as_bytes());
+        put_string(&mut buf, self.password.as_bytes());
+        connection.send(&buf).await?;
+
+        connection.expect_packet(server_packet::HELLO).await?;
+        // The name, version and revision of the server, followed by its time zone.
+        let stream = &mut connection.stream;
+        read_string(stream).await?;
+        read_varint(stream).await?;
+        read_varint(stream).await?;
+        if read_varint(stream).await? >= REVISION_WITH_SERVER_TIMEZONE {
+            read_string(stream).await?;
+        }
+
+        Ok(connection)
+    }
+}
+
+/// Puts the name, version and protocol revision of the client.
+fn put_client_version(buf: &mut BytesMut) {
+    put_string(buf, CLIENT_NAME.as_bytes());
+    put_varint(buf, env!("CARGO_PKG_VERSION_MAJOR").parse().unwrap_or(0));
+    put_varint(buf, env!("CARGO_PKG_VERSION_MINOR").parse().unwrap_or(0));
+    put_varint(buf, PROTOCOL_REVISION);
+}
+
+struct Connection {
+    stream: BufStream<MaybeTlsStream<TcpStream>>,
+    compression: bool,
+}
+
+impl Connection {
+    async fn ping(&mut self) -> Result<(), NativeError> {
+        let mut buf = BytesMut::new();
+        put_varint(&mut buf, client_packet::PING);
+        self.send(&buf).await?;
+        self.expect_packet(server_packet::PONG).await
+    }
+
+    /// Inserts the block with the query, once the server confirms the columns of the table
+    /// match the ones of the block.
+    async fn insert(
+        &mut self,
+        query: &str,
+        columns: &[(String, ClickhouseColumnType)],
+        block: &[u8],
+    ) -> Result<(), NativeError> {
+        let end_of_data = encode_block(&[], &[], self.compression);
+
+        let mut buf = BytesMut::new();
+        self.put_query(&mut buf, query);
+        // The end of the external tables, which are not used.
+        put_data(&mut buf, &end_of_data);
+        self.send(&buf).await?;
+
+        // The server replies with the header of the block to insert.
+        self.expect_packet(server_packet::DATA).await?;
+        read_string(&mut self.stream).await?;
+        let header = if self.compression {
+            let data = read_frame(&mut self.stream).await?;
+            read_block_header(&mut io::Cursor::new(data)).await?
+        } else {
+            read_block_header(&mut self.stream).await?
+        };
+        check_header(columns, &header)?;
+
+        buf.clear();
+        put_data(&mut buf, block);
+        put_data(&mut buf, &end_of_data);
+        self.send(&buf).await?;
+        self.expect_packet(server_packet::END_OF_STREAM).await
+    }
+
+    fn put_query(&self, buf: &mut BytesMut, query: &str) {
+        put_varint(buf, client_packet::QUERY);
+        // The ID of the query, left to the server to generate.
+        put_string(buf, b"");
+
+        // The client info, with the user, ID and address of the initial query left empty, as
+        // the query is not a distributed one.
+        buf.put_u8(QUERY_KIND_INITIAL);
+        put_string(buf, b"");
+        put_string(buf, b"");
+        put_string(buf, b"0.0.0.0:0");
+        buf.put_u8(INTERFACE_TCP);
+        // The OS user and host name of the client.
+        put_string(buf, b"");
+        put_string(buf, crate::get_hostname().unwrap_or_default().as_bytes());
+        put_client_version(buf);
+        // The quota key.
+        put_string(buf, b"");
+
+        // The settings, which are ended by an empty name.
+        put_string(buf, b"");
+
+        put_varint(buf, QUERY_STAGE_COMPLETE);
+        put_varint(buf, u64::from(self.compression));
+        put_string(buf, query.as_bytes());
+    }
+
+    async fn send(&mut self, buf: &[u8]) -> Result<(), NativeError> {
+        self.stream.write_all(buf).await.context(IoSnafu)?;
+        self.stream.flush().await.context(IoSnafu)
+    }
+
+    /// Reads the packets of the server up to the expected one, skipping the progress and
+    /// profiling ones.
+    async fn expect_packet(&mut self, expected: u64) -> Result<(), NativeError> {
+        loop {
+            match read_varint(&mut self.stream).await? {
+                packet if packet == expected => return Ok(()),
+                server_packet::EXCEPTION => return Err(self.read_exception().await?),
+                server_packet::PROGRESS => {
+                    // The rows and bytes read, and the total number of rows to read.
+                    for _ in 0..3 {
+                        read_varint(&mut self.stream).await?;
+                    }
+                }
+                server_packet::PROFILE_INFO => {
+                    // The rows, blocks and bytes read, whether a limit was applied and the
+                    // number of rows before it.
+                    for _ in 0..3 {
+                        read_varint(&mut self.stream).await?;
+                    }
+                    self.stream.read_u8().await.context(IoSnafu)?;
+                    read_varint(&mut self.stream).await?;
+                    self.stream.read_u8().await.context(IoSnafu)?;
+                }
+                packet => return UnexpectedPacketSnafu { packet }.fail(),
+            }
+        }
+    }
+
+    /// Reads the exception sent by the server, skipping the ones nested in it.
+    async fn read_exception(&mut self) -> Result<NativeError, NativeError> {
+        let code = self.stream.read_i32_le().await.context(IoSnafu)?;
+        let name = read_string(&mut self.stream).await?;
+        let message = read_string(&mut self.stream).await?;
+        // The stack trace, followed by whether an exception is nested.
+        read_string(&mut self.stream).await?;
+        let mut nested = self.stream.read_u8().await.context(IoSnafu)? != 0;
+        while nested {
+            self.stream.read_i32_le().await.context(IoSnafu)?;
+            for _ in 0..3 {
+                read_string(&mut self.stream).await?;
+            }
+            nested = self.stream.read_u8().await.context(IoSnafu)? != 0;
+        }
+
+        Ok(NativeError::Server {
+            code,
+            name,
+            message,
+        })
+    }
+}
+
+/// Puts the data packet of the block, which isn't the one of an external table.
+fn put_data(buf: &mut BytesMut, block: &[u8]) {
+    put_varint(buf, client_packet::DATA);
+    put_string(buf, b"");
+    buf.extend_from_slice(block);
+}
+
+struct NativeRequest {
+    block: Bytes,
+    count: usize,
+    events_byte_size: usize,
+    finalizers: EventFinalizers,
+}
+
+impl Finalizable for NativeRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+struct NativeResponse {
+    count: usize,
+    events_byte_size: usize,
+    byte_size: usize,
+}
+
+impl DriverResponse for NativeResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> EventsSent {
+        EventsSent {
+            count: self.count,
+            byte_size: self.events_byte_size,
+            output: None,
+        }
+    }
+
+    fn bytes_sent(&self) -> Option<BytesSent> {
+        Some(BytesSent {
+            byte_size: self.byte_size,
+            protocol: "tcp",
+        })
+    }
+}
+
+/// Inserts the blocks, reusing the connections of the previous inserts that succeeded.
+#[derive(Clone)]
+struct NativeService {
+    connector: Arc<NativeConnector>,
+    query: Arc<str>,
+    columns: Arc<Vec<(String, ClickhouseColumnType)>>,
+    idle: Arc<Mutex<Vec<Connection>>>,
+}
+
+impl Service<NativeRequest> for NativeService {
+    type Response = NativeResponse;
+    type Error = NativeError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: NativeRequest) -> Self::Future {
+        let service = self.clone();
+
+        Box::pin(async move {
+            let idle = service.idle.lock().expect("mutex poisoned").pop();
+            let mut connection = match idle {
+                Some(connection) => connection,
+                None => service.connector.connect().await?,
+            };
+            // The connection is dropped on errors, as it may be left in the middle of the insert.
+            connection
+                .insert(&service.query, &service.columns, &request.block)
+                .await?;
+            service
+                .idle
+                .lock()
+                .expect("mutex poisoned")
+                .push(connection);
+
+            Ok(NativeResponse {
+                count: request.count,
+                events_byte_size: request.events_byte_size,
+                byte_size: request.block.len(),
+            })
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+struct NativeRetryLogic;
+
+impl RetryLogic for NativeRetryLogic {
+    type Error = NativeError;
+    type Response = NativeResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        error.is_retriable()
+    }
+}
+
+struct NativeSink {
+    service: NativeService,
+    request: TowerRequestConfig,
+    encoder: NativeEncoder,
+    batch_settings: BatcherSettings,
+}
+
+impl NativeSink {
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let request = self.request.unwrap_with(&TowerRequestConfig::default());
+        let service = ServiceBuilder::new()
+            .settings(request, NativeRetryLogic)
+            .service(self.service);
+        let encoder = self.encoder;
+
+        input
+            .batched(self.batch_settings.into_byte_size_config())
+            .map(|events| encoder.encode(events))
+            .into_driver(service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for NativeSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_varints() {
+        let mut buf = BytesMut::new();
+        put_varint(&mut buf, 1);
+        put_varint(&mut buf, 300);
+        put_varint(&mut buf, u64::MAX);
+
+        assert_eq!(
+            &buf[..],
+            &[1, 0xac, 0x02, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01][..]
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_varints() {
+        let mut reader = io::Cursor::new(vec![0xac, 0x02, 0x05]);
+
+        assert_eq!(read_varint(&mut reader).await.unwrap(), 300);
+        assert_eq!(read_varint(&mut reader).await.unwrap(), 5);
+    }
+
+    #[tokio::test]
+    async fn decompresses_compressed_frames() {
+        let data = "vector".repeat(MAX_FRAME_SIZE / 4).into_bytes();
+        let compressed = compress(&data);
+        assert!(compressed.len() < data.len());
+
+        let mut reader = io::Cursor::new(compressed);
+        let mut decompressed = read_frame(&mut reader).await.unwrap();
+        decompressed.extend(read_frame(&mut reader).await.unwrap());
+        assert_eq!(decompressed, data);
+    }
+
+    #[tokio::test]
+    async fn rejects_corrupted_frames() {
+        let mut compressed = compress(b"vector").to_vec();
+        let last = compressed.len() - 1;
+        compressed[last] ^= 0xff;
+
+        let error = read_frame(&mut io::Cursor::new(compressed))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, NativeError::InvalidData { .. }));
+    }
+
+    #[tokio::test]
+    async fn encodes_blocks() {
+        let columns = vec![
+            ("message".to_owned(), ClickhouseColumnType::String),
+            ("status".to_owned(), ClickhouseColumnType::UInt16),
+        ];
+        let mut log = LogEvent::from("hello");
+        log.insert("status", 200);
+
+        let block = encode_block(&columns, &[log], false);
+        let header = read_block_header(&mut io::Cursor::new(encode_block(&columns, &[], false)))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            header,
+            vec![
+                ("message".to_owned(), "String".to_owned()),
+                ("status".to_owned(), "UInt16".to_owned()),
+            ]
+        );
+        assert!(block.ends_with(b"\x07message\x06String\x05hello\x06status\x06UInt16\xc8\x00"));
+    }
+
+    #[test]
+    fn checks_header() {
+        let columns = vec![("timestamp".to_owned(), ClickhouseColumnType::DateTime(None))];
+
+        assert!(check_header(
+            &columns,
+            &[("timestamp".to_owned(), "DateTime('UTC')".to_owned())]
+        )
+        .is_ok());
+        assert!(matches!(
+            check_header(
+                &columns,
+                &[("timestamp".to_owned(), "LowCardinality(String)".to_owned())]
+            ),
+            Err(NativeError::ColumnType { .. })
+        ));
+    }
+
+    #[test]
+    fn quotes_identifiers() {
+        let columns = vec![("message".to_owned(), ClickhouseColumnType::String)];
+
+        assert_eq!(
+            insert_query("logs", "my_\"table\"", &columns),
+            r#"INSERT INTO "logs"."my_\"table\"" ("message") VALUES"#
+        );
+    }
+}
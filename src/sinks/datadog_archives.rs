@@ -47,7 +47,9 @@ use crate::{
         },
         gcs_common::{
             self,
-            config::{GcsPredefinedAcl, GcsRetryLogic, GcsStorageClass, BASE_URL},
+            config::{
+                GcsEncryptionConfig, GcsPredefinedAcl, GcsRetryLogic, GcsStorageClass, BASE_URL,
+            },
             service::{GcsRequest, GcsRequestSettings, GcsService},
             sink::GcsSink,
         },
@@ -152,6 +154,8 @@ pub struct GcsConfig {
     acl: Option<GcsPredefinedAcl>,
     storage_class: Option<GcsStorageClass>,
     metadata: Option<HashMap<String, String>>,
+    #[serde(default)]
+    encryption: GcsEncryptionConfig,
     #[serde(flatten)]
     auth: GcpAuthConfig,
 }
@@ -319,7 +323,7 @@ impl DatadogArchivesSinkConfig {
             .map(|acl| HeaderValue::from_str(&to_string(acl)).unwrap());
         let storage_class = gcs_config.storage_class.unwrap_or_default();
         let storage_class = HeaderValue::from_str(&to_string(storage_class)).unwrap();
-        let metadata = gcs_config
+        let mut metadata = gcs_config
             .metadata
             .as_ref()
             .map(|metadata| {
@@ -329,6 +333,7 @@ impl DatadogArchivesSinkConfig {
                     .collect::<Result<Vec<_>, _>>()
             })
             .unwrap_or_else(|| Ok(vec![]))?;
+        metadata.extend(gcs_config.encryption.headers()?);
         let request_builder = DatadogGcsRequestBuilder {
             bucket: self.bucket.clone(),
             key_prefix: self.key_prefix.clone(),
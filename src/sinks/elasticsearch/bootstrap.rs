@@ -0,0 +1,267 @@
+use http::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::{DataStreamConfig, ElasticsearchCommon, ParseError};
+use crate::{http::HttpClient, sinks::HealthcheckError};
+
+/// The index template and ILM policy of the data streams, created by the healthcheck when they
+/// don't exist, as Elastic Agent does for the data streams of its integrations.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct DataStreamBootstrapConfig {
+    /// The name of the index template, which defaults to `<type>-<dataset>`.
+    pub template_name: Option<String>,
+    /// The patterns of the data streams the index template applies to, which default to
+    /// `<type>-<dataset>-*`.
+    #[serde(default)]
+    pub index_patterns: Vec<String>,
+    /// The priority of the index template, higher than the one of the built-in templates of
+    /// Elasticsearch by default.
+    #[serde(default = "default_priority")]
+    pub priority: u32,
+    /// The component templates the index template is composed of.
+    #[serde(default)]
+    pub composed_of: Vec<String>,
+    pub ilm_policy: Option<IlmPolicyConfig>,
+}
+
+const fn default_priority() -> u32 {
+    200
+}
+
+/// The ILM policy of the data streams, which rolls their backing indices over and deletes them.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct IlmPolicyConfig {
+    /// The name of the policy, which defaults to the name of the index template.
+    pub name: Option<String>,
+    #[serde(default = "default_rollover_max_age")]
+    pub rollover_max_age: String,
+    #[serde(default = "default_rollover_max_primary_shard_size")]
+    pub rollover_max_primary_shard_size: String,
+    /// How long after their rollover the backing indices are deleted, if they are.
+    pub delete_after: Option<String>,
+}
+
+fn default_rollover_max_age() -> String {
+    "30d".into()
+}
+
+fn default_rollover_max_primary_shard_size() -> String {
+    "50gb".into()
+}
+
+/// The resources bootstrapped by the healthcheck.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataStreamBootstrap {
+    pub template_name: String,
+    pub index_template: Value,
+    pub ilm_policy: Option<(String, Value)>,
+}
+
+impl DataStreamBootstrap {
+    pub(super) fn new(
+        config: &DataStreamBootstrapConfig,
+        data_stream: &DataStreamConfig,
+    ) -> Result<Self, ParseError> {
+        // The defaults are only known when neither the type nor the dataset are templated.
+        let prefix =
+            (!data_stream.dtype.is_dynamic() && !data_stream.dataset.is_dynamic()).then(|| {
+                format!(
+                    "{}-{}",
+                    data_stream.dtype.get_ref(),
+                    data_stream.dataset.get_ref()
+                )
+            });
+
+        let template_name = match (&config.template_name, &prefix) {
+            (Some(name), _) => name.clone(),
+            (None, Some(prefix)) => prefix.clone(),
+            (None, None) => {
+                return Err(ParseError::BootstrapOptionRequired {
+                    option: "template_name",
+                })
+            }
+        };
+        let index_patterns = match (config.index_patterns.is_empty(), &prefix) {
+            (false, _) => config.index_patterns.clone(),
+            (true, Some(prefix)) => vec![format!("{}-*", prefix)],
+            (true, None) => {
+                return Err(ParseError::BootstrapOptionRequired {
+                    option: "index_patterns",
+                })
+            }
+        };
+
+        let ilm_policy = config.ilm_policy.as_ref().map(|policy| {
+            let name = policy.name.clone().unwrap_or_else(|| template_name.clone());
+            let mut phases = json!({
+                "hot": {
+                    "actions": {
+                        "rollover": {
+                            "max_age": policy.rollover_max_age,
+                            "max_primary_shard_size": policy.rollover_max_primary_shard_size,
+                        }
+                    }
+                }
+            });
+            if let Some(delete_after) = &policy.delete_after {
+                phases["delete"] = json!({
+                    "min_age": delete_after,
+                    "actions": { "delete": {} }
+                });
+            }
+            let body = json!({
+                "policy": {
+                    "phases": phases,
+                    "_meta": { "managed_by": "vector" }
+                }
+            });
+            (name, body)
+        });
+
+        let mut index_template = json!({
+            "index_patterns": index_patterns,
+            "data_stream": {},
+            "priority": config.priority,
+            "composed_of": config.composed_of,
+            "_meta": { "managed_by": "vector" }
+        });
+        if let Some((name, _)) = &ilm_policy {
+            index_template["template"] = json!({
+                "settings": { "index.lifecycle.name": name }
+            });
+        }
+
+        Ok(Self {
+            template_name,
+            index_template,
+            ilm_policy,
+        })
+    }
+
+    /// Creates the ILM policy and the index template, unless they already exist.
+    pub(super) async fn run(
+        &self,
+        common: &ElasticsearchCommon,
+        client: &HttpClient,
+    ) -> crate::Result<()> {
+        if let Some((name, body)) = &self.ilm_policy {
+            create_if_missing(common, client, &format!("_ilm/policy/{}", name), body).await?;
+        }
+        create_if_missing(
+            common,
+            client,
+            &format!("_index_template/{}", self.template_name),
+            &self.index_template,
+        )
+        .await
+    }
+}
+
+async fn create_if_missing(
+    common: &ElasticsearchCommon,
+    client: &HttpClient,
+    path: &str,
+    body: &Value,
+) -> crate::Result<()> {
+    let response = common.send(client, Method::GET, path, None).await?;
+    match response.status() {
+        StatusCode::OK => return Ok(()),
+        StatusCode::NOT_FOUND => {}
+        status => return Err(HealthcheckError::UnexpectedStatus { status }.into()),
+    }
+
+    let response = common.send(client, Method::PUT, path, Some(body)).await?;
+    match response.status() {
+        StatusCode::OK => {
+            info!(message = "Created Elasticsearch resource.", path);
+            Ok(())
+        }
+        status => Err(HealthcheckError::UnexpectedStatus { status }.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use super::*;
+    use crate::template::Template;
+
+    fn bootstrap_config(toml: &str) -> DataStreamBootstrapConfig {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn derives_names_from_static_data_stream() {
+        let data_stream = DataStreamConfig {
+            dataset: Template::try_from("nginx").unwrap(),
+            ..Default::default()
+        };
+        let bootstrap = DataStreamBootstrap::new(
+            &bootstrap_config(
+                r#"
+                ilm_policy.delete_after = "7d"
+                "#,
+            ),
+            &data_stream,
+        )
+        .unwrap();
+
+        assert_eq!(bootstrap.template_name, "logs-nginx");
+        assert_eq!(
+            bootstrap.index_template,
+            json!({
+                "index_patterns": ["logs-nginx-*"],
+                "data_stream": {},
+                "priority": 200,
+                "composed_of": [],
+                "template": { "settings": { "index.lifecycle.name": "logs-nginx" } },
+                "_meta": { "managed_by": "vector" }
+            })
+        );
+        let (name, policy) = bootstrap.ilm_policy.unwrap();
+        assert_eq!(name, "logs-nginx");
+        assert_eq!(
+            policy["policy"]["phases"]["hot"]["actions"]["rollover"]["max_age"],
+            "30d"
+        );
+        assert_eq!(policy["policy"]["phases"]["delete"]["min_age"], "7d");
+    }
+
+    #[test]
+    fn requires_names_for_templated_data_stream() {
+        let data_stream = DataStreamConfig {
+            dataset: Template::try_from("{{ service }}").unwrap(),
+            ..Default::default()
+        };
+
+        let error = DataStreamBootstrap::new(&bootstrap_config(""), &data_stream).unwrap_err();
+        assert!(matches!(
+            error,
+            ParseError::BootstrapOptionRequired {
+                option: "template_name"
+            }
+        ));
+
+        let bootstrap = DataStreamBootstrap::new(
+            &bootstrap_config(
+                r#"
+                template_name = "vector-logs"
+                index_patterns = ["logs-*-vector"]
+                "#,
+            ),
+            &data_stream,
+        )
+        .unwrap();
+        assert_eq!(bootstrap.template_name, "vector-logs");
+        assert_eq!(
+            bootstrap.index_template["index_patterns"],
+            json!(["logs-*-vector"])
+        );
+        assert!(bootstrap.ilm_policy.is_none());
+        assert!(bootstrap.index_template.get("template").is_none());
+    }
+}
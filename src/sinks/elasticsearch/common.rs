@@ -6,11 +6,12 @@ use aws_sigv4::SigningParams;
 use aws_types::credentials::{ProvideCredentials, SharedCredentialsProvider};
 use aws_types::region::Region;
 use bytes::Bytes;
-use http::{StatusCode, Uri};
+use http::{Method, Response, StatusCode, Uri};
 use snafu::ResultExt;
 
 use super::{
-    request_builder::ElasticsearchRequestBuilder, ElasticsearchEncoder, InvalidHostSnafu, Request,
+    bootstrap::DataStreamBootstrap, request_builder::ElasticsearchRequestBuilder,
+    ElasticsearchEncoder, InvalidHostSnafu, Request,
 };
 use crate::{
     http::{Auth, HttpClient, MaybeAuth},
@@ -38,6 +39,7 @@ pub struct ElasticsearchCommon {
     pub request: RequestConfig,
     pub query_params: HashMap<String, String>,
     pub metric_to_log: MetricToLog,
+    pub bootstrap: Option<DataStreamBootstrap>,
 }
 
 impl ElasticsearchCommon {
@@ -59,14 +61,25 @@ impl ElasticsearchCommon {
                 user: user.clone(),
                 password: password.clone(),
             }),
-            _ => None,
+            Some(ElasticsearchAuth::ApiKey { api_key }) => Some(Auth::ApiKey {
+                api_key: api_key.clone(),
+            }),
+            Some(ElasticsearchAuth::Bearer { token }) => Some(Auth::Bearer {
+                token: token.clone(),
+            }),
+            Some(ElasticsearchAuth::Aws(_)) | None => None,
         };
         let uri = config.endpoint.parse::<UriSerde>()?;
         let http_auth = authorization.choose_one(&uri.auth)?;
         let base_url = uri.uri.to_string().trim_end_matches('/').to_owned();
 
         let aws_auth = match &config.auth {
-            Some(ElasticsearchAuth::Basic { .. }) | None => None,
+            Some(
+                ElasticsearchAuth::Basic { .. }
+                | ElasticsearchAuth::ApiKey { .. }
+                | ElasticsearchAuth::Bearer { .. },
+            )
+            | None => None,
             Some(ElasticsearchAuth::Aws(aws)) => {
                 let region = config
                     .aws
@@ -80,6 +93,15 @@ impl ElasticsearchCommon {
         };
 
         let mode = config.common_mode()?;
+        let bootstrap = mode
+            .as_data_stream_config()
+            .and_then(|data_stream| {
+                data_stream
+                    .bootstrap
+                    .as_ref()
+                    .map(|bootstrap| DataStreamBootstrap::new(bootstrap, data_stream))
+            })
+            .transpose()?;
 
         let doc_type = config.doc_type.clone().unwrap_or_else(|| "_doc".into());
         let request_builder = ElasticsearchRequestBuilder {
@@ -137,11 +159,37 @@ impl ElasticsearchCommon {
             region,
             tls_settings,
             metric_to_log,
+            bootstrap,
         })
     }
 
     pub async fn healthcheck(self, client: HttpClient) -> crate::Result<()> {
-        let mut builder = Request::get(format!("{}/_cluster/health", self.base_url));
+        let response = self
+            .send(&client, Method::GET, "_cluster/health", None)
+            .await?;
+        match response.status() {
+            StatusCode::OK => {}
+            status => return Err(HealthcheckError::UnexpectedStatus { status }.into()),
+        }
+
+        if let Some(bootstrap) = &self.bootstrap {
+            bootstrap.run(&self, &client).await?;
+        }
+        Ok(())
+    }
+
+    /// Sends a request to the API of the cluster, with the authentication and headers of the
+    /// requests of the sink.
+    pub(super) async fn send(
+        &self,
+        client: &HttpClient,
+        method: Method,
+        path: &str,
+        body: Option<&serde_json::Value>,
+    ) -> crate::Result<Response<hyper::Body>> {
+        let mut builder = Request::builder()
+            .method(method)
+            .uri(format!("{}/{}", self.base_url, path));
 
         if let Some(authorization) = &self.http_auth {
             builder = authorization.apply_builder(builder);
@@ -151,17 +199,19 @@ impl ElasticsearchCommon {
             builder = builder.header(&header[..], &value[..]);
         }
 
-        let mut request = builder.body(Bytes::new())?;
+        let body = match body {
+            Some(body) => {
+                builder = builder.header("Content-Type", "application/json");
+                Bytes::from(serde_json::to_vec(body)?)
+            }
+            None => Bytes::new(),
+        };
+        let mut request = builder.body(body)?;
 
         if let Some(credentials_provider) = &self.aws_auth {
             sign_request(&mut request, credentials_provider, &self.region).await?;
         }
-        let response = client.send(request.map(hyper::Body::from)).await?;
-
-        match response.status() {
-            StatusCode::OK => Ok(()),
-            status => Err(HealthcheckError::UnexpectedStatus { status }.into()),
-        }
+        Ok(client.send(request.map(hyper::Body::from)).await?)
     }
 }
 
@@ -20,8 +20,8 @@ use crate::{
             retry::ElasticsearchRetryLogic,
             service::{ElasticsearchService, HttpRequestBuilder},
             sink::ElasticsearchSink,
-            BatchActionTemplateSnafu, ElasticsearchAuth, ElasticsearchCommon,
-            ElasticsearchCommonMode, ElasticsearchMode, IndexTemplateSnafu,
+            BatchActionTemplateSnafu, DataStreamBootstrapConfig, ElasticsearchAuth,
+            ElasticsearchCommon, ElasticsearchCommonMode, ElasticsearchMode, IndexTemplateSnafu,
         },
         util::{
             http::RequestConfig, BatchConfig, Compression, RealtimeSizeBasedDefaultBatchSettings,
@@ -144,6 +144,7 @@ pub struct DataStreamConfig {
     pub auto_routing: bool,
     #[serde(default = "DataStreamConfig::default_sync_fields")]
     pub sync_fields: bool,
+    pub bootstrap: Option<DataStreamBootstrapConfig>,
 }
 
 impl Default for DataStreamConfig {
@@ -154,6 +155,7 @@ impl Default for DataStreamConfig {
             namespace: Self::default_namespace(),
             auto_routing: Self::default_auto_routing(),
             sync_fields: Self::default_sync_fields(),
+            bootstrap: None,
         }
     }
 }
@@ -389,4 +391,20 @@ mod tests {
         assert!(matches!(config.mode, ElasticsearchMode::DataStream));
         assert!(config.data_stream.is_some());
     }
+
+    #[test]
+    fn parse_data_stream_bootstrap() {
+        let config = toml::from_str::<ElasticsearchConfig>(
+            r#"
+            endpoint = ""
+            mode = "data_stream"
+            data_stream.dataset = "nginx"
+            data_stream.bootstrap.ilm_policy.rollover_max_age = "1d"
+        "#,
+        )
+        .unwrap();
+        let bootstrap = config.data_stream.unwrap().bootstrap.unwrap();
+        assert_eq!(bootstrap.priority, 200);
+        assert_eq!(bootstrap.ilm_policy.unwrap().rollover_max_age, "1d");
+    }
 }
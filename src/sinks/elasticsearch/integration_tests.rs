@@ -1,4 +1,4 @@
-use std::{fs::File, io::Read};
+use std::{convert::TryFrom, fs::File, io::Read};
 
 use aws_smithy_http::body::SdkBody;
 use bytes::Bytes;
@@ -21,6 +21,7 @@ use crate::{
         util::{BatchConfig, Compression},
         HealthcheckError,
     },
+    template::Template,
     test_util::{
         components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
         random_events_with_stream, random_string, trace_init,
@@ -330,6 +331,64 @@ async fn insert_events_in_data_stream() {
     run_insert_tests_with_config(&cfg, false, BatchStatus::Delivered).await;
 }
 
+#[tokio::test]
+async fn bootstraps_data_stream_template() {
+    trace_init();
+    let dataset = random_string(10).to_lowercase();
+
+    let cfg = ElasticsearchConfig {
+        endpoint: http_server(),
+        mode: ElasticsearchMode::DataStream,
+        data_stream: Some(DataStreamConfig {
+            dataset: Template::try_from(dataset.as_str()).unwrap(),
+            bootstrap: Some(DataStreamBootstrapConfig {
+                template_name: None,
+                index_patterns: Vec::new(),
+                priority: 200,
+                composed_of: Vec::new(),
+                ilm_policy: Some(IlmPolicyConfig {
+                    name: None,
+                    rollover_max_age: "1d".into(),
+                    rollover_max_primary_shard_size: "1gb".into(),
+                    delete_after: Some("7d".into()),
+                }),
+            }),
+            ..Default::default()
+        }),
+        ..config()
+    };
+
+    // The healthcheck creates the resources once, and leaves them as is afterwards.
+    for _ in 0..2 {
+        let (_, healthcheck) = cfg.build(SinkContext::new_test()).await.unwrap();
+        healthcheck.await.expect("Health check failed");
+    }
+
+    let client = create_http_client();
+    let name = format!("logs-{}", dataset);
+    let template = client
+        .get(format!("{}/_index_template/{}", http_server(), name))
+        .send()
+        .await
+        .unwrap()
+        .json::<Value>()
+        .await
+        .unwrap();
+    let template = &template["index_templates"][0]["index_template"];
+    assert_eq!(template["index_patterns"], json!([format!("{}-*", name)]));
+    assert_eq!(
+        template["template"]["settings"]["index"]["lifecycle"]["name"],
+        name
+    );
+
+    let response = client
+        .get(format!("{}/_ilm/policy/{}", http_server(), name))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
 async fn run_insert_tests(
     mut config: ElasticsearchConfig,
     break_events: bool,
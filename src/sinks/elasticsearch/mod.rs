@@ -1,3 +1,4 @@
+mod bootstrap;
 mod common;
 mod config;
 mod encoder;
@@ -15,6 +16,7 @@ mod integration_tests;
 
 use std::convert::TryFrom;
 
+pub use bootstrap::{DataStreamBootstrapConfig, IlmPolicyConfig};
 pub use common::*;
 pub use config::*;
 pub use encoder::ElasticsearchEncoder;
@@ -34,6 +36,8 @@ use crate::{
 #[serde(deny_unknown_fields, rename_all = "snake_case", tag = "strategy")]
 pub enum ElasticsearchAuth {
     Basic { user: String, password: String },
+    ApiKey { api_key: String },
+    Bearer { token: String },
     Aws(AwsAuthentication),
 }
 
@@ -58,6 +62,10 @@ pub enum BulkAction {
     Create,
 }
 
+/// The types of the data streams created by the built-in index templates of Elasticsearch, for the
+/// indices matching `<type>-*-*`.
+const BUILT_IN_DATA_STREAM_TYPES: [&str; 4] = ["logs", "metrics", "synthetics", "traces"];
+
 #[allow(clippy::trivially_copy_pass_by_ref)]
 impl BulkAction {
     pub const fn as_str(&self) -> &'static str {
@@ -73,6 +81,20 @@ impl BulkAction {
             BulkAction::Create => "/create",
         }
     }
+
+    /// Selects the action of the events written to the index when none is configured, which is
+    /// `create` for the data streams of the built-in index templates, as they only accept it,
+    /// and `index` otherwise.
+    fn auto(index: &str) -> Self {
+        match index.split_once('-') {
+            Some((dtype, rest))
+                if BUILT_IN_DATA_STREAM_TYPES.contains(&dtype) && rest.contains('-') =>
+            {
+                Self::Create
+            }
+            _ => Self::Index,
+        }
+    }
 }
 
 impl TryFrom<&str> for BulkAction {
@@ -119,7 +141,7 @@ impl ElasticsearchCommonMode {
         }
     }
 
-    fn bulk_action<'a>(&self, event: impl Into<EventRef<'a>>) -> Option<BulkAction> {
+    fn bulk_action<'a>(&self, event: impl Into<EventRef<'a>>, index: &str) -> Option<BulkAction> {
         match self {
             ElasticsearchCommonMode::Bulk {
                 action: bulk_action,
@@ -136,7 +158,7 @@ impl ElasticsearchCommonMode {
                     })
                     .ok()
                     .and_then(|value| BulkAction::try_from(value.as_str()).ok()),
-                None => Some(BulkAction::Index),
+                None => Some(BulkAction::auto(index)),
             },
             // avoid the interpolation
             ElasticsearchCommonMode::DataStream(_) => Some(BulkAction::Create),
@@ -164,4 +186,9 @@ pub enum ParseError {
     BatchActionTemplate { source: TemplateParseError },
     #[snafu(display("aws.region required when AWS authentication is in use"))]
     RegionRequired,
+    #[snafu(display(
+        "data_stream.bootstrap.{} must be set when data_stream.type or data_stream.dataset is templated",
+        option
+    ))]
+    BootstrapOptionRequired { option: &'static str },
 }
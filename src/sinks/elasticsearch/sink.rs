@@ -98,7 +98,7 @@ pub fn process_log(
     id_key_field: &Option<String>,
 ) -> Option<ProcessedEvent> {
     let index = mode.index(&log)?;
-    let bulk_action = mode.bulk_action(&log)?;
+    let bulk_action = mode.bulk_action(&log, &index)?;
 
     if let Some(cfg) = mode.as_data_stream_config() {
         cfg.sync_fields(&mut log);
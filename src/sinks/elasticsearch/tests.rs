@@ -3,6 +3,7 @@ use std::{collections::BTreeMap, convert::TryFrom};
 use crate::{
     codecs::Transformer,
     event::{LogEvent, Metric, MetricKind, MetricValue, Value},
+    http::Auth,
     sinks::{
         elasticsearch::{
             sink::process_log, BulkAction, BulkConfig, DataStreamConfig, ElasticsearchCommon,
@@ -13,6 +14,37 @@ use crate::{
     template::Template,
 };
 
+#[tokio::test]
+async fn applies_api_key_and_bearer_auth() {
+    for (auth, expected) in [
+        (
+            r#"auth = { strategy = "api_key", api_key = "aWQ6a2V5" }"#,
+            Auth::ApiKey {
+                api_key: "aWQ6a2V5".into(),
+            },
+        ),
+        (
+            r#"auth = { strategy = "bearer", token = "AAEAAWVsYXN0aWM" }"#,
+            Auth::Bearer {
+                token: "AAEAAWVsYXN0aWM".into(),
+            },
+        ),
+    ] {
+        let config = toml::from_str::<ElasticsearchConfig>(&format!(
+            r#"
+            endpoint = "https://example.com"
+            {}
+            "#,
+            auth
+        ))
+        .unwrap();
+        let es = ElasticsearchCommon::parse_config(&config).await.unwrap();
+
+        assert_eq!(es.http_auth, Some(expected));
+        assert!(es.aws_auth.is_none());
+    }
+}
+
 #[tokio::test]
 async fn sets_create_action_when_configured() {
     use chrono::{TimeZone, Utc};
@@ -202,7 +234,7 @@ async fn decode_bulk_action_error() {
     let mut log = LogEvent::from("hello world");
     log.insert("foo", "bar");
     log.insert("idx", "purple");
-    let action = es.mode.bulk_action(&log);
+    let action = es.mode.bulk_action(&log, "vector");
     assert!(action.is_none());
 }
 
@@ -219,10 +251,34 @@ async fn decode_bulk_action() {
     let es = ElasticsearchCommon::parse_config(&config).await.unwrap();
 
     let log = LogEvent::from("hello there");
-    let action = es.mode.bulk_action(&log).unwrap();
+    let action = es.mode.bulk_action(&log, "vector").unwrap();
     assert!(matches!(action, BulkAction::Create));
 }
 
+#[tokio::test]
+async fn selects_create_action_for_data_streams() {
+    let config = ElasticsearchConfig {
+        bulk: Some(BulkConfig {
+            action: None,
+            index: Some(String::from("{{ idx }}")),
+        }),
+        endpoint: String::from("https://example.com"),
+        ..Default::default()
+    };
+    let es = ElasticsearchCommon::parse_config(&config).await.unwrap();
+
+    let log = LogEvent::from("hello there");
+    for (index, action) in [
+        ("logs-nginx-default", BulkAction::Create),
+        ("metrics-system.cpu-production", BulkAction::Create),
+        ("logs-nginx", BulkAction::Index),
+        ("vector-2022.08.01", BulkAction::Index),
+        ("application-logs-default", BulkAction::Index),
+    ] {
+        assert_eq!(es.mode.bulk_action(&log, index), Some(action));
+    }
+}
+
 #[tokio::test]
 async fn encode_datastream_mode_no_sync() {
     use chrono::{TimeZone, Utc};
@@ -0,0 +1,718 @@
+//! This sink streams log events into Google BigQuery tables.
+//! See https://cloud.google.com/bigquery/docs/reference/rest/v2/tabledata/insertAll
+//! for more information.
+use std::io;
+
+use bytes::Bytes;
+use futures_util::{future::BoxFuture, task::Poll};
+use goauth::scopes::Scope;
+use http::{header::HeaderValue, Request, StatusCode, Uri};
+use hyper::Body;
+use indoc::indoc;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use snafu::{ResultExt, Snafu};
+use tower::{Service, ServiceBuilder};
+use vector_core::{
+    config::{AcknowledgementsConfig, Input},
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
+    internal_event::{BytesSent, EventsSent},
+    partition::Partitioner,
+    sink::VectorSink,
+    stream::DriverResponse,
+};
+
+use crate::{
+    codecs::Transformer,
+    config::{GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    gcp::{GcpAuthConfig, GcpAuthenticator},
+    http::{CallRequestSnafu, HttpClient, HttpError},
+    internal_events::TemplateRenderingError,
+    sinks::{
+        gcs_common::{config::healthcheck_response, sink::GcsSink},
+        util::{
+            encoding::{as_tracked_write, Encoder},
+            metadata::{RequestMetadata, RequestMetadataBuilder},
+            request_builder::EncodeResult,
+            retries::{RetryAction, RetryLogic},
+            BatchConfig, Compression, RequestBuilder, SinkBatchSettings, TowerRequestConfig,
+        },
+        Healthcheck,
+    },
+    template::Template,
+    tls::{TlsConfig, TlsSettings},
+};
+
+const NAME: &str = "gcp_bigquery";
+
+const BIGQUERY_URL: &str = "https://bigquery.googleapis.com";
+
+/// BigQuery recommends at most 500 rows per streaming insert request, and rejects requests larger
+/// than 10MB.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BigqueryDefaultBatchSettings;
+
+impl SinkBatchSettings for BigqueryDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(500);
+    const MAX_BYTES: Option<usize> = Some(10_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+#[derive(Debug, Snafu)]
+enum BigqueryHealthcheckError {
+    #[snafu(display("Dataset not found"))]
+    DatasetNotFound,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct BigqueryConfig {
+    pub endpoint: Option<String>,
+    pub project: String,
+    pub dataset: Template,
+    pub table: Template,
+    pub insert_id_field: Option<String>,
+    #[serde(default)]
+    pub skip_invalid_rows: bool,
+    #[serde(default)]
+    pub ignore_unknown_values: bool,
+    #[serde(flatten)]
+    pub auth: GcpAuthConfig,
+    #[serde(default)]
+    pub batch: BatchConfig<BigqueryDefaultBatchSettings>,
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+    pub tls: Option<TlsConfig>,
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+inventory::submit! {
+    SinkDescription::new::<BigqueryConfig>(NAME)
+}
+
+impl GenerateConfig for BigqueryConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc! {r#"
+            credentials_path = "/path/to/credentials.json"
+            project = "my-project"
+            dataset = "my_dataset"
+            table = "my_table"
+        "#})
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "gcp_bigquery")]
+impl SinkConfig for BigqueryConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let creds = self.auth.build(Scope::CloudPlatform).await?;
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, cx.proxy())?;
+
+        let base_url = format!(
+            "{}/bigquery/v2/projects/{}",
+            self.endpoint
+                .as_deref()
+                .unwrap_or(BIGQUERY_URL)
+                .trim_end_matches('/'),
+            self.project
+        );
+
+        let healthcheck = self.build_healthcheck(client.clone(), &base_url, creds.clone())?;
+        let service = BigqueryService::new(client, base_url, creds);
+        let sink = self.build_sink(service)?;
+
+        Ok((sink, healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn sink_type(&self) -> &'static str {
+        NAME
+    }
+
+    fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
+        Some(&self.acknowledgements)
+    }
+}
+
+impl BigqueryConfig {
+    fn build_sink(&self, service: BigqueryService) -> crate::Result<VectorSink> {
+        use crate::sinks::util::service::ServiceBuilderExt;
+
+        let request = self.request.unwrap_with(&TowerRequestConfig {
+            rate_limit_num: Some(1000),
+            ..Default::default()
+        });
+
+        let batch_settings = self.batch.into_batcher_settings()?;
+
+        let partitioner = BigqueryPartitioner {
+            dataset: self.dataset.clone(),
+            table: self.table.clone(),
+        };
+
+        let svc = ServiceBuilder::new()
+            .settings(request, BigqueryRetryLogic)
+            .service(service);
+
+        let request_builder = BigqueryRequestBuilder {
+            encoder: BigqueryEncoder {
+                insert_id_field: self.insert_id_field.clone(),
+                skip_invalid_rows: self.skip_invalid_rows,
+                ignore_unknown_values: self.ignore_unknown_values,
+                transformer: self.encoding.clone(),
+            },
+        };
+
+        let sink = GcsSink::new(svc, request_builder, partitioner, batch_settings);
+
+        Ok(VectorSink::from_event_streamsink(sink))
+    }
+
+    /// Checks that the dataset exists, or that the datasets of the project can be listed when the
+    /// dataset is templated.
+    fn build_healthcheck(
+        &self,
+        client: HttpClient,
+        base_url: &str,
+        auth: GcpAuthenticator,
+    ) -> crate::Result<Healthcheck> {
+        let uri = if self.dataset.is_dynamic() {
+            format!("{}/datasets?maxResults=1", base_url)
+        } else {
+            format!("{}/datasets/{}", base_url, self.dataset.get_ref())
+        }
+        .parse::<Uri>()?;
+
+        let healthcheck = async move {
+            let mut request = http::Request::get(&uri).body(Body::empty())?;
+            auth.apply(&mut request);
+
+            let response = client.send(request).await?;
+            healthcheck_response(
+                response,
+                auth,
+                BigqueryHealthcheckError::DatasetNotFound.into(),
+            )
+        };
+
+        Ok(Box::pin(healthcheck))
+    }
+}
+
+/// Events are batched per table, as each request can only target a single one.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct BigqueryPartitionKey {
+    pub dataset: String,
+    pub table: String,
+}
+
+/// Partitions events based on the rendered `dataset` and `table` templates.
+struct BigqueryPartitioner {
+    dataset: Template,
+    table: Template,
+}
+
+impl BigqueryPartitioner {
+    fn render(&self, template: &Template, field: &'static str, item: &Event) -> Option<String> {
+        template
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some(field),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+}
+
+impl Partitioner for BigqueryPartitioner {
+    type Item = Event;
+    type Key = Option<BigqueryPartitionKey>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        let dataset = self.render(&self.dataset, "dataset", item)?;
+        let table = self.render(&self.table, "table", item)?;
+        Some(BigqueryPartitionKey { dataset, table })
+    }
+}
+
+#[derive(Clone, Debug)]
+struct BigqueryEncoder {
+    insert_id_field: Option<String>,
+    skip_invalid_rows: bool,
+    ignore_unknown_values: bool,
+    transformer: Transformer,
+}
+
+impl Encoder<Vec<Event>> for BigqueryEncoder {
+    fn encode_input(&self, events: Vec<Event>, writer: &mut dyn io::Write) -> io::Result<usize> {
+        let rows = events
+            .into_iter()
+            .map(|mut event| {
+                // The insert ID is looked up before the transformer runs, so the field can be
+                // excluded from the row itself.
+                let insert_id = self.insert_id_field.as_ref().and_then(|field| {
+                    event
+                        .as_log()
+                        .get(field.as_str())
+                        .map(|value| value.to_string_lossy())
+                });
+                self.transformer.transform(&mut event);
+
+                let mut row = json!({ "json": event.as_log() });
+                if let Some(insert_id) = insert_id {
+                    row.as_object_mut()
+                        .unwrap()
+                        .insert("insertId".to_string(), insert_id.into());
+                }
+                row
+            })
+            .collect::<Vec<_>>();
+
+        let body = json!({
+            "kind": "bigquery#tableDataInsertAllRequest",
+            "skipInvalidRows": self.skip_invalid_rows,
+            "ignoreUnknownValues": self.ignore_unknown_values,
+            "rows": rows,
+        });
+
+        as_tracked_write::<_, _, io::Error>(writer, &body, |writer, body| {
+            serde_json::to_writer(writer, body)?;
+            Ok(())
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BigqueryRequest {
+    pub key: BigqueryPartitionKey,
+    pub body: Bytes,
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for BigqueryRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct BigqueryRequestBuilder {
+    encoder: BigqueryEncoder,
+}
+
+impl RequestBuilder<(BigqueryPartitionKey, Vec<Event>)> for BigqueryRequestBuilder {
+    type Metadata = (BigqueryPartitionKey, EventFinalizers, RequestMetadataBuilder);
+    type Events = Vec<Event>;
+    type Encoder = BigqueryEncoder;
+    type Payload = Bytes;
+    type Request = BigqueryRequest;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        Compression::None
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        input: (BigqueryPartitionKey, Vec<Event>),
+    ) -> (Self::Metadata, Self::Events) {
+        let (key, mut events) = input;
+        let finalizers = events.take_finalizers();
+        let metadata = RequestMetadata::builder(&events);
+
+        ((key, finalizers, metadata), events)
+    }
+
+    fn build_request(
+        &self,
+        metadata: Self::Metadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        let (key, finalizers, metadata_builder) = metadata;
+        let metadata = metadata_builder.build(&payload);
+
+        BigqueryRequest {
+            key,
+            body: payload.into_payload(),
+            finalizers,
+            metadata,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BigqueryService {
+    client: HttpClient,
+    base_url: String,
+    creds: GcpAuthenticator,
+}
+
+impl BigqueryService {
+    pub const fn new(client: HttpClient, base_url: String, creds: GcpAuthenticator) -> Self {
+        Self {
+            client,
+            base_url,
+            creds,
+        }
+    }
+}
+
+impl Service<BigqueryRequest> for BigqueryService {
+    type Response = BigqueryResponse;
+    type Error = HttpError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _: &mut std::task::Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: BigqueryRequest) -> Self::Future {
+        let uri = format!(
+            "{}/datasets/{}/tables/{}/insertAll",
+            self.base_url, request.key.dataset, request.key.table
+        );
+        let mut builder = Request::post(uri);
+        let headers = builder.headers_mut().unwrap();
+        headers.insert(
+            "content-type",
+            HeaderValue::from_str("application/json").unwrap(),
+        );
+        headers.insert(
+            "content-length",
+            HeaderValue::from_str(&request.body.len().to_string()).unwrap(),
+        );
+
+        let mut http_request = builder.body(Body::from(request.body)).unwrap();
+        self.creds.apply(&mut http_request);
+
+        let mut client = self.client.clone();
+        let creds = self.creds.clone();
+        Box::pin(async move {
+            let response = client.call(http_request).await?;
+            if response.status() == StatusCode::UNAUTHORIZED {
+                creds.force_refresh();
+            }
+
+            // Rows can be rejected even when the request succeeds, so the body is always read.
+            let (parts, body) = response.into_parts();
+            let body = hyper::body::to_bytes(body)
+                .await
+                .context(CallRequestSnafu)?;
+            let insert_errors = serde_json::from_slice::<InsertAllResponse>(&body)
+                .map(|response| response.insert_errors)
+                .unwrap_or_default();
+
+            Ok(BigqueryResponse {
+                inner: http::Response::from_parts(parts, body),
+                insert_errors,
+                protocol: "http",
+                metadata: request.metadata,
+            })
+        })
+    }
+}
+
+/// The response body of a streaming insert, listing the rows that could not be inserted.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InsertAllResponse {
+    #[serde(default)]
+    insert_errors: Vec<InsertError>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InsertError {
+    #[serde(default)]
+    index: usize,
+    #[serde(default)]
+    errors: Vec<InsertErrorDetail>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InsertErrorDetail {
+    #[serde(default)]
+    reason: String,
+    #[serde(default)]
+    location: String,
+    #[serde(default)]
+    message: String,
+}
+
+#[derive(Debug)]
+pub struct BigqueryResponse {
+    pub inner: http::Response<Bytes>,
+    pub insert_errors: Vec<InsertError>,
+    pub protocol: &'static str,
+    pub metadata: RequestMetadata,
+}
+
+impl BigqueryResponse {
+    /// Describes the first rejected row, as the other ones usually fail for the same reason.
+    fn insert_error_reason(&self) -> Option<String> {
+        let row = self.insert_errors.first()?;
+        let detail = row
+            .errors
+            .iter()
+            .find(|detail| detail.reason != "stopped")
+            .or_else(|| row.errors.first());
+
+        Some(match detail {
+            Some(detail) => format!(
+                "{} rows rejected, row {}: {} ({}) at {:?}",
+                self.insert_errors.len(),
+                row.index,
+                detail.message,
+                detail.reason,
+                detail.location
+            ),
+            None => format!("{} rows rejected", self.insert_errors.len()),
+        })
+    }
+}
+
+impl DriverResponse for BigqueryResponse {
+    fn event_status(&self) -> EventStatus {
+        let status = self.inner.status();
+        if status.is_success() && self.insert_errors.is_empty() {
+            EventStatus::Delivered
+        } else if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+            EventStatus::Errored
+        } else {
+            EventStatus::Rejected
+        }
+    }
+
+    fn events_sent(&self) -> EventsSent {
+        EventsSent {
+            count: self.metadata.event_count(),
+            byte_size: self.metadata.events_byte_size(),
+            output: None,
+        }
+    }
+
+    fn bytes_sent(&self) -> Option<BytesSent> {
+        Some(BytesSent {
+            byte_size: self.metadata.request_encoded_size(),
+            protocol: self.protocol,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct BigqueryRetryLogic;
+
+impl RetryLogic for BigqueryRetryLogic {
+    type Error = HttpError;
+    type Response = BigqueryResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        error.is_retriable()
+    }
+
+    fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
+        let status = response.inner.status();
+
+        match status {
+            StatusCode::TOO_MANY_REQUESTS => RetryAction::Retry("too many requests".into()),
+            StatusCode::NOT_IMPLEMENTED => {
+                RetryAction::DontRetry("endpoint not implemented".into())
+            }
+            _ if status.is_server_error() => RetryAction::Retry(status.to_string().into()),
+            // Rejected rows are caused by their content, sending them again would fail the same way.
+            _ if status.is_success() => match response.insert_error_reason() {
+                Some(reason) => RetryAction::DontRetry(reason.into()),
+                None => RetryAction::Successful,
+            },
+            _ => RetryAction::DontRetry(format!("response status: {}", status).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<BigqueryConfig>();
+    }
+
+    fn build_config(extra: &str) -> BigqueryConfig {
+        toml::from_str(&format!(
+            indoc! {r#"
+                project = "my-project"
+                dataset = "{{{{ dataset }}}}"
+                table = "logs_%Y%m%d"
+                {}
+            "#},
+            extra
+        ))
+        .unwrap()
+    }
+
+    fn build_payload(config: &BigqueryConfig, events: Vec<Event>) -> serde_json::Value {
+        let request_builder = BigqueryRequestBuilder {
+            encoder: BigqueryEncoder {
+                insert_id_field: config.insert_id_field.clone(),
+                skip_invalid_rows: config.skip_invalid_rows,
+                ignore_unknown_values: config.ignore_unknown_values,
+                transformer: config.encoding.clone(),
+            },
+        };
+        let key = BigqueryPartitionKey {
+            dataset: "my_dataset".into(),
+            table: "my_table".into(),
+        };
+        let (metadata, events) = request_builder.split_input((key, events));
+        let payload = request_builder.encode_events(events).unwrap();
+        let request = request_builder.build_request(metadata, payload);
+        serde_json::from_slice(&request.body).unwrap()
+    }
+
+    fn build_response(status: StatusCode, body: serde_json::Value) -> BigqueryResponse {
+        let body = Bytes::from(body.to_string());
+        let insert_errors = serde_json::from_slice::<InsertAllResponse>(&body)
+            .map(|response| response.insert_errors)
+            .unwrap_or_default();
+        BigqueryResponse {
+            inner: http::Response::builder().status(status).body(body).unwrap(),
+            insert_errors,
+            protocol: "http",
+            metadata: RequestMetadata::builder(&Vec::<Event>::new())
+                .with_request_size(std::num::NonZeroUsize::new(1).unwrap()),
+        }
+    }
+
+    #[test]
+    fn encodes_rows_with_insert_ids() {
+        let config = build_config(indoc! {r#"
+            insert_id_field = "id"
+            skip_invalid_rows = true
+            encoding.except_fields = ["id"]
+        "#});
+
+        let mut log = LogEvent::from("hello world");
+        log.insert("id", "abc");
+        log.insert("status", 200);
+        let body = build_payload(&config, vec![Event::from(log)]);
+
+        assert_eq!(body["skipInvalidRows"], true);
+        assert_eq!(body["ignoreUnknownValues"], false);
+        let row = &body["rows"][0];
+        assert_eq!(row["insertId"], "abc");
+        assert_eq!(row["json"]["message"], "hello world");
+        assert_eq!(row["json"]["status"], 200);
+        assert!(row["json"].get("id").is_none());
+    }
+
+    #[test]
+    fn omits_insert_id_by_default() {
+        let config = build_config("");
+        let body = build_payload(&config, vec![Event::from(LogEvent::from("hello"))]);
+
+        assert!(body["rows"][0].get("insertId").is_none());
+    }
+
+    #[test]
+    fn partitions_by_dataset_and_table() {
+        let config = build_config("");
+        let partitioner = BigqueryPartitioner {
+            dataset: config.dataset.clone(),
+            table: config.table.clone(),
+        };
+
+        let mut log = LogEvent::from("hello");
+        log.insert("dataset", "web");
+        let key = partitioner.partition(&Event::from(log)).unwrap();
+        assert_eq!(key.dataset, "web");
+        assert!(key.table.starts_with("logs_"));
+
+        assert!(partitioner
+            .partition(&Event::from(LogEvent::from("no dataset")))
+            .is_none());
+    }
+
+    #[test]
+    fn rejects_rows_with_insert_errors() {
+        let response = build_response(
+            StatusCode::OK,
+            json!({
+                "kind": "bigquery#tableDataInsertAllResponse",
+                "insertErrors": [
+                    {
+                        "index": 0,
+                        "errors": [{
+                            "reason": "invalid",
+                            "location": "status",
+                            "message": "Cannot convert value to integer.",
+                        }],
+                    },
+                    {
+                        "index": 1,
+                        "errors": [{ "reason": "stopped", "location": "", "message": "" }],
+                    },
+                ],
+            }),
+        );
+
+        assert_eq!(response.event_status(), EventStatus::Rejected);
+        match BigqueryRetryLogic.should_retry_response(&response) {
+            RetryAction::DontRetry(reason) => {
+                assert!(reason.contains("2 rows rejected"));
+                assert!(reason.contains("Cannot convert value to integer."));
+            }
+            _ => panic!("expected the response not to be retried"),
+        }
+    }
+
+    #[test]
+    fn delivers_rows_without_insert_errors() {
+        let response = build_response(
+            StatusCode::OK,
+            json!({ "kind": "bigquery#tableDataInsertAllResponse" }),
+        );
+
+        assert_eq!(response.event_status(), EventStatus::Delivered);
+        assert!(matches!(
+            BigqueryRetryLogic.should_retry_response(&response),
+            RetryAction::Successful
+        ));
+    }
+
+    #[test]
+    fn retries_server_errors() {
+        let response = build_response(StatusCode::SERVICE_UNAVAILABLE, json!({}));
+
+        assert_eq!(response.event_status(), EventStatus::Errored);
+        assert!(matches!(
+            BigqueryRetryLogic.should_retry_response(&response),
+            RetryAction::Retry(_)
+        ));
+    }
+}
@@ -0,0 +1,220 @@
+//! Periodically reports heartbeats and delivery statistics to Chronicle's forwarder API, so the
+//! Vector instance shows up as a healthy collector in the Chronicle console.
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Weak,
+    },
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use http::{header::HeaderValue, Request};
+use hyper::Body;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use snafu::Snafu;
+
+use crate::{
+    gcp::GcpAuthenticator, http::HttpClient, internal_events::ChronicleForwarderHeartbeatError,
+    sinks::HealthcheckError,
+};
+
+const fn default_interval_secs() -> u64 {
+    60
+}
+
+/// Configuration for reporting forwarder heartbeats to Chronicle.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ForwarderStatsConfig {
+    /// The ID of the forwarder, as registered in Chronicle, that this sink reports as.
+    pub forwarder_id: String,
+
+    /// The interval, in seconds, between two heartbeats.
+    #[serde(default = "default_interval_secs")]
+    pub interval_secs: u64,
+}
+
+#[derive(Debug, Snafu)]
+enum ForwarderStatsError {
+    #[snafu(display("Forwarder `interval_secs` must be greater than zero"))]
+    ZeroInterval,
+}
+
+impl ForwarderStatsConfig {
+    pub(super) fn build(
+        &self,
+        client: HttpClient,
+        base_url: &str,
+        creds: GcpAuthenticator,
+    ) -> crate::Result<(ForwarderHeartbeat, Arc<ForwarderStats>)> {
+        if self.interval_secs == 0 {
+            return Err(ForwarderStatsError::ZeroInterval.into());
+        }
+
+        let stats = Arc::new(ForwarderStats::default());
+        let heartbeat = ForwarderHeartbeat {
+            client,
+            url: format!("{}/v2/forwarders/{}:heartbeat", base_url, self.forwarder_id),
+            creds,
+            forwarder_id: self.forwarder_id.clone(),
+            interval: Duration::from_secs(self.interval_secs),
+            stats: Arc::downgrade(&stats),
+        };
+
+        Ok((heartbeat, stats))
+    }
+}
+
+/// Delivery statistics accumulated between two heartbeats.
+#[derive(Debug, Default)]
+pub struct ForwarderStats {
+    events_sent: AtomicU64,
+    bytes_sent: AtomicU64,
+    requests_failed: AtomicU64,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct ForwarderStatsSnapshot {
+    events_sent: u64,
+    bytes_sent: u64,
+    requests_failed: u64,
+}
+
+impl ForwarderStats {
+    pub fn record_sent(&self, events: usize, bytes: usize) {
+        self.events_sent.fetch_add(events as u64, Ordering::Relaxed);
+        self.bytes_sent.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_failed(&self) {
+        self.requests_failed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the statistics since the previous call, resetting them.
+    fn take(&self) -> ForwarderStatsSnapshot {
+        ForwarderStatsSnapshot {
+            events_sent: self.events_sent.swap(0, Ordering::Relaxed),
+            bytes_sent: self.bytes_sent.swap(0, Ordering::Relaxed),
+            requests_failed: self.requests_failed.swap(0, Ordering::Relaxed),
+        }
+    }
+}
+
+pub struct ForwarderHeartbeat {
+    client: HttpClient,
+    url: String,
+    creds: GcpAuthenticator,
+    forwarder_id: String,
+    interval: Duration,
+    stats: Weak<ForwarderStats>,
+}
+
+impl ForwarderHeartbeat {
+    /// Sends heartbeats until the sink holding the statistics is dropped.
+    pub async fn run(self) {
+        self.creds.spawn_regenerate_token();
+
+        let mut interval = tokio::time::interval(self.interval);
+        let mut period_start = Utc::now();
+        loop {
+            interval.tick().await;
+
+            let stats = match self.stats.upgrade() {
+                Some(stats) => stats.take(),
+                None => break,
+            };
+
+            let period_end = Utc::now();
+            if let Err(error) = self.send(&stats, period_start, period_end).await {
+                emit!(ChronicleForwarderHeartbeatError { error });
+            }
+            period_start = period_end;
+        }
+    }
+
+    fn body(
+        &self,
+        stats: &ForwarderStatsSnapshot,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> serde_json::Value {
+        json!({
+            "forwarder_id": self.forwarder_id,
+            "collector": {
+                "name": "vector",
+                "version": crate::get_version(),
+                "hostname": crate::get_hostname().ok(),
+            },
+            "stats": {
+                "period_start_time": period_start.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                "period_end_time": period_end.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                "events_sent": stats.events_sent,
+                "bytes_sent": stats.bytes_sent,
+                "requests_failed": stats.requests_failed,
+            },
+        })
+    }
+
+    async fn send(
+        &self,
+        stats: &ForwarderStatsSnapshot,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> crate::Result<()> {
+        let body = serde_json::to_vec(&self.body(stats, period_start, period_end))?;
+
+        let mut request = Request::post(&self.url)
+            .header("content-type", HeaderValue::from_static("application/json"))
+            .body(Body::from(body))?;
+        self.creds.apply(&mut request);
+
+        let response = self.client.send(request).await?;
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            Err(HealthcheckError::UnexpectedStatus { status }.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_are_reset_on_take() {
+        let stats = ForwarderStats::default();
+        stats.record_sent(10, 1024);
+        stats.record_sent(5, 512);
+        stats.record_failed();
+
+        assert_eq!(
+            stats.take(),
+            ForwarderStatsSnapshot {
+                events_sent: 15,
+                bytes_sent: 1536,
+                requests_failed: 1,
+            }
+        );
+        assert_eq!(stats.take(), ForwarderStatsSnapshot::default());
+    }
+
+    #[test]
+    fn rejects_zero_interval() {
+        let config: ForwarderStatsConfig = toml::from_str(
+            r#"
+                forwarder_id = "forwarder"
+                interval_secs = 0
+            "#,
+        )
+        .unwrap();
+
+        let client = HttpClient::new(None, &Default::default()).unwrap();
+        assert!(config
+            .build(client, "http://localhost", GcpAuthenticator::None)
+            .is_err());
+    }
+}
@@ -0,0 +1,442 @@
+//! This sink sends data to Google Chronicles UDM events endpoint.
+//! See https://cloud.google.com/chronicle/docs/reference/ingestion-api#udmevents
+//! for more information.
+use std::{fmt, io, num::NonZeroUsize};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::{future, stream::BoxStream, StreamExt};
+use goauth::scopes::Scope;
+use indoc::indoc;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use snafu::Snafu;
+use tower::{Service, ServiceBuilder};
+use vector_core::{
+    config::{AcknowledgementsConfig, Input},
+    event::{Event, EventFinalizers, EventStatus, Finalizable, LogEvent, Value},
+    sink::{StreamSink, VectorSink},
+    stream::{BatcherSettings, DriverResponse},
+};
+
+use crate::{
+    codecs::Transformer,
+    config::{GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    gcp::{GcpAuthConfig, GcpAuthenticator},
+    http::HttpClient,
+    internal_events::ChronicleUdmInvalidEvent,
+    sinks::{
+        gcp::chronicle_unstructured::{
+            build_healthcheck, create_endpoint, ChronicleDefaultBatchSettings, ChronicleError,
+            ChronicleRequest, ChronicleRetryLogic, ChronicleService, Region,
+        },
+        util::{
+            encoding::{as_tracked_write, Encoder},
+            metadata::{RequestMetadata, RequestMetadataBuilder},
+            request_builder::EncodeResult,
+            BatchConfig, Compression, RequestBuilder, SinkBuilderExt, TowerRequestConfig,
+        },
+        Healthcheck,
+    },
+    tls::{TlsConfig, TlsSettings},
+};
+
+const NAME: &str = "gcp_chronicle_udm";
+
+/// Fields that every UDM event must contain for Chronicle to accept it.
+const EVENT_TYPE_FIELD: &str = "metadata.event_type";
+const EVENT_TIMESTAMP_FIELD: &str = "metadata.event_timestamp";
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ChronicleUdmConfig {
+    pub endpoint: Option<String>,
+    pub region: Option<Region>,
+    pub customer_id: String,
+    #[serde(flatten)]
+    pub auth: GcpAuthConfig,
+    #[serde(default)]
+    pub batch: BatchConfig<ChronicleDefaultBatchSettings>,
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+    #[serde(default)]
+    pub compression: Compression,
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+    pub tls: Option<TlsConfig>,
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+inventory::submit! {
+    SinkDescription::new::<ChronicleUdmConfig>(NAME)
+}
+
+impl GenerateConfig for ChronicleUdmConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc! {r#"
+            credentials_path = "/path/to/credentials.json"
+            customer_id = "customer_id"
+            region = "us"
+        "#})
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "gcp_chronicle_udm")]
+impl SinkConfig for ChronicleUdmConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let creds = self.auth.build(Scope::MalachiteIngestion).await?;
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, cx.proxy())?;
+
+        let endpoint = self.create_endpoint("v2/udmevents:batchCreate")?;
+
+        // For the healthcheck we see if we can fetch the list of available log types.
+        let healthcheck_endpoint = self.create_endpoint("v2/logtypes")?;
+
+        let healthcheck = build_healthcheck(client.clone(), &healthcheck_endpoint, creds.clone())?;
+        let sink = self.build_sink(client, endpoint, creds)?;
+
+        Ok((sink, healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn sink_type(&self) -> &'static str {
+        NAME
+    }
+
+    fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
+        Some(&self.acknowledgements)
+    }
+}
+
+impl ChronicleUdmConfig {
+    fn build_sink(
+        &self,
+        client: HttpClient,
+        base_url: String,
+        creds: GcpAuthenticator,
+    ) -> crate::Result<VectorSink> {
+        use crate::sinks::util::service::ServiceBuilderExt;
+
+        let request = self.request.unwrap_with(&TowerRequestConfig {
+            rate_limit_num: Some(1000),
+            ..Default::default()
+        });
+
+        let batch_settings = self.batch.into_batcher_settings()?;
+
+        let service = ServiceBuilder::new()
+            .settings(request, ChronicleRetryLogic)
+            .service(ChronicleService::new(client, base_url, creds));
+
+        let request_builder = ChronicleUdmRequestBuilder {
+            encoder: ChronicleUdmEncoder {
+                customer_id: self.customer_id.clone(),
+                transformer: self.encoding.clone(),
+            },
+            compression: self.compression,
+        };
+
+        let sink = ChronicleUdmSink {
+            service,
+            request_builder,
+            batch_settings,
+        };
+
+        Ok(VectorSink::from_event_streamsink(sink))
+    }
+
+    fn create_endpoint(&self, path: &str) -> Result<String, ChronicleError> {
+        create_endpoint(self.endpoint.as_deref(), self.region, path)
+    }
+}
+
+#[derive(Debug, Snafu, PartialEq)]
+pub enum UdmValidationError {
+    #[snafu(display("Required UDM field {:?} is missing", field))]
+    MissingField { field: &'static str },
+    #[snafu(display("UDM field {:?} must be {}", field, expected))]
+    InvalidField {
+        field: &'static str,
+        expected: &'static str,
+    },
+}
+
+impl UdmValidationError {
+    pub const fn field(&self) -> &'static str {
+        match self {
+            Self::MissingField { field } | Self::InvalidField { field, .. } => field,
+        }
+    }
+}
+
+/// Checks that the event carries the fields required by every UDM event type.
+pub fn validate_udm_event(log: &LogEvent) -> Result<(), UdmValidationError> {
+    match log.get(EVENT_TYPE_FIELD) {
+        None => {
+            return Err(UdmValidationError::MissingField {
+                field: EVENT_TYPE_FIELD,
+            })
+        }
+        Some(Value::Bytes(_)) => (),
+        Some(_) => {
+            return Err(UdmValidationError::InvalidField {
+                field: EVENT_TYPE_FIELD,
+                expected: "a string",
+            })
+        }
+    }
+
+    match log.get(EVENT_TIMESTAMP_FIELD) {
+        None => Err(UdmValidationError::MissingField {
+            field: EVENT_TIMESTAMP_FIELD,
+        }),
+        Some(Value::Timestamp(_)) => Ok(()),
+        Some(Value::Bytes(bytes))
+            if chrono::DateTime::parse_from_rfc3339(&String::from_utf8_lossy(bytes)).is_ok() =>
+        {
+            Ok(())
+        }
+        Some(_) => Err(UdmValidationError::InvalidField {
+            field: EVENT_TIMESTAMP_FIELD,
+            expected: "an RFC 3339 timestamp",
+        }),
+    }
+}
+
+/// Drops events which Chronicle would reject, marking them as such so they are not retried.
+fn validate_event(mut event: Event) -> Option<Event> {
+    match validate_udm_event(event.as_log()) {
+        Ok(()) => Some(event),
+        Err(error) => {
+            emit!(ChronicleUdmInvalidEvent { error });
+            event.take_finalizers().update_status(EventStatus::Rejected);
+            None
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ChronicleUdmEncoder {
+    customer_id: String,
+    transformer: Transformer,
+}
+
+impl Encoder<Vec<Event>> for ChronicleUdmEncoder {
+    fn encode_input(&self, events: Vec<Event>, writer: &mut dyn io::Write) -> io::Result<usize> {
+        let events = events
+            .into_iter()
+            .map(|mut event| {
+                self.transformer.transform(&mut event);
+                event.into_log()
+            })
+            .collect::<Vec<_>>();
+
+        let json = json!({
+            "customer_id": self.customer_id,
+            "events": events,
+        });
+
+        let size = as_tracked_write::<_, _, io::Error>(writer, &json, |writer, json| {
+            serde_json::to_writer(writer, json)?;
+            Ok(())
+        })?;
+
+        Ok(size)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ChronicleUdmRequestBuilder {
+    encoder: ChronicleUdmEncoder,
+    compression: Compression,
+}
+
+impl RequestBuilder<Vec<Event>> for ChronicleUdmRequestBuilder {
+    type Metadata = (EventFinalizers, RequestMetadataBuilder);
+    type Events = Vec<Event>;
+    type Encoder = ChronicleUdmEncoder;
+    type Payload = Bytes;
+    type Request = ChronicleRequest;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(&self, input: Vec<Event>) -> (Self::Metadata, Self::Events) {
+        let mut events = input;
+        let finalizers = events.take_finalizers();
+
+        let metadata = RequestMetadata::builder(&events);
+        ((finalizers, metadata), events)
+    }
+
+    fn build_request(
+        &self,
+        metadata: Self::Metadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        let (finalizers, metadata_builder) = metadata;
+
+        let metadata = metadata_builder.build(&payload);
+        let body = payload.into_payload();
+
+        ChronicleRequest {
+            body,
+            compression: self.compression,
+            finalizers,
+            metadata,
+            log_type: None,
+        }
+    }
+}
+
+struct ChronicleUdmSink<S> {
+    service: S,
+    request_builder: ChronicleUdmRequestBuilder,
+    batch_settings: BatcherSettings,
+}
+
+impl<S> ChronicleUdmSink<S>
+where
+    S: Service<ChronicleRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let builder_limit = NonZeroUsize::new(64);
+
+        input
+            .filter_map(|event| future::ready(validate_event(event)))
+            .batched(self.batch_settings.into_byte_size_config())
+            .request_builder(builder_limit, self.request_builder)
+            .filter_map(|request| async move {
+                match request {
+                    Err(e) => {
+                        error!("Failed to build Chronicle UDM request: {:?}.", e);
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl<S> StreamSink<Event> for ChronicleUdmSink<S>
+where
+    S: Service<ChronicleRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::{BatchNotifier, BatchStatus};
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ChronicleUdmConfig>();
+    }
+
+    fn udm_event() -> LogEvent {
+        let mut log = LogEvent::default();
+        log.insert(EVENT_TYPE_FIELD, "NETWORK_DNS");
+        log.insert(EVENT_TIMESTAMP_FIELD, "2022-06-01T12:00:00Z");
+        log
+    }
+
+    #[test]
+    fn validates_required_fields() {
+        assert_eq!(validate_udm_event(&udm_event()), Ok(()));
+
+        let mut log = udm_event();
+        log.insert(EVENT_TIMESTAMP_FIELD, chrono::Utc::now());
+        assert_eq!(validate_udm_event(&log), Ok(()));
+
+        let mut log = udm_event();
+        log.remove(EVENT_TYPE_FIELD);
+        assert_eq!(
+            validate_udm_event(&log),
+            Err(UdmValidationError::MissingField {
+                field: EVENT_TYPE_FIELD
+            })
+        );
+
+        let mut log = udm_event();
+        log.insert(EVENT_TIMESTAMP_FIELD, "yesterday");
+        assert_eq!(
+            validate_udm_event(&log),
+            Err(UdmValidationError::InvalidField {
+                field: EVENT_TIMESTAMP_FIELD,
+                expected: "an RFC 3339 timestamp",
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_events() {
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let event = Event::from(LogEvent::default()).with_batch_notifier(&batch);
+        drop(batch);
+
+        assert!(validate_event(event).is_none());
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+    }
+
+    #[test]
+    fn encodes_batch_body() {
+        let encoder = ChronicleUdmEncoder {
+            customer_id: "customer_id".to_string(),
+            transformer: Default::default(),
+        };
+
+        let mut body = Vec::new();
+        encoder
+            .encode_input(vec![Event::from(udm_event())], &mut body)
+            .unwrap();
+
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "customer_id": "customer_id",
+                "events": [{
+                    "metadata": {
+                        "event_type": "NETWORK_DNS",
+                        "event_timestamp": "2022-06-01T12:00:00Z",
+                    }
+                }],
+            })
+        );
+    }
+}
@@ -4,39 +4,39 @@
 use bytes::{Bytes, BytesMut};
 use futures_util::{future::BoxFuture, task::Poll};
 use goauth::scopes::Scope;
-use http::{header::HeaderValue, Request, Uri};
+use http::{header::HeaderValue, Request, StatusCode, Uri};
 use hyper::Body;
 use indoc::indoc;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use snafu::Snafu;
-use std::io;
+use snafu::{ResultExt, Snafu};
+use std::{collections::HashMap, io, sync::Arc, time::Duration};
 use tokio_util::codec::Encoder as _;
 use tower::{Service, ServiceBuilder};
 use vector_core::{
     config::{AcknowledgementsConfig, Input},
-    event::{Event, EventFinalizers, Finalizable},
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
+    internal_event::{BytesSent, EventsSent},
+    partition::Partitioner,
     sink::VectorSink,
+    stream::DriverResponse,
 };
 
 use crate::{
     codecs::{self, EncodingConfig},
     config::{log_schema, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     gcp::{GcpAuthConfig, GcpAuthenticator},
-    http::{HttpClient, HttpError},
+    http::{CallRequestSnafu, HttpClient, HttpError},
+    internal_events::{ChronicleFallbackLogType, ChronicleLogTypeSent, TemplateRenderingError},
     sinks::{
-        gcs_common::{
-            config::{healthcheck_response, GcsRetryLogic},
-            service::GcsResponse,
-            sink::GcsSink,
-        },
+        gcp::chronicle_forwarder::{ForwarderStats, ForwarderStatsConfig},
+        gcs_common::{config::healthcheck_response, sink::GcsSink},
         util::{
             encoding::{as_tracked_write, Encoder},
             metadata::{RequestMetadata, RequestMetadataBuilder},
-            partitioner::KeyPartitioner,
             request_builder::EncodeResult,
-            BatchConfig, BulkSizeBasedDefaultBatchSettings, Compression, RequestBuilder,
-            TowerRequestConfig,
+            retries::{RetryAction, RetryLogic},
+            BatchConfig, Compression, RequestBuilder, SinkBatchSettings, TowerRequestConfig,
         },
         Healthcheck,
     },
@@ -46,6 +46,17 @@ use crate::{
 
 const NAME: &str = "gcp_chronicle_unstructured";
 
+/// Chronicle rejects requests containing too many entries regardless of their size, so batches are
+/// capped by event count in addition to their size.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ChronicleDefaultBatchSettings;
+
+impl SinkBatchSettings for ChronicleDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(1000);
+    const MAX_BYTES: Option<usize> = Some(10_000_000);
+    const TIMEOUT_SECS: f64 = 300.0;
+}
+
 #[derive(Debug, Snafu)]
 #[snafu(visibility(pub))]
 pub enum GcsHealthcheckError {
@@ -73,22 +84,38 @@ impl Region {
             Region::Asia => "https://asia-southeast1-malachiteingestion-pa.googleapis.com",
         }
     }
+
+    /// The forwarder management API is served from a different host than the ingestion API.
+    const fn forwarder_endpoint(self) -> &'static str {
+        match self {
+            Region::Eu => "https://europe-backstory.googleapis.com",
+            Region::Us => "https://backstory.googleapis.com",
+            Region::Asia => "https://asia-southeast1-backstory.googleapis.com",
+        }
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct ChronicleUnstructuredConfig {
     pub endpoint: Option<String>,
     pub region: Option<Region>,
-    pub customer_id: String,
+    pub customer_id: Template,
     #[serde(flatten)]
     pub auth: GcpAuthConfig,
     #[serde(default)]
-    pub batch: BatchConfig<BulkSizeBasedDefaultBatchSettings>,
+    pub batch: BatchConfig<ChronicleDefaultBatchSettings>,
     pub encoding: EncodingConfig,
     #[serde(default)]
+    pub compression: Compression,
+    #[serde(default)]
     pub request: TowerRequestConfig,
     pub tls: Option<TlsConfig>,
     pub log_type: Template,
+    pub fallback_log_type: Option<String>,
+    pub namespace: Option<Template>,
+    #[serde(default)]
+    pub labels: HashMap<String, Template>,
+    pub forwarder_stats: Option<ForwarderStatsConfig>,
     #[serde(
         default,
         deserialize_with = "crate::serde::bool_or_struct",
@@ -154,7 +181,25 @@ impl SinkConfig for ChronicleUnstructuredConfig {
         let healthcheck_endpoint = self.create_endpoint("v2/logtypes")?;
 
         let healthcheck = build_healthcheck(client.clone(), &healthcheck_endpoint, creds.clone())?;
-        let sink = self.build_sink(client, endpoint, creds)?;
+
+        let mut service = ChronicleService::new(client.clone(), endpoint, creds);
+        if let Some(forwarder_stats) = &self.forwarder_stats {
+            // The forwarder API is not covered by the ingestion scope.
+            let forwarder_creds = self.auth.build(Scope::CloudPlatform).await?;
+            let forwarder_endpoint = match (&self.endpoint, self.region) {
+                (Some(endpoint), None) => endpoint.trim_end_matches('/'),
+                (None, Some(region)) => region.forwarder_endpoint(),
+                (Some(_), Some(_)) => return Err(ChronicleError::BothRegionAndEndpoint.into()),
+                (None, None) => return Err(ChronicleError::RegionOrEndpoint.into()),
+            };
+
+            let (heartbeat, stats) =
+                forwarder_stats.build(client, forwarder_endpoint, forwarder_creds)?;
+            tokio::spawn(heartbeat.run());
+            service = service.with_forwarder_stats(stats);
+        }
+
+        let sink = self.build_sink(service)?;
 
         Ok((sink, healthcheck))
     }
@@ -173,12 +218,7 @@ impl SinkConfig for ChronicleUnstructuredConfig {
 }
 
 impl ChronicleUnstructuredConfig {
-    fn build_sink(
-        &self,
-        client: HttpClient,
-        base_url: String,
-        creds: GcpAuthenticator,
-    ) -> crate::Result<VectorSink> {
+    fn build_sink(&self, service: ChronicleService) -> crate::Result<VectorSink> {
         use crate::sinks::util::service::ServiceBuilderExt;
 
         let request = self.request.unwrap_with(&TowerRequestConfig {
@@ -191,8 +231,8 @@ impl ChronicleUnstructuredConfig {
         let partitioner = self.key_partitioner()?;
 
         let svc = ServiceBuilder::new()
-            .settings(request, GcsRetryLogic)
-            .service(ChronicleService::new(client, base_url, creds));
+            .settings(request, ChronicleRetryLogic)
+            .service(service);
 
         let request_settings = RequestSettings::new(self)?;
 
@@ -201,29 +241,110 @@ impl ChronicleUnstructuredConfig {
         Ok(VectorSink::from_event_streamsink(sink))
     }
 
-    fn key_partitioner(&self) -> crate::Result<KeyPartitioner> {
-        Ok(KeyPartitioner::new(self.log_type.clone()))
+    fn key_partitioner(&self) -> crate::Result<ChroniclePartitioner> {
+        Ok(ChroniclePartitioner {
+            log_type: self.log_type.clone(),
+            fallback_log_type: self.fallback_log_type.clone(),
+            customer_id: self.customer_id.clone(),
+        })
     }
 
     fn create_endpoint(&self, path: &str) -> Result<String, ChronicleError> {
-        Ok(format!(
-            "{}/{}",
-            match (&self.endpoint, self.region) {
-                (Some(endpoint), None) => endpoint.trim_end_matches('/'),
-                (None, Some(region)) => region.endpoint(),
-                (Some(_), Some(_)) => return Err(ChronicleError::BothRegionAndEndpoint),
-                (None, None) => return Err(ChronicleError::RegionOrEndpoint),
-            },
-            path
-        ))
+        create_endpoint(self.endpoint.as_deref(), self.region, path)
+    }
+}
+
+/// Builds the URL for the given API path from either an explicit endpoint or a region.
+pub(super) fn create_endpoint(
+    endpoint: Option<&str>,
+    region: Option<Region>,
+    path: &str,
+) -> Result<String, ChronicleError> {
+    Ok(format!(
+        "{}/{}",
+        match (endpoint, region) {
+            (Some(endpoint), None) => endpoint.trim_end_matches('/'),
+            (None, Some(region)) => region.endpoint(),
+            (Some(_), Some(_)) => return Err(ChronicleError::BothRegionAndEndpoint),
+            (None, None) => return Err(ChronicleError::RegionOrEndpoint),
+        },
+        path
+    ))
+}
+
+/// Events are batched per log type and customer, as each request can only target a single
+/// combination of the two.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ChroniclePartitionKey {
+    pub log_type: String,
+    pub customer_id: String,
+}
+
+/// Partitions events based on the rendered `log_type` and `customer_id` templates.
+pub struct ChroniclePartitioner {
+    log_type: Template,
+    fallback_log_type: Option<String>,
+    customer_id: Template,
+}
+
+impl ChroniclePartitioner {
+    fn render_log_type(&self, item: &Event) -> Option<String> {
+        match (self.log_type.render_string(item), &self.fallback_log_type) {
+            (Ok(log_type), _) => Some(log_type),
+            (Err(error), Some(fallback_log_type)) => {
+                emit!(ChronicleFallbackLogType {
+                    error,
+                    fallback_log_type,
+                });
+                Some(fallback_log_type.clone())
+            }
+            (Err(error), None) => {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("log_type"),
+                    drop_event: true,
+                });
+                None
+            }
+        }
+    }
+
+    fn render(&self, template: &Template, field: &'static str, item: &Event) -> Option<String> {
+        template
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some(field),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+}
+
+impl Partitioner for ChroniclePartitioner {
+    type Item = Event;
+    type Key = Option<ChroniclePartitionKey>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        let log_type = self.render_log_type(item)?;
+        let customer_id = self.render(&self.customer_id, "customer_id", item)?;
+        Some(ChroniclePartitionKey {
+            log_type,
+            customer_id,
+        })
     }
 }
 
 #[derive(Clone, Debug)]
 pub struct ChronicleRequest {
     pub body: Bytes,
+    pub compression: Compression,
     pub finalizers: EventFinalizers,
     pub metadata: RequestMetadata,
+    /// The rendered log type of the partition the request was built from, if any.
+    pub log_type: Option<String>,
 }
 
 impl Finalizable for ChronicleRequest {
@@ -234,15 +355,52 @@ impl Finalizable for ChronicleRequest {
 
 #[derive(Clone, Debug)]
 struct ChronicleEncoder {
-    customer_id: String,
+    namespace: Option<Template>,
+    labels: Vec<(String, Template)>,
     encoder: codecs::Encoder<()>,
     transformer: codecs::Transformer,
 }
 
-impl Encoder<(String, Vec<Event>)> for ChronicleEncoder {
+impl ChronicleEncoder {
+    fn render_namespace(&self, event: &Event) -> Option<String> {
+        self.namespace.as_ref().and_then(|namespace| {
+            namespace
+                .render_string(event)
+                .map_err(|error| {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("namespace"),
+                        drop_event: false,
+                    })
+                })
+                .ok()
+        })
+    }
+
+    fn render_labels(&self, event: &Event) -> Vec<serde_json::Value> {
+        self.labels
+            .iter()
+            .filter_map(|(key, value)| {
+                value
+                    .render_string(event)
+                    .map_err(|error| {
+                        emit!(TemplateRenderingError {
+                            error,
+                            field: Some(key),
+                            drop_event: false,
+                        })
+                    })
+                    .ok()
+                    .map(|value| json!({ "key": key, "value": value }))
+            })
+            .collect()
+    }
+}
+
+impl Encoder<(ChroniclePartitionKey, Vec<Event>)> for ChronicleEncoder {
     fn encode_input(
         &self,
-        input: (String, Vec<Event>),
+        input: (ChroniclePartitionKey, Vec<Event>),
         writer: &mut dyn io::Write,
     ) -> io::Result<usize> {
         let (partition_key, events) = input;
@@ -255,6 +413,8 @@ impl Encoder<(String, Vec<Event>)> for ChronicleEncoder {
                     .get(log_schema().timestamp_key())
                     .and_then(|ts| ts.as_timestamp())
                     .cloned();
+                let namespace = self.render_namespace(&event);
+                let labels = self.render_labels(&event);
                 let mut bytes = BytesMut::new();
                 self.transformer.transform(&mut event);
                 encoder.encode(event, &mut bytes).ok()?;
@@ -271,13 +431,27 @@ impl Encoder<(String, Vec<Event>)> for ChronicleEncoder {
                     );
                 }
 
+                if let Some(namespace) = namespace {
+                    value
+                        .as_object_mut()
+                        .unwrap()
+                        .insert("namespace".to_string(), namespace.into());
+                }
+
+                if !labels.is_empty() {
+                    value
+                        .as_object_mut()
+                        .unwrap()
+                        .insert("labels".to_string(), labels.into());
+                }
+
                 Some(value)
             })
             .collect::<Vec<_>>();
 
         let json = json!({
-            "customer_id": self.customer_id,
-            "log_type": partition_key,
+            "customer_id": partition_key.customer_id,
+            "log_type": partition_key.log_type,
             "entries": events,
         });
 
@@ -296,6 +470,7 @@ impl Encoder<(String, Vec<Event>)> for ChronicleEncoder {
 #[derive(Clone, Debug)]
 struct RequestSettings {
     encoder: ChronicleEncoder,
+    compression: Compression,
 }
 
 struct ChronicleRequestPayload {
@@ -314,28 +489,32 @@ impl AsRef<[u8]> for ChronicleRequestPayload {
     }
 }
 
-impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
-    type Metadata = (EventFinalizers, RequestMetadataBuilder);
-    type Events = (String, Vec<Event>);
+impl RequestBuilder<(ChroniclePartitionKey, Vec<Event>)> for RequestSettings {
+    type Metadata = (EventFinalizers, RequestMetadataBuilder, String);
+    type Events = (ChroniclePartitionKey, Vec<Event>);
     type Encoder = ChronicleEncoder;
     type Payload = ChronicleRequestPayload;
     type Request = ChronicleRequest;
     type Error = io::Error;
 
     fn compression(&self) -> Compression {
-        Compression::None
+        self.compression
     }
 
     fn encoder(&self) -> &Self::Encoder {
         &self.encoder
     }
 
-    fn split_input(&self, input: (String, Vec<Event>)) -> (Self::Metadata, Self::Events) {
+    fn split_input(
+        &self,
+        input: (ChroniclePartitionKey, Vec<Event>),
+    ) -> (Self::Metadata, Self::Events) {
         let (partition_key, mut events) = input;
         let finalizers = events.take_finalizers();
 
         let metadata = RequestMetadata::builder(&events);
-        ((finalizers, metadata), (partition_key, events))
+        let log_type = partition_key.log_type.clone();
+        ((finalizers, metadata, log_type), (partition_key, events))
     }
 
     fn build_request(
@@ -343,15 +522,17 @@ impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
         metadata: Self::Metadata,
         payload: EncodeResult<Self::Payload>,
     ) -> Self::Request {
-        let (finalizers, metadata_builder) = metadata;
+        let (finalizers, metadata_builder, log_type) = metadata;
 
         let metadata = metadata_builder.build(&payload);
         let body = payload.into_payload().bytes;
 
         ChronicleRequest {
             body,
+            compression: self.compression,
             finalizers,
             metadata,
+            log_type: Some(log_type),
         }
     }
 }
@@ -361,12 +542,22 @@ impl RequestSettings {
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.config().build()?;
         let encoder = crate::codecs::Encoder::<()>::new(serializer);
+        let mut labels = config
+            .labels
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect::<Vec<_>>();
+        labels.sort_by(|(a, _), (b, _)| a.cmp(b));
         let encoder = ChronicleEncoder {
-            customer_id: config.customer_id.clone(),
+            namespace: config.namespace.clone(),
+            labels,
             encoder,
             transformer,
         };
-        Ok(Self { encoder })
+        Ok(Self {
+            encoder,
+            compression: config.compression,
+        })
     }
 }
 
@@ -375,6 +566,7 @@ pub struct ChronicleService {
     client: HttpClient,
     base_url: String,
     creds: GcpAuthenticator,
+    forwarder_stats: Option<Arc<ForwarderStats>>,
 }
 
 impl ChronicleService {
@@ -383,12 +575,19 @@ impl ChronicleService {
             client,
             base_url,
             creds,
+            forwarder_stats: None,
         }
     }
+
+    /// Records the outcome of each request, to be reported to Chronicle's forwarder API.
+    pub fn with_forwarder_stats(mut self, stats: Arc<ForwarderStats>) -> Self {
+        self.forwarder_stats = Some(stats);
+        self
+    }
 }
 
 impl Service<ChronicleRequest> for ChronicleService {
-    type Response = GcsResponse;
+    type Response = ChronicleResponse;
     type Error = HttpError;
     type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
 
@@ -407,22 +606,506 @@ impl Service<ChronicleRequest> for ChronicleService {
             "content-length",
             HeaderValue::from_str(&request.body.len().to_string()).unwrap(),
         );
+        if let Some(ce) = request.compression.content_encoding() {
+            headers.insert("content-encoding", HeaderValue::from_static(ce));
+        }
 
         let mut http_request = builder.body(Body::from(request.body)).unwrap();
         self.creds.apply(&mut http_request);
 
         let mut client = self.client.clone();
+        let creds = self.creds.clone();
+        let forwarder_stats = self.forwarder_stats.clone();
         Box::pin(async move {
-            let result = client.call(http_request).await;
-            result.map(|inner| GcsResponse {
-                inner,
+            let response = client.call(http_request).await;
+            if let Ok(response) = &response {
+                if response.status() == StatusCode::UNAUTHORIZED {
+                    creds.force_refresh();
+                }
+            }
+            if let (Ok(response), Some(log_type)) = (&response, &request.log_type) {
+                if response.status().is_success() {
+                    emit!(ChronicleLogTypeSent {
+                        log_type,
+                        count: request.metadata.event_count(),
+                        byte_size: request.metadata.request_wire_size(),
+                    });
+                }
+            }
+            if let Some(stats) = &forwarder_stats {
+                match &response {
+                    Ok(response) if response.status().is_success() => stats.record_sent(
+                        request.metadata.event_count(),
+                        request.metadata.request_wire_size(),
+                    ),
+                    _ => stats.record_failed(),
+                }
+            }
+            let response = response?;
+
+            // The body is read up front so the retry logic can inspect any error details.
+            let (parts, body) = response.into_parts();
+            let body = hyper::body::to_bytes(body)
+                .await
+                .context(CallRequestSnafu)?;
+
+            Ok(ChronicleResponse {
+                inner: http::Response::from_parts(parts, body),
                 protocol: "http",
                 metadata: request.metadata,
+                log_type: request.log_type,
             })
         })
     }
 }
 
+#[derive(Debug)]
+pub struct ChronicleResponse {
+    pub inner: http::Response<Bytes>,
+    pub protocol: &'static str,
+    pub metadata: RequestMetadata,
+    pub log_type: Option<String>,
+}
+
+impl DriverResponse for ChronicleResponse {
+    fn event_status(&self) -> EventStatus {
+        let status = self.inner.status();
+        if status.is_success() {
+            EventStatus::Delivered
+        } else if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+            EventStatus::Errored
+        } else {
+            EventStatus::Rejected
+        }
+    }
+
+    fn events_sent(&self) -> EventsSent {
+        EventsSent {
+            count: self.metadata.event_count(),
+            byte_size: self.metadata.events_byte_size(),
+            output: None,
+        }
+    }
+
+    fn bytes_sent(&self) -> Option<BytesSent> {
+        Some(BytesSent {
+            byte_size: self.metadata.request_encoded_size(),
+            protocol: self.protocol,
+        })
+    }
+}
+
+/// The error body returned by Google APIs.
+/// See https://cloud.google.com/apis/design/errors#http_mapping
+#[derive(Debug, Deserialize)]
+struct ChronicleErrorResponse {
+    error: ChronicleErrorStatus,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChronicleErrorStatus {
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    details: Vec<serde_json::Value>,
+}
+
+impl ChronicleErrorStatus {
+    /// Whether the batch may succeed when sent again. Quota exhaustion and transient failures can
+    /// be retried, while schema or permission errors mean the batch will always be rejected.
+    fn is_retriable(&self) -> Option<bool> {
+        match self.status.as_str() {
+            "RESOURCE_EXHAUSTED" | "UNAVAILABLE" | "ABORTED" | "DEADLINE_EXCEEDED" | "INTERNAL" => {
+                Some(true)
+            }
+            "INVALID_ARGUMENT"
+            | "FAILED_PRECONDITION"
+            | "OUT_OF_RANGE"
+            | "PERMISSION_DENIED"
+            | "UNAUTHENTICATED"
+            | "NOT_FOUND"
+            | "ALREADY_EXISTS"
+            | "UNIMPLEMENTED" => Some(false),
+            _ => None,
+        }
+    }
+
+    /// The delay requested by a `google.rpc.RetryInfo` error detail, if any.
+    fn retry_delay(&self) -> Option<Duration> {
+        self.details
+            .iter()
+            .filter(|detail| {
+                detail["@type"]
+                    .as_str()
+                    .map_or(false, |kind| kind.ends_with("google.rpc.RetryInfo"))
+            })
+            .find_map(|detail| detail["retryDelay"].as_str())
+            .and_then(|delay| delay.strip_suffix('s'))
+            .and_then(|secs| secs.parse::<f64>().ok())
+            .filter(|secs| secs.is_finite() && *secs >= 0.0)
+            .map(Duration::from_secs_f64)
+    }
+}
+
+/// Parses a `Retry-After` header, which is either a number of seconds or an HTTP date.
+fn retry_after(response: &http::Response<Bytes>) -> Option<Duration> {
+    let value = response
+        .headers()
+        .get(http::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim();
+
+    match value.parse::<u64>() {
+        Ok(secs) => Some(Duration::from_secs(secs)),
+        Err(_) => chrono::DateTime::parse_from_rfc2822(value)
+            .ok()
+            .map(|date| date.with_timezone(&chrono::Utc) - chrono::Utc::now())
+            .map(|delay| delay.to_std().unwrap_or_default()),
+    }
+}
+
+#[derive(Clone)]
+pub struct ChronicleRetryLogic;
+
+impl RetryLogic for ChronicleRetryLogic {
+    type Error = HttpError;
+    type Response = ChronicleResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        error.is_retriable()
+    }
+
+    fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
+        let status = response.inner.status();
+        if status.is_success() {
+            return RetryAction::Successful;
+        }
+
+        let error = serde_json::from_slice::<ChronicleErrorResponse>(response.inner.body())
+            .ok()
+            .map(|response| response.error);
+
+        let reason = match &error {
+            Some(error) if !error.message.is_empty() => {
+                format!("response status: {}, error: {}", status, error.message)
+            }
+            _ => format!("response status: {}", status),
+        };
+
+        let retriable = error
+            .as_ref()
+            .and_then(ChronicleErrorStatus::is_retriable)
+            .unwrap_or_else(|| {
+                status == StatusCode::TOO_MANY_REQUESTS
+                    || (status.is_server_error() && status != StatusCode::NOT_IMPLEMENTED)
+            });
+
+        if !retriable {
+            return RetryAction::DontRetry(reason.into());
+        }
+
+        match retry_after(&response.inner)
+            .or_else(|| error.as_ref().and_then(ChronicleErrorStatus::retry_delay))
+        {
+            Some(delay) => RetryAction::RetryAfter(reason.into(), delay),
+            None => RetryAction::Retry(reason.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::GzDecoder;
+    use futures::StreamExt;
+    use vector_core::event::{BatchNotifier, BatchStatus, LogEvent};
+
+    use super::*;
+    use crate::{
+        sinks::util::test::build_test_server,
+        test_util::{next_addr, random_events_with_stream},
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ChronicleUnstructuredConfig>();
+    }
+
+    fn build_config(extra: &str) -> ChronicleUnstructuredConfig {
+        toml::from_str(&format!(
+            indoc! {r#"
+                region = "us"
+                customer_id = "customer_id"
+                log_type = "{{{{ log_type }}}}"
+                encoding.codec = "text"
+                {}
+            "#},
+            extra
+        ))
+        .unwrap()
+    }
+
+    fn build_payload(config: &ChronicleUnstructuredConfig, events: Vec<Event>) -> Bytes {
+        let settings = RequestSettings::new(config).unwrap();
+        let key = ChroniclePartitionKey {
+            log_type: "WINDOWS_DNS".to_string(),
+            customer_id: "customer_id".to_string(),
+        };
+        let (metadata, events) = settings.split_input((key, events));
+        let payload = settings.encode_events(events).unwrap();
+        settings.build_request(metadata, payload).body
+    }
+
+    #[test]
+    fn request_carries_partition_log_type() {
+        let settings = RequestSettings::new(&build_config("")).unwrap();
+        let key = ChroniclePartitionKey {
+            log_type: "WINDOWS_DNS".to_string(),
+            customer_id: "customer_id".to_string(),
+        };
+        let events = vec![Event::from(LogEvent::from("hello world"))];
+        let (metadata, events) = settings.split_input((key, events));
+        let payload = settings.encode_events(events).unwrap();
+        let request = settings.build_request(metadata, payload);
+
+        assert_eq!(request.log_type.as_deref(), Some("WINDOWS_DNS"));
+        assert_eq!(request.metadata.event_count(), 1);
+    }
+
+    #[test]
+    fn compresses_request_body() {
+        let config = build_config(r#"compression = "gzip""#);
+        assert_eq!(config.compression, Compression::gzip_default());
+
+        let event = Event::from(LogEvent::from("hello world"));
+        let body = build_payload(&config, vec![event]);
+
+        let mut decompressed = String::new();
+        GzDecoder::new(body.as_ref())
+            .read_to_string(&mut decompressed)
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_str(&decompressed).unwrap();
+        assert_eq!(json["entries"][0]["log_text"], "hello world");
+    }
+
+    #[test]
+    fn renders_namespace_and_labels() {
+        let config = build_config(indoc! {r#"
+            namespace = "{{ env }}"
+            labels.team = "{{ team }}"
+            labels.source = "vector"
+        "#});
+
+        let mut log = LogEvent::from("hello world");
+        log.insert("env", "production");
+        log.insert("team", "security");
+        let body = build_payload(&config, vec![Event::from(log)]);
+
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let entry = &json["entries"][0];
+        assert_eq!(entry["namespace"], "production");
+        assert_eq!(
+            entry["labels"],
+            json!([
+                { "key": "source", "value": "vector" },
+                { "key": "team", "value": "security" },
+            ])
+        );
+    }
+
+    #[test]
+    fn batches_are_capped_by_event_count() {
+        let settings = build_config("").batch.into_batcher_settings().unwrap();
+        assert_eq!(settings.item_limit, 1000);
+        assert_eq!(settings.size_limit, 10_000_000);
+
+        let settings = build_config("batch.max_events = 10")
+            .batch
+            .into_batcher_settings()
+            .unwrap();
+        assert_eq!(settings.item_limit, 10);
+    }
+
+    #[tokio::test]
+    async fn splits_partitions_exceeding_max_events() {
+        let addr = next_addr();
+        let config: ChronicleUnstructuredConfig = toml::from_str(&format!(
+            indoc! {r#"
+                endpoint = "http://{}"
+                customer_id = "customer_id"
+                log_type = "WINDOWS_DNS"
+                encoding.codec = "text"
+                skip_authentication = true
+                batch.max_events = 10
+                batch.timeout_secs = 1
+            "#},
+            addr
+        ))
+        .unwrap();
+        let (sink, _healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (rx, trigger, server) = build_test_server(addr);
+        tokio::spawn(server);
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (_, events) = random_events_with_stream(100, 25, Some(batch));
+        sink.run(events).await.unwrap();
+        drop(trigger);
+
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        let mut entries = rx
+            .map(|(_, body)| {
+                let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                json["entries"].as_array().unwrap().len()
+            })
+            .collect::<Vec<_>>()
+            .await;
+        entries.sort_unstable();
+        assert_eq!(entries, vec![5, 10, 10]);
+    }
+
+    #[test]
+    fn partitions_by_log_type_and_customer_id() {
+        let config = build_config("");
+        let config = ChronicleUnstructuredConfig {
+            customer_id: Template::try_from("{{ tenant }}").unwrap(),
+            ..config
+        };
+        let partitioner = config.key_partitioner().unwrap();
+
+        let mut log = LogEvent::from("hello world");
+        log.insert("log_type", "WINDOWS_DNS");
+        log.insert("tenant", "c8c65bfa-5f2c-42d4-9189-64bb7b939f2c");
+        assert_eq!(
+            partitioner.partition(&Event::from(log.clone())),
+            Some(ChroniclePartitionKey {
+                log_type: "WINDOWS_DNS".to_string(),
+                customer_id: "c8c65bfa-5f2c-42d4-9189-64bb7b939f2c".to_string(),
+            })
+        );
+
+        log.remove("tenant");
+        assert_eq!(partitioner.partition(&Event::from(log)), None);
+    }
+
+    fn response(status: u16, body: &str) -> ChronicleResponse {
+        response_with_headers(status, body, &[])
+    }
+
+    fn response_with_headers(
+        status: u16,
+        body: &str,
+        headers: &[(&str, &str)],
+    ) -> ChronicleResponse {
+        let mut builder = http::Response::builder().status(status);
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        ChronicleResponse {
+            inner: builder
+                .body(Bytes::copy_from_slice(body.as_bytes()))
+                .unwrap(),
+            protocol: "http",
+            metadata: RequestMetadata::builder(&Vec::<Event>::new())
+                .with_request_size(std::num::NonZeroUsize::new(1).unwrap()),
+            log_type: Some("WINDOWS_DNS".to_string()),
+        }
+    }
+
+    #[test]
+    fn retry_logic_handles_quota_errors() {
+        let logic = ChronicleRetryLogic;
+
+        assert!(logic
+            .should_retry_response(&response(200, "{}"))
+            .is_successful());
+
+        let quota = r#"{"error": {"code": 429, "message": "Quota exceeded", "status": "RESOURCE_EXHAUSTED"}}"#;
+        assert!(logic
+            .should_retry_response(&response(429, quota))
+            .is_retryable());
+
+        match logic.should_retry_response(&response_with_headers(
+            429,
+            quota,
+            &[("retry-after", "30")],
+        )) {
+            RetryAction::RetryAfter(_, delay) => assert_eq!(delay, Duration::from_secs(30)),
+            _ => panic!("expected a delayed retry"),
+        }
+
+        let retry_info = r#"{"error": {"code": 429, "status": "RESOURCE_EXHAUSTED", "details": [
+            {"@type": "type.googleapis.com/google.rpc.RetryInfo", "retryDelay": "1.5s"}
+        ]}}"#;
+        match logic.should_retry_response(&response(429, retry_info)) {
+            RetryAction::RetryAfter(_, delay) => assert_eq!(delay, Duration::from_millis(1500)),
+            _ => panic!("expected a delayed retry"),
+        }
+    }
+
+    #[test]
+    fn retry_logic_rejects_invalid_batches() {
+        let logic = ChronicleRetryLogic;
+
+        let invalid = r#"{"error": {"code": 400, "message": "invalid log type", "status": "INVALID_ARGUMENT"}}"#;
+        match logic.should_retry_response(&response(400, invalid)) {
+            RetryAction::DontRetry(reason) => assert!(reason.contains("invalid log type")),
+            _ => panic!("expected the batch to be rejected"),
+        }
+
+        // Some quota errors are reported with a 400 status code.
+        let quota = r#"{"error": {"code": 400, "status": "RESOURCE_EXHAUSTED"}}"#;
+        assert!(logic
+            .should_retry_response(&response(400, quota))
+            .is_retryable());
+
+        assert!(logic
+            .should_retry_response(&response(503, "unavailable"))
+            .is_retryable());
+        assert!(logic
+            .should_retry_response(&response(501, "not implemented"))
+            .is_not_retryable());
+    }
+
+    #[test]
+    fn falls_back_to_default_log_type() {
+        let partitioner = build_config("").key_partitioner().unwrap();
+        let event = Event::from(LogEvent::from("hello world"));
+        assert_eq!(partitioner.partition(&event), None);
+
+        let partitioner = build_config(r#"fallback_log_type = "CATCH_ALL""#)
+            .key_partitioner()
+            .unwrap();
+        assert_eq!(
+            partitioner.partition(&event),
+            Some(ChroniclePartitionKey {
+                log_type: "CATCH_ALL".to_string(),
+                customer_id: "customer_id".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn skips_unrenderable_namespace_and_labels() {
+        let config = build_config(indoc! {r#"
+            namespace = "{{ env }}"
+            labels.team = "{{ team }}"
+        "#});
+
+        let body = build_payload(&config, vec![Event::from(LogEvent::from("hello world"))]);
+
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let entry = json["entries"][0].as_object().unwrap();
+        assert!(!entry.contains_key("namespace"));
+        assert!(!entry.contains_key("labels"));
+    }
+}
+
 #[cfg(all(test, feature = "chronicle-integration-tests"))]
 mod integration_tests {
     use reqwest::{Client, Method, Response};
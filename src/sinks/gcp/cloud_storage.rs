@@ -2,7 +2,6 @@ use std::{collections::HashMap, convert::TryFrom, io};
 
 use bytes::Bytes;
 use chrono::Utc;
-use codecs::encoding::Framer;
 use http::header::{HeaderName, HeaderValue};
 use indoc::indoc;
 use serde::{Deserialize, Serialize};
@@ -13,7 +12,7 @@ use uuid::Uuid;
 use vector_core::event::{EventFinalizers, Finalizable};
 
 use crate::{
-    codecs::{Encoder, EncodingConfigWithFraming, SinkType, Transformer},
+    codecs::EncodingConfigWithFraming,
     config::{
         AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext,
         SinkDescription,
@@ -25,13 +24,15 @@ use crate::{
     sinks::{
         gcs_common::{
             config::{
-                build_healthcheck, GcsPredefinedAcl, GcsRetryLogic, GcsStorageClass, BASE_URL,
+                build_healthcheck, GcsEncryptionConfig, GcsPredefinedAcl, GcsRetryLogic,
+                GcsStorageClass, BASE_URL,
             },
             service::{GcsRequest, GcsRequestSettings, GcsService},
             sink::GcsSink,
         },
         util::{
             batch::BatchConfig,
+            encoding::ObjectEncoder,
             metadata::{RequestMetadata, RequestMetadataBuilder},
             partitioner::KeyPartitioner,
             request_builder::EncodeResult,
@@ -60,6 +61,8 @@ pub struct GcsSinkConfig {
     acl: Option<GcsPredefinedAcl>,
     storage_class: Option<GcsStorageClass>,
     metadata: Option<HashMap<String, String>>,
+    #[serde(default)]
+    encryption: GcsEncryptionConfig,
     key_prefix: Option<String>,
     filename_time_format: Option<String>,
     filename_append_uuid: Option<bool>,
@@ -90,6 +93,7 @@ fn default_config(encoding: EncodingConfigWithFraming) -> GcsSinkConfig {
         acl: Default::default(),
         storage_class: Default::default(),
         metadata: Default::default(),
+        encryption: Default::default(),
         key_prefix: Default::default(),
         filename_time_format: Default::default(),
         filename_append_uuid: Default::default(),
@@ -200,14 +204,14 @@ struct RequestSettings {
     extension: String,
     time_format: String,
     append_uuid: bool,
-    encoder: (Transformer, Encoder<Framer>),
+    encoder: ObjectEncoder,
     compression: Compression,
 }
 
 impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
     type Metadata = (String, EventFinalizers, RequestMetadataBuilder);
     type Events = Vec<Event>;
-    type Encoder = (Transformer, Encoder<Framer>);
+    type Encoder = ObjectEncoder;
     type Payload = Bytes;
     type Request = GcsRequest;
     type Error = io::Error;
@@ -269,20 +273,18 @@ impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
 
 impl RequestSettings {
     fn new(config: &GcsSinkConfig) -> crate::Result<Self> {
-        let transformer = config.encoding.transformer();
-        let (framer, serializer) = config.encoding.build(SinkType::MessageBased)?;
-        let encoder = Encoder::<Framer>::new(framer, serializer);
+        let encoder = ObjectEncoder::build(&config.encoding)?;
+        let compression = encoder.compression(config.compression);
         let acl = config
             .acl
             .map(|acl| HeaderValue::from_str(&to_string(acl)).unwrap());
         let content_type = HeaderValue::from_str(encoder.content_type()).unwrap();
-        let content_encoding = config
-            .compression
+        let content_encoding = compression
             .content_encoding()
             .map(|ce| HeaderValue::from_str(&to_string(ce)).unwrap());
         let storage_class = config.storage_class.unwrap_or_default();
         let storage_class = HeaderValue::from_str(&to_string(storage_class)).unwrap();
-        let metadata = config
+        let mut metadata = config
             .metadata
             .as_ref()
             .map(|metadata| {
@@ -292,10 +294,11 @@ impl RequestSettings {
                     .collect::<Result<Vec<_>, _>>()
             })
             .unwrap_or_else(|| Ok(vec![]))?;
+        metadata.extend(config.encryption.headers()?);
         let extension = config
             .filename_extension
             .clone()
-            .unwrap_or_else(|| config.compression.extension().into());
+            .unwrap_or_else(|| encoder.extension(compression).into());
         let time_format = config
             .filename_time_format
             .clone()
@@ -310,8 +313,8 @@ impl RequestSettings {
             extension,
             time_format,
             append_uuid,
-            compression: config.compression,
-            encoder: (transformer, encoder),
+            compression,
+            encoder,
         })
     }
 }
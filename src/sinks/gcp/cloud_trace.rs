@@ -0,0 +1,491 @@
+//! This sink sends trace events to Google Cloud Trace.
+//! See https://cloud.google.com/trace/docs/reference/v2/rest/v2/projects.traces/batchWrite
+//! for more information.
+use std::collections::BTreeMap;
+
+use bytes::Bytes;
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures::{FutureExt, SinkExt};
+use http::{Request, Uri};
+use hyper::Body;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, value::RawValue};
+use snafu::Snafu;
+
+use crate::{
+    config::{AcknowledgementsConfig, Input, SinkConfig, SinkContext, SinkDescription},
+    event::{Event, Value},
+    gcp::{GcpAuthConfig, GcpAuthenticator, Scope},
+    http::HttpClient,
+    sinks::{
+        gcs_common::config::healthcheck_response,
+        util::{
+            http::{BatchedHttpSink, HttpEventEncoder, HttpSink},
+            BatchConfig, BoxedRawValue, JsonArrayBuffer, RealtimeSizeBasedDefaultBatchSettings,
+            TowerRequestConfig,
+        },
+        Healthcheck, VectorSink,
+    },
+    tls::{TlsConfig, TlsSettings},
+};
+
+#[derive(Debug, Snafu)]
+enum HealthcheckError {
+    #[snafu(display("Project not found"))]
+    NotFound,
+}
+
+// Limits of the Cloud Trace API: https://cloud.google.com/trace/docs/quotas#trace-limits
+const MAX_BATCH_PAYLOAD_SIZE: usize = 10_000_000;
+const MAX_ATTRIBUTES: usize = 32;
+const MAX_DISPLAY_NAME_BYTES: usize = 128;
+const MAX_ATTRIBUTE_KEY_BYTES: usize = 128;
+const MAX_ATTRIBUTE_VALUE_BYTES: usize = 256;
+
+/// Google's canonical code for errors which don't fit any other category.
+const STATUS_CODE_UNKNOWN: i32 = 2;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct CloudTraceConfig {
+    #[serde(skip, default = "default_endpoint")]
+    endpoint: String,
+
+    pub project_id: String,
+
+    #[serde(flatten)]
+    pub auth: GcpAuthConfig,
+
+    #[serde(default)]
+    pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    pub tls: Option<TlsConfig>,
+
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+fn default_endpoint() -> String {
+    "https://cloudtrace.googleapis.com".to_string()
+}
+
+#[derive(Clone, Debug)]
+struct CloudTraceSink {
+    project_id: String,
+    auth: GcpAuthenticator,
+    uri: Uri,
+}
+
+inventory::submit! {
+    SinkDescription::new::<CloudTraceConfig>("gcp_cloud_trace")
+}
+
+impl_generate_config_from_default!(CloudTraceConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "gcp_cloud_trace")]
+impl SinkConfig for CloudTraceConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let auth = self.auth.build(Scope::CloudPlatform).await?;
+
+        let batch = self
+            .batch
+            .validate()?
+            .limit_max_bytes(MAX_BATCH_PAYLOAD_SIZE)?
+            .into_batch_settings()?;
+        let request = self.request.unwrap_with(&TowerRequestConfig {
+            rate_limit_num: Some(1000),
+            rate_limit_duration_secs: Some(1),
+            ..Default::default()
+        });
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls_settings, cx.proxy())?;
+
+        let sink = CloudTraceSink {
+            project_id: self.project_id.clone(),
+            auth,
+            uri: format!(
+                "{}/v2/projects/{}/traces:batchWrite",
+                self.endpoint, self.project_id
+            )
+            .parse()?,
+        };
+
+        let healthcheck = healthcheck(client.clone(), sink.clone()).boxed();
+
+        let sink = BatchedHttpSink::new(
+            sink,
+            JsonArrayBuffer::new(batch.size),
+            request,
+            batch.timeout,
+            client,
+        )
+        .sink_map_err(|error| error!(message = "Fatal gcp_cloud_trace sink error.", %error));
+
+        Ok((VectorSink::from_event_sink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::trace()
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "gcp_cloud_trace"
+    }
+
+    fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
+        Some(&self.acknowledgements)
+    }
+}
+
+struct CloudTraceEventEncoder {
+    project_id: String,
+}
+
+impl HttpEventEncoder<serde_json::Value> for CloudTraceEventEncoder {
+    /// Encodes a trace as the array of its spans, as Cloud Trace doesn't group spans per trace.
+    fn encode_event(&mut self, event: Event) -> Option<serde_json::Value> {
+        let trace = event.into_trace();
+        let spans = match trace.get("spans") {
+            Some(Value::Array(spans)) => spans
+                .iter()
+                .filter_map(Value::as_object)
+                .filter_map(|span| self.convert_span(span))
+                .collect::<Vec<_>>(),
+            _ => vec![],
+        };
+
+        if spans.is_empty() {
+            warn!(
+                message = "Trace contains no valid spans, dropping it.",
+                internal_log_rate_secs = 10
+            );
+            return None;
+        }
+
+        Some(json!(spans))
+    }
+}
+
+impl CloudTraceEventEncoder {
+    fn convert_span(&self, span: &BTreeMap<String, Value>) -> Option<serde_json::Value> {
+        let integer = |key: &str| match span.get(key) {
+            Some(Value::Integer(value)) => Some(*value),
+            _ => None,
+        };
+        let string = |key: &str| span.get(key).map(Value::to_string_lossy);
+
+        // Both identifiers must be non-zero for Cloud Trace to accept the span.
+        let trace_id = integer("trace_id").filter(|id| *id != 0)? as u64;
+        let span_id = integer("span_id").filter(|id| *id != 0)? as u64;
+        let start = match span.get("start") {
+            Some(Value::Timestamp(start)) => *start,
+            _ => return None,
+        };
+        let end = start + chrono::Duration::nanoseconds(integer("duration").unwrap_or(0));
+
+        let trace_id = format!("{:032x}", trace_id);
+        let span_id = format!("{:016x}", span_id);
+
+        let meta = span
+            .get("meta")
+            .and_then(Value::as_object)
+            .map(|meta| {
+                meta.iter()
+                    .map(|(key, value)| (key.as_str(), value.to_string_lossy()))
+                    .collect::<BTreeMap<_, _>>()
+            })
+            .unwrap_or_default();
+
+        let mut attributes = serde_json::Map::new();
+        let mut dropped_attributes = 0;
+        let known = [
+            ("service.name", string("service")),
+            ("resource.name", string("resource")),
+            ("span.type", string("type")),
+        ];
+        let attribute_values = known
+            .into_iter()
+            .filter_map(|(key, value)| Some((key.to_string(), value?)))
+            .chain(meta.iter().map(|(key, value)| (key.to_string(), value.clone())));
+        for (key, value) in attribute_values {
+            if attributes.len() == MAX_ATTRIBUTES {
+                dropped_attributes += 1;
+                continue;
+            }
+            let (key, _) = truncate(&key, MAX_ATTRIBUTE_KEY_BYTES);
+            attributes.insert(
+                key.to_string(),
+                json!({ "stringValue": truncatable_string(&value, MAX_ATTRIBUTE_VALUE_BYTES) }),
+            );
+        }
+
+        let mut converted = json!({
+            "name": format!("projects/{}/traces/{}/spans/{}", self.project_id, trace_id, span_id),
+            "spanId": span_id,
+            "displayName": truncatable_string(
+                &string("name").unwrap_or_default(),
+                MAX_DISPLAY_NAME_BYTES,
+            ),
+            "startTime": format_time(start),
+            "endTime": format_time(end),
+            "attributes": {
+                "attributeMap": attributes,
+                "droppedAttributesCount": dropped_attributes,
+            },
+        });
+        let object = converted.as_object_mut().unwrap();
+
+        if let Some(parent_id) = integer("parent_id").filter(|id| *id != 0) {
+            object.insert(
+                "parentSpanId".into(),
+                format!("{:016x}", parent_id as u64).into(),
+            );
+        }
+
+        if let Some(kind) = meta.get("span.kind").and_then(|kind| span_kind(kind)) {
+            object.insert("spanKind".into(), kind.into());
+        }
+
+        if integer("error").unwrap_or(0) != 0 {
+            object.insert(
+                "status".into(),
+                json!({
+                    "code": STATUS_CODE_UNKNOWN,
+                    "message": meta.get("error.msg").cloned().unwrap_or_default(),
+                }),
+            );
+        }
+
+        Some(converted)
+    }
+}
+
+fn span_kind(kind: &str) -> Option<&'static str> {
+    match kind {
+        "server" => Some("SERVER"),
+        "client" => Some("CLIENT"),
+        "producer" => Some("PRODUCER"),
+        "consumer" => Some("CONSUMER"),
+        "internal" => Some("INTERNAL"),
+        _ => None,
+    }
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Nanos, true)
+}
+
+/// Truncates a string to at most `max_bytes`, on a character boundary, returning the truncated
+/// string and the number of bytes removed.
+fn truncate(value: &str, max_bytes: usize) -> (&str, usize) {
+    if value.len() <= max_bytes {
+        return (value, 0);
+    }
+    let mut end = max_bytes;
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    (&value[..end], value.len() - end)
+}
+
+fn truncatable_string(value: &str, max_bytes: usize) -> serde_json::Value {
+    let (value, truncated) = truncate(value, max_bytes);
+    json!({
+        "value": value,
+        "truncatedByteCount": truncated,
+    })
+}
+
+#[async_trait::async_trait]
+impl HttpSink for CloudTraceSink {
+    type Input = serde_json::Value;
+    type Output = Vec<BoxedRawValue>;
+    type Encoder = CloudTraceEventEncoder;
+
+    fn build_encoder(&self) -> Self::Encoder {
+        CloudTraceEventEncoder {
+            project_id: self.project_id.clone(),
+        }
+    }
+
+    async fn build_request(&self, events: Self::Output) -> crate::Result<Request<Bytes>> {
+        // Each event holds the array of spans of a trace, which are flattened into a single list.
+        let spans = events
+            .iter()
+            .map(|trace| serde_json::from_str::<Vec<Box<RawValue>>>(trace.get()))
+            .collect::<Result<Vec<_>, _>>()?
+            .concat();
+        let body = json!({ "spans": spans });
+
+        let body = crate::serde::json::to_bytes(&body).unwrap().freeze();
+
+        let mut request = Request::post(self.uri.clone())
+            .header("Content-Type", "application/json")
+            .body(body)
+            .unwrap();
+        self.auth.apply(&mut request);
+
+        Ok(request)
+    }
+}
+
+async fn healthcheck(client: HttpClient, sink: CloudTraceSink) -> crate::Result<()> {
+    let request = sink.build_request(vec![]).await?.map(Body::from);
+
+    let response = client.send(request).await?;
+    healthcheck_response(
+        response,
+        sink.auth.clone(),
+        HealthcheckError::NotFound.into(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use futures::{future::ready, stream};
+    use vector_core::event::TraceEvent;
+
+    use super::*;
+    use crate::{
+        config::{GenerateConfig, SinkConfig, SinkContext},
+        test_util::{
+            components::{run_and_assert_sink_compliance, SINK_TAGS},
+            http::{always_200_response, spawn_blackhole_http_server},
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<CloudTraceConfig>();
+    }
+
+    fn span(span_id: i64, parent_id: i64, error: i64) -> Value {
+        Value::from(BTreeMap::from([
+            ("trace_id".to_string(), Value::from(0x1234_i64)),
+            ("span_id".to_string(), Value::from(span_id)),
+            ("parent_id".to_string(), Value::from(parent_id)),
+            ("name".to_string(), Value::from("http.request")),
+            ("service".to_string(), Value::from("checkout")),
+            ("resource".to_string(), Value::from("GET /cart")),
+            (
+                "start".to_string(),
+                Value::from(Utc.ymd(2022, 8, 1).and_hms_nano(12, 0, 0, 0)),
+            ),
+            ("duration".to_string(), Value::from(1_500_000_i64)),
+            ("error".to_string(), Value::from(error)),
+            (
+                "meta".to_string(),
+                Value::from(BTreeMap::from([
+                    ("span.kind".to_string(), Value::from("server")),
+                    ("error.msg".to_string(), Value::from("boom")),
+                ])),
+            ),
+        ]))
+    }
+
+    fn trace(spans: Vec<Value>) -> Event {
+        let mut trace = TraceEvent::default();
+        trace.insert("spans", Value::Array(spans));
+        Event::Trace(trace)
+    }
+
+    fn encoder() -> CloudTraceEventEncoder {
+        CloudTraceEventEncoder {
+            project_id: "my-project".into(),
+        }
+    }
+
+    #[test]
+    fn encodes_spans() {
+        let spans = encoder()
+            .encode_event(trace(vec![span(1, 0, 0), span(2, 1, 1)]))
+            .unwrap();
+
+        let root = &spans[0];
+        assert_eq!(
+            root["name"],
+            "projects/my-project/traces/00000000000000000000000000001234/spans/0000000000000001"
+        );
+        assert_eq!(root["spanId"], "0000000000000001");
+        assert!(root.get("parentSpanId").is_none());
+        assert_eq!(root["displayName"]["value"], "http.request");
+        assert_eq!(root["startTime"], "2022-08-01T12:00:00.000000000Z");
+        assert_eq!(root["endTime"], "2022-08-01T12:00:00.001500000Z");
+        assert_eq!(root["spanKind"], "SERVER");
+        assert_eq!(
+            root["attributes"]["attributeMap"]["service.name"]["stringValue"]["value"],
+            "checkout"
+        );
+        assert!(root.get("status").is_none());
+
+        let child = &spans[1];
+        assert_eq!(child["parentSpanId"], "0000000000000001");
+        assert_eq!(child["status"]["code"], STATUS_CODE_UNKNOWN);
+        assert_eq!(child["status"]["message"], "boom");
+    }
+
+    #[test]
+    fn drops_traces_without_valid_spans() {
+        assert!(encoder().encode_event(trace(vec![span(0, 0, 0)])).is_none());
+        assert!(encoder().encode_event(trace(vec![])).is_none());
+    }
+
+    #[test]
+    fn truncates_on_char_boundaries() {
+        assert_eq!(truncate("hello", 10), ("hello", 0));
+        assert_eq!(truncate("hello", 3), ("hel", 2));
+        assert_eq!(truncate("héllo", 2), ("h", 5));
+    }
+
+    #[tokio::test]
+    async fn flattens_spans_into_a_single_request() {
+        let sink = CloudTraceSink {
+            project_id: "my-project".into(),
+            auth: GcpAuthenticator::None,
+            uri: "http://localhost/v2/projects/my-project/traces:batchWrite"
+                .parse()
+                .unwrap(),
+        };
+        let mut encoder = sink.build_encoder();
+        let events = vec![
+            encoder.encode_event(trace(vec![span(1, 0, 0), span(2, 1, 0)])),
+            encoder.encode_event(trace(vec![span(3, 0, 0)])),
+        ]
+        .into_iter()
+        .map(|spans| serde_json::value::to_raw_value(&spans.unwrap()).unwrap())
+        .collect();
+
+        let request = sink.build_request(events).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(request.body()).unwrap();
+        assert_eq!(body["spans"].as_array().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn component_spec_compliance() {
+        let mock_endpoint = spawn_blackhole_http_server(always_200_response).await;
+
+        let config = CloudTraceConfig::generate_config().to_string();
+        let mut config =
+            toml::from_str::<CloudTraceConfig>(&config).expect("config should be valid");
+
+        // If we don't override the credentials path/API key, it tries to directly call out to the Google Instance
+        // Metadata API, which we clearly don't have in unit tests. :)
+        config.auth.credentials_path = None;
+        config.auth.api_key = Some("fake".to_string());
+        config.endpoint = mock_endpoint.to_string();
+
+        let context = SinkContext::new_test();
+        let (sink, _healthcheck) = config.build(context).await.unwrap();
+
+        let event = trace(vec![span(1, 0, 0)]);
+        run_and_assert_sink_compliance(sink, stream::once(ready(event)), &SINK_TAGS).await;
+    }
+}
@@ -1,7 +1,11 @@
 use serde::{Deserialize, Serialize};
 
+pub mod bigquery;
+mod chronicle_forwarder;
+pub mod chronicle_udm;
 pub mod chronicle_unstructured;
 pub mod cloud_storage;
+pub mod cloud_trace;
 pub mod pubsub;
 pub mod stackdriver_logs;
 pub mod stackdriver_metrics;
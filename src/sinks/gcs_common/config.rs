@@ -1,7 +1,11 @@
 use futures::FutureExt;
-use http::{StatusCode, Uri};
+use http::{
+    header::{HeaderName, HeaderValue},
+    StatusCode, Uri,
+};
 use hyper::Body;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use snafu::Snafu;
 
 use crate::{
@@ -40,6 +44,74 @@ pub enum GcsStorageClass {
     Archive,
 }
 
+/// Server-side encryption applied to the objects written to the bucket.
+///
+/// Either a customer-supplied encryption key (CSEK) or the name of a Cloud KMS key (CMEK) can be
+/// set, but not both.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct GcsEncryptionConfig {
+    /// Base64-encoded AES-256 key used to encrypt the objects.
+    pub encryption_key: Option<String>,
+
+    /// Resource name of the Cloud KMS key used to encrypt the objects, in the form
+    /// `projects/<project>/locations/<location>/keyRings/<ring>/cryptoKeys/<key>`.
+    pub kms_key_name: Option<String>,
+}
+
+#[derive(Debug, Snafu)]
+pub enum GcsEncryptionError {
+    #[snafu(display("Only one of `encryption_key` and `kms_key_name` can be set"))]
+    ConflictingKeys,
+    #[snafu(display("`encryption_key` must be valid base64: {}", source))]
+    InvalidKeyEncoding { source: base64::DecodeError },
+    #[snafu(display(
+        "`encryption_key` must decode to a 256-bit key, found {} bytes",
+        length
+    ))]
+    InvalidKeyLength { length: usize },
+}
+
+impl GcsEncryptionConfig {
+    /// Builds the headers to send with every object upload.
+    pub fn headers(&self) -> crate::Result<Vec<(HeaderName, HeaderValue)>> {
+        match (&self.encryption_key, &self.kms_key_name) {
+            (Some(_), Some(_)) => Err(GcsEncryptionError::ConflictingKeys.into()),
+            (Some(key), None) => {
+                let decoded = base64::decode(key)
+                    .map_err(|source| GcsEncryptionError::InvalidKeyEncoding { source })?;
+                if decoded.len() != 32 {
+                    return Err(GcsEncryptionError::InvalidKeyLength {
+                        length: decoded.len(),
+                    }
+                    .into());
+                }
+                let hash = base64::encode(Sha256::digest(&decoded));
+
+                Ok(vec![
+                    (
+                        HeaderName::from_static("x-goog-encryption-algorithm"),
+                        HeaderValue::from_static("AES256"),
+                    ),
+                    (
+                        HeaderName::from_static("x-goog-encryption-key"),
+                        HeaderValue::from_str(key)?,
+                    ),
+                    (
+                        HeaderName::from_static("x-goog-encryption-key-sha256"),
+                        HeaderValue::from_str(&hash)?,
+                    ),
+                ])
+            }
+            (None, Some(name)) => Ok(vec![(
+                HeaderName::from_static("x-goog-encryption-kms-key-name"),
+                HeaderValue::from_str(name)?,
+            )]),
+            (None, None) => Ok(vec![]),
+        }
+    }
+}
+
 #[derive(Debug, Snafu)]
 pub enum GcsError {
     #[snafu(display("Bucket {:?} not found", bucket))]
@@ -78,6 +150,7 @@ pub fn healthcheck_response(
     // health check runs at startup, after a health check is a
     // good place to create the regeneration task.
     auth.spawn_regenerate_token();
+    auth.token_health()?;
     match response.status() {
         StatusCode::OK => Ok(()),
         StatusCode::FORBIDDEN => Err(GcpError::HealthcheckForbidden.into()),
@@ -112,3 +185,79 @@ impl RetryLogic for GcsRetryLogic {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header_names(headers: &[(HeaderName, HeaderValue)]) -> Vec<&str> {
+        headers.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    #[test]
+    fn encryption_headers_default_to_empty() {
+        let headers = GcsEncryptionConfig::default().headers().unwrap();
+        assert!(headers.is_empty());
+    }
+
+    #[test]
+    fn encryption_headers_for_customer_key() {
+        let key = base64::encode([7u8; 32]);
+        let config = GcsEncryptionConfig {
+            encryption_key: Some(key.clone()),
+            kms_key_name: None,
+        };
+        let headers = config.headers().unwrap();
+
+        assert_eq!(
+            header_names(&headers),
+            vec![
+                "x-goog-encryption-algorithm",
+                "x-goog-encryption-key",
+                "x-goog-encryption-key-sha256"
+            ]
+        );
+        assert_eq!(headers[1].1, key.as_str());
+        assert_eq!(
+            headers[2].1,
+            base64::encode(Sha256::digest(&[7u8; 32])).as_str()
+        );
+    }
+
+    #[test]
+    fn encryption_headers_for_kms_key() {
+        let name = "projects/p/locations/global/keyRings/r/cryptoKeys/k";
+        let config = GcsEncryptionConfig {
+            encryption_key: None,
+            kms_key_name: Some(name.into()),
+        };
+        let headers = config.headers().unwrap();
+
+        assert_eq!(
+            header_names(&headers),
+            vec!["x-goog-encryption-kms-key-name"]
+        );
+        assert_eq!(headers[0].1, name);
+    }
+
+    #[test]
+    fn encryption_rejects_invalid_keys() {
+        let conflicting = GcsEncryptionConfig {
+            encryption_key: Some(base64::encode([7u8; 32])),
+            kms_key_name: Some("projects/p/locations/global/keyRings/r/cryptoKeys/k".into()),
+        };
+        assert!(conflicting.headers().is_err());
+
+        let short = GcsEncryptionConfig {
+            encryption_key: Some(base64::encode([7u8; 16])),
+            kms_key_name: None,
+        };
+        assert!(short.headers().is_err());
+
+        let garbage = GcsEncryptionConfig {
+            encryption_key: Some("not base64!".into()),
+            kms_key_name: None,
+        };
+        assert!(garbage.headers().is_err());
+    }
+}
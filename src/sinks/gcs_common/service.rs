@@ -4,7 +4,7 @@ use bytes::Bytes;
 use futures::future::BoxFuture;
 use http::{
     header::{HeaderName, HeaderValue},
-    Request, Uri,
+    Request, StatusCode, Uri,
 };
 use hyper::Body;
 use tower::Service;
@@ -134,8 +134,14 @@ impl Service<GcsRequest> for GcsService {
         self.auth.apply(&mut http_request);
 
         let mut client = self.client.clone();
+        let auth = self.auth.clone();
         Box::pin(async move {
             let result = client.call(http_request).await;
+            if let Ok(response) = &result {
+                if response.status() == StatusCode::UNAUTHORIZED {
+                    auth.force_refresh();
+                }
+            }
             result.map(|inner| GcsResponse {
                 inner,
                 protocol,
@@ -41,6 +41,14 @@ pub(crate) struct KafkaSinkConfig {
     pub librdkafka_options: HashMap<String, String>,
     #[serde(alias = "headers_field")] // accidentally released as `headers_field` in 0.18
     pub headers_key: Option<String>,
+    /// Produces messages with the idempotent producer, so that messages retried by the producer are
+    /// not duplicated.
+    #[serde(default)]
+    pub idempotence: bool,
+    /// Produces messages in transactions, which are committed once their messages are delivered.
+    ///
+    /// Implies `idempotence`.
+    pub transaction: Option<KafkaTransactionConfig>,
     #[serde(
         default,
         deserialize_with = "crate::serde::bool_or_struct",
@@ -49,6 +57,30 @@ pub(crate) struct KafkaSinkConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct KafkaTransactionConfig {
+    /// The transactional ID of the producer.
+    ///
+    /// It must be unique to the Vector instance, and stable across its restarts, for the
+    /// transactions left open by a previous instance to be aborted.
+    pub transactional_id: String,
+    /// How often the open transaction is committed.
+    #[serde(default = "default_commit_interval_ms")]
+    pub commit_interval_ms: u64,
+    /// How long the broker waits for a transaction to be committed before aborting it.
+    #[serde(default = "default_transaction_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+const fn default_commit_interval_ms() -> u64 {
+    1000
+}
+
+const fn default_transaction_timeout_ms() -> u64 {
+    60000 // default in librdkafka
+}
+
 const fn default_socket_timeout_ms() -> u64 {
     60000 // default in librdkafka
 }
@@ -82,6 +114,18 @@ impl KafkaSinkConfig {
                     .set("compression.codec", &to_string(self.compression))
                     .set("message.timeout.ms", &self.message_timeout_ms.to_string());
 
+                if self.idempotence || self.transaction.is_some() {
+                    client_config.set("enable.idempotence", "true");
+                }
+                if let Some(transaction) = &self.transaction {
+                    client_config
+                        .set("transactional.id", &transaction.transactional_id)
+                        .set(
+                            "transaction.timeout.ms",
+                            &transaction.timeout_ms.to_string(),
+                        );
+                }
+
                 if let Some(value) = self.batch.timeout_secs {
                     // Delay in milliseconds to wait for messages in the producer queue to accumulate before
                     // constructing message batches (MessageSets) to transmit to brokers. A higher value
@@ -171,6 +215,8 @@ impl GenerateConfig for KafkaSinkConfig {
             message_timeout_ms: default_message_timeout_ms(),
             librdkafka_options: Default::default(),
             headers_key: None,
+            idempotence: false,
+            transaction: None,
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -207,4 +253,25 @@ mod tests {
     fn generate_config() {
         KafkaSinkConfig::generate_config();
     }
+
+    #[test]
+    fn sets_transactional_options() {
+        let config: KafkaSinkConfig = toml::from_str(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "logs"
+            encoding.codec = "json"
+            transaction.transactional_id = "vector-1"
+            "#,
+        )
+        .unwrap();
+
+        let client_config = config.to_rdkafka(KafkaRole::Producer).unwrap();
+        assert_eq!(client_config.get("enable.idempotence"), Some("true"));
+        assert_eq!(client_config.get("transactional.id"), Some("vector-1"));
+        assert_eq!(client_config.get("transaction.timeout.ms"), Some("60000"));
+
+        let client_config = config.to_rdkafka(KafkaRole::Consumer).unwrap();
+        assert_eq!(client_config.get("transactional.id"), None);
+    }
 }
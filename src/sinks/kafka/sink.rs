@@ -13,10 +13,14 @@ use tokio::time::Duration;
 use tower::limit::ConcurrencyLimit;
 use vector_core::config::log_schema;
 
-use super::config::{KafkaRole, KafkaSinkConfig};
+use super::{
+    config::{KafkaRole, KafkaSinkConfig, KafkaTransactionConfig},
+    transaction::{KafkaTransaction, TransactionalProducer},
+};
 use crate::{
     codecs::{Encoder, Transformer},
     event::{Event, LogEvent},
+    internal_events::KafkaTransactionError,
     kafka::KafkaStatisticsContext,
     sinks::{
         kafka::{
@@ -34,15 +38,26 @@ pub(super) enum BuildError {
     KafkaCreateFailed { source: KafkaError },
     #[snafu(display("invalid topic template: {}", source))]
     TopicTemplate { source: TemplateParseError },
+    #[snafu(display(
+        "transaction commit interval of {}ms must be shorter than the transaction timeout of {}ms",
+        commit_interval_ms,
+        timeout_ms
+    ))]
+    TransactionCommitInterval {
+        commit_interval_ms: u64,
+        timeout_ms: u64,
+    },
 }
 
 pub struct KafkaSink {
     transformer: Transformer,
     encoder: Encoder<()>,
     service: KafkaService,
+    producer: FutureProducer<KafkaStatisticsContext>,
     topic: Template,
     key_field: Option<String>,
     headers_key: Option<String>,
+    transaction: Option<KafkaTransactionConfig>,
 }
 
 pub(crate) fn create_producer(
@@ -56,6 +71,15 @@ pub(crate) fn create_producer(
 
 impl KafkaSink {
     pub(crate) fn new(config: KafkaSinkConfig) -> crate::Result<Self> {
+        if let Some(transaction) = &config.transaction {
+            if transaction.commit_interval_ms >= transaction.timeout_ms {
+                return Err(Box::new(BuildError::TransactionCommitInterval {
+                    commit_interval_ms: transaction.commit_interval_ms,
+                    timeout_ms: transaction.timeout_ms,
+                }));
+            }
+        }
+
         let producer_config = config.to_rdkafka(KafkaRole::Producer)?;
         let producer = create_producer(producer_config)?;
         let transformer = config.encoding.transformer();
@@ -66,13 +90,19 @@ impl KafkaSink {
             headers_key: config.headers_key,
             transformer,
             encoder,
-            service: KafkaService::new(producer),
+            service: KafkaService::new(producer.clone()),
+            producer,
             topic: Template::try_from(config.topic).context(TopicTemplateSnafu)?,
             key_field: config.key_field,
+            transaction: config.transaction,
         })
     }
 
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        if let Some(transaction) = self.transaction.clone() {
+            return self.run_transactional(input, transaction).await;
+        }
+
         // rdkafka will internally retry forever, so we need some limit to prevent this from overflowing
         let service = ConcurrencyLimit::new(self.service, QUEUED_MIN_MESSAGES as usize);
         let mut request_builder = KafkaRequestBuilder {
@@ -88,6 +118,75 @@ impl KafkaSink {
             .into_driver(service);
         sink.run().await
     }
+
+    /// Produces the events in transactions committed every commit interval, so that the events
+    /// are only acknowledged once the transaction of their messages is committed.
+    async fn run_transactional(
+        self: Box<Self>,
+        input: BoxStream<'_, Event>,
+        config: KafkaTransactionConfig,
+    ) -> Result<(), ()> {
+        let producer =
+            TransactionalProducer::new(self.producer, Duration::from_millis(config.timeout_ms));
+        if let Err(error) = producer.init().await {
+            emit!(KafkaTransactionError {
+                error,
+                operation: "init",
+                count: 0,
+            });
+            return Err(());
+        }
+
+        let mut service = self.service;
+        let mut request_builder = KafkaRequestBuilder {
+            key_field: self.key_field,
+            headers_key: self.headers_key,
+            topic_template: self.topic,
+            transformer: self.transformer,
+            encoder: self.encoder,
+            log_schema: log_schema(),
+        };
+        let mut input = input
+            .filter_map(|event| future::ready(request_builder.build_request(event)))
+            .fuse();
+        let mut commit_interval =
+            tokio::time::interval(Duration::from_millis(config.commit_interval_ms));
+        let mut transaction = KafkaTransaction::default();
+
+        loop {
+            tokio::select! {
+                _ = transaction.poll_in_flight(), if transaction.in_flight() > 0 => {}
+                request = input.next(), if transaction.in_flight() < QUEUED_MIN_MESSAGES as usize => {
+                    match request {
+                        Some(request) => {
+                            if transaction.is_empty() {
+                                if let Err(error) = producer.begin() {
+                                    emit!(KafkaTransactionError {
+                                        error,
+                                        operation: "begin",
+                                        count: 0,
+                                    });
+                                    return Err(());
+                                }
+                            }
+                            transaction.push(&mut service, request);
+                        }
+                        None => break,
+                    }
+                }
+                _ = commit_interval.tick() => {
+                    if !transaction.is_empty() {
+                        producer.complete(std::mem::take(&mut transaction)).await?;
+                    }
+                }
+            }
+        }
+
+        if !transaction.is_empty() {
+            producer.complete(transaction).await?;
+        }
+        Ok(())
+    }
 }
 
 pub(crate) async fn healthcheck(config: KafkaSinkConfig) -> crate::Result<()> {
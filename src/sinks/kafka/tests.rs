@@ -25,7 +25,7 @@ mod integration_test {
         kafka::{KafkaAuthConfig, KafkaCompression, KafkaSaslConfig},
         sinks::{
             kafka::{
-                config::{KafkaRole, KafkaSinkConfig},
+                config::{KafkaRole, KafkaSinkConfig, KafkaTransactionConfig},
                 sink::KafkaSink,
                 *,
             },
@@ -65,6 +65,8 @@ mod integration_test {
             message_timeout_ms: 300000,
             librdkafka_options: HashMap::new(),
             headers_key: None,
+            idempotence: false,
+            transaction: None,
             acknowledgements: Default::default(),
         };
         self::sink::healthcheck(config).await.unwrap();
@@ -120,6 +122,8 @@ mod integration_test {
             batch,
             librdkafka_options,
             headers_key: None,
+            idempotence: false,
+            transaction: None,
             acknowledgements: Default::default(),
         };
         config.clone().to_rdkafka(KafkaRole::Consumer)?;
@@ -230,6 +234,74 @@ mod integration_test {
         .await;
     }
 
+    #[tokio::test]
+    async fn kafka_happy_path_transactional() {
+        crate::test_util::trace_init();
+
+        let server = kafka_address(9091);
+        let topic = format!("test-{}", random_string(10));
+        let config = KafkaSinkConfig {
+            bootstrap_servers: server.clone(),
+            topic: topic.clone(),
+            key_field: None,
+            encoding: TextSerializerConfig::new().into(),
+            batch: BatchConfig::default(),
+            compression: KafkaCompression::None,
+            auth: KafkaAuthConfig::default(),
+            socket_timeout_ms: 60000,
+            message_timeout_ms: 300000,
+            librdkafka_options: HashMap::new(),
+            headers_key: None,
+            idempotence: false,
+            transaction: Some(KafkaTransactionConfig {
+                transactional_id: format!("vector-{}", random_string(10)),
+                commit_interval_ms: 100,
+                timeout_ms: 60000,
+            }),
+            acknowledgements: Default::default(),
+        };
+        let sink = KafkaSink::new(config).unwrap();
+        let sink = VectorSink::from_event_streamsink(sink);
+
+        let num_events = 1000;
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (input, events) = random_lines_with_stream(100, num_events, Some(batch));
+        run_and_assert_sink_compliance(sink, events, &SINK_TAGS).await;
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        // only the messages of committed transactions are read back
+        let mut client_config = rdkafka::ClientConfig::new();
+        client_config.set("bootstrap.servers", server.as_str());
+        client_config.set("group.id", &random_string(10));
+        client_config.set("isolation.level", "read_committed");
+
+        let mut tpl = TopicPartitionList::new();
+        tpl.add_partition(&topic, 0)
+            .set_offset(Offset::Beginning)
+            .unwrap();
+
+        let consumer: BaseConsumer = client_config.create().unwrap();
+        consumer.assign(&tpl).unwrap();
+
+        let mut failures = 0;
+        let mut out = Vec::new();
+        while failures < 100 {
+            match consumer.poll(Duration::from_secs(3)) {
+                Some(Ok(msg)) => {
+                    let s: &str = msg.payload_view().unwrap().unwrap();
+                    out.push(s.to_owned());
+                }
+                None if out.len() >= input.len() => break,
+                _ => {
+                    failures += 1;
+                    thread::sleep(Duration::from_millis(50));
+                }
+            }
+        }
+
+        assert_eq!(out, input);
+    }
+
     async fn kafka_happy_path(
         server: String,
         sasl: Option<KafkaSaslConfig>,
@@ -251,6 +323,8 @@ mod integration_test {
             message_timeout_ms: 300000,
             librdkafka_options: HashMap::new(),
             headers_key: Some(headers_key.clone()),
+            idempotence: false,
+            transaction: None,
             acknowledgements: Default::default(),
         };
         let topic = format!("{}-{}", topic, chrono::Utc::now().format("%Y%m%d"));
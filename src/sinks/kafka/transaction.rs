@@ -0,0 +1,168 @@
+use std::time::Duration;
+
+use futures::{future::BoxFuture, stream::FuturesUnordered, StreamExt};
+use rdkafka::{
+    error::{KafkaError, KafkaResult},
+    producer::{FutureProducer, Producer},
+};
+use tower::Service;
+use vector_core::internal_event::EventsSent;
+
+use crate::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    internal_events::KafkaTransactionError,
+    kafka::KafkaStatisticsContext,
+    sinks::kafka::service::{KafkaRequest, KafkaResponse, KafkaService},
+};
+
+/// The messages produced in the open transaction, which are delivered once it is committed.
+#[derive(Default)]
+pub(super) struct KafkaTransaction {
+    in_flight: FuturesUnordered<BoxFuture<'static, Result<KafkaResponse, KafkaError>>>,
+    finalizers: EventFinalizers,
+    count: usize,
+    byte_size: usize,
+    error: Option<KafkaError>,
+}
+
+impl KafkaTransaction {
+    pub(super) const fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub(super) fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    pub(super) fn push(&mut self, service: &mut KafkaService, mut request: KafkaRequest) {
+        self.finalizers.merge(request.take_finalizers());
+        self.count += 1;
+        self.byte_size += request.event_byte_size;
+        self.in_flight.push(service.call(request));
+    }
+
+    /// Drives the messages in flight, resolving once one of them is delivered or failed.
+    pub(super) async fn poll_in_flight(&mut self) {
+        if let Some(Err(error)) = self.in_flight.next().await {
+            self.error.get_or_insert(error);
+        }
+    }
+}
+
+/// Runs the transactions of a producer configured with a transactional ID.
+pub(super) struct TransactionalProducer {
+    producer: FutureProducer<KafkaStatisticsContext>,
+    timeout: Duration,
+}
+
+impl TransactionalProducer {
+    pub(super) const fn new(
+        producer: FutureProducer<KafkaStatisticsContext>,
+        timeout: Duration,
+    ) -> Self {
+        Self { producer, timeout }
+    }
+
+    /// Registers the transactional ID of the producer, aborting the transactions left open by
+    /// previous producers with the same ID.
+    pub(super) async fn init(&self) -> KafkaResult<()> {
+        let timeout = self.timeout;
+        self.blocking(move |producer| producer.init_transactions(timeout))
+            .await
+    }
+
+    pub(super) fn begin(&self) -> KafkaResult<()> {
+        self.producer.begin_transaction()
+    }
+
+    /// Completes the transaction, committing it once all of its messages are delivered and
+    /// aborting it otherwise, and updates the status of its events accordingly.
+    ///
+    /// Returns an error when the producer can't be used anymore.
+    pub(super) async fn complete(&self, mut transaction: KafkaTransaction) -> Result<(), ()> {
+        while !transaction.in_flight.is_empty() {
+            transaction.poll_in_flight().await;
+        }
+
+        let result = match transaction.error.take() {
+            Some(error) => Err(("produce", error)),
+            None => self.commit().await.map_err(|error| ("commit", error)),
+        };
+
+        match result {
+            Ok(()) => {
+                transaction.finalizers.update_status(EventStatus::Delivered);
+                emit!(EventsSent {
+                    count: transaction.count,
+                    byte_size: transaction.byte_size,
+                    output: None,
+                });
+                Ok(())
+            }
+            Err((operation, error)) => {
+                transaction.finalizers.update_status(EventStatus::Errored);
+                let fatal = is_fatal(&error);
+                emit!(KafkaTransactionError {
+                    error,
+                    operation,
+                    count: transaction.count,
+                });
+                if fatal {
+                    return Err(());
+                }
+
+                if let Err(error) = self.abort().await {
+                    let fatal = is_fatal(&error);
+                    emit!(KafkaTransactionError {
+                        error,
+                        operation: "abort",
+                        count: transaction.count,
+                    });
+                    if fatal {
+                        return Err(());
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+
+    async fn commit(&self) -> KafkaResult<()> {
+        loop {
+            let timeout = self.timeout;
+            match self
+                .blocking(move |producer| producer.commit_transaction(timeout))
+                .await
+            {
+                Err(KafkaError::Transaction(error)) if error.is_retriable() => {
+                    debug!(message = "Retrying transaction commit.", %error);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn abort(&self) -> KafkaResult<()> {
+        let timeout = self.timeout;
+        self.blocking(move |producer| producer.abort_transaction(timeout))
+            .await
+    }
+
+    /// Runs the blocking transactional operation of the producer on the blocking thread pool.
+    async fn blocking<F>(&self, operation: F) -> KafkaResult<()>
+    where
+        F: FnOnce(&FutureProducer<KafkaStatisticsContext>) -> KafkaResult<()> + Send + 'static,
+    {
+        let producer = self.producer.clone();
+        tokio::task::spawn_blocking(move || operation(&producer))
+            .await
+            .expect("transactional operation panicked")
+    }
+}
+
+fn is_fatal(error: &KafkaError) -> bool {
+    match error {
+        KafkaError::Transaction(error) => error.is_fatal(),
+        _ => false,
+    }
+}
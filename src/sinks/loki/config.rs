@@ -31,6 +31,8 @@ pub struct LokiConfig {
     pub compression: Compression,
     #[serde(default)]
     pub out_of_order_action: OutOfOrderAction,
+    #[serde(default)]
+    pub out_of_order_window_secs: u64,
     pub auth: Option<Auth>,
     #[serde(default)]
     pub request: TowerRequestConfig,
@@ -62,6 +64,7 @@ pub enum OutOfOrderAction {
     Drop,
     RewriteTimestamp,
     Accept,
+    Shard,
 }
 
 impl GenerateConfig for LokiConfig {
@@ -25,7 +25,7 @@ use crate::{
     http::HttpClient,
     internal_events::{
         LokiEventUnlabeled, LokiOutOfOrderEventDropped, LokiOutOfOrderEventRewritten,
-        TemplateRenderingError,
+        LokiOutOfOrderEventSharded, TemplateRenderingError,
     },
     sinks::util::{
         builder::SinkBuilderExt,
@@ -37,6 +37,13 @@ use crate::{
     template::Template,
 };
 
+/// The label added to the events sent to a shard of their stream.
+const SHARD_LABEL: &str = "stream_shard";
+
+/// The maximum number of shards of a stream, including the stream itself. The out-of-order events
+/// that don't fit in any of them are dropped.
+const MAX_SHARDS: usize = 16;
+
 #[derive(Clone)]
 pub struct KeyPartitioner(Option<Template>);
 
@@ -240,6 +247,7 @@ impl EventEncoder {
 
 struct FilteredRecord {
     pub rewritten: bool,
+    pub sharded: bool,
     pub inner: LokiRecord,
 }
 
@@ -247,6 +255,15 @@ impl FilteredRecord {
     pub const fn rewritten(inner: LokiRecord) -> Self {
         Self {
             rewritten: true,
+            sharded: false,
+            inner,
+        }
+    }
+
+    pub const fn sharded(inner: LokiRecord) -> Self {
+        Self {
+            rewritten: false,
+            sharded: true,
             inner,
         }
     }
@@ -254,6 +271,7 @@ impl FilteredRecord {
     pub const fn valid(inner: LokiRecord) -> Self {
         Self {
             rewritten: false,
+            sharded: false,
             inner,
         }
     }
@@ -274,39 +292,70 @@ impl ByteSizeOf for FilteredRecord {
 }
 
 struct RecordFilter {
-    timestamps: HashMap<PartitionKey, i64>,
+    /// The latest timestamp of each shard of the streams, the first one being the stream itself.
+    timestamps: HashMap<PartitionKey, Vec<i64>>,
     out_of_order_action: OutOfOrderAction,
+    /// How far behind the latest timestamp of a stream its events are accepted, in nanoseconds.
+    window: i64,
 }
 
 impl RecordFilter {
-    fn new(out_of_order_action: OutOfOrderAction) -> Self {
+    fn new(out_of_order_action: OutOfOrderAction, window_secs: u64) -> Self {
         Self {
             timestamps: HashMap::new(),
             out_of_order_action,
+            window: i64::try_from(window_secs)
+                .unwrap_or(i64::MAX)
+                .saturating_mul(1_000_000_000),
         }
     }
 }
 
 impl RecordFilter {
     pub fn filter_record(&mut self, mut record: LokiRecord) -> Option<FilteredRecord> {
-        if let Some(latest) = self.timestamps.get_mut(&record.partition) {
-            if record.event.timestamp < *latest {
-                match self.out_of_order_action {
-                    OutOfOrderAction::Drop => None,
-                    OutOfOrderAction::RewriteTimestamp => {
-                        record.event.timestamp = *latest;
-                        Some(FilteredRecord::rewritten(record))
+        let timestamp = record.event.timestamp;
+        let shards = match self.timestamps.get_mut(&record.partition) {
+            Some(shards) => shards,
+            None => {
+                self.timestamps
+                    .insert(record.partition.clone(), vec![timestamp]);
+                return Some(FilteredRecord::valid(record));
+            }
+        };
+
+        let window = self.window;
+        let accepts = |latest: i64| timestamp >= latest.saturating_sub(window);
+        if accepts(shards[0]) {
+            shards[0] = shards[0].max(timestamp);
+            return Some(FilteredRecord::valid(record));
+        }
+
+        match self.out_of_order_action {
+            OutOfOrderAction::Drop => None,
+            OutOfOrderAction::RewriteTimestamp => {
+                record.event.timestamp = shards[0].saturating_sub(window);
+                Some(FilteredRecord::rewritten(record))
+            }
+            OutOfOrderAction::Accept => Some(FilteredRecord::valid(record)),
+            OutOfOrderAction::Shard => {
+                let shard = match shards.iter().position(|latest| accepts(*latest)) {
+                    Some(shard) => {
+                        shards[shard] = shards[shard].max(timestamp);
+                        shard
                     }
-                    OutOfOrderAction::Accept => Some(FilteredRecord::valid(record)),
-                }
-            } else {
-                *latest = record.event.timestamp;
-                Some(FilteredRecord::valid(record))
+                    None if shards.len() < MAX_SHARDS => {
+                        shards.push(timestamp);
+                        shards.len() - 1
+                    }
+                    None => return None,
+                };
+                record
+                    .labels
+                    .push((SHARD_LABEL.to_owned(), shard.to_string()));
+                record.partition =
+                    PartitionKey::new(record.partition.tenant_id.clone(), &mut record.labels);
+                Some(FilteredRecord::sharded(record))
             }
-        } else {
-            self.timestamps
-                .insert(record.partition.clone(), record.event.timestamp);
-            Some(FilteredRecord::valid(record))
         }
     }
 }
@@ -316,6 +365,7 @@ pub struct LokiSink {
     pub(super) encoder: EventEncoder,
     batch_settings: BatcherSettings,
     out_of_order_action: OutOfOrderAction,
+    out_of_order_window_secs: u64,
     service: Svc<LokiService, LokiRetryLogic>,
 }
 
@@ -334,7 +384,9 @@ impl LokiSink {
         // of 1 for now.
         let request_limits = match config.out_of_order_action {
             OutOfOrderAction::Accept => config.request.unwrap_with(&Default::default()),
-            OutOfOrderAction::Drop | OutOfOrderAction::RewriteTimestamp => {
+            OutOfOrderAction::Drop
+            | OutOfOrderAction::RewriteTimestamp
+            | OutOfOrderAction::Shard => {
                 let mut settings = config.request.unwrap_with(&Default::default());
                 settings.concurrency = Some(1);
                 settings
@@ -364,21 +416,22 @@ impl LokiSink {
             },
             batch_settings: config.batch.into_batcher_settings()?,
             out_of_order_action: config.out_of_order_action,
+            out_of_order_window_secs: config.out_of_order_window_secs,
             service,
         })
     }
 
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let mut encoder = self.encoder.clone();
-        let mut filter = RecordFilter::new(self.out_of_order_action);
+        let mut filter = RecordFilter::new(self.out_of_order_action, self.out_of_order_window_secs);
 
         // out_of_order_action's that require a complete ordering are limited to building 1 request
         // at a time
         let request_builder_concurrency = match self.out_of_order_action {
             OutOfOrderAction::Accept => NonZeroUsize::new(50).expect("static"),
-            OutOfOrderAction::Drop | OutOfOrderAction::RewriteTimestamp => {
-                NonZeroUsize::new(1).expect("static")
-            }
+            OutOfOrderAction::Drop
+            | OutOfOrderAction::RewriteTimestamp
+            | OutOfOrderAction::Shard => NonZeroUsize::new(1).expect("static"),
         };
 
         let sink = input
@@ -389,6 +442,7 @@ impl LokiSink {
             .filter_map(|(partition, batch)| async {
                 if let Some(partition) = partition {
                     let mut count: usize = 0;
+                    let mut sharded: usize = 0;
                     let result = batch
                         .into_iter()
                         .flatten()
@@ -396,12 +450,18 @@ impl LokiSink {
                             if event.rewritten {
                                 count += 1;
                             }
+                            if event.sharded {
+                                sharded += 1;
+                            }
                             event.inner
                         })
                         .collect::<Vec<_>>();
                     if count > 0 {
                         emit!(LokiOutOfOrderEventRewritten { count });
                     }
+                    if sharded > 0 {
+                        emit!(LokiOutOfOrderEventSharded { count: sharded });
+                    }
                     Some((partition, result))
                 } else {
                     emit!(LokiOutOfOrderEventDropped { count: batch.len() });
@@ -445,14 +505,21 @@ mod tests {
         convert::TryFrom,
     };
 
+    use bytes::Bytes;
     use codecs::JsonSerializer;
     use futures::stream::StreamExt;
     use vector_core::event::{Event, LogEvent, Value};
 
-    use super::{EventEncoder, KeyPartitioner, RecordFilter};
+    use super::{EventEncoder, KeyPartitioner, RecordFilter, MAX_SHARDS};
     use crate::{
-        codecs::Encoder, config::log_schema, sinks::loki::config::OutOfOrderAction,
-        template::Template, test_util::random_lines,
+        codecs::Encoder,
+        config::log_schema,
+        sinks::loki::{
+            config::OutOfOrderAction,
+            event::{LokiEvent, LokiRecord, PartitionKey},
+        },
+        template::Template,
+        test_util::random_lines,
     };
 
     #[test]
@@ -599,7 +666,7 @@ mod tests {
                 event
             })
             .collect::<Vec<_>>();
-        let mut filter = RecordFilter::new(OutOfOrderAction::Drop);
+        let mut filter = RecordFilter::new(OutOfOrderAction::Drop, 0);
         let stream = futures::stream::iter(events)
             .map(|event| encoder.encode_event(event))
             .filter_map(|event| async { event })
@@ -614,4 +681,71 @@ mod tests {
         }
         assert_eq!(result.len(), 17);
     }
+
+    fn record(timestamp: i64) -> LokiRecord {
+        let mut labels = vec![("app".to_owned(), "web".to_owned())];
+        LokiRecord {
+            partition: PartitionKey::new(None, &mut labels),
+            labels,
+            event: LokiEvent {
+                timestamp,
+                event: Bytes::from("hello"),
+            },
+            finalizers: Default::default(),
+        }
+    }
+
+    const SECOND: i64 = 1_000_000_000;
+
+    #[test]
+    fn filter_accepts_events_within_window() {
+        let mut filter = RecordFilter::new(OutOfOrderAction::Drop, 60);
+        assert!(filter.filter_record(record(100 * SECOND)).is_some());
+        assert!(filter.filter_record(record(50 * SECOND)).is_some());
+        assert!(filter.filter_record(record(39 * SECOND)).is_none());
+    }
+
+    #[test]
+    fn filter_rewrites_timestamp_to_window_start() {
+        let mut filter = RecordFilter::new(OutOfOrderAction::RewriteTimestamp, 60);
+        filter.filter_record(record(100 * SECOND)).unwrap();
+        let filtered = filter.filter_record(record(10 * SECOND)).unwrap();
+        assert!(filtered.rewritten);
+        assert_eq!(filtered.inner.event.timestamp, 40 * SECOND);
+    }
+
+    #[test]
+    fn filter_shards_events_outside_window() {
+        let mut filter = RecordFilter::new(OutOfOrderAction::Shard, 60);
+        let first = filter.filter_record(record(1000 * SECOND)).unwrap();
+        assert!(!first.sharded);
+
+        let sharded = filter.filter_record(record(100 * SECOND)).unwrap();
+        assert!(sharded.sharded);
+        assert_ne!(sharded.partition(), first.partition());
+        assert!(sharded
+            .inner
+            .labels
+            .contains(&("stream_shard".to_owned(), "1".to_owned())));
+
+        // Events fitting in the window of the shard are sent to it.
+        let again = filter.filter_record(record(90 * SECOND)).unwrap();
+        assert_eq!(again.partition(), sharded.partition());
+
+        // Events older than the window of every shard get a new one.
+        let other = filter.filter_record(record(10 * SECOND)).unwrap();
+        assert!(other
+            .inner
+            .labels
+            .contains(&("stream_shard".to_owned(), "2".to_owned())));
+    }
+
+    #[test]
+    fn filter_drops_events_beyond_max_shards() {
+        let mut filter = RecordFilter::new(OutOfOrderAction::Shard, 0);
+        for timestamp in (0..MAX_SHARDS as i64).rev() {
+            assert!(filter.filter_record(record(timestamp)).is_some());
+        }
+        assert!(filter.filter_record(record(-1)).is_none());
+    }
 }
@@ -3,26 +3,40 @@ use snafu::Snafu;
 
 pub mod util;
 
+#[cfg(feature = "sinks-amqp")]
+pub mod amqp;
 #[cfg(feature = "sinks-aws_cloudwatch_logs")]
 pub mod aws_cloudwatch_logs;
 #[cfg(feature = "sinks-aws_cloudwatch_metrics")]
 pub mod aws_cloudwatch_metrics;
+#[cfg(feature = "sinks-aws_eventbridge")]
+pub mod aws_eventbridge;
 #[cfg(feature = "sinks-aws_kinesis_firehose")]
 pub mod aws_kinesis_firehose;
 #[cfg(feature = "sinks-aws_kinesis_streams")]
 pub mod aws_kinesis_streams;
 #[cfg(feature = "sinks-aws_s3")]
 pub mod aws_s3;
+#[cfg(feature = "sinks-aws_security_lake")]
+pub mod aws_security_lake;
 #[cfg(feature = "sinks-aws_sqs")]
 pub mod aws_sqs;
 #[cfg(feature = "sinks-axiom")]
 pub mod axiom;
 #[cfg(feature = "sinks-azure_blob")]
 pub mod azure_blob;
-#[cfg(any(feature = "sinks-azure_blob", feature = "sinks-datadog_archives"))]
+#[cfg(any(
+    feature = "sinks-azure_blob",
+    feature = "sinks-datadog_archives",
+    feature = "sources-azure_event_hubs"
+))]
 pub mod azure_common;
+#[cfg(feature = "sinks-azure_data_explorer")]
+pub mod azure_data_explorer;
 #[cfg(feature = "sinks-azure_monitor_logs")]
 pub mod azure_monitor_logs;
+#[cfg(feature = "sinks-azure_monitor_logs_dcr")]
+pub mod azure_monitor_logs_dcr;
 #[cfg(feature = "sinks-blackhole")]
 pub mod blackhole;
 #[cfg(feature = "sinks-clickhouse")]
@@ -60,18 +74,26 @@ pub mod kafka;
 pub mod logdna;
 #[cfg(feature = "sinks-loki")]
 pub mod loki;
+#[cfg(feature = "sinks-mqtt")]
+pub mod mqtt;
 #[cfg(feature = "sinks-nats")]
 pub mod nats;
 #[cfg(feature = "sinks-new_relic")]
 pub mod new_relic;
 #[cfg(feature = "sinks-new_relic_logs")]
 pub mod new_relic_logs;
+#[cfg(feature = "sinks-opentelemetry")]
+pub mod opentelemetry;
 #[cfg(feature = "sinks-papertrail")]
 pub mod papertrail;
+#[cfg(feature = "sinks-postgres")]
+pub mod postgres;
 #[cfg(feature = "sinks-prometheus")]
 pub mod prometheus;
 #[cfg(feature = "sinks-pulsar")]
 pub mod pulsar;
+#[cfg(feature = "sinks-questdb")]
+pub mod questdb;
 #[cfg(feature = "sinks-redis")]
 pub mod redis;
 #[cfg(all(
@@ -87,6 +109,8 @@ pub mod socket;
 pub mod splunk_hec;
 #[cfg(feature = "sinks-statsd")]
 pub mod statsd;
+#[cfg(feature = "sinks-tempo")]
+pub mod tempo;
 #[cfg(feature = "sinks-vector")]
 pub mod vector;
 #[cfg(feature = "sinks-websocket")]
@@ -122,6 +146,10 @@ pub enum HealthcheckError {
 #[derive(Clone, Debug)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Sinks {
+    /// AMQP.
+    #[cfg(feature = "sinks-amqp")]
+    Amqp(#[configurable(derived)] amqp::AmqpSinkConfig),
+
     /// AWS CloudWatch Logs.
     #[cfg(feature = "sinks-aws_cloudwatch_logs")]
     AwsCloudwatchLogs(#[configurable(derived)] aws_cloudwatch_logs::CloudwatchLogsSinkConfig),
@@ -132,6 +160,10 @@ pub enum Sinks {
         #[configurable(derived)] aws_cloudwatch_metrics::CloudWatchMetricsSinkConfig,
     ),
 
+    /// AWS EventBridge.
+    #[cfg(feature = "sinks-aws_eventbridge")]
+    AwsEventBridge(#[configurable(derived)] aws_eventbridge::AwsEventBridgeSinkConfig),
+
     /// AWS Kinesis Firehose.
     #[cfg(feature = "sinks-aws_kinesis_firehose")]
     AwsKinesisFirehose(#[configurable(derived)] aws_kinesis_firehose::KinesisFirehoseSinkConfig),
@@ -144,6 +176,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-aws_s3")]
     AwsS3(#[configurable(derived)] aws_s3::S3SinkConfig),
 
+    /// AWS Security Lake.
+    #[cfg(feature = "sinks-aws_security_lake")]
+    AwsSecurityLake(#[configurable(derived)] aws_security_lake::AwsSecurityLakeSinkConfig),
+
     /// AWS SQS.
     #[cfg(feature = "sinks-aws_sqs")]
     AwsSqs(#[configurable(derived)] aws_sqs::SqsSinkConfig),
@@ -152,10 +188,20 @@ pub enum Sinks {
     #[cfg(feature = "sinks-azure_blob")]
     AzureBlob(#[configurable(derived)] azure_blob::AzureBlobSinkConfig),
 
+    /// Azure Data Explorer.
+    #[cfg(feature = "sinks-azure_data_explorer")]
+    AzureDataExplorer(#[configurable(derived)] azure_data_explorer::AzureDataExplorerSinkConfig),
+
     /// Azure Monitor Logs.
     #[cfg(feature = "sinks-azure_monitor_logs")]
     AzureMonitorLogs(#[configurable(derived)] azure_monitor_logs::AzureMonitorLogsConfig),
 
+    /// Azure Monitor Logs Ingestion API.
+    #[cfg(feature = "sinks-azure_monitor_logs_dcr")]
+    AzureMonitorLogsDcr(
+        #[configurable(derived)] azure_monitor_logs_dcr::AzureMonitorLogsDcrSinkConfig,
+    ),
+
     /// Blackhole.
     #[cfg(feature = "sinks-blackhole")]
     Blackhole(#[configurable(derived)] blackhole::BlackholeConfig),
@@ -248,6 +294,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-loki")]
     Loki(#[configurable(derived)] loki::LokiConfig),
 
+    /// MQTT.
+    #[cfg(feature = "sinks-mqtt")]
+    Mqtt(#[configurable(derived)] mqtt::MqttSinkConfig),
+
     /// NATS.
     #[cfg(feature = "sinks-nats")]
     Nats(#[configurable(derived)] nats::NatsSinkConfig),
@@ -260,10 +310,18 @@ pub enum Sinks {
     #[cfg(feature = "sinks-new_relic_logs")]
     NewrelicLogs(#[configurable(derived)] new_relic_logs::NewRelicLogsConfig),
 
+    /// OpenTelemetry.
+    #[cfg(feature = "sinks-opentelemetry")]
+    Opentelemetry(#[configurable(derived)] opentelemetry::OpentelemetrySinkConfig),
+
     /// Papertrail.
     #[cfg(feature = "sinks-papertrail")]
     Papertrail(#[configurable(derived)] papertrail::PapertrailConfig),
 
+    /// PostgreSQL.
+    #[cfg(feature = "sinks-postgres")]
+    Postgres(#[configurable(derived)] postgres::PostgresSinkConfig),
+
     /// Prometheus Exporter.
     #[cfg(feature = "sinks-prometheus")]
     PrometheusExporter(#[configurable(derived)] prometheus::exporter::PrometheusExporterConfig),
@@ -276,6 +334,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-pulsar")]
     Pulsar(#[configurable(derived)] pulsar::PulsarSinkConfig),
 
+    /// QuestDB.
+    #[cfg(feature = "sinks-questdb")]
+    Questdb(#[configurable(derived)] questdb::QuestDbSinkConfig),
+
     /// Redis.
     #[cfg(feature = "sinks-redis")]
     Redis(#[configurable(derived)] redis::RedisSinkConfig),
@@ -304,6 +366,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-statsd")]
     Statsd(#[configurable(derived)] statsd::StatsdSinkConfig),
 
+    /// Grafana Tempo.
+    #[cfg(feature = "sinks-tempo")]
+    Tempo(#[configurable(derived)] tempo::TempoSinkConfig),
+
     /// Vector.
     #[cfg(feature = "sinks-vector")]
     Vector(#[configurable(derived)] vector::VectorConfig),
@@ -0,0 +1,424 @@
+use std::{convert::TryFrom, time::Duration};
+
+use async_trait::async_trait;
+use bytes::BytesMut;
+use codecs::JsonSerializerConfig;
+use futures::{stream::BoxStream, FutureExt, StreamExt};
+use rumqttc::{
+    AsyncClient, Event as MqttEvent, EventLoop, Key, MqttOptions, Outgoing, Packet, QoS,
+    TlsConfiguration, Transport,
+};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use tokio_util::codec::Encoder as _;
+use vector_common::internal_event::{BytesSent, EventsSent};
+use vector_core::ByteSizeOf;
+
+use crate::{
+    codecs::{Encoder, EncodingConfig, Transformer},
+    config::{
+        AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext,
+        SinkDescription,
+    },
+    event::{Event, EventStatus, Finalizable},
+    internal_events::{MqttClientError, MqttConnectionError, TemplateRenderingError},
+    sinks::util::StreamSink,
+    template::{Template, TemplateParseError},
+    tls::{MaybeTlsSettings, TlsEnableableConfig, TlsError},
+};
+
+/// The number of requests the client queues up before publishing waits on the event loop.
+const CLIENT_CAPACITY: usize = 100;
+
+/// The delay before the event loop reconnects after a connection error.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("invalid encoding: {}", source))]
+    Encoding {
+        source: codecs::encoding::BuildError,
+    },
+    #[snafu(display("invalid topic template: {}", source))]
+    TopicTemplate { source: TemplateParseError },
+    #[snafu(display("MQTT TLS Error: {}", source))]
+    Tls { source: TlsError },
+}
+
+/// The MQTT quality of service level messages are published with.
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Eq, PartialEq, Serialize)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MqttQualityOfService {
+    /// Messages are delivered at least once, and may be duplicated.
+    #[derivative(Default)]
+    AtLeastOnce,
+
+    /// Messages are delivered at most once, and may be lost.
+    AtMostOnce,
+
+    /// Messages are delivered exactly once.
+    ExactlyOnce,
+}
+
+impl From<MqttQualityOfService> for QoS {
+    fn from(quality_of_service: MqttQualityOfService) -> Self {
+        match quality_of_service {
+            MqttQualityOfService::AtLeastOnce => QoS::AtLeastOnce,
+            MqttQualityOfService::AtMostOnce => QoS::AtMostOnce,
+            MqttQualityOfService::ExactlyOnce => QoS::ExactlyOnce,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MqttSinkConfig {
+    host: String,
+    #[serde(default = "default_port")]
+    port: u16,
+    user: Option<String>,
+    password: Option<String>,
+    #[serde(default = "default_client_id")]
+    client_id: String,
+    /// The interval, in seconds, at which the client pings the broker when idle.
+    #[serde(default = "default_keep_alive")]
+    keep_alive: u16,
+    #[serde(default = "crate::serde::default_false")]
+    clean_session: bool,
+    tls: Option<TlsEnableableConfig>,
+    topic: String,
+    #[serde(default)]
+    quality_of_service: MqttQualityOfService,
+    #[serde(default = "crate::serde::default_false")]
+    retain: bool,
+    encoding: EncodingConfig,
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+const fn default_port() -> u16 {
+    1883
+}
+
+fn default_client_id() -> String {
+    String::from("vector")
+}
+
+const fn default_keep_alive() -> u16 {
+    60
+}
+
+inventory::submit! {
+    SinkDescription::new::<MqttSinkConfig>("mqtt")
+}
+
+impl GenerateConfig for MqttSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            host: "localhost".into(),
+            port: default_port(),
+            user: None,
+            password: None,
+            client_id: default_client_id(),
+            keep_alive: default_keep_alive(),
+            clean_session: false,
+            tls: None,
+            topic: "vector".into(),
+            quality_of_service: Default::default(),
+            retain: false,
+            encoding: JsonSerializerConfig::new().into(),
+            acknowledgements: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "mqtt")]
+impl SinkConfig for MqttSinkConfig {
+    async fn build(
+        &self,
+        _cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let sink = MqttSink::new(self)?;
+        let healthcheck = healthcheck(self.options()?).boxed();
+        Ok((super::VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(self.encoding.config().input_type() & DataType::Log)
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "mqtt"
+    }
+
+    fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
+        Some(&self.acknowledgements)
+    }
+}
+
+impl MqttSinkConfig {
+    fn options(&self) -> Result<MqttOptions, BuildError> {
+        let mut options = MqttOptions::new(&self.client_id, &self.host, self.port);
+        options.set_keep_alive(Duration::from_secs(self.keep_alive.into()));
+        options.set_clean_session(self.clean_session);
+        if let Some(user) = &self.user {
+            options.set_credentials(user, self.password.as_deref().unwrap_or_default());
+        }
+
+        let tls = MaybeTlsSettings::from_config(&self.tls, false).context(TlsSnafu)?;
+        if let Some(tls) = tls.tls() {
+            options.set_transport(Transport::Tls(TlsConfiguration::Simple {
+                ca: tls.authorities_pem().flatten().collect(),
+                alpn: None,
+                // The identity key is always PKCS#8 encoded, which `rumqttc` only reads as
+                // an ECC key.
+                client_auth: tls.identity_pem().map(|(cert, key)| (cert, Key::ECC(key))),
+            }));
+        }
+
+        Ok(options)
+    }
+}
+
+/// Connects to the broker and waits for it to acknowledge the connection.
+async fn healthcheck(options: MqttOptions) -> crate::Result<()> {
+    let (_client, mut event_loop) = AsyncClient::new(options, 1);
+    loop {
+        if let MqttEvent::Incoming(Packet::ConnAck(_)) = event_loop.poll().await? {
+            return Ok(());
+        }
+    }
+}
+
+pub struct MqttSink {
+    transformer: Transformer,
+    encoder: Encoder<()>,
+    options: MqttOptions,
+    topic: Template,
+    quality_of_service: QoS,
+    retain: bool,
+}
+
+impl MqttSink {
+    fn new(config: &MqttSinkConfig) -> Result<Self, BuildError> {
+        let transformer = config.encoding.transformer();
+        let serializer = config.encoding.build().context(EncodingSnafu)?;
+        let encoder = Encoder::<()>::new(serializer);
+
+        Ok(MqttSink {
+            transformer,
+            encoder,
+            options: config.options()?,
+            topic: Template::try_from(config.topic.as_str()).context(TopicTemplateSnafu)?,
+            quality_of_service: config.quality_of_service.into(),
+            retain: config.retain,
+        })
+    }
+}
+
+/// Drives the connection to the broker until the client disconnects.
+///
+/// The event loop reconnects on the next poll after an error, so the client keeps publishing
+/// across broker restarts.
+async fn run_event_loop(mut event_loop: EventLoop) {
+    loop {
+        match event_loop.poll().await {
+            Ok(MqttEvent::Outgoing(Outgoing::Disconnect)) => break,
+            Ok(_) => {}
+            Err(error) => {
+                emit!(MqttConnectionError { error });
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for MqttSink {
+    async fn run(mut self: Box<Self>, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let (client, event_loop) = AsyncClient::new(self.options.clone(), CLIENT_CAPACITY);
+        let event_loop = tokio::spawn(run_event_loop(event_loop));
+
+        while let Some(mut event) = input.next().await {
+            let finalizers = event.take_finalizers();
+
+            let topic = match self.topic.render_string(&event) {
+                Ok(topic) => topic,
+                Err(error) => {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("topic"),
+                        drop_event: true,
+                    });
+                    finalizers.update_status(EventStatus::Errored);
+                    continue;
+                }
+            };
+
+            self.transformer.transform(&mut event);
+
+            let event_byte_size = event.size_of();
+
+            let mut bytes = BytesMut::new();
+            if self.encoder.encode(event, &mut bytes).is_err() {
+                // Error is handled by `Encoder`.
+                finalizers.update_status(EventStatus::Errored);
+                continue;
+            }
+
+            // The message is considered delivered once the event loop accepts it, which then
+            // retries it until the broker acknowledges it according to the quality of service.
+            match client
+                .publish(topic, self.quality_of_service, self.retain, bytes.to_vec())
+                .await
+            {
+                Err(error) => {
+                    finalizers.update_status(EventStatus::Errored);
+
+                    emit!(MqttClientError { error });
+                }
+                Ok(()) => {
+                    finalizers.update_status(EventStatus::Delivered);
+
+                    emit!(EventsSent {
+                        byte_size: event_byte_size,
+                        count: 1,
+                        output: None
+                    });
+                    emit!(BytesSent {
+                        byte_size: bytes.len(),
+                        protocol: "tcp"
+                    });
+                }
+            }
+        }
+
+        // The disconnect request is queued behind the pending messages, so the event loop sends
+        // them before it stops.
+        if let Err(error) = client.disconnect().await {
+            emit!(MqttClientError { error });
+        }
+        if event_loop.await.is_err() {
+            error!(message = "MQTT event loop panicked.");
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MqttSinkConfig>();
+    }
+
+    #[test]
+    fn parses_quality_of_service() {
+        let config: MqttSinkConfig = toml::from_str(
+            r#"
+            host = "localhost"
+            topic = "vector"
+            quality_of_service = "exactly_once"
+            encoding.codec = "json"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.quality_of_service, MqttQualityOfService::ExactlyOnce);
+        assert_eq!(QoS::from(config.quality_of_service), QoS::ExactlyOnce);
+    }
+}
+
+#[cfg(feature = "mqtt-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use codecs::TextSerializerConfig;
+
+    use super::*;
+    use crate::{
+        sinks::VectorSink,
+        test_util::{
+            components::{run_and_assert_sink_compliance, SINK_TAGS},
+            random_lines_with_stream, random_string, trace_init,
+        },
+    };
+
+    fn mqtt_host() -> String {
+        std::env::var("MQTT_HOST").unwrap_or_else(|_| String::from("localhost"))
+    }
+
+    fn config(topic: &str) -> MqttSinkConfig {
+        MqttSinkConfig {
+            host: mqtt_host(),
+            port: default_port(),
+            user: None,
+            password: None,
+            client_id: format!("vector-{}", random_string(10)),
+            keep_alive: default_keep_alive(),
+            clean_session: true,
+            tls: None,
+            topic: topic.into(),
+            quality_of_service: MqttQualityOfService::AtLeastOnce,
+            retain: false,
+            encoding: TextSerializerConfig::new().into(),
+            acknowledgements: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn healthcheck_succeeds() {
+        trace_init();
+
+        let options = config("vector").options().unwrap();
+        healthcheck(options).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn publishes_messages() {
+        trace_init();
+
+        let topic = format!("test-{}", random_string(10));
+        let config = config(&topic);
+
+        // Subscribe with a separate client before publishing.
+        let mut options =
+            MqttOptions::new(format!("test-{}", random_string(10)), mqtt_host(), 1883);
+        options.set_clean_session(true);
+        let (consumer, mut event_loop) = AsyncClient::new(options, 10);
+        consumer.subscribe(&topic, QoS::AtLeastOnce).await.unwrap();
+        loop {
+            if let MqttEvent::Incoming(Packet::SubAck(_)) = event_loop.poll().await.unwrap() {
+                break;
+            }
+        }
+
+        let sink = VectorSink::from_event_streamsink(MqttSink::new(&config).unwrap());
+        let num_events = 100;
+        let (input, events) = random_lines_with_stream(100, num_events, None);
+        run_and_assert_sink_compliance(sink, events, &SINK_TAGS).await;
+
+        let mut output = Vec::new();
+        while output.len() < num_events {
+            let event = tokio::time::timeout(Duration::from_secs(10), event_loop.poll())
+                .await
+                .expect("timed out waiting for messages")
+                .unwrap();
+            if let MqttEvent::Incoming(Packet::Publish(publish)) = event {
+                assert_eq!(publish.topic, topic);
+                output.push(String::from_utf8_lossy(&publish.payload).to_string());
+            }
+        }
+
+        assert_eq!(output, input);
+    }
+}
@@ -1,14 +1,16 @@
-use std::convert::TryFrom;
+use std::{collections::HashMap, convert::TryFrom, time::Duration};
 
 use async_trait::async_trait;
-use bytes::BytesMut;
+use bytes::{Bytes, BytesMut};
 use codecs::JsonSerializerConfig;
 use futures::{stream::BoxStream, FutureExt, StreamExt, TryFutureExt};
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
+use tokio::time::Instant;
 use tokio_util::codec::Encoder as _;
+use uuid::Uuid;
 use vector_common::internal_event::{BytesSent, EventsSent};
-use vector_core::ByteSizeOf;
+use vector_core::{stream::BatcherSettings, ByteSizeOf};
 
 use crate::{
     codecs::{Encoder, EncodingConfig, Transformer},
@@ -16,14 +18,23 @@ use crate::{
         AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext,
         SinkDescription,
     },
-    event::{Event, EventStatus, Finalizable},
-    internal_events::{NatsEventSendError, TemplateRenderingError},
-    nats::{from_tls_auth_config, NatsAuthConfig, NatsConfigError},
-    sinks::util::StreamSink,
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
+    internal_events::{NatsEventSendError, NatsPublishAckError, TemplateRenderingError},
+    nats::{
+        from_tls_auth_config, parse_jetstream_response, JetStreamError, NatsAuthConfig,
+        NatsConfigError,
+    },
+    sinks::util::{batch::BatchError, BatchConfig, SinkBatchSettings, SinkBuilderExt, StreamSink},
     template::{Template, TemplateParseError},
     tls::TlsEnableableConfig,
 };
 
+/// The header JetStream identifies messages by to discard the duplicates.
+const MSG_ID_HEADER: &str = "Nats-Msg-Id";
+
+/// How long to wait before publishing the messages not acknowledged by JetStream again.
+const REPUBLISH_DELAY: Duration = Duration::from_secs(1);
+
 #[derive(Debug, Snafu)]
 enum BuildError {
     #[snafu(display("invalid encoding: {}", source))]
@@ -36,6 +47,21 @@ enum BuildError {
     Config { source: NatsConfigError },
     #[snafu(display("NATS Connect Error: {}", source))]
     Connect { source: std::io::Error },
+    #[snafu(display("NATS Subscribe Error: {}", source))]
+    Subscribe { source: std::io::Error },
+    #[snafu(display("invalid message ID template: {}", source))]
+    MessageIdTemplate { source: TemplateParseError },
+    #[snafu(display("invalid batch settings: {}", source))]
+    Batch { source: BatchError },
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NatsJetStreamDefaultBatchSettings;
+
+impl SinkBatchSettings for NatsJetStreamDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(100);
+    const MAX_BYTES: Option<usize> = None;
+    const TIMEOUT_SECS: f64 = 1.0;
 }
 
 /**
@@ -58,12 +84,42 @@ pub struct NatsSinkConfig {
     url: String,
     tls: Option<TlsEnableableConfig>,
     auth: Option<NatsAuthConfig>,
+    jetstream: Option<NatsSinkJetStreamConfig>,
+}
+
+/// Publishes the messages to JetStream, delivering the events once the stream acknowledged their
+/// message.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct NatsSinkJetStreamConfig {
+    /// The ID of each message, which JetStream discards the messages published again with within
+    /// the duplicate window of the stream.
+    ///
+    /// By default, a random ID is generated for each message, and reused when it's published again.
+    message_id: Option<String>,
+    /// The duplicate window of the streams published to, in seconds, during which the messages not
+    /// acknowledged are published again.
+    #[serde(default = "default_duplicate_window_secs")]
+    duplicate_window_secs: u64,
+    /// How long to wait for the acknowledgements of the messages, in seconds.
+    #[serde(default = "default_ack_timeout_secs")]
+    ack_timeout_secs: u64,
+    #[serde(default)]
+    batch: BatchConfig<NatsJetStreamDefaultBatchSettings>,
 }
 
 fn default_name() -> String {
     String::from("vector")
 }
 
+const fn default_duplicate_window_secs() -> u64 {
+    120 // default in the NATS server
+}
+
+const fn default_ack_timeout_secs() -> u64 {
+    5
+}
+
 inventory::submit! {
     SinkDescription::new::<NatsSinkConfig>("nats")
 }
@@ -78,6 +134,7 @@ impl GenerateConfig for NatsSinkConfig {
             subject: "from.vector".into(),
             tls: None,
             url: "nats://127.0.0.1:4222".into(),
+            jetstream: None,
         })
         .unwrap()
     }
@@ -133,6 +190,7 @@ pub struct NatsSink {
     encoder: Encoder<()>,
     connection: nats::asynk::Connection,
     subject: Template,
+    jetstream: Option<JetStreamPublisher>,
 }
 
 impl NatsSink {
@@ -141,19 +199,97 @@ impl NatsSink {
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.build().context(EncodingSnafu)?;
         let encoder = Encoder::<()>::new(serializer);
+        let jetstream = match config.jetstream {
+            Some(jetstream) => Some(JetStreamPublisher::new(&connection, jetstream).await?),
+            None => None,
+        };
 
         Ok(NatsSink {
             connection,
             transformer,
             encoder,
             subject: Template::try_from(config.subject).context(SubjectTemplateSnafu)?,
+            jetstream,
+        })
+    }
+
+    /// Encodes the event into a message, or updates the status of the event if that fails.
+    fn encode_message(
+        &mut self,
+        mut event: Event,
+        message_id: Option<&Template>,
+    ) -> Option<JetStreamMessage> {
+        let finalizers = event.take_finalizers();
+
+        let subject = match self.subject.render_string(&event) {
+            Ok(subject) => subject,
+            Err(error) => {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("subject"),
+                    drop_event: true,
+                });
+                finalizers.update_status(EventStatus::Errored);
+                return None;
+            }
+        };
+        let id = match message_id.map(|template| template.render_string(&event)) {
+            Some(Ok(id)) => id,
+            Some(Err(error)) => {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("message_id"),
+                    drop_event: true,
+                });
+                finalizers.update_status(EventStatus::Errored);
+                return None;
+            }
+            None => Uuid::new_v4().to_string(),
+        };
+
+        self.transformer.transform(&mut event);
+        let event_byte_size = event.size_of();
+        let mut bytes = BytesMut::new();
+        if self.encoder.encode(event, &mut bytes).is_err() {
+            // Error is handled by `Encoder`.
+            finalizers.update_status(EventStatus::Errored);
+            return None;
+        }
+
+        Some(JetStreamMessage {
+            subject,
+            id,
+            payload: bytes.freeze(),
+            event_byte_size,
+            finalizers,
         })
     }
+
+    async fn run_jetstream(
+        mut self: Box<Self>,
+        mut publisher: JetStreamPublisher,
+        input: BoxStream<'_, Event>,
+    ) -> Result<(), ()> {
+        let mut batches = input.batched(publisher.batch_settings.into_byte_size_config());
+        while let Some(events) = batches.next().await {
+            let messages = events
+                .into_iter()
+                .filter_map(|event| self.encode_message(event, publisher.message_id.as_ref()))
+                .collect();
+            publisher.publish(&self.connection, messages).await;
+        }
+
+        Ok(())
+    }
 }
 
 #[async_trait]
 impl StreamSink<Event> for NatsSink {
     async fn run(mut self: Box<Self>, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        if let Some(publisher) = self.jetstream.take() {
+            return self.run_jetstream(publisher, input).await;
+        }
+
         while let Some(mut event) = input.next().await {
             let finalizers = event.take_finalizers();
 
@@ -207,6 +343,162 @@ impl StreamSink<Event> for NatsSink {
     }
 }
 
+/// A message published to JetStream.
+struct JetStreamMessage {
+    subject: String,
+    id: String,
+    payload: Bytes,
+    event_byte_size: usize,
+    finalizers: EventFinalizers,
+}
+
+/// The acknowledgement of a message stored by JetStream.
+#[derive(Deserialize)]
+struct PubAck {
+    #[allow(dead_code)]
+    stream: String,
+    #[serde(default)]
+    duplicate: bool,
+}
+
+/// Publishes the batches of messages to JetStream, publishing the messages not acknowledged again
+/// within the duplicate window with the same ID.
+struct JetStreamPublisher {
+    message_id: Option<Template>,
+    duplicate_window: Duration,
+    ack_timeout: Duration,
+    batch_settings: BatcherSettings,
+    /// The prefix of the reply subjects the acknowledgements are received on.
+    inbox: String,
+    acks: nats::asynk::Subscription,
+    next_reply: u64,
+}
+
+impl JetStreamPublisher {
+    async fn new(
+        connection: &nats::asynk::Connection,
+        config: NatsSinkJetStreamConfig,
+    ) -> Result<Self, BuildError> {
+        let inbox = format!("_INBOX.{}", Uuid::new_v4());
+        let acks = connection
+            .subscribe(&format!("{}.*", inbox))
+            .await
+            .context(SubscribeSnafu)?;
+        let message_id = config
+            .message_id
+            .map(Template::try_from)
+            .transpose()
+            .context(MessageIdTemplateSnafu)?;
+
+        Ok(Self {
+            message_id,
+            duplicate_window: Duration::from_secs(config.duplicate_window_secs),
+            ack_timeout: Duration::from_secs(config.ack_timeout_secs),
+            batch_settings: config.batch.into_batcher_settings().context(BatchSnafu)?,
+            inbox,
+            acks,
+            next_reply: 0,
+        })
+    }
+
+    /// Publishes the messages until they are all acknowledged, failed, or published for as long
+    /// as the duplicate window, and updates the status of their events.
+    async fn publish(
+        &mut self,
+        connection: &nats::asynk::Connection,
+        messages: Vec<JetStreamMessage>,
+    ) {
+        let deadline = Instant::now() + self.duplicate_window;
+        let mut unacked: Vec<JetStreamMessage> = messages;
+
+        loop {
+            let mut in_flight = HashMap::new();
+            for (index, message) in unacked.iter().enumerate() {
+                let reply = format!("{}.{}", self.inbox, self.next_reply);
+                self.next_reply += 1;
+                let mut headers = nats::header::HeaderMap::new();
+                headers.insert(MSG_ID_HEADER, message.id.as_str());
+                match connection
+                    .publish_with_reply_or_headers(
+                        &message.subject,
+                        Some(&reply),
+                        Some(&headers),
+                        &message.payload,
+                    )
+                    .await
+                {
+                    Ok(()) => {
+                        in_flight.insert(reply, index);
+                    }
+                    Err(error) => emit!(NatsEventSendError { error }),
+                }
+            }
+
+            let mut results = HashMap::new();
+            let acks_deadline = Instant::now() + self.ack_timeout;
+            while results.len() < in_flight.len() {
+                let ack = match tokio::time::timeout_at(acks_deadline, self.acks.next()).await {
+                    Ok(Some(ack)) => ack,
+                    Ok(None) | Err(_) => break,
+                };
+                if let Some(index) = in_flight.get(&ack.subject) {
+                    results.insert(*index, parse_jetstream_response::<PubAck>(&ack.data));
+                }
+            }
+
+            // Only the messages not acknowledged in time are published again, as the ones
+            // acknowledged with an error would fail again.
+            let mut retry = Vec::new();
+            for (index, message) in unacked.into_iter().enumerate() {
+                match results.remove(&index) {
+                    Some(Ok(ack)) => {
+                        if ack.duplicate {
+                            debug!(message = "Message was already stored by JetStream.", id = %message.id);
+                        }
+                        finalize(message, Ok(()));
+                    }
+                    Some(Err(error)) => finalize(message, Err(error)),
+                    None => retry.push(message),
+                }
+            }
+
+            if retry.is_empty() {
+                break;
+            }
+            if Instant::now() + REPUBLISH_DELAY >= deadline {
+                for message in retry {
+                    finalize(message, Err(JetStreamError::Timeout));
+                }
+                break;
+            }
+            tokio::time::sleep(REPUBLISH_DELAY).await;
+            unacked = retry;
+        }
+    }
+}
+
+/// Updates the status of the events of the message according to its acknowledgement.
+fn finalize(message: JetStreamMessage, result: Result<(), JetStreamError>) {
+    match result {
+        Ok(()) => {
+            message.finalizers.update_status(EventStatus::Delivered);
+            emit!(EventsSent {
+                byte_size: message.event_byte_size,
+                count: 1,
+                output: None
+            });
+            emit!(BytesSent {
+                byte_size: message.payload.len(),
+                protocol: "tcp"
+            });
+        }
+        Err(error) => {
+            message.finalizers.update_status(EventStatus::Errored);
+            emit!(NatsPublishAckError { error, count: 1 });
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -215,6 +507,37 @@ mod tests {
     fn generate_config() {
         crate::test_util::test_generate_config::<NatsSinkConfig>();
     }
+
+    #[test]
+    fn parses_jetstream_config() {
+        let config: NatsSinkConfig = toml::from_str(
+            r#"
+            url = "nats://127.0.0.1:4222"
+            subject = "logs.{{ service }}"
+            encoding.codec = "json"
+            jetstream.message_id = "{{ id }}"
+            jetstream.batch.max_events = 10
+            "#,
+        )
+        .unwrap();
+
+        let jetstream = config.jetstream.unwrap();
+        assert_eq!(jetstream.message_id.as_deref(), Some("{{ id }}"));
+        assert_eq!(jetstream.duplicate_window_secs, 120);
+        assert_eq!(jetstream.ack_timeout_secs, 5);
+        assert_eq!(jetstream.batch.max_events, Some(10));
+    }
+
+    #[test]
+    fn parses_pub_ack() {
+        let ack =
+            parse_jetstream_response::<PubAck>(br#"{"stream":"logs","seq":42,"duplicate":true}"#)
+                .unwrap();
+        assert!(ack.duplicate);
+
+        let ack = parse_jetstream_response::<PubAck>(br#"{"stream":"logs","seq":43}"#).unwrap();
+        assert!(!ack.duplicate);
+    }
 }
 
 #[cfg(feature = "nats-integration-tests")]
@@ -289,6 +612,7 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: None,
             tls: None,
             auth: None,
         };
@@ -315,6 +639,7 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: None,
             tls: None,
             auth: Some(NatsAuthConfig::UserPassword {
                 user_password: NatsAuthUserPassword {
@@ -343,6 +668,7 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: None,
             tls: None,
             auth: Some(NatsAuthConfig::UserPassword {
                 user_password: NatsAuthUserPassword {
@@ -374,6 +700,7 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: None,
             tls: None,
             auth: Some(NatsAuthConfig::Token {
                 token: NatsAuthToken {
@@ -404,6 +731,7 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: None,
             tls: None,
             auth: Some(NatsAuthConfig::Token {
                 token: NatsAuthToken {
@@ -434,6 +762,7 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: None,
             tls: None,
             auth: Some(NatsAuthConfig::Nkey {
                 nkey: NatsAuthNKey {
@@ -465,6 +794,7 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: None,
             tls: None,
             auth: Some(NatsAuthConfig::Nkey {
                 nkey: NatsAuthNKey {
@@ -496,6 +826,7 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: None,
             tls: Some(TlsEnableableConfig {
                 enabled: Some(true),
                 options: TlsConfig {
@@ -528,6 +859,7 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: None,
             tls: None,
             auth: None,
         };
@@ -554,6 +886,7 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: None,
             tls: Some(TlsEnableableConfig {
                 enabled: Some(true),
                 options: TlsConfig {
@@ -588,6 +921,7 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: None,
             tls: Some(TlsEnableableConfig {
                 enabled: Some(true),
                 options: TlsConfig {
@@ -620,6 +954,7 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: None,
             tls: Some(TlsEnableableConfig {
                 enabled: Some(true),
                 options: TlsConfig {
@@ -656,6 +991,7 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: None,
             tls: Some(TlsEnableableConfig {
                 enabled: Some(true),
                 options: TlsConfig {
@@ -677,4 +1013,64 @@ mod integration_tests {
             r
         );
     }
+
+    #[tokio::test]
+    async fn nats_jetstream_publish() {
+        trace_init();
+
+        let stream = format!("test-{}", random_string(10));
+        let subject = format!("test.{}", random_string(10));
+        let url =
+            std::env::var("NATS_ADDRESS").unwrap_or_else(|_| String::from("nats://localhost:4222"));
+
+        let conf = NatsSinkConfig {
+            acknowledgements: Default::default(),
+            encoding: TextSerializerConfig::new().into(),
+            connection_name: "".to_owned(),
+            subject: subject.clone(),
+            url,
+            jetstream: Some(NatsSinkJetStreamConfig {
+                message_id: Some("{{ message }}".into()),
+                duplicate_window_secs: 120,
+                ack_timeout_secs: 5,
+                batch: Default::default(),
+            }),
+            tls: None,
+            auth: None,
+        };
+
+        let connection = conf.connect().await.unwrap();
+        let _: serde_json::Value = crate::nats::jetstream_request(
+            &connection,
+            &format!("$JS.API.STREAM.CREATE.{}", stream),
+            &serde_json::json!({ "name": stream, "subjects": [subject] }),
+        )
+        .await
+        .unwrap();
+
+        let sink = NatsSink::new(conf.clone()).await.unwrap();
+        let sink = VectorSink::from_event_streamsink(sink);
+        let num_events = 100;
+        let (input, events) = random_lines_with_stream(100, num_events, None);
+        // The messages published twice are discarded by JetStream, as they have the same ID.
+        let events = events.chain(futures::stream::iter(
+            input
+                .iter()
+                .take(10)
+                .map(|line| {
+                    crate::event::EventArray::from(crate::event::LogEvent::from(line.as_str()))
+                })
+                .collect::<Vec<_>>(),
+        ));
+        run_and_assert_sink_compliance(sink, events, &SINK_TAGS).await;
+
+        let info: serde_json::Value = crate::nats::jetstream_request(
+            &connection,
+            &format!("$JS.API.STREAM.INFO.{}", stream),
+            &serde_json::json!({}),
+        )
+        .await
+        .unwrap();
+        assert_eq!(info["state"]["messages"], num_events);
+    }
 }
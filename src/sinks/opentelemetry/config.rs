@@ -0,0 +1,170 @@
+use futures::future::join_all;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use tonic::metadata::{Ascii, MetadataKey, MetadataMap, MetadataValue};
+use tower::ServiceBuilder;
+
+use crate::{
+    config::{
+        AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext, SinkDescription,
+        SinkHealthcheckOptions,
+    },
+    sinks::{
+        opentelemetry::{
+            service::{ExportRequest, OpentelemetryResponse, OpentelemetryService},
+            sink::OpentelemetrySink,
+            OpentelemetrySinkError,
+        },
+        util::{
+            retries::RetryLogic, BatchConfig, RealtimeEventBasedDefaultBatchSettings,
+            ServiceBuilderExt, TowerRequestConfig,
+        },
+        vector::v2::{new_client, with_default_scheme},
+        Healthcheck, VectorSink,
+    },
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+};
+
+/// The compression applied to the requests sent to the OTLP endpoint.
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Eq, PartialEq, Serialize)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OpentelemetryCompression {
+    /// Requests are sent uncompressed.
+    #[derivative(Default)]
+    None,
+
+    /// Requests are compressed with gzip.
+    Gzip,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct OpentelemetrySinkConfig {
+    /// The endpoint of the OTLP/gRPC receiver, such as `http://localhost:4317`.
+    endpoint: String,
+    #[serde(default)]
+    compression: OpentelemetryCompression,
+    /// Headers sent as gRPC metadata with each request, such as authentication tokens.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    headers: IndexMap<String, String>,
+    #[serde(default)]
+    pub batch: BatchConfig<RealtimeEventBasedDefaultBatchSettings>,
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+    #[serde(default)]
+    tls: Option<TlsEnableableConfig>,
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+inventory::submit! {
+    SinkDescription::new::<OpentelemetrySinkConfig>("opentelemetry")
+}
+
+impl GenerateConfig for OpentelemetrySinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"endpoint = "http://localhost:4317""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "opentelemetry")]
+impl SinkConfig for OpentelemetrySinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        let uri = with_default_scheme(&self.endpoint, tls.is_tls())?;
+        let client = new_client(&tls, cx.proxy())?;
+        let metadata = build_metadata(&self.headers)?;
+
+        let service = OpentelemetryService::new(client, uri, self.compression, metadata);
+        let healthcheck = healthcheck(service.clone(), cx.healthcheck);
+
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+        let batch_settings = self.batch.into_batcher_settings()?;
+        let service = ServiceBuilder::new()
+            .settings(request_settings, OpentelemetryRetryLogic)
+            .service(service);
+
+        let sink = OpentelemetrySink {
+            batch_settings,
+            service,
+        };
+
+        Ok((
+            VectorSink::from_event_streamsink(sink),
+            Box::pin(healthcheck),
+        ))
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "opentelemetry"
+    }
+
+    fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
+        Some(&self.acknowledgements)
+    }
+}
+
+/// Builds the gRPC metadata sent with each request from the configured headers.
+pub(crate) fn build_metadata(headers: &IndexMap<String, String>) -> crate::Result<MetadataMap> {
+    let mut metadata = MetadataMap::new();
+    for (name, value) in headers {
+        let key = MetadataKey::from_bytes(name.to_lowercase().as_bytes())
+            .map_err(|_| OpentelemetrySinkError::InvalidHeader { name: name.clone() })?;
+        let value = value
+            .parse::<MetadataValue<Ascii>>()
+            .map_err(|_| OpentelemetrySinkError::InvalidHeader { name: name.clone() })?;
+        metadata.insert(key, value);
+    }
+    Ok(metadata)
+}
+
+/// Checks that the endpoint accepts any of the logs, metrics, or traces export requests.
+///
+/// OTLP has no health service, so empty requests are exported instead.
+async fn healthcheck(
+    service: OpentelemetryService,
+    options: SinkHealthcheckOptions,
+) -> crate::Result<()> {
+    if !options.enabled {
+        return Ok(());
+    }
+
+    let requests = [
+        ExportRequest::Logs(Default::default()),
+        ExportRequest::Metrics(Default::default()),
+        ExportRequest::Traces(Default::default()),
+    ];
+    let results = join_all(
+        requests
+            .into_iter()
+            .map(|request| service.clone().export(request)),
+    )
+    .await;
+
+    match results.into_iter().find(Result::is_ok) {
+        Some(_) => Ok(()),
+        None => Err(Box::new(OpentelemetrySinkError::Health)),
+    }
+}
+
+#[derive(Debug, Clone)]
+struct OpentelemetryRetryLogic;
+
+impl RetryLogic for OpentelemetryRetryLogic {
+    type Error = OpentelemetrySinkError;
+    type Response = OpentelemetryResponse;
+
+    fn is_retriable_error(&self, err: &Self::Error) -> bool {
+        err.is_retriable()
+    }
+}
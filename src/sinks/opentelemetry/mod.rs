@@ -0,0 +1,47 @@
+use snafu::Snafu;
+
+mod config;
+mod service;
+mod sink;
+#[cfg(test)]
+mod tests;
+
+pub(crate) use config::build_metadata;
+pub use config::{OpentelemetryCompression, OpentelemetrySinkConfig};
+pub(crate) use service::{ExportRequest, OpentelemetryService};
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub))]
+pub enum OpentelemetrySinkError {
+    #[snafu(display("Request failed: {}", source))]
+    Request { source: tonic::Status },
+
+    #[snafu(display("OTLP endpoint unhealthy"))]
+    Health,
+
+    #[snafu(display("Invalid header: {}", name))]
+    InvalidHeader { name: String },
+}
+
+impl OpentelemetrySinkError {
+    pub(crate) fn is_retriable(&self) -> bool {
+        use tonic::Code::*;
+
+        match self {
+            // The codes OTLP receivers respond with to retriable errors, see
+            //
+            // <https://github.com/open-telemetry/opentelemetry-specification/blob/main/specification/protocol/otlp.md#failures>
+            Self::Request { source } => matches!(
+                source.code(),
+                Cancelled
+                    | DeadlineExceeded
+                    | ResourceExhausted
+                    | Aborted
+                    | OutOfRange
+                    | Unavailable
+                    | DataLoss
+            ),
+            _ => true,
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use std::task::{Context, Poll};
+
+use futures::future::BoxFuture;
+use http::Uri;
+use hyper::client::HttpConnector;
+use hyper_openssl::HttpsConnector;
+use hyper_proxy::ProxyConnector;
+use prost::Message;
+use tonic::{body::BoxBody, metadata::MetadataMap};
+use vector_core::{internal_event::EventsSent, stream::DriverResponse};
+
+use crate::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    internal_events::EndpointBytesSent,
+    opentelemetry::{
+        LogService::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
+        MetricsService::{
+            metrics_service_client::MetricsServiceClient, ExportMetricsServiceRequest,
+        },
+        TraceService::{trace_service_client::TraceServiceClient, ExportTraceServiceRequest},
+    },
+    sinks::{
+        opentelemetry::{config::OpentelemetryCompression, OpentelemetrySinkError},
+        util::uri,
+        vector::v2::HyperSvc,
+    },
+    Error,
+};
+
+/// The export request of one of the OTLP services.
+#[derive(Clone, Debug)]
+pub enum ExportRequest {
+    Logs(ExportLogsServiceRequest),
+    Metrics(ExportMetricsServiceRequest),
+    Traces(ExportTraceServiceRequest),
+}
+
+impl ExportRequest {
+    fn encoded_len(&self) -> usize {
+        match self {
+            Self::Logs(request) => request.encoded_len(),
+            Self::Metrics(request) => request.encoded_len(),
+            Self::Traces(request) => request.encoded_len(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct OpentelemetryRequest {
+    pub payload: ExportRequest,
+    pub finalizers: EventFinalizers,
+    pub events_count: usize,
+    pub events_byte_size: usize,
+}
+
+impl Finalizable for OpentelemetryRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+pub struct OpentelemetryResponse {
+    events_count: usize,
+    events_byte_size: usize,
+}
+
+impl DriverResponse for OpentelemetryResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> EventsSent {
+        EventsSent {
+            count: self.events_count,
+            byte_size: self.events_byte_size,
+            output: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct OpentelemetryService {
+    logs: LogsServiceClient<HyperSvc>,
+    metrics: MetricsServiceClient<HyperSvc>,
+    traces: TraceServiceClient<HyperSvc>,
+    metadata: MetadataMap,
+    protocol: String,
+    endpoint: String,
+}
+
+impl OpentelemetryService {
+    pub fn new(
+        hyper_client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
+        uri: Uri,
+        compression: OpentelemetryCompression,
+        metadata: MetadataMap,
+    ) -> Self {
+        let (protocol, endpoint) = uri::protocol_endpoint(uri.clone());
+        let service = HyperSvc::new(uri, hyper_client);
+        let mut logs = LogsServiceClient::new(service.clone());
+        let mut metrics = MetricsServiceClient::new(service.clone());
+        let mut traces = TraceServiceClient::new(service);
+
+        if compression == OpentelemetryCompression::Gzip {
+            logs = logs.send_gzip();
+            metrics = metrics.send_gzip();
+            traces = traces.send_gzip();
+        }
+        Self {
+            logs,
+            metrics,
+            traces,
+            metadata,
+            protocol,
+            endpoint,
+        }
+    }
+
+    /// Exports the request to the service of its type.
+    pub async fn export(mut self, request: ExportRequest) -> Result<(), OpentelemetrySinkError> {
+        let result = match request {
+            ExportRequest::Logs(request) => {
+                let request = self.request(request);
+                self.logs.export(request).await.map(drop)
+            }
+            ExportRequest::Metrics(request) => {
+                let request = self.request(request);
+                self.metrics.export(request).await.map(drop)
+            }
+            ExportRequest::Traces(request) => {
+                let request = self.request(request);
+                self.traces.export(request).await.map(drop)
+            }
+        };
+        result.map_err(|source| OpentelemetrySinkError::Request { source })
+    }
+
+    fn request<T>(&self, message: T) -> tonic::Request<T> {
+        let mut request = tonic::Request::new(message);
+        *request.metadata_mut() = self.metadata.clone();
+        request
+    }
+}
+
+impl tower::Service<OpentelemetryRequest> for OpentelemetryService {
+    type Response = OpentelemetryResponse;
+    type Error = Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Readiness of the clients is awaited by the `export` calls, as done by the `vector` sink.
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: OpentelemetryRequest) -> Self::Future {
+        let service = self.clone();
+        let byte_size = request.payload.encoded_len();
+        let OpentelemetryRequest {
+            payload,
+            events_count,
+            events_byte_size,
+            ..
+        } = request;
+
+        Box::pin(async move {
+            let protocol = service.protocol.clone();
+            let endpoint = service.endpoint.clone();
+            service.export(payload).await?;
+            emit!(EndpointBytesSent {
+                byte_size,
+                protocol: &protocol,
+                endpoint: &endpoint,
+            });
+            Ok(OpentelemetryResponse {
+                events_count,
+                events_byte_size,
+            })
+        })
+    }
+}
@@ -0,0 +1,104 @@
+use std::fmt;
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use tower::Service;
+use vector_core::{
+    partition::Partitioner,
+    stream::{BatcherSettings, DriverResponse},
+    ByteSizeOf,
+};
+
+use crate::{
+    event::{Event, EventFinalizers, Finalizable},
+    sinks::{
+        opentelemetry::service::{ExportRequest, OpentelemetryRequest},
+        util::{SinkBuilderExt, StreamSink},
+    },
+};
+
+/// The type of the events, as logs, metrics, and traces are exported to different services.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum EventType {
+    Log,
+    Metric,
+    Trace,
+}
+
+struct EventTypePartitioner;
+
+impl Partitioner for EventTypePartitioner {
+    type Item = Event;
+    type Key = EventType;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        match item {
+            Event::Log(_) => EventType::Log,
+            Event::Metric(_) => EventType::Metric,
+            Event::Trace(_) => EventType::Trace,
+        }
+    }
+}
+
+pub struct OpentelemetrySink<S> {
+    pub batch_settings: BatcherSettings,
+    pub service: S,
+}
+
+impl<S> OpentelemetrySink<S>
+where
+    S: Service<OpentelemetryRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        input
+            .batched_partitioned(EventTypePartitioner, self.batch_settings)
+            .map(|(event_type, events)| build_request(event_type, events))
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+fn build_request(event_type: EventType, mut events: Vec<Event>) -> OpentelemetryRequest {
+    let mut finalizers = EventFinalizers::default();
+    let mut events_byte_size = 0;
+    for event in &mut events {
+        finalizers.merge(event.take_finalizers());
+        events_byte_size += event.size_of();
+    }
+    let events_count = events.len();
+
+    let events = events.into_iter();
+    let payload = match event_type {
+        EventType::Log => ExportRequest::Logs(events.filter_map(Event::try_into_log).collect()),
+        EventType::Metric => {
+            ExportRequest::Metrics(events.filter_map(Event::try_into_metric).collect())
+        }
+        EventType::Trace => {
+            ExportRequest::Traces(events.filter_map(Event::try_into_trace).collect())
+        }
+    };
+
+    OpentelemetryRequest {
+        payload,
+        finalizers,
+        events_count,
+        events_byte_size,
+    }
+}
+
+#[async_trait]
+impl<S> StreamSink<Event> for OpentelemetrySink<S>
+where
+    S: Service<OpentelemetryRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
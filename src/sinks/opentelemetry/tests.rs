@@ -0,0 +1,164 @@
+use bytes::{BufMut, Bytes, BytesMut};
+use futures::{channel::mpsc, stream, StreamExt};
+use http::request::Parts;
+use hyper::Method;
+use prost::Message;
+use vector_core::event::{BatchNotifier, BatchStatus};
+
+use super::*;
+use crate::{
+    config::{SinkConfig, SinkContext},
+    event::{Event, LogEvent, Metric, MetricKind, MetricValue},
+    opentelemetry::{
+        Common::any_value,
+        LogService::{ExportLogsServiceRequest, ExportLogsServiceResponse},
+        MetricsService::ExportMetricsServiceRequest,
+    },
+    sinks::util::test::build_test_server_generic,
+    test_util::{
+        components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+        next_addr,
+    },
+};
+
+// one byte for the compression flag plus four bytes for the length
+const GRPC_HEADER_SIZE: usize = 5;
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<OpentelemetrySinkConfig>();
+}
+
+/// Starts a server answering every request with the given gRPC status and an empty response.
+fn start_server(
+    status: &'static str,
+) -> (
+    std::net::SocketAddr,
+    mpsc::Receiver<(Parts, Bytes)>,
+    stream_cancel::Trigger,
+) {
+    let addr = next_addr();
+    let (rx, trigger, server) = build_test_server_generic(addr, move || {
+        // The export responses of all services are empty messages, which encode the same.
+        hyper::Response::builder()
+            .header("grpc-status", status)
+            .header("content-type", "application/grpc")
+            .body(hyper::Body::from(encode_body(ExportLogsServiceResponse {})))
+            .unwrap()
+    });
+    tokio::spawn(server);
+    (addr, rx, trigger)
+}
+
+#[tokio::test]
+async fn exports_logs_and_metrics() {
+    let (addr, rx, trigger) = start_server("0");
+    let config = format!(
+        r#"endpoint = "http://{}"
+        headers.Authorization = "Bearer token""#,
+        addr
+    );
+    let config: OpentelemetrySinkConfig = toml::from_str(&config).unwrap();
+    let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let log = LogEvent::from("hello").with_batch_notifier(&batch);
+    let metric = Metric::new(
+        "requests",
+        MetricKind::Incremental,
+        MetricValue::Counter { value: 1.0 },
+    )
+    .with_batch_notifier(&batch);
+    drop(batch);
+    let events = stream::iter([Event::from(log), Event::from(metric)]);
+
+    run_and_assert_sink_compliance(sink, events, &HTTP_SINK_TAGS).await;
+    drop(trigger);
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+    let mut requests = rx.collect::<Vec<_>>().await;
+    requests.sort_by(|(a, _), (b, _)| a.uri.path().cmp(b.uri.path()));
+    assert_eq!(requests.len(), 2);
+    for (parts, _) in &requests {
+        assert_eq!(Method::POST, parts.method);
+        assert_eq!(parts.headers["authorization"], "Bearer token");
+    }
+
+    let (parts, body) = &requests[0];
+    assert_eq!(
+        parts.uri.path(),
+        "/opentelemetry.proto.collector.logs.v1.LogsService/Export"
+    );
+    let request = ExportLogsServiceRequest::decode(body.slice(GRPC_HEADER_SIZE..)).unwrap();
+    let record = &request.resource_logs[0].scope_logs[0].log_records[0];
+    assert_eq!(
+        record.body.clone().and_then(|body| body.value),
+        Some(any_value::Value::StringValue("hello".into()))
+    );
+
+    let (parts, body) = &requests[1];
+    assert_eq!(
+        parts.uri.path(),
+        "/opentelemetry.proto.collector.metrics.v1.MetricsService/Export"
+    );
+    let request = ExportMetricsServiceRequest::decode(body.slice(GRPC_HEADER_SIZE..)).unwrap();
+    assert_eq!(
+        request.resource_metrics[0].scope_metrics[0].metrics[0].name,
+        "requests"
+    );
+}
+
+#[tokio::test]
+async fn acknowledges_error() {
+    let (addr, _rx, trigger) = start_server("3"); // invalid argument
+    let config = format!(r#"endpoint = "http://{}""#, addr);
+    let config: OpentelemetrySinkConfig = toml::from_str(&config).unwrap();
+    let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let log = LogEvent::from("hello").with_batch_notifier(&batch);
+    drop(batch);
+
+    sink.run_events([Event::from(log)])
+        .await
+        .expect("Running sink failed");
+    drop(trigger);
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+}
+
+#[tokio::test]
+async fn healthcheck() {
+    let (addr, _rx, _trigger) = start_server("0");
+    let config = format!(r#"endpoint = "http://{}""#, addr);
+    let config: OpentelemetrySinkConfig = toml::from_str(&config).unwrap();
+    let (_, healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+    healthcheck.await.unwrap();
+
+    let (addr, _rx, _trigger) = start_server("12"); // unimplemented
+    let config = format!(r#"endpoint = "http://{}""#, addr);
+    let config: OpentelemetrySinkConfig = toml::from_str(&config).unwrap();
+    let (_, healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+    assert!(healthcheck.await.is_err());
+}
+
+#[tokio::test]
+async fn rejects_invalid_headers() {
+    let config: OpentelemetrySinkConfig = toml::from_str(
+        r#"endpoint = "http://localhost:4317"
+        headers."in valid" = "value""#,
+    )
+    .unwrap();
+    assert!(config.build(SinkContext::new_test()).await.is_err());
+}
+
+/// Encodes the message as the body of a gRPC response.
+fn encode_body<T>(msg: T) -> Bytes
+where
+    T: prost::Message,
+{
+    let mut buf = BytesMut::with_capacity(msg.encoded_len() + GRPC_HEADER_SIZE);
+    buf.put_u8(0); // compression flag, 0 means "no compression"
+    buf.put_u32(msg.encoded_len() as u32);
+    msg.encode(&mut buf).unwrap();
+    buf.freeze()
+}
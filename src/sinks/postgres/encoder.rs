@@ -0,0 +1,114 @@
+use vector_core::ByteSizeOf;
+
+use super::service::{PostgresMetadata, PostgresRequest};
+use crate::{
+    codecs::Transformer,
+    event::{Event, EventFinalizers, Finalizable, LogEvent, Value},
+};
+
+/// Builds the rows of the events, with the values of the fields mapped to the columns.
+pub(super) struct PostgresEncoder {
+    /// The paths of the fields written to the columns, in the order of the columns.
+    pub(super) fields: Vec<String>,
+    pub(super) transformer: Transformer,
+}
+
+impl PostgresEncoder {
+    pub(super) fn encode(&self, table: String, events: Vec<Event>) -> PostgresRequest {
+        let mut finalizers = EventFinalizers::default();
+        let mut byte_size = 0;
+        let count = events.len();
+
+        let rows = events
+            .into_iter()
+            .map(|mut event| {
+                finalizers.merge(event.take_finalizers());
+                byte_size += event.size_of();
+                self.transformer.transform(&mut event);
+
+                let log = into_log(event);
+                self.fields
+                    .iter()
+                    .map(|field| log.get(field.as_str()).cloned().unwrap_or(Value::Null))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        PostgresRequest {
+            table,
+            rows,
+            metadata: PostgresMetadata {
+                count,
+                byte_size,
+                finalizers,
+            },
+        }
+    }
+}
+
+/// Converts metrics to logs with the fields of their JSON representation, such as `name`,
+/// `tags.host`, or `counter.value`.
+fn into_log(event: Event) -> LogEvent {
+    match event {
+        Event::Metric(metric) => match serde_json::to_value(&metric).map(Value::from) {
+            Ok(Value::Object(fields)) => LogEvent::from(fields),
+            _ => LogEvent::default(),
+        },
+        event => event.into_log(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::metric::{Metric, MetricKind, MetricValue};
+
+    fn encoder(fields: &[&str]) -> PostgresEncoder {
+        PostgresEncoder {
+            fields: fields.iter().map(|field| field.to_string()).collect(),
+            transformer: Default::default(),
+        }
+    }
+
+    #[test]
+    fn maps_log_fields_to_columns() {
+        let mut log = LogEvent::from("hello");
+        log.insert("request.status", 200);
+
+        let request = encoder(&["message", "request.status", "missing"])
+            .encode("logs".into(), vec![log.into()]);
+
+        assert_eq!(request.table, "logs");
+        assert_eq!(request.metadata.count, 1);
+        assert_eq!(
+            request.rows,
+            vec![vec![Value::from("hello"), Value::from(200), Value::Null]]
+        );
+    }
+
+    #[test]
+    fn maps_metric_fields_to_columns() {
+        let metric = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 2.0 },
+        )
+        .with_tags(Some(
+            vec![("host".to_owned(), "a".to_owned())]
+                .into_iter()
+                .collect(),
+        ));
+
+        let request = encoder(&["name", "tags.host", "counter.value"])
+            .encode("metrics".into(), vec![metric.into()]);
+
+        assert_eq!(
+            request.rows,
+            vec![vec![
+                Value::from("requests"),
+                Value::from("a"),
+                Value::from(2.0)
+            ]]
+        );
+    }
+}
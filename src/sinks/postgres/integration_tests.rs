@@ -0,0 +1,128 @@
+use futures::stream;
+use tokio_postgres::NoTls;
+
+use super::PostgresSinkConfig;
+use crate::{
+    config::{SinkConfig, SinkContext},
+    event::{BatchNotifier, BatchStatus, Event, LogEvent},
+    test_util::{
+        components::{run_and_assert_sink_compliance, SINK_TAGS},
+        random_string, trace_init,
+    },
+};
+
+fn pg_url() -> String {
+    let host = std::env::var("PG_HOST").unwrap_or_else(|_| "localhost".into());
+    format!("postgres://vector:vector@{}/postgres", host)
+}
+
+fn config(table: &str, method: &str) -> PostgresSinkConfig {
+    toml::from_str(&format!(
+        r#"
+        endpoint = "{}"
+        table = "{}"
+        method = "{}"
+        batch.timeout_secs = 1
+
+        [columns]
+        time = "timestamp"
+        message = "message"
+        status = "status"
+        "#,
+        pg_url(),
+        table,
+        method
+    ))
+    .unwrap()
+}
+
+async fn client() -> tokio_postgres::Client {
+    let (client, connection) = tokio_postgres::connect(&pg_url(), NoTls).await.unwrap();
+    tokio::spawn(connection);
+    client
+}
+
+async fn create_table(client: &tokio_postgres::Client) -> String {
+    let table = format!("logs_{}", random_string(10).to_lowercase());
+    client
+        .batch_execute(&format!(
+            "CREATE TABLE {} (time timestamptz, message text, status integer)",
+            table
+        ))
+        .await
+        .unwrap();
+    table
+}
+
+async fn writes_logs(method: &str) {
+    trace_init();
+
+    let client = client().await;
+    let table = create_table(&client).await;
+    let (sink, healthcheck) = config(&table, method)
+        .build(SinkContext::new_test())
+        .await
+        .unwrap();
+    healthcheck.await.unwrap();
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let events = (0..10)
+        .map(|index| {
+            let mut log =
+                LogEvent::from(format!("message \"{}\"", index)).with_batch_notifier(&batch);
+            log.insert("status", index);
+            Event::from(log)
+        })
+        .collect::<Vec<_>>();
+    drop(batch);
+
+    run_and_assert_sink_compliance(sink, stream::iter(events), &SINK_TAGS).await;
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+    let rows = client
+        .query(
+            format!(
+                "SELECT message, status, time FROM {} ORDER BY status",
+                table
+            )
+            .as_str(),
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 10);
+    for (index, row) in rows.iter().enumerate() {
+        assert_eq!(row.get::<_, String>(0), format!("message \"{}\"", index));
+        assert_eq!(row.get::<_, i32>(1), index as i32);
+        assert!(row
+            .get::<_, Option<chrono::DateTime<chrono::Utc>>>(2)
+            .is_some());
+    }
+}
+
+#[tokio::test]
+async fn insert_logs() {
+    writes_logs("insert").await;
+}
+
+#[tokio::test]
+async fn copy_logs() {
+    writes_logs("copy").await;
+}
+
+#[tokio::test]
+async fn rejects_rows_of_missing_table() {
+    trace_init();
+
+    let (sink, _) = config("missing", "insert")
+        .build(SinkContext::new_test())
+        .await
+        .unwrap();
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let event = Event::from(LogEvent::from("hello").with_batch_notifier(&batch));
+    drop(batch);
+
+    sink.run_events(vec![event]).await.unwrap();
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+}
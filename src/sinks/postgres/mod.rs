@@ -0,0 +1,94 @@
+//! The `postgres` sink.
+//!
+//! Writes logs and metrics as rows of PostgreSQL tables, including TimescaleDB hypertables. Each
+//! batch is written in a single transaction, with multi-row `INSERT` statements or a `COPY`, so
+//! that a batch retried after a failure is never written twice.
+
+use snafu::Snafu;
+use tokio_postgres::{error::SqlState, types::Type, Error as PgError};
+
+use crate::config::SinkDescription;
+
+mod config;
+mod encoder;
+mod service;
+mod sink;
+mod value;
+
+#[cfg(all(test, feature = "postgres-integration-tests"))]
+mod integration_tests;
+
+pub use self::config::{PostgresInsertMethod, PostgresSinkConfig};
+
+inventory::submit! {
+    SinkDescription::new::<PostgresSinkConfig>("postgres")
+}
+
+/// The classes of the SQLSTATE codes of the transient errors, namely connection exceptions,
+/// transaction rollbacks, insufficient resources, and operator interventions.
+const RETRIABLE_ERROR_CLASSES: [&str; 4] = ["08", "40", "53", "57"];
+
+#[derive(Debug, Snafu)]
+pub(super) enum PostgresError {
+    #[snafu(display("Failed to connect: {}", source))]
+    Connect { source: PgError },
+
+    #[snafu(display("Failed to write rows: {}", source))]
+    Write { source: PgError },
+
+    #[snafu(display("Column {:?} has unsupported type {}", column, ty))]
+    UnsupportedType { column: String, ty: Type },
+}
+
+impl PostgresError {
+    pub(super) fn is_retriable(&self) -> bool {
+        match self {
+            Self::Connect { .. } => true,
+            // Errors without a SQLSTATE code are the ones of the connection itself.
+            Self::Write { source } => source.code().map_or(true, is_retriable_code),
+            Self::UnsupportedType { .. } => false,
+        }
+    }
+}
+
+fn is_retriable_code(code: &SqlState) -> bool {
+    RETRIABLE_ERROR_CLASSES
+        .iter()
+        .any(|class| code.code().starts_with(class))
+}
+
+/// Quotes the identifier so that it is used as is, whatever its case and characters.
+pub(super) fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+/// Quotes the table name, which may be qualified by its schema as in `schema.table`.
+pub(super) fn quote_table(table: &str) -> String {
+    table
+        .split('.')
+        .map(quote_identifier)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_tables() {
+        assert_eq!(quote_table("logs"), r#""logs""#);
+        assert_eq!(quote_table("public.Logs"), r#""public"."Logs""#);
+        assert_eq!(quote_table(r#"my"table"#), r#""my""table""#);
+    }
+
+    #[test]
+    fn retries_transient_errors() {
+        assert!(is_retriable_code(&SqlState::T_R_DEADLOCK_DETECTED));
+        assert!(is_retriable_code(&SqlState::ADMIN_SHUTDOWN));
+        assert!(is_retriable_code(&SqlState::CONNECTION_FAILURE));
+        assert!(is_retriable_code(&SqlState::TOO_MANY_CONNECTIONS));
+        assert!(!is_retriable_code(&SqlState::UNDEFINED_TABLE));
+        assert!(!is_retriable_code(&SqlState::INVALID_TEXT_REPRESENTATION));
+    }
+}
@@ -0,0 +1,342 @@
+use std::{
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use bytes::{Bytes, BytesMut};
+use futures::{future::BoxFuture, SinkExt};
+use postgres_openssl::MakeTlsConnector;
+use snafu::ResultExt;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio_postgres::{
+    types::ToSql, Client, Config, CopyInSink, Error as PgError, NoTls, Transaction,
+};
+use tower::Service;
+use vector_core::{
+    internal_event::{BytesSent, EventsSent},
+    stream::DriverResponse,
+    ByteSizeOf,
+};
+
+use super::{
+    config::PostgresInsertMethod,
+    quote_table,
+    value::{encode_csv, PostgresValue},
+    ConnectSnafu, PostgresError, WriteSnafu,
+};
+use crate::{
+    event::{EventFinalizers, EventStatus, Finalizable, Value},
+    sinks::util::retries::RetryLogic,
+};
+
+/// The maximum number of parameters of a statement.
+const MAX_PARAMETERS: usize = u16::MAX as usize;
+
+#[derive(Clone, Debug)]
+pub(super) struct PostgresRequest {
+    pub(super) table: String,
+    pub(super) rows: Vec<Vec<Value>>,
+    pub(super) metadata: PostgresMetadata,
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct PostgresMetadata {
+    pub(super) count: usize,
+    pub(super) byte_size: usize,
+    pub(super) finalizers: EventFinalizers,
+}
+
+impl Finalizable for PostgresRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.metadata.finalizers)
+    }
+}
+
+pub(super) struct PostgresResponse {
+    count: usize,
+    events_byte_size: usize,
+    byte_size: usize,
+}
+
+impl DriverResponse for PostgresResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> EventsSent {
+        EventsSent {
+            count: self.count,
+            byte_size: self.events_byte_size,
+            output: None,
+        }
+    }
+
+    fn bytes_sent(&self) -> Option<BytesSent> {
+        Some(BytesSent {
+            byte_size: self.byte_size,
+            protocol: "postgres",
+        })
+    }
+}
+
+/// The connections to the database, of which at most `max_connections` are open at once.
+pub(super) struct PostgresPool {
+    config: Config,
+    tls: Option<MakeTlsConnector>,
+    idle: Mutex<Vec<Client>>,
+    permits: Arc<Semaphore>,
+}
+
+struct PooledClient {
+    client: Client,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl PostgresPool {
+    pub(super) fn new(
+        config: Config,
+        tls: Option<MakeTlsConnector>,
+        max_connections: usize,
+    ) -> Self {
+        Self {
+            config,
+            tls,
+            idle: Mutex::new(Vec::new()),
+            permits: Arc::new(Semaphore::new(max_connections)),
+        }
+    }
+
+    /// Takes an idle connection of the pool, or opens a new one once the number of open
+    /// connections allows it.
+    async fn get(&self) -> Result<PooledClient, PgError> {
+        let permit = Arc::clone(&self.permits)
+            .acquire_owned()
+            .await
+            .expect("pool semaphore is never closed");
+
+        let idle = {
+            let mut idle = self.idle.lock().expect("mutex poisoned");
+            idle.retain(|client| !client.is_closed());
+            idle.pop()
+        };
+        let client = match idle {
+            Some(client) => client,
+            None => self.connect().await?,
+        };
+
+        Ok(PooledClient {
+            client,
+            _permit: permit,
+        })
+    }
+
+    /// Returns the connection to the pool, unless it was closed.
+    fn release(&self, pooled: PooledClient) {
+        if !pooled.client.is_closed() {
+            self.idle
+                .lock()
+                .expect("mutex poisoned")
+                .push(pooled.client);
+        }
+    }
+
+    pub(super) async fn connect(&self) -> Result<Client, PgError> {
+        let client = match &self.tls {
+            Some(tls) => {
+                let (client, connection) = self.config.connect(tls.clone()).await?;
+                tokio::spawn(connection);
+                client
+            }
+            None => {
+                let (client, connection) = self.config.connect(NoTls).await?;
+                tokio::spawn(connection);
+                client
+            }
+        };
+        Ok(client)
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct PostgresService {
+    pool: Arc<PostgresPool>,
+    columns: Arc<Vec<String>>,
+    method: PostgresInsertMethod,
+}
+
+impl PostgresService {
+    pub(super) const fn new(
+        pool: Arc<PostgresPool>,
+        columns: Arc<Vec<String>>,
+        method: PostgresInsertMethod,
+    ) -> Self {
+        Self {
+            pool,
+            columns,
+            method,
+        }
+    }
+}
+
+impl Service<PostgresRequest> for PostgresService {
+    type Response = PostgresResponse;
+    type Error = PostgresError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: PostgresRequest) -> Self::Future {
+        let pool = Arc::clone(&self.pool);
+        let columns = Arc::clone(&self.columns);
+        let method = self.method;
+
+        Box::pin(async move {
+            let table = quote_table(&request.table);
+            let mut pooled = pool.get().await.context(ConnectSnafu)?;
+
+            // The rows are written in a single transaction, which is rolled back when any of
+            // them fails to be written, so that the request can be retried as a whole.
+            let result = match pooled.client.transaction().await.context(WriteSnafu) {
+                Ok(transaction) => {
+                    write(transaction, &table, &columns, &request.rows, method).await
+                }
+                Err(error) => Err(error),
+            };
+            pool.release(pooled);
+            let byte_size = result?;
+
+            Ok(PostgresResponse {
+                count: request.metadata.count,
+                events_byte_size: request.metadata.byte_size,
+                byte_size,
+            })
+        })
+    }
+}
+
+/// Writes the rows in the transaction, and commits it.
+///
+/// Returns the number of bytes of the written values.
+async fn write(
+    transaction: Transaction<'_>,
+    table: &str,
+    columns: &[String],
+    rows: &[Vec<Value>],
+    method: PostgresInsertMethod,
+) -> Result<usize, PostgresError> {
+    let byte_size = match method {
+        PostgresInsertMethod::Insert => {
+            let rows_per_statement = (MAX_PARAMETERS / columns.len()).max(1);
+            for chunk in rows.chunks(rows_per_statement) {
+                let statement = transaction
+                    .prepare(&insert_statement(table, columns, chunk.len()))
+                    .await
+                    .context(WriteSnafu)?;
+                for (index, ty) in statement.params().iter().enumerate() {
+                    if !<PostgresValue as ToSql>::accepts(ty) {
+                        return Err(PostgresError::UnsupportedType {
+                            column: columns[index % columns.len()].clone(),
+                            ty: ty.clone(),
+                        });
+                    }
+                }
+
+                let values = chunk
+                    .iter()
+                    .flatten()
+                    .map(PostgresValue)
+                    .collect::<Vec<_>>();
+                let params = values
+                    .iter()
+                    .map(|value| value as &(dyn ToSql + Sync))
+                    .collect::<Vec<_>>();
+                transaction
+                    .execute(&statement, &params)
+                    .await
+                    .context(WriteSnafu)?;
+            }
+            rows.iter().flatten().map(ByteSizeOf::size_of).sum()
+        }
+        PostgresInsertMethod::Copy => {
+            let mut body = BytesMut::new();
+            encode_csv(rows, &mut body);
+            let byte_size = body.len();
+
+            let sink: CopyInSink<Bytes> = transaction
+                .copy_in(&copy_statement(table, columns))
+                .await
+                .context(WriteSnafu)?;
+            futures::pin_mut!(sink);
+            sink.send(body.freeze()).await.context(WriteSnafu)?;
+            sink.finish().await.context(WriteSnafu)?;
+            byte_size
+        }
+    };
+
+    transaction.commit().await.context(WriteSnafu)?;
+    Ok(byte_size)
+}
+
+/// Builds the statement inserting the given number of rows in the table.
+fn insert_statement(table: &str, columns: &[String], rows: usize) -> String {
+    let mut statement = format!("INSERT INTO {} ({}) VALUES ", table, columns.join(", "));
+    for row in 0..rows {
+        if row > 0 {
+            statement.push_str(", ");
+        }
+        let params = (1..=columns.len())
+            .map(|column| format!("${}", row * columns.len() + column))
+            .collect::<Vec<_>>();
+        statement.push('(');
+        statement.push_str(&params.join(", "));
+        statement.push(')');
+    }
+    statement
+}
+
+fn copy_statement(table: &str, columns: &[String]) -> String {
+    format!(
+        "COPY {} ({}) FROM STDIN WITH (FORMAT csv)",
+        table,
+        columns.join(", ")
+    )
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct PostgresRetryLogic;
+
+impl RetryLogic for PostgresRetryLogic {
+    type Error = PostgresError;
+    type Response = PostgresResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        error.is_retriable()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns() -> Vec<String> {
+        vec![r#""timestamp""#.into(), r#""message""#.into()]
+    }
+
+    #[test]
+    fn builds_insert_statement() {
+        assert_eq!(
+            insert_statement(r#""public"."logs""#, &columns(), 2),
+            r#"INSERT INTO "public"."logs" ("timestamp", "message") VALUES ($1, $2), ($3, $4)"#
+        );
+    }
+
+    #[test]
+    fn builds_copy_statement() {
+        assert_eq!(
+            copy_statement(r#""logs""#, &columns()),
+            r#"COPY "logs" ("timestamp", "message") FROM STDIN WITH (FORMAT csv)"#
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use tower::ServiceBuilder;
+use vector_core::{partition::Partitioner, sink::StreamSink, stream::BatcherSettings};
+
+use super::{
+    encoder::PostgresEncoder,
+    service::{PostgresRetryLogic, PostgresService},
+};
+use crate::{
+    event::Event,
+    internal_events::TemplateRenderingError,
+    sinks::util::{ServiceBuilderExt, SinkBuilderExt, TowerRequestConfig},
+    template::Template,
+};
+
+/// Partitions the events by the table they are written to.
+pub(super) struct TablePartitioner {
+    pub(super) table: Template,
+}
+
+impl Partitioner for TablePartitioner {
+    type Item = Event;
+    type Key = Option<String>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        self.table
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("table"),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+}
+
+pub(super) struct PostgresSink {
+    pub(super) service: PostgresService,
+    pub(super) request: TowerRequestConfig,
+    pub(super) encoder: PostgresEncoder,
+    pub(super) partitioner: TablePartitioner,
+    pub(super) batch_settings: BatcherSettings,
+}
+
+impl PostgresSink {
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let request = self.request.unwrap_with(&TowerRequestConfig::default());
+        let service = ServiceBuilder::new()
+            .settings(request, PostgresRetryLogic)
+            .service(self.service);
+        let encoder = self.encoder;
+
+        input
+            .batched_partitioned(self.partitioner, self.batch_settings)
+            .filter_map(|(table, events)| async move { table.map(move |table| (table, events)) })
+            .map(move |(table, events)| encoder.encode(table, events))
+            .into_driver(service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for PostgresSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
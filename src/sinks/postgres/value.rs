@@ -0,0 +1,212 @@
+use std::{convert::TryFrom, error::Error};
+
+use bytes::{BufMut, BytesMut};
+use chrono::{DateTime, Utc};
+use tokio_postgres::types::{to_sql_checked, IsNull, ToSql, Type};
+
+use crate::event::Value;
+
+/// The value of a field, written as a parameter of the type of its column.
+///
+/// Values that can't be converted to the type of their column are written as `NULL`.
+#[derive(Debug)]
+pub(super) struct PostgresValue<'a>(pub(super) &'a Value);
+
+impl PostgresValue<'_> {
+    fn to_i64(&self) -> Option<i64> {
+        match self.0 {
+            Value::Integer(integer) => Some(*integer),
+            Value::Float(float) if float.into_inner().fract() == 0.0 => {
+                Some(float.into_inner() as i64)
+            }
+            Value::Bytes(bytes) => std::str::from_utf8(bytes).ok()?.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    fn to_f64(&self) -> Option<f64> {
+        match self.0 {
+            Value::Integer(integer) => Some(*integer as f64),
+            Value::Float(float) => Some(float.into_inner()),
+            Value::Bytes(bytes) => std::str::from_utf8(bytes).ok()?.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    fn to_bool(&self) -> Option<bool> {
+        match self.0 {
+            Value::Boolean(boolean) => Some(*boolean),
+            Value::Bytes(bytes) => std::str::from_utf8(bytes).ok()?.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    fn to_timestamp(&self) -> Option<DateTime<Utc>> {
+        match self.0 {
+            Value::Timestamp(timestamp) => Some(*timestamp),
+            Value::Bytes(bytes) => DateTime::parse_from_rfc3339(std::str::from_utf8(bytes).ok()?)
+                .ok()
+                .map(|timestamp| timestamp.with_timezone(&Utc)),
+            _ => None,
+        }
+    }
+}
+
+impl ToSql for PostgresValue<'_> {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        if matches!(self.0, Value::Null) {
+            return Ok(IsNull::Yes);
+        }
+
+        match *ty {
+            Type::BOOL => match self.to_bool() {
+                Some(boolean) => boolean.to_sql(ty, out),
+                None => Ok(IsNull::Yes),
+            },
+            Type::INT2 => match self.to_i64().and_then(|int| i16::try_from(int).ok()) {
+                Some(int) => int.to_sql(ty, out),
+                None => Ok(IsNull::Yes),
+            },
+            Type::INT4 => match self.to_i64().and_then(|int| i32::try_from(int).ok()) {
+                Some(int) => int.to_sql(ty, out),
+                None => Ok(IsNull::Yes),
+            },
+            Type::INT8 => match self.to_i64() {
+                Some(int) => int.to_sql(ty, out),
+                None => Ok(IsNull::Yes),
+            },
+            Type::FLOAT4 => match self.to_f64() {
+                Some(float) => (float as f32).to_sql(ty, out),
+                None => Ok(IsNull::Yes),
+            },
+            Type::FLOAT8 => match self.to_f64() {
+                Some(float) => float.to_sql(ty, out),
+                None => Ok(IsNull::Yes),
+            },
+            Type::TIMESTAMPTZ => match self.to_timestamp() {
+                Some(timestamp) => timestamp.to_sql(ty, out),
+                None => Ok(IsNull::Yes),
+            },
+            Type::TIMESTAMP => match self.to_timestamp() {
+                Some(timestamp) => timestamp.naive_utc().to_sql(ty, out),
+                None => Ok(IsNull::Yes),
+            },
+            Type::JSON | Type::JSONB => {
+                // The binary format of `jsonb` is the one of `json` preceded by its version.
+                if *ty == Type::JSONB {
+                    out.put_u8(1);
+                }
+                serde_json::to_writer(out.writer(), self.0)?;
+                Ok(IsNull::No)
+            }
+            _ => self.0.to_string_lossy().as_str().to_sql(ty, out),
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(
+            *ty,
+            Type::BOOL
+                | Type::INT2
+                | Type::INT4
+                | Type::INT8
+                | Type::FLOAT4
+                | Type::FLOAT8
+                | Type::TIMESTAMPTZ
+                | Type::TIMESTAMP
+                | Type::JSON
+                | Type::JSONB
+        ) || <&str as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+/// Encodes the rows as CSV records, as read by `COPY ... WITH (FORMAT csv)`.
+///
+/// The values are quoted so that only the missing ones are read as `NULL`.
+pub(super) fn encode_csv(rows: &[Vec<Value>], out: &mut BytesMut) {
+    for row in rows {
+        for (index, value) in row.iter().enumerate() {
+            if index > 0 {
+                out.put_u8(b',');
+            }
+            if matches!(value, Value::Null) {
+                continue;
+            }
+            out.put_u8(b'"');
+            out.put_slice(value.to_string_lossy().replace('"', "\"\"").as_bytes());
+            out.put_u8(b'"');
+        }
+        out.put_u8(b'\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn encode(value: Value, ty: &Type) -> Option<BytesMut> {
+        let mut out = BytesMut::new();
+        match PostgresValue(&value).to_sql(ty, &mut out).unwrap() {
+            IsNull::Yes => None,
+            IsNull::No => Some(out),
+        }
+    }
+
+    #[test]
+    fn converts_values_to_column_types() {
+        assert_eq!(
+            encode(Value::from("42"), &Type::INT4).unwrap()[..],
+            42i32.to_be_bytes()
+        );
+        assert_eq!(
+            encode(Value::from(2.0), &Type::INT8).unwrap()[..],
+            2i64.to_be_bytes()
+        );
+        assert_eq!(
+            encode(Value::from(3), &Type::FLOAT8).unwrap()[..],
+            3f64.to_be_bytes()
+        );
+        assert_eq!(encode(Value::from("true"), &Type::BOOL).unwrap()[..], [1]);
+        assert_eq!(encode(Value::from(42), &Type::TEXT).unwrap()[..], b"42"[..]);
+        assert_eq!(
+            encode(Value::from(vec![1, 2]), &Type::JSONB).unwrap()[..],
+            b"\x01[1,2]"[..]
+        );
+        assert_eq!(
+            encode(Value::from("2022-08-01T10:00:00+02:00"), &Type::TIMESTAMPTZ),
+            encode(
+                Value::from(Utc.ymd(2022, 8, 1).and_hms(8, 0, 0)),
+                &Type::TIMESTAMPTZ
+            )
+        );
+    }
+
+    #[test]
+    fn writes_unconvertible_values_as_null() {
+        assert!(encode(Value::Null, &Type::TEXT).is_none());
+        assert!(encode(Value::from("forty-two"), &Type::INT4).is_none());
+        assert!(encode(Value::from(1.5), &Type::INT8).is_none());
+        assert!(encode(Value::from(70_000), &Type::INT2).is_none());
+        assert!(encode(Value::from(true), &Type::TIMESTAMPTZ).is_none());
+    }
+
+    #[test]
+    fn encodes_csv() {
+        let rows = vec![
+            vec![Value::from("say \"hi\""), Value::from(1), Value::Null],
+            vec![Value::from(""), Value::Null, Value::from(true)],
+        ];
+        let mut out = BytesMut::new();
+        encode_csv(&rows, &mut out);
+
+        assert_eq!(&out[..], b"\"say \"\"hi\"\"\",\"1\",\n\"\",,\"true\"\n");
+    }
+}
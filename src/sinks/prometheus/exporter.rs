@@ -39,7 +39,7 @@ use crate::{
     internal_events::PrometheusServerRequestComplete,
     sinks::{
         util::{
-            buffer::metrics::{MetricNormalize, MetricNormalizer, MetricSet},
+            buffer::metrics::{sketch_to_histogram, MetricNormalize, MetricNormalizer, MetricSet},
             statistic::validate_quantiles,
             StreamSink,
         },
@@ -71,6 +71,8 @@ pub struct PrometheusExporterConfig {
     pub quantiles: Vec<f64>,
     #[serde(default = "default_distributions_as_summaries")]
     pub distributions_as_summaries: bool,
+    #[serde(default = "default_sketches_as_histograms")]
+    pub sketches_as_histograms: bool,
     #[serde(default = "default_flush_period_secs")]
     #[serde_as(as = "serde_with::DurationSeconds<u64>")]
     pub flush_period_secs: Duration,
@@ -87,6 +89,7 @@ impl Default for PrometheusExporterConfig {
             buckets: super::default_histogram_buckets(),
             quantiles: super::default_summary_quantiles(),
             distributions_as_summaries: default_distributions_as_summaries(),
+            sketches_as_histograms: default_sketches_as_histograms(),
             flush_period_secs: default_flush_period_secs(),
             suppress_timestamp: default_suppress_timestamp(),
         }
@@ -103,6 +106,10 @@ const fn default_distributions_as_summaries() -> bool {
     false
 }
 
+const fn default_sketches_as_histograms() -> bool {
+    false
+}
+
 const fn default_flush_period_secs() -> Duration {
     Duration::from_secs(60)
 }
@@ -289,6 +296,7 @@ impl Hash for MetricRef {
 
 struct PrometheusExporterMetricNormalizer {
     distributions_as_summaries: bool,
+    sketches_as_histograms: bool,
     buckets: Vec<f64>,
 }
 
@@ -317,6 +325,10 @@ impl MetricNormalize for PrometheusExporterMetricNormalizer {
                 let data = MetricData::from_parts(time, kind, new_value);
                 Metric::from_parts(series, data, metadata)
             }
+            // Sketches are otherwise rendered as aggregated summaries by the collector.
+            MetricValue::Sketch { .. } if self.sketches_as_histograms => {
+                sketch_to_histogram(metric, &self.buckets)
+            }
             _ => metric,
         };
 
@@ -465,6 +477,7 @@ impl StreamSink<Event> for PrometheusExporter {
         let flush_period = self.config.flush_period_secs;
         let mut normalizer = MetricNormalizer::from(PrometheusExporterMetricNormalizer {
             distributions_as_summaries: self.config.distributions_as_summaries,
+            sketches_as_histograms: self.config.sketches_as_histograms,
             buckets: self.config.buckets.clone(),
         });
 
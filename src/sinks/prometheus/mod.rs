@@ -4,6 +4,7 @@ use vector_core::event::Metric;
 mod collector;
 pub(crate) mod exporter;
 pub(crate) mod remote_write;
+mod series_buffer;
 
 fn default_histogram_buckets() -> Vec<f64> {
     vec![
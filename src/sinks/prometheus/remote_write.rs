@@ -1,14 +1,19 @@
-use std::task;
+use std::{task, time::Duration};
 
+use async_trait::async_trait;
 use bytes::{Bytes, BytesMut};
-use futures::{future::BoxFuture, stream, FutureExt, SinkExt};
+use chrono::Utc;
+use futures::{future::BoxFuture, stream::BoxStream, FutureExt, Sink, SinkExt, StreamExt};
 use http::Uri;
 use prost::Message;
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
 use vector_core::ByteSizeOf;
 
-use super::collector::{self, MetricCollector as _};
+use super::{
+    collector::{self, MetricCollector as _},
+    series_buffer::SeriesBuffer,
+};
 use crate::{
     config::{self, AcknowledgementsConfig, Input, SinkConfig, SinkDescription},
     event::{Event, Metric},
@@ -18,9 +23,11 @@ use crate::{
         self,
         util::{
             batch::BatchConfig,
-            buffer::metrics::{MetricNormalize, MetricNormalizer, MetricSet, MetricsBuffer},
+            buffer::metrics::{
+                sketch_to_histogram, MetricNormalize, MetricNormalizer, MetricSet, MetricsBuffer,
+            },
             http::HttpRetryLogic,
-            EncodedEvent, PartitionBuffer, PartitionInnerBuffer, SinkBatchSettings,
+            EncodedEvent, PartitionBuffer, PartitionInnerBuffer, SinkBatchSettings, StreamSink,
             TowerRequestConfig,
         },
     },
@@ -37,10 +44,15 @@ impl SinkBatchSettings for PrometheusRemoteWriteDefaultBatchSettings {
     const TIMEOUT_SECS: f64 = 1.0;
 }
 
+/// The staleness marker of Prometheus, a NaN value which differs from the NaN of normal samples.
+const STALE_NAN: u64 = 0x7ff0_0000_0000_0002;
+
 #[derive(Debug, Snafu)]
 enum Errors {
     #[snafu(display(r#"Prometheus remote_write sink cannot accept "set" metrics"#))]
     SetMetricInvalid,
+    #[snafu(display(r#""staleness_timeout_secs" must be greater than "reorder_window_secs""#))]
+    StalenessTimeoutTooShort,
 }
 
 #[derive(Debug, Default, Deserialize, Serialize)]
@@ -54,6 +66,13 @@ pub struct RemoteWriteConfig {
     pub buckets: Vec<f64>,
     #[serde(default = "super::default_summary_quantiles")]
     pub quantiles: Vec<f64>,
+    #[serde(default)]
+    pub sketches_as_histograms: bool,
+
+    #[serde(default)]
+    pub reorder_window_secs: u64,
+    #[serde(default)]
+    pub staleness_timeout_secs: Option<u64>,
 
     #[serde(default)]
     pub batch: BatchConfig<PrometheusRemoteWriteDefaultBatchSettings>,
@@ -81,6 +100,12 @@ impl SinkConfig for RemoteWriteConfig {
         &self,
         cx: config::SinkContext,
     ) -> crate::Result<(sinks::VectorSink, sinks::Healthcheck)> {
+        if let Some(staleness_timeout_secs) = self.staleness_timeout_secs {
+            if staleness_timeout_secs <= self.reorder_window_secs {
+                return Err(Box::new(Errors::StalenessTimeoutTooShort));
+            }
+        }
+
         let endpoint = self.endpoint.parse::<Uri>().context(sinks::UriParseSnafu)?;
         let tls_settings = TlsSettings::from_options(&self.tls)?;
         let batch = self.batch.into_batch_settings()?;
@@ -102,40 +127,34 @@ impl SinkConfig for RemoteWriteConfig {
             auth,
         };
 
-        let sink = {
-            let buffer = PartitionBuffer::new(MetricsBuffer::new(batch.size));
-            let mut normalizer = MetricNormalizer::<PrometheusMetricNormalize>::default();
+        let normalizer = MetricNormalizer::from(PrometheusMetricNormalize {
+            sketch_buckets: if self.sketches_as_histograms {
+                Some(self.buckets.clone())
+            } else {
+                None
+            },
+        });
+        let reorder_window = if self.reorder_window_secs > 0 {
+            Some(chrono::Duration::seconds(self.reorder_window_secs as i64))
+        } else {
+            None
+        };
+        let staleness_timeout = self
+            .staleness_timeout_secs
+            .map(|secs| chrono::Duration::seconds(secs as i64));
 
-            request_settings
+        let buffer = PartitionBuffer::new(MetricsBuffer::new(batch.size));
+        let sink = RemoteWriteSink {
+            sink: request_settings
                 .partition_sink(HttpRetryLogic, service, buffer, batch.timeout)
-                .with_flat_map(move |event: Event| {
-                    let byte_size = event.size_of();
-                    stream::iter(normalizer.normalize(event.into_metric()).map(|event| {
-                        let tenant_id = tenant_id.as_ref().and_then(|template| {
-                            template
-                                .render_string(&event)
-                                .map_err(|error| {
-                                    emit!(TemplateRenderingError {
-                                        error,
-                                        field: Some("tenant_id"),
-                                        drop_event: false,
-                                    })
-                                })
-                                .ok()
-                        });
-                        let key = PartitionKey { tenant_id };
-                        Ok(EncodedEvent::new(
-                            PartitionInnerBuffer::new(event, key),
-                            byte_size,
-                        ))
-                    }))
-                })
                 .sink_map_err(
                     |error| error!(message = "Prometheus remote_write sink error.", %error),
-                )
+                ),
+            series: SeriesBuffer::new(normalizer, reorder_window, staleness_timeout),
+            tenant_id,
         };
 
-        Ok((sinks::VectorSink::from_event_sink(sink), healthcheck))
+        Ok((sinks::VectorSink::from_event_streamsink(sink), healthcheck))
     }
 
     fn input(&self) -> Input {
@@ -154,6 +173,82 @@ impl SinkConfig for RemoteWriteConfig {
 #[derive(Clone, Eq, Hash, PartialEq)]
 struct PartitionKey {
     tenant_id: Option<String>,
+    /// Whether the metrics are staleness markers, which are sent in their own requests.
+    stale: bool,
+}
+
+struct RemoteWriteSink<S> {
+    sink: S,
+    series: SeriesBuffer,
+    tenant_id: Option<Template>,
+}
+
+#[async_trait]
+impl<S> StreamSink<Event> for RemoteWriteSink<S>
+where
+    S: Sink<EncodedEvent<PartitionInnerBuffer<Metric, PartitionKey>>, Error = ()> + Send + Unpin,
+{
+    async fn run(self: Box<Self>, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let Self {
+            sink,
+            mut series,
+            tenant_id,
+        } = *self;
+        let tenant_id = tenant_id.as_ref();
+        let mut flush = tokio::time::interval(Duration::from_secs(1));
+
+        let events = async_stream::stream! {
+            let mut output = Vec::new();
+            let mut done = false;
+            while !done {
+                tokio::select! {
+                    _ = flush.tick() => {
+                        let now = Utc::now();
+                        let markers = series.stale_markers(now);
+                        output.extend(series.flush(now).into_iter().map(|metric| (metric, false)));
+                        output.extend(markers.into_iter().map(|metric| (metric, true)));
+                    },
+                    maybe_event = input.next() => {
+                        let metrics = match maybe_event {
+                            None => {
+                                done = true;
+                                series.flush_all(Utc::now())
+                            }
+                            Some(event) => series.push(event.into_metric(), Utc::now()),
+                        };
+                        output.extend(metrics.into_iter().map(|metric| (metric, false)));
+                    }
+                };
+                for (metric, stale) in output.drain(..) {
+                    yield encode_event(tenant_id, metric, stale);
+                }
+            }
+        };
+
+        events.map(Ok).forward(sink).await
+    }
+}
+
+fn encode_event(
+    tenant_id: Option<&Template>,
+    metric: Metric,
+    stale: bool,
+) -> EncodedEvent<PartitionInnerBuffer<Metric, PartitionKey>> {
+    let byte_size = metric.size_of();
+    let tenant_id = tenant_id.and_then(|template| {
+        template
+            .render_string(&metric)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("tenant_id"),
+                    drop_event: false,
+                })
+            })
+            .ok()
+    });
+    let key = PartitionKey { tenant_id, stale };
+    EncodedEvent::new(PartitionInnerBuffer::new(metric, key), byte_size)
 }
 
 async fn healthcheck(endpoint: Uri, client: HttpClient) -> crate::Result<()> {
@@ -170,10 +265,18 @@ async fn healthcheck(endpoint: Uri, client: HttpClient) -> crate::Result<()> {
 }
 
 #[derive(Default)]
-pub struct PrometheusMetricNormalize;
+pub struct PrometheusMetricNormalize {
+    /// The bucket bounds of the histograms that sketches are converted to, if they are converted.
+    sketch_buckets: Option<Vec<f64>>,
+}
 
 impl MetricNormalize for PrometheusMetricNormalize {
     fn normalize(&mut self, state: &mut MetricSet, metric: Metric) -> Option<Metric> {
+        // Sketches are converted first, so that their histograms are accumulated like any other.
+        let metric = match &self.sketch_buckets {
+            Some(buckets) => sketch_to_histogram(metric, buckets),
+            None => metric,
+        };
         state.make_absolute(metric)
     }
 }
@@ -189,7 +292,7 @@ struct RemoteWriteService {
 }
 
 impl RemoteWriteService {
-    fn encode_events(&self, metrics: Vec<Metric>) -> Bytes {
+    fn encode_events(&self, metrics: Vec<Metric>, stale: bool) -> Bytes {
         let mut time_series = collector::TimeSeries::new();
         for metric in metrics {
             time_series.encode_metric(
@@ -199,7 +302,16 @@ impl RemoteWriteService {
                 &metric,
             );
         }
-        let request = time_series.finish();
+        let mut request = time_series.finish();
+        if stale {
+            for sample in request
+                .timeseries
+                .iter_mut()
+                .flat_map(|series| series.samples.iter_mut())
+            {
+                sample.value = f64::from_bits(STALE_NAN);
+            }
+        }
 
         let mut out = BytesMut::with_capacity(request.encoded_len());
         request.encode(&mut out).expect("Out of memory");
@@ -218,7 +330,7 @@ impl tower::Service<PartitionInnerBuffer<Vec<Metric>, PartitionKey>> for RemoteW
 
     fn call(&mut self, buffer: PartitionInnerBuffer<Vec<Metric>, PartitionKey>) -> Self::Future {
         let (events, key) = buffer.into_parts();
-        let body = self.encode_events(events);
+        let body = self.encode_events(events, key.stale);
         let body = snap_block(body);
 
         let mut builder = http::Request::post(self.endpoint.clone())
@@ -260,7 +372,8 @@ mod tests {
     use super::*;
     use crate::{
         config::SinkContext,
-        event::{MetricKind, MetricValue},
+        event::{metric::Bucket, MetricKind, MetricValue},
+        metrics::AgentDDSketch,
         sinks::util::test::build_test_server,
         test_util,
     };
@@ -386,6 +499,138 @@ mod tests {
         check_output(2, "counter-1", 26.0);
     }
 
+    #[tokio::test]
+    async fn sends_sketches_as_histograms() {
+        let mut sketch = AgentDDSketch::with_agent_defaults();
+        sketch.insert_many(&[0.5, 1.5, 1.5, 7.0]);
+        let event = Metric::new("latency", MetricKind::Incremental, sketch.into())
+            .with_timestamp(Some(chrono::Utc::now()))
+            .into();
+
+        let outputs = send_request(
+            indoc! {r#"
+                buckets = [1.0, 2.0, 5.0]
+                sketches_as_histograms = true
+            "#},
+            vec![event],
+        )
+        .await;
+
+        assert_eq!(outputs.len(), 1);
+        let (_, req) = &outputs[0];
+        let samples = req
+            .timeseries
+            .iter()
+            .map(|series| {
+                let name = &series.labels[0].value;
+                let le = series.labels.get(1).map(|label| label.value.as_str());
+                (name.as_str(), le, series.samples[0].value)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            samples,
+            vec![
+                ("latency_bucket", Some("1"), 1.0),
+                ("latency_bucket", Some("2"), 3.0),
+                ("latency_bucket", Some("5"), 3.0),
+                ("latency_bucket", Some("+Inf"), 4.0),
+                ("latency_sum", None, 10.5),
+                ("latency_count", None, 4.0),
+            ]
+        );
+        assert_eq!(req.metadata[0].r#type, proto::MetricType::Histogram as i32);
+    }
+
+    #[tokio::test]
+    async fn sends_sketches_as_summaries_by_default() {
+        // Native histograms are not part of the remote write protocol implemented by the sink, so a
+        // sketch is never sent as one: without `sketches_as_histograms`, it is sent as a summary.
+        let mut sketch = AgentDDSketch::with_agent_defaults();
+        sketch.insert_many(&[0.5, 1.5, 1.5, 7.0]);
+        let event = Metric::new("latency", MetricKind::Incremental, sketch.into())
+            .with_timestamp(Some(chrono::Utc::now()))
+            .into();
+
+        let outputs = send_request("quantiles = [0.5]", vec![event]).await;
+
+        assert_eq!(outputs.len(), 1);
+        let (_, req) = &outputs[0];
+        let series = req
+            .timeseries
+            .iter()
+            .map(|series| {
+                let name = series.labels[0].value.as_str();
+                let quantile = series.labels.get(1).map(|label| label.value.as_str());
+                (name, quantile)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            series,
+            vec![
+                ("latency", Some("0.5")),
+                ("latency_sum", None),
+                ("latency_count", None),
+            ]
+        );
+        assert_eq!(req.timeseries[2].samples[0].value, 4.0);
+        assert_eq!(req.metadata[0].r#type, proto::MetricType::Summary as i32);
+    }
+
+    #[test]
+    fn encodes_staleness_markers() {
+        let service = RemoteWriteService {
+            endpoint: "http://localhost:9090/write".parse().unwrap(),
+            default_namespace: None,
+            client: HttpClient::new(None, &Default::default()).unwrap(),
+            buckets: vec![1.0],
+            quantiles: vec![],
+            auth: None,
+        };
+        let metric = Metric::new(
+            "latency",
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: vec![Bucket {
+                    upper_limit: 1.0,
+                    count: 1,
+                }],
+                count: 1,
+                sum: 0.5,
+            },
+        );
+
+        let body = service.encode_events(vec![metric], true);
+        let request = proto::WriteRequest::decode(body).unwrap();
+
+        // Every series of the histogram is marked as stale.
+        assert_eq!(request.timeseries.len(), 4);
+        assert!(request
+            .timeseries
+            .iter()
+            .flat_map(|series| series.samples.iter())
+            .all(|sample| sample.value.to_bits() == STALE_NAN));
+    }
+
+    #[tokio::test]
+    async fn rejects_staleness_timeout_within_reorder_window() {
+        let config = toml::from_str::<RemoteWriteConfig>(indoc! {r#"
+            endpoint = "http://localhost:9090/write"
+            reorder_window_secs = 60
+            staleness_timeout_secs = 30
+        "#})
+        .unwrap();
+
+        let error = config
+            .build(SinkContext::new_test())
+            .await
+            .map(|_| ())
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#""staleness_timeout_secs" must be greater than "reorder_window_secs""#
+        );
+    }
+
     async fn send_request(
         config: &str,
         events: Vec<Event>,
@@ -0,0 +1,257 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Duration, Utc};
+use vector_core::event::{metric::MetricSeries, EventMetadata, Metric};
+
+use super::remote_write::PrometheusMetricNormalize;
+use crate::{
+    internal_events::PrometheusRemoteWriteOutOfOrderSampleDropped,
+    sinks::util::buffer::metrics::MetricNormalizer,
+};
+
+/// The state of a series sent by the sink.
+struct SeriesState {
+    /// The timestamp of the latest sample sent for the series.
+    latest: DateTime<Utc>,
+    /// When a sample of the series was last received.
+    last_seen: DateTime<Utc>,
+    /// The latest sample sent for the series, from which its staleness marker is built.
+    metric: Metric,
+}
+
+/// Buffers the samples of the `prometheus_remote_write` sink, so that they are sent in timestamp
+/// order, and tracks the series sent, so that a staleness marker is sent for the series that stop
+/// being reported.
+///
+/// Samples are normalized once they leave the buffer, so that incremental metrics are accumulated
+/// in timestamp order.
+pub(super) struct SeriesBuffer {
+    normalizer: MetricNormalizer<PrometheusMetricNormalize>,
+    reorder_window: Option<Duration>,
+    staleness_timeout: Option<Duration>,
+    /// The samples waiting for the end of their reorder window, by timestamp.
+    pending: BTreeMap<DateTime<Utc>, Vec<Metric>>,
+    series: HashMap<MetricSeries, SeriesState>,
+}
+
+impl SeriesBuffer {
+    pub(super) fn new(
+        normalizer: MetricNormalizer<PrometheusMetricNormalize>,
+        reorder_window: Option<Duration>,
+        staleness_timeout: Option<Duration>,
+    ) -> Self {
+        Self {
+            normalizer,
+            reorder_window,
+            staleness_timeout,
+            pending: BTreeMap::new(),
+            series: HashMap::new(),
+        }
+    }
+
+    /// Buffers a sample until the reorder window after its timestamp ends, and returns the samples
+    /// which can be sent right away.
+    ///
+    /// Samples without a timestamp are timestamped with the time they are received.
+    pub(super) fn push(&mut self, metric: Metric, now: DateTime<Utc>) -> Vec<Metric> {
+        if self.reorder_window.is_none() && self.staleness_timeout.is_none() {
+            return self.normalizer.normalize(metric).into_iter().collect();
+        }
+
+        let timestamp = metric.timestamp().unwrap_or(now);
+        let metric = metric.with_timestamp(Some(timestamp));
+        if let Some(state) = self.series.get_mut(metric.series()) {
+            state.last_seen = now;
+        }
+
+        if self.reorder_window.is_none() {
+            return self.release(vec![metric], now);
+        }
+        self.pending.entry(timestamp).or_default().push(metric);
+        Vec::new()
+    }
+
+    /// Returns the samples whose reorder window ended, in timestamp order.
+    pub(super) fn flush(&mut self, now: DateTime<Utc>) -> Vec<Metric> {
+        let reorder_window = match self.reorder_window {
+            Some(reorder_window) => reorder_window,
+            None => return Vec::new(),
+        };
+
+        let later = self
+            .pending
+            .split_off(&(now - reorder_window + Duration::nanoseconds(1)));
+        let ready = std::mem::replace(&mut self.pending, later);
+        self.release(ready.into_values().flatten().collect(), now)
+    }
+
+    /// Returns all the buffered samples, in timestamp order.
+    pub(super) fn flush_all(&mut self, now: DateTime<Utc>) -> Vec<Metric> {
+        let ready = std::mem::take(&mut self.pending);
+        self.release(ready.into_values().flatten().collect(), now)
+    }
+
+    /// Forgets the series that weren't received for the staleness timeout, and returns their
+    /// staleness markers.
+    ///
+    /// A marker is the latest sample of the series, timestamped with the current time. Its values
+    /// are replaced by the staleness marker of Prometheus when it is encoded.
+    pub(super) fn stale_markers(&mut self, now: DateTime<Utc>) -> Vec<Metric> {
+        let staleness_timeout = match self.staleness_timeout {
+            Some(staleness_timeout) => staleness_timeout,
+            None => {
+                // The series are only tracked to drop out-of-order samples, which can't arrive
+                // after their reorder window.
+                if let Some(reorder_window) = self.reorder_window {
+                    self.series
+                        .retain(|_, state| now - state.last_seen < reorder_window);
+                }
+                return Vec::new();
+            }
+        };
+
+        let stale = self
+            .series
+            .iter()
+            .filter(|(_, state)| now - state.last_seen >= staleness_timeout)
+            .map(|(series, _)| series.clone())
+            .collect::<Vec<_>>();
+        stale
+            .into_iter()
+            .filter_map(|series| self.series.remove(&series))
+            .map(|state| state.metric.with_timestamp(Some(now)))
+            .collect()
+    }
+
+    /// Normalizes the samples, in order, dropping the samples older than the latest sample sent
+    /// for their series, which downstream databases would reject.
+    fn release(&mut self, metrics: Vec<Metric>, now: DateTime<Utc>) -> Vec<Metric> {
+        let mut released = Vec::with_capacity(metrics.len());
+        for metric in metrics {
+            let timestamp = metric
+                .timestamp()
+                .expect("buffered samples are always timestamped");
+            if let Some(state) = self.series.get(metric.series()) {
+                if timestamp < state.latest {
+                    emit!(PrometheusRemoteWriteOutOfOrderSampleDropped {
+                        metric_name: metric.name(),
+                    });
+                    continue;
+                }
+            }
+
+            if let Some(metric) = self.normalizer.normalize(metric) {
+                // The finalizers of the sample aren't held by its staleness marker.
+                let (series, data, _) = metric.clone().into_parts();
+                let marker = Metric::from_parts(series.clone(), data, EventMetadata::default());
+                let last_seen = self
+                    .series
+                    .get(&series)
+                    .map_or(now, |state| state.last_seen);
+                self.series.insert(
+                    series,
+                    SeriesState {
+                        latest: timestamp,
+                        last_seen,
+                        metric: marker,
+                    },
+                );
+                released.push(metric);
+            }
+        }
+        released
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use vector_core::event::{MetricKind, MetricValue};
+
+    use super::*;
+
+    fn buffer(reorder_window_secs: i64, staleness_timeout_secs: Option<i64>) -> SeriesBuffer {
+        SeriesBuffer::new(
+            MetricNormalizer::from(PrometheusMetricNormalize::default()),
+            Some(Duration::seconds(reorder_window_secs)),
+            staleness_timeout_secs.map(Duration::seconds),
+        )
+    }
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        Utc.timestamp(1_600_000_000 + secs, 0)
+    }
+
+    fn counter(name: &str, value: f64, timestamp: DateTime<Utc>) -> Metric {
+        Metric::new(
+            name,
+            MetricKind::Incremental,
+            MetricValue::Counter { value },
+        )
+        .with_timestamp(Some(timestamp))
+    }
+
+    fn values(metrics: &[Metric]) -> Vec<(i64, f64)> {
+        metrics
+            .iter()
+            .map(|metric| {
+                let secs = metric.timestamp().unwrap().timestamp() - at(0).timestamp();
+                match metric.value() {
+                    MetricValue::Counter { value } => (secs, *value),
+                    value => panic!("unexpected value {:?}", value),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reorders_samples_within_the_window() {
+        let mut buffer = buffer(10, None);
+
+        for (secs, value) in [(3, 1.0), (1, 2.0), (2, 3.0)] {
+            assert!(buffer
+                .push(counter("requests", value, at(secs)), at(4))
+                .is_empty());
+        }
+        assert!(buffer.flush(at(10)).is_empty());
+
+        // Counters are accumulated in timestamp order.
+        let released = buffer.flush(at(12));
+        assert_eq!(values(&released), vec![(1, 2.0), (2, 5.0)]);
+        let released = buffer.flush(at(13));
+        assert_eq!(values(&released), vec![(3, 6.0)]);
+    }
+
+    #[test]
+    fn drops_samples_older_than_the_sent_ones() {
+        let mut buffer = buffer(10, None);
+
+        buffer.push(counter("requests", 1.0, at(5)), at(5));
+        assert_eq!(values(&buffer.flush(at(15))), vec![(5, 1.0)]);
+
+        buffer.push(counter("requests", 1.0, at(4)), at(15));
+        buffer.push(counter("requests", 1.0, at(6)), at(15));
+        assert_eq!(values(&buffer.flush_all(at(15))), vec![(6, 2.0)]);
+    }
+
+    #[test]
+    fn marks_stale_series() {
+        let mut buffer = buffer(1, Some(30));
+
+        buffer.push(counter("requests", 1.0, at(0)), at(0));
+        buffer.push(counter("errors", 1.0, at(0)), at(0));
+        buffer.flush(at(1));
+
+        buffer.push(counter("requests", 1.0, at(20)), at(20));
+        buffer.flush(at(21));
+
+        let markers = buffer.stale_markers(at(31));
+        assert_eq!(markers.len(), 1);
+        assert_eq!(markers[0].name(), "errors");
+        assert_eq!(markers[0].timestamp(), Some(at(31)));
+
+        // Stale series are forgotten, so they are only marked once.
+        assert!(buffer.stale_markers(at(40)).is_empty());
+        assert_eq!(buffer.stale_markers(at(50))[0].name(), "requests");
+    }
+}
@@ -1,37 +1,50 @@
-use std::{
-    num::NonZeroUsize,
-    pin::Pin,
-    task::{Context, Poll},
-};
+use std::{collections::HashMap, convert::TryFrom};
 
+use async_trait::async_trait;
 use bytes::BytesMut;
 use codecs::{encoding::SerializerConfig, TextSerializerConfig};
-use futures::{future::BoxFuture, ready, stream::FuturesUnordered, FutureExt, Sink, Stream};
+use futures::{future::join_all, stream::BoxStream, FutureExt, StreamExt};
 use pulsar::authentication::oauth2::{OAuth2Authentication, OAuth2Params};
 use pulsar::error::AuthenticationError;
 use pulsar::{
-    message::proto, producer::SendFuture, proto::CommandSendReceipt, Authentication,
-    Error as PulsarError, Producer, Pulsar, TokioExecutor,
+    message::proto,
+    producer::{Message, ProducerOptions},
+    Authentication, Error as PulsarError, Producer, Pulsar, TokioExecutor,
 };
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::Encoder as _;
 use vector_common::internal_event::{BytesSent, EventsSent};
-use vector_core::config::log_schema;
+use vector_core::{
+    config::log_schema, partition::Partitioner, stream::BatcherSettings, ByteSizeOf,
+};
 
 use crate::{
     codecs::{Encoder, EncodingConfig, Transformer},
     config::{
         AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext, SinkDescription,
     },
-    event::{Event, EventFinalizers, Finalizable},
-    sinks::util::metadata::RequestMetadata,
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
+    internal_events::{PulsarSendingError, TemplateRenderingError},
+    sinks::util::{BatchConfig, SinkBatchSettings, SinkBuilderExt, StreamSink},
+    template::{Template, TemplateParseError},
 };
 
 #[derive(Debug, Snafu)]
 enum BuildError {
     #[snafu(display("creating pulsar producer failed: {}", source))]
     CreatePulsarSink { source: PulsarError },
+    #[snafu(display("invalid topic template: {}", source))]
+    TopicTemplate { source: TemplateParseError },
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PulsarDefaultBatchSettings;
+
+impl SinkBatchSettings for PulsarDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(1000);
+    const MAX_BYTES: Option<usize> = None;
+    const TIMEOUT_SECS: f64 = 1.0;
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -40,8 +53,17 @@ pub struct PulsarSinkConfig {
     #[serde(alias = "address")]
     endpoint: String,
     topic: String,
+    partition_key_field: Option<String>,
+    #[serde(default)]
+    batch: BatchConfig<PulsarDefaultBatchSettings>,
     pub encoding: EncodingConfig,
     auth: Option<AuthConfig>,
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -60,39 +82,6 @@ pub struct OAuth2Config {
 }
 
 type PulsarProducer = Producer<TokioExecutor>;
-type BoxedPulsarProducer = Box<PulsarProducer>;
-
-enum PulsarSinkState {
-    None,
-    Ready(BoxedPulsarProducer),
-    Sending(
-        BoxFuture<
-            'static,
-            (
-                BoxedPulsarProducer,
-                Result<SendFuture, PulsarError>,
-                RequestMetadata,
-                EventFinalizers,
-            ),
-        >,
-    ),
-}
-
-struct PulsarSink {
-    transformer: Transformer,
-    encoder: Encoder<()>,
-    state: PulsarSinkState,
-    in_flight: FuturesUnordered<
-        BoxFuture<
-            'static,
-            (
-                Result<CommandSendReceipt, PulsarError>,
-                RequestMetadata,
-                EventFinalizers,
-            ),
-        >,
-    >,
-}
 
 inventory::submit! {
     SinkDescription::new::<PulsarSinkConfig>("pulsar")
@@ -103,8 +92,11 @@ impl GenerateConfig for PulsarSinkConfig {
         toml::Value::try_from(Self {
             endpoint: "pulsar://127.0.0.1:6650".to_string(),
             topic: "topic-1234".to_string(),
+            partition_key_field: None,
+            batch: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             auth: None,
+            acknowledgements: Default::default(),
         })
         .unwrap()
     }
@@ -117,24 +109,18 @@ impl SinkConfig for PulsarSinkConfig {
         &self,
         _cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
-        let producer = self
-            .create_pulsar_producer()
-            .await
-            .context(CreatePulsarSinkSnafu)?;
+        let sink = self.build_sink().await?;
 
-        let transformer = self.encoding.transformer();
-        let serializer = self.encoding.build()?;
-        let encoder = Encoder::<()>::new(serializer);
-
-        let sink = PulsarSink::new(producer, transformer, encoder)?;
-
-        let producer = self
-            .create_pulsar_producer()
-            .await
-            .context(CreatePulsarSinkSnafu)?;
-        let healthcheck = healthcheck(producer).boxed();
+        let healthcheck = healthcheck(
+            self.create_pulsar_client()
+                .await
+                .context(CreatePulsarSinkSnafu)?,
+            sink.topic.clone(),
+            self.producer_options(),
+        )
+        .boxed();
 
-        Ok((super::VectorSink::from_event_sink(sink), healthcheck))
+        Ok((super::VectorSink::from_event_streamsink(sink), healthcheck))
     }
 
     fn input(&self) -> Input {
@@ -146,12 +132,33 @@ impl SinkConfig for PulsarSinkConfig {
     }
 
     fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
-        None
+        Some(&self.acknowledgements)
     }
 }
 
 impl PulsarSinkConfig {
-    async fn create_pulsar_producer(&self) -> Result<PulsarProducer, PulsarError> {
+    async fn build_sink(&self) -> crate::Result<PulsarSink> {
+        let pulsar = self
+            .create_pulsar_client()
+            .await
+            .context(CreatePulsarSinkSnafu)?;
+
+        let transformer = self.encoding.transformer();
+        let serializer = self.encoding.build()?;
+        let encoder = Encoder::<()>::new(serializer);
+
+        Ok(PulsarSink {
+            pulsar,
+            producer_options: self.producer_options(),
+            topic: Template::try_from(self.topic.as_str()).context(TopicTemplateSnafu)?,
+            partition_key_field: self.partition_key_field.clone(),
+            transformer,
+            encoder,
+            batch_settings: self.batch.into_batcher_settings()?,
+        })
+    }
+
+    async fn create_pulsar_client(&self) -> Result<Pulsar<TokioExecutor>, PulsarError> {
         let mut builder = Pulsar::builder(&self.endpoint, TokioExecutor);
         if let Some(auth) = &self.auth {
             builder = match (
@@ -178,155 +185,237 @@ impl PulsarSinkConfig {
             };
         }
 
-        let pulsar = builder.build().await?;
-        if let SerializerConfig::Avro { avro } = self.encoding.config() {
-            pulsar
-                .producer()
-                .with_options(pulsar::producer::ProducerOptions {
-                    schema: Some(proto::Schema {
-                        schema_data: avro.schema.as_bytes().into(),
-                        r#type: proto::schema::Type::Avro as i32,
-                        ..Default::default()
-                    }),
-                    ..Default::default()
-                })
-                .with_topic(&self.topic)
-                .build()
-                .await
-        } else {
-            pulsar.producer().with_topic(&self.topic).build().await
-        }
+        builder.build().await
     }
-}
 
-async fn healthcheck(producer: PulsarProducer) -> crate::Result<()> {
-    producer.check_connection().await.map_err(Into::into)
-}
-
-impl PulsarSink {
-    fn new(
-        producer: PulsarProducer,
-        transformer: Transformer,
-        encoder: Encoder<()>,
-    ) -> crate::Result<Self> {
-        Ok(Self {
-            transformer,
-            encoder,
-            state: PulsarSinkState::Ready(Box::new(producer)),
-            in_flight: FuturesUnordered::new(),
-        })
-    }
+    fn producer_options(&self) -> ProducerOptions {
+        // Messages are batched by the producer up to the size of the batches of the sink, which
+        // flushes the producer at the end of each of its batches.
+        let batch_size = self
+            .batch
+            .max_events
+            .or(PulsarDefaultBatchSettings::MAX_EVENTS)
+            .map(|max_events| max_events as u32);
+        let schema = match self.encoding.config() {
+            SerializerConfig::Avro { avro } => Some(proto::Schema {
+                schema_data: avro.schema.as_bytes().into(),
+                r#type: proto::schema::Type::Avro as i32,
+                ..Default::default()
+            }),
+            _ => None,
+        };
 
-    fn poll_in_flight_prepare(&mut self, cx: &mut Context<'_>) -> Poll<()> {
-        if let PulsarSinkState::Sending(fut) = &mut self.state {
-            let (producer, result, metadata, finalizers) = ready!(fut.as_mut().poll(cx));
-
-            self.state = PulsarSinkState::Ready(producer);
-            self.in_flight.push(Box::pin(async move {
-                let result = match result {
-                    Ok(fut) => fut.await,
-                    Err(error) => Err(error),
-                };
-                (result, metadata, finalizers)
-            }));
+        ProducerOptions {
+            schema,
+            batch_size,
+            ..Default::default()
         }
-
-        Poll::Ready(())
     }
 }
 
-impl Sink<Event> for PulsarSink {
-    type Error = ();
+async fn create_producer(
+    pulsar: &Pulsar<TokioExecutor>,
+    topic: &str,
+    options: ProducerOptions,
+) -> Result<PulsarProducer, PulsarError> {
+    pulsar
+        .producer()
+        .with_options(options)
+        .with_topic(topic)
+        .build()
+        .await
+}
 
-    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        ready!(self.poll_in_flight_prepare(cx));
-        Poll::Ready(Ok(()))
+/// Checks the connection of a producer to the topic, or only the connection to the cluster when
+/// the topic depends on the events.
+async fn healthcheck(
+    pulsar: Pulsar<TokioExecutor>,
+    topic: Template,
+    options: ProducerOptions,
+) -> crate::Result<()> {
+    if topic.is_dynamic() {
+        return Ok(());
     }
 
-    fn start_send(mut self: Pin<&mut Self>, mut event: Event) -> Result<(), Self::Error> {
-        assert!(
-            matches!(self.state, PulsarSinkState::Ready(_)),
-            "Expected `poll_ready` to be called first."
-        );
+    let producer = create_producer(&pulsar, topic.get_ref(), options).await?;
+    producer.check_connection().await.map_err(Into::into)
+}
 
-        let event_time = event.maybe_as_log().and_then(|log| {
-            log.get(log_schema().timestamp_key())
-                .and_then(|v| v.as_timestamp().map(|dt| dt.timestamp_millis()))
-        });
+/// Partitions the events by the topic and the partition key of their messages.
+struct PulsarPartitioner<'a> {
+    topic: &'a Template,
+    partition_key_field: Option<&'a str>,
+}
 
-        let metadata_builder = RequestMetadata::builder(&event);
-        self.transformer.transform(&mut event);
+impl<'a> Partitioner for PulsarPartitioner<'a> {
+    type Item = Event;
+    type Key = Option<(String, Option<String>)>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        let topic = self
+            .topic
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("topic"),
+                    drop_event: true,
+                });
+            })
+            .ok()?;
+        let partition_key = self.partition_key_field.and_then(|field| {
+            item.maybe_as_log()
+                .and_then(|log| log.get(field))
+                .map(|value| value.to_string_lossy())
+        });
+        Some((topic, partition_key))
+    }
+}
 
-        let finalizers = event.take_finalizers();
-        let mut bytes = BytesMut::new();
-        self.encoder.encode(event, &mut bytes).map_err(|_| {
-            // Error is handled by `Encoder`.
-        })?;
+struct PulsarSink {
+    pulsar: Pulsar<TokioExecutor>,
+    producer_options: ProducerOptions,
+    topic: Template,
+    partition_key_field: Option<String>,
+    transformer: Transformer,
+    encoder: Encoder<()>,
+    batch_settings: BatcherSettings,
+}
 
-        let bytes_len =
-            NonZeroUsize::new(bytes.len()).expect("payload should never be zero length");
-        let metadata = metadata_builder.with_request_size(bytes_len);
+impl PulsarSink {
+    /// Sends the events as messages to the topic, as a single batch of messages of the producer.
+    async fn send(
+        &self,
+        producer: &mut PulsarProducer,
+        partition_key: Option<String>,
+        events: Vec<Event>,
+    ) -> Result<(), PulsarError> {
+        let mut finalizers = EventFinalizers::default();
+        let mut receipts = Vec::with_capacity(events.len());
+        let mut count = 0;
+        let mut byte_size = 0;
+        let mut bytes_sent = 0;
+
+        let mut encoder = self.encoder.clone();
+        let mut result = Ok(());
+        for mut event in events {
+            finalizers.merge(event.take_finalizers());
+            if result.is_err() {
+                continue;
+            }
 
-        let mut producer = match std::mem::replace(&mut self.state, PulsarSinkState::None) {
-            PulsarSinkState::Ready(producer) => producer,
-            _ => unreachable!(),
-        };
+            let event_byte_size = event.size_of();
+            let event_time = event.maybe_as_log().and_then(|log| {
+                log.get(log_schema().timestamp_key())
+                    .and_then(|v| v.as_timestamp().map(|dt| dt.timestamp_millis() as u64))
+            });
+
+            self.transformer.transform(&mut event);
+            let mut bytes = BytesMut::new();
+            if encoder.encode(event, &mut bytes).is_err() {
+                // Error is handled by `Encoder`.
+                continue;
+            }
 
-        let _ = std::mem::replace(
-            &mut self.state,
-            PulsarSinkState::Sending(Box::pin(async move {
-                let mut builder = producer.create_message().with_content(bytes.as_ref());
-                if let Some(et) = event_time {
-                    builder = builder.event_time(et as u64);
+            let payload_len = bytes.len();
+            let message = Message {
+                payload: bytes.to_vec(),
+                partition_key: partition_key.clone(),
+                event_time,
+                ..Default::default()
+            };
+            match producer.send(message).await {
+                Ok(receipt) => receipts.push(receipt),
+                Err(error) => {
+                    result = Err(error);
+                    continue;
                 }
-                let result = builder.send().await;
-                (producer, result, metadata, finalizers)
-            })),
-        );
+            }
+            count += 1;
+            byte_size += event_byte_size;
+            bytes_sent += payload_len;
+        }
 
-        Ok(())
+        if result.is_ok() {
+            result = producer.send_batch().await;
+        }
+        if result.is_ok() {
+            result = join_all(receipts)
+                .await
+                .into_iter()
+                .find_map(Result::err)
+                .map_or(Ok(()), Err);
+        }
+
+        match result {
+            Ok(()) => {
+                finalizers.update_status(EventStatus::Delivered);
+
+                emit!(EventsSent {
+                    count,
+                    byte_size,
+                    output: None,
+                });
+                emit!(BytesSent {
+                    byte_size: bytes_sent,
+                    protocol: "tcp",
+                });
+                Ok(())
+            }
+            Err(error) => {
+                finalizers.update_status(EventStatus::Errored);
+                Err(error)
+            }
+        }
     }
+}
 
-    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        ready!(self.poll_in_flight_prepare(cx));
-
-        let this = Pin::into_inner(self);
-        while !this.in_flight.is_empty() {
-            match ready!(Pin::new(&mut this.in_flight).poll_next(cx)) {
-                Some((Ok(result), metadata, finalizers)) => {
-                    trace!(
-                        message = "Pulsar sink produced message.",
-                        message_id = ?result.message_id,
-                        producer_id = %result.producer_id,
-                        sequence_id = %result.sequence_id,
-                    );
-
-                    emit!(EventsSent {
-                        count: metadata.event_count(),
-                        byte_size: metadata.events_byte_size(),
-                        output: None,
-                    });
-
-                    emit!(BytesSent {
-                        byte_size: metadata.request_encoded_size(),
-                        protocol: "tcp",
-                    });
-
-                    drop(finalizers);
-                }
-                Some((Err(error), _, _)) => {
-                    error!(message = "Pulsar sink generated an error.", %error);
-                    return Poll::Ready(Err(()));
+#[async_trait]
+impl StreamSink<Event> for PulsarSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let partitioner = PulsarPartitioner {
+            topic: &self.topic,
+            partition_key_field: self.partition_key_field.as_deref(),
+        };
+        let mut batches = input
+            .batched_partitioned(partitioner, self.batch_settings)
+            .filter_map(|(key, batch)| async move { key.map(move |key| (key, batch)) });
+
+        let mut producers: HashMap<String, PulsarProducer> = HashMap::new();
+        while let Some(((topic, partition_key), events)) = batches.next().await {
+            let count = events.len();
+            if !producers.contains_key(&topic) {
+                match create_producer(&self.pulsar, &topic, self.producer_options.clone()).await {
+                    Ok(producer) => {
+                        producers.insert(topic.clone(), producer);
+                    }
+                    Err(error) => {
+                        for mut event in events {
+                            event.take_finalizers().update_status(EventStatus::Errored);
+                        }
+                        emit!(PulsarSendingError { error, count });
+                        continue;
+                    }
                 }
-                None => break,
+            }
+
+            let producer = producers
+                .get_mut(&topic)
+                .expect("producer was just created");
+            if let Err(error) = self.send(producer, partition_key, events).await {
+                emit!(PulsarSendingError { error, count });
+                // The producer is created again for the next batch of the topic.
+                producers.remove(&topic);
             }
         }
 
-        Poll::Ready(Ok(()))
-    }
+        for (_, mut producer) in producers {
+            if let Err(error) = producer.close().await {
+                error!(message = "Failed to close Pulsar producer.", %error);
+            }
+        }
 
-    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        self.poll_flush(cx)
+        Ok(())
     }
 }
 
@@ -338,6 +427,42 @@ mod tests {
     fn generate_config() {
         crate::test_util::test_generate_config::<PulsarSinkConfig>();
     }
+
+    #[test]
+    fn partitions_by_topic_and_key() {
+        let topic = Template::try_from("logs-{{ service }}").unwrap();
+        let partitioner = PulsarPartitioner {
+            topic: &topic,
+            partition_key_field: Some("user_id"),
+        };
+
+        let mut log = crate::event::LogEvent::from("hello");
+        log.insert("service", "api");
+        log.insert("user_id", "alice");
+        assert_eq!(
+            partitioner.partition(&log.into()),
+            Some(("logs-api".to_owned(), Some("alice".to_owned())))
+        );
+
+        let log = crate::event::LogEvent::from("hello");
+        assert_eq!(partitioner.partition(&log.into()), None);
+    }
+
+    #[test]
+    fn batches_producer_messages() {
+        let config: PulsarSinkConfig = toml::from_str(
+            r#"
+            endpoint = "pulsar://127.0.0.1:6650"
+            topic = "logs-{{ service }}"
+            partition_key_field = "user_id"
+            batch.max_events = 50
+            encoding.codec = "json"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.producer_options().batch_size, Some(50));
+    }
 }
 
 #[cfg(feature = "pulsar-integration-tests")]
@@ -368,8 +493,11 @@ mod integration_tests {
         let cnf = PulsarSinkConfig {
             endpoint: pulsar_address(),
             topic: topic.clone(),
+            partition_key_field: None,
+            batch: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             auth: None,
+            acknowledgements: Default::default(),
         };
 
         let pulsar = Pulsar::<TokioExecutor>::builder(&cnf.endpoint, TokioExecutor)
@@ -390,12 +518,7 @@ mod integration_tests {
             .await
             .unwrap();
 
-        let producer = cnf.create_pulsar_producer().await.unwrap();
-        let transformer = cnf.encoding.transformer();
-        let serializer = cnf.encoding.build().unwrap();
-        let encoder = Encoder::<()>::new(serializer);
-        let sink = PulsarSink::new(producer, transformer, encoder).unwrap();
-        let sink = VectorSink::from_event_sink(sink);
+        let sink = VectorSink::from_event_streamsink(cnf.build_sink().await.unwrap());
         run_and_assert_sink_compliance(sink, events, &SINK_TAGS).await;
 
         for line in input {
@@ -407,4 +530,64 @@ mod integration_tests {
             assert_eq!(String::from_utf8_lossy(&msg.payload.data), line);
         }
     }
+
+    #[tokio::test]
+    async fn pulsar_templated_topic_and_partition_key() {
+        trace_init();
+
+        let prefix = format!("test-{}", random_string(10));
+        let cnf = PulsarSinkConfig {
+            endpoint: pulsar_address(),
+            topic: format!("{}-{{{{ service }}}}", prefix),
+            partition_key_field: Some("user_id".to_owned()),
+            batch: Default::default(),
+            encoding: TextSerializerConfig::new().into(),
+            auth: None,
+            acknowledgements: Default::default(),
+        };
+
+        let pulsar = Pulsar::<TokioExecutor>::builder(&cnf.endpoint, TokioExecutor)
+            .build()
+            .await
+            .unwrap();
+        let mut consumers = Vec::new();
+        for service in ["api", "web"] {
+            let consumer = pulsar
+                .consumer()
+                .with_topic(format!("{}-{}", prefix, service))
+                .with_consumer_name("VectorTestConsumer")
+                .with_subscription_type(SubType::Exclusive)
+                .with_subscription("VectorTestSub")
+                .build::<String>()
+                .await
+                .unwrap();
+            consumers.push((service, consumer));
+        }
+
+        let events = (0..10).map(|index| {
+            let mut log = crate::event::LogEvent::from(format!("message {}", index));
+            log.insert("service", if index % 2 == 0 { "api" } else { "web" });
+            log.insert("user_id", format!("user-{}", index % 3));
+            Event::from(log)
+        });
+        let sink = VectorSink::from_event_streamsink(cnf.build_sink().await.unwrap());
+        run_and_assert_sink_compliance(sink, futures::stream::iter(events), &SINK_TAGS).await;
+
+        for (service, consumer) in &mut consumers {
+            for _ in 0..5 {
+                let msg = consumer.next().await.unwrap().unwrap();
+                consumer.ack(&msg).await.unwrap();
+
+                let index: usize = String::from_utf8_lossy(&msg.payload.data)
+                    .trim_start_matches("message ")
+                    .parse()
+                    .unwrap();
+                assert_eq!(*service, if index % 2 == 0 { "api" } else { "web" });
+                assert_eq!(
+                    msg.payload.metadata.partition_key,
+                    Some(format!("user-{}", index % 3))
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,160 @@
+use std::{collections::HashMap, convert::TryFrom};
+
+use indoc::indoc;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    encoder::{QuestDbColumnType, QuestDbEncoder},
+    sink::QuestDbSink,
+};
+use crate::{
+    codecs::Transformer,
+    config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
+    sinks::{
+        util::{statistic::validate_quantiles, tcp::TcpSinkConfig, BatchConfig, SinkBatchSettings},
+        Healthcheck, VectorSink,
+    },
+    tcp::TcpKeepaliveConfig,
+    template::Template,
+    tls::TlsEnableableConfig,
+};
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct QuestDbDefaultBatchSettings;
+
+impl SinkBatchSettings for QuestDbDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(1_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct QuestDbSinkConfig {
+    /// The address of the InfluxDB Line Protocol endpoint of QuestDB, such as `127.0.0.1:9009`.
+    pub address: String,
+    /// The table events are written to.
+    ///
+    /// Defaults to `logs` for logs, and to the namespaced name of metrics for metrics.
+    pub table: Option<String>,
+    /// The namespace of metrics without one.
+    pub default_namespace: Option<String>,
+    /// The column types log fields are written as, by field name.
+    ///
+    /// Fields without a type hint are written as the column type matching their value.
+    #[serde(default)]
+    pub type_hints: HashMap<String, QuestDbColumnType>,
+    /// The quantiles written for distributions and sketches.
+    #[serde(default = "default_quantiles")]
+    pub quantiles: Vec<f64>,
+    #[serde(
+        skip_serializing_if = "crate::serde::skip_serializing_if_default",
+        default
+    )]
+    pub encoding: Transformer,
+    #[serde(default)]
+    pub batch: BatchConfig<QuestDbDefaultBatchSettings>,
+    pub keepalive: Option<TcpKeepaliveConfig>,
+    pub tls: Option<TlsEnableableConfig>,
+    pub send_buffer_bytes: Option<usize>,
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+fn default_quantiles() -> Vec<f64> {
+    vec![0.5, 0.75, 0.9, 0.95, 0.99]
+}
+
+impl GenerateConfig for QuestDbSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc! {r#"
+            address = "127.0.0.1:9009"
+            type_hints.level = "symbol"
+        "#})
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "questdb")]
+impl SinkConfig for QuestDbSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        validate_quantiles(&self.quantiles)?;
+
+        let connector = TcpSinkConfig::new(
+            self.address.clone(),
+            self.keepalive,
+            self.tls.clone(),
+            self.send_buffer_bytes,
+        )
+        .build_connector()?;
+
+        let encoder = QuestDbEncoder {
+            table: self.table.as_deref().map(Template::try_from).transpose()?,
+            default_namespace: self.default_namespace.clone(),
+            type_hints: self.type_hints.clone(),
+            quantiles: self.quantiles.clone(),
+            transformer: self.encoding.clone(),
+        };
+
+        let healthcheck = {
+            let connector = connector.clone();
+            Box::pin(async move { connector.healthcheck().await })
+        };
+        let sink = QuestDbSink {
+            connector,
+            encoder,
+            batch_settings: self.batch.into_batcher_settings()?,
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(DataType::Log | DataType::Metric)
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "questdb"
+    }
+
+    fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
+        Some(&self.acknowledgements)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<QuestDbSinkConfig>();
+    }
+
+    #[test]
+    fn parses_type_hints() {
+        let config: QuestDbSinkConfig = toml::from_str(indoc! {r#"
+            address = "127.0.0.1:9009"
+            table = "{{ service }}_logs"
+
+            [type_hints]
+            level = "symbol"
+            status = "long"
+            "request.duration" = "double"
+        "#})
+        .unwrap();
+
+        assert_eq!(config.type_hints.len(), 3);
+        assert_eq!(config.type_hints["status"], QuestDbColumnType::Long);
+        assert_eq!(
+            config.type_hints["request.duration"],
+            QuestDbColumnType::Double
+        );
+        assert_eq!(config.quantiles, default_quantiles());
+    }
+}
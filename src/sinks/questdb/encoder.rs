@@ -0,0 +1,611 @@
+use std::collections::{BTreeMap, HashMap};
+
+use bytes::{BufMut, BytesMut};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use vector_core::event::metric::{MetricSketch, Quantile};
+
+use crate::{
+    codecs::Transformer,
+    config::log_schema,
+    event::{
+        metric::{Metric, MetricValue, StatisticKind},
+        Event, LogEvent, Value,
+    },
+    sinks::util::{encode_namespace, statistic::DistributionStatistic},
+    template::{Template, TemplateRenderingError},
+};
+
+/// Characters QuestDB doesn't allow in table names.
+const ILLEGAL_TABLE_CHARS: &str = "?,'\"\\/:()+*%~\n\r\0";
+
+/// Characters QuestDB doesn't allow in column names.
+const ILLEGAL_COLUMN_CHARS: &str = "?.,'\"\\/:()+-*%~ \n\r\0";
+
+/// The table logs are written to when no table is configured.
+const DEFAULT_LOGS_TABLE: &str = "logs";
+
+/// The QuestDB column type a field is written as.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuestDbColumnType {
+    /// An indexed string, for fields with a limited number of distinct values.
+    Symbol,
+
+    /// A string.
+    String,
+
+    /// A 64-bit signed integer.
+    Long,
+
+    /// A 64-bit floating point number.
+    Double,
+
+    /// A boolean.
+    Boolean,
+
+    /// A timestamp, with microsecond precision.
+    Timestamp,
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum EncodeError {
+    #[snafu(display("failed to render the table: {}", source))]
+    Table { source: TemplateRenderingError },
+    #[snafu(display("field {:?} can't be written as a {:?} column", field, column_type))]
+    TypeHint {
+        field: String,
+        column_type: QuestDbColumnType,
+    },
+    #[snafu(display("the row has no columns"))]
+    NoColumns,
+}
+
+#[derive(Debug, PartialEq)]
+enum Column {
+    String(String),
+    Long(i64),
+    Double(f64),
+    Boolean(bool),
+    Timestamp(DateTime<Utc>),
+}
+
+/// A row of a table, in the order ILP expects its parts.
+#[derive(Debug, Default)]
+struct Row {
+    symbols: BTreeMap<String, String>,
+    columns: BTreeMap<String, Column>,
+    timestamp: Option<DateTime<Utc>>,
+}
+
+impl Row {
+    fn insert(&mut self, field: &str, column_type: QuestDbColumnType, value: &Value) -> bool {
+        let name = sanitize(field, ILLEGAL_COLUMN_CHARS);
+        if column_type == QuestDbColumnType::Symbol {
+            self.symbols.insert(name, value.to_string_lossy());
+            return true;
+        }
+
+        match convert(value, column_type) {
+            Some(column) => {
+                self.columns.insert(name, column);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // https://questdb.io/docs/reference/api/ilp/overview/
+    fn encode(self, table: &str, output: &mut BytesMut) -> Result<(), EncodeError> {
+        if self.columns.is_empty() {
+            return Err(EncodeError::NoColumns);
+        }
+
+        encode_name(table, output);
+        for (name, value) in self.symbols {
+            output.put_u8(b',');
+            encode_name(&name, output);
+            output.put_u8(b'=');
+            encode_name(&value, output);
+        }
+
+        let mut separator = b' ';
+        for (name, column) in self.columns {
+            output.put_u8(separator);
+            separator = b',';
+            encode_name(&name, output);
+            output.put_u8(b'=');
+            match column {
+                Column::String(value) => {
+                    output.put_u8(b'"');
+                    for c in value.chars() {
+                        if "\\\"\n".contains(c) {
+                            output.put_u8(b'\\');
+                        }
+                        let mut c_buffer: [u8; 4] = [0; 4];
+                        output.put_slice(c.encode_utf8(&mut c_buffer).as_bytes());
+                    }
+                    output.put_u8(b'"');
+                }
+                Column::Long(value) => {
+                    output.put_slice(value.to_string().as_bytes());
+                    output.put_u8(b'i');
+                }
+                Column::Double(value) => encode_double(value, output),
+                Column::Boolean(value) => output.put_slice(value.to_string().as_bytes()),
+                Column::Timestamp(value) => {
+                    output.put_slice(value.timestamp_micros().to_string().as_bytes());
+                    output.put_u8(b't');
+                }
+            }
+        }
+
+        // Rows without a designated timestamp are given the time of the server on ingestion.
+        if let Some(timestamp) = self.timestamp {
+            output.put_u8(b' ');
+            output.put_slice(timestamp.timestamp_nanos().to_string().as_bytes());
+        }
+        output.put_u8(b'\n');
+        Ok(())
+    }
+}
+
+/// Encodes events as rows of the InfluxDB Line Protocol.
+#[derive(Clone, Debug)]
+pub(super) struct QuestDbEncoder {
+    pub(super) table: Option<Template>,
+    pub(super) default_namespace: Option<String>,
+    pub(super) type_hints: HashMap<String, QuestDbColumnType>,
+    pub(super) quantiles: Vec<f64>,
+    pub(super) transformer: Transformer,
+}
+
+impl QuestDbEncoder {
+    /// Appends the row of the event to the output, leaving the output untouched on errors.
+    pub(super) fn encode_event(
+        &self,
+        event: Event,
+        output: &mut BytesMut,
+    ) -> Result<(), EncodeError> {
+        let table = self
+            .table
+            .as_ref()
+            .map(|table| table.render_string(&event))
+            .transpose()
+            .map_err(|source| EncodeError::Table { source })?;
+
+        match event {
+            Event::Log(log) => {
+                let mut event = Event::from(log);
+                self.transformer.transform(&mut event);
+                let table = table.unwrap_or_else(|| DEFAULT_LOGS_TABLE.to_owned());
+                self.encode_log(event.into_log())?
+                    .encode(&sanitize(&table, ILLEGAL_TABLE_CHARS), output)
+            }
+            Event::Metric(metric) => {
+                let table = table.unwrap_or_else(|| {
+                    encode_namespace(
+                        metric.namespace().or(self.default_namespace.as_deref()),
+                        '_',
+                        metric.name(),
+                    )
+                });
+                self.encode_metric(&metric)
+                    .encode(&sanitize(&table, ILLEGAL_TABLE_CHARS), output)
+            }
+            Event::Trace(_) => unreachable!("traces are not accepted by the sink"),
+        }
+    }
+
+    fn encode_log(&self, mut log: LogEvent) -> Result<Row, EncodeError> {
+        let mut row = Row::default();
+        if !self.type_hints.contains_key(log_schema().timestamp_key()) {
+            if let Some(Value::Timestamp(timestamp)) = log.get(log_schema().timestamp_key()) {
+                row.timestamp = Some(*timestamp);
+                log.remove(log_schema().timestamp_key());
+            }
+        }
+
+        for (field, value) in log.convert_to_fields() {
+            if matches!(value, Value::Null) {
+                continue;
+            }
+
+            let column_type = self.type_hints.get(&field).copied().unwrap_or_else(|| {
+                if field == log_schema().host_key() || field == log_schema().source_type_key() {
+                    QuestDbColumnType::Symbol
+                } else {
+                    infer(value)
+                }
+            });
+            if !row.insert(&field, column_type, value) {
+                return Err(EncodeError::TypeHint { field, column_type });
+            }
+        }
+
+        Ok(row)
+    }
+
+    fn encode_metric(&self, metric: &Metric) -> Row {
+        let mut row = Row {
+            symbols: metric.tags().cloned().unwrap_or_default(),
+            timestamp: metric.timestamp(),
+            ..Default::default()
+        };
+
+        let columns = &mut row.columns;
+        match metric.value() {
+            MetricValue::Counter { value } | MetricValue::Gauge { value } => {
+                columns.insert("value".to_owned(), Column::Double(*value));
+            }
+            MetricValue::Set { values } => {
+                columns.insert("value".to_owned(), Column::Long(values.len() as i64));
+            }
+            MetricValue::Distribution { samples, statistic } => {
+                let quantiles = match statistic {
+                    StatisticKind::Histogram => &[0.95] as &[_],
+                    StatisticKind::Summary => &self.quantiles,
+                };
+                if let Some(statistic) = DistributionStatistic::from_samples(samples, quantiles) {
+                    columns.insert("min".to_owned(), Column::Double(statistic.min));
+                    columns.insert("max".to_owned(), Column::Double(statistic.max));
+                    columns.insert("median".to_owned(), Column::Double(statistic.median));
+                    columns.insert("avg".to_owned(), Column::Double(statistic.avg));
+                    columns.insert("sum".to_owned(), Column::Double(statistic.sum));
+                    columns.insert("count".to_owned(), Column::Long(statistic.count as i64));
+                    for (quantile, value) in statistic.quantiles {
+                        columns.insert(percentile(quantile), Column::Double(value));
+                    }
+                }
+            }
+            MetricValue::AggregatedHistogram {
+                buckets,
+                count,
+                sum,
+            } => {
+                for bucket in buckets {
+                    columns.insert(
+                        sanitize(
+                            &format!("bucket_{}", bucket.upper_limit),
+                            ILLEGAL_COLUMN_CHARS,
+                        ),
+                        Column::Long(bucket.count as i64),
+                    );
+                }
+                columns.insert("count".to_owned(), Column::Long(*count as i64));
+                columns.insert("sum".to_owned(), Column::Double(*sum));
+            }
+            MetricValue::AggregatedSummary {
+                quantiles,
+                count,
+                sum,
+            } => {
+                for quantile in quantiles {
+                    columns.insert(
+                        percentile(quantile.quantile),
+                        Column::Double(quantile.value),
+                    );
+                }
+                columns.insert("count".to_owned(), Column::Long(*count as i64));
+                columns.insert("sum".to_owned(), Column::Double(*sum));
+            }
+            MetricValue::Sketch { sketch } => match sketch {
+                MetricSketch::AgentDDSketch(ddsketch) => {
+                    for &quantile in &self.quantiles {
+                        columns.insert(
+                            percentile(quantile),
+                            Column::Double(ddsketch.quantile(quantile).unwrap_or(0.0)),
+                        );
+                    }
+                    columns.insert(
+                        "count".to_owned(),
+                        Column::Long(i64::from(ddsketch.count())),
+                    );
+                    columns.insert(
+                        "min".to_owned(),
+                        Column::Double(ddsketch.min().unwrap_or(f64::MAX)),
+                    );
+                    columns.insert(
+                        "max".to_owned(),
+                        Column::Double(ddsketch.max().unwrap_or(f64::MIN)),
+                    );
+                    columns.insert(
+                        "sum".to_owned(),
+                        Column::Double(ddsketch.sum().unwrap_or(0.0)),
+                    );
+                    columns.insert(
+                        "avg".to_owned(),
+                        Column::Double(ddsketch.avg().unwrap_or(0.0)),
+                    );
+                }
+            },
+        }
+
+        row
+    }
+}
+
+/// The name of the column of a quantile, such as `p99` for the 0.99 quantile.
+fn percentile(quantile: f64) -> String {
+    let quantile = Quantile {
+        quantile,
+        value: 0.0,
+    };
+    format!("p{}", quantile.to_percentile_string())
+}
+
+/// The column type of a field without a type hint.
+const fn infer(value: &Value) -> QuestDbColumnType {
+    match value {
+        Value::Integer(_) => QuestDbColumnType::Long,
+        Value::Float(_) => QuestDbColumnType::Double,
+        Value::Boolean(_) => QuestDbColumnType::Boolean,
+        Value::Timestamp(_) => QuestDbColumnType::Timestamp,
+        _ => QuestDbColumnType::String,
+    }
+}
+
+/// Converts the value to a column of the type, parsing strings into the other types.
+fn convert(value: &Value, column_type: QuestDbColumnType) -> Option<Column> {
+    match (column_type, value) {
+        (QuestDbColumnType::String, value) => Some(Column::String(value.to_string_lossy())),
+        (QuestDbColumnType::Long, Value::Integer(value)) => Some(Column::Long(*value)),
+        (QuestDbColumnType::Long, Value::Float(value)) => {
+            Some(Column::Long(value.into_inner() as i64))
+        }
+        (QuestDbColumnType::Long, Value::Bytes(value)) => String::from_utf8_lossy(value)
+            .trim()
+            .parse()
+            .ok()
+            .map(Column::Long),
+        (QuestDbColumnType::Double, Value::Integer(value)) => Some(Column::Double(*value as f64)),
+        (QuestDbColumnType::Double, Value::Float(value)) => {
+            Some(Column::Double(value.into_inner()))
+        }
+        (QuestDbColumnType::Double, Value::Bytes(value)) => String::from_utf8_lossy(value)
+            .trim()
+            .parse()
+            .ok()
+            .map(Column::Double),
+        (QuestDbColumnType::Boolean, Value::Boolean(value)) => Some(Column::Boolean(*value)),
+        (QuestDbColumnType::Boolean, Value::Bytes(value)) => String::from_utf8_lossy(value)
+            .trim()
+            .parse()
+            .ok()
+            .map(Column::Boolean),
+        (QuestDbColumnType::Timestamp, Value::Timestamp(value)) => Some(Column::Timestamp(*value)),
+        (QuestDbColumnType::Timestamp, Value::Bytes(value)) => {
+            DateTime::parse_from_rfc3339(String::from_utf8_lossy(value).trim())
+                .ok()
+                .map(|timestamp| Column::Timestamp(timestamp.with_timezone(&Utc)))
+        }
+        _ => None,
+    }
+}
+
+/// Replaces the characters QuestDB doesn't allow in names, such as the `.` separating the names
+/// of nested fields in column names, with `_`.
+fn sanitize(name: &str, illegal: &str) -> String {
+    name.chars()
+        .map(|c| if illegal.contains(c) { '_' } else { c })
+        .collect()
+}
+
+fn encode_name(name: &str, output: &mut BytesMut) {
+    for c in name.chars() {
+        if "\\, =".contains(c) {
+            output.put_u8(b'\\');
+        }
+        let mut c_buffer: [u8; 4] = [0; 4];
+        output.put_slice(c.encode_utf8(&mut c_buffer).as_bytes());
+    }
+}
+
+fn encode_double(value: f64, output: &mut BytesMut) {
+    if value.is_nan() {
+        output.put_slice(b"NaN");
+    } else if value.is_infinite() {
+        output.put_slice(if value > 0.0 {
+            b"Infinity"
+        } else {
+            b"-Infinity"
+        });
+    } else {
+        output.put_slice(value.to_string().as_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, convert::TryFrom};
+
+    use chrono::TimeZone;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::event::metric::MetricKind;
+
+    fn encoder() -> QuestDbEncoder {
+        QuestDbEncoder {
+            table: None,
+            default_namespace: None,
+            type_hints: HashMap::new(),
+            quantiles: vec![0.5, 0.99],
+            transformer: Default::default(),
+        }
+    }
+
+    fn encode(encoder: &QuestDbEncoder, event: Event) -> Result<String, EncodeError> {
+        let mut output = BytesMut::new();
+        encoder.encode_event(event, &mut output)?;
+        Ok(String::from_utf8(output.to_vec()).unwrap())
+    }
+
+    fn ts() -> DateTime<Utc> {
+        Utc.ymd(2022, 9, 1).and_hms_nano(12, 30, 0, 123_456_789)
+    }
+
+    #[test]
+    fn encodes_log() {
+        let mut log = LogEvent::default();
+        log.insert("message", "GET /index.html");
+        log.insert("host", "web-1");
+        log.insert("status", 200);
+        log.insert("duration", 1.5);
+        log.insert("cached", false);
+        log.insert("http.path", "/index.html");
+        log.insert("timestamp", ts());
+
+        let mut encoder = encoder();
+        encoder.table = Some(Template::try_from("http_{{ host }}").unwrap());
+
+        assert_eq!(
+            encode(&encoder, log.into()).unwrap(),
+            "http_web-1,host=web-1 cached=false,duration=1.5,http_path=\"/index.html\",message=\"GET /index.html\",status=200i 1662035400123456789\n"
+        );
+    }
+
+    #[test]
+    fn applies_type_hints() {
+        let mut log = LogEvent::default();
+        log.insert("message", "done");
+        log.insert("level", "info");
+        log.insert("status", "404");
+        log.insert("bytes", 512);
+        log.insert("started_at", "2022-09-01T12:30:00.123456Z");
+
+        let mut encoder = encoder();
+        encoder.type_hints = vec![
+            ("level".to_owned(), QuestDbColumnType::Symbol),
+            ("status".to_owned(), QuestDbColumnType::Long),
+            ("bytes".to_owned(), QuestDbColumnType::Double),
+            ("started_at".to_owned(), QuestDbColumnType::Timestamp),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            encode(&encoder, log.into()).unwrap(),
+            "logs,level=info bytes=512,message=\"done\",started_at=1662035400123456t,status=404i\n"
+        );
+    }
+
+    #[test]
+    fn rejects_unconvertible_type_hint() {
+        let mut log = LogEvent::default();
+        log.insert("status", "not found");
+
+        let mut encoder = encoder();
+        encoder.type_hints = vec![("status".to_owned(), QuestDbColumnType::Long)]
+            .into_iter()
+            .collect();
+
+        let error = encode(&encoder, log.into()).unwrap_err();
+        assert!(matches!(
+            error,
+            EncodeError::TypeHint {
+                column_type: QuestDbColumnType::Long,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn escapes_names_and_strings() {
+        let mut log = LogEvent::default();
+        log.insert("host", "web 1,eu=west");
+        log.insert("quote", "say \"hi\"\\\nbye");
+
+        assert_eq!(
+            encode(&encoder(), log.into()).unwrap(),
+            "logs,host=web\\ 1\\,eu\\=west quote=\"say \\\"hi\\\"\\\\\\\nbye\"\n"
+        );
+    }
+
+    #[test]
+    fn rejects_row_without_columns() {
+        let mut log = LogEvent::default();
+        log.insert("host", "web-1");
+
+        assert!(matches!(
+            encode(&encoder(), log.into()),
+            Err(EncodeError::NoColumns)
+        ));
+    }
+
+    #[test]
+    fn encodes_counter() {
+        let metric = Metric::new(
+            "requests",
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 42.0 },
+        )
+        .with_namespace(Some("http"))
+        .with_tags(Some(
+            vec![("host".to_owned(), "web-1".to_owned())]
+                .into_iter()
+                .collect(),
+        ))
+        .with_timestamp(Some(ts()));
+
+        assert_eq!(
+            encode(&encoder(), metric.into()).unwrap(),
+            "http_requests,host=web-1 value=42 1662035400123456789\n"
+        );
+    }
+
+    #[test]
+    fn encodes_set_with_default_namespace() {
+        let metric = Metric::new(
+            "users",
+            MetricKind::Incremental,
+            MetricValue::Set {
+                values: vec!["alice".to_owned(), "bob".to_owned()]
+                    .into_iter()
+                    .collect::<BTreeSet<_>>(),
+            },
+        );
+
+        let mut encoder = encoder();
+        encoder.default_namespace = Some("app".to_owned());
+
+        assert_eq!(
+            encode(&encoder, metric.into()).unwrap(),
+            "app_users value=2i\n"
+        );
+    }
+
+    #[test]
+    fn encodes_summary_distribution() {
+        let metric = Metric::new(
+            "latency",
+            MetricKind::Incremental,
+            MetricValue::Distribution {
+                samples: vector_core::samples![1.0 => 1, 2.0 => 1, 3.0 => 2],
+                statistic: StatisticKind::Summary,
+            },
+        );
+
+        assert_eq!(
+            encode(&encoder(), metric.into()).unwrap(),
+            "latency avg=2.25,count=4i,max=3,median=2,min=1,p50=2,p99=3,sum=9\n"
+        );
+    }
+
+    #[test]
+    fn encodes_non_finite_doubles() {
+        let metric = Metric::new(
+            "temperature",
+            MetricKind::Absolute,
+            MetricValue::Gauge {
+                value: f64::NEG_INFINITY,
+            },
+        );
+
+        assert_eq!(
+            encode(&encoder(), metric.into()).unwrap(),
+            "temperature value=-Infinity\n"
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use std::time::Duration;
+
+use serde_json::Value as JsonValue;
+
+use super::QuestDbSinkConfig;
+use crate::{
+    config::{SinkConfig, SinkContext},
+    event::{
+        metric::{Metric, MetricKind, MetricValue},
+        Event,
+    },
+    test_util::{
+        components::{run_and_assert_sink_compliance, SINK_TAGS},
+        random_lines_with_stream, random_string, trace_init,
+    },
+};
+
+fn questdb_host() -> String {
+    std::env::var("QUESTDB_HOST").unwrap_or_else(|_| String::from("localhost"))
+}
+
+fn config(table: &str) -> QuestDbSinkConfig {
+    toml::from_str(&format!(
+        r#"
+        address = "{}:9009"
+        table = "{}"
+        batch.timeout_secs = 1
+        "#,
+        questdb_host(),
+        table
+    ))
+    .unwrap()
+}
+
+/// Runs the query on the HTTP endpoint of QuestDB, returning the rows of its result.
+async fn query(query: &str) -> Option<Vec<JsonValue>> {
+    let response = reqwest::Client::new()
+        .get(format!("http://{}:9000/exec", questdb_host()))
+        .query(&[("query", query)])
+        .send()
+        .await
+        .unwrap();
+    let body: JsonValue = response.json().await.unwrap();
+    body["dataset"].as_array().cloned()
+}
+
+/// Waits for the rows written to the table to be committed, returning the number of rows.
+async fn count_rows(table: &str, expected: usize) -> usize {
+    let mut count = 0;
+    for _ in 0..30 {
+        count = query(&format!("SELECT count() FROM '{}'", table))
+            .await
+            .and_then(|rows| rows.first().and_then(|row| row[0].as_u64()))
+            .unwrap_or(0) as usize;
+        if count >= expected {
+            break;
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    count
+}
+
+#[tokio::test]
+async fn healthcheck() {
+    trace_init();
+
+    let (_, healthcheck) = config("healthcheck")
+        .build(SinkContext::new_test())
+        .await
+        .unwrap();
+    healthcheck.await.unwrap();
+}
+
+#[tokio::test]
+async fn writes_logs() {
+    trace_init();
+
+    let table = format!("logs_{}", random_string(10));
+    let (sink, _) = config(&table).build(SinkContext::new_test()).await.unwrap();
+
+    let num_events = 100;
+    let (_, events) = random_lines_with_stream(100, num_events, None);
+    run_and_assert_sink_compliance(sink, events, &SINK_TAGS).await;
+
+    assert_eq!(count_rows(&table, num_events).await, num_events);
+}
+
+#[tokio::test]
+async fn writes_metrics() {
+    trace_init();
+
+    let table = format!("metrics_{}", random_string(10));
+    let (sink, _) = config(&table).build(SinkContext::new_test()).await.unwrap();
+
+    let num_events = 10;
+    let events = (0..num_events).map(|index| {
+        Event::from(Metric::new(
+            "requests",
+            MetricKind::Absolute,
+            MetricValue::Gauge {
+                value: index as f64,
+            },
+        ))
+    });
+    run_and_assert_sink_compliance(sink, futures::stream::iter(events), &SINK_TAGS).await;
+
+    assert_eq!(count_rows(&table, num_events).await, num_events);
+    let rows = query(&format!("SELECT sum(value) FROM '{}'", table))
+        .await
+        .unwrap();
+    assert_eq!(rows[0][0].as_f64(), Some(45.0));
+}
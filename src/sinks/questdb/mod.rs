@@ -0,0 +1,19 @@
+//! The QuestDB sink.
+//!
+//! Writes logs and metrics to QuestDB as rows of the InfluxDB Line Protocol (ILP), sent in batches
+//! over a TCP connection to the ILP endpoint of the database.
+
+mod config;
+mod encoder;
+mod sink;
+
+#[cfg(all(test, feature = "questdb-integration-tests"))]
+mod integration_tests;
+
+use crate::config::SinkDescription;
+
+pub use self::{config::QuestDbSinkConfig, encoder::QuestDbColumnType};
+
+inventory::submit! {
+    SinkDescription::new::<QuestDbSinkConfig>("questdb")
+}
@@ -0,0 +1,117 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bytes::BytesMut;
+use futures::{stream::BoxStream, StreamExt};
+use tokio::{io::AsyncWriteExt, net::TcpStream, time::sleep};
+use vector_common::internal_event::{BytesSent, EventsSent};
+use vector_core::{stream::BatcherSettings, ByteSizeOf};
+
+use super::encoder::{EncodeError, QuestDbEncoder};
+use crate::{
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
+    internal_events::{QuestDbEncodingError, TcpSocketError, TemplateRenderingError},
+    sinks::util::{retries::ExponentialBackoff, tcp::TcpConnector, SinkBuilderExt, StreamSink},
+    tls::MaybeTlsStream,
+};
+
+pub(super) struct QuestDbSink {
+    pub(super) connector: TcpConnector,
+    pub(super) encoder: QuestDbEncoder,
+    pub(super) batch_settings: BatcherSettings,
+}
+
+impl QuestDbSink {
+    /// Writes the rows to the connection, reconnecting and writing them again until they are
+    /// written in full.
+    async fn write(&self, connection: &mut Option<MaybeTlsStream<TcpStream>>, rows: &[u8]) {
+        let mut backoff = ExponentialBackoff::from_millis(2)
+            .factor(250)
+            .max_delay(Duration::from_secs(60));
+        loop {
+            let stream = match connection {
+                Some(stream) => stream,
+                None => connection.insert(self.connector.connect_backoff().await),
+            };
+
+            let result = match stream.write_all(rows).await {
+                Ok(()) => stream.flush().await,
+                Err(error) => Err(error),
+            };
+            match result {
+                Ok(()) => return,
+                Err(error) => {
+                    emit!(TcpSocketError { error });
+                    *connection = None;
+                    sleep(backoff.next().unwrap()).await;
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for QuestDbSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let mut connection = None;
+        let mut batches = input.batched(self.batch_settings.into_byte_size_config());
+
+        while let Some(events) = batches.next().await {
+            let mut finalizers = EventFinalizers::default();
+            let mut rows = BytesMut::new();
+            let mut count = 0;
+            let mut byte_size = 0;
+
+            for mut event in events {
+                let event_finalizers = event.take_finalizers();
+                let event_byte_size = event.size_of();
+                match self.encoder.encode_event(event, &mut rows) {
+                    Ok(()) => {
+                        finalizers.merge(event_finalizers);
+                        count += 1;
+                        byte_size += event_byte_size;
+                    }
+                    Err(error) => {
+                        event_finalizers.update_status(EventStatus::Errored);
+                        match error {
+                            EncodeError::Table { source } => emit!(TemplateRenderingError {
+                                error: source,
+                                field: Some("table"),
+                                drop_event: true,
+                            }),
+                            error => emit!(QuestDbEncodingError {
+                                error: error.into()
+                            }),
+                        }
+                    }
+                }
+            }
+
+            if rows.is_empty() {
+                continue;
+            }
+
+            // ILP over TCP doesn't acknowledge rows, so they are delivered once written.
+            self.write(&mut connection, &rows).await;
+            finalizers.update_status(EventStatus::Delivered);
+
+            emit!(EventsSent {
+                count,
+                byte_size,
+                output: None,
+            });
+            emit!(BytesSent {
+                byte_size: rows.len(),
+                protocol: "tcp",
+            });
+        }
+
+        if let Some(mut stream) = connection {
+            if let Err(error) = stream.shutdown().await {
+                emit!(TcpSocketError { error });
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,5 +1,8 @@
 use codecs::{
-    encoding::{Framer, FramingConfig},
+    encoding::{
+        ChunkedGelfEncoderConfig, ChunkedGelfEncoderOptions, Framer, FramingConfig,
+        SerializerConfig,
+    },
     TextSerializerConfig,
 };
 use serde::{Deserialize, Serialize};
@@ -45,6 +48,12 @@ pub struct UdpMode {
     #[serde(flatten)]
     config: UdpSinkConfig,
     encoding: EncodingConfig,
+    /// Options for splitting GELF messages exceeding the size of a datagram into chunks.
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    chunked_gelf: ChunkedGelfEncoderOptions,
 }
 
 #[cfg(unix)]
@@ -98,11 +107,24 @@ impl SinkConfig for SocketSinkConfig {
                 let encoder = Encoder::<Framer>::new(framer, serializer);
                 config.build(transformer, encoder)
             }
-            Mode::Udp(UdpMode { config, encoding }) => {
+            Mode::Udp(UdpMode {
+                config,
+                encoding,
+                chunked_gelf,
+            }) => {
                 let transformer = encoding.transformer();
+                let chunker = match encoding.config() {
+                    SerializerConfig::Gelf => Some(
+                        ChunkedGelfEncoderConfig {
+                            chunked_gelf: chunked_gelf.clone(),
+                        }
+                        .build()?,
+                    ),
+                    _ => None,
+                };
                 let serializer = encoding.build()?;
                 let encoder = Encoder::<()>::new(serializer);
-                config.build(transformer, encoder)
+                config.build(transformer, encoder, chunker)
             }
             #[cfg(unix)]
             Mode::Unix(UnixMode { config, encoding }) => {
@@ -173,6 +195,7 @@ mod test {
             mode: Mode::Udp(UdpMode {
                 config: UdpSinkConfig::from_address(addr.to_string()),
                 encoding: JsonSerializerConfig::new().into(),
+                chunked_gelf: Default::default(),
             }),
         };
         let context = SinkContext::new_test();
@@ -7,7 +7,10 @@ use std::{
 
 use hyper::Body;
 use serde::{Deserialize, Serialize};
-use tokio::sync::{mpsc::Receiver, oneshot::Sender};
+use tokio::{
+    sync::{mpsc::Receiver, oneshot::Sender},
+    time::Instant,
+};
 use vector_core::event::EventStatus;
 
 use super::service::{HttpRequestBuilder, MetadataFields};
@@ -26,6 +29,7 @@ pub struct HecClientAcknowledgementsConfig {
     pub indexer_acknowledgements_enabled: bool,
     pub query_interval: NonZeroU8,
     pub retry_limit: NonZeroU8,
+    pub timeout_secs: Option<NonZeroU64>,
     pub max_pending_acks: NonZeroU64,
     #[serde(
         default,
@@ -42,6 +46,7 @@ impl Default for HecClientAcknowledgementsConfig {
             indexer_acknowledgements_enabled: true,
             query_interval: NonZeroU8::new(10).unwrap(),
             retry_limit: NonZeroU8::new(30).unwrap(),
+            timeout_secs: None,
             max_pending_acks: NonZeroU64::new(1_000_000).unwrap(),
             inner: Default::default(),
         }
@@ -66,9 +71,17 @@ pub enum HecAckApiError {
     ServerSendQuery,
 }
 
+/// An ack id pending query, along with the number of remaining queries and its expiration.
+struct PendingAck {
+    retries: u8,
+    expires_at: Option<Instant>,
+    sender: Sender<EventStatus>,
+}
+
 struct HecAckClient {
-    acks: HashMap<u64, (u8, Sender<EventStatus>)>,
+    acks: HashMap<u64, PendingAck>,
     retry_limit: u8,
+    timeout: Option<Duration>,
     client: HttpClient,
     http_request_builder: Arc<HttpRequestBuilder>,
 }
@@ -76,12 +89,14 @@ struct HecAckClient {
 impl HecAckClient {
     fn new(
         retry_limit: u8,
+        timeout: Option<Duration>,
         client: HttpClient,
         http_request_builder: Arc<HttpRequestBuilder>,
     ) -> Self {
         Self {
             acks: HashMap::new(),
             retry_limit,
+            timeout,
             client,
             http_request_builder,
         }
@@ -89,13 +104,18 @@ impl HecAckClient {
 
     /// Adds an ack id to be queried
     fn add(&mut self, ack_id: u64, ack_event_status_sender: Sender<EventStatus>) {
-        self.acks
-            .insert(ack_id, (self.retry_limit, ack_event_status_sender));
+        let pending = PendingAck {
+            retries: self.retry_limit,
+            expires_at: self.timeout.map(|timeout| Instant::now() + timeout),
+            sender: ack_event_status_sender,
+        };
+        self.acks.insert(ack_id, pending);
         emit!(SplunkIndexerAcknowledgementAckAdded);
     }
 
     /// Queries Splunk HEC with stored ack ids and finalizes events that are successfully acked
     async fn run(&mut self) {
+        self.expire_timed_out_ack_ids();
         let ack_query_body = self.get_ack_query_body();
         if !ack_query_body.acks.is_empty() {
             let ack_query_response = self.send_ack_query_request(&ack_query_body).await;
@@ -145,8 +165,8 @@ impl HecAckClient {
     fn finalize_delivered_ack_ids(&mut self, ack_ids: &[u64]) {
         let mut removed_count = 0.0;
         for ack_id in ack_ids {
-            if let Some((_, ack_event_status_sender)) = self.acks.remove(ack_id) {
-                let _ = ack_event_status_sender.send(EventStatus::Delivered);
+            if let Some(pending) = self.acks.remove(ack_id) {
+                let _ = pending.sender.send(EventStatus::Delivered);
                 removed_count += 1.0;
                 debug!(message = "Finalized ack id.", ?ack_id);
             }
@@ -165,23 +185,54 @@ impl HecAckClient {
 
     /// Decrements retry count on all stored ack ids by 1
     fn decrement_retries(&mut self) {
-        for (retries, _) in self.acks.values_mut() {
-            *retries = retries.checked_sub(1).unwrap_or(0);
+        for pending in self.acks.values_mut() {
+            pending.retries = pending.retries.checked_sub(1).unwrap_or(0);
         }
     }
 
+    /// Removes the ack ids pending for longer than the timeout, finalizing their events as
+    /// rejected since Splunk didn't confirm their indexing in time
+    fn expire_timed_out_ack_ids(&mut self) {
+        let now = Instant::now();
+        let timed_out_ack_ids = self
+            .acks
+            .iter()
+            .filter_map(|(ack_id, pending)| {
+                pending
+                    .expires_at
+                    .filter(|expires_at| *expires_at <= now)
+                    .map(|_| *ack_id)
+            })
+            .collect::<Vec<_>>();
+        if timed_out_ack_ids.is_empty() {
+            return;
+        }
+        for ack_id in &timed_out_ack_ids {
+            if let Some(pending) = self.acks.remove(ack_id) {
+                let _ = pending.sender.send(EventStatus::Rejected);
+            }
+        }
+        debug!(
+            message = "Ack ids timed out.",
+            count = timed_out_ack_ids.len()
+        );
+        emit!(SplunkIndexerAcknowledgementAcksRemoved {
+            count: timed_out_ack_ids.len() as f64
+        });
+    }
+
     /// Removes all expired ack ids (those with a retry count of 0) and
     /// finalizes associated events with the given status
     fn expire_ack_ids_with_status(&mut self, status: EventStatus) {
         let expired_ack_ids = self
             .acks
             .iter()
-            .filter_map(|(ack_id, (retries, _))| (*retries == 0).then(|| *ack_id))
+            .filter_map(|(ack_id, pending)| (pending.retries == 0).then(|| *ack_id))
             .collect::<Vec<_>>();
         let mut removed_count = 0.0;
         for ack_id in expired_ack_ids {
-            if let Some((_, ack_event_status_sender)) = self.acks.remove(&ack_id) {
-                let _ = ack_event_status_sender.send(status);
+            if let Some(pending) = self.acks.remove(&ack_id) {
+                let _ = pending.sender.send(status);
                 removed_count += 1.0;
             }
         }
@@ -241,6 +292,9 @@ pub async fn run_acknowledgements(
     ));
     let mut ack_client = HecAckClient::new(
         indexer_acknowledgements.retry_limit.get(),
+        indexer_acknowledgements
+            .timeout_secs
+            .map(|secs| Duration::from_secs(secs.get())),
         client,
         http_request_builder,
     );
@@ -265,7 +319,7 @@ pub async fn run_acknowledgements(
 
 #[cfg(test)]
 mod tests {
-    use std::sync::Arc;
+    use std::{sync::Arc, time::Duration};
 
     use futures_util::{stream::FuturesUnordered, StreamExt};
     use tokio::sync::oneshot::{self, Receiver};
@@ -283,6 +337,10 @@ mod tests {
     };
 
     fn get_ack_client(retry_limit: u8) -> HecAckClient {
+        get_ack_client_with_timeout(retry_limit, None)
+    }
+
+    fn get_ack_client_with_timeout(retry_limit: u8, timeout: Option<Duration>) -> HecAckClient {
         let client = HttpClient::new(None, &ProxyConfig::default()).unwrap();
         let http_request_builder = HttpRequestBuilder::new(
             String::from(""),
@@ -290,7 +348,7 @@ mod tests {
             String::from(""),
             Compression::default(),
         );
-        HecAckClient::new(retry_limit, client, Arc::new(http_request_builder))
+        HecAckClient::new(retry_limit, timeout, client, Arc::new(http_request_builder))
     }
 
     fn populate_ack_client(
@@ -360,4 +418,24 @@ mod tests {
             assert_eq!(EventStatus::Rejected, status.unwrap());
         }
     }
+
+    #[tokio::test]
+    async fn test_expire_timed_out_ack_ids() {
+        tokio::time::pause();
+        let mut ack_client = get_ack_client_with_timeout(30, Some(Duration::from_secs(60)));
+        let ack_status_rxs = populate_ack_client(&mut ack_client, &[0, 1]);
+
+        ack_client.expire_timed_out_ack_ids();
+        assert_eq!(ack_client.acks.len(), 2);
+
+        tokio::time::advance(Duration::from_secs(60)).await;
+        let _ = populate_ack_client(&mut ack_client, &[2]);
+        ack_client.expire_timed_out_ack_ids();
+        assert_eq!(ack_client.get_ack_query_body().acks, vec![2]);
+
+        let mut statuses = ack_status_rxs.into_iter().collect::<FuturesUnordered<_>>();
+        while let Some(status) = statuses.next().await {
+            assert_eq!(EventStatus::Rejected, status.unwrap());
+        }
+    }
 }
@@ -0,0 +1,170 @@
+use futures::FutureExt;
+use http::{HeaderMap, HeaderName, HeaderValue, Uri};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use tower::ServiceBuilder;
+
+use super::{
+    service::{HttpExporter, TempoRetryLogic, TempoService, Transport},
+    sink::TempoSink,
+    TempoSinkError,
+};
+use crate::{
+    config::{
+        AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext,
+        SinkHealthcheckOptions,
+    },
+    http::HttpClient,
+    sinks::{
+        opentelemetry::{build_metadata, OpentelemetryCompression, OpentelemetryService},
+        util::{
+            BatchConfig, RealtimeEventBasedDefaultBatchSettings, ServiceBuilderExt,
+            TowerRequestConfig,
+        },
+        vector::v2::{new_client, with_default_scheme},
+        Healthcheck, VectorSink,
+    },
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+};
+
+/// The header holding the tenant ID when Tempo runs in multi-tenant mode.
+const TENANT_HEADER: &str = "X-Scope-OrgID";
+
+/// The path of the OTLP/HTTP traces receiver.
+const TRACES_PATH: &str = "/v1/traces";
+
+/// The protocol the traces are exported with.
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Eq, PartialEq, Serialize)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TempoProtocol {
+    /// OTLP/gRPC, received by Tempo on port 4317 by default.
+    #[derivative(Default)]
+    Grpc,
+
+    /// OTLP/HTTP with protobuf-encoded bodies, received by Tempo on port 4318 by default.
+    Http,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct TempoSinkConfig {
+    /// The endpoint of the Tempo distributor, such as `http://localhost:4317`.
+    endpoint: String,
+    #[serde(default)]
+    protocol: TempoProtocol,
+    /// The ID of the tenant to export the traces to, when Tempo runs in multi-tenant mode.
+    tenant_id: Option<String>,
+    #[serde(default)]
+    compression: OpentelemetryCompression,
+    /// Headers sent with each request, such as authentication tokens.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    headers: IndexMap<String, String>,
+    #[serde(default)]
+    pub batch: BatchConfig<RealtimeEventBasedDefaultBatchSettings>,
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+    #[serde(default)]
+    tls: Option<TlsEnableableConfig>,
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for TempoSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"endpoint = "http://localhost:4317""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "tempo")]
+impl SinkConfig for TempoSinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        let uri = with_default_scheme(&self.endpoint, tls.is_tls())?;
+        let headers = self.headers();
+
+        let (transport, uri) = match self.protocol {
+            TempoProtocol::Grpc => {
+                let client = new_client(&tls, cx.proxy())?;
+                let metadata = build_metadata(&headers)?;
+                let service =
+                    OpentelemetryService::new(client, uri.clone(), self.compression, metadata);
+                (Transport::Grpc(service), uri)
+            }
+            TempoProtocol::Http => {
+                let uri: Uri =
+                    format!("{}{}", uri.to_string().trim_end_matches('/'), TRACES_PATH).parse()?;
+                let exporter = HttpExporter {
+                    client: HttpClient::new(tls, cx.proxy())?,
+                    uri: uri.clone(),
+                    compression: self.compression,
+                    headers: build_header_map(&headers)?,
+                };
+                (Transport::Http(exporter), uri)
+            }
+        };
+        let service = TempoService::new(transport, uri);
+        let healthcheck = healthcheck(service.clone(), cx.healthcheck).boxed();
+
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+        let batch_settings = self.batch.into_batcher_settings()?;
+        let service = ServiceBuilder::new()
+            .settings(request_settings, TempoRetryLogic)
+            .service(service);
+
+        let sink = TempoSink {
+            batch_settings,
+            service,
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::trace()
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "tempo"
+    }
+
+    fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
+        Some(&self.acknowledgements)
+    }
+}
+
+impl TempoSinkConfig {
+    /// The configured headers, along with the tenant one if the tenant ID is set.
+    fn headers(&self) -> IndexMap<String, String> {
+        let mut headers = self.headers.clone();
+        if let Some(tenant_id) = &self.tenant_id {
+            headers.insert(TENANT_HEADER.to_owned(), tenant_id.clone());
+        }
+        headers
+    }
+}
+
+fn build_header_map(headers: &IndexMap<String, String>) -> crate::Result<HeaderMap> {
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        let invalid = || TempoSinkError::InvalidHeader { name: name.clone() };
+        let key = HeaderName::from_bytes(name.as_bytes()).map_err(|_| invalid())?;
+        let value = HeaderValue::from_str(value).map_err(|_| invalid())?;
+        map.insert(key, value);
+    }
+    Ok(map)
+}
+
+/// Checks that the distributor accepts an empty export request, as OTLP has no health service.
+async fn healthcheck(service: TempoService, options: SinkHealthcheckOptions) -> crate::Result<()> {
+    if !options.enabled {
+        return Ok(());
+    }
+    service.export(Default::default()).await?;
+    Ok(())
+}
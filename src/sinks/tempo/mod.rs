@@ -0,0 +1,57 @@
+//! The `tempo` sink.
+//!
+//! Exports traces to Grafana Tempo over OTLP, either with gRPC as done by the `opentelemetry` sink
+//! or with protobuf-encoded HTTP requests. The spans are batched by trace ID, so that the spans of
+//! a trace are sent together to the distributor.
+
+use http::StatusCode;
+use snafu::Snafu;
+
+use crate::{
+    config::SinkDescription, http::HttpError, sinks::opentelemetry::OpentelemetrySinkError,
+};
+
+mod config;
+mod service;
+mod sink;
+#[cfg(test)]
+mod tests;
+
+pub use self::config::{TempoProtocol, TempoSinkConfig};
+
+inventory::submit! {
+    SinkDescription::new::<TempoSinkConfig>("tempo")
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum TempoSinkError {
+    #[snafu(display("{}", source))]
+    Grpc { source: OpentelemetrySinkError },
+
+    #[snafu(display("Request failed: {}", source))]
+    Http { source: HttpError },
+
+    #[snafu(display("Tempo responded with status {}", status))]
+    Status { status: StatusCode },
+
+    #[snafu(display("Invalid header: {}", name))]
+    InvalidHeader { name: String },
+}
+
+impl TempoSinkError {
+    fn is_retriable(&self) -> bool {
+        match self {
+            Self::Grpc { source } => source.is_retriable(),
+            Self::Http { .. } => true,
+            // The statuses OTLP/HTTP receivers respond with to retriable errors.
+            Self::Status { status } => matches!(
+                *status,
+                StatusCode::TOO_MANY_REQUESTS
+                    | StatusCode::BAD_GATEWAY
+                    | StatusCode::SERVICE_UNAVAILABLE
+                    | StatusCode::GATEWAY_TIMEOUT
+            ),
+            Self::InvalidHeader { .. } => false,
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use std::{
+    io::Write,
+    task::{Context, Poll},
+};
+
+use flate2::write::GzEncoder;
+use futures::future::BoxFuture;
+use http::{header::CONTENT_ENCODING, header::CONTENT_TYPE, HeaderMap, Request, Uri};
+use hyper::Body;
+use prost::Message;
+use snafu::ResultExt;
+use tower::Service;
+use vector_core::{internal_event::EventsSent, stream::DriverResponse};
+
+use super::{GrpcSnafu, HttpSnafu, TempoSinkError};
+use crate::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    http::HttpClient,
+    internal_events::EndpointBytesSent,
+    opentelemetry::TraceService::ExportTraceServiceRequest,
+    sinks::{
+        opentelemetry::{ExportRequest, OpentelemetryCompression, OpentelemetryService},
+        util::{retries::RetryLogic, uri},
+    },
+};
+
+#[derive(Clone, Debug)]
+pub(super) struct TempoRequest {
+    pub(super) payload: ExportTraceServiceRequest,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) events_count: usize,
+    pub(super) events_byte_size: usize,
+}
+
+impl Finalizable for TempoRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+pub(super) struct TempoResponse {
+    events_count: usize,
+    events_byte_size: usize,
+}
+
+impl DriverResponse for TempoResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> EventsSent {
+        EventsSent {
+            count: self.events_count,
+            byte_size: self.events_byte_size,
+            output: None,
+        }
+    }
+}
+
+/// Exports the trace requests with OTLP/HTTP, as protobuf-encoded `POST` requests.
+#[derive(Clone)]
+pub(super) struct HttpExporter {
+    pub(super) client: HttpClient,
+    pub(super) uri: Uri,
+    pub(super) compression: OpentelemetryCompression,
+    pub(super) headers: HeaderMap,
+}
+
+impl HttpExporter {
+    async fn export(self, request: ExportTraceServiceRequest) -> Result<(), TempoSinkError> {
+        let mut builder = Request::post(self.uri).header(CONTENT_TYPE, "application/x-protobuf");
+        let body = match self.compression {
+            OpentelemetryCompression::None => request.encode_to_vec(),
+            OpentelemetryCompression::Gzip => {
+                builder = builder.header(CONTENT_ENCODING, "gzip");
+                let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder
+                    .write_all(&request.encode_to_vec())
+                    .and_then(|_| encoder.finish())
+                    .expect("Writing to a Vec can't fail")
+            }
+        };
+        let mut request = builder
+            .body(Body::from(body))
+            .expect("Building request can't fail");
+        request.headers_mut().extend(self.headers);
+
+        let response = self.client.send(request).await.context(HttpSnafu)?;
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            Err(TempoSinkError::Status { status })
+        }
+    }
+}
+
+/// The transport the traces are exported with.
+#[derive(Clone)]
+pub(super) enum Transport {
+    Grpc(OpentelemetryService),
+    Http(HttpExporter),
+}
+
+#[derive(Clone)]
+pub(super) struct TempoService {
+    transport: Transport,
+    protocol: String,
+    endpoint: String,
+}
+
+impl TempoService {
+    pub(super) fn new(transport: Transport, uri: Uri) -> Self {
+        let (protocol, endpoint) = uri::protocol_endpoint(uri);
+        Self {
+            transport,
+            protocol,
+            endpoint,
+        }
+    }
+
+    pub(super) async fn export(
+        self,
+        request: ExportTraceServiceRequest,
+    ) -> Result<(), TempoSinkError> {
+        match self.transport {
+            Transport::Grpc(service) => service
+                .export(ExportRequest::Traces(request))
+                .await
+                .context(GrpcSnafu),
+            Transport::Http(exporter) => exporter.export(request).await,
+        }
+    }
+}
+
+impl Service<TempoRequest> for TempoService {
+    type Response = TempoResponse;
+    type Error = TempoSinkError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: TempoRequest) -> Self::Future {
+        let service = self.clone();
+        let byte_size = request.payload.encoded_len();
+        let TempoRequest {
+            payload,
+            events_count,
+            events_byte_size,
+            ..
+        } = request;
+
+        Box::pin(async move {
+            let protocol = service.protocol.clone();
+            let endpoint = service.endpoint.clone();
+            service.export(payload).await?;
+            emit!(EndpointBytesSent {
+                byte_size,
+                protocol: &protocol,
+                endpoint: &endpoint,
+            });
+            Ok(TempoResponse {
+                events_count,
+                events_byte_size,
+            })
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct TempoRetryLogic;
+
+impl RetryLogic for TempoRetryLogic {
+    type Error = TempoSinkError;
+    type Response = TempoResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        error.is_retriable()
+    }
+}
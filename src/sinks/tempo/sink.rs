@@ -0,0 +1,84 @@
+use std::fmt;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::{future, stream::BoxStream, StreamExt};
+use tower::Service;
+use vector_core::{
+    partition::Partitioner,
+    stream::{BatcherSettings, DriverResponse},
+    ByteSizeOf,
+};
+
+use super::service::TempoRequest;
+use crate::{
+    event::{Event, EventFinalizers, Finalizable, TraceEvent, Value},
+    sinks::util::{SinkBuilderExt, StreamSink},
+};
+
+/// The field of the traces holding their ID, as set by the `opentelemetry` source.
+const TRACE_ID_KEY: &str = "trace_id";
+
+/// Partitions the spans by trace ID, so that the spans of a trace are exported together.
+struct TraceIdPartitioner;
+
+impl Partitioner for TraceIdPartitioner {
+    type Item = TraceEvent;
+    type Key = Option<Bytes>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        item.get(TRACE_ID_KEY).and_then(Value::as_bytes).cloned()
+    }
+}
+
+pub(super) struct TempoSink<S> {
+    pub(super) batch_settings: BatcherSettings,
+    pub(super) service: S,
+}
+
+impl<S> TempoSink<S>
+where
+    S: Service<TempoRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        input
+            .filter_map(|event| future::ready(event.try_into_trace()))
+            .batched_partitioned(TraceIdPartitioner, self.batch_settings)
+            .map(|(_, traces)| build_request(traces))
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+fn build_request(mut traces: Vec<TraceEvent>) -> TempoRequest {
+    let mut finalizers = EventFinalizers::default();
+    let mut events_byte_size = 0;
+    for trace in &mut traces {
+        finalizers.merge(trace.take_finalizers());
+        events_byte_size += trace.size_of();
+    }
+
+    TempoRequest {
+        events_count: traces.len(),
+        payload: traces.into_iter().collect(),
+        finalizers,
+        events_byte_size,
+    }
+}
+
+#[async_trait]
+impl<S> StreamSink<Event> for TempoSink<S>
+where
+    S: Service<TempoRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
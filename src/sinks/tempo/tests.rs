@@ -0,0 +1,222 @@
+use std::{collections::BTreeMap, io::Read};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use flate2::read::GzDecoder;
+use futures::{channel::mpsc, StreamExt};
+use http::{request::Parts, StatusCode};
+use prost::Message;
+use vector_core::event::{BatchNotifier, BatchStatus};
+
+use super::*;
+use crate::{
+    config::{SinkConfig, SinkContext},
+    event::{Event, TraceEvent, Value},
+    opentelemetry::TraceService::{ExportTraceServiceRequest, ExportTraceServiceResponse},
+    sinks::{
+        util::test::{build_test_server_generic, build_test_server_status},
+        VectorSink,
+    },
+    test_util::{
+        components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+        next_addr,
+    },
+};
+
+// one byte for the compression flag plus four bytes for the length
+const GRPC_HEADER_SIZE: usize = 5;
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<TempoSinkConfig>();
+}
+
+fn span(trace_id: &str, name: &str, batch: &BatchNotifier) -> Event {
+    let mut fields = BTreeMap::new();
+    fields.insert("trace_id".to_owned(), Value::from(trace_id));
+    fields.insert("name".to_owned(), Value::from(name));
+    Event::from(TraceEvent::from(fields).with_batch_notifier(batch))
+}
+
+/// The names of the spans of each request, sorted to not depend on the order of the batches.
+fn span_names(requests: impl IntoIterator<Item = ExportTraceServiceRequest>) -> Vec<Vec<String>> {
+    let mut names = requests
+        .into_iter()
+        .map(|request| {
+            let mut names = request
+                .resource_spans
+                .into_iter()
+                .flat_map(|spans| spans.scope_spans)
+                .flat_map(|spans| spans.spans)
+                .map(|span| span.name)
+                .collect::<Vec<_>>();
+            names.sort();
+            names
+        })
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+async fn build(config: String) -> VectorSink {
+    let config: TempoSinkConfig = toml::from_str(&config).unwrap();
+    config.build(SinkContext::new_test()).await.unwrap().0
+}
+
+fn start_grpc_server() -> (
+    std::net::SocketAddr,
+    mpsc::Receiver<(Parts, Bytes)>,
+    stream_cancel::Trigger,
+) {
+    let addr = next_addr();
+    let (rx, trigger, server) = build_test_server_generic(addr, move || {
+        hyper::Response::builder()
+            .header("grpc-status", "0")
+            .header("content-type", "application/grpc")
+            .body(hyper::Body::from(encode_body(
+                ExportTraceServiceResponse {},
+            )))
+            .unwrap()
+    });
+    tokio::spawn(server);
+    (addr, rx, trigger)
+}
+
+#[tokio::test]
+async fn exports_traces_over_grpc_by_trace_id() {
+    let (addr, rx, trigger) = start_grpc_server();
+    let sink = build(format!(
+        r#"endpoint = "http://{}"
+        tenant_id = "team-a""#,
+        addr
+    ))
+    .await;
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let events = vec![
+        span("0af7651916cd43dd8448eb211c80319c", "a1", &batch),
+        span("4bf92f3577b34da6a3ce929d0e0e4736", "b1", &batch),
+        span("0af7651916cd43dd8448eb211c80319c", "a2", &batch),
+    ];
+    drop(batch);
+
+    run_and_assert_sink_compliance(sink, futures::stream::iter(events), &HTTP_SINK_TAGS).await;
+    drop(trigger);
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+    let requests = rx.collect::<Vec<_>>().await;
+    for (parts, _) in &requests {
+        assert_eq!(
+            parts.uri.path(),
+            "/opentelemetry.proto.collector.trace.v1.TraceService/Export"
+        );
+        assert_eq!(parts.headers["x-scope-orgid"], "team-a");
+    }
+    let requests = requests.into_iter().map(|(_, body)| {
+        ExportTraceServiceRequest::decode(body.slice(GRPC_HEADER_SIZE..)).unwrap()
+    });
+    assert_eq!(span_names(requests), vec![vec!["a1", "a2"], vec!["b1"]]);
+}
+
+#[tokio::test]
+async fn exports_traces_over_http() {
+    let addr = next_addr();
+    let (rx, trigger, server) = build_test_server_status(addr, StatusCode::OK);
+    tokio::spawn(server);
+    let sink = build(format!(
+        r#"endpoint = "http://{}"
+        protocol = "http"
+        compression = "gzip"
+        headers.Authorization = "Bearer token""#,
+        addr
+    ))
+    .await;
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let events = vec![span("0af7651916cd43dd8448eb211c80319c", "a1", &batch)];
+    drop(batch);
+
+    run_and_assert_sink_compliance(sink, futures::stream::iter(events), &HTTP_SINK_TAGS).await;
+    drop(trigger);
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+    let requests = rx.collect::<Vec<_>>().await;
+    assert_eq!(requests.len(), 1);
+    let (parts, body) = &requests[0];
+    assert_eq!(parts.uri.path(), "/v1/traces");
+    assert_eq!(parts.headers["content-type"], "application/x-protobuf");
+    assert_eq!(parts.headers["content-encoding"], "gzip");
+    assert_eq!(parts.headers["authorization"], "Bearer token");
+
+    let mut decoded = Vec::new();
+    GzDecoder::new(&body[..]).read_to_end(&mut decoded).unwrap();
+    let request = ExportTraceServiceRequest::decode(&decoded[..]).unwrap();
+    assert_eq!(span_names([request]), vec![vec!["a1"]]);
+}
+
+#[tokio::test]
+async fn rejects_traces_on_client_error() {
+    let addr = next_addr();
+    let (_rx, trigger, server) = build_test_server_status(addr, StatusCode::BAD_REQUEST);
+    tokio::spawn(server);
+    let sink = build(format!(
+        r#"endpoint = "http://{}"
+        protocol = "http""#,
+        addr
+    ))
+    .await;
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let events = vec![span("0af7651916cd43dd8448eb211c80319c", "a1", &batch)];
+    drop(batch);
+
+    sink.run_events(events).await.expect("Running sink failed");
+    drop(trigger);
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+}
+
+#[tokio::test]
+async fn healthcheck() {
+    let addr = next_addr();
+    let (_rx, _trigger, server) = build_test_server_status(addr, StatusCode::OK);
+    tokio::spawn(server);
+    let config = format!(
+        r#"endpoint = "http://{}"
+        protocol = "http""#,
+        addr
+    );
+    let config: TempoSinkConfig = toml::from_str(&config).unwrap();
+    let (_, healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+    healthcheck.await.unwrap();
+
+    let addr = next_addr();
+    let (_rx, _trigger, server) = build_test_server_status(addr, StatusCode::NOT_FOUND);
+    tokio::spawn(server);
+    let config = format!(
+        r#"endpoint = "http://{}"
+        protocol = "http""#,
+        addr
+    );
+    let config: TempoSinkConfig = toml::from_str(&config).unwrap();
+    let (_, healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+    assert!(healthcheck.await.is_err());
+}
+
+#[test]
+fn retries_transient_statuses() {
+    let error = |status| TempoSinkError::Status { status };
+    assert!(error(StatusCode::SERVICE_UNAVAILABLE).is_retriable());
+    assert!(error(StatusCode::TOO_MANY_REQUESTS).is_retriable());
+    assert!(!error(StatusCode::BAD_REQUEST).is_retriable());
+}
+
+/// Encodes the message as the body of a gRPC response.
+fn encode_body<T>(msg: T) -> Bytes
+where
+    T: prost::Message,
+{
+    let mut buf = BytesMut::with_capacity(msg.encoded_len() + GRPC_HEADER_SIZE);
+    buf.put_u8(0); // compression flag, 0 means "no compression"
+    buf.put_u32(msg.encoded_len() as u32);
+    msg.encode(&mut buf).unwrap();
+    buf.freeze()
+}
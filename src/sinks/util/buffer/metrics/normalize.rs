@@ -79,6 +79,17 @@ impl<N> From<N> for MetricNormalizer<N> {
     }
 }
 
+/// Converts a sketch into an aggregated histogram, with the given bucket bounds.
+///
+/// This allows sending sketches, such as the ones received from the Datadog Agent, to systems which only support
+/// histograms with fixed buckets, such as Prometheus. If the metric is not a sketch, it is passed back unmodified.
+pub fn sketch_to_histogram(metric: Metric, buckets: &[f64]) -> Metric {
+    match metric.value().sketch_to_agg_histogram(buckets) {
+        Some(value) => metric.with_value(value),
+        None => metric,
+    }
+}
+
 type MetricEntry = (MetricData, EventMetadata);
 
 /// Metric storage for use with normalization.
@@ -2,9 +2,15 @@ use std::io;
 
 use bytes::BytesMut;
 use codecs::encoding::Framer;
+#[cfg(feature = "codecs-parquet")]
+use codecs::encoding::{ParquetSerializer, ParquetSerializerConfig, SerializerConfig};
 use tokio_util::codec::Encoder as _;
 
-use crate::{codecs::Transformer, event::Event};
+use super::Compression;
+use crate::{
+    codecs::{EncodingConfigWithFraming, SinkType, Transformer},
+    event::Event,
+};
 
 pub trait Encoder<T> {
     /// Encodes the input into the provided writer.
@@ -66,6 +72,90 @@ impl Encoder<Event> for (Transformer, crate::codecs::Encoder<()>) {
     }
 }
 
+pub const PARQUET_CONTENT_TYPE: &str = "application/vnd.apache.parquet";
+
+/// Encodes the batches of the sinks writing them as objects, either event by event with the
+/// framing of the encoding, or as whole Parquet files.
+#[derive(Clone, Debug)]
+pub enum ObjectEncoder {
+    Framed((Transformer, crate::codecs::Encoder<Framer>)),
+    #[cfg(feature = "codecs-parquet")]
+    Parquet(Transformer, ParquetSerializer),
+}
+
+impl ObjectEncoder {
+    pub fn build(config: &EncodingConfigWithFraming) -> crate::Result<Self> {
+        let transformer = config.transformer();
+        #[cfg(feature = "codecs-parquet")]
+        if let SerializerConfig::Parquet { parquet } = config.config().1 {
+            return Ok(Self::Parquet(
+                transformer,
+                ParquetSerializerConfig::new(parquet.clone()).build()?,
+            ));
+        }
+
+        let (framer, serializer) = config.build(SinkType::MessageBased)?;
+        Ok(Self::Framed((
+            transformer,
+            crate::codecs::Encoder::<Framer>::new(framer, serializer),
+        )))
+    }
+
+    pub const fn is_parquet(&self) -> bool {
+        #[cfg(feature = "codecs-parquet")]
+        if let Self::Parquet(..) = self {
+            return true;
+        }
+        false
+    }
+
+    /// The compression of the objects, which are left uncompressed when written as Parquet
+    /// files, as these compress their own pages.
+    pub const fn compression(&self, compression: Compression) -> Compression {
+        match self {
+            Self::Framed(_) => compression,
+            #[cfg(feature = "codecs-parquet")]
+            Self::Parquet(..) => Compression::None,
+        }
+    }
+
+    /// The default extension of the objects.
+    pub const fn extension(&self, compression: Compression) -> &'static str {
+        match self {
+            Self::Framed(_) => compression.extension(),
+            #[cfg(feature = "codecs-parquet")]
+            Self::Parquet(..) => "parquet",
+        }
+    }
+
+    pub const fn content_type(&self) -> &str {
+        match self {
+            Self::Framed((_, encoder)) => encoder.content_type(),
+            #[cfg(feature = "codecs-parquet")]
+            Self::Parquet(..) => PARQUET_CONTENT_TYPE,
+        }
+    }
+}
+
+impl Encoder<Vec<Event>> for ObjectEncoder {
+    fn encode_input(&self, events: Vec<Event>, writer: &mut dyn io::Write) -> io::Result<usize> {
+        match self {
+            Self::Framed(encoder) => encoder.encode_input(events, writer),
+            #[cfg(feature = "codecs-parquet")]
+            Self::Parquet(transformer, serializer) => {
+                let mut events = events;
+                for event in &mut events {
+                    transformer.transform(event);
+                }
+                let file = serializer
+                    .serialize(events)
+                    .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+                writer.write_all(&file).map(|()| file.len())
+            }
+        }
+    }
+}
+
 pub fn as_tracked_write<F, I, E>(inner: &mut dyn io::Write, input: I, f: F) -> io::Result<usize>
 where
     F: FnOnce(&mut dyn io::Write, I) -> Result<(), E>,
@@ -309,4 +399,36 @@ mod tests {
 
         assert_eq!(String::from_utf8(writer).unwrap(), r#"value"#);
     }
+
+    #[cfg(feature = "codecs-parquet")]
+    #[test]
+    fn test_encode_batch_parquet() {
+        let encoding = ObjectEncoder::build(&EncodingConfigWithFraming::new(
+            None,
+            ParquetSerializerConfig::new(Default::default()).into(),
+            Transformer::default(),
+        ))
+        .unwrap();
+        assert!(encoding.is_parquet());
+        assert_eq!(
+            encoding.compression(Compression::gzip_default()),
+            Compression::None
+        );
+        assert_eq!(encoding.extension(Compression::gzip_default()), "parquet");
+
+        let mut writer = Vec::new();
+        let written = encoding
+            .encode_input(
+                vec![Event::Log(LogEvent::from(BTreeMap::from([(
+                    String::from("key"),
+                    Value::from("value"),
+                )])))],
+                &mut writer,
+            )
+            .unwrap();
+        assert_eq!(written, writer.len());
+
+        assert!(writer.starts_with(b"PAR1"));
+        assert!(writer.ends_with(b"PAR1"));
+    }
 }
@@ -16,6 +16,9 @@ use crate::Error;
 pub enum RetryAction {
     /// Indicate that this request should be retried with a reason
     Retry(Cow<'static, str>),
+    /// Indicate that this request should be retried with a reason, once the delay requested by the
+    /// service (i.e. through a `Retry-After` header) has passed
+    RetryAfter(Cow<'static, str>, Duration),
     /// Indicate that this request should not be retried with a reason
     DontRetry(Cow<'static, str>),
     /// Indicate that this request should not be retried but the request was successful
@@ -81,11 +84,18 @@ impl<L: RetryLogic> FixedRetryPolicy<L> {
     }
 
     fn build_retry(&self) -> RetryPolicyFuture<L> {
+        self.build_retry_after(self.backoff())
+    }
+
+    fn build_retry_after(&self, delay: Duration) -> RetryPolicyFuture<L> {
         let policy = self.advance();
-        let delay = Box::pin(sleep(self.backoff()));
+        let delay = cmp::min(delay, self.max_duration);
 
-        debug!(message = "Retrying request.", delay_ms = %self.backoff().as_millis());
-        RetryPolicyFuture { delay, policy }
+        debug!(message = "Retrying request.", delay_ms = %delay.as_millis());
+        RetryPolicyFuture {
+            delay: Box::pin(sleep(delay)),
+            policy,
+        }
     }
 }
 
@@ -112,6 +122,23 @@ where
                     Some(self.build_retry())
                 }
 
+                RetryAction::RetryAfter(reason, delay) => {
+                    if self.remaining_attempts == 0 {
+                        error!(
+                            message = "OK/retry response but retries exhausted; dropping the request.",
+                            reason = ?reason
+                        );
+                        return None;
+                    }
+
+                    warn!(
+                        message = "Retrying after response.",
+                        reason = %reason,
+                        retry_after_secs = %delay.as_secs_f64()
+                    );
+                    Some(self.build_retry_after(delay))
+                }
+
                 RetryAction::DontRetry(reason) => {
                     error!(message = "Not retriable; dropping the request.", reason = ?reason);
                     None
@@ -170,7 +197,7 @@ impl<L: RetryLogic> Future for RetryPolicyFuture<L> {
 
 impl RetryAction {
     pub const fn is_retryable(&self) -> bool {
-        matches!(self, RetryAction::Retry(_))
+        matches!(self, RetryAction::Retry(_) | RetryAction::RetryAfter(..))
     }
 
     pub const fn is_not_retryable(&self) -> bool {
@@ -348,6 +375,33 @@ mod tests {
         assert_eq!(fut.await.unwrap(), "world");
     }
 
+    #[tokio::test]
+    async fn retry_after_response() {
+        trace_init();
+
+        time::pause();
+
+        let policy = FixedRetryPolicy::new(
+            5,
+            Duration::from_secs(1),
+            Duration::from_secs(10),
+            RetryAfterLogic,
+        );
+
+        let (mut svc, mut handle) = mock::spawn_layer(RetryLayer::new(policy));
+
+        assert_ready_ok!(svc.poll_ready());
+
+        let start = time::Instant::now();
+        let fut = tokio::spawn(svc.call("hello"));
+
+        assert_request_eq!(handle, "hello").send_response("busy");
+        assert_request_eq!(handle, "hello").send_response("world");
+        assert!(start.elapsed() >= Duration::from_secs(5));
+
+        assert_eq!(fut.await.unwrap().unwrap(), "world");
+    }
+
     #[test]
     fn backoff_grows_to_max() {
         let mut policy = FixedRetryPolicy::new(
@@ -392,6 +446,25 @@ mod tests {
         }
     }
 
+    #[derive(Debug, Clone)]
+    struct RetryAfterLogic;
+
+    impl RetryLogic for RetryAfterLogic {
+        type Error = Error;
+        type Response = &'static str;
+
+        fn is_retriable_error(&self, error: &Self::Error) -> bool {
+            error.0
+        }
+
+        fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
+            match *response {
+                "busy" => RetryAction::RetryAfter("busy".into(), Duration::from_secs(5)),
+                _ => RetryAction::Successful,
+            }
+        }
+    }
+
     #[derive(Debug)]
     struct Error(bool);
 
@@ -10,6 +10,7 @@ use tower::{
 use vector_config::configurable_component;
 
 pub use crate::sinks::util::service::{
+    balance::{Balancer, LoadBalancingConfig, LoadBalancingStrategy},
     concurrency::{concurrency_is_none, Concurrency},
     map::Map,
 };
@@ -23,6 +24,7 @@ use crate::sinks::util::{
     Batch, BatchSink, Partition, PartitionBatchSink,
 };
 
+mod balance;
 mod concurrency;
 mod map;
 
@@ -0,0 +1,359 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::future::BoxFuture;
+use tokio::time::Instant;
+use tower::{Service, ServiceExt};
+use vector_config::configurable_component;
+
+use crate::sinks::util::retries::RetryLogic;
+
+const fn default_initial_backoff_secs() -> u64 {
+    1
+}
+
+const fn default_max_backoff_secs() -> u64 {
+    60
+}
+
+/// Load balancing settings for sinks sending requests to multiple endpoints.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct LoadBalancingConfig {
+    #[configurable(derived)]
+    #[serde(default)]
+    pub strategy: LoadBalancingStrategy,
+
+    /// The time, in seconds, an endpoint is excluded from load balancing after a request to it failed.
+    ///
+    /// The time doubles with each consecutive failure of the endpoint, up to `max_backoff_secs`. If all
+    /// endpoints are excluded, requests are sent to any of them.
+    #[serde(default = "default_initial_backoff_secs")]
+    #[derivative(Default(value = "default_initial_backoff_secs()"))]
+    pub initial_backoff_secs: u64,
+
+    /// The maximum time, in seconds, an endpoint is excluded from load balancing.
+    #[serde(default = "default_max_backoff_secs")]
+    #[derivative(Default(value = "default_max_backoff_secs()"))]
+    pub max_backoff_secs: u64,
+}
+
+/// The strategy used to pick the endpoint of each request.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LoadBalancingStrategy {
+    /// Requests are sent to each endpoint in turn.
+    #[derivative(Default)]
+    RoundRobin,
+
+    /// Requests are sent to the endpoint with the fewest requests in flight.
+    LeastOutstanding,
+}
+
+#[derive(Debug, Default)]
+struct EndpointState {
+    outstanding: AtomicUsize,
+    /// The number of consecutive failures, and until when the endpoint is excluded.
+    health: Mutex<(u32, Option<Instant>)>,
+}
+
+impl EndpointState {
+    fn is_healthy(&self, now: Instant) -> bool {
+        let (_, excluded_until) = *self.health.lock().expect("poisoned lock");
+        excluded_until.map_or(true, |until| now >= until)
+    }
+
+    fn succeeded(&self) {
+        *self.health.lock().expect("poisoned lock") = (0, None);
+    }
+
+    /// Excludes the endpoint, returning for how long.
+    fn failed(&self, config: &LoadBalancingConfig) -> Duration {
+        let mut health = self.health.lock().expect("poisoned lock");
+        let failures = health.0.saturating_add(1);
+        let backoff = Duration::from_secs(
+            config
+                .initial_backoff_secs
+                .saturating_mul(1 << (failures - 1).min(16))
+                .min(config.max_backoff_secs),
+        );
+        *health = (failures, Some(Instant::now() + backoff));
+        backoff
+    }
+}
+
+/// Counts a request as in flight until it is dropped.
+struct InFlight(Arc<EndpointState>);
+
+impl InFlight {
+    fn new(state: Arc<EndpointState>) -> Self {
+        state.outstanding.fetch_add(1, Ordering::Relaxed);
+        Self(state)
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.outstanding.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Endpoint<S> {
+    name: String,
+    service: S,
+    state: Arc<EndpointState>,
+}
+
+/// Distributes requests across the services of multiple endpoints.
+///
+/// Endpoints whose requests fail with an error deemed retriable by the retry logic are excluded
+/// until their backoff elapses, so that retried requests fail over to the remaining endpoints.
+#[derive(Clone, Debug)]
+pub struct Balancer<S, L> {
+    endpoints: Vec<Endpoint<S>>,
+    config: LoadBalancingConfig,
+    logic: L,
+    next: Arc<AtomicUsize>,
+}
+
+impl<S, L> Balancer<S, L> {
+    /// Creates a new `Balancer` from the names and services of at least one endpoint.
+    pub fn new(
+        endpoints: impl IntoIterator<Item = (String, S)>,
+        config: LoadBalancingConfig,
+        logic: L,
+    ) -> Self {
+        let endpoints = endpoints
+            .into_iter()
+            .map(|(name, service)| Endpoint {
+                name,
+                service,
+                state: Arc::default(),
+            })
+            .collect::<Vec<_>>();
+        assert!(!endpoints.is_empty(), "at least one endpoint is required");
+
+        Self {
+            endpoints,
+            config,
+            logic,
+            next: Arc::default(),
+        }
+    }
+
+    /// Picks the endpoint of the next request among the healthy endpoints, or among all of them if
+    /// none is healthy.
+    fn pick(&self) -> &Endpoint<S> {
+        let now = Instant::now();
+        let len = self.endpoints.len();
+        let start = self.next.fetch_add(1, Ordering::Relaxed) % len;
+        let mut healthy = (0..len)
+            .map(|offset| &self.endpoints[(start + offset) % len])
+            .filter(|endpoint| endpoint.state.is_healthy(now));
+
+        let endpoint = match self.config.strategy {
+            LoadBalancingStrategy::RoundRobin => healthy.next(),
+            // Ties are broken in turn, starting at the same endpoint as round robin.
+            LoadBalancingStrategy::LeastOutstanding => {
+                healthy.min_by_key(|endpoint| endpoint.state.outstanding.load(Ordering::Relaxed))
+            }
+        };
+        endpoint.unwrap_or(&self.endpoints[start])
+    }
+}
+
+impl<S, L, Request> Service<Request> for Balancer<S, L>
+where
+    S: Service<Request, Error = crate::Error> + Clone + Send + 'static,
+    S::Response: Send + 'static,
+    S::Future: Send + 'static,
+    L: RetryLogic,
+    Request: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = crate::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // The endpoint is only picked once the request is made, so its readiness is awaited then.
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let endpoint = self.pick();
+        let name = endpoint.name.clone();
+        let service = endpoint.service.clone();
+        let in_flight = InFlight::new(Arc::clone(&endpoint.state));
+        let config = self.config;
+        let logic = self.logic.clone();
+
+        Box::pin(async move {
+            let result = service.oneshot(request).await;
+            let state = &in_flight.0;
+            match &result {
+                Ok(_) => state.succeeded(),
+                Err(error)
+                    if error
+                        .downcast_ref::<L::Error>()
+                        .map_or(true, |error| logic.is_retriable_error(error)) =>
+                {
+                    let backoff = state.failed(&config);
+                    warn!(
+                        message = "Request to endpoint failed, excluding it from load balancing.",
+                        endpoint = %name,
+                        backoff_secs = backoff.as_secs(),
+                        internal_log_rate_secs = 10,
+                    );
+                }
+                Err(_) => {}
+            }
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::future::{self, FutureExt};
+    use tokio::sync::oneshot;
+
+    use super::*;
+
+    #[derive(Clone)]
+    struct TestRetryLogic;
+
+    #[derive(Debug, snafu::Snafu)]
+    #[snafu(display("test error"))]
+    struct TestError {
+        retriable: bool,
+    }
+
+    impl RetryLogic for TestRetryLogic {
+        type Error = TestError;
+        type Response = &'static str;
+
+        fn is_retriable_error(&self, error: &Self::Error) -> bool {
+            error.retriable
+        }
+    }
+
+    /// Resolves the request future before responding with its name, or an error if it's failing.
+    #[derive(Clone)]
+    struct TestService {
+        name: &'static str,
+        failing: Option<bool>,
+    }
+
+    impl Service<BoxFuture<'static, ()>> for TestService {
+        type Response = &'static str;
+        type Error = crate::Error;
+        type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: BoxFuture<'static, ()>) -> Self::Future {
+            let Self { name, failing } = self.clone();
+            Box::pin(async move {
+                request.await;
+                match failing {
+                    Some(retriable) => Err(TestError { retriable }.into()),
+                    None => Ok(name),
+                }
+            })
+        }
+    }
+
+    fn balancer(
+        strategy: LoadBalancingStrategy,
+        failing: Option<bool>,
+    ) -> Balancer<TestService, TestRetryLogic> {
+        Balancer::new(
+            [
+                ("a".to_owned(), TestService { name: "a", failing }),
+                (
+                    "b".to_owned(),
+                    TestService {
+                        name: "b",
+                        failing: None,
+                    },
+                ),
+            ],
+            LoadBalancingConfig {
+                strategy,
+                ..Default::default()
+            },
+            TestRetryLogic,
+        )
+    }
+
+    async fn send(
+        balancer: &mut Balancer<TestService, TestRetryLogic>,
+    ) -> Result<&'static str, crate::Error> {
+        balancer.call(future::ready(()).boxed()).await
+    }
+
+    #[tokio::test]
+    async fn round_robin() {
+        let mut balancer = balancer(LoadBalancingStrategy::RoundRobin, None);
+
+        let mut names = Vec::new();
+        for _ in 0..4 {
+            names.push(send(&mut balancer).await.unwrap());
+        }
+        assert_eq!(names, ["a", "b", "a", "b"]);
+    }
+
+    #[tokio::test]
+    async fn least_outstanding() {
+        let mut balancer = balancer(LoadBalancingStrategy::LeastOutstanding, None);
+
+        let (tx, rx) = oneshot::channel::<()>();
+        let pending = balancer.call(rx.map(|_| ()).boxed());
+
+        // The first endpoint is skipped while its request is in flight.
+        for _ in 0..3 {
+            assert_eq!(send(&mut balancer).await.unwrap(), "b");
+        }
+
+        drop(tx);
+        assert_eq!(pending.await.unwrap(), "a");
+    }
+
+    #[tokio::test]
+    async fn excludes_failing_endpoints() {
+        tokio::time::pause();
+        let mut balancer = balancer(LoadBalancingStrategy::RoundRobin, Some(true));
+
+        assert!(send(&mut balancer).await.is_err());
+        for _ in 0..3 {
+            assert_eq!(send(&mut balancer).await.unwrap(), "b");
+        }
+
+        // The endpoint is picked again once its backoff elapsed.
+        tokio::time::advance(Duration::from_secs(1)).await;
+        assert!(send(&mut balancer).await.is_err());
+        assert_eq!(send(&mut balancer).await.unwrap(), "b");
+    }
+
+    #[tokio::test]
+    async fn keeps_endpoints_failing_with_non_retriable_errors() {
+        let mut balancer = balancer(LoadBalancingStrategy::RoundRobin, Some(false));
+
+        assert!(send(&mut balancer).await.is_err());
+        assert_eq!(send(&mut balancer).await.unwrap(), "b");
+        assert!(send(&mut balancer).await.is_err());
+    }
+}
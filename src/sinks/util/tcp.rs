@@ -85,16 +85,26 @@ impl TcpSinkConfig {
         }
     }
 
+    pub fn build_connector(&self) -> crate::Result<TcpConnector> {
+        let uri = self.address.parse::<http::Uri>()?;
+        let host = uri.host().ok_or(SinkBuildError::MissingHost)?.to_string();
+        let port = uri.port_u16().ok_or(SinkBuildError::MissingPort)?;
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        Ok(TcpConnector::new(
+            host,
+            port,
+            self.keepalive,
+            tls,
+            self.send_buffer_bytes,
+        ))
+    }
+
     pub fn build(
         &self,
         transformer: Transformer,
         encoder: impl Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync + 'static,
     ) -> crate::Result<(VectorSink, Healthcheck)> {
-        let uri = self.address.parse::<http::Uri>()?;
-        let host = uri.host().ok_or(SinkBuildError::MissingHost)?.to_string();
-        let port = uri.port_u16().ok_or(SinkBuildError::MissingPort)?;
-        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
-        let connector = TcpConnector::new(host, port, self.keepalive, tls, self.send_buffer_bytes);
+        let connector = self.build_connector()?;
         let sink = TcpSink::new(connector.clone(), transformer, encoder);
 
         Ok((
@@ -105,7 +115,7 @@ impl TcpSinkConfig {
 }
 
 #[derive(Clone)]
-struct TcpConnector {
+pub struct TcpConnector {
     host: String,
     port: u16,
     keepalive: Option<TcpKeepaliveConfig>,
@@ -172,7 +182,7 @@ impl TcpConnector {
             })
     }
 
-    async fn connect_backoff(&self) -> MaybeTlsStream<TcpStream> {
+    pub async fn connect_backoff(&self) -> MaybeTlsStream<TcpStream> {
         let mut backoff = Self::fresh_backoff();
         loop {
             match self.connect().await {
@@ -190,7 +200,7 @@ impl TcpConnector {
         }
     }
 
-    async fn healthcheck(&self) -> crate::Result<()> {
+    pub async fn healthcheck(&self) -> crate::Result<()> {
         self.connect().await.map(|_| ()).map_err(Into::into)
     }
 }
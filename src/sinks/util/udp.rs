@@ -6,7 +6,8 @@ use std::{
 };
 
 use async_trait::async_trait;
-use bytes::BytesMut;
+use bytes::{Bytes, BytesMut};
+use codecs::encoding::ChunkedGelfEncoder;
 use futures::{future::BoxFuture, ready, stream::BoxStream, FutureExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
@@ -80,9 +81,10 @@ impl UdpSinkConfig {
         &self,
         transformer: Transformer,
         encoder: impl Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync + 'static,
+        chunker: Option<ChunkedGelfEncoder>,
     ) -> crate::Result<(VectorSink, Healthcheck)> {
         let connector = self.build_connector()?;
-        let sink = UdpSink::new(connector.clone(), transformer, encoder);
+        let sink = UdpSink::new(connector.clone(), transformer, encoder, chunker);
         Ok((
             VectorSink::from_event_streamsink(sink),
             async move { connector.healthcheck().await }.boxed(),
@@ -249,17 +251,25 @@ where
     connector: UdpConnector,
     transformer: Transformer,
     encoder: E,
+    /// Splits encoded events exceeding the size of a datagram, if set.
+    chunker: Option<ChunkedGelfEncoder>,
 }
 
 impl<E> UdpSink<E>
 where
     E: Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync,
 {
-    const fn new(connector: UdpConnector, transformer: Transformer, encoder: E) -> Self {
+    const fn new(
+        connector: UdpConnector,
+        transformer: Transformer,
+        encoder: E,
+        chunker: Option<ChunkedGelfEncoder>,
+    ) -> Self {
         Self {
             connector,
             transformer,
             encoder,
+            chunker,
         }
     }
 }
@@ -286,7 +296,18 @@ where
                     continue;
                 }
 
-                match udp_send(&mut socket, &bytes).await {
+                let datagrams = match &self.chunker {
+                    Some(chunker) => match chunker.encode(&bytes) {
+                        Ok(datagrams) => datagrams,
+                        Err(error) => {
+                            error!(message = "Failed chunking event.", %error, internal_log_rate_secs = 10);
+                            continue;
+                        }
+                    },
+                    None => vec![bytes.freeze()],
+                };
+
+                match udp_send_all(&mut socket, &datagrams).await {
                     Ok(()) => {
                         emit!(SocketEventsSent {
                             mode: SocketMode::Udp,
@@ -295,7 +316,7 @@ where
                         });
 
                         emit!(BytesSent {
-                            byte_size: bytes.len(),
+                            byte_size: datagrams.iter().map(Bytes::len).sum(),
                             protocol: "udp",
                         });
                     }
@@ -324,6 +345,13 @@ async fn udp_send(socket: &mut UdpSocket, buf: &[u8]) -> tokio::io::Result<()> {
     Ok(())
 }
 
+async fn udp_send_all(socket: &mut UdpSocket, datagrams: &[Bytes]) -> tokio::io::Result<()> {
+    for datagram in datagrams {
+        udp_send(socket, datagram).await?;
+    }
+    Ok(())
+}
+
 fn find_bind_address(remote_addr: &SocketAddr) -> SocketAddr {
     match remote_addr {
         SocketAddr::V4(_) => SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0),
@@ -1,12 +1,47 @@
 pub mod v1;
 pub mod v2;
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::config::{
     AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext, SinkDescription,
 };
 
+/// The compression applied to the events sent to the `vector` source.
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Eq, PartialEq, Serialize)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum VectorCompression {
+    /// Events are sent uncompressed.
+    #[derivative(Default)]
+    None,
+
+    /// Events are compressed with gzip.
+    Gzip,
+
+    /// Events are compressed with zstd.
+    Zstd,
+}
+
+/// Deserializes the `compression` option, which used to be a boolean enabling gzip compression.
+fn bool_or_compression<'de, D>(deserializer: D) -> Result<VectorCompression, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum BoolOrCompression {
+        Bool(bool),
+        Compression(VectorCompression),
+    }
+
+    Ok(match BoolOrCompression::deserialize(deserializer)? {
+        BoolOrCompression::Bool(true) => VectorCompression::Gzip,
+        BoolOrCompression::Bool(false) => VectorCompression::None,
+        BoolOrCompression::Compression(compression) => compression,
+    })
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 enum V1 {
     #[serde(rename = "1")]
@@ -65,7 +100,7 @@ impl SinkConfig for VectorConfig {
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
         match self {
-            VectorConfig::V1(v1) => v1.config.build().await,
+            VectorConfig::V1(v1) => v1.config.build(cx).await,
             VectorConfig::V2(v2) => v2.config.build(cx).await,
         }
     }
@@ -80,7 +115,7 @@ impl SinkConfig for VectorConfig {
 
     fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
         match self {
-            Self::V1(_) => None,
+            Self::V1(v1) => Some(&v1.config.acknowledgements),
             Self::V2(v2) => Some(&v2.config.acknowledgements),
         }
     }
@@ -88,8 +123,30 @@ impl SinkConfig for VectorConfig {
 
 #[cfg(test)]
 mod test {
+    use super::{bool_or_compression, VectorCompression};
+
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<super::VectorConfig>();
     }
+
+    #[test]
+    fn compression_accepts_booleans() {
+        #[derive(serde::Deserialize)]
+        struct Config {
+            #[serde(deserialize_with = "bool_or_compression")]
+            compression: VectorCompression,
+        }
+
+        for (input, expected) in [
+            ("true", VectorCompression::Gzip),
+            ("false", VectorCompression::None),
+            (r#""gzip""#, VectorCompression::Gzip),
+            (r#""zstd""#, VectorCompression::Zstd),
+            (r#""none""#, VectorCompression::None),
+        ] {
+            let config: Config = toml::from_str(&format!("compression = {}", input)).unwrap();
+            assert_eq!(config.compression, expected);
+        }
+    }
 }
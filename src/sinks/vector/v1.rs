@@ -1,17 +1,42 @@
+use std::{io, pin::Pin, time::Duration};
+
+use async_trait::async_trait;
 use bytes::{BufMut, BytesMut};
+use futures::{stream::BoxStream, StreamExt};
 use prost::Message;
 use serde::{Deserialize, Serialize};
 use snafu::Snafu;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
 use tokio_util::codec::Encoder;
-use vector_core::event::{proto, Event};
+use vector_common::internal_event::{BytesSent, EventsSent};
+use vector_core::{
+    event::{proto, Event, EventStatus, Finalizable},
+    ByteSizeOf,
+};
 
 use crate::{
-    config::GenerateConfig,
-    sinks::{util::tcp::TcpSinkConfig, Healthcheck, VectorSink},
+    config::{AcknowledgementsConfig, GenerateConfig, SinkContext},
+    internal_events::TcpSocketError,
+    proto::v1::{AckStatus, FrameCompression, HANDSHAKE_MARKER},
+    sinks::{
+        util::{
+            tcp::{TcpConnector, TcpSinkConfig},
+            StreamSink,
+        },
+        vector::VectorCompression,
+        Healthcheck, VectorSink,
+    },
     tcp::TcpKeepaliveConfig,
-    tls::TlsEnableableConfig,
+    tls::{MaybeTlsStream, TlsEnableableConfig},
 };
 
+/// The time to wait for the `vector` source to answer the compression handshake. Sources not
+/// supporting compression ignore the handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct VectorConfig {
@@ -19,6 +44,17 @@ pub struct VectorConfig {
     keepalive: Option<TcpKeepaliveConfig>,
     tls: Option<TlsEnableableConfig>,
     send_buffer_bytes: Option<usize>,
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    compression: VectorCompression,
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub(in crate::sinks::vector) acknowledgements: AcknowledgementsConfig,
 }
 
 impl VectorConfig {
@@ -26,7 +62,15 @@ impl VectorConfig {
         self.tls = config;
     }
 
-    pub const fn new(
+    pub fn set_acknowledgements(&mut self, config: AcknowledgementsConfig) {
+        self.acknowledgements = config;
+    }
+
+    pub fn set_compression(&mut self, compression: VectorCompression) {
+        self.compression = compression;
+    }
+
+    pub fn new(
         address: String,
         keepalive: Option<TcpKeepaliveConfig>,
         tls: Option<TlsEnableableConfig>,
@@ -37,10 +81,12 @@ impl VectorConfig {
             keepalive,
             tls,
             send_buffer_bytes,
+            compression: VectorCompression::None,
+            acknowledgements: Default::default(),
         }
     }
 
-    pub const fn from_address(address: String) -> Self {
+    pub fn from_address(address: String) -> Self {
         Self::new(address, None, None, None)
     }
 }
@@ -60,39 +106,208 @@ impl GenerateConfig for VectorConfig {
 }
 
 impl VectorConfig {
-    pub(crate) async fn build(&self) -> crate::Result<(VectorSink, Healthcheck)> {
+    pub(crate) async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
         let sink_config = TcpSinkConfig::new(
             self.address.clone(),
             self.keepalive,
             self.tls.clone(),
             self.send_buffer_bytes,
         );
-        sink_config.build(Default::default(), VectorEncoder)
+
+        let acknowledgements = self
+            .acknowledgements
+            .merge_default(&cx.globals.acknowledgements)
+            .enabled();
+        let compression = match self.compression {
+            VectorCompression::None => None,
+            VectorCompression::Gzip => Some(FrameCompression::Gzip),
+            VectorCompression::Zstd => Some(FrameCompression::Zstd),
+        };
+
+        if acknowledgements || compression.is_some() {
+            let connector = sink_config.build_connector()?;
+            let sink = VectorFrameSink {
+                connector: connector.clone(),
+                acknowledgements,
+                compression,
+            };
+            Ok((
+                VectorSink::from_event_streamsink(sink),
+                Box::pin(async move { connector.healthcheck().await }),
+            ))
+        } else {
+            sink_config.build(Default::default(), VectorEncoder { compression: None })
+        }
     }
 }
 
 #[derive(Debug, Clone)]
-struct VectorEncoder;
+struct VectorEncoder {
+    /// The compression negotiated for the connection, if any.
+    compression: Option<FrameCompression>,
+}
 
 impl Encoder<Event> for VectorEncoder {
     type Error = codecs::encoding::Error;
 
     fn encode(&mut self, event: Event, out: &mut BytesMut) -> Result<(), Self::Error> {
         let data = proto::EventWrapper::from(event);
-        let event_len = data.encoded_len();
-        let full_len = event_len + 4;
 
-        let capacity = out.capacity();
-        if capacity < full_len {
-            out.reserve(full_len - capacity);
+        match self.compression {
+            None => {
+                let event_len = data.encoded_len();
+                let full_len = event_len + 4;
+
+                let capacity = out.capacity();
+                if capacity < full_len {
+                    out.reserve(full_len - capacity);
+                }
+                out.put_u32(event_len as u32);
+                data.encode(out).unwrap();
+            }
+            Some(compression) => {
+                let payload = compression.compress(&data.encode_to_vec())?;
+                out.reserve(payload.len() + 5);
+                out.put_u32(payload.len() as u32 + 1);
+                out.put_u8(compression.as_byte());
+                out.extend_from_slice(&payload);
+            }
         }
-        out.put_u32(event_len as u32);
-        data.encode(out).unwrap();
 
         Ok(())
     }
 }
 
+/// Sends events one at a time over connections negotiating the frame compression, optionally
+/// waiting for the `vector` source to acknowledge each event before sending the next one.
+struct VectorFrameSink {
+    connector: TcpConnector,
+    acknowledgements: bool,
+    compression: Option<FrameCompression>,
+}
+
+#[async_trait]
+impl StreamSink<Event> for VectorFrameSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let mut input = input.peekable();
+
+        let mut requested_compression = self.compression;
+        while Pin::new(&mut input).peek().await.is_some() {
+            let mut stream = self.connector.connect_backoff().await;
+
+            let mut encoder = VectorEncoder { compression: None };
+            if let Some(requested) = requested_compression {
+                match negotiate_compression(&mut stream, requested).await {
+                    Ok(compression) => encoder.compression = Some(compression),
+                    Err(error)
+                        if matches!(
+                            error.kind(),
+                            io::ErrorKind::UnexpectedEof | io::ErrorKind::TimedOut
+                        ) =>
+                    {
+                        warn!(
+                            message = "The `vector` source did not answer the compression handshake, sending uncompressed frames.",
+                            compression = ?requested,
+                        );
+                        requested_compression = None;
+                        continue;
+                    }
+                    Err(error) => {
+                        emit!(TcpSocketError { error });
+                        continue;
+                    }
+                }
+            }
+
+            while let Some(mut event) = input.next().await {
+                let byte_size = event.size_of();
+                let finalizers = event.take_finalizers();
+                let mut bytes = BytesMut::new();
+                if encoder.encode(event, &mut bytes).is_err() {
+                    continue;
+                }
+
+                let result = if self.acknowledgements {
+                    send_and_wait_for_ack(&mut stream, &bytes).await
+                } else {
+                    stream
+                        .write_all(&bytes)
+                        .await
+                        .map(|()| AckStatus::Delivered)
+                };
+
+                match result {
+                    Ok(status) => {
+                        finalizers.update_status(status.into());
+                        if status != AckStatus::Delivered {
+                            // The source closes the connection after failing to deliver events.
+                            break;
+                        }
+
+                        emit!(EventsSent {
+                            count: 1,
+                            byte_size,
+                            output: None,
+                        });
+                        emit!(BytesSent {
+                            byte_size: bytes.len(),
+                            protocol: "tcp",
+                        });
+                    }
+                    Err(error) => {
+                        finalizers.update_status(EventStatus::Errored);
+                        emit!(TcpSocketError { error });
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Sends the compression handshake, returning the compression accepted by the `vector` source.
+async fn negotiate_compression(
+    stream: &mut MaybeTlsStream<TcpStream>,
+    requested: FrameCompression,
+) -> io::Result<FrameCompression> {
+    let mut handshake = BytesMut::with_capacity(6);
+    handshake.put_u32(2);
+    handshake.put_u8(HANDSHAKE_MARKER);
+    handshake.put_u8(requested.as_byte());
+    stream.write_all(&handshake).await?;
+
+    let byte = tokio::time::timeout(HANDSHAKE_TIMEOUT, stream.read_u8())
+        .await
+        .map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::TimedOut,
+                "Timed out waiting for the compression handshake",
+            )
+        })??;
+    FrameCompression::from_byte(byte).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Invalid frame compression: {}", byte),
+        )
+    })
+}
+
+async fn send_and_wait_for_ack(
+    stream: &mut MaybeTlsStream<TcpStream>,
+    bytes: &[u8],
+) -> io::Result<AckStatus> {
+    stream.write_all(bytes).await?;
+    let byte = stream.read_u8().await?;
+    AckStatus::from_byte(byte).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Invalid acknowledgement status: {}", byte),
+        )
+    })
+}
+
 #[derive(Debug, Snafu)]
 enum HealthcheckError {
     #[snafu(display("Connect error: {}", source))]
@@ -105,7 +320,7 @@ mod test {
     use vector_core::event::{Event, LogEvent};
 
     use crate::{
-        config::GenerateConfig,
+        config::{GenerateConfig, SinkContext},
         test_util::{
             components::{run_and_assert_sink_compliance, SINK_TAGS},
             next_addr, wait_for_tcp, CountReceiver,
@@ -132,7 +347,7 @@ mod test {
         config.address = mock_endpoint_addr.to_string();
         config.tls = Some(TlsEnableableConfig::default());
 
-        let (sink, _healthcheck) = config.build().await.unwrap();
+        let (sink, _healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
 
         let event = Event::Log(LogEvent::from("simple message"));
         run_and_assert_sink_compliance(sink, stream::once(ready(event)), &SINK_TAGS).await;
@@ -13,13 +13,18 @@ use crate::{
     proto::vector as proto,
     sinks::{
         util::{
-            retries::RetryLogic, BatchConfig, RealtimeEventBasedDefaultBatchSettings,
-            ServiceBuilderExt, TowerRequestConfig,
+            retries::RetryLogic,
+            service::{Balancer, LoadBalancingConfig},
+            BatchConfig, RealtimeEventBasedDefaultBatchSettings, ServiceBuilderExt,
+            TowerRequestConfig,
         },
-        vector::v2::{
-            service::{VectorResponse, VectorService},
-            sink::VectorSink,
-            VectorSinkError,
+        vector::{
+            v2::{
+                service::{VectorResponse, VectorService},
+                sink::VectorSink,
+                VectorSinkError,
+            },
+            VectorCompression,
         },
         Healthcheck, VectorSink as VectorSinkType,
     },
@@ -29,9 +34,16 @@ use crate::{
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct VectorConfig {
-    address: String,
+    address: Option<String>,
     #[serde(default)]
-    compression: bool,
+    endpoints: Vec<String>,
+    #[serde(default)]
+    load_balancing: LoadBalancingConfig,
+    #[serde(
+        default,
+        deserialize_with = "crate::sinks::vector::bool_or_compression"
+    )]
+    compression: VectorCompression,
     #[serde(default)]
     pub batch: BatchConfig<RealtimeEventBasedDefaultBatchSettings>,
     #[serde(default)]
@@ -54,8 +66,10 @@ impl GenerateConfig for VectorConfig {
 
 fn default_config(address: &str) -> VectorConfig {
     VectorConfig {
-        address: address.to_owned(),
-        compression: false,
+        address: Some(address.to_owned()),
+        endpoints: Vec::new(),
+        load_balancing: LoadBalancingConfig::default(),
+        compression: VectorCompression::None,
         batch: BatchConfig::default(),
         request: TowerRequestConfig::default(),
         tls: None,
@@ -69,19 +83,30 @@ impl VectorConfig {
         cx: SinkContext,
     ) -> crate::Result<(VectorSinkType, Healthcheck)> {
         let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
-        let uri = with_default_scheme(&self.address, tls.is_tls())?;
+        let uris = self
+            .addresses()?
+            .into_iter()
+            .map(|address| with_default_scheme(address, tls.is_tls()))
+            .collect::<crate::Result<Vec<_>>>()?;
 
         let client = new_client(&tls, cx.proxy())?;
 
-        let healthcheck_uri = cx
-            .healthcheck
-            .uri
-            .clone()
-            .map(|uri| uri.uri)
-            .unwrap_or_else(|| uri.clone());
-        let healthcheck_client = VectorService::new(client.clone(), healthcheck_uri, false);
-        let healthcheck = healthcheck(healthcheck_client, cx.healthcheck);
-        let service = VectorService::new(client, uri, self.compression);
+        let healthcheck_uris = match cx.healthcheck.uri.clone() {
+            Some(uri) => vec![uri.uri],
+            None => uris.clone(),
+        };
+        let healthcheck_clients = healthcheck_uris
+            .into_iter()
+            .map(|uri| VectorService::new(client.clone(), uri, VectorCompression::None))
+            .collect();
+        let healthcheck = healthcheck(healthcheck_clients, cx.healthcheck);
+        let services = uris.into_iter().map(|uri| {
+            (
+                uri.to_string(),
+                VectorService::new(client.clone(), uri, self.compression),
+            )
+        });
+        let service = Balancer::new(services, self.load_balancing, VectorGrpcRetryLogic);
         let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
         let batch_settings = self.batch.into_batcher_settings()?;
 
@@ -99,24 +124,36 @@ impl VectorConfig {
             Box::pin(healthcheck),
         ))
     }
+
+    /// The addresses of the endpoints to send events to.
+    fn addresses(&self) -> crate::Result<Vec<&str>> {
+        match (&self.address, self.endpoints.is_empty()) {
+            (Some(address), true) => Ok(vec![address.as_str()]),
+            (None, false) => Ok(self.endpoints.iter().map(String::as_str).collect()),
+            (Some(_), false) => Err("Only one of `address` and `endpoints` can be set.".into()),
+            (None, true) => Err("Either `address` or `endpoints` must be set.".into()),
+        }
+    }
 }
 
-/// Check to see if the remote service accepts new events.
+/// Check to see if any of the remote services accepts new events.
 async fn healthcheck(
-    mut service: VectorService,
+    services: Vec<VectorService>,
     options: SinkHealthcheckOptions,
 ) -> crate::Result<()> {
     if !options.enabled {
         return Ok(());
     }
 
-    let request = service.client.health_check(proto::HealthCheckRequest {});
+    for mut service in services {
+        let request = service.client.health_check(proto::HealthCheckRequest {});
 
-    if let Ok(response) = request.await {
-        let status = proto::ServingStatus::from_i32(response.into_inner().status);
+        if let Ok(response) = request.await {
+            let status = proto::ServingStatus::from_i32(response.into_inner().status);
 
-        if let Some(proto::ServingStatus::Serving) = status {
-            return Ok(());
+            if let Some(proto::ServingStatus::Serving) = status {
+                return Ok(());
+            }
         }
     }
 
@@ -157,7 +194,7 @@ pub fn with_default_scheme(address: &str, tls: bool) -> crate::Result<Uri> {
     }
 }
 
-fn new_client(
+pub(crate) fn new_client(
     tls_settings: &MaybeTlsSettings,
     proxy_config: &ProxyConfig,
 ) -> crate::Result<hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>> {
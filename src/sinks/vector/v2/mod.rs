@@ -5,6 +5,8 @@ mod service;
 mod sink;
 
 pub use config::VectorConfig;
+pub(crate) use config::{new_client, with_default_scheme};
+pub(crate) use service::HyperSvc;
 
 #[derive(Debug, Snafu)]
 #[snafu(visibility(pub))]
@@ -92,6 +94,124 @@ mod tests {
         assert_eq!(input_lines, output_lines);
     }
 
+    #[tokio::test]
+    async fn deliver_zstd_compressed_message() {
+        let in_addr = next_addr();
+
+        let config = format!(
+            r#"address = "http://{}/"
+            compression = "zstd""#,
+            in_addr
+        );
+        let config: VectorConfig = toml::from_str(&config).unwrap();
+
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+        let (rx, trigger, server) = build_test_server_generic(in_addr, move || {
+            hyper::Response::builder()
+                .header("grpc-status", "0") // OK
+                .header("content-type", "application/grpc")
+                .body(hyper::Body::from(encode_body(proto::PushEventsResponse {})))
+                .unwrap()
+        });
+
+        tokio::spawn(server);
+
+        let (input_lines, events) = random_lines_with_stream(8, 10, None);
+        sink.run(events).await.expect("Running sink failed");
+        drop(trigger);
+
+        let output_lines = rx
+            .map(|(parts, body)| {
+                assert_eq!(
+                    "zstd",
+                    parts
+                        .headers
+                        .get("grpc-encoding")
+                        .unwrap()
+                        .to_str()
+                        .unwrap()
+                );
+                // compression flag, 1 means "compressed"
+                assert_eq!(body[0], 1);
+
+                let body = zstd::stream::decode_all(&body[GRPC_HEADER_SIZE..]).unwrap();
+                let req = proto::PushEventsRequest::decode(Bytes::from(body)).unwrap();
+                req.events
+                    .into_iter()
+                    .map(|event| {
+                        Event::from(event)
+                            .as_log()
+                            .get("message")
+                            .unwrap()
+                            .to_string_lossy()
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        assert_eq!(input_lines, output_lines);
+    }
+
+    #[tokio::test]
+    async fn deliver_messages_to_multiple_endpoints() {
+        let addrs = [next_addr(), next_addr()];
+
+        let config = format!(
+            r#"endpoints = ["http://{}/", "http://{}/"]
+            batch.max_events = 1"#,
+            addrs[0], addrs[1]
+        );
+        let config: VectorConfig = toml::from_str(&config).unwrap();
+
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+        let mut receivers = Vec::new();
+        let mut triggers = Vec::new();
+        for addr in addrs {
+            let (rx, trigger, server) = build_test_server_generic(addr, move || {
+                hyper::Response::builder()
+                    .header("grpc-status", "0") // OK
+                    .header("content-type", "application/grpc")
+                    .body(hyper::Body::from(encode_body(proto::PushEventsResponse {})))
+                    .unwrap()
+            });
+            tokio::spawn(server);
+            receivers.push(rx);
+            triggers.push(trigger);
+        }
+
+        let (mut input_lines, events) = random_lines_with_stream(8, 10, None);
+        sink.run(events).await.expect("Running sink failed");
+        drop(triggers);
+
+        let mut output_lines = Vec::new();
+        for rx in receivers {
+            let lines = get_received(rx, |_| {}).await;
+            // Requests are sent to each endpoint in turn.
+            assert_eq!(lines.len(), 5);
+            output_lines.extend(lines);
+        }
+
+        input_lines.sort();
+        output_lines.sort();
+        assert_eq!(input_lines, output_lines);
+    }
+
+    #[tokio::test]
+    async fn requires_single_address_option() {
+        for config in [
+            "",
+            r#"address = "http://127.0.0.1:6000/"
+            endpoints = ["http://127.0.0.1:6001/"]"#,
+        ] {
+            let config: VectorConfig = toml::from_str(config).unwrap();
+            assert!(config.build(SinkContext::new_test()).await.is_err());
+        }
+    }
+
     #[tokio::test]
     async fn acknowledges_error() {
         let num_lines = 10;
@@ -1,13 +1,17 @@
-use std::task::{Context, Poll};
+use std::{
+    io,
+    task::{Context, Poll},
+};
 
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 use futures::{future::BoxFuture, TryFutureExt};
-use http::Uri;
-use hyper::client::HttpConnector;
+use http::{header::HeaderValue, Uri};
+use hyper::{body::HttpBody, client::HttpConnector};
 use hyper_openssl::HttpsConnector;
 use hyper_proxy::ProxyConnector;
 use prost::Message;
 use proto_event::EventWrapper;
-use tonic::{body::BoxBody, IntoRequest};
+use tonic::{body::BoxBody, IntoRequest, Status};
 use vector_core::{
     event::proto as proto_event, internal_event::EventsSent, stream::DriverResponse,
 };
@@ -16,10 +20,17 @@ use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
     internal_events::EndpointBytesSent,
     proto::vector as proto_vector,
-    sinks::{util::uri, vector::v2::VectorSinkError},
+    sinks::{
+        util::uri,
+        vector::{v2::VectorSinkError, VectorCompression},
+    },
     Error,
 };
 
+// Every gRPC message has a five byte header: a compressed flag, followed by the length of the message.
+const GRPC_MESSAGE_HEADER_LEN: usize = 5;
+const GRPC_ENCODING_HEADER: &str = "grpc-encoding";
+
 #[derive(Clone, Debug)]
 pub struct VectorService {
     pub client: proto_vector::Client<HyperSvc>,
@@ -63,15 +74,15 @@ impl VectorService {
     pub fn new(
         hyper_client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
         uri: Uri,
-        compression: bool,
+        compression: VectorCompression,
     ) -> Self {
         let (protocol, endpoint) = uri::protocol_endpoint(uri.clone());
         let mut proto_client = proto_vector::Client::new(HyperSvc {
-            uri,
-            client: hyper_client,
+            zstd: compression == VectorCompression::Zstd,
+            ..HyperSvc::new(uri, hyper_client)
         });
 
-        if compression {
+        if compression == VectorCompression::Gzip {
             proto_client = proto_client.send_gzip();
         }
         Self {
@@ -132,11 +143,27 @@ impl tower::Service<VectorRequest> for VectorService {
 pub struct HyperSvc {
     uri: Uri,
     client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
+    /// Whether to compress requests with zstd, which `tonic` does not support itself.
+    zstd: bool,
+}
+
+impl HyperSvc {
+    /// Creates a service sending the requests of a gRPC client to the given URI.
+    pub(crate) const fn new(
+        uri: Uri,
+        client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
+    ) -> Self {
+        Self {
+            uri,
+            client,
+            zstd: false,
+        }
+    }
 }
 
 impl tower::Service<hyper::Request<BoxBody>> for HyperSvc {
     type Response = hyper::Response<hyper::Body>;
-    type Error = hyper::Error;
+    type Error = Error;
     type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
 
     fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
@@ -153,6 +180,52 @@ impl tower::Service<hyper::Request<BoxBody>> for HyperSvc {
 
         *req.uri_mut() = uri;
 
-        Box::pin(self.client.request(req))
+        let client = self.client.clone();
+        if !self.zstd {
+            return Box::pin(client.request(req).err_into());
+        }
+
+        Box::pin(async move {
+            let (mut parts, body) = req.into_parts();
+            let body = zstd_compress_messages(hyper::body::to_bytes(body).await?)?;
+            parts
+                .headers
+                .insert(GRPC_ENCODING_HEADER, HeaderValue::from_static("zstd"));
+            let body = hyper::Body::from(body)
+                .map_err(|error| Status::internal(error.to_string()))
+                .boxed_unsync();
+
+            Ok(client
+                .request(hyper::Request::from_parts(parts, body))
+                .await?)
+        })
+    }
+}
+
+/// Compresses each of the uncompressed messages of a gRPC request body with zstd.
+fn zstd_compress_messages(mut body: Bytes) -> io::Result<Bytes> {
+    let mut compressed = BytesMut::with_capacity(body.len());
+    while body.has_remaining() {
+        if body.len() < GRPC_MESSAGE_HEADER_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "truncated gRPC message header",
+            ));
+        }
+        body.advance(1);
+        let message_len = body.get_u32() as usize;
+        if body.len() < message_len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "truncated gRPC message",
+            ));
+        }
+
+        let message = zstd::stream::encode_all(&body.split_to(message_len)[..], 0)?;
+        compressed.put_u8(1);
+        compressed.put_u32(message.len() as u32);
+        compressed.extend_from_slice(&message);
     }
+
+    Ok(compressed.freeze())
 }
@@ -4,9 +4,10 @@ use snafu::ResultExt;
 
 use crate::{
     codecs::EncodingConfig,
+    common::websocket::{ConnectSnafu, WebSocketConnector, WebSocketError},
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
     sinks::{
-        websocket::sink::{ConnectSnafu, WebSocketConnector, WebSocketError, WebSocketSink},
+        websocket::{server::WebSocketServerSink, sink::WebSocketSink},
         Healthcheck, VectorSink,
     },
     tls::{MaybeTlsSettings, TlsEnableableConfig},
@@ -15,6 +16,16 @@ use crate::{
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct WebSocketSinkConfig {
     pub uri: String,
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub mode: WebSocketMode,
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub server: WebSocketServerConfig,
     pub tls: Option<TlsEnableableConfig>,
     pub encoding: EncodingConfig,
     pub ping_interval: Option<u64>,
@@ -27,10 +38,53 @@ pub struct WebSocketSinkConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// Whether the sink connects to `uri`, or listens on it for clients to connect.
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, PartialEq, Eq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WebSocketMode {
+    #[derivative(Default)]
+    Client,
+    Server,
+}
+
+/// The options of the `server` mode.
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, PartialEq, Eq)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct WebSocketServerConfig {
+    /// The maximum number of messages queued for each client.
+    #[serde(default = "default_client_queue_size")]
+    #[derivative(Default(value = "default_client_queue_size()"))]
+    pub client_queue_size: usize,
+    /// What happens to the messages for a client whose queue is full.
+    #[serde(default)]
+    pub when_full: ClientQueueFullMode,
+}
+
+const fn default_client_queue_size() -> usize {
+    1000
+}
+
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, PartialEq, Eq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientQueueFullMode {
+    /// The new message is dropped.
+    #[derivative(Default)]
+    DropNewest,
+    /// The oldest message of the queue is dropped to make room for the new one.
+    DropOldest,
+    /// The client is disconnected.
+    Disconnect,
+}
+
 impl GenerateConfig for WebSocketSinkConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
             uri: "ws://127.0.0.1:9000/endpoint".into(),
+            mode: WebSocketMode::Client,
+            server: Default::default(),
             tls: None,
             encoding: JsonSerializerConfig::new().into(),
             ping_interval: None,
@@ -45,6 +99,15 @@ impl GenerateConfig for WebSocketSinkConfig {
 #[typetag::serde(name = "websocket")]
 impl SinkConfig for WebSocketSinkConfig {
     async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        if self.mode == WebSocketMode::Server {
+            let tls = MaybeTlsSettings::from_config(&self.tls, true)?;
+            let sink = WebSocketServerSink::bind(self, tls).await?;
+            return Ok((
+                VectorSink::from_event_streamsink(sink),
+                Box::pin(async { Ok(()) }),
+            ));
+        }
+
         let connector = self.build_connector()?;
         let ws_sink = WebSocketSink::new(self, connector.clone())?;
 
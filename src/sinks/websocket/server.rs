@@ -0,0 +1,545 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    io,
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use bytes::BytesMut;
+use futures::{stream::BoxStream, SinkExt, StreamExt};
+use http::{StatusCode, Uri};
+use snafu::{ResultExt, Snafu};
+use tokio::{net::TcpStream, sync::Notify};
+use tokio_tungstenite::{
+    accept_hdr_async,
+    tungstenite::{
+        error::Error as WsError,
+        handshake::server::{ErrorResponse, Request, Response},
+        protocol::Message,
+    },
+    WebSocketStream as WsStream,
+};
+use tokio_util::codec::Encoder as _;
+use vector_core::{
+    internal_event::{BytesSent, EventsSent},
+    ByteSizeOf,
+};
+
+use crate::{
+    codecs::{Encoder, Transformer},
+    common::websocket::is_closed,
+    conditions::{Condition, ConditionalConfig, VrlConfig},
+    emit,
+    event::{Event, EventStatus, Finalizable},
+    internal_events::{
+        ConnectionOpen, OpenGauge, WsClientEventsDropped, WsConnectionError,
+        WsConnectionEstablished, WsConnectionFailedError, WsConnectionShutdown,
+    },
+    sinks::{
+        util::StreamSink,
+        websocket::{
+            config::{ClientQueueFullMode, WebSocketSinkConfig},
+            sink::PingInterval,
+        },
+    },
+    tls::{MaybeTlsIncomingStream, MaybeTlsListener, MaybeTlsSettings},
+};
+
+/// The query parameter of the VRL condition the events sent to a client must match.
+const FILTER_PARAM: &str = "filter";
+
+#[derive(Debug, Snafu)]
+enum ServerBuildError {
+    #[snafu(display("Invalid URI {:?}: {}", uri, source))]
+    InvalidUri {
+        uri: String,
+        source: http::uri::InvalidUri,
+    },
+    #[snafu(display("The host of URI {:?} must be an IP address to listen on", uri))]
+    InvalidAddress { uri: String },
+}
+
+type Clients = Arc<Mutex<HashMap<u64, Client>>>;
+
+struct Client {
+    filter: Option<Condition>,
+    queue: Arc<ClientQueue>,
+}
+
+/// The options of the connections of the clients.
+#[derive(Clone)]
+struct ClientOptions {
+    path: String,
+    queue_size: usize,
+    when_full: ClientQueueFullMode,
+    ping_interval: Option<u64>,
+    ping_timeout: Option<u64>,
+}
+
+/// The `server` mode of the sink, which sends the events to the clients connected to it, each of
+/// which may subscribe to the events matching a VRL condition.
+pub struct WebSocketServerSink {
+    transformer: Transformer,
+    encoder: Encoder<()>,
+    listener: MaybeTlsListener,
+    options: ClientOptions,
+}
+
+impl WebSocketServerSink {
+    pub(super) async fn bind(
+        config: &WebSocketSinkConfig,
+        tls: MaybeTlsSettings,
+    ) -> crate::Result<Self> {
+        let transformer = config.encoding.transformer();
+        let serializer = config.encoding.build()?;
+        let encoder = Encoder::<()>::new(serializer);
+
+        let (address, path) = listen_address(&config.uri)?;
+        let listener = tls.bind(&address).await?;
+
+        Ok(Self {
+            transformer,
+            encoder,
+            listener,
+            options: ClientOptions {
+                path,
+                queue_size: config.server.client_queue_size,
+                when_full: config.server.when_full,
+                ping_interval: config.ping_interval.filter(|v| *v > 0),
+                ping_timeout: config.ping_timeout.filter(|v| *v > 0),
+            },
+        })
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for WebSocketServerSink {
+    async fn run(self: Box<Self>, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let Self {
+            transformer,
+            mut encoder,
+            listener,
+            options,
+        } = *self;
+
+        let clients = Clients::default();
+        let accept = tokio::spawn(accept_clients(listener, Arc::clone(&clients), options));
+
+        while let Some(event) = input.next().await {
+            send_event(event, &transformer, &mut encoder, &clients);
+        }
+
+        accept.abort();
+        for client in clients.lock().expect("clients mutex poisoned").values() {
+            client.queue.close();
+        }
+
+        Ok(())
+    }
+}
+
+/// Queues the event for the clients whose filter it matches.
+fn send_event(
+    mut event: Event,
+    transformer: &Transformer,
+    encoder: &mut Encoder<()>,
+    clients: &Clients,
+) {
+    let finalizers = event.take_finalizers();
+    transformer.transform(&mut event);
+    let event_byte_size = event.size_of();
+
+    let mut queues = Vec::new();
+    for client in clients.lock().expect("clients mutex poisoned").values() {
+        let matches = match &client.filter {
+            Some(filter) => {
+                let (matches, checked) = filter.check(event);
+                event = checked;
+                matches
+            }
+            None => true,
+        };
+        if matches {
+            queues.push(Arc::clone(&client.queue));
+        }
+    }
+    if queues.is_empty() {
+        finalizers.update_status(EventStatus::Delivered);
+        return;
+    }
+
+    let mut bytes = BytesMut::new();
+    if encoder.encode(event, &mut bytes).is_err() {
+        // Error is handled by `Encoder`.
+        finalizers.update_status(EventStatus::Errored);
+        return;
+    }
+    finalizers.update_status(EventStatus::Delivered);
+
+    let message = Message::text(String::from_utf8_lossy(&bytes));
+    let message_len = message.len();
+    let dropped = queues
+        .iter()
+        .map(|queue| queue.push(message.clone()))
+        .sum::<usize>();
+    if dropped > 0 {
+        emit!(WsClientEventsDropped { count: dropped });
+    }
+
+    emit!(EventsSent {
+        count: 1,
+        byte_size: event_byte_size,
+        output: None
+    });
+    emit!(BytesSent {
+        byte_size: message_len * queues.len(),
+        protocol: "websocket"
+    });
+}
+
+async fn accept_clients(mut listener: MaybeTlsListener, clients: Clients, options: ClientOptions) {
+    let mut next_id = 0;
+    loop {
+        match listener.accept().await {
+            Ok(stream) => {
+                tokio::spawn(serve_client(
+                    next_id,
+                    stream,
+                    Arc::clone(&clients),
+                    options.clone(),
+                ));
+                next_id += 1;
+            }
+            Err(error) => emit!(WsConnectionFailedError {
+                error: Box::new(error)
+            }),
+        }
+    }
+}
+
+async fn serve_client(
+    id: u64,
+    stream: MaybeTlsIncomingStream<TcpStream>,
+    clients: Clients,
+    options: ClientOptions,
+) {
+    let mut filter = None;
+    let callback = |request: &Request, response: Response| {
+        if request.uri().path() != options.path {
+            return Err(error_response(StatusCode::NOT_FOUND, "Not found.".into()));
+        }
+        match build_filter(request.uri()) {
+            Ok(condition) => {
+                filter = condition;
+                Ok(response)
+            }
+            Err(error) => Err(error_response(
+                StatusCode::BAD_REQUEST,
+                format!("Invalid filter: {}", error),
+            )),
+        }
+    };
+    let ws_stream = match accept_hdr_async(stream, callback).await {
+        Ok(ws_stream) => ws_stream,
+        Err(error) => {
+            emit!(WsConnectionError { error });
+            return;
+        }
+    };
+
+    emit!(WsConnectionEstablished);
+    let _open_token = OpenGauge::new().open(|count| emit!(ConnectionOpen { count }));
+
+    let queue = Arc::new(ClientQueue::new(options.queue_size, options.when_full));
+    clients.lock().expect("clients mutex poisoned").insert(
+        id,
+        Client {
+            filter,
+            queue: Arc::clone(&queue),
+        },
+    );
+
+    let result = send_messages(ws_stream, &queue, &options).await;
+    clients.lock().expect("clients mutex poisoned").remove(&id);
+
+    if let Err(error) = result {
+        if is_closed(&error) {
+            emit!(WsConnectionShutdown);
+        } else {
+            emit!(WsConnectionError { error });
+        }
+    }
+}
+
+/// Sends the messages of the queue to the client until either of them closes.
+async fn send_messages(
+    ws_stream: WsStream<MaybeTlsIncomingStream<TcpStream>>,
+    queue: &ClientQueue,
+    options: &ClientOptions,
+) -> Result<(), WsError> {
+    let (mut ws_sink, mut ws_stream) = ws_stream.split();
+    let mut ping_interval = PingInterval::new(options.ping_interval);
+    let mut last_pong = Instant::now();
+
+    loop {
+        tokio::select! {
+            message = queue.pop() => match message {
+                Some(message) => ws_sink.send(message).await?,
+                None => return ws_sink.close().await,
+            },
+
+            _ = ping_interval.tick() => {
+                if let Some(ping_timeout) = options.ping_timeout {
+                    if last_pong.elapsed() > Duration::from_secs(ping_timeout) {
+                        return Err(WsError::Io(io::Error::new(
+                            io::ErrorKind::TimedOut,
+                            "Pong not received in time",
+                        )));
+                    }
+                }
+                ws_sink.send(Message::Ping(Vec::new())).await?;
+            },
+
+            message = ws_stream.next() => match message {
+                Some(Ok(Message::Pong(_))) => last_pong = Instant::now(),
+                Some(Ok(_)) => {}
+                Some(Err(error)) => return Err(error),
+                None => return Ok(()),
+            },
+        }
+    }
+}
+
+/// Returns the address to listen on and the path of the URI.
+fn listen_address(uri: &str) -> Result<(SocketAddr, String), ServerBuildError> {
+    let parsed = uri.parse::<Uri>().context(InvalidUriSnafu { uri })?;
+    let port = parsed
+        .port_u16()
+        .unwrap_or(if parsed.scheme_str() == Some("wss") {
+            443
+        } else {
+            80
+        });
+    let ip = parsed
+        .host()
+        .and_then(|host| {
+            host.trim_start_matches('[')
+                .trim_end_matches(']')
+                .parse::<IpAddr>()
+                .ok()
+        })
+        .ok_or_else(|| ServerBuildError::InvalidAddress { uri: uri.into() })?;
+
+    Ok((SocketAddr::new(ip, port), parsed.path().to_owned()))
+}
+
+/// Compiles the VRL condition of the `filter` query parameter of the request, if any.
+fn build_filter(uri: &Uri) -> crate::Result<Option<Condition>> {
+    uri.query()
+        .and_then(|query| {
+            url::form_urlencoded::parse(query.as_bytes())
+                .find(|(key, _)| key == FILTER_PARAM)
+                .map(|(_, source)| source.into_owned())
+        })
+        .filter(|source| !source.is_empty())
+        .map(|source| {
+            VrlConfig {
+                source,
+                runtime: Default::default(),
+            }
+            .build(&enrichment::TableRegistry::default())
+        })
+        .transpose()
+}
+
+fn error_response(status: StatusCode, body: String) -> ErrorResponse {
+    let mut response = ErrorResponse::new(Some(body));
+    *response.status_mut() = status;
+    response
+}
+
+/// The bounded queue of the messages for a client.
+struct ClientQueue {
+    state: Mutex<QueueState>,
+    notify: Notify,
+    capacity: usize,
+    when_full: ClientQueueFullMode,
+}
+
+#[derive(Default)]
+struct QueueState {
+    messages: VecDeque<Message>,
+    closed: bool,
+}
+
+impl ClientQueue {
+    fn new(capacity: usize, when_full: ClientQueueFullMode) -> Self {
+        Self {
+            state: Mutex::default(),
+            notify: Notify::new(),
+            capacity,
+            when_full,
+        }
+    }
+
+    /// Queues the message, returning the number of messages dropped for the client.
+    fn push(&self, message: Message) -> usize {
+        let mut state = self.state.lock().expect("queue mutex poisoned");
+        if state.closed {
+            return 1;
+        }
+
+        let mut dropped = 0;
+        if state.messages.len() >= self.capacity {
+            match self.when_full {
+                ClientQueueFullMode::DropNewest => return 1,
+                ClientQueueFullMode::DropOldest => {
+                    state.messages.pop_front();
+                    dropped = 1;
+                }
+                ClientQueueFullMode::Disconnect => {
+                    dropped = state.messages.len() + 1;
+                    state.messages.clear();
+                    state.closed = true;
+                }
+            }
+        }
+        if !state.closed {
+            state.messages.push_back(message);
+        }
+        drop(state);
+
+        self.notify.notify_one();
+        dropped
+    }
+
+    /// Closes the queue, once its remaining messages are sent.
+    fn close(&self) {
+        self.state.lock().expect("queue mutex poisoned").closed = true;
+        self.notify.notify_one();
+    }
+
+    /// Waits for the next message, or `None` once the queue is closed and empty.
+    async fn pop(&self) -> Option<Message> {
+        loop {
+            {
+                let mut state = self.state.lock().expect("queue mutex poisoned");
+                if let Some(message) = state.messages.pop_front() {
+                    return Some(message);
+                }
+                if state.closed {
+                    return None;
+                }
+            }
+            self.notify.notified().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codecs::TextSerializerConfig;
+    use futures::FutureExt;
+    use tokio::time;
+    use tokio_stream::wrappers::UnboundedReceiverStream;
+    use tokio_tungstenite::connect_async;
+
+    use super::*;
+    use crate::{
+        config::{SinkConfig, SinkContext},
+        event::EventArray,
+        sinks::websocket::config::WebSocketMode,
+        test_util::{next_addr, trace_init},
+    };
+
+    fn pop_now(queue: &ClientQueue) -> Option<Message> {
+        queue.pop().now_or_never().flatten()
+    }
+
+    #[test]
+    fn client_queue_when_full() {
+        let queue = ClientQueue::new(2, ClientQueueFullMode::DropNewest);
+        let pushed = ["a", "b", "c"].map(|text| queue.push(Message::text(text)));
+        assert_eq!(pushed, [0, 0, 1]);
+        assert_eq!(pop_now(&queue), Some(Message::text("a")));
+        assert_eq!(pop_now(&queue), Some(Message::text("b")));
+        assert_eq!(pop_now(&queue), None);
+
+        let queue = ClientQueue::new(2, ClientQueueFullMode::DropOldest);
+        let pushed = ["a", "b", "c"].map(|text| queue.push(Message::text(text)));
+        assert_eq!(pushed, [0, 0, 1]);
+        assert_eq!(pop_now(&queue), Some(Message::text("b")));
+        assert_eq!(pop_now(&queue), Some(Message::text("c")));
+
+        let queue = ClientQueue::new(2, ClientQueueFullMode::Disconnect);
+        let pushed = ["a", "b", "c", "d"].map(|text| queue.push(Message::text(text)));
+        assert_eq!(pushed, [0, 0, 3, 1]);
+        assert_eq!(queue.pop().now_or_never(), Some(None));
+    }
+
+    #[test]
+    fn listen_addresses() {
+        assert_eq!(
+            listen_address("ws://0.0.0.0:9000/events").unwrap(),
+            ("0.0.0.0:9000".parse().unwrap(), "/events".into())
+        );
+        assert_eq!(
+            listen_address("wss://[::1]").unwrap(),
+            ("[::1]:443".parse().unwrap(), "/".into())
+        );
+        assert!(listen_address("ws://localhost:9000").is_err());
+    }
+
+    #[tokio::test]
+    async fn sends_events_matching_client_filters() {
+        trace_init();
+
+        let addr = next_addr();
+        let config = WebSocketSinkConfig {
+            uri: format!("ws://{}/events", addr),
+            mode: WebSocketMode::Server,
+            server: Default::default(),
+            tls: None,
+            encoding: TextSerializerConfig::new().into(),
+            ping_interval: None,
+            ping_timeout: None,
+            acknowledgements: Default::default(),
+        };
+        let (sink, _healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel::<Event>();
+        tokio::spawn(sink.run(UnboundedReceiverStream::new(rx).map(EventArray::from)));
+
+        let (mut all, _) = connect_async(format!("ws://{}/events", addr))
+            .await
+            .unwrap();
+        let (mut filtered, _) = connect_async(format!(
+            "ws://{}/events?filter=.message%20%3D%3D%20%22b%22",
+            addr
+        ))
+        .await
+        .unwrap();
+        let rejected =
+            connect_async(format!("ws://{}/events?filter=.message%20%3D%3D", addr)).await;
+        assert!(
+            matches!(rejected, Err(WsError::Http(response)) if response.status() == StatusCode::BAD_REQUEST)
+        );
+        let not_found = connect_async(format!("ws://{}/other", addr)).await;
+        assert!(
+            matches!(not_found, Err(WsError::Http(response)) if response.status() == StatusCode::NOT_FOUND)
+        );
+
+        // Let the clients be registered before sending the events.
+        time::sleep(Duration::from_millis(100)).await;
+        for message in ["a", "b", "c"] {
+            tx.send(Event::from(message)).unwrap();
+        }
+
+        for message in ["a", "b", "c"] {
+            assert_eq!(all.next().await.unwrap().unwrap(), Message::text(message));
+        }
+        assert_eq!(filtered.next().await.unwrap().unwrap(), Message::text("b"));
+    }
+}
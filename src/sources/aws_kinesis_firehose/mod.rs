@@ -1,6 +1,6 @@
 use std::{fmt, net::SocketAddr};
 
-use codecs::decoding::{DeserializerConfig, FramingConfig};
+use codecs::decoding::{DecodingErrorMode, DeserializerConfig, FramingConfig};
 use futures::FutureExt;
 use tracing::Span;
 use vector_config::configurable_component;
@@ -56,6 +56,10 @@ pub struct AwsKinesisFirehoseConfig {
     #[serde(default = "default_decoding")]
     decoding: DeserializerConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    on_error: DecodingErrorMode,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
@@ -103,7 +107,8 @@ impl SourceConfig for AwsKinesisFirehoseConfig {
             self.decoding.clone(),
             LogNamespace::Legacy,
         )
-        .build();
+        .with_error_mode(self.on_error)
+        .build()?;
         let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
 
         let svc = filters::firehose(
@@ -131,7 +136,9 @@ impl SourceConfig for AwsKinesisFirehoseConfig {
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
-        vec![Output::default(self.decoding.output_type())]
+        vec![Output::default(
+            self.on_error.output_type(self.decoding.output_type()),
+        )]
     }
 
     fn source_type(&self) -> &'static str {
@@ -160,6 +167,7 @@ impl GenerateConfig for AwsKinesisFirehoseConfig {
             record_compression: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_error: Default::default(),
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -243,6 +251,7 @@ mod tests {
                 record_compression,
                 framing: default_framing_message_based(),
                 decoding: default_decoding(),
+                on_error: Default::default(),
                 acknowledgements: true.into(),
             }
             .build(cx)
@@ -0,0 +1,282 @@
+use std::time::Duration;
+
+use codecs::decoding::{DecodingErrorMode, DeserializerConfig, FramingConfig};
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use super::{
+    consumer::{ReadMode, ShardReader},
+    lease::LeaseTable,
+    worker::Worker,
+};
+use crate::{
+    aws::{auth::AwsAuthentication, create_client, region::RegionOrEndpoint},
+    codecs::DecodingConfig,
+    common::{dynamodb::DynamoDbClientBuilder, kinesis::KinesisClientBuilder},
+    config::{AcknowledgementsConfig, Output, SourceConfig, SourceContext},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    tls::TlsConfig,
+};
+
+/// How the records of the shards are read.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Mode {
+    /// The records are polled with `GetRecords` requests, sharing the read throughput of the
+    /// shards with the other consumers of the stream.
+    #[derivative(Default)]
+    Polling,
+
+    /// The records are pushed with `SubscribeToShard` to a consumer registered for the
+    /// application, which gets a dedicated read throughput for each shard.
+    EnhancedFanOut,
+}
+
+/// Where to start reading the shards that don't have a checkpoint yet.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum InitialPosition {
+    /// Only the records added after the shard was first leased are read.
+    #[derivative(Default)]
+    Latest,
+
+    /// All the records retained by the shard are read.
+    TrimHorizon,
+}
+
+impl InitialPosition {
+    pub(super) const fn checkpoint(self) -> &'static str {
+        match self {
+            Self::Latest => super::lease::LATEST,
+            Self::TrimHorizon => super::lease::TRIM_HORIZON,
+        }
+    }
+}
+
+/// Configuration for the `aws_kinesis_streams` source.
+#[configurable_component(source)]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct AwsKinesisStreamsConfig {
+    #[serde(flatten)]
+    pub region: RegionOrEndpoint,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub auth: AwsAuthentication,
+
+    /// The name of the stream to read the records of.
+    pub stream_name: String,
+
+    /// The name of the application the records are read for.
+    ///
+    /// The Vector instances configured with the same application share the shards of the stream
+    /// between them, and the stream consumer used in the `enhanced_fan_out` mode is registered
+    /// under this name.
+    pub application_name: String,
+
+    /// The name of the DynamoDB table storing the leases and checkpoints of the shards.
+    ///
+    /// The table is created if it doesn't exist. Defaults to the name of the application.
+    #[serde(default)]
+    pub lease_table: Option<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub mode: Mode,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub initial_position: InitialPosition,
+
+    /// How long to wait between `GetRecords` requests to a shard when the last one returned no
+    /// records, in milliseconds.
+    ///
+    /// Only used in the `polling` mode.
+    #[serde(default = "default_poll_interval_ms")]
+    #[derivative(Default(value = "default_poll_interval_ms()"))]
+    pub poll_interval_ms: u64,
+
+    /// The maximum number of records returned by each `GetRecords` request.
+    ///
+    /// Only used in the `polling` mode.
+    #[serde(default = "default_max_records")]
+    #[derivative(Default(value = "default_max_records()"))]
+    pub max_records: u32,
+
+    /// How long a lease can go without being renewed by its owner before another instance takes
+    /// it, in seconds.
+    #[serde(default = "default_lease_duration_secs")]
+    #[derivative(Default(value = "default_lease_duration_secs()"))]
+    pub lease_duration_secs: u64,
+
+    /// How often the shards of the stream are listed to create the leases of the new ones, in
+    /// seconds.
+    #[serde(default = "default_shard_sync_interval_secs")]
+    #[derivative(Default(value = "default_shard_sync_interval_secs()"))]
+    pub shard_sync_interval_secs: u64,
+
+    /// The identifier of this instance in the lease table.
+    ///
+    /// Must be unique among the instances of the application. Defaults to the hostname followed
+    /// by a random identifier.
+    #[serde(default)]
+    pub worker_id: Option<String>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    pub framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    pub decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub on_error: DecodingErrorMode,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    pub acknowledgements: AcknowledgementsConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+}
+
+const fn default_poll_interval_ms() -> u64 {
+    1000
+}
+
+const fn default_max_records() -> u32 {
+    10_000
+}
+
+const fn default_lease_duration_secs() -> u64 {
+    10
+}
+
+const fn default_shard_sync_interval_secs() -> u64 {
+    60
+}
+
+impl_generate_config_from_default!(AwsKinesisStreamsConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "aws_kinesis_streams")]
+impl SourceConfig for AwsKinesisStreamsConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<crate::sources::Source> {
+        let kinesis_client = create_client::<KinesisClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region.endpoint()?,
+            &cx.proxy,
+            &self.tls,
+            false,
+        )
+        .await?;
+        let dynamodb_client = create_client::<DynamoDbClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region.endpoint()?,
+            &cx.proxy,
+            &self.tls,
+            false,
+        )
+        .await?;
+
+        let decoder = DecodingConfig::new(
+            self.framing.clone(),
+            self.decoding.clone(),
+            LogNamespace::Legacy,
+        )
+        .with_error_mode(self.on_error)
+        .build()?;
+        let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
+
+        let worker_id = match &self.worker_id {
+            Some(worker_id) => worker_id.clone(),
+            None => format!("{}-{}", crate::get_hostname()?, uuid::Uuid::new_v4()),
+        };
+        let leases = LeaseTable::new(
+            dynamodb_client,
+            self.lease_table
+                .clone()
+                .unwrap_or_else(|| self.application_name.clone()),
+            worker_id,
+        );
+        let mode = match self.mode {
+            Mode::Polling => ReadMode::Polling {
+                interval: Duration::from_millis(self.poll_interval_ms),
+                max_records: self.max_records.min(i32::MAX as u32) as i32,
+            },
+            Mode::EnhancedFanOut => ReadMode::EnhancedFanOut {
+                consumer_name: self.application_name.clone(),
+            },
+        };
+        let reader = ShardReader::new(
+            kinesis_client,
+            self.stream_name.clone(),
+            mode,
+            decoder,
+            acknowledgements,
+        );
+
+        let worker = Worker::new(
+            reader,
+            leases,
+            self.initial_position,
+            Duration::from_secs(self.lease_duration_secs),
+            Duration::from_secs(self.shard_sync_interval_secs),
+        );
+        Ok(Box::pin(worker.run(cx.out, cx.shutdown)))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(
+            self.on_error.output_type(self.decoding.output_type()),
+        )]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "aws_kinesis_streams"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AwsKinesisStreamsConfig>();
+    }
+
+    #[test]
+    fn parses_modes() {
+        let config: AwsKinesisStreamsConfig = toml::from_str(
+            r#"
+            region = "us-east-1"
+            stream_name = "logs"
+            application_name = "vector"
+            mode = "enhanced_fan_out"
+            initial_position = "trim_horizon"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.mode, Mode::EnhancedFanOut);
+        assert_eq!(config.initial_position, InitialPosition::TrimHorizon);
+        assert_eq!(config.lease_duration_secs, 10);
+    }
+}
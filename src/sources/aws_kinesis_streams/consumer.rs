@@ -0,0 +1,575 @@
+use std::time::Duration;
+
+use aws_sdk_kinesis::{
+    error::{
+        DescribeStreamConsumerError, DescribeStreamSummaryError, GetRecordsError,
+        GetShardIteratorError, ListShardsError, RegisterStreamConsumerError, SubscribeToShardError,
+    },
+    model::{
+        ConsumerStatus, Record, ShardIteratorType, StartingPosition, SubscribeToShardEventStream,
+    },
+    types::SdkError,
+    Client as KinesisClient,
+};
+use chrono::{TimeZone, Utc};
+use snafu::{ResultExt, Snafu};
+
+use super::lease::{LeaseError, LeaseTable, Shard, LATEST, SHARD_END, TRIM_HORIZON};
+use crate::{
+    codecs::Decoder,
+    event::{BatchNotifier, BatchStatus},
+    internal_events::{AwsKinesisStreamsShardReadError, EndpointBytesReceived, StreamClosedError},
+    sources::util,
+    SourceSender,
+};
+
+#[derive(Debug, Snafu)]
+pub(super) enum ReadError {
+    #[snafu(display("Failed to list the shards: {}", source))]
+    ListShards { source: SdkError<ListShardsError> },
+    #[snafu(display("Failed to describe the stream: {}", source))]
+    DescribeStream {
+        source: SdkError<DescribeStreamSummaryError>,
+    },
+    #[snafu(display("Failed to register the stream consumer: {}", source))]
+    RegisterConsumer {
+        source: SdkError<RegisterStreamConsumerError>,
+    },
+    #[snafu(display("Failed to describe the stream consumer: {}", source))]
+    DescribeConsumer {
+        source: SdkError<DescribeStreamConsumerError>,
+    },
+    #[snafu(display("The stream consumer didn't become active in time"))]
+    ConsumerNotActive,
+    #[snafu(display("The response of {} is missing its {}", operation, field))]
+    MissingField {
+        operation: &'static str,
+        field: &'static str,
+    },
+    #[snafu(display("Failed to get a shard iterator: {}", source))]
+    GetShardIterator {
+        source: SdkError<GetShardIteratorError>,
+    },
+    #[snafu(display("Failed to get the records of the shard: {}", source))]
+    GetRecords { source: SdkError<GetRecordsError> },
+    #[snafu(display("Failed to subscribe to the shard: {}", source))]
+    SubscribeToShard {
+        source: SdkError<SubscribeToShardError>,
+    },
+    #[snafu(display("Failed to receive the records of the subscription: {}", source))]
+    ReceiveEvent { source: crate::Error },
+    #[snafu(display("{}", source))]
+    Checkpoint { source: LeaseError },
+}
+
+/// How the records of the shards are read.
+#[derive(Clone, Debug)]
+pub(super) enum ReadMode {
+    Polling {
+        interval: Duration,
+        max_records: i32,
+    },
+    EnhancedFanOut {
+        consumer_name: String,
+    },
+}
+
+/// Why the reading of a shard stopped.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum ShardOutcome {
+    /// All the records of the closed shard have been read.
+    Ended,
+    /// The lease of the shard was taken by another worker.
+    LeaseLost,
+    /// The events can't be sent anymore, as Vector is shutting down.
+    Closed,
+}
+
+/// What to do after sending the events of a batch of records.
+enum Progress {
+    Continue,
+    /// Read the records again from the last checkpoint, as their events failed to be delivered.
+    Rewind,
+    Stop(ShardOutcome),
+}
+
+/// Where to read the records of a shard from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Position {
+    Latest,
+    TrimHorizon,
+    After(String),
+}
+
+impl Position {
+    fn from_checkpoint(checkpoint: &str) -> Self {
+        match checkpoint {
+            LATEST => Self::Latest,
+            TRIM_HORIZON => Self::TrimHorizon,
+            sequence_number => Self::After(sequence_number.to_owned()),
+        }
+    }
+
+    fn iterator_type(&self) -> ShardIteratorType {
+        match self {
+            Self::Latest => ShardIteratorType::Latest,
+            Self::TrimHorizon => ShardIteratorType::TrimHorizon,
+            Self::After(_) => ShardIteratorType::AfterSequenceNumber,
+        }
+    }
+
+    fn sequence_number(&self) -> Option<String> {
+        match self {
+            Self::After(sequence_number) => Some(sequence_number.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// Reads the records of the shards of the stream, and sends their events.
+#[derive(Clone)]
+pub(super) struct ShardReader {
+    client: KinesisClient,
+    stream_name: String,
+    mode: ReadMode,
+    decoder: Decoder,
+    acknowledgements: bool,
+    consumer_arn: Option<String>,
+}
+
+impl ShardReader {
+    pub(super) const fn new(
+        client: KinesisClient,
+        stream_name: String,
+        mode: ReadMode,
+        decoder: Decoder,
+        acknowledgements: bool,
+    ) -> Self {
+        Self {
+            client,
+            stream_name,
+            mode,
+            decoder,
+            acknowledgements,
+            consumer_arn: None,
+        }
+    }
+
+    /// Registers the stream consumer of the application in the `enhanced_fan_out` mode, and waits
+    /// for it to become active.
+    pub(super) async fn prepare(&mut self) -> Result<(), ReadError> {
+        let consumer_name = match &self.mode {
+            ReadMode::Polling { .. } => return Ok(()),
+            ReadMode::EnhancedFanOut { consumer_name } => consumer_name.clone(),
+        };
+
+        let stream_arn = self
+            .client
+            .describe_stream_summary()
+            .stream_name(&self.stream_name)
+            .send()
+            .await
+            .context(DescribeStreamSnafu)?
+            .stream_description_summary
+            .and_then(|summary| summary.stream_arn)
+            .ok_or(ReadError::MissingField {
+                operation: "DescribeStreamSummary",
+                field: "StreamARN",
+            })?;
+
+        let result = self
+            .client
+            .register_stream_consumer()
+            .stream_arn(&stream_arn)
+            .consumer_name(&consumer_name)
+            .send()
+            .await;
+        match result {
+            Ok(_) => info!(message = "Registered the stream consumer.", %consumer_name),
+            Err(SdkError::ServiceError { err, .. }) if err.is_resource_in_use_exception() => {}
+            Err(source) => return Err(ReadError::RegisterConsumer { source }),
+        }
+
+        for _ in 0..60 {
+            let description = self
+                .client
+                .describe_stream_consumer()
+                .stream_arn(&stream_arn)
+                .consumer_name(&consumer_name)
+                .send()
+                .await
+                .context(DescribeConsumerSnafu)?
+                .consumer_description;
+            if let Some(description) = description {
+                if description.consumer_status == Some(ConsumerStatus::Active) {
+                    self.consumer_arn =
+                        Some(description.consumer_arn.ok_or(ReadError::MissingField {
+                            operation: "DescribeStreamConsumer",
+                            field: "ConsumerARN",
+                        })?);
+                    return Ok(());
+                }
+            }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+        Err(ReadError::ConsumerNotActive)
+    }
+
+    pub(super) async fn list_shards(&self) -> Result<Vec<Shard>, ReadError> {
+        let mut shards = Vec::new();
+        let mut next_token = None;
+        loop {
+            // The name of the stream can't be set along with the token of the next page.
+            let request = match next_token {
+                Some(token) => self.client.list_shards().next_token(token),
+                None => self.client.list_shards().stream_name(&self.stream_name),
+            };
+            let output = request.send().await.context(ListShardsSnafu)?;
+
+            shards.extend(
+                output
+                    .shards
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|shard| {
+                        Some(Shard {
+                            id: shard.shard_id?,
+                            parents: shard
+                                .parent_shard_id
+                                .into_iter()
+                                .chain(shard.adjacent_parent_shard_id)
+                                .collect(),
+                        })
+                    }),
+            );
+
+            match output.next_token {
+                Some(token) => next_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(shards)
+    }
+
+    /// Reads the shard from its checkpoint until it ends or the lease is lost, checkpointing
+    /// each batch of records once its events have been delivered.
+    pub(super) async fn read_shard(
+        &self,
+        shard_id: &str,
+        checkpoint: &str,
+        leases: &LeaseTable,
+        out: &mut SourceSender,
+    ) -> ShardOutcome {
+        let mut position = Position::from_checkpoint(checkpoint);
+        loop {
+            let result = match &self.mode {
+                ReadMode::Polling {
+                    interval,
+                    max_records,
+                } => {
+                    self.poll(
+                        shard_id,
+                        &mut position,
+                        *interval,
+                        *max_records,
+                        leases,
+                        out,
+                    )
+                    .await
+                }
+                ReadMode::EnhancedFanOut { .. } => {
+                    self.subscribe(shard_id, &mut position, leases, out).await
+                }
+            };
+            match result {
+                Ok(outcome) => return outcome,
+                Err(error) => {
+                    emit!(AwsKinesisStreamsShardReadError {
+                        shard_id,
+                        error: &error,
+                    });
+                    // Prevent rapid errors from flooding the logs.
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+            }
+        }
+    }
+
+    async fn poll(
+        &self,
+        shard_id: &str,
+        position: &mut Position,
+        interval: Duration,
+        max_records: i32,
+        leases: &LeaseTable,
+        out: &mut SourceSender,
+    ) -> Result<ShardOutcome, ReadError> {
+        let mut iterator = self.shard_iterator(shard_id, position).await?;
+        loop {
+            let result = self
+                .client
+                .get_records()
+                .shard_iterator(iterator)
+                .limit(max_records)
+                .send()
+                .await;
+            let output = match result {
+                Ok(output) => output,
+                Err(SdkError::ServiceError { err, .. }) if err.is_expired_iterator_exception() => {
+                    iterator = self.shard_iterator(shard_id, position).await?;
+                    continue;
+                }
+                Err(source) => return Err(ReadError::GetRecords { source }),
+            };
+
+            let records = output.records.unwrap_or_default();
+            let empty = records.is_empty();
+            match self
+                .process(shard_id, records, position, leases, out)
+                .await?
+            {
+                Progress::Continue => {}
+                Progress::Rewind => {
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    iterator = self.shard_iterator(shard_id, position).await?;
+                    continue;
+                }
+                Progress::Stop(outcome) => return Ok(outcome),
+            }
+
+            match output.next_shard_iterator {
+                Some(next) => iterator = next,
+                // The shard has been closed by a split or merge, and all its records were read.
+                None => return self.end_shard(shard_id, leases).await,
+            }
+            if empty {
+                tokio::time::sleep(interval).await;
+            }
+        }
+    }
+
+    async fn shard_iterator(
+        &self,
+        shard_id: &str,
+        position: &Position,
+    ) -> Result<String, ReadError> {
+        self.client
+            .get_shard_iterator()
+            .stream_name(&self.stream_name)
+            .shard_id(shard_id)
+            .shard_iterator_type(position.iterator_type())
+            .set_starting_sequence_number(position.sequence_number())
+            .send()
+            .await
+            .context(GetShardIteratorSnafu)?
+            .shard_iterator
+            .ok_or(ReadError::MissingField {
+                operation: "GetShardIterator",
+                field: "ShardIterator",
+            })
+    }
+
+    async fn subscribe(
+        &self,
+        shard_id: &str,
+        position: &mut Position,
+        leases: &LeaseTable,
+        out: &mut SourceSender,
+    ) -> Result<ShardOutcome, ReadError> {
+        let consumer_arn = self
+            .consumer_arn
+            .as_ref()
+            .expect("the stream consumer is registered before reading the shards");
+        loop {
+            let mut output = self
+                .client
+                .subscribe_to_shard()
+                .consumer_arn(consumer_arn)
+                .shard_id(shard_id)
+                .starting_position(
+                    StartingPosition::builder()
+                        .r#type(position.iterator_type())
+                        .set_sequence_number(position.sequence_number())
+                        .build(),
+                )
+                .send()
+                .await
+                .context(SubscribeToShardSnafu)?;
+
+            // Subscriptions last for 5 minutes, after which the shard is subscribed to again from
+            // the last position.
+            loop {
+                let event =
+                    output
+                        .event_stream
+                        .recv()
+                        .await
+                        .map_err(|error| ReadError::ReceiveEvent {
+                            source: Box::new(error),
+                        })?;
+                let event = match event {
+                    Some(SubscribeToShardEventStream::SubscribeToShardEvent(event)) => event,
+                    Some(_) => continue,
+                    None => break,
+                };
+
+                let records = event.records.unwrap_or_default();
+                let empty = records.is_empty();
+                match self
+                    .process(shard_id, records, position, leases, out)
+                    .await?
+                {
+                    Progress::Continue => {}
+                    Progress::Rewind => {
+                        tokio::time::sleep(Duration::from_secs(1)).await;
+                        break;
+                    }
+                    Progress::Stop(outcome) => return Ok(outcome),
+                }
+
+                match event.continuation_sequence_number {
+                    Some(sequence_number) if empty => *position = Position::After(sequence_number),
+                    Some(_) => {}
+                    // The shard has been closed by a split or merge, and all its records were
+                    // read.
+                    None => return self.end_shard(shard_id, leases).await,
+                }
+            }
+        }
+    }
+
+    async fn end_shard(
+        &self,
+        shard_id: &str,
+        leases: &LeaseTable,
+    ) -> Result<ShardOutcome, ReadError> {
+        let owned = leases
+            .checkpoint(shard_id, SHARD_END)
+            .await
+            .context(CheckpointSnafu)?;
+        Ok(if owned {
+            ShardOutcome::Ended
+        } else {
+            ShardOutcome::LeaseLost
+        })
+    }
+
+    /// Sends the events of the records, then checkpoints the last one once they are delivered.
+    async fn process(
+        &self,
+        shard_id: &str,
+        records: Vec<Record>,
+        position: &mut Position,
+        leases: &LeaseTable,
+        out: &mut SourceSender,
+    ) -> Result<Progress, ReadError> {
+        let last_sequence_number = match records
+            .last()
+            .and_then(|record| record.sequence_number.clone())
+        {
+            Some(sequence_number) => sequence_number,
+            None => return Ok(Progress::Continue),
+        };
+
+        let byte_size = records
+            .iter()
+            .map(|record| record.data.as_ref().map_or(0, |data| data.as_ref().len()))
+            .sum();
+        emit!(EndpointBytesReceived {
+            byte_size,
+            protocol: "https",
+            endpoint: &self.stream_name,
+        });
+
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
+        let mut events = Vec::with_capacity(records.len());
+        for record in records {
+            let timestamp = record
+                .approximate_arrival_timestamp
+                .map(|ts| Utc.timestamp(ts.secs(), ts.subsec_nanos()));
+            let data = record
+                .data
+                .map(|data| data.into_inner())
+                .unwrap_or_default();
+            let partition_key = record.partition_key;
+            let sequence_number = record.sequence_number;
+            let decoded = util::decode_message(
+                self.decoder.clone(),
+                "aws_kinesis_streams",
+                &data,
+                timestamp,
+                &batch,
+            )
+            .map(|mut event| {
+                if let Some(log) = event.maybe_as_log_mut() {
+                    log.insert("stream", self.stream_name.clone());
+                    log.insert("shard_id", shard_id.to_owned());
+                    if let Some(partition_key) = &partition_key {
+                        log.insert("partition_key", partition_key.clone());
+                    }
+                    if let Some(sequence_number) = &sequence_number {
+                        log.insert("sequence_number", sequence_number.clone());
+                    }
+                }
+                event
+            });
+            events.extend(decoded);
+        }
+        drop(batch);
+
+        let count = events.len();
+        if let Err(error) = out.send_batch(events).await {
+            emit!(StreamClosedError { error, count });
+            return Ok(Progress::Stop(ShardOutcome::Closed));
+        }
+
+        if let Some(receiver) = receiver {
+            match receiver.await {
+                BatchStatus::Delivered => {}
+                BatchStatus::Errored => return Ok(Progress::Rewind),
+                BatchStatus::Rejected => {
+                    error!(
+                        message = "Sink reported events were rejected.",
+                        internal_log_rate_secs = 5,
+                    );
+                    // Failed events cannot be retried, so continue to checkpoint the records.
+                }
+            }
+        }
+
+        let owned = leases
+            .checkpoint(shard_id, &last_sequence_number)
+            .await
+            .context(CheckpointSnafu)?;
+        if !owned {
+            return Ok(Progress::Stop(ShardOutcome::LeaseLost));
+        }
+        *position = Position::After(last_sequence_number);
+        Ok(Progress::Continue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions_from_checkpoints() {
+        assert_eq!(Position::from_checkpoint(LATEST), Position::Latest);
+        assert_eq!(
+            Position::from_checkpoint(TRIM_HORIZON),
+            Position::TrimHorizon
+        );
+
+        let position =
+            Position::from_checkpoint("49590338271490256608559692538361571095921575989136588898");
+        assert_eq!(
+            position.iterator_type(),
+            ShardIteratorType::AfterSequenceNumber
+        );
+        assert_eq!(
+            position.sequence_number().as_deref(),
+            Some("49590338271490256608559692538361571095921575989136588898")
+        );
+    }
+}
@@ -0,0 +1,656 @@
+//! The leases of the shards, stored in a DynamoDB table shared by the instances of the
+//! application.
+//!
+//! The table follows the layout of the Kinesis Client Library: each item is keyed by the ID of
+//! its shard, and holds the worker owning the lease, a counter incremented by the owner whenever
+//! it renews the lease, the checkpoint of the shard and the IDs of its parent shards. A lease
+//! whose counter hasn't changed for the lease duration is considered expired, and can be taken by
+//! any instance.
+
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant},
+};
+
+use aws_sdk_dynamodb::{
+    error::{CreateTableError, DescribeTableError, PutItemError, ScanError, UpdateItemError},
+    model::{
+        AttributeDefinition, AttributeValue, BillingMode, KeySchemaElement, KeyType,
+        ScalarAttributeType, TableStatus,
+    },
+    types::SdkError,
+    Client as DynamoDbClient,
+};
+use snafu::{ResultExt, Snafu};
+
+/// The checkpoint of the shards read from their latest record.
+pub(super) const LATEST: &str = "LATEST";
+/// The checkpoint of the shards read from their oldest record.
+pub(super) const TRIM_HORIZON: &str = "TRIM_HORIZON";
+/// The checkpoint of the shards whose records have all been read.
+pub(super) const SHARD_END: &str = "SHARD_END";
+
+const LEASE_KEY: &str = "leaseKey";
+const LEASE_OWNER: &str = "leaseOwner";
+const LEASE_COUNTER: &str = "leaseCounter";
+const CHECKPOINT: &str = "checkpoint";
+const PARENT_SHARD_IDS: &str = "parentShardIds";
+
+#[derive(Debug, Snafu)]
+pub(super) enum LeaseError {
+    #[snafu(display("Failed to describe the lease table: {}", source))]
+    DescribeTable {
+        source: SdkError<DescribeTableError>,
+    },
+    #[snafu(display("Failed to create the lease table: {}", source))]
+    CreateTable { source: SdkError<CreateTableError> },
+    #[snafu(display("The lease table didn't become active in time"))]
+    TableNotActive,
+    #[snafu(display("Failed to list the leases: {}", source))]
+    ListLeases { source: SdkError<ScanError> },
+    #[snafu(display("Failed to create the lease of shard {}: {}", shard_id, source))]
+    CreateLease {
+        shard_id: String,
+        source: SdkError<PutItemError>,
+    },
+    #[snafu(display("Failed to update the lease of shard {}: {}", shard_id, source))]
+    UpdateLease {
+        shard_id: String,
+        source: SdkError<UpdateItemError>,
+    },
+    #[snafu(display("Invalid lease in the lease table: missing attribute {}", attribute))]
+    InvalidLease { attribute: &'static str },
+}
+
+/// The lease of a shard.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(super) struct Lease {
+    pub(super) shard_id: String,
+    pub(super) owner: Option<String>,
+    pub(super) counter: u64,
+    pub(super) checkpoint: String,
+    pub(super) parents: Vec<String>,
+}
+
+impl Lease {
+    pub(super) fn new(shard_id: String, checkpoint: &str, parents: Vec<String>) -> Self {
+        Self {
+            shard_id,
+            owner: None,
+            counter: 0,
+            checkpoint: checkpoint.to_owned(),
+            parents,
+        }
+    }
+
+    pub(super) fn is_ended(&self) -> bool {
+        self.checkpoint == SHARD_END
+    }
+
+    fn from_item(item: &HashMap<String, AttributeValue>) -> Result<Self, LeaseError> {
+        let string = |attribute| {
+            item.get(attribute)
+                .and_then(|value| value.as_s().ok())
+                .cloned()
+        };
+        Ok(Self {
+            shard_id: string(LEASE_KEY).ok_or(LeaseError::InvalidLease {
+                attribute: LEASE_KEY,
+            })?,
+            owner: string(LEASE_OWNER),
+            counter: item
+                .get(LEASE_COUNTER)
+                .and_then(|value| value.as_n().ok())
+                .and_then(|counter| counter.parse().ok())
+                .ok_or(LeaseError::InvalidLease {
+                    attribute: LEASE_COUNTER,
+                })?,
+            checkpoint: string(CHECKPOINT).ok_or(LeaseError::InvalidLease {
+                attribute: CHECKPOINT,
+            })?,
+            parents: item
+                .get(PARENT_SHARD_IDS)
+                .and_then(|value| value.as_ss().ok())
+                .cloned()
+                .unwrap_or_default(),
+        })
+    }
+
+    fn to_item(&self) -> HashMap<String, AttributeValue> {
+        let mut item = HashMap::from([
+            (LEASE_KEY.into(), AttributeValue::S(self.shard_id.clone())),
+            (
+                LEASE_COUNTER.into(),
+                AttributeValue::N(self.counter.to_string()),
+            ),
+            (
+                CHECKPOINT.into(),
+                AttributeValue::S(self.checkpoint.clone()),
+            ),
+        ]);
+        if let Some(owner) = &self.owner {
+            item.insert(LEASE_OWNER.into(), AttributeValue::S(owner.clone()));
+        }
+        // DynamoDB doesn't allow empty sets.
+        if !self.parents.is_empty() {
+            item.insert(
+                PARENT_SHARD_IDS.into(),
+                AttributeValue::Ss(self.parents.clone()),
+            );
+        }
+        item
+    }
+}
+
+/// A shard of the stream, as listed by `ListShards`.
+#[derive(Clone, Debug)]
+pub(super) struct Shard {
+    pub(super) id: String,
+    pub(super) parents: Vec<String>,
+}
+
+/// Returns the leases of the shards that don't have one yet.
+///
+/// The shards created by the split or merge of a leased shard are read from their oldest record,
+/// so that none of the records written after the resharding are missed, while the others are read
+/// from the initial position.
+pub(super) fn new_leases(shards: &[Shard], leases: &[Lease], initial_position: &str) -> Vec<Lease> {
+    let leased = leases
+        .iter()
+        .map(|lease| lease.shard_id.as_str())
+        .collect::<HashSet<_>>();
+    shards
+        .iter()
+        .filter(|shard| !leased.contains(shard.id.as_str()))
+        .map(|shard| {
+            let checkpoint = if shard
+                .parents
+                .iter()
+                .any(|parent| leased.contains(parent.as_str()))
+            {
+                TRIM_HORIZON
+            } else {
+                initial_position
+            };
+            Lease::new(shard.id.clone(), checkpoint, shard.parents.clone())
+        })
+        .collect()
+}
+
+/// The lease table of the application, as seen by one of its workers.
+#[derive(Clone)]
+pub(super) struct LeaseTable {
+    client: DynamoDbClient,
+    table_name: String,
+    worker_id: String,
+}
+
+impl LeaseTable {
+    pub(super) const fn new(client: DynamoDbClient, table_name: String, worker_id: String) -> Self {
+        Self {
+            client,
+            table_name,
+            worker_id,
+        }
+    }
+
+    pub(super) fn worker_id(&self) -> &str {
+        &self.worker_id
+    }
+
+    /// Creates the table if it doesn't exist, and waits for it to become active.
+    pub(super) async fn ensure_exists(&self) -> Result<(), LeaseError> {
+        let result = self
+            .client
+            .create_table()
+            .table_name(&self.table_name)
+            .key_schema(
+                KeySchemaElement::builder()
+                    .attribute_name(LEASE_KEY)
+                    .key_type(KeyType::Hash)
+                    .build(),
+            )
+            .attribute_definitions(
+                AttributeDefinition::builder()
+                    .attribute_name(LEASE_KEY)
+                    .attribute_type(ScalarAttributeType::S)
+                    .build(),
+            )
+            .billing_mode(BillingMode::PayPerRequest)
+            .send()
+            .await;
+        match result {
+            Ok(_) => info!(message = "Created the lease table.", table = %self.table_name),
+            Err(SdkError::ServiceError { err, .. }) if err.is_resource_in_use_exception() => {}
+            Err(source) => return Err(LeaseError::CreateTable { source }),
+        }
+
+        for _ in 0..60 {
+            let status = self
+                .client
+                .describe_table()
+                .table_name(&self.table_name)
+                .send()
+                .await
+                .context(DescribeTableSnafu)?
+                .table
+                .and_then(|table| table.table_status);
+            if status == Some(TableStatus::Active) {
+                return Ok(());
+            }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+        Err(LeaseError::TableNotActive)
+    }
+
+    pub(super) async fn list(&self) -> Result<Vec<Lease>, LeaseError> {
+        let mut leases = Vec::new();
+        let mut start_key = None;
+        loop {
+            let output = self
+                .client
+                .scan()
+                .table_name(&self.table_name)
+                .consistent_read(true)
+                .set_exclusive_start_key(start_key)
+                .send()
+                .await
+                .context(ListLeasesSnafu)?;
+
+            for item in output.items.unwrap_or_default() {
+                leases.push(Lease::from_item(&item)?);
+            }
+
+            match output.last_evaluated_key {
+                Some(key) if !key.is_empty() => start_key = Some(key),
+                _ => break,
+            }
+        }
+        Ok(leases)
+    }
+
+    /// Creates the lease, unless another worker already did.
+    pub(super) async fn create(&self, lease: &Lease) -> Result<(), LeaseError> {
+        let result = self
+            .client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(lease.to_item()))
+            .condition_expression("attribute_not_exists(leaseKey)")
+            .send()
+            .await;
+        match result {
+            Ok(_) => Ok(()),
+            Err(SdkError::ServiceError { err, .. })
+                if err.is_conditional_check_failed_exception() =>
+            {
+                Ok(())
+            }
+            Err(source) => Err(LeaseError::CreateLease {
+                shard_id: lease.shard_id.clone(),
+                source,
+            }),
+        }
+    }
+
+    /// Takes the lease for this worker, returning its new counter, or `None` when it has been
+    /// renewed or taken by another worker since it was listed.
+    pub(super) async fn take(&self, lease: &Lease) -> Result<Option<u64>, LeaseError> {
+        let counter = lease.counter + 1;
+        let updated = self
+            .update(
+                &lease.shard_id,
+                "SET leaseOwner = :owner, leaseCounter = :counter",
+                "leaseCounter = :expected",
+                HashMap::from([
+                    (":owner".into(), AttributeValue::S(self.worker_id.clone())),
+                    (":counter".into(), AttributeValue::N(counter.to_string())),
+                    (
+                        ":expected".into(),
+                        AttributeValue::N(lease.counter.to_string()),
+                    ),
+                ]),
+            )
+            .await?;
+        Ok(updated.then(|| counter))
+    }
+
+    /// Renews the lease owned by this worker, returning its new counter, or `None` when it has
+    /// been taken by another worker.
+    pub(super) async fn renew(
+        &self,
+        shard_id: &str,
+        counter: u64,
+    ) -> Result<Option<u64>, LeaseError> {
+        let updated = self
+            .update(
+                shard_id,
+                "SET leaseCounter = :counter",
+                "leaseOwner = :owner AND leaseCounter = :expected",
+                HashMap::from([
+                    (":owner".into(), AttributeValue::S(self.worker_id.clone())),
+                    (
+                        ":counter".into(),
+                        AttributeValue::N((counter + 1).to_string()),
+                    ),
+                    (":expected".into(), AttributeValue::N(counter.to_string())),
+                ]),
+            )
+            .await?;
+        Ok(updated.then(|| counter + 1))
+    }
+
+    /// Gives up the lease owned by this worker, so that another one can take it right away.
+    pub(super) async fn release(&self, shard_id: &str, counter: u64) -> Result<(), LeaseError> {
+        self.update(
+            shard_id,
+            "REMOVE leaseOwner SET leaseCounter = :counter",
+            "leaseOwner = :owner",
+            HashMap::from([
+                (":owner".into(), AttributeValue::S(self.worker_id.clone())),
+                (
+                    ":counter".into(),
+                    AttributeValue::N((counter + 1).to_string()),
+                ),
+            ]),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Sets the checkpoint of the shard whose lease is owned by this worker, returning whether it
+    /// still owns the lease.
+    pub(super) async fn checkpoint(
+        &self,
+        shard_id: &str,
+        checkpoint: &str,
+    ) -> Result<bool, LeaseError> {
+        self.update(
+            shard_id,
+            "SET checkpoint = :checkpoint",
+            "leaseOwner = :owner",
+            HashMap::from([
+                (":owner".into(), AttributeValue::S(self.worker_id.clone())),
+                (
+                    ":checkpoint".into(),
+                    AttributeValue::S(checkpoint.to_owned()),
+                ),
+            ]),
+        )
+        .await
+    }
+
+    /// Updates the lease if the condition holds, returning whether it did.
+    async fn update(
+        &self,
+        shard_id: &str,
+        update: &str,
+        condition: &str,
+        values: HashMap<String, AttributeValue>,
+    ) -> Result<bool, LeaseError> {
+        let result = self
+            .client
+            .update_item()
+            .table_name(&self.table_name)
+            .key(LEASE_KEY, AttributeValue::S(shard_id.to_owned()))
+            .update_expression(update)
+            .condition_expression(condition)
+            .set_expression_attribute_values(Some(values))
+            .send()
+            .await;
+        match result {
+            Ok(_) => Ok(true),
+            Err(SdkError::ServiceError { err, .. })
+                if err.is_conditional_check_failed_exception() =>
+            {
+                Ok(false)
+            }
+            Err(source) => Err(LeaseError::UpdateLease {
+                shard_id: shard_id.to_owned(),
+                source,
+            }),
+        }
+    }
+}
+
+/// Tracks the counters of the leases to find the expired ones, as the clocks of the workers can't
+/// be compared.
+#[derive(Debug, Default)]
+pub(super) struct ExpiryTracker {
+    seen: HashMap<String, (u64, Instant)>,
+}
+
+impl ExpiryTracker {
+    /// Returns the IDs of the shards whose leases have an owner but haven't been renewed for
+    /// `duration`.
+    pub(super) fn expired(
+        &mut self,
+        leases: &[Lease],
+        now: Instant,
+        duration: Duration,
+    ) -> HashSet<String> {
+        self.seen
+            .retain(|shard_id, _| leases.iter().any(|lease| &lease.shard_id == shard_id));
+
+        leases
+            .iter()
+            .filter(|lease| {
+                let seen = self
+                    .seen
+                    .entry(lease.shard_id.clone())
+                    .or_insert((lease.counter, now));
+                if seen.0 != lease.counter {
+                    *seen = (lease.counter, now);
+                }
+                lease.owner.is_some() && now.duration_since(seen.1) >= duration
+            })
+            .map(|lease| lease.shard_id.clone())
+            .collect()
+    }
+}
+
+/// Chooses the leases this worker should take so that the unfinished shards are spread evenly
+/// across the workers.
+///
+/// The leases without owner or expired are taken first, once the shards they were split or merged
+/// from have been read to their end so that the records of each partition key are read in order.
+/// When none are available, a single lease is stolen from the most loaded worker if it owns more
+/// than its share.
+pub(super) fn leases_to_take<'a>(
+    leases: &'a [Lease],
+    expired: &HashSet<String>,
+    worker_id: &str,
+) -> Vec<&'a Lease> {
+    let ended = leases
+        .iter()
+        .filter(|lease| lease.is_ended())
+        .map(|lease| lease.shard_id.as_str())
+        .collect::<HashSet<_>>();
+    let known = leases
+        .iter()
+        .map(|lease| lease.shard_id.as_str())
+        .collect::<HashSet<_>>();
+    let active = leases
+        .iter()
+        .filter(|lease| !lease.is_ended())
+        .collect::<Vec<_>>();
+
+    let mut owned = HashMap::<&str, Vec<&Lease>>::new();
+    owned.insert(worker_id, Vec::new());
+    let mut available = Vec::new();
+    for lease in &active {
+        match &lease.owner {
+            Some(owner) if !expired.contains(&lease.shard_id) => {
+                owned.entry(owner.as_str()).or_default().push(lease)
+            }
+            _ => {
+                let parents_done = lease.parents.iter().all(|parent| {
+                    ended.contains(parent.as_str()) || !known.contains(parent.as_str())
+                });
+                if parents_done {
+                    available.push(*lease);
+                }
+            }
+        }
+    }
+
+    let target = (active.len() + owned.len() - 1) / owned.len();
+    let mine = owned[worker_id].len();
+    if mine >= target {
+        return Vec::new();
+    }
+
+    available.sort_by(|a, b| a.shard_id.cmp(&b.shard_id));
+    available.truncate(target - mine);
+    if available.is_empty() {
+        let most_loaded = owned
+            .iter()
+            .filter(|(owner, _)| **owner != worker_id)
+            .max_by_key(|(owner, leases)| (leases.len(), *owner))
+            .map(|(_, leases)| leases);
+        if let Some(leases) = most_loaded {
+            if leases.len() > target {
+                available.extend(leases.iter().min_by_key(|lease| &lease.shard_id));
+            }
+        }
+    }
+    available
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lease(shard_id: &str, owner: Option<&str>, checkpoint: &str, parents: &[&str]) -> Lease {
+        Lease {
+            shard_id: shard_id.into(),
+            owner: owner.map(Into::into),
+            counter: 1,
+            checkpoint: checkpoint.into(),
+            parents: parents.iter().map(|parent| (*parent).into()).collect(),
+        }
+    }
+
+    fn shard_ids(leases: Vec<&Lease>) -> Vec<&str> {
+        leases
+            .into_iter()
+            .map(|lease| lease.shard_id.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn converts_leases_to_items() {
+        let lease = lease("shard-1", Some("worker-a"), "123", &["shard-0"]);
+        assert_eq!(Lease::from_item(&lease.to_item()).unwrap(), lease);
+
+        let lease = Lease::new("shard-2".into(), LATEST, Vec::new());
+        assert!(!lease.to_item().contains_key(PARENT_SHARD_IDS));
+        assert_eq!(Lease::from_item(&lease.to_item()).unwrap(), lease);
+    }
+
+    #[test]
+    fn takes_available_leases_up_to_share() {
+        let leases = [
+            lease("shard-1", None, LATEST, &[]),
+            lease("shard-2", None, LATEST, &[]),
+            lease("shard-3", Some("worker-b"), LATEST, &[]),
+            lease("shard-4", Some("worker-c"), LATEST, &[]),
+        ];
+
+        let taken = leases_to_take(&leases, &HashSet::new(), "worker-a");
+        assert_eq!(shard_ids(taken), ["shard-1", "shard-2"]);
+    }
+
+    #[test]
+    fn takes_expired_leases() {
+        let leases = [
+            lease("shard-1", Some("worker-b"), LATEST, &[]),
+            lease("shard-2", Some("worker-b"), LATEST, &[]),
+        ];
+        let expired = HashSet::from(["shard-1".to_owned(), "shard-2".to_owned()]);
+
+        let taken = leases_to_take(&leases, &expired, "worker-a");
+        assert_eq!(shard_ids(taken), ["shard-1", "shard-2"]);
+    }
+
+    #[test]
+    fn waits_for_parent_shards() {
+        let leases = [
+            lease("shard-1", Some("worker-b"), "123", &[]),
+            lease("shard-2", None, TRIM_HORIZON, &["shard-1"]),
+            lease("shard-3", None, SHARD_END, &[]),
+            lease("shard-4", None, TRIM_HORIZON, &["shard-3", "shard-0"]),
+        ];
+
+        let taken = leases_to_take(&leases, &HashSet::new(), "worker-a");
+        assert_eq!(shard_ids(taken), ["shard-4"]);
+    }
+
+    #[test]
+    fn steals_from_most_loaded_worker() {
+        let leases = [
+            lease("shard-1", Some("worker-b"), LATEST, &[]),
+            lease("shard-2", Some("worker-b"), LATEST, &[]),
+            lease("shard-3", Some("worker-b"), LATEST, &[]),
+            lease("shard-4", Some("worker-a"), LATEST, &[]),
+        ];
+
+        let taken = leases_to_take(&leases, &HashSet::new(), "worker-a");
+        assert_eq!(shard_ids(taken), ["shard-1"]);
+
+        let leases = [
+            lease("shard-1", Some("worker-b"), LATEST, &[]),
+            lease("shard-2", Some("worker-b"), LATEST, &[]),
+            lease("shard-3", Some("worker-a"), LATEST, &[]),
+        ];
+        assert!(leases_to_take(&leases, &HashSet::new(), "worker-a").is_empty());
+    }
+
+    #[test]
+    fn creates_leases_of_new_shards() {
+        let shards = [
+            Shard {
+                id: "shard-1".into(),
+                parents: Vec::new(),
+            },
+            Shard {
+                id: "shard-2".into(),
+                parents: vec!["shard-1".into()],
+            },
+            Shard {
+                id: "shard-3".into(),
+                parents: vec!["shard-0".into()],
+            },
+        ];
+        let leases = [lease("shard-1", Some("worker-a"), "123", &[])];
+
+        let created = new_leases(&shards, &leases, LATEST);
+        assert_eq!(
+            created,
+            [
+                Lease::new("shard-2".into(), TRIM_HORIZON, vec!["shard-1".into()]),
+                Lease::new("shard-3".into(), LATEST, vec!["shard-0".into()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn expires_leases_not_renewed() {
+        let mut tracker = ExpiryTracker::default();
+        let duration = Duration::from_secs(10);
+        let start = Instant::now();
+        let mut leases = vec![
+            lease("shard-1", Some("worker-b"), LATEST, &[]),
+            lease("shard-2", Some("worker-b"), LATEST, &[]),
+        ];
+
+        assert!(tracker.expired(&leases, start, duration).is_empty());
+
+        leases[1].counter += 1;
+        let expired = tracker.expired(&leases, start + Duration::from_secs(5), duration);
+        assert!(expired.is_empty());
+
+        let expired = tracker.expired(&leases, start + Duration::from_secs(11), duration);
+        assert_eq!(expired, HashSet::from(["shard-1".to_owned()]));
+    }
+}
@@ -0,0 +1,20 @@
+//! The `aws_kinesis_streams` source.
+//!
+//! The shards of the stream are shared between the Vector instances reading it for the same
+//! application through leases stored in a DynamoDB table. Each instance takes its share of the
+//! leases, renews them while it reads their shards, and checkpoints the sequence number of the
+//! last record of each batch in the lease once its events have been delivered, so that another
+//! instance taking the lease resumes reading where it was left.
+
+mod config;
+mod consumer;
+mod lease;
+mod worker;
+
+pub use config::AwsKinesisStreamsConfig;
+
+use crate::config::SourceDescription;
+
+inventory::submit! {
+    SourceDescription::new::<AwsKinesisStreamsConfig>("aws_kinesis_streams")
+}
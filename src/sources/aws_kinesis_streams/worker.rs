@@ -0,0 +1,233 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use futures::FutureExt;
+use tokio::{
+    pin, select,
+    sync::{mpsc, oneshot},
+    task::JoinHandle,
+};
+
+use super::{
+    config::InitialPosition,
+    consumer::{ShardOutcome, ShardReader},
+    lease::{leases_to_take, new_leases, ExpiryTracker, Lease, LeaseTable},
+};
+use crate::{internal_events::AwsKinesisStreamsLeaseError, shutdown::ShutdownSignal, SourceSender};
+
+/// A lease owned by this worker, whose shard is being read.
+struct OwnedLease {
+    counter: u64,
+    stop: oneshot::Sender<()>,
+    handle: JoinHandle<()>,
+}
+
+/// Keeps the leases of this worker, and reads their shards.
+pub(super) struct Worker {
+    reader: ShardReader,
+    leases: LeaseTable,
+    initial_position: InitialPosition,
+    lease_duration: Duration,
+    shard_sync_interval: Duration,
+    owned: HashMap<String, OwnedLease>,
+    expiry: ExpiryTracker,
+}
+
+impl Worker {
+    pub(super) fn new(
+        reader: ShardReader,
+        leases: LeaseTable,
+        initial_position: InitialPosition,
+        lease_duration: Duration,
+        shard_sync_interval: Duration,
+    ) -> Self {
+        Self {
+            reader,
+            leases,
+            initial_position,
+            lease_duration,
+            shard_sync_interval,
+            owned: HashMap::new(),
+            expiry: ExpiryTracker::default(),
+        }
+    }
+
+    pub(super) async fn run(
+        mut self,
+        out: SourceSender,
+        shutdown: ShutdownSignal,
+    ) -> Result<(), ()> {
+        let shutdown = shutdown.fuse();
+        pin!(shutdown);
+
+        select! {
+            _ = &mut shutdown => return Ok(()),
+            result = self.leases.ensure_exists() => {
+                if let Err(error) = result {
+                    emit!(AwsKinesisStreamsLeaseError { error: &error });
+                    return Err(());
+                }
+            },
+        }
+        select! {
+            _ = &mut shutdown => return Ok(()),
+            result = self.reader.prepare() => {
+                if let Err(error) = result {
+                    error!(message = "Failed to register the stream consumer.", %error);
+                    return Err(());
+                }
+            },
+        }
+
+        let (finished_tx, mut finished) = mpsc::unbounded_channel();
+        // Leases are renewed well before they expire, so that a slow renewal doesn't lose them.
+        let mut renew = tokio::time::interval(self.lease_duration / 3);
+        let mut take = tokio::time::interval(self.lease_duration);
+        let mut sync = tokio::time::interval(self.shard_sync_interval);
+        loop {
+            select! {
+                _ = &mut shutdown => break,
+                _ = sync.tick() => self.sync_shards().await,
+                _ = take.tick() => self.take_leases(&out, &finished_tx).await,
+                _ = renew.tick() => self.renew_leases().await,
+                Some((shard_id, outcome)) = finished.recv() => {
+                    self.owned.remove(&shard_id);
+                    if outcome == ShardOutcome::Ended {
+                        // Create the leases of the shards it was split or merged into right away.
+                        self.sync_shards().await;
+                    }
+                },
+            }
+        }
+
+        self.release_leases().await;
+        Ok(())
+    }
+
+    /// Creates the leases of the shards that don't have one yet.
+    async fn sync_shards(&self) {
+        let shards = match self.reader.list_shards().await {
+            Ok(shards) => shards,
+            Err(error) => {
+                error!(message = "Failed to list the shards of the stream.", %error);
+                return;
+            }
+        };
+        let leases = match self.leases.list().await {
+            Ok(leases) => leases,
+            Err(error) => {
+                emit!(AwsKinesisStreamsLeaseError { error: &error });
+                return;
+            }
+        };
+
+        for lease in new_leases(&shards, &leases, self.initial_position.checkpoint()) {
+            if let Err(error) = self.leases.create(&lease).await {
+                emit!(AwsKinesisStreamsLeaseError { error: &error });
+            }
+        }
+    }
+
+    /// Takes this worker's share of the leases, and starts reading their shards.
+    async fn take_leases(
+        &mut self,
+        out: &SourceSender,
+        finished: &mpsc::UnboundedSender<(String, ShardOutcome)>,
+    ) {
+        let mut leases = match self.leases.list().await {
+            Ok(leases) => leases,
+            Err(error) => {
+                emit!(AwsKinesisStreamsLeaseError { error: &error });
+                return;
+            }
+        };
+        // The leases left by a previous run with the same worker ID aren't being read.
+        for lease in &mut leases {
+            if lease.owner.as_deref() == Some(self.leases.worker_id())
+                && !self.owned.contains_key(&lease.shard_id)
+            {
+                lease.owner = None;
+            }
+        }
+
+        let expired = self
+            .expiry
+            .expired(&leases, Instant::now(), self.lease_duration);
+        for lease in leases_to_take(&leases, &expired, self.leases.worker_id()) {
+            match self.leases.take(lease).await {
+                Ok(Some(counter)) => self.start(lease, counter, out, finished),
+                Ok(None) => {}
+                Err(error) => emit!(AwsKinesisStreamsLeaseError { error: &error }),
+            }
+        }
+    }
+
+    fn start(
+        &mut self,
+        lease: &Lease,
+        counter: u64,
+        out: &SourceSender,
+        finished: &mpsc::UnboundedSender<(String, ShardOutcome)>,
+    ) {
+        info!(message = "Took the lease of shard.", shard_id = %lease.shard_id);
+
+        let (stop, stopped) = oneshot::channel();
+        let reader = self.reader.clone();
+        let leases = self.leases.clone();
+        let mut out = out.clone();
+        let finished = finished.clone();
+        let shard_id = lease.shard_id.clone();
+        let checkpoint = lease.checkpoint.clone();
+        let handle = tokio::spawn(async move {
+            let outcome = select! {
+                outcome = reader.read_shard(&shard_id, &checkpoint, &leases, &mut out) => outcome,
+                _ = stopped => return,
+            };
+            _ = finished.send((shard_id, outcome));
+        });
+
+        self.owned.insert(
+            lease.shard_id.clone(),
+            OwnedLease {
+                counter,
+                stop,
+                handle,
+            },
+        );
+    }
+
+    /// Renews the leases of this worker, and stops reading the shards of the ones taken by
+    /// other workers.
+    async fn renew_leases(&mut self) {
+        let mut lost = Vec::new();
+        for (shard_id, lease) in &mut self.owned {
+            match self.leases.renew(shard_id, lease.counter).await {
+                Ok(Some(counter)) => lease.counter = counter,
+                Ok(None) => lost.push(shard_id.clone()),
+                // The lease is kept until another worker takes it.
+                Err(error) => emit!(AwsKinesisStreamsLeaseError { error: &error }),
+            }
+        }
+
+        for shard_id in lost {
+            if let Some(lease) = self.owned.remove(&shard_id) {
+                info!(message = "Lost the lease of shard.", %shard_id);
+                _ = lease.stop.send(());
+            }
+        }
+    }
+
+    /// Stops reading the shards, and releases their leases so that the other workers can take
+    /// them right away.
+    async fn release_leases(&mut self) {
+        for (shard_id, lease) in self.owned.drain() {
+            _ = lease.stop.send(());
+            _ = lease.handle.await;
+            if let Err(error) = self.leases.release(&shard_id, lease.counter).await {
+                emit!(AwsKinesisStreamsLeaseError { error: &error });
+            }
+        }
+    }
+}
@@ -1,14 +1,20 @@
 use std::convert::TryInto;
+use std::future::ready;
 use std::io::ErrorKind;
 
 use async_compression::tokio::bufread;
 use aws_sdk_s3::types::ByteStream;
+use aws_sdk_s3::Client as S3Client;
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use codecs::CharacterDelimitedDecoder;
 use futures::stream;
-use futures::{stream::StreamExt, TryStreamExt};
+use futures::{stream::StreamExt, Stream, TryStreamExt};
+use lookup::path;
 use snafu::Snafu;
-use tokio_util::io::StreamReader;
+use tokio_util::{codec::FramedRead, io::StreamReader};
 use vector_config::configurable_component;
-use vector_core::config::LogNamespace;
+use vector_core::{config::LogNamespace, ByteSizeOf};
 
 use super::util::MultilineConfig;
 use crate::aws::create_client;
@@ -19,15 +25,21 @@ use crate::tls::TlsConfig;
 use crate::{
     aws::auth::AwsAuthentication,
     config::{
-        AcknowledgementsConfig, DataType, Output, ProxyConfig, SourceConfig, SourceContext,
-        SourceDescription,
+        log_schema, AcknowledgementsConfig, DataType, Output, ProxyConfig, SourceConfig,
+        SourceContext, SourceDescription,
     },
-    line_agg,
+    event::{BatchNotifier, BatchStatus, LogEvent},
+    internal_events::{BytesReceived, OldEventsReceived, StreamClosedError},
+    line_agg::{self, LineAgg},
     serde::bool_or_struct,
+    SourceSender,
 };
 
+mod poll;
 pub mod sqs;
 
+use self::sqs::ProcessingError;
+
 /// Compression scheme for objects retrieved from S3.
 #[configurable_component]
 #[derive(Clone, Copy, Debug, Derivative, PartialEq)]
@@ -59,6 +71,12 @@ enum Strategy {
     /// Consumes objects by processing bucket notification events sent to an [AWS SQS queue](\(urls.aws_sqs)).
     #[derivative(Default)]
     Sqs,
+
+    /// Consumes objects by periodically listing a prefix of a bucket, for buckets whose notifications can't be
+    /// sent to an SQS queue.
+    ///
+    /// The objects already ingested are tracked with a checkpoint persisted in the data directory.
+    Poll,
 }
 
 /// Configuration for the `aws_s3` source.
@@ -85,6 +103,11 @@ pub struct AwsS3Config {
     /// Only relevant when `strategy = "sqs"`.
     sqs: Option<sqs::Config>,
 
+    /// Configuration options for polling.
+    ///
+    /// Only relevant when `strategy = "poll"`.
+    poll: Option<poll::Config>,
+
     /// The ARN of an [IAM role](\(urls.aws_iam_role)) to assume at startup.
     #[deprecated]
     assume_role: Option<String>,
@@ -128,6 +151,11 @@ impl SourceConfig for AwsS3Config {
                     .await?
                     .run(cx, self.acknowledgements),
             )),
+            Strategy::Poll => Ok(Box::pin(
+                self.create_poll_ingestor(multiline_config, &cx)
+                    .await?
+                    .run(cx, self.acknowledgements),
+            )),
         }
     }
 
@@ -199,6 +227,65 @@ impl AwsS3Config {
     }
 }
 
+impl AwsS3Config {
+    async fn create_poll_ingestor(
+        &self,
+        multiline: Option<line_agg::Config>,
+        cx: &SourceContext,
+    ) -> crate::Result<poll::Ingestor> {
+        let poll = self
+            .poll
+            .as_ref()
+            .ok_or(CreatePollIngestorError::ConfigMissing)?;
+        if poll.interval_secs == 0 {
+            return Err(CreatePollIngestorError::InvalidInterval.into());
+        }
+        let region = self
+            .region
+            .region()
+            .ok_or(CreatePollIngestorError::RegionMissing)?;
+        let endpoint = self
+            .region
+            .endpoint()
+            .map_err(|_| CreatePollIngestorError::InvalidEndpoint)?;
+
+        let s3_client = create_client::<S3ClientBuilder>(
+            &self.auth,
+            Some(region.clone()),
+            endpoint,
+            &cx.proxy,
+            &self.tls_options,
+            false,
+        )
+        .await?;
+
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(poll.data_dir.as_ref(), cx.key.id())?;
+
+        Ok(poll::Ingestor::new(
+            region.to_string(),
+            s3_client,
+            poll,
+            self.compression,
+            multiline,
+            data_dir,
+        )?)
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum CreatePollIngestorError {
+    #[snafu(display("Configuration for `poll` required when strategy=poll"))]
+    ConfigMissing,
+    #[snafu(display("`poll.interval_secs` must be greater than zero"))]
+    InvalidInterval,
+    #[snafu(display("Region is required"))]
+    RegionMissing,
+    #[snafu(display("Endpoint is invalid"))]
+    InvalidEndpoint,
+}
+
 #[derive(Debug, Snafu)]
 enum CreateSqsIngestorError {
     #[snafu(display("Unable to initialize: {}", source))]
@@ -215,6 +302,184 @@ enum CreateSqsIngestorError {
     InvalidEndpoint,
 }
 
+/// Reads objects into events, whatever the strategy used to find them.
+pub(super) struct ObjectReader {
+    s3_client: S3Client,
+    compression: Compression,
+    multiline: Option<line_agg::Config>,
+}
+
+impl ObjectReader {
+    pub(super) const fn new(
+        s3_client: S3Client,
+        compression: Compression,
+        multiline: Option<line_agg::Config>,
+    ) -> Self {
+        Self {
+            s3_client,
+            compression,
+            multiline,
+        }
+    }
+
+    /// Sends the lines of the object as events, waiting for them to be delivered when
+    /// acknowledgements are enabled.
+    pub(super) async fn read_object(
+        &self,
+        out: &mut SourceSender,
+        acknowledgements: bool,
+        bucket: &str,
+        key: &str,
+        region: &str,
+    ) -> Result<(), ProcessingError> {
+        let object_result = self
+            .s3_client
+            .get_object()
+            .bucket(bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|source| ProcessingError::GetObject {
+                source,
+                bucket: bucket.to_owned(),
+                key: key.to_owned(),
+            });
+
+        let object = object_result?;
+
+        let metadata = object.metadata;
+        let timestamp = object
+            .last_modified
+            .map(|ts| Utc.timestamp(ts.secs(), ts.subsec_nanos()))
+            .unwrap_or_else(Utc::now);
+
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(acknowledgements);
+        let object_reader = super::s3_object_decoder(
+            self.compression,
+            key,
+            object.content_encoding.as_deref(),
+            object.content_type.as_deref(),
+            object.body,
+        )
+        .await;
+
+        // Record the read error seen to propagate up later so we avoid ack'ing the SQS
+        // message
+        //
+        // String is used as we cannot clone std::io::Error to take ownership in closure
+        //
+        // FramedRead likely stops when it gets an i/o error but I found it more clear to
+        // show that we `take_while` there hasn't been an error
+        //
+        // This can result in objects being partially processed before an error, but we
+        // prefer duplicate lines over message loss. Future work could include recording
+        // the offset of the object that has been read, but this would only be relevant in
+        // the case that the same vector instance processes the same message.
+        let mut read_error = None;
+        let lines: Box<dyn Stream<Item = Bytes> + Send + Unpin> = Box::new(
+            FramedRead::new(object_reader, CharacterDelimitedDecoder::new(b'\n'))
+                .map(|res| {
+                    res.map(|bytes| {
+                        emit!(BytesReceived {
+                            byte_size: bytes.len(),
+                            protocol: "http",
+                        });
+                        bytes
+                    })
+                    .map_err(|err| {
+                        read_error = Some(err);
+                    })
+                    .ok()
+                })
+                .take_while(|res| ready(res.is_some()))
+                .map(|r| r.expect("validated by take_while")),
+        );
+
+        let lines = match &self.multiline {
+            Some(config) => Box::new(
+                LineAgg::new(
+                    lines.map(|line| ((), line, ())),
+                    line_agg::Logic::new(config.clone()),
+                )
+                .map(|(_src, line, _context)| line),
+            ),
+            None => lines,
+        };
+
+        let bucket_name = Bytes::from(bucket.as_bytes().to_vec());
+        let object_key = Bytes::from(key.as_bytes().to_vec());
+        let aws_region = Bytes::from(region.as_bytes().to_vec());
+
+        let mut stream = lines.filter_map(move |line| {
+            let mut log = LogEvent::from_bytes_legacy(&line).with_batch_notifier_option(&batch);
+
+            log.insert(path!("bucket"), bucket_name.clone());
+            log.insert(path!("object"), object_key.clone());
+            log.insert(path!("region"), aws_region.clone());
+            log.insert(log_schema().source_type_key(), Bytes::from("aws_s3"));
+            log.insert(log_schema().timestamp_key(), timestamp);
+
+            if let Some(metadata) = &metadata {
+                for (key, value) in metadata {
+                    log.insert(key.as_str(), value.clone());
+                }
+            }
+
+            emit!(OldEventsReceived {
+                count: 1,
+                byte_size: log.size_of()
+            });
+
+            ready(Some(log))
+        });
+
+        let send_error = match out.send_event_stream(&mut stream).await {
+            Ok(_) => None,
+            Err(error) => {
+                // count is set to 0 to have no discarded events considering
+                // the events are not yet acknowledged and will be retried in
+                // case of error
+                emit!(StreamClosedError { error, count: 0 });
+                Some(crate::source_sender::ClosedError)
+            }
+        };
+
+        // Up above, `lines` captures `read_error`, and eventually is captured by `stream`,
+        // so we explicitly drop it so that we can again utilize `read_error` below.
+        drop(stream);
+
+        if let Some(error) = read_error {
+            Err(ProcessingError::ReadObject {
+                source: error,
+                bucket: bucket.to_owned(),
+                key: key.to_owned(),
+            })
+        } else if let Some(error) = send_error {
+            Err(ProcessingError::PipelineSend {
+                source: error,
+                bucket: bucket.to_owned(),
+                key: key.to_owned(),
+            })
+        } else {
+            match receiver {
+                None => Ok(()),
+                Some(receiver) => match receiver.await {
+                    BatchStatus::Delivered => Ok(()),
+                    BatchStatus::Errored => Err(ProcessingError::ErrorAcknowledgement),
+                    BatchStatus::Rejected => {
+                        error!(
+                            message = "Sink reported events were rejected.",
+                            internal_log_rate_secs = 5,
+                        );
+                        // Failed events cannot be retried, so continue to delete the SQS source message.
+                        Ok(())
+                    }
+                },
+            }
+        }
+    }
+}
+
 /// None if body is empty
 async fn s3_object_decoder(
     compression: Compression,
@@ -0,0 +1,365 @@
+use std::{
+    collections::BTreeSet,
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use aws_sdk_s3::error::ListObjectsV2Error;
+use aws_sdk_s3::Client as S3Client;
+use aws_smithy_client::SdkError;
+use chrono::{DateTime, TimeZone, Utc};
+use futures::FutureExt;
+use serde::{Deserialize, Serialize};
+use tokio::{pin, select};
+use vector_config::configurable_component;
+
+use super::{sqs::ProcessingError, ObjectReader};
+use crate::{
+    config::{AcknowledgementsConfig, SourceContext},
+    internal_events::{S3CheckpointWriteError, S3ObjectListError, S3ObjectProcessingError},
+    line_agg, SourceSender,
+};
+
+const CHECKPOINT_FILENAME: &str = "checkpoint.json";
+const TMP_CHECKPOINT_FILENAME: &str = "checkpoint.json.tmp";
+
+/// Polling configuration options.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Config {
+    /// The name of the bucket to list the objects of.
+    pub(super) bucket: String,
+
+    /// The prefix of the keys of the objects to ingest.
+    ///
+    /// By default, all the objects of the bucket are ingested.
+    #[serde(default)]
+    pub(super) key_prefix: Option<String>,
+
+    /// How long to wait between listings of the objects, in seconds.
+    #[serde(default = "default_interval_secs")]
+    #[derivative(Default(value = "default_interval_secs()"))]
+    pub(super) interval_secs: u64,
+
+    /// Whether the keys of new objects always sort after the keys of the existing ones.
+    ///
+    /// This is the case when the keys start with the time the objects are written at, such as the
+    /// keys written by the `aws_s3` sink. Only the keys after the last ingested one are then
+    /// listed, instead of all the keys of the prefix on every poll, and the modification time of
+    /// the objects is ignored.
+    #[serde(default)]
+    pub(super) ordered_keys: bool,
+
+    /// The directory used to persist the checkpoint of the ingested objects.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+    #[serde(default)]
+    pub(super) data_dir: Option<PathBuf>,
+}
+
+const fn default_interval_secs() -> u64 {
+    60
+}
+
+/// The last ingested objects, which the objects of the next listings are compared to.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+struct Checkpoint {
+    /// The modification time of the last ingested objects.
+    last_modified: Option<DateTime<Utc>>,
+    /// The keys of the ingested objects modified at `last_modified`, as the modification time of
+    /// objects has a precision of a second.
+    keys: BTreeSet<String>,
+    /// The greatest key of the ingested objects.
+    last_key: Option<String>,
+}
+
+impl Checkpoint {
+    fn is_new(&self, key: &str, last_modified: DateTime<Utc>, ordered_keys: bool) -> bool {
+        if ordered_keys {
+            return self
+                .last_key
+                .as_ref()
+                .map_or(true, |last_key| key > last_key.as_str());
+        }
+        match self.last_modified {
+            None => true,
+            Some(checkpoint) if last_modified == checkpoint => !self.keys.contains(key),
+            Some(checkpoint) => last_modified > checkpoint,
+        }
+    }
+
+    fn advance(&mut self, key: &str, last_modified: DateTime<Utc>) {
+        match self.last_modified {
+            Some(checkpoint) if last_modified == checkpoint => {
+                self.keys.insert(key.to_owned());
+            }
+            Some(checkpoint) if last_modified < checkpoint => {}
+            _ => {
+                self.last_modified = Some(last_modified);
+                self.keys = BTreeSet::from([key.to_owned()]);
+            }
+        }
+        if self
+            .last_key
+            .as_ref()
+            .map_or(true, |last_key| key > last_key.as_str())
+        {
+            self.last_key = Some(key.to_owned());
+        }
+    }
+}
+
+/// Persists the checkpoint as JSON, replacing the previous one atomically.
+struct Checkpointer {
+    directory: PathBuf,
+    checkpoint: Checkpoint,
+}
+
+impl Checkpointer {
+    fn new(directory: PathBuf) -> io::Result<Self> {
+        let checkpoint = match std::fs::read(directory.join(CHECKPOINT_FILENAME)) {
+            Ok(contents) => serde_json::from_slice(&contents)?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Checkpoint::default(),
+            Err(error) => return Err(error),
+        };
+        Ok(Self {
+            directory,
+            checkpoint,
+        })
+    }
+
+    async fn set(&mut self, key: &str, last_modified: DateTime<Utc>) {
+        self.checkpoint.advance(key, last_modified);
+
+        let path = self.directory.join(CHECKPOINT_FILENAME);
+        if let Err(error) = write_checkpoint(&self.directory, &path, &self.checkpoint).await {
+            emit!(S3CheckpointWriteError {
+                path: &path,
+                error: &error,
+            });
+        }
+    }
+}
+
+async fn write_checkpoint(
+    directory: &Path,
+    path: &Path,
+    checkpoint: &Checkpoint,
+) -> io::Result<()> {
+    let tmp_path = directory.join(TMP_CHECKPOINT_FILENAME);
+    tokio::fs::write(&tmp_path, serde_json::to_vec(checkpoint)?).await?;
+    tokio::fs::rename(&tmp_path, path).await
+}
+
+/// An object to ingest.
+struct Object {
+    key: String,
+    last_modified: DateTime<Utc>,
+}
+
+pub(super) struct Ingestor {
+    s3_client: S3Client,
+    reader: ObjectReader,
+    region: String,
+    bucket: String,
+    key_prefix: Option<String>,
+    interval: Duration,
+    ordered_keys: bool,
+    checkpointer: Checkpointer,
+}
+
+impl Ingestor {
+    pub(super) fn new(
+        region: String,
+        s3_client: S3Client,
+        config: &Config,
+        compression: super::Compression,
+        multiline: Option<line_agg::Config>,
+        data_dir: PathBuf,
+    ) -> io::Result<Self> {
+        Ok(Self {
+            s3_client: s3_client.clone(),
+            reader: ObjectReader::new(s3_client, compression, multiline),
+            region,
+            bucket: config.bucket.clone(),
+            key_prefix: config.key_prefix.clone(),
+            interval: Duration::from_secs(config.interval_secs),
+            ordered_keys: config.ordered_keys,
+            checkpointer: Checkpointer::new(data_dir)?,
+        })
+    }
+
+    pub(super) async fn run(
+        mut self,
+        cx: SourceContext,
+        acknowledgements: AcknowledgementsConfig,
+    ) -> Result<(), ()> {
+        let acknowledgements = cx.do_acknowledgements(&acknowledgements);
+        let mut out = cx.out;
+        let shutdown = cx.shutdown.fuse();
+        pin!(shutdown);
+
+        let mut interval = tokio::time::interval(self.interval);
+        loop {
+            select! {
+                _ = &mut shutdown => break,
+                _ = interval.tick() => {},
+            }
+            select! {
+                _ = &mut shutdown => break,
+                _ = self.poll(&mut out, acknowledgements) => {},
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Ingests the objects added since the checkpoint, in the order of their modification time or
+    /// key, advancing the checkpoint as each of them is ingested.
+    async fn poll(&mut self, out: &mut SourceSender, acknowledgements: bool) {
+        let objects = match self.list_objects().await {
+            Ok(objects) => objects,
+            Err(error) => {
+                emit!(S3ObjectListError {
+                    bucket: &self.bucket,
+                    error: &error,
+                });
+                return;
+            }
+        };
+
+        for object in objects {
+            let result = self
+                .reader
+                .read_object(
+                    out,
+                    acknowledgements,
+                    &self.bucket,
+                    &object.key,
+                    &self.region,
+                )
+                .await;
+            match result {
+                Ok(()) => {}
+                // Reading the object again would fail the same way, after sending the same
+                // events again.
+                Err(error @ ProcessingError::ReadObject { .. }) => {
+                    emit!(S3ObjectProcessingError { error: &error });
+                }
+                // The object is read again on the next poll.
+                Err(error) => {
+                    emit!(S3ObjectProcessingError { error: &error });
+                    return;
+                }
+            }
+            self.checkpointer
+                .set(&object.key, object.last_modified)
+                .await;
+        }
+    }
+
+    /// Lists the objects added since the checkpoint, in the order they should be ingested.
+    async fn list_objects(&self) -> Result<Vec<Object>, SdkError<ListObjectsV2Error>> {
+        let checkpoint = &self.checkpointer.checkpoint;
+        let start_after = self
+            .ordered_keys
+            .then(|| checkpoint.last_key.clone())
+            .flatten();
+
+        let mut objects = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let output = self
+                .s3_client
+                .list_objects_v2()
+                .bucket(self.bucket.clone())
+                .set_prefix(self.key_prefix.clone())
+                .set_start_after(start_after.clone())
+                .set_continuation_token(continuation_token)
+                .send()
+                .await?;
+
+            objects.extend(
+                output
+                    .contents
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|object| {
+                        let key = object.key?;
+                        let last_modified = object
+                            .last_modified
+                            .map(|ts| Utc.timestamp(ts.secs(), ts.subsec_nanos()))
+                            .unwrap_or_else(Utc::now);
+                        // Skip the placeholders of the folders created by the console.
+                        (!key.ends_with('/')
+                            && checkpoint.is_new(&key, last_modified, self.ordered_keys))
+                        .then(|| Object { key, last_modified })
+                    }),
+            );
+
+            match output.next_continuation_token {
+                Some(token) if output.is_truncated => continuation_token = Some(token),
+                _ => break,
+            }
+        }
+
+        if self.ordered_keys {
+            objects.sort_by(|a, b| a.key.cmp(&b.key));
+        } else {
+            objects.sort_by(|a, b| (a.last_modified, &a.key).cmp(&(b.last_modified, &b.key)));
+        }
+        Ok(objects)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(secs: i64) -> DateTime<Utc> {
+        Utc.timestamp(secs, 0)
+    }
+
+    #[test]
+    fn checkpoint_by_modification_time() {
+        let mut checkpoint = Checkpoint::default();
+        assert!(checkpoint.is_new("b.log", time(10), false));
+
+        checkpoint.advance("b.log", time(10));
+        assert!(!checkpoint.is_new("b.log", time(10), false));
+        assert!(checkpoint.is_new("a.log", time(10), false));
+        assert!(!checkpoint.is_new("c.log", time(9), false));
+        assert!(checkpoint.is_new("a.log", time(11), false));
+
+        checkpoint.advance("a.log", time(10));
+        assert_eq!(checkpoint.keys.len(), 2);
+        checkpoint.advance("a.log", time(11));
+        assert_eq!(checkpoint.keys, BTreeSet::from(["a.log".to_owned()]));
+        assert_eq!(checkpoint.last_key.as_deref(), Some("b.log"));
+    }
+
+    #[test]
+    fn checkpoint_by_key() {
+        let mut checkpoint = Checkpoint::default();
+        checkpoint.advance("2022/08/01/b.log", time(10));
+
+        assert!(!checkpoint.is_new("2022/08/01/a.log", time(20), true));
+        assert!(checkpoint.is_new("2022/08/02/a.log", time(5), true));
+    }
+
+    #[tokio::test]
+    async fn persists_checkpoint() {
+        let directory = tempfile::tempdir().unwrap();
+
+        let mut checkpointer = Checkpointer::new(directory.path().to_owned()).unwrap();
+        assert_eq!(checkpointer.checkpoint, Checkpoint::default());
+        checkpointer.set("a.log", time(10)).await;
+
+        let checkpointer = Checkpointer::new(directory.path().to_owned()).unwrap();
+        assert_eq!(checkpointer.checkpoint.last_modified, Some(time(10)));
+        assert_eq!(checkpointer.checkpoint.last_key.as_deref(), Some("a.log"));
+    }
+}
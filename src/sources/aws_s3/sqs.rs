@@ -1,4 +1,4 @@
-use std::{future::ready, panic, sync::Arc};
+use std::{panic, sync::Arc};
 
 use aws_sdk_s3::error::GetObjectError;
 use aws_sdk_s3::Client as S3Client;
@@ -8,34 +8,28 @@ use aws_sdk_sqs::output::DeleteMessageBatchOutput;
 use aws_sdk_sqs::Client as SqsClient;
 use aws_smithy_client::SdkError;
 use aws_types::region::Region;
-use bytes::Bytes;
-use chrono::{TimeZone, Utc};
-use codecs::{decoding::FramingError, CharacterDelimitedDecoder};
-use futures::{FutureExt, Stream, StreamExt, TryFutureExt};
+use codecs::decoding::FramingError;
+use futures::{FutureExt, TryFutureExt};
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use snafu::{ResultExt, Snafu};
 use tokio::{pin, select};
-use tokio_util::codec::FramedRead;
 use tracing::Instrument;
 use vector_config::configurable_component;
-use vector_core::ByteSizeOf;
 
+use super::ObjectReader;
 use crate::tls::TlsConfig;
 use crate::{
-    config::{log_schema, AcknowledgementsConfig, SourceContext},
-    event::{BatchNotifier, BatchStatus, LogEvent},
+    config::{AcknowledgementsConfig, SourceContext},
     internal_events::{
-        BytesReceived, OldEventsReceived, SqsMessageDeleteBatchError, SqsMessageDeletePartialError,
-        SqsMessageDeleteSucceeded, SqsMessageProcessingError, SqsMessageProcessingSucceeded,
-        SqsMessageReceiveError, SqsMessageReceiveSucceeded, SqsS3EventRecordInvalidEventIgnored,
-        StreamClosedError,
+        SqsMessageDeleteBatchError, SqsMessageDeletePartialError, SqsMessageDeleteSucceeded,
+        SqsMessageProcessingError, SqsMessageProcessingSucceeded, SqsMessageReceiveError,
+        SqsMessageReceiveSucceeded, SqsS3EventRecordInvalidEventIgnored,
     },
-    line_agg::{self, LineAgg},
+    line_agg,
     shutdown::ShutdownSignal,
     SourceSender,
 };
-use lookup::path;
 
 static SUPPORTED_S3S_EVENT_VERSION: Lazy<semver::VersionReq> =
     Lazy::new(|| semver::VersionReq::parse("~2").unwrap());
@@ -172,11 +166,8 @@ pub enum ProcessingError {
 pub struct State {
     region: Region,
 
-    s3_client: S3Client,
     sqs_client: SqsClient,
-
-    multiline: Option<line_agg::Config>,
-    compression: super::Compression,
+    reader: ObjectReader,
 
     queue_url: String,
     poll_secs: i32,
@@ -201,11 +192,8 @@ impl Ingestor {
         let state = Arc::new(State {
             region,
 
-            s3_client,
             sqs_client,
-
-            compression,
-            multiline,
+            reader: ObjectReader::new(s3_client, compression, multiline),
 
             queue_url: config.queue_url,
             poll_secs: config.poll_secs as i32,
@@ -420,151 +408,16 @@ impl IngestorProcess {
             });
         }
 
-        let object_result = self
-            .state
-            .s3_client
-            .get_object()
-            .bucket(s3_event.s3.bucket.name.clone())
-            .key(s3_event.s3.object.key.clone())
-            .send()
+        self.state
+            .reader
+            .read_object(
+                &mut self.out,
+                self.acknowledgements,
+                &s3_event.s3.bucket.name,
+                &s3_event.s3.object.key,
+                &s3_event.aws_region,
+            )
             .await
-            .context(GetObjectSnafu {
-                bucket: s3_event.s3.bucket.name.clone(),
-                key: s3_event.s3.object.key.clone(),
-            });
-
-        let object = object_result?;
-
-        let metadata = object.metadata;
-        let timestamp = object
-            .last_modified
-            .map(|ts| Utc.timestamp(ts.secs(), ts.subsec_nanos()))
-            .unwrap_or_else(Utc::now);
-
-        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
-        let object_reader = super::s3_object_decoder(
-            self.state.compression,
-            &s3_event.s3.object.key,
-            object.content_encoding.as_deref(),
-            object.content_type.as_deref(),
-            object.body,
-        )
-        .await;
-
-        // Record the read error seen to propagate up later so we avoid ack'ing the SQS
-        // message
-        //
-        // String is used as we cannot clone std::io::Error to take ownership in closure
-        //
-        // FramedRead likely stops when it gets an i/o error but I found it more clear to
-        // show that we `take_while` there hasn't been an error
-        //
-        // This can result in objects being partially processed before an error, but we
-        // prefer duplicate lines over message loss. Future work could include recording
-        // the offset of the object that has been read, but this would only be relevant in
-        // the case that the same vector instance processes the same message.
-        let mut read_error = None;
-        let lines: Box<dyn Stream<Item = Bytes> + Send + Unpin> = Box::new(
-            FramedRead::new(object_reader, CharacterDelimitedDecoder::new(b'\n'))
-                .map(|res| {
-                    res.map(|bytes| {
-                        emit!(BytesReceived {
-                            byte_size: bytes.len(),
-                            protocol: "http",
-                        });
-                        bytes
-                    })
-                    .map_err(|err| {
-                        read_error = Some(err);
-                    })
-                    .ok()
-                })
-                .take_while(|res| ready(res.is_some()))
-                .map(|r| r.expect("validated by take_while")),
-        );
-
-        let lines = match &self.state.multiline {
-            Some(config) => Box::new(
-                LineAgg::new(
-                    lines.map(|line| ((), line, ())),
-                    line_agg::Logic::new(config.clone()),
-                )
-                .map(|(_src, line, _context)| line),
-            ),
-            None => lines,
-        };
-
-        let bucket_name = Bytes::from(s3_event.s3.bucket.name.as_str().as_bytes().to_vec());
-        let object_key = Bytes::from(s3_event.s3.object.key.as_str().as_bytes().to_vec());
-        let aws_region = Bytes::from(s3_event.aws_region.as_str().as_bytes().to_vec());
-
-        let mut stream = lines.filter_map(move |line| {
-            let mut log = LogEvent::from_bytes_legacy(&line).with_batch_notifier_option(&batch);
-
-            log.insert(path!("bucket"), bucket_name.clone());
-            log.insert(path!("object"), object_key.clone());
-            log.insert(path!("region"), aws_region.clone());
-            log.insert(log_schema().source_type_key(), Bytes::from("aws_s3"));
-            log.insert(log_schema().timestamp_key(), timestamp);
-
-            if let Some(metadata) = &metadata {
-                for (key, value) in metadata {
-                    log.insert(key.as_str(), value.clone());
-                }
-            }
-
-            emit!(OldEventsReceived {
-                count: 1,
-                byte_size: log.size_of()
-            });
-
-            ready(Some(log))
-        });
-
-        let send_error = match self.out.send_event_stream(&mut stream).await {
-            Ok(_) => None,
-            Err(error) => {
-                // count is set to 0 to have no discarded events considering
-                // the events are not yet acknowledged and will be retried in
-                // case of error
-                emit!(StreamClosedError { error, count: 0 });
-                Some(crate::source_sender::ClosedError)
-            }
-        };
-
-        // Up above, `lines` captures `read_error`, and eventually is captured by `stream`,
-        // so we explicitly drop it so that we can again utilize `read_error` below.
-        drop(stream);
-
-        if let Some(error) = read_error {
-            Err(ProcessingError::ReadObject {
-                source: error,
-                bucket: s3_event.s3.bucket.name.clone(),
-                key: s3_event.s3.object.key.clone(),
-            })
-        } else if let Some(error) = send_error {
-            Err(ProcessingError::PipelineSend {
-                source: error,
-                bucket: s3_event.s3.bucket.name.clone(),
-                key: s3_event.s3.object.key.clone(),
-            })
-        } else {
-            match receiver {
-                None => Ok(()),
-                Some(receiver) => match receiver.await {
-                    BatchStatus::Delivered => Ok(()),
-                    BatchStatus::Errored => Err(ProcessingError::ErrorAcknowledgement),
-                    BatchStatus::Rejected => {
-                        error!(
-                            message = "Sink reported events were rejected.",
-                            internal_log_rate_secs = 5,
-                        );
-                        // Failed events cannot be retried, so continue to delete the SQS source message.
-                        Ok(())
-                    }
-                },
-            }
-        }
     }
 
     async fn receive_messages(&mut self) -> Result<Vec<Message>, SdkError<ReceiveMessageError>> {
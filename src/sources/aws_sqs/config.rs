@@ -1,4 +1,4 @@
-use codecs::decoding::{DeserializerConfig, FramingConfig};
+use codecs::decoding::{DecodingErrorMode, DeserializerConfig, FramingConfig};
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 
@@ -79,6 +79,10 @@ pub struct AwsSqsConfig {
     #[derivative(Default(value = "default_decoding()"))]
     pub decoding: DeserializerConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub on_error: DecodingErrorMode,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     pub acknowledgements: AcknowledgementsConfig,
@@ -97,7 +101,8 @@ impl SourceConfig for AwsSqsConfig {
             self.decoding.clone(),
             LogNamespace::Legacy,
         )
-        .build();
+        .with_error_mode(self.on_error)
+        .build()?;
         let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
 
         Ok(Box::pin(
@@ -116,7 +121,9 @@ impl SourceConfig for AwsSqsConfig {
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
-        vec![Output::default(self.decoding.output_type())]
+        vec![Output::default(
+            self.on_error.output_type(self.decoding.output_type()),
+        )]
     }
 
     fn source_type(&self) -> &'static str {
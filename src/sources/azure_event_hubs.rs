@@ -0,0 +1,387 @@
+use std::{collections::HashMap, sync::Arc};
+
+use azure_core::HttpError;
+use azure_storage_blobs::prelude::*;
+use bytes::Bytes;
+use codecs::decoding::{DecodingErrorMode, DeserializerConfig, FramingConfig};
+use http::StatusCode;
+use snafu::Snafu;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    config::{AcknowledgementsConfig, Output, SourceConfig, SourceContext, SourceDescription},
+    internal_events::{AzureEventHubsCheckpointLoadError, AzureEventHubsCheckpointStoreError},
+    kafka::{KafkaAuthConfig, KafkaSaslConfig},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    sinks::azure_common,
+    sources::kafka::{KafkaSourceConfig, KafkaSourceOptions, OffsetStore},
+    tls::TlsEnableableConfig,
+};
+
+/// The port of the Kafka-compatible endpoint of Event Hubs namespaces.
+const KAFKA_PORT: u16 = 9093;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("The connection string has no `Endpoint`"))]
+    MissingEndpoint,
+    #[snafu(display(
+        "The event hub must be set by either `event_hub` or the `EntityPath` of the connection string"
+    ))]
+    MissingEventHub,
+}
+
+/// Configuration for the `azure_event_hubs` source.
+#[configurable_component(source)]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct AzureEventHubsSourceConfig {
+    /// The connection string of the Event Hubs namespace, or of the event hub.
+    ///
+    /// The event hub is consumed through the Kafka-compatible endpoint of the namespace, which is
+    /// authenticated with the connection string.
+    connection_string: String,
+
+    /// The name of the event hub to consume from.
+    ///
+    /// Defaults to the `EntityPath` of the connection string.
+    event_hub: Option<String>,
+
+    /// The consumer group to consume the event hub as.
+    ///
+    /// The partitions of the event hub are balanced between the Vector instances in the same
+    /// consumer group.
+    #[serde(default = "default_consumer_group")]
+    #[derivative(Default(value = "default_consumer_group()"))]
+    consumer_group: String,
+
+    /// Where to start consuming partitions that have neither an offset committed by the consumer
+    /// group nor a checkpoint.
+    ///
+    /// See the [librdkafka documentation](https://github.com/edenhill/librdkafka/blob/master/CONFIGURATION.md) for the `auto.offset.reset` option for further clarification.
+    #[serde(default = "default_auto_offset_reset")]
+    #[derivative(Default(value = "default_auto_offset_reset()"))]
+    auto_offset_reset: String,
+
+    #[configurable(derived)]
+    checkpoint: Option<AzureEventHubsCheckpointConfig>,
+
+    /// Advanced options set directly on the underlying `librdkafka` client.
+    ///
+    /// See the [librdkafka documentation](https://github.com/edenhill/librdkafka/blob/master/CONFIGURATION.md) for details.
+    librdkafka_options: Option<HashMap<String, String>>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    on_error: DecodingErrorMode,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+/// Checkpointing of the consumed events to Azure Blob Storage.
+///
+/// Each partition is checkpointed to its own blob, which the partition resumes from when it's
+/// ahead of the offset committed by the consumer group, such as when the consumer group is new.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+pub struct AzureEventHubsCheckpointConfig {
+    /// The connection string of the storage account.
+    ///
+    /// Either `connection_string` or `storage_account` must be set.
+    connection_string: Option<String>,
+
+    /// The name of the storage account, authenticated with Azure's default credentials.
+    ///
+    /// Either `connection_string` or `storage_account` must be set.
+    storage_account: Option<String>,
+
+    /// The name of the container to store the checkpoints in.
+    container_name: String,
+}
+
+fn default_consumer_group() -> String {
+    "$Default".into()
+}
+
+fn default_auto_offset_reset() -> String {
+    "latest".into()
+}
+
+inventory::submit! {
+    SourceDescription::new::<AzureEventHubsSourceConfig>("azure_event_hubs")
+}
+
+impl_generate_config_from_default!(AzureEventHubsSourceConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "azure_event_hubs")]
+impl SourceConfig for AzureEventHubsSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let connection = ConnectionString::parse(&self.connection_string)?;
+        let event_hub = self
+            .event_hub
+            .clone()
+            .or_else(|| connection.entity_path.clone())
+            .ok_or(BuildError::MissingEventHub)?;
+
+        let offset_store = match &self.checkpoint {
+            Some(checkpoint) => {
+                let client = azure_common::config::build_client(
+                    checkpoint.connection_string.clone(),
+                    checkpoint.storage_account.clone(),
+                    checkpoint.container_name.clone(),
+                )?;
+                let store: Arc<dyn OffsetStore> = Arc::new(BlobCheckpointStore {
+                    client,
+                    namespace: connection.namespace.clone(),
+                    consumer_group: self.consumer_group.clone(),
+                });
+                Some(store)
+            }
+            None => None,
+        };
+
+        let options = KafkaSourceOptions {
+            source_type: "azure_event_hubs",
+            offset_store,
+        };
+        self.kafka_config(&connection, event_hub)
+            .build_with_options(cx, options)
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(
+            self.on_error.output_type(self.decoding.output_type()),
+        )]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "azure_event_hubs"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+impl AzureEventHubsSourceConfig {
+    /// Configures the `kafka` source to consume from the Kafka-compatible endpoint of the
+    /// namespace, see
+    ///
+    /// <https://docs.microsoft.com/en-us/azure/event-hubs/event-hubs-kafka-connect-tutorial>
+    fn kafka_config(&self, connection: &ConnectionString, event_hub: String) -> KafkaSourceConfig {
+        KafkaSourceConfig {
+            bootstrap_servers: format!("{}:{}", connection.namespace, KAFKA_PORT),
+            topics: vec![event_hub],
+            group_id: self.consumer_group.clone(),
+            auto_offset_reset: self.auto_offset_reset.clone(),
+            librdkafka_options: self.librdkafka_options.clone(),
+            auth: KafkaAuthConfig {
+                sasl: Some(KafkaSaslConfig {
+                    enabled: Some(true),
+                    username: Some("$ConnectionString".into()),
+                    password: Some(self.connection_string.clone()),
+                    mechanism: Some("PLAIN".into()),
+                }),
+                tls: Some(TlsEnableableConfig {
+                    enabled: Some(true),
+                    options: Default::default(),
+                }),
+            },
+            framing: self.framing.clone(),
+            decoding: self.decoding.clone(),
+            on_error: self.on_error,
+            acknowledgements: self.acknowledgements,
+            ..Default::default()
+        }
+    }
+}
+
+/// The parts of an Event Hubs connection string, such as
+/// `Endpoint=sb://<namespace>.servicebus.windows.net/;SharedAccessKeyName=<name>;SharedAccessKey=<key>;EntityPath=<event hub>`.
+#[derive(Debug, PartialEq)]
+struct ConnectionString {
+    /// The fully qualified host name of the namespace.
+    namespace: String,
+    entity_path: Option<String>,
+}
+
+impl ConnectionString {
+    fn parse(connection_string: &str) -> Result<Self, BuildError> {
+        let mut namespace = None;
+        let mut entity_path = None;
+        for (key, value) in connection_string
+            .split(';')
+            .filter_map(|part| part.trim().split_once('='))
+        {
+            if key.eq_ignore_ascii_case("Endpoint") {
+                let host = value.split_once("://").map_or(value, |(_, host)| host);
+                namespace = Some(host.trim_end_matches('/').to_owned());
+            } else if key.eq_ignore_ascii_case("EntityPath") {
+                entity_path = Some(value.to_owned());
+            }
+        }
+
+        Ok(Self {
+            namespace: namespace
+                .filter(|namespace| !namespace.is_empty())
+                .ok_or(BuildError::MissingEndpoint)?,
+            entity_path: entity_path.filter(|entity_path| !entity_path.is_empty()),
+        })
+    }
+}
+
+/// Stores the offset of the last event consumed from each partition as the content of the
+/// `<namespace>/<event hub>/<consumer group>/checkpoint/<partition>` blob.
+struct BlobCheckpointStore {
+    client: Arc<ContainerClient>,
+    namespace: String,
+    consumer_group: String,
+}
+
+impl BlobCheckpointStore {
+    fn blob_name(&self, event_hub: &str, partition: i32) -> String {
+        format!(
+            "{}/{}/{}/checkpoint/{}",
+            self.namespace, event_hub, self.consumer_group, partition
+        )
+        .to_lowercase()
+    }
+}
+
+#[async_trait::async_trait]
+impl OffsetStore for BlobCheckpointStore {
+    async fn load(&self, event_hub: &str, partition: i32) -> Option<i64> {
+        let blob = self.blob_name(event_hub, partition);
+        let result = Arc::clone(&self.client)
+            .as_blob_client(blob.as_str())
+            .get()
+            .execute()
+            .await;
+
+        match result {
+            Ok(response) => match String::from_utf8_lossy(&response.data).trim().parse() {
+                Ok(offset) => Some(offset),
+                Err(error) => {
+                    emit!(AzureEventHubsCheckpointLoadError {
+                        error: Box::new(error),
+                        partition,
+                    });
+                    None
+                }
+            },
+            Err(error) => {
+                if !matches!(
+                    error.downcast_ref::<HttpError>(),
+                    Some(HttpError::StatusCode {
+                        status: StatusCode::NOT_FOUND,
+                        ..
+                    })
+                ) {
+                    emit!(AzureEventHubsCheckpointLoadError { error, partition });
+                }
+                None
+            }
+        }
+    }
+
+    async fn store(&self, event_hub: &str, partition: i32, offset: i64) {
+        let blob = self.blob_name(event_hub, partition);
+        let result = Arc::clone(&self.client)
+            .as_blob_client(blob.as_str())
+            .put_block_blob(Bytes::from(offset.to_string()))
+            .content_type("text/plain")
+            .execute()
+            .await;
+
+        if let Err(error) = result {
+            emit!(AzureEventHubsCheckpointStoreError { error, partition });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AzureEventHubsSourceConfig>();
+    }
+
+    #[test]
+    fn parses_connection_string() {
+        let connection = ConnectionString::parse(
+            "Endpoint=sb://vector.servicebus.windows.net/;SharedAccessKeyName=listen;SharedAccessKey=a2V5=;EntityPath=logs",
+        )
+        .unwrap();
+
+        assert_eq!(
+            connection,
+            ConnectionString {
+                namespace: "vector.servicebus.windows.net".into(),
+                entity_path: Some("logs".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_namespace_connection_string() {
+        let connection = ConnectionString::parse(
+            "Endpoint=sb://vector.servicebus.windows.net/;SharedAccessKeyName=listen;SharedAccessKey=a2V5=",
+        )
+        .unwrap();
+
+        assert_eq!(connection.namespace, "vector.servicebus.windows.net");
+        assert_eq!(connection.entity_path, None);
+    }
+
+    #[test]
+    fn rejects_connection_string_without_endpoint() {
+        assert!(matches!(
+            ConnectionString::parse("SharedAccessKeyName=listen;SharedAccessKey=a2V5="),
+            Err(BuildError::MissingEndpoint)
+        ));
+    }
+
+    #[test]
+    fn configures_kafka_endpoint() {
+        let config: AzureEventHubsSourceConfig = toml::from_str(
+            r#"
+            connection_string = "Endpoint=sb://vector.servicebus.windows.net/;SharedAccessKeyName=listen;SharedAccessKey=a2V5="
+            event_hub = "logs"
+            consumer_group = "vector"
+            "#,
+        )
+        .unwrap();
+        let connection = ConnectionString::parse(&config.connection_string).unwrap();
+        let kafka = config.kafka_config(&connection, "logs".into());
+
+        assert_eq!(
+            kafka.bootstrap_servers,
+            "vector.servicebus.windows.net:9093"
+        );
+        assert_eq!(kafka.topics, vec!["logs".to_string()]);
+        assert_eq!(kafka.group_id, "vector");
+        assert_eq!(kafka.auto_offset_reset, "latest");
+        let sasl = kafka.auth.sasl.unwrap();
+        assert_eq!(sasl.username.as_deref(), Some("$ConnectionString"));
+        assert_eq!(sasl.password, Some(config.connection_string));
+        assert_eq!(sasl.mechanism.as_deref(), Some("PLAIN"));
+        assert_eq!(kafka.auth.tls.unwrap().enabled, Some(true));
+    }
+}
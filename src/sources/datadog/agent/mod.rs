@@ -19,7 +19,7 @@ use std::{fmt::Debug, io::Read, net::SocketAddr, sync::Arc};
 
 use bytes::{Buf, Bytes};
 use chrono::{serde::ts_milliseconds, DateTime, Utc};
-use codecs::decoding::{DeserializerConfig, FramingConfig};
+use codecs::decoding::{DecodingErrorMode, DeserializerConfig, FramingConfig};
 use flate2::read::{MultiGzDecoder, ZlibDecoder};
 use futures::FutureExt;
 use http::StatusCode;
@@ -100,6 +100,10 @@ pub struct DatadogAgentConfig {
     #[serde(default = "default_decoding")]
     decoding: DeserializerConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    on_error: DecodingErrorMode,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
@@ -113,6 +117,7 @@ impl GenerateConfig for DatadogAgentConfig {
             store_api_key: true,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_error: Default::default(),
             acknowledgements: AcknowledgementsConfig::default(),
             disable_logs: false,
             disable_metrics: false,
@@ -148,7 +153,9 @@ impl SourceConfig for DatadogAgentConfig {
             .clone();
 
         let decoder =
-            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace).build();
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .with_error_mode(self.on_error)
+                .build()?;
 
         let tls = MaybeTlsSettings::from_config(&self.tls, true)?;
         let source = DatadogAgentSource::new(
@@ -221,7 +228,24 @@ impl SourceConfig for DatadogAgentConfig {
                     DeserializerConfig::NativeJson => {
                         self.decoding.schema_definition(log_namespace)
                     }
-                    DeserializerConfig::Gelf => self.decoding.schema_definition(log_namespace),
+                    DeserializerConfig::Gelf { .. } => {
+                        self.decoding.schema_definition(log_namespace)
+                    }
+                    #[cfg(feature = "codecs-avro-registry")]
+                    DeserializerConfig::Avro { .. } => {
+                        self.decoding.schema_definition(log_namespace)
+                    }
+                    DeserializerConfig::Cef => self.decoding.schema_definition(log_namespace),
+                    #[cfg(feature = "codecs-msgpack")]
+                    DeserializerConfig::Msgpack => self.decoding.schema_definition(log_namespace),
+                    #[cfg(feature = "codecs-csv")]
+                    DeserializerConfig::Csv { .. } => {
+                        self.decoding.schema_definition(log_namespace)
+                    }
+                    #[cfg(feature = "codecs-protobuf-dynamic")]
+                    DeserializerConfig::Protobuf { .. } => {
+                        self.decoding.schema_definition(log_namespace)
+                    }
                 }
             }
             LogNamespace::Vector => self
@@ -1465,6 +1465,7 @@ fn test_config_outputs() {
             store_api_key: true,
             framing: default_framing_message_based(),
             decoding,
+            on_error: Default::default(),
             acknowledgements: Default::default(),
             multiple_outputs,
             disable_logs: false,
@@ -3,7 +3,7 @@ use std::task::Poll;
 use bytes::Bytes;
 use chrono::Utc;
 use codecs::{
-    decoding::{DeserializerConfig, FramingConfig},
+    decoding::{DecodingErrorMode, DeserializerConfig, FramingConfig},
     StreamDecodingError,
 };
 use fakedata::logs::*;
@@ -55,6 +55,10 @@ pub struct DemoLogsConfig {
     #[configurable(derived)]
     #[derivative(Default(value = "default_decoding()"))]
     pub decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub on_error: DecodingErrorMode,
 }
 
 const fn default_interval() -> f64 {
@@ -160,6 +164,7 @@ impl DemoLogsConfig {
             },
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_error: Default::default(),
         }
     }
 }
@@ -248,7 +253,8 @@ impl SourceConfig for DemoLogsConfig {
             self.decoding.clone(),
             LogNamespace::Legacy,
         )
-        .build();
+        .with_error_mode(self.on_error)
+        .build()?;
         Ok(Box::pin(demo_logs_source(
             self.interval,
             self.count,
@@ -260,7 +266,9 @@ impl SourceConfig for DemoLogsConfig {
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
-        vec![Output::default(self.decoding.output_type())]
+        vec![Output::default(
+            self.on_error.output_type(self.decoding.output_type()),
+        )]
     }
 
     fn source_type(&self) -> &'static str {
@@ -322,7 +330,8 @@ mod tests {
             default_decoding(),
             LogNamespace::Legacy,
         )
-        .build();
+        .build()
+        .unwrap();
         demo_logs_source(
             config.interval,
             config.count,
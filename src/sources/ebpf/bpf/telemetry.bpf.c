@@ -0,0 +1,253 @@
+// SPDX-License-Identifier: (MPL-2.0 OR GPL-2.0)
+/*
+ * The eBPF programs of the `ebpf` source, which send the process executions, TCP connections and
+ * DNS queries of the host to Vector through a ring buffer.
+ */
+#include "telemetry.h"
+
+#include <bpf/bpf_core_read.h>
+#include <bpf/bpf_endian.h>
+#include <bpf/bpf_helpers.h>
+#include <bpf/bpf_tracing.h>
+
+char LICENSE[] SEC("license") = "Dual MPL/GPL";
+
+/* The kernel types, with only the fields read by the programs. */
+
+struct task_struct {
+	int tgid;
+	struct task_struct *real_parent;
+} __attribute__((preserve_access_index));
+
+struct linux_binprm {
+	const char *filename;
+} __attribute__((preserve_access_index));
+
+struct in6_addr {
+	__u8 addr[16];
+};
+
+struct sock_common {
+	__be32 skc_daddr;
+	__be32 skc_rcv_saddr;
+	__be16 skc_dport;
+	__u16 skc_num;
+	unsigned short skc_family;
+	struct in6_addr skc_v6_daddr;
+	struct in6_addr skc_v6_rcv_saddr;
+} __attribute__((preserve_access_index));
+
+struct sock {
+	struct sock_common __sk_common;
+} __attribute__((preserve_access_index));
+
+struct trace_event_raw_inet_sock_set_state {
+	int oldstate;
+	int newstate;
+	__u16 sport;
+	__u16 dport;
+	__u16 family;
+	__u16 protocol;
+	__u8 saddr[4];
+	__u8 daddr[4];
+	__u8 saddr_v6[16];
+	__u8 daddr_v6[16];
+} __attribute__((preserve_access_index));
+
+struct iovec {
+	void *iov_base;
+	size_t iov_len;
+} __attribute__((preserve_access_index));
+
+enum iter_type {
+	ITER_UBUF,
+};
+
+struct iov_iter {
+	__u8 iter_type;
+	size_t count;
+	const struct iovec *iov;
+	void *ubuf;
+} __attribute__((preserve_access_index));
+
+/* Kernels 6.4 and later renamed the `iov` field of the iterators. */
+struct iov_iter___new {
+	const struct iovec *__iov;
+} __attribute__((preserve_access_index));
+
+struct msghdr {
+	void *msg_name;
+	struct iov_iter msg_iter;
+} __attribute__((preserve_access_index));
+
+struct sockaddr_in {
+	unsigned short sin_family;
+	__be16 sin_port;
+};
+
+struct {
+	__uint(type, BPF_MAP_TYPE_RINGBUF);
+	/* Overridden by Vector with the `ring_buffer_size` option. */
+	__uint(max_entries, 256 * 1024);
+} events SEC(".maps");
+
+static __always_inline struct event *reserve_event(__u32 kind)
+{
+	struct event *event = bpf_ringbuf_reserve(&events, sizeof(*event), 0);
+	if (!event)
+		return NULL;
+
+	event->kind = kind;
+	event->pid = bpf_get_current_pid_tgid() >> 32;
+	event->ppid = 0;
+	event->uid = (__u32)bpf_get_current_uid_gid();
+	bpf_get_current_comm(&event->comm, sizeof(event->comm));
+	event->filename[0] = '\0';
+	event->family = 0;
+	event->sport = 0;
+	event->dport = 0;
+	event->dns_len = 0;
+	return event;
+}
+
+SEC("tp_btf/sched_process_exec")
+int BPF_PROG(handle_exec, struct task_struct *task, int old_pid, struct linux_binprm *bprm)
+{
+	struct event *event = reserve_event(EVENT_PROCESS_EXEC);
+	if (!event)
+		return 0;
+
+	event->pid = BPF_CORE_READ(task, tgid);
+	event->ppid = BPF_CORE_READ(task, real_parent, tgid);
+	bpf_probe_read_kernel_str(&event->filename, sizeof(event->filename),
+				  BPF_CORE_READ(bprm, filename));
+	bpf_ringbuf_submit(event, 0);
+	return 0;
+}
+
+/*
+ * The connections are reported when they are initiated, in the context of the connecting process:
+ * they may not be established afterwards.
+ */
+SEC("tracepoint/sock/inet_sock_set_state")
+int handle_set_state(struct trace_event_raw_inet_sock_set_state *ctx)
+{
+	if (ctx->protocol != IPPROTO_TCP || ctx->oldstate != TCP_CLOSE ||
+	    ctx->newstate != TCP_SYN_SENT)
+		return 0;
+
+	struct event *event = reserve_event(EVENT_TCP_CONNECT);
+	if (!event)
+		return 0;
+
+	event->family = ctx->family;
+	event->sport = ctx->sport;
+	event->dport = ctx->dport;
+	if (ctx->family == AF_INET) {
+		__builtin_memcpy(&event->saddr, ctx->saddr, 4);
+		__builtin_memcpy(&event->daddr, ctx->daddr, 4);
+	} else {
+		__builtin_memcpy(&event->saddr, ctx->saddr_v6, 16);
+		__builtin_memcpy(&event->daddr, ctx->daddr_v6, 16);
+	}
+	bpf_ringbuf_submit(event, 0);
+	return 0;
+}
+
+SEC("fexit/inet_csk_accept")
+int BPF_PROG(handle_accept, struct sock *sk, int flags, int *err, bool kern, struct sock *newsk)
+{
+	if (!newsk)
+		return 0;
+
+	struct event *event = reserve_event(EVENT_TCP_ACCEPT);
+	if (!event)
+		return 0;
+
+	__u16 family = BPF_CORE_READ(newsk, __sk_common.skc_family);
+	event->family = family;
+	event->sport = bpf_ntohs(BPF_CORE_READ(newsk, __sk_common.skc_dport));
+	event->dport = BPF_CORE_READ(newsk, __sk_common.skc_num);
+	if (family == AF_INET) {
+		BPF_CORE_READ_INTO((__be32 *)&event->saddr, newsk, __sk_common.skc_daddr);
+		BPF_CORE_READ_INTO((__be32 *)&event->daddr, newsk, __sk_common.skc_rcv_saddr);
+	} else {
+		BPF_CORE_READ_INTO(&event->saddr, newsk, __sk_common.skc_v6_daddr);
+		BPF_CORE_READ_INTO(&event->daddr, newsk, __sk_common.skc_v6_rcv_saddr);
+	}
+	bpf_ringbuf_submit(event, 0);
+	return 0;
+}
+
+/* Returns the destination port of the datagram, in network byte order. */
+static __always_inline __be16 destination_port(struct sock *sk, struct msghdr *msg)
+{
+	void *name = BPF_CORE_READ(msg, msg_name);
+	if (!name)
+		return BPF_CORE_READ(sk, __sk_common.skc_dport);
+
+	/* The port is at the same offset in `sockaddr_in` and `sockaddr_in6`. */
+	struct sockaddr_in addr = {};
+	bpf_probe_read_kernel(&addr, sizeof(addr), name);
+	return addr.sin_port;
+}
+
+/* Returns the first segment of the payload of the datagram, in user memory. */
+static __always_inline const void *payload(struct msghdr *msg, __u32 *len)
+{
+	struct iov_iter *iter = &msg->msg_iter;
+
+	if (bpf_core_enum_value_exists(enum iter_type, ITER_UBUF) &&
+	    BPF_CORE_READ(iter, iter_type) == bpf_core_enum_value(enum iter_type, ITER_UBUF)) {
+		*len = BPF_CORE_READ(iter, count);
+		return BPF_CORE_READ(iter, ubuf);
+	}
+
+	const struct iovec *iov;
+	if (bpf_core_field_exists(((struct iov_iter___new *)iter)->__iov))
+		iov = BPF_CORE_READ((struct iov_iter___new *)iter, __iov);
+	else
+		iov = BPF_CORE_READ(iter, iov);
+	if (!iov)
+		return NULL;
+
+	*len = BPF_CORE_READ(iov, iov_len);
+	return BPF_CORE_READ(iov, iov_base);
+}
+
+static __always_inline int handle_sendmsg(struct sock *sk, struct msghdr *msg)
+{
+	if (destination_port(sk, msg) != bpf_htons(DNS_PORT))
+		return 0;
+
+	__u32 len = 0;
+	const void *data = payload(msg, &len);
+	if (!data || len == 0)
+		return 0;
+
+	struct event *event = reserve_event(EVENT_DNS_QUERY);
+	if (!event)
+		return 0;
+
+	if (len > MAX_DNS_LEN)
+		len = MAX_DNS_LEN;
+	if (bpf_probe_read_user(&event->dns, len, data) != 0) {
+		bpf_ringbuf_discard(event, 0);
+		return 0;
+	}
+	event->dns_len = len;
+	bpf_ringbuf_submit(event, 0);
+	return 0;
+}
+
+SEC("fentry/udp_sendmsg")
+int BPF_PROG(handle_udp_sendmsg, struct sock *sk, struct msghdr *msg, size_t size)
+{
+	return handle_sendmsg(sk, msg);
+}
+
+SEC("fentry/udpv6_sendmsg")
+int BPF_PROG(handle_udpv6_sendmsg, struct sock *sk, struct msghdr *msg, size_t size)
+{
+	return handle_sendmsg(sk, msg);
+}
@@ -0,0 +1,74 @@
+/* SPDX-License-Identifier: (MPL-2.0 OR GPL-2.0) */
+/*
+ * The types shared by the eBPF programs of the `ebpf` source and Vector.
+ *
+ * The programs are compiled without the kernel headers: the kernel types they read are declared
+ * with only the fields they use, and relocated against the BTF of the running kernel (CO-RE).
+ */
+#ifndef __TELEMETRY_H
+#define __TELEMETRY_H
+
+typedef signed char __s8;
+typedef unsigned char __u8;
+typedef signed short __s16;
+typedef unsigned short __u16;
+typedef signed int __s32;
+typedef unsigned int __u32;
+typedef signed long long __s64;
+typedef unsigned long long __u64;
+typedef __u16 __be16;
+typedef __u32 __be32;
+typedef __u64 __be64;
+typedef __u16 __sum16;
+typedef __u32 __wsum;
+typedef unsigned long size_t;
+typedef _Bool bool;
+
+#define BPF_MAP_TYPE_RINGBUF 27
+
+#define TASK_COMM_LEN 16
+#define MAX_FILENAME_LEN 256
+#define MAX_DNS_LEN 512
+
+#define AF_INET 2
+#define AF_INET6 10
+#define IPPROTO_TCP 6
+
+/* The states of the TCP sockets, from `include/net/tcp_states.h`. */
+#define TCP_SYN_SENT 2
+#define TCP_CLOSE 7
+
+#define DNS_PORT 53
+
+enum event_kind {
+	EVENT_PROCESS_EXEC = 1,
+	EVENT_TCP_CONNECT = 2,
+	EVENT_TCP_ACCEPT = 3,
+	EVENT_DNS_QUERY = 4,
+};
+
+/*
+ * An event sent to Vector through the ring buffer. The layout is decoded by `events.rs`, so both
+ * must be changed together.
+ *
+ * The connections are always from the source address to the destination one: for the accepted
+ * connections, the source is the peer.
+ */
+struct event {
+	__u32 kind;
+	__u32 pid;
+	__u32 ppid;
+	__u32 uid;
+	char comm[TASK_COMM_LEN];
+	char filename[MAX_FILENAME_LEN];
+	__u16 family;
+	__u16 sport;
+	__u16 dport;
+	__u16 _pad;
+	__u8 saddr[16];
+	__u8 daddr[16];
+	__u32 dns_len;
+	__u8 dns[MAX_DNS_LEN];
+};
+
+#endif /* __TELEMETRY_H */
@@ -0,0 +1,116 @@
+//! Parsing of the question of the DNS queries captured by the eBPF programs.
+
+const HEADER_LEN: usize = 12;
+const MAX_LABELS: usize = 128;
+
+pub(super) struct Question {
+    pub(super) id: u16,
+    pub(super) name: String,
+    pub(super) record_type: String,
+}
+
+/// Parses the first question of a DNS query. Returns `None` when the message isn't a query, or
+/// is truncated.
+pub(super) fn parse_question(message: &[u8]) -> Option<Question> {
+    if message.len() < HEADER_LEN {
+        return None;
+    }
+    let id = u16::from_be_bytes([message[0], message[1]]);
+    let is_response = message[2] & 0x80 != 0;
+    let question_count = u16::from_be_bytes([message[4], message[5]]);
+    if is_response || question_count == 0 {
+        return None;
+    }
+
+    let mut labels = Vec::new();
+    let mut offset = HEADER_LEN;
+    loop {
+        let len = *message.get(offset)? as usize;
+        offset += 1;
+        if len == 0 {
+            break;
+        }
+        // The names of the questions are never compressed, as they are the first names of the
+        // message.
+        if len & 0xc0 != 0 || labels.len() == MAX_LABELS {
+            return None;
+        }
+        let label = message.get(offset..offset + len)?;
+        labels.push(String::from_utf8_lossy(label));
+        offset += len;
+    }
+    let record_type = message.get(offset..offset + 2)?;
+    let record_type = u16::from_be_bytes([record_type[0], record_type[1]]);
+
+    Some(Question {
+        id,
+        name: labels.join("."),
+        record_type: record_type_name(record_type)
+            .map_or_else(|| format!("TYPE{}", record_type), Into::into),
+    })
+}
+
+fn record_type_name(record_type: u16) -> Option<&'static str> {
+    Some(match record_type {
+        1 => "A",
+        2 => "NS",
+        5 => "CNAME",
+        6 => "SOA",
+        12 => "PTR",
+        15 => "MX",
+        16 => "TXT",
+        28 => "AAAA",
+        33 => "SRV",
+        35 => "NAPTR",
+        43 => "DS",
+        48 => "DNSKEY",
+        64 => "SVCB",
+        65 => "HTTPS",
+        255 => "ANY",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(name: &[&str], record_type: u16) -> Vec<u8> {
+        let mut message = vec![0x12, 0x34, 0x01, 0x00, 0x00, 0x01, 0, 0, 0, 0, 0, 0];
+        for label in name {
+            message.push(label.len() as u8);
+            message.extend_from_slice(label.as_bytes());
+        }
+        message.push(0);
+        message.extend_from_slice(&record_type.to_be_bytes());
+        message.extend_from_slice(&1u16.to_be_bytes());
+        message
+    }
+
+    #[test]
+    fn parses_queries() {
+        let question = parse_question(&query(&["www", "example", "com"], 28)).unwrap();
+
+        assert_eq!(question.id, 0x1234);
+        assert_eq!(question.name, "www.example.com");
+        assert_eq!(question.record_type, "AAAA");
+    }
+
+    #[test]
+    fn names_unknown_record_types() {
+        let question = parse_question(&query(&["example", "com"], 99)).unwrap();
+
+        assert_eq!(question.record_type, "TYPE99");
+    }
+
+    #[test]
+    fn ignores_responses_and_truncated_queries() {
+        let mut response = query(&["example", "com"], 1);
+        response[2] |= 0x80;
+        assert!(parse_question(&response).is_none());
+
+        let truncated = query(&["example", "com"], 1);
+        assert!(parse_question(&truncated[..truncated.len() - 4]).is_none());
+        assert!(parse_question(&truncated[..8]).is_none());
+    }
+}
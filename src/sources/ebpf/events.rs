@@ -0,0 +1,227 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use bytes::Bytes;
+use chrono::Utc;
+use snafu::Snafu;
+
+use super::{dns, EventType};
+use crate::{config::log_schema, event::LogEvent};
+
+// The layout of `struct event` in `bpf/telemetry.h`.
+const KIND: usize = 0;
+const PID: usize = 4;
+const PPID: usize = 8;
+const UID: usize = 12;
+const COMM: usize = 16;
+const COMM_LEN: usize = 16;
+const FILENAME: usize = COMM + COMM_LEN;
+const FILENAME_LEN: usize = 256;
+const FAMILY: usize = FILENAME + FILENAME_LEN;
+const SPORT: usize = FAMILY + 2;
+const DPORT: usize = SPORT + 2;
+const SADDR: usize = DPORT + 4;
+const DADDR: usize = SADDR + 16;
+const DNS_LEN: usize = DADDR + 16;
+const DNS: usize = DNS_LEN + 4;
+const MAX_DNS_LEN: usize = 512;
+const EVENT_SIZE: usize = DNS + MAX_DNS_LEN;
+
+const AF_INET: u16 = 2;
+const AF_INET6: u16 = 10;
+
+#[derive(Debug, Snafu)]
+pub(super) enum DecodeError {
+    #[snafu(display("Event has {} bytes instead of {}", len, EVENT_SIZE))]
+    InvalidSize { len: usize },
+    #[snafu(display("Unknown event kind {}", kind))]
+    UnknownKind { kind: u32 },
+    #[snafu(display("Unknown address family {}", family))]
+    UnknownFamily { family: u16 },
+}
+
+impl EventType {
+    /// Returns the type of the events of `enum event_kind` in `bpf/telemetry.h`.
+    const fn from_kind(kind: u32) -> Option<Self> {
+        match kind {
+            1 => Some(Self::ProcessExec),
+            2 => Some(Self::TcpConnect),
+            3 => Some(Self::TcpAccept),
+            4 => Some(Self::DnsQuery),
+            _ => None,
+        }
+    }
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::ProcessExec => "process_exec",
+            Self::TcpConnect => "tcp_connect",
+            Self::TcpAccept => "tcp_accept",
+            Self::DnsQuery => "dns_query",
+        }
+    }
+}
+
+/// Decodes an event sent by the eBPF programs into a log. Returns `None` for the DNS queries
+/// that can't be parsed, which are usually other traffic sent to port 53.
+pub(super) fn decode_event(data: &[u8], host: &str) -> Result<Option<LogEvent>, DecodeError> {
+    if data.len() != EVENT_SIZE {
+        return Err(DecodeError::InvalidSize { len: data.len() });
+    }
+    let kind = u32_at(data, KIND);
+    let kind = EventType::from_kind(kind).ok_or(DecodeError::UnknownKind { kind })?;
+
+    let mut log = LogEvent::default();
+    log.insert(log_schema().timestamp_key(), Utc::now());
+    log.insert(log_schema().host_key(), host);
+    log.insert(log_schema().source_type_key(), Bytes::from("ebpf"));
+    log.insert("event_type", kind.as_str());
+    log.insert("process.pid", u32_at(data, PID));
+    log.insert("process.uid", u32_at(data, UID));
+    log.insert("process.name", c_string(&data[COMM..COMM + COMM_LEN]));
+
+    match kind {
+        EventType::ProcessExec => {
+            log.insert("process.ppid", u32_at(data, PPID));
+            log.insert(
+                "process.executable",
+                c_string(&data[FILENAME..FILENAME + FILENAME_LEN]),
+            );
+        }
+        EventType::TcpConnect | EventType::TcpAccept => {
+            let family = u16_at(data, FAMILY);
+            log.insert(
+                "network.source.ip",
+                address(data, SADDR, family)?.to_string(),
+            );
+            log.insert("network.source.port", u16_at(data, SPORT));
+            log.insert(
+                "network.destination.ip",
+                address(data, DADDR, family)?.to_string(),
+            );
+            log.insert("network.destination.port", u16_at(data, DPORT));
+        }
+        EventType::DnsQuery => {
+            let len = (u32_at(data, DNS_LEN) as usize).min(MAX_DNS_LEN);
+            let question = match dns::parse_question(&data[DNS..DNS + len]) {
+                Some(question) => question,
+                None => return Ok(None),
+            };
+            log.insert("dns.id", question.id);
+            log.insert("dns.question.name", question.name);
+            log.insert("dns.question.type", question.record_type);
+        }
+    }
+
+    Ok(Some(log))
+}
+
+fn u16_at(data: &[u8], offset: usize) -> u16 {
+    u16::from_ne_bytes([data[offset], data[offset + 1]])
+}
+
+fn u32_at(data: &[u8], offset: usize) -> u32 {
+    let mut bytes = [0; 4];
+    bytes.copy_from_slice(&data[offset..offset + 4]);
+    u32::from_ne_bytes(bytes)
+}
+
+fn address(data: &[u8], offset: usize, family: u16) -> Result<IpAddr, DecodeError> {
+    match family {
+        AF_INET => {
+            let mut octets = [0; 4];
+            octets.copy_from_slice(&data[offset..offset + 4]);
+            Ok(Ipv4Addr::from(octets).into())
+        }
+        AF_INET6 => {
+            let mut octets = [0; 16];
+            octets.copy_from_slice(&data[offset..offset + 16]);
+            Ok(Ipv6Addr::from(octets).into())
+        }
+        family => Err(DecodeError::UnknownFamily { family }),
+    }
+}
+
+fn c_string(data: &[u8]) -> String {
+    let len = data.iter().position(|c| *c == 0).unwrap_or(data.len());
+    String::from_utf8_lossy(&data[..len]).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: u32) -> Vec<u8> {
+        let mut data = vec![0; EVENT_SIZE];
+        data[KIND..KIND + 4].copy_from_slice(&kind.to_ne_bytes());
+        data[PID..PID + 4].copy_from_slice(&1234u32.to_ne_bytes());
+        data[PPID..PPID + 4].copy_from_slice(&1u32.to_ne_bytes());
+        data[UID..UID + 4].copy_from_slice(&1000u32.to_ne_bytes());
+        data[COMM..COMM + 4].copy_from_slice(b"curl");
+        data
+    }
+
+    #[test]
+    fn decodes_process_executions() {
+        let mut data = event(1);
+        data[FILENAME..FILENAME + 13].copy_from_slice(b"/usr/bin/curl");
+
+        let log = decode_event(&data, "host").unwrap().unwrap();
+
+        assert_eq!(log["event_type"], "process_exec".into());
+        assert_eq!(log["process.pid"], 1234.into());
+        assert_eq!(log["process.ppid"], 1.into());
+        assert_eq!(log["process.uid"], 1000.into());
+        assert_eq!(log["process.name"], "curl".into());
+        assert_eq!(log["process.executable"], "/usr/bin/curl".into());
+        assert_eq!(log[log_schema().host_key()], "host".into());
+        assert_eq!(log[log_schema().source_type_key()], "ebpf".into());
+    }
+
+    #[test]
+    fn decodes_tcp_connections() {
+        let mut data = event(2);
+        data[FAMILY..FAMILY + 2].copy_from_slice(&AF_INET.to_ne_bytes());
+        data[SPORT..SPORT + 2].copy_from_slice(&43210u16.to_ne_bytes());
+        data[DPORT..DPORT + 2].copy_from_slice(&443u16.to_ne_bytes());
+        data[SADDR..SADDR + 4].copy_from_slice(&[10, 0, 0, 1]);
+        data[DADDR..DADDR + 4].copy_from_slice(&[93, 184, 216, 34]);
+
+        let log = decode_event(&data, "host").unwrap().unwrap();
+
+        assert_eq!(log["event_type"], "tcp_connect".into());
+        assert_eq!(log["network.source.ip"], "10.0.0.1".into());
+        assert_eq!(log["network.source.port"], 43210.into());
+        assert_eq!(log["network.destination.ip"], "93.184.216.34".into());
+        assert_eq!(log["network.destination.port"], 443.into());
+        assert!(log.get("process.ppid").is_none());
+    }
+
+    #[test]
+    fn decodes_ipv6_connections() {
+        let mut data = event(3);
+        data[FAMILY..FAMILY + 2].copy_from_slice(&AF_INET6.to_ne_bytes());
+        data[SADDR + 15] = 1;
+        data[DADDR + 15] = 1;
+
+        let log = decode_event(&data, "host").unwrap().unwrap();
+
+        assert_eq!(log["event_type"], "tcp_accept".into());
+        assert_eq!(log["network.source.ip"], "::1".into());
+    }
+
+    #[test]
+    fn rejects_invalid_events() {
+        assert!(matches!(
+            decode_event(&[0; 16], "host"),
+            Err(DecodeError::InvalidSize { len: 16 })
+        ));
+        assert!(matches!(
+            decode_event(&event(9), "host"),
+            Err(DecodeError::UnknownKind { kind: 9 })
+        ));
+        assert!(matches!(
+            decode_event(&event(2), "host"),
+            Err(DecodeError::UnknownFamily { family: 0 })
+        ));
+    }
+}
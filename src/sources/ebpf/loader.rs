@@ -0,0 +1,125 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use libbpf_rs::{
+    skel::{OpenSkel, Skel, SkelBuilder},
+    RingBufferBuilder,
+};
+use nix::{
+    errno::Errno,
+    sys::resource::{setrlimit, Resource},
+};
+use tokio::sync::{mpsc, oneshot};
+
+use super::EventType;
+use crate::internal_events::EbpfReadError;
+
+#[allow(clippy::all, clippy::pedantic, unreachable_pub)]
+mod skel {
+    include!(concat!(env!("OUT_DIR"), "/telemetry.skel.rs"));
+}
+
+use self::skel::{TelemetrySkel, TelemetrySkelBuilder};
+
+/// How long to wait for events before checking whether the source is stopping.
+const POLL_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Loads and attaches the eBPF programs emitting the given types of events, and sends the events
+/// they write to the ring buffer to `out` until the source stops.
+///
+/// The programs are handled by a dedicated thread, as the handles of libbpf can't be shared
+/// between threads. Returns once they are attached, with the errors preventing it.
+pub(super) async fn spawn(
+    events: Vec<EventType>,
+    ring_buffer_size: u32,
+    out: mpsc::Sender<Vec<u8>>,
+    stopped: Arc<AtomicBool>,
+) -> crate::Result<thread::JoinHandle<()>> {
+    let (attached, attach_result) = oneshot::channel();
+    let handle = thread::Builder::new().name("ebpf".into()).spawn(move || {
+        let skel = match load(&events, ring_buffer_size) {
+            Ok(skel) => {
+                let _ = attached.send(Ok(()));
+                skel
+            }
+            Err(error) => {
+                let _ = attached.send(Err(error));
+                return;
+            }
+        };
+        poll(&skel, &out, &stopped);
+    })?;
+
+    match attach_result.await {
+        Ok(Ok(())) => Ok(handle),
+        Ok(Err(error)) => Err(format!("Unable to load the eBPF programs: {}", error).into()),
+        Err(_) => Err("The eBPF programs loader stopped unexpectedly".into()),
+    }
+}
+
+fn load(events: &[EventType], ring_buffer_size: u32) -> libbpf_rs::Result<TelemetrySkel<'static>> {
+    // Kernels before 5.11 account the memory of the maps against the locked memory limit.
+    if let Err(error) = setrlimit(Resource::RLIMIT_MEMLOCK, None, None) {
+        warn!(message = "Unable to remove the locked memory limit.", %error);
+    }
+
+    let mut open = TelemetrySkelBuilder::default().open()?;
+    open.maps_mut().events().set_max_entries(ring_buffer_size)?;
+    let mut progs = open.progs_mut();
+    progs
+        .handle_exec()
+        .set_autoload(events.contains(&EventType::ProcessExec))?;
+    progs
+        .handle_set_state()
+        .set_autoload(events.contains(&EventType::TcpConnect))?;
+    progs
+        .handle_accept()
+        .set_autoload(events.contains(&EventType::TcpAccept))?;
+    let dns = events.contains(&EventType::DnsQuery);
+    progs.handle_udp_sendmsg().set_autoload(dns)?;
+    progs.handle_udpv6_sendmsg().set_autoload(dns)?;
+
+    let mut skel = open.load()?;
+    skel.attach()?;
+    Ok(skel)
+}
+
+fn poll(skel: &TelemetrySkel, out: &mpsc::Sender<Vec<u8>>, stopped: &AtomicBool) {
+    let maps = skel.maps();
+    let mut builder = RingBufferBuilder::new();
+    let ring_buffer = builder
+        .add(maps.events(), |data: &[u8]| {
+            // Stops the polling when the source is gone.
+            match out.blocking_send(data.to_vec()) {
+                Ok(()) => 0,
+                Err(_) => -1,
+            }
+        })
+        .and_then(|builder| builder.build());
+    let ring_buffer = match ring_buffer {
+        Ok(ring_buffer) => ring_buffer,
+        Err(error) => {
+            emit!(EbpfReadError { error: &error });
+            return;
+        }
+    };
+
+    while !stopped.load(Ordering::Relaxed) && !out.is_closed() {
+        match ring_buffer.poll(POLL_TIMEOUT) {
+            Ok(()) => {}
+            Err(libbpf_rs::Error::System(errno)) if errno == Errno::EINTR as i32 => {}
+            Err(error) => {
+                if !out.is_closed() {
+                    emit!(EbpfReadError { error: &error });
+                }
+                return;
+            }
+        }
+    }
+}
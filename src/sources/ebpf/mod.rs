@@ -0,0 +1,240 @@
+//! The `ebpf` source.
+//!
+//! The eBPF programs in `bpf/telemetry.bpf.c` are compiled into a CO-RE object when Vector is
+//! built, and embedded in Vector. They are loaded and attached when the source starts, and write
+//! the process executions, TCP connections and DNS queries of the host to a ring buffer, which is
+//! polled from a dedicated thread. The events are decoded into logs following the field names of
+//! the Elastic Common Schema.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use snafu::Snafu;
+use tokio::{sync::mpsc, task::spawn_blocking};
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, ByteSizeOf};
+
+use crate::{
+    config::{DataType, GenerateConfig, Output, SourceConfig, SourceContext, SourceDescription},
+    event::LogEvent,
+    internal_events::{BytesReceived, EbpfDecodeError, OldEventsReceived, StreamClosedError},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+mod dns;
+mod events;
+mod loader;
+
+/// The maximum number of events sent at once.
+const MAX_BATCH_SIZE: usize = 1000;
+
+/// The smallest ring buffer accepted by the kernel, which is the size of a page.
+const MIN_RING_BUFFER_SIZE: u32 = 4096;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("At least one type of events must be configured"))]
+    NoEvents,
+    #[snafu(display(
+        "The ring buffer size must be a power of two of at least {} bytes, got {}",
+        MIN_RING_BUFFER_SIZE,
+        size
+    ))]
+    InvalidRingBufferSize { size: u32 },
+}
+
+/// A type of events collected by the eBPF programs.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EventType {
+    /// The executions of programs by the processes.
+    ProcessExec,
+
+    /// The outgoing TCP connections, reported when they are initiated.
+    TcpConnect,
+
+    /// The incoming TCP connections, reported when they are accepted.
+    TcpAccept,
+
+    /// The DNS queries sent over UDP.
+    DnsQuery,
+}
+
+/// Configuration for the `ebpf` source.
+#[configurable_component(source)]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct EbpfConfig {
+    /// The types of events to collect.
+    ///
+    /// Only the eBPF programs collecting these events are loaded.
+    #[serde(default = "default_events")]
+    #[derivative(Default(value = "default_events()"))]
+    pub events: Vec<EventType>,
+
+    /// The size of the ring buffer the events are written to by the eBPF programs, in bytes.
+    ///
+    /// Must be a power of two. The events are dropped by the eBPF programs while the ring buffer
+    /// is full.
+    #[serde(default = "default_ring_buffer_size")]
+    #[derivative(Default(value = "default_ring_buffer_size()"))]
+    pub ring_buffer_size: u32,
+}
+
+fn default_events() -> Vec<EventType> {
+    vec![
+        EventType::ProcessExec,
+        EventType::TcpConnect,
+        EventType::TcpAccept,
+        EventType::DnsQuery,
+    ]
+}
+
+const fn default_ring_buffer_size() -> u32 {
+    1024 * 1024
+}
+
+inventory::submit! {
+    SourceDescription::new::<EbpfConfig>("ebpf")
+}
+
+impl_generate_config_from_default!(EbpfConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "ebpf")]
+impl SourceConfig for EbpfConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.events.is_empty() {
+            return Err(BuildError::NoEvents.into());
+        }
+        let size = self.ring_buffer_size;
+        if size < MIN_RING_BUFFER_SIZE || !size.is_power_of_two() {
+            return Err(BuildError::InvalidRingBufferSize { size }.into());
+        }
+
+        let host = crate::get_hostname()?;
+        let (sender, receiver) = mpsc::channel(MAX_BATCH_SIZE);
+        let stopped = Arc::new(AtomicBool::new(false));
+        let poller = loader::spawn(self.events.clone(), size, sender, Arc::clone(&stopped)).await?;
+
+        let source = EbpfSource { host, out: cx.out };
+        Ok(Box::pin(async move {
+            let result = source.run(receiver, cx.shutdown).await;
+            stopped.store(true, Ordering::Relaxed);
+            // The programs are detached once the polling thread is done.
+            if spawn_blocking(move || poller.join()).await.is_err() {
+                error!(message = "eBPF polling thread panicked.");
+            }
+            result
+        }))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "ebpf"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+struct EbpfSource {
+    host: String,
+    out: SourceSender,
+}
+
+impl EbpfSource {
+    async fn run(
+        mut self,
+        mut receiver: mpsc::Receiver<Vec<u8>>,
+        mut shutdown: ShutdownSignal,
+    ) -> Result<(), ()> {
+        loop {
+            let data = tokio::select! {
+                _ = &mut shutdown => break,
+                data = receiver.recv() => match data {
+                    Some(data) => data,
+                    None => break,
+                },
+            };
+
+            let mut batch = vec![data];
+            while batch.len() < MAX_BATCH_SIZE {
+                match receiver.try_recv() {
+                    Ok(data) => batch.push(data),
+                    Err(_) => break,
+                }
+            }
+            if !self.handle_batch(batch).await {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sends the events of the batch. Returns `false` when the events can no longer be sent.
+    async fn handle_batch(&mut self, batch: Vec<Vec<u8>>) -> bool {
+        emit!(BytesReceived {
+            byte_size: batch.iter().map(Vec::len).sum(),
+            protocol: "ebpf",
+        });
+
+        let events = batch
+            .iter()
+            .filter_map(|data| match events::decode_event(data, &self.host) {
+                Ok(log) => log,
+                Err(error) => {
+                    emit!(EbpfDecodeError { error: &error });
+                    None
+                }
+            })
+            .collect::<Vec<LogEvent>>();
+        if events.is_empty() {
+            return true;
+        }
+
+        let count = events.len();
+        emit!(OldEventsReceived {
+            count,
+            byte_size: events.size_of(),
+        });
+        if let Err(error) = self.out.send_batch(events).await {
+            emit!(StreamClosedError { error, count });
+            return false;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<EbpfConfig>();
+    }
+
+    #[test]
+    fn parses_config() {
+        let config: EbpfConfig = toml::from_str(
+            r#"
+            events = ["process_exec", "dns_query"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.events, [EventType::ProcessExec, EventType::DnsQuery]);
+        assert_eq!(config.ring_buffer_size, 1024 * 1024);
+    }
+}
@@ -7,7 +7,7 @@ use std::{
 use bytes::Bytes;
 use chrono::Utc;
 use codecs::{
-    decoding::{DeserializerConfig, FramingConfig},
+    decoding::{DecodingErrorMode, DeserializerConfig, FramingConfig},
     StreamDecodingError,
 };
 use futures::{FutureExt, StreamExt};
@@ -76,6 +76,10 @@ pub struct ExecConfig {
     #[configurable(derived)]
     #[serde(default = "default_decoding")]
     decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    on_error: DecodingErrorMode,
 }
 
 /// Mode of operation for running the command.
@@ -138,6 +142,7 @@ impl Default for ExecConfig {
             maximum_buffer_size_bytes: default_maximum_buffer_size(),
             framing: None,
             decoding: default_decoding(),
+            on_error: Default::default(),
         }
     }
 }
@@ -228,8 +233,9 @@ impl SourceConfig for ExecConfig {
             .framing
             .clone()
             .unwrap_or_else(|| self.decoding.default_stream_framing());
-        let decoder =
-            DecodingConfig::new(framing, self.decoding.clone(), LogNamespace::Legacy).build();
+        let decoder = DecodingConfig::new(framing, self.decoding.clone(), LogNamespace::Legacy)
+            .with_error_mode(self.on_error)
+            .build()?;
 
         match &self.mode {
             Mode::Scheduled => {
@@ -262,7 +268,9 @@ impl SourceConfig for ExecConfig {
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
-        vec![Output::default(self.decoding.output_type())]
+        vec![Output::default(
+            self.on_error.output_type(self.decoding.output_type()),
+        )]
     }
 
     fn source_type(&self) -> &'static str {
@@ -651,6 +659,7 @@ mod tests {
             maximum_buffer_size_bytes: default_maximum_buffer_size(),
             framing: None,
             decoding: default_decoding(),
+            on_error: Default::default(),
         };
 
         let command = build_command(&config);
@@ -763,6 +772,7 @@ mod tests {
             maximum_buffer_size_bytes: default_maximum_buffer_size(),
             framing: None,
             decoding: default_decoding(),
+            on_error: Default::default(),
         }
     }
 }
@@ -0,0 +1,337 @@
+//! The handshake phase of the forward protocol, used to authenticate clients with a shared key and,
+//! optionally, a username and password.
+//!
+//! https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1#handshake-messages
+
+use std::io;
+
+use bytes::BytesMut;
+use rmpv::Value;
+use sha2::{Digest, Sha512};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use vector_config::configurable_component;
+
+use super::DecodeError;
+
+// The PING message is small, so anything larger than this is not a valid handshake.
+const MAX_PING_LEN: usize = 64 * 1024;
+
+/// Forward protocol handshake configuration.
+///
+/// When set, clients must authenticate with the shared key, as done by the `<security>` section of
+/// fluentd and the `Shared_Key` option of fluent-bit, before sending any events.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct FluentSecurityConfig {
+    /// The key shared with the clients.
+    shared_key: String,
+
+    /// The hostname sent to clients in the handshake.
+    ///
+    /// Defaults to the hostname of the machine Vector is running on.
+    self_hostname: Option<String>,
+
+    /// The users clients must authenticate as, in addition to the shared key.
+    ///
+    /// If empty, clients are not required to authenticate as a user.
+    #[serde(default)]
+    users: Vec<FluentUserConfig>,
+}
+
+/// A user clients can authenticate as.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct FluentUserConfig {
+    /// The username of the user.
+    username: String,
+
+    /// The password of the user.
+    password: String,
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct FluentHandshake {
+    shared_key: String,
+    self_hostname: String,
+    users: Vec<FluentUserConfig>,
+}
+
+impl FluentHandshake {
+    pub(super) fn from_config(config: &FluentSecurityConfig) -> crate::Result<Self> {
+        let self_hostname = match &config.self_hostname {
+            Some(hostname) => hostname.clone(),
+            None => crate::get_hostname()?,
+        };
+        Ok(Self {
+            shared_key: config.shared_key.clone(),
+            self_hostname,
+            users: config.users.clone(),
+        })
+    }
+
+    /// Sends the HELO message and validates the PING answering it, answering in turn with a PONG.
+    ///
+    /// Returns an error if the client failed to authenticate, after letting it know why.
+    pub(super) async fn perform<S>(&self, socket: &mut S) -> Result<(), DecodeError>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let nonce = rand::random::<[u8; 16]>();
+        let auth_salt = rand::random::<[u8; 16]>();
+
+        let auth = if self.users.is_empty() {
+            Value::from("")
+        } else {
+            Value::from(&auth_salt[..])
+        };
+        let helo = Value::Array(vec![
+            "HELO".into(),
+            Value::Map(vec![
+                ("nonce".into(), Value::from(&nonce[..])),
+                ("auth".into(), auth),
+                ("keepalive".into(), true.into()),
+            ]),
+        ]);
+        write_message(socket, &helo).await?;
+
+        let ping = read_message(socket).await?;
+        let result = self.validate_ping(&ping, &nonce, &auth_salt);
+        let (reason, shared_key_digest) = match result {
+            Ok(shared_key_salt) => (
+                "",
+                self.shared_key_digest(shared_key_salt, self.self_hostname.as_bytes(), &nonce),
+            ),
+            Err(reason) => (reason, String::new()),
+        };
+
+        let pong = Value::Array(vec![
+            "PONG".into(),
+            result.is_ok().into(),
+            reason.into(),
+            self.self_hostname.as_str().into(),
+            shared_key_digest.into(),
+        ]);
+        write_message(socket, &pong).await?;
+
+        result.map(|_| ()).map_err(DecodeError::Handshake)
+    }
+
+    /// Validates the PING message, returning the shared key salt chosen by the client.
+    fn validate_ping<'a>(
+        &self,
+        ping: &'a Value,
+        nonce: &[u8],
+        auth_salt: &[u8],
+    ) -> Result<&'a [u8], &'static str> {
+        let fields = match ping.as_array().map(Vec::as_slice) {
+            Some([kind, fields @ ..]) if kind.as_str() == Some("PING") => {
+                fields.iter().map(value_bytes).collect::<Option<Vec<_>>>()
+            }
+            _ => return Err("expected a PING message"),
+        };
+        let [hostname, shared_key_salt, shared_key_digest, username, password_digest] =
+            match fields.and_then(|fields| <[&[u8]; 5]>::try_from(fields.as_slice()).ok()) {
+                Some(fields) => fields,
+                None => return Err("invalid PING message"),
+            };
+
+        if hostname == self.self_hostname.as_bytes() {
+            return Err("same hostname between input and output: invalid configuration");
+        }
+        if shared_key_digest
+            != self
+                .shared_key_digest(shared_key_salt, hostname, nonce)
+                .as_bytes()
+        {
+            return Err("shared_key mismatch");
+        }
+
+        if !self.users.is_empty() {
+            let authenticated = self.users.iter().any(|user| {
+                user.username.as_bytes() == username
+                    && password_digest
+                        == hex_digest(&[
+                            auth_salt,
+                            user.username.as_bytes(),
+                            user.password.as_bytes(),
+                        ])
+                        .as_bytes()
+            });
+            if !authenticated {
+                return Err("username/password mismatch");
+            }
+        }
+
+        Ok(shared_key_salt)
+    }
+
+    fn shared_key_digest(&self, salt: &[u8], hostname: &[u8], nonce: &[u8]) -> String {
+        hex_digest(&[salt, hostname, nonce, self.shared_key.as_bytes()])
+    }
+}
+
+/// Both strings and binaries are accepted, as clients differ in which they use.
+fn value_bytes(value: &Value) -> Option<&[u8]> {
+    match value {
+        Value::String(string) => Some(string.as_bytes()),
+        Value::Binary(bytes) => Some(bytes),
+        _ => None,
+    }
+}
+
+fn hex_digest(parts: &[&[u8]]) -> String {
+    let mut hasher = Sha512::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hex::encode(hasher.finalize())
+}
+
+async fn write_message<S>(socket: &mut S, message: &Value) -> Result<(), DecodeError>
+where
+    S: AsyncWrite + Unpin,
+{
+    let mut buf = Vec::new();
+    rmpv::encode::write_value(&mut buf, message).expect("writing to a Vec can't fail");
+    socket.write_all(&buf).await?;
+    Ok(())
+}
+
+async fn read_message<S>(socket: &mut S) -> Result<Value, DecodeError>
+where
+    S: AsyncRead + Unpin,
+{
+    let mut buf = BytesMut::new();
+    loop {
+        if socket.read_buf(&mut buf).await? == 0 {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+
+        let mut cursor = io::Cursor::new(&buf[..]);
+        match rmpv::decode::read_value(&mut cursor) {
+            // Clients wait for the PONG before sending events, so there can't be any data following.
+            Ok(value) if cursor.position() as usize == buf.len() => return Ok(value),
+            Ok(_) => return Err(DecodeError::Handshake("unexpected data after PING message")),
+            Err(
+                rmpv::decode::Error::InvalidMarkerRead(error)
+                | rmpv::decode::Error::InvalidDataRead(error),
+            ) if error.kind() == io::ErrorKind::UnexpectedEof => {
+                if buf.len() >= MAX_PING_LEN {
+                    return Err(DecodeError::Handshake("PING message is too large"));
+                }
+            }
+            Err(error) => return Err(io::Error::new(io::ErrorKind::InvalidData, error).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{duplex, DuplexStream};
+
+    use super::*;
+
+    fn handshake(users: Vec<FluentUserConfig>) -> FluentHandshake {
+        FluentHandshake {
+            shared_key: "secret".to_owned(),
+            self_hostname: "server".to_owned(),
+            users,
+        }
+    }
+
+    /// Answers the HELO as a client would, returning the PONG.
+    async fn ping(
+        client: &mut DuplexStream,
+        shared_key: &str,
+        user: Option<(&str, &str)>,
+    ) -> Vec<Value> {
+        let helo = read_message(client).await.unwrap();
+        let options = helo.as_array().unwrap()[1].as_map().unwrap().clone();
+        let option = |key: &str| {
+            options
+                .iter()
+                .find(|(k, _)| k.as_str() == Some(key))
+                .and_then(|(_, v)| value_bytes(v))
+                .unwrap()
+                .to_vec()
+        };
+        let (nonce, auth_salt) = (option("nonce"), option("auth"));
+
+        let (username, password_digest) = match user {
+            Some((username, password)) => (
+                username,
+                hex_digest(&[&auth_salt, username.as_bytes(), password.as_bytes()]),
+            ),
+            None => ("", String::new()),
+        };
+        let ping = Value::Array(vec![
+            "PING".into(),
+            "client".into(),
+            "salt".into(),
+            hex_digest(&[b"salt", b"client", &nonce, shared_key.as_bytes()]).into(),
+            username.into(),
+            password_digest.into(),
+        ]);
+        write_message(client, &ping).await.unwrap();
+
+        let pong = read_message(client).await.unwrap();
+        pong.as_array().unwrap().clone()
+    }
+
+    async fn run(
+        handshake: FluentHandshake,
+        shared_key: &str,
+        user: Option<(&str, &str)>,
+    ) -> (Result<(), DecodeError>, Vec<Value>) {
+        let (mut client, mut server) = duplex(1024);
+        let server = tokio::spawn(async move { handshake.perform(&mut server).await });
+        let pong = ping(&mut client, shared_key, user).await;
+        (server.await.unwrap(), pong)
+    }
+
+    #[tokio::test]
+    async fn accepts_shared_key() {
+        let (result, pong) = run(handshake(vec![]), "secret", None).await;
+        assert!(result.is_ok());
+        assert_eq!(pong[0].as_str(), Some("PONG"));
+        assert_eq!(pong[1].as_bool(), Some(true));
+        assert_eq!(pong[3].as_str(), Some("server"));
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_shared_key() {
+        let (result, pong) = run(handshake(vec![]), "wrong", None).await;
+        assert!(matches!(
+            result,
+            Err(DecodeError::Handshake("shared_key mismatch"))
+        ));
+        assert_eq!(pong[1].as_bool(), Some(false));
+        assert_eq!(pong[2].as_str(), Some("shared_key mismatch"));
+    }
+
+    #[tokio::test]
+    async fn authenticates_users() {
+        let users = vec![FluentUserConfig {
+            username: "alice".to_owned(),
+            password: "hunter2".to_owned(),
+        }];
+
+        let (result, _) = run(
+            handshake(users.clone()),
+            "secret",
+            Some(("alice", "hunter2")),
+        )
+        .await;
+        assert!(result.is_ok());
+
+        let (result, pong) =
+            run(handshake(users.clone()), "secret", Some(("alice", "wrong"))).await;
+        assert!(result.is_err());
+        assert_eq!(pong[2].as_str(), Some("username/password mismatch"));
+
+        let (result, _) = run(handshake(users), "secret", None).await;
+        assert!(result.is_err());
+    }
+}
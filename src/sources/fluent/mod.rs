@@ -4,10 +4,12 @@ use std::net::SocketAddr;
 use bytes::{Buf, Bytes, BytesMut};
 use codecs::StreamDecodingError;
 use flate2::read::MultiGzDecoder;
+use futures::{future::BoxFuture, FutureExt};
 use lookup::path;
 use rmp_serde::{decode, Deserializer};
 use serde::Deserialize;
 use smallvec::{smallvec, SmallVec};
+use tokio::net::TcpStream;
 use tokio_util::codec::Decoder;
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
@@ -19,13 +21,16 @@ use crate::{
         SourceConfig, SourceContext, SourceDescription,
     },
     event::{Event, LogEvent},
-    internal_events::{FluentMessageDecodeError, FluentMessageReceived},
+    internal_events::{FluentHandshakeError, FluentMessageDecodeError, FluentMessageReceived},
     serde::bool_or_struct,
     tcp::TcpKeepaliveConfig,
-    tls::{MaybeTlsSettings, TlsSourceConfig},
+    tls::{MaybeTlsIncomingStream, MaybeTlsSettings, TlsClientAuthorization, TlsSourceConfig},
 };
 
+mod handshake;
 mod message;
+use self::handshake::FluentHandshake;
+pub use self::handshake::{FluentSecurityConfig, FluentUserConfig};
 use self::message::{FluentEntry, FluentMessage, FluentRecord, FluentTag, FluentTimestamp};
 
 /// Configuration for the `fluent` source.
@@ -38,6 +43,14 @@ pub struct FluentConfig {
     /// The maximum number of TCP connections that will be allowed at any given time.
     connection_limit: Option<u32>,
 
+    /// Whether to expect each connection to start with a PROXY protocol header.
+    ///
+    /// Load balancers such as HAProxy send this header to pass on the address of the original client,
+    /// which is then used as the peer address of the connection. Both version 1 and 2 of the protocol
+    /// are supported. Connections not starting with a valid header are closed.
+    #[serde(default)]
+    proxy_protocol: bool,
+
     #[configurable(derived)]
     keepalive: Option<TcpKeepaliveConfig>,
 
@@ -49,6 +62,9 @@ pub struct FluentConfig {
     #[configurable(derived)]
     tls: Option<TlsSourceConfig>,
 
+    #[configurable(derived)]
+    security: Option<FluentSecurityConfig>,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
@@ -64,9 +80,11 @@ impl GenerateConfig for FluentConfig {
             address: SocketListenAddr::SocketAddr("0.0.0.0:24224".parse().unwrap()),
             keepalive: None,
             tls: None,
+            security: None,
             receive_buffer_bytes: None,
             acknowledgements: Default::default(),
             connection_limit: Some(2),
+            proxy_protocol: false,
         })
         .unwrap()
     }
@@ -76,13 +94,19 @@ impl GenerateConfig for FluentConfig {
 #[typetag::serde(name = "fluent")]
 impl SourceConfig for FluentConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
-        let source = FluentSource {};
+        let handshake = self
+            .security
+            .as_ref()
+            .map(FluentHandshake::from_config)
+            .transpose()?;
+        let source = FluentSource { handshake };
         let shutdown_secs = 30;
         let tls_config = self.tls.as_ref().map(|tls| tls.tls_config.clone());
         let tls_client_metadata_key = self
             .tls
             .as_ref()
             .and_then(|tls| tls.client_metadata_key.clone());
+        let tls_client_authorization = TlsClientAuthorization::from_config(&self.tls)?;
         let tls = MaybeTlsSettings::from_config(&tls_config, true)?;
         source.run(
             self.address,
@@ -90,10 +114,12 @@ impl SourceConfig for FluentConfig {
             shutdown_secs,
             tls,
             tls_client_metadata_key,
+            tls_client_authorization,
             self.receive_buffer_bytes,
             cx,
             self.acknowledgements,
             self.connection_limit,
+            self.proxy_protocol,
         )
     }
 
@@ -115,7 +141,9 @@ impl SourceConfig for FluentConfig {
 }
 
 #[derive(Debug, Clone)]
-struct FluentSource;
+struct FluentSource {
+    handshake: Option<FluentHandshake>,
+}
 
 impl TcpSource for FluentSource {
     type Error = DecodeError;
@@ -127,6 +155,22 @@ impl TcpSource for FluentSource {
         FluentDecoder::new()
     }
 
+    fn handshake<'a>(
+        &'a self,
+        socket: &'a mut MaybeTlsIncomingStream<TcpStream>,
+    ) -> BoxFuture<'a, Result<(), Self::Error>> {
+        async move {
+            if let Some(handshake) = &self.handshake {
+                handshake.perform(socket).await.map_err(|error| {
+                    emit!(FluentHandshakeError { error: &error });
+                    error
+                })?;
+            }
+            Ok(())
+        }
+        .boxed()
+    }
+
     fn handle_events(&self, events: &mut [Event], host: SocketAddr) {
         for event in events {
             let log = event.as_mut_log();
@@ -148,6 +192,7 @@ pub enum DecodeError {
     Decode(decode::Error),
     UnknownCompression(String),
     UnexpectedValue(rmpv::Value),
+    Handshake(&'static str),
 }
 
 impl std::fmt::Display for DecodeError {
@@ -161,6 +206,7 @@ impl std::fmt::Display for DecodeError {
             DecodeError::UnexpectedValue(value) => {
                 write!(f, "unexpected msgpack value, ignoring: {}", value)
             }
+            DecodeError::Handshake(reason) => write!(f, "handshake failed: {}", reason),
         }
     }
 }
@@ -172,6 +218,7 @@ impl StreamDecodingError for DecodeError {
             DecodeError::Decode(_) => true,
             DecodeError::UnknownCompression(_) => true,
             DecodeError::UnexpectedValue(_) => true,
+            DecodeError::Handshake(_) => false,
         }
     }
 }
@@ -877,10 +924,12 @@ mod tests {
         let source = FluentConfig {
             address: address.into(),
             tls: None,
+            security: None,
             keepalive: None,
             receive_buffer_bytes: None,
             acknowledgements: true.into(),
             connection_limit: None,
+            proxy_protocol: false,
         }
         .build(SourceContext::new_test(sender, None))
         .await
@@ -1126,10 +1175,12 @@ mod integration_tests {
             FluentConfig {
                 address: address.into(),
                 tls: None,
+                security: None,
                 keepalive: None,
                 receive_buffer_bytes: None,
                 acknowledgements: false.into(),
                 connection_limit: None,
+                proxy_protocol: false,
             }
             .build(SourceContext::new_test(sender, None))
             .await
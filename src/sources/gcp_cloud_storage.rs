@@ -0,0 +1,731 @@
+//! Ingests objects from Google Cloud Storage buckets, driven by the bucket's Pub/Sub
+//! notifications. This is the GCP counterpart of the `aws_s3` source's SQS strategy.
+use std::{collections::HashMap, io::ErrorKind, time::Duration};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use codecs::{
+    decoding::{DecodingErrorMode, DeserializerConfig, FramingConfig},
+    NewlineDelimitedDecoderConfig,
+};
+use derivative::Derivative;
+use futures::{StreamExt, TryStreamExt};
+use http::{header::CONTENT_TYPE, Request, StatusCode};
+use hyper::Body;
+use lookup::path;
+use serde::Deserialize;
+use snafu::{ResultExt, Snafu};
+use tokio_util::{codec::FramedRead, io::StreamReader};
+use vector_common::internal_event::EventsReceived;
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, ByteSizeOf};
+
+use crate::{
+    codecs::{Decoder, DecodingConfig},
+    config::{log_schema, AcknowledgementsConfig, DataType, Output, SourceConfig, SourceContext},
+    event::{BatchNotifier, BatchStatus, MaybeAsLogMut},
+    gcp::{GcpAuthConfig, GcpAuthenticator, Scope, PUBSUB_URL},
+    http::HttpClient,
+    internal_events::{
+        BytesReceived, GcsNotificationAcknowledgeError, GcsNotificationIgnored,
+        GcsNotificationPullError, GcsObjectProcessingError, StreamClosedError,
+    },
+    serde::{bool_or_struct, default_decoding},
+    shutdown::ShutdownSignal,
+    tls::{TlsConfig, TlsSettings},
+    SourceSender,
+};
+
+const STORAGE_URL: &str = "https://storage.googleapis.com";
+
+/// The notification event type sent once a new object (or a new generation of an existing
+/// object) has been successfully created in the bucket.
+const OBJECT_FINALIZE: &str = "OBJECT_FINALIZE";
+
+/// Compression scheme for objects retrieved from Cloud Storage.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, PartialEq)]
+#[serde(rename_all = "lowercase")]
+#[derivative(Default)]
+pub enum Compression {
+    /// Automatically attempt to determine the compression scheme.
+    ///
+    /// Vector will try to determine the compression scheme of the object from its: `Content-Encoding` and
+    /// `Content-Type` metadata, as well as the object name suffix (e.g. `.gz`).
+    ///
+    /// It will fallback to 'none' if the compression scheme cannot be determined.
+    #[derivative(Default)]
+    Auto,
+    /// Uncompressed.
+    None,
+    /// GZIP.
+    Gzip,
+    /// ZSTD.
+    Zstd,
+}
+
+/// Configuration for the `gcp_cloud_storage` source.
+#[configurable_component(source)]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct GcsSourceConfig {
+    /// The project which owns the Pub/Sub subscription.
+    pub project: String,
+
+    /// The Pub/Sub subscription receiving the bucket's notifications.
+    ///
+    /// The notifications must be configured with the `JSON_API_V1` or `NONE` payload format.
+    pub subscription: String,
+
+    /// The endpoint from which to pull bucket notifications.
+    pub endpoint: Option<String>,
+
+    /// The endpoint from which to download objects.
+    pub storage_endpoint: Option<String>,
+
+    #[serde(flatten)]
+    pub auth: GcpAuthConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    /// The maximum number of notifications to pull in a single request.
+    #[serde(default = "default_max_messages")]
+    #[derivative(Default(value = "default_max_messages()"))]
+    pub max_messages: u32,
+
+    /// How long to wait, in seconds, before pulling again when the subscription had no
+    /// notifications or the previous pull failed.
+    #[serde(default = "default_poll_secs")]
+    #[derivative(Default(value = "default_poll_secs()"))]
+    pub poll_secs: u32,
+
+    /// The compression scheme used for decompressing objects retrieved from Cloud Storage.
+    #[serde(default)]
+    pub compression: Compression,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_stream_based")]
+    #[derivative(Default(value = "default_framing_stream_based()"))]
+    pub framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    pub decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub on_error: DecodingErrorMode,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+const fn default_max_messages() -> u32 {
+    10
+}
+
+const fn default_poll_secs() -> u32 {
+    15
+}
+
+fn default_framing_stream_based() -> FramingConfig {
+    NewlineDelimitedDecoderConfig::new().into()
+}
+
+impl_generate_config_from_default!(GcsSourceConfig);
+
+#[derive(Debug, Snafu)]
+enum GcsSourceError {
+    #[snafu(display("`max_messages` must be greater than zero"))]
+    ZeroMaxMessages,
+    #[snafu(display("Unexpected status {} from {}", status, url))]
+    UnexpectedStatus { status: StatusCode, url: String },
+    #[snafu(display("Invalid notification payload: {}", source))]
+    InvalidPayload { source: serde_json::Error },
+    #[snafu(display("Notification is missing the `{}` attribute", attribute))]
+    MissingAttribute { attribute: &'static str },
+    #[snafu(display("Failed to read object {}/{}: {}", bucket, object, source))]
+    ReadObject {
+        source: crate::codecs::decoding::Error,
+        bucket: String,
+        object: String,
+    },
+    #[snafu(display("Failed to flush all of {}/{}: {}", bucket, object, source))]
+    PipelineSend {
+        source: crate::source_sender::ClosedError,
+        bucket: String,
+        object: String,
+    },
+    #[snafu(display("Sink reported an error delivering events"))]
+    ErrorAcknowledgement,
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "gcp_cloud_storage")]
+impl SourceConfig for GcsSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<crate::sources::Source> {
+        if self.max_messages == 0 {
+            return Err(GcsSourceError::ZeroMaxMessages.into());
+        }
+
+        let auth = self.auth.build(Scope::CloudPlatform).await?;
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, &cx.proxy)?;
+
+        let subscription_url = format!(
+            "{}/v1/projects/{}/subscriptions/{}",
+            self.endpoint.as_deref().unwrap_or(PUBSUB_URL),
+            self.project,
+            self.subscription
+        );
+
+        let source = GcsSource {
+            client,
+            auth,
+            pull_url: format!("{}:pull", subscription_url),
+            acknowledge_url: format!("{}:acknowledge", subscription_url),
+            storage_endpoint: self
+                .storage_endpoint
+                .clone()
+                .unwrap_or_else(|| STORAGE_URL.to_owned()),
+            max_messages: self.max_messages,
+            poll_interval: Duration::from_secs(self.poll_secs.into()),
+            compression: self.compression,
+            decoder: DecodingConfig::new(
+                self.framing.clone(),
+                self.decoding.clone(),
+                LogNamespace::Legacy,
+            )
+            .with_error_mode(self.on_error)
+            .build()?,
+            acknowledgements: cx.do_acknowledgements(&self.acknowledgements),
+            out: cx.out,
+        };
+
+        Ok(Box::pin(source.run(cx.shutdown)))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "gcp_cloud_storage"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PullResponse {
+    #[serde(default)]
+    received_messages: Vec<ReceivedMessage>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReceivedMessage {
+    ack_id: String,
+    message: PubsubMessage,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PubsubMessage {
+    #[serde(default)]
+    data: String,
+    #[serde(default)]
+    attributes: HashMap<String, String>,
+    #[serde(default)]
+    message_id: String,
+    publish_time: Option<DateTime<Utc>>,
+}
+
+/// The subset of the `JSON_API_V1` notification payload, which is the object's resource
+/// representation, that is used when ingesting the object.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+struct ObjectResource {
+    updated: Option<DateTime<Utc>>,
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Notification {
+    event_type: String,
+    bucket: String,
+    object: String,
+    timestamp: Option<DateTime<Utc>>,
+    metadata: HashMap<String, String>,
+}
+
+impl Notification {
+    fn from_message(message: &PubsubMessage) -> crate::Result<Self> {
+        let attribute = |attribute: &'static str| {
+            message
+                .attributes
+                .get(attribute)
+                .cloned()
+                .ok_or(GcsSourceError::MissingAttribute { attribute })
+        };
+
+        // Notifications using the `NONE` payload format carry no data.
+        let resource = if message.data.is_empty() {
+            ObjectResource::default()
+        } else {
+            let data = base64::decode(&message.data)?;
+            serde_json::from_slice(&data).context(InvalidPayloadSnafu)?
+        };
+
+        Ok(Self {
+            event_type: attribute("eventType")?,
+            bucket: attribute("bucketId")?,
+            object: attribute("objectId")?,
+            timestamp: resource.updated.or(message.publish_time),
+            metadata: resource.metadata,
+        })
+    }
+}
+
+struct GcsSource {
+    client: HttpClient,
+    auth: GcpAuthenticator,
+    pull_url: String,
+    acknowledge_url: String,
+    storage_endpoint: String,
+    max_messages: u32,
+    poll_interval: Duration,
+    compression: Compression,
+    decoder: Decoder,
+    acknowledgements: bool,
+    out: SourceSender,
+}
+
+impl GcsSource {
+    async fn run(mut self, mut shutdown: ShutdownSignal) -> Result<(), ()> {
+        let _token_regenerator = self.auth.spawn_regenerate_token();
+
+        loop {
+            let messages = tokio::select! {
+                _ = &mut shutdown => break,
+                result = self.pull() => result,
+            };
+
+            let idle = match messages {
+                Ok(messages) => {
+                    let idle = messages.is_empty();
+                    self.handle_messages(messages).await;
+                    idle
+                }
+                Err(error) => {
+                    emit!(GcsNotificationPullError { error: &error });
+                    true
+                }
+            };
+
+            if idle {
+                tokio::select! {
+                    _ = &mut shutdown => break,
+                    _ = tokio::time::sleep(self.poll_interval) => {},
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn pull(&self) -> crate::Result<Vec<ReceivedMessage>> {
+        let body = serde_json::json!({ "maxMessages": self.max_messages });
+        let response = self.post(&self.pull_url, &body).await?;
+        let body = hyper::body::to_bytes(response.into_body()).await?;
+        let response: PullResponse = serde_json::from_slice(&body)?;
+        Ok(response.received_messages)
+    }
+
+    async fn acknowledge(&self, ack_ids: Vec<String>) -> crate::Result<()> {
+        let body = serde_json::json!({ "ackIds": ack_ids });
+        self.post(&self.acknowledge_url, &body).await?;
+        Ok(())
+    }
+
+    async fn post(
+        &self,
+        url: &str,
+        body: &serde_json::Value,
+    ) -> crate::Result<http::Response<Body>> {
+        let mut request = Request::post(url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_vec(body)?))?;
+        self.auth.apply(&mut request);
+
+        let response = self.client.send(request).await?;
+        if response.status().is_success() {
+            Ok(response)
+        } else {
+            if response.status() == StatusCode::UNAUTHORIZED {
+                self.auth.force_refresh();
+            }
+            Err(GcsSourceError::UnexpectedStatus {
+                status: response.status(),
+                url: url.to_owned(),
+            }
+            .into())
+        }
+    }
+
+    async fn handle_messages(&mut self, messages: Vec<ReceivedMessage>) {
+        let mut ack_ids = Vec::with_capacity(messages.len());
+        for received in messages {
+            // Notifications that failed to process are not acknowledged, so Pub/Sub redelivers
+            // them once their acknowledgement deadline expires.
+            match self.handle_message(&received.message).await {
+                Ok(()) => ack_ids.push(received.ack_id),
+                Err(error) => emit!(GcsObjectProcessingError {
+                    message_id: &received.message.message_id,
+                    error: &error,
+                }),
+            }
+        }
+
+        if !ack_ids.is_empty() {
+            let count = ack_ids.len();
+            if let Err(error) = self.acknowledge(ack_ids).await {
+                emit!(GcsNotificationAcknowledgeError {
+                    error: &error,
+                    count,
+                });
+            }
+        }
+    }
+
+    async fn handle_message(&mut self, message: &PubsubMessage) -> crate::Result<()> {
+        let notification = Notification::from_message(message)?;
+        if notification.event_type != OBJECT_FINALIZE {
+            emit!(GcsNotificationIgnored {
+                bucket: &notification.bucket,
+                object: &notification.object,
+                event_type: &notification.event_type,
+            });
+            return Ok(());
+        }
+
+        let url = object_url(
+            &self.storage_endpoint,
+            &notification.bucket,
+            &notification.object,
+        );
+        let mut request = Request::get(&url).body(Body::empty())?;
+        self.auth.apply(&mut request);
+
+        let response = self.client.send(request).await?;
+        if !response.status().is_success() {
+            if response.status() == StatusCode::UNAUTHORIZED {
+                self.auth.force_refresh();
+            }
+            return Err(GcsSourceError::UnexpectedStatus {
+                status: response.status(),
+                url,
+            }
+            .into());
+        }
+
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(ToOwned::to_owned)
+        };
+        let content_encoding = header(http::header::CONTENT_ENCODING);
+        let content_type = header(CONTENT_TYPE);
+
+        let reader = object_reader(
+            self.compression,
+            &notification.object,
+            content_encoding.as_deref(),
+            content_type.as_deref(),
+            response.into_body(),
+        );
+
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
+        let bucket = Bytes::from(notification.bucket.clone());
+        let object = Bytes::from(notification.object.clone());
+        let timestamp = notification.timestamp.unwrap_or_else(Utc::now);
+
+        // This can result in objects being partially processed before an error, but we prefer
+        // duplicate events over message loss.
+        let mut frames = FramedRead::new(reader, self.decoder.clone());
+        while let Some(result) = frames.next().await {
+            let (mut events, byte_size) = match result {
+                Ok(frame) => frame,
+                Err(error) => {
+                    // Error is logged by `crate::codecs::Decoder`, only a failure to read the
+                    // object stops its processing.
+                    if error.can_continue() {
+                        continue;
+                    }
+                    return Err(GcsSourceError::ReadObject {
+                        source: error,
+                        bucket: notification.bucket,
+                        object: notification.object,
+                    }
+                    .into());
+                }
+            };
+            emit!(BytesReceived {
+                byte_size,
+                protocol: "http",
+            });
+
+            for event in events.iter_mut() {
+                if let Some(log) = event.maybe_as_log_mut() {
+                    log.try_insert(path!("bucket"), bucket.clone());
+                    log.try_insert(path!("object"), object.clone());
+                    log.try_insert(
+                        log_schema().source_type_key(),
+                        Bytes::from("gcp_cloud_storage"),
+                    );
+                    log.try_insert(log_schema().timestamp_key(), timestamp);
+                    for (key, value) in &notification.metadata {
+                        log.try_insert(key.as_str(), value.clone());
+                    }
+                }
+            }
+            let events = events
+                .into_iter()
+                .map(|event| event.with_batch_notifier_option(&batch))
+                .collect::<Vec<_>>();
+
+            emit!(EventsReceived {
+                count: events.len(),
+                byte_size: events.size_of(),
+            });
+
+            if let Err(error) = self.out.send_batch(events).await {
+                // count is set to 0 to have no discarded events considering the events are not
+                // yet acknowledged and will be retried in case of error
+                emit!(StreamClosedError { error, count: 0 });
+                return Err(GcsSourceError::PipelineSend {
+                    source: error,
+                    bucket: notification.bucket,
+                    object: notification.object,
+                }
+                .into());
+            }
+        }
+        drop(batch);
+
+        match receiver {
+            None => Ok(()),
+            Some(receiver) => match receiver.await {
+                BatchStatus::Delivered => Ok(()),
+                BatchStatus::Errored => Err(GcsSourceError::ErrorAcknowledgement.into()),
+                BatchStatus::Rejected => {
+                    error!(
+                        message = "Sink reported events were rejected.",
+                        internal_log_rate_secs = 5,
+                    );
+                    // Failed events cannot be retried, so continue to acknowledge the notification.
+                    Ok(())
+                }
+            },
+        }
+    }
+}
+
+fn object_url(endpoint: &str, bucket: &str, object: &str) -> String {
+    format!(
+        "{}/storage/v1/b/{}/o/{}?alt=media",
+        endpoint,
+        percent_encoding::utf8_percent_encode(bucket, percent_encoding::NON_ALPHANUMERIC),
+        percent_encoding::utf8_percent_encode(object, percent_encoding::NON_ALPHANUMERIC),
+    )
+}
+
+fn object_reader(
+    compression: Compression,
+    object: &str,
+    content_encoding: Option<&str>,
+    content_type: Option<&str>,
+    body: Body,
+) -> Box<dyn tokio::io::AsyncRead + Send + Unpin> {
+    use async_compression::tokio::bufread;
+
+    let reader = tokio::io::BufReader::new(StreamReader::new(
+        body.map_err(|error| std::io::Error::new(ErrorKind::Other, error)),
+    ));
+
+    let compression = match compression {
+        Compression::Auto => determine_compression(content_encoding, content_type, object)
+            .unwrap_or(Compression::None),
+        _ => compression,
+    };
+
+    match compression {
+        Compression::Auto => unreachable!(), // is mapped above
+        Compression::None => Box::new(reader),
+        Compression::Gzip => Box::new({
+            let mut decoder = bufread::GzipDecoder::new(reader);
+            decoder.multiple_members(true);
+            decoder
+        }),
+        Compression::Zstd => Box::new({
+            let mut decoder = bufread::ZstdDecoder::new(reader);
+            decoder.multiple_members(true);
+            decoder
+        }),
+    }
+}
+
+/// Try to determine the compression from, in this order, the object's content encoding, its
+/// content type, and its name's extension.
+fn determine_compression(
+    content_encoding: Option<&str>,
+    content_type: Option<&str>,
+    object: &str,
+) -> Option<Compression> {
+    let from_encoding = content_encoding.and_then(|encoding| match encoding {
+        "gzip" => Some(Compression::Gzip),
+        "zstd" => Some(Compression::Zstd),
+        _ => None,
+    });
+    let from_type = || {
+        content_type.and_then(|content_type| match content_type {
+            "application/gzip" | "application/x-gzip" => Some(Compression::Gzip),
+            "application/zstd" => Some(Compression::Zstd),
+            _ => None,
+        })
+    };
+    let from_extension = || {
+        std::path::Path::new(object)
+            .extension()
+            .and_then(std::ffi::OsStr::to_str)
+            .and_then(|extension| match extension {
+                "gz" => Some(Compression::Gzip),
+                "zst" => Some(Compression::Zstd),
+                _ => None,
+            })
+    };
+
+    from_encoding.or_else(from_type).or_else(from_extension)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<GcsSourceConfig>();
+    }
+
+    #[test]
+    fn determines_compression() {
+        let cases = vec![
+            ("out.log", Some("gzip"), None, Some(Compression::Gzip)),
+            (
+                "out.log",
+                None,
+                Some("application/zstd"),
+                Some(Compression::Zstd),
+            ),
+            ("out.log.gz", None, None, Some(Compression::Gzip)),
+            (
+                "out.log.zst",
+                Some("identity"),
+                None,
+                Some(Compression::Zstd),
+            ),
+            ("out.txt", None, Some("text/plain"), None),
+        ];
+        for (object, content_encoding, content_type, expected) in cases {
+            assert_eq!(
+                determine_compression(content_encoding, content_type, object),
+                expected,
+                "object={:?} content_encoding={:?} content_type={:?}",
+                object,
+                content_encoding,
+                content_type,
+            );
+        }
+    }
+
+    #[test]
+    fn encodes_object_url() {
+        assert_eq!(
+            object_url("https://storage.googleapis.com", "logs", "2022/09/app log.gz"),
+            "https://storage.googleapis.com/storage/v1/b/logs/o/2022%2F09%2Fapp%20log%2Egz?alt=media"
+        );
+    }
+
+    #[test]
+    fn parses_json_api_notification() {
+        let message: PubsubMessage = serde_json::from_value(serde_json::json!({
+            "data": base64::encode(serde_json::to_vec(&serde_json::json!({
+                "name": "app.log",
+                "bucket": "logs",
+                "updated": "2022-09-01T12:00:00.000Z",
+                "metadata": { "team": "infra" },
+            })).unwrap()),
+            "attributes": {
+                "eventType": "OBJECT_FINALIZE",
+                "bucketId": "logs",
+                "objectId": "app.log",
+                "payloadFormat": "JSON_API_V1",
+            },
+            "messageId": "1",
+            "publishTime": "2022-09-01T12:00:01.000Z",
+        }))
+        .unwrap();
+
+        let notification = Notification::from_message(&message).unwrap();
+        assert_eq!(notification.event_type, OBJECT_FINALIZE);
+        assert_eq!(notification.bucket, "logs");
+        assert_eq!(notification.object, "app.log");
+        assert_eq!(
+            notification.timestamp,
+            Some("2022-09-01T12:00:00Z".parse().unwrap())
+        );
+        assert_eq!(notification.metadata["team"], "infra");
+    }
+
+    #[test]
+    fn parses_notification_without_payload() {
+        let message: PubsubMessage = serde_json::from_value(serde_json::json!({
+            "attributes": {
+                "eventType": "OBJECT_DELETE",
+                "bucketId": "logs",
+                "objectId": "app.log",
+                "payloadFormat": "NONE",
+            },
+            "messageId": "1",
+            "publishTime": "2022-09-01T12:00:01.000Z",
+        }))
+        .unwrap();
+
+        let notification = Notification::from_message(&message).unwrap();
+        assert_eq!(notification.event_type, "OBJECT_DELETE");
+        assert_eq!(
+            notification.timestamp,
+            Some("2022-09-01T12:00:01Z".parse().unwrap())
+        );
+        assert!(notification.metadata.is_empty());
+    }
+
+    #[test]
+    fn rejects_notification_without_object() {
+        let message: PubsubMessage = serde_json::from_value(serde_json::json!({
+            "attributes": { "eventType": "OBJECT_FINALIZE", "bucketId": "logs" },
+        }))
+        .unwrap();
+
+        assert!(Notification::from_message(&message).is_err());
+    }
+}
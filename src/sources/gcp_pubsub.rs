@@ -5,7 +5,7 @@ use std::{
 };
 
 use chrono::{DateTime, NaiveDateTime, Utc};
-use codecs::decoding::{DeserializerConfig, FramingConfig};
+use codecs::decoding::{DecodingErrorMode, DeserializerConfig, FramingConfig};
 use derivative::Derivative;
 use futures::{stream, stream::FuturesUnordered, FutureExt, Stream, StreamExt, TryFutureExt};
 use http::uri::{InvalidUri, Scheme, Uri};
@@ -176,6 +176,10 @@ pub struct PubsubConfig {
     #[derivative(Default(value = "default_decoding()"))]
     pub decoding: DeserializerConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub on_error: DecodingErrorMode,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     pub acknowledgements: AcknowledgementsConfig,
@@ -273,7 +277,8 @@ impl SourceConfig for PubsubConfig {
                 self.decoding.clone(),
                 LogNamespace::Legacy,
             )
-            .build(),
+            .with_error_mode(self.on_error)
+            .build()?,
             acknowledgements: cx.do_acknowledgements(&self.acknowledgements),
             shutdown: cx.shutdown,
             out: cx.out,
@@ -0,0 +1,584 @@
+//! The `grpc_server` source.
+//!
+//! The method served is found in a file descriptor set supplied by the user, so that custom agents
+//! can push their own messages to Vector over gRPC. Each message of the calls, whether they are
+//! unary or streaming, is decoded into an event, and the calls are answered with a single response
+//! message configured by the user once all their messages are accepted.
+
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use chrono::Utc;
+use codecs::{BytesDecoderConfig, ProtobufDeserializerConfig};
+use futures::{future::BoxFuture, FutureExt, TryFutureExt};
+use http::{header::CONTENT_TYPE, HeaderMap, HeaderValue, Request, Response};
+use http_body::Body as _;
+use hyper::Body;
+use prost::Message;
+use prost_reflect::{DescriptorPool, MethodDescriptor};
+use snafu::{ResultExt, Snafu};
+use tonic::{body::BoxBody, Status};
+use tower::Service;
+use vector_config::configurable_component;
+use vector_core::{
+    config::LogNamespace,
+    event::{BatchNotifier, BatchStatus, Event},
+    ByteSizeOf,
+};
+
+use crate::{
+    codecs::{Decoder, DecodingConfig},
+    config::{
+        log_schema, AcknowledgementsConfig, DataType, GenerateConfig, Output, Resource,
+        SourceConfig, SourceContext, SourceDescription,
+    },
+    internal_events::{EventsReceived, StreamClosedError},
+    serde::bool_or_struct,
+    sources::{util::grpc::run_grpc_server_with_service, Source},
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+    SourceSender,
+};
+
+mod response;
+
+// Every gRPC message has a five byte header: a compressed flag, and the length of the message.
+const GRPC_MESSAGE_HEADER_LEN: usize = 5;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Could not read descriptor set {}: {}", path.display(), source))]
+    ReadDescriptorSet {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Could not parse descriptor set {}: {}", path.display(), message))]
+    ParseDescriptorSet { path: PathBuf, message: String },
+    #[snafu(display("Method {:?} must be of the form `package.Service/Method`", method))]
+    InvalidMethodName { method: String },
+    #[snafu(display("Method {:?} not found in descriptor set {}", method, path.display()))]
+    MethodNotFound { method: String, path: PathBuf },
+    #[snafu(display("Invalid `response`: {}", message))]
+    InvalidResponse { message: String },
+}
+
+/// Configuration for the `grpc_server` source.
+#[configurable_component(source)]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct GrpcServerConfig {
+    /// The address to listen for connections on.
+    ///
+    /// It _must_ include a port.
+    address: SocketAddr,
+
+    /// The path to the compiled file descriptor set of the service, as produced by
+    /// `protoc --include_imports --descriptor_set_out`.
+    desc_file: PathBuf,
+
+    /// The fully qualified name of the method to serve, such as `package.Service/Method`.
+    method: String,
+
+    /// The fields of the response message of the method, which is empty by default.
+    ///
+    /// The enum values are either their names or their numbers.
+    response: Option<toml::Value>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+inventory::submit! {
+    SourceDescription::new::<GrpcServerConfig>("grpc_server")
+}
+
+impl GenerateConfig for GrpcServerConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc::indoc! {r#"
+            address = "0.0.0.0:50051"
+            desc_file = "/etc/vector/agent.desc"
+            method = "agent.Ingest/Push"
+        "#})
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "grpc_server")]
+impl SourceConfig for GrpcServerConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<Source> {
+        let method = find_method(&self.desc_file, &self.method)?;
+
+        let fields = match &self.response {
+            Some(toml::Value::Table(fields)) => fields.clone(),
+            Some(_) => {
+                return Err(BuildError::InvalidResponse {
+                    message: "must be a table of the fields of the response message".into(),
+                }
+                .into())
+            }
+            None => Default::default(),
+        };
+        let response = response::build_message(method.output(), &fields)
+            .map_err(|message| BuildError::InvalidResponse { message })?
+            .encode_to_vec();
+
+        let decoder = DecodingConfig::new(
+            BytesDecoderConfig::new().into(),
+            ProtobufDeserializerConfig::new(
+                self.desc_file.clone(),
+                method.input().full_name().to_owned(),
+            )
+            .into(),
+            LogNamespace::Legacy,
+        )
+        .build()?;
+
+        let service = GrpcServerService {
+            path: format!("/{}", self.method.trim_start_matches('.')),
+            decoder,
+            response: frame_message(&response),
+            out: cx.out,
+            acknowledgements: cx.do_acknowledgements(&self.acknowledgements),
+        };
+        let tls_settings = MaybeTlsSettings::from_config(&self.tls, true)?;
+
+        Ok(
+            run_grpc_server_with_service(self.address, tls_settings, service, cx.shutdown)
+                .map_err(|error| {
+                    error!(message = "Source future failed.", %error);
+                })
+                .boxed(),
+        )
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "grpc_server"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::tcp(self.address)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+/// Finds the method of the descriptor set, whose name is of the form `package.Service/Method`.
+fn find_method(path: &Path, method: &str) -> Result<MethodDescriptor, BuildError> {
+    let bytes = std::fs::read(path).context(ReadDescriptorSetSnafu { path })?;
+    let pool = DescriptorPool::decode(bytes.as_slice()).map_err(|error| {
+        BuildError::ParseDescriptorSet {
+            path: path.to_owned(),
+            message: error.to_string(),
+        }
+    })?;
+
+    let (service_name, method_name) =
+        method
+            .split_once('/')
+            .ok_or_else(|| BuildError::InvalidMethodName {
+                method: method.to_owned(),
+            })?;
+    pool.get_service_by_name(service_name.trim_start_matches('.'))
+        .and_then(|service| {
+            service
+                .methods()
+                .find(|candidate| candidate.name() == method_name)
+        })
+        .ok_or_else(|| BuildError::MethodNotFound {
+            method: method.to_owned(),
+            path: path.to_owned(),
+        })
+}
+
+/// Prefixes the message with the gRPC message header, as an uncompressed message.
+fn frame_message(message: &[u8]) -> Bytes {
+    let mut frame = BytesMut::with_capacity(GRPC_MESSAGE_HEADER_LEN + message.len());
+    frame.put_u8(0);
+    frame.put_u32(message.len() as u32);
+    frame.put_slice(message);
+    frame.freeze()
+}
+
+/// Returns the next message of the buffer, once it's complete.
+fn next_message(buffer: &mut BytesMut) -> Result<Option<Bytes>, Status> {
+    if buffer.len() < GRPC_MESSAGE_HEADER_LEN {
+        return Ok(None);
+    }
+    if buffer[0] != 0 {
+        return Err(Status::internal("Compressed message was not decompressed"));
+    }
+    let length = u32::from_be_bytes([buffer[1], buffer[2], buffer[3], buffer[4]]) as usize;
+    if buffer.len() < GRPC_MESSAGE_HEADER_LEN + length {
+        return Ok(None);
+    }
+    buffer.advance(GRPC_MESSAGE_HEADER_LEN);
+    Ok(Some(buffer.split_to(length).freeze()))
+}
+
+#[derive(Clone)]
+struct GrpcServerService {
+    path: String,
+    decoder: Decoder,
+    response: Bytes,
+    out: SourceSender,
+    acknowledgements: bool,
+}
+
+impl Service<Request<Body>> for GrpcServerService {
+    type Response = Response<BoxBody>;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let mut service = self.clone();
+        Box::pin(async move {
+            Ok(match service.handle_call(request).await {
+                Ok(()) => {
+                    let mut response = Response::new(
+                        ResponseBody {
+                            message: Some(service.response.clone()),
+                            trailers: Some(ok_trailers()),
+                        }
+                        .boxed_unsync(),
+                    );
+                    response
+                        .headers_mut()
+                        .insert(CONTENT_TYPE, HeaderValue::from_static("application/grpc"));
+                    response
+                }
+                Err(status) => status.to_http(),
+            })
+        })
+    }
+}
+
+impl GrpcServerService {
+    /// Handles the messages of the call as they come in, until the client ends its stream.
+    async fn handle_call(&mut self, request: Request<Body>) -> Result<(), Status> {
+        if request.uri().path() != self.path {
+            return Err(Status::unimplemented(format!(
+                "Method {} is not served",
+                request.uri().path()
+            )));
+        }
+
+        let mut body = request.into_body();
+        let mut buffer = BytesMut::new();
+        while let Some(chunk) = body.data().await {
+            let chunk = chunk.map_err(|_| Status::internal("Failed to read the request"))?;
+            buffer.put(chunk);
+            while let Some(message) = next_message(&mut buffer)? {
+                self.handle_message(message).await?;
+            }
+        }
+
+        if buffer.is_empty() {
+            Ok(())
+        } else {
+            Err(Status::invalid_argument("Incomplete message"))
+        }
+    }
+
+    async fn handle_message(&mut self, message: Bytes) -> Result<(), Status> {
+        let mut events = Vec::new();
+        let mut buffer = BytesMut::from(message.as_ref());
+        let mut decoder = self.decoder.clone();
+        while let Some((decoded, _)) =
+            tokio_util::codec::Decoder::decode_eof(&mut decoder, &mut buffer)
+                .map_err(|error| Status::invalid_argument(error.to_string()))?
+        {
+            events.extend(decoded);
+        }
+
+        let now = Utc::now();
+        for event in &mut events {
+            let log = event.as_mut_log();
+            log.try_insert(log_schema().source_type_key(), Bytes::from("grpc_server"));
+            log.try_insert(log_schema().timestamp_key(), now);
+        }
+
+        let count = events.len();
+        emit!(EventsReceived {
+            count,
+            byte_size: events.size_of(),
+        });
+
+        let receiver = BatchNotifier::maybe_apply_to(self.acknowledgements, &mut events);
+        self.out.send_batch(events).await.map_err(|error| {
+            let message = error.to_string();
+            emit!(StreamClosedError { error, count });
+            Status::unavailable(message)
+        })?;
+
+        let status = match receiver {
+            Some(receiver) => receiver.await,
+            None => BatchStatus::Delivered,
+        };
+        match status {
+            BatchStatus::Errored => Err(Status::internal("Delivery error")),
+            BatchStatus::Rejected => Err(Status::data_loss("Delivery failed")),
+            BatchStatus::Delivered => Ok(()),
+        }
+    }
+}
+
+fn ok_trailers() -> HeaderMap {
+    let mut trailers = HeaderMap::new();
+    trailers.insert("grpc-status", HeaderValue::from_static("0"));
+    trailers
+}
+
+/// The body of a successful response: the response message, then the trailers of the status.
+struct ResponseBody {
+    message: Option<Bytes>,
+    trailers: Option<HeaderMap>,
+}
+
+impl http_body::Body for ResponseBody {
+    type Data = Bytes;
+    type Error = Status;
+
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        Poll::Ready(self.message.take().map(Ok))
+    }
+
+    fn poll_trailers(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+        Poll::Ready(Ok(self.trailers.take()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prost_reflect::DynamicMessage;
+    use prost_types::{
+        field_descriptor_proto::{Label, Type},
+        DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+        MethodDescriptorProto, ServiceDescriptorProto,
+    };
+
+    use super::*;
+    use crate::{
+        event::EventStatus,
+        test_util::{
+            collect_n,
+            components::{assert_source_compliance, SOURCE_TAGS},
+            next_addr, wait_for_tcp,
+        },
+    };
+
+    fn field(name: &str, number: i32, r#type: Type) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.to_owned()),
+            number: Some(number),
+            label: Some(Label::Optional as i32),
+            r#type: Some(r#type as i32),
+            ..Default::default()
+        }
+    }
+
+    /// Writes a descriptor set equivalent to:
+    ///
+    /// ```protobuf
+    /// package agent;
+    /// message Record { string message = 1; int64 count = 2; }
+    /// message Ack { bool accepted = 1; string note = 2; }
+    /// service Ingest { rpc Push(stream Record) returns (Ack); }
+    /// ```
+    fn write_descriptor_set(path: &Path) {
+        let file = FileDescriptorProto {
+            name: Some("agent.proto".to_owned()),
+            package: Some("agent".to_owned()),
+            syntax: Some("proto3".to_owned()),
+            message_type: vec![
+                DescriptorProto {
+                    name: Some("Record".to_owned()),
+                    field: vec![
+                        field("message", 1, Type::String),
+                        field("count", 2, Type::Int64),
+                    ],
+                    ..Default::default()
+                },
+                DescriptorProto {
+                    name: Some("Ack".to_owned()),
+                    field: vec![
+                        field("accepted", 1, Type::Bool),
+                        field("note", 2, Type::String),
+                    ],
+                    ..Default::default()
+                },
+            ],
+            service: vec![ServiceDescriptorProto {
+                name: Some("Ingest".to_owned()),
+                method: vec![MethodDescriptorProto {
+                    name: Some("Push".to_owned()),
+                    input_type: Some(".agent.Record".to_owned()),
+                    output_type: Some(".agent.Ack".to_owned()),
+                    client_streaming: Some(true),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let set = FileDescriptorSet { file: vec![file] };
+        std::fs::write(path, set.encode_to_vec()).unwrap();
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<GrpcServerConfig>();
+    }
+
+    #[test]
+    fn finds_methods() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("agent.desc");
+        write_descriptor_set(&path);
+
+        let method = find_method(&path, "agent.Ingest/Push").unwrap();
+        assert_eq!(method.input().full_name(), "agent.Record");
+        assert_eq!(method.output().full_name(), "agent.Ack");
+
+        assert!(matches!(
+            find_method(&path, "agent.Ingest.Push"),
+            Err(BuildError::InvalidMethodName { .. })
+        ));
+        assert!(matches!(
+            find_method(&path, "agent.Ingest/Pull"),
+            Err(BuildError::MethodNotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn receives_streamed_messages() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("agent.desc");
+        write_descriptor_set(&path);
+        let method = find_method(&path, "agent.Ingest/Push").unwrap();
+
+        let address = next_addr();
+        let config: GrpcServerConfig = toml::from_str(&format!(
+            r#"
+            address = "{}"
+            desc_file = "{}"
+            method = "agent.Ingest/Push"
+            response = {{ accepted = true, note = "thanks" }}
+            "#,
+            address,
+            path.display()
+        ))
+        .unwrap();
+
+        let events = assert_source_compliance(&SOURCE_TAGS, async move {
+            let (sender, recv) = SourceSender::new_test_finalize(EventStatus::Delivered);
+            let source = config
+                .build(SourceContext::new_test(sender, None))
+                .await
+                .unwrap();
+            tokio::spawn(source);
+            wait_for_tcp(address).await;
+
+            let mut body = BytesMut::new();
+            for (message, count) in [("first", 1), ("second", 2)] {
+                let mut record = DynamicMessage::new(method.input());
+                record.set_field_by_name("message", prost_reflect::Value::String(message.into()));
+                record.set_field_by_name("count", prost_reflect::Value::I64(count));
+                body.put(frame_message(&record.encode_to_vec()));
+            }
+
+            let client = hyper::Client::builder().http2_only(true).build_http();
+            let request = Request::post(format!("http://{}/agent.Ingest/Push", address))
+                .header(CONTENT_TYPE, "application/grpc")
+                .body(Body::from(body.freeze()))
+                .unwrap();
+            let mut response = client.request(request).await.unwrap();
+            let mut data = BytesMut::new();
+            while let Some(chunk) = response.body_mut().data().await {
+                data.put(chunk.unwrap());
+            }
+            let trailers = response.body_mut().trailers().await.unwrap().unwrap();
+            assert_eq!(trailers["grpc-status"], "0");
+
+            let message = next_message(&mut data).unwrap().unwrap();
+            let ack = DynamicMessage::decode(method.output(), message).unwrap();
+            assert_eq!(
+                ack.get_field_by_name("accepted").unwrap().as_bool(),
+                Some(true)
+            );
+            assert_eq!(
+                ack.get_field_by_name("note").unwrap().as_str(),
+                Some("thanks")
+            );
+
+            collect_n(recv, 2).await
+        })
+        .await;
+
+        let log = events[0].as_log();
+        assert_eq!(log["message"], "first".into());
+        assert_eq!(log["count"], 1.into());
+        assert_eq!(log[log_schema().source_type_key()], "grpc_server".into());
+        assert_eq!(events[1].as_log()["message"], "second".into());
+    }
+
+    #[tokio::test]
+    async fn rejects_other_methods() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("agent.desc");
+        write_descriptor_set(&path);
+
+        let address = next_addr();
+        let config: GrpcServerConfig = toml::from_str(&format!(
+            r#"
+            address = "{}"
+            desc_file = "{}"
+            method = "agent.Ingest/Push"
+            "#,
+            address,
+            path.display()
+        ))
+        .unwrap();
+        let source = config
+            .build(SourceContext::new_test(SourceSender::new_test().0, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        wait_for_tcp(address).await;
+
+        let client = hyper::Client::builder().http2_only(true).build_http();
+        let request = Request::post(format!("http://{}/agent.Ingest/Pull", address))
+            .header(CONTENT_TYPE, "application/grpc")
+            .body(Body::empty())
+            .unwrap();
+        let response = client.request(request).await.unwrap();
+        let status = Status::from_header_map(response.headers()).unwrap();
+        assert_eq!(status.code(), tonic::Code::Unimplemented);
+    }
+}
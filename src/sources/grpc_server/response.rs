@@ -0,0 +1,83 @@
+use bytes::Bytes;
+use prost_reflect::{
+    DynamicMessage, FieldDescriptor, Kind, MessageDescriptor, Value as ProtobufValue,
+};
+
+/// Builds a message from a table of its fields, such as the response message of the method from
+/// the `response` of the configuration. The enum values are either their names or their numbers.
+pub(super) fn build_message(
+    descriptor: MessageDescriptor,
+    fields: &toml::value::Table,
+) -> Result<DynamicMessage, String> {
+    let mut message = DynamicMessage::new(descriptor.clone());
+    for (name, value) in fields {
+        let field = descriptor.get_field_by_name(name).ok_or_else(|| {
+            format!(
+                "Field {:?} not found in message {}",
+                name,
+                descriptor.full_name()
+            )
+        })?;
+        let value = if field.is_map() {
+            return Err(format!("Map field {:?} is not supported", name));
+        } else if field.is_list() {
+            match value {
+                toml::Value::Array(values) => ProtobufValue::List(
+                    values
+                        .iter()
+                        .map(|value| to_protobuf_value(value, &field))
+                        .collect::<Result<_, _>>()?,
+                ),
+                _ => return Err(format!("Repeated field {:?} must be an array", name)),
+            }
+        } else {
+            to_protobuf_value(value, &field)?
+        };
+        message.set_field(&field, value);
+    }
+    Ok(message)
+}
+
+fn to_protobuf_value(
+    value: &toml::Value,
+    field: &FieldDescriptor,
+) -> Result<ProtobufValue, String> {
+    let invalid = || format!("Invalid value {} of field {:?}", value, field.name());
+
+    Ok(match (field.kind(), value) {
+        (Kind::Double, toml::Value::Float(value)) => ProtobufValue::F64(*value),
+        (Kind::Double, toml::Value::Integer(value)) => ProtobufValue::F64(*value as f64),
+        (Kind::Float, toml::Value::Float(value)) => ProtobufValue::F32(*value as f32),
+        (Kind::Float, toml::Value::Integer(value)) => ProtobufValue::F32(*value as f32),
+        (Kind::Int32 | Kind::Sint32 | Kind::Sfixed32, toml::Value::Integer(value)) => {
+            ProtobufValue::I32(i32::try_from(*value).map_err(|_| invalid())?)
+        }
+        (Kind::Int64 | Kind::Sint64 | Kind::Sfixed64, toml::Value::Integer(value)) => {
+            ProtobufValue::I64(*value)
+        }
+        (Kind::Uint32 | Kind::Fixed32, toml::Value::Integer(value)) => {
+            ProtobufValue::U32(u32::try_from(*value).map_err(|_| invalid())?)
+        }
+        (Kind::Uint64 | Kind::Fixed64, toml::Value::Integer(value)) => {
+            ProtobufValue::U64(u64::try_from(*value).map_err(|_| invalid())?)
+        }
+        (Kind::Bool, toml::Value::Boolean(value)) => ProtobufValue::Bool(*value),
+        (Kind::String, toml::Value::String(value)) => ProtobufValue::String(value.clone()),
+        (Kind::Bytes, toml::Value::String(value)) => {
+            ProtobufValue::Bytes(Bytes::from(value.clone()))
+        }
+        (Kind::Enum(descriptor), toml::Value::String(name)) => ProtobufValue::EnumNumber(
+            descriptor
+                .get_value_by_name(name)
+                .ok_or_else(invalid)?
+                .number(),
+        ),
+        (Kind::Enum(_), toml::Value::Integer(number)) => {
+            ProtobufValue::EnumNumber(i32::try_from(*number).map_err(|_| invalid())?)
+        }
+        (Kind::Message(descriptor), toml::Value::Table(fields)) => {
+            ProtobufValue::Message(build_message(descriptor, fields)?)
+        }
+        _ => return Err(invalid()),
+    })
+}
@@ -8,7 +8,7 @@ use std::{
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 use chrono::{DateTime, Utc};
 use codecs::{
-    decoding::{DeserializerConfig, FramingConfig},
+    decoding::{DecodingErrorMode, DeserializerConfig, FramingConfig},
     StreamDecodingError,
 };
 use smallvec::SmallVec;
@@ -59,6 +59,10 @@ pub struct LogplexConfig {
     #[serde(default = "default_decoding")]
     decoding: DeserializerConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    on_error: DecodingErrorMode,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
@@ -81,6 +85,7 @@ impl GenerateConfig for LogplexConfig {
             auth: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_error: Default::default(),
             acknowledgements: AcknowledgementsConfig::default(),
         })
         .unwrap()
@@ -116,7 +121,8 @@ impl SourceConfig for LogplexConfig {
             self.decoding.clone(),
             LogNamespace::Legacy,
         )
-        .build();
+        .with_error_mode(self.on_error)
+        .build()?;
         let source = LogplexSource {
             query_parameters: self.query_parameters.clone(),
             decoder,
@@ -134,7 +140,9 @@ impl SourceConfig for LogplexConfig {
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
-        vec![Output::default(self.decoding.output_type())]
+        vec![Output::default(
+            self.on_error.output_type(self.decoding.output_type()),
+        )]
     }
 
     fn source_type(&self) -> &'static str {
@@ -353,6 +361,7 @@ mod tests {
                 auth,
                 framing: default_framing_message_based(),
                 decoding: default_decoding(),
+                on_error: Default::default(),
                 acknowledgements: acknowledgements.into(),
             }
             .build(context)
@@ -27,6 +27,7 @@ mod disk;
 mod filesystem;
 mod memory;
 mod network;
+mod process;
 
 /// Collector types.
 #[configurable_component]
@@ -57,6 +58,11 @@ pub enum Collector {
 
     /// Network.
     Network,
+
+    /// Process.
+    ///
+    /// Not enabled by default, as it emits metrics for each process.
+    Process,
 }
 
 /// Filtering configuration.
@@ -82,7 +88,7 @@ pub struct HostMetricsConfig {
 
     /// The list of host metric collector services to use.
     ///
-    /// Defaults to all collectors.
+    /// Defaults to all collectors, except the `process` collector.
     pub collectors: Option<Vec<Collector>>,
 
     /// Overrides the default namespace for the metrics emitted by the source.
@@ -108,6 +114,10 @@ pub struct HostMetricsConfig {
     #[configurable(derived)]
     #[serde(default)]
     pub network: network::NetworkConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub process: process::ProcessConfig,
 }
 
 const fn default_scrape_interval() -> f64 {
@@ -183,7 +193,7 @@ impl HostMetricsConfig {
 
     fn has_collector(&self, collector: Collector) -> bool {
         match &self.collectors {
-            None => true,
+            None => collector != Collector::Process,
             Some(collectors) => collectors.iter().any(|&c| c == collector),
         }
     }
@@ -240,6 +250,9 @@ impl HostMetrics {
         if self.config.has_collector(Collector::Network) {
             metrics.extend(add_collector("network", self.network_metrics().await));
         }
+        if self.config.has_collector(Collector::Process) {
+            metrics.extend(add_collector("process", self.process_metrics().await));
+        }
         if let Ok(hostname) = &hostname {
             for metric in &mut metrics {
                 metric.insert_tag("host".into(), hostname.into());
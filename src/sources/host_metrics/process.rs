@@ -0,0 +1,239 @@
+use std::collections::BTreeMap;
+
+use chrono::Utc;
+use futures::StreamExt;
+#[cfg(target_os = "linux")]
+use heim::process::os::linux::ProcessExt;
+use heim::{
+    process::Process,
+    units::{information::byte, time::second},
+};
+use vector_config::configurable_component;
+
+use super::{FilterList, HostMetrics};
+use crate::event::metric::Metric;
+
+/// Options for the “process” metrics collector.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+pub struct ProcessConfig {
+    /// Lists of process name patterns to include or exclude.
+    #[serde(default)]
+    names: FilterList,
+
+    /// Lists of process command line patterns to include or exclude.
+    ///
+    /// The command line is matched with its arguments separated by spaces.
+    #[serde(default)]
+    cmdlines: FilterList,
+}
+
+impl HostMetrics {
+    pub async fn process_metrics(&self) -> Vec<Metric> {
+        match heim::process::processes().await {
+            Ok(processes) => {
+                // The processes that exit while being listed fail to load, and are skipped
+                // without being reported, as they do on every scrape.
+                let processes = processes
+                    .filter_map(|result| async { result.ok() })
+                    .collect::<Vec<_>>()
+                    .await;
+
+                let mut metrics = Vec::new();
+                for process in processes {
+                    if let Some(name) = self.matching_process_name(&process).await {
+                        metrics.extend(self.single_process_metrics(&process, name).await);
+                    }
+                }
+                metrics
+            }
+            Err(error) => {
+                error!(message = "Failed to load processes.", %error, internal_log_rate_secs = 60);
+                vec![]
+            }
+        }
+    }
+
+    /// Returns the name of the process, when the process is matched by the filters.
+    async fn matching_process_name(&self, process: &Process) -> Option<String> {
+        let config = &self.config.process;
+        let name = process.name().await.ok()?;
+        if !config.names.contains_str(Some(&name)) {
+            return None;
+        }
+        let cmdline = process
+            .command()
+            .await
+            .ok()
+            .map(|command| command.to_os_string().to_string_lossy().into_owned());
+        config
+            .cmdlines
+            .contains_str(cmdline.as_deref())
+            .then(|| name)
+    }
+
+    async fn single_process_metrics(&self, process: &Process, name: String) -> Vec<Metric> {
+        let timestamp = Utc::now();
+        let tags = BTreeMap::from([
+            (String::from("pid"), process.pid().to_string()),
+            (String::from("name"), name),
+        ]);
+
+        let mut metrics = Vec::new();
+        // Like the processes exiting while being listed, the processes whose details can't be
+        // read, such as the processes of other users, only have the metrics that could be read.
+        if let Ok(times) = process.cpu_time().await {
+            for (mode, time) in [("user", times.user()), ("system", times.system())] {
+                let mut tags = tags.clone();
+                tags.insert(String::from("mode"), String::from(mode));
+                metrics.push(self.counter(
+                    "process_cpu_seconds_total",
+                    timestamp,
+                    time.get::<second>(),
+                    tags,
+                ));
+            }
+        }
+        if let Ok(memory) = process.memory().await {
+            metrics.push(self.gauge(
+                "process_memory_rss_bytes",
+                timestamp,
+                memory.rss().get::<byte>() as f64,
+                tags.clone(),
+            ));
+            metrics.push(self.gauge(
+                "process_memory_virtual_bytes",
+                timestamp,
+                memory.vms().get::<byte>() as f64,
+                tags.clone(),
+            ));
+        }
+        #[cfg(target_os = "linux")]
+        {
+            if let Some(open_fds) = count_open_fds(process.pid()) {
+                metrics.push(self.gauge(
+                    "process_open_fds",
+                    timestamp,
+                    open_fds as f64,
+                    tags.clone(),
+                ));
+            }
+            if let Ok(counters) = process.io_counters().await {
+                metrics.push(self.counter(
+                    "process_io_read_bytes_total",
+                    timestamp,
+                    counters.bytes_read().get::<byte>() as f64,
+                    tags.clone(),
+                ));
+                metrics.push(self.counter(
+                    "process_io_written_bytes_total",
+                    timestamp,
+                    counters.bytes_written().get::<byte>() as f64,
+                    tags,
+                ));
+            }
+        }
+        metrics
+    }
+}
+
+/// Counts the file descriptors the process has open, which `heim` doesn't provide.
+#[cfg(target_os = "linux")]
+fn count_open_fds(pid: heim::process::Pid) -> Option<usize> {
+    let procfs_root = std::env::var_os("PROCFS_ROOT").unwrap_or_else(|| "/proc".into());
+    let fds = std::path::Path::new(&procfs_root)
+        .join(pid.to_string())
+        .join("fd");
+    std::fs::read_dir(fds).ok().map(Iterator::count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::{tests::count_tag, FilterList, HostMetrics, HostMetricsConfig, PatternWrapper},
+        ProcessConfig,
+    };
+    use crate::event::metric::Metric;
+
+    fn count_own_process(metrics: &[Metric]) -> usize {
+        let pid = std::process::id().to_string();
+        metrics
+            .iter()
+            .filter(|metric| metric.tags().unwrap().get("pid") == Some(&pid))
+            .count()
+    }
+
+    fn own_cmdline_pattern() -> PatternWrapper {
+        let cmdline = std::env::args().collect::<Vec<_>>().join(" ");
+        PatternWrapper::try_from(glob::Pattern::escape(&cmdline)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn generates_process_metrics() {
+        let metrics = HostMetrics::new(HostMetricsConfig::default())
+            .process_metrics()
+            .await;
+        assert!(!metrics.is_empty());
+
+        // All metrics are named process_*
+        assert!(!metrics
+            .iter()
+            .any(|metric| !metric.name().starts_with("process_")));
+
+        // They should all have "pid" and "name" tags
+        assert_eq!(count_tag(&metrics, "pid"), metrics.len());
+        assert_eq!(count_tag(&metrics, "name"), metrics.len());
+
+        // The details of the test process itself can always be read
+        assert!(count_own_process(&metrics) > 0);
+    }
+
+    #[tokio::test]
+    async fn process_metrics_filters_on_name() {
+        let metrics = HostMetrics::new(HostMetricsConfig {
+            process: ProcessConfig {
+                names: FilterList {
+                    includes: None,
+                    excludes: Some(vec![PatternWrapper::try_from("*".to_string()).unwrap()]),
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .process_metrics()
+        .await;
+        assert!(metrics.is_empty());
+    }
+
+    #[tokio::test]
+    async fn process_metrics_filters_on_cmdline() {
+        let included = HostMetrics::new(HostMetricsConfig {
+            process: ProcessConfig {
+                cmdlines: FilterList {
+                    includes: Some(vec![own_cmdline_pattern()]),
+                    excludes: None,
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .process_metrics()
+        .await;
+        assert!(count_own_process(&included) > 0);
+
+        let excluded = HostMetrics::new(HostMetricsConfig {
+            process: ProcessConfig {
+                cmdlines: FilterList {
+                    includes: None,
+                    excludes: Some(vec![own_cmdline_pattern()]),
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .process_metrics()
+        .await;
+        assert!(!excluded.is_empty());
+        assert_eq!(count_own_process(&excluded), 0);
+    }
+}
@@ -1,13 +1,16 @@
-use std::{collections::HashMap, net::SocketAddr};
+use std::{
+    collections::{BTreeMap, HashMap},
+    net::SocketAddr,
+};
 
 use bytes::{Bytes, BytesMut};
 use chrono::Utc;
 use codecs::{
-    decoding::{DeserializerConfig, FramingConfig},
+    decoding::{DecodingErrorMode, DeserializerConfig, FramingConfig},
     BytesDecoderConfig, BytesDeserializerConfig, JsonDeserializerConfig,
     NewlineDelimitedDecoderConfig,
 };
-use http::StatusCode;
+use http::{Method, StatusCode};
 use lookup::path;
 use tokio_util::codec::Decoder as _;
 use vector_config::configurable_component;
@@ -20,11 +23,14 @@ use crate::{
         log_schema, AcknowledgementsConfig, DataType, GenerateConfig, Output, Resource,
         SourceConfig, SourceContext, SourceDescription,
     },
-    event::{Event, Value},
+    event::{Event, LogEvent, Value},
+    internal_events::TemplateRenderingError,
     serde::{bool_or_struct, default_decoding},
     sources::util::{
-        add_query_parameters, Encoding, ErrorMessage, HttpSource, HttpSourceAuthConfig,
+        add_query_parameters, Encoding, ErrorMessage, HttpResponse, HttpRoute, HttpSource,
+        HttpSourceAuthConfig,
     },
+    template::Template,
     tls::TlsEnableableConfig,
 };
 
@@ -54,6 +60,61 @@ pub enum HttpMethod {
     Delete,
 }
 
+/// Configuration of a route of the `http` source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct HttpRouteConfig {
+    /// The URL path on which the requests of the route shall be sent.
+    path: String,
+
+    /// Whether or not to treat the `path` of the route as an absolute path.
+    ///
+    /// Defaults to the `strict_path` of the source.
+    strict_path: Option<bool>,
+
+    /// Specifies the action of the HTTP requests of the route.
+    ///
+    /// Defaults to the `method` of the source.
+    method: Option<HttpMethod>,
+
+    /// Framing configuration of the requests of the route.
+    ///
+    /// Defaults to the `framing` of the source.
+    framing: Option<FramingConfig>,
+
+    /// Decoding configuration of the requests of the route.
+    ///
+    /// Defaults to the `decoding` of the source.
+    decoding: Option<DeserializerConfig>,
+
+    /// The response to the requests of the route.
+    ///
+    /// Defaults to the `response` of the source.
+    response: Option<HttpResponseConfig>,
+}
+
+/// Response to the requests whose events were accepted.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct HttpResponseConfig {
+    /// The status code of the response.
+    ///
+    /// This is a template rendered against the first event of the request, such as `"{{ status }}"`.
+    #[serde(default = "default_response_status")]
+    status: Template,
+
+    /// The body of the response.
+    ///
+    /// This is a template rendered against the first event of the request.
+    body: Option<Template>,
+}
+
+fn default_response_status() -> Template {
+    Template::try_from("200").expect("valid template")
+}
+
 /// Configuration for the `http` source.
 #[configurable_component(source)]
 #[derive(Clone, Debug)]
@@ -100,6 +161,17 @@ pub struct SimpleHttpConfig {
     #[serde(default = "default_path_key")]
     path_key: String,
 
+    /// The event key in which the HTTP method of the request will be stored.
+    method_key: Option<String>,
+
+    /// The event key in which all the HTTP headers of the request will be stored, as an object of
+    /// their lowercase names and their values.
+    headers_key: Option<String>,
+
+    /// The event key in which all the URL query parameters of the request will be stored, as an
+    /// object of their names and values.
+    query_parameters_key: Option<String>,
+
     /// Specifies the action of the HTTP request.
     #[serde(default)]
     method: HttpMethod,
@@ -113,6 +185,19 @@ pub struct SimpleHttpConfig {
     #[configurable(derived)]
     decoding: Option<DeserializerConfig>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    on_error: DecodingErrorMode,
+
+    #[configurable(derived)]
+    response: Option<HttpResponseConfig>,
+
+    /// The routes on which requests are accepted, instead of `path`.
+    ///
+    /// The settings of the source are the defaults of the settings of the routes.
+    #[serde(default)]
+    routes: Vec<HttpRouteConfig>,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
@@ -133,10 +218,16 @@ impl GenerateConfig for SimpleHttpConfig {
             auth: None,
             path: "/".to_string(),
             path_key: "path".to_string(),
+            method_key: None,
+            headers_key: None,
+            query_parameters_key: None,
             method: HttpMethod::Post,
             strict_path: true,
             framing: None,
             decoding: Some(default_decoding()),
+            on_error: DecodingErrorMode::Drop,
+            response: None,
+            routes: Vec::new(),
             acknowledgements: AcknowledgementsConfig::default(),
         })
         .unwrap()
@@ -156,18 +247,29 @@ struct SimpleHttpSource {
     headers: Vec<String>,
     query_parameters: Vec<String>,
     path_key: String,
+    method_key: Option<String>,
+    headers_key: Option<String>,
+    query_parameters_key: Option<String>,
+    routes: Vec<SimpleHttpRoute>,
+}
+
+#[derive(Clone)]
+struct SimpleHttpRoute {
     decoder: Decoder,
+    response: Option<HttpResponseConfig>,
 }
 
-impl HttpSource for SimpleHttpSource {
-    fn build_events(
+impl SimpleHttpSource {
+    fn decode_events(
         &self,
+        route: usize,
+        method: Option<&Method>,
         body: Bytes,
         header_map: HeaderMap,
         query_parameters: HashMap<String, String>,
         request_path: &str,
     ) -> Result<Vec<Event>, ErrorMessage> {
-        let mut decoder = self.decoder.clone();
+        let mut decoder = self.routes[route].decoder.clone();
         let mut events = Vec::new();
         let mut bytes = BytesMut::new();
         bytes.extend_from_slice(&body);
@@ -187,9 +289,18 @@ impl HttpSource for SimpleHttpSource {
             }
         }
 
+        if let Some(key) = &self.headers_key {
+            add_all_headers(&mut events, key, &header_map);
+        }
+        if let Some(key) = &self.query_parameters_key {
+            add_all_query_parameters(&mut events, key, &query_parameters);
+        }
         add_headers(&mut events, &self.headers, header_map);
         add_query_parameters(&mut events, &self.query_parameters, query_parameters);
         add_path(&mut events, self.path_key.as_str(), request_path);
+        if let (Some(key), Some(method)) = (&self.method_key, method) {
+            add_method(&mut events, key, method);
+        }
 
         let now = Utc::now();
         for event in &mut events {
@@ -203,6 +314,79 @@ impl HttpSource for SimpleHttpSource {
     }
 }
 
+impl HttpSource for SimpleHttpSource {
+    fn build_events(
+        &self,
+        body: Bytes,
+        header_map: HeaderMap,
+        query_parameters: HashMap<String, String>,
+        request_path: &str,
+    ) -> Result<Vec<Event>, ErrorMessage> {
+        self.decode_events(0, None, body, header_map, query_parameters, request_path)
+    }
+
+    fn build_route_events(
+        &self,
+        route: usize,
+        method: &Method,
+        body: Bytes,
+        header_map: HeaderMap,
+        query_parameters: HashMap<String, String>,
+        request_path: &str,
+    ) -> Result<Vec<Event>, ErrorMessage> {
+        self.decode_events(
+            route,
+            Some(method),
+            body,
+            header_map,
+            query_parameters,
+            request_path,
+        )
+    }
+
+    fn build_response(&self, route: usize, events: &[Event]) -> Option<HttpResponse> {
+        let config = self.routes[route].response.as_ref()?;
+        let empty = LogEvent::default();
+        let log = events
+            .first()
+            .and_then(Event::maybe_as_log)
+            .unwrap_or(&empty);
+
+        let status = match config.status.render_string(log) {
+            Ok(status) => status
+                .trim()
+                .parse::<u16>()
+                .ok()
+                .and_then(|status| StatusCode::from_u16(status).ok()),
+            Err(error) => {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("response.status"),
+                    drop_event: false,
+                });
+                None
+            }
+        };
+        let body = match config.body.as_ref().map(|body| body.render(log)) {
+            Some(Ok(body)) => body,
+            Some(Err(error)) => {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("response.body"),
+                    drop_event: false,
+                });
+                Bytes::new()
+            }
+            None => Bytes::new(),
+        };
+
+        Some(HttpResponse {
+            status: status.unwrap_or(StatusCode::OK),
+            body,
+        })
+    }
+}
+
 #[async_trait::async_trait]
 #[typetag::serde(name = "http")]
 impl SourceConfig for SimpleHttpConfig {
@@ -211,7 +395,92 @@ impl SourceConfig for SimpleHttpConfig {
             return Err("Using `encoding` is deprecated and does not have any effect when `decoding` or `framing` is provided. Configure `framing` and `decoding` instead.".into());
         }
 
-        let (framing, decoding) = if let Some(encoding) = self.encoding {
+        let (framing, decoding) = self.framing_and_decoding();
+        let default_route = HttpRouteConfig {
+            path: self.path.clone(),
+            strict_path: None,
+            method: None,
+            framing: None,
+            decoding: None,
+            response: None,
+        };
+        let route_configs = if self.routes.is_empty() {
+            std::slice::from_ref(&default_route)
+        } else {
+            self.routes.as_slice()
+        };
+
+        let mut routes = Vec::with_capacity(route_configs.len());
+        let mut source_routes = Vec::with_capacity(route_configs.len());
+        for route in route_configs {
+            let decoding = route.decoding.clone().unwrap_or_else(|| decoding.clone());
+            let framing = route
+                .framing
+                .clone()
+                .or_else(|| route.decoding.as_ref().map(|d| d.default_stream_framing()))
+                .unwrap_or_else(|| framing.clone());
+            let decoder = DecodingConfig::new(framing, decoding, LogNamespace::Legacy)
+                .with_error_mode(self.on_error)
+                .build()?;
+
+            routes.push(HttpRoute {
+                path: route.path.clone(),
+                method: route.method.unwrap_or(self.method),
+                strict_path: route.strict_path.unwrap_or(self.strict_path),
+            });
+            source_routes.push(SimpleHttpRoute {
+                decoder,
+                response: route.response.clone().or_else(|| self.response.clone()),
+            });
+        }
+
+        let source = SimpleHttpSource {
+            headers: self.headers.clone(),
+            query_parameters: self.query_parameters.clone(),
+            path_key: self.path_key.clone(),
+            method_key: self.method_key.clone(),
+            headers_key: self.headers_key.clone(),
+            query_parameters_key: self.query_parameters_key.clone(),
+            routes: source_routes,
+        };
+        source.run_routes(
+            self.address,
+            routes,
+            &self.tls,
+            &self.auth,
+            cx,
+            self.acknowledgements,
+        )
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        let output_type = |decoding: Option<&DeserializerConfig>| {
+            decoding.map(|d| d.output_type()).unwrap_or(DataType::Log)
+        };
+        let mut data_type = output_type(self.decoding.as_ref());
+        for route in &self.routes {
+            data_type |= output_type(route.decoding.as_ref().or(self.decoding.as_ref()));
+        }
+        vec![Output::default(self.on_error.output_type(data_type))]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "http"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::tcp(self.address)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+impl SimpleHttpConfig {
+    /// Returns the framing and decoding of the source, which are the defaults of the routes.
+    fn framing_and_decoding(&self) -> (FramingConfig, DeserializerConfig) {
+        if let Some(encoding) = self.encoding {
             match encoding {
                 Encoding::Text => (
                     NewlineDelimitedDecoderConfig::new().into(),
@@ -237,46 +506,51 @@ impl SourceConfig for SimpleHttpConfig {
                 .clone()
                 .unwrap_or_else(|| decoding.default_stream_framing());
             (framing, decoding)
-        };
-
-        let decoder = DecodingConfig::new(framing, decoding, LogNamespace::Legacy).build();
-        let source = SimpleHttpSource {
-            headers: self.headers.clone(),
-            query_parameters: self.query_parameters.clone(),
-            path_key: self.path_key.clone(),
-            decoder,
-        };
-        source.run(
-            self.address,
-            self.path.as_str(),
-            self.method,
-            self.strict_path,
-            &self.tls,
-            &self.auth,
-            cx,
-            self.acknowledgements,
-        )
+        }
     }
+}
 
-    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
-        vec![Output::default(
-            self.decoding
-                .as_ref()
-                .map(|d| d.output_type())
-                .unwrap_or(DataType::Log),
-        )]
+fn add_method(events: &mut [Event], key: &str, method: &Method) {
+    for event in events.iter_mut() {
+        event
+            .as_mut_log()
+            .try_insert(key, Value::from(method.as_str().to_owned()));
     }
+}
 
-    fn source_type(&self) -> &'static str {
-        "http"
-    }
+fn add_all_headers(events: &mut [Event], key: &str, headers: &HeaderMap) {
+    let headers = headers
+        .keys()
+        .map(|name| {
+            let value = headers.get(name).map(HeaderValue::as_bytes);
+            (
+                name.as_str().to_owned(),
+                Value::from(value.map(Bytes::copy_from_slice)),
+            )
+        })
+        .collect::<BTreeMap<_, _>>();
 
-    fn resources(&self) -> Vec<Resource> {
-        vec![Resource::tcp(self.address)]
+    for event in events.iter_mut() {
+        event
+            .as_mut_log()
+            .try_insert(key, Value::Object(headers.clone()));
     }
+}
 
-    fn can_acknowledge(&self) -> bool {
-        true
+fn add_all_query_parameters(
+    events: &mut [Event],
+    key: &str,
+    query_parameters: &HashMap<String, String>,
+) {
+    let query_parameters = query_parameters
+        .iter()
+        .map(|(name, value)| (name.clone(), Value::from(value.clone())))
+        .collect::<BTreeMap<_, _>>();
+
+    for event in events.iter_mut() {
+        event
+            .as_mut_log()
+            .try_insert(key, Value::Object(query_parameters.clone()));
     }
 }
 
@@ -308,7 +582,7 @@ mod tests {
     use std::{collections::BTreeMap, io::Write, net::SocketAddr};
 
     use codecs::{
-        decoding::{DeserializerConfig, FramingConfig},
+        decoding::{DecodingErrorMode, DeserializerConfig, FramingConfig},
         BytesDecoderConfig, JsonDeserializerConfig,
     };
     use flate2::{
@@ -371,10 +645,16 @@ mod tests {
                 auth: None,
                 strict_path,
                 path_key,
+                method_key: None,
+                headers_key: None,
+                query_parameters_key: None,
                 path,
                 method,
                 framing,
                 decoding,
+                on_error: DecodingErrorMode::Drop,
+                response: None,
+                routes: Vec::new(),
                 acknowledgements: acknowledgements.into(),
             }
             .build(context)
@@ -1109,4 +1389,70 @@ mod tests {
 
         assert_eq!(200, send_request(addr, "GET", "", "/").await);
     }
+
+    #[tokio::test]
+    async fn http_routes() {
+        components::init_test();
+        let (sender, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let address = next_addr();
+        let config: SimpleHttpConfig = toml::from_str(&format!(
+            r#"
+            address = "{}"
+            method_key = "http_method"
+            headers_key = "http_headers"
+            query_parameters_key = "http_query"
+
+            [[routes]]
+            path = "/logs"
+
+            [[routes]]
+            path = "/items"
+            method = "PUT"
+            decoding.codec = "json"
+            response.status = "{{{{ status }}}}"
+            response.body = "{{{{ id }}}} accepted"
+            "#,
+            address
+        ))
+        .unwrap();
+        let context = SourceContext::new_test(sender, None);
+        tokio::spawn(async move { config.build(context).await.unwrap().await.unwrap() });
+        wait_for_tcp(address).await;
+
+        let client = reqwest::Client::new();
+        let events = spawn_collect_n(
+            async move {
+                let response = client
+                    .put(&format!("http://{}/items?tenant=a", address))
+                    .header("x-request-id", "42")
+                    .body(r#"{"id": "abc", "status": 202}"#)
+                    .send()
+                    .await
+                    .unwrap();
+                assert_eq!(response.status().as_u16(), 202);
+                assert_eq!(response.text().await.unwrap(), "abc accepted");
+
+                assert_eq!(200, send_with_path(address, "line", "/logs").await);
+                assert_ne!(200, send_with_path(address, "line", "/other").await);
+            },
+            rx,
+            2,
+        )
+        .await;
+
+        let log = events[0].as_log();
+        assert_eq!(log["id"], "abc".into());
+        assert_eq!(log["path"], "/items".into());
+        assert_eq!(log["http_method"], "PUT".into());
+        assert_eq!(
+            log["http_headers"].as_object().unwrap()["x-request-id"],
+            "42".into()
+        );
+        assert_eq!(log["http_query.tenant"], "a".into());
+
+        let log = events[1].as_log();
+        assert_eq!(log[log_schema().message_key()], "line".into());
+        assert_eq!(log["path"], "/logs".into());
+        assert_eq!(log["http_method"], "POST".into());
+    }
 }
@@ -0,0 +1,174 @@
+use serde_json::Value;
+use snafu::Snafu;
+
+#[derive(Debug, PartialEq, Snafu)]
+pub enum JsonPathError {
+    #[snafu(display("JSONPath {:?} must start with `$`", path))]
+    MissingRoot { path: String },
+    #[snafu(display("Invalid JSONPath {:?} at position {}", path, position))]
+    InvalidSegment { path: String, position: usize },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Segment {
+    Field(String),
+    Index(i64),
+    Wildcard,
+}
+
+/// A JSONPath made of fields (`.name` or `['name']`), indices (`[0]`, or `[-1]` from the end)
+/// and wildcards (`.*` or `[*]`), such as `$.data.items[*]`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonPath {
+    segments: Vec<Segment>,
+}
+
+impl JsonPath {
+    pub fn parse(path: &str) -> Result<Self, JsonPathError> {
+        let rest = path
+            .strip_prefix('$')
+            .ok_or_else(|| JsonPathError::MissingRoot { path: path.into() })?;
+        let invalid = |rest: &str| JsonPathError::InvalidSegment {
+            path: path.into(),
+            position: path.len() - rest.len(),
+        };
+
+        let mut segments = Vec::new();
+        let mut rest = rest;
+        while !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix('.') {
+                let end = after.find(|c| c == '.' || c == '[').unwrap_or(after.len());
+                let segment = match &after[..end] {
+                    "" => return Err(invalid(rest)),
+                    "*" => Segment::Wildcard,
+                    name => Segment::Field(name.into()),
+                };
+                segments.push(segment);
+                rest = &after[end..];
+            } else if let Some(after) = rest.strip_prefix('[') {
+                let end = after.find(']').ok_or_else(|| invalid(rest))?;
+                let inner = after[..end].trim();
+                let segment = if inner == "*" {
+                    Segment::Wildcard
+                } else if let Some(name) = inner
+                    .strip_prefix('\'')
+                    .and_then(|inner| inner.strip_suffix('\''))
+                    .or_else(|| {
+                        inner
+                            .strip_prefix('"')
+                            .and_then(|inner| inner.strip_suffix('"'))
+                    })
+                {
+                    Segment::Field(name.into())
+                } else {
+                    Segment::Index(inner.parse().map_err(|_| invalid(rest))?)
+                };
+                segments.push(segment);
+                rest = &after[end + 1..];
+            } else {
+                return Err(invalid(rest));
+            }
+        }
+        Ok(Self { segments })
+    }
+
+    /// Returns the values matched by the path.
+    pub fn select<'a>(&self, value: &'a Value) -> Vec<&'a Value> {
+        let mut values = vec![value];
+        for segment in &self.segments {
+            values = values
+                .into_iter()
+                .flat_map(|value| -> Vec<&'a Value> {
+                    match (segment, value) {
+                        (Segment::Field(name), Value::Object(object)) => {
+                            object.get(name).into_iter().collect()
+                        }
+                        (Segment::Index(index), Value::Array(array)) => {
+                            let index = if *index < 0 {
+                                array.len() as i64 + index
+                            } else {
+                                *index
+                            };
+                            usize::try_from(index)
+                                .ok()
+                                .and_then(|index| array.get(index))
+                                .into_iter()
+                                .collect()
+                        }
+                        (Segment::Wildcard, Value::Array(array)) => array.iter().collect(),
+                        (Segment::Wildcard, Value::Object(object)) => object.values().collect(),
+                        _ => Vec::new(),
+                    }
+                })
+                .collect();
+        }
+        values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parses_paths() {
+        assert_eq!(
+            JsonPath::parse("$.data['the items'][*].id[-1]").unwrap(),
+            JsonPath {
+                segments: vec![
+                    Segment::Field("data".into()),
+                    Segment::Field("the items".into()),
+                    Segment::Wildcard,
+                    Segment::Field("id".into()),
+                    Segment::Index(-1),
+                ]
+            }
+        );
+        assert_eq!(
+            JsonPath::parse("$").unwrap(),
+            JsonPath {
+                segments: Vec::new()
+            }
+        );
+        assert_eq!(
+            JsonPath::parse("data.items").unwrap_err(),
+            JsonPathError::MissingRoot {
+                path: "data.items".into()
+            }
+        );
+        assert_eq!(
+            JsonPath::parse("$.data..items").unwrap_err(),
+            JsonPathError::InvalidSegment {
+                path: "$.data..items".into(),
+                position: 6
+            }
+        );
+        assert_eq!(
+            JsonPath::parse("$.items[first]").unwrap_err(),
+            JsonPathError::InvalidSegment {
+                path: "$.items[first]".into(),
+                position: 7
+            }
+        );
+    }
+
+    #[test]
+    fn selects_values() {
+        let value = json!({
+            "data": {
+                "items": [{"id": 1}, {"id": 2}, {"name": "three"}],
+                "next": null,
+            },
+        });
+        let select = |path: &str| JsonPath::parse(path).unwrap().select(&value);
+
+        assert_eq!(select("$.data.items[*].id"), vec![&json!(1), &json!(2)]);
+        assert_eq!(select("$.data.items[-1].name"), vec![&json!("three")]);
+        assert_eq!(select("$.data.next"), vec![&Value::Null]);
+        assert!(select("$.data.cursor").is_empty());
+        assert!(select("$.data.items[5]").is_empty());
+        assert_eq!(select("$.*.next"), vec![&Value::Null]);
+    }
+}
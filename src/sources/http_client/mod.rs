@@ -0,0 +1,545 @@
+//! The `http_client` source.
+//!
+//! The endpoint is requested at each interval, with the query parameters rendered for the
+//! request, and the pages of the response are followed according to the pagination strategy. The
+//! events are decoded from the responses with the configured codecs, or are the values selected
+//! from the JSON responses by a JSONPath.
+
+use std::{collections::BTreeMap, time::Duration};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use codecs::decoding::{DecodingErrorMode, DeserializerConfig, FramingConfig};
+use futures::StreamExt;
+use hyper::{Body, Request};
+use indexmap::IndexMap;
+use snafu::{ResultExt, Snafu};
+use tokio_stream::wrappers::IntervalStream;
+use url::Url;
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, ByteSizeOf};
+
+use self::{
+    json_path::{JsonPath, JsonPathError},
+    pagination::{PageResponse, Pagination, PaginationConfig},
+};
+use crate::{
+    codecs::{Decoder, DecodingConfig},
+    config::{
+        log_schema, DataType, GenerateConfig, Output, ProxyConfig, SourceConfig, SourceContext,
+        SourceDescription,
+    },
+    event::{Event, LogEvent, Value},
+    http::{Auth, HttpClient},
+    internal_events::{
+        EndpointBytesReceived, EventsReceived, HttpClientHttpError, HttpClientHttpResponseError,
+        HttpClientParseError, RequestCompleted, StreamClosedError,
+    },
+    serde::{default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    sources::util::decode_message,
+    template::{Template, TemplateParseError},
+    tls::{TlsConfig, TlsSettings},
+    SourceSender,
+};
+
+mod json_path;
+mod pagination;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Invalid endpoint: {}", source))]
+    InvalidEndpoint { source: url::ParseError },
+    #[snafu(display("Invalid template of query parameter {:?}: {}", param, source))]
+    InvalidQueryTemplate {
+        param: String,
+        source: TemplateParseError,
+    },
+    #[snafu(display("Invalid `records_path`: {}", source))]
+    InvalidRecordsPath { source: JsonPathError },
+    #[snafu(display("Invalid `pagination.cursor_path`: {}", source))]
+    InvalidCursorPath { source: JsonPathError },
+    #[snafu(display("`max_pages` must be greater than zero"))]
+    NoPages,
+}
+
+/// Configuration for the `http_client` source.
+#[configurable_component(source)]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct HttpClientConfig {
+    /// The endpoint to request.
+    endpoint: String,
+
+    /// The interval between the requests of the endpoint, in seconds.
+    #[serde(default = "default_scrape_interval_secs")]
+    #[derivative(Default(value = "default_scrape_interval_secs()"))]
+    scrape_interval_secs: u64,
+
+    /// Custom parameters for the query string of the requests, which are added to the ones of the
+    /// endpoint.
+    ///
+    /// The values are templates rendered for each request, with `timestamp` being the time of the
+    /// request and `previous_timestamp` the time of the previous request, such as
+    /// `{{ previous_timestamp }}` or `%Y-%m-%d`.
+    #[serde(default)]
+    query: IndexMap<String, Vec<String>>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pagination: PaginationConfig,
+
+    /// The maximum number of pages requested at each interval.
+    #[serde(default = "default_max_pages")]
+    #[derivative(Default(value = "default_max_pages()"))]
+    max_pages: usize,
+
+    /// The JSONPath of the records of the JSON responses, such as `$.data.items`, each of which is
+    /// an event. The arrays selected are split into their elements.
+    ///
+    /// When set, the responses are parsed as JSON instead of being decoded by `framing` and
+    /// `decoding`.
+    records_path: Option<String>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    on_error: DecodingErrorMode,
+
+    #[configurable(derived)]
+    tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    auth: Option<Auth>,
+}
+
+const fn default_scrape_interval_secs() -> u64 {
+    15
+}
+
+const fn default_max_pages() -> usize {
+    100
+}
+
+inventory::submit! {
+    SourceDescription::new::<HttpClientConfig>("http_client")
+}
+
+impl GenerateConfig for HttpClientConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            endpoint: "http://localhost:8080/items".to_string(),
+            ..Self::default()
+        })
+        .unwrap()
+    }
+}
+
+impl HttpClientConfig {
+    fn build_source(&self, proxy: &ProxyConfig) -> crate::Result<HttpClientSource> {
+        if self.max_pages == 0 {
+            return Err(BuildError::NoPages.into());
+        }
+        let endpoint = Url::parse(&self.endpoint).context(InvalidEndpointSnafu)?;
+        let query = self
+            .query
+            .iter()
+            .flat_map(|(param, values)| values.iter().map(move |value| (param, value)))
+            .map(|(param, value)| {
+                Template::try_from(value.as_str())
+                    .map(|template| (param.clone(), template))
+                    .context(InvalidQueryTemplateSnafu { param })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let records_path = self
+            .records_path
+            .as_deref()
+            .map(JsonPath::parse)
+            .transpose()
+            .context(InvalidRecordsPathSnafu)?;
+        let pagination = Pagination::try_from(&self.pagination).context(InvalidCursorPathSnafu)?;
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, proxy)?;
+        let decoder = DecodingConfig::new(
+            self.framing.clone(),
+            self.decoding.clone(),
+            LogNamespace::Legacy,
+        )
+        .with_error_mode(self.on_error)
+        .build()?;
+
+        Ok(HttpClientSource {
+            endpoint,
+            query,
+            pagination,
+            max_pages: self.max_pages,
+            records_path,
+            decoder,
+            client,
+            auth: self.auth.clone(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "http_client")]
+impl SourceConfig for HttpClientConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let source = self.build_source(&cx.proxy)?;
+        let interval = Duration::from_secs(self.scrape_interval_secs);
+        Ok(Box::pin(source.run(interval, cx.out, cx.shutdown)))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        match self.records_path {
+            Some(_) => vec![Output::default(DataType::Log)],
+            None => vec![Output::default(
+                self.on_error.output_type(self.decoding.output_type()),
+            )],
+        }
+    }
+
+    fn source_type(&self) -> &'static str {
+        "http_client"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+struct HttpClientSource {
+    endpoint: Url,
+    query: Vec<(String, Template)>,
+    pagination: Pagination,
+    max_pages: usize,
+    records_path: Option<JsonPath>,
+    decoder: Decoder,
+    client: HttpClient,
+    auth: Option<Auth>,
+}
+
+impl HttpClientSource {
+    async fn run(
+        self,
+        interval: Duration,
+        mut out: SourceSender,
+        shutdown: ShutdownSignal,
+    ) -> Result<(), ()> {
+        let mut ticks = IntervalStream::new(tokio::time::interval(interval)).take_until(shutdown);
+        let mut previous_timestamp = Utc::now() - chrono::Duration::from_std(interval).unwrap();
+
+        while ticks.next().await.is_some() {
+            let timestamp = Utc::now();
+            let mut url = match self.first_page(timestamp, previous_timestamp) {
+                Some(url) => url,
+                None => continue,
+            };
+            previous_timestamp = timestamp;
+
+            for page in 1..=self.max_pages {
+                let (events, next_url) = match self.request(&url).await {
+                    Some(response) => response,
+                    None => break,
+                };
+
+                let count = events.len();
+                if let Err(error) = out.send_batch(events).await {
+                    emit!(StreamClosedError { error, count });
+                    return Err(());
+                }
+
+                url = match next_url {
+                    Some(next_url) => next_url,
+                    None => break,
+                };
+                if page == self.max_pages {
+                    warn!(
+                        message = "Reached the maximum number of pages, skipping the next ones.",
+                        max_pages = self.max_pages,
+                        next_url = %url,
+                        internal_log_rate_secs = 60,
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the URL of the first page, with the query parameters rendered for the request.
+    fn first_page(
+        &self,
+        timestamp: DateTime<Utc>,
+        previous_timestamp: DateTime<Utc>,
+    ) -> Option<Url> {
+        let mut context = LogEvent::default();
+        context.insert(log_schema().timestamp_key(), timestamp);
+        context.insert("previous_timestamp", previous_timestamp);
+
+        let mut url = self.endpoint.clone();
+        if !self.query.is_empty() {
+            let mut pairs = url.query_pairs_mut();
+            for (param, template) in &self.query {
+                match template.render_string(&context) {
+                    Ok(value) => pairs.append_pair(param, &value),
+                    Err(error) => {
+                        error!(
+                            message = "Failed to render the query parameter, skipping the request.",
+                            %param,
+                            %error,
+                            internal_log_rate_secs = 60,
+                        );
+                        return None;
+                    }
+                };
+            }
+        }
+        Some(self.pagination.first_page(url))
+    }
+
+    /// Requests a page, and returns its events and the URL of the next page.
+    async fn request(&self, url: &Url) -> Option<(Vec<Event>, Option<Url>)> {
+        let uri = url.as_str().parse::<http::Uri>().ok()?;
+        let mut request = Request::get(&uri)
+            .body(Body::empty())
+            .expect("error creating request");
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+
+        let start = std::time::Instant::now();
+        let response = match self.client.send(request).await {
+            Ok(response) => response,
+            Err(error) => {
+                emit!(HttpClientHttpError {
+                    error: error.into(),
+                    url: url.as_str(),
+                });
+                return None;
+            }
+        };
+        let (parts, body) = response.into_parts();
+        if !parts.status.is_success() {
+            emit!(HttpClientHttpResponseError {
+                code: parts.status,
+                url: url.as_str(),
+            });
+            return None;
+        }
+        let body = match hyper::body::to_bytes(body).await {
+            Ok(body) => body,
+            Err(error) => {
+                emit!(HttpClientHttpError {
+                    error: error.into(),
+                    url: url.as_str(),
+                });
+                return None;
+            }
+        };
+        emit!(EndpointBytesReceived {
+            byte_size: body.len(),
+            protocol: "http",
+            endpoint: url.as_str(),
+        });
+        emit!(RequestCompleted {
+            start,
+            end: std::time::Instant::now(),
+        });
+
+        let json = if self.records_path.is_some() || self.pagination.needs_body() {
+            match serde_json::from_slice::<serde_json::Value>(&body) {
+                Ok(json) => Some(json),
+                Err(error) => {
+                    emit!(HttpClientParseError {
+                        error,
+                        url: url.as_str(),
+                    });
+                    return None;
+                }
+            }
+        } else {
+            None
+        };
+
+        let events = match (&self.records_path, &json) {
+            (Some(records_path), Some(json)) => select_events(records_path, json),
+            _ => decode_message(
+                self.decoder.clone(),
+                "http_client",
+                &body,
+                Some(Utc::now()),
+                &None,
+            )
+            .collect(),
+        };
+
+        let next_url = self.pagination.next_page(PageResponse {
+            url,
+            headers: &parts.headers,
+            body: json.as_ref(),
+            event_count: events.len(),
+        });
+        Some((events, next_url))
+    }
+}
+
+/// Returns the events of the records selected from the response, splitting the arrays.
+fn select_events(records_path: &JsonPath, json: &serde_json::Value) -> Vec<Event> {
+    let timestamp = Utc::now();
+    let events = records_path
+        .select(json)
+        .into_iter()
+        .flat_map(|record| match record {
+            serde_json::Value::Array(records) => records.iter().collect(),
+            record => vec![record],
+        })
+        .map(|record| {
+            let mut log = match Value::from(record.clone()) {
+                Value::Object(fields) => LogEvent::from(fields),
+                value => {
+                    let mut log = LogEvent::default();
+                    log.insert(log_schema().message_key(), value);
+                    log
+                }
+            };
+            log.try_insert(log_schema().source_type_key(), Bytes::from("http_client"));
+            log.try_insert(log_schema().timestamp_key(), timestamp);
+            Event::from(log)
+        })
+        .collect::<Vec<_>>();
+
+    emit!(EventsReceived {
+        count: events.len(),
+        byte_size: events.size_of(),
+    });
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use serde_json::json;
+    use warp::Filter;
+
+    use super::*;
+    use crate::test_util::{
+        components::{run_and_assert_source_compliance, HTTP_PULL_SOURCE_TAGS},
+        next_addr, wait_for_tcp,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<HttpClientConfig>();
+    }
+
+    #[test]
+    fn selects_records() {
+        let records_path = JsonPath::parse("$.data").unwrap();
+        let events = select_events(
+            &records_path,
+            &json!({"data": [{"id": 1, "user.name": "alice"}, "two"]}),
+        );
+
+        assert_eq!(events.len(), 2);
+        let log = events[0].as_log();
+        assert_eq!(log["id"], 1.into());
+        assert_eq!(
+            log.as_map().unwrap().get("user.name"),
+            Some(&Value::from("alice"))
+        );
+        assert_eq!(log[log_schema().source_type_key()], "http_client".into());
+        assert_eq!(events[1].as_log()[log_schema().message_key()], "two".into());
+    }
+
+    #[test]
+    fn renders_query_templates() {
+        let config: HttpClientConfig = toml::from_str(
+            r#"
+            endpoint = "http://localhost/items?limit=10"
+            query.date = ["%Y-%m-%d"]
+            query.since = ["{{ previous_timestamp }}"]
+            "#,
+        )
+        .unwrap();
+        let source = config.build_source(&ProxyConfig::default()).unwrap();
+        let timestamp = Utc.ymd(2022, 8, 1).and_hms(12, 0, 0);
+        let previous_timestamp = Utc.ymd(2022, 8, 1).and_hms(11, 59, 45);
+
+        let url = source.first_page(timestamp, previous_timestamp).unwrap();
+        let pairs = url.query_pairs().into_owned().collect::<Vec<_>>();
+        assert_eq!(pairs[0], ("limit".into(), "10".into()));
+        assert_eq!(pairs[1], ("date".into(), "2022-08-01".into()));
+        assert_eq!(pairs[2].0, "since");
+        assert!(pairs[2].1.starts_with("2022-08-01T11:59:45"));
+    }
+
+    #[test]
+    fn rejects_invalid_configs() {
+        for config in [
+            r#"endpoint = "localhost/items""#,
+            r#"endpoint = "http://localhost/items"
+            records_path = "items""#,
+            r#"endpoint = "http://localhost/items"
+            query.since = ["{{ previous_timestamp"]"#,
+            r#"endpoint = "http://localhost/items"
+            max_pages = 0"#,
+        ] {
+            let config: HttpClientConfig = toml::from_str(config).unwrap();
+            assert!(config.build_source(&ProxyConfig::default()).is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn follows_pages() {
+        let address = next_addr();
+        let items = warp::path!("items")
+            .and(warp::query::<std::collections::HashMap<String, String>>())
+            .map(|query: std::collections::HashMap<String, String>| {
+                let body = match query.get("after").map(String::as_str) {
+                    None => json!({"items": [{"id": 1}, {"id": 2}], "next": "b"}),
+                    Some("b") => json!({"items": [{"id": 3}], "next": null}),
+                    Some(_) => json!({"items": []}),
+                };
+                warp::reply::json(&body)
+            });
+        tokio::spawn(warp::serve(items).run(address));
+        wait_for_tcp(address).await;
+
+        let config: HttpClientConfig = toml::from_str(&format!(
+            r#"
+            endpoint = "http://{}/items"
+            scrape_interval_secs = 1
+            records_path = "$.items"
+            pagination.strategy = "cursor"
+            pagination.cursor_path = "$.next"
+            pagination.query_param = "after"
+            "#,
+            address
+        ))
+        .unwrap();
+
+        let events = run_and_assert_source_compliance(
+            config,
+            Duration::from_millis(500),
+            &HTTP_PULL_SOURCE_TAGS,
+        )
+        .await;
+        let ids = events
+            .iter()
+            .map(|event| event.as_log()["id"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![1.into(), 2.into(), 3.into()]);
+    }
+}
@@ -0,0 +1,300 @@
+use http::{header::LINK, HeaderMap};
+use url::Url;
+use vector_config::configurable_component;
+
+use super::json_path::{JsonPath, JsonPathError};
+
+/// Pagination configuration.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+pub enum PaginationConfig {
+    /// Only the endpoint itself is requested.
+    #[derivative(Default)]
+    None,
+
+    /// The next page is requested with the cursor found in the response body, until the response
+    /// has no cursor.
+    Cursor {
+        /// The JSONPath of the cursor in the response body, such as `$.meta.next_cursor`.
+        cursor_path: String,
+
+        /// The query parameter the cursor is passed with.
+        query_param: String,
+    },
+
+    /// The next page is the URL of the `next` relation of the `Link` header of the response,
+    /// until the response has no such relation.
+    LinkHeader,
+
+    /// The pages are requested by number, until a page has no events.
+    Page {
+        /// The query parameter the page number is passed with.
+        #[serde(default = "default_page_param")]
+        query_param: String,
+
+        /// The number of the first page.
+        #[serde(default = "default_start_page")]
+        start_page: u64,
+    },
+}
+
+fn default_page_param() -> String {
+    "page".to_owned()
+}
+
+const fn default_start_page() -> u64 {
+    1
+}
+
+/// The pagination strategy, with its JSONPath parsed.
+#[derive(Debug)]
+pub(super) enum Pagination {
+    None,
+    Cursor {
+        cursor_path: JsonPath,
+        param: String,
+    },
+    LinkHeader,
+    Page {
+        param: String,
+        start_page: u64,
+    },
+}
+
+/// What the response of a page tells about the next page.
+pub(super) struct PageResponse<'a> {
+    pub(super) url: &'a Url,
+    pub(super) headers: &'a HeaderMap,
+    pub(super) body: Option<&'a serde_json::Value>,
+    pub(super) event_count: usize,
+}
+
+impl TryFrom<&PaginationConfig> for Pagination {
+    type Error = JsonPathError;
+
+    fn try_from(config: &PaginationConfig) -> Result<Self, Self::Error> {
+        Ok(match config {
+            PaginationConfig::None => Self::None,
+            PaginationConfig::Cursor {
+                cursor_path,
+                query_param,
+            } => Self::Cursor {
+                cursor_path: JsonPath::parse(cursor_path)?,
+                param: query_param.clone(),
+            },
+            PaginationConfig::LinkHeader => Self::LinkHeader,
+            PaginationConfig::Page {
+                query_param,
+                start_page,
+            } => Self::Page {
+                param: query_param.clone(),
+                start_page: *start_page,
+            },
+        })
+    }
+}
+
+impl Pagination {
+    /// Whether the response body is needed to find the next page.
+    pub(super) const fn needs_body(&self) -> bool {
+        matches!(self, Self::Cursor { .. })
+    }
+
+    /// Returns the URL of the first page.
+    pub(super) fn first_page(&self, mut url: Url) -> Url {
+        if let Self::Page { param, start_page } = self {
+            set_query_param(&mut url, param, &start_page.to_string());
+        }
+        url
+    }
+
+    /// Returns the URL of the next page, if any.
+    pub(super) fn next_page(&self, response: PageResponse<'_>) -> Option<Url> {
+        match self {
+            Self::None => None,
+            Self::Cursor { cursor_path, param } => {
+                let cursor = match cursor_path.select(response.body?).first()? {
+                    serde_json::Value::String(cursor) if !cursor.is_empty() => cursor.clone(),
+                    serde_json::Value::Number(cursor) => cursor.to_string(),
+                    _ => return None,
+                };
+                let mut url = response.url.clone();
+                set_query_param(&mut url, param, &cursor);
+                Some(url)
+            }
+            Self::LinkHeader => response
+                .headers
+                .get_all(LINK)
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .find_map(find_next_link)
+                .and_then(|link| response.url.join(link).ok()),
+            Self::Page { param, start_page } => {
+                if response.event_count == 0 {
+                    return None;
+                }
+                let page = response
+                    .url
+                    .query_pairs()
+                    .find(|(key, _)| key == param)
+                    .and_then(|(_, page)| page.parse::<u64>().ok())
+                    .unwrap_or(*start_page);
+                let mut url = response.url.clone();
+                set_query_param(&mut url, param, &(page + 1).to_string());
+                Some(url)
+            }
+        }
+    }
+}
+
+/// Sets the query parameter, replacing its previous values.
+fn set_query_param(url: &mut Url, param: &str, value: &str) {
+    let pairs = url
+        .query_pairs()
+        .filter(|(key, _)| key != param)
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect::<Vec<_>>();
+    url.query_pairs_mut()
+        .clear()
+        .extend_pairs(pairs)
+        .append_pair(param, value);
+}
+
+/// Returns the URL of the `next` relation of a `Link` header, such as
+/// `<https://example.com/items?page=2>; rel="next", <https://example.com/items?page=5>; rel="last"`.
+fn find_next_link(header: &str) -> Option<&str> {
+    header.split(',').find_map(|link| {
+        let mut parts = link.split(';');
+        let target = parts.next()?.trim().strip_prefix('<')?.strip_suffix('>')?;
+        parts
+            .filter_map(|param| param.split_once('='))
+            .any(|(key, value)| {
+                key.trim().eq_ignore_ascii_case("rel")
+                    && value
+                        .trim()
+                        .trim_matches('"')
+                        .split_whitespace()
+                        .any(|rel| rel.eq_ignore_ascii_case("next"))
+            })
+            .then(|| target)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderValue;
+    use serde_json::json;
+
+    use super::*;
+
+    fn next_page(
+        pagination: &Pagination,
+        url: &str,
+        headers: &HeaderMap,
+        body: Option<&serde_json::Value>,
+        event_count: usize,
+    ) -> Option<String> {
+        pagination
+            .next_page(PageResponse {
+                url: &Url::parse(url).unwrap(),
+                headers,
+                body,
+                event_count,
+            })
+            .map(String::from)
+    }
+
+    #[test]
+    fn follows_cursors() {
+        let pagination = Pagination::try_from(&PaginationConfig::Cursor {
+            cursor_path: "$.meta.next".into(),
+            query_param: "after".into(),
+        })
+        .unwrap();
+        let url = "https://example.com/items?limit=10&after=a";
+
+        assert_eq!(
+            next_page(
+                &pagination,
+                url,
+                &HeaderMap::new(),
+                Some(&json!({"meta": {"next": "b"}})),
+                10
+            ),
+            Some("https://example.com/items?limit=10&after=b".into())
+        );
+        assert_eq!(
+            next_page(
+                &pagination,
+                url,
+                &HeaderMap::new(),
+                Some(&json!({"meta": {"next": 42}})),
+                10
+            ),
+            Some("https://example.com/items?limit=10&after=42".into())
+        );
+        for body in [
+            json!({"meta": {"next": null}}),
+            json!({"meta": {"next": ""}}),
+            json!({"meta": {}}),
+        ] {
+            assert_eq!(
+                next_page(&pagination, url, &HeaderMap::new(), Some(&body), 10),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn follows_link_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            LINK,
+            HeaderValue::from_static(
+                r#"</items?page=1>; rel="prev", </items?page=3>; rel="next", </items?page=9>; rel="last""#,
+            ),
+        );
+
+        assert_eq!(
+            next_page(
+                &Pagination::LinkHeader,
+                "https://example.com/items?page=2",
+                &headers,
+                None,
+                10
+            ),
+            Some("https://example.com/items?page=3".into())
+        );
+        assert_eq!(
+            next_page(
+                &Pagination::LinkHeader,
+                "https://example.com/items?page=9",
+                &HeaderMap::new(),
+                None,
+                10
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn follows_page_numbers() {
+        let pagination = Pagination::Page {
+            param: "page".into(),
+            start_page: 0,
+        };
+        let first = pagination.first_page(Url::parse("https://example.com/items?page=7").unwrap());
+        assert_eq!(first.as_str(), "https://example.com/items?page=0");
+
+        assert_eq!(
+            next_page(&pagination, first.as_str(), &HeaderMap::new(), None, 10),
+            Some("https://example.com/items?page=1".into())
+        );
+        assert_eq!(
+            next_page(&pagination, first.as_str(), &HeaderMap::new(), None, 0),
+            None
+        );
+    }
+}
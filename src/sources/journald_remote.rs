@@ -0,0 +1,459 @@
+//! The `journald_remote` source.
+//!
+//! Receives the journal entries uploaded by `systemd-journal-upload`, which posts them to the
+//! `/upload` path in the [journal export format][export_format]: each entry is a list of fields
+//! terminated by an empty line, where each field is either a `NAME=value` line, or a `NAME` line
+//! followed by the little-endian 64 bits size of the value, the value and a newline for the values
+//! that contain newlines or binary data.
+//!
+//! [export_format]: https://systemd.io/JOURNAL_EXPORT_FORMATS/#journal-export-format
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    net::SocketAddr,
+};
+
+use bytes::Bytes;
+use chrono::TimeZone;
+use snafu::Snafu;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+use warp::http::{header::CONTENT_TYPE, HeaderMap, StatusCode};
+
+use crate::{
+    config::{
+        log_schema, AcknowledgementsConfig, DataType, GenerateConfig, Output, Resource,
+        SourceConfig, SourceContext, SourceDescription,
+    },
+    event::{Event, LogEvent, Value},
+    serde::bool_or_struct,
+    sources::{
+        http::HttpMethod,
+        util::{ErrorMessage, HttpSource, HttpSourceAuthConfig},
+    },
+    tls::TlsEnableableConfig,
+};
+
+const CONTENT_TYPE_JOURNAL: &str = "application/vnd.fdo.journal";
+const HOSTNAME: &str = "_HOSTNAME";
+const MESSAGE: &str = "MESSAGE";
+const SOURCE_TIMESTAMP: &str = "_SOURCE_REALTIME_TIMESTAMP";
+const RECEIVED_TIMESTAMP: &str = "__REALTIME_TIMESTAMP";
+
+/// Configuration for the `journald_remote` source.
+#[configurable_component(source)]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct JournaldRemoteConfig {
+    /// The address to listen for uploads on.
+    ///
+    /// `systemd-journal-upload` sends the journal entries to port `19532` by default.
+    address: SocketAddr,
+
+    #[configurable(derived)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    auth: Option<HttpSourceAuthConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+inventory::submit! {
+    SourceDescription::new::<JournaldRemoteConfig>("journald_remote")
+}
+
+impl GenerateConfig for JournaldRemoteConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: "0.0.0.0:19532".parse().unwrap(),
+            tls: None,
+            auth: None,
+            acknowledgements: AcknowledgementsConfig::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "journald_remote")]
+impl SourceConfig for JournaldRemoteConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        JournaldRemoteSource.run(
+            self.address,
+            "upload",
+            HttpMethod::Post,
+            true,
+            &self.tls,
+            &self.auth,
+            cx,
+            self.acknowledgements,
+        )
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "journald_remote"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::tcp(self.address)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone)]
+struct JournaldRemoteSource;
+
+impl HttpSource for JournaldRemoteSource {
+    fn build_events(
+        &self,
+        body: Bytes,
+        header_map: HeaderMap,
+        _query_parameters: HashMap<String, String>,
+        _path: &str,
+    ) -> Result<Vec<Event>, ErrorMessage> {
+        let content_type = header_map
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok());
+        if content_type != Some(CONTENT_TYPE_JOURNAL) {
+            return Err(ErrorMessage::new(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                format!("Content-Type must be {:?}", CONTENT_TYPE_JOURNAL),
+            ));
+        }
+
+        parse_entries(&body)
+            .map(|entries| entries.into_iter().map(create_event).collect())
+            .map_err(|error| {
+                ErrorMessage::new(
+                    StatusCode::BAD_REQUEST,
+                    format!("Invalid journal export format: {}", error),
+                )
+            })
+    }
+}
+
+#[derive(Debug, Snafu, PartialEq)]
+enum ParseError {
+    #[snafu(display("Field name on line {} is not terminated", line))]
+    UnterminatedField { line: usize },
+    #[snafu(display("Binary field {:?} is truncated", name))]
+    TruncatedBinaryField { name: String },
+    #[snafu(display("Binary field {:?} is not terminated by a newline", name))]
+    UnterminatedBinaryField { name: String },
+}
+
+type Entry = BTreeMap<String, Value>;
+
+/// Parses the entries of a body in the journal export format. The fields that appear several
+/// times in an entry are collected into an array.
+fn parse_entries(mut body: &[u8]) -> Result<Vec<Entry>, ParseError> {
+    let mut entries = Vec::new();
+    let mut entry = Entry::new();
+    let mut line = 1;
+
+    while !body.is_empty() {
+        let end = body
+            .iter()
+            .position(|c| *c == b'\n')
+            .ok_or(ParseError::UnterminatedField { line })?;
+        let field = &body[..end];
+        body = &body[end + 1..];
+        line += 1;
+
+        if field.is_empty() {
+            if !entry.is_empty() {
+                entries.push(std::mem::take(&mut entry));
+            }
+            continue;
+        }
+
+        let (name, value) = match field.iter().position(|c| *c == b'=') {
+            Some(equal) => (&field[..equal], Bytes::copy_from_slice(&field[equal + 1..])),
+            None => {
+                let name = String::from_utf8_lossy(field).into_owned();
+                if body.len() < 8 {
+                    return Err(ParseError::TruncatedBinaryField { name });
+                }
+                let mut size = [0; 8];
+                size.copy_from_slice(&body[..8]);
+                let size = u64::from_le_bytes(size) as usize;
+                body = &body[8..];
+                if body.len() <= size {
+                    return Err(ParseError::TruncatedBinaryField { name });
+                }
+                if body[size] != b'\n' {
+                    return Err(ParseError::UnterminatedBinaryField { name });
+                }
+                let value = Bytes::copy_from_slice(&body[..size]);
+                line += count_lines(&body[..=size]);
+                body = &body[size + 1..];
+                (field, value)
+            }
+        };
+        insert_field(
+            &mut entry,
+            String::from_utf8_lossy(name).into_owned(),
+            value,
+        );
+    }
+    // The last entry may not be terminated by an empty line.
+    if !entry.is_empty() {
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+fn count_lines(data: &[u8]) -> usize {
+    data.iter().filter(|c| **c == b'\n').count()
+}
+
+fn insert_field(entry: &mut Entry, name: String, value: Bytes) {
+    match entry.get_mut(&name) {
+        None => {
+            entry.insert(name, Value::Bytes(value));
+        }
+        Some(Value::Array(values)) => values.push(Value::Bytes(value)),
+        Some(existing) => {
+            let first = std::mem::replace(existing, Value::Null);
+            *existing = Value::Array(vec![first, Value::Bytes(value)]);
+        }
+    }
+}
+
+fn create_event(entry: Entry) -> Event {
+    let mut log = LogEvent::from(entry);
+
+    // Convert some journald-specific field names into Vector standard ones, as the `journald`
+    // source does.
+    if let Some(message) = log.remove(MESSAGE) {
+        log.insert(log_schema().message_key(), message);
+    }
+    if let Some(host) = log.remove(HOSTNAME) {
+        log.insert(log_schema().host_key(), host);
+    }
+    // Translate the timestamp, and so leave both old and new names.
+    if let Some(Value::Bytes(timestamp)) = log
+        .get(SOURCE_TIMESTAMP)
+        .or_else(|| log.get(RECEIVED_TIMESTAMP))
+    {
+        if let Ok(timestamp) = String::from_utf8_lossy(timestamp).parse::<u64>() {
+            let timestamp = chrono::Utc.timestamp(
+                (timestamp / 1_000_000) as i64,
+                (timestamp % 1_000_000) as u32 * 1_000,
+            );
+            log.insert(log_schema().timestamp_key(), Value::Timestamp(timestamp));
+        }
+    }
+    log.try_insert(
+        log_schema().source_type_key(),
+        Bytes::from("journald_remote"),
+    );
+
+    log.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use futures::Stream;
+    use vector_core::event::EventStatus;
+
+    use super::*;
+    use crate::{
+        test_util::{
+            components::{assert_source_compliance, HTTP_PUSH_SOURCE_TAGS},
+            next_addr, spawn_collect_n, wait_for_tcp,
+        },
+        SourceSender,
+    };
+
+    const SAMPLE_BODY: &[u8] =
+        b"__CURSOR=s=739ad463348b4ceca5a9e69c95a3c93f;i=4ece7;b=6c7c6013a8ee4a8b8ca52dbd86eb13d2\n\
+__REALTIME_TIMESTAMP=1342540861416351\n\
+_HOSTNAME=fedora\n\
+_SYSTEMD_UNIT=sshd.service\n\
+MESSAGE=Accepted publickey for root\n\
+\n\
+__REALTIME_TIMESTAMP=1342540861421465\n\
+_HOSTNAME=fedora\n\
+PRIORITY=6\n\
+MESSAGE=Started session\n\
+\n";
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<JournaldRemoteConfig>();
+    }
+
+    #[test]
+    fn parses_text_fields() {
+        let entries = parse_entries(SAMPLE_BODY).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["_SYSTEMD_UNIT"], "sshd.service".into());
+        assert_eq!(entries[0]["MESSAGE"], "Accepted publickey for root".into());
+        assert_eq!(entries[1]["PRIORITY"], "6".into());
+    }
+
+    #[test]
+    fn parses_binary_fields() {
+        let mut body = b"MESSAGE\n".to_vec();
+        body.extend_from_slice(&11u64.to_le_bytes());
+        body.extend_from_slice(b"foo\nbar\0baz\n");
+        body.extend_from_slice(b"PRIORITY=3\n");
+
+        let entries = parse_entries(&body).unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            entries[0]["MESSAGE"],
+            Value::from(Bytes::from_static(b"foo\nbar\0baz"))
+        );
+        assert_eq!(entries[0]["PRIORITY"], "3".into());
+    }
+
+    #[test]
+    fn collects_repeated_fields() {
+        let entries = parse_entries(b"TAG=a\nTAG=b\nTAG=c\n\n").unwrap();
+
+        assert_eq!(
+            entries[0]["TAG"],
+            Value::Array(vec!["a".into(), "b".into(), "c".into()])
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_bodies() {
+        assert_eq!(
+            parse_entries(b"MESSAGE=foo\nPRIORITY=3").unwrap_err(),
+            ParseError::UnterminatedField { line: 2 }
+        );
+
+        let mut body = b"MESSAGE\n".to_vec();
+        body.extend_from_slice(&100u64.to_le_bytes());
+        body.extend_from_slice(b"foo\n");
+        assert_eq!(
+            parse_entries(&body).unwrap_err(),
+            ParseError::TruncatedBinaryField {
+                name: "MESSAGE".into()
+            }
+        );
+
+        let mut body = b"MESSAGE\n".to_vec();
+        body.extend_from_slice(&3u64.to_le_bytes());
+        body.extend_from_slice(b"foo!");
+        assert_eq!(
+            parse_entries(&body).unwrap_err(),
+            ParseError::UnterminatedBinaryField {
+                name: "MESSAGE".into()
+            }
+        );
+    }
+
+    async fn source(status: EventStatus) -> (impl Stream<Item = Event> + Unpin, SocketAddr) {
+        let (sender, recv) = SourceSender::new_test_finalize(status);
+        let address = next_addr();
+        let context = SourceContext::new_test(sender, None);
+        tokio::spawn(async move {
+            JournaldRemoteConfig {
+                address,
+                tls: None,
+                auth: None,
+                acknowledgements: true.into(),
+            }
+            .build(context)
+            .await
+            .unwrap()
+            .await
+            .unwrap()
+        });
+        wait_for_tcp(address).await;
+        (recv, address)
+    }
+
+    async fn send(address: SocketAddr, content_type: &str, body: &'static [u8]) -> u16 {
+        reqwest::Client::new()
+            .post(&format!("http://{}/upload", address))
+            .header("Content-Type", content_type)
+            .body(body)
+            .send()
+            .await
+            .unwrap()
+            .status()
+            .as_u16()
+    }
+
+    #[tokio::test]
+    async fn receives_uploads() {
+        assert_source_compliance(&HTTP_PUSH_SOURCE_TAGS, async {
+            let (rx, addr) = source(EventStatus::Delivered).await;
+
+            let events = spawn_collect_n(
+                async move { assert_eq!(200, send(addr, CONTENT_TYPE_JOURNAL, SAMPLE_BODY).await) },
+                rx,
+                2,
+            )
+            .await;
+
+            let log = events[0].as_log();
+            assert_eq!(
+                log[log_schema().message_key()],
+                "Accepted publickey for root".into()
+            );
+            assert_eq!(log[log_schema().host_key()], "fedora".into());
+            assert_eq!(
+                log[log_schema().timestamp_key()],
+                Value::Timestamp(Utc.timestamp(1342540861, 416351000))
+            );
+            assert_eq!(
+                log[log_schema().source_type_key()],
+                "journald_remote".into()
+            );
+            assert_eq!(log["_SYSTEMD_UNIT"], "sshd.service".into());
+            assert_eq!(
+                events[1].as_log()[log_schema().message_key()],
+                "Started session".into()
+            );
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn rejects_other_content_types() {
+        let (_rx, addr) = source(EventStatus::Delivered).await;
+
+        assert_eq!(415, send(addr, "text/plain", SAMPLE_BODY).await);
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_uploads() {
+        let (_rx, addr) = source(EventStatus::Delivered).await;
+
+        assert_eq!(400, send(addr, CONTENT_TYPE_JOURNAL, b"MESSAGE").await);
+    }
+
+    #[tokio::test]
+    async fn reports_rejected_events() {
+        let (rx, addr) = source(EventStatus::Rejected).await;
+
+        let events = spawn_collect_n(
+            async move { assert_eq!(400, send(addr, CONTENT_TYPE_JOURNAL, SAMPLE_BODY).await) },
+            rx,
+            2,
+        )
+        .await;
+
+        assert_eq!(events.len(), 2);
+    }
+}
@@ -2,22 +2,25 @@ use std::{
     collections::{BTreeMap, HashMap, HashSet},
     io::Cursor,
     sync::Arc,
+    time::Duration,
 };
 
 use async_stream::stream;
 use bytes::Bytes;
 use chrono::{DateTime, TimeZone, Utc};
 use codecs::{
-    decoding::{DeserializerConfig, FramingConfig},
+    decoding::{DecodingErrorMode, DeserializerConfig, FramingConfig},
     StreamDecodingError,
 };
-use futures::{Stream, StreamExt};
+use futures::{future::join_all, Stream, StreamExt};
 use rdkafka::{
     config::ClientConfig,
-    consumer::{Consumer, StreamConsumer},
+    consumer::{Consumer, ConsumerContext, Rebalance, StreamConsumer},
     message::{BorrowedMessage, Headers, Message},
+    ClientContext, Offset, Statistics, TopicPartitionList,
 };
 use snafu::{ResultExt, Snafu};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 use tokio_util::codec::FramedRead;
 
 use vector_config::configurable_component;
@@ -28,8 +31,8 @@ use vector_common::{byte_size_of::ByteSizeOf, finalizer::OrderedFinalizer};
 use crate::{
     codecs::{Decoder, DecodingConfig},
     config::{
-        log_schema, AcknowledgementsConfig, LogSchema, Output, SourceConfig, SourceContext,
-        SourceDescription,
+        log_schema, AcknowledgementsConfig, DataType, LogSchema, Output, SourceConfig,
+        SourceContext, SourceDescription,
     },
     event::{BatchNotifier, BatchStatus, Event, Value},
     internal_events::{
@@ -48,6 +51,12 @@ enum BuildError {
     KafkaCreateError { source: rdkafka::error::KafkaError },
     #[snafu(display("Could not subscribe to Kafka topics: {}", source))]
     KafkaSubscribeError { source: rdkafka::error::KafkaError },
+    #[snafu(display("Could not resolve the offsets of Kafka partitions: {}", source))]
+    KafkaOffsetsForTimesError { source: rdkafka::error::KafkaError },
+    #[snafu(display("Could not assign Kafka partitions: {}", source))]
+    KafkaAssignError { source: rdkafka::error::KafkaError },
+    #[snafu(display("Exactly one of `topics` and `assignments` must be set"))]
+    TopicsOrAssignments,
 }
 
 /// Configuration for the `kafka` source.
@@ -62,36 +71,54 @@ pub struct KafkaSourceConfig {
     /// allowing discovering all other hosts in the cluster.
     ///
     /// Must be in the form of `host:port`, and comma-separated.
-    bootstrap_servers: String,
+    pub(crate) bootstrap_servers: String,
 
     /// The Kafka topics names to read events from.
     ///
     /// Regular expression syntax is supported if the topic begins with `^`.
-    topics: Vec<String>,
+    ///
+    /// Can't be set along with `assignments`.
+    #[serde(default)]
+    pub(crate) topics: Vec<String>,
+
+    /// The partitions to read events from, bypassing the balancing of the partitions between the
+    /// members of the consumer group.
+    ///
+    /// Each partition is read from its configured offset, regardless of the offsets committed for
+    /// the consumer group.
+    ///
+    /// Can't be set along with `topics`.
+    #[serde(default)]
+    pub(crate) assignments: Vec<KafkaPartitionAssignment>,
 
     /// The consumer group name to be used to consume events from Kafka.
-    group_id: String,
+    pub(crate) group_id: String,
 
     /// If offsets for consumer group do not exist, set them using this strategy.
     ///
     /// See the [librdkafka documentation](https://github.com/edenhill/librdkafka/blob/master/CONFIGURATION.md) for the `auto.offset.reset` option for further clarification.
     #[serde(default = "default_auto_offset_reset")]
-    auto_offset_reset: String,
+    #[derivative(Default(value = "default_auto_offset_reset()"))]
+    pub(crate) auto_offset_reset: String,
 
     /// The Kafka session timeout, in milliseconds.
     #[serde(default = "default_session_timeout_ms")]
+    #[derivative(Default(value = "default_session_timeout_ms()"))]
     session_timeout_ms: u64,
 
     /// Timeout for network requests, in milliseconds.
     #[serde(default = "default_socket_timeout_ms")]
+    #[derivative(Default(value = "default_socket_timeout_ms()"))]
     socket_timeout_ms: u64,
 
     /// Maximum time the broker may wait to fill the response, in milliseconds.
     #[serde(default = "default_fetch_wait_max_ms")]
+    #[derivative(Default(value = "default_fetch_wait_max_ms()"))]
     fetch_wait_max_ms: u64,
 
     /// The frequency that the consumer offsets are committed (written) to offset storage, in milliseconds.
     #[serde(default = "default_commit_interval_ms")]
+    #[derivative(Default(value = "default_commit_interval_ms()"))]
     commit_interval_ms: u64,
 
     /// Overrides the name of the log field used to add the message key to each event.
@@ -100,6 +127,7 @@ pub struct KafkaSourceConfig {
     ///
     /// By default, `"message_key"` is used.
     #[serde(default = "default_key_field")]
+    #[derivative(Default(value = "default_key_field()"))]
     key_field: String,
 
     /// Overrides the name of the log field used to add the topic to each event.
@@ -108,6 +136,7 @@ pub struct KafkaSourceConfig {
     ///
     /// By default, `"topic"` is used.
     #[serde(default = "default_topic_key")]
+    #[derivative(Default(value = "default_topic_key()"))]
     topic_key: String,
 
     /// Overrides the name of the log field used to add the partition to each event.
@@ -116,6 +145,7 @@ pub struct KafkaSourceConfig {
     ///
     /// By default, `"partition"` is used.
     #[serde(default = "default_partition_key")]
+    #[derivative(Default(value = "default_partition_key()"))]
     partition_key: String,
 
     /// Overrides the name of the log field used to add the offset to each event.
@@ -124,6 +154,7 @@ pub struct KafkaSourceConfig {
     ///
     /// By default, `"offset"` is used.
     #[serde(default = "default_offset_key")]
+    #[derivative(Default(value = "default_offset_key()"))]
     offset_key: String,
 
     /// Overrides the name of the log field used to add the headers to each event.
@@ -132,29 +163,124 @@ pub struct KafkaSourceConfig {
     ///
     /// By default, `"headers"` is used.
     #[serde(default = "default_headers_key")]
+    #[derivative(Default(value = "default_headers_key()"))]
     headers_key: String,
 
     /// Advanced options set directly on the underlying `librdkafka` client.
     ///
     /// See the [librdkafka documentation](https://github.com/edenhill/librdkafka/blob/master/CONFIGURATION.md) for details.
-    librdkafka_options: Option<HashMap<String, String>>,
+    pub(crate) librdkafka_options: Option<HashMap<String, String>>,
 
     #[serde(flatten)]
-    auth: KafkaAuthConfig,
+    pub(crate) auth: KafkaAuthConfig,
 
     #[configurable(derived)]
     #[serde(default = "default_framing_message_based")]
     #[derivative(Default(value = "default_framing_message_based()"))]
-    framing: FramingConfig,
+    pub(crate) framing: FramingConfig,
 
     #[configurable(derived)]
     #[serde(default = "default_decoding")]
     #[derivative(Default(value = "default_decoding()"))]
-    decoding: DeserializerConfig,
+    pub(crate) decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub(crate) on_error: DecodingErrorMode,
+
+    #[configurable(derived)]
+    pub(crate) decoding_routes: Option<KafkaDecodingRoutes>,
 
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
-    acknowledgements: AcknowledgementsConfig,
+    pub(crate) acknowledgements: AcknowledgementsConfig,
+}
+
+/// Selects the deserializer of each message by the value of one of its headers, such as its
+/// content type, to read topics holding messages of mixed formats.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct KafkaDecodingRoutes {
+    /// The header whose value selects the deserializer of the message.
+    pub(crate) header: String,
+
+    /// The deserializers of the messages, by the value of their header.
+    ///
+    /// The messages without the header, or with a value not listed here, are decoded with
+    /// `decoding`.
+    pub(crate) routes: HashMap<String, DeserializerConfig>,
+}
+
+/// A partition statically assigned to the `kafka` source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct KafkaPartitionAssignment {
+    /// The topic of the partition.
+    pub(crate) topic: String,
+
+    /// The partition to read events from.
+    pub(crate) partition: i32,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub(crate) offset: KafkaStartOffset,
+}
+
+/// The offset a statically assigned partition is read from.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum KafkaStartOffset {
+    /// The first message of the partition.
+    Earliest,
+
+    /// The first message written to the partition once the source is started.
+    #[derivative(Default)]
+    Latest,
+
+    /// The first message whose timestamp, in milliseconds since the Unix epoch, is at or after
+    /// the given one.
+    Timestamp(#[configurable(transparent)] i64),
+
+    /// The message at the given offset.
+    Absolute(#[configurable(transparent)] i64),
+}
+
+impl KafkaStartOffset {
+    /// The offset to assign the partition at, which is the timestamp to resolve into an offset
+    /// for the `timestamp` variant.
+    const fn to_offset(self) -> Offset {
+        match self {
+            Self::Earliest => Offset::Beginning,
+            Self::Latest => Offset::End,
+            Self::Timestamp(timestamp) => Offset::Offset(timestamp),
+            Self::Absolute(offset) => Offset::Offset(offset),
+        }
+    }
+}
+
+/// A store of the consumed offsets kept outside of Kafka, such as the checkpoints of the
+/// `azure_event_hubs` source.
+#[async_trait::async_trait]
+pub(crate) trait OffsetStore: Send + Sync {
+    /// Loads the offset of the last message processed from the partition, if any.
+    async fn load(&self, topic: &str, partition: i32) -> Option<i64>;
+
+    /// Stores the offset of the last message processed from the partition.
+    async fn store(&self, topic: &str, partition: i32, offset: i64);
+}
+
+/// Options for the sources built on top of the `kafka` source.
+pub(crate) struct KafkaSourceOptions {
+    /// The source type added to the events.
+    pub(crate) source_type: &'static str,
+
+    /// The store the processed offsets are saved to in addition to Kafka, and the assigned
+    /// partitions resume from when it's ahead of the offsets committed to Kafka.
+    pub(crate) offset_store: Option<Arc<dyn OffsetStore>>,
 }
 
 const fn default_session_timeout_ms() -> u64 {
@@ -207,18 +333,43 @@ impl_generate_config_from_default!(KafkaSourceConfig);
 #[typetag::serde(name = "kafka")]
 impl SourceConfig for KafkaSourceConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
-        let consumer = create_consumer(self)?;
-        let decoder = DecodingConfig::new(
-            self.framing.clone(),
-            self.decoding.clone(),
-            LogNamespace::Legacy,
-        )
-        .build();
+        let options = KafkaSourceOptions {
+            source_type: "kafka",
+            offset_store: None,
+        };
+        self.build_with_options(cx, options)
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(self.output_type())]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "kafka"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+impl KafkaSourceConfig {
+    pub(crate) fn build_with_options(
+        &self,
+        cx: SourceContext,
+        options: KafkaSourceOptions,
+    ) -> crate::Result<super::Source> {
+        let (assignments_tx, assignments_rx) = mpsc::unbounded_channel();
+        let assignments_tx = options.offset_store.is_some().then(|| assignments_tx);
+        let consumer = create_consumer(self, assignments_tx)?;
+        let decoder = self.build_decoder()?;
         let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
 
         Ok(Box::pin(kafka_source(
             self.clone(),
             consumer,
+            assignments_rx,
+            options,
             decoder,
             cx.shutdown,
             cx.out,
@@ -226,23 +377,73 @@ impl SourceConfig for KafkaSourceConfig {
         )))
     }
 
-    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
-        vec![Output::default(self.decoding.output_type())]
+    /// The type of the events decoded by any of the deserializers.
+    pub(crate) fn output_type(&self) -> DataType {
+        self.decoding_routes
+            .iter()
+            .flat_map(|routes| routes.routes.values())
+            .fold(
+                self.on_error.output_type(self.decoding.output_type()),
+                |output_type, decoding| output_type | decoding.output_type(),
+            )
     }
 
-    fn source_type(&self) -> &'static str {
-        "kafka"
+    fn build_decoder(&self) -> crate::Result<MessageDecoder> {
+        let build = |decoding: &DeserializerConfig| {
+            DecodingConfig::new(self.framing.clone(), decoding.clone(), LogNamespace::Legacy)
+                .with_error_mode(self.on_error)
+                .build()
+        };
+        let mut decoder = MessageDecoder::from(build(&self.decoding)?);
+        if let Some(routes) = &self.decoding_routes {
+            decoder.header = Some(routes.header.clone());
+            for (value, decoding) in &routes.routes {
+                decoder.routes.insert(value.clone(), build(decoding)?);
+            }
+        }
+        Ok(decoder)
     }
+}
 
-    fn can_acknowledge(&self) -> bool {
-        true
+/// The decoders of the messages, selected by the value of one of their headers.
+struct MessageDecoder {
+    default: Decoder,
+    header: Option<String>,
+    routes: HashMap<String, Decoder>,
+}
+
+impl From<Decoder> for MessageDecoder {
+    fn from(default: Decoder) -> Self {
+        Self {
+            default,
+            header: None,
+            routes: HashMap::new(),
+        }
+    }
+}
+
+impl MessageDecoder {
+    fn select(&self, msg: &BorrowedMessage<'_>) -> &Decoder {
+        let header = match (&self.header, msg.headers()) {
+            (Some(header), Some(headers)) => (0..headers.count())
+                .filter_map(|i| headers.get(i))
+                .find(|(key, _)| key == header),
+            _ => None,
+        };
+        header
+            .and_then(|(_, value)| std::str::from_utf8(value).ok())
+            .and_then(|value| self.routes.get(value))
+            .unwrap_or(&self.default)
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn kafka_source(
     config: KafkaSourceConfig,
-    consumer: StreamConsumer<KafkaStatisticsContext>,
-    decoder: Decoder,
+    consumer: StreamConsumer<KafkaSourceContext>,
+    mut assignments: UnboundedReceiver<TopicPartitionList>,
+    options: KafkaSourceOptions,
+    decoder: MessageDecoder,
     mut shutdown: ShutdownSignal,
     mut out: SourceSender,
     acknowledgements: bool,
@@ -251,16 +452,26 @@ async fn kafka_source(
     let (finalizer, mut ack_stream) =
         OrderedFinalizer::<FinalizerEntry>::maybe_new(acknowledgements, shutdown.clone());
     let mut stream = consumer.stream();
-    let keys = Keys::from(log_schema(), &config);
+    let keys = Keys::from(log_schema(), &config, options.source_type);
 
     let mut topics = Topics::new(&config);
+    let mut offsets = Offsets {
+        consumer: Arc::clone(&consumer),
+        offset_store: options.offset_store,
+        pending: HashMap::new(),
+    };
+    let mut flush_interval =
+        tokio::time::interval(Duration::from_millis(config.commit_interval_ms));
 
     loop {
         tokio::select! {
             _ = &mut shutdown => break,
             entry = ack_stream.next() => if let Some((status, entry)) = entry {
-                handle_ack(&mut topics, status, entry, &consumer);
+                handle_ack(&mut topics, status, entry, &mut offsets);
             },
+            // Only receives assignments when there is an offset store.
+            Some(partitions) = assignments.recv() => offsets.resume(partitions).await,
+            _ = flush_interval.tick(), if offsets.offset_store.is_some() => offsets.flush().await,
             message = stream.next() => match message {
                 None => break,  // WHY?
                 Some(Err(error)) => emit!(KafkaReadError { error }),
@@ -272,17 +483,104 @@ async fn kafka_source(
                         partition: msg.partition(),
                     });
 
-                    parse_message(msg, &decoder, keys, &finalizer, &mut out, &consumer, &topics).await;
+                    parse_message(msg, &decoder, keys, &finalizer, &mut out, &mut offsets, &topics).await;
                 }
             },
         }
     }
 
+    offsets.flush().await;
+
     Ok(())
 }
 
+/// Stores the offsets of the processed messages, for the consumer to commit them to Kafka and to
+/// save them to the offset store, if any.
+struct Offsets {
+    consumer: Arc<StreamConsumer<KafkaSourceContext>>,
+    offset_store: Option<Arc<dyn OffsetStore>>,
+    pending: HashMap<(String, i32), i64>,
+}
+
+impl Offsets {
+    fn store(&mut self, topic: &str, partition: i32, offset: i64) {
+        if let Err(error) = self.consumer.store_offset(topic, partition, offset) {
+            emit!(KafkaOffsetUpdateError { error });
+        }
+        if self.offset_store.is_some() {
+            self.pending.insert((topic.to_owned(), partition), offset);
+        }
+    }
+
+    /// Saves the offsets stored since the last flush to the offset store.
+    async fn flush(&mut self) {
+        if let Some(offset_store) = &self.offset_store {
+            join_all(
+                self.pending
+                    .drain()
+                    .map(|((topic, partition), offset)| async move {
+                        offset_store.store(&topic, partition, offset).await
+                    }),
+            )
+            .await;
+        }
+    }
+
+    /// Resumes the assigned partitions from the offset store where it's ahead of the offsets
+    /// committed to Kafka.
+    async fn resume(&self, partitions: TopicPartitionList) {
+        let offset_store = match &self.offset_store {
+            Some(offset_store) => offset_store,
+            None => return,
+        };
+        let committed = match self
+            .consumer
+            .committed_offsets(partitions, Duration::from_secs(10))
+        {
+            Ok(committed) => committed
+                .elements()
+                .iter()
+                .map(|element| {
+                    (
+                        element.topic().to_owned(),
+                        element.partition(),
+                        element.offset(),
+                    )
+                })
+                .collect::<Vec<_>>(),
+            Err(error) => {
+                emit!(KafkaOffsetUpdateError { error });
+                return;
+            }
+        };
+
+        for (topic, partition, committed) in committed {
+            if let Some(offset) = offset_store.load(&topic, partition).await {
+                // Kafka commits the offset of the next message to consume.
+                let next = offset + 1;
+                let behind = match committed {
+                    Offset::Offset(committed) => committed < next,
+                    _ => true,
+                };
+                if behind {
+                    if let Err(error) = self.consumer.seek(
+                        &topic,
+                        partition,
+                        Offset::Offset(next),
+                        Duration::from_secs(10),
+                    ) {
+                        emit!(KafkaOffsetUpdateError { error });
+                    }
+                }
+            }
+        }
+    }
+}
+
 struct Topics {
     subscribed: HashSet<String>,
+    /// The statically assigned partitions, which are paused instead of unsubscribed from.
+    assigned: Vec<(String, i32)>,
     failed: HashSet<String>,
 }
 
@@ -290,6 +588,11 @@ impl Topics {
     fn new(config: &KafkaSourceConfig) -> Self {
         Self {
             subscribed: config.topics.iter().cloned().collect(),
+            assigned: config
+                .assignments
+                .iter()
+                .map(|assignment| (assignment.topic.clone(), assignment.partition))
+                .collect(),
             failed: Default::default(),
         }
     }
@@ -299,13 +602,11 @@ fn handle_ack(
     topics: &mut Topics,
     status: BatchStatus,
     entry: FinalizerEntry,
-    consumer: &StreamConsumer<KafkaStatisticsContext>,
+    offsets: &mut Offsets,
 ) {
     if !topics.failed.contains(&entry.topic) {
         if status == BatchStatus::Delivered {
-            if let Err(error) = consumer.store_offset(&entry.topic, entry.partition, entry.offset) {
-                emit!(KafkaOffsetUpdateError { error });
-            }
+            offsets.store(&entry.topic, entry.partition, entry.offset);
         } else {
             emit!(KafkaNegativeAcknowledgmentError {
                 topic: &entry.topic,
@@ -321,7 +622,17 @@ fn handle_ack(
             // response.
             // 2. The topic list may contain wildcards, in which case
             // there may not be an exact match for the topic name.
-            if topics.subscribed.remove(&entry.topic) {
+            if !topics.assigned.is_empty() {
+                // Statically assigned partitions can't be unsubscribed
+                // from, so the partitions of the topic are paused.
+                let mut partitions = TopicPartitionList::new();
+                for (topic, partition) in &topics.assigned {
+                    if *topic == entry.topic {
+                        partitions.add_partition(topic, *partition);
+                    }
+                }
+                let _ = offsets.consumer.pause(&partitions);
+            } else if topics.subscribed.remove(&entry.topic) {
                 let topics: Vec<&str> = topics.subscribed.iter().map(|s| s.as_str()).collect();
                 // There is no direct way to unsubscribe from a named
                 // topic, as the unsubscribe library function drops
@@ -329,7 +640,7 @@ fn handle_ack(
                 // replaces the list of subscriptions, from which we
                 // have removed the topic above.  Ignore any errors,
                 // as we drop output from the topic below anyways.
-                let _ = consumer.subscribe(&topics);
+                let _ = offsets.consumer.subscribe(&topics);
             }
             // Don't update the offset after a failed ack
             topics.failed.insert(entry.topic);
@@ -339,13 +650,17 @@ fn handle_ack(
 
 async fn parse_message(
     msg: BorrowedMessage<'_>,
-    decoder: &Decoder,
+    decoder: &MessageDecoder,
     keys: Keys<'_>,
     finalizer: &Option<OrderedFinalizer<FinalizerEntry>>,
     out: &mut SourceSender,
-    consumer: &Arc<StreamConsumer<KafkaStatisticsContext>>,
+    offsets: &mut Offsets,
     topics: &Topics,
 ) {
+    if let Some(payload) = msg.payload() {
+        decoder.select(&msg).prefetch(payload).await;
+    }
+
     if let Some((count, mut stream)) = parse_stream(&msg, decoder, keys, topics) {
         match finalizer {
             Some(finalizer) => {
@@ -367,13 +682,7 @@ async fn parse_message(
                 Err(error) => {
                     emit!(StreamClosedError { error, count });
                 }
-                Ok(_) => {
-                    if let Err(error) =
-                        consumer.store_offset(msg.topic(), msg.partition(), msg.offset())
-                    {
-                        emit!(KafkaOffsetUpdateError { error });
-                    }
-                }
+                Ok(_) => offsets.store(msg.topic(), msg.partition(), msg.offset()),
             },
         }
     }
@@ -382,7 +691,7 @@ async fn parse_message(
 // Turn the received message into a stream of parsed events.
 fn parse_stream<'a>(
     msg: &BorrowedMessage<'a>,
-    decoder: &Decoder,
+    decoder: &MessageDecoder,
     keys: Keys<'a>,
     topics: &Topics,
 ) -> Option<(usize, impl Stream<Item = Event> + 'a)> {
@@ -396,7 +705,7 @@ fn parse_stream<'a>(
 
     let payload = Cursor::new(Bytes::copy_from_slice(payload));
 
-    let mut stream = FramedRead::new(payload, decoder.clone());
+    let mut stream = FramedRead::new(payload, decoder.select(msg).clone());
     let (count, _) = stream.size_hint();
     let stream = stream! {
         while let Some(result) = stream.next().await {
@@ -430,6 +739,7 @@ fn parse_stream<'a>(
 #[derive(Clone, Copy)]
 struct Keys<'a> {
     source_type: &'a str,
+    source_type_name: &'static str,
     timestamp: &'a str,
     key_field: &'a str,
     topic: &'a str,
@@ -439,9 +749,14 @@ struct Keys<'a> {
 }
 
 impl<'a> Keys<'a> {
-    fn from(schema: &'a LogSchema, config: &'a KafkaSourceConfig) -> Self {
+    fn from(
+        schema: &'a LogSchema,
+        config: &'a KafkaSourceConfig,
+        source_type_name: &'static str,
+    ) -> Self {
         Self {
             source_type: schema.source_type_key(),
+            source_type_name,
             timestamp: schema.timestamp_key(),
             key_field: config.key_field.as_str(),
             topic: config.topic_key.as_str(),
@@ -501,7 +816,7 @@ impl ReceivedMessage {
 
     fn apply(&self, keys: &Keys<'_>, event: &mut Event) {
         if let Event::Log(ref mut log) = event {
-            log.insert(keys.source_type, Bytes::from("kafka"));
+            log.insert(keys.source_type, Bytes::from(keys.source_type_name));
             log.insert(keys.timestamp, self.timestamp);
             log.insert(keys.key_field, self.key.clone());
             log.insert(keys.topic, Value::from(self.topic.clone()));
@@ -529,9 +844,30 @@ impl<'a> From<BorrowedMessage<'a>> for FinalizerEntry {
     }
 }
 
+/// The context of the consumer, which forwards the partitions assigned to it to the source when
+/// there is an offset store to resume them from.
+struct KafkaSourceContext {
+    assignments: Option<UnboundedSender<TopicPartitionList>>,
+}
+
+impl ClientContext for KafkaSourceContext {
+    fn stats(&self, statistics: Statistics) {
+        KafkaStatisticsContext.stats(statistics);
+    }
+}
+
+impl ConsumerContext for KafkaSourceContext {
+    fn post_rebalance(&self, rebalance: &Rebalance<'_>) {
+        if let (Some(assignments), Rebalance::Assign(partitions)) = (&self.assignments, rebalance) {
+            let _ = assignments.send((*partitions).clone());
+        }
+    }
+}
+
 fn create_consumer(
     config: &KafkaSourceConfig,
-) -> crate::Result<StreamConsumer<KafkaStatisticsContext>> {
+    assignments: Option<UnboundedSender<TopicPartitionList>>,
+) -> crate::Result<StreamConsumer<KafkaSourceContext>> {
     let mut client_config = ClientConfig::new();
     client_config
         .set("group.id", &config.group_id)
@@ -558,15 +894,59 @@ fn create_consumer(
         }
     }
 
+    if config.topics.is_empty() == config.assignments.is_empty() {
+        return Err(Box::new(BuildError::TopicsOrAssignments));
+    }
+
     let consumer = client_config
-        .create_with_context::<_, StreamConsumer<_>>(KafkaStatisticsContext)
+        .create_with_context::<_, StreamConsumer<_>>(KafkaSourceContext { assignments })
         .context(KafkaCreateSnafu)?;
-    let topics: Vec<&str> = config.topics.iter().map(|s| s.as_str()).collect();
-    consumer.subscribe(&topics).context(KafkaSubscribeSnafu)?;
+    if config.assignments.is_empty() {
+        let topics: Vec<&str> = config.topics.iter().map(|s| s.as_str()).collect();
+        consumer.subscribe(&topics).context(KafkaSubscribeSnafu)?;
+    } else {
+        let partitions = assigned_partitions(config, &consumer)?;
+        consumer.assign(&partitions).context(KafkaAssignSnafu)?;
+    }
 
     Ok(consumer)
 }
 
+/// Builds the list of the statically assigned partitions, resolving the offsets of the ones read
+/// from a timestamp.
+fn assigned_partitions(
+    config: &KafkaSourceConfig,
+    consumer: &StreamConsumer<KafkaSourceContext>,
+) -> crate::Result<TopicPartitionList> {
+    let mut partitions = TopicPartitionList::new();
+    let mut timestamps = TopicPartitionList::new();
+    for assignment in &config.assignments {
+        let list = match assignment.offset {
+            KafkaStartOffset::Timestamp(_) => &mut timestamps,
+            _ => &mut partitions,
+        };
+        list.add_partition_offset(
+            &assignment.topic,
+            assignment.partition,
+            assignment.offset.to_offset(),
+        )
+        .context(KafkaAssignSnafu)?;
+    }
+
+    if timestamps.count() > 0 {
+        let resolved = consumer
+            .offsets_for_times(timestamps, Duration::from_millis(config.socket_timeout_ms))
+            .context(KafkaOffsetsForTimesSnafu)?;
+        for element in resolved.elements() {
+            partitions
+                .add_partition_offset(element.topic(), element.partition(), element.offset())
+                .context(KafkaAssignSnafu)?;
+        }
+    }
+
+    Ok(partitions)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -606,7 +986,7 @@ mod test {
     #[tokio::test]
     async fn consumer_create_ok() {
         let config = make_config("topic", "group");
-        assert!(create_consumer(&config).is_ok());
+        assert!(create_consumer(&config, None).is_ok());
     }
 
     #[tokio::test]
@@ -615,7 +995,97 @@ mod test {
             auto_offset_reset: "incorrect-auto-offset-reset".to_string(),
             ..make_config("topic", "group")
         };
-        assert!(create_consumer(&config).is_err());
+        assert!(create_consumer(&config, None).is_err());
+    }
+
+    #[tokio::test]
+    async fn consumer_create_assignments_ok() {
+        let config = KafkaSourceConfig {
+            topics: Vec::new(),
+            assignments: vec![KafkaPartitionAssignment {
+                topic: "topic".into(),
+                partition: 0,
+                offset: KafkaStartOffset::Absolute(42),
+            }],
+            ..make_config("topic", "group")
+        };
+        assert!(create_consumer(&config, None).is_ok());
+    }
+
+    #[tokio::test]
+    async fn consumer_create_topics_and_assignments() {
+        let config = KafkaSourceConfig {
+            assignments: vec![KafkaPartitionAssignment {
+                topic: "topic".into(),
+                partition: 0,
+                offset: KafkaStartOffset::Earliest,
+            }],
+            ..make_config("topic", "group")
+        };
+        assert!(create_consumer(&config, None).is_err());
+    }
+
+    #[test]
+    fn parses_decoding_routes() {
+        let config: KafkaSourceConfig = toml::from_str(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topics = ["logs"]
+            group_id = "group"
+            decoding_routes.header = "content-type"
+            decoding_routes.routes."application/json".codec = "json"
+            decoding_routes.routes."text/plain".codec = "bytes"
+            "#,
+        )
+        .unwrap();
+
+        let routes = config.decoding_routes.as_ref().unwrap();
+        assert_eq!(routes.header, "content-type");
+        assert_eq!(routes.routes.len(), 2);
+        assert_eq!(config.output_type(), DataType::Log);
+
+        let decoder = config.build_decoder().unwrap();
+        assert_eq!(decoder.header.as_deref(), Some("content-type"));
+        assert_eq!(decoder.routes.len(), 2);
+    }
+
+    #[test]
+    fn parses_assignments() {
+        let config: KafkaSourceConfig = toml::from_str(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            group_id = "group"
+
+            [[assignments]]
+            topic = "logs"
+            partition = 0
+            offset = "earliest"
+
+            [[assignments]]
+            topic = "logs"
+            partition = 1
+            offset.timestamp = 1660000000000
+
+            [[assignments]]
+            topic = "logs"
+            partition = 2
+            "#,
+        )
+        .unwrap();
+
+        let offsets: Vec<_> = config
+            .assignments
+            .iter()
+            .map(|assignment| (assignment.partition, assignment.offset))
+            .collect();
+        assert_eq!(
+            offsets,
+            vec![
+                (0, KafkaStartOffset::Earliest),
+                (1, KafkaStartOffset::Timestamp(1660000000000)),
+                (2, KafkaStartOffset::Latest),
+            ]
+        );
     }
 }
 
@@ -693,6 +1163,128 @@ mod integration_test {
         send_receive(true, 2).await;
     }
 
+    #[tokio::test]
+    async fn decodes_by_header_value() {
+        let topic = format!("test-topic-{}", random_string(10));
+        let group_id = format!("test-group-{}", random_string(10));
+        let config = KafkaSourceConfig {
+            decoding_routes: Some(KafkaDecodingRoutes {
+                header: "content-type".into(),
+                routes: vec![("application/json".to_owned(), DeserializerConfig::Json)]
+                    .into_iter()
+                    .collect(),
+            }),
+            ..make_config(&topic, &group_id)
+        };
+
+        let producer: FutureProducer = client_config(None);
+        for (payload, content_type) in [
+            (
+                r#"{"message":"json message","level":"info"}"#,
+                "application/json",
+            ),
+            ("text message", "text/plain"),
+        ] {
+            let record = FutureRecord::to(&topic)
+                .payload(payload)
+                .key("my key")
+                .headers(OwnedHeaders::new().add("content-type", content_type));
+            if let Err(error) = producer.send(record, Timeout::Never).await {
+                panic!("Cannot send event to Kafka: {:?}", error);
+            }
+        }
+
+        let (trigger_shutdown, shutdown, shutdown_done) = ShutdownSignal::new_wired();
+        let (tx, rx) = SourceSender::new_test();
+        let consumer = create_consumer(&config, None).unwrap();
+        let decoder = config.build_decoder().unwrap();
+        let (_, assignments) = mpsc::unbounded_channel();
+        let options = KafkaSourceOptions {
+            source_type: "kafka",
+            offset_store: None,
+        };
+        tokio::spawn(kafka_source(
+            config,
+            consumer,
+            assignments,
+            options,
+            decoder,
+            shutdown,
+            tx,
+            false,
+        ));
+        let events = collect_n(rx, 2).await;
+        drop(trigger_shutdown);
+        shutdown_done.await;
+
+        assert_eq!(events[0].as_log()["message"], "json message".into());
+        assert_eq!(events[0].as_log()["level"], "info".into());
+        assert_eq!(events[1].as_log()["message"], "text message".into());
+        assert!(!events[1].as_log().contains("level"));
+    }
+
+    #[tokio::test]
+    async fn consumes_assigned_partition_from_offset() {
+        const SEND_COUNT: usize = 10;
+        const START_OFFSET: usize = 4;
+
+        let topic = format!("test-topic-{}", random_string(10));
+        let group_id = format!("test-group-{}", random_string(10));
+        let config = KafkaSourceConfig {
+            topics: Vec::new(),
+            assignments: vec![KafkaPartitionAssignment {
+                topic: topic.clone(),
+                partition: 0,
+                offset: KafkaStartOffset::Absolute(START_OFFSET as i64),
+            }],
+            ..make_config(&topic, &group_id)
+        };
+
+        send_events(
+            &topic,
+            SEND_COUNT,
+            "my key",
+            "my message",
+            Utc::now().timestamp_millis(),
+            "my header",
+            "my header value",
+        )
+        .await;
+
+        let (trigger_shutdown, shutdown, shutdown_done) = ShutdownSignal::new_wired();
+        let (tx, rx) = SourceSender::new_test();
+        let consumer = create_consumer(&config, None).unwrap();
+        let (_, assignments) = mpsc::unbounded_channel();
+        let options = KafkaSourceOptions {
+            source_type: "kafka",
+            offset_store: None,
+        };
+        tokio::spawn(kafka_source(
+            config,
+            consumer,
+            assignments,
+            options,
+            crate::codecs::Decoder::default().into(),
+            shutdown,
+            tx,
+            false,
+        ));
+        let events = collect_n(rx, SEND_COUNT - START_OFFSET).await;
+        drop(trigger_shutdown);
+        shutdown_done.await;
+
+        for (i, event) in events.into_iter().enumerate() {
+            assert_eq!(
+                event.as_log()[log_schema().message_key()],
+                format!("my message {}", START_OFFSET + i).into()
+            );
+            assert_eq!(
+                event.as_log()["offset"],
+                Value::from((START_OFFSET + i) as i64)
+            );
+        }
+    }
+
     async fn send_receive(acknowledgements: bool, receive_count: usize) {
         const SEND_COUNT: usize = 10;
 
@@ -716,11 +1308,18 @@ mod integration_test {
         let events = assert_source_compliance(&["protocol", "topic", "partition"], async move {
             let (trigger_shutdown, shutdown, shutdown_done) = ShutdownSignal::new_wired();
             let (tx, rx) = SourceSender::new_test_error_after(receive_count);
-            let consumer = create_consumer(&config).unwrap();
+            let consumer = create_consumer(&config, None).unwrap();
+            let (_, assignments) = mpsc::unbounded_channel();
+            let options = KafkaSourceOptions {
+                source_type: "kafka",
+                offset_store: None,
+            };
             tokio::spawn(kafka_source(
                 config,
                 consumer,
-                crate::codecs::Decoder::default(),
+                assignments,
+                options,
+                crate::codecs::Decoder::default().into(),
                 shutdown,
                 tx,
                 acknowledgements,
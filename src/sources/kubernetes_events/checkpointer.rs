@@ -0,0 +1,92 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+const CHECKPOINT_FILENAME: &str = "checkpoints.json";
+const CHECKPOINT_TMP_FILENAME: &str = "checkpoints.new.json";
+
+/// Where the watch of the events of a scope was left.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub(super) struct Checkpoint {
+    /// The resource version to resume the watch from.
+    pub(super) resource_version: String,
+    /// The time of the last event read, which tells the events that happened while the watch
+    /// was interrupted apart when it can't be resumed.
+    pub(super) since: DateTime<Utc>,
+}
+
+/// Stores the checkpoints of the scopes, which are written to the data directory when flushed.
+pub(super) struct Checkpointer {
+    path: PathBuf,
+    tmp_path: PathBuf,
+    checkpoints: BTreeMap<String, Checkpoint>,
+    dirty: bool,
+}
+
+impl Checkpointer {
+    pub(super) fn load(data_dir: &Path) -> io::Result<Self> {
+        let path = data_dir.join(CHECKPOINT_FILENAME);
+        let checkpoints = match fs::read(&path) {
+            Ok(contents) => serde_json::from_slice(&contents)?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(error) => return Err(error),
+        };
+        Ok(Self {
+            path,
+            tmp_path: data_dir.join(CHECKPOINT_TMP_FILENAME),
+            checkpoints,
+            dirty: false,
+        })
+    }
+
+    pub(super) fn get(&self, scope: &str) -> Option<&Checkpoint> {
+        self.checkpoints.get(scope)
+    }
+
+    pub(super) fn set(&mut self, scope: String, checkpoint: Checkpoint) {
+        self.checkpoints.insert(scope, checkpoint);
+        self.dirty = true;
+    }
+
+    /// Writes the checkpoints, if they changed since they were last written.
+    pub(super) fn flush(&mut self) -> io::Result<()> {
+        if self.dirty {
+            // Written to a temporary file first, so that the checkpoints aren't lost when Vector
+            // stops while writing them.
+            fs::write(&self.tmp_path, serde_json::to_vec(&self.checkpoints)?)?;
+            fs::rename(&self.tmp_path, &self.path)?;
+            self.dirty = false;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn persists_checkpoints() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let checkpoint = Checkpoint {
+            resource_version: "12345".into(),
+            since: Utc.ymd(2022, 8, 1).and_hms(12, 0, 0),
+        };
+
+        let mut checkpointer = Checkpointer::load(data_dir.path()).unwrap();
+        assert_eq!(checkpointer.get("default"), None);
+        checkpointer.set("default".into(), checkpoint.clone());
+        checkpointer.flush().unwrap();
+
+        let checkpointer = Checkpointer::load(data_dir.path()).unwrap();
+        assert_eq!(checkpointer.get("default"), Some(&checkpoint));
+        assert_eq!(checkpointer.get(""), None);
+    }
+}
@@ -0,0 +1,369 @@
+//! The `kubernetes_events` source.
+//!
+//! The `Event`s of the namespaces are watched from the Kubernetes API, each namespace by a task of
+//! its own, or all of them by a single task when no namespace is configured. The watches are
+//! resumed from the resource version stored in the data directory after a restart, and the events
+//! that were already read are skipped when they have to be listed again.
+
+use std::{io, path::PathBuf, time::Duration};
+
+use chrono::Utc;
+use k8s_openapi::api::core::v1::Event as KubernetesEvent;
+use kube::{
+    api::{Api, ListParams},
+    config::{self, KubeConfigOptions},
+    Client, Config as ClientConfig,
+};
+use snafu::{ResultExt, Snafu};
+use tokio::sync::mpsc;
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, ByteSizeOf};
+
+use self::{
+    checkpointer::Checkpointer,
+    watcher::{last_observed, Batch, ScopeWatcher},
+};
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Output, SourceConfig, SourceContext,
+        SourceDescription,
+    },
+    event::{LogEvent, Value},
+    internal_events::{
+        BytesReceived, KubernetesEventsCheckpointError, KubernetesEventsConversionError,
+        OldEventsReceived, StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+mod checkpointer;
+mod watcher;
+
+/// How often the checkpoints of the watches are written to the data directory.
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long the watches last before being resumed, which is shorter than the timeout of the API
+/// server.
+const WATCH_TIMEOUT_SECS: u32 = 290;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Unable to load the checkpoints of the watches: {}", source))]
+    LoadCheckpoints { source: io::Error },
+}
+
+/// Configuration for the `kubernetes_events` source.
+#[configurable_component(source)]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct KubernetesEventsConfig {
+    /// The namespaces to watch the events of.
+    ///
+    /// By default, the events of all the namespaces are watched, which requires Vector to be
+    /// allowed to list and watch the events of the whole cluster. When namespaces are configured,
+    /// Vector only needs to be allowed to list and watch the events of these namespaces.
+    namespaces: Vec<String>,
+
+    /// The label selector to filter the events with.
+    label_selector: Option<String>,
+
+    /// The field selector to filter the events with, such as `type=Warning` or
+    /// `involvedObject.kind=Pod`.
+    field_selector: Option<String>,
+
+    /// Read the events already in the cluster when Vector starts for the first time, instead of
+    /// only the events that happen afterwards.
+    read_existing_events: bool,
+
+    /// The directory used to persist the resource versions the watches are resumed from.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is
+    /// running as has write permissions to this directory.
+    data_dir: Option<PathBuf>,
+
+    /// Optional path to a kubeconfig file readable by Vector. If not set,
+    /// Vector will try to connect to Kubernetes using in-cluster configuration.
+    kube_config_file: Option<PathBuf>,
+}
+
+inventory::submit! {
+    SourceDescription::new::<KubernetesEventsConfig>("kubernetes_events")
+}
+
+impl_generate_config_from_default!(KubernetesEventsConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "kubernetes_events")]
+impl SourceConfig for KubernetesEventsConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        // If the user passed a custom Kubeconfig use it, otherwise
+        // we attempt to load the local kubec-config, followed by the
+        // in-cluster environment variables
+        let client_config = match &self.kube_config_file {
+            Some(kc) => {
+                ClientConfig::from_custom_kubeconfig(
+                    config::Kubeconfig::read_from(kc)?,
+                    &KubeConfigOptions::default(),
+                )
+                .await?
+            }
+            None => ClientConfig::infer().await?,
+        };
+        let client = Client::try_from(client_config)?;
+
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        let checkpointer = Checkpointer::load(&data_dir).context(LoadCheckpointsSnafu)?;
+
+        let mut list_params = ListParams::default()
+            .allow_bookmarks()
+            .timeout(WATCH_TIMEOUT_SECS);
+        if let Some(label_selector) = &self.label_selector {
+            list_params = list_params.labels(label_selector);
+        }
+        if let Some(field_selector) = &self.field_selector {
+            list_params = list_params.fields(field_selector);
+        }
+
+        let scopes = if self.namespaces.is_empty() {
+            vec![(String::new(), Api::all(client))]
+        } else {
+            self.namespaces
+                .iter()
+                .map(|namespace| {
+                    (
+                        namespace.clone(),
+                        Api::namespaced(client.clone(), namespace),
+                    )
+                })
+                .collect()
+        };
+
+        let since = (!self.read_existing_events).then(Utc::now);
+        let (sender, batches) = mpsc::channel(scopes.len());
+        let watchers = scopes
+            .into_iter()
+            .map(|(scope, api)| {
+                let checkpoint = checkpointer.get(&scope).cloned();
+                ScopeWatcher::new(
+                    scope,
+                    api,
+                    list_params.clone(),
+                    checkpoint,
+                    since,
+                    sender.clone(),
+                )
+            })
+            .collect();
+
+        let source = KubernetesEventsSource {
+            checkpointer,
+            out: cx.out,
+        };
+        Ok(Box::pin(source.run(watchers, batches, cx.shutdown)))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "kubernetes_events"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+struct KubernetesEventsSource {
+    checkpointer: Checkpointer,
+    out: SourceSender,
+}
+
+impl KubernetesEventsSource {
+    async fn run(
+        mut self,
+        watchers: Vec<ScopeWatcher>,
+        mut batches: mpsc::Receiver<Batch>,
+        mut shutdown: ShutdownSignal,
+    ) -> Result<(), ()> {
+        let watchers = watchers
+            .into_iter()
+            .map(|watcher| tokio::spawn(watcher.run()))
+            .collect::<Vec<_>>();
+        let mut checkpoint_interval = tokio::time::interval(CHECKPOINT_INTERVAL);
+
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => break,
+                _ = checkpoint_interval.tick() => self.flush(),
+                batch = batches.recv() => match batch {
+                    None => break,
+                    Some(batch) => if !self.handle_batch(batch).await {
+                        break;
+                    },
+                },
+            }
+        }
+
+        // The watches are waiting for events, and never complete on their own.
+        for watcher in watchers {
+            watcher.abort();
+        }
+        self.flush();
+
+        Ok(())
+    }
+
+    /// Sends the events of the batch. Returns `false` when the events can no longer be sent.
+    async fn handle_batch(&mut self, batch: Batch) -> bool {
+        let Batch {
+            scope,
+            events,
+            checkpoint,
+        } = batch;
+
+        if !events.is_empty() {
+            let mut byte_size = 0;
+            let events = events
+                .into_iter()
+                .filter_map(|event| match create_event(&event) {
+                    Ok((log, size)) => {
+                        byte_size += size;
+                        Some(log)
+                    }
+                    Err(error) => {
+                        emit!(KubernetesEventsConversionError { error });
+                        None
+                    }
+                })
+                .collect::<Vec<LogEvent>>();
+            emit!(BytesReceived {
+                byte_size,
+                protocol: "http",
+            });
+
+            let count = events.len();
+            emit!(OldEventsReceived {
+                count,
+                byte_size: events.size_of(),
+            });
+            if let Err(error) = self.out.send_batch(events).await {
+                emit!(StreamClosedError { error, count });
+                return false;
+            }
+        }
+
+        self.checkpointer.set(scope, checkpoint);
+        true
+    }
+
+    fn flush(&mut self) {
+        if let Err(error) = self.checkpointer.flush() {
+            emit!(KubernetesEventsCheckpointError { error });
+        }
+    }
+}
+
+/// Converts the event into a log with the fields of the event, and returns the size of the event
+/// as read from the API.
+fn create_event(event: &KubernetesEvent) -> serde_json::Result<(LogEvent, usize)> {
+    let json = serde_json::to_vec(event)?;
+    let mut value = serde_json::from_slice::<serde_json::Value>(&json)?;
+    // The managed fields are only relevant to the clients updating the events.
+    if let Some(metadata) = value
+        .get_mut("metadata")
+        .and_then(serde_json::Value::as_object_mut)
+    {
+        metadata.remove("managedFields");
+    }
+
+    let mut log = match Value::from(value) {
+        Value::Object(fields) => LogEvent::from(fields),
+        _ => LogEvent::default(),
+    };
+    if let Some(message) = log.remove("message") {
+        log.insert(log_schema().message_key(), message);
+    }
+    log.insert(
+        log_schema().timestamp_key(),
+        last_observed(event).unwrap_or_else(Utc::now),
+    );
+    log.insert(log_schema().source_type_key(), "kubernetes_events");
+    Ok((log, json.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use k8s_openapi::api::core::v1::ObjectReference;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::{ManagedFieldsEntry, ObjectMeta, Time};
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<KubernetesEventsConfig>();
+    }
+
+    #[test]
+    fn parses_config() {
+        let config: KubernetesEventsConfig = toml::from_str(
+            r#"
+            namespaces = ["default", "kube-system"]
+            field_selector = "type=Warning"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.namespaces, ["default", "kube-system"]);
+        assert_eq!(config.field_selector.as_deref(), Some("type=Warning"));
+        assert!(!config.read_existing_events);
+    }
+
+    #[test]
+    fn converts_events() {
+        let last = Utc.ymd(2022, 8, 1).and_hms(12, 5, 0);
+        let event = KubernetesEvent {
+            metadata: ObjectMeta {
+                name: Some("web-0.1700".into()),
+                namespace: Some("default".into()),
+                managed_fields: Some(vec![ManagedFieldsEntry::default()]),
+                ..ObjectMeta::default()
+            },
+            involved_object: ObjectReference {
+                kind: Some("Pod".into()),
+                name: Some("web-0".into()),
+                ..ObjectReference::default()
+            },
+            reason: Some("BackOff".into()),
+            message: Some("Back-off restarting failed container".into()),
+            type_: Some("Warning".into()),
+            count: Some(5),
+            last_timestamp: Some(Time(last)),
+            ..KubernetesEvent::default()
+        };
+
+        let (log, byte_size) = create_event(&event).unwrap();
+
+        assert!(byte_size > 0);
+        assert_eq!(
+            log[log_schema().message_key()],
+            "Back-off restarting failed container".into()
+        );
+        assert_eq!(log[log_schema().timestamp_key()], Value::Timestamp(last));
+        assert_eq!(
+            log[log_schema().source_type_key()],
+            "kubernetes_events".into()
+        );
+        assert_eq!(log["reason"], "BackOff".into());
+        assert_eq!(log["type"], "Warning".into());
+        assert_eq!(log["count"], 5.into());
+        assert_eq!(log["involvedObject.kind"], "Pod".into());
+        assert_eq!(log["metadata.namespace"], "default".into());
+        assert!(log.get("metadata.managedFields").is_none());
+    }
+}
@@ -0,0 +1,251 @@
+use std::{collections::HashMap, time::Duration};
+
+use chrono::{DateTime, Utc};
+use futures::{StreamExt, TryStreamExt};
+use k8s_openapi::api::core::v1::Event;
+use kube::api::{Api, ListParams, WatchEvent};
+use tokio::sync::mpsc;
+
+use super::checkpointer::Checkpoint;
+use crate::internal_events::KubernetesEventsWatchError;
+
+/// How long to wait before watching the events again after an error.
+const BACKOFF_DURATION: Duration = Duration::from_secs(5);
+
+/// The status code of the watch errors telling that the resource version to resume from is too
+/// old, and that the events must be listed again.
+const GONE: u16 = 410;
+
+/// The events read from a scope, with the checkpoint to store once they are sent.
+pub(super) struct Batch {
+    pub(super) scope: String,
+    pub(super) events: Vec<Event>,
+    pub(super) checkpoint: Checkpoint,
+}
+
+/// Watches the events of a namespace, or of all the namespaces.
+///
+/// The watch is resumed from the resource version of the last event read, or from the bookmarks
+/// sent by the API server. When the resource version is too old to resume from, the events are
+/// listed again, and only the ones that changed since they were last read are sent.
+pub(super) struct ScopeWatcher {
+    scope: String,
+    api: Api<Event>,
+    list_params: ListParams,
+    resource_version: Option<String>,
+    /// The time of the last event read. When the events are listed, the events that weren't read
+    /// yet are only sent when they happened after it, which sends all the events when missing.
+    since: Option<DateTime<Utc>>,
+    /// The resource versions of the events read, by uid, which tell the events already sent apart.
+    seen: HashMap<String, String>,
+    out: mpsc::Sender<Batch>,
+}
+
+impl ScopeWatcher {
+    pub(super) fn new(
+        scope: String,
+        api: Api<Event>,
+        list_params: ListParams,
+        checkpoint: Option<Checkpoint>,
+        since: Option<DateTime<Utc>>,
+        out: mpsc::Sender<Batch>,
+    ) -> Self {
+        let (resource_version, since) = match checkpoint {
+            Some(checkpoint) => (Some(checkpoint.resource_version), Some(checkpoint.since)),
+            None => (None, since),
+        };
+        Self {
+            scope,
+            api,
+            list_params,
+            resource_version,
+            since,
+            seen: HashMap::new(),
+            out,
+        }
+    }
+
+    pub(super) async fn run(mut self) {
+        while !self.out.is_closed() {
+            if let Err(error) = self.watch().await {
+                emit!(KubernetesEventsWatchError {
+                    namespace: &self.scope,
+                    error: &error,
+                });
+                tokio::time::sleep(BACKOFF_DURATION).await;
+            }
+        }
+    }
+
+    /// Watches the events until the watch times out, or the events must be listed again.
+    async fn watch(&mut self) -> kube::Result<()> {
+        let resource_version = match self.resource_version.clone() {
+            Some(resource_version) => resource_version,
+            None => self.list().await?,
+        };
+
+        // The stream is watched from a handle of its own, as the state of the watcher is updated as
+        // the events are read.
+        let api = self.api.clone();
+        let list_params = self.list_params.clone();
+        let mut stream = api.watch(&list_params, &resource_version).await?.boxed();
+        while let Some(event) = stream.try_next().await? {
+            match event {
+                WatchEvent::Added(event) | WatchEvent::Modified(event) => {
+                    self.resource_version = event.metadata.resource_version.clone();
+                    let events = if self.observe(&event) {
+                        vec![event]
+                    } else {
+                        Vec::new()
+                    };
+                    self.send(events).await;
+                }
+                WatchEvent::Deleted(event) => {
+                    self.resource_version = event.metadata.resource_version.clone();
+                    if let Some(uid) = &event.metadata.uid {
+                        self.seen.remove(uid);
+                    }
+                }
+                WatchEvent::Bookmark(bookmark) => {
+                    self.resource_version = Some(bookmark.metadata.resource_version);
+                    self.send(Vec::new()).await;
+                }
+                WatchEvent::Error(error) if error.code == GONE => {
+                    debug!(
+                        message = "Resource version is too old, listing events again.",
+                        namespace = %self.scope,
+                    );
+                    self.resource_version = None;
+                    return Ok(());
+                }
+                WatchEvent::Error(error) => return Err(kube::Error::Api(error)),
+            }
+        }
+        Ok(())
+    }
+
+    /// Lists the events, sends the ones that weren't read yet, and returns the resource version to
+    /// watch them from.
+    async fn list(&mut self) -> kube::Result<String> {
+        let list = self.api.list(&self.list_params).await?;
+
+        let mut seen = HashMap::with_capacity(list.items.len());
+        let mut events = Vec::new();
+        let mut latest = self.since;
+        for event in list.items {
+            let observed = last_observed(&event);
+            latest = latest.max(observed);
+            let is_new = match (&event.metadata.uid, &event.metadata.resource_version) {
+                (Some(uid), Some(resource_version)) => {
+                    let is_new = match self.seen.get(uid) {
+                        Some(seen) => seen != resource_version,
+                        None => match (self.since, observed) {
+                            (Some(since), Some(observed)) => observed >= since,
+                            _ => true,
+                        },
+                    };
+                    seen.insert(uid.clone(), resource_version.clone());
+                    is_new
+                }
+                _ => true,
+            };
+            if is_new {
+                events.push(event);
+            }
+        }
+        self.seen = seen;
+        self.since = Some(latest.unwrap_or_else(Utc::now));
+
+        let resource_version = list.metadata.resource_version.unwrap_or_default();
+        self.resource_version = Some(resource_version.clone());
+        self.send(events).await;
+        Ok(resource_version)
+    }
+
+    /// Records that the event was read. Returns `false` when it was already read.
+    fn observe(&mut self, event: &Event) -> bool {
+        if let Some(observed) = last_observed(event) {
+            self.since = Some(self.since.map_or(observed, |since| since.max(observed)));
+        }
+        match (&event.metadata.uid, &event.metadata.resource_version) {
+            (Some(uid), Some(resource_version)) => self
+                .seen
+                .insert(uid.clone(), resource_version.clone())
+                .map_or(true, |seen| &seen != resource_version),
+            _ => true,
+        }
+    }
+
+    async fn send(&mut self, events: Vec<Event>) {
+        let checkpoint = match (&self.resource_version, self.since) {
+            (Some(resource_version), Some(since)) => Checkpoint {
+                resource_version: resource_version.clone(),
+                since,
+            },
+            _ => return,
+        };
+        // The source is stopping when the events can't be sent.
+        let _ = self
+            .out
+            .send(Batch {
+                scope: self.scope.clone(),
+                events,
+                checkpoint,
+            })
+            .await;
+    }
+}
+
+/// Returns the last time the event happened.
+pub(super) fn last_observed(event: &Event) -> Option<DateTime<Utc>> {
+    event
+        .series
+        .as_ref()
+        .and_then(|series| series.last_observed_time.as_ref())
+        .map(|time| time.0)
+        .or_else(|| event.last_timestamp.as_ref().map(|time| time.0))
+        .or_else(|| event.event_time.as_ref().map(|time| time.0))
+        .or_else(|| event.first_timestamp.as_ref().map(|time| time.0))
+        .or_else(|| {
+            event
+                .metadata
+                .creation_timestamp
+                .as_ref()
+                .map(|time| time.0)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use k8s_openapi::{
+        api::core::v1::EventSeries,
+        apimachinery::pkg::apis::meta::v1::{MicroTime, ObjectMeta, Time},
+    };
+
+    use super::*;
+
+    #[test]
+    fn finds_last_observed_time() {
+        let first = Utc.ymd(2022, 8, 1).and_hms(12, 0, 0);
+        let last = Utc.ymd(2022, 8, 1).and_hms(12, 5, 0);
+        let mut event = Event {
+            metadata: ObjectMeta {
+                creation_timestamp: Some(Time(first)),
+                ..ObjectMeta::default()
+            },
+            ..Event::default()
+        };
+        assert_eq!(last_observed(&event), Some(first));
+
+        event.last_timestamp = Some(Time(last));
+        assert_eq!(last_observed(&event), Some(last));
+
+        let series_last = Utc.ymd(2022, 8, 1).and_hms(12, 10, 0);
+        event.series = Some(EventSeries {
+            count: Some(3),
+            last_observed_time: Some(MicroTime(series_last)),
+        });
+        assert_eq!(last_observed(&event), Some(series_last));
+    }
+}
@@ -14,7 +14,11 @@ use file_source::{
     ReadFrom,
 };
 use futures_util::Stream;
-use k8s_openapi::api::core::v1::{Namespace, Node, Pod};
+use k8s_openapi::api::{
+    apps::v1::{Deployment, ReplicaSet},
+    batch::v1::Job,
+    core::v1::{Namespace, Node, Pod},
+};
 use kube::{
     api::{Api, ListParams},
     config::{self, KubeConfigOptions},
@@ -51,6 +55,7 @@ mod k8s_paths_provider;
 mod lifecycle;
 mod namespace_metadata_annotator;
 mod node_metadata_annotator;
+mod owner_metadata_annotator;
 mod parser;
 mod partial_events_merger;
 mod path_helpers;
@@ -60,6 +65,7 @@ mod util;
 
 use self::namespace_metadata_annotator::NamespaceMetadataAnnotator;
 use self::node_metadata_annotator::NodeMetadataAnnotator;
+use self::owner_metadata_annotator::{OwnerMetadataAnnotator, OwnerStates};
 use self::parser::Parser;
 use self::pod_metadata_annotator::PodMetadataAnnotator;
 use futures::{future::FutureExt, stream::StreamExt};
@@ -110,6 +116,22 @@ pub struct Config {
     #[configurable(derived)]
     node_annotation_fields: node_metadata_annotator::FieldsSpec,
 
+    /// Whether or not to annotate the events with the names of the owners of their `Pod`, such as
+    /// their `Deployment`, `StatefulSet` or `CronJob`.
+    ///
+    /// The `ReplicaSet`s, `Deployment`s and `Job`s of the cluster are watched to follow the chain of
+    /// owners, which requires Vector to be allowed to list and watch them.
+    owner_metadata: bool,
+
+    #[configurable(derived)]
+    owner_annotation_fields: owner_metadata_annotator::FieldsSpec,
+
+    /// A list of glob patterns of the annotations of the `Pod`s and `Deployment`s to annotate the
+    /// events with, such as `app.kubernetes.io/*`.
+    ///
+    /// By default, all the annotations are added to the events.
+    annotations_allowlist: Vec<String>,
+
     /// A list of glob patterns to exclude from reading the files.
     exclude_paths_glob_patterns: Vec<PathBuf>,
 
@@ -179,6 +201,9 @@ impl Default for Config {
             pod_annotation_fields: pod_metadata_annotator::FieldsSpec::default(),
             namespace_annotation_fields: namespace_metadata_annotator::FieldsSpec::default(),
             node_annotation_fields: node_metadata_annotator::FieldsSpec::default(),
+            owner_metadata: false,
+            owner_annotation_fields: owner_metadata_annotator::FieldsSpec::default(),
+            annotations_allowlist: Vec::new(),
             exclude_paths_glob_patterns: default_path_exclusion(),
             max_read_bytes: default_max_read_bytes(),
             max_line_bytes: default_max_line_bytes(),
@@ -227,6 +252,9 @@ struct Source {
     pod_fields_spec: pod_metadata_annotator::FieldsSpec,
     namespace_fields_spec: namespace_metadata_annotator::FieldsSpec,
     node_field_spec: node_metadata_annotator::FieldsSpec,
+    owner_metadata: bool,
+    owner_fields_spec: owner_metadata_annotator::FieldsSpec,
+    annotations_allowlist: Vec<glob::Pattern>,
     field_selector: String,
     label_selector: String,
     namespace_label_selector: String,
@@ -284,6 +312,7 @@ impl Source {
         let data_dir = globals.resolve_and_make_data_subdir(config.data_dir.as_ref(), key.id())?;
 
         let exclude_paths = prepare_exclude_paths(config)?;
+        let annotations_allowlist = prepare_annotations_allowlist(config)?;
 
         let glob_minimum_cooldown =
             Duration::from_millis(config.glob_minimum_cooldown_ms.try_into().expect(
@@ -304,6 +333,9 @@ impl Source {
             pod_fields_spec: config.pod_annotation_fields.clone(),
             namespace_fields_spec: config.namespace_annotation_fields.clone(),
             node_field_spec: config.node_annotation_fields.clone(),
+            owner_metadata: config.owner_metadata,
+            owner_fields_spec: config.owner_annotation_fields.clone(),
+            annotations_allowlist,
             field_selector,
             label_selector,
             namespace_label_selector,
@@ -331,6 +363,9 @@ impl Source {
             pod_fields_spec,
             namespace_fields_spec,
             node_field_spec,
+            owner_metadata,
+            owner_fields_spec,
+            annotations_allowlist,
             field_selector,
             label_selector,
             namespace_label_selector,
@@ -386,7 +421,7 @@ impl Source {
 
         // -----------------------------------------------------------------
 
-        let nodes = Api::<Node>::all(client);
+        let nodes = Api::<Node>::all(client.clone());
         let node_watcher = watcher(
             nodes,
             ListParams {
@@ -403,9 +438,38 @@ impl Source {
             delay_deletion,
         )));
 
+        // -----------------------------------------------------------------
+
+        let owner_annotator = if owner_metadata {
+            let replicasets = reflect(
+                &mut reflectors,
+                Api::<ReplicaSet>::all(client.clone()),
+                delay_deletion,
+            );
+            let deployments = reflect(
+                &mut reflectors,
+                Api::<Deployment>::all(client.clone()),
+                delay_deletion,
+            );
+            let jobs = reflect(&mut reflectors, Api::<Job>::all(client), delay_deletion);
+            Some(OwnerMetadataAnnotator::new(
+                pod_state.clone(),
+                OwnerStates {
+                    replicasets,
+                    deployments,
+                    jobs,
+                },
+                owner_fields_spec,
+                annotations_allowlist.clone(),
+            ))
+        } else {
+            None
+        };
+
         let paths_provider =
             K8sPathsProvider::new(pod_state.clone(), ns_state.clone(), exclude_paths);
-        let annotator = PodMetadataAnnotator::new(pod_state, pod_fields_spec);
+        let annotator =
+            PodMetadataAnnotator::new(pod_state, pod_fields_spec, annotations_allowlist);
         let ns_annotator = NamespaceMetadataAnnotator::new(ns_state, namespace_fields_spec);
         let node_annotator = NodeMetadataAnnotator::new(node_state, node_field_spec);
 
@@ -498,6 +562,10 @@ impl Source {
             if file_info.is_none() {
                 emit!(KubernetesLogsEventAnnotationError { event: &event });
             } else {
+                if let (Some(owner_annotator), Some(info)) = (&owner_annotator, &file_info) {
+                    owner_annotator.annotate(&mut event, info.pod_namespace, info.pod_name);
+                }
+
                 let namespace = file_info.as_ref().map(|info| info.pod_namespace);
 
                 if let Some(name) = namespace {
@@ -574,6 +642,32 @@ impl Source {
     }
 }
 
+/// Watches all the objects of a kind, and returns their state.
+fn reflect<K>(
+    reflectors: &mut Vec<tokio::task::JoinHandle<()>>,
+    api: Api<K>,
+    delay_deletion: Duration,
+) -> reflector::Store<K>
+where
+    K: kube::Resource
+        + Clone
+        + std::fmt::Debug
+        + serde::de::DeserializeOwned
+        + Send
+        + Sync
+        + 'static,
+    K::DynamicType: Default + Eq + std::hash::Hash + Clone,
+{
+    let store_w = reflector::store::Writer::default();
+    let state = store_w.as_reader();
+    reflectors.push(tokio::spawn(custom_reflector(
+        store_w,
+        watcher(api, ListParams::default()),
+        delay_deletion,
+    )));
+    state
+}
+
 fn create_event(line: Bytes, file: &str, ingestion_timestamp_field: Option<&str>) -> Event {
     let mut event = LogEvent::from_bytes_legacy(&line);
 
@@ -657,6 +751,15 @@ fn prepare_exclude_paths(config: &Config) -> crate::Result<Vec<glob::Pattern>> {
     Ok(exclude_paths)
 }
 
+// This function constructs the patterns of the annotations added to the events.
+fn prepare_annotations_allowlist(config: &Config) -> crate::Result<Vec<glob::Pattern>> {
+    config
+        .annotations_allowlist
+        .iter()
+        .map(|pattern| Ok(glob::Pattern::new(pattern)?))
+        .collect()
+}
+
 // This function constructs the effective field selector to use, based on
 // the specified configuration.
 fn prepare_field_selector(config: &Config, self_node_name: &str) -> crate::Result<String> {
@@ -0,0 +1,386 @@
+//! Annotates events with the metadata of the owners of their pod.
+
+#![deny(missing_docs)]
+
+use k8s_openapi::{
+    api::{
+        apps::v1::{Deployment, ReplicaSet},
+        batch::v1::Job,
+        core::v1::Pod,
+    },
+    apimachinery::pkg::apis::meta::v1::{ObjectMeta, OwnerReference},
+};
+use kube::runtime::reflector::{store::Store, ObjectRef};
+use lookup::lookup_v2::{parse_path, OwnedSegment};
+use vector_config::configurable_component;
+
+use super::pod_metadata_annotator::is_annotation_allowed;
+use crate::event::{Event, LogEvent};
+
+/// Configuration for how the events are annotated with the metadata of the owners of their `Pod`.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields, default)]
+pub struct FieldsSpec {
+    /// Event field for the name of the `ReplicaSet` owning the `Pod`.
+    pub replicaset_name: String,
+
+    /// Event field for the name of the `Deployment` owning the `ReplicaSet` of the `Pod`.
+    pub deployment_name: String,
+
+    /// Event field for Deployment labels.
+    pub deployment_labels: String,
+
+    /// Event field for Deployment annotations.
+    pub deployment_annotations: String,
+
+    /// Event field for the name of the `StatefulSet` owning the `Pod`.
+    pub statefulset_name: String,
+
+    /// Event field for the name of the `DaemonSet` owning the `Pod`.
+    pub daemonset_name: String,
+
+    /// Event field for the name of the `Job` owning the `Pod`.
+    pub job_name: String,
+
+    /// Event field for the name of the `CronJob` owning the `Job` of the `Pod`.
+    pub cronjob_name: String,
+}
+
+impl Default for FieldsSpec {
+    fn default() -> Self {
+        Self {
+            replicaset_name: "kubernetes.replicaset_name".to_owned(),
+            deployment_name: "kubernetes.deployment_name".to_owned(),
+            deployment_labels: "kubernetes.deployment_labels".to_owned(),
+            deployment_annotations: "kubernetes.deployment_annotations".to_owned(),
+            statefulset_name: "kubernetes.statefulset_name".to_owned(),
+            daemonset_name: "kubernetes.daemonset_name".to_owned(),
+            job_name: "kubernetes.job_name".to_owned(),
+            cronjob_name: "kubernetes.cronjob_name".to_owned(),
+        }
+    }
+}
+
+/// The states of the objects that own the pods.
+pub struct OwnerStates {
+    /// The state of the `ReplicaSet`s.
+    pub replicasets: Store<ReplicaSet>,
+    /// The state of the `Deployment`s.
+    pub deployments: Store<Deployment>,
+    /// The state of the `Job`s.
+    pub jobs: Store<Job>,
+}
+
+/// Annotate the event with the metadata of the owners of its pod.
+pub struct OwnerMetadataAnnotator {
+    pods_state_reader: Store<Pod>,
+    owner_states: OwnerStates,
+    fields_spec: FieldsSpec,
+    annotations_allowlist: Vec<glob::Pattern>,
+}
+
+impl OwnerMetadataAnnotator {
+    /// Create a new [`OwnerMetadataAnnotator`].
+    pub const fn new(
+        pods_state_reader: Store<Pod>,
+        owner_states: OwnerStates,
+        fields_spec: FieldsSpec,
+        annotations_allowlist: Vec<glob::Pattern>,
+    ) -> Self {
+        Self {
+            pods_state_reader,
+            owner_states,
+            fields_spec,
+            annotations_allowlist,
+        }
+    }
+}
+
+impl OwnerMetadataAnnotator {
+    /// Annotates an event with the names of the owners of its pod, following the owner references
+    /// from the pod to the `Deployment` or `CronJob` at the top of the chain.
+    /// The owners that are not known yet are skipped.
+    pub fn annotate(&self, event: &mut Event, pod_namespace: &str, pod_name: &str) -> Option<()> {
+        let log = event.as_mut_log();
+        let obj = ObjectRef::<Pod>::new(pod_name).within(pod_namespace);
+        let resource = self.pods_state_reader.get(&obj)?;
+        let pod: &Pod = resource.as_ref();
+        let owner = controller(&pod.metadata)?;
+
+        match owner.kind.as_str() {
+            "ReplicaSet" => {
+                log.insert(
+                    self.fields_spec.replicaset_name.as_str(),
+                    owner.name.clone(),
+                );
+                let obj = ObjectRef::<ReplicaSet>::new(&owner.name).within(pod_namespace);
+                let replicaset = self.owner_states.replicasets.get(&obj)?;
+                let owner = controller(&replicaset.metadata).filter(|o| o.kind == "Deployment")?;
+
+                log.insert(
+                    self.fields_spec.deployment_name.as_str(),
+                    owner.name.clone(),
+                );
+                let obj = ObjectRef::<Deployment>::new(&owner.name).within(pod_namespace);
+                let deployment = self.owner_states.deployments.get(&obj)?;
+                annotate_from_deployment_metadata(
+                    log,
+                    &self.fields_spec,
+                    &self.annotations_allowlist,
+                    &deployment.metadata,
+                );
+            }
+            "Job" => {
+                log.insert(self.fields_spec.job_name.as_str(), owner.name.clone());
+                let obj = ObjectRef::<Job>::new(&owner.name).within(pod_namespace);
+                let job = self.owner_states.jobs.get(&obj)?;
+                let owner = controller(&job.metadata).filter(|o| o.kind == "CronJob")?;
+
+                log.insert(self.fields_spec.cronjob_name.as_str(), owner.name.clone());
+            }
+            "StatefulSet" => {
+                log.insert(
+                    self.fields_spec.statefulset_name.as_str(),
+                    owner.name.clone(),
+                );
+            }
+            "DaemonSet" => {
+                log.insert(self.fields_spec.daemonset_name.as_str(), owner.name.clone());
+            }
+            _ => {}
+        }
+        Some(())
+    }
+}
+
+/// Returns the owner managing the object, or its first owner when none is marked as such.
+fn controller(metadata: &ObjectMeta) -> Option<&OwnerReference> {
+    let owners = metadata.owner_references.as_ref()?;
+    owners
+        .iter()
+        .find(|owner| owner.controller == Some(true))
+        .or_else(|| owners.first())
+}
+
+fn annotate_from_deployment_metadata(
+    log: &mut LogEvent,
+    fields_spec: &FieldsSpec,
+    annotations_allowlist: &[glob::Pattern],
+    metadata: &ObjectMeta,
+) {
+    if let Some(labels) = &metadata.labels {
+        // Calculate and cache the prefix path.
+        let prefix_path = parse_path(&fields_spec.deployment_labels);
+        for (key, val) in labels.iter() {
+            let mut path = prefix_path.clone().segments;
+            path.push(OwnedSegment::Field(key.clone()));
+            log.insert(&path, val.to_owned());
+        }
+    }
+
+    if let Some(annotations) = &metadata.annotations {
+        let prefix_path = parse_path(&fields_spec.deployment_annotations);
+        for (key, val) in annotations.iter() {
+            if is_annotation_allowed(annotations_allowlist, key) {
+                let mut path = prefix_path.clone().segments;
+                path.push(OwnedSegment::Field(key.clone()));
+                log.insert(&path, val.to_owned());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use kube::runtime::{reflector::store::Writer, watcher};
+    use vector_common::assert_event_data_eq;
+
+    use super::*;
+
+    fn owner(kind: &str, name: &str, controller: bool) -> OwnerReference {
+        OwnerReference {
+            api_version: "v1".to_owned(),
+            kind: kind.to_owned(),
+            name: name.to_owned(),
+            uid: format!("{}-uid", name),
+            controller: Some(controller),
+            ..Default::default()
+        }
+    }
+
+    fn metadata(name: &str, owners: Vec<OwnerReference>) -> ObjectMeta {
+        ObjectMeta {
+            name: Some(name.to_owned()),
+            namespace: Some("sandbox0-ns".to_owned()),
+            owner_references: Some(owners),
+            ..ObjectMeta::default()
+        }
+    }
+
+    fn store<K>(objects: Vec<K>) -> Store<K>
+    where
+        K: kube::Resource + Clone,
+        K::DynamicType: Default + Eq + std::hash::Hash + Clone,
+    {
+        let mut writer = Writer::default();
+        for object in objects {
+            writer.apply_watcher_event(&watcher::Event::Applied(object));
+        }
+        writer.as_reader()
+    }
+
+    fn annotator(pod: Pod, owner_states: OwnerStates) -> OwnerMetadataAnnotator {
+        OwnerMetadataAnnotator::new(
+            store(vec![pod]),
+            owner_states,
+            FieldsSpec::default(),
+            vec![glob::Pattern::new("team.example.com/*").unwrap()],
+        )
+    }
+
+    fn annotate(annotator: &OwnerMetadataAnnotator) -> LogEvent {
+        let mut event = Event::from(LogEvent::default());
+        annotator.annotate(&mut event, "sandbox0-ns", "sandbox0-pod");
+        event.into_log()
+    }
+
+    #[test]
+    fn test_annotate_deployment() {
+        let annotator = annotator(
+            Pod {
+                metadata: metadata(
+                    "sandbox0-pod",
+                    vec![owner("ReplicaSet", "sandbox0-rs", true)],
+                ),
+                ..Pod::default()
+            },
+            OwnerStates {
+                replicasets: store(vec![ReplicaSet {
+                    metadata: metadata(
+                        "sandbox0-rs",
+                        vec![owner("Deployment", "sandbox0-deploy", true)],
+                    ),
+                    ..ReplicaSet::default()
+                }]),
+                deployments: store(vec![Deployment {
+                    metadata: ObjectMeta {
+                        labels: Some(
+                            vec![("app".to_owned(), "sandbox0".to_owned())]
+                                .into_iter()
+                                .collect(),
+                        ),
+                        annotations: Some(
+                            vec![
+                                ("team.example.com/owner".to_owned(), "infra".to_owned()),
+                                (
+                                    "deployment.kubernetes.io/revision".to_owned(),
+                                    "3".to_owned(),
+                                ),
+                            ]
+                            .into_iter()
+                            .collect(),
+                        ),
+                        ..metadata("sandbox0-deploy", vec![])
+                    },
+                    ..Deployment::default()
+                }]),
+                jobs: store(vec![]),
+            },
+        );
+
+        let mut expected = LogEvent::default();
+        expected.insert("kubernetes.replicaset_name", "sandbox0-rs");
+        expected.insert("kubernetes.deployment_name", "sandbox0-deploy");
+        expected.insert("kubernetes.deployment_labels.app", "sandbox0");
+        expected.insert(
+            r#"kubernetes.deployment_annotations."team.example.com/owner""#,
+            "infra",
+        );
+        assert_event_data_eq!(annotate(&annotator), expected);
+    }
+
+    #[test]
+    fn test_annotate_unknown_owners() {
+        // The deployment is known from the replicaset even though it isn't in the state yet.
+        let annotator = annotator(
+            Pod {
+                metadata: metadata(
+                    "sandbox0-pod",
+                    vec![owner("ReplicaSet", "sandbox0-rs", true)],
+                ),
+                ..Pod::default()
+            },
+            OwnerStates {
+                replicasets: store(vec![ReplicaSet {
+                    metadata: metadata(
+                        "sandbox0-rs",
+                        vec![owner("Deployment", "sandbox0-deploy", true)],
+                    ),
+                    ..ReplicaSet::default()
+                }]),
+                deployments: store(vec![]),
+                jobs: store(vec![]),
+            },
+        );
+
+        let mut expected = LogEvent::default();
+        expected.insert("kubernetes.replicaset_name", "sandbox0-rs");
+        expected.insert("kubernetes.deployment_name", "sandbox0-deploy");
+        assert_event_data_eq!(annotate(&annotator), expected);
+    }
+
+    #[test]
+    fn test_annotate_cronjob() {
+        let annotator = annotator(
+            Pod {
+                metadata: metadata(
+                    "sandbox0-pod",
+                    vec![
+                        owner("Workflow", "sandbox0-workflow", false),
+                        owner("Job", "sandbox0-job", true),
+                    ],
+                ),
+                ..Pod::default()
+            },
+            OwnerStates {
+                replicasets: store(vec![]),
+                deployments: store(vec![]),
+                jobs: store(vec![Job {
+                    metadata: metadata(
+                        "sandbox0-job",
+                        vec![owner("CronJob", "sandbox0-cronjob", true)],
+                    ),
+                    ..Job::default()
+                }]),
+            },
+        );
+
+        let mut expected = LogEvent::default();
+        expected.insert("kubernetes.job_name", "sandbox0-job");
+        expected.insert("kubernetes.cronjob_name", "sandbox0-cronjob");
+        assert_event_data_eq!(annotate(&annotator), expected);
+    }
+
+    #[test]
+    fn test_annotate_statefulset() {
+        let annotator = annotator(
+            Pod {
+                metadata: metadata(
+                    "sandbox0-pod",
+                    vec![owner("StatefulSet", "sandbox0-sts", true)],
+                ),
+                ..Pod::default()
+            },
+            OwnerStates {
+                replicasets: store(vec![]),
+                deployments: store(vec![]),
+                jobs: store(vec![]),
+            },
+        );
+
+        let mut expected = LogEvent::default();
+        expected.insert("kubernetes.statefulset_name", "sandbox0-sts");
+        assert_event_data_eq!(annotate(&annotator), expected);
+    }
+}
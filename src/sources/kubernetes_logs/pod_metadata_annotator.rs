@@ -78,14 +78,20 @@ impl Default for FieldsSpec {
 pub struct PodMetadataAnnotator {
     pods_state_reader: Store<Pod>,
     fields_spec: FieldsSpec,
+    annotations_allowlist: Vec<glob::Pattern>,
 }
 
 impl PodMetadataAnnotator {
     /// Create a new [`PodMetadataAnnotator`].
-    pub const fn new(pods_state_reader: Store<Pod>, fields_spec: FieldsSpec) -> Self {
+    pub const fn new(
+        pods_state_reader: Store<Pod>,
+        fields_spec: FieldsSpec,
+        annotations_allowlist: Vec<glob::Pattern>,
+    ) -> Self {
         Self {
             pods_state_reader,
             fields_spec,
+            annotations_allowlist,
         }
     }
 }
@@ -102,7 +108,12 @@ impl PodMetadataAnnotator {
         let pod: &Pod = resource.as_ref();
 
         annotate_from_file_info(log, &self.fields_spec, &file_info);
-        annotate_from_metadata(log, &self.fields_spec, &pod.metadata);
+        annotate_from_metadata(
+            log,
+            &self.fields_spec,
+            &self.annotations_allowlist,
+            &pod.metadata,
+        );
 
         let container;
         if let Some(ref pod_spec) = pod.spec {
@@ -143,7 +154,21 @@ fn annotate_from_file_info(
     );
 }
 
-fn annotate_from_metadata(log: &mut LogEvent, fields_spec: &FieldsSpec, metadata: &ObjectMeta) {
+/// Returns whether the annotation is added to the events, which is the case of all the annotations
+/// when the allowlist is empty.
+pub(super) fn is_annotation_allowed(annotations_allowlist: &[glob::Pattern], key: &str) -> bool {
+    annotations_allowlist.is_empty()
+        || annotations_allowlist
+            .iter()
+            .any(|pattern| pattern.matches(key))
+}
+
+fn annotate_from_metadata(
+    log: &mut LogEvent,
+    fields_spec: &FieldsSpec,
+    annotations_allowlist: &[glob::Pattern],
+    metadata: &ObjectMeta,
+) {
     for (key, val) in [
         (&fields_spec.pod_name, &metadata.name),
         (&fields_spec.pod_namespace, &metadata.namespace),
@@ -176,9 +201,11 @@ fn annotate_from_metadata(log: &mut LogEvent, fields_spec: &FieldsSpec, metadata
     if let Some(annotations) = &metadata.annotations {
         let prefix_path = parse_path(&fields_spec.pod_annotations);
         for (key, val) in annotations.iter() {
-            let mut path = prefix_path.clone().segments;
-            path.push(OwnedSegment::Field(key.clone()));
-            log.insert(&path, val.to_owned());
+            if is_annotation_allowed(annotations_allowlist, key) {
+                let mut path = prefix_path.clone().segments;
+                path.push(OwnedSegment::Field(key.clone()));
+                log.insert(&path, val.to_owned());
+            }
         }
     }
 }
@@ -363,11 +390,53 @@ mod tests {
 
         for (fields_spec, metadata, expected) in cases.into_iter() {
             let mut log = LogEvent::default();
-            annotate_from_metadata(&mut log, &fields_spec, &metadata);
+            annotate_from_metadata(&mut log, &fields_spec, &[], &metadata);
             assert_event_data_eq!(log, expected);
         }
     }
 
+    #[test]
+    fn test_annotate_from_metadata_with_allowlist() {
+        let metadata = ObjectMeta {
+            annotations: Some(
+                vec![
+                    ("app.kubernetes.io/name".to_owned(), "val0".to_owned()),
+                    ("app.kubernetes.io/version".to_owned(), "val1".to_owned()),
+                    ("team.example.com/owner".to_owned(), "val2".to_owned()),
+                    (
+                        "kubectl.kubernetes.io/restartedAt".to_owned(),
+                        "val3".to_owned(),
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+            ),
+            ..ObjectMeta::default()
+        };
+        let allowlist = [
+            glob::Pattern::new("app.kubernetes.io/*").unwrap(),
+            glob::Pattern::new("team.example.com/owner").unwrap(),
+        ];
+
+        let mut log = LogEvent::default();
+        annotate_from_metadata(&mut log, &FieldsSpec::default(), &allowlist, &metadata);
+
+        let mut expected = LogEvent::default();
+        expected.insert(
+            r#"kubernetes.pod_annotations."app.kubernetes.io/name""#,
+            "val0",
+        );
+        expected.insert(
+            r#"kubernetes.pod_annotations."app.kubernetes.io/version""#,
+            "val1",
+        );
+        expected.insert(
+            r#"kubernetes.pod_annotations."team.example.com/owner""#,
+            "val2",
+        );
+        assert_event_data_eq!(log, expected);
+    }
+
     #[test]
     fn test_annotate_from_file_info() {
         let cases = vec![(
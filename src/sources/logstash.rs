@@ -24,7 +24,7 @@ use crate::{
     event::{Event, LogEvent, Value},
     serde::bool_or_struct,
     tcp::TcpKeepaliveConfig,
-    tls::{MaybeTlsSettings, TlsSourceConfig},
+    tls::{MaybeTlsSettings, TlsClientAuthorization, TlsSourceConfig},
     types,
 };
 
@@ -49,6 +49,14 @@ pub struct LogstashConfig {
     /// The maximum number of TCP connections that will be allowed at any given time.
     connection_limit: Option<u32>,
 
+    /// Whether to expect each connection to start with a PROXY protocol header.
+    ///
+    /// Load balancers such as HAProxy send this header to pass on the address of the original client,
+    /// which is then used as the peer address of the connection. Both version 1 and 2 of the protocol
+    /// are supported. Connections not starting with a valid header are closed.
+    #[serde(default)]
+    proxy_protocol: bool,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
@@ -67,6 +75,7 @@ impl GenerateConfig for LogstashConfig {
             receive_buffer_bytes: None,
             acknowledgements: Default::default(),
             connection_limit: None,
+            proxy_protocol: false,
         })
         .unwrap()
     }
@@ -85,6 +94,7 @@ impl SourceConfig for LogstashConfig {
             .tls
             .as_ref()
             .and_then(|tls| tls.client_metadata_key.clone());
+        let tls_client_authorization = TlsClientAuthorization::from_config(&self.tls)?;
         let tls = MaybeTlsSettings::from_config(&tls_config, true)?;
         source.run(
             self.address,
@@ -92,10 +102,12 @@ impl SourceConfig for LogstashConfig {
             shutdown_secs,
             tls,
             tls_client_metadata_key,
+            tls_client_authorization,
             self.receive_buffer_bytes,
             cx,
             self.acknowledgements,
             self.connection_limit,
+            self.proxy_protocol,
         )
     }
 
@@ -629,6 +641,7 @@ mod test {
                 receive_buffer_bytes: None,
                 acknowledgements: true.into(),
                 connection_limit: None,
+                proxy_protocol: false,
             }
             .build(SourceContext::new_test(sender, None))
             .await
@@ -792,6 +805,7 @@ mod integration_tests {
         let tls_config = TlsSourceConfig {
             client_metadata_key: None,
             tls_config: tls_options,
+            ..Default::default()
         };
         tokio::spawn(async move {
             LogstashConfig {
@@ -801,6 +815,7 @@ mod integration_tests {
                 receive_buffer_bytes: None,
                 acknowledgements: false.into(),
                 connection_limit: None,
+                proxy_protocol: false,
             }
             .build(SourceContext::new_test(sender, None))
             .await
@@ -1,15 +1,21 @@
 use snafu::Snafu;
 
+#[cfg(feature = "sources-amqp")]
+pub mod amqp;
 #[cfg(feature = "sources-apache_metrics")]
 pub mod apache_metrics;
 #[cfg(feature = "sources-aws_ecs_metrics")]
 pub mod aws_ecs_metrics;
 #[cfg(feature = "sources-aws_kinesis_firehose")]
 pub mod aws_kinesis_firehose;
+#[cfg(feature = "sources-aws_kinesis_streams")]
+pub mod aws_kinesis_streams;
 #[cfg(feature = "sources-aws_s3")]
 pub mod aws_s3;
 #[cfg(feature = "sources-aws_sqs")]
 pub mod aws_sqs;
+#[cfg(feature = "sources-azure_event_hubs")]
+pub mod azure_event_hubs;
 #[cfg(any(feature = "sources-datadog_agent"))]
 pub mod datadog;
 #[cfg(feature = "sources-demo_logs")]
@@ -18,6 +24,8 @@ pub mod demo_logs;
 pub mod dnstap;
 #[cfg(feature = "sources-docker_logs")]
 pub mod docker_logs;
+#[cfg(all(target_os = "linux", feature = "sources-ebpf"))]
+pub mod ebpf;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 pub mod eventstoredb_metrics;
 #[cfg(feature = "sources-exec")]
@@ -26,22 +34,32 @@ pub mod exec;
 pub mod file;
 #[cfg(feature = "sources-fluent")]
 pub mod fluent;
+#[cfg(feature = "sources-gcp_cloud_storage")]
+pub mod gcp_cloud_storage;
 #[cfg(feature = "sources-gcp_pubsub")]
 pub mod gcp_pubsub;
+#[cfg(feature = "sources-grpc_server")]
+pub mod grpc_server;
 #[cfg(feature = "sources-heroku_logs")]
 pub mod heroku_logs;
 #[cfg(feature = "sources-host_metrics")]
 pub mod host_metrics;
 #[cfg(feature = "sources-http")]
 pub mod http;
+#[cfg(feature = "sources-http_client")]
+pub mod http_client;
 #[cfg(feature = "sources-internal_logs")]
 pub mod internal_logs;
 #[cfg(feature = "sources-internal_metrics")]
 pub mod internal_metrics;
 #[cfg(all(unix, feature = "sources-journald"))]
 pub mod journald;
+#[cfg(feature = "sources-journald_remote")]
+pub mod journald_remote;
 #[cfg(feature = "sources-kafka")]
 pub mod kafka;
+#[cfg(feature = "sources-kubernetes_events")]
+pub mod kubernetes_events;
 #[cfg(feature = "sources-kubernetes_logs")]
 pub mod kubernetes_logs;
 #[cfg(all(feature = "sources-logstash"))]
@@ -64,6 +82,8 @@ pub mod redis;
 pub mod socket;
 #[cfg(feature = "sources-splunk_hec")]
 pub mod splunk_hec;
+#[cfg(feature = "sources-sql_query")]
+pub mod sql_query;
 #[cfg(feature = "sources-statsd")]
 pub mod statsd;
 #[cfg(feature = "sources-stdin")]
@@ -72,6 +92,10 @@ pub mod stdin;
 pub mod syslog;
 #[cfg(feature = "sources-vector")]
 pub mod vector;
+#[cfg(feature = "sources-websocket_client")]
+pub mod websocket_client;
+#[cfg(all(windows, feature = "sources-windows_eventlog"))]
+pub mod windows_eventlog;
 
 pub(crate) mod util;
 
@@ -90,6 +114,10 @@ enum BuildError {
 #[derive(Clone, Debug)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Sources {
+    /// AMQP.
+    #[cfg(feature = "sources-amqp")]
+    Amqp(#[configurable(derived)] amqp::AmqpSourceConfig),
+
     /// Apache HTTP Server (HTTPD) Metrics.
     #[cfg(feature = "sources-apache_metrics")]
     ApacheMetrics(#[configurable(derived)] apache_metrics::ApacheMetricsConfig),
@@ -102,6 +130,10 @@ pub enum Sources {
     #[cfg(feature = "sources-aws_kinesis_firehose")]
     AwsKinesisFirehose(#[configurable(derived)] aws_kinesis_firehose::AwsKinesisFirehoseConfig),
 
+    /// AWS Kinesis Data Streams.
+    #[cfg(feature = "sources-aws_kinesis_streams")]
+    AwsKinesisStreams(#[configurable(derived)] aws_kinesis_streams::AwsKinesisStreamsConfig),
+
     /// AWS S3.
     #[cfg(feature = "sources-aws_s3")]
     AwsS3(#[configurable(derived)] aws_s3::AwsS3Config),
@@ -110,6 +142,10 @@ pub enum Sources {
     #[cfg(feature = "sources-aws_sqs")]
     AwsSqs(#[configurable(derived)] aws_sqs::AwsSqsConfig),
 
+    /// Azure Event Hubs.
+    #[cfg(feature = "sources-azure_event_hubs")]
+    AzureEventHubs(#[configurable(derived)] azure_event_hubs::AzureEventHubsSourceConfig),
+
     /// Datadog Agent.
     #[cfg(feature = "sources-datadog_agent")]
     DatadogAgent(#[configurable(derived)] datadog::agent::DatadogAgentConfig),
@@ -126,6 +162,10 @@ pub enum Sources {
     #[cfg(feature = "sources-docker_logs")]
     DockerLogs(#[configurable(derived)] docker_logs::DockerLogsConfig),
 
+    /// eBPF.
+    #[cfg(all(target_os = "linux", feature = "sources-ebpf"))]
+    Ebpf(#[configurable(derived)] ebpf::EbpfConfig),
+
     /// EventStoreDB Metrics.
     #[cfg(feature = "sources-eventstoredb_metrics")]
     EventstoreDbMetrics(#[configurable(derived)] eventstoredb_metrics::EventStoreDbConfig),
@@ -142,10 +182,18 @@ pub enum Sources {
     #[cfg(feature = "sources-fluent")]
     Fluent(#[configurable(derived)] fluent::FluentConfig),
 
+    /// GCP Cloud Storage.
+    #[cfg(feature = "sources-gcp_cloud_storage")]
+    GcpCloudStorage(#[configurable(derived)] gcp_cloud_storage::GcsSourceConfig),
+
     /// GCP Pub/Sub.
     #[cfg(feature = "sources-gcp_pubsub")]
     GcpPubsub(#[configurable(derived)] gcp_pubsub::PubsubConfig),
 
+    /// gRPC server.
+    #[cfg(feature = "sources-grpc_server")]
+    GrpcServer(#[configurable(derived)] grpc_server::GrpcServerConfig),
+
     /// Generator.
     #[cfg(feature = "sources-demo_logs")]
     Generator(#[configurable(derived)] demo_logs::DemoLogsCompatConfig),
@@ -162,6 +210,10 @@ pub enum Sources {
     #[cfg(feature = "sources-http")]
     Http(#[configurable(derived)] http::SimpleHttpConfig),
 
+    /// HTTP client.
+    #[cfg(feature = "sources-http_client")]
+    HttpClient(#[configurable(derived)] http_client::HttpClientConfig),
+
     /// Internal Logs.
     #[cfg(feature = "sources-internal_logs")]
     InternalLogs(#[configurable(derived)] internal_logs::InternalLogsConfig),
@@ -174,10 +226,18 @@ pub enum Sources {
     #[cfg(all(unix, feature = "sources-journald"))]
     Journald(#[configurable(derived)] journald::JournaldConfig),
 
+    /// Journald Remote.
+    #[cfg(feature = "sources-journald_remote")]
+    JournaldRemote(#[configurable(derived)] journald_remote::JournaldRemoteConfig),
+
     /// Kafka.
     #[cfg(feature = "sources-kafka")]
     Kafka(#[configurable(derived)] kafka::KafkaSourceConfig),
 
+    /// Kubernetes Events.
+    #[cfg(feature = "sources-kubernetes_events")]
+    KubernetesEvents(#[configurable(derived)] kubernetes_events::KubernetesEventsConfig),
+
     /// Kubernetes Logs.
     #[cfg(feature = "sources-kubernetes_logs")]
     KubernetesLogs(#[configurable(derived)] kubernetes_logs::Config),
@@ -226,6 +286,10 @@ pub enum Sources {
     #[cfg(feature = "sources-splunk_hec")]
     SplunkHec(#[configurable(derived)] splunk_hec::SplunkConfig),
 
+    /// SQL query.
+    #[cfg(feature = "sources-sql_query")]
+    SqlQuery(#[configurable(derived)] sql_query::SqlQueryConfig),
+
     /// Statsd.
     #[cfg(feature = "sources-statsd")]
     Statsd(#[configurable(derived)] statsd::StatsdConfig),
@@ -241,6 +305,14 @@ pub enum Sources {
     /// Vector.
     #[cfg(feature = "sources-vector")]
     Vector(#[configurable(derived)] vector::VectorConfig),
+
+    /// WebSocket client.
+    #[cfg(feature = "sources-websocket_client")]
+    WebsocketClient(#[configurable(derived)] websocket_client::WebSocketClientConfig),
+
+    /// Windows Event Log.
+    #[cfg(all(windows, feature = "sources-windows_eventlog"))]
+    WindowsEventlog(#[configurable(derived)] windows_eventlog::WindowsEventLogConfig),
 }
 
 #[cfg(test)]
@@ -1,9 +1,14 @@
+use std::time::Duration;
+
 use bytes::Bytes;
 use chrono::Utc;
-use codecs::decoding::{DeserializerConfig, FramingConfig, StreamDecodingError};
+use codecs::decoding::{DecodingErrorMode, DeserializerConfig, FramingConfig, StreamDecodingError};
 use futures::{pin_mut, stream, Stream, StreamExt};
+use serde_json::json;
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::FramedRead;
+use uuid::Uuid;
+use vector_common::finalizer::UnorderedFinalizer;
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 
@@ -11,16 +16,34 @@ use vector_core::ByteSizeOf;
 
 use crate::{
     codecs::{Decoder, DecodingConfig},
-    config::{log_schema, GenerateConfig, Output, SourceConfig, SourceContext, SourceDescription},
-    event::Event,
-    internal_events::{BytesReceived, OldEventsReceived, StreamClosedError},
-    nats::{from_tls_auth_config, NatsAuthConfig, NatsConfigError},
-    serde::{default_decoding, default_framing_message_based},
+    config::{
+        log_schema, AcknowledgementsConfig, GenerateConfig, Output, SourceConfig, SourceContext,
+        SourceDescription,
+    },
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{
+        BytesReceived, NatsAcknowledgementError, NatsPullError, OldEventsReceived,
+        StreamClosedError,
+    },
+    nats::{
+        from_tls_auth_config, jetstream_request, JetStreamError, NatsAuthConfig, NatsConfigError,
+    },
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
     shutdown::ShutdownSignal,
+    sources::util,
     tls::TlsEnableableConfig,
     SourceSender,
 };
 
+/// How long a pull request waits for messages before the server expires it.
+const PULL_EXPIRES: Duration = Duration::from_secs(5);
+
+/// How long to wait for the messages of a pull request before sending a new one, in case it was
+/// lost, such as on reconnection.
+const PULL_TIMEOUT: Duration = Duration::from_secs(10);
+
+type Finalizer = UnorderedFinalizer<String>;
+
 #[derive(Debug, Snafu)]
 enum BuildError {
     #[snafu(display("NATS Config Error: {}", source))]
@@ -29,6 +52,8 @@ enum BuildError {
     Connect { source: std::io::Error },
     #[snafu(display("NATS Subscribe Error: {}", source))]
     Subscribe { source: std::io::Error },
+    #[snafu(display("NATS JetStream Error: {}", source))]
+    JetStream { source: JetStreamError },
 }
 
 /// Configuration for the `nats` source.
@@ -69,6 +94,57 @@ pub struct NatsSourceConfig {
     #[serde(default = "default_decoding")]
     #[derivative(Default(value = "default_decoding()"))]
     decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    on_error: DecodingErrorMode,
+
+    #[configurable(derived)]
+    jetstream: Option<NatsJetStreamConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+/// Options for consuming a JetStream stream through a durable consumer, instead of subscribing to
+/// the subject.
+///
+/// The consumer only receives the messages of the stream matching `subject`.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct NatsJetStreamConfig {
+    /// The name of the stream to consume.
+    stream: String,
+
+    /// The name of the durable consumer, which is created if it doesn't exist.
+    ///
+    /// The server keeps track of the messages acknowledged by the consumer, so that Vector resumes
+    /// from the first unacknowledged message when it is restarted.
+    durable_name: String,
+
+    /// How long the server waits for a message to be acknowledged before delivering it again, in
+    /// seconds.
+    #[serde(default = "default_ack_wait_secs")]
+    ack_wait_secs: u64,
+
+    /// The maximum number of times a message is delivered.
+    ///
+    /// By default, messages are delivered until they are acknowledged.
+    max_deliver: Option<i64>,
+
+    /// The maximum number of messages pulled from the consumer at once.
+    #[serde(default = "default_batch_size")]
+    batch_size: usize,
+}
+
+const fn default_ack_wait_secs() -> u64 {
+    30 // default in the NATS server
+}
+
+const fn default_batch_size() -> usize {
+    100
 }
 
 inventory::submit! {
@@ -91,14 +167,29 @@ impl GenerateConfig for NatsSourceConfig {
 #[typetag::serde(name = "nats")]
 impl SourceConfig for NatsSourceConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
-        let (connection, subscription) = create_subscription(self).await?;
         let decoder = DecodingConfig::new(
             self.framing.clone(),
             self.decoding.clone(),
             LogNamespace::Legacy,
         )
-        .build();
+        .with_error_mode(self.on_error)
+        .build()?;
+
+        if let Some(jetstream) = &self.jetstream {
+            let connection = self.connect().await?;
+            let consumer = create_consumer(&connection, jetstream, &self.subject).await?;
+            let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
+            return Ok(Box::pin(jetstream_source(
+                connection,
+                consumer,
+                decoder,
+                cx.shutdown,
+                cx.out,
+                acknowledgements,
+            )));
+        }
 
+        let (connection, subscription) = create_subscription(self).await?;
         Ok(Box::pin(nats_source(
             connection,
             subscription,
@@ -109,7 +200,9 @@ impl SourceConfig for NatsSourceConfig {
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
-        vec![Output::default(self.decoding.output_type())]
+        vec![Output::default(
+            self.on_error.output_type(self.decoding.output_type()),
+        )]
     }
 
     fn source_type(&self) -> &'static str {
@@ -117,7 +210,7 @@ impl SourceConfig for NatsSourceConfig {
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        self.jetstream.is_some()
     }
 }
 
@@ -211,6 +304,166 @@ async fn create_subscription(
     Ok((nc, subscription))
 }
 
+/// A durable JetStream consumer, whose messages are pulled to an inbox subscription.
+struct JetStreamConsumer {
+    /// The subject of the pull requests of the consumer.
+    next_subject: String,
+    inbox: String,
+    subscription: nats::asynk::Subscription,
+    batch_size: usize,
+}
+
+impl JetStreamConsumer {
+    /// Requests the next batch of messages of the consumer, which are delivered to the inbox.
+    async fn pull(&self, connection: &nats::asynk::Connection) -> std::io::Result<()> {
+        let request = json!({
+            "batch": self.batch_size,
+            "expires": PULL_EXPIRES.as_nanos() as u64,
+        });
+        connection
+            .publish_request(&self.next_subject, &self.inbox, request.to_string())
+            .await
+    }
+}
+
+/// Creates the durable consumer, or checks that it exists, and subscribes to the inbox its
+/// messages are pulled to.
+async fn create_consumer(
+    connection: &nats::asynk::Connection,
+    config: &NatsJetStreamConfig,
+    subject: &str,
+) -> Result<JetStreamConsumer, BuildError> {
+    let request = json!({
+        "stream_name": config.stream,
+        "config": {
+            "durable_name": config.durable_name,
+            "deliver_policy": "all",
+            "ack_policy": "explicit",
+            "ack_wait": Duration::from_secs(config.ack_wait_secs).as_nanos() as u64,
+            "max_deliver": config.max_deliver.unwrap_or(-1),
+            "filter_subject": subject,
+        },
+    });
+    jetstream_request::<serde_json::Value>(
+        connection,
+        &format!(
+            "$JS.API.CONSUMER.DURABLE.CREATE.{}.{}",
+            config.stream, config.durable_name
+        ),
+        &request,
+    )
+    .await
+    .context(JetStreamSnafu)?;
+
+    let inbox = format!("_INBOX.{}", Uuid::new_v4());
+    let subscription = connection.subscribe(&inbox).await.context(SubscribeSnafu)?;
+
+    Ok(JetStreamConsumer {
+        next_subject: format!(
+            "$JS.API.CONSUMER.MSG.NEXT.{}.{}",
+            config.stream, config.durable_name
+        ),
+        inbox,
+        subscription,
+        batch_size: config.batch_size,
+    })
+}
+
+async fn jetstream_source(
+    connection: nats::asynk::Connection,
+    consumer: JetStreamConsumer,
+    decoder: Decoder,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+    acknowledgements: bool,
+) -> Result<(), ()> {
+    let (finalizer, mut ack_stream) = Finalizer::maybe_new(acknowledgements, shutdown.clone());
+    // The number of messages of the last pull request which may still be delivered.
+    let mut pending = 0;
+
+    loop {
+        if pending == 0 {
+            match consumer.pull(&connection).await {
+                Ok(()) => pending = consumer.batch_size,
+                Err(error) => emit!(NatsPullError { error }),
+            }
+        }
+
+        tokio::select! {
+            _ = &mut shutdown => break,
+            entry = ack_stream.next() => if let Some((status, reply)) = entry {
+                acknowledge(&connection, status, &reply).await;
+            },
+            message = tokio::time::timeout(PULL_TIMEOUT, consumer.subscription.next()) => match message {
+                Ok(Some(message)) => match &message.reply {
+                    Some(reply) => {
+                        pending = pending.saturating_sub(1);
+                        handle_message(&connection, &message.data, reply, &decoder, &finalizer, &mut out).await;
+                    }
+                    // Status messages, such as the expiration of the pull request, have no reply
+                    // subject to acknowledge them to.
+                    None => pending = 0,
+                },
+                Ok(None) => break,
+                Err(_) => pending = 0,
+            },
+        }
+    }
+
+    // Wait for the messages in flight to be acknowledged so they are not delivered again.
+    drop(finalizer);
+    while let Some((status, reply)) = ack_stream.next().await {
+        acknowledge(&connection, status, &reply).await;
+    }
+
+    Ok(())
+}
+
+async fn handle_message(
+    connection: &nats::asynk::Connection,
+    data: &[u8],
+    reply: &str,
+    decoder: &Decoder,
+    finalizer: &Option<Finalizer>,
+    out: &mut SourceSender,
+) {
+    emit!(BytesReceived {
+        byte_size: data.len(),
+        protocol: "tcp",
+    });
+
+    let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(finalizer.is_some());
+    let events = util::decode_message(decoder.clone(), "nats", data, Some(Utc::now()), &batch)
+        .collect::<Vec<_>>();
+    drop(batch);
+
+    let count = events.len();
+    match out.send_batch(events).await {
+        Err(error) => emit!(StreamClosedError { error, count }),
+        Ok(()) => match receiver {
+            Some(receiver) => finalizer
+                .as_ref()
+                .expect("Finalizer must have been set up for acknowledgements")
+                .add(reply.to_owned(), receiver),
+            None => acknowledge(connection, BatchStatus::Delivered, reply).await,
+        },
+    }
+}
+
+/// Acknowledges the message according to the status of its events.
+///
+/// Errored messages are delivered again, while rejected ones are not.
+async fn acknowledge(connection: &nats::asynk::Connection, status: BatchStatus, reply: &str) {
+    let ack = match status {
+        BatchStatus::Delivered => "+ACK",
+        BatchStatus::Errored => "-NAK",
+        BatchStatus::Rejected => "+TERM",
+    };
+    if let Err(error) = connection.publish(reply, ack).await {
+        emit!(NatsAcknowledgementError { error });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::print_stdout)] //tests
@@ -250,7 +503,9 @@ mod integration_tests {
                 conf.decoding.clone(),
                 LogNamespace::Legacy,
             )
-            .build();
+            .with_error_mode(conf.on_error)
+            .build()
+            .unwrap();
             tokio::spawn(nats_source(nc, sub, decoder, ShutdownSignal::noop(), tx));
             nc_pub.publish(&subject, msg).await.unwrap();
 
@@ -276,6 +531,9 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_error: Default::default(),
+            jetstream: None,
+            acknowledgements: Default::default(),
             tls: None,
             auth: None,
         };
@@ -301,6 +559,9 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_error: Default::default(),
+            jetstream: None,
+            acknowledgements: Default::default(),
             tls: None,
             auth: Some(NatsAuthConfig::UserPassword {
                 user_password: NatsAuthUserPassword {
@@ -331,6 +592,9 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_error: Default::default(),
+            jetstream: None,
+            acknowledgements: Default::default(),
             tls: None,
             auth: Some(NatsAuthConfig::UserPassword {
                 user_password: NatsAuthUserPassword {
@@ -361,6 +625,9 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_error: Default::default(),
+            jetstream: None,
+            acknowledgements: Default::default(),
             tls: None,
             auth: Some(NatsAuthConfig::Token {
                 token: NatsAuthToken {
@@ -390,6 +657,9 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_error: Default::default(),
+            jetstream: None,
+            acknowledgements: Default::default(),
             tls: None,
             auth: Some(NatsAuthConfig::Token {
                 token: NatsAuthToken {
@@ -419,6 +689,9 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_error: Default::default(),
+            jetstream: None,
+            acknowledgements: Default::default(),
             tls: None,
             auth: Some(NatsAuthConfig::Nkey {
                 nkey: NatsAuthNKey {
@@ -449,6 +722,9 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_error: Default::default(),
+            jetstream: None,
+            acknowledgements: Default::default(),
             tls: None,
             auth: Some(NatsAuthConfig::Nkey {
                 nkey: NatsAuthNKey {
@@ -479,6 +755,9 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_error: Default::default(),
+            jetstream: None,
+            acknowledgements: Default::default(),
             tls: Some(TlsEnableableConfig {
                 enabled: Some(true),
                 options: TlsConfig {
@@ -510,6 +789,9 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_error: Default::default(),
+            jetstream: None,
+            acknowledgements: Default::default(),
             tls: None,
             auth: None,
         };
@@ -535,6 +817,9 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_error: Default::default(),
+            jetstream: None,
+            acknowledgements: Default::default(),
             tls: Some(TlsEnableableConfig {
                 enabled: Some(true),
                 options: TlsConfig {
@@ -568,6 +853,9 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_error: Default::default(),
+            jetstream: None,
+            acknowledgements: Default::default(),
             tls: Some(TlsEnableableConfig {
                 enabled: Some(true),
                 options: TlsConfig {
@@ -599,6 +887,9 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_error: Default::default(),
+            jetstream: None,
+            acknowledgements: Default::default(),
             tls: Some(TlsEnableableConfig {
                 enabled: Some(true),
                 options: TlsConfig {
@@ -634,6 +925,9 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_error: Default::default(),
+            jetstream: None,
+            acknowledgements: Default::default(),
             tls: Some(TlsEnableableConfig {
                 enabled: Some(true),
                 options: TlsConfig {
@@ -655,4 +949,88 @@ mod integration_tests {
             r
         );
     }
+
+    #[tokio::test]
+    async fn nats_jetstream_durable_consumer() {
+        let stream = format!("test-{}", random_string(10));
+        let subject = format!("test.{}", random_string(10));
+        let url =
+            std::env::var("NATS_ADDRESS").unwrap_or_else(|_| String::from("nats://localhost:4222"));
+
+        let conf = NatsSourceConfig {
+            connection_name: "".to_owned(),
+            subject: subject.clone(),
+            url,
+            queue: None,
+            framing: default_framing_message_based(),
+            decoding: default_decoding(),
+            on_error: Default::default(),
+            jetstream: Some(NatsJetStreamConfig {
+                stream: stream.clone(),
+                durable_name: "vector".into(),
+                ack_wait_secs: 30,
+                max_deliver: None,
+                batch_size: 10,
+            }),
+            acknowledgements: Default::default(),
+            tls: None,
+            auth: None,
+        };
+
+        let connection = conf.connect().await.unwrap();
+        jetstream_request::<serde_json::Value>(
+            &connection,
+            &format!("$JS.API.STREAM.CREATE.{}", stream),
+            &json!({ "name": stream, "subjects": [subject] }),
+        )
+        .await
+        .unwrap();
+        for i in 0..3 {
+            // JetStream acknowledges the messages it stores.
+            connection
+                .request(&subject, format!("my message {}", i))
+                .await
+                .unwrap();
+        }
+
+        let consumer = create_consumer(&connection, conf.jetstream.as_ref().unwrap(), &subject)
+            .await
+            .unwrap();
+        let events = assert_source_compliance(&SOURCE_TAGS, async move {
+            let (trigger_shutdown, shutdown, shutdown_done) = ShutdownSignal::new_wired();
+            let (tx, rx) = SourceSender::new_test_finalize(crate::event::EventStatus::Delivered);
+            tokio::spawn(jetstream_source(
+                connection,
+                consumer,
+                Decoder::default(),
+                shutdown,
+                tx,
+                true,
+            ));
+            let events = collect_n(rx, 3).await;
+            drop(trigger_shutdown);
+            shutdown_done.await;
+            events
+        })
+        .await;
+
+        for (i, event) in events.iter().enumerate() {
+            assert_eq!(
+                event.as_log()[log_schema().message_key()],
+                format!("my message {}", i).into()
+            );
+        }
+
+        // The messages were acknowledged, so the consumer has none left to deliver.
+        let connection = conf.connect().await.unwrap();
+        let info: serde_json::Value = jetstream_request(
+            &connection,
+            &format!("$JS.API.CONSUMER.INFO.{}.vector", stream),
+            &json!({}),
+        )
+        .await
+        .unwrap();
+        assert_eq!(info["num_ack_pending"], 0);
+        assert_eq!(info["num_pending"], 0);
+    }
 }
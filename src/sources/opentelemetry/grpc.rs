@@ -0,0 +1,121 @@
+use crate::{
+    internal_events::{EventsReceived, StreamClosedError},
+    opentelemetry::{
+        LogService::{
+            logs_service_server::LogsService, ExportLogsServiceRequest, ExportLogsServiceResponse,
+        },
+        MetricsService::{
+            metrics_service_server::MetricsService, ExportMetricsServiceRequest,
+            ExportMetricsServiceResponse,
+        },
+        TraceService::{
+            trace_service_server::TraceService, ExportTraceServiceRequest,
+            ExportTraceServiceResponse,
+        },
+    },
+    sources::opentelemetry::{LOGS, METRICS, TRACES},
+    SourceSender,
+};
+use futures::TryFutureExt;
+
+use tonic::{Request, Response, Status};
+
+use vector_core::{
+    event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event},
+    ByteSizeOf,
+};
+
+#[derive(Debug, Clone)]
+pub(crate) struct Service {
+    pub(crate) pipeline: SourceSender,
+    pub(crate) acknowledgements: bool,
+}
+
+#[tonic::async_trait]
+impl LogsService for Service {
+    async fn export(
+        &self,
+        request: Request<ExportLogsServiceRequest>,
+    ) -> Result<Response<ExportLogsServiceResponse>, Status> {
+        let events = request
+            .into_inner()
+            .resource_logs
+            .into_iter()
+            .flat_map(|v| v.into_iter())
+            .collect();
+
+        self.handle_events(events, LOGS).await?;
+        Ok(Response::new(ExportLogsServiceResponse {}))
+    }
+}
+
+#[tonic::async_trait]
+impl MetricsService for Service {
+    async fn export(
+        &self,
+        request: Request<ExportMetricsServiceRequest>,
+    ) -> Result<Response<ExportMetricsServiceResponse>, Status> {
+        let events = request
+            .into_inner()
+            .resource_metrics
+            .into_iter()
+            .flat_map(|v| v.into_iter())
+            .collect();
+
+        self.handle_events(events, METRICS).await?;
+        Ok(Response::new(ExportMetricsServiceResponse {}))
+    }
+}
+
+#[tonic::async_trait]
+impl TraceService for Service {
+    async fn export(
+        &self,
+        request: Request<ExportTraceServiceRequest>,
+    ) -> Result<Response<ExportTraceServiceResponse>, Status> {
+        let events = request
+            .into_inner()
+            .resource_spans
+            .into_iter()
+            .flat_map(|v| v.into_iter())
+            .collect();
+
+        self.handle_events(events, TRACES).await?;
+        Ok(Response::new(ExportTraceServiceResponse {}))
+    }
+}
+
+impl Service {
+    async fn handle_events(&self, mut events: Vec<Event>, output: &str) -> Result<(), Status> {
+        let count = events.len();
+        let byte_size = events.size_of();
+
+        emit!(EventsReceived { count, byte_size });
+
+        let receiver = BatchNotifier::maybe_apply_to(self.acknowledgements, &mut events);
+
+        self.pipeline
+            .clone()
+            .send_batch_named(output, events)
+            .map_err(|error| {
+                let message = error.to_string();
+                emit!(StreamClosedError { error, count });
+                Status::unavailable(message)
+            })
+            .and_then(|_| handle_batch_status(receiver))
+            .await
+    }
+}
+
+async fn handle_batch_status(receiver: Option<BatchStatusReceiver>) -> Result<(), Status> {
+    let status = match receiver {
+        Some(receiver) => receiver.await,
+        None => BatchStatus::Delivered,
+    };
+
+    match status {
+        BatchStatus::Errored => Err(Status::internal("Delivery error")),
+        BatchStatus::Rejected => Err(Status::data_loss("Delivery failed")),
+        BatchStatus::Delivered => Ok(()),
+    }
+}
@@ -0,0 +1,211 @@
+use std::net::SocketAddr;
+
+use bytes::Bytes;
+use futures::FutureExt;
+use http::StatusCode;
+use prost::Message;
+use tracing::Span;
+use vector_core::{
+    event::{BatchNotifier, BatchStatus, Event},
+    ByteSizeOf,
+};
+use warp::{filters::BoxedFilter, reply::Response, Filter, Rejection, Reply};
+
+use crate::{
+    internal_events::{HttpBytesReceived, HttpEventsReceived, StreamClosedError},
+    opentelemetry::{
+        LogService::{ExportLogsServiceRequest, ExportLogsServiceResponse},
+        MetricsService::{ExportMetricsServiceRequest, ExportMetricsServiceResponse},
+        TraceService::{ExportTraceServiceRequest, ExportTraceServiceResponse},
+    },
+    shutdown::ShutdownSignal,
+    sources::{
+        opentelemetry::{LOGS, METRICS, TRACES},
+        util::{decode, ErrorMessage},
+    },
+    tls::MaybeTlsSettings,
+    SourceSender,
+};
+
+pub(crate) async fn run_http_server(
+    address: SocketAddr,
+    tls_settings: MaybeTlsSettings,
+    filters: BoxedFilter<(Response,)>,
+    shutdown: ShutdownSignal,
+) -> crate::Result<()> {
+    let listener = tls_settings.bind(&address).await?;
+
+    info!(message = "Building HTTP server.", address = %address);
+
+    let span = Span::current();
+    let routes = filters
+        .with(warp::trace(move |_info| span.clone()))
+        .recover(|r: Rejection| async move {
+            if let Some(e_msg) = r.find::<ErrorMessage>() {
+                let json = warp::reply::json(e_msg);
+                Ok(warp::reply::with_status(json, e_msg.status_code()))
+            } else {
+                // other internal error - will return 500 internal server error
+                Err(r)
+            }
+        });
+    warp::serve(routes)
+        .serve_incoming_with_graceful_shutdown(listener.accept_stream(), shutdown.map(|_| ()))
+        .await;
+
+    Ok(())
+}
+
+/// Builds the filters of the OTLP/HTTP endpoints, which accept the same protobuf encoded requests as
+/// the gRPC services.
+pub(crate) fn build_warp_filter(
+    acknowledgements: bool,
+    out: SourceSender,
+    protocol: &'static str,
+) -> BoxedFilter<(Response,)> {
+    let logs = build_export_filter(
+        LOGS,
+        acknowledgements,
+        out.clone(),
+        protocol,
+        |request: ExportLogsServiceRequest| {
+            let events = request
+                .resource_logs
+                .into_iter()
+                .flat_map(|v| v.into_iter())
+                .collect();
+            (events, ExportLogsServiceResponse {}.encode_to_vec())
+        },
+    );
+    let metrics = build_export_filter(
+        METRICS,
+        acknowledgements,
+        out.clone(),
+        protocol,
+        |request: ExportMetricsServiceRequest| {
+            let events = request
+                .resource_metrics
+                .into_iter()
+                .flat_map(|v| v.into_iter())
+                .collect();
+            (events, ExportMetricsServiceResponse {}.encode_to_vec())
+        },
+    );
+    let traces = build_export_filter(
+        TRACES,
+        acknowledgements,
+        out,
+        protocol,
+        |request: ExportTraceServiceRequest| {
+            let events = request
+                .resource_spans
+                .into_iter()
+                .flat_map(|v| v.into_iter())
+                .collect();
+            (events, ExportTraceServiceResponse {}.encode_to_vec())
+        },
+    );
+
+    logs.or(metrics).unify().or(traces).unify().boxed()
+}
+
+/// Builds the filter of the `/v1/<output>` endpoint, converting each request into the events sent to
+/// the output of the same name and the encoded response.
+fn build_export_filter<R>(
+    output: &'static str,
+    acknowledgements: bool,
+    out: SourceSender,
+    protocol: &'static str,
+    handle: fn(R) -> (Vec<Event>, Vec<u8>),
+) -> BoxedFilter<(Response,)>
+where
+    R: Message + Default + 'static,
+{
+    warp::post()
+        .and(warp::path("v1"))
+        .and(warp::path(output))
+        .and(warp::path::end())
+        .and(warp::header::exact_ignore_case(
+            "content-type",
+            "application/x-protobuf",
+        ))
+        .and(warp::header::optional::<String>("content-encoding"))
+        .and(warp::body::bytes())
+        .and_then(move |encoding_header: Option<String>, body: Bytes| {
+            let http_path = format!("/v1/{}", output);
+            let request = decode(&encoding_header, body).and_then(|body| {
+                emit!(HttpBytesReceived {
+                    byte_size: body.len(),
+                    http_path: &http_path,
+                    protocol,
+                });
+                R::decode(body).map_err(|error| {
+                    ErrorMessage::new(
+                        StatusCode::BAD_REQUEST,
+                        format!("Could not decode request: {}", error),
+                    )
+                })
+            });
+            handle_request(
+                request.map(handle),
+                acknowledgements,
+                out.clone(),
+                output,
+                http_path,
+                protocol,
+            )
+        })
+        .boxed()
+}
+
+async fn handle_request(
+    request: Result<(Vec<Event>, Vec<u8>), ErrorMessage>,
+    acknowledgements: bool,
+    mut out: SourceSender,
+    output: &'static str,
+    http_path: String,
+    protocol: &'static str,
+) -> Result<Response, Rejection> {
+    let (mut events, response) = request.map_err(warp::reject::custom)?;
+
+    let count = events.len();
+    emit!(HttpEventsReceived {
+        count,
+        byte_size: events.size_of(),
+        http_path: &http_path,
+        protocol,
+    });
+
+    let receiver = BatchNotifier::maybe_apply_to(acknowledgements, &mut events);
+
+    out.send_batch_named(output, events)
+        .await
+        .map_err(|error| {
+            emit!(StreamClosedError { error, count });
+            warp::reject::custom(ErrorMessage::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Source is shutting down".into(),
+            ))
+        })?;
+
+    let status = match receiver {
+        Some(receiver) => receiver.await,
+        None => BatchStatus::Delivered,
+    };
+    match status {
+        BatchStatus::Delivered => {
+            Ok(
+                warp::reply::with_header(response, "content-type", "application/x-protobuf")
+                    .into_response(),
+            )
+        }
+        BatchStatus::Errored => Err(warp::reject::custom(ErrorMessage::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Error delivering contents to sink".into(),
+        ))),
+        BatchStatus::Rejected => Err(warp::reject::custom(ErrorMessage::new(
+            StatusCode::BAD_REQUEST,
+            "Contents failed to deliver to sink".into(),
+        ))),
+    }
+}
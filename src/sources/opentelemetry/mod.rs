@@ -1,11 +1,12 @@
 #[cfg(test)]
 mod tests;
 
-mod log;
+mod grpc;
+mod http;
 
 use std::net::SocketAddr;
 
-use futures::TryFutureExt;
+use futures::{future::join, FutureExt, TryFutureExt};
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 
@@ -14,21 +15,46 @@ use crate::{
         AcknowledgementsConfig, DataType, GenerateConfig, Output, Resource, SourceConfig,
         SourceContext, SourceDescription,
     },
-    opentelemetry::LogService::logs_service_server::LogsServiceServer,
+    opentelemetry::{
+        LogService::logs_service_server::LogsServiceServer,
+        MetricsService::metrics_service_server::MetricsServiceServer,
+        TraceService::trace_service_server::TraceServiceServer,
+    },
     serde::bool_or_struct,
-    sources::{util::grpc::run_grpc_server, Source},
+    sources::{util::grpc::run_grpc_server_with_routes, Source},
     tls::{MaybeTlsSettings, TlsEnableableConfig},
 };
 
-use self::log::Service;
+use self::{
+    grpc::Service,
+    http::{build_warp_filter, run_http_server},
+};
 
 pub const LOGS: &str = "logs";
+pub const METRICS: &str = "metrics";
+pub const TRACES: &str = "traces";
 
 /// Configuration for the `opentelemetry` source.
 #[configurable_component(source)]
 #[derive(Clone, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct OpentelemetryConfig {
+    #[configurable(derived)]
+    grpc: GrpcConfig,
+
+    #[configurable(derived)]
+    http: HttpConfig,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+/// Configuration for the `opentelemetry` gRPC server.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+struct GrpcConfig {
     /// The address to listen for connections on.
     ///
     /// It _must_ include a port.
@@ -37,17 +63,34 @@ pub struct OpentelemetryConfig {
     #[configurable(derived)]
     #[serde(default)]
     tls: Option<TlsEnableableConfig>,
+}
+
+/// Configuration for the `opentelemetry` HTTP server.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+struct HttpConfig {
+    /// The address to listen for connections on.
+    ///
+    /// It _must_ include a port.
+    address: SocketAddr,
 
     #[configurable(derived)]
-    #[serde(default, deserialize_with = "bool_or_struct")]
-    acknowledgements: AcknowledgementsConfig,
+    #[serde(default)]
+    tls: Option<TlsEnableableConfig>,
 }
 
 impl GenerateConfig for OpentelemetryConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
-            address: "0.0.0.0:4317".parse().unwrap(),
-            tls: Default::default(),
+            grpc: GrpcConfig {
+                address: "0.0.0.0:4317".parse().unwrap(),
+                tls: Default::default(),
+            },
+            http: HttpConfig {
+                address: "0.0.0.0:4318".parse().unwrap(),
+                tls: Default::default(),
+            },
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -62,23 +105,52 @@ inventory::submit! {
 #[typetag::serde(name = "opentelemetry")]
 impl SourceConfig for OpentelemetryConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<Source> {
-        let tls_settings = MaybeTlsSettings::from_config(&self.tls, true)?;
         let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
-        let service = LogsServiceServer::new(Service {
-            pipeline: cx.out,
-            acknowledgements,
-        })
-        .accept_gzip();
-        let source =
-            run_grpc_server(self.address, tls_settings, service, cx.shutdown).map_err(|error| {
-                error!(message = "Source future failed.", %error);
-            });
 
-        Ok(Box::pin(source))
+        let grpc_tls_settings = MaybeTlsSettings::from_config(&self.grpc.tls, true)?;
+        let service = Service {
+            pipeline: cx.out.clone(),
+            acknowledgements,
+        };
+        let grpc_source = run_grpc_server_with_routes(
+            self.grpc.address,
+            grpc_tls_settings,
+            move |server| {
+                server
+                    .add_service(LogsServiceServer::new(service.clone()).accept_gzip())
+                    .add_service(MetricsServiceServer::new(service.clone()).accept_gzip())
+                    .add_service(TraceServiceServer::new(service).accept_gzip())
+            },
+            cx.shutdown.clone(),
+        )
+        .map_err(|error| {
+            error!(message = "Source future failed.", %error);
+        });
+
+        let http_tls_settings = MaybeTlsSettings::from_config(&self.http.tls, true)?;
+        let protocol = http_tls_settings.http_protocol_name();
+        let filters = build_warp_filter(acknowledgements, cx.out, protocol);
+        let http_source =
+            run_http_server(self.http.address, http_tls_settings, filters, cx.shutdown).map_err(
+                |error| {
+                    error!(message = "Source future failed.", %error);
+                },
+            );
+
+        Ok(join(grpc_source, http_source)
+            .map(|result| match result {
+                (Ok(()), Ok(())) => Ok(()),
+                _ => Err(()),
+            })
+            .boxed())
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
-        vec![Output::default(DataType::Log).with_port(LOGS)]
+        vec![
+            Output::default(DataType::Log).with_port(LOGS),
+            Output::default(DataType::Metric).with_port(METRICS),
+            Output::default(DataType::Trace).with_port(TRACES),
+        ]
     }
 
     fn source_type(&self) -> &'static str {
@@ -86,7 +158,10 @@ impl SourceConfig for OpentelemetryConfig {
     }
 
     fn resources(&self) -> Vec<Resource> {
-        vec![Resource::tcp(self.address)]
+        vec![
+            Resource::tcp(self.grpc.address),
+            Resource::tcp(self.http.address),
+        ]
     }
 
     fn can_acknowledge(&self) -> bool {
@@ -1,13 +1,26 @@
 use crate::{
     config::{SourceConfig, SourceContext},
-    event::{into_event_stream, Event, EventStatus, LogEvent, Value},
+    event::{
+        into_event_stream,
+        metric::{MetricKind, MetricValue},
+        Event, EventStatus, LogEvent, Value,
+    },
     opentelemetry::{
-        Common::{any_value, AnyValue, KeyValue},
+        Common::{any_value, AnyValue, InstrumentationScope, KeyValue},
         LogService::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
         Logs::{LogRecord, ResourceLogs, ScopeLogs},
+        Metrics::{
+            metric::Data, number_data_point, AggregationTemporality, Metric as OtelMetric,
+            NumberDataPoint, ResourceMetrics, ScopeMetrics, Sum,
+        },
+        MetricsService::{
+            metrics_service_client::MetricsServiceClient, ExportMetricsServiceRequest,
+        },
         Resource as OtelResource,
+        Trace::{span::SpanKind, ResourceSpans, ScopeSpans, Span},
+        TraceService::{trace_service_client::TraceServiceClient, ExportTraceServiceRequest},
     },
-    sources::opentelemetry::{OpentelemetryConfig, LOGS},
+    sources::opentelemetry::{OpentelemetryConfig, LOGS, METRICS, TRACES},
     test_util::{
         self,
         components::{assert_source_compliance, SOURCE_TAGS},
@@ -17,7 +30,8 @@ use crate::{
 use chrono::{TimeZone, Utc};
 use futures::Stream;
 use futures_util::StreamExt;
-use std::collections::BTreeMap;
+use prost::Message;
+use std::{collections::BTreeMap, net::SocketAddr};
 use tonic::Request;
 
 #[test]
@@ -28,19 +42,10 @@ fn generate_config() {
 #[tokio::test]
 async fn receive_message() {
     assert_source_compliance(&SOURCE_TAGS, async {
-        let addr = test_util::next_addr();
-        let config = format!(r#"address = "{}""#, addr);
-        let source: OpentelemetryConfig = toml::from_str(&config).unwrap();
-        let (sender, logs_output, _) = new_source(EventStatus::Delivered);
-        let server = source
-            .build(SourceContext::new_test(sender, None))
-            .await
-            .unwrap();
-        tokio::spawn(server);
-        test_util::wait_for_tcp(addr).await;
+        let (grpc_addr, _, outputs) = start_source().await;
 
         // send request via grpc client
-        let mut client = LogsServiceClient::connect(format!("http://{}", addr))
+        let mut client = LogsServiceClient::connect(format!("http://{}", grpc_addr))
             .await
             .unwrap();
         let req = Request::new(ExportLogsServiceRequest {
@@ -82,7 +87,7 @@ async fn receive_message() {
             }],
         });
         let _ = client.export(req).await;
-        let mut output = test_util::collect_ready(logs_output).await;
+        let mut output = test_util::collect_ready(outputs.logs).await;
         // we just send one, so only one output
         assert_eq!(output.len(), 1);
         let actual_event = output.pop().unwrap();
@@ -111,18 +116,260 @@ async fn receive_message() {
     .await;
 }
 
+#[tokio::test]
+async fn receive_metrics() {
+    assert_source_compliance(&SOURCE_TAGS, async {
+        let (grpc_addr, _, outputs) = start_source().await;
+
+        let mut client = MetricsServiceClient::connect(format!("http://{}", grpc_addr))
+            .await
+            .unwrap();
+        let req = Request::new(ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                resource: Some(OtelResource {
+                    attributes: vec![string_attribute("res_key", "res_val")],
+                    dropped_attributes_count: 0,
+                }),
+                scope_metrics: vec![ScopeMetrics {
+                    scope: Some(InstrumentationScope {
+                        name: "scope_name".into(),
+                        version: "1.0".into(),
+                        attributes: vec![],
+                        dropped_attributes_count: 0,
+                    }),
+                    metrics: vec![OtelMetric {
+                        name: "requests".into(),
+                        description: String::new(),
+                        unit: String::new(),
+                        data: Some(Data::Sum(Sum {
+                            data_points: vec![NumberDataPoint {
+                                attributes: vec![string_attribute("attr_key", "attr_val")],
+                                start_time_unix_nano: 0,
+                                time_unix_nano: 1,
+                                value: Some(number_data_point::Value::AsInt(3)),
+                                exemplars: vec![],
+                                flags: 0,
+                            }],
+                            aggregation_temporality: AggregationTemporality::Delta as i32,
+                            is_monotonic: true,
+                        })),
+                    }],
+                    schema_url: "v1".into(),
+                }],
+                schema_url: "v1".into(),
+            }],
+        });
+        let _ = client.export(req).await;
+        let mut output = test_util::collect_ready(outputs.metrics).await;
+        assert_eq!(output.len(), 1);
+        let metric = output.pop().unwrap().into_metric();
+        assert_eq!(metric.name(), "requests");
+        assert_eq!(metric.kind(), MetricKind::Incremental);
+        assert_eq!(metric.value(), &MetricValue::Counter { value: 3.0 });
+        assert_eq!(metric.timestamp(), Some(Utc.timestamp_nanos(1)));
+        let tags = metric.tags().unwrap();
+        assert_eq!(tags["resources.res_key"], "res_val");
+        assert_eq!(tags["scope.name"], "scope_name");
+        assert_eq!(tags["scope.version"], "1.0");
+        assert_eq!(tags["attr_key"], "attr_val");
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn receive_traces() {
+    assert_source_compliance(&SOURCE_TAGS, async {
+        let (grpc_addr, _, outputs) = start_source().await;
+
+        let mut client = TraceServiceClient::connect(format!("http://{}", grpc_addr))
+            .await
+            .unwrap();
+        let req = Request::new(ExportTraceServiceRequest {
+            resource_spans: vec![traces_request()],
+        });
+        let _ = client.export(req).await;
+        let mut output = test_util::collect_ready(outputs.traces).await;
+        assert_eq!(output.len(), 1);
+        let trace = output.pop().unwrap().into_trace();
+        assert_eq!(
+            trace.get("trace_id"),
+            Some(&Value::from("4ac52aadf321c2e531db005df08792f5"))
+        );
+        assert_eq!(trace.get("span_id"), Some(&Value::from("0b9e4bda2a55530d")));
+        assert_eq!(trace.get("name"), Some(&Value::from("span")));
+        assert_eq!(
+            trace.get("kind"),
+            Some(&Value::from(SpanKind::Server as i32))
+        );
+        assert_eq!(
+            trace.get("resources.res_key"),
+            Some(&Value::from("res_val"))
+        );
+        assert_eq!(trace.get("scope.name"), Some(&Value::from("scope_name")));
+        assert_eq!(
+            trace.get("start_timestamp"),
+            Some(&Value::from(Utc.timestamp_nanos(1)))
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn receive_http_traces() {
+    assert_source_compliance(&SOURCE_TAGS, async {
+        let (_, http_addr, outputs) = start_source().await;
+
+        let body = ExportTraceServiceRequest {
+            resource_spans: vec![traces_request()],
+        }
+        .encode_to_vec();
+        let response = reqwest::Client::new()
+            .post(format!("http://{}/v1/traces", http_addr))
+            .header("content-type", "application/x-protobuf")
+            .body(body)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.headers()["content-type"], "application/x-protobuf");
+
+        let mut output = test_util::collect_ready(outputs.traces).await;
+        assert_eq!(output.len(), 1);
+        let trace = output.pop().unwrap().into_trace();
+        assert_eq!(trace.get("name"), Some(&Value::from("span")));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn rejects_invalid_http_requests() {
+    let (_, http_addr, _) = start_source().await;
+
+    let response = reqwest::Client::new()
+        .post(format!("http://{}/v1/logs", http_addr))
+        .header("content-type", "application/x-protobuf")
+        .body("not protobuf")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    let response = reqwest::Client::new()
+        .post(format!("http://{}/v1/logs", http_addr))
+        .header("content-type", "application/json")
+        .body("{}")
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_client_error());
+}
+
+fn traces_request() -> ResourceSpans {
+    ResourceSpans {
+        resource: Some(OtelResource {
+            attributes: vec![string_attribute("res_key", "res_val")],
+            dropped_attributes_count: 0,
+        }),
+        scope_spans: vec![ScopeSpans {
+            scope: Some(InstrumentationScope {
+                name: "scope_name".into(),
+                version: String::new(),
+                attributes: vec![],
+                dropped_attributes_count: 0,
+            }),
+            spans: vec![Span {
+                trace_id: str_into_hex_bytes("4ac52aadf321c2e531db005df08792f5"),
+                span_id: str_into_hex_bytes("0b9e4bda2a55530d"),
+                trace_state: String::new(),
+                parent_span_id: vec![],
+                name: "span".into(),
+                kind: SpanKind::Server as i32,
+                start_time_unix_nano: 1,
+                end_time_unix_nano: 2,
+                attributes: vec![],
+                dropped_attributes_count: 0,
+                events: vec![],
+                dropped_events_count: 0,
+                links: vec![],
+                dropped_links_count: 0,
+                status: None,
+            }],
+            schema_url: "v1".into(),
+        }],
+        schema_url: "v1".into(),
+    }
+}
+
+fn string_attribute(key: &str, value: &str) -> KeyValue {
+    KeyValue {
+        key: key.into(),
+        value: Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value.into())),
+        }),
+    }
+}
+
+struct Outputs<L, M, T> {
+    logs: L,
+    metrics: M,
+    traces: T,
+}
+
+async fn start_source() -> (
+    SocketAddr,
+    SocketAddr,
+    Outputs<impl Stream<Item = Event>, impl Stream<Item = Event>, impl Stream<Item = Event>>,
+) {
+    let grpc_addr = test_util::next_addr();
+    let http_addr = test_util::next_addr();
+    let config = format!(
+        r#"
+            [grpc]
+            address = "{}"
+
+            [http]
+            address = "{}"
+        "#,
+        grpc_addr, http_addr
+    );
+    let source: OpentelemetryConfig = toml::from_str(&config).unwrap();
+    let (sender, outputs, _) = new_source(EventStatus::Delivered);
+    let server = source
+        .build(SourceContext::new_test(sender, None))
+        .await
+        .unwrap();
+    tokio::spawn(server);
+    test_util::wait_for_tcp(grpc_addr).await;
+    test_util::wait_for_tcp(http_addr).await;
+    (grpc_addr, http_addr, outputs)
+}
+
 fn new_source(
     status: EventStatus,
 ) -> (
     SourceSender,
-    impl Stream<Item = Event>,
+    Outputs<impl Stream<Item = Event>, impl Stream<Item = Event>, impl Stream<Item = Event>>,
     impl Stream<Item = Event>,
 ) {
     let (mut sender, recv) = SourceSender::new_test_finalize(status);
-    let logs_output = sender
+    let logs = sender
         .add_outputs(status, LOGS.to_string())
         .flat_map(into_event_stream);
-    (sender, logs_output, recv)
+    let metrics = sender
+        .add_outputs(status, METRICS.to_string())
+        .flat_map(into_event_stream);
+    let traces = sender
+        .add_outputs(status, TRACES.to_string())
+        .flat_map(into_event_stream);
+    (
+        sender,
+        Outputs {
+            logs,
+            metrics,
+            traces,
+        },
+        recv,
+    )
 }
 
 fn str_into_hex_bytes(s: &str) -> Vec<u8> {
@@ -749,6 +749,7 @@ mod test {
                 buckets: vec![1.0, 2.0, 4.0],
                 quantiles: vec![],
                 distributions_as_summaries: false,
+                sketches_as_histograms: false,
                 flush_period_secs: Duration::from_secs(1),
                 suppress_timestamp: false,
             },
@@ -1,7 +1,7 @@
 use bytes::Bytes;
 use chrono::Utc;
 use codecs::{
-    decoding::{DeserializerConfig, FramingConfig},
+    decoding::{DecodingErrorMode, DeserializerConfig, FramingConfig},
     StreamDecodingError,
 };
 use futures::StreamExt;
@@ -13,15 +13,19 @@ use vector_core::ByteSizeOf;
 
 use crate::{
     codecs::{Decoder, DecodingConfig},
-    config::{log_schema, GenerateConfig, Output, SourceConfig, SourceContext, SourceDescription},
+    config::{
+        log_schema, AcknowledgementsConfig, GenerateConfig, Output, SourceConfig, SourceContext,
+        SourceDescription,
+    },
     event::Event,
     internal_events::{BytesReceived, EventsReceived, StreamClosedError},
-    serde::{default_decoding, default_framing_message_based},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
     SourceSender,
 };
 
 mod channel;
 mod list;
+mod stream;
 
 #[derive(Debug, Snafu)]
 enum BuildError {
@@ -43,6 +47,11 @@ pub enum DataTypeConfig {
     ///
     /// This is based on Redis' Pub/Sub capabilities.
     Channel,
+
+    /// The `stream` data type.
+    ///
+    /// The entries of the stream are read as a member of a consumer group.
+    Stream,
 }
 
 /// Options for the Redis `list` data type.
@@ -54,6 +63,97 @@ pub struct ListOption {
     method: Method,
 }
 
+/// Options for the Redis `stream` data type.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct StreamOption {
+    /// The consumer group to read the entries of the stream as a member of.
+    ///
+    /// The group is created, along with the stream, if it doesn't exist.
+    #[serde(default = "default_group")]
+    #[derivative(Default(value = "default_group()"))]
+    group: String,
+
+    /// The name of the consumer in the group.
+    ///
+    /// It must be unique to the Vector instance, and stable across its restarts, for the entries
+    /// read before a restart to be read again.
+    #[serde(default = "default_consumer")]
+    #[derivative(Default(value = "default_consumer()"))]
+    consumer: String,
+
+    /// The ID of the entry after which the consumer group reads the stream when it is created.
+    ///
+    /// `$` reads the entries added once the group is created, and `0` the whole stream.
+    #[serde(default = "default_start_id")]
+    #[derivative(Default(value = "default_start_id()"))]
+    start_id: String,
+
+    /// The field of the entries holding the message to decode.
+    #[serde(default = "default_field")]
+    #[derivative(Default(value = "default_field()"))]
+    field: String,
+
+    /// Sets the name of the log field to use to add the ID of the entry to each event.
+    ///
+    /// By default, this is not set and the field will not be automatically added.
+    id_key: Option<String>,
+
+    /// The maximum number of entries read at once.
+    #[serde(default = "default_count")]
+    #[derivative(Default(value = "default_count()"))]
+    count: usize,
+
+    /// How long to wait for new entries, in milliseconds.
+    #[serde(default = "default_block_ms")]
+    #[derivative(Default(value = "default_block_ms()"))]
+    block_ms: usize,
+
+    /// How long, in milliseconds, an entry is pending before it is claimed by this consumer.
+    ///
+    /// This lets the entries of the consumers that crashed, or whose events failed to be
+    /// delivered, be read again.
+    #[serde(default = "default_min_idle_ms")]
+    #[derivative(Default(value = "default_min_idle_ms()"))]
+    min_idle_ms: u64,
+}
+
+impl StreamOption {
+    const fn min_idle(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.min_idle_ms)
+    }
+}
+
+fn default_group() -> String {
+    "vector".into()
+}
+
+fn default_consumer() -> String {
+    "vector".into()
+}
+
+fn default_start_id() -> String {
+    "$".into()
+}
+
+fn default_field() -> String {
+    "message".into()
+}
+
+const fn default_count() -> usize {
+    100
+}
+
+const fn default_block_ms() -> usize {
+    1000
+}
+
+const fn default_min_idle_ms() -> u64 {
+    60000
+}
+
 /// Method for getting events from the `list` data type.
 #[configurable_component]
 #[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
@@ -99,6 +199,9 @@ pub struct RedisSourceConfig {
     #[configurable(derived)]
     list: Option<ListOption>,
 
+    #[configurable(derived)]
+    stream: Option<StreamOption>,
+
     /// The Redis URL to connect to.
     ///
     /// The URL must take the form of `protocol://server:port/db` where the `protocol` can either be `redis` or `rediss` for connections secured via TLS.
@@ -123,6 +226,14 @@ pub struct RedisSourceConfig {
     #[serde(default = "default_decoding")]
     #[derivative(Default(value = "default_decoding()"))]
     decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    on_error: DecodingErrorMode,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
 }
 
 impl GenerateConfig for RedisSourceConfig {
@@ -160,7 +271,8 @@ impl SourceConfig for RedisSourceConfig {
             self.decoding.clone(),
             LogNamespace::Legacy,
         )
-        .build();
+        .with_error_mode(self.on_error)
+        .build()?;
 
         match self.data_type {
             DataTypeConfig::List => {
@@ -187,11 +299,27 @@ impl SourceConfig for RedisSourceConfig {
                 )
                 .await
             }
+            DataTypeConfig::Stream => {
+                let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
+                stream::consume(
+                    client,
+                    connection_info,
+                    self.key.clone(),
+                    self.redis_key.clone(),
+                    self.stream.clone().unwrap_or_default(),
+                    decoder,
+                    cx,
+                    acknowledgements,
+                )
+                .await
+            }
         }
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
-        vec![Output::default(self.decoding.output_type())]
+        vec![Output::default(
+            self.on_error.output_type(self.decoding.output_type()),
+        )]
     }
 
     fn source_type(&self) -> &'static str {
@@ -199,7 +327,7 @@ impl SourceConfig for RedisSourceConfig {
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        matches!(self.data_type, DataTypeConfig::Stream)
     }
 }
 
@@ -264,6 +392,32 @@ mod test {
     fn generate_config() {
         crate::test_util::test_generate_config::<RedisSourceConfig>();
     }
+
+    #[test]
+    fn parses_stream_options() {
+        let config: RedisSourceConfig = toml::from_str(
+            r#"
+            url = "redis://127.0.0.1:6379/0"
+            key = "vector"
+            data_type = "stream"
+            stream.group = "consumers"
+            stream.consumer = "vector-0"
+            stream.id_key = "id"
+            acknowledgements = true
+            "#,
+        )
+        .unwrap();
+
+        assert!(matches!(config.data_type, DataTypeConfig::Stream));
+        assert!(config.can_acknowledge());
+        let stream = config.stream.unwrap();
+        assert_eq!(stream.group, "consumers");
+        assert_eq!(stream.consumer, "vector-0");
+        assert_eq!(stream.start_id, "$");
+        assert_eq!(stream.field, "message");
+        assert_eq!(stream.id_key.as_deref(), Some("id"));
+        assert_eq!(stream.min_idle(), std::time::Duration::from_secs(60));
+    }
 }
 
 #[cfg(all(test, feature = "redis-integration-tests"))]
@@ -272,6 +426,8 @@ mod integration_test {
 
     use super::*;
     use crate::config::log_schema;
+    use crate::event::EventStatus;
+    use crate::shutdown::ShutdownSignal;
     use crate::test_util::components::{run_and_assert_source_compliance_n, SOURCE_TAGS};
     use crate::{
         test_util::{collect_n, random_string},
@@ -296,6 +452,7 @@ mod integration_test {
         // Now run the source and make sure we get all three events.
         let config = RedisSourceConfig {
             data_type: DataTypeConfig::List,
+            stream: None,
             list: Some(ListOption {
                 method: Method::Rpop,
             }),
@@ -304,6 +461,8 @@ mod integration_test {
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_error: Default::default(),
+            acknowledgements: Default::default(),
         };
 
         let events = run_and_assert_source_compliance_n(config, 3, &SOURCE_TAGS).await;
@@ -329,6 +488,7 @@ mod integration_test {
         // Now run the source and make sure we get all three events.
         let config = RedisSourceConfig {
             data_type: DataTypeConfig::List,
+            stream: None,
             list: Some(ListOption {
                 method: Method::Lpop,
             }),
@@ -337,6 +497,8 @@ mod integration_test {
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_error: Default::default(),
+            acknowledgements: Default::default(),
         };
 
         let events = run_and_assert_source_compliance_n(config, 3, &SOURCE_TAGS).await;
@@ -346,6 +508,62 @@ mod integration_test {
         assert_eq!(events[2].as_log()[log_schema().message_key()], "3".into());
     }
 
+    #[tokio::test]
+    async fn redis_source_stream_acknowledges_entries() {
+        let client = redis::Client::open(REDIS_SERVER).unwrap();
+        let mut conn = client.get_tokio_connection_manager().await.unwrap();
+
+        let key = format!("test-stream-{}", random_string(10));
+        debug!("Test key name: {}.", key);
+
+        for message in ["1", "2", "3"] {
+            let _: String = conn.xadd(&key, "*", &[("message", message)]).await.unwrap();
+        }
+
+        let config = RedisSourceConfig {
+            data_type: DataTypeConfig::Stream,
+            list: None,
+            stream: Some(StreamOption {
+                start_id: "0".to_owned(),
+                id_key: Some("id".to_owned()),
+                ..Default::default()
+            }),
+            url: REDIS_SERVER.to_owned(),
+            key: key.clone(),
+            redis_key: None,
+            framing: default_framing_message_based(),
+            decoding: default_decoding(),
+            on_error: Default::default(),
+            acknowledgements: true.into(),
+        };
+
+        let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let (trigger, shutdown, _) = ShutdownSignal::new_wired();
+        let context = SourceContext::new_test(tx, None);
+        let context = SourceContext {
+            shutdown,
+            ..context
+        };
+        let source = config
+            .build(context)
+            .await
+            .expect("source should not fail to build");
+        let handle = tokio::spawn(source);
+
+        let events = collect_n(rx, 3).await;
+        for (event, message) in events.iter().zip(["1", "2", "3"]) {
+            assert_eq!(event.as_log()[log_schema().message_key()], message.into());
+            assert!(event.as_log().contains("id"));
+        }
+
+        drop(trigger);
+        handle.await.unwrap().unwrap();
+
+        let pending: redis::streams::StreamPendingReply =
+            conn.xpending(&key, "vector").await.unwrap();
+        assert_eq!(pending.count(), 0);
+    }
+
     #[tokio::test]
     async fn redis_source_channel_consume_event() {
         let key = format!("test-channel-{}", random_string(10));
@@ -355,11 +573,14 @@ mod integration_test {
         let config = RedisSourceConfig {
             data_type: DataTypeConfig::Channel,
             list: None,
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_error: Default::default(),
+            acknowledgements: Default::default(),
         };
 
         let (tx, rx) = SourceSender::new_test();
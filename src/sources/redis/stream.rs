@@ -0,0 +1,285 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use futures::StreamExt;
+use redis::{
+    aio::ConnectionManager,
+    streams::{StreamId, StreamRangeReply, StreamReadOptions, StreamReadReply},
+    AsyncCommands, FromRedisValue, RedisResult, Value,
+};
+use snafu::{ResultExt, Snafu};
+use tokio::time::Instant;
+use vector_common::finalizer::UnorderedFinalizer;
+
+use super::StreamOption;
+use crate::{
+    codecs,
+    config::SourceContext,
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{
+        BytesReceived, RedisAcknowledgementError, RedisReceiveEventError, StreamClosedError,
+    },
+    sources::{redis::ConnectionInfo, util, Source},
+    SourceSender,
+};
+
+type Finalizer = UnorderedFinalizer<String>;
+
+/// How long to wait before reading the stream again after an error.
+const READ_ERROR_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Failed to create connection: {}", source))]
+    Connection { source: redis::RedisError },
+    #[snafu(display("Failed to create consumer group: {}", source))]
+    CreateGroup { source: redis::RedisError },
+}
+
+pub async fn consume(
+    client: redis::Client,
+    connection_info: ConnectionInfo,
+    key: String,
+    redis_key: Option<String>,
+    options: StreamOption,
+    decoder: codecs::Decoder,
+    cx: SourceContext,
+    acknowledgements: bool,
+) -> crate::Result<Source> {
+    let mut conn = client
+        .get_tokio_connection_manager()
+        .await
+        .context(ConnectionSnafu {})?;
+    // The entries are acknowledged on their own connection, as the reads block theirs.
+    let ack_conn = client
+        .get_tokio_connection_manager()
+        .await
+        .context(ConnectionSnafu {})?;
+
+    let created: RedisResult<()> = conn
+        .xgroup_create_mkstream(&key, &options.group, &options.start_id)
+        .await;
+    match created {
+        Err(error) if error.code() != Some("BUSYGROUP") => {
+            return Err(BuildError::CreateGroup { source: error }.into())
+        }
+        _ => {}
+    }
+
+    Ok(Box::pin(async move {
+        let mut shutdown = cx.shutdown.clone();
+        let (finalizer, ack_stream) = Finalizer::maybe_new(acknowledgements, cx.shutdown);
+        let acker = Acker {
+            conn: ack_conn,
+            key: key.clone(),
+            group: options.group.clone(),
+        };
+        let ack_task = tokio::spawn(acker.clone().run(ack_stream));
+
+        let mut consumer = StreamConsumer {
+            conn,
+            acker,
+            connection_info,
+            key,
+            redis_key,
+            options,
+            decoder,
+            out: cx.out,
+            finalizer,
+        };
+
+        // The entries delivered to this consumer before it was restarted are read first.
+        let mut read_id = "0".to_owned();
+        let mut claim_cursor = "0-0".to_owned();
+        let mut last_claim = Instant::now();
+        loop {
+            if last_claim.elapsed() >= consumer.options.min_idle() {
+                last_claim = Instant::now();
+                match consumer.claim(&mut claim_cursor).await {
+                    Ok(entries) => consumer.handle_entries(entries).await?,
+                    Err(error) => emit!(RedisReceiveEventError::from(error)),
+                }
+            }
+
+            let result = tokio::select! {
+                _ = &mut shutdown => break,
+                result = consumer.read(&read_id) => result,
+            };
+            match result {
+                Ok(entries) => {
+                    // Once the pending entries are read, only the new ones are.
+                    if read_id != ">" {
+                        read_id = match entries.last() {
+                            Some(entry) => entry.id.clone(),
+                            None => ">".to_owned(),
+                        };
+                    }
+                    consumer.handle_entries(entries).await?;
+                }
+                Err(error) => {
+                    emit!(RedisReceiveEventError::from(error));
+                    tokio::time::sleep(READ_ERROR_DELAY).await;
+                }
+            }
+        }
+
+        // Wait for the entries in flight to be acknowledged so they are not claimed again.
+        drop(consumer);
+        let _ = ack_task.await;
+        Ok(())
+    }))
+}
+
+struct StreamConsumer {
+    conn: ConnectionManager,
+    acker: Acker,
+    connection_info: ConnectionInfo,
+    key: String,
+    redis_key: Option<String>,
+    options: StreamOption,
+    decoder: codecs::Decoder,
+    out: SourceSender,
+    finalizer: Option<Finalizer>,
+}
+
+impl StreamConsumer {
+    /// Reads the entries of the stream after the given ID, or the new ones for `>`.
+    async fn read(&mut self, id: &str) -> RedisResult<Vec<StreamId>> {
+        let options = StreamReadOptions::default()
+            .group(&self.options.group, &self.options.consumer)
+            .count(self.options.count)
+            .block(self.options.block_ms);
+        let reply: Option<StreamReadReply> = self
+            .conn
+            .xread_options(&[&self.key], &[id], &options)
+            .await?;
+        Ok(reply
+            .into_iter()
+            .flat_map(|reply| reply.keys)
+            .flat_map(|key| key.ids)
+            .collect())
+    }
+
+    /// Claims the entries pending for longer than the minimum idle time, such as the ones of the
+    /// consumers that crashed, or whose events failed to be delivered.
+    async fn claim(&mut self, cursor: &mut String) -> RedisResult<Vec<StreamId>> {
+        let reply: Value = redis::cmd("XAUTOCLAIM")
+            .arg(&self.key)
+            .arg(&self.options.group)
+            .arg(&self.options.consumer)
+            .arg(self.options.min_idle_ms)
+            .arg(cursor.as_str())
+            .arg("COUNT")
+            .arg(self.options.count)
+            .query_async(&mut self.conn)
+            .await?;
+
+        // The reply holds the cursor of the next call and the claimed entries, followed by the
+        // IDs of the deleted entries since Redis 7.
+        let items = match reply {
+            Value::Bulk(items) if items.len() >= 2 => items,
+            _ => return Err((redis::ErrorKind::TypeError, "Unexpected XAUTOCLAIM reply").into()),
+        };
+        *cursor = String::from_redis_value(&items[0])?;
+        let entries = StreamRangeReply::from_redis_value(&items[1])?;
+        Ok(entries.ids)
+    }
+
+    async fn handle_entries(&mut self, entries: Vec<StreamId>) -> Result<(), ()> {
+        for entry in entries {
+            self.handle_entry(entry).await?;
+        }
+        Ok(())
+    }
+
+    async fn handle_entry(&mut self, entry: StreamId) -> Result<(), ()> {
+        // Entries without the field are acknowledged without producing events.
+        let data = entry
+            .map
+            .get(&self.options.field)
+            .and_then(|value| Vec::<u8>::from_redis_value(value).ok())
+            .unwrap_or_default();
+        emit!(BytesReceived {
+            byte_size: data.len(),
+            protocol: self.connection_info.protocol,
+        });
+
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.finalizer.is_some());
+        let events = util::decode_message(
+            self.decoder.clone(),
+            "redis",
+            &data,
+            Some(Utc::now()),
+            &batch,
+        )
+        .map(|mut event| {
+            if let Event::Log(ref mut log) = event {
+                if let Some(redis_key) = &self.redis_key {
+                    log.insert(redis_key.as_str(), self.key.clone());
+                }
+                if let Some(id_key) = &self.options.id_key {
+                    log.insert(id_key.as_str(), entry.id.clone());
+                }
+            }
+            event
+        })
+        .collect::<Vec<_>>();
+        drop(batch);
+
+        let count = events.len();
+        match self.out.send_batch(events).await {
+            Err(error) => {
+                emit!(StreamClosedError { error, count });
+                Err(())
+            }
+            Ok(()) => {
+                match receiver {
+                    Some(receiver) => self
+                        .finalizer
+                        .as_ref()
+                        .expect("Finalizer must have been set up for acknowledgements")
+                        .add(entry.id, receiver),
+                    None => {
+                        self.acker
+                            .acknowledge(BatchStatus::Delivered, &entry.id)
+                            .await
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Acknowledges the entries of the consumer group.
+#[derive(Clone)]
+struct Acker {
+    conn: ConnectionManager,
+    key: String,
+    group: String,
+}
+
+impl Acker {
+    async fn run(
+        mut self,
+        mut ack_stream: impl futures::Stream<Item = (BatchStatus, String)> + Unpin,
+    ) {
+        while let Some((status, id)) = ack_stream.next().await {
+            self.acknowledge(status, &id).await;
+        }
+    }
+
+    /// Acknowledges the entry according to the status of its events.
+    ///
+    /// The entries whose events failed to be delivered are left pending, to be claimed again once
+    /// they are idle for long enough, while the rejected ones are acknowledged.
+    async fn acknowledge(&mut self, status: BatchStatus, id: &str) {
+        if status == BatchStatus::Errored {
+            return;
+        }
+        let result: RedisResult<i64> = self.conn.xack(&self.key, &self.group, &[id]).await;
+        if let Err(error) = result {
+            emit!(RedisAcknowledgementError::from(error));
+        }
+    }
+}
@@ -16,7 +16,7 @@ use crate::{
         SourceDescription,
     },
     sources::util::TcpSource,
-    tls::MaybeTlsSettings,
+    tls::{MaybeTlsSettings, TlsClientAuthorization},
 };
 
 /// Configuration for the `socket` source.
@@ -59,12 +59,18 @@ impl SocketConfig {
 
     fn output_type(&self) -> DataType {
         match &self.mode {
-            Mode::Tcp(config) => config.decoding().output_type(),
-            Mode::Udp(config) => config.decoding().output_type(),
+            Mode::Tcp(config) => config
+                .on_error()
+                .output_type(config.decoding().output_type()),
+            Mode::Udp(config) => config
+                .on_error()
+                .output_type(config.decoding().output_type()),
             #[cfg(unix)]
-            Mode::UnixDatagram(config) => config.decoding.output_type(),
+            Mode::UnixDatagram(config) => {
+                config.on_error.output_type(config.decoding.output_type())
+            }
             #[cfg(unix)]
-            Mode::UnixStream(config) => config.decoding.output_type(),
+            Mode::UnixStream(config) => config.on_error.output_type(config.decoding.output_type()),
         }
     }
 }
@@ -127,7 +133,9 @@ impl SourceConfig for SocketConfig {
                     }
                 };
 
-                let decoder = DecodingConfig::new(framing, decoding, LogNamespace::Legacy).build();
+                let decoder = DecodingConfig::new(framing, decoding, LogNamespace::Legacy)
+                    .with_error_mode(config.on_error())
+                    .build()?;
 
                 let tcp = tcp::RawTcpSource::new(config.clone(), decoder);
                 let tls_config = config.tls().as_ref().map(|tls| tls.tls_config.clone());
@@ -135,6 +143,7 @@ impl SourceConfig for SocketConfig {
                     .tls()
                     .as_ref()
                     .and_then(|tls| tls.client_metadata_key.clone());
+                let tls_client_authorization = TlsClientAuthorization::from_config(config.tls())?;
                 let tls = MaybeTlsSettings::from_config(&tls_config, true)?;
                 tcp.run(
                     config.address(),
@@ -142,10 +151,12 @@ impl SourceConfig for SocketConfig {
                     config.shutdown_timeout_secs(),
                     tls,
                     tls_client_metadata_key,
+                    tls_client_authorization,
                     config.receive_buffer_bytes(),
                     cx,
                     false.into(),
                     config.connection_limit,
+                    config.proxy_protocol(),
                 )
             }
             Mode::Udp(config) => {
@@ -158,7 +169,8 @@ impl SourceConfig for SocketConfig {
                     config.decoding().clone(),
                     LogNamespace::Legacy,
                 )
-                .build();
+                .with_error_mode(config.on_error())
+                .build()?;
                 Ok(udp::udp(config, host_key, decoder, cx.shutdown, cx.out))
             }
             #[cfg(unix)]
@@ -171,7 +183,8 @@ impl SourceConfig for SocketConfig {
                     config.decoding.clone(),
                     LogNamespace::Legacy,
                 )
-                .build();
+                .with_error_mode(config.on_error)
+                .build()?;
                 unix::unix_datagram(
                     config.path,
                     config.socket_file_mode,
@@ -207,7 +220,9 @@ impl SourceConfig for SocketConfig {
                     }
                 };
 
-                let decoder = DecodingConfig::new(framing, decoding, LogNamespace::Legacy).build();
+                let decoder = DecodingConfig::new(framing, decoding, LogNamespace::Legacy)
+                    .with_error_mode(config.on_error)
+                    .build()?;
 
                 let host_key = config
                     .host_key
@@ -261,9 +276,12 @@ mod test {
     };
 
     use bytes::{BufMut, Bytes, BytesMut};
-    use codecs::NewlineDelimitedDecoderConfig;
     #[cfg(unix)]
     use codecs::{decoding::CharacterDelimitedDecoderOptions, CharacterDelimitedDecoderConfig};
+    use codecs::{
+        decoding::{DecodingErrorMode, DeserializerConfig},
+        NewlineDelimitedDecoderConfig,
+    };
     use futures::{stream, StreamExt};
     use tokio::{
         task::JoinHandle,
@@ -298,7 +316,7 @@ mod test {
             components::{assert_source_compliance, SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS},
             next_addr, random_string, send_lines, send_lines_tls, wait_for_tcp,
         },
-        tls::{self, TlsConfig, TlsEnableableConfig, TlsSourceConfig},
+        tls::{self, TlsClientAuthorization, TlsConfig, TlsEnableableConfig, TlsSourceConfig},
         SourceSender,
     };
 
@@ -335,6 +353,39 @@ mod test {
         .await;
     }
 
+    #[tokio::test]
+    async fn tcp_it_includes_proxied_host() {
+        let (tx, mut rx) = SourceSender::new_test();
+        let addr = next_addr();
+
+        let mut config = TcpConfig::from_address(addr.into());
+        config.set_proxy_protocol(true);
+
+        let server = SocketConfig::from(config)
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+
+        wait_for_tcp(addr).await;
+        // Each line is terminated by a newline, completing the CRLF ending the header.
+        send_lines(
+            addr,
+            vec![
+                "PROXY TCP4 192.0.2.1 192.0.2.2 56324 443\r".to_owned(),
+                "test".to_owned(),
+            ]
+            .into_iter(),
+        )
+        .await
+        .unwrap();
+
+        let event = rx.next().await.unwrap();
+        assert_eq!(event.as_log()[log_schema().message_key()], "test".into());
+        assert_eq!(event.as_log()[log_schema().host_key()], "192.0.2.1".into());
+        assert_eq!(event.as_log()["port"], 56324.into());
+    }
+
     #[tokio::test]
     async fn tcp_splits_on_newline() {
         let (tx, rx) = SourceSender::new_test();
@@ -358,6 +409,77 @@ mod test {
         assert_eq!(events[1].as_log()[log_schema().message_key()], "bar".into());
     }
 
+    /// Sends a JSON line, a malformed line and another JSON line to a TCP socket source decoding
+    /// JSON, and collects the given number of events.
+    async fn decode_malformed_json_lines(
+        on_error: Option<DecodingErrorMode>,
+        n: usize,
+    ) -> Vec<Event> {
+        let (tx, rx) = SourceSender::new_test();
+        let addr = next_addr();
+
+        let mut config = TcpConfig::from_address(addr.into());
+        config.set_decoding(DeserializerConfig::Json);
+        if let Some(on_error) = on_error {
+            config.set_on_error(on_error);
+        }
+
+        let server = SocketConfig::from(config)
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+
+        wait_for_tcp(addr).await;
+        send_lines(
+            addr,
+            vec![
+                r#"{"foo":"bar"}"#.to_owned(),
+                "{".to_owned(),
+                r#"{"foo":"baz"}"#.to_owned(),
+            ]
+            .into_iter(),
+        )
+        .await
+        .unwrap();
+
+        collect_n(rx, n).await
+    }
+
+    #[tokio::test]
+    async fn tcp_drops_decoding_errors_by_default() {
+        let events = decode_malformed_json_lines(None, 2).await;
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].as_log()["foo"], "bar".into());
+        assert_eq!(events[1].as_log()["foo"], "baz".into());
+    }
+
+    #[tokio::test]
+    async fn tcp_emits_raw_decoding_errors() {
+        let events = decode_malformed_json_lines(Some(DecodingErrorMode::Raw), 3).await;
+
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].as_log()["foo"], "bar".into());
+        assert_eq!(events[1].as_log()[log_schema().message_key()], "{".into());
+        assert!(!events[1].as_log().contains("decoding_error"));
+        assert_eq!(events[2].as_log()["foo"], "baz".into());
+    }
+
+    #[tokio::test]
+    async fn tcp_routes_decoding_errors() {
+        let events = decode_malformed_json_lines(Some(DecodingErrorMode::Route), 3).await;
+
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].as_log()["foo"], "bar".into());
+        assert!(!events[0].as_log().contains("decoding_error"));
+        assert_eq!(events[1].as_log()[log_schema().message_key()], "{".into());
+        let error = events[1].as_log()["decoding_error"].to_string_lossy();
+        assert!(error.starts_with("Error parsing JSON: "), "{}", error);
+        assert!(error.contains("EOF while parsing an object"), "{}", error);
+        assert_eq!(events[2].as_log()["foo"], "baz".into());
+    }
+
     #[tokio::test]
     async fn tcp_it_includes_source_type() {
         assert_source_compliance(&SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS, async {
@@ -442,6 +564,7 @@ mod test {
                     },
                 },
                 client_metadata_key: Some("tls_peer".into()),
+                ..Default::default()
             }));
 
             let server = SocketConfig::from(config)
@@ -487,6 +610,80 @@ mod test {
         .await;
     }
 
+    async fn tcp_with_tls_client_allowlist(allowed_subject: &str) -> Option<Event> {
+        let (tx, mut rx) = SourceSender::new_test();
+        let addr = next_addr();
+
+        let mut config = TcpConfig::from_address(addr.into());
+        config.set_tls(Some(TlsSourceConfig {
+            tls_config: TlsEnableableConfig {
+                enabled: Some(true),
+                options: TlsConfig {
+                    verify_certificate: Some(true),
+                    crt_file: Some(tls::TEST_PEM_CRT_PATH.into()),
+                    key_file: Some(tls::TEST_PEM_KEY_PATH.into()),
+                    ca_file: Some(tls::TEST_PEM_CA_PATH.into()),
+                    ..Default::default()
+                },
+            },
+            client_allowed_subjects: vec![allowed_subject.into()],
+            ..Default::default()
+        }));
+
+        let server = SocketConfig::from(config)
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+
+        wait_for_tcp(addr).await;
+        // Sending fails once the source closes the connection of a rejected client.
+        let _ = send_lines_tls(
+            addr,
+            "localhost".into(),
+            vec!["one line".to_owned()].into_iter(),
+            std::path::Path::new(tls::TEST_PEM_CA_PATH),
+            std::path::Path::new(tls::TEST_PEM_CLIENT_CRT_PATH),
+            std::path::Path::new(tls::TEST_PEM_CLIENT_KEY_PATH),
+        )
+        .await;
+
+        timeout(Duration::from_secs(1), rx.next())
+            .await
+            .ok()
+            .flatten()
+    }
+
+    #[tokio::test]
+    async fn tcp_with_tls_allowed_client() {
+        let event = tcp_with_tls_client_allowlist("localhost").await.unwrap();
+        assert_eq!(
+            event.as_log()[log_schema().message_key()],
+            "one line".into()
+        );
+
+        let event = tcp_with_tls_client_allowlist(
+            "CN=localhost,OU=Vector,O=Datadog,L=New York,ST=New York,C=US",
+        )
+        .await;
+        assert!(event.is_some());
+    }
+
+    #[tokio::test]
+    async fn tcp_with_tls_rejected_client() {
+        assert!(tcp_with_tls_client_allowlist("other").await.is_none());
+    }
+
+    #[test]
+    fn tcp_tls_client_allowlist_requires_verification() {
+        let config = Some(TlsSourceConfig {
+            tls_config: TlsEnableableConfig::test_config(),
+            client_allowed_sans: vec!["localhost".into()],
+            ..Default::default()
+        });
+        assert!(TlsClientAuthorization::from_config(&config).is_err());
+    }
+
     #[tokio::test]
     async fn tcp_shutdown_simple() {
         assert_source_compliance(&SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS, async {
@@ -1,6 +1,6 @@
 use bytes::Bytes;
 use chrono::Utc;
-use codecs::decoding::{DeserializerConfig, FramingConfig};
+use codecs::decoding::{DecodingErrorMode, DeserializerConfig, FramingConfig};
 use smallvec::SmallVec;
 use vector_config::configurable_component;
 
@@ -58,12 +58,24 @@ pub struct TcpConfig {
     /// The maximum number of TCP connections that will be allowed at any given time.
     pub connection_limit: Option<u32>,
 
+    /// Whether to expect each connection to start with a PROXY protocol header.
+    ///
+    /// Load balancers such as HAProxy send this header to pass on the address of the original client,
+    /// which is then used as the peer address of the connection. Both version 1 and 2 of the protocol
+    /// are supported. Connections not starting with a valid header are closed.
+    #[serde(default)]
+    proxy_protocol: bool,
+
     #[configurable(derived)]
     framing: Option<FramingConfig>,
 
     #[configurable(derived)]
     #[serde(default = "default_decoding")]
     decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    on_error: DecodingErrorMode,
 }
 
 const fn default_shutdown_timeout_secs() -> u64 {
@@ -83,7 +95,9 @@ impl TcpConfig {
             receive_buffer_bytes: None,
             framing: None,
             decoding: default_decoding(),
+            on_error: DecodingErrorMode::Drop,
             connection_limit: None,
+            proxy_protocol: false,
         }
     }
 
@@ -103,6 +117,10 @@ impl TcpConfig {
         &self.decoding
     }
 
+    pub const fn on_error(&self) -> DecodingErrorMode {
+        self.on_error
+    }
+
     pub const fn address(&self) -> SocketListenAddr {
         self.address
     }
@@ -123,6 +141,10 @@ impl TcpConfig {
         self.receive_buffer_bytes
     }
 
+    pub const fn proxy_protocol(&self) -> bool {
+        self.proxy_protocol
+    }
+
     pub fn set_max_length(&mut self, val: Option<usize>) -> &mut Self {
         self.max_length = val;
         self
@@ -147,6 +169,16 @@ impl TcpConfig {
         self.decoding = val;
         self
     }
+
+    pub fn set_on_error(&mut self, val: DecodingErrorMode) -> &mut Self {
+        self.on_error = val;
+        self
+    }
+
+    pub fn set_proxy_protocol(&mut self, val: bool) -> &mut Self {
+        self.proxy_protocol = val;
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -3,7 +3,7 @@ use std::net::SocketAddr;
 use bytes::{Bytes, BytesMut};
 use chrono::Utc;
 use codecs::{
-    decoding::{DeserializerConfig, FramingConfig},
+    decoding::{DecodingErrorMode, DeserializerConfig, FramingConfig},
     StreamDecodingError,
 };
 use futures::StreamExt;
@@ -65,6 +65,10 @@ pub struct UdpConfig {
     #[configurable(derived)]
     #[serde(default = "default_decoding")]
     decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    on_error: DecodingErrorMode,
 }
 
 impl UdpConfig {
@@ -80,6 +84,10 @@ impl UdpConfig {
         &self.decoding
     }
 
+    pub(super) const fn on_error(&self) -> DecodingErrorMode {
+        self.on_error
+    }
+
     pub(super) const fn address(&self) -> SocketAddr {
         self.address
     }
@@ -93,6 +101,7 @@ impl UdpConfig {
             receive_buffer_bytes: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            on_error: DecodingErrorMode::Drop,
         }
     }
 }
@@ -2,7 +2,7 @@ use std::path::PathBuf;
 
 use bytes::Bytes;
 use chrono::Utc;
-use codecs::decoding::{DeserializerConfig, FramingConfig};
+use codecs::decoding::{DecodingErrorMode, DeserializerConfig, FramingConfig};
 use vector_config::configurable_component;
 
 use crate::{
@@ -53,6 +53,10 @@ pub struct UnixConfig {
     #[configurable(derived)]
     #[serde(default = "default_decoding")]
     pub decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub on_error: DecodingErrorMode,
 }
 
 impl UnixConfig {
@@ -64,6 +68,7 @@ impl UnixConfig {
             host_key: None,
             framing: None,
             decoding: default_decoding(),
+            on_error: DecodingErrorMode::Drop,
         }
     }
 }
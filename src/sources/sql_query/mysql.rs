@@ -0,0 +1,128 @@
+use bytes::Bytes;
+use chrono::{NaiveDate, Utc};
+use mysql_async::{prelude::Queryable, Error as MysqlError, Opts, Params, Pool, Row};
+
+use super::SqlRow;
+use crate::event::Value;
+
+/// Runs the queries on a MySQL database, from a pool reconnecting when the connections are lost.
+pub(super) struct MysqlDatabase {
+    pool: Pool,
+}
+
+impl MysqlDatabase {
+    pub(super) fn new(endpoint: &str) -> crate::Result<Self> {
+        let opts = Opts::from_url(endpoint)?;
+        Ok(Self {
+            pool: Pool::new(opts),
+        })
+    }
+
+    pub(super) async fn query(
+        &mut self,
+        query: &str,
+        checkpoint: Option<i64>,
+    ) -> Result<Vec<SqlRow>, MysqlError> {
+        let mut conn = self.pool.get_conn().await?;
+
+        // Each placeholder is a parameter of its own.
+        let placeholders = query.matches(super::CHECKPOINT_PLACEHOLDER).count();
+        let rows: Vec<Row> = match checkpoint {
+            Some(checkpoint) if placeholders > 0 => {
+                let query = query.replace(super::CHECKPOINT_PLACEHOLDER, "?");
+                let params = vec![mysql_async::Value::Int(checkpoint); placeholders];
+                conn.exec(query, Params::Positional(params)).await?
+            }
+            _ => conn.query(query).await?,
+        };
+
+        Ok(rows.iter().map(convert_row).collect())
+    }
+
+    pub(super) async fn close(self) {
+        if let Err(error) = self.pool.disconnect().await {
+            debug!(message = "Failed to close the connections.", %error);
+        }
+    }
+}
+
+fn convert_row(row: &Row) -> SqlRow {
+    row.columns_ref()
+        .iter()
+        .enumerate()
+        .map(|(index, column)| {
+            let value = row.as_ref(index).map_or(Value::Null, convert_value);
+            (column.name_str().into_owned(), value)
+        })
+        .collect()
+}
+
+/// Converts the values of the text protocol, which are all bytes, and the ones of the binary
+/// protocol used by the queries with parameters.
+fn convert_value(value: &mysql_async::Value) -> Value {
+    match value {
+        mysql_async::Value::NULL => Value::Null,
+        mysql_async::Value::Bytes(bytes) => match std::str::from_utf8(bytes) {
+            Ok(text) => Value::from(text),
+            Err(_) => Value::from(Bytes::copy_from_slice(bytes)),
+        },
+        mysql_async::Value::Int(value) => Value::from(*value),
+        mysql_async::Value::UInt(value) => match i64::try_from(*value) {
+            Ok(value) => Value::from(value),
+            Err(_) => Value::from(*value as f64),
+        },
+        mysql_async::Value::Float(value) => Value::from(f64::from(*value)),
+        mysql_async::Value::Double(value) => Value::from(*value),
+        mysql_async::Value::Date(year, month, day, hour, minute, second, micros) => {
+            match NaiveDate::from_ymd_opt(i32::from(*year), u32::from(*month), u32::from(*day))
+                .and_then(|date| {
+                    date.and_hms_micro_opt(
+                        u32::from(*hour),
+                        u32::from(*minute),
+                        u32::from(*second),
+                        *micros,
+                    )
+                }) {
+                Some(datetime) => Value::from(chrono::DateTime::<Utc>::from_utc(datetime, Utc)),
+                None => Value::Null,
+            }
+        }
+        mysql_async::Value::Time(negative, days, hours, minutes, seconds, micros) => {
+            Value::from(format!(
+                "{}{}:{:02}:{:02}.{:06}",
+                if *negative { "-" } else { "" },
+                u32::from(*days) * 24 + u32::from(*hours),
+                minutes,
+                seconds,
+                micros
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn converts_values() {
+        assert_eq!(
+            convert_value(&mysql_async::Value::Bytes(b"42.50".to_vec())),
+            Value::from("42.50")
+        );
+        assert_eq!(
+            convert_value(&mysql_async::Value::UInt(u64::MAX)),
+            Value::from(u64::MAX as f64)
+        );
+        assert_eq!(
+            convert_value(&mysql_async::Value::Date(2022, 8, 1, 12, 30, 0, 0)),
+            Value::from(Utc.ymd(2022, 8, 1).and_hms(12, 30, 0))
+        );
+        assert_eq!(
+            convert_value(&mysql_async::Value::Time(true, 1, 2, 3, 4, 5)),
+            Value::from("-26:03:04.000005")
+        );
+    }
+}
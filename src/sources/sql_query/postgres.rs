@@ -0,0 +1,149 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use postgres_openssl::MakeTlsConnector;
+use tokio_postgres::{types::Type, Client, Config, Error as PgError, NoTls, Row};
+
+use super::SqlRow;
+use crate::{
+    event::Value,
+    tls::{tls_connector_builder, MaybeTlsSettings, TlsConfig, TlsSettings},
+};
+
+/// Runs the queries on a PostgreSQL database, reconnecting when the connection is lost.
+pub(super) struct PostgresDatabase {
+    config: Config,
+    tls: Option<MakeTlsConnector>,
+    client: Option<Client>,
+}
+
+impl PostgresDatabase {
+    pub(super) fn new(endpoint: &str, tls: &Option<TlsConfig>) -> crate::Result<Self> {
+        let config = Config::from_str(endpoint)?;
+        let tls = match tls {
+            Some(_) => {
+                let settings = TlsSettings::from_options(tls)?;
+                let builder = tls_connector_builder(&MaybeTlsSettings::from(settings.clone()))?;
+                let mut connector = MakeTlsConnector::new(builder.build());
+                connector.set_callback(move |config, _domain| {
+                    settings.apply_connect_configuration(config);
+                    Ok(())
+                });
+                Some(connector)
+            }
+            None => None,
+        };
+        Ok(Self {
+            config,
+            tls,
+            client: None,
+        })
+    }
+
+    async fn connect(&self) -> Result<Client, PgError> {
+        let client = match &self.tls {
+            Some(tls) => {
+                let (client, connection) = self.config.connect(tls.clone()).await?;
+                tokio::spawn(connection);
+                client
+            }
+            None => {
+                let (client, connection) = self.config.connect(NoTls).await?;
+                tokio::spawn(connection);
+                client
+            }
+        };
+        Ok(client)
+    }
+
+    pub(super) async fn query(
+        &mut self,
+        query: &str,
+        checkpoint: Option<i64>,
+    ) -> Result<Vec<SqlRow>, PgError> {
+        let client = match self.client.take() {
+            Some(client) if !client.is_closed() => client,
+            _ => self.connect().await?,
+        };
+
+        let query = query.replace(super::CHECKPOINT_PLACEHOLDER, "$1::int8");
+        let rows = match &checkpoint {
+            Some(checkpoint) if query.contains("$1") => client.query(&query, &[checkpoint]).await,
+            _ => client.query(&query, &[]).await,
+        }?;
+        // The client is only kept when it worked, to connect again after the errors.
+        self.client = Some(client);
+
+        Ok(rows.iter().map(convert_row).collect())
+    }
+}
+
+fn convert_row(row: &Row) -> SqlRow {
+    row.columns()
+        .iter()
+        .enumerate()
+        .map(|(index, column)| {
+            let value = convert_value(row, index, column.type_()).unwrap_or_else(|| {
+                debug!(
+                    message = "Unsupported column type, set to null.",
+                    column = %column.name(),
+                    r#type = %column.type_(),
+                );
+                None
+            });
+            (column.name().to_owned(), value.unwrap_or(Value::Null))
+        })
+        .collect()
+}
+
+/// Returns the value of the column, which is `None` for the types that aren't supported, and
+/// `Some(None)` for the `NULL` values.
+fn convert_value(row: &Row, index: usize, ty: &Type) -> Option<Option<Value>> {
+    let value = match *ty {
+        Type::BOOL => row.try_get::<_, Option<bool>>(index).ok()?.map(Value::from),
+        Type::CHAR => row
+            .try_get::<_, Option<i8>>(index)
+            .ok()?
+            .map(|value| Value::from(i64::from(value))),
+        Type::INT2 => row
+            .try_get::<_, Option<i16>>(index)
+            .ok()?
+            .map(|value| Value::from(i64::from(value))),
+        Type::INT4 => row
+            .try_get::<_, Option<i32>>(index)
+            .ok()?
+            .map(|value| Value::from(i64::from(value))),
+        Type::OID => row
+            .try_get::<_, Option<u32>>(index)
+            .ok()?
+            .map(|value| Value::from(i64::from(value))),
+        Type::INT8 => row.try_get::<_, Option<i64>>(index).ok()?.map(Value::from),
+        Type::FLOAT4 => row
+            .try_get::<_, Option<f32>>(index)
+            .ok()?
+            .map(|value| Value::from(f64::from(value))),
+        Type::FLOAT8 => row.try_get::<_, Option<f64>>(index).ok()?.map(Value::from),
+        Type::TIMESTAMPTZ => row
+            .try_get::<_, Option<DateTime<Utc>>>(index)
+            .ok()?
+            .map(Value::from),
+        Type::TIMESTAMP => row
+            .try_get::<_, Option<NaiveDateTime>>(index)
+            .ok()?
+            .map(|value| Value::from(DateTime::<Utc>::from_utc(value, Utc))),
+        Type::DATE => row
+            .try_get::<_, Option<NaiveDate>>(index)
+            .ok()?
+            .map(|value| Value::from(value.to_string())),
+        Type::BYTEA => row
+            .try_get::<_, Option<Vec<u8>>>(index)
+            .ok()?
+            .map(|value| Value::from(bytes::Bytes::from(value))),
+        // The text types, and the types that can be read as text such as `citext`.
+        _ => row
+            .try_get::<_, Option<String>>(index)
+            .ok()?
+            .map(Value::from),
+    };
+    Some(value)
+}
@@ -26,7 +26,7 @@ use crate::{
     },
     shutdown::ShutdownSignal,
     tcp::TcpKeepaliveConfig,
-    tls::{MaybeTlsSettings, TlsSourceConfig},
+    tls::{MaybeTlsSettings, TlsClientAuthorization, TlsSourceConfig},
     udp, SourceSender,
 };
 
@@ -102,6 +102,14 @@ pub struct TcpConfig {
 
     /// The maximum number of TCP connections that will be allowed at any given time.
     connection_limit: Option<u32>,
+
+    /// Whether to expect each connection to start with a PROXY protocol header.
+    ///
+    /// Load balancers such as HAProxy send this header to pass on the address of the original client,
+    /// which is then used as the peer address of the connection. Both version 1 and 2 of the protocol
+    /// are supported. Connections not starting with a valid header are closed.
+    #[serde(default)]
+    proxy_protocol: bool,
 }
 
 impl TcpConfig {
@@ -115,6 +123,7 @@ impl TcpConfig {
             shutdown_timeout_secs: default_shutdown_timeout_secs(),
             receive_buffer_bytes: None,
             connection_limit: None,
+            proxy_protocol: false,
         }
     }
 }
@@ -150,6 +159,7 @@ impl SourceConfig for StatsdConfig {
                     .tls
                     .as_ref()
                     .and_then(|tls| tls.client_metadata_key.clone());
+                let tls_client_authorization = TlsClientAuthorization::from_config(&config.tls)?;
                 let tls = MaybeTlsSettings::from_config(&tls_config, true)?;
                 StatsdTcpSource.run(
                     config.address,
@@ -157,10 +167,12 @@ impl SourceConfig for StatsdConfig {
                     config.shutdown_timeout_secs,
                     tls,
                     tls_client_metadata_key,
+                    tls_client_authorization,
                     config.receive_buffer_bytes,
                     cx,
                     false.into(),
                     config.connection_limit,
+                    config.proxy_protocol,
                 )
             }
             #[cfg(unix)]
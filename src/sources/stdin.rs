@@ -4,7 +4,7 @@ use async_stream::stream;
 use bytes::Bytes;
 use chrono::Utc;
 use codecs::{
-    decoding::{DeserializerConfig, FramingConfig},
+    decoding::{DecodingErrorMode, DeserializerConfig, FramingConfig},
     StreamDecodingError,
 };
 use futures::{channel::mpsc, executor, SinkExt, StreamExt};
@@ -45,6 +45,10 @@ pub struct StdinConfig {
     #[configurable(derived)]
     #[serde(default = "default_decoding")]
     pub decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub on_error: DecodingErrorMode,
 }
 
 impl Default for StdinConfig {
@@ -54,6 +58,7 @@ impl Default for StdinConfig {
             host_key: Default::default(),
             framing: None,
             decoding: default_decoding(),
+            on_error: Default::default(),
         }
     }
 }
@@ -77,7 +82,9 @@ impl SourceConfig for StdinConfig {
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
-        vec![Output::default(self.decoding.output_type())]
+        vec![Output::default(
+            self.on_error.output_type(self.decoding.output_type()),
+        )]
     }
 
     fn source_type(&self) -> &'static str {
@@ -110,7 +117,9 @@ where
     let framing = config
         .framing
         .unwrap_or_else(|| config.decoding.default_stream_framing());
-    let decoder = DecodingConfig::new(framing, config.decoding, LogNamespace::Legacy).build();
+    let decoder = DecodingConfig::new(framing, config.decoding, LogNamespace::Legacy)
+        .with_error_mode(config.on_error)
+        .build()?;
 
     let (mut sender, receiver) = mpsc::channel(1024);
 
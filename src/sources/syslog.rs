@@ -28,7 +28,7 @@ use crate::{
     shutdown::ShutdownSignal,
     sources::util::{SocketListenAddr, TcpNullAcker, TcpSource},
     tcp::TcpKeepaliveConfig,
-    tls::{MaybeTlsSettings, TlsSourceConfig},
+    tls::{MaybeTlsSettings, TlsClientAuthorization, TlsSourceConfig},
     udp, SourceSender,
 };
 
@@ -77,6 +77,14 @@ pub enum Mode {
 
         /// The maximum number of TCP connections that will be allowed at any given time.
         connection_limit: Option<u32>,
+
+        /// Whether to expect each connection to start with a PROXY protocol header.
+        ///
+        /// Load balancers such as HAProxy send this header to pass on the address of the original client,
+        /// which is then used as the peer address of the connection. Both version 1 and 2 of the protocol
+        /// are supported. Connections not starting with a valid header are closed.
+        #[serde(default)]
+        proxy_protocol: bool,
     },
 
     /// Listen on UDP.
@@ -129,6 +137,7 @@ impl GenerateConfig for SyslogConfig {
                 tls: None,
                 receive_buffer_bytes: None,
                 connection_limit: None,
+                proxy_protocol: false,
             },
             host_key: None,
             max_length: crate::serde::default_max_length(),
@@ -153,6 +162,7 @@ impl SourceConfig for SyslogConfig {
                 tls,
                 receive_buffer_bytes,
                 connection_limit,
+                proxy_protocol,
             } => {
                 let source = SyslogTcpSource {
                     max_length: self.max_length,
@@ -162,6 +172,7 @@ impl SourceConfig for SyslogConfig {
                 let tls_config = tls.as_ref().map(|tls| tls.tls_config.clone());
                 let tls_client_metadata_key =
                     tls.as_ref().and_then(|tls| tls.client_metadata_key.clone());
+                let tls_client_authorization = TlsClientAuthorization::from_config(tls)?;
                 let tls = MaybeTlsSettings::from_config(&tls_config, true)?;
                 source.run(
                     address,
@@ -169,10 +180,12 @@ impl SourceConfig for SyslogConfig {
                     shutdown_secs,
                     tls,
                     tls_client_metadata_key,
+                    tls_client_authorization,
                     receive_buffer_bytes,
                     cx,
                     false.into(),
                     connection_limit,
+                    proxy_protocol,
                 )
             }
             Mode::Udp {
@@ -803,6 +816,7 @@ mod test {
                 tls: None,
                 receive_buffer_bytes: None,
                 connection_limit: None,
+                proxy_protocol: false,
             });
 
             let key = ComponentKey::from("in");
@@ -946,6 +960,7 @@ mod test {
                 tls: None,
                 receive_buffer_bytes: None,
                 connection_limit: None,
+                proxy_protocol: false,
             });
 
             let key = ComponentKey::from("in");
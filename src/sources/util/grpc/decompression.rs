@@ -1,6 +1,6 @@
 use std::{
     cmp,
-    io::Write,
+    io::{self, Write},
     mem,
     pin::Pin,
     task::{Context, Poll},
@@ -28,8 +28,10 @@ const GRPC_MESSAGE_HEADER_LEN: usize = mem::size_of::<u8>() + mem::size_of::<u32
 const GRPC_ENCODING_HEADER: &str = "grpc-encoding";
 const GRPC_ACCEPT_ENCODING_HEADER: &str = "grpc-accept-encoding";
 
+#[derive(Clone, Copy)]
 enum CompressionScheme {
     Gzip,
+    Zstd,
 }
 
 impl CompressionScheme {
@@ -49,6 +51,7 @@ impl CompressionScheme {
                 None => Ok(None),
                 Some(scheme) => match scheme.as_str() {
                     "gzip" => Ok(Some(CompressionScheme::Gzip)),
+                    "zstd" => Ok(Some(CompressionScheme::Zstd)),
                     other => Err(Status::unimplemented(format!(
                         "compression scheme `{}` is not supported",
                         other
@@ -58,7 +61,7 @@ impl CompressionScheme {
             .map_err(|mut status| {
                 status.metadata_mut().insert(
                     GRPC_ACCEPT_ENCODING_HEADER,
-                    AsciiMetadataValue::from_static("gzip,identity"),
+                    AsciiMetadataValue::from_static("gzip,zstd,identity"),
                 );
                 status
             })
@@ -77,18 +80,46 @@ impl Default for State {
     }
 }
 
-fn new_decompressor() -> GzDecoder<Vec<u8>> {
-    // Create the backing buffer for the decompressor and set the compression flag to false (0) and pre-allocate
-    // the space for the length prefix, which we'll fill out once we've finalized the decompressor.
-    let mut buf = Vec::new();
-    buf.resize(GRPC_MESSAGE_HEADER_LEN, 0x00);
+enum Decompressor {
+    Gzip(GzDecoder<Vec<u8>>),
+    Zstd(zstd::stream::write::Decoder<'static, Vec<u8>>),
+}
+
+impl Decompressor {
+    fn new(scheme: CompressionScheme) -> io::Result<Self> {
+        // Create the backing buffer for the decompressor and set the compression flag to false (0) and pre-allocate
+        // the space for the length prefix, which we'll fill out once we've finalized the decompressor.
+        let mut buf = Vec::new();
+        buf.resize(GRPC_MESSAGE_HEADER_LEN, 0x00);
+
+        Ok(match scheme {
+            CompressionScheme::Gzip => Self::Gzip(GzDecoder::new(buf)),
+            CompressionScheme::Zstd => Self::Zstd(zstd::stream::write::Decoder::new(buf)?),
+        })
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        match self {
+            Self::Gzip(decoder) => decoder.write_all(buf),
+            Self::Zstd(decoder) => decoder.write_all(buf),
+        }
+    }
 
-    GzDecoder::new(buf)
+    fn finish(self) -> io::Result<Vec<u8>> {
+        match self {
+            Self::Gzip(decoder) => decoder.finish(),
+            Self::Zstd(mut decoder) => {
+                decoder.flush()?;
+                Ok(decoder.into_inner())
+            }
+        }
+    }
 }
 
 async fn drive_body_decompression(
     mut source: Body,
     mut destination: Sender,
+    scheme: Option<CompressionScheme>,
 ) -> Result<usize, Status> {
     let mut state = State::default();
     let mut buf = BytesMut::new();
@@ -170,7 +201,22 @@ async fn drive_body_decompression(
                             // the decompressor. This is _technically_ synchronous but there's really no way to do it
                             // asynchronously since we already have the data, and that's the only asynchronous part.
                             let to_take = cmp::min(available, *remaining);
-                            let decompressor = decompressor.get_or_insert_with(new_decompressor);
+                            let decompressor = match decompressor.as_mut() {
+                                Some(decompressor) => decompressor,
+                                None => {
+                                    let scheme = scheme.ok_or_else(|| {
+                                        Status::internal(format!(
+                                            "received a compressed message without a `{}` header",
+                                            GRPC_ENCODING_HEADER
+                                        ))
+                                    })?;
+                                    let new_decompressor =
+                                        Decompressor::new(scheme).map_err(|_| {
+                                            Status::internal("failed to create decompressor")
+                                        })?;
+                                    decompressor.insert(new_decompressor)
+                                }
+                            };
                             if decompressor.write_all(&buf[..to_take]).is_err() {
                                 return Err(Status::internal("failed to write to decompressor"));
                             }
@@ -188,14 +234,10 @@ async fn drive_body_decompression(
                             .expect("consumed decompressor when no decompressor was present")
                             .finish();
 
-                        // The only I/O errors that occur during `finish` should be I/O errors from writing to the internal
-                        // buffer, but `Vec<T>` is infallible in this regard, so this should be impossible without having
-                        // first panicked due to memory exhaustion.
-                        let mut buf = result.map_err(|_| {
-                            Status::internal(
-                                "reached impossible error during decompressor finalization",
-                            )
-                        })?;
+                        // Writing to the internal buffer is infallible, so errors during `finish` can only come from
+                        // a truncated or otherwise invalid compressed message.
+                        let mut buf = result
+                            .map_err(|_| Status::internal("failed to finalize decompressor"))?;
                         bytes_received += buf.len();
 
                         // Write the length of our decompressed message in the pre-allocated slot for the message's length prefix.
@@ -241,12 +283,13 @@ async fn drive_body_decompression(
 async fn drive_request<F, E>(
     source: Body,
     destination: Sender,
+    scheme: Option<CompressionScheme>,
     inner: F,
 ) -> Result<Response<BoxBody>, E>
 where
     F: Future<Output = Result<Response<BoxBody>, E>>,
 {
-    let body_decompression = drive_body_decompression(source, destination);
+    let body_decompression = drive_body_decompression(source, destination, scheme);
 
     pin!(inner);
     pin!(body_decompression);
@@ -316,15 +359,17 @@ where
             // can support decompression based on the indicated compression scheme... so wrap the body to decompress, if
             // need be, and then track the bytes that flowed through.
             //
-            // TODO: Actually use the scheme given back to us to support other compression schemes.
-            Ok(_) => {
+            // As the inner service only ever sees decompressed messages, the encoding header is removed, since `tonic`
+            // itself would reject the schemes it doesn't support.
+            Ok(scheme) => {
                 let (destination, decompressed_body) = Body::channel();
-                let (req_parts, req_body) = req.into_parts();
+                let (mut req_parts, req_body) = req.into_parts();
+                req_parts.headers.remove(GRPC_ENCODING_HEADER);
                 let mapped_req = Request::from_parts(req_parts, decompressed_body);
 
                 let inner = self.inner.call(mapped_req);
 
-                drive_request(req_body, destination, inner).boxed()
+                drive_request(req_body, destination, scheme, inner).boxed()
             }
         }
     }
@@ -349,7 +394,8 @@ where
 /// request was valid, and was processed -- we can now report the number of bytes (after decompression) that were
 /// received _and_ processed correctly.
 ///
-/// The only supported compression scheme is gzip, which is also the only supported compression scheme in `tonic` itself.
+/// The supported compression schemes are gzip, which is also the only supported compression scheme in `tonic` itself,
+/// and zstd.
 #[derive(Clone, Default)]
 pub struct DecompressionAndMetricsLayer;
 
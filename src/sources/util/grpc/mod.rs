@@ -1,24 +1,42 @@
 use crate::{
     shutdown::{ShutdownSignal, ShutdownSignalToken},
-    tls::MaybeTlsSettings,
+    tls::{MaybeTlsSettings, TlsClientAuthorization},
 };
-use futures::FutureExt;
+use futures::{FutureExt, Stream};
 use http::{Request, Response};
-use hyper::Body;
+use hyper::{service::make_service_fn, Body, Server as HyperServer};
 use std::{convert::Infallible, net::SocketAddr};
+#[cfg(unix)]
+use std::{fs::remove_file, path::PathBuf};
+use tokio::io::{AsyncRead, AsyncWrite};
+#[cfg(unix)]
+use tokio::net::UnixListener;
+#[cfg(unix)]
+use tokio_stream::wrappers::UnixListenerStream;
 use tonic::{
     body::BoxBody,
-    transport::server::{NamedService, Server},
+    transport::server::{Connected, NamedService, Router, Server},
+};
+use tower::{
+    layer::util::{Identity, Stack},
+    Layer, Service,
 };
-use tower::Service;
 use tracing::{Instrument, Span};
 
+#[cfg(unix)]
+use crate::{internal_events::UnixSocketFileDeleteError, sources::util::change_socket_permissions};
+
 mod decompression;
 pub use self::decompression::{DecompressionAndMetrics, DecompressionAndMetricsLayer};
 
+type GrpcLayer = Stack<DecompressionAndMetricsLayer, Identity>;
+
+/// Runs a gRPC server serving `service`, closing the connections of clients whose certificate isn't
+/// allowed by `tls_client_authorization`.
 pub async fn run_grpc_server<S>(
     address: SocketAddr,
     tls_settings: MaybeTlsSettings,
+    tls_client_authorization: Option<TlsClientAuthorization>,
     service: S,
     shutdown: ShutdownSignal,
 ) -> crate::Result<()>
@@ -30,14 +48,123 @@ where
         + 'static,
     S::Future: Send + 'static,
 {
-    let span = Span::current();
-    let (tx, rx) = tokio::sync::oneshot::channel::<ShutdownSignalToken>();
+    let listener = tls_settings
+        .bind(&address)
+        .await?
+        .with_client_authorization(tls_client_authorization);
+    let stream = listener.accept_stream();
+
+    info!(message = "Building gRPC server.", address = %address);
+
+    serve(stream, |server| server.add_service(service), shutdown).await
+}
+
+/// Runs a gRPC server serving all the services added to it by `add_services`, such as one per
+/// type of request of a protocol.
+pub async fn run_grpc_server_with_routes<F>(
+    address: SocketAddr,
+    tls_settings: MaybeTlsSettings,
+    add_services: F,
+    shutdown: ShutdownSignal,
+) -> crate::Result<()>
+where
+    F: FnOnce(&mut Server<GrpcLayer>) -> Router<GrpcLayer>,
+{
     let listener = tls_settings.bind(&address).await?;
     let stream = listener.accept_stream();
 
     info!(message = "Building gRPC server.", address = %address);
 
-    Server::builder()
+    serve(stream, add_services, shutdown).await
+}
+
+/// Runs a gRPC server handing all the requests to `service`, which routes them itself, such as for
+/// the services that are only known at runtime.
+pub async fn run_grpc_server_with_service<S>(
+    address: SocketAddr,
+    tls_settings: MaybeTlsSettings,
+    service: S,
+    shutdown: ShutdownSignal,
+) -> crate::Result<()>
+where
+    S: Service<Request<Body>, Response = Response<BoxBody>, Error = Infallible>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send + 'static,
+{
+    let listener = tls_settings.bind(&address).await?;
+    let incoming = hyper::server::accept::from_stream(listener.accept_stream());
+
+    info!(message = "Building gRPC server.", address = %address);
+
+    let span = Span::current();
+    let (tx, rx) = tokio::sync::oneshot::channel::<ShutdownSignalToken>();
+    let service = DecompressionAndMetricsLayer::default().layer(service);
+
+    HyperServer::builder(incoming)
+        .http2_only(true)
+        .serve(make_service_fn(move |_| {
+            let service = service.clone();
+            async move { Ok::<_, Infallible>(service) }
+        }))
+        .with_graceful_shutdown(shutdown.map(|token| tx.send(token).unwrap()))
+        .instrument(span)
+        .await?;
+
+    drop(rx.await);
+
+    Ok(())
+}
+
+/// Runs a gRPC server listening on a Unix domain stream socket, which is deleted once the server
+/// shuts down.
+#[cfg(unix)]
+pub async fn run_grpc_server_unix<S>(
+    path: PathBuf,
+    socket_file_mode: Option<u32>,
+    service: S,
+    shutdown: ShutdownSignal,
+) -> crate::Result<()>
+where
+    S: Service<Request<Body>, Response = Response<BoxBody>, Error = Infallible>
+        + NamedService
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send + 'static,
+{
+    let listener = UnixListener::bind(&path)?;
+    change_socket_permissions(&path, socket_file_mode)?;
+    let stream = UnixListenerStream::new(listener);
+
+    info!(message = "Building gRPC server.", path = ?path);
+
+    let result = serve(stream, |server| server.add_service(service), shutdown).await;
+
+    if let Err(error) = remove_file(&path) {
+        emit!(UnixSocketFileDeleteError { path: &path, error });
+    }
+
+    result
+}
+
+async fn serve<F, I, IO, IE>(
+    incoming: I,
+    add_services: F,
+    shutdown: ShutdownSignal,
+) -> crate::Result<()>
+where
+    F: FnOnce(&mut Server<GrpcLayer>) -> Router<GrpcLayer>,
+    I: Stream<Item = Result<IO, IE>>,
+    IO: AsyncRead + AsyncWrite + Connected + Unpin + Send + 'static,
+    IO::ConnectInfo: Clone + Send + Sync + 'static,
+    IE: Into<crate::Error>,
+{
+    let span = Span::current();
+    let (tx, rx) = tokio::sync::oneshot::channel::<ShutdownSignalToken>();
+
+    let mut server = Server::builder()
         .trace_fn(move |_| span.clone())
         // This layer explicitly decompresses payloads, if compressed, and reports the number of message bytes we've
         // received if the message is processed successfully, aka `BytesReceived`. We do this because otherwise the only
@@ -48,9 +175,10 @@ where
         // use independent `tower` layers when the request body itself (the body type, not the actual bytes) must be
         // modified or wrapped.. so instead of a cleaner design, we're opting here to bake it all together until the
         // crates are sufficiently flexible for us to craft a better design.
-        .layer(DecompressionAndMetricsLayer::default())
-        .add_service(service)
-        .serve_with_incoming_shutdown(stream, shutdown.map(|token| tx.send(token).unwrap()))
+        .layer(DecompressionAndMetricsLayer::default());
+
+    add_services(&mut server)
+        .serve_with_incoming_shutdown(incoming, shutdown.map(|token| tx.send(token).unwrap()))
         .in_current_span()
         .await?;
 
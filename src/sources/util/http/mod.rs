@@ -20,6 +20,6 @@ pub use encoding::decode;
 #[cfg(feature = "sources-utils-http-error")]
 pub use error::ErrorMessage;
 #[cfg(feature = "sources-utils-http-prelude")]
-pub use prelude::HttpSource;
+pub use prelude::{HttpResponse, HttpRoute, HttpSource};
 #[cfg(feature = "sources-utils-http-query")]
 pub use query::add_query_parameters;
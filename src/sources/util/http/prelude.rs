@@ -13,9 +13,9 @@ use warp::{
         path::{FullPath, Tail},
         BoxedFilter,
     },
-    http::{HeaderMap, StatusCode},
+    http::{HeaderMap, Method, StatusCode},
     reject::Rejection,
-    Filter,
+    Filter, Reply,
 };
 
 use crate::{
@@ -32,6 +32,21 @@ use super::{
     error::ErrorMessage,
 };
 
+/// A route of an HTTP source, on which the requests of a method are accepted.
+#[derive(Clone, Debug)]
+pub struct HttpRoute {
+    pub path: String,
+    pub method: HttpMethod,
+    pub strict_path: bool,
+}
+
+/// The response to a request whose events were delivered, instead of an empty `200 OK`.
+#[derive(Clone, Debug)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub body: Bytes,
+}
+
 #[async_trait]
 pub trait HttpSource: Clone + Send + Sync + 'static {
     fn build_events(
@@ -42,6 +57,26 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
         path: &str,
     ) -> Result<Vec<Event>, ErrorMessage>;
 
+    /// Builds the events of a request to the route at `route` of `run_routes`, which are the ones
+    /// of `build_events` by default.
+    fn build_route_events(
+        &self,
+        _route: usize,
+        _method: &Method,
+        body: Bytes,
+        header_map: HeaderMap,
+        query_parameters: HashMap<String, String>,
+        path: &str,
+    ) -> Result<Vec<Event>, ErrorMessage> {
+        self.build_events(body, header_map, query_parameters, path)
+    }
+
+    /// Builds the response to a request to the route at `route` of `run_routes`, once its events
+    /// are delivered.
+    fn build_response(&self, _route: usize, _events: &[Event]) -> Option<HttpResponse> {
+        None
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn run(
         self,
@@ -53,42 +88,38 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
         auth: &Option<HttpSourceAuthConfig>,
         cx: SourceContext,
         acknowledgements: AcknowledgementsConfig,
+    ) -> crate::Result<crate::sources::Source> {
+        let route = HttpRoute {
+            path: path.to_owned(),
+            method,
+            strict_path,
+        };
+        self.run_routes(address, vec![route], tls, auth, cx, acknowledgements)
+    }
+
+    fn run_routes(
+        self,
+        address: SocketAddr,
+        routes: Vec<HttpRoute>,
+        tls: &Option<TlsEnableableConfig>,
+        auth: &Option<HttpSourceAuthConfig>,
+        cx: SourceContext,
+        acknowledgements: AcknowledgementsConfig,
     ) -> crate::Result<crate::sources::Source> {
         let tls = MaybeTlsSettings::from_config(tls, true)?;
         let protocol = tls.http_protocol_name();
         let auth = HttpSourceAuth::try_from(auth.as_ref())?;
-        let path = path.to_owned();
         let acknowledgements = cx.do_acknowledgements(&acknowledgements);
         Ok(Box::pin(async move {
             let span = Span::current();
-            let mut filter: BoxedFilter<()> = match method {
-                HttpMethod::Head => warp::head().boxed(),
-                HttpMethod::Get => warp::get().boxed(),
-                HttpMethod::Put => warp::put().boxed(),
-                HttpMethod::Post => warp::post().boxed(),
-                HttpMethod::Patch => warp::patch().boxed(),
-                HttpMethod::Delete => warp::delete().boxed(),
-            };
-
-            // https://github.com/rust-lang/rust-clippy/issues/8148
-            #[allow(clippy::unnecessary_to_owned)]
-            for s in path.split('/').filter(|&x| !x.is_empty()) {
-                filter = filter.and(warp::path(s.to_string())).boxed()
-            }
+            let filter = routes
+                .into_iter()
+                .enumerate()
+                .map(|(index, route)| route_filter(index, route))
+                .reduce(|filter, route| filter.or(route).unify().boxed())
+                .expect("HTTP sources have at least one route");
             let svc = filter
-                .and(warp::path::tail())
-                .and_then(move |tail: Tail| async move {
-                    if !strict_path || tail.as_str().is_empty() {
-                        Ok(())
-                    } else {
-                        debug!(message = "Path rejected.");
-                        Err(warp::reject::custom(ErrorMessage::new(
-                            StatusCode::NOT_FOUND,
-                            "Not found".to_string(),
-                        )))
-                    }
-                })
-                .untuple_one()
+                .and(warp::method())
                 .and(warp::path::full())
                 .and(warp::header::optional::<String>("authorization"))
                 .and(warp::header::optional::<String>("content-encoding"))
@@ -96,7 +127,9 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
                 .and(warp::body::bytes())
                 .and(warp::query::<HashMap<String, String>>())
                 .and_then(
-                    move |path: FullPath,
+                    move |route: usize,
+                          method: Method,
+                          path: FullPath,
                           auth_header,
                           encoding_header,
                           headers: HeaderMap,
@@ -114,7 +147,14 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
                             .is_valid(&auth_header)
                             .and_then(|()| decode(&encoding_header, body))
                             .and_then(|body| {
-                                self.build_events(body, headers, query_parameters, path.as_str())
+                                self.build_route_events(
+                                    route,
+                                    &method,
+                                    body,
+                                    headers,
+                                    query_parameters,
+                                    path.as_str(),
+                                )
                             })
                             .map(|events| {
                                 emit!(HttpEventsReceived {
@@ -123,7 +163,8 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
                                     http_path,
                                     protocol,
                                 });
-                                events
+                                let response = self.build_response(route, &events);
+                                (events, response)
                             });
 
                         handle_request(events, acknowledgements, cx.out.clone())
@@ -156,6 +197,39 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
     }
 }
 
+/// Returns the filter of the requests of the route, which extracts its index.
+fn route_filter(index: usize, route: HttpRoute) -> BoxedFilter<(usize,)> {
+    let mut filter: BoxedFilter<()> = match route.method {
+        HttpMethod::Head => warp::head().boxed(),
+        HttpMethod::Get => warp::get().boxed(),
+        HttpMethod::Put => warp::put().boxed(),
+        HttpMethod::Post => warp::post().boxed(),
+        HttpMethod::Patch => warp::patch().boxed(),
+        HttpMethod::Delete => warp::delete().boxed(),
+    };
+
+    // https://github.com/rust-lang/rust-clippy/issues/8148
+    #[allow(clippy::unnecessary_to_owned)]
+    for s in route.path.split('/').filter(|&x| !x.is_empty()) {
+        filter = filter.and(warp::path(s.to_string())).boxed()
+    }
+    let strict_path = route.strict_path;
+    filter
+        .and(warp::path::tail())
+        .and_then(move |tail: Tail| async move {
+            if !strict_path || tail.as_str().is_empty() {
+                Ok(index)
+            } else {
+                debug!(message = "Path rejected.");
+                Err(warp::reject::custom(ErrorMessage::new(
+                    StatusCode::NOT_FOUND,
+                    "Not found".to_string(),
+                )))
+            }
+        })
+        .boxed()
+}
+
 struct RejectShuttingDown;
 
 impl fmt::Debug for RejectShuttingDown {
@@ -167,12 +241,12 @@ impl fmt::Debug for RejectShuttingDown {
 impl warp::reject::Reject for RejectShuttingDown {}
 
 async fn handle_request(
-    events: Result<Vec<Event>, ErrorMessage>,
+    events: Result<(Vec<Event>, Option<HttpResponse>), ErrorMessage>,
     acknowledgements: bool,
     mut out: SourceSender,
 ) -> Result<impl warp::Reply, Rejection> {
     match events {
-        Ok(mut events) => {
+        Ok((mut events, response)) => {
             let receiver = BatchNotifier::maybe_apply_to(acknowledgements, &mut events);
 
             out.send_batch(events)
@@ -185,6 +259,14 @@ async fn handle_request(
                 })
                 .and_then(|_| handle_batch_status(receiver))
                 .await
+                .map(|()| match response {
+                    Some(response) => {
+                        let mut reply = warp::reply::Response::new(response.body.into());
+                        *reply.status_mut() = response.status;
+                        reply
+                    }
+                    None => warp::reply().into_response(),
+                })
         }
         Err(error) => {
             emit!(HttpBadRequest::new(error.code(), error.message()));
@@ -193,13 +275,11 @@ async fn handle_request(
     }
 }
 
-async fn handle_batch_status(
-    receiver: Option<BatchStatusReceiver>,
-) -> Result<impl warp::Reply, Rejection> {
+async fn handle_batch_status(receiver: Option<BatchStatusReceiver>) -> Result<(), Rejection> {
     match receiver {
-        None => Ok(warp::reply()),
+        None => Ok(()),
         Some(receiver) => match receiver.await {
-            BatchStatus::Delivered => Ok(warp::reply()),
+            BatchStatus::Delivered => Ok(()),
             BatchStatus::Errored => Err(warp::reject::custom(ErrorMessage::new(
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "Error delivering contents to sink".into(),
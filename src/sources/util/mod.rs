@@ -13,7 +13,16 @@ pub mod grpc;
     feature = "sources-utils-http-query"
 ))]
 mod http;
-#[cfg(any(feature = "sources-aws_sqs", feature = "sources-gcp_pubsub"))]
+#[cfg(any(
+    feature = "sources-amqp",
+    feature = "sources-aws_kinesis_streams",
+    feature = "sources-aws_sqs",
+    feature = "sources-gcp_pubsub",
+    feature = "sources-http_client",
+    feature = "sources-nats",
+    feature = "sources-redis",
+    feature = "sources-websocket_client"
+))]
 mod message_decoding;
 pub mod multiline_config;
 #[cfg(all(feature = "sources-utils-tls", feature = "listenfd"))]
@@ -61,8 +70,17 @@ pub use self::http::decode;
 #[cfg(feature = "sources-utils-http-error")]
 pub use self::http::ErrorMessage;
 #[cfg(feature = "sources-utils-http-prelude")]
-pub use self::http::HttpSource;
+pub use self::http::{HttpResponse, HttpRoute, HttpSource};
 #[cfg(feature = "sources-utils-http-auth")]
 pub use self::http::HttpSourceAuthConfig;
-#[cfg(any(feature = "sources-aws_sqs", feature = "sources-gcp_pubsub"))]
+#[cfg(any(
+    feature = "sources-amqp",
+    feature = "sources-aws_kinesis_streams",
+    feature = "sources-aws_sqs",
+    feature = "sources-gcp_pubsub",
+    feature = "sources-http_client",
+    feature = "sources-nats",
+    feature = "sources-redis",
+    feature = "sources-websocket_client"
+))]
 pub use self::message_decoding::decode_message;
@@ -6,7 +6,10 @@ use std::{fmt, io, mem::drop, time::Duration};
 
 use bytes::Bytes;
 use codecs::StreamDecodingError;
-use futures::{future::BoxFuture, FutureExt, StreamExt};
+use futures::{
+    future::{self, BoxFuture},
+    FutureExt, StreamExt,
+};
 use listenfd::ListenFd;
 use serde::{de, Deserialize, Deserializer};
 use smallvec::SmallVec;
@@ -30,11 +33,15 @@ use crate::{
     event::{BatchNotifier, BatchStatus, Event},
     internal_events::{
         ConnectionOpen, OpenGauge, SocketEventsReceived, SocketMode, StreamClosedError,
-        TcpBytesReceived, TcpSendAckError, TcpSocketTlsConnectionError,
+        TcpBytesReceived, TcpSendAckError, TcpSocketTlsClientUnauthorized,
+        TcpSocketTlsConnectionError,
     },
     shutdown::ShutdownSignal,
     tcp::TcpKeepaliveConfig,
-    tls::{CertificateMetadata, MaybeTlsIncomingStream, MaybeTlsListener, MaybeTlsSettings},
+    tls::{
+        CertificateMetadata, MaybeTlsIncomingStream, MaybeTlsListener, MaybeTlsSettings,
+        TlsClientAuthorization,
+    },
     SourceSender,
 };
 
@@ -112,6 +119,16 @@ where
 
     fn decoder(&self) -> Self::Decoder;
 
+    /// Performs a handshake on each new connection, before any frames are decoded from it.
+    ///
+    /// The connection is closed if the handshake fails.
+    fn handshake<'a>(
+        &'a self,
+        _socket: &'a mut MaybeTlsIncomingStream<TcpStream>,
+    ) -> BoxFuture<'a, Result<(), Self::Error>> {
+        future::ok(()).boxed()
+    }
+
     fn handle_events(&self, _events: &mut [Event], _host: std::net::SocketAddr) {}
 
     fn build_acker(&self, item: &[Self::Item]) -> Self::Acker;
@@ -124,10 +141,12 @@ where
         shutdown_timeout_secs: u64,
         tls: MaybeTlsSettings,
         tls_client_metadata_key: Option<String>,
+        tls_client_authorization: Option<TlsClientAuthorization>,
         receive_buffer_bytes: Option<usize>,
         cx: SourceContext,
         acknowledgements: AcknowledgementsConfig,
         max_connections: Option<u32>,
+        proxy_protocol: bool,
     ) -> crate::Result<crate::sources::Source> {
         let acknowledgements = cx.do_acknowledgements(&acknowledgements);
 
@@ -136,7 +155,7 @@ where
         Ok(Box::pin(async move {
             let listener = match make_listener(addr, listenfd, &tls).await {
                 None => return Err(()),
-                Some(listener) => listener,
+                Some(listener) => listener.with_proxy_protocol(proxy_protocol),
             };
 
             info!(
@@ -171,6 +190,7 @@ where
                     let connection_gauge = connection_gauge.clone();
                     let request_limiter = request_limiter.clone();
                     let tls_client_metadata_key = tls_client_metadata_key.clone();
+                    let tls_client_authorization = tls_client_authorization.clone();
 
                     async move {
                         let socket = match connection {
@@ -209,11 +229,11 @@ where
                                 receive_buffer_bytes,
                                 source,
                                 tripwire,
-                                peer_addr,
                                 out,
                                 acknowledgements,
                                 request_limiter,
                                 tls_client_metadata_key.clone(),
+                                tls_client_authorization,
                             );
 
                             tokio::spawn(
@@ -240,11 +260,11 @@ async fn handle_stream<T>(
     receive_buffer_bytes: Option<usize>,
     source: T,
     mut tripwire: BoxFuture<'static, ()>,
-    peer_addr: SocketAddr,
     mut out: SourceSender,
     acknowledgements: bool,
     request_limiter: RequestLimiter,
     tls_client_metadata_key: Option<String>,
+    tls_client_authorization: Option<TlsClientAuthorization>,
 ) where
     <<T as TcpSource>::Decoder as tokio_util::codec::Decoder>::Item: std::marker::Send,
     T: TcpSource,
@@ -261,6 +281,9 @@ async fn handle_stream<T>(
         }
     };
 
+    // With the PROXY protocol, this is the address of the original client read during the handshake.
+    let peer_addr = socket.peer_addr();
+
     if let Some(keepalive) = keepalive {
         if let Err(error) = socket.set_keepalive(keepalive) {
             warn!(message = "Failed configuring TCP keepalive.", %error);
@@ -273,6 +296,35 @@ async fn handle_stream<T>(
         }
     }
 
+    let certificate_metadata = socket
+        .ssl_stream()
+        .and_then(|stream| stream.ssl().peer_certificate())
+        .map(CertificateMetadata::from_x509);
+
+    if let Some(authorization) = &tls_client_authorization {
+        if !authorization.is_authorized(certificate_metadata.as_ref()) {
+            emit!(TcpSocketTlsClientUnauthorized {
+                peer_addr,
+                subject: certificate_metadata
+                    .as_ref()
+                    .map(CertificateMetadata::subject),
+            });
+            return;
+        }
+    }
+
+    tokio::select! {
+        result = source.handshake(&mut socket) => {
+            if let Err(error) = result {
+                debug!(message = "Handshake failed, closing connection.", %error);
+                return;
+            }
+        },
+        _ = &mut shutdown_signal => {
+            return;
+        }
+    };
+
     let socket = socket.after_read(move |byte_size| {
         emit!(TcpBytesReceived {
             byte_size,
@@ -280,12 +332,6 @@ async fn handle_stream<T>(
         });
     });
 
-    let certificate_metadata = socket
-        .get_ref()
-        .ssl_stream()
-        .and_then(|stream| stream.ssl().peer_certificate())
-        .map(CertificateMetadata::from_x509);
-
     let reader = FramedRead::new(socket, source.decoder());
     let mut reader = ReadyFrames::new(reader);
 
@@ -353,6 +399,9 @@ async fn handle_stream<T>(
                             if let Some(certificate_metadata) = &certificate_metadata {
                                 let mut metadata: BTreeMap<String, value::Value> = BTreeMap::new();
                                 metadata.insert("subject".to_string(), certificate_metadata.subject().into());
+                                if !certificate_metadata.subject_alt_names.is_empty() {
+                                    metadata.insert("subject_alt_names".to_string(), certificate_metadata.subject_alt_names.clone().into());
+                                }
                                 for event in &mut events {
                                     let log = event.as_mut_log();
                                     log.insert(&tls_client_metadata_key[..], value::Value::from(metadata.clone()));
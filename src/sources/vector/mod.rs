@@ -1,6 +1,7 @@
 pub mod v1;
 pub mod v2;
 
+use derivative::Derivative;
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 
@@ -8,6 +9,21 @@ use crate::config::{
     GenerateConfig, Output, Resource, SourceConfig, SourceContext, SourceDescription,
 };
 
+/// Listening mode for the `vector` source.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Mode {
+    /// Listen on TCP, at `address`.
+    #[derivative(Default)]
+    Tcp,
+
+    /// Listen on a Unix domain socket, in stream mode, at `path`.
+    #[cfg(unix)]
+    Unix,
+}
+
 /// Marker type for the version one of the configuration for the `vector` source.
 #[configurable_component]
 #[derive(Clone, Debug)]
@@ -1,36 +1,66 @@
-use bytes::Bytes;
+#[cfg(unix)]
+use std::path::PathBuf;
+
+use bytes::{Bytes, BytesMut};
 use codecs::{
-    decoding::{self, Deserializer, Framer},
+    decoding::{self, format::Deserializer as _, BoxedFramingError, Deserializer, Framer},
     LengthDelimitedDecoder,
 };
 use prost::Message;
 use smallvec::{smallvec, SmallVec};
+use tokio_util::codec::Decoder as _;
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 use vector_core::ByteSizeOf;
 
 use crate::{
     codecs::Decoder,
-    config::{DataType, GenerateConfig, Output, Resource, SourceContext},
+    config::{AcknowledgementsConfig, DataType, GenerateConfig, Output, Resource, SourceContext},
     event::{proto, Event},
-    internal_events::{BytesReceived, OldEventsReceived, VectorProtoDecodeError},
+    internal_events::{
+        BytesReceived, DecoderDeserializeFailed, DecoderFramingFailed, OldEventsReceived,
+        VectorProtoDecodeError,
+    },
+    proto::v1::{AckStatus, FrameCompression, HANDSHAKE_MARKER},
+    serde::bool_or_struct,
     sources::{
-        util::{SocketListenAddr, TcpNullAcker, TcpSource},
+        util::{SocketListenAddr, TcpSource, TcpSourceAck, TcpSourceAcker},
         Source,
     },
     tcp::TcpKeepaliveConfig,
-    tls::{MaybeTlsSettings, TlsSourceConfig},
+    tls::{MaybeTlsSettings, TlsClientAuthorization, TlsSourceConfig},
 };
 
+use super::Mode;
+#[cfg(unix)]
+use crate::sources::util::build_unix_stream_source;
+
 /// Configuration for version one of the `vector` source.
 #[configurable_component]
 #[derive(Clone, Debug)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct VectorConfig {
+    #[configurable(derived)]
+    #[serde(default)]
+    mode: Mode,
+
     /// The address to listen for connections on.
     ///
-    /// It _must_ include a port.
-    address: SocketListenAddr,
+    /// It _must_ include a port. Required when `mode` is `tcp`.
+    address: Option<SocketListenAddr>,
+
+    /// The Unix socket path to listen for connections on.
+    ///
+    /// This should be an absolute path. Required when `mode` is `unix`.
+    #[cfg(unix)]
+    path: Option<PathBuf>,
+
+    /// Unix file mode bits to be applied to the unix socket file as its designated file permissions.
+    ///
+    /// Note that the file mode value can be specified in any numeric format supported by your configuration
+    /// language, but it is most intuitive to use an octal number.
+    #[cfg(unix)]
+    socket_file_mode: Option<u32>,
 
     #[configurable(derived)]
     keepalive: Option<TcpKeepaliveConfig>,
@@ -46,6 +76,27 @@ pub(crate) struct VectorConfig {
 
     #[configurable(derived)]
     tls: Option<TlsSourceConfig>,
+
+    /// Whether to expect each connection to start with a PROXY protocol header.
+    ///
+    /// Load balancers such as HAProxy send this header to pass on the address of the original client,
+    /// which is then used as the peer address of the connection. Both version 1 and 2 of the protocol
+    /// are supported. Connections not starting with a valid header are closed. Only used when `mode`
+    /// is `tcp`.
+    #[serde(default)]
+    proxy_protocol: bool,
+
+    /// Whether to accept compressed event frames.
+    ///
+    /// The compression is negotiated with each `vector` sink connecting to this source, which
+    /// sends uncompressed frames if this is disabled. Compression is not supported when `mode` is
+    /// `unix`.
+    #[serde(default)]
+    compression: bool,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
 }
 
 const fn default_shutdown_timeout_secs() -> u64 {
@@ -60,13 +111,50 @@ impl VectorConfig {
         self.tls = config;
     }
 
-    pub const fn from_address(address: SocketListenAddr) -> Self {
+    #[cfg(test)]
+    #[allow(unused)]
+    pub fn set_compression(&mut self, compression: bool) {
+        self.compression = compression;
+    }
+
+    #[cfg(test)]
+    #[allow(unused)]
+    pub fn set_acknowledgements(&mut self, config: AcknowledgementsConfig) {
+        self.acknowledgements = config;
+    }
+
+    pub fn from_address(address: SocketListenAddr) -> Self {
         Self {
-            address,
+            mode: Mode::Tcp,
+            address: Some(address),
+            #[cfg(unix)]
+            path: None,
+            #[cfg(unix)]
+            socket_file_mode: None,
+            keepalive: None,
+            shutdown_timeout_secs: default_shutdown_timeout_secs(),
+            tls: None,
+            receive_buffer_bytes: None,
+            compression: false,
+            proxy_protocol: false,
+            acknowledgements: AcknowledgementsConfig::default(),
+        }
+    }
+
+    #[cfg(all(test, unix))]
+    pub fn from_path(path: PathBuf) -> Self {
+        Self {
+            mode: Mode::Unix,
+            address: None,
+            path: Some(path),
+            socket_file_mode: None,
             keepalive: None,
             shutdown_timeout_secs: default_shutdown_timeout_secs(),
             tls: None,
             receive_buffer_bytes: None,
+            compression: false,
+            proxy_protocol: false,
+            acknowledgements: AcknowledgementsConfig::default(),
         }
     }
 }
@@ -82,24 +170,53 @@ impl GenerateConfig for VectorConfig {
 
 impl VectorConfig {
     pub(super) async fn build(&self, cx: SourceContext) -> crate::Result<Source> {
-        let vector = VectorSource;
+        let vector = VectorSource {
+            acknowledgements: cx.do_acknowledgements(&self.acknowledgements),
+            compression: self.compression,
+        };
+
+        let address = match self.mode {
+            Mode::Tcp => self
+                .address
+                .ok_or("The `address` option is required when `mode` is `tcp`.")?,
+            // Event frames are not acknowledged over Unix sockets.
+            #[cfg(unix)]
+            Mode::Unix => {
+                let path = self
+                    .path
+                    .clone()
+                    .ok_or("The `path` option is required when `mode` is `unix`.")?;
+                return build_unix_stream_source(
+                    path,
+                    self.socket_file_mode,
+                    decoder("unix"),
+                    |_, _| {},
+                    cx.shutdown,
+                    cx.out,
+                );
+            }
+        };
+
         let tls_config = self.tls.as_ref().map(|tls| tls.tls_config.clone());
         let tls_client_metadata_key = self
             .tls
             .as_ref()
             .and_then(|tls| tls.client_metadata_key.clone());
 
+        let tls_client_authorization = TlsClientAuthorization::from_config(&self.tls)?;
         let tls = MaybeTlsSettings::from_config(&tls_config, true)?;
         vector.run(
-            self.address,
+            address,
             self.keepalive,
             self.shutdown_timeout_secs,
             tls,
             tls_client_metadata_key,
+            tls_client_authorization,
             self.receive_buffer_bytes,
             cx,
-            false.into(),
+            self.acknowledgements,
             None,
+            self.proxy_protocol,
         )
     }
 
@@ -112,12 +229,24 @@ impl VectorConfig {
     }
 
     pub(super) fn resources(&self) -> Vec<Resource> {
-        vec![self.address.into()]
+        match (self.mode, self.address) {
+            (Mode::Tcp, Some(address)) => vec![address.into()],
+            _ => vec![],
+        }
     }
 }
 
+fn decoder(protocol: &'static str) -> Decoder {
+    Decoder::new(
+        Framer::LengthDelimited(LengthDelimitedDecoder::new()),
+        Deserializer::Boxed(Box::new(VectorDeserializer { protocol })),
+    )
+}
+
 #[derive(Debug, Clone)]
-struct VectorDeserializer;
+struct VectorDeserializer {
+    protocol: &'static str,
+}
 
 impl decoding::format::Deserializer for VectorDeserializer {
     fn parse(
@@ -128,7 +257,7 @@ impl decoding::format::Deserializer for VectorDeserializer {
         let byte_size = bytes.len();
         emit!(BytesReceived {
             byte_size,
-            protocol: "tcp",
+            protocol: self.protocol,
         });
 
         match proto::EventWrapper::decode(bytes).map(Event::from) {
@@ -147,24 +276,178 @@ impl decoding::format::Deserializer for VectorDeserializer {
     }
 }
 
+/// A frame received from the `vector` sink.
+enum VectorFrame {
+    /// The events of an event frame.
+    Events(SmallVec<[Event; 1]>),
+
+    /// A handshake, along with the compression accepted for the rest of the connection.
+    Handshake(FrameCompression),
+}
+
+impl From<VectorFrame> for SmallVec<[Event; 1]> {
+    fn from(frame: VectorFrame) -> Self {
+        match frame {
+            VectorFrame::Events(events) => events,
+            VectorFrame::Handshake(_) => SmallVec::new(),
+        }
+    }
+}
+
+/// Decodes the frames of a TCP connection, which may start with a compression handshake.
+#[derive(Debug, Clone)]
+struct VectorDecoder {
+    framer: LengthDelimitedDecoder,
+    deserializer: VectorDeserializer,
+    accept_compression: bool,
+    /// The compression negotiated by the handshake, if any. Once negotiated, each frame starts
+    /// with the compression applied to the rest of the frame.
+    negotiated: Option<FrameCompression>,
+}
+
+impl VectorDecoder {
+    fn handle_framing_result(
+        &mut self,
+        frame: Result<Option<Bytes>, BoxedFramingError>,
+    ) -> Result<Option<(VectorFrame, usize)>, decoding::Error> {
+        let frame = frame.map_err(|error| {
+            emit!(DecoderFramingFailed { error: &error });
+            decoding::Error::FramingError(error)
+        })?;
+
+        let frame = match frame {
+            Some(frame) => frame,
+            None => return Ok(None),
+        };
+
+        let byte_size = frame.len();
+        let frame = match self.negotiated {
+            None if frame.first() == Some(&HANDSHAKE_MARKER) => {
+                let accepted = frame
+                    .get(1)
+                    .copied()
+                    .and_then(FrameCompression::from_byte)
+                    .filter(|_| self.accept_compression)
+                    .unwrap_or(FrameCompression::None);
+                self.negotiated = Some(accepted);
+                VectorFrame::Handshake(accepted)
+            }
+            None => VectorFrame::Events(self.parse(frame)?),
+            Some(negotiated) => {
+                let payload = self.decompress(frame, negotiated)?;
+                VectorFrame::Events(self.parse(payload)?)
+            }
+        };
+
+        Ok(Some((frame, byte_size)))
+    }
+
+    fn decompress(
+        &self,
+        frame: Bytes,
+        negotiated: FrameCompression,
+    ) -> Result<Bytes, decoding::Error> {
+        let compression = frame.first().copied().and_then(FrameCompression::from_byte);
+        let result: crate::Result<Bytes> = match compression {
+            Some(compression)
+                if compression == FrameCompression::None || compression == negotiated =>
+            {
+                compression
+                    .decompress(&frame[1..])
+                    .map(Bytes::from)
+                    .map_err(Into::into)
+            }
+            _ => Err(format!(
+                "Frame compression {:?} does not match the negotiated compression {:?}",
+                compression, negotiated
+            )
+            .into()),
+        };
+
+        result.map_err(|error| {
+            emit!(DecoderDeserializeFailed { error: &error });
+            decoding::Error::ParsingError(error)
+        })
+    }
+
+    fn parse(&self, payload: Bytes) -> Result<SmallVec<[Event; 1]>, decoding::Error> {
+        self.deserializer
+            .parse(payload, LogNamespace::Legacy)
+            .map_err(|error| {
+                emit!(DecoderDeserializeFailed { error: &error });
+                decoding::Error::ParsingError(error)
+            })
+    }
+}
+
+impl tokio_util::codec::Decoder for VectorDecoder {
+    type Item = (VectorFrame, usize);
+    type Error = decoding::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let frame = self.framer.decode(buf);
+        self.handle_framing_result(frame)
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let frame = self.framer.decode_eof(buf);
+        self.handle_framing_result(frame)
+    }
+}
+
 #[derive(Debug, Clone)]
-struct VectorSource;
+struct VectorSource {
+    acknowledgements: bool,
+    compression: bool,
+}
 
 impl TcpSource for VectorSource {
     type Error = decoding::Error;
-    type Item = SmallVec<[Event; 1]>;
-    type Decoder = Decoder;
-    type Acker = TcpNullAcker;
+    type Item = VectorFrame;
+    type Decoder = VectorDecoder;
+    type Acker = VectorAcker;
 
     fn decoder(&self) -> Self::Decoder {
-        Decoder::new(
-            Framer::LengthDelimited(LengthDelimitedDecoder::new()),
-            Deserializer::Boxed(Box::new(VectorDeserializer)),
-        )
+        VectorDecoder {
+            framer: LengthDelimitedDecoder::new(),
+            deserializer: VectorDeserializer { protocol: "tcp" },
+            accept_compression: self.compression,
+            negotiated: None,
+        }
     }
 
-    fn build_acker(&self, _: &[Self::Item]) -> Self::Acker {
-        TcpNullAcker
+    fn build_acker(&self, frames: &[Self::Item]) -> Self::Acker {
+        VectorAcker {
+            acknowledgements: self.acknowledgements,
+            handshake: frames.iter().find_map(|frame| match frame {
+                VectorFrame::Handshake(compression) => Some(*compression),
+                VectorFrame::Events(_) => None,
+            }),
+        }
+    }
+}
+
+/// Answers the compression handshake with the accepted compression, and acknowledges each event
+/// frame with its delivery status, if acknowledgements are enabled.
+struct VectorAcker {
+    acknowledgements: bool,
+    handshake: Option<FrameCompression>,
+}
+
+impl TcpSourceAcker for VectorAcker {
+    fn build_ack(self, ack: TcpSourceAck) -> Option<Bytes> {
+        if let Some(compression) = self.handshake {
+            return Some(Bytes::copy_from_slice(&[compression.as_byte()]));
+        }
+
+        self.acknowledgements.then(|| {
+            let status = match ack {
+                TcpSourceAck::Ack => AckStatus::Delivered,
+                TcpSourceAck::Error => AckStatus::Errored,
+                TcpSourceAck::Reject => AckStatus::Rejected,
+            };
+            Bytes::copy_from_slice(&[status.as_byte()])
+        })
     }
 }
 
@@ -190,13 +473,13 @@ mod test {
 
     use super::VectorConfig;
     use crate::{
-        config::{ComponentKey, GlobalOptions, SourceContext},
+        config::{ComponentKey, GlobalOptions, SinkContext, SourceContext},
         event::{
             metric::{MetricKind, MetricValue},
-            Event, LogEvent, Metric,
+            BatchNotifier, BatchStatus, Event, EventStatus, LogEvent, Metric,
         },
         shutdown::ShutdownSignal,
-        sinks::vector::v1::VectorConfig as SinkConfig,
+        sinks::vector::{v1::VectorConfig as SinkConfig, VectorCompression},
         test_util::{
             collect_ready,
             components::{assert_source_compliance, SOCKET_PUSH_SOURCE_TAGS},
@@ -221,7 +504,7 @@ mod test {
         tokio::spawn(server);
         wait_for_tcp(addr).await;
 
-        let (sink, _) = sink.build().await.unwrap();
+        let (sink, _) = sink.build(SinkContext::new_test()).await.unwrap();
 
         let events = vec![
             Event::Log(LogEvent::from("test")),
@@ -272,6 +555,7 @@ mod test {
                     config.set_tls(Some(TlsSourceConfig {
                         tls_config: TlsEnableableConfig::test_config(),
                         client_metadata_key: None,
+                        ..Default::default()
                     }));
                     config
                 },
@@ -292,6 +576,64 @@ mod test {
         .await;
     }
 
+    #[tokio::test]
+    async fn it_works_with_compressing_vector_sink() {
+        for (accept_compression, compression) in [
+            (true, VectorCompression::Gzip),
+            (true, VectorCompression::Zstd),
+            // The source negotiates uncompressed frames.
+            (false, VectorCompression::Zstd),
+        ] {
+            let addr = next_addr();
+            stream_test(
+                addr,
+                {
+                    let mut config = VectorConfig::from_address(addr.into());
+                    config.set_compression(accept_compression);
+                    config
+                },
+                {
+                    let mut config = SinkConfig::from_address(format!("localhost:{}", addr.port()));
+                    config.set_compression(compression);
+                    config
+                },
+            )
+            .await;
+        }
+    }
+
+    #[tokio::test]
+    async fn it_acknowledges_events_from_vector_sink() {
+        let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let addr = next_addr();
+
+        let mut source = VectorConfig::from_address(addr.into());
+        source.set_acknowledgements(true.into());
+        let server = source
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        wait_for_tcp(addr).await;
+
+        let mut sink = SinkConfig::from_address(format!("localhost:{}", addr.port()));
+        sink.set_acknowledgements(true.into());
+        let (sink, _) = sink.build(SinkContext::new_test()).await.unwrap();
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let events = vec![
+            Event::Log(LogEvent::from("acknowledged")).with_batch_notifier(&batch),
+            Event::Log(LogEvent::from("events")).with_batch_notifier(&batch),
+        ];
+        drop(batch);
+
+        sink.run_events(events).await.unwrap();
+        assert_eq!(receiver.await, BatchStatus::Delivered);
+
+        let output = collect_ready(rx).await;
+        assert_eq!(output.len(), 2);
+    }
+
     #[tokio::test]
     async fn it_closes_stream_on_garbage_data() {
         trace_init();
@@ -380,4 +722,49 @@ mod test {
         let output = collect_ready(rx).await;
         assert_event_data_eq!([Event::from(event)][..], output.as_slice());
     }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn it_processes_stream_of_protobufs_over_unix_socket() {
+        trace_init();
+        let (tx, rx) = SourceSender::new_test();
+        let path = tempfile::tempdir().unwrap().into_path().join("vector_test");
+
+        let config = VectorConfig::from_path(path.clone());
+        assert!(config.resources().is_empty());
+
+        let server = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+
+        while std::os::unix::net::UnixStream::connect(&path).is_err() {
+            tokio::task::yield_now().await;
+        }
+
+        let event = proto::EventWrapper::from(Event::Log(LogEvent::from("short")));
+        let mut out = BytesMut::with_capacity(event.encoded_len());
+        event.encode(&mut out).unwrap();
+
+        let stream = tokio::net::UnixStream::connect(&path).await.unwrap();
+        let mut sink = FramedWrite::new(stream, LengthDelimitedCodec::new());
+        sink.send(out.into()).await.unwrap();
+        sink.into_inner().shutdown().await.unwrap();
+
+        sleep(Duration::from_millis(50)).await;
+
+        let output = collect_ready(rx).await;
+        assert_event_data_eq!([Event::from(event)][..], output.as_slice());
+    }
+
+    #[tokio::test]
+    async fn it_requires_an_address_in_tcp_mode() {
+        let config: VectorConfig = toml::from_str(r#"mode = "tcp""#).unwrap();
+        let (tx, _rx) = SourceSender::new_test();
+        assert!(config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .is_err());
+    }
 }
@@ -1,6 +1,8 @@
 use std::net::SocketAddr;
+#[cfg(unix)]
+use std::path::PathBuf;
 
-use futures::TryFutureExt;
+use futures::{FutureExt, TryFutureExt};
 use tokio::net::TcpStream;
 use tonic::{
     transport::{server::Connected, Certificate},
@@ -18,10 +20,14 @@ use crate::{
     proto::vector as proto,
     serde::bool_or_struct,
     sources::{util::grpc::run_grpc_server, Source},
-    tls::{MaybeTlsIncomingStream, MaybeTlsSettings, TlsEnableableConfig},
+    tls::{MaybeTlsIncomingStream, MaybeTlsSettings, TlsClientAuthorization, TlsSourceConfig},
     SourceSender,
 };
 
+use super::Mode;
+#[cfg(unix)]
+use crate::sources::util::grpc::run_grpc_server_unix;
+
 #[derive(Debug, Clone)]
 pub struct Service {
     pipeline: SourceSender,
@@ -93,10 +99,27 @@ async fn handle_batch_status(receiver: Option<BatchStatusReceiver>) -> Result<()
 #[derive(Clone, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct VectorConfig {
+    #[configurable(derived)]
+    #[serde(default)]
+    pub mode: Mode,
+
     /// The address to listen for connections on.
     ///
-    /// It _must_ include a port.
-    pub address: SocketAddr,
+    /// It _must_ include a port. Required when `mode` is `tcp`.
+    pub address: Option<SocketAddr>,
+
+    /// The Unix socket path to listen for connections on.
+    ///
+    /// This should be an absolute path. Required when `mode` is `unix`.
+    #[cfg(unix)]
+    pub path: Option<PathBuf>,
+
+    /// Unix file mode bits to be applied to the unix socket file as its designated file permissions.
+    ///
+    /// Note that the file mode value can be specified in any numeric format supported by your configuration
+    /// language, but it is most intuitive to use an octal number.
+    #[cfg(unix)]
+    pub socket_file_mode: Option<u32>,
 
     /// The timeout, in seconds, before a connection is forcefully closed during shutdown.
     #[serde(default = "default_shutdown_timeout_secs")]
@@ -104,7 +127,7 @@ pub struct VectorConfig {
 
     #[configurable(derived)]
     #[serde(default)]
-    tls: Option<TlsEnableableConfig>,
+    tls: Option<TlsSourceConfig>,
 
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
@@ -118,7 +141,12 @@ const fn default_shutdown_timeout_secs() -> u64 {
 impl GenerateConfig for VectorConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
-            address: "0.0.0.0:6000".parse().unwrap(),
+            mode: Mode::Tcp,
+            address: Some("0.0.0.0:6000".parse().unwrap()),
+            #[cfg(unix)]
+            path: None,
+            #[cfg(unix)]
+            socket_file_mode: None,
             shutdown_timeout_secs: default_shutdown_timeout_secs(),
             tls: None,
             acknowledgements: Default::default(),
@@ -129,7 +157,6 @@ impl GenerateConfig for VectorConfig {
 
 impl VectorConfig {
     pub(super) async fn build(&self, cx: SourceContext) -> crate::Result<Source> {
-        let tls_settings = MaybeTlsSettings::from_config(&self.tls, true)?;
         let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
         let service = proto::Server::new(Service {
             pipeline: cx.out,
@@ -137,12 +164,46 @@ impl VectorConfig {
         })
         .accept_gzip();
 
-        let source =
-            run_grpc_server(self.address, tls_settings, service, cx.shutdown).map_err(|error| {
-                error!(message = "Source future failed.", %error);
-            });
+        let source = match self.mode {
+            Mode::Tcp => {
+                let address = self
+                    .address
+                    .ok_or("The `address` option is required when `mode` is `tcp`.")?;
+                if self
+                    .tls
+                    .as_ref()
+                    .map_or(false, |tls| tls.client_metadata_key.is_some())
+                {
+                    return Err(
+                        "The `tls.client_metadata_key` option isn't supported by version 2 of the `vector` source."
+                            .into(),
+                    );
+                }
+                let tls_config = self.tls.as_ref().map(|tls| tls.tls_config.clone());
+                let tls_client_authorization = TlsClientAuthorization::from_config(&self.tls)?;
+                let tls_settings = MaybeTlsSettings::from_config(&tls_config, true)?;
+                run_grpc_server(
+                    address,
+                    tls_settings,
+                    tls_client_authorization,
+                    service,
+                    cx.shutdown,
+                )
+                .boxed()
+            }
+            #[cfg(unix)]
+            Mode::Unix => {
+                let path = self
+                    .path
+                    .clone()
+                    .ok_or("The `path` option is required when `mode` is `unix`.")?;
+                run_grpc_server_unix(path, self.socket_file_mode, service, cx.shutdown).boxed()
+            }
+        };
 
-        Ok(Box::pin(source))
+        Ok(Box::pin(source.map_err(|error| {
+            error!(message = "Source future failed.", %error);
+        })))
     }
 
     pub(super) fn outputs(&self) -> Vec<Output> {
@@ -154,7 +215,10 @@ impl VectorConfig {
     }
 
     pub(super) fn resources(&self) -> Vec<Resource> {
-        vec![Resource::tcp(self.address)]
+        match (self.mode, self.address) {
+            (Mode::Tcp, Some(address)) => vec![Resource::tcp(address)],
+            _ => vec![],
+        }
     }
 }
 
@@ -186,6 +250,9 @@ impl Connected for MaybeTlsIncomingStream<TcpStream> {
 #[cfg(feature = "sinks-vector")]
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
+    use futures::StreamExt;
     use vector_common::assert_event_data_eq;
 
     use super::*;
@@ -196,7 +263,7 @@ mod tests {
             self,
             components::{assert_source_compliance, SOURCE_TAGS},
         },
-        SourceSender,
+        tls, SourceSender,
     };
 
     #[tokio::test]
@@ -266,4 +333,117 @@ mod tests {
         })
         .await;
     }
+
+    #[tokio::test]
+    async fn receive_zstd_compressed_message() {
+        assert_source_compliance(&SOURCE_TAGS, async {
+            let addr = test_util::next_addr();
+            let config = format!(r#"address = "{}""#, addr);
+            let source: VectorConfig = toml::from_str(&config).unwrap();
+
+            let (tx, rx) = SourceSender::new_test();
+            let server = source
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(server);
+            test_util::wait_for_tcp(addr).await;
+
+            let config = format!(
+                r#"address = "{}"
+            compression = "zstd""#,
+                addr
+            );
+            let sink: SinkConfig = toml::from_str(&config).unwrap();
+            let (sink, _) = sink.build(SinkContext::new_test()).await.unwrap();
+
+            let (events, stream) = test_util::random_events_with_stream(100, 100, None);
+            sink.run(stream).await.unwrap();
+
+            let output = test_util::collect_ready(rx).await;
+            assert_event_data_eq!(events, output);
+        })
+        .await;
+    }
+
+    async fn receive_message_with_tls_client_allowlist(allowed_subject: &str) -> Option<Event> {
+        let addr = test_util::next_addr();
+        let config = format!(
+            r#"address = "{}"
+            tls.enabled = true
+            tls.verify_certificate = true
+            tls.crt_file = "{}"
+            tls.key_file = "{}"
+            tls.ca_file = "{}"
+            tls.client_allowed_subjects = ["{}"]"#,
+            addr,
+            tls::TEST_PEM_CRT_PATH,
+            tls::TEST_PEM_KEY_PATH,
+            tls::TEST_PEM_CA_PATH,
+            allowed_subject,
+        );
+        let source: VectorConfig = toml::from_str(&config).unwrap();
+
+        let (tx, mut rx) = SourceSender::new_test();
+        let server = source
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        test_util::wait_for_tcp(addr).await;
+
+        let config = format!(
+            r#"address = "https://{}"
+            tls.enabled = true
+            tls.crt_file = "{}"
+            tls.key_file = "{}"
+            tls.ca_file = "{}"
+            tls.verify_hostname = false"#,
+            addr,
+            tls::TEST_PEM_CLIENT_CRT_PATH,
+            tls::TEST_PEM_CLIENT_KEY_PATH,
+            tls::TEST_PEM_CA_PATH,
+        );
+        let sink: SinkConfig = toml::from_str(&config).unwrap();
+        let (sink, _) = sink.build(SinkContext::new_test()).await.unwrap();
+
+        // The sink keeps retrying the requests of a rejected client, so it's never awaited.
+        let (_, stream) = test_util::random_events_with_stream(100, 1, None);
+        tokio::spawn(sink.run(stream));
+
+        tokio::time::timeout(Duration::from_secs(3), rx.next())
+            .await
+            .ok()
+            .flatten()
+    }
+
+    #[tokio::test]
+    async fn receive_message_from_allowed_client() {
+        assert!(receive_message_with_tls_client_allowlist("localhost")
+            .await
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn reject_disallowed_client() {
+        assert!(receive_message_with_tls_client_allowlist("other")
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn reject_client_metadata_key() {
+        let config = format!(
+            r#"address = "{}"
+            tls.client_metadata_key = "tls_client""#,
+            test_util::next_addr(),
+        );
+        let source: VectorConfig = toml::from_str(&config).unwrap();
+
+        let (tx, _) = SourceSender::new_test();
+        assert!(source
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .is_err());
+    }
 }
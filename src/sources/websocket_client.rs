@@ -0,0 +1,315 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use codecs::decoding::{DecodingErrorMode, DeserializerConfig, FramingConfig};
+use futures::{future, SinkExt, StreamExt};
+use snafu::ResultExt;
+use tokio::time;
+use tokio_tungstenite::tungstenite::protocol::Message;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    codecs::{Decoder, DecodingConfig},
+    common::websocket::{is_closed, ConnectSnafu, WebSocketConnector},
+    config::{GenerateConfig, Output, SourceConfig, SourceContext, SourceDescription},
+    internal_events::{
+        BytesReceived, ConnectionOpen, OpenGauge, StreamClosedError, WsConnectionError,
+        WsConnectionShutdown,
+    },
+    serde::{default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    sources::util::decode_message,
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+    SourceSender,
+};
+
+/// Configuration for the `websocket_client` source.
+#[configurable_component(source)]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct WebSocketClientConfig {
+    /// The WebSocket URI to connect to, such as `wss://stream.example.com/feed`.
+    ///
+    /// TLS is used for `wss://` URIs.
+    uri: String,
+
+    /// A text message sent to the server after each connection, such as the subscription
+    /// request of the feed.
+    subscribe_message: Option<String>,
+
+    /// The interval between the pings sent to the server to keep the connection alive, in seconds.
+    ///
+    /// By default, no pings are sent.
+    ping_interval_secs: Option<u64>,
+
+    #[configurable(derived)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    on_error: DecodingErrorMode,
+}
+
+inventory::submit! {
+    SourceDescription::new::<WebSocketClientConfig>("websocket_client")
+}
+
+impl GenerateConfig for WebSocketClientConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            uri = "wss://stream.example.com/feed"
+            subscribe_message = '{"op": "subscribe", "channel": "trades"}'"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "websocket_client")]
+impl SourceConfig for WebSocketClientConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let tls = MaybeTlsSettings::from_config(&self.tls, false).context(ConnectSnafu)?;
+        let connector = WebSocketConnector::new(self.uri.clone(), tls)?;
+        let decoder = DecodingConfig::new(
+            self.framing.clone(),
+            self.decoding.clone(),
+            LogNamespace::Legacy,
+        )
+        .with_error_mode(self.on_error)
+        .build()?;
+
+        let source = WebSocketClientSource {
+            connector,
+            decoder,
+            subscribe_message: self.subscribe_message.clone(),
+            ping_interval: self
+                .ping_interval_secs
+                .filter(|secs| *secs > 0)
+                .map(Duration::from_secs),
+        };
+        Ok(Box::pin(source.run(cx.out, cx.shutdown)))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(
+            self.on_error.output_type(self.decoding.output_type()),
+        )]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "websocket_client"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+struct WebSocketClientSource {
+    connector: WebSocketConnector,
+    decoder: Decoder,
+    subscribe_message: Option<String>,
+    ping_interval: Option<Duration>,
+}
+
+impl WebSocketClientSource {
+    /// Receives the messages of the server until shutdown, reconnecting with backoff whenever the
+    /// connection is closed or fails.
+    async fn run(self, mut out: SourceSender, mut shutdown: ShutdownSignal) -> Result<(), ()> {
+        loop {
+            let ws_stream = tokio::select! {
+                ws_stream = self.connector.connect_backoff() => ws_stream,
+                _ = &mut shutdown => return Ok(()),
+            };
+            let _open_token = OpenGauge::new().open(|count| emit!(ConnectionOpen { count }));
+            let (mut ws_sink, mut ws_stream) = ws_stream.split();
+
+            if let Some(message) = &self.subscribe_message {
+                if let Err(error) = ws_sink.send(Message::text(message.clone())).await {
+                    emit!(WsConnectionError { error });
+                    continue;
+                }
+            }
+
+            let mut ping_interval = self.ping_interval.map(time::interval);
+            loop {
+                let result = tokio::select! {
+                    _ = &mut shutdown => {
+                        let _ = ws_sink.close().await;
+                        return Ok(());
+                    },
+
+                    _ = tick(&mut ping_interval) => {
+                        ws_sink.send(Message::Ping(Vec::new())).await
+                    },
+
+                    message = ws_stream.next() => match message {
+                        // Pongs are sent automatically by tungstenite during reading from the stream.
+                        Some(Ok(Message::Text(text))) => {
+                            self.handle_message(text.as_bytes(), &mut out).await?;
+                            Ok(())
+                        }
+                        Some(Ok(Message::Binary(data))) => {
+                            self.handle_message(&data, &mut out).await?;
+                            Ok(())
+                        }
+                        Some(Ok(_)) => Ok(()),
+                        Some(Err(error)) => Err(error),
+                        None => {
+                            emit!(WsConnectionShutdown);
+                            break;
+                        }
+                    },
+                };
+
+                if let Err(error) = result {
+                    if is_closed(&error) {
+                        emit!(WsConnectionShutdown);
+                    } else {
+                        emit!(WsConnectionError { error });
+                    }
+                    break;
+                }
+            }
+        }
+    }
+
+    async fn handle_message(&self, message: &[u8], out: &mut SourceSender) -> Result<(), ()> {
+        emit!(BytesReceived {
+            byte_size: message.len(),
+            protocol: "websocket",
+        });
+
+        let events = decode_message(
+            self.decoder.clone(),
+            "websocket_client",
+            message,
+            Some(Utc::now()),
+            &None,
+        )
+        .collect::<Vec<_>>();
+        let count = events.len();
+        out.send_batch(events).await.map_err(|error| {
+            emit!(StreamClosedError { error, count });
+        })
+    }
+}
+
+/// Waits for the next tick of the interval, or forever without one.
+async fn tick(interval: &mut Option<time::Interval>) {
+    match interval {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => future::pending().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::Stream;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::accept_async;
+
+    use super::*;
+    use crate::{
+        config::log_schema,
+        event::Event,
+        test_util::{collect_n, next_addr, trace_init},
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WebSocketClientConfig>();
+    }
+
+    async fn run_source(uri: String) -> impl Stream<Item = Event> + Unpin {
+        let config = WebSocketClientConfig {
+            uri,
+            subscribe_message: Some("subscribe".into()),
+            ..Default::default()
+        };
+        let (tx, rx) = SourceSender::new_test();
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        rx
+    }
+
+    #[tokio::test]
+    async fn receives_messages_after_subscribing() {
+        trace_init();
+
+        let addr = next_addr();
+        let listener = TcpListener::bind(addr).await.unwrap();
+        let rx = run_source(format!("ws://{}", addr)).await;
+
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws_stream = accept_async(stream).await.unwrap();
+        let subscription = ws_stream.next().await.unwrap().unwrap();
+        assert_eq!(subscription, Message::text("subscribe"));
+        ws_stream.send(Message::text("first")).await.unwrap();
+        ws_stream
+            .send(Message::binary(b"second".to_vec()))
+            .await
+            .unwrap();
+
+        let events = collect_n(rx, 2).await;
+        let messages = events
+            .iter()
+            .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["first", "second"]);
+        assert_eq!(
+            events[0].as_log()[log_schema().source_type_key()],
+            "websocket_client".into()
+        );
+    }
+
+    #[tokio::test]
+    async fn reconnects_and_subscribes_again() {
+        trace_init();
+
+        let addr = next_addr();
+        let listener = TcpListener::bind(addr).await.unwrap();
+        let rx = run_source(format!("ws://{}", addr)).await;
+
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws_stream = accept_async(stream).await.unwrap();
+        assert_eq!(
+            ws_stream.next().await.unwrap().unwrap(),
+            Message::text("subscribe")
+        );
+        ws_stream.close(None).await.unwrap();
+        drop(ws_stream);
+
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws_stream = accept_async(stream).await.unwrap();
+        assert_eq!(
+            ws_stream.next().await.unwrap().unwrap(),
+            Message::text("subscribe")
+        );
+        ws_stream.send(Message::text("again")).await.unwrap();
+
+        let events = collect_n(rx, 1).await;
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "again".into()
+        );
+    }
+}
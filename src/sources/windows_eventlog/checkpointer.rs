@@ -0,0 +1,74 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+const CHECKPOINT_FILENAME: &str = "bookmarks.json";
+const CHECKPOINT_TMP_FILENAME: &str = "bookmarks.new.json";
+
+/// Stores the bookmarks of the last events read from the channels, which are written to the
+/// data directory when flushed.
+pub(super) struct Checkpointer {
+    path: PathBuf,
+    tmp_path: PathBuf,
+    bookmarks: BTreeMap<String, String>,
+    dirty: bool,
+}
+
+impl Checkpointer {
+    pub(super) fn load(data_dir: &Path) -> io::Result<Self> {
+        let path = data_dir.join(CHECKPOINT_FILENAME);
+        let bookmarks = match fs::read(&path) {
+            Ok(contents) => serde_json::from_slice(&contents)?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(error) => return Err(error),
+        };
+        Ok(Self {
+            path,
+            tmp_path: data_dir.join(CHECKPOINT_TMP_FILENAME),
+            bookmarks,
+            dirty: false,
+        })
+    }
+
+    pub(super) fn get(&self, channel: &str) -> Option<&str> {
+        self.bookmarks.get(channel).map(String::as_str)
+    }
+
+    pub(super) fn set(&mut self, channel: String, bookmark: String) {
+        self.bookmarks.insert(channel, bookmark);
+        self.dirty = true;
+    }
+
+    /// Writes the bookmarks, if they changed since they were last written.
+    pub(super) fn flush(&mut self) -> io::Result<()> {
+        if self.dirty {
+            // Written to a temporary file first, so that the bookmarks aren't lost when Vector
+            // stops while writing them.
+            fs::write(&self.tmp_path, serde_json::to_vec(&self.bookmarks)?)?;
+            fs::rename(&self.tmp_path, &self.path)?;
+            self.dirty = false;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn persists_bookmarks() {
+        let data_dir = tempfile::tempdir().unwrap();
+
+        let mut checkpointer = Checkpointer::load(data_dir.path()).unwrap();
+        assert_eq!(checkpointer.get("System"), None);
+        checkpointer.set("System".into(), "<BookmarkList/>".into());
+        checkpointer.flush().unwrap();
+
+        let checkpointer = Checkpointer::load(data_dir.path()).unwrap();
+        assert_eq!(checkpointer.get("System"), Some("<BookmarkList/>"));
+        assert_eq!(checkpointer.get("Application"), None);
+    }
+}
@@ -0,0 +1,366 @@
+//! The `windows_eventlog` source.
+//!
+//! Each channel is read by a subscription of the Event Log API, pulled from a blocking task
+//! whenever the Event Log service signals new events. The events are rendered as XML, and either
+//! kept as is or converted to the fields of the logs. The bookmark of the last event read from
+//! each channel is stored in the data directory once the events are delivered, so that the
+//! channels are read from where they were left after a restart.
+
+use std::{
+    collections::HashSet,
+    io,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use futures::StreamExt;
+use snafu::{ResultExt, Snafu};
+use tokio::{sync::mpsc, task::spawn_blocking};
+use vector_common::finalizer::OrderedFinalizer;
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, ByteSizeOf};
+
+use self::{
+    checkpointer::Checkpointer,
+    parser::parse_event,
+    subscription::{RawBatch, Subscription},
+};
+use crate::{
+    config::{
+        AcknowledgementsConfig, DataType, GenerateConfig, Output, SourceConfig, SourceContext,
+        SourceDescription,
+    },
+    event::{BatchNotifier, BatchStatus, LogEvent},
+    internal_events::{
+        BytesReceived, OldEventsReceived, StreamClosedError, WindowsEventLogCheckpointError,
+        WindowsEventLogNegativeAcknowledgmentError, WindowsEventLogParseError,
+    },
+    serde::bool_or_struct,
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+mod checkpointer;
+mod parser;
+mod subscription;
+
+/// How often the bookmarks of the channels are written to the data directory.
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("At least one channel must be configured"))]
+    NoChannels,
+    #[snafu(display("Unable to load the bookmarks of the channels: {}", source))]
+    LoadBookmarks { source: io::Error },
+    #[snafu(display("Unable to subscribe to channel {:?}: {}", channel, source))]
+    Subscribe { channel: String, source: io::Error },
+}
+
+/// The format the events are rendered to.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RenderFormat {
+    /// The fields of the events are converted to the fields of the logs, and the message of the
+    /// events is the message of the logs.
+    #[derivative(Default)]
+    Json,
+
+    /// The XML of the events is the message of the logs, and the message of the events is stored
+    /// in the `rendered_message` field.
+    Xml,
+}
+
+/// Configuration for the `windows_eventlog` source.
+#[configurable_component(source)]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct WindowsEventLogConfig {
+    /// The channels to read the events of, such as `System`, `Application` or
+    /// `Microsoft-Windows-Sysmon/Operational`.
+    pub channels: Vec<String>,
+
+    /// The XPath query selecting the events read from the channels, such as
+    /// `*[System[(Level=1 or Level=2)]]`.
+    ///
+    /// By default, all the events are read.
+    #[serde(default = "default_xpath_query")]
+    #[derivative(Default(value = "default_xpath_query()"))]
+    pub xpath_query: String,
+
+    /// Read the events already in the channels that don't have a bookmark yet, instead of only the
+    /// events published after Vector starts.
+    #[serde(default)]
+    pub read_existing_events: bool,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub render_format: RenderFormat,
+
+    /// Render the message of the events with the message files of their publisher.
+    ///
+    /// The message isn't available for the events of the publishers that aren't registered on the
+    /// host.
+    #[serde(default = "crate::serde::default_true")]
+    #[derivative(Default(value = "true"))]
+    pub render_message: bool,
+
+    /// The maximum number of events read from a channel at once.
+    #[serde(default = "default_batch_size")]
+    #[derivative(Default(value = "default_batch_size()"))]
+    pub batch_size: usize,
+
+    /// The directory used to persist the bookmarks of the channels.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is
+    /// running as has write permissions to this directory.
+    #[serde(default)]
+    pub data_dir: Option<PathBuf>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+fn default_xpath_query() -> String {
+    "*".into()
+}
+
+const fn default_batch_size() -> usize {
+    100
+}
+
+inventory::submit! {
+    SourceDescription::new::<WindowsEventLogConfig>("windows_eventlog")
+}
+
+impl GenerateConfig for WindowsEventLogConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"channels = ["Application", "System"]"#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "windows_eventlog")]
+impl SourceConfig for WindowsEventLogConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.channels.is_empty() {
+            return Err(BuildError::NoChannels.into());
+        }
+
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        let checkpointer = Checkpointer::load(&data_dir).context(LoadBookmarksSnafu)?;
+
+        let subscriptions = self
+            .channels
+            .iter()
+            .map(|channel| {
+                Subscription::new(
+                    channel.clone(),
+                    &self.xpath_query,
+                    checkpointer.get(channel),
+                    self.read_existing_events,
+                    self.render_message,
+                )
+                .context(SubscribeSnafu { channel })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let source = WindowsEventLogSource {
+            render_format: self.render_format,
+            batch_size: self.batch_size,
+            acknowledgements: cx.do_acknowledgements(&self.acknowledgements),
+            checkpointer,
+            out: cx.out,
+        };
+        Ok(Box::pin(source.run(subscriptions, cx.shutdown)))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "windows_eventlog"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+/// The bookmark to store once the events read before it are delivered.
+struct Bookmark {
+    channel: String,
+    bookmark: String,
+}
+
+struct WindowsEventLogSource {
+    render_format: RenderFormat,
+    batch_size: usize,
+    acknowledgements: bool,
+    checkpointer: Checkpointer,
+    out: SourceSender,
+}
+
+impl WindowsEventLogSource {
+    async fn run(
+        mut self,
+        subscriptions: Vec<Subscription>,
+        mut shutdown: ShutdownSignal,
+    ) -> Result<(), ()> {
+        let (sender, mut batches) = mpsc::channel(subscriptions.len());
+        let stopped = Arc::new(AtomicBool::new(false));
+        let readers = subscriptions
+            .into_iter()
+            .map(|subscription| {
+                let sender = sender.clone();
+                let stopped = Arc::clone(&stopped);
+                let batch_size = self.batch_size;
+                spawn_blocking(move || subscription.run(batch_size, sender, stopped))
+            })
+            .collect::<Vec<_>>();
+        drop(sender);
+
+        let (finalizer, mut ack_stream) =
+            OrderedFinalizer::<Bookmark>::maybe_new(self.acknowledgements, shutdown.clone());
+        // The channels whose events were not delivered, which are no longer read so that they are
+        // read again from their last delivered event after a restart.
+        let mut failed = HashSet::new();
+        let mut checkpoint_interval = tokio::time::interval(CHECKPOINT_INTERVAL);
+
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => break,
+                entry = ack_stream.next() => if let Some((status, entry)) = entry {
+                    if status == BatchStatus::Delivered {
+                        if !failed.contains(&entry.channel) {
+                            self.checkpointer.set(entry.channel, entry.bookmark);
+                        }
+                    } else {
+                        emit!(WindowsEventLogNegativeAcknowledgmentError { channel: &entry.channel });
+                        failed.insert(entry.channel);
+                    }
+                },
+                _ = checkpoint_interval.tick() => self.flush(),
+                batch = batches.recv() => match batch {
+                    None => break,
+                    Some(batch) if failed.contains(&batch.channel) => {}
+                    Some(batch) => if !self.handle_batch(batch, finalizer.as_ref()).await {
+                        break;
+                    },
+                },
+            }
+        }
+
+        stopped.store(true, Ordering::Relaxed);
+        // Unblocks the readers waiting to send their events.
+        drop(batches);
+        for reader in readers {
+            if let Err(error) = reader.await {
+                error!(message = "Event Log reader panicked.", %error);
+            }
+        }
+        self.flush();
+
+        Ok(())
+    }
+
+    /// Sends the events of the batch. Returns `false` when the events can no longer be sent.
+    async fn handle_batch(
+        &mut self,
+        batch: RawBatch,
+        finalizer: Option<&OrderedFinalizer<Bookmark>>,
+    ) -> bool {
+        let RawBatch {
+            channel,
+            events,
+            bookmark,
+        } = batch;
+
+        emit!(BytesReceived {
+            byte_size: events.iter().map(|event| event.xml.len()).sum(),
+            protocol: "eventlog",
+        });
+
+        let (notifier, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
+        let events = events
+            .into_iter()
+            .filter_map(|event| match parse_event(event, self.render_format) {
+                Ok(log) => Some(log.with_batch_notifier_option(&notifier)),
+                Err(error) => {
+                    emit!(WindowsEventLogParseError {
+                        channel: &channel,
+                        error
+                    });
+                    None
+                }
+            })
+            .collect::<Vec<LogEvent>>();
+        drop(notifier);
+
+        if !events.is_empty() {
+            let count = events.len();
+            emit!(OldEventsReceived {
+                count,
+                byte_size: events.size_of(),
+            });
+            if let Err(error) = self.out.send_batch(events).await {
+                emit!(StreamClosedError { error, count });
+                return false;
+            }
+        }
+
+        match (finalizer, receiver) {
+            (Some(finalizer), Some(receiver)) => {
+                finalizer.add(Bookmark { channel, bookmark }, receiver)
+            }
+            _ => self.checkpointer.set(channel, bookmark),
+        }
+        true
+    }
+
+    fn flush(&mut self) {
+        if let Err(error) = self.checkpointer.flush() {
+            emit!(WindowsEventLogCheckpointError { error });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WindowsEventLogConfig>();
+    }
+
+    #[test]
+    fn parses_config() {
+        let config: WindowsEventLogConfig = toml::from_str(
+            r#"
+            channels = ["Security"]
+            xpath_query = "*[System[(EventID=4624)]]"
+            render_format = "xml"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.channels, ["Security"]);
+        assert_eq!(config.xpath_query, "*[System[(EventID=4624)]]");
+        assert_eq!(config.render_format, RenderFormat::Xml);
+        assert!(config.render_message);
+        assert!(!config.read_existing_events);
+        assert_eq!(config.batch_size, 100);
+    }
+}
@@ -0,0 +1,307 @@
+use std::collections::BTreeMap;
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use roxmltree::{Document, Node};
+
+use super::RenderFormat;
+use crate::{
+    config::log_schema,
+    event::{LogEvent, Value},
+};
+
+/// An event read from a channel, rendered as XML by the Event Log service.
+pub(super) struct RawEvent {
+    pub(super) xml: String,
+    /// The message of the event, rendered with the message files of its publisher.
+    pub(super) message: Option<String>,
+}
+
+/// Returns the name of the publisher of the event, used to render its message.
+pub(super) fn provider_name(xml: &str) -> Option<String> {
+    let document = Document::parse(xml).ok()?;
+    let system = child(document.root_element(), "System")?;
+    child(system, "Provider")?.attribute("Name").map(Into::into)
+}
+
+/// Creates the log of an event, either with the fields of the event or with its XML.
+pub(super) fn parse_event(
+    event: RawEvent,
+    format: RenderFormat,
+) -> Result<LogEvent, roxmltree::Error> {
+    let document = Document::parse(&event.xml)?;
+    let root = document.root_element();
+    let system = child(root, "System");
+    let system_text = |name| system.and_then(|system| child(system, name)).and_then(text);
+
+    let mut log = LogEvent::default();
+    if let Some(timestamp) = system
+        .and_then(|system| child(system, "TimeCreated"))
+        .and_then(|node| node.attribute("SystemTime"))
+        .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+    {
+        log.insert(log_schema().timestamp_key(), timestamp.with_timezone(&Utc));
+    }
+    if let Some(computer) = system_text("Computer") {
+        log.insert(log_schema().host_key(), computer);
+    }
+    if let Some(channel) = system_text("Channel") {
+        log.insert("channel", channel);
+    }
+
+    match format {
+        RenderFormat::Xml => {
+            if let Some(message) = event.message {
+                log.insert("rendered_message", message);
+            }
+            log.insert(log_schema().message_key(), event.xml.as_str());
+        }
+        RenderFormat::Json => {
+            if let Some(message) = event.message {
+                log.insert(log_schema().message_key(), message);
+            }
+            if let Some(system) = system {
+                insert_system_fields(&mut log, system);
+            }
+            if let Some(event_data) = child(root, "EventData") {
+                log.insert("event_data", event_data_value(event_data));
+            }
+            if let Some(user_data) = child(root, "UserData").and_then(first_element) {
+                log.insert("user_data", element_value(user_data));
+            }
+        }
+    }
+
+    log.insert(
+        log_schema().source_type_key(),
+        Bytes::from("windows_eventlog"),
+    );
+    Ok(log)
+}
+
+fn insert_system_fields(log: &mut LogEvent, system: Node) {
+    if let Some(provider) = child(system, "Provider") {
+        if let Some(name) = provider.attribute("Name") {
+            log.insert("provider_name", name);
+        }
+        if let Some(guid) = provider.attribute("Guid") {
+            log.insert("provider_guid", guid);
+        }
+    }
+    for (element, field) in [
+        ("EventID", "event_id"),
+        ("Version", "version"),
+        ("Level", "level"),
+        ("Task", "task"),
+        ("Opcode", "opcode"),
+        ("EventRecordID", "record_id"),
+    ] {
+        if let Some(value) = child(system, element).and_then(text) {
+            log.insert(field, integer_or_string(value));
+        }
+    }
+    if let Some(keywords) = child(system, "Keywords").and_then(text) {
+        log.insert("keywords", keywords);
+    }
+    if let Some(activity_id) =
+        child(system, "Correlation").and_then(|node| node.attribute("ActivityID"))
+    {
+        log.insert("activity_id", activity_id);
+    }
+    if let Some(execution) = child(system, "Execution") {
+        for (attribute, field) in [("ProcessID", "process_id"), ("ThreadID", "thread_id")] {
+            if let Some(value) = execution.attribute(attribute) {
+                log.insert(field, integer_or_string(value));
+            }
+        }
+    }
+    if let Some(user_id) = child(system, "Security").and_then(|node| node.attribute("UserID")) {
+        log.insert("user_id", user_id);
+    }
+}
+
+/// The `Data` elements of the event data are named by their `Name` attribute, or numbered from
+/// `param1` when the publisher doesn't name them, as for the events of the classic event logs.
+fn event_data_value(event_data: Node) -> Value {
+    let mut fields = BTreeMap::new();
+    let data = event_data
+        .children()
+        .filter(|node| node.is_element() && node.tag_name().name() == "Data");
+    for (index, data) in data.enumerate() {
+        let name = data
+            .attribute("Name")
+            .map_or_else(|| format!("param{}", index + 1), Into::into);
+        fields.insert(name, text(data).unwrap_or_default().into());
+    }
+    Value::Object(fields)
+}
+
+/// Converts the user data of an event, which has a schema of its own, to an object of its child
+/// elements.
+fn element_value(element: Node) -> Value {
+    if element.children().any(|node| node.is_element()) {
+        Value::Object(
+            element
+                .children()
+                .filter(Node::is_element)
+                .map(|child| (child.tag_name().name().into(), element_value(child)))
+                .collect(),
+        )
+    } else {
+        text(element).unwrap_or_default().into()
+    }
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children()
+        .find(|node| node.is_element() && node.tag_name().name() == name)
+}
+
+fn first_element<'a, 'input>(node: Node<'a, 'input>) -> Option<Node<'a, 'input>> {
+    node.children().find(Node::is_element)
+}
+
+fn text<'a>(node: Node<'a, '_>) -> Option<&'a str> {
+    node.text().map(str::trim)
+}
+
+fn integer_or_string(value: &str) -> Value {
+    value
+        .parse::<i64>()
+        .map_or_else(|_| value.into(), Value::Integer)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    const LOGON: &str = r#"<Event xmlns='http://schemas.microsoft.com/win/2004/08/events/event'>
+  <System>
+    <Provider Name='Microsoft-Windows-Security-Auditing' Guid='{54849625-5478-4994-a5ba-3e3b0328c30d}'/>
+    <EventID>4624</EventID>
+    <Version>2</Version>
+    <Level>0</Level>
+    <Task>12544</Task>
+    <Opcode>0</Opcode>
+    <Keywords>0x8020000000000000</Keywords>
+    <TimeCreated SystemTime='2022-10-01T12:30:00.2500000Z'/>
+    <EventRecordID>1234</EventRecordID>
+    <Correlation ActivityID='{f4a5e4c0-d5a9-0001-f6e4-a5f4a9d5d801}'/>
+    <Execution ProcessID='636' ThreadID='700'/>
+    <Channel>Security</Channel>
+    <Computer>WIN-SERVER</Computer>
+    <Security/>
+  </System>
+  <EventData>
+    <Data Name='SubjectUserSid'>S-1-5-18</Data>
+    <Data Name='LogonType'>5</Data>
+  </EventData>
+</Event>"#;
+
+    fn raw_event(xml: &str, message: Option<&str>) -> RawEvent {
+        RawEvent {
+            xml: xml.into(),
+            message: message.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn parses_event_fields() {
+        let log = parse_event(
+            raw_event(LOGON, Some("An account was successfully logged on.")),
+            RenderFormat::Json,
+        )
+        .unwrap();
+
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Utc.ymd(2022, 10, 1).and_hms_milli(12, 30, 0, 250).into()
+        );
+        assert_eq!(log[log_schema().host_key()], "WIN-SERVER".into());
+        assert_eq!(
+            log[log_schema().message_key()],
+            "An account was successfully logged on.".into()
+        );
+        assert_eq!(log["channel"], "Security".into());
+        assert_eq!(
+            log["provider_name"],
+            "Microsoft-Windows-Security-Auditing".into()
+        );
+        assert_eq!(log["event_id"], 4624.into());
+        assert_eq!(log["record_id"], 1234.into());
+        assert_eq!(log["keywords"], "0x8020000000000000".into());
+        assert_eq!(log["process_id"], 636.into());
+        assert_eq!(log["event_data.SubjectUserSid"], "S-1-5-18".into());
+        assert_eq!(log["event_data.LogonType"], "5".into());
+        assert!(log.get("user_id").is_none());
+        assert_eq!(
+            log[log_schema().source_type_key()],
+            "windows_eventlog".into()
+        );
+    }
+
+    #[test]
+    fn numbers_unnamed_event_data() {
+        let xml = r#"<Event xmlns='http://schemas.microsoft.com/win/2004/08/events/event'>
+  <System><Provider Name='MyApp'/><EventID Qualifiers='0'>1000</EventID><Channel>Application</Channel></System>
+  <EventData><Data>first</Data><Data>second</Data></EventData>
+</Event>"#;
+
+        let log = parse_event(raw_event(xml, None), RenderFormat::Json).unwrap();
+
+        assert_eq!(log["event_data.param1"], "first".into());
+        assert_eq!(log["event_data.param2"], "second".into());
+        assert!(log.get(log_schema().message_key()).is_none());
+    }
+
+    #[test]
+    fn parses_user_data() {
+        let xml = r#"<Event xmlns='http://schemas.microsoft.com/win/2004/08/events/event'>
+  <System><Provider Name='Microsoft-Windows-Eventlog'/><EventID>1102</EventID><Channel>Security</Channel></System>
+  <UserData>
+    <LogFileCleared xmlns='http://manifests.microsoft.com/win/2004/08/windows/eventlog'>
+      <SubjectUserName>admin</SubjectUserName>
+      <SubjectLogonId>0x3e7</SubjectLogonId>
+    </LogFileCleared>
+  </UserData>
+</Event>"#;
+
+        let log = parse_event(raw_event(xml, None), RenderFormat::Json).unwrap();
+
+        assert_eq!(log["user_data.SubjectUserName"], "admin".into());
+        assert_eq!(log["user_data.SubjectLogonId"], "0x3e7".into());
+    }
+
+    #[test]
+    fn keeps_xml_of_events() {
+        let log = parse_event(
+            raw_event(LOGON, Some("An account was successfully logged on.")),
+            RenderFormat::Xml,
+        )
+        .unwrap();
+
+        assert_eq!(log[log_schema().message_key()], LOGON.into());
+        assert_eq!(
+            log["rendered_message"],
+            "An account was successfully logged on.".into()
+        );
+        assert_eq!(log["channel"], "Security".into());
+        assert!(log.get("event_id").is_none());
+    }
+
+    #[test]
+    fn finds_provider_name() {
+        assert_eq!(
+            provider_name(LOGON).as_deref(),
+            Some("Microsoft-Windows-Security-Auditing")
+        );
+        assert_eq!(provider_name("<Event/>"), None);
+    }
+
+    #[test]
+    fn rejects_invalid_xml() {
+        assert!(parse_event(raw_event("<Event>", None), RenderFormat::Json).is_err());
+    }
+}
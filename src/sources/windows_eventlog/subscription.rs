@@ -0,0 +1,350 @@
+use std::{
+    collections::HashMap,
+    ffi::OsStr,
+    io,
+    os::windows::ffi::OsStrExt,
+    ptr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use tokio::sync::mpsc;
+use windows_sys::Win32::{
+    Foundation::{
+        CloseHandle, GetLastError, ERROR_INSUFFICIENT_BUFFER, ERROR_NO_MORE_ITEMS, HANDLE,
+    },
+    System::{
+        EventLog::{
+            EvtClose, EvtCreateBookmark, EvtFormatMessage, EvtFormatMessageEvent, EvtNext,
+            EvtOpenPublisherMetadata, EvtRender, EvtRenderBookmark, EvtRenderEventXml,
+            EvtSubscribe, EvtSubscribeStartAfterBookmark, EvtSubscribeStartAtOldestRecord,
+            EvtSubscribeToFutureEvents, EvtUpdateBookmark, EVT_HANDLE,
+        },
+        Threading::{CreateEventW, ResetEvent, WaitForSingleObject},
+    },
+};
+
+use super::parser::{provider_name, RawEvent};
+use crate::internal_events::WindowsEventLogReadError;
+
+/// How long to wait for new events before checking whether the source is stopping.
+const WAIT_TIMEOUT_MS: u32 = 500;
+
+const BACKOFF_DURATION: Duration = Duration::from_secs(5);
+
+/// The events read from a channel, with the bookmark of the last of them.
+pub(super) struct RawBatch {
+    pub(super) channel: String,
+    pub(super) events: Vec<RawEvent>,
+    pub(super) bookmark: String,
+}
+
+/// A handle of the Event Log API, closed when dropped.
+struct EvtHandle(EVT_HANDLE);
+
+impl EvtHandle {
+    fn new(handle: EVT_HANDLE) -> io::Result<Self> {
+        if handle == 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(Self(handle))
+        }
+    }
+}
+
+impl Drop for EvtHandle {
+    fn drop(&mut self) {
+        unsafe { EvtClose(self.0) };
+    }
+}
+
+/// The event signaled by the Event Log service when new events are published to the channel.
+struct Signal(HANDLE);
+
+impl Drop for Signal {
+    fn drop(&mut self) {
+        unsafe { CloseHandle(self.0) };
+    }
+}
+
+/// A pull subscription to the events of a channel.
+pub(super) struct Subscription {
+    channel: String,
+    query: Vec<u16>,
+    signal: Signal,
+    subscription: EvtHandle,
+    bookmark: EvtHandle,
+    /// The metadata of the publishers, used to render the messages of their events. `None` when
+    /// the publisher isn't registered on this host.
+    publishers: HashMap<String, Option<EvtHandle>>,
+    render_message: bool,
+}
+
+impl Subscription {
+    /// Subscribes to the events of the channel matching the XPath query, starting after the
+    /// bookmark if there is one.
+    pub(super) fn new(
+        channel: String,
+        query: &str,
+        bookmark: Option<&str>,
+        read_existing_events: bool,
+        render_message: bool,
+    ) -> io::Result<Self> {
+        let signal = unsafe { CreateEventW(ptr::null(), 1, 1, ptr::null()) };
+        if signal == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let signal = Signal(signal);
+
+        let bookmark_handle = match bookmark {
+            Some(xml) => unsafe { EvtCreateBookmark(wide(xml).as_ptr()) },
+            None => unsafe { EvtCreateBookmark(ptr::null()) },
+        };
+        let bookmark_handle = EvtHandle::new(bookmark_handle)?;
+        let flags = if bookmark.is_some() {
+            // The subscription starts at the oldest event when the bookmarked one has been
+            // cleared from the channel.
+            EvtSubscribeStartAfterBookmark
+        } else if read_existing_events {
+            EvtSubscribeStartAtOldestRecord
+        } else {
+            EvtSubscribeToFutureEvents
+        };
+
+        let query = wide(query);
+        let subscription = subscribe(&channel, &query, &signal, &bookmark_handle, flags)?;
+        Ok(Self {
+            channel,
+            query,
+            signal,
+            subscription,
+            bookmark: bookmark_handle,
+            publishers: HashMap::new(),
+            render_message,
+        })
+    }
+
+    /// Subscribes again after the last event read, once the subscription has failed.
+    fn resubscribe(&mut self) -> io::Result<()> {
+        self.subscription = subscribe(
+            &self.channel,
+            &self.query,
+            &self.signal,
+            &self.bookmark,
+            EvtSubscribeStartAfterBookmark,
+        )?;
+        Ok(())
+    }
+
+    /// Reads the events of the channel until the source stops, sending them in batches of at
+    /// most `batch_size` events.
+    pub(super) fn run(
+        mut self,
+        batch_size: usize,
+        out: mpsc::Sender<RawBatch>,
+        stopped: Arc<AtomicBool>,
+    ) {
+        while !stopped.load(Ordering::Relaxed) {
+            unsafe {
+                WaitForSingleObject(self.signal.0, WAIT_TIMEOUT_MS);
+                // The event is reset before reading, so that the events published while reading
+                // signal it again.
+                ResetEvent(self.signal.0);
+            }
+
+            loop {
+                match self.next_batch(batch_size) {
+                    Ok(Some(batch)) => {
+                        if out.blocking_send(batch).is_err() {
+                            return;
+                        }
+                    }
+                    Ok(None) => break,
+                    Err(error) => {
+                        emit!(WindowsEventLogReadError {
+                            channel: &self.channel,
+                            error
+                        });
+                        thread::sleep(BACKOFF_DURATION);
+                        if let Err(error) = self.resubscribe() {
+                            emit!(WindowsEventLogReadError {
+                                channel: &self.channel,
+                                error
+                            });
+                        }
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    fn next_batch(&mut self, batch_size: usize) -> io::Result<Option<RawBatch>> {
+        let mut handles = vec![0; batch_size];
+        let mut returned = 0;
+        let read = unsafe {
+            EvtNext(
+                self.subscription.0,
+                batch_size as u32,
+                handles.as_mut_ptr(),
+                0,
+                0,
+                &mut returned,
+            )
+        };
+        if read == 0 {
+            let error = unsafe { GetLastError() };
+            return if error == ERROR_NO_MORE_ITEMS {
+                Ok(None)
+            } else {
+                Err(io::Error::from_raw_os_error(error as i32))
+            };
+        }
+
+        // Wrapped first so that all the handles are closed when one of the events fails to
+        // render.
+        let handles = handles[..returned as usize]
+            .iter()
+            .map(|handle| EvtHandle(*handle))
+            .collect::<Vec<_>>();
+        let mut events = Vec::with_capacity(handles.len());
+        for handle in &handles {
+            let xml = render(handle, EvtRenderEventXml)?;
+            let message = if self.render_message {
+                self.format_message(handle, &xml)
+            } else {
+                None
+            };
+            events.push(RawEvent { xml, message });
+
+            if unsafe { EvtUpdateBookmark(self.bookmark.0, handle.0) } == 0 {
+                return Err(io::Error::last_os_error());
+            }
+        }
+
+        Ok(Some(RawBatch {
+            channel: self.channel.clone(),
+            events,
+            bookmark: render(&self.bookmark, EvtRenderBookmark)?,
+        }))
+    }
+
+    /// Renders the message of the event, which isn't available when the publisher of the event
+    /// isn't registered on this host or has no message for it.
+    fn format_message(&mut self, event: &EvtHandle, xml: &str) -> Option<String> {
+        let provider = provider_name(xml)?;
+        let metadata = self
+            .publishers
+            .entry(provider)
+            .or_insert_with_key(|provider| {
+                let handle = unsafe {
+                    EvtOpenPublisherMetadata(0, wide(provider).as_ptr(), ptr::null(), 0, 0)
+                };
+                EvtHandle::new(handle).ok()
+            })
+            .as_ref()?;
+
+        let mut used = 0;
+        let format = |buffer: &mut [u16], used: &mut u32| unsafe {
+            EvtFormatMessage(
+                metadata.0,
+                event.0,
+                0,
+                0,
+                ptr::null(),
+                EvtFormatMessageEvent,
+                buffer.len() as u32,
+                buffer.as_mut_ptr(),
+                used,
+            )
+        };
+        if format(&mut [], &mut used) == 0 && unsafe { GetLastError() } != ERROR_INSUFFICIENT_BUFFER
+        {
+            return None;
+        }
+        let mut buffer = vec![0; used as usize];
+        if format(&mut buffer, &mut used) == 0 {
+            return None;
+        }
+        Some(from_wide(&buffer))
+    }
+}
+
+fn subscribe(
+    channel: &str,
+    query: &[u16],
+    signal: &Signal,
+    bookmark: &EvtHandle,
+    flags: u32,
+) -> io::Result<EvtHandle> {
+    let channel = wide(channel);
+    let bookmark = if flags == EvtSubscribeStartAfterBookmark {
+        bookmark.0
+    } else {
+        0
+    };
+    let handle = unsafe {
+        EvtSubscribe(
+            0,
+            signal.0,
+            channel.as_ptr(),
+            query.as_ptr(),
+            bookmark,
+            ptr::null(),
+            None,
+            flags,
+        )
+    };
+    EvtHandle::new(handle)
+}
+
+/// Renders an event or a bookmark as XML.
+fn render(handle: &EvtHandle, flags: u32) -> io::Result<String> {
+    let mut used = 0;
+    let mut count = 0;
+    let rendered = unsafe {
+        EvtRender(
+            0,
+            handle.0,
+            flags,
+            0,
+            ptr::null_mut(),
+            &mut used,
+            &mut count,
+        )
+    };
+    if rendered == 0 && unsafe { GetLastError() } != ERROR_INSUFFICIENT_BUFFER {
+        return Err(io::Error::last_os_error());
+    }
+
+    // The size of the buffer is in bytes, and the XML is a UTF-16 string.
+    let mut buffer = vec![0u16; (used as usize + 1) / 2];
+    let rendered = unsafe {
+        EvtRender(
+            0,
+            handle.0,
+            flags,
+            (buffer.len() * 2) as u32,
+            buffer.as_mut_ptr().cast(),
+            &mut used,
+            &mut count,
+        )
+    };
+    if rendered == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(from_wide(&buffer))
+}
+
+fn wide(value: &str) -> Vec<u16> {
+    OsStr::new(value).encode_wide().chain(Some(0)).collect()
+}
+
+fn from_wide(buffer: &[u16]) -> String {
+    let len = buffer.iter().position(|c| *c == 0).unwrap_or(buffer.len());
+    String::from_utf16_lossy(&buffer[..len])
+}
@@ -1,3 +1,5 @@
+pub(crate) mod proxy_protocol;
+
 use socket2::SockRef;
 use tokio::net::TcpStream;
 use vector_config::configurable_component;
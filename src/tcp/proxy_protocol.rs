@@ -0,0 +1,196 @@
+//! Parsing of the [PROXY protocol][proxy_protocol] header sent by load balancers, such as HAProxy
+//! or AWS Network Load Balancers, at the start of each connection.
+//!
+//! [proxy_protocol]: https://www.haproxy.org/download/2.6/doc/proxy-protocol.txt
+
+use std::{
+    io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    str,
+};
+
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+const V1_PREFIX: &[u8] = b"PROXY ";
+// The longest possible version one header, including the trailing CRLF.
+const V1_MAX_LEN: usize = 107;
+const V2_SIGNATURE: &[u8] = b"\r\n\r\n\0\r\nQUIT\n";
+const V2_HEADER_LEN: usize = 16;
+
+const V2_COMMAND_LOCAL: u8 = 0x20;
+const V2_COMMAND_PROXY: u8 = 0x21;
+const V2_FAMILY_INET: u8 = 0x1;
+const V2_FAMILY_INET6: u8 = 0x2;
+
+/// Reads the PROXY protocol header from the start of a connection, without reading any further.
+///
+/// Returns the address of the original peer, or `None` if the header doesn't hold one, such as
+/// for health checks of the load balancer itself.
+pub(crate) async fn read_header<S>(stream: &mut S) -> io::Result<Option<SocketAddr>>
+where
+    S: AsyncRead + Unpin,
+{
+    let mut header = vec![0; V1_PREFIX.len()];
+    stream.read_exact(&mut header).await?;
+
+    if header == V1_PREFIX {
+        // The header is terminated by a CRLF, so it must be read byte by byte to not consume any
+        // of the data following it.
+        while !header.ends_with(b"\r\n") {
+            if header.len() == V1_MAX_LEN {
+                return Err(invalid_data(
+                    "PROXY protocol header exceeds the maximum length",
+                ));
+            }
+            header.push(stream.read_u8().await?);
+        }
+        parse_v1(&header[V1_PREFIX.len()..header.len() - 2])
+    } else if V2_SIGNATURE.starts_with(&header) {
+        header.resize(V2_HEADER_LEN, 0);
+        stream.read_exact(&mut header[V1_PREFIX.len()..]).await?;
+        if &header[..V2_SIGNATURE.len()] != V2_SIGNATURE {
+            return Err(invalid_data("Invalid PROXY protocol signature"));
+        }
+
+        let len = u16::from_be_bytes([header[14], header[15]]);
+        let mut addresses = vec![0; usize::from(len)];
+        stream.read_exact(&mut addresses).await?;
+        parse_v2(header[12], header[13], &addresses)
+    } else {
+        Err(invalid_data(
+            "Connection did not start with a PROXY protocol header",
+        ))
+    }
+}
+
+/// Parses the fields of a version one header, such as `TCP4 192.0.2.1 192.0.2.2 56324 443`.
+fn parse_v1(fields: &[u8]) -> io::Result<Option<SocketAddr>> {
+    let fields = str::from_utf8(fields)
+        .map_err(|_| invalid_data("PROXY protocol header is not valid ASCII"))?;
+    let mut fields = fields.split(' ');
+
+    match fields.next() {
+        Some("TCP4") | Some("TCP6") => {
+            let source = fields.next();
+            let _destination = fields.next();
+            let source_port = fields.next();
+
+            match (
+                source.map(str::parse::<IpAddr>),
+                source_port.map(str::parse::<u16>),
+            ) {
+                (Some(Ok(ip)), Some(Ok(port))) => Ok(Some(SocketAddr::new(ip, port))),
+                _ => Err(invalid_data("Invalid address in PROXY protocol header")),
+            }
+        }
+        Some("UNKNOWN") => Ok(None),
+        _ => Err(invalid_data(
+            "Unsupported protocol in PROXY protocol header",
+        )),
+    }
+}
+
+/// Parses the addresses of a version two header, ignoring any TLVs following them.
+fn parse_v2(version_command: u8, family: u8, addresses: &[u8]) -> io::Result<Option<SocketAddr>> {
+    match version_command {
+        V2_COMMAND_LOCAL => return Ok(None),
+        V2_COMMAND_PROXY => {}
+        _ => {
+            return Err(invalid_data(
+                "Unsupported PROXY protocol version or command",
+            ))
+        }
+    }
+
+    let address = match family >> 4 {
+        V2_FAMILY_INET if addresses.len() >= 12 => {
+            let ip = Ipv4Addr::new(addresses[0], addresses[1], addresses[2], addresses[3]);
+            let port = u16::from_be_bytes([addresses[8], addresses[9]]);
+            Some(SocketAddr::new(ip.into(), port))
+        }
+        V2_FAMILY_INET6 if addresses.len() >= 36 => {
+            let mut octets = [0; 16];
+            octets.copy_from_slice(&addresses[..16]);
+            let port = u16::from_be_bytes([addresses[32], addresses[33]]);
+            Some(SocketAddr::new(Ipv6Addr::from(octets).into(), port))
+        }
+        V2_FAMILY_INET | V2_FAMILY_INET6 => {
+            return Err(invalid_data("Truncated address in PROXY protocol header"))
+        }
+        // Unix sockets and unspecified families don't carry a network address.
+        _ => None,
+    };
+
+    Ok(address)
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn read(mut input: &[u8]) -> (io::Result<Option<SocketAddr>>, &[u8]) {
+        let result = read_header(&mut input).await;
+        (result, input)
+    }
+
+    #[tokio::test]
+    async fn reads_v1_header() {
+        let (result, rest) = read(b"PROXY TCP4 192.0.2.1 192.0.2.2 56324 443\r\nhello").await;
+        assert_eq!(result.unwrap(), Some("192.0.2.1:56324".parse().unwrap()));
+        assert_eq!(rest, b"hello");
+
+        let (result, rest) = read(b"PROXY TCP6 2001:db8::1 2001:db8::2 56324 443\r\n").await;
+        assert_eq!(
+            result.unwrap(),
+            Some("[2001:db8::1]:56324".parse().unwrap())
+        );
+        assert!(rest.is_empty());
+
+        let (result, _) = read(b"PROXY UNKNOWN\r\n").await;
+        assert_eq!(result.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn reads_v2_header() {
+        let mut input = V2_SIGNATURE.to_vec();
+        input.extend_from_slice(&[V2_COMMAND_PROXY, 0x11, 0, 15]);
+        input.extend_from_slice(&[192, 0, 2, 1, 192, 0, 2, 2, 0xdc, 0x04, 0x01, 0xbb]);
+        // A TLV following the addresses.
+        input.extend_from_slice(&[0x04, 0, 0]);
+        input.extend_from_slice(b"hello");
+
+        let (result, rest) = read(&input).await;
+        assert_eq!(result.unwrap(), Some("192.0.2.1:56324".parse().unwrap()));
+        assert_eq!(rest, b"hello");
+    }
+
+    #[tokio::test]
+    async fn reads_v2_local_header() {
+        let mut input = V2_SIGNATURE.to_vec();
+        input.extend_from_slice(&[V2_COMMAND_LOCAL, 0x00, 0, 0]);
+
+        let (result, rest) = read(&input).await;
+        assert_eq!(result.unwrap(), None);
+        assert!(rest.is_empty());
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_headers() {
+        for input in [
+            &b"hello world\r\n"[..],
+            &b"PROXY TCP4 192.0.2.1\r\n"[..],
+            &b"PROXY UDP4 192.0.2.1 192.0.2.2 56324 443\r\n"[..],
+            &b"\r\n\r\n\0\r\nQUIT\r\x21\x11\0\0"[..],
+        ] {
+            assert!(read(input).await.0.is_err());
+        }
+
+        let mut input = b"PROXY ".to_vec();
+        input.extend_from_slice(&[b'a'; V1_MAX_LEN]);
+        assert!(read(&input).await.0.is_err());
+    }
+}
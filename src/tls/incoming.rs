@@ -18,12 +18,21 @@ use tokio_openssl::SslStream;
 
 use super::{
     CreateAcceptorSnafu, HandshakeSnafu, IncomingListenerSnafu, MaybeTlsSettings, MaybeTlsStream,
-    SslBuildSnafu, TcpBindSnafu, TlsError, TlsSettings,
+    ProxyProtocolSnafu, SslBuildSnafu, TcpBindSnafu, TlsError, TlsSettings,
 };
+use crate::tcp::proxy_protocol;
 #[cfg(feature = "sources-utils-tcp-keepalive")]
 use crate::tcp::TcpKeepaliveConfig;
 #[cfg(feature = "sources-utils-tcp-socket")]
-use {crate::tcp, openssl::x509::X509, std::collections::HashMap};
+use {
+    super::TlsSourceConfig,
+    crate::{internal_events::TcpSocketTlsClientUnauthorized, tcp},
+    openssl::x509::X509,
+    std::{
+        collections::HashMap,
+        net::{Ipv4Addr, Ipv6Addr},
+    },
+};
 
 impl TlsSettings {
     pub(crate) fn acceptor(&self) -> crate::tls::Result<SslAcceptor> {
@@ -48,22 +57,57 @@ impl MaybeTlsSettings {
             Self::Raw(()) => None,
         };
 
-        Ok(MaybeTlsListener { listener, acceptor })
+        Ok(MaybeTlsListener {
+            listener,
+            acceptor,
+            proxy_protocol: false,
+            #[cfg(feature = "sources-utils-tcp-socket")]
+            client_authorization: None,
+        })
     }
 }
 
 pub struct MaybeTlsListener {
     listener: TcpListener,
     acceptor: Option<SslAcceptor>,
+    proxy_protocol: bool,
+    #[cfg(feature = "sources-utils-tcp-socket")]
+    client_authorization: Option<TlsClientAuthorization>,
 }
 
 impl MaybeTlsListener {
+    /// Expects each accepted connection to start with a PROXY protocol header, which replaces the
+    /// peer address of the connection with the address of the original client.
+    #[allow(unused)]
+    pub(crate) const fn with_proxy_protocol(mut self, proxy_protocol: bool) -> Self {
+        self.proxy_protocol = proxy_protocol;
+        self
+    }
+
+    /// Closes accepted connections whose client certificate isn't allowed by `client_authorization`
+    /// as part of the TLS handshake, for servers that don't inspect the certificate themselves.
+    #[cfg(feature = "sources-utils-tcp-socket")]
+    pub(crate) fn with_client_authorization(
+        mut self,
+        client_authorization: Option<TlsClientAuthorization>,
+    ) -> Self {
+        self.client_authorization = client_authorization;
+        self
+    }
+
     pub(crate) async fn accept(&mut self) -> crate::tls::Result<MaybeTlsIncomingStream<TcpStream>> {
         self.listener
             .accept()
             .await
             .map(|(stream, peer_addr)| {
-                MaybeTlsIncomingStream::new(stream, peer_addr, self.acceptor.clone())
+                MaybeTlsIncomingStream::new(
+                    stream,
+                    peer_addr,
+                    self.acceptor.clone(),
+                    self.proxy_protocol,
+                    #[cfg(feature = "sources-utils-tcp-socket")]
+                    self.client_authorization.clone(),
+                )
             })
             .context(IncomingListenerSnafu)
     }
@@ -137,6 +181,9 @@ impl From<TcpListener> for MaybeTlsListener {
         Self {
             listener,
             acceptor: None,
+            proxy_protocol: false,
+            #[cfg(feature = "sources-utils-tcp-socket")]
+            client_authorization: None,
         }
     }
 }
@@ -145,13 +192,16 @@ pub struct MaybeTlsIncomingStream<S> {
     state: StreamState<S>,
     // BoxFuture doesn't allow access to the inner stream, but users
     // of MaybeTlsIncomingStream want access to the peer address while
-    // still handshaking, so we have to cache it here.
+    // still handshaking, so we have to cache it here. Once accepted, it
+    // is replaced by the address read from the PROXY protocol header.
     peer_addr: SocketAddr,
 }
 
+type AcceptResult<S> = Result<(MaybeTlsStream<S>, Option<SocketAddr>), TlsError>;
+
 enum StreamState<S> {
     Accepted(MaybeTlsStream<S>),
-    Accepting(BoxFuture<'static, Result<SslStream<S>, TlsError>>),
+    Accepting(BoxFuture<'static, AcceptResult<S>>),
     AcceptError(String),
     Closed,
 }
@@ -221,31 +271,77 @@ impl MaybeTlsIncomingStream<TcpStream> {
         stream: TcpStream,
         peer_addr: SocketAddr,
         acceptor: Option<SslAcceptor>,
+        proxy_protocol: bool,
+        #[cfg(feature = "sources-utils-tcp-socket")] client_authorization: Option<
+            TlsClientAuthorization,
+        >,
     ) -> Self {
-        let state = match acceptor {
-            Some(acceptor) => StreamState::Accepting(
+        let state = if acceptor.is_none() && !proxy_protocol {
+            StreamState::Accepted(MaybeTlsStream::Raw(stream))
+        } else {
+            StreamState::Accepting(
                 async move {
-                    let ssl = Ssl::new(acceptor.context()).context(SslBuildSnafu)?;
-                    let mut stream = SslStream::new(ssl, stream).context(SslBuildSnafu)?;
-                    Pin::new(&mut stream)
-                        .accept()
-                        .await
-                        .context(HandshakeSnafu)?;
-                    Ok(stream)
+                    let mut stream = stream;
+                    #[cfg(feature = "sources-utils-tcp-socket")]
+                    let connection_addr = peer_addr;
+                    // The header is sent before the TLS handshake.
+                    let peer_addr = if proxy_protocol {
+                        proxy_protocol::read_header(&mut stream)
+                            .await
+                            .context(ProxyProtocolSnafu)?
+                    } else {
+                        None
+                    };
+
+                    let stream = match acceptor {
+                        Some(acceptor) => {
+                            let ssl = Ssl::new(acceptor.context()).context(SslBuildSnafu)?;
+                            let mut stream = SslStream::new(ssl, stream).context(SslBuildSnafu)?;
+                            Pin::new(&mut stream)
+                                .accept()
+                                .await
+                                .context(HandshakeSnafu)?;
+                            #[cfg(feature = "sources-utils-tcp-socket")]
+                            if let Some(authorization) = &client_authorization {
+                                let certificate = stream
+                                    .ssl()
+                                    .peer_certificate()
+                                    .map(CertificateMetadata::from_x509);
+                                if !authorization.is_authorized(certificate.as_ref()) {
+                                    emit!(TcpSocketTlsClientUnauthorized {
+                                        peer_addr: peer_addr.unwrap_or(connection_addr),
+                                        subject: certificate
+                                            .as_ref()
+                                            .map(CertificateMetadata::subject),
+                                    });
+                                    return Err(TlsError::ClientUnauthorized);
+                                }
+                            }
+                            MaybeTlsStream::Tls(stream)
+                        }
+                        None => MaybeTlsStream::Raw(stream),
+                    };
+                    Ok((stream, peer_addr))
                 }
                 .boxed(),
-            ),
-            None => StreamState::Accepted(MaybeTlsStream::Raw(stream)),
+            )
         };
         Self { state, peer_addr }
     }
 
+    fn accepted(&mut self, stream: MaybeTlsStream<TcpStream>, peer_addr: Option<SocketAddr>) {
+        if let Some(peer_addr) = peer_addr {
+            self.peer_addr = peer_addr;
+        }
+        self.state = StreamState::Accepted(stream);
+    }
+
     // Explicit handshake method
     #[cfg(feature = "listenfd")]
     pub(crate) async fn handshake(&mut self) -> crate::tls::Result<()> {
         if let StreamState::Accepting(fut) = &mut self.state {
-            let stream = fut.await?;
-            self.state = StreamState::Accepted(MaybeTlsStream::Tls(stream));
+            let (stream, peer_addr) = fut.await?;
+            self.accepted(stream, peer_addr);
         }
 
         Ok(())
@@ -291,8 +387,8 @@ impl MaybeTlsIncomingStream<TcpStream> {
             return match &mut this.state {
                 StreamState::Accepted(stream) => poll_fn(Pin::new(stream), cx),
                 StreamState::Accepting(fut) => match futures::ready!(fut.as_mut().poll(cx)) {
-                    Ok(stream) => {
-                        this.state = StreamState::Accepted(MaybeTlsStream::Tls(stream));
+                    Ok((stream, peer_addr)) => {
+                        this.accepted(stream, peer_addr);
                         continue;
                     }
                     Err(error) => {
@@ -340,8 +436,8 @@ impl AsyncWrite for MaybeTlsIncomingStream<TcpStream> {
                 poll_result => poll_result,
             },
             StreamState::Accepting(fut) => match futures::ready!(fut.as_mut().poll(cx)) {
-                Ok(stream) => {
-                    this.state = StreamState::Accepted(MaybeTlsStream::Tls(stream));
+                Ok((stream, peer_addr)) => {
+                    this.accepted(stream, peer_addr);
                     Poll::Pending
                 }
                 Err(error) => {
@@ -367,6 +463,7 @@ pub struct CertificateMetadata {
     pub organization_name: Option<String>,
     pub organizational_unit_name: Option<String>,
     pub common_name: Option<String>,
+    pub subject_alt_names: Vec<String>,
 }
 
 #[cfg(feature = "sources-utils-tcp-socket")]
@@ -380,6 +477,21 @@ impl CertificateMetadata {
             };
             subject_metadata.insert(entry.object().to_string(), data_string);
         }
+        let subject_alt_names = cert
+            .subject_alt_names()
+            .map(|names| {
+                names
+                    .iter()
+                    .filter_map(|name| {
+                        name.dnsname()
+                            .or_else(|| name.email())
+                            .or_else(|| name.uri())
+                            .map(str::to_owned)
+                            .or_else(|| name.ipaddress().and_then(ip_address_to_string))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
         return CertificateMetadata {
             country_name: subject_metadata.get("countryName").cloned(),
             state_or_province_name: subject_metadata.get("stateOrProvinceName").cloned(),
@@ -387,6 +499,7 @@ impl CertificateMetadata {
             organization_name: subject_metadata.get("organizationName").cloned(),
             organizational_unit_name: subject_metadata.get("organizationalUnitName").cloned(),
             common_name: subject_metadata.get("commonName").cloned(),
+            subject_alt_names,
         };
     }
 
@@ -414,6 +527,68 @@ impl CertificateMetadata {
     }
 }
 
+#[cfg(feature = "sources-utils-tcp-socket")]
+fn ip_address_to_string(octets: &[u8]) -> Option<String> {
+    match octets.len() {
+        4 => <[u8; 4]>::try_from(octets)
+            .ok()
+            .map(|octets| Ipv4Addr::from(octets).to_string()),
+        16 => <[u8; 16]>::try_from(octets)
+            .ok()
+            .map(|octets| Ipv6Addr::from(octets).to_string()),
+        _ => None,
+    }
+}
+
+/// Allowlists restricting which verified client certificates may connect.
+#[cfg(feature = "sources-utils-tcp-socket")]
+#[derive(Clone, Debug)]
+pub struct TlsClientAuthorization {
+    allowed_subjects: Vec<String>,
+    allowed_sans: Vec<String>,
+}
+
+#[cfg(feature = "sources-utils-tcp-socket")]
+impl TlsClientAuthorization {
+    /// Returns `None` if no allowlist is configured, in which case any verified client is accepted.
+    pub(crate) fn from_config(
+        config: &Option<TlsSourceConfig>,
+    ) -> crate::tls::Result<Option<Self>> {
+        let config = match config {
+            Some(config)
+                if !config.client_allowed_subjects.is_empty()
+                    || !config.client_allowed_sans.is_empty() =>
+            {
+                config
+            }
+            _ => return Ok(None),
+        };
+
+        // Without verification, the client certificate is neither requested nor trusted.
+        let tls = &config.tls_config;
+        if tls.enabled != Some(true) || tls.options.verify_certificate != Some(true) {
+            return Err(TlsError::MissingClientVerification);
+        }
+
+        Ok(Some(Self {
+            allowed_subjects: config.client_allowed_subjects.clone(),
+            allowed_sans: config.client_allowed_sans.clone(),
+        }))
+    }
+
+    pub(crate) fn is_authorized(&self, certificate: Option<&CertificateMetadata>) -> bool {
+        certificate.map_or(false, |certificate| {
+            let subject = certificate.subject();
+            self.allowed_subjects.iter().any(|allowed| {
+                *allowed == subject || certificate.common_name.as_ref() == Some(allowed)
+            }) || certificate
+                .subject_alt_names
+                .iter()
+                .any(|name| self.allowed_sans.contains(name))
+        })
+    }
+}
+
 #[cfg(all(test, feature = "sources-utils-tcp-socket"))]
 mod test {
     use super::*;
@@ -427,6 +602,7 @@ mod test {
             organization_name: Some("organization".to_owned()),
             organizational_unit_name: Some("org_unit".to_owned()),
             state_or_province_name: Some("state".to_owned()),
+            subject_alt_names: Vec::new(),
         };
 
         let expected = format!(
@@ -450,6 +626,7 @@ mod test {
             organization_name: Some("organization".to_owned()),
             organizational_unit_name: Some("org_unit".to_owned()),
             state_or_province_name: None,
+            subject_alt_names: Vec::new(),
         };
 
         let expected = format!(
@@ -461,4 +638,30 @@ mod test {
         );
         assert_eq!(expected, example_meta.subject())
     }
+
+    #[test]
+    fn client_authorization() {
+        let authorization = TlsClientAuthorization {
+            allowed_subjects: vec!["client".to_owned(), "CN=other,O=Acme".to_owned()],
+            allowed_sans: vec!["client.example.com".to_owned(), "192.0.2.1".to_owned()],
+        };
+        let certificate = |common_name: &str, subject_alt_names: &[&str]| CertificateMetadata {
+            common_name: Some(common_name.to_owned()),
+            country_name: None,
+            locality_name: None,
+            organization_name: Some("Acme".to_owned()),
+            organizational_unit_name: None,
+            state_or_province_name: None,
+            subject_alt_names: subject_alt_names
+                .iter()
+                .map(|&name| name.to_owned())
+                .collect(),
+        };
+
+        assert!(authorization.is_authorized(Some(&certificate("client", &[]))));
+        assert!(authorization.is_authorized(Some(&certificate("other", &[]))));
+        assert!(authorization.is_authorized(Some(&certificate("unknown", &["192.0.2.1"]))));
+        assert!(!authorization.is_authorized(Some(&certificate("unknown", &["example.com"]))));
+        assert!(!authorization.is_authorized(None));
+    }
 }
@@ -17,7 +17,9 @@ mod outgoing;
 mod settings;
 
 #[cfg(all(feature = "sources-utils-tls", feature = "listenfd"))]
-pub(crate) use incoming::{CertificateMetadata, MaybeTlsIncomingStream, MaybeTlsListener};
+pub(crate) use incoming::{
+    CertificateMetadata, MaybeTlsIncomingStream, MaybeTlsListener, TlsClientAuthorization,
+};
 pub(crate) use maybe_tls::MaybeTls;
 #[cfg(all(test, feature = "kafka-integration-tests"))]
 pub use settings::TEST_PEM_INTERMEDIATE_CA_PATH;
@@ -83,10 +85,18 @@ pub enum TlsError {
     },
     #[snafu(display("TLS configuration requires a certificate when enabled"))]
     MissingRequiredIdentity,
+    #[snafu(display(
+        "Client certificate allowlists require TLS to be enabled with `verify_certificate`"
+    ))]
+    MissingClientVerification,
+    #[snafu(display("Client certificate is not allowed to connect"))]
+    ClientUnauthorized,
     #[snafu(display("TLS handshake failed: {}", source))]
     Handshake { source: openssl::ssl::Error },
     #[snafu(display("Incoming listener failed: {}", source))]
     IncomingListener { source: tokio::io::Error },
+    #[snafu(display("Reading the PROXY protocol header failed: {}", source))]
+    ProxyProtocol { source: std::io::Error },
     #[snafu(display("Creating the TLS acceptor failed: {}", source))]
     CreateAcceptor { source: ErrorStack },
     #[snafu(display("Error building SSL context: {}", source))]
@@ -77,6 +77,22 @@ impl TlsEnableableConfig {
 pub struct TlsSourceConfig {
     /// Event field for client certificate metadata.
     pub client_metadata_key: Option<String>,
+
+    /// Subjects of client certificates allowed to connect.
+    ///
+    /// Each entry is matched against the Common Name of the certificate's subject, or against the
+    /// full subject, such as `CN=client,O=Acme`. If this or `client_allowed_sans` is set, connections
+    /// whose client certificate matches neither are closed. Requires `verify_certificate` to be
+    /// enabled.
+    #[serde(default)]
+    pub client_allowed_subjects: Vec<String>,
+
+    /// Subject Alternative Names of client certificates allowed to connect.
+    ///
+    /// Each entry is matched against the DNS names, email addresses, URIs, and IP addresses in the
+    /// certificate's Subject Alternative Name extension. Requires `verify_certificate` to be enabled.
+    #[serde(default)]
+    pub client_allowed_sans: Vec<String>,
     #[serde(flatten)]
     pub tls_config: TlsEnableableConfig,
 }
@@ -203,7 +219,7 @@ impl TlsSettings {
         })
     }
 
-    #[cfg(feature = "sources-gcp_pubsub")]
+    #[cfg(any(feature = "sources-gcp_pubsub", feature = "sinks-mqtt"))]
     pub fn identity_pem(&self) -> Option<(Vec<u8>, Vec<u8>)> {
         self.identity().map(|identity| {
             let mut cert = identity.cert.to_pem().expect("Invalid stored identity");
@@ -224,7 +240,20 @@ impl TlsSettings {
         })
     }
 
-    #[cfg(feature = "sources-gcp_pubsub")]
+    /// The identity as a DER encoded PKCS#12 archive, along with its password.
+    #[cfg(any(feature = "sources-amqp", feature = "sinks-amqp"))]
+    pub fn identity_pkcs12(&self) -> Option<(Vec<u8>, String)> {
+        self.identity
+            .as_ref()
+            .map(|identity| (identity.0.clone(), identity.1.clone()))
+    }
+
+    #[cfg(any(
+        feature = "sources-amqp",
+        feature = "sources-gcp_pubsub",
+        feature = "sinks-amqp",
+        feature = "sinks-mqtt"
+    ))]
     pub fn authorities_pem(&self) -> impl Iterator<Item = Vec<u8>> + '_ {
         self.authorities.iter().map(|authority| {
             authority
@@ -2,7 +2,10 @@ use std::{
     collections::HashMap,
     future::ready,
     num::NonZeroUsize,
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
     time::Instant,
 };
 
@@ -11,6 +14,7 @@ use once_cell::sync::Lazy;
 use stream_cancel::{StreamExt as StreamCancelExt, Trigger, Tripwire};
 use tokio::{
     select,
+    task::JoinHandle,
     time::{timeout, Duration},
 };
 use tracing::Instrument;
@@ -35,11 +39,11 @@ use super::{
 };
 use crate::{
     config::{
-        ComponentKey, DataType, Input, Output, OutputId, ProxyConfig, SinkContext, SourceContext,
-        TransformContext, TransformOuter,
+        ComponentKey, DataType, EnrichmentTableConfig, GlobalOptions, Input, Output, OutputId,
+        ProxyConfig, SinkContext, SourceContext, TransformContext, TransformOuter,
     },
     event::{EventArray, EventContainer},
-    internal_events::EventsReceived,
+    internal_events::{EnrichmentTableLoaded, EnrichmentTableReloadError, EventsReceived},
     shutdown::SourceShutdownCoordinator,
     source_sender::CHUNK_SIZE,
     spawn_named,
@@ -48,7 +52,7 @@ use crate::{
     SourceSender,
 };
 
-static ENRICHMENT_TABLES: Lazy<enrichment::TableRegistry> =
+pub(super) static ENRICHMENT_TABLES: Lazy<enrichment::TableRegistry> =
     Lazy::new(enrichment::TableRegistry::default);
 
 pub(crate) static SOURCE_SENDER_BUFFER_SIZE: Lazy<usize> =
@@ -73,7 +77,7 @@ pub(self) async fn load_enrichment_tables<'a>(
     let mut errors = vec![];
 
     // Build enrichment tables
-    'tables: for (name, table) in config.enrichment_tables.iter() {
+    for (name, table) in config.enrichment_tables.iter() {
         let table_name = name.to_string();
         if ENRICHMENT_TABLES.needs_reload(&table_name) {
             let indexes = if !diff.enrichment_tables.contains_new(name) {
@@ -93,24 +97,21 @@ pub(self) async fn load_enrichment_tables<'a>(
             };
 
             if let Some(indexes) = indexes {
-                for (case, index) in indexes {
-                    match table
-                        .add_index(case, &index.iter().map(|s| s.as_ref()).collect::<Vec<_>>())
-                    {
-                        Ok(_) => (),
-                        Err(error) => {
-                            // If there is an error adding an index we do not want to use the reloaded
-                            // data, the previously loaded data will still need to be used.
-                            // Just report the error and continue.
-                            error!(message = "Unable to add index to reloaded enrichment table.",
-                                    table = ?name.to_string(),
-                                    %error);
-                            continue 'tables;
-                        }
-                    }
+                if let Err(error) = add_enrichment_table_indexes(&mut table, indexes) {
+                    // If there is an error adding an index we do not want to use the reloaded
+                    // data, the previously loaded data will still need to be used.
+                    // Just report the error and continue.
+                    error!(message = "Unable to add index to reloaded enrichment table.",
+                            table = ?name.to_string(),
+                            %error);
+                    continue;
                 }
             }
 
+            emit!(EnrichmentTableLoaded {
+                table: &table_name,
+                rows: table.rows(),
+            });
             enrichment_tables.insert(table_name, table);
         }
     }
@@ -120,6 +121,74 @@ pub(self) async fn load_enrichment_tables<'a>(
     (&ENRICHMENT_TABLES, errors)
 }
 
+/// Adds the indexes of the previously loaded data of a table to its reloaded data.
+fn add_enrichment_table_indexes(
+    table: &mut Box<dyn enrichment::Table + Send + Sync>,
+    indexes: Vec<(enrichment::Case, Vec<String>)>,
+) -> Result<(), String> {
+    for (case, index) in indexes {
+        table.add_index(case, &index.iter().map(|s| s.as_ref()).collect::<Vec<_>>())?;
+    }
+    Ok(())
+}
+
+/// Spawns the tasks reloading the enrichment tables that have a reload interval.
+///
+/// At its interval, each table is checked for changes to its data. A table whose data changed is
+/// rebuilt and swapped in, whilst the transforms keep searching the previously loaded data, so
+/// that the configuration doesn't need to be reloaded. Tables aren't reloaded while `paused` is
+/// set.
+pub(super) fn spawn_enrichment_table_reloads(
+    config: &super::Config,
+    paused: Arc<AtomicBool>,
+) -> Vec<JoinHandle<()>> {
+    config
+        .enrichment_tables
+        .iter()
+        .filter_map(|(name, table)| {
+            let period = Duration::from_secs(table.reload_interval_secs?.get());
+            let name = name.to_string();
+            let table = dyn_clone::clone_box(&*table.inner);
+            let globals = config.global.clone();
+            let paused = Arc::clone(&paused);
+
+            Some(tokio::spawn(async move {
+                let mut interval =
+                    tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+                loop {
+                    interval.tick().await;
+                    if paused.load(Ordering::Relaxed) || !ENRICHMENT_TABLES.needs_reload(&name) {
+                        continue;
+                    }
+
+                    match reload_enrichment_table(&name, &*table, &globals).await {
+                        Ok(rows) => emit!(EnrichmentTableLoaded { table: &name, rows }),
+                        Err(error) => emit!(EnrichmentTableReloadError {
+                            table: &name,
+                            error,
+                        }),
+                    }
+                }
+            }))
+        })
+        .collect()
+}
+
+async fn reload_enrichment_table(
+    name: &str,
+    table: &dyn EnrichmentTableConfig,
+    globals: &GlobalOptions,
+) -> Result<Option<usize>, String> {
+    let mut reloaded = table
+        .build(globals)
+        .await
+        .map_err(|error| error.to_string())?;
+    add_enrichment_table_indexes(&mut reloaded, ENRICHMENT_TABLES.index_fields(name))?;
+    let rows = reloaded.rows();
+    ENRICHMENT_TABLES.swap_table(name, reloaded)?;
+    Ok(rows)
+}
+
 pub struct Pieces {
     pub(super) inputs: HashMap<ComponentKey, (BufferSender<EventArray>, Vec<OutputId>)>,
     pub(crate) outputs: HashMap<ComponentKey, HashMap<Option<String>, fanout::ControlChannel>>,
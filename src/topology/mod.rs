@@ -89,6 +89,7 @@ pub async fn start_validated(
     }
     running_topology.connect_diff(&diff, &mut pieces).await;
     running_topology.spawn_diff(&diff, pieces);
+    running_topology.spawn_enrichment_table_reloads();
 
     Some((running_topology, abort_rx))
 }
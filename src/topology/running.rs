@@ -9,6 +9,7 @@ use std::{
 use futures::{future, Future, FutureExt};
 use tokio::{
     sync::{mpsc, watch},
+    task::JoinHandle,
     time::{interval, sleep_until, Duration, Instant},
 };
 use tracing::Instrument;
@@ -43,6 +44,25 @@ pub struct RunningTopology {
     abort_tx: mpsc::UnboundedSender<()>,
     watch: (WatchTx, WatchRx),
     pub(crate) running: Arc<AtomicBool>,
+    enrichment_table_reloads: Vec<JoinHandle<()>>,
+    enrichment_table_reloads_paused: Arc<AtomicBool>,
+}
+
+/// Pauses the reloads of the enrichment tables until it is dropped, so that they are resumed on
+/// every path out of a configuration reload.
+struct PausedEnrichmentTableReloads(Arc<AtomicBool>);
+
+impl PausedEnrichmentTableReloads {
+    fn new(paused: &Arc<AtomicBool>) -> Self {
+        paused.store(true, Ordering::Relaxed);
+        Self(Arc::clone(paused))
+    }
+}
+
+impl Drop for PausedEnrichmentTableReloads {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
 }
 
 impl RunningTopology {
@@ -58,6 +78,8 @@ impl RunningTopology {
             abort_tx,
             watch: watch::channel(TapResource::default()),
             running: Arc::new(AtomicBool::new(true)),
+            enrichment_table_reloads: Vec::new(),
+            enrichment_table_reloads_paused: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -97,9 +119,10 @@ impl RunningTopology {
     /// poll for when the tasks have completed. Once the returned future is
     /// dropped then everything from this RunningTopology instance is fully
     /// dropped.
-    pub fn stop(self) -> impl Future<Output = ()> {
+    pub fn stop(mut self) -> impl Future<Output = ()> {
         // Update the API's health endpoint to signal shutdown
         self.running.store(false, Ordering::Relaxed);
+        self.stop_enrichment_table_reloads();
         // Create handy handles collections of all tasks for the subsequent
         // operations.
         let mut wait_handles = Vec::new();
@@ -214,6 +237,10 @@ impl RunningTopology {
             return Ok(false);
         }
 
+        // The enrichment tables are reloaded along with the configuration, so their own reloads
+        // are paused until the configuration is reloaded, or restored.
+        let _paused = PausedEnrichmentTableReloads::new(&self.enrichment_table_reloads_paused);
+
         // Calculate the change between the current configuration and the new configuration, and
         // shutdown any components that are changing so that we can reclaim their buffers before
         // spawning the new version of the component.
@@ -245,6 +272,7 @@ impl RunningTopology {
                 self.connect_diff(&diff, &mut new_pieces).await;
                 self.spawn_diff(&diff, new_pieces);
                 self.config = new_config;
+                self.spawn_enrichment_table_reloads();
 
                 info!("New configuration loaded successfully.");
 
@@ -277,6 +305,22 @@ impl RunningTopology {
         Err(())
     }
 
+    /// Starts reloading the enrichment tables of the current configuration that have a reload
+    /// interval.
+    pub(crate) fn spawn_enrichment_table_reloads(&mut self) {
+        self.stop_enrichment_table_reloads();
+        self.enrichment_table_reloads = builder::spawn_enrichment_table_reloads(
+            &self.config,
+            Arc::clone(&self.enrichment_table_reloads_paused),
+        );
+    }
+
+    fn stop_enrichment_table_reloads(&mut self) {
+        for reload in self.enrichment_table_reloads.drain(..) {
+            reload.abort();
+        }
+    }
+
     pub(crate) async fn run_healthchecks(
         &mut self,
         diff: &ConfigDiff,
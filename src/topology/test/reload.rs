@@ -302,3 +302,111 @@ async fn reload_sink_test(
         _ = crash_stream.next() => panic!(),
     }
 }
+
+#[cfg(feature = "enrichment-tables-file")]
+fn file_table_config(
+    builder: &mut crate::config::ConfigBuilder,
+    name: &str,
+    path: &std::path::Path,
+) {
+    let table: crate::enrichment_tables::file::FileConfig = toml::from_str(&format!(
+        r#"
+        file.path = {:?}
+        file.encoding.type = "csv"
+        "#,
+        path
+    ))
+    .unwrap();
+    builder.add_enrichment_table(name, table);
+    builder
+        .enrichment_tables
+        .get_mut(&ComponentKey::from(name))
+        .unwrap()
+        .reload_interval_secs = NonZeroU64::new(1);
+}
+
+#[cfg(feature = "enrichment-tables-file")]
+fn table_contains(name: &str, value: &str) -> bool {
+    crate::topology::builder::ENRICHMENT_TABLES
+        .as_readonly()
+        .find_table_row(
+            name,
+            enrichment::Case::Sensitive,
+            &[enrichment::Condition::Equals {
+                field: "field",
+                value: value.into(),
+            }],
+            None,
+            None,
+        )
+        .is_ok()
+}
+
+#[cfg(feature = "enrichment-tables-file")]
+async fn wait_for_table_row(name: &'static str, value: &'static str) {
+    crate::test_util::wait_for(move || async move { table_contains(name, value) }).await;
+}
+
+#[cfg(feature = "enrichment-tables-file")]
+#[tokio::test]
+async fn enrichment_table_failed_reload_keeps_old_data() {
+    let name = "failed_reload_table";
+    let path = crate::test_util::temp_file();
+    std::fs::write(&path, "field\nold\n").unwrap();
+
+    let mut config = Config::builder();
+    config.add_source("in", crate::test_util::mock::basic_source().1);
+    config.add_sink("out", &["in"], basic_sink(1).1);
+    file_table_config(&mut config, name, &path);
+
+    let (_topology, _crash) = start_topology(config.build().unwrap(), false).await;
+    assert!(table_contains(name, "old"));
+
+    // Rows with more fields than the headers can't be loaded.
+    std::fs::write(&path, "field\na,b\n").unwrap();
+    sleep(Duration::from_millis(2500)).await;
+    assert!(table_contains(name, "old"));
+
+    // The table is reloaded once the file is fixed.
+    std::fs::write(&path, "field\nnew\n").unwrap();
+    wait_for_table_row(name, "new").await;
+    assert!(!table_contains(name, "old"));
+}
+
+#[cfg(feature = "enrichment-tables-file")]
+#[tokio::test]
+async fn enrichment_table_reloads_survive_config_reloads() {
+    let name = "config_reload_table";
+    let path = crate::test_util::temp_file();
+    std::fs::write(&path, "field\nfirst\n").unwrap();
+    let address_0 = next_addr();
+    let address_1 = next_addr();
+
+    let config = |address| {
+        let mut config = Config::builder();
+        config.add_source("in", splunk_source_config(address));
+        config.add_sink("out", &["in"], basic_sink(1).1);
+        file_table_config(&mut config, name, &path);
+        config.build().unwrap()
+    };
+
+    let (mut topology, _crash) = start_topology(config(address_0), false).await;
+    assert!(table_contains(name, "first"));
+
+    // The reloads are respawned with the new configuration.
+    assert!(topology
+        .reload_config_and_respawn(config(address_1))
+        .await
+        .unwrap());
+    std::fs::write(&path, "field\nsecond\n").unwrap();
+    wait_for_table_row(name, "second").await;
+
+    // The reloads are resumed when the old configuration is restored.
+    let _bind = TcpListener::bind(address_0).unwrap();
+    assert!(!topology
+        .reload_config_and_respawn(config(address_0))
+        .await
+        .unwrap());
+    std::fs::write(&path, "field\nthird\n").unwrap();
+    wait_for_table_row(name, "third").await;
+}
@@ -0,0 +1,342 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use snafu::Snafu;
+use vector_config::configurable_component;
+
+use crate::{
+    conditions::{AnyCondition, Condition},
+    config::{
+        DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
+        TransformDescription,
+    },
+    event::Event,
+    internal_events::AdaptiveSampleEventDiscarded,
+    schema,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+/// Configuration for the `adaptive_sample` transform.
+#[configurable_component(transform)]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AdaptiveSampleConfig {
+    /// The number of events per second that the transform aims to forward.
+    ///
+    /// The sampling rates are adjusted at the end of each window, from the number of events received during the window.
+    pub target_events_per_sec: f64,
+
+    /// The name of the field whose values are sampled separately.
+    ///
+    /// The target is shared between the values, so that rare values are forwarded in full, while frequent values are
+    /// sampled at a higher rate. If left unspecified, all events share a single sampling rate. Events without the field
+    /// share the sampling rate of an empty value.
+    pub key_field: Option<String>,
+
+    /// The duration of the window over which the events are counted, in seconds, before the sampling rates are
+    /// adjusted.
+    #[serde(default = "default_window_secs")]
+    pub window_secs: u64,
+
+    /// The name of the field in which the sampling rate of the forwarded events is stored.
+    ///
+    /// The sampling rate is expressed as `N`, meaning that the event was forwarded out of every `N` events with the
+    /// same key, so that multiplying the counts of the forwarded events by it estimates the counts before sampling.
+    #[serde(default = "default_sample_rate_field")]
+    pub sample_rate_field: String,
+
+    /// A logical condition used to exclude events from sampling.
+    pub exclude: Option<AnyCondition>,
+}
+
+const fn default_window_secs() -> u64 {
+    10
+}
+
+fn default_sample_rate_field() -> String {
+    "sample_rate".to_owned()
+}
+
+#[derive(Debug, PartialEq, Snafu)]
+enum ConfigError {
+    #[snafu(display("`target_events_per_sec` must be greater than zero"))]
+    TargetNotPositive,
+    #[snafu(display("`window_secs` must be greater than zero"))]
+    WindowZero,
+}
+
+inventory::submit! {
+    TransformDescription::new::<AdaptiveSampleConfig>("adaptive_sample")
+}
+
+impl GenerateConfig for AdaptiveSampleConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            target_events_per_sec: 100.0,
+            key_field: None,
+            window_secs: default_window_secs(),
+            sample_rate_field: default_sample_rate_field(),
+            exclude: None::<AnyCondition>,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "adaptive_sample")]
+impl TransformConfig for AdaptiveSampleConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        AdaptiveSample::new(self, context).map(Transform::function)
+    }
+
+    fn input(&self) -> Input {
+        Input::new(DataType::Log | DataType::Trace)
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Log | DataType::Trace)]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "adaptive_sample"
+    }
+}
+
+/// The sampling state of the events sharing a key.
+#[derive(Clone, Debug)]
+struct KeyState {
+    /// One out of every `rate` events is forwarded.
+    rate: u64,
+    /// The number of events received during the current window.
+    received: u64,
+}
+
+impl Default for KeyState {
+    fn default() -> Self {
+        Self {
+            rate: 1,
+            received: 0,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AdaptiveSample {
+    target_events_per_sec: f64,
+    key_field: Option<String>,
+    window: Duration,
+    sample_rate_field: String,
+    exclude: Option<Condition>,
+    window_start: Instant,
+    keys: HashMap<String, KeyState>,
+}
+
+impl AdaptiveSample {
+    pub fn new(config: &AdaptiveSampleConfig, context: &TransformContext) -> crate::Result<Self> {
+        if !config.target_events_per_sec.is_finite() || config.target_events_per_sec <= 0.0 {
+            return Err(Box::new(ConfigError::TargetNotPositive));
+        }
+        if config.window_secs == 0 {
+            return Err(Box::new(ConfigError::WindowZero));
+        }
+
+        Ok(Self {
+            target_events_per_sec: config.target_events_per_sec,
+            key_field: config.key_field.clone(),
+            window: Duration::from_secs(config.window_secs),
+            sample_rate_field: config.sample_rate_field.clone(),
+            exclude: config
+                .exclude
+                .as_ref()
+                .map(|condition| condition.build(&context.enrichment_tables))
+                .transpose()?,
+            window_start: Instant::now(),
+            keys: HashMap::new(),
+        })
+    }
+
+    fn sample(&mut self, output: &mut OutputBuffer, event: Event, now: Instant) {
+        let mut event = match self.exclude.as_ref() {
+            Some(condition) => {
+                let (result, event) = condition.check(event);
+                if result {
+                    output.push(event);
+                    return;
+                }
+                event
+            }
+            None => event,
+        };
+
+        if now.duration_since(self.window_start) >= self.window {
+            self.adjust_rates(now);
+        }
+
+        let key = self
+            .key_field
+            .as_ref()
+            .and_then(|key_field| match &event {
+                Event::Log(event) => event.get(key_field.as_str()),
+                Event::Trace(event) => event.get(key_field.as_str()),
+                Event::Metric(_) => panic!("component can never receive metric events"),
+            })
+            .map(|value| value.to_string_lossy())
+            .unwrap_or_default();
+
+        let state = self.keys.entry(key).or_default();
+        let forward = state.received % state.rate == 0;
+        state.received += 1;
+
+        if forward {
+            let rate = state.rate as i64;
+            match event {
+                Event::Log(ref mut event) => event.insert(self.sample_rate_field.as_str(), rate),
+                Event::Trace(ref mut event) => event.insert(self.sample_rate_field.as_str(), rate),
+                Event::Metric(_) => panic!("component can never receive metric events"),
+            };
+            output.push(event);
+        } else {
+            emit!(AdaptiveSampleEventDiscarded);
+        }
+    }
+
+    /// Shares the target between the keys received during the window that ended, and sets the
+    /// sampling rate of each key so that its events are forwarded within its share.
+    ///
+    /// Keys receiving fewer events than an even share are forwarded in full, and the rest of their
+    /// share is split between the other keys.
+    fn adjust_rates(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.window_start).as_secs_f64();
+        self.window_start = now;
+
+        // Keys that received no event are forgotten, and start again from forwarding every event.
+        self.keys.retain(|_, state| state.received > 0);
+
+        let mut keys = self.keys.values_mut().collect::<Vec<_>>();
+        keys.sort_by_key(|state| state.received);
+
+        let mut remaining = self.target_events_per_sec;
+        let count = keys.len();
+        for (index, state) in keys.into_iter().enumerate() {
+            let received = state.received as f64 / elapsed;
+            let share = remaining / (count - index) as f64;
+            let forwarded = received.min(share);
+            remaining -= forwarded;
+
+            state.rate = (received / forwarded).ceil().max(1.0) as u64;
+            state.received = 0;
+        }
+    }
+}
+
+impl FunctionTransform for AdaptiveSample {
+    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        self.sample(output, event, Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{LogEvent, Value};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AdaptiveSampleConfig>();
+    }
+
+    fn sampler(config: &str) -> AdaptiveSample {
+        let config = toml::from_str::<AdaptiveSampleConfig>(config).unwrap();
+        AdaptiveSample::new(&config, &Default::default()).unwrap()
+    }
+
+    /// Sends the events of a window, one per key in the list, and returns the forwarded events.
+    fn run_window(sampler: &mut AdaptiveSample, keys: &[&str], now: Instant) -> Vec<Event> {
+        let mut output = OutputBuffer::with_capacity(keys.len());
+        for key in keys {
+            let mut log = LogEvent::from("message");
+            log.insert("service", *key);
+            sampler.sample(&mut output, log.into(), now);
+        }
+        output.into_events().collect()
+    }
+
+    fn rates(events: &[Event], key: &str) -> Vec<Value> {
+        events
+            .iter()
+            .filter(|event| event.as_log()["service"] == key.into())
+            .map(|event| event.as_log()["sample_rate"].clone())
+            .collect()
+    }
+
+    #[test]
+    fn adjusts_to_the_target() {
+        let mut sampler = sampler("target_events_per_sec = 10\nwindow_secs = 10");
+        let start = sampler.window_start;
+
+        // Nothing is known about the first window, so every event is forwarded.
+        let events = run_window(&mut sampler, &["a"; 1000], start);
+        assert_eq!(events.len(), 1000);
+        assert_eq!(rates(&events, "a")[0], Value::from(1));
+
+        // 100 events per second are received, so 1 out of 10 is forwarded.
+        let events = run_window(&mut sampler, &["a"; 1000], start + Duration::from_secs(10));
+        assert_eq!(events.len(), 100);
+        assert!(rates(&events, "a")
+            .iter()
+            .all(|rate| *rate == Value::from(10)));
+    }
+
+    #[test]
+    fn shares_the_target_between_keys() {
+        let mut sampler =
+            sampler("target_events_per_sec = 10\nwindow_secs = 10\nkey_field = \"service\"");
+        let start = sampler.window_start;
+
+        let mut keys = vec!["frequent"; 1000];
+        keys.extend(["rare"; 20]);
+        run_window(&mut sampler, &keys, start);
+
+        // The rare key uses 2 of the 10 events per second, the frequent key gets the other 8.
+        let events = run_window(&mut sampler, &keys, start + Duration::from_secs(10));
+        assert_eq!(rates(&events, "rare"), vec![Value::from(1); 20]);
+        assert_eq!(rates(&events, "frequent"), vec![Value::from(13); 77]);
+    }
+
+    #[test]
+    fn passes_excluded_events() {
+        let mut sampler = sampler(
+            r#"
+            target_events_per_sec = 1
+            window_secs = 1
+            exclude = '.service == "important"'
+            "#,
+        );
+        let start = sampler.window_start;
+
+        run_window(&mut sampler, &["important"; 100], start);
+        let events = run_window(
+            &mut sampler,
+            &["important"; 100],
+            start + Duration::from_secs(1),
+        );
+        assert_eq!(events.len(), 100);
+        assert!(events
+            .iter()
+            .all(|event| event.as_log().get("sample_rate").is_none()));
+    }
+
+    #[test]
+    fn rejects_invalid_configs() {
+        let config = toml::from_str::<AdaptiveSampleConfig>("target_events_per_sec = 0").unwrap();
+        let error = AdaptiveSample::new(&config, &Default::default())
+            .map(|_| ())
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "`target_events_per_sec` must be greater than zero"
+        );
+    }
+}
@@ -0,0 +1,536 @@
+use std::{
+    collections::{hash_map, BTreeMap, HashMap, VecDeque},
+    pin::Pin,
+    time::Duration,
+};
+
+use async_stream::stream;
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use vector_config::configurable_component;
+
+use crate::{
+    config::{
+        log_schema, DataType, Input, Output, TransformConfig, TransformContext,
+        TransformDescription,
+    },
+    event::{discriminant::Discriminant, Event, EventMetadata, LogEvent, Value},
+    internal_events::AggregateLogsFlushed,
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+/// Configuration for the `aggregate_logs` transform.
+#[configurable_component(transform)]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct AggregateLogsConfig {
+    /// An ordered list of fields by which to group events.
+    ///
+    /// Each group with matching values for the specified fields is summarized independently. When no fields are
+    /// specified, all the events of a window are summarized in a single event.
+    pub group_by: Vec<String>,
+
+    /// The length of the windows, in milliseconds.
+    #[serde(default = "default_window_ms")]
+    #[derivative(Default(value = "default_window_ms()"))]
+    pub window_ms: u64,
+
+    /// The interval between the starts of two consecutive windows, in milliseconds.
+    ///
+    /// By default, windows are tumbling: each event belongs to a single window. When lower than `window_ms`, windows
+    /// are sliding and overlap, so that each event is summarized in `window_ms / slide_ms` windows. `window_ms` must
+    /// be a multiple of `slide_ms`.
+    pub slide_ms: Option<u64>,
+
+    /// A list of numeric fields to summarize with their sum, minimum and maximum.
+    ///
+    /// The values that are missing or not numeric are ignored.
+    pub numeric_fields: Vec<String>,
+
+    /// The maximum number of messages kept as samples of each window.
+    #[serde(default = "default_max_samples")]
+    #[derivative(Default(value = "default_max_samples()"))]
+    pub max_samples: usize,
+}
+
+const fn default_window_ms() -> u64 {
+    10 * 1000
+}
+
+const fn default_max_samples() -> usize {
+    5
+}
+
+inventory::submit! {
+    TransformDescription::new::<AggregateLogsConfig>("aggregate_logs")
+}
+
+impl_generate_config_from_default!(AggregateLogsConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "aggregate_logs")]
+impl TransformConfig for AggregateLogsConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        AggregateLogs::new(self).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "aggregate_logs"
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct NumericStats {
+    sum: f64,
+    min: f64,
+    max: f64,
+}
+
+impl NumericStats {
+    const fn new(value: f64) -> Self {
+        Self {
+            sum: value,
+            min: value,
+            max: value,
+        }
+    }
+
+    fn add(&mut self, other: Self) {
+        self.sum += other.sum;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    fn into_value(self) -> Value {
+        let mut stats = BTreeMap::new();
+        stats.insert("sum".to_owned(), Value::from_f64_or_zero(self.sum));
+        stats.insert("min".to_owned(), Value::from_f64_or_zero(self.min));
+        stats.insert("max".to_owned(), Value::from_f64_or_zero(self.max));
+        Value::Object(stats)
+    }
+}
+
+/// The summary of the events of a group received during a window.
+#[derive(Clone, Debug)]
+struct Summary {
+    group: Vec<Option<Value>>,
+    count: u64,
+    numeric: Vec<Option<NumericStats>>,
+    samples: Vec<Value>,
+    metadata: EventMetadata,
+}
+
+impl Summary {
+    fn new(group: Vec<Option<Value>>, numeric_fields: usize) -> Self {
+        Self {
+            group,
+            count: 0,
+            numeric: vec![None; numeric_fields],
+            samples: Vec::new(),
+            metadata: EventMetadata::default(),
+        }
+    }
+
+    fn record(&mut self, event: LogEvent, numeric_fields: &[String], max_samples: usize) {
+        self.count += 1;
+
+        for (stats, field) in self.numeric.iter_mut().zip(numeric_fields) {
+            let value = match event.get(field.as_str()) {
+                Some(Value::Integer(value)) => *value as f64,
+                Some(Value::Float(value)) => value.into_inner(),
+                _ => continue,
+            };
+            match stats {
+                Some(stats) => stats.add(NumericStats::new(value)),
+                None => *stats = Some(NumericStats::new(value)),
+            }
+        }
+
+        if self.samples.len() < max_samples {
+            if let Some(message) = event.get(log_schema().message_key()) {
+                self.samples.push(message.clone());
+            }
+        }
+
+        let (_, metadata) = event.into_parts();
+        self.metadata.merge(metadata);
+    }
+
+    fn merge(&mut self, other: Self, max_samples: usize) {
+        self.count += other.count;
+
+        for (stats, other) in self.numeric.iter_mut().zip(other.numeric) {
+            match (stats.as_mut(), other) {
+                (Some(stats), Some(other)) => stats.add(other),
+                (None, other) => *stats = other,
+                (Some(_), None) => (),
+            }
+        }
+
+        let available = max_samples.saturating_sub(self.samples.len());
+        self.samples
+            .extend(other.samples.into_iter().take(available));
+
+        self.metadata.merge(other.metadata);
+    }
+
+    fn into_event(
+        self,
+        group_by: &[String],
+        numeric_fields: &[String],
+        window_start: DateTime<Utc>,
+        window_end: DateTime<Utc>,
+    ) -> LogEvent {
+        let mut event = LogEvent::new_with_metadata(self.metadata);
+        for (field, value) in group_by.iter().zip(self.group) {
+            if let Some(value) = value {
+                event.insert(field.as_str(), value);
+            }
+        }
+        for (field, stats) in numeric_fields.iter().zip(self.numeric) {
+            if let Some(stats) = stats {
+                event.insert(field.as_str(), stats.into_value());
+            }
+        }
+        event.insert("count", self.count);
+        event.insert("samples", Value::Array(self.samples));
+        event.insert("window_start", window_start);
+        event.insert(log_schema().timestamp_key(), window_end);
+        event
+    }
+}
+
+/// The summaries of the events received during a slide of the windows.
+#[derive(Debug)]
+struct Pane {
+    start: DateTime<Utc>,
+    summaries: HashMap<Discriminant, Summary>,
+}
+
+impl Pane {
+    fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            start,
+            summaries: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AggregateLogs {
+    group_by: Vec<String>,
+    numeric_fields: Vec<String>,
+    max_samples: usize,
+    slide: Duration,
+    panes_per_window: usize,
+    panes: VecDeque<Pane>,
+}
+
+impl AggregateLogs {
+    pub fn new(config: &AggregateLogsConfig) -> crate::Result<Self> {
+        if config.window_ms == 0 {
+            return Err("`window_ms` must be greater than zero".into());
+        }
+        let slide_ms = config.slide_ms.unwrap_or(config.window_ms);
+        if slide_ms == 0 || config.window_ms % slide_ms != 0 {
+            return Err("`window_ms` must be a multiple of `slide_ms`".into());
+        }
+
+        Ok(Self {
+            group_by: config.group_by.clone(),
+            numeric_fields: config.numeric_fields.clone(),
+            max_samples: config.max_samples,
+            slide: Duration::from_millis(slide_ms),
+            panes_per_window: (config.window_ms / slide_ms) as usize,
+            panes: VecDeque::from([Pane::new(Utc::now())]),
+        })
+    }
+
+    fn record(&mut self, event: Event) {
+        let event = event.into_log();
+        let discriminant = Discriminant::from_log_event(&event, &self.group_by);
+
+        let pane = self
+            .panes
+            .back_mut()
+            .expect("there is always a current pane");
+        let summary = pane.summaries.entry(discriminant).or_insert_with(|| {
+            let group = self
+                .group_by
+                .iter()
+                .map(|field| event.get(field.as_str()).cloned())
+                .collect();
+            Summary::new(group, self.numeric_fields.len())
+        });
+        summary.record(event, &self.numeric_fields, self.max_samples);
+    }
+
+    /// Summarizes the window ending now and starts the next slide of the windows.
+    fn flush_into(&mut self, output: &mut Vec<Event>) {
+        let window_end = Utc::now();
+        let window_start = self.panes.front().map_or(window_end, |pane| pane.start);
+
+        self.panes.push_back(Pane::new(window_end));
+        let mut windows = if self.panes.len() > self.panes_per_window {
+            self.panes
+                .pop_front()
+                .map(|pane| pane.summaries)
+                .unwrap_or_default()
+        } else {
+            HashMap::new()
+        };
+        // The summaries of the panes that are part of the next windows too are kept.
+        for pane in &self.panes {
+            for (discriminant, summary) in &pane.summaries {
+                match windows.entry(discriminant.clone()) {
+                    hash_map::Entry::Vacant(entry) => {
+                        entry.insert(summary.clone());
+                    }
+                    hash_map::Entry::Occupied(mut entry) => {
+                        entry.get_mut().merge(summary.clone(), self.max_samples);
+                    }
+                }
+            }
+        }
+
+        let count = windows.len();
+        output.extend(windows.into_values().map(|summary| {
+            Event::from(summary.into_event(
+                &self.group_by,
+                &self.numeric_fields,
+                window_start,
+                window_end,
+            ))
+        }));
+
+        emit!(AggregateLogsFlushed { count });
+    }
+}
+
+impl TaskTransform<Event> for AggregateLogs {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut flush_stream =
+            tokio::time::interval_at(tokio::time::Instant::now() + self.slide, self.slide);
+
+        Box::pin(stream! {
+            let mut output = Vec::new();
+            let mut done = false;
+            while !done {
+                tokio::select! {
+                    _ = flush_stream.tick() => {
+                        self.flush_into(&mut output);
+                    },
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            None => {
+                                self.flush_into(&mut output);
+                                done = true;
+                            }
+                            Some(event) => self.record(event),
+                        }
+                    }
+                };
+                for event in output.drain(..) {
+                    yield event;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::task::Poll;
+
+    use futures::{stream, SinkExt};
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AggregateLogsConfig>();
+    }
+
+    fn make_event(message: &str, service: &str, duration: impl Into<Value>) -> Event {
+        let mut event = LogEvent::from(message);
+        event.insert("service", service);
+        event.insert("duration", duration);
+        event.into()
+    }
+
+    fn aggregate(config: &str) -> AggregateLogs {
+        AggregateLogs::new(&toml::from_str(config).unwrap()).unwrap()
+    }
+
+    fn find<'a>(events: &'a [Event], service: &str) -> &'a LogEvent {
+        events
+            .iter()
+            .map(Event::as_log)
+            .find(|log| log["service"] == service.into())
+            .unwrap()
+    }
+
+    #[test]
+    fn summarizes_groups() {
+        let mut aggregate = aggregate(
+            r#"
+group_by = ["service"]
+numeric_fields = ["duration"]
+max_samples = 2
+"#,
+        );
+
+        aggregate.record(make_event("first", "api", 3));
+        aggregate.record(make_event("second", "api", 1.5));
+        aggregate.record(make_event("third", "api", 7));
+        aggregate.record(make_event("fourth", "db", "slow"));
+
+        let mut output = Vec::new();
+        aggregate.flush_into(&mut output);
+        assert_eq!(output.len(), 2);
+
+        let api = find(&output, "api");
+        assert_eq!(api["count"], Value::from(3));
+        assert_eq!(api["duration.sum"], Value::from(11.5));
+        assert_eq!(api["duration.min"], Value::from(1.5));
+        assert_eq!(api["duration.max"], Value::from(7.0));
+        assert_eq!(
+            api["samples"],
+            Value::Array(vec!["first".into(), "second".into()])
+        );
+        assert!(api.get("window_start").is_some());
+
+        let db = find(&output, "db");
+        assert_eq!(db["count"], Value::from(1));
+        assert!(db.get("duration").is_none());
+        assert_eq!(db["samples"], Value::Array(vec!["fourth".into()]));
+
+        // Tumbling windows don't overlap.
+        output.clear();
+        aggregate.flush_into(&mut output);
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn sliding_windows() {
+        let mut aggregate = aggregate(
+            r#"
+window_ms = 3000
+slide_ms = 1000
+"#,
+        );
+
+        aggregate.record(make_event("first", "api", 1));
+        let mut output = Vec::new();
+        aggregate.flush_into(&mut output);
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].as_log()["count"], Value::from(1));
+
+        aggregate.record(make_event("second", "api", 1));
+        output.clear();
+        aggregate.flush_into(&mut output);
+        assert_eq!(output[0].as_log()["count"], Value::from(2));
+
+        output.clear();
+        aggregate.flush_into(&mut output);
+        assert_eq!(output[0].as_log()["count"], Value::from(2));
+        assert_eq!(
+            output[0].as_log()["samples"],
+            Value::Array(vec!["first".into(), "second".into()])
+        );
+
+        // The first event left the window.
+        output.clear();
+        aggregate.flush_into(&mut output);
+        assert_eq!(output[0].as_log()["count"], Value::from(1));
+
+        output.clear();
+        aggregate.flush_into(&mut output);
+        output.clear();
+        aggregate.flush_into(&mut output);
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn invalid_slide() {
+        let config = toml::from_str::<AggregateLogsConfig>(
+            r#"
+window_ms = 3000
+slide_ms = 2000
+"#,
+        )
+        .unwrap();
+        assert!(AggregateLogs::new(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn transform_shutdown() {
+        let aggregate = toml::from_str::<AggregateLogsConfig>(
+            r#"
+group_by = ["service"]
+window_ms = 999999
+"#,
+        )
+        .unwrap()
+        .build(&TransformContext::default())
+        .await
+        .unwrap()
+        .into_task();
+
+        let inputs = vec![
+            make_event("first", "api", 1),
+            make_event("second", "db", 1),
+            make_event("third", "api", 1),
+        ];
+        let out_stream = aggregate.transform_events(Box::pin(stream::iter(inputs)));
+        let output = out_stream.collect::<Vec<_>>().await;
+
+        assert_eq!(output.len(), 2);
+        assert_eq!(find(&output, "api")["count"], Value::from(2));
+        assert_eq!(find(&output, "db")["count"], Value::from(1));
+    }
+
+    #[tokio::test]
+    async fn transform_interval() {
+        let aggregate = toml::from_str::<AggregateLogsConfig>("")
+            .unwrap()
+            .build(&TransformContext::default())
+            .await
+            .unwrap()
+            .into_task();
+
+        let (mut tx, rx) = futures::channel::mpsc::channel(10);
+
+        tokio::time::pause();
+        let mut out_stream = aggregate.transform_events(Box::pin(rx));
+
+        tx.send(make_event("first", "api", 1)).await.unwrap();
+        tx.send(make_event("second", "api", 1)).await.unwrap();
+        // The window hasn't ended yet.
+        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+
+        tokio::time::advance(Duration::from_secs(11)).await;
+        let event = out_stream.next().await.unwrap();
+        assert_eq!(event.as_log()["count"], Value::from(2));
+        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+
+        tx.disconnect();
+        assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
+    }
+}
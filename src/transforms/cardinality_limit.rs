@@ -0,0 +1,318 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
+    hash::{Hash, Hasher},
+};
+
+use vector_config::configurable_component;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
+        TransformDescription,
+    },
+    event::{metric::MetricTags, Event, Metric},
+    internal_events::{CardinalityLimitLimitingSeries, CardinalityLimitSeriesLimitReached},
+    schema,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+/// The tag of the series that new series are aggregated into with the `overflow` action.
+pub const OVERFLOW_TAG: &str = "overflow";
+
+/// Configuration for the `cardinality_limit` transform.
+#[configurable_component(transform)]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CardinalityLimitConfig {
+    /// How many distinct series, or sets of tags, to accept for any given metric name.
+    #[serde(default = "default_series_limit")]
+    pub series_limit: u32,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub limit_exceeded_action: LimitExceededAction,
+}
+
+/// Possible actions to take when a metric event arrives that would exceed the series limit of its metric name.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitExceededAction {
+    /// Drop the metric event.
+    #[derivative(Default)]
+    DropSeries,
+
+    /// Replace the tags of the metric event with a single `overflow` tag, so that all the new series of the metric
+    /// name are aggregated into a single series.
+    Overflow,
+
+    /// Remove the tags whose value was never accepted for the metric name.
+    ///
+    /// The metric event is dropped if the remaining tags still make up a new series.
+    StripTags,
+}
+
+impl LimitExceededAction {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::DropSeries => "drop_series",
+            Self::Overflow => "overflow",
+            Self::StripTags => "strip_tags",
+        }
+    }
+}
+
+const fn default_series_limit() -> u32 {
+    1000
+}
+
+inventory::submit! {
+    TransformDescription::new::<CardinalityLimitConfig>("cardinality_limit")
+}
+
+impl GenerateConfig for CardinalityLimitConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            series_limit: default_series_limit(),
+            limit_exceeded_action: LimitExceededAction::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "cardinality_limit")]
+impl TransformConfig for CardinalityLimitConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::function(CardinalityLimit::new(self)))
+    }
+
+    fn input(&self) -> Input {
+        Input::metric()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Metric)]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "cardinality_limit"
+    }
+}
+
+/// The series accepted for a metric name.
+#[derive(Clone, Debug, Default)]
+struct AcceptedSeries {
+    /// The hashes of the tags of the accepted series.
+    series: HashSet<u64>,
+    /// The values of the tags of the accepted series, only tracked for the `strip_tags` action.
+    tag_values: HashMap<String, HashSet<String>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CardinalityLimit {
+    series_limit: usize,
+    limit_exceeded_action: LimitExceededAction,
+    accepted: HashMap<(Option<String>, String), AcceptedSeries>,
+}
+
+impl CardinalityLimit {
+    pub fn new(config: &CardinalityLimitConfig) -> Self {
+        Self {
+            series_limit: config.series_limit as usize,
+            limit_exceeded_action: config.limit_exceeded_action,
+            accepted: HashMap::new(),
+        }
+    }
+
+    fn transform_one(&mut self, metric: Metric) -> Option<Metric> {
+        let name = (
+            metric.namespace().map(ToOwned::to_owned),
+            metric.name().to_owned(),
+        );
+        let hash = hash_tags(metric.tags());
+        let strip_tags = self.limit_exceeded_action == LimitExceededAction::StripTags;
+        let accepted = self.accepted.entry(name).or_default();
+
+        if accepted.series.contains(&hash) {
+            return Some(metric);
+        }
+
+        if accepted.series.len() < self.series_limit {
+            accepted.series.insert(hash);
+            if strip_tags {
+                for (key, value) in metric.tags().into_iter().flatten() {
+                    accepted
+                        .tag_values
+                        .entry(key.clone())
+                        .or_default()
+                        .insert(value.clone());
+                }
+            }
+            if accepted.series.len() == self.series_limit {
+                emit!(CardinalityLimitSeriesLimitReached {
+                    metric_name: metric.name(),
+                });
+            }
+            return Some(metric);
+        }
+
+        emit!(CardinalityLimitLimitingSeries {
+            metric_name: metric.name(),
+            action: self.limit_exceeded_action.as_str(),
+        });
+        match self.limit_exceeded_action {
+            LimitExceededAction::DropSeries => None,
+            LimitExceededAction::Overflow => {
+                let tags = MetricTags::from([(OVERFLOW_TAG.to_owned(), "true".to_owned())]);
+                Some(metric.with_tags(Some(tags)))
+            }
+            LimitExceededAction::StripTags => {
+                let mut metric = metric;
+                let offending = metric
+                    .tags()
+                    .into_iter()
+                    .flatten()
+                    .filter(|(key, value)| {
+                        !accepted
+                            .tag_values
+                            .get(*key)
+                            .map_or(false, |values| values.contains(*value))
+                    })
+                    .map(|(key, _)| key.clone())
+                    .collect::<Vec<_>>();
+                for key in offending {
+                    metric.remove_tag(&key);
+                }
+
+                if accepted.series.contains(&hash_tags(metric.tags())) {
+                    Some(metric)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+/// Hashes the tags of a series, with no tags and empty tags hashing alike.
+fn hash_tags(tags: Option<&MetricTags>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for (key, value) in tags.into_iter().flatten() {
+        key.hash(&mut hasher);
+        value.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+impl FunctionTransform for CardinalityLimit {
+    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        if let Some(metric) = self.transform_one(event.into_metric()) {
+            output.push(Event::Metric(metric));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::metric::{MetricKind, MetricValue};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<CardinalityLimitConfig>();
+    }
+
+    fn make_transform(action: LimitExceededAction) -> CardinalityLimit {
+        CardinalityLimit::new(&CardinalityLimitConfig {
+            series_limit: 2,
+            limit_exceeded_action: action,
+        })
+    }
+
+    fn make_metric(name: &str, tags: &[(&str, &str)]) -> Metric {
+        let tags = tags
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        Metric::new(
+            name,
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        )
+        .with_tags(Some(tags))
+    }
+
+    fn tags(metric: Option<Metric>) -> Option<Vec<(String, String)>> {
+        metric.map(|metric| {
+            metric
+                .tags()
+                .cloned()
+                .unwrap_or_default()
+                .into_iter()
+                .collect()
+        })
+    }
+
+    fn tag(key: &str, value: &str) -> (String, String) {
+        (key.to_owned(), value.to_owned())
+    }
+
+    #[test]
+    fn drops_new_series() {
+        let mut transform = make_transform(LimitExceededAction::DropSeries);
+
+        for host in ["a", "b", "a", "b"] {
+            let metric = make_metric("requests", &[("host", host)]);
+            assert!(transform.transform_one(metric).is_some());
+        }
+        assert!(transform
+            .transform_one(make_metric("requests", &[("host", "c")]))
+            .is_none());
+
+        // Each metric name has its own limit.
+        assert!(transform
+            .transform_one(make_metric("errors", &[("host", "c")]))
+            .is_some());
+    }
+
+    #[test]
+    fn aggregates_new_series_into_overflow() {
+        let mut transform = make_transform(LimitExceededAction::Overflow);
+
+        for host in ["a", "b"] {
+            transform.transform_one(make_metric("requests", &[("host", host)]));
+        }
+        for host in ["c", "d"] {
+            let metric = make_metric("requests", &[("host", host), ("path", "/")]);
+            assert_eq!(
+                tags(transform.transform_one(metric)),
+                Some(vec![tag(OVERFLOW_TAG, "true")])
+            );
+        }
+        assert_eq!(
+            tags(transform.transform_one(make_metric("requests", &[("host", "a")]))),
+            Some(vec![tag("host", "a")])
+        );
+    }
+
+    #[test]
+    fn strips_offending_tags() {
+        let mut transform = make_transform(LimitExceededAction::StripTags);
+
+        transform.transform_one(make_metric("requests", &[("host", "a")]));
+        transform.transform_one(make_metric("requests", &[("host", "b"), ("code", "200")]));
+
+        // The new `request_id` tag is stripped, which leaves a known series.
+        let metric = make_metric("requests", &[("host", "a"), ("request_id", "1234")]);
+        assert_eq!(
+            tags(transform.transform_one(metric)),
+            Some(vec![tag("host", "a")])
+        );
+
+        // Only known tag values remain, but they make up a new series.
+        let metric = make_metric("requests", &[("host", "a"), ("code", "200")]);
+        assert_eq!(tags(transform.transform_one(metric)), None);
+    }
+}
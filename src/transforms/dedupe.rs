@@ -1,8 +1,9 @@
-use std::{future::ready, pin::Pin};
+use std::{future::ready, pin::Pin, time::Duration};
 
 use bytes::Bytes;
 use futures::{Stream, StreamExt};
 use lru::LruCache;
+use tokio::time::Instant;
 use vector_config::configurable_component;
 
 use crate::{
@@ -41,6 +42,13 @@ pub enum FieldMatchConfig {
 pub struct CacheConfig {
     /// Number of events to cache and use for comparing incoming events to previously seen events.
     pub num_events: usize,
+
+    /// The number of seconds after which a cached event expires.
+    ///
+    /// An incoming event matching an expired entry is not considered a duplicate and renews the entry. By default,
+    /// entries only leave the cache when evicted by newer ones.
+    #[serde(default)]
+    pub time_to_live_secs: Option<u64>,
 }
 
 /// Configuration for the `dedupe` transform.
@@ -55,10 +63,20 @@ pub struct DedupeConfig {
     #[configurable(derived)]
     #[serde(default = "default_cache_config")]
     pub cache: CacheConfig,
+
+    /// The name of the field added to the next event passing through with the number of duplicate events discarded
+    /// since the previous one.
+    ///
+    /// The field is only added when duplicates were discarded.
+    #[serde(default)]
+    pub count_field: Option<String>,
 }
 
 const fn default_cache_config() -> CacheConfig {
-    CacheConfig { num_events: 5000 }
+    CacheConfig {
+        num_events: 5000,
+        time_to_live_secs: None,
+    }
 }
 
 impl DedupeConfig {
@@ -80,7 +98,10 @@ impl DedupeConfig {
 
 pub struct Dedupe {
     fields: FieldMatchConfig,
-    cache: LruCache<CacheEntry, bool>,
+    cache: LruCache<CacheEntry, Instant>,
+    time_to_live: Option<Duration>,
+    count_field: Option<String>,
+    discarded: u64,
 }
 
 inventory::submit! {
@@ -92,6 +113,7 @@ impl GenerateConfig for DedupeConfig {
         toml::Value::try_from(Self {
             fields: None,
             cache: default_cache_config(),
+            count_field: None,
         })
         .unwrap()
     }
@@ -170,15 +192,36 @@ impl Dedupe {
         Self {
             fields,
             cache: LruCache::new(num_entries),
+            time_to_live: config.cache.time_to_live_secs.map(Duration::from_secs),
+            count_field: config.count_field,
+            discarded: 0,
         }
     }
 
-    fn transform_one(&mut self, event: Event) -> Option<Event> {
+    fn transform_one(&mut self, mut event: Event) -> Option<Event> {
         let cache_entry = build_cache_entry(&event, &self.fields);
-        if self.cache.put(cache_entry, true).is_some() {
+        let now = Instant::now();
+        let is_duplicate = match self.cache.get(&cache_entry) {
+            Some(cached_at) => self
+                .time_to_live
+                .map_or(true, |time_to_live| now - *cached_at < time_to_live),
+            None => false,
+        };
+
+        if is_duplicate {
+            self.discarded += 1;
             emit!(DedupeEventDiscarded { event });
             None
         } else {
+            self.cache.put(cache_entry, now);
+            if let Some(count_field) = &self.count_field {
+                if self.discarded > 0 {
+                    event
+                        .as_mut_log()
+                        .insert(count_field.as_str(), self.discarded);
+                }
+            }
+            self.discarded = 0;
             Some(event)
         }
     }
@@ -250,8 +293,12 @@ mod tests {
 
     fn make_match_transform(num_events: usize, fields: Vec<String>) -> Dedupe {
         Dedupe::new(DedupeConfig {
-            cache: CacheConfig { num_events },
+            cache: CacheConfig {
+                num_events,
+                time_to_live_secs: None,
+            },
             fields: Some(FieldMatchConfig::MatchFields(fields)),
+            count_field: None,
         })
     }
 
@@ -261,8 +308,12 @@ mod tests {
         fields.extend(given_fields);
 
         Dedupe::new(DedupeConfig {
-            cache: CacheConfig { num_events },
+            cache: CacheConfig {
+                num_events,
+                time_to_live_secs: None,
+            },
             fields: Some(FieldMatchConfig::IgnoreFields(fields)),
+            count_field: None,
         })
     }
 
@@ -502,4 +553,60 @@ mod tests {
         let new_event = transform.transform_one(event2.clone()).unwrap();
         assert_eq!(new_event, event2);
     }
+
+    #[tokio::test]
+    async fn dedupe_time_to_live() {
+        tokio::time::pause();
+
+        let mut transform = Dedupe::new(DedupeConfig {
+            cache: CacheConfig {
+                num_events: 5,
+                time_to_live_secs: Some(10),
+            },
+            fields: Some(FieldMatchConfig::MatchFields(vec!["matched".into()])),
+            count_field: None,
+        });
+
+        let mut event = Event::Log(LogEvent::from("message"));
+        event.as_mut_log().insert("matched", "some value");
+
+        assert!(transform.transform_one(event.clone()).is_some());
+        tokio::time::advance(Duration::from_secs(5)).await;
+        assert_eq!(None, transform.transform_one(event.clone()));
+
+        // The entry expires 10 seconds after the first event, even though a duplicate was received since.
+        tokio::time::advance(Duration::from_secs(5)).await;
+        assert!(transform.transform_one(event.clone()).is_some());
+        assert_eq!(None, transform.transform_one(event));
+    }
+
+    #[test]
+    fn dedupe_count_field() {
+        let mut transform = Dedupe::new(DedupeConfig {
+            cache: default_cache_config(),
+            fields: Some(FieldMatchConfig::MatchFields(vec!["matched".into()])),
+            count_field: Some("duplicates".into()),
+        });
+
+        let mut event1 = Event::Log(LogEvent::from("message"));
+        event1.as_mut_log().insert("matched", "some value");
+
+        let mut event2 = Event::Log(LogEvent::from("message"));
+        event2.as_mut_log().insert("matched", "some value2");
+
+        let new_event = transform.transform_one(event1.clone()).unwrap();
+        assert_eq!(new_event, event1);
+        assert_eq!(None, transform.transform_one(event1.clone()));
+        assert_eq!(None, transform.transform_one(event1));
+
+        // The next event passing through has the number of discarded duplicates.
+        let new_event = transform.transform_one(event2.clone()).unwrap();
+        assert_eq!(new_event.as_log()["duplicates"], Value::from(2));
+
+        // The count is reset.
+        let mut event3 = Event::Log(LogEvent::from("message"));
+        event3.as_mut_log().insert("matched", "some value3");
+        let new_event = transform.transform_one(event3.clone()).unwrap();
+        assert_eq!(new_event, event3);
+    }
 }
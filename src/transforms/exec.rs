@@ -0,0 +1,518 @@
+use std::{
+    io,
+    path::PathBuf,
+    pin::Pin,
+    process::{ExitStatus, Stdio},
+    sync::Arc,
+    time::Duration,
+};
+
+use bytes::BytesMut;
+use codecs::{
+    decoding::{DeserializerConfig, FramingConfig},
+    encoding::{FramingConfig as EncodingFramingConfig, SerializerConfig},
+    NewlineDelimitedDecoderOptions, StreamDecodingError,
+};
+use futures::{Stream, StreamExt};
+use snafu::Snafu;
+use tokio::{
+    io::AsyncWriteExt,
+    process::{Child, ChildStdin, ChildStdout, Command},
+    sync::{mpsc, Mutex},
+    task::JoinHandle,
+    time::{sleep, timeout},
+};
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_util::codec::{Encoder as _, FramedRead};
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    codecs::{
+        encoding::{EncodingConfigWithFraming, SinkType, Transformer},
+        Decoder, DecodingConfig, Encoder,
+    },
+    config::{DataType, Input, Output, TransformConfig, TransformContext, TransformDescription},
+    event::Event,
+    internal_events::ExecProcessingError,
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+/// Configuration for the `exec` transform.
+#[configurable_component(transform)]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ExecConfig {
+    /// The command to be run, plus any arguments required.
+    ///
+    /// The events are written to the standard input of the command, and the events it writes to its standard output
+    /// are sent downstream. Both are encoded as native JSON.
+    pub command: Vec<String>,
+
+    /// The directory in which to run the command.
+    #[serde(default)]
+    pub working_directory: Option<PathBuf>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub framing: ExecFraming,
+
+    /// The maximum number of events written to the command at once.
+    #[serde(default = "default_max_batch_events")]
+    pub max_batch_events: usize,
+
+    /// The number of instances of the command processing events concurrently.
+    #[serde(default = "default_concurrency")]
+    pub concurrency: usize,
+
+    /// The time, in seconds, that the command has to accept a batch of events, or to exit once its standard input is
+    /// closed.
+    ///
+    /// If a batch isn't accepted in time, the command is killed and restarted, and the events of the batch are
+    /// discarded.
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+
+    /// The amount of time, in seconds, that Vector will wait before restarting a command that exited or failed.
+    #[serde(default = "default_respawn_interval_secs")]
+    pub respawn_interval_secs: u64,
+}
+
+/// How the events are delimited on the standard input and output of the command.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecFraming {
+    /// Each event is followed by a newline.
+    #[derivative(Default)]
+    NewlineDelimited,
+
+    /// Each event is prefixed by its length, as a 4-byte big-endian integer.
+    LengthDelimited,
+}
+
+const fn default_max_batch_events() -> usize {
+    100
+}
+
+const fn default_concurrency() -> usize {
+    1
+}
+
+const fn default_timeout_secs() -> u64 {
+    30
+}
+
+const fn default_respawn_interval_secs() -> u64 {
+    5
+}
+
+#[derive(Debug, PartialEq, Snafu)]
+pub enum ExecConfigError {
+    #[snafu(display("A non-empty list for command must be provided"))]
+    CommandEmpty,
+    #[snafu(display("`{}` must be greater than zero", option))]
+    Zero { option: &'static str },
+}
+
+inventory::submit! {
+    TransformDescription::new::<ExecConfig>("exec")
+}
+
+impl crate::config::GenerateConfig for ExecConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            command: vec!["./transform.py".to_owned()],
+            working_directory: None,
+            framing: ExecFraming::default(),
+            max_batch_events: default_max_batch_events(),
+            concurrency: default_concurrency(),
+            timeout_secs: default_timeout_secs(),
+            respawn_interval_secs: default_respawn_interval_secs(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "exec")]
+impl TransformConfig for ExecConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Exec::new(self).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::all())]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "exec"
+    }
+}
+
+#[derive(Debug)]
+pub struct Exec {
+    command: Vec<String>,
+    command_line: String,
+    working_directory: Option<PathBuf>,
+    encoder: Encoder<codecs::encoding::Framer>,
+    decoder: Decoder,
+    max_batch_events: usize,
+    concurrency: usize,
+    timeout: Duration,
+    respawn_interval: Duration,
+}
+
+impl Exec {
+    pub fn new(config: &ExecConfig) -> crate::Result<Self> {
+        if config.command.is_empty() {
+            return Err(Box::new(ExecConfigError::CommandEmpty));
+        }
+        for (option, value) in [
+            ("max_batch_events", config.max_batch_events),
+            ("concurrency", config.concurrency),
+        ] {
+            if value == 0 {
+                return Err(Box::new(ExecConfigError::Zero { option }));
+            }
+        }
+
+        let (encoding_framing, decoding_framing) = match config.framing {
+            ExecFraming::NewlineDelimited => (
+                EncodingFramingConfig::NewlineDelimited,
+                FramingConfig::NewlineDelimited {
+                    newline_delimited: NewlineDelimitedDecoderOptions::default(),
+                },
+            ),
+            ExecFraming::LengthDelimited => (
+                EncodingFramingConfig::LengthDelimited,
+                FramingConfig::LengthDelimited,
+            ),
+        };
+        let (framer, serializer) = EncodingConfigWithFraming::new(
+            Some(encoding_framing),
+            SerializerConfig::NativeJson,
+            Transformer::default(),
+        )
+        .build(SinkType::StreamBased)?;
+        let decoder = DecodingConfig::new(
+            decoding_framing,
+            DeserializerConfig::NativeJson,
+            LogNamespace::Legacy,
+        )
+        .build()?;
+
+        Ok(Self {
+            command: config.command.clone(),
+            command_line: config.command.join(" "),
+            working_directory: config.working_directory.clone(),
+            encoder: Encoder::<codecs::encoding::Framer>::new(framer, serializer),
+            decoder,
+            max_batch_events: config.max_batch_events,
+            concurrency: config.concurrency,
+            timeout: Duration::from_secs(config.timeout_secs),
+            respawn_interval: Duration::from_secs(config.respawn_interval_secs),
+        })
+    }
+}
+
+impl TaskTransform<Event> for Exec {
+    fn transform(
+        self: Box<Self>,
+        input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let exec: Arc<Self> = Arc::from(self);
+        let (output_tx, output_rx) = mpsc::channel(exec.max_batch_events);
+
+        // The batches are shared by the workers, so that each batch goes to the first idle one.
+        let (batch_tx, batch_rx) = mpsc::channel(exec.concurrency);
+        let batch_rx = Arc::new(Mutex::new(batch_rx));
+        for _ in 0..exec.concurrency {
+            let worker = Worker {
+                exec: Arc::clone(&exec),
+                encoder: exec.encoder.clone(),
+                output: output_tx.clone(),
+                process: None,
+            };
+            tokio::spawn(worker.run(Arc::clone(&batch_rx)));
+        }
+
+        let max_batch_events = exec.max_batch_events;
+        tokio::spawn(async move {
+            let mut batches = input_rx.ready_chunks(max_batch_events);
+            while let Some(batch) = batches.next().await {
+                if batch_tx.send(batch).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        // The output ends once the workers and the readers of the commands' output are done.
+        Box::pin(ReceiverStream::new(output_rx))
+    }
+}
+
+/// A running instance of the command.
+struct Process {
+    child: Child,
+    stdin: ChildStdin,
+    reader: JoinHandle<()>,
+}
+
+struct Worker {
+    exec: Arc<Exec>,
+    encoder: Encoder<codecs::encoding::Framer>,
+    output: mpsc::Sender<Event>,
+    process: Option<Process>,
+}
+
+impl Worker {
+    async fn run(mut self, batches: Arc<Mutex<mpsc::Receiver<Vec<Event>>>>) {
+        loop {
+            let batch = batches.lock().await.recv().await;
+            match batch {
+                Some(batch) => self.process_batch(batch).await,
+                None => break,
+            }
+        }
+
+        if let Some(process) = self.process.take() {
+            self.shutdown(process).await;
+        }
+    }
+
+    async fn process_batch(&mut self, batch: Vec<Event>) {
+        let count = batch.len();
+        let mut buffer = BytesMut::new();
+        for event in batch {
+            // Errors are emitted by the encoder, and the event is skipped.
+            let _ = self.encoder.encode(event, &mut buffer);
+        }
+
+        if let Err(error) = self.write(&buffer).await {
+            emit!(ExecProcessingError {
+                command: &self.exec.command_line,
+                error,
+                dropped_events: count,
+            });
+            if let Some(mut process) = self.process.take() {
+                let _ = process.child.kill().await;
+            }
+            sleep(self.exec.respawn_interval).await;
+        }
+    }
+
+    async fn write(&mut self, buffer: &[u8]) -> io::Result<()> {
+        // A command that exited is restarted before the batch is written to it.
+        if let Some(status) = self.exit_status() {
+            self.process = None;
+            emit!(ExecProcessingError {
+                command: &self.exec.command_line,
+                error: io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("command exited with {}", status)
+                ),
+                dropped_events: 0,
+            });
+            sleep(self.exec.respawn_interval).await;
+        }
+
+        if self.process.is_none() {
+            self.process = Some(self.spawn()?);
+        }
+        let process = self.process.as_mut().expect("command is running");
+
+        timeout(self.exec.timeout, async {
+            process.stdin.write_all(buffer).await?;
+            process.stdin.flush().await
+        })
+        .await
+        .map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::TimedOut,
+                "timed out writing events to the command",
+            )
+        })?
+    }
+
+    fn exit_status(&mut self) -> Option<ExitStatus> {
+        self.process
+            .as_mut()
+            .and_then(|process| process.child.try_wait().ok().flatten())
+    }
+
+    fn spawn(&self) -> io::Result<Process> {
+        let mut command = Command::new(&self.exec.command[0]);
+        command
+            .args(&self.exec.command[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .kill_on_drop(true);
+        if let Some(working_directory) = &self.exec.working_directory {
+            command.current_dir(working_directory);
+        }
+
+        let mut child = command.spawn()?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let reader = tokio::spawn(read_events(
+            stdout,
+            self.exec.decoder.clone(),
+            self.output.clone(),
+        ));
+
+        Ok(Process {
+            child,
+            stdin,
+            reader,
+        })
+    }
+
+    /// Closes the standard input of the command, and waits for it to write its remaining events.
+    async fn shutdown(&self, process: Process) {
+        let Process {
+            mut child,
+            stdin,
+            reader,
+        } = process;
+        drop(stdin);
+
+        if timeout(self.exec.timeout, reader).await.is_err() {
+            emit!(ExecProcessingError {
+                command: &self.exec.command_line,
+                error: io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    "timed out waiting for the command to exit"
+                ),
+                dropped_events: 0,
+            });
+            let _ = child.kill().await;
+        } else {
+            let _ = child.wait().await;
+        }
+    }
+}
+
+async fn read_events(stdout: ChildStdout, decoder: Decoder, output: mpsc::Sender<Event>) {
+    let mut frames = FramedRead::new(stdout, decoder);
+    while let Some(result) = frames.next().await {
+        match result {
+            Ok((events, _byte_size)) => {
+                for event in events {
+                    if output.send(event).await.is_err() {
+                        return;
+                    }
+                }
+            }
+            Err(error) => {
+                // Error is logged by `crate::codecs::Decoder`, no further
+                // handling is needed here.
+                if !error.can_continue() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+
+    use super::*;
+    use crate::event::LogEvent;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ExecConfig>();
+    }
+
+    fn exec(config: &str) -> Box<Exec> {
+        Box::new(Exec::new(&toml::from_str(config).unwrap()).unwrap())
+    }
+
+    /// Returns the messages of the events output by the transform.
+    async fn run(exec: Box<Exec>, messages: Vec<String>) -> Vec<String> {
+        let events = messages
+            .into_iter()
+            .map(|message| Event::from(LogEvent::from(message)));
+        exec.transform(Box::pin(stream::iter(events)))
+            .map(|event| event.as_log()["message"].to_string_lossy())
+            .collect()
+            .await
+    }
+
+    fn messages() -> Vec<String> {
+        (0..10).map(|index| format!("event {}", index)).collect()
+    }
+
+    #[test]
+    fn rejects_invalid_configs() {
+        let error = Exec::new(&toml::from_str("command = []").unwrap()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "A non-empty list for command must be provided"
+        );
+
+        let error = Exec::new(&toml::from_str("command = [\"cat\"]\nconcurrency = 0").unwrap())
+            .unwrap_err();
+        assert_eq!(error.to_string(), "`concurrency` must be greater than zero");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn processes_events() {
+        let output = run(
+            exec("command = [\"cat\"]\nmax_batch_events = 3"),
+            messages(),
+        )
+        .await;
+        assert_eq!(output, messages());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn processes_length_delimited_events() {
+        let output = run(
+            exec("command = [\"cat\"]\nframing = \"length_delimited\""),
+            messages(),
+        )
+        .await;
+        assert_eq!(output, messages());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn processes_events_concurrently() {
+        let mut output = run(exec("command = [\"cat\"]\nconcurrency = 4"), messages()).await;
+        output.sort();
+        assert_eq!(output, messages());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn filters_events() {
+        let output = run(exec("command = [\"grep\", \"event 1\"]"), messages()).await;
+        assert_eq!(output, vec!["event 1".to_owned()]);
+    }
+
+    #[tokio::test]
+    async fn discards_events_when_the_command_fails() {
+        let output = run(
+            exec("command = [\"./does-not-exist\"]\nrespawn_interval_secs = 0"),
+            messages(),
+        )
+        .await;
+        assert!(output.is_empty());
+    }
+}
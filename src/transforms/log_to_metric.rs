@@ -1,7 +1,10 @@
 use std::{collections::BTreeMap, convert::TryFrom, num::ParseFloatError};
 
 use indexmap::IndexMap;
+use vector_common::TimeZone;
 use vector_config::configurable_component;
+use vector_core::compile_vrl;
+use vrl::{diagnostic::Formatter, state::LocalEnv, Program, Runtime};
 
 use crate::{
     config::{
@@ -10,11 +13,11 @@ use crate::{
     },
     event::{
         metric::{Metric, MetricKind, MetricValue, StatisticKind},
-        Event, Value,
+        Event, Value, VrlTarget,
     },
     internal_events::{
-        LogToMetricFieldNullError, LogToMetricParseFloatError, LogToMetricTemplateParseError,
-        ParserMissingFieldError,
+        LogToMetricExpressionError, LogToMetricFieldNullError, LogToMetricNativeConversionError,
+        LogToMetricParseFloatError, LogToMetricTemplateParseError, ParserMissingFieldError,
     },
     schema,
     template::{Template, TemplateParseError, TemplateRenderingError},
@@ -27,7 +30,24 @@ use crate::{
 #[serde(deny_unknown_fields)]
 pub struct LogToMetricConfig {
     /// A list of metrics to generate.
+    #[serde(default)]
     pub metrics: Vec<MetricConfig>,
+
+    /// Converts the log events back to the metrics they were generated from by the `metric_to_log`
+    /// transform, instead of generating `metrics` from them.
+    ///
+    /// `metrics` can't be set along with this option.
+    #[serde(default)]
+    pub all_metrics: bool,
+
+    /// Name of the tag the host of the log events is moved to, when converting them with
+    /// `all_metrics`.
+    ///
+    /// This is the `host_tag` of the `metric_to_log` transform, whose value it moves to the `host`
+    /// field, where the field key uses the [global `host_key` option][global_host_key].
+    ///
+    /// [global_host_key]: https://vector.dev/docs/reference/configuration/global-options#log_schema.host_key
+    pub host_tag: Option<String>,
 }
 
 /// Specification of a counter derived from a log event.
@@ -49,12 +69,24 @@ pub struct CounterConfig {
     #[serde(default = "default_increment_by_value")]
     increment_by_value: bool,
 
+    /// A [VRL][vrl] expression computing the value the counter is incremented by.
+    ///
+    /// When set, it's used instead of the value of `field`, which the event doesn't need to have.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    value_expression: Option<String>,
+
     #[configurable(derived)]
     #[serde(default = "default_kind")]
     kind: MetricKind,
 
     /// Tags to apply to the counter.
     tags: Option<IndexMap<String, String>>,
+
+    /// A [VRL][vrl] expression returning an object of tags, which are added to `tags`.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    tags_expression: Option<String>,
 }
 
 /// Specification of a gauge derived from a log event.
@@ -74,6 +106,18 @@ pub struct GaugeConfig {
 
     /// Tags to apply to the gauge.
     pub tags: Option<IndexMap<String, String>>,
+
+    /// A [VRL][vrl] expression computing the value of the gauge.
+    ///
+    /// When set, it's used instead of the value of `field`, which the event doesn't need to have.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    pub value_expression: Option<String>,
+
+    /// A [VRL][vrl] expression returning an object of tags, which are added to `tags`.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    pub tags_expression: Option<String>,
 }
 
 /// Specification of a set derived from a log event.
@@ -93,6 +137,18 @@ pub struct SetConfig {
 
     /// Tags to apply to the set.
     tags: Option<IndexMap<String, String>>,
+
+    /// A [VRL][vrl] expression computing the value added to the set.
+    ///
+    /// When set, it's used instead of the value of `field`, which the event doesn't need to have.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    value_expression: Option<String>,
+
+    /// A [VRL][vrl] expression returning an object of tags, which are added to `tags`.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    tags_expression: Option<String>,
 }
 
 /// Specification of a histogram derived from a log event.
@@ -112,6 +168,18 @@ pub struct HistogramConfig {
 
     /// Tags to apply to the histogram.
     tags: Option<IndexMap<String, String>>,
+
+    /// A [VRL][vrl] expression computing the value sampled by the histogram.
+    ///
+    /// When set, it's used instead of the value of `field`, which the event doesn't need to have.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    value_expression: Option<String>,
+
+    /// A [VRL][vrl] expression returning an object of tags, which are added to `tags`.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    tags_expression: Option<String>,
 }
 
 /// Specification of a summary derived from a log event.
@@ -131,6 +199,18 @@ pub struct SummaryConfig {
 
     /// Tags to apply to the summary.
     tags: Option<IndexMap<String, String>>,
+
+    /// A [VRL][vrl] expression computing the value sampled by the summary.
+    ///
+    /// When set, it's used instead of the value of `field`, which the event doesn't need to have.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    value_expression: Option<String>,
+
+    /// A [VRL][vrl] expression returning an object of tags, which are added to `tags`.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    tags_expression: Option<String>,
 }
 
 /// Specification of a metric derived from a log event.
@@ -164,6 +244,37 @@ impl MetricConfig {
             MetricConfig::Summary(SummaryConfig { field, .. }) => field,
         }
     }
+
+    fn expressions(&self) -> (Option<&str>, Option<&str>) {
+        let (value, tags) = match self {
+            MetricConfig::Counter(CounterConfig {
+                value_expression,
+                tags_expression,
+                ..
+            })
+            | MetricConfig::Histogram(HistogramConfig {
+                value_expression,
+                tags_expression,
+                ..
+            })
+            | MetricConfig::Gauge(GaugeConfig {
+                value_expression,
+                tags_expression,
+                ..
+            })
+            | MetricConfig::Set(SetConfig {
+                value_expression,
+                tags_expression,
+                ..
+            })
+            | MetricConfig::Summary(SummaryConfig {
+                value_expression,
+                tags_expression,
+                ..
+            }) => (value_expression, tags_expression),
+        };
+        (value.as_deref(), tags.as_deref())
+    }
 }
 
 const fn default_increment_by_value() -> bool {
@@ -177,6 +288,53 @@ const fn default_kind() -> MetricKind {
 #[derive(Debug, Clone)]
 pub struct LogToMetric {
     config: LogToMetricConfig,
+    expressions: Vec<MetricExpressions>,
+    host_tag: String,
+}
+
+/// The compiled VRL expressions of a metric.
+#[derive(Debug, Clone)]
+struct MetricExpressions {
+    value: Option<Expression>,
+    tags: Option<Expression>,
+}
+
+/// A VRL expression computing a value from an event, without modifying it.
+#[derive(Debug, Clone)]
+struct Expression {
+    program: Program,
+}
+
+impl Expression {
+    fn compile(source: &str, enrichment_tables: &enrichment::TableRegistry) -> crate::Result<Self> {
+        let functions = vrl_stdlib::all()
+            .into_iter()
+            .chain(enrichment::vrl_functions().into_iter())
+            .chain(vector_vrl_functions::vrl_functions())
+            .collect::<Vec<_>>();
+
+        let mut state = vrl::state::ExternalEnv::default().read_only();
+        state.set_external_context(enrichment_tables.clone());
+
+        let (program, warnings) = compile_vrl(source, &functions, &mut state, LocalEnv::default())
+            .map_err(|diagnostics| Formatter::new(source, diagnostics).colored().to_string())?;
+
+        if !warnings.is_empty() {
+            let warnings = Formatter::new(source, warnings).colored().to_string();
+            warn!(message = "VRL compilation warning.", %warnings);
+        }
+
+        Ok(Self { program })
+    }
+
+    fn resolve(&self, event: &Event) -> Result<Value, TransformError> {
+        let mut target = VrlTarget::new(event.clone(), self.program.info());
+        Runtime::default()
+            .resolve(&mut target, &self.program, &TimeZone::default())
+            .map_err(|error| TransformError::ExpressionError {
+                error: error.to_string(),
+            })
+    }
 }
 
 inventory::submit! {
@@ -191,9 +349,13 @@ impl GenerateConfig for LogToMetricConfig {
                 name: None,
                 namespace: None,
                 increment_by_value: false,
+                value_expression: None,
                 kind: MetricKind::Incremental,
                 tags: None,
+                tags_expression: None,
             })],
+            all_metrics: false,
+            host_tag: None,
         })
         .unwrap()
     }
@@ -202,8 +364,15 @@ impl GenerateConfig for LogToMetricConfig {
 #[async_trait::async_trait]
 #[typetag::serde(name = "log_to_metric")]
 impl TransformConfig for LogToMetricConfig {
-    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(LogToMetric::new(self.clone())))
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        if self.all_metrics && !self.metrics.is_empty() {
+            return Err("`metrics` can't be set when `all_metrics` is enabled".into());
+        }
+
+        Ok(Transform::function(LogToMetric::new(
+            self.clone(),
+            &context.enrichment_tables,
+        )?))
     }
 
     fn input(&self) -> Input {
@@ -224,8 +393,39 @@ impl TransformConfig for LogToMetricConfig {
 }
 
 impl LogToMetric {
-    pub const fn new(config: LogToMetricConfig) -> Self {
-        LogToMetric { config }
+    pub fn new(
+        config: LogToMetricConfig,
+        enrichment_tables: &enrichment::TableRegistry,
+    ) -> crate::Result<Self> {
+        let compile = |source: Option<&str>| {
+            source
+                .map(|source| Expression::compile(source, enrichment_tables))
+                .transpose()
+        };
+        let host_tag = format!(
+            "tags.{}",
+            config
+                .host_tag
+                .as_deref()
+                .unwrap_or_else(|| log_schema().host_key())
+        );
+        let expressions = config
+            .metrics
+            .iter()
+            .map(|metric| {
+                let (value, tags) = metric.expressions();
+                Ok(MetricExpressions {
+                    value: compile(value)?,
+                    tags: compile(tags)?,
+                })
+            })
+            .collect::<crate::Result<_>>()?;
+
+        Ok(LogToMetric {
+            config,
+            expressions,
+            host_tag,
+        })
     }
 }
 
@@ -242,6 +442,9 @@ enum TransformError {
         field: String,
         error: ParseFloatError,
     },
+    ExpressionError {
+        error: String,
+    },
 }
 
 fn render_template(s: &str, event: &Event) -> Result<String, TransformError> {
@@ -283,7 +486,42 @@ fn render_tags(
     })
 }
 
-fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformError> {
+/// Renders the tags, adding the ones returned by the tags expression, if any.
+fn render_all_tags(
+    tags: &Option<IndexMap<String, String>>,
+    expression: Option<&Expression>,
+    event: &Event,
+) -> Result<Option<BTreeMap<String, String>>, TransformError> {
+    let mut tags = render_tags(tags, event)?;
+    if let Some(expression) = expression {
+        match expression.resolve(event)? {
+            Value::Object(fields) => {
+                let map = tags.get_or_insert_with(BTreeMap::new);
+                for (name, value) in fields {
+                    if !value.is_null() {
+                        map.insert(name, value.to_string_lossy());
+                    }
+                }
+            }
+            Value::Null => {}
+            value => {
+                return Err(TransformError::ExpressionError {
+                    error: format!(
+                        "the tags expression must return an object, not {}",
+                        value.kind_str()
+                    ),
+                })
+            }
+        }
+    }
+    Ok(tags.filter(|tags| !tags.is_empty()))
+}
+
+fn to_metric(
+    config: &MetricConfig,
+    expressions: &MetricExpressions,
+    event: &Event,
+) -> Result<Metric, TransformError> {
     let log = event.as_log();
 
     let timestamp = log
@@ -294,7 +532,15 @@ fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformEr
 
     let field = config.field();
 
-    let value = match log.get(field) {
+    let resolved;
+    let value = match &expressions.value {
+        Some(expression) => {
+            resolved = expression.resolve(event)?;
+            Some(&resolved)
+        }
+        None => log.get(field),
+    };
+    let value = match value {
         None => Err(TransformError::FieldNotFound {
             field: field.to_string(),
         }),
@@ -306,7 +552,7 @@ fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformEr
 
     match config {
         MetricConfig::Counter(counter) => {
-            let value = if counter.increment_by_value {
+            let value = if counter.increment_by_value || counter.value_expression.is_some() {
                 value.to_string_lossy().parse().map_err(|error| {
                     TransformError::ParseFloatError {
                         field: counter.field.clone(),
@@ -325,7 +571,7 @@ fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformEr
                 .map(|namespace| render_template(namespace, event))
                 .transpose()?;
 
-            let tags = render_tags(&counter.tags, event)?;
+            let tags = render_all_tags(&counter.tags, expressions.tags.as_ref(), event)?;
 
             Ok(Metric::new_with_metadata(
                 name,
@@ -353,7 +599,7 @@ fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformEr
                 .map(|namespace| render_template(namespace, event))
                 .transpose()?;
 
-            let tags = render_tags(&hist.tags, event)?;
+            let tags = render_all_tags(&hist.tags, expressions.tags.as_ref(), event)?;
 
             Ok(Metric::new_with_metadata(
                 name,
@@ -384,7 +630,7 @@ fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformEr
                 .map(|namespace| render_template(namespace, event))
                 .transpose()?;
 
-            let tags = render_tags(&summary.tags, event)?;
+            let tags = render_all_tags(&summary.tags, expressions.tags.as_ref(), event)?;
 
             Ok(Metric::new_with_metadata(
                 name,
@@ -415,7 +661,7 @@ fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformEr
                 .map(|namespace| render_template(namespace, event))
                 .transpose()?;
 
-            let tags = render_tags(&gauge.tags, event)?;
+            let tags = render_all_tags(&gauge.tags, expressions.tags.as_ref(), event)?;
 
             Ok(Metric::new_with_metadata(
                 name,
@@ -438,7 +684,7 @@ fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformEr
                 .map(|namespace| render_template(namespace, event))
                 .transpose()?;
 
-            let tags = render_tags(&set.tags, event)?;
+            let tags = render_all_tags(&set.tags, expressions.tags.as_ref(), event)?;
 
             Ok(Metric::new_with_metadata(
                 name,
@@ -455,10 +701,76 @@ fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformEr
     }
 }
 
+/// Converts a log event back to the metric it was generated from by the `metric_to_log`
+/// transform, whose fields are the ones of the serialized metric.
+fn to_native_metric(event: Event, host_tag: &str) -> Result<Metric, serde_json::Error> {
+    let mut log = event.into_log();
+    if let Some(timestamp) = log.remove(log_schema().timestamp_key()) {
+        log.insert("timestamp", timestamp);
+    }
+    if let Some(host) = log.remove(log_schema().host_key()) {
+        log.insert(host_tag, host);
+    }
+
+    let (value, metadata) = log.into_parts();
+    let mut value = serde_json::to_value(value)?;
+    decode_infinite_limits(&mut value)?;
+
+    let (series, mut data, _) = serde_json::from_value::<Metric>(value)?.into_parts();
+    if let MetricValue::AggregatedHistogram { buckets, .. } = &mut data.value {
+        for bucket in buckets {
+            if bucket.upper_limit == f64::MAX {
+                bucket.upper_limit = f64::INFINITY;
+            } else if bucket.upper_limit == f64::MIN {
+                bucket.upper_limit = f64::NEG_INFINITY;
+            }
+        }
+    }
+    Ok(Metric::from_parts(series, data, metadata))
+}
+
+/// Decodes the infinite upper limits of the buckets of a histogram encoded by the `metric_to_log`
+/// transform, as the largest finite ones until the metric is deserialized, since JSON numbers
+/// can't be infinite.
+///
+/// The limits are encoded as `"+Inf"` and `"-Inf"`, but the other usual spellings of infinities,
+/// such as `"inf"` or `"-Infinity"`, are accepted regardless of their case. Other strings are
+/// rejected.
+fn decode_infinite_limits(value: &mut serde_json::Value) -> Result<(), serde_json::Error> {
+    if let Some(buckets) = value
+        .pointer_mut("/aggregated_histogram/buckets")
+        .and_then(serde_json::Value::as_array_mut)
+    {
+        for bucket in buckets {
+            let upper_limit = match bucket.get("upper_limit").and_then(|limit| limit.as_str()) {
+                Some(limit) => match limit.to_ascii_lowercase().as_str() {
+                    "inf" | "+inf" | "infinity" | "+infinity" => f64::MAX,
+                    "-inf" | "-infinity" => f64::MIN,
+                    _ => return Err(serde::de::Error::custom(format!(
+                        "invalid bucket upper limit {:?}, expected a number, \"+Inf\" or \"-Inf\"",
+                        limit
+                    ))),
+                },
+                None => continue,
+            };
+            bucket["upper_limit"] = upper_limit.into();
+        }
+    }
+    Ok(())
+}
+
 impl FunctionTransform for LogToMetric {
     fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
-        for config in self.config.metrics.iter() {
-            match to_metric(config, &event) {
+        if self.config.all_metrics {
+            match to_native_metric(event, &self.host_tag) {
+                Ok(metric) => output.push(Event::Metric(metric)),
+                Err(error) => emit!(LogToMetricNativeConversionError { error }),
+            }
+            return;
+        }
+
+        for (config, expressions) in self.config.metrics.iter().zip(&self.expressions) {
+            match to_metric(config, expressions, &event) {
                 Ok(metric) => {
                     output.push(Event::Metric(metric));
                 }
@@ -484,6 +796,11 @@ impl FunctionTransform for LogToMetric {
                 Err(TransformError::TemplateParseError(error)) => {
                     emit!(LogToMetricTemplateParseError { error })
                 }
+                Err(TransformError::ExpressionError { error }) => {
+                    emit!(LogToMetricExpressionError {
+                        error: error.as_ref()
+                    })
+                }
             }
         }
     }
@@ -535,7 +852,7 @@ mod tests {
 
         let event = create_event("status", "42");
         let metadata = event.metadata().clone();
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
         let metric = transform_one(&mut transform, event).unwrap();
 
         assert_eq!(
@@ -568,7 +885,7 @@ mod tests {
         event.as_mut_log().insert("code", "200");
         let metadata = event.metadata().clone();
 
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
         let metric = transform_one(&mut transform, event).unwrap();
 
         assert_eq!(
@@ -606,7 +923,7 @@ mod tests {
 
         let event = create_event("backtrace", "message");
         let metadata = event.metadata().clone();
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
         let metric = transform_one(&mut transform, event).unwrap();
 
         assert_eq!(
@@ -633,7 +950,7 @@ mod tests {
         );
 
         let event = create_event("success", "42");
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
 
         assert_eq!(transform_one(&mut transform, event), None);
     }
@@ -652,7 +969,7 @@ mod tests {
 
         let event = create_event("amount", "33.99");
         let metadata = event.metadata().clone();
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
         let metric = transform_one(&mut transform, event).unwrap();
 
         assert_eq!(
@@ -682,7 +999,7 @@ mod tests {
 
         let event = create_event("amount", "33.99");
         let metadata = event.metadata().clone();
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
         let metric = transform_one(&mut transform, event).unwrap();
 
         assert_eq!(
@@ -710,7 +1027,7 @@ mod tests {
 
         let event = create_event("memory_rss", "123");
         let metadata = event.metadata().clone();
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
         let metric = transform_one(&mut transform, event).unwrap();
 
         assert_eq!(
@@ -738,7 +1055,7 @@ mod tests {
         );
 
         let event = create_event("status", "not a number");
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
 
         assert_eq!(transform_one(&mut transform, event), None);
     }
@@ -755,7 +1072,7 @@ mod tests {
         );
 
         let event = create_event("not foo", "not a number");
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
 
         assert_eq!(transform_one(&mut transform, event), None);
     }
@@ -772,7 +1089,7 @@ mod tests {
         );
 
         let event = create_event("status", Value::Null);
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
 
         assert_eq!(transform_one(&mut transform, event), None);
     }
@@ -800,7 +1117,7 @@ mod tests {
         event.as_mut_log().insert("backtrace", "message");
         let metadata = event.metadata().clone();
 
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
 
         let mut output = OutputBuffer::default();
         transform.transform(&mut output, event);
@@ -856,7 +1173,7 @@ mod tests {
         event.as_mut_log().insert("service", "xyz");
         let metadata = event.metadata().clone();
 
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
 
         let mut output = OutputBuffer::default();
         transform.transform(&mut output, event);
@@ -900,7 +1217,7 @@ mod tests {
 
         let event = create_event("user_ip", "1.2.3.4");
         let metadata = event.metadata().clone();
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
         let metric = transform_one(&mut transform, event).unwrap();
 
         assert_eq!(
@@ -929,7 +1246,7 @@ mod tests {
 
         let event = create_event("response_time", "2.5");
         let metadata = event.metadata().clone();
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
         let metric = transform_one(&mut transform, event).unwrap();
 
         assert_eq!(
@@ -959,7 +1276,7 @@ mod tests {
 
         let event = create_event("response_time", "2.5");
         let metadata = event.metadata().clone();
-        let mut transform = LogToMetric::new(config);
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
         let metric = transform_one(&mut transform, event).unwrap();
 
         assert_eq!(
@@ -976,4 +1293,262 @@ mod tests {
             .with_timestamp(Some(ts()))
         );
     }
+
+    #[test]
+    fn response_time_histogram_from_expressions() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "histogram"
+            field = "response_time_ms"
+            name = "response_time_seconds"
+            value_expression = "to_float!(.response_time_ms) / 1000"
+            tags = {host = "localhost"}
+            tags_expression = '{"route": .route, "slow": to_float!(.response_time_ms) > 1000, "user": .user}'
+            "#,
+        );
+
+        let mut event = create_event("response_time_ms", "2500");
+        event.as_mut_log().insert("route", "/login");
+        let metadata = event.metadata().clone();
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
+        let metric = transform_one(&mut transform, event).unwrap();
+
+        assert_eq!(
+            metric.into_metric(),
+            Metric::new_with_metadata(
+                "response_time_seconds",
+                MetricKind::Incremental,
+                MetricValue::Distribution {
+                    samples: vector_core::samples![2.5 => 1],
+                    statistic: StatisticKind::Histogram
+                },
+                metadata
+            )
+            .with_tags(Some(
+                vec![
+                    ("host".to_owned(), "localhost".to_owned()),
+                    ("route".to_owned(), "/login".to_owned()),
+                    ("slow".to_owned(), "true".to_owned()),
+                ]
+                .into_iter()
+                .collect(),
+            ))
+            .with_timestamp(Some(ts()))
+        );
+    }
+
+    #[test]
+    fn user_set_from_expression() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "set"
+            field = "unique_users"
+            value_expression = "downcase!(.user)"
+            "#,
+        );
+
+        let event = create_event("user", "Alice");
+        let metadata = event.metadata().clone();
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
+        let metric = transform_one(&mut transform, event).unwrap();
+
+        assert_eq!(
+            metric.into_metric(),
+            Metric::new_with_metadata(
+                "unique_users",
+                MetricKind::Incremental,
+                MetricValue::Set {
+                    values: vec!["alice".into()].into_iter().collect()
+                },
+                metadata
+            )
+            .with_timestamp(Some(ts()))
+        );
+    }
+
+    #[test]
+    fn expression_failure() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "gauge"
+            field = "memory"
+            value_expression = "to_float!(.memory)"
+            "#,
+        );
+
+        let event = create_event("memory", "a lot");
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
+
+        assert_eq!(transform_one(&mut transform, event), None);
+    }
+
+    #[test]
+    fn invalid_expression() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "gauge"
+            field = "memory"
+            value_expression = "to_float!(.memory"
+            "#,
+        );
+
+        assert!(LogToMetric::new(config, &Default::default()).is_err());
+    }
+
+    #[cfg(feature = "transforms-metric_to_log")]
+    #[test]
+    fn round_trip_metric_to_log() {
+        use crate::transforms::metric_to_log::MetricToLog;
+
+        let config = parse_config("all_metrics = true");
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
+        let metric_to_log = MetricToLog::new(None, Default::default());
+
+        let metrics = vec![
+            Metric::new(
+                "requests_total",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 42.0 },
+            )
+            .with_namespace(Some("app"))
+            .with_tags(Some(
+                vec![
+                    ("host".to_owned(), "localhost".to_owned()),
+                    ("code".to_owned(), "200".to_owned()),
+                ]
+                .into_iter()
+                .collect(),
+            )),
+            Metric::new(
+                "users",
+                MetricKind::Incremental,
+                MetricValue::Set {
+                    values: vec!["alice".into(), "bob".into()].into_iter().collect(),
+                },
+            ),
+            Metric::new(
+                "response_time",
+                MetricKind::Incremental,
+                MetricValue::Distribution {
+                    samples: vector_core::samples![1.5 => 2, 3.0 => 1],
+                    statistic: StatisticKind::Histogram,
+                },
+            ),
+            Metric::new(
+                "request_duration_seconds",
+                MetricKind::Absolute,
+                MetricValue::AggregatedHistogram {
+                    buckets: vector_core::buckets![0.5 => 3, 1.0 => 5, f64::INFINITY => 6],
+                    count: 6,
+                    sum: 4.25,
+                },
+            ),
+            Metric::new(
+                "request_size_bytes",
+                MetricKind::Absolute,
+                MetricValue::AggregatedSummary {
+                    quantiles: vector_core::quantiles![0.5 => 120.0, 0.99 => 980.0],
+                    count: 20,
+                    sum: 4000.0,
+                },
+            ),
+        ];
+
+        for metric in metrics {
+            let metric = metric.with_timestamp(Some(ts()));
+            let log = metric_to_log.transform_one(metric.clone()).unwrap();
+
+            // Through JSON, as with a log-only transport.
+            let json = serde_json::to_value(&log).unwrap();
+            let log = LogEvent::try_from(json).unwrap();
+
+            let converted = transform_one(&mut transform, log.into()).unwrap();
+            let (series, data, _) = converted.into_metric().into_parts();
+            let (expected_series, expected_data, _) = metric.into_parts();
+            assert_eq!((series, data), (expected_series, expected_data));
+        }
+    }
+
+    #[tokio::test]
+    async fn all_metrics_rejects_metrics() {
+        let config = parse_config(
+            r#"
+            all_metrics = true
+
+            [[metrics]]
+            type = "counter"
+            field = "status"
+            "#,
+        );
+
+        let error = config
+            .build(&TransformContext::default())
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "`metrics` can't be set when `all_metrics` is enabled"
+        );
+    }
+
+    #[test]
+    fn decode_infinite_limits_spellings() {
+        for (limit, expected) in [
+            ("+Inf", f64::MAX),
+            ("-Inf", f64::MIN),
+            ("inf", f64::MAX),
+            ("+inf", f64::MAX),
+            ("-INF", f64::MIN),
+            ("Infinity", f64::MAX),
+            ("+Infinity", f64::MAX),
+            ("-infinity", f64::MIN),
+        ] {
+            let mut value = serde_json::json!({
+                "aggregated_histogram": {
+                    "buckets": [{ "upper_limit": limit, "count": 1 }],
+                },
+            });
+            decode_infinite_limits(&mut value).unwrap();
+            assert_eq!(
+                value.pointer("/aggregated_histogram/buckets/0/upper_limit"),
+                Some(&serde_json::json!(expected)),
+                "{}",
+                limit
+            );
+        }
+    }
+
+    #[test]
+    fn decode_infinite_limits_rejects_other_strings() {
+        for limit in ["NaN", "Inff", "max"] {
+            let mut value = serde_json::json!({
+                "aggregated_histogram": {
+                    "buckets": [{ "upper_limit": limit, "count": 1 }],
+                },
+            });
+            let error = decode_infinite_limits(&mut value).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                format!(
+                    "invalid bucket upper limit {:?}, expected a number, \"+Inf\" or \"-Inf\"",
+                    limit
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn native_conversion_failure() {
+        let config = parse_config("all_metrics = true");
+        let mut transform = LogToMetric::new(config, &Default::default()).unwrap();
+
+        let event = create_event("name", "no_value");
+
+        assert_eq!(transform_one(&mut transform, event), None);
+    }
 }
@@ -1,5 +1,6 @@
 pub mod v1;
 pub mod v2;
+pub mod v3;
 
 use vector_config::configurable_component;
 
@@ -51,6 +52,26 @@ pub struct LuaConfigV2 {
     config: v2::LuaConfig,
 }
 
+/// Marker type for the version three of the configuration for the `lua` transform.
+#[configurable_component]
+#[derive(Clone, Debug)]
+enum V3 {
+    /// Marker value for version three.
+    #[serde(rename = "3")]
+    V3,
+}
+
+/// Configuration for the version three of the `lua` transform.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct LuaConfigV3 {
+    /// Version of the configuration.
+    version: V3,
+
+    #[serde(flatten)]
+    config: v3::LuaConfig,
+}
+
 /// Configuration for the `lua` transform.
 #[configurable_component(transform)]
 #[derive(Clone, Debug)]
@@ -61,6 +82,9 @@ pub enum LuaConfig {
 
     /// Configuration for version two.
     V2(#[configurable(derived)] LuaConfigV2),
+
+    /// Configuration for version three.
+    V3(#[configurable(derived)] LuaConfigV3),
 }
 
 inventory::submit! {
@@ -84,6 +108,7 @@ impl TransformConfig for LuaConfig {
         match self {
             LuaConfig::V1(v1) => v1.config.build(),
             LuaConfig::V2(v2) => v2.config.build(),
+            LuaConfig::V3(v3) => v3.config.build(),
         }
     }
 
@@ -91,6 +116,7 @@ impl TransformConfig for LuaConfig {
         match self {
             LuaConfig::V1(v1) => v1.config.input(),
             LuaConfig::V2(v2) => v2.config.input(),
+            LuaConfig::V3(v3) => v3.config.input(),
         }
     }
 
@@ -98,6 +124,7 @@ impl TransformConfig for LuaConfig {
         match self {
             LuaConfig::V1(v1) => v1.config.outputs(merged_definition),
             LuaConfig::V2(v2) => v2.config.outputs(merged_definition),
+            LuaConfig::V3(v3) => v3.config.outputs(merged_definition),
         }
     }
 
@@ -105,6 +132,7 @@ impl TransformConfig for LuaConfig {
         match self {
             LuaConfig::V1(v1) => v1.config.transform_type(),
             LuaConfig::V2(v2) => v2.config.transform_type(),
+            LuaConfig::V3(v3) => v3.config.transform_type(),
         }
     }
 }
@@ -68,7 +68,7 @@ pub struct LuaConfig {
     timers: Vec<TimerConfig>,
 }
 
-fn default_config_paths() -> Vec<PathBuf> {
+pub(super) fn default_config_paths() -> Vec<PathBuf> {
     match CONFIG_PATHS.lock().ok() {
         Some(config_paths) => config_paths
             .clone()
@@ -91,7 +91,7 @@ fn default_config_paths() -> Vec<PathBuf> {
 #[configurable_component]
 #[derive(Clone, Debug)]
 #[serde(deny_unknown_fields)]
-struct HooksConfig {
+pub(super) struct HooksConfig {
     /// A function which is called when the first event comes, before calling `hooks.process`.
     ///
     /// It can produce new events using the `emit` function.
@@ -121,7 +121,7 @@ struct HooksConfig {
 /// A Lua timer.
 #[configurable_component]
 #[derive(Clone, Debug)]
-struct TimerConfig {
+pub(super) struct TimerConfig {
     /// The interval to execute the handler, in seconds.
     interval_seconds: u64,
 
@@ -197,8 +197,6 @@ impl Lua {
             .collect::<Vec<_>>()
             .join(";");
 
-        let mut timers = Vec::new();
-
         if !additional_paths.is_empty() {
             let package = lua.globals().get::<_, mlua::Table<'_>>("package")?;
             let current_paths = package
@@ -208,26 +206,37 @@ impl Lua {
             package.set("path", paths)?;
         }
 
-        if let Some(source) = &config.source {
+        Self::with_runtime(lua, config.source.as_deref(), &config.hooks, &config.timers)
+    }
+
+    /// Creates the transform from a Lua runtime whose `require` function is already configured.
+    pub(super) fn with_runtime(
+        lua: mlua::Lua,
+        source: Option<&str>,
+        hooks: &HooksConfig,
+        timers_config: &[TimerConfig],
+    ) -> crate::Result<Self> {
+        if let Some(source) = source {
             lua.load(source).eval().context(InvalidSourceSnafu)?;
         }
 
-        let hook_init_code = config.hooks.init.as_ref();
+        let hook_init_code = hooks.init.as_ref();
         let hook_init = hook_init_code
             .map(|code| make_registry_value(&lua, code))
             .transpose()
             .context(InvalidHooksInitSnafu)?;
 
         let hook_process =
-            make_registry_value(&lua, &config.hooks.process).context(InvalidHooksProcessSnafu)?;
+            make_registry_value(&lua, &hooks.process).context(InvalidHooksProcessSnafu)?;
 
-        let hook_shutdown_code = config.hooks.shutdown.as_ref();
+        let hook_shutdown_code = hooks.shutdown.as_ref();
         let hook_shutdown = hook_shutdown_code
             .map(|code| make_registry_value(&lua, code))
             .transpose()
             .context(InvalidHooksShutdownSnafu)?;
 
-        for (id, timer) in config.timers.iter().enumerate() {
+        let mut timers = Vec::new();
+        for (id, timer) in timers_config.iter().enumerate() {
             let handler_key = lua
                 .load(&timer.handler)
                 .eval::<mlua::Function>()
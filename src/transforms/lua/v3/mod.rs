@@ -0,0 +1,353 @@
+use std::path::PathBuf;
+
+use snafu::{ResultExt, Snafu};
+use vector_config::configurable_component;
+
+use super::v2::{default_config_paths, HooksConfig, Lua, TimerConfig};
+use crate::{
+    config::{DataType, Input, Output},
+    schema,
+    transforms::Transform,
+};
+
+#[derive(Debug, Snafu)]
+pub enum BuildError {
+    #[snafu(display("Cannot create Lua runtime: {}", source))]
+    InvalidRuntime { source: mlua::Error },
+    #[snafu(display("Invalid \"search_dirs\": {}", source))]
+    InvalidSearchDirs { source: mlua::Error },
+    #[snafu(display("Cannot apply \"require\" policy: {}", source))]
+    InvalidRequirePolicy { source: mlua::Error },
+}
+
+/// Configuration for the version three of the `lua` transform.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct LuaConfig {
+    /// The Lua program to initialize the transform with.
+    ///
+    /// The program can be used to to import external dependencies, as well as define the functions used for the various
+    /// lifecycle hooks. However, it's not strictly required, as the lifecycle hooks can be configured directly with
+    /// inline Lua source for each respective hook.
+    source: Option<String>,
+
+    /// A list of directories to search when loading a Lua file via the `require` function.
+    ///
+    /// If not specified, the modules are looked up in the directories of Vector’s configs.
+    #[serde(default = "default_config_paths")]
+    search_dirs: Vec<PathBuf>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    require: RequirePolicy,
+
+    #[configurable(derived)]
+    hooks: HooksConfig,
+
+    /// A list of timers which should be configured and executed periodically.
+    #[serde(default)]
+    timers: Vec<TimerConfig>,
+}
+
+/// Where the `require` function can load Lua modules from.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RequirePolicy {
+    /// Modules are only loaded from the `search_dirs`.
+    ///
+    /// C modules can't be loaded, and the `dofile` and `loadfile` functions are unavailable.
+    #[derivative(Default)]
+    SearchDirs,
+
+    /// Modules are loaded from the `search_dirs`, then from the default paths of Lua.
+    ///
+    /// C modules can be loaded, as with the version two of the transform.
+    System,
+
+    /// Modules can't be loaded: the `require`, `dofile` and `loadfile` functions are unavailable.
+    Disabled,
+}
+
+// Implementation of methods from `TransformConfig`, see the version two of the transform.
+impl LuaConfig {
+    pub fn build(&self) -> crate::Result<Transform> {
+        let lua = self.runtime()?;
+        Lua::with_runtime(lua, self.source.as_deref(), &self.hooks, &self.timers)
+            .map(Transform::event_task)
+    }
+
+    pub fn input(&self) -> Input {
+        Input::all()
+    }
+
+    pub fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::all())]
+    }
+
+    pub const fn transform_type(&self) -> &'static str {
+        "lua"
+    }
+
+    /// Creates the Lua runtime, with the `require` function restricted according to the policy.
+    fn runtime(&self) -> Result<mlua::Lua, BuildError> {
+        let search_paths = self
+            .search_dirs
+            .iter()
+            .map(|d| format!("{}/?.lua", d.to_string_lossy()))
+            .collect::<Vec<_>>()
+            .join(";");
+
+        match self.require {
+            RequirePolicy::System => {
+                // In order to support loading C modules in Lua, we need to create unsafe instance
+                // without debug library.
+                let lua = unsafe {
+                    mlua::Lua::unsafe_new_with(mlua::StdLib::ALL_SAFE, mlua::LuaOptions::default())
+                };
+                if !search_paths.is_empty() {
+                    let package = lua
+                        .globals()
+                        .get::<_, mlua::Table<'_>>("package")
+                        .context(InvalidSearchDirsSnafu)?;
+                    let current_paths = package
+                        .get::<_, String>("path")
+                        .unwrap_or_else(|_| ";".to_string());
+                    package
+                        .set("path", format!("{};{}", search_paths, current_paths))
+                        .context(InvalidSearchDirsSnafu)?;
+                }
+                Ok(lua)
+            }
+            RequirePolicy::SearchDirs => {
+                // The safe runtime refuses to load C modules.
+                let lua = mlua::Lua::new_with(mlua::StdLib::ALL_SAFE, mlua::LuaOptions::default())
+                    .context(InvalidRuntimeSnafu)?;
+                {
+                    let package = lua
+                        .globals()
+                        .get::<_, mlua::Table<'_>>("package")
+                        .context(InvalidSearchDirsSnafu)?;
+                    package
+                        .set("path", search_paths)
+                        .context(InvalidSearchDirsSnafu)?;
+                    package.set("cpath", "").context(InvalidSearchDirsSnafu)?;
+                }
+                remove_globals(&lua, &["dofile", "loadfile"])?;
+                Ok(lua)
+            }
+            RequirePolicy::Disabled => {
+                let lua = mlua::Lua::new_with(mlua::StdLib::ALL_SAFE, mlua::LuaOptions::default())
+                    .context(InvalidRuntimeSnafu)?;
+                remove_globals(&lua, &["require", "dofile", "loadfile"])?;
+                Ok(lua)
+            }
+        }
+    }
+}
+
+fn remove_globals(lua: &mlua::Lua, names: &[&str]) -> Result<(), BuildError> {
+    let globals = lua.globals();
+    for name in names {
+        globals
+            .set(*name, mlua::Value::Nil)
+            .context(InvalidRequirePolicySnafu)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs::File, io::Write};
+
+    use futures::{stream, StreamExt};
+
+    use super::*;
+    use crate::{
+        event::{metric::MetricValue, Event, LogEvent, TraceEvent, Value},
+        test_util::trace_init,
+    };
+
+    fn from_config(config: &str) -> crate::Result<Transform> {
+        toml::from_str::<LuaConfig>(config).unwrap().build()
+    }
+
+    async fn run(transform: Transform, events: Vec<Event>) -> Vec<Event> {
+        transform
+            .into_task()
+            .transform_events(Box::pin(stream::iter(events)))
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn lua_trace() {
+        trace_init();
+
+        let transform = from_config(
+            r#"
+            hooks.process = """function (event, emit)
+                event.trace.span_count = #event.trace.spans
+                emit(event)
+            end
+            """
+            "#,
+        )
+        .unwrap();
+
+        let mut trace = LogEvent::default();
+        trace.insert("spans", vec![Value::from("a"), Value::from("b")]);
+        let output = run(transform, vec![Event::Trace(TraceEvent::from(trace))]).await;
+
+        assert_eq!(output.len(), 1);
+        assert_eq!(
+            output[0].as_trace().get("span_count"),
+            Some(&Value::from(2))
+        );
+    }
+
+    #[tokio::test]
+    async fn lua_emits_all_event_types() {
+        trace_init();
+
+        let transform = from_config(
+            r#"
+            hooks.process = """function (event, emit)
+                emit(event)
+                emit({
+                    metric = {
+                        name = "events",
+                        counter = { value = 1 }
+                    }
+                })
+                emit({ trace = { from = "lua" } })
+            end
+            """
+            "#,
+        )
+        .unwrap();
+
+        let output = run(transform, vec![LogEvent::from("hello").into()]).await;
+
+        assert_eq!(output.len(), 3);
+        assert_eq!(output[0].as_log()["message"], "hello".into());
+        assert_eq!(output[1].as_metric().name(), "events");
+        assert_eq!(
+            output[1].as_metric().value(),
+            &MetricValue::Counter { value: 1.0 }
+        );
+        assert_eq!(output[2].as_trace().get("from"), Some(&Value::from("lua")));
+    }
+
+    #[tokio::test]
+    async fn lua_shutdown_emits_events() {
+        trace_init();
+
+        let transform = from_config(
+            r#"
+            source = """
+                count = 0
+            """
+            hooks.process = """function (event, emit)
+                count = count + 1
+            end
+            """
+            hooks.shutdown = """function (emit)
+                emit({ log = { count = count } })
+            end
+            """
+            "#,
+        )
+        .unwrap();
+
+        let events = (0..3).map(|_| LogEvent::default().into()).collect();
+        let output = run(transform, events).await;
+
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].as_log()["count"], Value::from(3));
+    }
+
+    fn write_module(dir: &std::path::Path) {
+        let mut file = File::create(dir.join("module.lua")).unwrap();
+        write!(
+            &mut file,
+            r#"
+            return {{ value = "from module" }}
+            "#
+        )
+        .unwrap();
+    }
+
+    fn require_config(dir: &std::path::Path, policy: &str) -> String {
+        format!(
+            r#"
+            require = "{}"
+            source = """
+                module = require("module")
+            """
+            hooks.process = """function (event, emit)
+                event.log.value = module.value
+                emit(event)
+            end
+            """
+            search_dirs = [{:?}]
+            "#,
+            policy,
+            dir.as_os_str()
+        )
+    }
+
+    #[tokio::test]
+    async fn lua_require_search_dirs() {
+        trace_init();
+
+        let dir = tempfile::tempdir().unwrap();
+        write_module(dir.path());
+
+        for policy in ["search_dirs", "system"] {
+            let transform = from_config(&require_config(dir.path(), policy)).unwrap();
+            let output = run(transform, vec![LogEvent::default().into()]).await;
+            assert_eq!(output[0].as_log()["value"], "from module".into());
+        }
+    }
+
+    #[test]
+    fn lua_require_outside_search_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        write_module(dir.path());
+        let other_dir = tempfile::tempdir().unwrap();
+
+        let error = from_config(&require_config(other_dir.path(), "search_dirs"))
+            .map(|_| ())
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("module 'module' not found"), "{}", error);
+
+        let error = from_config(
+            r#"
+            source = """
+                dofile("/etc/hostname")
+            """
+            hooks.process = "function (event, emit) end"
+            "#,
+        )
+        .map(|_| ())
+        .unwrap_err()
+        .to_string();
+        assert!(error.contains("attempt to call a nil value"), "{}", error);
+    }
+
+    #[test]
+    fn lua_require_disabled() {
+        let dir = tempfile::tempdir().unwrap();
+        write_module(dir.path());
+
+        let error = from_config(&require_config(dir.path(), "disabled"))
+            .map(|_| ())
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("attempt to call a nil value"), "{}", error);
+    }
+}
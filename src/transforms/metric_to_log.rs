@@ -9,7 +9,7 @@ use crate::{
         log_schema, DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
         TransformDescription,
     },
-    event::{self, Event, LogEvent, Metric},
+    event::{self, metric::MetricValue, Event, LogEvent, Metric},
     internal_events::MetricToLogSerializeError,
     schema,
     transforms::{FunctionTransform, OutputBuffer, Transform},
@@ -100,6 +100,10 @@ impl MetricToLog {
         serde_json::to_value(&metric)
             .map_err(|error| emit!(MetricToLogSerializeError { error }))
             .ok()
+            .map(|mut value| {
+                encode_infinite_limits(&metric, &mut value);
+                value
+            })
             .and_then(|value| match value {
                 Value::Object(object) => {
                     // TODO: Avoid a clone here
@@ -130,6 +134,28 @@ impl MetricToLog {
     }
 }
 
+/// Encodes the infinite upper limits of the buckets of a histogram as `+Inf` and `-Inf`, since
+/// JSON numbers can't be infinite, so that the `log_to_metric` transform can decode them.
+fn encode_infinite_limits(metric: &Metric, value: &mut Value) {
+    if let MetricValue::AggregatedHistogram { buckets, .. } = metric.value() {
+        if let Some(values) = value
+            .pointer_mut("/aggregated_histogram/buckets")
+            .and_then(Value::as_array_mut)
+        {
+            for (bucket, value) in buckets.iter().zip(values) {
+                if bucket.upper_limit.is_infinite() {
+                    value["upper_limit"] = if bucket.upper_limit > 0.0 {
+                        "+Inf"
+                    } else {
+                        "-Inf"
+                    }
+                    .into();
+                }
+            }
+        }
+    }
+}
+
 impl FunctionTransform for MetricToLog {
     fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
         let retval: Option<Event> = self
@@ -354,6 +380,26 @@ mod tests {
         assert_eq!(log.metadata(), &metadata);
     }
 
+    #[test]
+    fn transform_histogram_with_infinite_bucket() {
+        let histo = Metric::new(
+            "histo",
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: vector_core::buckets![1.0 => 10, f64::INFINITY => 20],
+                count: 30,
+                sum: 50.0,
+            },
+        );
+
+        let log = do_transform(histo).unwrap();
+
+        assert_eq!(
+            log.get("aggregated_histogram.buckets[1].upper_limit"),
+            Some(&Value::from("+Inf"))
+        );
+    }
+
     #[test]
     fn transform_summary() {
         let summary = Metric::new(
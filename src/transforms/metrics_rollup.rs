@@ -0,0 +1,406 @@
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    num::NonZeroU32,
+    pin::Pin,
+    time::Duration,
+};
+
+use async_stream::stream;
+use chrono::{DateTime, TimeZone, Utc};
+use futures::{Stream, StreamExt};
+use snafu::Snafu;
+use vector_config::configurable_component;
+
+use crate::{
+    config::{DataType, Input, Output, TransformConfig, TransformContext, TransformDescription},
+    event::{metric, Event, EventMetadata},
+    internal_events::{
+        MetricsRollupEventRecorded, MetricsRollupFlushed, MetricsRollupUpdateFailed,
+    },
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+/// Configuration for the `metrics_rollup` transform.
+#[configurable_component(transform)]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct MetricsRollupConfig {
+    /// The resolution of the metrics output by the transform, in seconds.
+    ///
+    /// The metrics of each series are rolled up into one metric per interval, whose timestamp is the start of the
+    /// interval. Intervals are aligned on the Unix epoch, so that a 60 seconds interval starts at the start of a minute.
+    #[serde(default = "default_interval_secs")]
+    pub interval_secs: u64,
+
+    /// How long to wait, in seconds, after the end of an interval before its metrics are flushed.
+    ///
+    /// Metrics with a timestamp in an interval that was already flushed are rolled up and flushed again, in a separate
+    /// metric for the same interval.
+    #[serde(default)]
+    pub allowed_lateness_secs: u64,
+}
+
+impl Default for MetricsRollupConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: default_interval_secs(),
+            allowed_lateness_secs: 0,
+        }
+    }
+}
+
+const fn default_interval_secs() -> u64 {
+    60
+}
+
+#[derive(Debug, PartialEq, Snafu)]
+enum ConfigError {
+    #[snafu(display("`interval_secs` must be between 1 and {}", MAX_INTERVAL_SECS))]
+    InvalidInterval,
+}
+
+/// The largest interval whose length in milliseconds fits in the `interval_ms` of a metric.
+const MAX_INTERVAL_SECS: u64 = u32::MAX as u64 / 1000;
+
+/// How often the intervals are checked for being ready to be flushed.
+const FLUSH_CHECK_PERIOD: Duration = Duration::from_secs(1);
+
+inventory::submit! {
+    TransformDescription::new::<MetricsRollupConfig>("metrics_rollup")
+}
+
+impl_generate_config_from_default!(MetricsRollupConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "metrics_rollup")]
+impl TransformConfig for MetricsRollupConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        MetricsRollup::new(self).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::metric()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Metric)]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "metrics_rollup"
+    }
+}
+
+type MetricEntry = (metric::MetricData, EventMetadata);
+
+#[derive(Debug)]
+pub struct MetricsRollup {
+    interval_secs: i64,
+    allowed_lateness_secs: i64,
+    /// The metrics of each interval, by the Unix timestamp of the start of the interval.
+    intervals: BTreeMap<i64, BTreeMap<metric::MetricSeries, MetricEntry>>,
+}
+
+impl MetricsRollup {
+    pub fn new(config: &MetricsRollupConfig) -> crate::Result<Self> {
+        if config.interval_secs == 0 || config.interval_secs > MAX_INTERVAL_SECS {
+            return Err(Box::new(ConfigError::InvalidInterval));
+        }
+
+        Ok(Self {
+            interval_secs: config.interval_secs as i64,
+            allowed_lateness_secs: config.allowed_lateness_secs.min(i64::MAX as u64) as i64,
+            intervals: BTreeMap::new(),
+        })
+    }
+
+    /// Records the metric in the interval of its timestamp, or of the current time if it has none.
+    fn record(&mut self, event: Event, now: DateTime<Utc>) {
+        let (series, data, metadata) = event.into_metric().into_parts();
+        let timestamp = data.time.timestamp.unwrap_or(now).timestamp();
+        let start = timestamp - timestamp.rem_euclid(self.interval_secs);
+        let interval = self.intervals.entry(start).or_default();
+
+        match interval.entry(series) {
+            Entry::Occupied(mut entry) => {
+                let existing = entry.get_mut();
+                match data.kind {
+                    metric::MetricKind::Incremental => {
+                        // In order to add the new value, both kinds must be incremental.
+                        if existing.0.kind == data.kind && existing.0.value.add(&data.value) {
+                            existing.1.merge(metadata);
+                        } else {
+                            emit!(MetricsRollupUpdateFailed);
+                            *existing = (data, metadata);
+                        }
+                    }
+                    metric::MetricKind::Absolute => {
+                        // The latest value of the interval replaces the older ones.
+                        if data.time.timestamp >= existing.0.time.timestamp {
+                            *existing = (data, metadata);
+                        }
+                    }
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert((data, metadata));
+            }
+        }
+
+        emit!(MetricsRollupEventRecorded);
+    }
+
+    /// Flushes the intervals which ended at least `allowed_lateness_secs` ago.
+    fn flush_ended_into(&mut self, now: DateTime<Utc>, output: &mut Vec<Event>) {
+        let last_ended = now.timestamp() - self.interval_secs - self.allowed_lateness_secs;
+        let open = self.intervals.split_off(&(last_ended + 1));
+        let ended = std::mem::replace(&mut self.intervals, open);
+        self.flush_intervals_into(ended, output);
+    }
+
+    fn flush_all_into(&mut self, output: &mut Vec<Event>) {
+        let intervals = std::mem::take(&mut self.intervals);
+        self.flush_intervals_into(intervals, output);
+    }
+
+    fn flush_intervals_into(
+        &self,
+        intervals: BTreeMap<i64, BTreeMap<metric::MetricSeries, MetricEntry>>,
+        output: &mut Vec<Event>,
+    ) {
+        let interval_ms = NonZeroU32::new(self.interval_secs as u32 * 1000);
+        for (start, metrics) in intervals {
+            emit!(MetricsRollupFlushed {
+                series: metrics.len()
+            });
+            for (series, (mut data, metadata)) in metrics {
+                data.time = metric::MetricTime {
+                    timestamp: Some(Utc.timestamp(start, 0)),
+                    interval_ms: match data.kind {
+                        metric::MetricKind::Incremental => interval_ms,
+                        metric::MetricKind::Absolute => None,
+                    },
+                };
+                let metric = metric::Metric::from_parts(series, data, metadata);
+                output.push(Event::Metric(metric));
+            }
+        }
+    }
+}
+
+impl TaskTransform<Event> for MetricsRollup {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut flush_stream = tokio::time::interval(FLUSH_CHECK_PERIOD);
+
+        Box::pin(stream! {
+            let mut output = Vec::new();
+            let mut done = false;
+            while !done {
+                tokio::select! {
+                    _ = flush_stream.tick() => {
+                        self.flush_ended_into(Utc::now(), &mut output);
+                    },
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            None => {
+                                self.flush_all_into(&mut output);
+                                done = true;
+                            }
+                            Some(event) => self.record(event, Utc::now()),
+                        }
+                    }
+                };
+                for event in output.drain(..) {
+                    yield event;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+
+    use super::*;
+    use crate::event::{
+        metric::{MetricKind, MetricValue, StatisticKind},
+        Metric,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MetricsRollupConfig>();
+    }
+
+    fn rollup(allowed_lateness_secs: u64) -> MetricsRollup {
+        MetricsRollup::new(&MetricsRollupConfig {
+            interval_secs: 60,
+            allowed_lateness_secs,
+        })
+        .unwrap()
+    }
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        Utc.timestamp(secs, 0)
+    }
+
+    fn make_metric(kind: MetricKind, value: MetricValue, secs: i64) -> Event {
+        Metric::new("requests", kind, value)
+            .with_timestamp(Some(at(secs)))
+            .into()
+    }
+
+    fn counter(value: f64, secs: i64) -> Event {
+        make_metric(
+            MetricKind::Incremental,
+            MetricValue::Counter { value },
+            secs,
+        )
+    }
+
+    #[test]
+    fn sums_counters_per_interval() {
+        let mut rollup = rollup(0);
+        rollup.record(counter(1.0, 60), at(60));
+        rollup.record(counter(2.0, 119), at(119));
+        rollup.record(counter(4.0, 120), at(120));
+
+        let mut output = Vec::new();
+        rollup.flush_all_into(&mut output);
+
+        let interval_ms = NonZeroU32::new(60_000);
+        assert_eq!(
+            output,
+            vec![
+                Metric::new(
+                    "requests",
+                    MetricKind::Incremental,
+                    MetricValue::Counter { value: 3.0 }
+                )
+                .with_timestamp(Some(at(60)))
+                .with_interval_ms(interval_ms)
+                .into(),
+                Metric::new(
+                    "requests",
+                    MetricKind::Incremental,
+                    MetricValue::Counter { value: 4.0 }
+                )
+                .with_timestamp(Some(at(120)))
+                .with_interval_ms(interval_ms)
+                .into(),
+            ]
+        );
+    }
+
+    #[test]
+    fn merges_distributions() {
+        let mut rollup = rollup(0);
+        for (value, secs) in [(1.0, 60), (2.0, 90)] {
+            let distribution = MetricValue::Distribution {
+                samples: vector_core::samples![value => 1],
+                statistic: StatisticKind::Histogram,
+            };
+            rollup.record(
+                make_metric(MetricKind::Incremental, distribution, secs),
+                at(secs),
+            );
+        }
+
+        let mut output = Vec::new();
+        rollup.flush_all_into(&mut output);
+
+        assert_eq!(output.len(), 1);
+        assert_eq!(
+            output[0].as_metric().value(),
+            &MetricValue::Distribution {
+                samples: vector_core::samples![1.0 => 1, 2.0 => 1],
+                statistic: StatisticKind::Histogram,
+            }
+        );
+    }
+
+    #[test]
+    fn keeps_latest_absolute_value() {
+        let mut rollup = rollup(0);
+        for (value, secs) in [(1.0, 70), (3.0, 90), (2.0, 80)] {
+            let gauge = MetricValue::Gauge { value };
+            rollup.record(make_metric(MetricKind::Absolute, gauge, secs), at(secs));
+        }
+
+        let mut output = Vec::new();
+        rollup.flush_all_into(&mut output);
+
+        assert_eq!(output.len(), 1);
+        assert_eq!(
+            output[0].as_metric().value(),
+            &MetricValue::Gauge { value: 3.0 }
+        );
+        assert_eq!(output[0].as_metric().timestamp(), Some(at(60)));
+        assert_eq!(output[0].as_metric().interval_ms(), None);
+    }
+
+    #[test]
+    fn flushes_ended_intervals() {
+        let mut rollup = rollup(10);
+        rollup.record(counter(1.0, 60), at(60));
+        rollup.record(counter(1.0, 120), at(120));
+
+        let mut output = Vec::new();
+        // The first interval ended at 120, but late metrics are allowed until 130.
+        rollup.flush_ended_into(at(129), &mut output);
+        assert!(output.is_empty());
+
+        rollup.flush_ended_into(at(130), &mut output);
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].as_metric().timestamp(), Some(at(60)));
+
+        // Late metrics are flushed separately.
+        output.clear();
+        rollup.record(counter(1.0, 100), at(131));
+        rollup.flush_ended_into(at(131), &mut output);
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].as_metric().timestamp(), Some(at(60)));
+    }
+
+    #[tokio::test]
+    async fn flushes_on_shutdown() {
+        let rollup = Box::new(rollup(0));
+        // Recent metrics, so that their interval isn't flushed before the input ends.
+        let now = Utc::now().timestamp();
+        let input = vec![counter(1.0, now), counter(2.0, now)];
+        let output = rollup
+            .transform(Box::pin(stream::iter(input)))
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(output.len(), 1);
+        assert_eq!(
+            output[0].as_metric().value(),
+            &MetricValue::Counter { value: 3.0 }
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_intervals() {
+        let error = MetricsRollup::new(&MetricsRollupConfig {
+            interval_secs: 0,
+            allowed_lateness_secs: 0,
+        })
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "`interval_secs` must be between 1 and {}",
+                MAX_INTERVAL_SECS
+            )
+        );
+    }
+}
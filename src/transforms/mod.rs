@@ -4,12 +4,20 @@ use std::collections::HashSet;
 use async_trait::async_trait;
 use snafu::Snafu;
 
+#[cfg(feature = "transforms-adaptive_sample")]
+pub mod adaptive_sample;
 #[cfg(feature = "transforms-aggregate")]
 pub mod aggregate;
+#[cfg(feature = "transforms-aggregate_logs")]
+pub mod aggregate_logs;
 #[cfg(feature = "transforms-aws_ec2_metadata")]
 pub mod aws_ec2_metadata;
+#[cfg(feature = "transforms-cardinality_limit")]
+pub mod cardinality_limit;
 #[cfg(feature = "transforms-dedupe")]
 pub mod dedupe;
+#[cfg(feature = "transforms-exec")]
+pub mod exec;
 #[cfg(feature = "transforms-filter")]
 pub mod filter;
 #[cfg(feature = "transforms-geoip")]
@@ -20,8 +28,12 @@ pub mod log_to_metric;
 pub mod lua;
 #[cfg(feature = "transforms-metric_to_log")]
 pub mod metric_to_log;
+#[cfg(feature = "transforms-metrics_rollup")]
+pub mod metrics_rollup;
 #[cfg(feature = "transforms-pipelines")]
 pub mod pipelines;
+#[cfg(feature = "transforms-redact_pii")]
+pub mod redact_pii;
 #[cfg(feature = "transforms-reduce")]
 pub mod reduce;
 #[cfg(feature = "transforms-remap")]
@@ -30,10 +42,16 @@ pub mod remap;
 pub mod route;
 #[cfg(feature = "transforms-sample")]
 pub mod sample;
+#[cfg(feature = "transforms-schema_guard")]
+pub mod schema_guard;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 pub mod tag_cardinality_limit;
+#[cfg(feature = "transforms-tail_sample")]
+pub mod tail_sample;
 #[cfg(feature = "transforms-throttle")]
 pub mod throttle;
+#[cfg(feature = "transforms-trace_sampling")]
+pub mod trace_sampling;
 
 use vector_config::configurable_component;
 pub use vector_core::transform::{
@@ -60,18 +78,34 @@ enum BuildError {
 #[derive(Clone, Debug)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Transforms {
+    /// Adaptive sample.
+    #[cfg(feature = "transforms-adaptive_sample")]
+    AdaptiveSample(#[configurable(derived)] adaptive_sample::AdaptiveSampleConfig),
+
     /// Aggregate.
     #[cfg(feature = "transforms-aggregate")]
     Aggregate(#[configurable(derived)] aggregate::AggregateConfig),
 
+    /// Aggregate logs.
+    #[cfg(feature = "transforms-aggregate_logs")]
+    AggregateLogs(#[configurable(derived)] aggregate_logs::AggregateLogsConfig),
+
     /// AWS EC2 metadata.
     #[cfg(feature = "transforms-aws_ec2_metadata")]
     AwsEc2Metadata(#[configurable(derived)] aws_ec2_metadata::Ec2Metadata),
 
+    /// Cardinality limit.
+    #[cfg(feature = "transforms-cardinality_limit")]
+    CardinalityLimit(#[configurable(derived)] cardinality_limit::CardinalityLimitConfig),
+
     /// Dedupe.
     #[cfg(feature = "transforms-dedupe")]
     Dedupe(#[configurable(derived)] dedupe::DedupeConfig),
 
+    /// Exec.
+    #[cfg(feature = "transforms-exec")]
+    Exec(#[configurable(derived)] exec::ExecConfig),
+
     /// Filter.
     #[cfg(feature = "transforms-filter")]
     Filter(#[configurable(derived)] filter::FilterConfig),
@@ -92,10 +126,18 @@ pub enum Transforms {
     #[cfg(feature = "transforms-metric_to_log")]
     MetricToLog(#[configurable(derived)] metric_to_log::MetricToLogConfig),
 
+    /// Metrics rollup.
+    #[cfg(feature = "transforms-metrics_rollup")]
+    MetricsRollup(#[configurable(derived)] metrics_rollup::MetricsRollupConfig),
+
     /// Pipelines.
     #[cfg(feature = "transforms-pipelines")]
     Pipelines(#[configurable(derived)] pipelines::PipelinesConfig),
 
+    /// Redact PII.
+    #[cfg(feature = "transforms-redact_pii")]
+    RedactPii(#[configurable(derived)] redact_pii::RedactPiiConfig),
+
     /// Reduce.
     #[cfg(feature = "transforms-reduce")]
     Reduce(#[configurable(derived)] reduce::ReduceConfig),
@@ -114,13 +156,25 @@ pub enum Transforms {
     #[serde(alias = "sampler")]
     Sample(#[configurable(derived)] sample::SampleConfig),
 
+    /// Schema guard.
+    #[cfg(feature = "transforms-schema_guard")]
+    SchemaGuard(#[configurable(derived)] schema_guard::SchemaGuardConfig),
+
     /// Tag cardinality limit.
     #[cfg(feature = "transforms-tag_cardinality_limit")]
     TagCardinalityLimit(#[configurable(derived)] tag_cardinality_limit::TagCardinalityLimitConfig),
 
+    /// Tail sample.
+    #[cfg(feature = "transforms-tail_sample")]
+    TailSample(#[configurable(derived)] tail_sample::TailSampleConfig),
+
     /// Throttle.
     #[cfg(feature = "transforms-throttle")]
     Throttle(#[configurable(derived)] throttle::ThrottleConfig),
+
+    /// Trace sampling.
+    #[cfg(feature = "transforms-trace_sampling")]
+    TraceSampling(#[configurable(derived)] trace_sampling::TraceSamplingConfig),
 }
 
 #[async_trait]
@@ -128,12 +182,20 @@ impl TransformConfig for Transforms {
     #[allow(unused_variables)]
     async fn build(&self, globals: &TransformContext) -> crate::Result<Transform> {
         match self {
+            #[cfg(feature = "transforms-adaptive_sample")]
+            Transforms::AdaptiveSample(inner) => inner.build(globals).await,
             #[cfg(feature = "transforms-aggregate")]
             Transforms::Aggregate(inner) => inner.build(globals).await,
+            #[cfg(feature = "transforms-aggregate_logs")]
+            Transforms::AggregateLogs(inner) => inner.build(globals).await,
             #[cfg(feature = "transforms-aws_ec2_metadata")]
             Transforms::AwsEc2Metadata(inner) => inner.build(globals).await,
+            #[cfg(feature = "transforms-cardinality_limit")]
+            Transforms::CardinalityLimit(inner) => inner.build(globals).await,
             #[cfg(feature = "transforms-dedupe")]
             Transforms::Dedupe(inner) => inner.build(globals).await,
+            #[cfg(feature = "transforms-exec")]
+            Transforms::Exec(inner) => inner.build(globals).await,
             #[cfg(feature = "transforms-filter")]
             Transforms::Filter(inner) => inner.build(globals).await,
             #[cfg(feature = "transforms-geoip")]
@@ -144,8 +206,12 @@ impl TransformConfig for Transforms {
             Transforms::Lua(inner) => inner.build(globals).await,
             #[cfg(feature = "transforms-metric_to_log")]
             Transforms::MetricToLog(inner) => inner.build(globals).await,
+            #[cfg(feature = "transforms-metrics_rollup")]
+            Transforms::MetricsRollup(inner) => inner.build(globals).await,
             #[cfg(feature = "transforms-pipelines")]
             Transforms::Pipelines(inner) => inner.build(globals).await,
+            #[cfg(feature = "transforms-redact_pii")]
+            Transforms::RedactPii(inner) => inner.build(globals).await,
             #[cfg(feature = "transforms-reduce")]
             Transforms::Reduce(inner) => inner.build(globals).await,
             #[cfg(feature = "transforms-remap")]
@@ -154,10 +220,16 @@ impl TransformConfig for Transforms {
             Transforms::Route(inner) => inner.build(globals).await,
             #[cfg(feature = "transforms-sample")]
             Transforms::Sample(inner) => inner.build(globals).await,
+            #[cfg(feature = "transforms-schema_guard")]
+            Transforms::SchemaGuard(inner) => inner.build(globals).await,
             #[cfg(feature = "transforms-tag_cardinality_limit")]
             Transforms::TagCardinalityLimit(inner) => inner.build(globals).await,
+            #[cfg(feature = "transforms-tail_sample")]
+            Transforms::TailSample(inner) => inner.build(globals).await,
             #[cfg(feature = "transforms-throttle")]
             Transforms::Throttle(inner) => inner.build(globals).await,
+            #[cfg(feature = "transforms-trace_sampling")]
+            Transforms::TraceSampling(inner) => inner.build(globals).await,
             #[allow(unreachable_patterns)]
             _ => unimplemented!(),
         }
@@ -165,12 +237,20 @@ impl TransformConfig for Transforms {
 
     fn input(&self) -> Input {
         match self {
+            #[cfg(feature = "transforms-adaptive_sample")]
+            Transforms::AdaptiveSample(inner) => inner.input(),
             #[cfg(feature = "transforms-aggregate")]
             Transforms::Aggregate(inner) => inner.input(),
+            #[cfg(feature = "transforms-aggregate_logs")]
+            Transforms::AggregateLogs(inner) => inner.input(),
             #[cfg(feature = "transforms-aws_ec2_metadata")]
             Transforms::AwsEc2Metadata(inner) => inner.input(),
+            #[cfg(feature = "transforms-cardinality_limit")]
+            Transforms::CardinalityLimit(inner) => inner.input(),
             #[cfg(feature = "transforms-dedupe")]
             Transforms::Dedupe(inner) => inner.input(),
+            #[cfg(feature = "transforms-exec")]
+            Transforms::Exec(inner) => inner.input(),
             #[cfg(feature = "transforms-filter")]
             Transforms::Filter(inner) => inner.input(),
             #[cfg(feature = "transforms-geoip")]
@@ -181,8 +261,12 @@ impl TransformConfig for Transforms {
             Transforms::Lua(inner) => inner.input(),
             #[cfg(feature = "transforms-metric_to_log")]
             Transforms::MetricToLog(inner) => inner.input(),
+            #[cfg(feature = "transforms-metrics_rollup")]
+            Transforms::MetricsRollup(inner) => inner.input(),
             #[cfg(feature = "transforms-pipelines")]
             Transforms::Pipelines(inner) => inner.input(),
+            #[cfg(feature = "transforms-redact_pii")]
+            Transforms::RedactPii(inner) => inner.input(),
             #[cfg(feature = "transforms-reduce")]
             Transforms::Reduce(inner) => inner.input(),
             #[cfg(feature = "transforms-remap")]
@@ -191,10 +275,16 @@ impl TransformConfig for Transforms {
             Transforms::Route(inner) => inner.input(),
             #[cfg(feature = "transforms-sample")]
             Transforms::Sample(inner) => inner.input(),
+            #[cfg(feature = "transforms-schema_guard")]
+            Transforms::SchemaGuard(inner) => inner.input(),
             #[cfg(feature = "transforms-tag_cardinality_limit")]
             Transforms::TagCardinalityLimit(inner) => inner.input(),
+            #[cfg(feature = "transforms-tail_sample")]
+            Transforms::TailSample(inner) => inner.input(),
             #[cfg(feature = "transforms-throttle")]
             Transforms::Throttle(inner) => inner.input(),
+            #[cfg(feature = "transforms-trace_sampling")]
+            Transforms::TraceSampling(inner) => inner.input(),
             #[allow(unreachable_patterns)]
             _ => unimplemented!(),
         }
@@ -203,12 +293,20 @@ impl TransformConfig for Transforms {
     #[allow(unused_variables)]
     fn outputs(&self, merged_definition: &schema::Definition) -> Vec<Output> {
         match self {
+            #[cfg(feature = "transforms-adaptive_sample")]
+            Transforms::AdaptiveSample(inner) => inner.outputs(merged_definition),
             #[cfg(feature = "transforms-aggregate")]
             Transforms::Aggregate(inner) => inner.outputs(merged_definition),
+            #[cfg(feature = "transforms-aggregate_logs")]
+            Transforms::AggregateLogs(inner) => inner.outputs(merged_definition),
             #[cfg(feature = "transforms-aws_ec2_metadata")]
             Transforms::AwsEc2Metadata(inner) => inner.outputs(merged_definition),
+            #[cfg(feature = "transforms-cardinality_limit")]
+            Transforms::CardinalityLimit(inner) => inner.outputs(merged_definition),
             #[cfg(feature = "transforms-dedupe")]
             Transforms::Dedupe(inner) => inner.outputs(merged_definition),
+            #[cfg(feature = "transforms-exec")]
+            Transforms::Exec(inner) => inner.outputs(merged_definition),
             #[cfg(feature = "transforms-filter")]
             Transforms::Filter(inner) => inner.outputs(merged_definition),
             #[cfg(feature = "transforms-geoip")]
@@ -219,8 +317,12 @@ impl TransformConfig for Transforms {
             Transforms::Lua(inner) => inner.outputs(merged_definition),
             #[cfg(feature = "transforms-metric_to_log")]
             Transforms::MetricToLog(inner) => inner.outputs(merged_definition),
+            #[cfg(feature = "transforms-metrics_rollup")]
+            Transforms::MetricsRollup(inner) => inner.outputs(merged_definition),
             #[cfg(feature = "transforms-pipelines")]
             Transforms::Pipelines(inner) => inner.outputs(merged_definition),
+            #[cfg(feature = "transforms-redact_pii")]
+            Transforms::RedactPii(inner) => inner.outputs(merged_definition),
             #[cfg(feature = "transforms-reduce")]
             Transforms::Reduce(inner) => inner.outputs(merged_definition),
             #[cfg(feature = "transforms-remap")]
@@ -229,10 +331,16 @@ impl TransformConfig for Transforms {
             Transforms::Route(inner) => inner.outputs(merged_definition),
             #[cfg(feature = "transforms-sample")]
             Transforms::Sample(inner) => inner.outputs(merged_definition),
+            #[cfg(feature = "transforms-schema_guard")]
+            Transforms::SchemaGuard(inner) => inner.outputs(merged_definition),
             #[cfg(feature = "transforms-tag_cardinality_limit")]
             Transforms::TagCardinalityLimit(inner) => inner.outputs(merged_definition),
+            #[cfg(feature = "transforms-tail_sample")]
+            Transforms::TailSample(inner) => inner.outputs(merged_definition),
             #[cfg(feature = "transforms-throttle")]
             Transforms::Throttle(inner) => inner.outputs(merged_definition),
+            #[cfg(feature = "transforms-trace_sampling")]
+            Transforms::TraceSampling(inner) => inner.outputs(merged_definition),
             #[allow(unreachable_patterns)]
             _ => unimplemented!(),
         }
@@ -240,12 +348,20 @@ impl TransformConfig for Transforms {
 
     fn transform_type(&self) -> &'static str {
         match self {
+            #[cfg(feature = "transforms-adaptive_sample")]
+            Transforms::AdaptiveSample(inner) => inner.transform_type(),
             #[cfg(feature = "transforms-aggregate")]
             Transforms::Aggregate(inner) => inner.transform_type(),
+            #[cfg(feature = "transforms-aggregate_logs")]
+            Transforms::AggregateLogs(inner) => inner.transform_type(),
             #[cfg(feature = "transforms-aws_ec2_metadata")]
             Transforms::AwsEc2Metadata(inner) => inner.transform_type(),
+            #[cfg(feature = "transforms-cardinality_limit")]
+            Transforms::CardinalityLimit(inner) => inner.transform_type(),
             #[cfg(feature = "transforms-dedupe")]
             Transforms::Dedupe(inner) => inner.transform_type(),
+            #[cfg(feature = "transforms-exec")]
+            Transforms::Exec(inner) => inner.transform_type(),
             #[cfg(feature = "transforms-filter")]
             Transforms::Filter(inner) => inner.transform_type(),
             #[cfg(feature = "transforms-geoip")]
@@ -256,8 +372,12 @@ impl TransformConfig for Transforms {
             Transforms::Lua(inner) => inner.transform_type(),
             #[cfg(feature = "transforms-metric_to_log")]
             Transforms::MetricToLog(inner) => inner.transform_type(),
+            #[cfg(feature = "transforms-metrics_rollup")]
+            Transforms::MetricsRollup(inner) => inner.transform_type(),
             #[cfg(feature = "transforms-pipelines")]
             Transforms::Pipelines(inner) => inner.transform_type(),
+            #[cfg(feature = "transforms-redact_pii")]
+            Transforms::RedactPii(inner) => inner.transform_type(),
             #[cfg(feature = "transforms-reduce")]
             Transforms::Reduce(inner) => inner.transform_type(),
             #[cfg(feature = "transforms-remap")]
@@ -266,10 +386,16 @@ impl TransformConfig for Transforms {
             Transforms::Route(inner) => inner.transform_type(),
             #[cfg(feature = "transforms-sample")]
             Transforms::Sample(inner) => inner.transform_type(),
+            #[cfg(feature = "transforms-schema_guard")]
+            Transforms::SchemaGuard(inner) => inner.transform_type(),
             #[cfg(feature = "transforms-tag_cardinality_limit")]
             Transforms::TagCardinalityLimit(inner) => inner.transform_type(),
+            #[cfg(feature = "transforms-tail_sample")]
+            Transforms::TailSample(inner) => inner.transform_type(),
             #[cfg(feature = "transforms-throttle")]
             Transforms::Throttle(inner) => inner.transform_type(),
+            #[cfg(feature = "transforms-trace_sampling")]
+            Transforms::TraceSampling(inner) => inner.transform_type(),
             #[allow(unreachable_patterns)]
             _ => unimplemented!(),
         }
@@ -277,12 +403,20 @@ impl TransformConfig for Transforms {
 
     fn typetag_name(&self) -> &'static str {
         match self {
+            #[cfg(feature = "transforms-adaptive_sample")]
+            Transforms::AdaptiveSample(inner) => inner.typetag_name(),
             #[cfg(feature = "transforms-aggregate")]
             Transforms::Aggregate(inner) => inner.typetag_name(),
+            #[cfg(feature = "transforms-aggregate_logs")]
+            Transforms::AggregateLogs(inner) => inner.typetag_name(),
             #[cfg(feature = "transforms-aws_ec2_metadata")]
             Transforms::AwsEc2Metadata(inner) => inner.typetag_name(),
+            #[cfg(feature = "transforms-cardinality_limit")]
+            Transforms::CardinalityLimit(inner) => inner.typetag_name(),
             #[cfg(feature = "transforms-dedupe")]
             Transforms::Dedupe(inner) => inner.typetag_name(),
+            #[cfg(feature = "transforms-exec")]
+            Transforms::Exec(inner) => inner.typetag_name(),
             #[cfg(feature = "transforms-filter")]
             Transforms::Filter(inner) => inner.typetag_name(),
             #[cfg(feature = "transforms-geoip")]
@@ -293,8 +427,12 @@ impl TransformConfig for Transforms {
             Transforms::Lua(inner) => inner.typetag_name(),
             #[cfg(feature = "transforms-metric_to_log")]
             Transforms::MetricToLog(inner) => inner.typetag_name(),
+            #[cfg(feature = "transforms-metrics_rollup")]
+            Transforms::MetricsRollup(inner) => inner.typetag_name(),
             #[cfg(feature = "transforms-pipelines")]
             Transforms::Pipelines(inner) => inner.typetag_name(),
+            #[cfg(feature = "transforms-redact_pii")]
+            Transforms::RedactPii(inner) => inner.typetag_name(),
             #[cfg(feature = "transforms-reduce")]
             Transforms::Reduce(inner) => inner.typetag_name(),
             #[cfg(feature = "transforms-remap")]
@@ -303,10 +441,16 @@ impl TransformConfig for Transforms {
             Transforms::Route(inner) => inner.typetag_name(),
             #[cfg(feature = "transforms-sample")]
             Transforms::Sample(inner) => inner.typetag_name(),
+            #[cfg(feature = "transforms-schema_guard")]
+            Transforms::SchemaGuard(inner) => inner.typetag_name(),
             #[cfg(feature = "transforms-tag_cardinality_limit")]
             Transforms::TagCardinalityLimit(inner) => inner.typetag_name(),
+            #[cfg(feature = "transforms-tail_sample")]
+            Transforms::TailSample(inner) => inner.typetag_name(),
             #[cfg(feature = "transforms-throttle")]
             Transforms::Throttle(inner) => inner.typetag_name(),
+            #[cfg(feature = "transforms-trace_sampling")]
+            Transforms::TraceSampling(inner) => inner.typetag_name(),
             #[allow(unreachable_patterns)]
             _ => unimplemented!(),
         }
@@ -314,12 +458,20 @@ impl TransformConfig for Transforms {
 
     fn typetag_deserialize(&self) {
         match self {
+            #[cfg(feature = "transforms-adaptive_sample")]
+            Transforms::AdaptiveSample(inner) => inner.typetag_deserialize(),
             #[cfg(feature = "transforms-aggregate")]
             Transforms::Aggregate(inner) => inner.typetag_deserialize(),
+            #[cfg(feature = "transforms-aggregate_logs")]
+            Transforms::AggregateLogs(inner) => inner.typetag_deserialize(),
             #[cfg(feature = "transforms-aws_ec2_metadata")]
             Transforms::AwsEc2Metadata(inner) => inner.typetag_deserialize(),
+            #[cfg(feature = "transforms-cardinality_limit")]
+            Transforms::CardinalityLimit(inner) => inner.typetag_deserialize(),
             #[cfg(feature = "transforms-dedupe")]
             Transforms::Dedupe(inner) => inner.typetag_deserialize(),
+            #[cfg(feature = "transforms-exec")]
+            Transforms::Exec(inner) => inner.typetag_deserialize(),
             #[cfg(feature = "transforms-filter")]
             Transforms::Filter(inner) => inner.typetag_deserialize(),
             #[cfg(feature = "transforms-geoip")]
@@ -330,8 +482,12 @@ impl TransformConfig for Transforms {
             Transforms::Lua(inner) => inner.typetag_deserialize(),
             #[cfg(feature = "transforms-metric_to_log")]
             Transforms::MetricToLog(inner) => inner.typetag_deserialize(),
+            #[cfg(feature = "transforms-metrics_rollup")]
+            Transforms::MetricsRollup(inner) => inner.typetag_deserialize(),
             #[cfg(feature = "transforms-pipelines")]
             Transforms::Pipelines(inner) => inner.typetag_deserialize(),
+            #[cfg(feature = "transforms-redact_pii")]
+            Transforms::RedactPii(inner) => inner.typetag_deserialize(),
             #[cfg(feature = "transforms-reduce")]
             Transforms::Reduce(inner) => inner.typetag_deserialize(),
             #[cfg(feature = "transforms-remap")]
@@ -340,10 +496,16 @@ impl TransformConfig for Transforms {
             Transforms::Route(inner) => inner.typetag_deserialize(),
             #[cfg(feature = "transforms-sample")]
             Transforms::Sample(inner) => inner.typetag_deserialize(),
+            #[cfg(feature = "transforms-schema_guard")]
+            Transforms::SchemaGuard(inner) => inner.typetag_deserialize(),
             #[cfg(feature = "transforms-tag_cardinality_limit")]
             Transforms::TagCardinalityLimit(inner) => inner.typetag_deserialize(),
+            #[cfg(feature = "transforms-tail_sample")]
+            Transforms::TailSample(inner) => inner.typetag_deserialize(),
             #[cfg(feature = "transforms-throttle")]
             Transforms::Throttle(inner) => inner.typetag_deserialize(),
+            #[cfg(feature = "transforms-trace_sampling")]
+            Transforms::TraceSampling(inner) => inner.typetag_deserialize(),
             #[allow(unreachable_patterns)]
             _ => unimplemented!(),
         }
@@ -352,12 +514,20 @@ impl TransformConfig for Transforms {
     #[allow(unused_variables)]
     fn nestable(&self, parents: &HashSet<&'static str>) -> bool {
         match self {
+            #[cfg(feature = "transforms-adaptive_sample")]
+            Transforms::AdaptiveSample(inner) => inner.nestable(parents),
             #[cfg(feature = "transforms-aggregate")]
             Transforms::Aggregate(inner) => inner.nestable(parents),
+            #[cfg(feature = "transforms-aggregate_logs")]
+            Transforms::AggregateLogs(inner) => inner.nestable(parents),
             #[cfg(feature = "transforms-aws_ec2_metadata")]
             Transforms::AwsEc2Metadata(inner) => inner.nestable(parents),
+            #[cfg(feature = "transforms-cardinality_limit")]
+            Transforms::CardinalityLimit(inner) => inner.nestable(parents),
             #[cfg(feature = "transforms-dedupe")]
             Transforms::Dedupe(inner) => inner.nestable(parents),
+            #[cfg(feature = "transforms-exec")]
+            Transforms::Exec(inner) => inner.nestable(parents),
             #[cfg(feature = "transforms-filter")]
             Transforms::Filter(inner) => inner.nestable(parents),
             #[cfg(feature = "transforms-geoip")]
@@ -368,8 +538,12 @@ impl TransformConfig for Transforms {
             Transforms::Lua(inner) => inner.nestable(parents),
             #[cfg(feature = "transforms-metric_to_log")]
             Transforms::MetricToLog(inner) => inner.nestable(parents),
+            #[cfg(feature = "transforms-metrics_rollup")]
+            Transforms::MetricsRollup(inner) => inner.nestable(parents),
             #[cfg(feature = "transforms-pipelines")]
             Transforms::Pipelines(inner) => inner.nestable(parents),
+            #[cfg(feature = "transforms-redact_pii")]
+            Transforms::RedactPii(inner) => inner.nestable(parents),
             #[cfg(feature = "transforms-reduce")]
             Transforms::Reduce(inner) => inner.nestable(parents),
             #[cfg(feature = "transforms-remap")]
@@ -378,10 +552,16 @@ impl TransformConfig for Transforms {
             Transforms::Route(inner) => inner.nestable(parents),
             #[cfg(feature = "transforms-sample")]
             Transforms::Sample(inner) => inner.nestable(parents),
+            #[cfg(feature = "transforms-schema_guard")]
+            Transforms::SchemaGuard(inner) => inner.nestable(parents),
             #[cfg(feature = "transforms-tag_cardinality_limit")]
             Transforms::TagCardinalityLimit(inner) => inner.nestable(parents),
+            #[cfg(feature = "transforms-tail_sample")]
+            Transforms::TailSample(inner) => inner.nestable(parents),
             #[cfg(feature = "transforms-throttle")]
             Transforms::Throttle(inner) => inner.nestable(parents),
+            #[cfg(feature = "transforms-trace_sampling")]
+            Transforms::TraceSampling(inner) => inner.nestable(parents),
             #[allow(unreachable_patterns)]
             _ => unimplemented!(),
         }
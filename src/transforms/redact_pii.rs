@@ -0,0 +1,500 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use hmac::{Hmac, Mac};
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use sha2::Sha256;
+use vector_config::configurable_component;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
+        TransformDescription,
+    },
+    event::{Event, Value},
+    internal_events::PiiRedacted,
+    schema,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+/// Configuration for the `redact_pii` transform.
+#[configurable_component(transform)]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RedactPiiConfig {
+    /// The detectors applied to the string values of the events, in order.
+    pub detectors: Vec<DetectorConfig>,
+
+    /// The fields scanned for PII, including the fields nested in them.
+    ///
+    /// All the fields of the events are scanned when empty.
+    #[serde(default)]
+    pub fields: Vec<String>,
+
+    /// The string replacing the PII found by the detectors with the `mask` action.
+    #[serde(default = "default_mask")]
+    pub mask: String,
+
+    /// The key of the HMAC-SHA256 replacing the PII found by the detectors with the `hash` action.
+    ///
+    /// Required when a detector uses the `hash` action. Keeping the key secret prevents the hashes from being
+    /// reversed by hashing guessed values.
+    pub hash_key: Option<String>,
+}
+
+/// A detector of PII, and the action taken on the PII it finds.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct DetectorConfig {
+    #[serde(flatten)]
+    pub kind: DetectorKind,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub action: RedactionAction,
+}
+
+/// The kind of PII found by a detector.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum DetectorKind {
+    /// Email addresses.
+    Email,
+
+    /// Credit card numbers, with or without space or dash separators, passing the Luhn checksum.
+    CreditCard,
+
+    /// IPv4 and IPv6 addresses.
+    IpAddress,
+
+    /// US social security numbers, in the `123-45-6789` form.
+    UsSsn,
+
+    /// UK national insurance numbers, such as `QQ 12 34 56 C`.
+    UkNino,
+
+    /// The matches of a user-provided regular expression.
+    Regex {
+        /// The regular expression matching the PII.
+        pattern: String,
+    },
+}
+
+/// The action taken on the PII found by a detector.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RedactionAction {
+    /// Replaces the PII with `mask`.
+    #[derivative(Default)]
+    Mask,
+
+    /// Replaces the PII with the hex-encoded HMAC-SHA256 of the PII, keyed with `hash_key`.
+    ///
+    /// Hashing keeps equal values correlatable without revealing them.
+    Hash,
+
+    /// Removes the field holding the PII.
+    Remove,
+}
+
+fn default_mask() -> String {
+    "[REDACTED]".to_owned()
+}
+
+inventory::submit! {
+    TransformDescription::new::<RedactPiiConfig>("redact_pii")
+}
+
+impl GenerateConfig for RedactPiiConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            [[detectors]]
+            type = "email"
+
+            [[detectors]]
+            type = "credit_card"
+            action = "remove"
+            "#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "redact_pii")]
+impl TransformConfig for RedactPiiConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        RedactPii::new(self).map(Transform::function)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "redact_pii"
+    }
+}
+
+static EMAIL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}").unwrap()
+});
+static CREDIT_CARD: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b\d(?:[ -]?\d){12,18}\b").unwrap());
+static IP_ADDRESS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:\d{1,3}\.){3}\d{1,3}\b|(?:[0-9A-Fa-f]{1,4})?(?::[0-9A-Fa-f]{0,4}){2,7}")
+        .unwrap()
+});
+static US_SSN: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b(\d{3})-(\d{2})-(\d{4})\b").unwrap());
+static UK_NINO: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b[A-CEGHJ-PR-TW-Z][A-CEGHJ-NPR-TW-Z] ?\d{2} ?\d{2} ?\d{2} ?[A-D]\b").unwrap()
+});
+
+/// Returns whether the digits of the number pass the Luhn checksum.
+fn luhn(number: &str) -> bool {
+    let mut sum = 0;
+    let digits = number.bytes().rev().filter(u8::is_ascii_digit);
+    for (i, digit) in digits.enumerate() {
+        let mut digit = u32::from(digit - b'0');
+        if i % 2 == 1 {
+            digit *= 2;
+            if digit > 9 {
+                digit -= 9;
+            }
+        }
+        sum += digit;
+    }
+    sum % 10 == 0
+}
+
+#[derive(Clone, Debug)]
+struct Detector {
+    kind: DetectorKind,
+    regex: Regex,
+    action: RedactionAction,
+}
+
+impl Detector {
+    fn new(config: &DetectorConfig) -> crate::Result<Self> {
+        let regex = match &config.kind {
+            DetectorKind::Email => EMAIL.clone(),
+            DetectorKind::CreditCard => CREDIT_CARD.clone(),
+            DetectorKind::IpAddress => IP_ADDRESS.clone(),
+            DetectorKind::UsSsn => US_SSN.clone(),
+            DetectorKind::UkNino => UK_NINO.clone(),
+            DetectorKind::Regex { pattern } => Regex::new(pattern)
+                .map_err(|error| format!("Invalid detector pattern {:?}: {}", pattern, error))?,
+        };
+        Ok(Self {
+            kind: config.kind.clone(),
+            regex,
+            action: config.action,
+        })
+    }
+
+    const fn name(&self) -> &'static str {
+        match self.kind {
+            DetectorKind::Email => "email",
+            DetectorKind::CreditCard => "credit_card",
+            DetectorKind::IpAddress => "ip_address",
+            DetectorKind::UsSsn => "us_ssn",
+            DetectorKind::UkNino => "uk_nino",
+            DetectorKind::Regex { .. } => "regex",
+        }
+    }
+
+    /// Returns whether the match is PII, ruling out the matches failing a checksum or holding
+    /// reserved values.
+    fn is_pii(&self, captures: &Captures) -> bool {
+        let matched = &captures[0];
+        match self.kind {
+            DetectorKind::CreditCard => luhn(matched),
+            DetectorKind::IpAddress => {
+                matched.parse::<Ipv4Addr>().is_ok() || matched.parse::<Ipv6Addr>().is_ok()
+            }
+            DetectorKind::UsSsn => {
+                let area = &captures[1];
+                area != "000"
+                    && area != "666"
+                    && !area.starts_with('9')
+                    && &captures[2] != "00"
+                    && &captures[3] != "0000"
+            }
+            _ => true,
+        }
+    }
+}
+
+/// The outcome of the redaction of a string.
+enum Redaction {
+    Unchanged,
+    Redacted(String),
+    Removed,
+}
+
+#[derive(Clone)]
+pub struct RedactPii {
+    detectors: Vec<Detector>,
+    fields: Vec<String>,
+    mask: String,
+    hash: Option<Hmac<Sha256>>,
+}
+
+impl RedactPii {
+    pub fn new(config: &RedactPiiConfig) -> crate::Result<Self> {
+        let detectors = config
+            .detectors
+            .iter()
+            .map(Detector::new)
+            .collect::<crate::Result<Vec<_>>>()?;
+        let hash = match &config.hash_key {
+            Some(key) => Some(
+                Hmac::<Sha256>::new_from_slice(key.as_bytes())
+                    .map_err(|error| format!("Invalid hash key: {}", error))?,
+            ),
+            None if detectors
+                .iter()
+                .any(|detector| detector.action == RedactionAction::Hash) =>
+            {
+                return Err("The `hash` action requires `hash_key` to be set".into())
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            detectors,
+            fields: config.fields.clone(),
+            mask: config.mask.clone(),
+            hash,
+        })
+    }
+
+    fn replacement(&self, action: RedactionAction, pii: &str) -> String {
+        match (action, &self.hash) {
+            (RedactionAction::Hash, Some(hash)) => {
+                let mut hash = hash.clone();
+                hash.update(pii.as_bytes());
+                hex::encode(hash.finalize().into_bytes())
+            }
+            _ => self.mask.clone(),
+        }
+    }
+
+    /// Redacts the PII of the string with the detectors, in order.
+    fn redact_str(&self, string: &str) -> Redaction {
+        let mut redacted: Option<String> = None;
+        for detector in &self.detectors {
+            let current = redacted.as_deref().unwrap_or(string);
+            if detector.action == RedactionAction::Remove {
+                if detector
+                    .regex
+                    .captures_iter(current)
+                    .any(|captures| detector.is_pii(&captures))
+                {
+                    emit!(PiiRedacted {
+                        detector: detector.name(),
+                        count: 1,
+                    });
+                    return Redaction::Removed;
+                }
+                continue;
+            }
+
+            let mut count = 0;
+            let replaced = detector.regex.replace_all(current, |captures: &Captures| {
+                if detector.is_pii(captures) {
+                    count += 1;
+                    self.replacement(detector.action, &captures[0])
+                } else {
+                    captures[0].to_owned()
+                }
+            });
+            if count > 0 {
+                emit!(PiiRedacted {
+                    detector: detector.name(),
+                    count,
+                });
+                redacted = Some(replaced.into_owned());
+            }
+        }
+        redacted.map_or(Redaction::Unchanged, Redaction::Redacted)
+    }
+
+    /// Redacts the PII of the value and of the values nested in it, returning `false` if the
+    /// value must be removed.
+    fn redact(&self, value: &mut Value) -> bool {
+        match value {
+            Value::Bytes(bytes) => {
+                let redaction = self.redact_str(&String::from_utf8_lossy(bytes));
+                match redaction {
+                    Redaction::Unchanged => {}
+                    Redaction::Redacted(redacted) => *value = Value::from(redacted),
+                    Redaction::Removed => return false,
+                }
+            }
+            Value::Object(map) => map.retain(|_, value| self.redact(value)),
+            Value::Array(values) => values.retain_mut(|value| self.redact(value)),
+            _ => {}
+        }
+        true
+    }
+}
+
+impl FunctionTransform for RedactPii {
+    fn transform(&mut self, output: &mut OutputBuffer, mut event: Event) {
+        let log = event.as_mut_log();
+        if self.fields.is_empty() {
+            self.redact(log.value_mut());
+        } else {
+            for field in &self.fields {
+                if let Some(value) = log.get_mut(field.as_str()) {
+                    if !self.redact(value) {
+                        log.remove(field.as_str());
+                    }
+                }
+            }
+        }
+        output.push(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{event::LogEvent, transforms::test::transform_one};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<RedactPiiConfig>();
+    }
+
+    fn redact(config: &str, event: LogEvent) -> LogEvent {
+        let mut redact = RedactPii::new(&toml::from_str(config).unwrap()).unwrap();
+        transform_one(&mut redact, event.into()).unwrap().into_log()
+    }
+
+    #[test]
+    fn masks_builtin_detectors() {
+        let event = LogEvent::from(
+            "user jane.doe@example.com paid with 4111 1111 1111 1111 from 10.0.0.1 and fe80::1, \
+             ssn 123-45-6789, nino QQ 12 34 56 C, order 4111 1111 1111 1112, ref 000-12-3456",
+        );
+        let event = redact(
+            r#"
+[[detectors]]
+type = "email"
+[[detectors]]
+type = "credit_card"
+[[detectors]]
+type = "ip_address"
+[[detectors]]
+type = "us_ssn"
+[[detectors]]
+type = "uk_nino"
+"#,
+            event,
+        );
+        assert_eq!(
+            event["message"],
+            "user [REDACTED] paid with [REDACTED] from [REDACTED] and [REDACTED], ssn [REDACTED], \
+             nino [REDACTED], order 4111 1111 1111 1112, ref 000-12-3456"
+                .into()
+        );
+    }
+
+    #[test]
+    fn redacts_nested_fields() {
+        let mut event = LogEvent::default();
+        event.insert("user.email", "jane.doe@example.com");
+        event.insert("user.card", "4111-1111-1111-1111");
+        event.insert("user.emails[0]", "john@example.org");
+        event.insert("user.emails[1]", "none");
+        event.insert("other", "jane.doe@example.com");
+
+        let event = redact(
+            r#"
+fields = ["user"]
+hash_key = "secret"
+
+[[detectors]]
+type = "email"
+action = "hash"
+
+[[detectors]]
+type = "credit_card"
+action = "remove"
+"#,
+            event,
+        );
+
+        let hash = event["user.email"].to_string_lossy();
+        assert_eq!(hash.len(), 64);
+        assert_ne!(hash, "jane.doe@example.com");
+        assert_ne!(event["user.emails[0]"].to_string_lossy(), hash);
+        assert_eq!(event["user.emails[1]"], "none".into());
+        assert!(!event.contains("user.card"));
+        assert_eq!(event["other"], "jane.doe@example.com".into());
+    }
+
+    #[test]
+    fn hashes_consistently() {
+        let config = r#"
+hash_key = "secret"
+
+[[detectors]]
+type = "regex"
+pattern = 'user-\d+'
+action = "hash"
+"#;
+        let first = redact(config, LogEvent::from("login user-42"));
+        let second = redact(config, LogEvent::from("logout user-42"));
+        let first = first["message"].to_string_lossy();
+        let second = second["message"].to_string_lossy();
+        assert_eq!(first.strip_prefix("login "), second.strip_prefix("logout "));
+        assert!(!first.contains("user-42"));
+    }
+
+    #[test]
+    fn removes_fields_with_pii() {
+        let mut event = LogEvent::from("contact jane.doe@example.com");
+        event.insert("host", "localhost");
+
+        let event = redact(
+            r#"
+[[detectors]]
+type = "email"
+action = "remove"
+"#,
+            event,
+        );
+        assert!(!event.contains("message"));
+        assert_eq!(event["host"], "localhost".into());
+    }
+
+    #[test]
+    fn requires_hash_key() {
+        let config = toml::from_str::<RedactPiiConfig>(
+            r#"
+[[detectors]]
+type = "email"
+action = "hash"
+"#,
+        )
+        .unwrap();
+        assert!(RedactPii::new(&config).is_err());
+    }
+
+    #[test]
+    fn luhn_checksum() {
+        assert!(luhn("4111111111111111"));
+        assert!(luhn("5500-0000-0000-0004"));
+        assert!(!luhn("4111111111111112"));
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::BTreeMap;
+
+use vector_common::TimeZone;
+use vector_core::compile_vrl;
+use vrl::{diagnostic::Formatter, state::LocalEnv, Program, Runtime};
+
+use crate::event::{Event, LogEvent, TargetEvents, Value, VrlTarget};
+
+/// A VRL program merging each event into the accumulated event.
+///
+/// The program is run with the accumulated event as the `accumulator` field of the target, and
+/// the incoming event as its `event` field. The `accumulator` field is the accumulated event once
+/// the program has run.
+#[derive(Debug)]
+pub struct MergeProgram {
+    program: Program,
+    runtime: Runtime,
+    timezone: TimeZone,
+}
+
+impl MergeProgram {
+    pub fn new(source: &str, enrichment_tables: &enrichment::TableRegistry) -> crate::Result<Self> {
+        let functions = vrl_stdlib::all()
+            .into_iter()
+            .chain(enrichment::vrl_functions().into_iter())
+            .chain(vector_vrl_functions::vrl_functions())
+            .collect::<Vec<_>>();
+
+        let mut state = vrl::state::ExternalEnv::default();
+        state.set_external_context(enrichment_tables.clone());
+
+        let (program, warnings) = compile_vrl(source, &functions, &mut state, LocalEnv::default())
+            .map_err(|diagnostics| Formatter::new(source, diagnostics).colored().to_string())?;
+
+        if !warnings.is_empty() {
+            let warnings = Formatter::new(source, warnings).colored().to_string();
+            warn!(message = "VRL compilation warning.", %warnings);
+        }
+
+        Ok(Self {
+            program,
+            runtime: Runtime::default(),
+            timezone: TimeZone::default(),
+        })
+    }
+
+    /// Returns the accumulated event with the event merged into it.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the program fails, or if the `accumulator` it leaves isn't an object.
+    pub fn merge(&mut self, accumulator: Value, event: Value) -> Result<Value, String> {
+        let target = LogEvent::from(BTreeMap::from([
+            ("accumulator".to_owned(), accumulator),
+            ("event".to_owned(), event),
+        ]));
+        let mut target = VrlTarget::new(Event::from(target), self.program.info());
+        let result = self
+            .runtime
+            .resolve(&mut target, &self.program, &self.timezone);
+        self.runtime.clear();
+        result.map_err(|error| error.to_string())?;
+
+        match target.into_events() {
+            TargetEvents::One(event) => match event.into_log().remove("accumulator") {
+                Some(accumulator @ Value::Object(_)) => Ok(accumulator),
+                _ => Err("`.accumulator` must be an object".to_owned()),
+            },
+            _ => Err("the merge program must not replace the target".to_owned()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_events() {
+        let mut program = MergeProgram::new(
+            ".accumulator.count = (int(.accumulator.count) ?? 0) + int!(.event.count)",
+            &enrichment::TableRegistry::default(),
+        )
+        .unwrap();
+
+        let accumulator = program
+            .merge(Value::Object(BTreeMap::new()), btreemap_value(2))
+            .unwrap();
+        let accumulator = program.merge(accumulator, btreemap_value(3)).unwrap();
+        assert_eq!(accumulator, btreemap_value(5));
+    }
+
+    #[test]
+    fn rejects_invalid_accumulators() {
+        let mut program =
+            MergeProgram::new(".accumulator = 1", &enrichment::TableRegistry::default()).unwrap();
+
+        assert_eq!(
+            program.merge(Value::Object(BTreeMap::new()), btreemap_value(1)),
+            Err("`.accumulator` must be an object".to_owned())
+        );
+    }
+
+    fn btreemap_value(count: i64) -> Value {
+        Value::Object(BTreeMap::from([("count".to_owned(), Value::from(count))]))
+    }
+}
@@ -14,14 +14,16 @@ use crate::{
     conditions::{AnyCondition, Condition},
     config::{DataType, Input, Output, TransformConfig, TransformContext, TransformDescription},
     event::{discriminant::Discriminant, Event, EventMetadata, LogEvent},
-    internal_events::ReduceStaleEventFlushed,
+    internal_events::{ReduceMergeError, ReduceStaleEventFlushed},
     schema,
     transforms::{TaskTransform, Transform},
 };
 
+mod merge_program;
 mod merge_strategy;
 
 use crate::event::Value;
+use merge_program::MergeProgram;
 pub use merge_strategy::*;
 
 /// Configuration for the `reduce` transform.
@@ -57,6 +59,14 @@ pub struct ReduceConfig {
     #[serde(default)]
     pub merge_strategies: IndexMap<String, MergeStrategy>,
 
+    /// A [Vector Remap Language](https://vector.dev/docs/reference/vrl) (VRL) program which merges each event into the
+    /// combined event, instead of the merge strategies.
+    ///
+    /// The program is run for each event, with the combined event as the `.accumulator` field and the event as the
+    /// `.event` field. The `.accumulator` field, which is initially an empty object, must remain an object and becomes
+    /// the combined event. Events for which the program fails are discarded.
+    pub merge: Option<String>,
+
     /// A condition used to distinguish the final event of a transaction.
     ///
     /// If this condition resolves to `true` for an event, the current transaction is immediately flushed with this event.
@@ -94,15 +104,38 @@ impl TransformConfig for ReduceConfig {
     }
 }
 
+/// How events are merged into the combined event.
+#[derive(Debug)]
+enum Merge {
+    Strategies(IndexMap<String, MergeStrategy>),
+    Program(MergeProgram),
+}
+
 #[derive(Debug)]
 struct ReduceState {
     fields: HashMap<String, Box<dyn ReduceValueMerger>>,
+    /// The combined event when the events are merged by a program, which replaces the fields.
+    accumulator: Option<Value>,
     stale_since: Instant,
     metadata: EventMetadata,
 }
 
 impl ReduceState {
-    fn new(e: LogEvent, strategies: &IndexMap<String, MergeStrategy>) -> Self {
+    fn new(e: LogEvent, merge: &mut Merge) -> Self {
+        let strategies = match merge {
+            Merge::Strategies(strategies) => strategies,
+            Merge::Program(program) => {
+                let (value, metadata) = e.into_parts();
+                let mut state = Self {
+                    fields: HashMap::new(),
+                    accumulator: Some(Value::Object(BTreeMap::new())),
+                    stale_since: Instant::now(),
+                    metadata,
+                };
+                state.merge_value(value, program);
+                return state;
+            }
+        };
         let (value, metadata) = e.into_parts();
 
         let fields = if let Value::Object(fields) = value {
@@ -129,14 +162,24 @@ impl ReduceState {
         Self {
             stale_since: Instant::now(),
             fields,
+            accumulator: None,
             metadata,
         }
     }
 
-    fn add_event(&mut self, e: LogEvent, strategies: &IndexMap<String, MergeStrategy>) {
+    fn add_event(&mut self, e: LogEvent, merge: &mut Merge) {
         let (value, metadata) = e.into_parts();
         self.metadata.merge(metadata);
 
+        let strategies = match merge {
+            Merge::Strategies(strategies) => strategies,
+            Merge::Program(program) => {
+                self.merge_value(value, program);
+                self.stale_since = Instant::now();
+                return;
+            }
+        };
+
         let fields = if let Value::Object(fields) = value {
             fields
         } else {
@@ -170,7 +213,25 @@ impl ReduceState {
         self.stale_since = Instant::now();
     }
 
+    fn merge_value(&mut self, value: Value, program: &mut MergeProgram) {
+        let accumulator = self
+            .accumulator
+            .take()
+            .unwrap_or_else(|| Value::Object(BTreeMap::new()));
+        self.accumulator = Some(match program.merge(accumulator.clone(), value) {
+            Ok(accumulator) => accumulator,
+            Err(error) => {
+                emit!(ReduceMergeError { error });
+                accumulator
+            }
+        });
+    }
+
     fn flush(mut self) -> LogEvent {
+        if let Some(accumulator) = self.accumulator {
+            return LogEvent::from_parts(accumulator, self.metadata);
+        }
+
         let mut event = LogEvent::new_with_metadata(self.metadata);
         for (k, v) in self.fields.drain() {
             if let Err(error) = v.insert_into(k, &mut event) {
@@ -185,7 +246,7 @@ pub struct Reduce {
     expire_after: Duration,
     flush_period: Duration,
     group_by: Vec<String>,
-    merge_strategies: IndexMap<String, MergeStrategy>,
+    merge: Merge,
     reduce_merge_states: HashMap<Discriminant, ReduceState>,
     ends_when: Option<Condition>,
     starts_when: Option<Condition>,
@@ -211,12 +272,19 @@ impl Reduce {
             .map(|c| c.build(enrichment_tables))
             .transpose()?;
         let group_by = config.group_by.clone().into_iter().collect();
+        let merge = match &config.merge {
+            Some(_) if !config.merge_strategies.is_empty() => {
+                return Err("only one of `merge` and `merge_strategies` can be provided".into())
+            }
+            Some(source) => Merge::Program(MergeProgram::new(source, enrichment_tables)?),
+            None => Merge::Strategies(config.merge_strategies.clone()),
+        };
 
         Ok(Reduce {
             expire_after: Duration::from_millis(config.expire_after_ms.unwrap_or(30000)),
             flush_period: Duration::from_millis(config.flush_period_ms.unwrap_or(1000)),
             group_by,
-            merge_strategies: config.merge_strategies.clone(),
+            merge,
             reduce_merge_states: HashMap::new(),
             ends_when,
             starts_when,
@@ -247,10 +315,10 @@ impl Reduce {
     fn push_or_new_reduce_state(&mut self, event: LogEvent, discriminant: Discriminant) {
         match self.reduce_merge_states.entry(discriminant) {
             hash_map::Entry::Vacant(entry) => {
-                entry.insert(ReduceState::new(event, &self.merge_strategies));
+                entry.insert(ReduceState::new(event, &mut self.merge));
             }
             hash_map::Entry::Occupied(mut entry) => {
-                entry.get_mut().add_event(event, &self.merge_strategies);
+                entry.get_mut().add_event(event, &mut self.merge);
             }
         }
     }
@@ -278,12 +346,10 @@ impl Reduce {
         } else if ends_here {
             output.push(match self.reduce_merge_states.remove(&discriminant) {
                 Some(mut state) => {
-                    state.add_event(event, &self.merge_strategies);
+                    state.add_event(event, &mut self.merge);
                     state.flush().into()
                 }
-                None => ReduceState::new(event, &self.merge_strategies)
-                    .flush()
-                    .into(),
+                None => ReduceState::new(event, &mut self.merge).flush().into(),
             })
         } else {
             self.push_or_new_reduce_state(event, discriminant)
@@ -341,6 +407,7 @@ impl TaskTransform<Event> for Reduce {
 #[cfg(test)]
 mod test {
     use serde_json::json;
+    use vector_common::btreemap;
 
     use super::*;
     use crate::{
@@ -467,6 +534,81 @@ merge_strategies.baz = "max"
         assert_eq!(output_1.metadata(), &metadata);
     }
 
+    #[tokio::test]
+    async fn reduce_merge_program() {
+        let reduce = toml::from_str::<ReduceConfig>(
+            r#"
+group_by = [ "session_id" ]
+merge = """
+.accumulator.session_id = .event.session_id
+.accumulator.pages = push(array(.accumulator.pages) ?? [], .event.page)
+.accumulator.duration = (int(.accumulator.duration) ?? 0) + int!(.event.duration)
+"""
+
+[ends_when]
+  type = "check_fields"
+  "logout.exists" = true
+"#,
+        )
+        .unwrap()
+        .build(&TransformContext::default())
+        .await
+        .unwrap();
+        let reduce = reduce.into_task();
+
+        let mut e_1 = LogEvent::from("test message 1");
+        e_1.insert("session_id", "1");
+        e_1.insert("page", "/home");
+        e_1.insert("duration", 3);
+        let metadata = e_1.metadata().clone();
+
+        let mut e_2 = LogEvent::from("test message 2");
+        e_2.insert("session_id", "1");
+        e_2.insert("page", "/cart");
+        e_2.insert("duration", "not a number");
+
+        let mut e_3 = LogEvent::from("test message 3");
+        e_3.insert("session_id", "1");
+        e_3.insert("page", "/checkout");
+        e_3.insert("duration", 4);
+        e_3.insert("logout", true);
+
+        let inputs = vec![e_1.into(), e_2.into(), e_3.into()];
+        let in_stream = Box::pin(stream::iter(inputs));
+        let mut out_stream = reduce.transform_events(in_stream);
+
+        // The event for which the program failed isn't merged.
+        let output_1 = out_stream.next().await.unwrap().into_log();
+        assert_eq!(
+            output_1.value(),
+            &Value::from(btreemap! {
+                "session_id" => "1",
+                "pages" => Value::Array(vec!["/home".into(), "/checkout".into()]),
+                "duration" => 7,
+            })
+        );
+        assert_eq!(output_1.metadata(), &metadata);
+    }
+
+    #[test]
+    fn merge_and_merge_strategies_are_exclusive() {
+        let config = toml::from_str::<ReduceConfig>(
+            r#"
+merge = ".accumulator = .event"
+merge_strategies.foo = "concat"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            Reduce::new(&config, &enrichment::TableRegistry::default())
+                .err()
+                .unwrap()
+                .to_string(),
+            "only one of `merge` and `merge_strategies` can be provided"
+        );
+    }
+
     #[tokio::test]
     async fn missing_group_by() {
         let reduce = toml::from_str::<ReduceConfig>(
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, HashMap},
     fs::File,
     io::{self, Read},
     path::PathBuf,
@@ -16,7 +16,11 @@ use vector_core::compile_vrl;
 use vector_core::config::LogNamespace;
 use vector_core::schema::Definition;
 
-use vector_vrl_functions::set_semantic_meaning::MeaningList;
+use vector_vrl_functions::{
+    route::{RouteOutputs, ROUTE_METADATA_KEY},
+    set_semantic_meaning::MeaningList,
+    state_store::StateStore,
+};
 use vrl::state::LocalEnv;
 use vrl::{
     diagnostic::{Formatter, Note},
@@ -99,6 +103,12 @@ pub struct RemapConfig {
     /// the event was dropped.
     pub reroute_dropped: bool,
 
+    /// The names of additional outputs that events can be sent to with the `route` function.
+    ///
+    /// Events that are not routed by the VRL program are sent to the default output. The `dropped` output is reserved
+    /// when `reroute_dropped` is set.
+    pub named_outputs: Vec<String>,
+
     #[configurable(derived)]
     #[serde(default)]
     pub runtime: VrlRuntime,
@@ -108,6 +118,7 @@ impl RemapConfig {
     fn compile_vrl_program(
         &self,
         enrichment_tables: enrichment::TableRegistry,
+        state_store: StateStore,
         merged_schema_definition: schema::Definition,
     ) -> Result<(
         vrl::Program,
@@ -140,6 +151,8 @@ impl RemapConfig {
         );
         state.set_external_context(enrichment_tables);
         state.set_external_context(MeaningList::default());
+        state.set_external_context(state_store);
+        state.set_external_context(RouteOutputs(self.named_outputs.clone()));
 
         compile_vrl(&source, &functions, &mut state, LocalEnv::default())
             .map_err(|diagnostics| {
@@ -198,6 +211,7 @@ impl TransformConfig for RemapConfig {
         let default_definition = self
             .compile_vrl_program(
                 enrichment::TableRegistry::default(),
+                StateStore::default(),
                 input_definition.clone(),
             )
             .map(|(_, _, _, state)| {
@@ -261,19 +275,27 @@ impl TransformConfig for RemapConfig {
             );
         }
 
-        let default_output =
-            Output::default(DataType::all()).with_schema_definition(default_definition);
+        let mut outputs = vec![
+            Output::default(DataType::all()).with_schema_definition(default_definition.clone())
+        ];
+
+        for name in &self.named_outputs {
+            outputs.push(
+                Output::default(DataType::all())
+                    .with_schema_definition(default_definition.clone())
+                    .with_port(name.clone()),
+            );
+        }
 
         if self.reroute_dropped {
-            vec![
-                default_output,
+            outputs.push(
                 Output::default(DataType::all())
                     .with_schema_definition(dropped_definition)
                     .with_port(DROPPED),
-            ]
-        } else {
-            vec![default_output]
+            );
         }
+
+        outputs
     }
 
     fn transform_type(&self) -> &'static str {
@@ -298,6 +320,7 @@ where
     reroute_dropped: bool,
     default_schema_definition: Arc<schema::Definition>,
     dropped_schema_definition: Arc<schema::Definition>,
+    named_schema_definitions: HashMap<String, Arc<schema::Definition>>,
     runner: Runner,
 }
 
@@ -341,8 +364,19 @@ impl Remap<AstRunner> {
         config: RemapConfig,
         context: &TransformContext,
     ) -> crate::Result<(Self, String)> {
+        // The state of the `get_state` and `set_state` functions is only persisted when there is
+        // a data directory to persist it in.
+        let state_store = match (&context.globals.data_dir, &context.key) {
+            (Some(data_dir), Some(key)) => {
+                let path = data_dir.join(key.id()).join("vrl_state.json");
+                StateStore::open(&path).with_context(|_| StateStoreOpenFailedSnafu { path })?
+            }
+            _ => StateStore::default(),
+        };
+
         let (program, warnings, _, _) = config.compile_vrl_program(
             context.enrichment_tables.clone(),
+            state_store,
             context.merged_schema_definition.clone(),
         )?;
 
@@ -376,6 +410,30 @@ where
             .expect("dropped schema required")
             .clone();
 
+        let mut named_schema_definitions = HashMap::new();
+        for name in &config.named_outputs {
+            if config.reroute_dropped && name == DROPPED {
+                return Err(Box::new(BuildError::ReservedNamedOutput {
+                    output: name.clone(),
+                }));
+            }
+
+            let schema_definition = context
+                .schema_definitions
+                .get(&Some(name.clone()))
+                .or_else(|| context.schema_definitions.get(&None))
+                .expect("named output schema required")
+                .clone();
+            if named_schema_definitions
+                .insert(name.clone(), Arc::new(schema_definition))
+                .is_some()
+            {
+                return Err(Box::new(BuildError::DuplicateNamedOutput {
+                    output: name.clone(),
+                }));
+            }
+        }
+
         Ok(Remap {
             component_key: context.key.clone(),
             program,
@@ -385,6 +443,7 @@ where
             reroute_dropped: config.reroute_dropped,
             default_schema_definition: Arc::new(default_schema_definition),
             dropped_schema_definition: Arc::new(dropped_schema_definition),
+            named_schema_definitions,
             runner,
         })
     }
@@ -448,6 +507,30 @@ where
         }
     }
 
+    /// Sends the event to the output it was routed to by the VRL program, if any.
+    fn push_routed(&self, mut event: Event, output: &mut TransformOutputsBuf) {
+        if !self.named_schema_definitions.is_empty() {
+            let route = event
+                .metadata_mut()
+                .value_mut()
+                .remove(path!(ROUTE_METADATA_KEY), false);
+            if let Some(value::Value::Bytes(name)) = route {
+                let name = String::from_utf8_lossy(&name);
+                if let Some((name, schema_definition)) =
+                    self.named_schema_definitions.get_key_value(name.as_ref())
+                {
+                    event
+                        .metadata_mut()
+                        .set_schema_definition(schema_definition);
+                    output.push_named(name, event);
+                    return;
+                }
+            }
+        }
+
+        push_default(event, output, &self.default_schema_definition)
+    }
+
     fn run_vrl(&mut self, target: &mut VrlTarget) -> std::result::Result<value::Value, Terminate> {
         self.runner.run(target, &self.program, &self.timezone)
     }
@@ -483,13 +566,13 @@ where
 
         match result {
             Ok(_) => match target.into_events() {
-                TargetEvents::One(event) => {
-                    push_default(event, output, &self.default_schema_definition)
+                TargetEvents::One(event) => self.push_routed(event, output),
+                TargetEvents::Logs(events) => {
+                    events.for_each(|event| self.push_routed(event, output))
+                }
+                TargetEvents::Traces(events) => {
+                    events.for_each(|event| self.push_routed(event, output))
                 }
-                TargetEvents::Logs(events) => events
-                    .for_each(|event| push_default(event, output, &self.default_schema_definition)),
-                TargetEvents::Traces(events) => events
-                    .for_each(|event| push_default(event, output, &self.default_schema_definition)),
             },
             Err(reason) => {
                 let (reason, error, drop) = match reason {
@@ -560,6 +643,12 @@ pub enum BuildError {
     FileOpenFailed { path: PathBuf, source: io::Error },
     #[snafu(display("Could not read vrl program {:?}: {}", path, source))]
     FileReadFailed { path: PathBuf, source: io::Error },
+    #[snafu(display("Could not open vrl state {:?}: {}", path, source))]
+    StateStoreOpenFailed { path: PathBuf, source: io::Error },
+    #[snafu(display("named output {:?} is reserved for rerouting dropped events", output))]
+    ReservedNamedOutput { output: String },
+    #[snafu(display("named output {:?} is declared more than once", output))]
+    DuplicateNamedOutput { output: String },
 }
 
 #[cfg(test)]
@@ -688,6 +777,45 @@ mod tests {
         assert!(tform.runner().runtime.is_empty());
     }
 
+    #[test]
+    fn check_remap_persists_state() {
+        let data_dir = crate::test_util::temp_dir();
+        let conf = RemapConfig {
+            source: Some(
+                r#".count = (int(get_state("count")) ?? 0) + 1
+  set_state("count", .count)
+"#
+                .to_string(),
+            ),
+            ..Default::default()
+        };
+        let build = || {
+            let mut context = TransformContext::new_test(HashMap::from([
+                (None, test_default_schema_definition()),
+                (Some(DROPPED.to_owned()), test_dropped_schema_definition()),
+            ]));
+            context.key = Some(ComponentKey::from("remap"));
+            context.globals.data_dir = Some(data_dir.clone());
+            Remap::new_ast(conf.clone(), &context).unwrap().0
+        };
+
+        let mut tform = build();
+        for count in 1..=2 {
+            let result = transform_one(&mut tform, Event::from(LogEvent::from("event"))).unwrap();
+            assert_eq!(result.as_log().get("count"), Some(&Value::from(count)));
+        }
+
+        // The state is written to the data directory when the transform is dropped.
+        drop(tform);
+        assert!(data_dir.join("remap").join("vrl_state.json").exists());
+
+        let mut tform = build();
+        let result = transform_one(&mut tform, Event::from(LogEvent::from("event"))).unwrap();
+        assert_eq!(result.as_log().get("count"), Some(&Value::from(3)));
+
+        std::fs::remove_dir_all(data_dir).unwrap();
+    }
+
     #[test]
     fn check_remap_adds() {
         let event = {
@@ -1297,6 +1425,97 @@ mod tests {
         assert!(out.named[DROPPED].is_empty());
     }
 
+    #[test]
+    fn check_remap_named_outputs() {
+        let conf = RemapConfig {
+            source: Some(formatdoc! {r#"
+                if .level == "error" {{
+                    route("errors")
+                }} else if exists(.output) {{
+                    route(string!(.output))
+                }}
+            "#}),
+            named_outputs: vec!["errors".to_owned(), "audit".to_owned()],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            conf.outputs(&schema::Definition::empty_legacy_namespace())
+                .into_iter()
+                .map(|output| output.port)
+                .collect::<Vec<_>>(),
+            vec![None, Some("errors".to_owned()), Some("audit".to_owned())]
+        );
+
+        let mut tform = remap(conf).unwrap();
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            vec![
+                Output::default(DataType::all()),
+                Output::default(DataType::all()).with_port("errors"),
+                Output::default(DataType::all()).with_port("audit"),
+            ],
+            1,
+        );
+        for event in [
+            serde_json::json!({"level": "error"}),
+            serde_json::json!({"level": "info", "output": "audit"}),
+            serde_json::json!({"level": "info"}),
+        ] {
+            tform.transform(Event::try_from(event).unwrap(), &mut outputs);
+        }
+
+        let errors = outputs.drain_named("errors").collect::<Vec<_>>();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].as_log()["level"], "error".into());
+        // The route is removed from the metadata of the event once it is sent to its output.
+        assert_eq!(
+            errors[0].metadata().value().get(path!(ROUTE_METADATA_KEY)),
+            None
+        );
+
+        let audit = outputs.drain_named("audit").collect::<Vec<_>>();
+        assert_eq!(audit.len(), 1);
+        assert_eq!(audit[0].as_log()["output"], "audit".into());
+
+        let default = outputs.drain().collect::<Vec<_>>();
+        assert_eq!(default.len(), 1);
+        assert_eq!(default[0].as_log()["level"], "info".into());
+        assert!(!default[0].as_log().contains("output"));
+    }
+
+    #[test]
+    fn check_remap_named_outputs_validation() {
+        let unknown = RemapConfig {
+            source: Some(r#"route("errors")"#.to_owned()),
+            ..Default::default()
+        };
+        assert!(remap(unknown)
+            .unwrap_err()
+            .to_string()
+            .contains(r#"unknown output "errors""#));
+
+        let reserved = RemapConfig {
+            source: Some(".".to_owned()),
+            named_outputs: vec![DROPPED.to_owned()],
+            reroute_dropped: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            remap(reserved).unwrap_err().to_string(),
+            r#"named output "dropped" is reserved for rerouting dropped events"#
+        );
+
+        let duplicate = RemapConfig {
+            source: Some(".".to_owned()),
+            named_outputs: vec!["errors".to_owned(), "errors".to_owned()],
+            ..Default::default()
+        };
+        assert_eq!(
+            remap(duplicate).unwrap_err().to_string(),
+            r#"named output "errors" is declared more than once"#
+        );
+    }
+
     #[tokio::test]
     async fn check_remap_branching_metrics_with_output() {
         init_test();
@@ -1,4 +1,5 @@
 use indexmap::IndexMap;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use vector_config::configurable_component;
 use vector_core::transform::SyncTransform;
@@ -19,6 +20,7 @@ pub(crate) const UNMATCHED_ROUTE: &str = "_unmatched";
 #[derive(Clone)]
 pub struct Route {
     conditions: Vec<(String, Condition)>,
+    split: Option<Split>,
 }
 
 impl Route {
@@ -28,7 +30,54 @@ impl Route {
             let condition = condition.build(&context.enrichment_tables)?;
             conditions.push((output_name.clone(), condition));
         }
-        Ok(Self { conditions })
+        let split = config.split.as_ref().map(Split::new);
+        Ok(Self { conditions, split })
+    }
+}
+
+/// Chooses one of the weighted outputs for each event.
+#[derive(Clone)]
+struct Split {
+    /// The weighted outputs, with the sum of the weights up to and including each output.
+    outputs: Vec<(String, u64)>,
+    total_weight: u64,
+    key_field: Option<String>,
+}
+
+impl Split {
+    fn new(config: &SplitConfig) -> Self {
+        let mut total_weight = 0;
+        let outputs = config
+            .weights
+            .iter()
+            .map(|(output_name, weight)| {
+                total_weight += u64::from(*weight);
+                (output_name.clone(), total_weight)
+            })
+            .collect();
+        Self {
+            outputs,
+            total_weight,
+            key_field: config.key_field.clone(),
+        }
+    }
+
+    fn output(&self, event: &Event) -> &str {
+        let key = self.key_field.as_ref().and_then(|key_field| match event {
+            Event::Log(log) => log.get(key_field.as_str()).map(|v| v.to_string_lossy()),
+            Event::Trace(trace) => trace.get(key_field.as_str()).map(|v| v.to_string_lossy()),
+            Event::Metric(metric) => metric.tag_value(key_field),
+        });
+
+        // Events with the same key always go to the same output, as long as the weights don't change.
+        let position = match key {
+            Some(key) => seahash::hash(key.as_bytes()) % self.total_weight,
+            None => rand::thread_rng().gen_range(0..self.total_weight),
+        };
+        let index = self
+            .outputs
+            .partition_point(|(_, cumulative_weight)| *cumulative_weight <= position);
+        &self.outputs[index].0
     }
 }
 
@@ -47,7 +96,9 @@ impl SyncTransform for Route {
                 check_failed += 1;
             }
         }
-        if check_failed == self.conditions.len() {
+        if let Some(split) = &self.split {
+            output.push_named(split.output(&event), event);
+        } else if check_failed == self.conditions.len() {
             output.push_named(UNMATCHED_ROUTE, event);
         }
     }
@@ -64,14 +115,38 @@ pub struct RouteConfig {
     /// an event doesn’t match any route, it will be sent to the `<transform_name>._unmatched` output.
     ///
     /// Both `_unmatched`, as well as `_default`, are reserved output names and cannot be used as a route name.
-    #[serde(alias = "lanes")]
+    #[serde(alias = "lanes", default)]
     route: IndexMap<String, AnyCondition>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    split: Option<SplitConfig>,
+}
+
+/// Splits the events between weighted outputs.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SplitConfig {
+    /// A table of output names to their weights.
+    ///
+    /// Each event is sent to exactly one of these outputs, which is chosen with a probability proportional to its
+    /// weight. For example, weights of `95` and `5` send 95% of the events to the first output, and 5% to the second
+    /// one. Events sent to one of these outputs are never sent to the `<transform_name>._unmatched` output.
+    weights: IndexMap<String, u32>,
+
+    /// The name of the field whose value is hashed to choose the output of the event.
+    ///
+    /// Events with the same value are consistently sent to the same output, as long as the weights don't change. For
+    /// metrics, this is the name of a tag. If left unspecified, or if the event doesn’t have `key_field`, the output is
+    /// chosen randomly.
+    key_field: Option<String>,
 }
 
 #[cfg(feature = "transforms-pipelines")]
 impl RouteConfig {
     pub(crate) const fn new(route: IndexMap<String, AnyCondition>) -> Self {
-        Self { route }
+        Self { route, split: None }
     }
 }
 
@@ -87,6 +162,7 @@ impl GenerateConfig for RouteConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
             route: IndexMap::new(),
+            split: None,
         })
         .unwrap()
     }
@@ -105,19 +181,47 @@ impl TransformConfig for RouteConfig {
     }
 
     fn validate(&self, _: &schema::Definition) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
         if self.route.contains_key(UNMATCHED_ROUTE) {
-            Err(vec![format!(
+            errors.push(format!(
                 "cannot have a named output with reserved name: `{UNMATCHED_ROUTE}`"
-            )])
-        } else {
+            ));
+        }
+        if let Some(split) = &self.split {
+            if split.weights.is_empty() {
+                errors.push("`split.weights` must contain at least one output".to_owned());
+            }
+            for (output_name, weight) in &split.weights {
+                if output_name == UNMATCHED_ROUTE {
+                    errors.push(format!(
+                        "cannot have a named output with reserved name: `{UNMATCHED_ROUTE}`"
+                    ));
+                } else if self.route.contains_key(output_name) {
+                    errors.push(format!(
+                        "`{output_name}` cannot be both a route and a weighted output"
+                    ));
+                }
+                if *weight == 0 {
+                    errors.push(format!(
+                        "the weight of the `{output_name}` output must be greater than zero"
+                    ));
+                }
+            }
+        }
+
+        if errors.is_empty() {
             Ok(())
+        } else {
+            Err(errors)
         }
     }
 
     fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        let split_outputs = self.split.iter().flat_map(|split| split.weights.keys());
         let mut result: Vec<Output> = self
             .route
             .keys()
+            .chain(split_outputs)
             .map(|output_name| Output::default(DataType::all()).with_port(output_name))
             .collect();
         result.push(Output::default(DataType::all()).with_port(UNMATCHED_ROUTE));
@@ -344,6 +448,96 @@ mod test {
         }
     }
 
+    fn split_outputs(config: &RouteConfig) -> TransformOutputsBuf {
+        TransformOutputsBuf::new_with_capacity(config.outputs(&schema::Definition::any()), 1)
+    }
+
+    #[test]
+    fn route_split_by_key_field() {
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            route.errors.type = "vrl"
+            route.errors.source = '.level == "error"'
+
+            split.key_field = "user"
+            split.weights.primary = 95
+            split.weights.canary = 5
+        "#,
+        )
+        .unwrap();
+        let mut transform = Route::new(&config, &Default::default()).unwrap();
+        let mut outputs = split_outputs(&config);
+
+        for user in 0..1000 {
+            for _ in 0..3 {
+                let event = Event::try_from(
+                    serde_json::json!({"user": format!("user{}", user), "level": "info"}),
+                )
+                .unwrap();
+                transform.transform(event, &mut outputs);
+            }
+        }
+
+        let primary: Vec<_> = outputs.drain_named("primary").collect();
+        let canary: Vec<_> = outputs.drain_named("canary").collect();
+        assert_eq!(primary.len() + canary.len(), 3000);
+        assert!(canary.len() > 60 && canary.len() < 300, "{}", canary.len());
+        // The events of each user all go to the same output.
+        for event in &canary {
+            let user = &event.as_log()["user"];
+            assert!(!primary.iter().any(|event| &event.as_log()["user"] == user));
+        }
+        assert_eq!(outputs.drain_named("errors").count(), 0);
+        assert_eq!(outputs.drain_named(UNMATCHED_ROUTE).count(), 0);
+    }
+
+    #[test]
+    fn route_split_randomly() {
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            split.weights.primary = 1
+            split.weights.canary = 1
+            split.key_field = "user"
+        "#,
+        )
+        .unwrap();
+        let mut transform = Route::new(&config, &Default::default()).unwrap();
+        let mut outputs = split_outputs(&config);
+
+        for _ in 0..1000 {
+            let event = Event::try_from(serde_json::json!({"message": "no user"})).unwrap();
+            transform.transform(event, &mut outputs);
+        }
+
+        let primary = outputs.drain_named("primary").count();
+        let canary = outputs.drain_named("canary").count();
+        assert_eq!(primary + canary, 1000);
+        assert!(primary > 350 && canary > 350, "{} {}", primary, canary);
+    }
+
+    #[test]
+    fn route_split_validation() {
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            route.primary.type = "is_log"
+
+            split.weights.primary = 1
+            split.weights._unmatched = 1
+            split.weights.canary = 0
+        "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.validate(&schema::Definition::any()),
+            Err(vec![
+                "`primary` cannot be both a route and a weighted output".to_owned(),
+                "cannot have a named output with reserved name: `_unmatched`".to_owned(),
+                "the weight of the `canary` output must be greater than zero".to_owned(),
+            ])
+        );
+    }
+
     #[tokio::test]
     async fn route_metrics_with_output_tag() {
         init_test();
@@ -0,0 +1,285 @@
+use std::{path::PathBuf, sync::Arc};
+
+use jsonschema::JSONSchema;
+use vector_config::configurable_component;
+use vector_core::transform::SyncTransform;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
+        TransformDescription,
+    },
+    event::{Event, Value},
+    internal_events::SchemaGuardValidationFailed,
+    schema,
+    transforms::{Transform, TransformOutputsBuf},
+};
+
+pub(crate) const INVALID_OUTPUT: &str = "invalid";
+
+/// Configuration for the `schema_guard` transform.
+#[configurable_component(transform)]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SchemaGuardConfig {
+    /// The [JSON Schema][json_schema] the events must conform to, as a JSON document.
+    ///
+    /// Either `schema` or `schema_file` must be set.
+    ///
+    /// [json_schema]: https://json-schema.org/
+    pub schema: Option<String>,
+
+    /// The path of a file holding the JSON Schema the events must conform to.
+    ///
+    /// Either `schema` or `schema_file` must be set.
+    pub schema_file: Option<PathBuf>,
+
+    /// The name of the field of the invalid events holding their validation errors.
+    #[serde(default = "default_errors_field")]
+    pub errors_field: String,
+
+    /// The maximum number of validation errors attached to an invalid event.
+    #[serde(default = "default_max_errors")]
+    pub max_errors: usize,
+}
+
+fn default_errors_field() -> String {
+    "schema_errors".to_owned()
+}
+
+const fn default_max_errors() -> usize {
+    10
+}
+
+inventory::submit! {
+    TransformDescription::new::<SchemaGuardConfig>("schema_guard")
+}
+
+impl GenerateConfig for SchemaGuardConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            schema = '''
+            {
+              "type": "object",
+              "required": ["message", "level"],
+              "properties": {
+                "level": { "enum": ["debug", "info", "warn", "error"] }
+              }
+            }
+            '''"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "schema_guard")]
+impl TransformConfig for SchemaGuardConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        SchemaGuard::new(self).map(Transform::synchronous)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![
+            Output::default(DataType::Log),
+            Output::default(DataType::Log).with_port(INVALID_OUTPUT),
+        ]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "schema_guard"
+    }
+
+    fn enable_concurrency(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone)]
+pub struct SchemaGuard {
+    schema: Arc<JSONSchema>,
+    errors_field: String,
+    max_errors: usize,
+}
+
+impl SchemaGuard {
+    pub fn new(config: &SchemaGuardConfig) -> crate::Result<Self> {
+        let schema = match (&config.schema, &config.schema_file) {
+            (Some(schema), None) => schema.clone(),
+            (None, Some(path)) => std::fs::read_to_string(path)
+                .map_err(|error| format!("Unable to read schema file {:?}: {}", path, error))?,
+            _ => return Err("Exactly one of `schema` and `schema_file` must be set".into()),
+        };
+        let schema = serde_json::from_str(&schema)
+            .map_err(|error| format!("Schema is not valid JSON: {}", error))?;
+        let schema = JSONSchema::compile(&schema)
+            .map_err(|error| format!("Invalid JSON Schema: {}", error))?;
+
+        Ok(Self {
+            schema: Arc::new(schema),
+            errors_field: config.errors_field.clone(),
+            max_errors: config.max_errors,
+        })
+    }
+
+    /// Returns the validation errors of the event, with the path of the invalid fields.
+    fn validate(&self, event: &Event) -> Vec<String> {
+        let instance = match serde_json::to_value(event.as_log()) {
+            Ok(instance) => instance,
+            Err(error) => return vec![error.to_string()],
+        };
+        match self.schema.validate(&instance) {
+            Ok(()) => Vec::new(),
+            Err(errors) => errors
+                .take(self.max_errors.max(1))
+                .map(|error| {
+                    let path = error.instance_path.to_string();
+                    if path.is_empty() {
+                        error.to_string()
+                    } else {
+                        format!("{}: {}", path, error)
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+impl SyncTransform for SchemaGuard {
+    fn transform(&mut self, mut event: Event, output: &mut TransformOutputsBuf) {
+        let errors = self.validate(&event);
+        if errors.is_empty() {
+            output.push(event);
+            return;
+        }
+
+        emit!(SchemaGuardValidationFailed { error: &errors[0] });
+        let errors = errors.into_iter().map(Value::from).collect::<Vec<_>>();
+        event
+            .as_mut_log()
+            .insert(self.errors_field.as_str(), Value::Array(errors));
+        output.push_named(INVALID_OUTPUT, event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::event::LogEvent;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SchemaGuardConfig>();
+    }
+
+    fn transform(guard: &mut SchemaGuard, event: Event) -> TransformOutputsBuf {
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            vec![
+                Output::default(DataType::Log),
+                Output::default(DataType::Log).with_port(INVALID_OUTPUT),
+            ],
+            1,
+        );
+        guard.transform(event, &mut outputs);
+        outputs
+    }
+
+    fn guard(config: &str) -> SchemaGuard {
+        SchemaGuard::new(&toml::from_str(config).unwrap()).unwrap()
+    }
+
+    const SCHEMA: &str = r#"
+schema = '''
+{
+  "type": "object",
+  "required": ["message", "level"],
+  "properties": {
+    "level": { "enum": ["info", "error"] },
+    "status": { "type": "integer" }
+  }
+}
+'''
+"#;
+
+    #[test]
+    fn forwards_valid_events() {
+        let mut guard = guard(SCHEMA);
+        let mut event = LogEvent::from("hello");
+        event.insert("level", "info");
+        event.insert("status", 200);
+        let event = Event::from(event);
+
+        let mut outputs = transform(&mut guard, event.clone());
+        assert_eq!(outputs.drain().collect::<Vec<_>>(), vec![event]);
+        assert_eq!(outputs.drain_named(INVALID_OUTPUT).count(), 0);
+    }
+
+    #[test]
+    fn routes_violations_with_errors() {
+        let mut guard = guard(SCHEMA);
+        let mut event = LogEvent::from("hello");
+        event.insert("status", "200");
+
+        let mut outputs = transform(&mut guard, event.into());
+        assert_eq!(outputs.drain().count(), 0);
+        let invalid = outputs.drain_named(INVALID_OUTPUT).collect::<Vec<_>>();
+        assert_eq!(invalid.len(), 1);
+
+        let errors = match &invalid[0].as_log()["schema_errors"] {
+            Value::Array(errors) => errors
+                .iter()
+                .map(Value::to_string_lossy)
+                .collect::<Vec<_>>(),
+            value => panic!("unexpected errors {:?}", value),
+        };
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().any(|error| error.contains("\"level\"")));
+        assert!(errors.iter().any(|error| error.starts_with("/status: ")));
+    }
+
+    #[test]
+    fn limits_errors() {
+        let mut guard = guard(&format!(
+            "{}\nmax_errors = 1\nerrors_field = \"errors\"",
+            SCHEMA
+        ));
+        let mut event = LogEvent::from("hello");
+        event.insert("status", "200");
+
+        let mut outputs = transform(&mut guard, event.into());
+        let invalid = outputs.drain_named(INVALID_OUTPUT).collect::<Vec<_>>();
+        assert!(
+            matches!(&invalid[0].as_log()["errors"], Value::Array(errors) if errors.len() == 1)
+        );
+    }
+
+    #[test]
+    fn reads_schema_file() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(file, r#"{{"required": ["level"]}}"#).unwrap();
+        let config = SchemaGuardConfig {
+            schema: None,
+            schema_file: Some(file.path().to_owned()),
+            errors_field: default_errors_field(),
+            max_errors: default_max_errors(),
+        };
+        let mut guard = SchemaGuard::new(&config).unwrap();
+
+        let mut outputs = transform(&mut guard, LogEvent::from("hello").into());
+        assert_eq!(outputs.drain_named(INVALID_OUTPUT).count(), 1);
+    }
+
+    #[test]
+    fn rejects_invalid_schemas() {
+        assert!(SchemaGuard::new(&toml::from_str("schema = '{\"type\": 12}'").unwrap()).is_err());
+        assert!(SchemaGuard::new(&toml::from_str("schema = 'not json'").unwrap()).is_err());
+        assert!(SchemaGuard::new(&toml::from_str("").unwrap()).is_err());
+    }
+}
@@ -0,0 +1,635 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    pin::Pin,
+    time::Duration,
+};
+
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use lru::LruCache;
+use tokio::time::Instant;
+use vector_config::configurable_component;
+
+use crate::{
+    conditions::{AnyCondition, Condition},
+    config::{
+        DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
+        TransformDescription,
+    },
+    event::{Event, Value},
+    internal_events::{TailSampleEventsDiscarded, TailSampleTraceEvicted},
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+/// Configuration for the `tail_sample` transform.
+#[configurable_component(transform)]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TailSampleConfig {
+    /// The policies deciding which traces are kept.
+    ///
+    /// A trace is kept when at least one of the policies matches it, and dropped otherwise.
+    pub policies: Vec<PolicyConfig>,
+
+    /// The maximum time to wait for the spans of a trace after its first span, in seconds.
+    ///
+    /// The policies are applied to the trace once its root span is received, or after this time.
+    #[serde(default = "default_decision_wait_secs")]
+    pub decision_wait_secs: f64,
+
+    /// The maximum number of traces buffered while waiting for their spans.
+    ///
+    /// When reached, the policies are applied to the oldest trace early.
+    #[serde(default = "default_max_traces")]
+    pub max_traces: usize,
+
+    /// The number of decisions remembered for the spans received after the decision on their trace.
+    #[serde(default = "default_max_decisions")]
+    pub max_decisions: usize,
+
+    /// The name of the field holding the trace ID of the spans.
+    ///
+    /// Spans without trace ID are always forwarded.
+    #[serde(default = "default_trace_id_field")]
+    pub trace_id_field: String,
+
+    /// The name of the field holding the ID of the parent span.
+    ///
+    /// Spans without parent ID, or with a null, zero, or empty one, are the root spans of their trace.
+    #[serde(default = "default_parent_id_field")]
+    pub parent_id_field: String,
+}
+
+/// A policy keeping the traces it matches.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum PolicyConfig {
+    /// Keeps the traces with at least one span with an error.
+    Error {
+        /// The name of the field of the spans holding the error.
+        ///
+        /// Errors are `true` booleans, non-zero integers, or non-empty strings.
+        #[serde(default = "default_error_field")]
+        field: String,
+    },
+
+    /// Keeps the traces with at least one span lasting at least `threshold`.
+    Latency {
+        /// The name of the field of the spans holding their duration.
+        #[serde(default = "default_duration_field")]
+        field: String,
+
+        /// The minimum duration of the spans, in the unit of `field`.
+        threshold: f64,
+    },
+
+    /// Keeps a fraction of the traces, consistently across the instances of the transform.
+    Probabilistic {
+        /// The probability with which traces are kept, between `0.0` and `1.0`.
+        probability: f64,
+    },
+
+    /// Keeps the traces with at least one span matching a condition.
+    Condition {
+        /// The condition matched against the spans.
+        condition: AnyCondition,
+    },
+}
+
+const fn default_decision_wait_secs() -> f64 {
+    30.0
+}
+
+const fn default_max_traces() -> usize {
+    10000
+}
+
+const fn default_max_decisions() -> usize {
+    100000
+}
+
+fn default_trace_id_field() -> String {
+    "trace_id".to_owned()
+}
+
+fn default_parent_id_field() -> String {
+    "parent_id".to_owned()
+}
+
+fn default_error_field() -> String {
+    "error".to_owned()
+}
+
+fn default_duration_field() -> String {
+    "duration".to_owned()
+}
+
+inventory::submit! {
+    TransformDescription::new::<TailSampleConfig>("tail_sample")
+}
+
+impl GenerateConfig for TailSampleConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            [[policies]]
+            type = "error"
+
+            [[policies]]
+            type = "probabilistic"
+            probability = 0.1"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "tail_sample")]
+impl TransformConfig for TailSampleConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        TailSample::new(self, &context.enrichment_tables).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::trace()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Trace)]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "tail_sample"
+    }
+}
+
+enum Policy {
+    Error { field: String },
+    Latency { field: String, threshold: f64 },
+    Probabilistic { threshold: u64 },
+    Condition { condition: Condition },
+}
+
+impl Policy {
+    fn new(
+        config: &PolicyConfig,
+        enrichment_tables: &enrichment::TableRegistry,
+    ) -> crate::Result<Self> {
+        Ok(match config {
+            PolicyConfig::Error { field } => Self::Error {
+                field: field.clone(),
+            },
+            PolicyConfig::Latency { field, threshold } => Self::Latency {
+                field: field.clone(),
+                threshold: *threshold,
+            },
+            PolicyConfig::Probabilistic { probability } => {
+                if !(0.0..=1.0).contains(probability) {
+                    return Err(format!(
+                        "Sampling probability must be between 0.0 and 1.0, got {}",
+                        probability
+                    )
+                    .into());
+                }
+                // The conversion saturates, so that a probability of `1.0` keeps all the traces.
+                Self::Probabilistic {
+                    threshold: (probability * u64::MAX as f64) as u64,
+                }
+            }
+            PolicyConfig::Condition { condition } => Self::Condition {
+                condition: condition.build(enrichment_tables)?,
+            },
+        })
+    }
+
+    /// Returns whether the trace matches the policy.
+    fn matches(&self, trace_id: &str, spans: &mut Vec<Event>) -> bool {
+        match self {
+            Self::Error { field } => {
+                spans
+                    .iter()
+                    .any(|span| match span.as_trace().get(field.as_str()) {
+                        Some(Value::Boolean(error)) => *error,
+                        Some(Value::Integer(error)) => *error != 0,
+                        Some(Value::Bytes(error)) => !error.is_empty(),
+                        _ => false,
+                    })
+            }
+            Self::Latency { field, threshold } => {
+                spans
+                    .iter()
+                    .any(|span| match span.as_trace().get(field.as_str()) {
+                        Some(Value::Integer(duration)) => *duration as f64 >= *threshold,
+                        Some(Value::Float(duration)) => duration.into_inner() >= *threshold,
+                        _ => false,
+                    })
+            }
+            Self::Probabilistic { threshold } => {
+                *threshold == u64::MAX || seahash::hash(trace_id.as_bytes()) < *threshold
+            }
+            Self::Condition { condition } => {
+                let mut matched = false;
+                *spans = std::mem::take(spans)
+                    .into_iter()
+                    .map(|span| {
+                        if matched {
+                            return span;
+                        }
+                        let (result, span) = condition.check(span);
+                        matched = result;
+                        span
+                    })
+                    .collect();
+                matched
+            }
+        }
+    }
+}
+
+/// The spans of a trace waiting for the decision.
+struct PendingTrace {
+    spans: Vec<Event>,
+    first_seen: Instant,
+}
+
+pub struct TailSample {
+    policies: Vec<Policy>,
+    decision_wait: Duration,
+    max_traces: usize,
+    trace_id_field: String,
+    parent_id_field: String,
+    pending: HashMap<String, PendingTrace>,
+    /// The trace IDs in the order of their first span, with the time of their first span.
+    arrivals: VecDeque<(Instant, String)>,
+    decisions: LruCache<String, bool>,
+}
+
+impl TailSample {
+    pub fn new(
+        config: &TailSampleConfig,
+        enrichment_tables: &enrichment::TableRegistry,
+    ) -> crate::Result<Self> {
+        if config.policies.is_empty() {
+            return Err("At least one policy is required".into());
+        }
+        if config.max_traces == 0 {
+            return Err("`max_traces` must be greater than zero".into());
+        }
+        if config.max_decisions == 0 {
+            return Err("`max_decisions` must be greater than zero".into());
+        }
+        if !config.decision_wait_secs.is_finite() || config.decision_wait_secs <= 0.0 {
+            return Err("`decision_wait_secs` must be a finite number greater than zero".into());
+        }
+        let policies = config
+            .policies
+            .iter()
+            .map(|policy| Policy::new(policy, enrichment_tables))
+            .collect::<crate::Result<_>>()?;
+
+        Ok(Self {
+            policies,
+            decision_wait: Duration::from_secs_f64(config.decision_wait_secs),
+            max_traces: config.max_traces,
+            trace_id_field: config.trace_id_field.clone(),
+            parent_id_field: config.parent_id_field.clone(),
+            pending: HashMap::new(),
+            arrivals: VecDeque::new(),
+            decisions: LruCache::new(config.max_decisions),
+        })
+    }
+
+    fn is_root(&self, span: &Event) -> bool {
+        match span.as_trace().get(self.parent_id_field.as_str()) {
+            None | Some(Value::Null) | Some(Value::Integer(0)) => true,
+            Some(Value::Bytes(parent_id)) => parent_id.is_empty(),
+            Some(_) => false,
+        }
+    }
+
+    fn record(&mut self, span: Event, output: &mut Vec<Event>) {
+        let trace_id = match span.as_trace().get(self.trace_id_field.as_str()) {
+            Some(trace_id) => trace_id.to_string_lossy(),
+            None => {
+                output.push(span);
+                return;
+            }
+        };
+
+        // The spans received after the decision on their trace follow it.
+        if let Some(keep) = self.decisions.get(&trace_id) {
+            if *keep {
+                output.push(span);
+            } else {
+                emit!(TailSampleEventsDiscarded { count: 1 });
+            }
+            return;
+        }
+
+        let is_root = self.is_root(&span);
+        let now = Instant::now();
+        let pending = self.pending.entry(trace_id.clone()).or_insert_with(|| {
+            self.arrivals.push_back((now, trace_id.clone()));
+            PendingTrace {
+                spans: Vec::new(),
+                first_seen: now,
+            }
+        });
+        pending.spans.push(span);
+
+        if is_root {
+            self.decide(trace_id, output);
+        } else if self.pending.len() > self.max_traces {
+            if let Some(trace_id) = self.next_arrival(None) {
+                emit!(TailSampleTraceEvicted);
+                self.decide(trace_id, output);
+            }
+        }
+    }
+
+    /// Pops the ID of the oldest pending trace, if its first span was received before `before`.
+    fn next_arrival(&mut self, before: Option<Instant>) -> Option<String> {
+        while let Some((first_seen, _)) = self.arrivals.front() {
+            if before.map_or(false, |before| *first_seen > before) {
+                return None;
+            }
+            let (first_seen, trace_id) = self.arrivals.pop_front()?;
+            // The trace may have been decided already, and then received new spans.
+            let is_pending = self
+                .pending
+                .get(&trace_id)
+                .map_or(false, |pending| pending.first_seen == first_seen);
+            if is_pending {
+                return Some(trace_id);
+            }
+        }
+        None
+    }
+
+    fn decide(&mut self, trace_id: String, output: &mut Vec<Event>) {
+        let mut spans = match self.pending.remove(&trace_id) {
+            Some(pending) => pending.spans,
+            None => return,
+        };
+
+        let keep = self
+            .policies
+            .iter()
+            .any(|policy| policy.matches(&trace_id, &mut spans));
+        if keep {
+            output.extend(spans);
+        } else {
+            emit!(TailSampleEventsDiscarded { count: spans.len() });
+        }
+        self.decisions.put(trace_id, keep);
+    }
+
+    /// Applies the policies to the traces that waited long enough for their spans.
+    fn flush_expired_into(&mut self, output: &mut Vec<Event>) {
+        let before = match Instant::now().checked_sub(self.decision_wait) {
+            Some(before) => before,
+            None => return,
+        };
+        while let Some(trace_id) = self.next_arrival(Some(before)) {
+            self.decide(trace_id, output);
+        }
+    }
+
+    fn flush_all_into(&mut self, output: &mut Vec<Event>) {
+        while let Some(trace_id) = self.next_arrival(None) {
+            self.decide(trace_id, output);
+        }
+    }
+}
+
+impl TaskTransform<Event> for TailSample {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let period = self.decision_wait.min(Duration::from_secs(1));
+        let mut flush_stream = tokio::time::interval_at(Instant::now() + period, period);
+
+        Box::pin(stream! {
+            let mut output = Vec::new();
+            let mut done = false;
+            while !done {
+                tokio::select! {
+                    _ = flush_stream.tick() => {
+                        self.flush_expired_into(&mut output);
+                    },
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            None => {
+                                self.flush_all_into(&mut output);
+                                done = true;
+                            }
+                            Some(event) => self.record(event, &mut output),
+                        }
+                    }
+                };
+                for event in output.drain(..) {
+                    yield event;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::task::Poll;
+
+    use futures::{stream, SinkExt};
+
+    use super::*;
+    use crate::event::{LogEvent, TraceEvent};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<TailSampleConfig>();
+    }
+
+    fn tail_sample(config: &str) -> TailSample {
+        TailSample::new(&toml::from_str(config).unwrap(), &Default::default()).unwrap()
+    }
+
+    fn span(trace_id: i64, span_id: i64, parent_id: i64, duration: i64, error: i64) -> Event {
+        let mut span = LogEvent::default();
+        span.insert("trace_id", trace_id);
+        span.insert("span_id", span_id);
+        span.insert("parent_id", parent_id);
+        span.insert("duration", duration);
+        span.insert("error", error);
+        Event::Trace(TraceEvent::from(span))
+    }
+
+    fn span_ids(events: &[Event]) -> Vec<Value> {
+        events
+            .iter()
+            .map(|event| event.as_trace().get("span_id").unwrap().clone())
+            .collect()
+    }
+
+    #[test]
+    fn rejects_invalid_limits() {
+        let policies = r#"
+[[policies]]
+type = "error"
+"#;
+        for limit in [
+            "max_traces = 0",
+            "max_decisions = 0",
+            "decision_wait_secs = 0.0",
+            "decision_wait_secs = -1.0",
+            "decision_wait_secs = nan",
+            "decision_wait_secs = inf",
+        ] {
+            let config: TailSampleConfig =
+                toml::from_str(&format!("{}\n{}", limit, policies)).unwrap();
+            assert!(
+                TailSample::new(&config, &Default::default()).is_err(),
+                "{}",
+                limit
+            );
+        }
+    }
+
+    #[test]
+    fn keeps_whole_traces_matching_policies() {
+        let mut tail_sample = tail_sample(
+            r#"
+[[policies]]
+type = "error"
+
+[[policies]]
+type = "latency"
+threshold = 1000
+"#,
+        );
+
+        let mut output = Vec::new();
+        // A trace with an error in a child span.
+        tail_sample.record(span(1, 11, 10, 50, 1), &mut output);
+        // A slow trace.
+        tail_sample.record(span(2, 21, 20, 2000, 0), &mut output);
+        // A fast trace without error.
+        tail_sample.record(span(3, 31, 30, 50, 0), &mut output);
+        assert!(output.is_empty());
+
+        tail_sample.record(span(3, 30, 0, 100, 0), &mut output);
+        assert!(output.is_empty());
+        tail_sample.record(span(2, 20, 0, 2100, 0), &mut output);
+        tail_sample.record(span(1, 10, 0, 100, 0), &mut output);
+        assert_eq!(
+            span_ids(&output),
+            vec![21.into(), 20.into(), 11.into(), 10.into()]
+        );
+
+        // The late spans follow the decision on their trace.
+        output.clear();
+        tail_sample.record(span(1, 12, 10, 50, 0), &mut output);
+        tail_sample.record(span(3, 32, 30, 50, 0), &mut output);
+        assert_eq!(span_ids(&output), vec![12.into()]);
+    }
+
+    #[test]
+    fn evicts_oldest_trace_when_full() {
+        let mut tail_sample = tail_sample(
+            r#"
+max_traces = 2
+
+[[policies]]
+type = "probabilistic"
+probability = 1.0
+"#,
+        );
+
+        let mut output = Vec::new();
+        tail_sample.record(span(1, 11, 10, 0, 0), &mut output);
+        tail_sample.record(span(2, 21, 20, 0, 0), &mut output);
+        tail_sample.record(span(1, 12, 10, 0, 0), &mut output);
+        assert!(output.is_empty());
+
+        tail_sample.record(span(3, 31, 30, 0, 0), &mut output);
+        assert_eq!(span_ids(&output), vec![11.into(), 12.into()]);
+    }
+
+    #[test]
+    fn condition_policy() {
+        let mut tail_sample = tail_sample(
+            r#"
+[[policies]]
+type = "condition"
+condition = '.span_id == 22'
+"#,
+        );
+
+        let mut output = Vec::new();
+        tail_sample.record(span(1, 11, 10, 0, 0), &mut output);
+        tail_sample.record(span(1, 10, 0, 0, 0), &mut output);
+        tail_sample.record(span(2, 22, 20, 0, 0), &mut output);
+        tail_sample.record(span(2, 20, 0, 0, 0), &mut output);
+        assert_eq!(span_ids(&output), vec![22.into(), 20.into()]);
+    }
+
+    #[tokio::test]
+    async fn decides_after_decision_wait() {
+        let tail_sample = toml::from_str::<TailSampleConfig>(
+            r#"
+decision_wait_secs = 10
+
+[[policies]]
+type = "probabilistic"
+probability = 1.0
+"#,
+        )
+        .unwrap()
+        .build(&TransformContext::default())
+        .await
+        .unwrap()
+        .into_task();
+
+        let (mut tx, rx) = futures::channel::mpsc::channel(10);
+
+        tokio::time::pause();
+        let mut out_stream = tail_sample.transform_events(Box::pin(rx));
+
+        tx.send(span(1, 11, 10, 0, 0)).await.unwrap();
+        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+
+        tokio::time::advance(Duration::from_secs(11)).await;
+        let event = out_stream.next().await.unwrap();
+        assert_eq!(span_ids(&[event]), vec![11.into()]);
+
+        tx.disconnect();
+        assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
+    }
+
+    #[tokio::test]
+    async fn decides_pending_traces_on_shutdown() {
+        let tail_sample = toml::from_str::<TailSampleConfig>(
+            r#"
+[[policies]]
+type = "error"
+"#,
+        )
+        .unwrap()
+        .build(&TransformContext::default())
+        .await
+        .unwrap()
+        .into_task();
+
+        let inputs = vec![span(1, 11, 10, 0, 1), span(2, 21, 20, 0, 0)];
+        let output = tail_sample
+            .transform_events(Box::pin(stream::iter(inputs)))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(span_ids(&output), vec![11.into()]);
+    }
+}
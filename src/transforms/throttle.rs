@@ -1,15 +1,21 @@
-use std::{num::NonZeroU32, pin::Pin, time::Duration};
+use std::{collections::HashMap, num::NonZeroU32, pin::Pin, time::Duration};
 
 use async_stream::stream;
+use enrichment::{Case, Condition, IndexHandle, TableRegistry, TableSearch};
 use futures::{stream, Stream, StreamExt};
-use governor::{clock, Quota, RateLimiter};
+use governor::{
+    clock,
+    middleware::NoOpMiddleware,
+    state::{InMemoryState, NotKeyed},
+    Quota, RateLimiter,
+};
 use snafu::Snafu;
 use vector_config::configurable_component;
 
 use crate::{
     conditions::{AnyCondition, Condition},
     config::{DataType, Input, Output, TransformConfig, TransformContext, TransformDescription},
-    event::Event,
+    event::{Event, Value},
     internal_events::{TemplateRenderingError, ThrottleEventDiscarded},
     schema,
     template::Template,
@@ -37,6 +43,38 @@ pub struct ThrottleConfig {
 
     /// A logical condition used to exclude events from sampling.
     exclude: Option<AnyCondition>,
+
+    /// Per-key thresholds looked up from an enrichment table.
+    ///
+    /// The `threshold` applies to the keys that aren't in the table. When the table is reloaded, the new thresholds
+    /// apply to the next events.
+    #[configurable(derived)]
+    quotas: Option<QuotasConfig>,
+}
+
+/// Configuration of the per-key thresholds looked up from an enrichment table.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct QuotasConfig {
+    /// The name of the enrichment table holding the thresholds.
+    table: String,
+
+    /// The column of the table matched against the rendered `key_field` of the events.
+    #[serde(default = "default_key_column")]
+    key_column: String,
+
+    /// The column of the table holding the number of events allowed per configured `window_secs` for the key.
+    #[serde(default = "default_threshold_column")]
+    threshold_column: String,
+}
+
+fn default_key_column() -> String {
+    "key".to_owned()
+}
+
+fn default_threshold_column() -> String {
+    "threshold".to_owned()
 }
 
 inventory::submit! {
@@ -71,6 +109,8 @@ pub struct Throttle<C: clock::Clock<Instant = I>, I: clock::Reference> {
     flush_keys_interval: Duration,
     key_field: Option<Template>,
     exclude: Option<Condition>,
+    quotas: Option<Quotas>,
+    window_secs: f64,
     clock: C,
 }
 
@@ -91,10 +131,8 @@ where
             None => return Err(Box::new(ConfigError::NonZero)),
         };
 
-        let quota = match Quota::with_period(Duration::from_secs_f64(
-            config.window_secs / threshold.get() as f64,
-        )) {
-            Some(quota) => quota.allow_burst(threshold),
+        let quota = match quota(threshold, config.window_secs) {
+            Some(quota) => quota,
             None => return Err(Box::new(ConfigError::NonZero)),
         };
         let exclude = config
@@ -103,16 +141,141 @@ where
             .map(|condition| condition.build(&context.enrichment_tables))
             .transpose()?;
 
+        if config.quotas.is_some() && config.key_field.is_none() {
+            return Err(Box::new(ConfigError::QuotasWithoutKeyField));
+        }
+        let quotas = config
+            .quotas
+            .as_ref()
+            .map(|quotas| Quotas::new(quotas, &context.enrichment_tables))
+            .transpose()?;
+
         Ok(Self {
             quota,
             clock,
             flush_keys_interval,
             key_field: config.key_field.clone(),
             exclude,
+            quotas,
+            window_secs: config.window_secs,
         })
     }
 }
 
+fn quota(threshold: NonZeroU32, window_secs: f64) -> Option<Quota> {
+    Quota::with_period(Duration::from_secs_f64(
+        window_secs / threshold.get() as f64,
+    ))
+    .map(|quota| quota.allow_burst(threshold))
+}
+
+/// Looks up the thresholds of the keys from an enrichment table.
+#[derive(Clone, Debug)]
+struct Quotas {
+    tables: TableSearch,
+    table: String,
+    key_column: String,
+    select: [String; 1],
+    index: IndexHandle,
+}
+
+impl Quotas {
+    fn new(config: &QuotasConfig, enrichment_tables: &TableRegistry) -> crate::Result<Self> {
+        let index = enrichment_tables.clone().add_index(
+            &config.table,
+            Case::Sensitive,
+            &[config.key_column.as_str()],
+        )?;
+
+        Ok(Self {
+            tables: enrichment_tables.as_readonly(),
+            table: config.table.clone(),
+            key_column: config.key_column.clone(),
+            select: [config.threshold_column.clone()],
+            index,
+        })
+    }
+
+    /// Returns the threshold of the key, if it's in the table with a valid one.
+    fn threshold(&self, key: &str) -> Option<NonZeroU32> {
+        let condition = [Condition::Equals {
+            field: &self.key_column,
+            value: key.into(),
+        }];
+        let row = self
+            .tables
+            .find_table_row(
+                &self.table,
+                Case::Sensitive,
+                &condition,
+                Some(&self.select[..]),
+                Some(self.index),
+            )
+            .ok()?;
+
+        let threshold = match row.get(&self.select[0])? {
+            Value::Integer(threshold) => u32::try_from(*threshold).ok(),
+            // The columns of CSV files without schema are strings.
+            Value::Bytes(threshold) => std::str::from_utf8(threshold).ok()?.trim().parse().ok(),
+            _ => None,
+        };
+        threshold.and_then(NonZeroU32::new)
+    }
+}
+
+struct QuotaLimiter<C: clock::Clock<Instant = I>, I: clock::Reference> {
+    threshold: NonZeroU32,
+    limiter: RateLimiter<NotKeyed, InMemoryState, C, NoOpMiddleware<I>>,
+    recent: bool,
+}
+
+/// The rate limiters of the keys with a threshold from the table.
+struct QuotaLimiters<C: clock::Clock<Instant = I>, I: clock::Reference> {
+    quotas: Quotas,
+    window_secs: f64,
+    clock: C,
+    limiters: HashMap<String, QuotaLimiter<C, I>>,
+}
+
+impl<C, I> QuotaLimiters<C, I>
+where
+    C: clock::Clock<Instant = I>,
+    I: clock::Reference,
+{
+    /// Checks whether an event of the key is allowed, or returns `None` if the key doesn't have
+    /// its own threshold.
+    fn check(&mut self, key: &str) -> Option<bool> {
+        let threshold = self.quotas.threshold(key)?;
+
+        // A limiter is only reused as long as the threshold of the key doesn't change.
+        let reusable = self
+            .limiters
+            .get(key)
+            .map_or(false, |limiter| limiter.threshold == threshold);
+        if !reusable {
+            let limiter = QuotaLimiter {
+                threshold,
+                limiter: RateLimiter::direct_with_clock(
+                    quota(threshold, self.window_secs)?,
+                    &self.clock,
+                ),
+                recent: false,
+            };
+            self.limiters.insert(key.to_owned(), limiter);
+        }
+
+        let limiter = self.limiters.get_mut(key)?;
+        limiter.recent = true;
+        Some(limiter.limiter.check().is_ok())
+    }
+
+    /// Removes the limiters of the keys without events since the last call.
+    fn retain_recent(&mut self) {
+        self.limiters
+            .retain(|_, limiter| std::mem::take(&mut limiter.recent));
+    }
+}
+
 impl<C, I> TaskTransform<Event> for Throttle<C, I>
 where
     C: clock::Clock<Instant = I> + Send + 'static,
@@ -130,6 +293,12 @@ where
         let mut flush_stream = tokio::time::interval(Duration::from_millis(1000));
 
         let limiter = RateLimiter::dashmap_with_clock(self.quota, &self.clock);
+        let mut quota_limiters = self.quotas.clone().map(|quotas| QuotaLimiters {
+            quotas,
+            window_secs: self.window_secs,
+            clock: self.clock.clone(),
+            limiters: HashMap::new(),
+        });
 
         Box::pin(
             stream! {
@@ -162,17 +331,18 @@ where
                                                 .ok()
                                         });
 
-                                        match limiter.check_key(&key) {
-                                            Ok(()) => {
-                                                output.push(event);
-                                            }
-                                            _ => {
-                                                if let Some(key) = key {
-                                                  emit!(ThrottleEventDiscarded{key})
-                                                } else {
-                                                  emit!(ThrottleEventDiscarded{key: "None".to_string()})
-                                                }
-                                            }
+                                        let allowed = key
+                                            .as_ref()
+                                            .zip(quota_limiters.as_mut())
+                                            .and_then(|(key, quota_limiters)| quota_limiters.check(key))
+                                            .unwrap_or_else(|| limiter.check_key(&key).is_ok());
+
+                                        if allowed {
+                                            output.push(event);
+                                        } else if let Some(key) = key {
+                                            emit!(ThrottleEventDiscarded{key})
+                                        } else {
+                                            emit!(ThrottleEventDiscarded{key: "None".to_string()})
                                         }
                                     } else {
                                         output.push(event)
@@ -183,6 +353,9 @@ where
                     }
                     _ = flush_keys.tick() => {
                         limiter.retain_recent();
+                        if let Some(quota_limiters) = quota_limiters.as_mut() {
+                            quota_limiters.retain_recent();
+                        }
                         false
                     }
                     _ = flush_stream.tick() => {
@@ -202,6 +375,9 @@ where
 pub enum ConfigError {
     #[snafu(display("`threshold`, and `window_secs` must be non-zero"))]
     NonZero,
+
+    #[snafu(display("`quotas` requires `key_field`"))]
+    QuotasWithoutKeyField,
 }
 
 #[cfg(test)]
@@ -210,6 +386,8 @@ mod tests {
 
     use futures::SinkExt;
 
+    use std::collections::BTreeMap;
+
     use super::*;
     use crate::event::LogEvent;
 
@@ -406,4 +584,141 @@ key_field = "{{ bucket }}"
         // And still nothing there
         assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
     }
+
+    #[derive(Clone)]
+    struct QuotasTable(BTreeMap<String, Value>);
+
+    impl enrichment::Table for QuotasTable {
+        fn find_table_row(
+            &self,
+            _case: Case,
+            condition: &[Condition],
+            _select: Option<&[String]>,
+            _index: Option<IndexHandle>,
+        ) -> Result<BTreeMap<String, Value>, String> {
+            match condition {
+                [Condition::Equals {
+                    field: "key",
+                    value,
+                }] => self
+                    .0
+                    .get(&value.to_string_lossy())
+                    .map(|threshold| BTreeMap::from([("threshold".to_owned(), threshold.clone())]))
+                    .ok_or_else(|| "no rows found".to_owned()),
+                _ => Err("unexpected condition".to_owned()),
+            }
+        }
+
+        fn find_table_rows(
+            &self,
+            case: Case,
+            condition: &[Condition],
+            select: Option<&[String]>,
+            index: Option<IndexHandle>,
+        ) -> Result<Vec<BTreeMap<String, Value>>, String> {
+            self.find_table_row(case, condition, select, index)
+                .map(|row| vec![row])
+        }
+
+        fn add_index(&mut self, _case: Case, fields: &[&str]) -> Result<IndexHandle, String> {
+            assert_eq!(fields, ["key"]);
+            Ok(IndexHandle(0))
+        }
+
+        fn index_fields(&self) -> Vec<(Case, Vec<String>)> {
+            Vec::new()
+        }
+
+        fn needs_reload(&self) -> bool {
+            false
+        }
+    }
+
+    #[tokio::test]
+    async fn throttle_quotas() {
+        let clock = clock::FakeRelativeClock::default();
+        let config = toml::from_str::<ThrottleConfig>(
+            r#"
+threshold = 1
+window_secs = 5
+key_field = "{{ tenant }}"
+quotas.table = "quotas"
+"#,
+        )
+        .unwrap();
+
+        let table = QuotasTable(BTreeMap::from([
+            ("large".to_owned(), Value::from(3)),
+            // The columns of CSV files are strings.
+            ("medium".to_owned(), Value::from("2")),
+        ]));
+        let enrichment_tables = TableRegistry::default();
+        enrichment_tables.load(HashMap::from([(
+            "quotas".to_owned(),
+            Box::new(table) as Box<dyn enrichment::Table + Send + Sync>,
+        )]));
+        let context = TransformContext {
+            enrichment_tables: enrichment_tables.clone(),
+            ..Default::default()
+        };
+
+        let throttle = Throttle::new(&config, &context, clock.clone())
+            .map(Transform::event_task)
+            .unwrap();
+        enrichment_tables.finish_load();
+
+        let throttle = throttle.into_task();
+
+        let (mut tx, rx) = futures::channel::mpsc::channel(20);
+        let mut out_stream = throttle.transform_events(Box::pin(rx));
+
+        // tokio interval is always immediately ready, so we poll once to make sure
+        // we trip it/set the interval in the future
+        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+
+        for tenant in ["large", "medium", "small"] {
+            for _ in 0..4 {
+                let mut log = LogEvent::default();
+                log.insert("tenant", tenant);
+                tx.send(log.into()).await.unwrap();
+            }
+        }
+
+        let mut counts = HashMap::new();
+        for _ in 0..6 {
+            let event = out_stream.next().await.unwrap();
+            *counts
+                .entry(event.as_log()["tenant"].to_string_lossy())
+                .or_insert(0) += 1;
+        }
+        assert_eq!(counts["large"], 3);
+        assert_eq!(counts["medium"], 2);
+        // Keys without quota have the default threshold.
+        assert_eq!(counts["small"], 1);
+
+        // We should be back to pending, having the other events dropped
+        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+
+        tx.disconnect();
+        assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
+    }
+
+    #[test]
+    fn quotas_require_key_field() {
+        let config = toml::from_str::<ThrottleConfig>(
+            r#"
+threshold = 1
+window_secs = 5
+quotas.table = "quotas"
+"#,
+        )
+        .unwrap();
+
+        assert!(Throttle::new(
+            &config,
+            &TransformContext::default(),
+            clock::FakeRelativeClock::default()
+        )
+        .is_err());
+    }
 }
@@ -0,0 +1,315 @@
+use std::collections::HashMap;
+
+use vector_config::configurable_component;
+
+use crate::{
+    conditions::{AnyCondition, Condition},
+    config::{
+        DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
+        TransformDescription,
+    },
+    event::{Event, Value},
+    internal_events::TraceSamplingEventDiscarded,
+    schema,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+/// Configuration for the `trace_sampling` transform.
+#[configurable_component(transform)]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TraceSamplingConfig {
+    /// The probability with which the events of a trace are forwarded, between `0.0` and `1.0`.
+    ///
+    /// For example, `probability = 0.1` means that the events of 1 out of every 10 traces are forwarded and the rest
+    /// are dropped.
+    pub probability: f64,
+
+    /// The name of the field holding the trace ID of the events.
+    ///
+    /// The trace ID is hashed to decide whether the event is forwarded, so that all the events of a trace get the same
+    /// decision. Events without trace ID are always forwarded.
+    #[serde(default = "default_trace_id_field")]
+    pub trace_id_field: String,
+
+    /// The name of the field holding the service of the events, used to look up `services`.
+    #[serde(default = "default_service_field")]
+    pub service_field: String,
+
+    /// A map of service names to the probabilities overriding `probability` for the events of the service.
+    #[serde(default)]
+    pub services: HashMap<String, f64>,
+
+    /// An ordered list of rules overriding the probability of the events they match.
+    ///
+    /// The probability of the first matching rule is used, and takes precedence over `services`.
+    #[serde(default)]
+    pub rules: Vec<SamplingRuleConfig>,
+}
+
+/// A rule overriding the sampling probability of the events matching a condition.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SamplingRuleConfig {
+    /// A logical condition matching the events to which the rule applies.
+    pub condition: AnyCondition,
+
+    /// The probability with which the events of the matching traces are forwarded, between `0.0` and `1.0`.
+    pub probability: f64,
+}
+
+fn default_trace_id_field() -> String {
+    "trace_id".to_owned()
+}
+
+fn default_service_field() -> String {
+    "service".to_owned()
+}
+
+inventory::submit! {
+    TransformDescription::new::<TraceSamplingConfig>("trace_sampling")
+}
+
+impl GenerateConfig for TraceSamplingConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            probability: 0.1,
+            trace_id_field: default_trace_id_field(),
+            service_field: default_service_field(),
+            services: HashMap::new(),
+            rules: Vec::new(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "trace_sampling")]
+impl TransformConfig for TraceSamplingConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        TraceSampling::new(self, &context.enrichment_tables).map(Transform::function)
+    }
+
+    fn input(&self) -> Input {
+        Input::new(DataType::Log | DataType::Trace)
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Log | DataType::Trace)]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "trace_sampling"
+    }
+}
+
+/// The hashes of the trace IDs below which the events are forwarded.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Threshold(u64);
+
+impl Threshold {
+    fn new(probability: f64) -> crate::Result<Self> {
+        if !(0.0..=1.0).contains(&probability) {
+            return Err(format!(
+                "Sampling probability must be between 0.0 and 1.0, got {}",
+                probability
+            )
+            .into());
+        }
+        // The conversion saturates, so that a probability of `1.0` keeps all the traces.
+        Ok(Self((probability * u64::MAX as f64) as u64))
+    }
+
+    const fn keeps(self, hash: u64) -> bool {
+        hash < self.0 || self.0 == u64::MAX
+    }
+}
+
+#[derive(Clone)]
+pub struct TraceSampling {
+    threshold: Threshold,
+    trace_id_field: String,
+    service_field: String,
+    services: HashMap<String, Threshold>,
+    rules: Vec<(Condition, Threshold)>,
+}
+
+impl TraceSampling {
+    pub fn new(
+        config: &TraceSamplingConfig,
+        enrichment_tables: &enrichment::TableRegistry,
+    ) -> crate::Result<Self> {
+        let services = config
+            .services
+            .iter()
+            .map(|(service, probability)| Ok((service.clone(), Threshold::new(*probability)?)))
+            .collect::<crate::Result<_>>()?;
+        let rules = config
+            .rules
+            .iter()
+            .map(|rule| {
+                Ok((
+                    rule.condition.build(enrichment_tables)?,
+                    Threshold::new(rule.probability)?,
+                ))
+            })
+            .collect::<crate::Result<_>>()?;
+
+        Ok(Self {
+            threshold: Threshold::new(config.probability)?,
+            trace_id_field: config.trace_id_field.clone(),
+            service_field: config.service_field.clone(),
+            services,
+            rules,
+        })
+    }
+
+    fn get<'a>(event: &'a Event, field: &str) -> Option<&'a Value> {
+        match event {
+            Event::Log(event) => event.get(field),
+            Event::Trace(event) => event.get(field),
+            Event::Metric(_) => panic!("component can never receive metric events"),
+        }
+    }
+
+    /// Returns the threshold of the first matching rule, of the service of the event, or the
+    /// default one.
+    fn threshold(&self, mut event: Event) -> (Threshold, Event) {
+        for (condition, threshold) in &self.rules {
+            let (matched, checked) = condition.check(event);
+            if matched {
+                return (*threshold, checked);
+            }
+            event = checked;
+        }
+
+        let threshold = Self::get(&event, &self.service_field)
+            .and_then(|service| self.services.get(service.to_string_lossy().as_str()))
+            .copied()
+            .unwrap_or(self.threshold);
+        (threshold, event)
+    }
+}
+
+impl FunctionTransform for TraceSampling {
+    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        let hash = match Self::get(&event, &self.trace_id_field) {
+            Some(trace_id) => seahash::hash(trace_id.to_string_lossy().as_bytes()),
+            None => {
+                output.push(event);
+                return;
+            }
+        };
+
+        let (threshold, event) = self.threshold(event);
+        if threshold.keeps(hash) {
+            output.push(event);
+        } else {
+            emit!(TraceSamplingEventDiscarded);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::{
+        event::{LogEvent, TraceEvent},
+        transforms::test::transform_one,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<TraceSamplingConfig>();
+    }
+
+    fn sampler(config: &str) -> TraceSampling {
+        TraceSampling::new(&toml::from_str(config).unwrap(), &Default::default()).unwrap()
+    }
+
+    fn span(trace_id: u64, service: &str) -> Event {
+        let mut event = LogEvent::default();
+        event.insert("trace_id", trace_id);
+        event.insert("service", service);
+        Event::Trace(TraceEvent::from(event))
+    }
+
+    fn passed(sampler: &mut TraceSampling, service: &str) -> usize {
+        (0..10000)
+            .filter_map(|trace_id| transform_one(sampler, span(trace_id, service)))
+            .count()
+    }
+
+    #[test]
+    fn samples_traces_at_roughly_the_configured_probability() {
+        let mut sampler = sampler("probability = 0.2");
+        assert_relative_eq!(
+            passed(&mut sampler, "api") as f64 / 10000.0,
+            0.2,
+            epsilon = 0.02
+        );
+
+        assert_eq!(passed(&mut sampler("probability = 0.0"), "api"), 0);
+        assert_eq!(passed(&mut sampler("probability = 1.0"), "api"), 10000);
+    }
+
+    #[test]
+    fn keeps_or_drops_all_the_events_of_a_trace() {
+        let mut sampler = sampler(
+            r#"
+probability = 0.5
+services.db = 0.1
+"#,
+        );
+
+        for trace_id in 0..1000 {
+            let api = transform_one(&mut sampler, span(trace_id, "api")).is_some();
+            let log = {
+                let mut log = LogEvent::from("request");
+                log.insert("trace_id", trace_id);
+                transform_one(&mut sampler, log.into()).is_some()
+            };
+            assert_eq!(api, log);
+
+            // Traces kept at a lower probability are also kept at a higher one.
+            if transform_one(&mut sampler, span(trace_id, "db")).is_some() {
+                assert!(api);
+            }
+        }
+    }
+
+    #[test]
+    fn services_and_rules_override_probability() {
+        let mut sampler = sampler(
+            r#"
+probability = 0.0
+services.checkout = 1.0
+services.api = 1.0
+
+[[rules]]
+condition = '.service == "api"'
+probability = 0.0
+"#,
+        );
+
+        assert_eq!(passed(&mut sampler, "checkout"), 10000);
+        assert_eq!(passed(&mut sampler, "api"), 0);
+        assert_eq!(passed(&mut sampler, "db"), 0);
+    }
+
+    #[test]
+    fn forwards_events_without_trace_id() {
+        let mut sampler = sampler("probability = 0.0");
+        let event = Event::from(LogEvent::from("message"));
+        assert_eq!(transform_one(&mut sampler, event.clone()), Some(event));
+    }
+
+    #[test]
+    fn rejects_invalid_probability() {
+        let config = toml::from_str::<TraceSamplingConfig>("probability = 1.5").unwrap();
+        assert!(TraceSampling::new(&config, &Default::default()).is_err());
+    }
+}
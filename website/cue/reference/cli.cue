@@ -586,7 +586,7 @@ cli: {
 			}
 		}
 		VECTOR_WATCH_CONFIG: {
-			description: "Watch for changes in the configuration file and reload accordingly"
+			description: "Watch for changes in the configuration file, and in the files of the enrichment tables, and reload accordingly"
 			type: bool: default: false
 		}
 	}
@@ -618,6 +618,28 @@ components: {
 							examples: ["/path/to/certificate_authority.crt"]
 						}
 					}
+					client_allowed_sans: {
+						common:      false
+						description: "The Subject Alternative Names of client certificates allowed to connect, matched against the DNS names, email addresses, URIs, and IP addresses of the certificate. Connections whose client certificate matches neither this nor `client_allowed_subjects` are closed. Requires `verify_certificate` to be enabled. Only supported by TCP based sources."
+						required:    false
+						type: array: {
+							default: []
+							items: type: string: {
+								examples: ["client.example.com", "192.0.2.1"]
+							}
+						}
+					}
+					client_allowed_subjects: {
+						common:      false
+						description: "The subjects of client certificates allowed to connect, matched against either the Common Name or the full subject of the certificate. Connections whose client certificate matches neither this nor `client_allowed_sans` are closed. Requires `verify_certificate` to be enabled. Only supported by TCP based sources."
+						required:    false
+						type: array: {
+							default: []
+							items: type: string: {
+								examples: ["client", "CN=client,O=Acme"]
+							}
+						}
+					}
 					client_metadata_key: {
 						common:      false
 						description: "The key name added to each event with the client certificate's metadata."
@@ -806,6 +828,13 @@ components: {
 				description: "Configures the authentication strategy."
 				required:    false
 				type: object: options: {
+					api_key: {
+						description: "The API key to send with the `ApiKey` scheme, encoded as base64."
+						required:    true
+						type: string: {
+							examples: ["${API_KEY}"]
+						}
+					}
 					password: {
 						description: "The basic authentication password."
 						required:    true
@@ -818,8 +847,9 @@ components: {
 						required:    true
 						type: string: {
 							enum: {
-								basic:  "The [basic authentication strategy](\(urls.basic_auth))."
-								bearer: "The bearer token authentication strategy."
+								api_key: "The API key authentication strategy, as used by Elasticsearch and Kibana."
+								basic:   "The [basic authentication strategy](\(urls.basic_auth))."
+								bearer:  "The bearer token authentication strategy."
 							}
 						}
 					}
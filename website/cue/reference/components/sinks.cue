@@ -196,6 +196,18 @@ components: sinks: [Name=string]: {
 											if codec == "avro" {
 												avro: "Avro encoded event with a given schema."
 											}
+											if codec == "cef" {
+												cef: "ArcSight [CEF](\(urls.arcsight_cef)) encoded event. The header is built from the `deviceVendor`, `deviceProduct`, `deviceVersion`, `deviceEventClassId`, `name` and `severity` fields, which are required, and `cefVersion`, which defaults to `0`. All other fields are encoded as extensions."
+											}
+											if codec == "msgpack" {
+												msgpack: "[MessagePack](\(urls.msgpack)) encoded event. Timestamps are encoded with the [timestamp extension type](\(urls.msgpack_timestamp)). When no framing is configured, messages are written back to back, since MessagePack values are self-delimiting."
+											}
+											if codec == "gelf" {
+												gelf: "[GELF](\(urls.graylog_gelf)) encoded event. Fields without an underscore prefix, other than the GELF standard fields, are prefixed with one. When no framing is configured, stream based sinks delimit messages with a null byte, as expected by Graylog's TCP GELF inputs."
+											}
+											if codec == "parquet" {
+												parquet: "[Apache Parquet](\(urls.parquet)) file holding the whole batch of events, with a schema that is either configured or inferred from the first batch. The objects are not compressed further, as Parquet compresses their pages itself."
+											}
 										}
 									}
 								}
@@ -224,6 +236,47 @@ components: sinks: [Name=string]: {
 											}
 										}
 									}
+									if codec == "parquet" {
+										parquet: {
+											description:   "Options for the `parquet` codec."
+											required:      false
+											relevant_when: "codec = `parquet`"
+											type: object: options: {
+												schema: {
+													description: "The Parquet message type the events are written with. Fields missing from the events are null, or the zero value of their type when required. When not set, the schema is inferred from the fields of the first batch, with all of them optional."
+													required:    false
+													type: string: {
+														default: null
+														examples: [
+															"""
+															message log { required binary message (UTF8); optional int64 timestamp (TIMESTAMP_MICROS); }
+															""",
+														]
+													}
+												}
+												compression: {
+													description: "The compression codec of the pages of the Parquet files."
+													required:    false
+													type: string: {
+														default: "snappy"
+														enum: {
+															none:   "The pages are not compressed."
+															snappy: "The pages are compressed with Snappy."
+															gzip:   "The pages are compressed with gzip."
+														}
+													}
+												}
+												row_group_size: {
+													description: "The maximum number of events in each row group of the Parquet files."
+													required:    false
+													type: uint: {
+														default: 10000
+														unit:    "events"
+													}
+												}
+											}
+										}
+									}
 								}
 							}
 
@@ -0,0 +1,96 @@
+package metadata
+
+components: sinks: amqp: {
+	title: "AMQP"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					enum: ["json", "text"]
+				}
+			}
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: false
+				can_verify_hostname:    false
+				enabled_default:        false
+			}
+			to: components._amqp.features.send.to
+		}
+	}
+
+	support: components._amqp.support
+
+	configuration: components._amqp.configuration & {
+		exchange: {
+			description: "The exchange to publish messages to. An empty string publishes to the default exchange."
+			required:    true
+			type: string: {
+				examples: ["", "logs", "logs-{{ application }}"]
+				syntax: "template"
+			}
+		}
+		routing_key: {
+			description: "The routing key of the published messages."
+			common:      true
+			required:    false
+			type: string: {
+				default: ""
+				examples: ["vector", "logs.{{ host }}"]
+				syntax: "template"
+			}
+		}
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	how_it_works: components._amqp.how_it_works & {
+		publisher_confirms: {
+			title: "Publisher confirms"
+			body:  """
+				Vector puts the channel it publishes on in confirm mode, and only considers an event delivered once the
+				broker confirms its message. Messages the broker negatively acknowledges are retried, while messages
+				that can't be routed to an existing exchange close the channel and fail the events. Each event is
+				published as its own message, and the `request.concurrency` option limits how many messages await their
+				confirmation at once.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_discarded_total:           components.sources.internal_metrics.output.metrics.events_discarded_total
+	}
+}
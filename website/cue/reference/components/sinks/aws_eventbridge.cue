@@ -0,0 +1,153 @@
+package metadata
+
+components: sinks: aws_eventbridge: components._aws & {
+	title: "AWS EventBridge"
+
+	description: """
+		Publishes logs to [Amazon EventBridge](\(urls.aws_eventbridge)) event buses, as the
+		`detail` of EventBridge events whose source, detail type and event bus are rendered from
+		the logs.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["AWS"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    256_000
+				max_events:   10
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.aws_eventbridge
+
+				interface: {
+					socket: {
+						api: {
+							title: "Amazon EventBridge PutEvents API"
+							url:   urls.aws_eventbridge_put_events
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		detail_type: {
+			description: "The detail type of the EventBridge events."
+			required:    true
+			type: string: {
+				examples: ["Order Created", "{{ type }}"]
+				syntax: "template"
+			}
+		}
+		event_bus: {
+			common:      true
+			description: "The name or ARN of the event bus the events are published to. By default, the events are published to the default event bus of the account."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["my-bus", "arn:aws:events:us-east-1:123456789012:event-bus/{{ team }}"]
+				syntax: "template"
+			}
+		}
+		source: {
+			description: "The source of the EventBridge events."
+			required:    true
+			type: string: {
+				examples: ["com.example.orders", "com.example.{{ service }}"]
+				syntax: "template"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		event_detail: {
+			title: "Event detail"
+			body: """
+				Each log is published as an EventBridge event whose `detail` is the log encoded as a
+				JSON object, and whose `time` is the timestamp of the log. The logs whose source,
+				detail type or event bus fail to render are dropped.
+				"""
+		}
+
+		partial_failures: {
+			title: "Partial failures"
+			body: """
+				EventBridge reports the outcome of each entry of a `PutEvents` request. The entries
+				that failed because they were throttled or because of an internal failure are
+				published again with the retries of the request, without the entries that were
+				already published. The entries rejected for another reason, such as an invalid detail
+				or a missing permission on the event bus, are not retried, and the events of their
+				batch are marked as rejected.
+				"""
+		}
+	}
+
+	permissions: iam: [
+		{
+			platform: "aws"
+			_service:  "events"
+			_docs_tag: "eventbridge"
+
+			policies: [
+				{
+					_action: "DescribeEventBus"
+					required_for: ["healthcheck"]
+				},
+				{
+					_action: "PutEvents"
+				},
+			]
+		},
+	]
+
+	telemetry: metrics: {
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_discarded_total:           components.sources.internal_metrics.output.metrics.events_discarded_total
+	}
+}
@@ -33,7 +33,7 @@ components: sinks: aws_s3: components._aws & {
 				codec: {
 					enabled: true
 					framing: true
-					enum: ["json", "text"]
+					enum: ["json", "text", "parquet"]
 				}
 			}
 			proxy: enabled: true
@@ -186,14 +186,42 @@ components: sinks: aws_s3: components._aws & {
 		key_prefix: {
 			category:    "File Naming"
 			common:      true
-			description: "A prefix to apply to all object key names. This should be used to partition your objects, and it's important to end this value with a `/` if you want this to be the root S3 \"folder\"."
+			description: "A prefix to apply to all object key names. This should be used to partition your objects, and it's important to end this value with a `/` if you want this to be the root S3 \"folder\". The `{{ partition_date }}`, `{{ partition_year }}`, `{{ partition_month }}`, `{{ partition_day }}` and `{{ partition_hour }}` helpers expand to the Hive-style partitions `date=%F`, `year=%Y`, `month=%m`, `day=%d` and `hour=%H` respectively."
 			required:    false
 			type: string: {
 				default: "date=%F/"
-				examples: ["date=%F/", "date=%F/hour=%H/", "year=%Y/month=%m/day=%d/", "application_id={{ application_id }}/date=%F/"]
+				examples: ["date=%F/", "date=%F/hour=%H/", "year=%Y/month=%m/day=%d/", "application_id={{ application_id }}/date=%F/", "{{ partition_date }}/{{ partition_hour }}/"]
 				syntax: "template"
 			}
 		}
+		manifest: {
+			common:      false
+			description: "Writes an object under the key prefix of each partition once all of its objects have been uploaded, so that downstream jobs only read complete partitions. A partition is complete once it has no upload in flight and no new object for `settle_secs`, or when Vector shuts down. The marker or manifest isn't written when an object of the partition failed to be uploaded."
+			required:    false
+			type: object: options: {
+				mode: {
+					common:      true
+					description: "The kind of object written for each complete partition."
+					required:    false
+					type: string: {
+						default: "success_marker"
+						enum: {
+							success_marker: "An empty `_SUCCESS` object, as written by Hadoop and Spark jobs."
+							manifest:       "A `_manifest.json` object listing the key, number of events, and size of the objects of the partition written since its previous manifest."
+						}
+					}
+				}
+				settle_secs: {
+					common:      false
+					description: "How long a partition must go without new objects before it's considered complete. This should be longer than `batch.timeout_secs`, so that the events still being batched for the partition are flushed first."
+					required:    false
+					type: uint: {
+						default: 60
+						unit:    "seconds"
+					}
+				}
+			}
+		}
 		server_side_encryption: {
 			category:    "Encryption"
 			common:      false
@@ -321,6 +349,20 @@ components: sinks: aws_s3: components._aws & {
 				"""
 		}
 
+		partition_manifests: {
+			title: "Partition manifests"
+			body:  """
+				When the [`manifest`](#manifest) option is set, Vector tracks the objects of each
+				partition, the rendered [`key_prefix`](#key_prefix), and writes a `_SUCCESS` marker
+				or a `_manifest.json` object under the prefix once the partition is complete. Query
+				engines such as Athena and Spark skip objects whose name starts with an underscore,
+				so these don't need to be excluded from the tables.
+
+				Events arriving late for a partition whose marker was already written produce new
+				objects, followed by a new marker or manifest once the partition settles again.
+				"""
+		}
+
 		object_tags_and_metadata: {
 			title: "Object Tags & metadata"
 			body:  """
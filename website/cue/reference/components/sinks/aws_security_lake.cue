@@ -0,0 +1,198 @@
+package metadata
+
+components: sinks: aws_security_lake: components._aws & {
+	title: "AWS Security Lake"
+
+	description: """
+		Writes logs to [Amazon Security Lake](\(urls.aws_security_lake)) as a custom source, converting
+		them to records of the [Open Cybersecurity Schema Framework (OCSF)](\(urls.ocsf)) stored as
+		Parquet objects in the S3 bucket of the data lake.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["AWS"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    100_000_000
+				timeout_secs: 300.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.aws_security_lake
+
+				interface: {
+					socket: {
+						api: {
+							title: "AWS S3 API"
+							url:   urls.aws_s3_endpoints
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The custom source must be registered in Security Lake, which creates the AWS Glue
+				table of its OCSF event class and the role allowed to write its objects.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		account_id: {
+			description: "The ID of the AWS account the events come from, which partitions the objects with the region."
+			required:    true
+			type: string: {
+				examples: ["123456789012"]
+			}
+		}
+		bucket: {
+			description: "The S3 bucket of the data lake. Do not include a leading `s3://` or a trailing `/`."
+			required:    true
+			type: string: {
+				examples: ["aws-security-data-lake-us-east-1-abcdefghij"]
+			}
+		}
+		class_uid: {
+			description: "The OCSF event class of the records, used for the logs that don't set their `class_uid` attribute."
+			required:    true
+			type: uint: {
+				examples: [1001, 3002]
+				unit: null
+			}
+		}
+		compression: {
+			common:      false
+			description: "The compression codec of the pages of the Parquet objects."
+			required:    false
+			type: string: {
+				default: "snappy"
+				enum: {
+					none:   "The pages are not compressed."
+					snappy: "The pages are compressed with Snappy."
+					gzip:   "The pages are compressed with gzip."
+				}
+			}
+		}
+		product_name: {
+			common:      false
+			description: "The product reported in the metadata of the records that don't set their own."
+			required:    false
+			type: string: {
+				default: "Vector"
+			}
+		}
+		source_name: {
+			description: "The name of the custom source the events are written as, under which their objects are stored in the `ext/` prefix of the bucket."
+			required:    true
+			type: string: {
+				examples: ["vector"]
+			}
+		}
+		vendor_name: {
+			common:      false
+			description: "The vendor of the product reported in the metadata of the records that don't set their own."
+			required:    false
+			type: string: {
+				default: "Vector"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		ocsf_records: {
+			title: "OCSF records"
+			body: """
+				Each log is written as a record with the attributes of the OCSF base event, which are
+				read from the fields of the same name, such as `activity_id`, `severity_id`,
+				`metadata.product.name` or `cloud.region`. The required attributes the logs don't set
+				are filled in: the class comes from the `class_uid` option, the category and type are
+				derived from the class and activity, the `time` attribute is read from the timestamp of
+				the logs, and the cloud attributes are those of the data lake.
+
+				The fields that aren't part of the schema, or whose value can't be converted to the type
+				of their attribute, are kept in the `unmapped` attribute, as a map of strings. Logs are
+				usually reshaped with a `remap` transform before this sink so that their fields match
+				the attributes of their event class.
+				"""
+		}
+
+		object_naming: {
+			title: "Object naming"
+			body: """
+				Objects are written under the prefix Security Lake expects for custom sources,
+				partitioned by region, account and day of the events:
+
+				```text
+				ext/<source_name>/region=<region>/accountId=<account_id>/eventDay=<YYYYMMDD>/<uuidv4>.parquet
+				```
+				"""
+		}
+	}
+
+	permissions: iam: [
+		{
+			platform:      "aws"
+			_service:      "s3"
+			_docs_tag:     "AmazonS3"
+			_url_fragment: "API"
+
+			policies: [
+				{
+					_action: "HeadBucket"
+					required_for: ["healthcheck"]
+				},
+				{
+					_action: "PutObject"
+				},
+			]
+		},
+	]
+
+	telemetry: metrics: {
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_discarded_total:           components.sources.internal_metrics.output.metrics.events_discarded_total
+	}
+}
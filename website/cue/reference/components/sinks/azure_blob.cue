@@ -33,7 +33,7 @@ components: sinks: azure_blob: {
 				codec: {
 					enabled: true
 					framing: true
-					enum: ["json", "text"]
+					enum: ["json", "text", "parquet"]
 				}
 			}
 			request: {
@@ -0,0 +1,187 @@
+package metadata
+
+components: sinks: azure_data_explorer: {
+	title: "Azure Data Explorer"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Azure"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       true
+				max_bytes:    10_000_000
+				timeout_secs: 300.0
+			}
+			compression: {
+				enabled: true
+				default: "gzip"
+				algorithms: ["none", "gzip"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					framing: true
+					enum: ["json", "text"]
+				}
+			}
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.azure_data_explorer
+
+				interface: {
+					socket: {
+						api: {
+							title: "Azure Data Explorer queued ingestion"
+							url:   urls.azure_data_explorer_queued_ingestion
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		ingestion_endpoint: {
+			description: "The ingestion endpoint of the cluster, which is the cluster URI prefixed with `ingest-`."
+			required:    true
+			type: string: {
+				examples: ["https://ingest-mycluster.westeurope.kusto.windows.net"]
+			}
+		}
+		database: {
+			description: "The database the events are ingested into."
+			required:    true
+			type: string: {
+				examples: ["logs", "{{ environment }}"]
+				syntax: "template"
+			}
+		}
+		table: {
+			description: "The table the events are ingested into."
+			required:    true
+			type: string: {
+				examples: ["events", "{{ application }}"]
+				syntax: "template"
+			}
+		}
+		format: {
+			common:      true
+			description: "The format Azure Data Explorer parses the ingested data as. It must match the output of the configured codec."
+			required:    false
+			type: string: {
+				default: "json"
+				enum: {
+					json: "JSON objects, one per line, such as the ones of the `json` codec."
+					csv:  "CSV records, one per line."
+				}
+			}
+		}
+		ingestion_mapping: {
+			common:      true
+			description: "The name of the ingestion mapping, created on the table beforehand, that maps the ingested data to the columns of the table. The kind of the mapping must match the `format`."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["events_json_mapping"]
+			}
+		}
+		auth: {
+			common:      false
+			description: "The credentials of an Azure AD application with the `Ingestor` role on the database. When unset, credentials are read in this order: [EnvironmentCredential](https://docs.rs/azure_identity/latest/azure_identity/struct.DefaultAzureCredential.html), ManagedIdentityCredential, AzureCliCredential."
+			required:    false
+			type: object: options: {
+				tenant_id: {
+					description: "The ID of the Azure AD tenant of the application."
+					required:    true
+					type: string: {
+						examples: ["72f988bf-86f1-41af-91ab-2d7cd011db47"]
+					}
+				}
+				client_id: {
+					description: "The client ID of the application."
+					required:    true
+					type: string: {
+						examples: ["8d2d5d1e-0f30-4e6a-a1b2-4f6a2f0b4e3d"]
+					}
+				}
+				client_secret: {
+					description: "The client secret of the application."
+					required:    true
+					type: string: {
+						examples: ["${AZURE_CLIENT_SECRET}"]
+					}
+				}
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		queued_ingestion: {
+			title: "Queued ingestion"
+			body:  """
+				Vector uses [queued ingestion](\(urls.azure_data_explorer_queued_ingestion)). Each batch
+				is uploaded as a blob to the temporary storage of the cluster, and an ingestion message
+				referencing the blob is posted to one of its ingestion queues. The cluster then ingests
+				the blob asynchronously.
+
+				Events are acknowledged once the ingestion message is enqueued. Failures that happen
+				during the ingestion itself, such as data not matching the ingestion mapping, are
+				reported by the cluster rather than by Vector.
+
+				The storage and queue URIs are fetched from the ingestion endpoint with the
+				`.get ingestion resources` command and refreshed every hour.
+				"""
+		}
+		partitioning: {
+			title: "Partitioning"
+			body:  """
+				The `database` and `table` options are templates. Events are batched separately for
+				each database and table they render to, and events for which either template fails
+				to render are dropped.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_discarded_total:           components.sources.internal_metrics.output.metrics.events_discarded_total
+		processing_errors_total:          components.sources.internal_metrics.output.metrics.processing_errors_total
+	}
+}
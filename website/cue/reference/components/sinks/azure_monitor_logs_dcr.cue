@@ -0,0 +1,171 @@
+package metadata
+
+components: sinks: azure_monitor_logs_dcr: {
+	title: "Azure Monitor Logs Ingestion"
+
+	description: """
+		Sends logs to Log Analytics workspaces, including the tables of Microsoft Sentinel, with the
+		[Logs Ingestion API](\(urls.azure_monitor_logs_ingestion_api)) of Azure Monitor, which replaces
+		the deprecated HTTP Data Collector API used by the `azure_monitor_logs` sink.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Azure"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    1_000_000
+				timeout_secs: 1.0
+			}
+			compression: {
+				enabled: true
+				default: "gzip"
+				algorithms: ["none", "gzip"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.azure_monitor_logs
+
+				interface: {
+					socket: {
+						api: {
+							title: "Azure Monitor Logs Ingestion API"
+							url:   urls.azure_monitor_logs_ingestion_api
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		endpoint: {
+			description: "The logs ingestion endpoint of the data collection endpoint (DCE) the logs are sent through."
+			required:    true
+			type: string: {
+				examples: ["https://my-dce-a1b2.westeurope-1.ingest.monitor.azure.com"]
+			}
+		}
+		dcr_immutable_id: {
+			description: "The immutable ID of the [data collection rule](\(urls.azure_monitor_data_collection_rules)) (DCR) the logs are sent to."
+			required:    true
+			type: string: {
+				examples: ["dcr-00000000000000000000000000000000"]
+			}
+		}
+		stream_name: {
+			description: "The stream of the data collection rule the logs are sent to, as declared in its `streamDeclarations`."
+			required:    true
+			type: string: {
+				examples: ["Custom-VectorLogs_CL", "Custom-{{ application }}_CL"]
+				syntax: "template"
+			}
+		}
+		time_generated_key: {
+			common:      false
+			description: "The field the timestamp of the logs is sent as, which is read by data collection rules into the `TimeGenerated` column of the tables."
+			required:    false
+			type: string: {
+				default: "TimeGenerated"
+			}
+		}
+		auth: {
+			common:      false
+			description: "The credentials of an Azure AD application with the `Monitoring Metrics Publisher` role on the data collection rule. When unset, credentials are read in this order: [EnvironmentCredential](https://docs.rs/azure_identity/latest/azure_identity/struct.DefaultAzureCredential.html), ManagedIdentityCredential, AzureCliCredential."
+			required:    false
+			type: object: options: {
+				tenant_id: {
+					description: "The ID of the Azure AD tenant of the application."
+					required:    true
+					type: string: {
+						examples: ["72f988bf-86f1-41af-91ab-2d7cd011db47"]
+					}
+				}
+				client_id: {
+					description: "The client ID of the application."
+					required:    true
+					type: string: {
+						examples: ["8d2d5d1e-0f30-4e6a-a1b2-4f6a2f0b4e3d"]
+					}
+				}
+				client_secret: {
+					description: "The client secret of the application."
+					required:    true
+					type: string: {
+						examples: ["${AZURE_CLIENT_SECRET}"]
+					}
+				}
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		data_collection_rules: {
+			title: "Data collection rules"
+			body:  """
+				Each batch is posted as a JSON array of the logs to a stream of the data collection rule,
+				whose columns must be declared in its `streamDeclarations`. The rule then transforms the
+				logs with its `transformKql` and sends them to the tables of its destinations, such as
+				custom tables of a Log Analytics workspace or the tables of Microsoft Sentinel.
+
+				Events are acknowledged once the Logs Ingestion API accepts them. Logs that don't match
+				the stream declaration or that the transformation fails on are reported by Azure
+				Monitor rather than by Vector.
+				"""
+		}
+		partitioning: {
+			title: "Partitioning"
+			body:  """
+				The `stream_name` option is a template. Events are batched separately for each stream
+				they render to, and events for which the template fails to render are dropped.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_discarded_total:           components.sources.internal_metrics.output.metrics.events_discarded_total
+		processing_errors_total:          components.sources.internal_metrics.output.metrics.processing_errors_total
+	}
+}
@@ -53,7 +53,7 @@ components: sinks: clickhouse: {
 							url:   urls.clickhouse_http
 						}
 						direction: "outgoing"
-						protocols: ["http"]
+						protocols: ["http", "tcp"]
 						ssl: "optional"
 					}
 				}
@@ -76,6 +76,32 @@ components: sinks: clickhouse: {
 			password_example: "${CLICKHOUSE_PASSWORD}"
 			username_example: "${CLICKHOUSE_USERNAME}"
 		}}
+		columns: {
+			common:      false
+			description: "The columns the events are inserted into with the `native` protocol, along with their ClickHouse types. The values of the columns are the ones of the fields of the same name. The values that are missing, or can't be converted to the type of their column, are inserted as null into `Nullable` columns, and as the default value of the type otherwise. The columns of the table that are not listed are set to their default values."
+			required:    false
+			type: object: {
+				examples: [
+					{
+						"timestamp": "DateTime64(3)"
+						"message":   "String"
+						"status":    "Nullable(UInt16)"
+						"tags":      "Array(String)"
+					},
+				]
+				options: {
+					"*": {
+						common:      false
+						description: "The type of the column, which is one of `String`, `Bool`, `Int8` to `Int64`, `UInt8` to `UInt64`, `Float32`, `Float64`, `DateTime` and `DateTime64`, along with the `Nullable` and `Array` versions of those types."
+						required:    true
+						type: string: {
+							examples: ["String", "Nullable(UInt16)", "DateTime64(3, 'UTC')"]
+							syntax: "literal"
+						}
+					}
+				}
+			}
+		}
 		database: {
 			common:      true
 			description: "The database that contains the table that data will be inserted into."
@@ -89,7 +115,19 @@ components: sinks: clickhouse: {
 			description: "The endpoint of the [Clickhouse](\(urls.clickhouse)) server."
 			required:    true
 			type: string: {
-				examples: ["http://localhost:8123"]
+				examples: ["http://localhost:8123", "tcp://localhost:9000"]
+			}
+		}
+		protocol: {
+			common:      false
+			description: "The protocol the events are inserted with."
+			required:    false
+			type: string: {
+				default: "http"
+				enum: {
+					http:   "The [HTTP interface](\(urls.clickhouse_http)), the events being inserted as JSON objects."
+					native: "The native TCP protocol, the events being inserted as blocks of columns, compressed with LZ4 unless `compression` is `none`. The columns are configured with `columns`, and the connection is secured with TLS when `tls` is set."
+				}
 			}
 		}
 		table: {
@@ -107,6 +145,23 @@ components: sinks: clickhouse: {
 		}
 	}
 
+	how_it_works: {
+		native_protocol: {
+			title: "Native protocol"
+			body:  """
+				When `protocol` is `native`, the batches of events are inserted as blocks of
+				columns over the native TCP protocol of ClickHouse, which spares the server the
+				parsing of the JSON objects and is considerably faster for high volumes of events.
+				The blocks are compressed with LZ4 unless `compression` is `none`.
+
+				Each column listed in `columns` takes its values from the field of the same name,
+				converted to the type of the column. The types must match the ones of the columns
+				of the table, which the server checks before each insert; the events inserted in
+				columns of the wrong type are rejected.
+				"""
+		}
+	}
+
 	input: {
 		logs:    true
 		metrics: null
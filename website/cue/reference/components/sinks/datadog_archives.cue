@@ -131,6 +131,7 @@ components: sinks: datadog_archives: {
 				options: {
 					acl:              sinks.gcp_cloud_storage.configuration.acl
 					credentials_path: sinks.gcp_cloud_storage.configuration.credentials_path
+					encryption:       sinks.gcp_cloud_storage.configuration.encryption
 					metadata:         sinks.gcp_cloud_storage.configuration.metadata
 					storage_class:    sinks.gcp_cloud_storage.configuration.storage_class
 				}
@@ -65,7 +65,8 @@ components: sinks: elasticsearch: {
 		requirements: [
 			#"""
 				Elasticsearch's Data streams feature requires Vector to be configured with the `create` `bulk.action`.
-				This is *not* enabled by default.
+				This is only selected by default for the `data_stream` mode, and for the indices matching the data
+				streams of the built-in index templates of Elasticsearch, such as `logs-*-*`.
 				"""#,
 		]
 		warnings: []
@@ -80,6 +81,13 @@ components: sinks: elasticsearch: {
 			type: object: {
 				examples: []
 				options: components._aws.configuration.auth.type.object.options & {
+					api_key: {
+						description: "The [API key](\(urls.elasticsearch_api_keys)) to authenticate with, as the base64 `encoded` credentials returned when creating it, or the base64 encoding of its ID and key joined by `:`."
+						required:    true
+						type: string: {
+							examples: ["${ELASTICSEARCH_API_KEY}"]
+						}
+					}
 					password: {
 						description: "The basic authentication password."
 						required:    true
@@ -92,11 +100,20 @@ components: sinks: elasticsearch: {
 						required:    true
 						type: string: {
 							enum: {
-								aws:   "Authentication strategy used for [AWS' hosted Elasticsearch service](\(urls.aws_elasticsearch))."
-								basic: "The [basic authentication strategy](\(urls.basic_auth))."
+								api_key: "Authentication with an [API key](\(urls.elasticsearch_api_keys))."
+								aws:     "Authentication strategy used for [AWS' hosted Elasticsearch service](\(urls.aws_elasticsearch))."
+								basic:   "The [basic authentication strategy](\(urls.basic_auth))."
+								bearer:  "Authentication with a bearer token, such as the [token of a service account](\(urls.elasticsearch_service_tokens))."
 							}
 						}
 					}
+					token: {
+						description: "The bearer token to authenticate with."
+						required:    true
+						type: string: {
+							examples: ["${ELASTICSEARCH_SERVICE_TOKEN}"]
+						}
+					}
 					user: {
 						description: "The basic authentication user name."
 						required:    true
@@ -138,10 +155,12 @@ components: sinks: elasticsearch: {
 						description: """
 							Action to use when making requests to the [Elasticsearch Bulk API](\(urls.elasticsearch_bulk)).
 							Currently, Vector only supports `index` and `create`. `update` and `delete` actions are not supported.
+							By default, `create` is used for the indices matching the `logs-*-*`, `metrics-*-*`, `synthetics-*-*`
+							and `traces-*-*` data streams of the built-in index templates of Elasticsearch, and `index` otherwise.
 							"""
 						required:    false
 						type: string: {
-							default: "index"
+							default: null
 							examples: ["index", "create", "{{ action }}"]
 							syntax: "template"
 						}
@@ -176,6 +195,98 @@ components: sinks: elasticsearch: {
 						required: false
 						type: bool: default: true
 					}
+					bootstrap: {
+						common:      false
+						description: "The index template and ILM policy of the data streams, created by the healthcheck when they don't exist, as Elastic Agent does for the data streams of its integrations. Existing templates and policies are left as is."
+						required:    false
+						type: object: {
+							examples: []
+							options: {
+								composed_of: {
+									common:      false
+									description: "The component templates the index template is composed of."
+									required:    false
+									type: array: {
+										default: []
+										items: type: string: examples: ["logs-mappings", "logs-settings"]
+									}
+								}
+								ilm_policy: {
+									common:      false
+									description: "The ILM policy of the backing indices of the data streams, set in the index template."
+									required:    false
+									type: object: {
+										examples: []
+										options: {
+											delete_after: {
+												common:      false
+												description: "How long after their rollover the backing indices are deleted. They are never deleted by default."
+												required:    false
+												type: string: {
+													default: null
+													examples: ["30d"]
+												}
+											}
+											name: {
+												common:      false
+												description: "The name of the policy, which defaults to the name of the index template."
+												required:    false
+												type: string: {
+													default: null
+													examples: ["vector-logs"]
+												}
+											}
+											rollover_max_age: {
+												common:      false
+												description: "The maximum age of the write index of the data streams before it is rolled over."
+												required:    false
+												type: string: {
+													default: "30d"
+													examples: ["1d"]
+												}
+											}
+											rollover_max_primary_shard_size: {
+												common:      false
+												description: "The maximum size of the primary shards of the write index of the data streams before it is rolled over."
+												required:    false
+												type: string: {
+													default: "50gb"
+													examples: ["10gb"]
+												}
+											}
+										}
+									}
+								}
+								index_patterns: {
+									common:      false
+									description: "The patterns of the data streams the index template applies to, which default to `<type>-<dataset>-*`. Required when `type` or `dataset` is templated."
+									required:    false
+									type: array: {
+										default: null
+										items: type: string: examples: ["logs-nginx.*-*"]
+									}
+								}
+								priority: {
+									common:      false
+									description: "The priority of the index template, higher than the one of the built-in index templates of Elasticsearch by default."
+									required:    false
+									type: uint: {
+										default: 200
+										unit:    null
+									}
+								}
+								template_name: {
+									common:      false
+									description: "The name of the index template, which defaults to `<type>-<dataset>`. Required when `type` or `dataset` is templated."
+									required:    false
+									type: string: {
+										default: null
+										examples: ["logs-nginx"]
+									}
+								}
+							}
+						}
+					}
 					dataset: {
 						common:      false
 						description: "The data stream dataset used to construct the data stream at index time."
@@ -335,6 +446,10 @@ components: sinks: elasticsearch: {
 				To use [Data streams](\(urls.elasticsearch_data_streams)), set the `mode` to
 				`data_stream`. Use the combination of `data_stream.type`, `data_stream.dataset` and
 				`data_stream.namespace` instead of `index`.
+
+				The data streams are created by Elasticsearch from the index template matching their name. The
+				healthcheck can create this template, and the ILM policy of their backing indices, with the
+				`data_stream.bootstrap` options.
 				"""
 		}
 
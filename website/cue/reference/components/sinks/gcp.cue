@@ -17,6 +17,11 @@ components: sinks: [Name=string]: {
 			}
 		}
 
+		telemetry: metrics: {
+			gcp_token_refresh_attempts_total: components.sources.internal_metrics.output.metrics.gcp_token_refresh_attempts_total
+			gcp_token_refresh_failures_total: components.sources.internal_metrics.output.metrics.gcp_token_refresh_failures_total
+		}
+
 		how_it_works: {
 			gcp_authentication: {
 				title: "GCP Authentication"
@@ -30,10 +35,28 @@ components: sinks: [Name=string]: {
 						1. If the [`GOOGLE_APPLICATION_CREDENTIALS`](#google_application_credentials) environment variable is set.
 						1. Finally, Vector will check for an [instance service account](\(urls.gcp_authentication_service_account)).
 
+						The credentials file can either be a service account key or an external account
+						configuration, as generated for [workload identity federation](\(urls.gcp_workload_identity_federation)).
+						The latter lets Vector running on AWS, Azure, or any platform issuing OIDC tokens
+						authenticate without a long-lived service account key.
+
 						If credentials aren't found, Vector's health checks fail and an error is
 						[logged](\(urls.vector_monitoring)).
 						"""
 			}
+
+			gcp_token_refresh: {
+				title: "Token refresh"
+				body:  """
+						Authentication tokens are renewed in the background, halfway through their
+						lifetime. Failed renewals are retried with an exponential backoff, and a request
+						rejected as unauthorized triggers an immediate renewal. After three consecutive
+						failed renewals, the health check reports the authentication as failing.
+
+						Renewals are tracked by the `gcp_token_refresh_attempts_total` and
+						`gcp_token_refresh_failures_total` internal metrics.
+						"""
+			}
 		}
 	}
 }
@@ -0,0 +1,170 @@
+package metadata
+
+components: sinks: gcp_bigquery: {
+	title: "GCP BigQuery"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["GCP"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				max_events:   500
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled:        true
+				rate_limit_num: 1000
+				headers:        false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.gcp_bigquery
+
+				interface: {
+					socket: {
+						api: {
+							title: "BigQuery streaming insert API"
+							url:   urls.gcp_bigquery_insert_all
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		api_key: configuration._gcp_api_key
+		credentials_path: {
+			category:    "Auth"
+			common:      true
+			description: "The filename for a Google Cloud service account credentials JSON file used to authenticate access to the BigQuery API. If this is unset, Vector checks the `GOOGLE_APPLICATION_CREDENTIALS` environment variable for a filename.\n\nIf no filename is named, Vector will attempt to fetch an instance service account for the compute instance the program is running on. If Vector is not running on a GCE instance, you must define a credentials file as above."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["/path/to/credentials.json"]
+			}
+		}
+		dataset: {
+			description: "The dataset containing the table to insert rows into."
+			required:    true
+			type: string: {
+				examples: ["my_dataset", "{{ team }}_logs"]
+				syntax: "template"
+			}
+		}
+		endpoint: {
+			common:      false
+			description: "The BigQuery API endpoint to send data to."
+			required:    false
+			type: string: {
+				default: "https://bigquery.googleapis.com"
+				examples: ["https://bigquery.googleapis.com"]
+			}
+		}
+		ignore_unknown_values: {
+			common:      false
+			description: "Whether to ignore fields that don't match the table schema instead of rejecting the row."
+			required:    false
+			type: bool: default: false
+		}
+		insert_id_field: {
+			common:      false
+			description: "The field used as the [insert ID](\(urls.gcp_bigquery_insert_id)) of each row, allowing BigQuery to deduplicate rows sent more than once. Rows are inserted without an insert ID when unset or when the field is missing."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["id"]
+			}
+		}
+		project: {
+			description: "The project containing the dataset."
+			required:    true
+			type: string: {
+				examples: ["my-project"]
+			}
+		}
+		skip_invalid_rows: {
+			common:      false
+			description: "Whether to insert the valid rows of a request even when some of its rows are invalid. When disabled, a single invalid row fails the whole request."
+			required:    false
+			type: bool: default: false
+		}
+		table: {
+			description: "The table to insert rows into."
+			required:    true
+			type: string: {
+				examples: ["my_table", "logs_%Y%m%d"]
+				syntax: "template"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		schema_mapping: {
+			title: "Schema mapping"
+			body: """
+				Each event is sent as a row whose fields are the top-level fields of the event, so the
+				events must already be shaped according to the table schema, for example by using a
+				`remap` transform. Nested fields map to `RECORD` columns and timestamps are sent as
+				RFC 3339 strings, unless `encoding.timestamp_format` is set to `unix`.
+
+				The `encoding.only_fields` and `encoding.except_fields` options select the fields sent
+				as columns, and `ignore_unknown_values` lets BigQuery drop fields missing from the
+				schema rather than reject the row.
+				"""
+		}
+		rejected_rows: {
+			title: "Rejected rows"
+			body: """
+				BigQuery reports rows that could not be inserted in the response of an otherwise
+				successful request. Such requests are not retried, since they would fail the same way,
+				and their events are marked as rejected. Requests failing with a server error or
+				because of rate limiting are retried.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+	}
+}
@@ -0,0 +1,156 @@
+package metadata
+
+components: sinks: gcp_chronicle_udm: {
+	title: "GCP Chronicle UDM"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["GCP"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				max_events:   1000
+				timeout_secs: 300.0
+			}
+			compression: {
+				enabled: true
+				default: "none"
+				algorithms: ["none", "gzip"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled:        true
+				rate_limit_num: 1000
+				headers:        false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.gcp_chronicle
+
+				interface: {
+					socket: {
+						api: {
+							title: "GCP XML Interface"
+							url:   urls.gcp_xml_interface
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		api_key: configuration._gcp_api_key
+		credentials_path: {
+			category:    "Auth"
+			common:      true
+			description: "The filename for a Google Cloud service account credentials JSON file used to authenticate access to the Cloud Storage API. If this is unset, Vector checks the `GOOGLE_APPLICATION_CREDENTIALS` environment variable for a filename.\n\nIf no filename is named, Vector will attempt to fetch an instance service account for the compute instance the program is running on. If Vector is not running on a GCE instance, you must define a credentials file as above."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["/path/to/credentials.json"]
+			}
+		}
+		endpoint: {
+			common:        false
+			description:   "The endpoint to send data to."
+			relevant_when: "region is not set"
+			required:      false
+			type: string: {
+				default: null
+				examples: ["127.0.0.1:8080", "example.com:12345"]
+			}
+		}
+		region: {
+			common:        false
+			description:   "The region to send data to."
+			required:      false
+			relevant_when: "endpoint is not set"
+			type: string: {
+				default: null
+				enum: {
+					us:   "United States"
+					eu:   "Europe"
+					asia: "Asia"
+				}
+			}
+		}
+		customer_id: {
+			description: "The Unique identifier (UUID) corresponding to the Chronicle instance."
+			required:    true
+			type: string: {
+				examples: ["c8c65bfa-5f2c-42d4-9189-64bb7b939f2c"]
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		retries: {
+			title: "Retries"
+			body: """
+				Requests that fail because of quota exhaustion or a transient error are retried. When Chronicle
+				asks for a delay, either through a `Retry-After` header or `RetryInfo` error details, Vector
+				waits that long before retrying, bounded by `request.retry_max_duration_secs`.
+
+				Batches that Chronicle rejects permanently, such as those failing schema validation
+				(`INVALID_ARGUMENT`), are not retried.
+				"""
+		}
+		udm_events: {
+			title: "UDM events"
+			body: """
+				Each event is sent as-is as a Unified Data Model (UDM) event, so it must already be shaped
+				according to the [UDM schema](https://cloud.google.com/chronicle/docs/reference/udm-field-list),
+				for example by using a `remap` transform.
+
+				Before sending, Vector validates that every event contains the fields required by all UDM
+				event types: `metadata.event_type`, which must be a string, and `metadata.event_timestamp`,
+				which must be a timestamp or an RFC 3339 formatted string. Events that fail validation are
+				rejected and discarded rather than sent to Chronicle.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+	}
+}
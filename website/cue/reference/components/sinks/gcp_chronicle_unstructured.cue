@@ -20,9 +20,15 @@ components: sinks: chronicle_unstructured: {
 				enabled:      true
 				common:       false
 				max_bytes:    10_000_000
+				max_events:   1000
 				timeout_secs: 300.0
 			}
-			compression: enabled: false
+			compression: {
+				enabled: true
+				default: "none"
+				algorithms: ["none", "gzip"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
 			encoding: {
 				enabled: true
 				codec: {
@@ -104,10 +110,11 @@ components: sinks: chronicle_unstructured: {
 			}
 		}
 		customer_id: {
-			description: "The Unique identifier (UUID) corresponding to the Chronicle instance."
+			description: "The Unique identifier (UUID) corresponding to the Chronicle instance. Events rendering to different customer IDs are sent in separate requests."
 			required:    true
 			type: string: {
-				examples: ["c8c65bfa-5f2c-42d4-9189-64bb7b939f2c"]
+				examples: ["c8c65bfa-5f2c-42d4-9189-64bb7b939f2c", "{{ customer_id }}"]
+				syntax: "template"
 			}
 		}
 		log_type: {
@@ -118,6 +125,73 @@ components: sinks: chronicle_unstructured: {
 				syntax: "template"
 			}
 		}
+		fallback_log_type: {
+			common:      false
+			description: "The log type to use for events whose `log_type` template fails to render, for example because a referenced field is missing. If not set, such events are dropped."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["VECTOR_UNKNOWN"]
+			}
+		}
+		namespace: {
+			common:      false
+			description: "The user-configured environment namespace to identify the data domain the logs originated from. This is rendered for each event and added to its log entry."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["production", "{{ namespace }}"]
+				syntax: "template"
+			}
+		}
+		forwarder_stats: {
+			common:      false
+			description: "Periodically report heartbeats and delivery statistics to Chronicle's forwarder API, so that this Vector instance is shown as a collector in the Chronicle console. The forwarder API is accessed with the `https://www.googleapis.com/auth/cloud-platform` scope."
+			required:    false
+			type: object: options: {
+				forwarder_id: {
+					description: "The ID of the forwarder, as registered in Chronicle, that this sink reports as."
+					required:    true
+					type: string: {
+						examples: ["1f2e3d4c-5b6a-7980-a1b2-c3d4e5f60718"]
+					}
+				}
+				interval_secs: {
+					common:      false
+					description: "The interval between two heartbeats."
+					required:    false
+					type: uint: {
+						default: 60
+						unit:    "seconds"
+					}
+				}
+			}
+		}
+		labels: {
+			common:      false
+			description: "A set of labels to attach to each log entry. Both the keys and values are included in the entry as a list of `key`/`value` pairs, ordered by key."
+			required:    false
+			type: object: {
+				examples: [
+					{
+						"team":   "{{ team }}"
+						"source": "vector"
+					},
+				]
+				options: {
+					"*": {
+						common:      false
+						description: "The value of the label."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["{{ team }}", "vector"]
+							syntax: "template"
+						}
+					}
+				}
+			}
+		}
 	}
 
 	input: {
@@ -127,12 +201,27 @@ components: sinks: chronicle_unstructured: {
 	}
 
 	how_it_works: {
+		retries: {
+			title: "Retries"
+			body: """
+				Requests that fail because of quota exhaustion or a transient error are retried. When Chronicle
+				asks for a delay, either through a `Retry-After` header or `RetryInfo` error details, Vector
+				waits that long before retrying, bounded by `request.retry_max_duration_secs`.
+
+				Batches that Chronicle rejects permanently, such as those failing schema validation
+				(`INVALID_ARGUMENT`), are not retried.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
-		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
-		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
-		events_discarded_total:           components.sources.internal_metrics.output.metrics.events_discarded_total
-		processing_errors_total:          components.sources.internal_metrics.output.metrics.processing_errors_total
+		chronicle_bytes_sent_total:               components.sources.internal_metrics.output.metrics.chronicle_bytes_sent_total
+		chronicle_events_sent_total:              components.sources.internal_metrics.output.metrics.chronicle_events_sent_total
+		chronicle_fallback_log_type_events_total: components.sources.internal_metrics.output.metrics.chronicle_fallback_log_type_events_total
+		chronicle_requests_sent_total:            components.sources.internal_metrics.output.metrics.chronicle_requests_sent_total
+		component_sent_events_total:              components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total:         components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_discarded_total:                   components.sources.internal_metrics.output.metrics.events_discarded_total
+		processing_errors_total:                  components.sources.internal_metrics.output.metrics.processing_errors_total
 	}
 }
@@ -33,7 +33,7 @@ components: sinks: gcp_cloud_storage: {
 				codec: {
 					enabled: true
 					framing: true
-					enum: ["json", "text"]
+					enum: ["json", "text", "parquet"]
 				}
 			}
 			proxy: enabled: true
@@ -108,6 +108,35 @@ components: sinks: gcp_cloud_storage: {
 				examples: ["/path/to/credentials.json"]
 			}
 		}
+		encryption: {
+			category:    "Storage"
+			common:      false
+			description: "Server-side encryption settings for the created objects. Only one of `encryption_key` and `kms_key_name` can be set."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					encryption_key: {
+						common:      false
+						description: "A base64-encoded AES-256 customer-supplied encryption key (CSEK) used to encrypt the created objects. See the [GCS customer-supplied encryption keys](\(urls.gcs_csek)) documentation for more details."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["${GCS_ENCRYPTION_KEY}"]
+						}
+					}
+					kms_key_name: {
+						common:      false
+						description: "The resource name of the Cloud KMS key used to encrypt the created objects. See the [GCS customer-managed encryption keys](\(urls.gcs_cmek)) documentation for more details."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["projects/my-project/locations/global/keyRings/my-ring/cryptoKeys/my-key"]
+						}
+					}
+				}
+			}
+		}
 		filename_append_uuid: {
 			category:    "File Naming"
 			common:      false
@@ -226,6 +255,18 @@ components: sinks: gcp_cloud_storage: {
 				"""
 		}
 
+		encryption: {
+			title: "Encryption"
+			body:  """
+					Objects can be encrypted with either a customer-supplied encryption key
+					(CSEK) or a customer-managed Cloud KMS key (CMEK) via the `encryption`
+					option. The key is sent along with every upload, so the objects written
+					by Vector are encrypted regardless of the bucket's default encryption
+					settings. When using a KMS key, the service account used by Cloud Storage
+					must be allowed to use it.
+					"""
+		}
+
 		storage_class: {
 			title: "Storage Class"
 			body:  """
@@ -0,0 +1,128 @@
+package metadata
+
+components: sinks: gcp_cloud_trace: {
+	title: "GCP Cloud Trace"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["GCP"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled:        true
+				rate_limit_num: 1000
+				headers:        false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.gcp_cloud_trace
+
+				interface: {
+					socket: {
+						api: {
+							title: "Cloud Trace v2 API"
+							url:   urls.gcp_cloud_trace_batch_write
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		api_key: configuration._gcp_api_key
+		credentials_path: {
+			category:    "Auth"
+			common:      true
+			description: "The filename for a Google Cloud service account credentials JSON file used to authenticate access to the Cloud Trace API. If this is unset, Vector checks the `GOOGLE_APPLICATION_CREDENTIALS` environment variable for a filename.\n\nIf no filename is named, Vector will attempt to fetch an instance service account for the compute instance the program is running on. If Vector is not running on a GCE instance, you must define a credentials file as above."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["/path/to/credentials.json"]
+			}
+		}
+		project_id: {
+			description: "The project ID to which to publish spans."
+			required:    true
+			type: string: {
+				examples: ["vector-123456"]
+			}
+		}
+	}
+
+	input: {
+		logs:    false
+		metrics: null
+		traces:  true
+	}
+
+	how_it_works: {
+		span_conversion: {
+			title: "Span conversion"
+			body: """
+				Each span of a trace event is converted into a Cloud Trace span. The 64-bit trace, span
+				and parent identifiers are hex-encoded, zero-padded to the width expected by Cloud Trace.
+				The span `name` becomes the display name, and the `service`, `resource` and `type` fields
+				as well as the `meta` tags are sent as attributes, up to the 32 attributes Cloud Trace
+				accepts per span. The `span.kind` tag sets the span kind, and spans with a non-zero
+				`error` are marked as failed, using the `error.msg` tag as the status message.
+
+				Spans without a trace ID, a span ID or a start time are dropped.
+				"""
+		}
+	}
+
+	permissions: iam: [
+		{
+			platform: "gcp"
+			_service: "cloudtrace"
+
+			policies: [
+				{
+					_action: "traces.patch"
+					required_for: ["operation", "healthcheck"]
+				},
+			]
+		},
+	]
+
+	telemetry: metrics: {
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
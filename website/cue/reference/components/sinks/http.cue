@@ -33,7 +33,7 @@ components: sinks: http: {
 				codec: {
 					enabled: true
 					framing: true
-					enum: ["json", "text"]
+					enum: ["json", "text", "gelf", "cef", "msgpack"]
 				}
 			}
 			proxy: enabled: true
@@ -51,6 +51,12 @@ components: sinks: kafka: {
 
 	configuration: {
 		bootstrap_servers: components._kafka.configuration.bootstrap_servers
+		idempotence: {
+			common:      false
+			description: "Enables the idempotent producer, which ensures that messages retried by the producer are written exactly once and in order to their partition."
+			required:    false
+			type: bool: default: false
+		}
 		key_field: {
 			common:      true
 			description: "The log field name or tags key to use for the topic key. If the field does not exist in the log or in tags, a blank value will be used. If unspecified, the key is not sent. Kafka uses a hash of the key to choose the partition or uses round-robin if the record has no key."
@@ -132,6 +138,41 @@ components: sinks: kafka: {
 				examples: ["headers"]
 			}
 		}
+		transaction: {
+			common:      false
+			description: "Produces the messages in transactions, committed once all of their messages are delivered. Implies `idempotence`."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					commit_interval_ms: {
+						common:      false
+						description: "How often the open transaction is committed. It must be shorter than `timeout_ms`."
+						required:    false
+						type: uint: {
+							default: 1000
+							unit:    "milliseconds"
+						}
+					}
+					timeout_ms: {
+						common:      false
+						description: "How long the broker waits for a transaction to be committed before aborting it."
+						required:    false
+						type: uint: {
+							default: 60000
+							unit:    "milliseconds"
+						}
+					}
+					transactional_id: {
+						description: "The transactional ID of the producer. It must be unique to the Vector instance and stable across its restarts."
+						required:    true
+						type: string: {
+							examples: ["vector-kafka-sink-1"]
+						}
+					}
+				}
+			}
+		}
 	}
 
 	input: {
@@ -147,7 +188,22 @@ components: sinks: kafka: {
 		traces: false
 	}
 
-	how_it_works: components._kafka.how_it_works
+	how_it_works: components._kafka.how_it_works & {
+		transactions: {
+			title: "Transactions"
+			body:  """
+				When `transaction` is set, the messages are produced with the idempotent producer
+				in transactions committed every `commit_interval_ms`. The events are acknowledged
+				once the transaction of their messages is committed, and the transaction is
+				aborted if any of its messages can't be delivered, so that consumers reading with
+				`isolation.level` set to `read_committed` never read the messages of retried
+				events twice.
+
+				On startup, the transactions left open by a previous producer with the same
+				`transactional_id` are aborted.
+				"""
+		}
+	}
 
 	telemetry: metrics: {
 		component_sent_events_total:         components.sources.internal_metrics.output.metrics.component_sent_events_total
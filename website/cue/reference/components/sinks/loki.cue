@@ -127,11 +127,26 @@ components: sinks: loki: {
 				default: "drop"
 				enum: {
 					"drop":              "Drop the event."
-					"rewrite_timestamp": "Rewrite timestamp of the event to the latest timestamp that was pushed."
+					"rewrite_timestamp": "Rewrite timestamp of the event to the earliest timestamp accepted by its stream, which is the latest timestamp that was pushed minus the `out_of_order_window_secs`."
 					"accept":            "Don't do anything, send events into Loki normally (needs Loki 2.4.0 and newer)"
+					"shard":             "Send the event to a shard of its stream, distinguished by a `stream_shard` label, whose timestamps accept it. A stream has at most 15 shards, beyond which the event is dropped."
 				}
 			}
 		}
+		out_of_order_window_secs: {
+			common: false
+			description: """
+				How far behind the latest timestamp pushed to a stream the timestamps of its events are accepted, before
+				they are handled according to the `out_of_order_action`. Set this to the out-of-order window of Loki 2.4.0
+				and newer, which is half of its `max_chunk_age`, or one hour by default, to only handle the events that
+				Loki would reject.
+				"""
+			required: false
+			type: uint: {
+				default: 0
+				unit:    "seconds"
+			}
+		}
 		remove_label_fields: {
 			common:      false
 			description: "If this is set to `true` then when labels are collected from events those fields will also get removed from the event."
@@ -228,6 +243,8 @@ components: sinks: loki: {
 		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
 		processed_bytes_total:            components.sources.internal_metrics.output.metrics.processed_bytes_total
 		processing_errors_total:          components.sources.internal_metrics.output.metrics.processing_errors_total
+		rewritten_timestamp_events_total: components.sources.internal_metrics.output.metrics.rewritten_timestamp_events_total
+		sharded_stream_events_total:      components.sources.internal_metrics.output.metrics.sharded_stream_events_total
 		streams_total:                    components.sources.internal_metrics.output.metrics.streams_total
 	}
 }
@@ -0,0 +1,172 @@
+package metadata
+
+components: sinks: mqtt: {
+	title: "MQTT"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					enum: ["json", "text"]
+				}
+			}
+			request: enabled: false
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.mqtt
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		host: {
+			description: "The host of the MQTT broker to publish to."
+			required:    true
+			type: string: {
+				examples: ["mqtt.example.com", "127.0.0.1"]
+			}
+		}
+		port: {
+			description: "The port of the MQTT broker."
+			common:      true
+			required:    false
+			type: uint: {
+				default: 1883
+				unit:    null
+			}
+		}
+		user: {
+			description: "The user name to authenticate with the broker."
+			common:      false
+			required:    false
+			type: string: {
+				default: null
+				examples: ["vector"]
+			}
+		}
+		password: {
+			description: "The password to authenticate with the broker. Only used if `user` is set."
+			common:      false
+			required:    false
+			type: string: {
+				default: null
+				examples: ["${MQTT_PASSWORD}"]
+			}
+		}
+		client_id: {
+			description: "The client identifier Vector connects to the broker with. It must be unique among the clients of the broker."
+			common:      false
+			required:    false
+			type: string: {
+				default: "vector"
+				examples: ["vector-edge-1"]
+			}
+		}
+		keep_alive: {
+			description: "The interval at which the client pings the broker when no messages are published."
+			common:      false
+			required:    false
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
+		clean_session: {
+			description: "Whether the broker discards the session state, such as unacknowledged messages, when the client disconnects."
+			common:      false
+			required:    false
+			type: bool: default: false
+		}
+		topic: {
+			description: "The MQTT topic to publish events to."
+			required:    true
+			type: string: {
+				examples: ["vector", "devices/{{ host }}/logs"]
+				syntax: "template"
+			}
+		}
+		quality_of_service: {
+			description: "The quality of service level messages are published with."
+			common:      false
+			required:    false
+			type: string: {
+				default: "at_least_once"
+				enum: {
+					at_least_once: "Messages are delivered at least once, and may be duplicated."
+					at_most_once:  "Messages are delivered at most once, and may be lost."
+					exactly_once:  "Messages are delivered exactly once."
+				}
+			}
+		}
+		retain: {
+			description: "Whether the broker retains the last message of each topic for new subscribers."
+			common:      false
+			required:    false
+			type: bool: default: false
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		delivery: {
+			title: "Delivery"
+			body:  """
+				Events are considered delivered once they are queued by the MQTT client, which publishes them to the broker
+				and retries them according to the quality of service level. Messages still unacknowledged by the broker when
+				Vector stops may be lost, unless `clean_session` is disabled and the broker keeps the session.
+				"""
+		}
+		tls: {
+			title: "TLS"
+			body:  """
+				When TLS is enabled, the certificate of the broker is only verified against the certificate authorities of
+				`tls.ca_file`, so it must be set to connect to brokers with publicly trusted certificates too.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
@@ -51,7 +51,74 @@ components: sinks: nats: {
 		notices: []
 	}
 
-	configuration: components._nats.configuration & {}
+	configuration: components._nats.configuration & {
+		jetstream: {
+			common:      false
+			description: "Publishes the messages to JetStream, delivering the events once the stream acknowledged their message."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					ack_timeout_secs: {
+						common:      false
+						description: "How long to wait for the acknowledgements of the messages."
+						required:    false
+						type: uint: {
+							default: 5
+							unit:    "seconds"
+						}
+					}
+					batch: {
+						common:      false
+						description: "Configures the batches of messages published before waiting for their acknowledgements."
+						required:    false
+						type: object: {
+							examples: []
+							options: {
+								max_events: {
+									common:      true
+									description: "The maximum size of a batch, in events, before it is published."
+									required:    false
+									type: uint: {
+										default: 100
+										unit:    "events"
+									}
+								}
+								timeout_secs: {
+									common:      true
+									description: "The maximum age of a batch before it is published."
+									required:    false
+									type: float: {
+										default: 1.0
+										unit:    "seconds"
+									}
+								}
+							}
+						}
+					}
+					duplicate_window_secs: {
+						common:      false
+						description: "The duplicate window of the streams published to, during which the messages not acknowledged are published again. It must not be longer than the `duplicate_window` of the streams."
+						required:    false
+						type: uint: {
+							default: 120
+							unit:    "seconds"
+						}
+					}
+					message_id: {
+						common:      false
+						description: "The ID of each message, which JetStream discards the messages published again with within the duplicate window of the stream. By default, a random ID is generated for each message, and reused when it's published again."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["{{ id }}"]
+							syntax: "template"
+						}
+					}
+				}
+			}
+		}
+	}
 
 	input: {
 		logs:    true
@@ -59,7 +126,17 @@ components: sinks: nats: {
 		traces:  false
 	}
 
-	how_it_works: components._nats.how_it_works
+	how_it_works: components._nats.how_it_works & {
+		jetstream: {
+			title: "JetStream"
+			body:  """
+				When `jetstream` is set, the events are published in batches, and are delivered
+				once the stream acknowledged their message. The messages not acknowledged within
+				`ack_timeout_secs` are published again with the same `Nats-Msg-Id` header, for up
+				to `duplicate_window_secs`, so that JetStream discards the ones it already stored.
+				"""
+		}
+	}
 
 	telemetry: metrics: {
 		events_discarded_total:  components.sources.internal_metrics.output.metrics.events_discarded_total
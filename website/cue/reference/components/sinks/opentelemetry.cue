@@ -0,0 +1,156 @@
+package metadata
+
+components: sinks: opentelemetry: {
+	_port: 4317
+
+	title: "OpenTelemetry"
+
+	description: """
+		Exports logs, metrics, and traces to any receiver of the OpenTelemetry protocol (OTLP) over gRPC, such
+		as the OpenTelemetry Collector.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_events:   1000
+				timeout_secs: 1.0
+			}
+			compression: enabled:       false
+			encoding: enabled:          false
+			send_buffer_bytes: enabled: false
+			keepalive: enabled:         false
+			request: {
+				enabled: true
+				headers: false
+			}
+
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.opentelemetry
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			summary:      true
+			set:          true
+		}
+		traces: true
+	}
+
+	configuration: {
+		endpoint: {
+			description: "The endpoint of the OTLP/gRPC receiver to export to. The scheme defaults to `http`, or `https` if TLS is enabled."
+			required:    true
+			type: string: {
+				examples: ["http://localhost:\(_port)"]
+			}
+		}
+		compression: {
+			description: "The gRPC compression of the requests."
+			common:      true
+			required:    false
+			type: string: {
+				default: "none"
+				enum: {
+					none: "Requests are sent uncompressed."
+					gzip: "Requests are compressed with gzip."
+				}
+			}
+		}
+		headers: {
+			description: "Headers sent as gRPC metadata with each request, such as authentication tokens required by the receiver."
+			common:      false
+			required:    false
+			type: object: {
+				examples: [{"Authorization": "Bearer ${OTLP_TOKEN}"}]
+				options: {
+					"*": {
+						description: "A header to send with each request."
+						required:    true
+						type: string: {
+							examples: ["Bearer ${OTLP_TOKEN}"]
+						}
+					}
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		conversion: {
+			title: "Conversion to OTLP"
+			body:  """
+				Events are converted as the inverse of the `opentelemetry` source, so that data received from OpenTelemetry
+				is exported unchanged. Logs, metrics, and traces are batched separately and exported to their respective
+				services, with the `resources` and `scope` fields of logs and traces, and the `resources.*` and `scope.*`
+				tags of metrics, grouping them by resource and instrumentation scope.
+
+				Log and trace fields without an OTLP equivalent, such as the `host` field, are exported as attributes, and the
+				other tags of metrics as attributes of their data point. The namespace of metrics is prepended to their name
+				with a `.`.
+				"""
+		}
+		metrics: {
+			title: "Metrics"
+			body:  """
+				Incremental metrics are exported with the delta aggregation temporality, and absolute metrics with the
+				cumulative one. Counters are exported as monotonic sums, absolute gauges as gauges and incremental gauges as
+				non-monotonic sums, sets as gauges of their number of values, distributions as histograms with a single
+				bucket, aggregated histograms as histograms, and aggregated summaries as summaries. Sketches are exported as
+				summaries of their 0.5, 0.75, 0.9, and 0.99 quantiles.
+				"""
+		}
+		healthcheck: {
+			title: "Health checks"
+			body:  """
+				As OTLP has no health service, the health check exports an empty request to each of the logs, metrics, and
+				traces services, and succeeds if any of them is accepted.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
@@ -124,6 +124,20 @@ components: sinks: prometheus_exporter: {
 			required:    false
 			type: bool: default: false
 		}
+		sketches_as_histograms: {
+			common:      false
+			description: """
+				Whether or not to render sketches, such as the ones received from the Datadog Agent, as a
+				[histogram](\(urls.vector_data_model)/metric#histogram) with the configured `buckets`, rather than as a
+				[summary](\(urls.vector_data_model)/metric#summary) with the configured `quantiles`. The bucket counts
+				are accurate within the relative accuracy of the sketch.
+
+				Sketches are only ever converted to classic histograms, with a `_bucket` series per bucket. Prometheus
+				native histograms are not supported, so a system expecting them receives classic histograms or summaries.
+				"""
+			required:    false
+			type: bool: default: false
+		}
 		suppress_timestamp: {
 			common:      false
 			description: "Whether or not to strip metric timestamp in the response."
@@ -118,6 +118,44 @@ components: sinks: prometheus_remote_write: {
 				items: type: float: examples: [0.5, 0.75, 0.9, 0.95, 0.99]
 			}
 		}
+		reorder_window_secs: {
+			common:      false
+			description: """
+				How long to hold samples after their timestamp, so that the samples received out of order within
+				this window are sent in timestamp order. Disabled when set to `0`.
+				"""
+			required:    false
+			type: uint: {
+				default: 0
+				unit:    "seconds"
+			}
+		}
+		sketches_as_histograms: {
+			common:      false
+			description: """
+				Whether or not to send sketches, such as the ones received from the Datadog Agent, as a histogram with
+				the configured `buckets`, rather than as a summary with the configured `quantiles`. The bucket counts are
+				accurate within the relative accuracy of the sketch.
+
+				Sketches are only ever converted to classic histograms, with a `_bucket` series per bucket. Prometheus
+				native histograms are not supported, so a system expecting them receives classic histograms or summaries.
+				"""
+			required:    false
+			type: bool: default: false
+		}
+		staleness_timeout_secs: {
+			common:      false
+			description: """
+				How long a series can go without receiving any sample before a staleness marker is sent for it.
+				Must be greater than `reorder_window_secs`. If not set, no staleness markers are sent.
+				"""
+			required:    false
+			type: uint: {
+				default: null
+				examples: [300]
+				unit: "seconds"
+			}
+		}
 		tenant_id: {
 			common:      false
 			description: "If set, a header named `X-Scope-OrgID` will be added to outgoing requests with the text of this setting. This may be used by Cortex or other remote services to identify the tenant making the request."
@@ -143,9 +181,35 @@ components: sinks: prometheus_remote_write: {
 		traces: false
 	}
 
+	how_it_works: {
+		out_of_order_samples: {
+			title: "Out-of-order Samples"
+			body: """
+				Prometheus, and most of the databases implementing its remote write protocol, reject the samples
+				older than the latest sample of their series. With `reorder_window_secs` set, samples are held
+				until their timestamp is older than the window, and then sent in timestamp order, so that samples
+				received late within the window are accepted. Samples without a timestamp are timestamped with the
+				time they are received.
+
+				Samples older than the latest sample sent for their series are dropped, and counted in the
+				`events_discarded_total` internal metric.
+				"""
+		}
+		staleness_markers: {
+			title: "Staleness Markers"
+			body: """
+				When a target disappears, Prometheus marks its series as stale, so that queries stop returning
+				their latest sample right away. With `staleness_timeout_secs` set, a staleness marker, the special
+				`NaN` value used by Prometheus, is sent for the series which haven't received any sample for the
+				timeout. A series receiving samples again after being marked stale is sent as usual.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_discarded_total:           components.sources.internal_metrics.output.metrics.events_discarded_total
 		processing_errors_total:          components.sources.internal_metrics.output.metrics.processing_errors_total
 	}
 }
@@ -7,15 +7,21 @@ components: sinks: pulsar: {
 		commonly_used: false
 		delivery:      "at_least_once"
 		development:   "beta"
-		egress_method: "stream"
+		egress_method: "batch"
 		service_providers: []
 		stateful: false
 	}
 
 	features: {
-		acknowledgements: false
+		acknowledgements: true
 		healthcheck: enabled: true
 		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_events:   1000
+				timeout_secs: 1.0
+			}
 			compression: enabled: false
 			encoding: {
 				enabled: true
@@ -128,11 +134,21 @@ components: sinks: pulsar: {
 				examples: ["pulsar://127.0.0.1:6650"]
 			}
 		}
+		partition_key_field: {
+			common:      true
+			description: "The log field name to use as the partition key of messages. Messages with the same partition key are written to the same partition of partitioned topics, and batched together."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["user_id"]
+			}
+		}
 		topic: {
 			description: "The Pulsar topic name to write events to."
 			required:    true
 			type: string: {
-				examples: ["topic-1234"]
+				examples: ["topic-1234", "logs-{{ service }}"]
+				syntax: "template"
 			}
 		}
 	}
@@ -143,6 +159,22 @@ components: sinks: pulsar: {
 		traces:  false
 	}
 
+	how_it_works: {
+		batching: {
+			title: "Batching"
+			body: """
+				Events are batched by topic and partition key, and each batch is sent as a single batch
+				message by the producer of its topic, so that the messages of a batch message share their
+				partition key, as expected by consumers with `Key_Shared` subscriptions. Producers are
+				created on the first batch of their topic.
+
+				Events are acknowledged once the broker has persisted their batch. Batches that fail to
+				be sent are not sent again, and the producer of their topic is created again for the next
+				batch of the topic.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		encode_errors_total: components.sources.internal_metrics.output.metrics.encode_errors_total
 	}
@@ -0,0 +1,171 @@
+package metadata
+
+components: sinks: questdb: {
+	title: "QuestDB"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    1_000_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			send_buffer_bytes: enabled: true
+			keepalive: enabled:         true
+			request: enabled:           false
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.questdb
+
+				interface: {
+					socket: {
+						api: {
+							title: "InfluxDB Line Protocol"
+							url:   urls.questdb_ilp
+						}
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		address: {
+			description: "The address of the InfluxDB Line Protocol endpoint of QuestDB."
+			required:    true
+			type: string: {
+				examples: ["127.0.0.1:9009", "questdb.example.com:9009"]
+			}
+		}
+		table: {
+			common:      true
+			description: "The table events are written to. Tables that don't exist are created by QuestDB. Defaults to `logs` for logs, and to the namespaced name of each metric for metrics."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["application_logs", "{{ service }}_logs"]
+				syntax: "template"
+			}
+		}
+		default_namespace: {
+			common:      false
+			description: "The namespace of metrics without one, used in the default table name of metrics."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["service"]
+			}
+		}
+		type_hints: {
+			common:      true
+			description: "The [column types](\(urls.questdb_types)) log fields are written as, by field name. Fields without a type hint are written as the column type matching their value. String values are parsed into the hinted type, and events with fields that can't be converted are dropped."
+			required:    false
+			type: object: {
+				examples: [{level: "symbol", status: "long", "request.duration": "double"}]
+				options: {
+					"*": {
+						common:      false
+						description: "The column type of the field."
+						required:    false
+						type: string: {
+							default: null
+							enum: {
+								symbol:    "An indexed string, for fields with a limited number of distinct values."
+								string:    "A string."
+								long:      "A 64-bit signed integer."
+								double:    "A 64-bit floating point number."
+								boolean:   "A boolean."
+								timestamp: "A timestamp, with microsecond precision. Strings are parsed as RFC 3339 timestamps."
+							}
+						}
+					}
+				}
+			}
+		}
+		quantiles: {
+			common:      false
+			description: "The quantiles written for distributions and sketches."
+			required:    false
+			type: array: {
+				default: [0.5, 0.75, 0.9, 0.95, 0.99]
+				items: type: float: examples: [0.5, 0.99]
+			}
+		}
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	how_it_works: {
+		rows: {
+			title: "Rows"
+			body: """
+				Each event is written as a row of its table. Fields of logs are written as columns, named after
+				the path of the field, with the characters QuestDB doesn't allow in column names, such as the `.`
+				separating the names of nested fields, replaced with `_`. The `host` and `source_type` fields
+				are written as symbols by default, and the timestamp of logs is the designated timestamp of
+				their row.
+
+				Metrics are written with their tags as symbols and their value as columns, such as `value` for
+				counters and gauges, and `count`, `sum` and a column per quantile, such as `p99`, for
+				distributions, histograms and summaries.
+				"""
+		}
+		delivery: {
+			title: "Delivery"
+			body: """
+				The InfluxDB Line Protocol over TCP doesn't acknowledge rows. Events are considered delivered
+				once their batch is written to the connection, which is written again on a new connection if
+				the write fails. QuestDB closes the connection on rows it can't parse, so rows of the batch
+				after such a row may be lost.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+	}
+}
@@ -22,7 +22,7 @@ components: sinks: socket: {
 				codec: {
 					enabled: true
 					framing: true
-					enum: ["json", "text"]
+					enum: ["json", "text", "gelf", "cef", "msgpack"]
 				}
 			}
 			send_buffer_bytes: {
@@ -66,6 +66,34 @@ components: sinks: socket: {
 				examples: ["92.12.333.224:5000"]
 			}
 		}
+		chunked_gelf: {
+			common:        false
+			description:   "Options for splitting GELF messages exceeding the size of a datagram into chunks, as defined by the GELF specification."
+			relevant_when: "mode = `udp` and encoding.codec = `gelf`"
+			required:      false
+			type: object: options: {
+				max_chunk_size: {
+					description: "The maximum size of a datagram, including the 12 byte chunk header. Messages are split into up to 128 chunks."
+					required:    false
+					type: uint: {
+						default: 8192
+						unit:    "bytes"
+					}
+				}
+				compression: {
+					description: "The compression applied to messages before they are chunked."
+					required:    false
+					type: string: {
+						default: "none"
+						enum: {
+							none: "Messages are sent uncompressed."
+							gzip: "Messages are compressed with gzip."
+							zlib: "Messages are compressed with zlib."
+						}
+					}
+				}
+			}
+		}
 		mode: {
 			description: "The type of socket to use."
 			required:    true
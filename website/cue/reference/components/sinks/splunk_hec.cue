@@ -31,6 +31,16 @@ components: sinks: _splunk_hec: {
 							unit:    null
 						}
 					}
+					timeout_secs: {
+						common:      false
+						description: "The maximum amount of time an ack id will be queried for its status, after which its events are rejected. Unlike `retry_limit`, this applies even when the queries fail."
+						required:    false
+						type: uint: {
+							default: null
+							examples: [300]
+							unit: "seconds"
+						}
+					}
 					max_pending_acks: {
 						common:      false
 						description: "The maximum number of ack ids pending query. Once reached, the sink will begin applying backpressure."
@@ -0,0 +1,159 @@
+package metadata
+
+components: sinks: tempo: {
+	_port: 4317
+
+	title: "Grafana Tempo"
+
+	description: """
+		Exports traces to Grafana Tempo with the OpenTelemetry protocol (OTLP), over gRPC or HTTP.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_events:   1000
+				timeout_secs: 1.0
+			}
+			compression: enabled:       false
+			encoding: enabled:          false
+			send_buffer_bytes: enabled: false
+			keepalive: enabled:         false
+			request: {
+				enabled: true
+				headers: false
+			}
+
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.tempo
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	input: {
+		logs:    false
+		metrics: null
+		traces:  true
+	}
+
+	configuration: {
+		endpoint: {
+			description: "The endpoint of the Tempo distributor to export to. The scheme defaults to `http`, or `https` if TLS is enabled. With the `http` protocol, the traces are sent to the `/v1/traces` path of the endpoint."
+			required:    true
+			type: string: {
+				examples: ["http://localhost:\(_port)", "http://localhost:4318"]
+			}
+		}
+		protocol: {
+			description: "The protocol to export the traces with."
+			common:      true
+			required:    false
+			type: string: {
+				default: "grpc"
+				enum: {
+					grpc: "OTLP/gRPC, received by Tempo on port 4317 by default."
+					http: "OTLP/HTTP with protobuf-encoded bodies, received by Tempo on port 4318 by default."
+				}
+			}
+		}
+		tenant_id: {
+			description: "The ID of the tenant to export the traces to, sent in the `X-Scope-OrgID` header, when Tempo runs in [multi-tenant mode](\(urls.tempo_multitenancy))."
+			common:      true
+			required:    false
+			type: string: {
+				default:  null
+				examples: ["team-a"]
+			}
+		}
+		compression: {
+			description: "The compression of the requests."
+			common:      true
+			required:    false
+			type: string: {
+				default: "none"
+				enum: {
+					none: "Requests are sent uncompressed."
+					gzip: "Requests are compressed with gzip."
+				}
+			}
+		}
+		headers: {
+			description: "Headers sent with each request, such as authentication tokens required by a gateway in front of Tempo."
+			common:      false
+			required:    false
+			type: object: {
+				examples: [{"Authorization": "Bearer ${TEMPO_TOKEN}"}]
+				options: {
+					"*": {
+						description: "A header to send with each request."
+						required:    true
+						type: string: {
+							examples: ["Bearer ${TEMPO_TOKEN}"]
+						}
+					}
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		conversion: {
+			title: "Conversion to OTLP"
+			body:  """
+				Traces are converted to OTLP spans as done by the `opentelemetry` sink, so that traces received from the
+				`opentelemetry` source are exported unchanged.
+				"""
+		}
+		batching: {
+			title: "Batching by trace"
+			body:  """
+				Spans are batched by their `trace_id` field, so that the spans of a trace are exported in the same request
+				and reach the same ingester. Spans without a trace ID are batched together.
+				"""
+		}
+		healthcheck: {
+			title: "Health checks"
+			body:  """
+				As OTLP has no health service, the health check exports an empty request, and succeeds if it is accepted.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
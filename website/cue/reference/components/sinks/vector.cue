@@ -78,23 +78,83 @@ components: sinks: vector: {
 
 	configuration: {
 		address: {
-			description: "The downstream Vector address to connect to. The address _must_ include a port."
-			required:    true
+			description: "The downstream Vector address to connect to. The address _must_ include a port. Required unless `endpoints` is set."
+			required:    false
 			type: string: {
+				default: null
 				examples: ["92.12.333.224:\(_port)"]
 			}
 		}
 		compression: {
-			description: "Enable gRPC compression with gzip."
-			common:      true
-			required:    false
-			type: bool: default: false
+			description: """
+				The compression applied to the events sent to the `vector` source. With version `2`, this
+				is the gRPC compression of the requests, and `true` is accepted as `gzip`. With version
+				`1`, each event frame is compressed once the compression is negotiated with the source,
+				falling back to uncompressed frames if the source does not accept it.
+				"""
+			common:   true
+			required: false
+			type: string: {
+				default: "none"
+				enum: {
+					none: "Events are sent uncompressed."
+					gzip: "Events are compressed with gzip."
+					zstd: "Events are compressed with zstd."
+				}
+			}
+		}
+		endpoints: {
+			description: "The addresses of multiple downstream Vector instances to balance requests across, instead of a single `address`. Each address _must_ include a port."
+			common:        false
+			relevant_when: "version = `2`"
+			required:      false
+			type: array: {
+				default: null
+				items: type: string: {
+					examples: ["92.12.333.224:\(_port)", "92.12.333.225:\(_port)"]
+				}
+			}
+		}
+		load_balancing: {
+			description:   "Configures how requests are balanced across the `endpoints`."
+			common:        false
+			relevant_when: "version = `2`"
+			required:      false
+			type: object: options: {
+				strategy: {
+					description: "The strategy used to pick the endpoint of each request."
+					required:    false
+					type: string: {
+						default: "round_robin"
+						enum: {
+							round_robin:       "Requests are sent to each endpoint in turn."
+							least_outstanding: "Requests are sent to the endpoint with the fewest requests in flight."
+						}
+					}
+				}
+				initial_backoff_secs: {
+					description: "The time an endpoint is excluded from load balancing after a request to it failed with a retriable error, so that the request is retried with the remaining endpoints. The time doubles with each consecutive failure of the endpoint, up to `max_backoff_secs`. If all endpoints are excluded, requests are sent to any of them."
+					required:    false
+					type: uint: {
+						default: 1
+						unit:    "seconds"
+					}
+				}
+				max_backoff_secs: {
+					description: "The maximum time an endpoint is excluded from load balancing."
+					required:    false
+					type: uint: {
+						default: 60
+						unit:    "seconds"
+					}
+				}
+			}
 		}
 		version: {
 			description: "Sink API version. Specifying this version ensures that Vector does not break backward compatibility."
 			common:      true
 			required:    false
-			warnings: ["Ensure you use the same version for both the sink and source."]
+			warnings: ["Ensure you use the same version for both the sink and source.", "With version `1`, acknowledgements are exchanged per event and must be enabled on both the sink and the source."]
 			type: string: {
 				enum: {
 					"1": "Vector sink API version 1"
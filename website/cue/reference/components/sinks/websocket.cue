@@ -61,10 +61,54 @@ components: sinks: websocket: {
 	}
 
 	configuration: {
+		mode: {
+			common:      false
+			description: "Whether Vector connects to `uri`, or listens on it for clients to connect."
+			required:    false
+			type: string: {
+				default: "client"
+				enum: {
+					client: "Vector connects to the WebSocket server at `uri` and sends it the events."
+					server: "Vector listens on the address of `uri` and sends the events to the clients connected to its path."
+				}
+			}
+		}
+		server: {
+			common:        false
+			description:   "The options of the `server` mode."
+			relevant_when: "mode = \"server\""
+			required:      false
+			type: object: options: {
+				client_queue_size: {
+					common:      false
+					description: "The maximum number of messages queued for each client."
+					required:    false
+					type: uint: {
+						default: 1000
+						unit:    "events"
+					}
+				}
+				when_full: {
+					common:      false
+					description: "What happens to the messages for a client whose queue is full."
+					required:    false
+					type: string: {
+						default: "drop_newest"
+						enum: {
+							drop_newest: "The new message is dropped."
+							drop_oldest: "The oldest message of the queue is dropped to make room for the new one."
+							disconnect:  "The client is disconnected."
+						}
+					}
+				}
+			}
+		}
 		uri: {
 			description: """
 				The WebSocket URI to connect to. This should include the protocol and host,
 				but can also include the port, path, and any other valid part of a URI.
+				In `server` mode, the host must be an IP address to listen on, and clients
+				connect to the path of the URI.
 				"""
 			required: true
 			warnings: []
@@ -102,7 +146,26 @@ components: sinks: websocket: {
 		traces:  false
 	}
 
+	how_it_works: {
+		subscriptions: {
+			title: "Subscriptions"
+			body: """
+				In `server` mode, each client receives the events sent while it is connected. A
+				client subscribes to part of the events with the `filter` query parameter of the
+				URI it connects to, a [VRL](\(urls.vrl_reference)) boolean expression such as
+				`.level == "error"`, so that only the events matching it are sent to the client.
+				Clients whose filter doesn't compile are rejected with a `400` status.
+
+				The messages for each client are queued up to `server.client_queue_size`, so
+				that a slow client doesn't slow down the others. When its queue is full, either
+				messages are dropped or the client is disconnected, depending on
+				`server.when_full`.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
 		open_connections:                 components.sources.internal_metrics.output.metrics.open_connections
 		connection_established_total:     components.sources.internal_metrics.output.metrics.connection_established_total
 		connection_failed_total:          components.sources.internal_metrics.output.metrics.connection_failed_total
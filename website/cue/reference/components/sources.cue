@@ -86,7 +86,9 @@ components: sources: [Name=string]: {
 								enum: {
 									bytes:               "Byte frames are passed through as-is according to the underlying I/O boundaries (e.g. split between messages or stream segments)."
 									character_delimited: "Byte frames which are delimited by a chosen character."
+									chunked_gelf:        "Byte frames which are [chunked GELF](\(urls.graylog_gelf_chunking)) messages reassembled from multiple datagrams. Datagrams which are not chunks are passed through as-is. Only relevant for datagram based sources, such as the `socket` source in `udp` mode."
 									length_delimited:    "Byte frames which are prefixed by an unsigned big-endian 32-bit integer indicating the length."
+									multiline:           "Byte frames which are newline delimited lines, aggregated into multiline messages such as stack traces. The aggregated lines are joined by a newline."
 									newline_delimited:   "Byte frames which are delimited by a newline character."
 									octet_counting:      "Byte frames according to the [octet counting](\(urls.rfc_6587_3_4_1)) format."
 								}
@@ -116,6 +118,75 @@ components: sources: [Name=string]: {
 								}
 							}
 						}
+						chunked_gelf: {
+							description:   "Options for `chunked_gelf` framing."
+							required:      false
+							common:        false
+							relevant_when: "method = `chunked_gelf`"
+							type: object: options: {
+								pending_messages_limit: {
+									description: "The maximum number of messages which can be reassembled at the same time. Chunks starting a new message are discarded while this limit is reached."
+									required:    false
+									common:      false
+									type: uint: {
+										default: 1000
+										unit:    null
+									}
+								}
+								timeout_secs: {
+									description: "The time to wait for all the chunks of a message to arrive. Messages that are still incomplete after this time are discarded."
+									required:    false
+									common:      false
+									type: float: {
+										default: 5.0
+										unit:    "seconds"
+									}
+								}
+							}
+						}
+						multiline: {
+							description:   "Options for `multiline` framing."
+							required:      true
+							relevant_when: "method = `multiline`"
+							type: object: options: {
+								condition_pattern: {
+									description: "Condition regex pattern to look for. Exact behavior is configured via `mode`."
+									required:    true
+									type: string: {
+										examples: ["^[\\s]+", "\\\\$", "^(INFO|ERROR) ", ";$"]
+										syntax: "regex"
+									}
+								}
+								mode: {
+									description: "Mode of operation, specifies how the `condition_pattern` is interpreted."
+									required:    true
+									type: string: {
+										enum: {
+											continue_through: "All consecutive lines matching this pattern are included in the group. The first line (the line that matched the start pattern) does not need to match the `ContinueThrough` pattern. This is useful in cases such as a Java stack trace, where some indicator in the line (such as leading whitespace) indicates that it is an extension of the preceding line."
+											continue_past:    "All consecutive lines matching this pattern, plus one additional line, are included in the group. This is useful in cases where a log message ends with a continuation marker, such as a backslash, indicating that the following line is part of the same message."
+											halt_before:      "All consecutive lines not matching this pattern are included in the group. This is useful where a log line contains a marker indicating that it begins a new message."
+											halt_with:        "All consecutive lines, up to and including the first line matching this pattern, are included in the group. This is useful where a log line ends with a termination marker, such as a semicolon."
+										}
+									}
+								}
+								start_pattern: {
+									description: "Start regex pattern to look for as a beginning of the message."
+									required:    true
+									type: string: {
+										examples: ["^[^\\s]", "\\\\$", "^(INFO|ERROR) ", "[^;]$"]
+										syntax: "regex"
+									}
+								}
+								timeout_ms: {
+									description: "The maximum time to wait for the continuation. Since frames are only decoded as data arrives, a buffered message is flushed once a line is received after this timeout, or when the stream or message ends."
+									required:    true
+									type: uint: {
+										examples: [1_000, 600_000]
+										unit: "milliseconds"
+									}
+								}
+							}
+						}
 						newline_delimited: {
 							description:   "Options for `newline_delimited` framing."
 							required:      false
@@ -166,14 +237,121 @@ components: sources: [Name=string]: {
 							type: string: {
 								default: "bytes"
 								enum: {
+									avro:        "Events being parsed from an Avro message in the [Confluent wire format](\(urls.confluent_wire_format)), with its schema resolved from a [Schema Registry](\(urls.confluent_schema_registry))."
 									bytes:       "Events containing the byte frame as-is."
+									cef:         "Events being parsed from an ArcSight [CEF](\(urls.arcsight_cef)) message, possibly preceded by a syslog header. The header fields are stored as `cefVersion`, `deviceVendor`, `deviceProduct`, `deviceVersion`, `deviceEventClassId`, `name` and `severity`, next to the extensions."
+									csv:         "Events being parsed from a [CSV](\(urls.csv)) record. The fields are named after the configured headers, or after the first record of the stream if none are configured."
 									json:        "Events being parsed from a JSON string."
 									syslog:      "Events being parsed from a Syslog message."
+									msgpack:     "Events being parsed from [MessagePack](\(urls.msgpack)) maps. A frame may hold several concatenated maps. [Timestamp extensions](\(urls.msgpack_timestamp)) and Fluentd `EventTime` extensions are decoded to timestamps."
 									native:      "Events being parsed from Vector's [native protobuf format](\(urls.native_proto_schema)) ([EXPERIMENTAL](/highlights/2022-03-31-native-event-codecs))."
 									native_json: "Events being parsed from Vector's [native JSON format](\(urls.native_json_schema)) ([EXPERIMENTAL](/highlights/2022-03-31-native-event-codecs))."
+									gelf:        "Events being parsed from a [GELF](\(urls.graylog_gelf)) message. Messages compressed with gzip or zlib are decompressed first."
+									protobuf:    "Events being parsed from a [Protocol Buffers](\(urls.protobuf)) message, described by a compiled file descriptor set. Enum values are decoded to their names."
+								}
+							}
+						}
+						avro: {
+							description:   "Options for the `avro` codec."
+							required:      true
+							relevant_when: "codec = `avro`"
+							type: object: options: {
+								schema_registry_url: {
+									description: "The URL of the Schema Registry to resolve the schemas from. Schemas are fetched once per ID and cached. The `kafka` source fetches the schemas before decoding the messages, whilst other sources fetch them in the background and fail to decode the messages referencing a schema until it is fetched. Requests time out after 10 seconds, and a schema which failed to be fetched is only requested again after a backoff of one second, doubled after each failure up to one minute."
+									required:    true
+									type: string: {
+										examples: ["http://localhost:8081"]
+									}
 								}
 							}
 						}
+						protobuf: {
+							description:   "Options for the `protobuf` codec."
+							required:      true
+							relevant_when: "codec = `protobuf`"
+							type: object: options: {
+								desc_file: {
+									description: "The path to the compiled file descriptor set, as produced by `protoc --include_imports --descriptor_set_out`."
+									required:    true
+									type: string: {
+										examples: ["/etc/vector/protobuf/events.desc"]
+									}
+								}
+								message_type: {
+									description: "The fully qualified name of the message type to decode."
+									required:    true
+									type: string: {
+										examples: ["package.Message"]
+									}
+								}
+							}
+						}
+						csv: {
+							description:   "Options for the `csv` codec."
+							required:      false
+							common:        false
+							relevant_when: "codec = `csv`"
+							type: object: options: {
+								delimiter: {
+									description: "The character separating the fields of a record."
+									required:    false
+									common:      false
+									type: string: {
+										default: ","
+										examples: [";", "\t"]
+									}
+								}
+								quote: {
+									description: "The character used to quote fields containing the delimiter. Quotes within a quoted field are escaped by doubling them."
+									required:    false
+									common:      false
+									type: string: {
+										default: "\""
+									}
+								}
+								headers: {
+									description: "The names of the columns, used as the field names of the decoded events. If not set, the first record of each stream, such as a file or the body of a request, is read as the header and doesn't produce an event."
+									required:    false
+									common:      false
+									type: array: {
+										default: null
+										items: type: string: {
+											examples: ["timestamp", "host", "message"]
+										}
+									}
+								}
+							}
+						}
+						gelf: {
+							description:   "Options for the `gelf` codec."
+							required:      false
+							common:        false
+							relevant_when: "codec = `gelf`"
+							type: object: options: {
+								max_decompressed_length: {
+									description: "The maximum length of a message once decompressed. Compressed messages exceeding this length once decompressed are discarded."
+									required:    false
+									common:      false
+									type: uint: {
+										default: 8388608
+										unit:    "bytes"
+									}
+								}
+							}
+						}
+					}
+				}
+				on_error: {
+					common:      false
+					description: "How frames that fail to be decoded are handled."
+					required:    false
+					type: string: {
+						default: "drop"
+						enum: {
+							drop:  "The frame is dropped, and the error is logged."
+							raw:   "The frame is emitted as a raw text event, as if it was decoded with the `bytes` codec."
+							route: "The frame is emitted as a raw text event, tagged with the error in the `decoding_error` field. This allows routing malformed frames to a dead-letter sink."
+						}
 					}
 				}
 			}
@@ -306,6 +484,17 @@ components: sources: [Name=string]: {
 									examples: [ "CN=localhost,OU=Vector,O=Datadog,L=New York,ST=New York,C=US"]
 								}
 							}
+							subject_alt_names: {
+								common:      false
+								description: "The DNS names, email addresses, URIs, and IP addresses from the Subject Alternative Name extension of the client TLS certificate. Only added if the certificate has any."
+								required:    false
+								type: array: {
+									default: null
+									items: type: string: {
+										examples: ["client.example.com"]
+									}
+								}
+							}
 						}
 					}
 				}
@@ -0,0 +1,163 @@
+package metadata
+
+components: sources: amqp: {
+	title: "AMQP"
+
+	features: {
+		acknowledgements: true
+		collect: {
+			tls: {
+				enabled:                true
+				can_verify_certificate: false
+				can_verify_hostname:    false
+				enabled_default:        false
+			}
+			checkpoint: enabled: false
+			from: components._amqp.features.collect.from
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: components._amqp.support
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: components._amqp.configuration & {
+		acknowledgements: configuration._source_acknowledgements
+		queue: {
+			common:      true
+			description: "The name of the queue to consume."
+			required:    false
+			type: string: {
+				default: "vector"
+				examples: ["logs"]
+			}
+		}
+		consumer: {
+			common:      false
+			description: "The consumer tag identifying Vector to the broker."
+			required:    false
+			type: string: {
+				default: "vector"
+				examples: ["vector-aggregator-1"]
+			}
+		}
+		prefetch_count: {
+			common:      false
+			description: "The maximum number of unacknowledged messages the broker delivers to Vector. `0` lets the broker deliver messages without limit."
+			required:    false
+			type: uint: {
+				default: 100
+				unit:    "messages"
+			}
+		}
+		exchange_key: {
+			common:      false
+			description: "The log field name to use for the exchange the message was published to."
+			required:    false
+			type: string: {
+				default: "exchange"
+				syntax:  "literal"
+			}
+		}
+		routing_key_field: {
+			common:      false
+			description: "The log field name to use for the routing key of the message."
+			required:    false
+			type: string: {
+				default: "routing"
+				syntax:  "literal"
+			}
+		}
+		offset_key: {
+			common:      false
+			description: "The log field name to use for the delivery tag of the message."
+			required:    false
+			type: string: {
+				default: "offset"
+				syntax:  "literal"
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: "An individual AMQP message"
+		fields: {
+			message: {
+				description: "The message body, parsed from the raw data."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+					syntax: "literal"
+				}
+			}
+			exchange: {
+				description: "The exchange the message was published to. Empty for the default exchange."
+				required:    true
+				type: string: {
+					examples: ["logs"]
+					syntax: "literal"
+				}
+			}
+			routing: {
+				description: "The routing key the message was published with."
+				required:    true
+				type: string: {
+					examples: ["app.errors"]
+					syntax: "literal"
+				}
+			}
+			offset: {
+				description: "The delivery tag of the message, unique within the channel it was delivered on."
+				required:    true
+				type: uint: {
+					examples: [42]
+					unit: null
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The `timestamp` property of the message if it is set, otherwise the time the message was received."
+			}
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+
+	how_it_works: components._amqp.how_it_works & {
+		acknowledgements: {
+			title: "Acknowledgements"
+			body:  """
+				Messages are acknowledged to the broker once their events are delivered, or as soon as they are received if
+				acknowledgements are disabled. Messages whose events fail to be delivered are requeued, while those rejected
+				by a sink are rejected without being requeued, so they are dead-lettered if the queue is configured to.
+				"""
+		}
+		reconnection: {
+			title: "Reconnection"
+			body:  """
+				When the connection to the broker fails or is closed, Vector reconnects with an exponential backoff of up to
+				a minute. The broker redelivers the messages left unacknowledged on the closed connection.
+				"""
+		}
+	}
+}
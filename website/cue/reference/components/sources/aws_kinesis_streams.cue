@@ -0,0 +1,297 @@
+package metadata
+
+components: sources: aws_kinesis_streams: components._aws & {
+	title: "AWS Kinesis Data Streams"
+
+	features: {
+		acknowledgements: true
+		collect: {
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			checkpoint: enabled: true
+			proxy: enabled:      true
+			from: service:       services.aws_kinesis_data_streams
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: [
+			"""
+				The AWS Kinesis Data Streams source requires a Kinesis data stream, and the
+				permission to create or use a DynamoDB table storing the leases of its shards.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._source_acknowledgements
+		stream_name: {
+			description: "The name of the stream to read the records of."
+			required:    true
+			type: string: {
+				examples: ["my-stream"]
+			}
+		}
+		application_name: {
+			description: "The name of the application the records are read for. The Vector instances configured with the same application share the shards of the stream between them, and the stream consumer used in the `enhanced_fan_out` mode is registered under this name."
+			required:    true
+			type: string: {
+				examples: ["vector"]
+			}
+		}
+		lease_table: {
+			common:      false
+			description: "The name of the DynamoDB table storing the leases and checkpoints of the shards. The table is created if it doesn't exist. Defaults to the name of the application."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["vector-leases"]
+			}
+		}
+		mode: {
+			common:      true
+			description: "How the records of the shards are read."
+			required:    false
+			type: string: {
+				default: "polling"
+				enum: {
+					polling:          "The records are polled with `GetRecords` requests, sharing the read throughput of the shards with the other consumers of the stream."
+					enhanced_fan_out: "The records are pushed to a [stream consumer](\(urls.aws_kinesis_streams_enhanced_fan_out)) registered for the application, which gets a dedicated read throughput for each shard."
+				}
+			}
+		}
+		initial_position: {
+			common:      true
+			description: "Where to start reading the shards that don't have a checkpoint yet."
+			required:    false
+			type: string: {
+				default: "latest"
+				enum: {
+					latest:       "Only the records added after the shard was first leased are read."
+					trim_horizon: "All the records retained by the shard are read."
+				}
+			}
+		}
+		poll_interval_ms: {
+			common:      false
+			description: "How long to wait between `GetRecords` requests to a shard when the last one returned no records. Only used in the `polling` mode."
+			required:    false
+			type: uint: {
+				default: 1000
+				unit:    "milliseconds"
+			}
+		}
+		max_records: {
+			common:      false
+			description: "The maximum number of records returned by each `GetRecords` request. Only used in the `polling` mode."
+			required:    false
+			type: uint: {
+				default: 10000
+				unit:    "records"
+			}
+		}
+		lease_duration_secs: {
+			common:      false
+			description: "How long a lease can go without being renewed by its owner before another instance takes it."
+			required:    false
+			type: uint: {
+				default: 10
+				unit:    "seconds"
+			}
+		}
+		shard_sync_interval_secs: {
+			common:      false
+			description: "How often the shards of the stream are listed to create the leases of the new ones."
+			required:    false
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
+		worker_id: {
+			common:      false
+			description: "The identifier of this instance in the lease table. Must be unique among the instances of the application. Defaults to the hostname followed by a random identifier."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["vector-0"]
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: "An individual Kinesis record"
+		fields: {
+			message: {
+				description: "The data of the Kinesis record."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The approximate time the record was added to the stream."
+			}
+			stream: {
+				description: "The name of the stream."
+				required:    true
+				type: string: {
+					examples: ["my-stream"]
+				}
+			}
+			shard_id: {
+				description: "The ID of the shard the record was read from."
+				required:    true
+				type: string: {
+					examples: ["shardId-000000000000"]
+				}
+			}
+			partition_key: {
+				description: "The partition key of the record."
+				required:    true
+				type: string: {
+					examples: ["host-1"]
+				}
+			}
+			sequence_number: {
+				description: "The sequence number of the record in its shard."
+				required:    true
+				type: string: {
+					examples: ["49590338271490256608559692538361571095921575989136588898"]
+				}
+			}
+		}
+	}
+
+	permissions: iam: [
+		{
+			platform: "aws"
+			_service: "kinesis"
+
+			policies: [
+				{
+					_action: "ListShards"
+				},
+				{
+					_action:       "GetShardIterator"
+					required_when: "[`mode`](#mode) is set to `polling`"
+				},
+				{
+					_action:       "GetRecords"
+					required_when: "[`mode`](#mode) is set to `polling`"
+				},
+				{
+					_action:       "DescribeStreamSummary"
+					required_when: "[`mode`](#mode) is set to `enhanced_fan_out`"
+				},
+				{
+					_action:       "RegisterStreamConsumer"
+					required_when: "[`mode`](#mode) is set to `enhanced_fan_out`"
+				},
+				{
+					_action:       "DescribeStreamConsumer"
+					required_when: "[`mode`](#mode) is set to `enhanced_fan_out`"
+				},
+				{
+					_action:       "SubscribeToShard"
+					required_when: "[`mode`](#mode) is set to `enhanced_fan_out`"
+				},
+			]
+		},
+		{
+			platform:  "aws"
+			_service:  "dynamodb"
+			_docs_tag: "amazondynamodb"
+
+			policies: [
+				{
+					_action: "CreateTable"
+				},
+				{
+					_action: "DescribeTable"
+				},
+				{
+					_action: "Scan"
+				},
+				{
+					_action: "PutItem"
+				},
+				{
+					_action: "UpdateItem"
+				},
+			]
+		},
+	]
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+
+	how_it_works: {
+		leases: {
+			title: "Shard leases"
+			body: """
+				The Vector instances configured with the same `application_name` share the shards of
+				the stream between them through leases stored in a DynamoDB table, in the same layout
+				as the [Kinesis Client Library](\(urls.aws_kinesis_streams_kcl)). Each instance takes
+				its share of the leases of the open shards, and renews them while it reads their
+				records. The leases of the instances that stop renewing them for
+				`lease_duration_secs` are taken by the others, and an instance with fewer leases than
+				its share takes one from the most loaded instance, so the shards are rebalanced as
+				instances join or leave.
+
+				The shards created by splitting or merging a shard are only read once all the records
+				of their parents have been read, so that the records of each partition key are read in
+				order.
+				"""
+		}
+		checkpointing: {
+			title: "Checkpointing"
+			body: """
+				The sequence number of the last record of each batch is stored in the lease of its
+				shard once its events have been sent, or delivered to the sinks when
+				`acknowledgements` are enabled. An instance taking the lease resumes reading the
+				shard from there, so records may be read again after a crash or rebalancing but are
+				not lost. When the sinks fail to deliver the events of a batch, its records are read
+				again from the last checkpoint.
+				"""
+		}
+	}
+}
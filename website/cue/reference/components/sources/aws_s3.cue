@@ -52,7 +52,8 @@ components: sources: aws_s3: components._aws & {
 			type: string: {
 				default: "sqs"
 				enum: {
-					sqs: "Consume S3 objects by polling for bucket notifications sent to an [AWS SQS queue](\(urls.aws_sqs))."
+					sqs:  "Consume S3 objects by polling for bucket notifications sent to an [AWS SQS queue](\(urls.aws_sqs))."
+					poll: "Consume S3 objects by periodically listing a prefix of a bucket, for buckets whose notifications can't be sent to an SQS queue."
 				}
 			}
 		}
@@ -114,6 +115,58 @@ components: sources: aws_s3: components._aws & {
 		}
 	}
 
+		poll: {
+			common:      false
+			description: "Polling strategy options. Required if strategy=`poll`."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					bucket: {
+						description: "The name of the bucket to list the objects of."
+						required:    true
+						type: string: {
+							examples: ["my-bucket"]
+						}
+					}
+					key_prefix: {
+						common:      true
+						description: "The prefix of the keys of the objects to ingest. By default, all the objects of the bucket are ingested."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["AWSLogs/111111111111/vpcflowlogs/"]
+						}
+					}
+					interval_secs: {
+						common:      true
+						description: "How long to wait between listings of the objects."
+						required:    false
+						type: uint: {
+							default: 60
+							unit:    "seconds"
+						}
+					}
+					ordered_keys: {
+						common:      false
+						description: "Whether the keys of new objects always sort after the keys of the existing ones, such as keys starting with the time the objects are written at. Only the keys after the last ingested one are then listed, instead of all the keys of the prefix on every poll, and the modification time of the objects is ignored."
+						required:    false
+						type: bool: default: false
+					}
+					data_dir: {
+						common:      false
+						description: "The directory used to persist the checkpoint of the ingested objects. By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["/var/local/lib/vector/"]
+						}
+					}
+				}
+			}
+		}
+	}
+
 	output: logs: object: {
 		description: "A line from an S3 object."
 		fields: {
@@ -152,6 +205,23 @@ components: sources: aws_s3: components._aws & {
 	}
 
 	how_it_works: {
+		polling: {
+			title: "Polling without SQS"
+			body:  """
+				With `strategy = "poll"`, Vector lists the objects of the configured prefix every
+				`poll.interval_secs` and ingests the objects it hasn't seen yet, in the order of
+				their modification time, or of their key when `poll.ordered_keys` is set. A
+				checkpoint of the last ingested objects is persisted in the data directory after
+				each object, so that restarting Vector doesn't ingest them again.
+
+				When an object can't be fetched or its events aren't delivered, the poll stops and
+				the object is ingested again on the next one. Objects that can't be read, such as
+				corrupted archives, are skipped after their readable lines are sent. Objects
+				modified before the checkpoint, which can happen when a large upload completes
+				after smaller ones, aren't ingested.
+				"""
+		}
+
 		events: {
 			title: "Handling events from the `aws_s3` source"
 			body:  """
@@ -200,6 +270,10 @@ components: sources: aws_s3: components._aws & {
 				{
 					_action: "GetObject"
 				},
+				{
+					_action:       "ListBucket"
+					required_when: "[`strategy`](#strategy) is set to `poll`"
+				},
 			]
 		},
 		{
@@ -0,0 +1,210 @@
+package metadata
+
+components: sources: azure_event_hubs: {
+	title: "Azure Event Hubs"
+
+	features: {
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: true
+			tls: enabled: false
+			from: {
+				service: services.azure_event_hubs
+				interface: {
+					socket: {
+						api: {
+							title: "Event Hubs Kafka protocol"
+							url:   urls.azure_event_hubs_kafka
+						}
+						direction: "outgoing"
+						port:      9093
+						protocols: ["tcp"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		requirements: [
+			"""
+				The Event Hubs namespace must be of the Standard tier or above, which expose the Kafka-compatible
+				endpoint.
+				""",
+		]
+		notices: []
+		warnings: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._source_acknowledgements
+		auto_offset_reset: {
+			common:      false
+			description: """
+				Where to start consuming partitions that have neither an offset committed by the consumer group nor a
+				checkpoint. See the [librdkafka documentation](\(urls.librdkafka_config)) for the `auto.offset.reset`
+				option for further clarification.
+				"""
+			required:    false
+			type: string: {
+				default: "latest"
+				examples: ["earliest", "latest"]
+			}
+		}
+		checkpoint: {
+			common:      false
+			description: "Checkpointing of the consumed events to Azure Blob Storage."
+			required:    false
+			type: object: options: {
+				connection_string: {
+					description: "The connection string of the storage account. Either `connection_string` or `storage_account` must be set."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["DefaultEndpointsProtocol=https;AccountName=mylogstorage;AccountKey=storageaccountkeybase64encoded;EndpointSuffix=core.windows.net"]
+					}
+				}
+				storage_account: {
+					description: "The name of the storage account, authenticated with Azure's default credentials. Either `connection_string` or `storage_account` must be set."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["mylogstorage"]
+					}
+				}
+				container_name: {
+					description: "The name of the container to store the checkpoints in."
+					required:    true
+					type: string: {
+						examples: ["checkpoints"]
+					}
+				}
+			}
+		}
+		connection_string: {
+			description: """
+				The connection string of the Event Hubs namespace, or of the event hub. The event hub is consumed through
+				the Kafka-compatible endpoint of the namespace, which is authenticated with the connection string.
+				"""
+			required:    true
+			type: string: {
+				examples: ["Endpoint=sb://mynamespace.servicebus.windows.net/;SharedAccessKeyName=listen;SharedAccessKey=keybase64encoded;EntityPath=logs"]
+			}
+		}
+		consumer_group: {
+			common:      true
+			description: "The consumer group to consume the event hub as. The partitions of the event hub are balanced between the Vector instances in the same consumer group."
+			required:    false
+			type: string: {
+				default: "$Default"
+				examples: ["vector"]
+			}
+		}
+		event_hub: {
+			common:      true
+			description: "The name of the event hub to consume from. Defaults to the `EntityPath` of the connection string."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["logs"]
+			}
+		}
+		librdkafka_options: components._kafka.configuration.librdkafka_options
+	}
+
+	output: logs: record: {
+		description: "An individual event of the event hub"
+		fields: {
+			message: {
+				description: "The raw body of the event."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+				}
+			}
+			offset: {
+				description: "The Kafka offset of the event within its partition."
+				required:    true
+				type: uint: {
+					examples: [100]
+					unit: null
+				}
+			}
+			partition: {
+				description: "The partition of the event hub that the event came from."
+				required:    true
+				type: uint: {
+					examples: [0]
+					unit: null
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time the event was enqueued, or the current time if it cannot be fetched."
+			}
+			topic: {
+				description: "The event hub that the event came from."
+				required:    true
+				type: string: {
+					examples: ["logs"]
+				}
+			}
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+
+	how_it_works: {
+		kafka: {
+			title: "Kafka-compatible endpoint"
+			body:  """
+				Vector consumes the event hub through the Kafka-compatible endpoint of its namespace, on port 9093 of the
+				host of the `Endpoint` of the connection string. The connection is encrypted with TLS and authenticated with
+				the SASL `PLAIN` mechanism, using the connection string as the password.
+				"""
+		}
+		partitions: {
+			title: "Sharing partitions"
+			body:  """
+				Vector instances consuming the event hub with the same consumer group share its partitions, which are
+				rebalanced between the instances as they come and go. The offsets of the consumed events are committed to
+				the consumer group, so that the partitions resume where they left off.
+				"""
+		}
+		checkpoints: {
+			title: "Checkpoints"
+			body:  """
+				When `checkpoint` is set, the offset of the last event consumed from each partition is also stored in the
+				`<namespace>/<event hub>/<consumer group>/checkpoint/<partition>` blob of the container. The partitions
+				assigned to Vector resume from their checkpoint when it's ahead of the offset committed to the consumer
+				group, such as when the consumer group is new or its offsets expired. The checkpoints are stored every five
+				seconds, and when Vector shuts down.
+				"""
+		}
+	}
+}
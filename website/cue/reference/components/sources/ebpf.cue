@@ -0,0 +1,184 @@
+package metadata
+
+components: sources: ebpf: {
+	title: "eBPF"
+
+	description: """
+		Loads [eBPF](\(urls.ebpf)) programs in the Linux kernel to collect the process
+		executions, TCP connections, and DNS queries of the host as logs.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["daemon"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: service:       services.host
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  false
+			"armv7-unknown-linux-musleabihf": false
+			"x86_64-apple-darwin":            false
+			"x86_64-pc-windows-msv":          false
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+
+		requirements: [
+			"""
+				The kernel must be version 5.8 or later, and expose its types with
+				[BTF](\(urls.ebpf_btf)), which is the case when `/sys/kernel/btf/vmlinux`
+				exists.
+				""",
+			"""
+				Vector must run as `root`, or with the `CAP_BPF` and `CAP_PERFMON`
+				capabilities.
+				""",
+			"""
+				The source is only included in Vector when it's built with the `sources-ebpf`
+				feature, which requires `clang`.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		events: {
+			common:      true
+			description: "The types of events to collect. Only the eBPF programs collecting these events are loaded."
+			required:    false
+			type: array: {
+				default: ["process_exec", "tcp_connect", "tcp_accept", "dns_query"]
+				items: type: string: {
+					enum: {
+						process_exec: "The executions of programs by the processes."
+						tcp_connect:  "The outgoing TCP connections, reported when they are initiated."
+						tcp_accept:   "The incoming TCP connections, reported when they are accepted."
+						dns_query:    "The DNS queries sent over UDP."
+					}
+				}
+			}
+		}
+		ring_buffer_size: {
+			common:      false
+			description: "The size of the ring buffer the events are written to by the eBPF programs. Must be a power of two. The events are dropped by the eBPF programs while the ring buffer is full."
+			required:    false
+			type: uint: {
+				default: 1048576
+				unit:    "bytes"
+			}
+		}
+	}
+
+	output: logs: event: {
+		description: "An event collected by the eBPF programs."
+		fields: {
+			dns: {
+				description: "The question of the DNS query. Only for the `dns_query` events."
+				required:    false
+				common:      true
+				type: object: {
+					examples: [{"id": 4660, "question": {"name": "www.example.com", "type": "AAAA"}}]
+					options: {}
+				}
+			}
+			event_type: {
+				description: "The type of the event."
+				required:    true
+				type: string: {
+					enum: {
+						process_exec: "A program was executed."
+						tcp_connect:  "An outgoing TCP connection was initiated."
+						tcp_accept:   "An incoming TCP connection was accepted."
+						dns_query:    "A DNS query was sent."
+					}
+				}
+			}
+			host: fields._local_host
+			network: {
+				description: "The source and destination of the connection. Only for the `tcp_connect` and `tcp_accept` events."
+				required:    false
+				common:      true
+				type: object: {
+					examples: [{"source": {"ip": "10.0.0.1", "port": 43210}, "destination": {"ip": "93.184.216.34", "port": 443}}]
+					options: {}
+				}
+			}
+			process: {
+				description: "The process the event happened in, with its `pid`, `uid`, and `name`. The `process_exec` events also have the `ppid` of the process, and the path of the `executable`."
+				required:    true
+				type: object: {
+					examples: [{"pid": 1234, "ppid": 1, "uid": 1000, "name": "curl", "executable": "/usr/bin/curl"}]
+					options: {}
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["ebpf"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		programs: {
+			title: "eBPF programs"
+			body: """
+				The eBPF programs are compiled once, when Vector is built, and adapted to the
+				running kernel when they are loaded thanks to its BTF types. They are attached to
+				the `sched_process_exec` and `inet_sock_set_state` tracepoints, and to the
+				`inet_csk_accept`, `udp_sendmsg`, and `udpv6_sendmsg` functions of the kernel.
+				They are detached when the source stops.
+				"""
+		}
+
+		ring_buffer: {
+			title: "Ring buffer"
+			body: """
+				The eBPF programs write the events to a ring buffer shared with Vector. When
+				Vector doesn't read the events as fast as they happen, the ring buffer fills up
+				and the new events are dropped. Increase the `ring_buffer_size` option for hosts
+				with bursts of events.
+				"""
+		}
+
+		dns_queries: {
+			title: "DNS queries"
+			body: """
+				The DNS queries are the UDP datagrams sent to port 53. Only the first question of
+				each query is reported, and the queries sent over TCP, or with DNS over TLS or
+				HTTPS, aren't collected.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total:     components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
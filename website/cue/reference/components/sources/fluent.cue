@@ -69,6 +69,63 @@ components: sources: fluent: {
 				unit:    "concurrency"
 			}
 		}
+		proxy_protocol: {
+			common: false
+			description: """
+				Whether to expect each connection to start with a PROXY protocol header, as sent by
+				load balancers such as HAProxy or AWS Network Load Balancers. The address of the
+				original client from the header is then used as the peer address of the connection.
+				Both version 1 and 2 of the protocol are supported. Connections not starting with a
+				valid header are closed.
+				"""
+			required: false
+			type: bool: default: false
+		}
+		security: {
+			common:      false
+			description: "Requires clients to authenticate with the handshake of the Fluent protocol before sending events."
+			required:    false
+			type: object: options: {
+				shared_key: {
+					description: "The key shared with the clients."
+					required:    true
+					type: string: {
+						examples: ["${FLUENT_SHARED_KEY}"]
+					}
+				}
+				self_hostname: {
+					description: "The hostname sent to clients in the handshake. Defaults to the hostname of the machine Vector is running on."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["vector.example.com"]
+					}
+				}
+				users: {
+					description: "The users clients must authenticate as, in addition to the shared key. If empty, clients are not required to authenticate as a user."
+					required:    false
+					type: array: {
+						default: []
+						items: type: object: options: {
+							username: {
+								description: "The username of the user."
+								required:    true
+								type: string: {
+									examples: ["fluent-bit"]
+								}
+							}
+							password: {
+								description: "The password of the user."
+								required:    true
+								type: string: {
+									examples: ["${FLUENT_PASSWORD}"]
+								}
+							}
+						}
+					}
+				}
+			}
+		}
 	}
 
 	output: logs: line: {
@@ -181,15 +238,18 @@ components: sources: fluent: {
 		secure_mode: {
 			title: "Secure forward mode support"
 			body:  """
-				The `fluent` source currently supports using TLS, but does not support the authentication part of the
-				Fluent protocol including:
+				The `fluent` source supports TLS as well as the authentication part of the Fluent protocol,
+				configured with the `security` option:
 
 				- Shared key
 				- Username and password
 
-				And so these options of the secure forward output plugins for Fluent and Fluent Bit cannot be used.
+				When `security` is set, clients must complete the handshake with the shared key, and with one of the
+				configured `users` if any, before sending events. Clients failing to authenticate are told why and their
+				connection is closed.
 
-				If you would find this useful, [please let us know](\(urls.vector_repo)/issues/7532).
+				For Fluentd, this corresponds to the `<security>` section of the `forward` output plugin, and for Fluent
+				Bit, to the `Shared_Key`, `Username` and `Password` options of the `forward` output plugin.
 				"""
 		}
 	}
@@ -0,0 +1,197 @@
+package metadata
+
+components: sources: gcp_cloud_storage: {
+	title: "GCP Cloud Storage"
+
+	features: {
+		acknowledgements: true
+		collect: {
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+			}
+			checkpoint: enabled: false
+			proxy: enabled:      true
+			from: service:       services.gcp_cloud_storage
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "newline_delimited"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: [
+			"""
+				The bucket must publish [Pub/Sub notifications](\(urls.gcs_pubsub_notifications)) with the
+				`JSON_API_V1` or `NONE` payload format to a topic, and that topic must have a subscription
+				for Vector to pull from.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._source_acknowledgements
+		api_key:          configuration._gcp_api_key
+		credentials_path: configuration._gcp_credentials_path
+		compression: {
+			common:      false
+			description: "The compression format of the objects."
+			required:    false
+			type: string: {
+				default: "auto"
+				enum: {
+					auto: "Vector will try to determine the compression format of the object from its: `Content-Encoding` metadata, `Content-Type` metadata, and name suffix (e.g. `.gz`). It will fallback to 'none' if it cannot determine the compression."
+					gzip: "GZIP format."
+					zstd: "ZSTD format."
+					none: "Uncompressed."
+				}
+			}
+		}
+		endpoint: {
+			common:      false
+			description: "The endpoint from which to pull bucket notifications."
+			required:    false
+			type: string: {
+				default: "https://pubsub.googleapis.com"
+				examples: ["https://us-central1-pubsub.googleapis.com"]
+			}
+		}
+		max_messages: {
+			common:      false
+			description: "The maximum number of notifications to pull in a single request."
+			required:    false
+			type: uint: {
+				default: 10
+				examples: [1, 100]
+				unit: null
+			}
+		}
+		poll_secs: {
+			common:      false
+			description: "How long to wait before pulling again when the subscription had no notifications or the previous pull failed."
+			required:    false
+			type: uint: {
+				default: 15
+				unit:    "seconds"
+			}
+		}
+		project: {
+			description: "The project which owns the Pub/Sub subscription."
+			required:    true
+			type: string: {
+				examples: ["vector-123456"]
+			}
+		}
+		storage_endpoint: {
+			common:      false
+			description: "The endpoint from which to download objects."
+			required:    false
+			type: string: {
+				default: "https://storage.googleapis.com"
+				examples: ["http://localhost:4443"]
+			}
+		}
+		subscription: {
+			description: "The Pub/Sub subscription receiving the bucket's notifications."
+			required:    true
+			type: string: {
+				examples: ["vector-bucket-notifications"]
+			}
+		}
+	}
+
+	output: logs: object: {
+		description: "A line read from a Cloud Storage object."
+		fields: {
+			message: {
+				description: "A line from the object."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+					syntax: "literal"
+				}
+			}
+			bucket: {
+				description: "The bucket of the object the line came from."
+				required:    true
+				type: string: {
+					examples: ["my-bucket"]
+					syntax: "literal"
+				}
+			}
+			object: {
+				description: "The name of the object the line came from."
+				required:    true
+				type: string: {
+					examples: ["date=2022-09-01/app.log.gz"]
+					syntax: "literal"
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The last time the object was updated, falling back to the time the notification was published when the notification has no payload."
+			}
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		gcs_notification_ignored_total:       components.sources.internal_metrics.output.metrics.gcs_notification_ignored_total
+	}
+
+	how_it_works: {
+		notifications: {
+			title: "Bucket notifications"
+			body: """
+				The `gcp_cloud_storage` source pulls the bucket's notifications from a Pub/Sub
+				subscription. Only `OBJECT_FINALIZE` notifications, which are sent when an object is
+				created or overwritten, cause the object to be downloaded, decompressed and decoded;
+				all other notifications are acknowledged and skipped.
+
+				A notification is acknowledged once all of the events read from its object have been
+				processed by the sink(s), if the sink(s) have `acknowledgements` enabled, or sent
+				downstream otherwise. Notifications that could not be processed are left
+				unacknowledged and are redelivered by Pub/Sub once their acknowledgement deadline
+				expires, which can lead to duplicate events for objects that were partially read.
+				"""
+		}
+		custom_metadata: {
+			title: "Custom metadata"
+			body: """
+				When the notification uses the `JSON_API_V1` payload format, the object's
+				[custom metadata](\(urls.gcs_custom_metadata)) is added as fields to each event.
+				"""
+		}
+	}
+}
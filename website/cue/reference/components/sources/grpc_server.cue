@@ -0,0 +1,133 @@
+package metadata
+
+components: sources: grpc_server: {
+	_port: 50051
+
+	title: "gRPC Server"
+
+	description: """
+		Receives the messages of a gRPC method described by a user supplied file descriptor
+		set, such as the ones pushed by custom in-house agents.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: {
+					name:     "gRPC client"
+					thing:    "a \(name)"
+					url:      urls.grpc
+					versions: null
+				}
+
+				interface: socket: {
+					direction: "incoming"
+					port:      _port
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+			receive_buffer_bytes: enabled: false
+			keepalive: enabled:            true
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._source_acknowledgements
+		address: {
+			description: "The address to listen for connections on. It _must_ include a port."
+			required:    true
+			type: string: {
+				examples: ["0.0.0.0:\(_port)"]
+			}
+		}
+		desc_file: {
+			description: "The path to the compiled [file descriptor set](\(urls.protobuf)) of the service, as produced by `protoc --include_imports --descriptor_set_out`."
+			required:    true
+			type: string: {
+				examples: ["/etc/vector/agent.desc"]
+			}
+		}
+		method: {
+			description: "The fully qualified name of the method to serve."
+			required:    true
+			type: string: {
+				examples: ["agent.Ingest/Push"]
+			}
+		}
+		response: {
+			common:      false
+			description: "The fields of the response message of the method, which is empty by default. The enum values are either their names or their numbers."
+			required:    false
+			type: object: {
+				examples: [{"accepted": true, "note": "thanks"}]
+				options: {}
+			}
+		}
+	}
+
+	output: logs: message: {
+		description: "A request message of the method."
+		fields: {
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["grpc_server"]
+				}
+			}
+			timestamp: fields._current_timestamp
+			"*": {
+				description: "The fields of the message, including the ones set to their defaults. The enum values are their names."
+				required:    true
+				type: "*": {}
+			}
+		}
+	}
+
+	how_it_works: {
+		calls: {
+			title: "Calls"
+			body: """
+				The unary calls and the streaming calls are all accepted. Each message sent by the
+				client is an event, and once all the messages of the call are delivered, the call is
+				answered with the single `response` message. The messages can be compressed with gzip
+				or zstd. Calls to other methods fail with the `UNIMPLEMENTED` status.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total:     components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -68,7 +68,7 @@ components: sources: host_metrics: {
 
 	configuration: {
 		collectors: {
-			description: "The list of host metric collector services to use. Defaults to all collectors."
+			description: "The list of host metric collector services to use. Defaults to all collectors, except the `process` collector."
 			common:      true
 			required:    false
 			type: array: {
@@ -83,6 +83,7 @@ components: sources: host_metrics: {
 						host:       "Metrics related to host"
 						memory:     "Metrics related to memory utilization."
 						network:    "Metrics related to network utilization."
+						process:    "Metrics related to the resource usage of each process."
 					}
 				}
 			}
@@ -401,6 +402,95 @@ components: sources: host_metrics: {
 				}
 			}
 		}
+		process: {
+			common:      false
+			description: #"Options for the "process" metrics collector."#
+			required:    false
+			type: object: options: {
+				cmdlines: {
+					common:      false
+					required:    false
+					description: "Lists of process command line patterns to include or exclude."
+					type: object: options: {
+						includes: {
+							required: false
+							common:   false
+							description: """
+								The list of process command line patterns for which to gather process metrics.
+
+								Defaults to including all processes.
+
+								The patterns are matched using globbing.
+								"""
+							type: array: {
+								default: ["*"]
+								items: type: string: {
+									examples: ["*kafka.Kafka*"]
+								}
+							}
+						}
+						excludes: {
+							required: false
+							common:   false
+							description: """
+								The list of process command line patterns for which to not gather process metrics.
+
+								Defaults to excluding no processes.
+
+								The patterns are matched using globbing.
+								"""
+							type: array: {
+								default: []
+								items: type: string: {
+									examples: ["*kafka.Kafka*"]
+								}
+							}
+						}
+					}
+				}
+				names: {
+					common:      false
+					required:    false
+					description: "Lists of process name patterns to include or exclude."
+					type: object: options: {
+						includes: {
+							required: false
+							common:   false
+							description: """
+								The list of process name patterns for which to gather process metrics.
+
+								Defaults to including all processes.
+
+								The patterns are matched using globbing.
+								"""
+							type: array: {
+								default: ["*"]
+								items: type: string: {
+									examples: ["nginx", "java*"]
+								}
+							}
+						}
+						excludes: {
+							required: false
+							common:   false
+							description: """
+								The list of process name patterns for which to not gather process metrics.
+
+								Defaults to excluding no processes.
+
+								The patterns are matched using globbing.
+								"""
+							type: array: {
+								default: []
+								items: type: string: {
+									examples: ["nginx", "java*"]
+								}
+							}
+						}
+					}
+				}
+			}
+		}
 	}
 
 	output: metrics: {
@@ -494,6 +584,21 @@ components: sources: host_metrics: {
 		network_transmit_packets_drop_total: _host & _network_nomac & {description: "The number of packets dropped during transmits on this interface."}
 		network_transmit_packets_total:      _host & _network_nomac & {description: "The number of packets transmitted on this interface."}
 
+		// Host process
+		process_cpu_seconds_total: _host & _process_counter & {
+			description: "The number of CPU seconds used by the process in different operating modes."
+			tags: mode: {
+				description: "Which mode the CPU was running in during the given time."
+				required:    true
+				examples: ["system", "user"]
+			}
+		}
+		process_io_read_bytes_total:    _host & _process_counter & _process_linux & {description: "The number of bytes the process read from storage."}
+		process_io_written_bytes_total: _host & _process_counter & _process_linux & {description: "The number of bytes the process wrote to storage."}
+		process_memory_rss_bytes:       _host & _process_gauge & {description:                    "The number of bytes of main memory used by the process."}
+		process_memory_virtual_bytes:   _host & _process_gauge & {description:                    "The number of bytes of virtual memory used by the process."}
+		process_open_fds:               _host & _process_gauge & _process_linux & {description:   "The number of file descriptors the process has open."}
+
 		// Helpers
 		_host: {
 			default_namespace: "host"
@@ -583,6 +688,28 @@ components: sources: host_metrics: {
 			}
 		}
 		_network_nomac: _network_gauge & {relevant_when: "OS is not macOS"}
+		_process_tags: _host_metrics_tags & {
+			collector: examples: ["process"]
+			name: {
+				description: "The name of the process."
+				required:    true
+				examples: ["nginx"]
+			}
+			pid: {
+				description: "The identifier of the process."
+				required:    true
+				examples: ["1234"]
+			}
+		}
+		_process_counter: {
+			type: "counter"
+			tags: _process_tags
+		}
+		_process_gauge: {
+			type: "gauge"
+			tags: _process_tags
+		}
+		_process_linux: {relevant_when: "OS is Linux"}
 	}
 
 	telemetry: metrics: {
@@ -144,6 +144,116 @@ components: sources: http: {
 				}
 			}
 		}
+		method_key: {
+			common:      false
+			description: "The event key in which the HTTP method of the request will be stored."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["http_method"]
+			}
+		}
+		headers_key: {
+			common:      false
+			description: "The event key in which all the HTTP headers of the request will be stored, as an object of their lowercase names and their values."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["http_headers"]
+			}
+		}
+		query_parameters_key: {
+			common:      false
+			description: "The event key in which all the URL query parameters of the request will be stored, as an object of their names and values."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["http_query"]
+			}
+		}
+		response: {
+			common:      false
+			description: "The response to the requests whose events were accepted, instead of an empty `200 OK` response."
+			required:    false
+			type: object: options: {
+				body: {
+					common:      true
+					description: "The body of the response, rendered against the first event of the request."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["{{ id }} accepted"]
+						syntax: "template"
+					}
+				}
+				status: {
+					common:      true
+					description: "The status code of the response, rendered against the first event of the request. The responses whose status can't be rendered have the `200` status."
+					required:    false
+					type: string: {
+						default: "200"
+						examples: ["202", "{{ status }}"]
+						syntax: "template"
+					}
+				}
+			}
+		}
+		routes: {
+			common:      false
+			description: "The routes on which requests are accepted, instead of `path`. The settings of the source are the defaults of the settings of the routes."
+			required:    false
+			type: array: {
+				default: []
+				items: type: object: options: {
+					decoding: {
+						common:      false
+						description: "The decoding configuration of the requests of the route, which defaults to the `decoding` of the source."
+						required:    false
+						type: object: options: codec: {
+							description: "The codec to use for decoding events."
+							required:    true
+							type: string: examples: ["json", "bytes"]
+						}
+					}
+					framing: {
+						common:      false
+						description: "The framing configuration of the requests of the route, which defaults to the `framing` of the source."
+						required:    false
+						type: object: options: method: {
+							description: "The framing method."
+							required:    true
+							type: string: examples: ["newline_delimited", "bytes"]
+						}
+					}
+					method: {
+						common:      false
+						description: "The method of the requests of the route, which defaults to the `method` of the source."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["PUT"]
+						}
+					}
+					path: {
+						description: "The URL path on which the requests of the route shall be sent."
+						required:    true
+						type: string: examples: ["/logs", "/items"]
+					}
+					response: {
+						common:      false
+						description: "The response to the requests of the route, with the same options as the `response` of the source, which it defaults to."
+						required:    false
+						type: object: options: {}
+					}
+					strict_path: {
+						common:      false
+						description: "Whether or not to treat the `path` of the route as an absolute path, which defaults to the `strict_path` of the source."
+						required:    false
+						type: bool: default: null
+					}
+				}
+			}
+		}
 	}
 	output: logs: {
 		text: {
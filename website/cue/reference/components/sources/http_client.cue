@@ -0,0 +1,230 @@
+package metadata
+
+components: sources: http_client: {
+	title: "HTTP Client"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["daemon", "sidecar", "aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: {
+					name:     "HTTP server"
+					thing:    "an \(name)"
+					url:      urls.http_client
+					versions: null
+				}
+
+				interface: socket: {
+					direction: "outgoing"
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+			proxy: enabled: true
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		endpoint: {
+			description: "The endpoint to request."
+			required:    true
+			type: string: {
+				examples: ["https://api.example.com/v1/items?limit=100"]
+			}
+		}
+		max_pages: {
+			common:      false
+			description: "The maximum number of pages requested at each interval."
+			required:    false
+			type: uint: {
+				default: 100
+				unit:    null
+			}
+		}
+		pagination: {
+			common:      true
+			description: "How the next pages of a response are requested."
+			required:    false
+			type: object: options: {
+				cursor_path: {
+					description:   "The [JSONPath](\(urls.json_path)) of the cursor in the response body."
+					relevant_when: "strategy = \"cursor\""
+					required:      true
+					type: string: {
+						examples: ["$.meta.next_cursor"]
+					}
+				}
+				query_param: {
+					description:   "The query parameter the cursor or the page number is passed with."
+					relevant_when: "strategy = \"cursor\" or strategy = \"page\""
+					required:      false
+					type: string: {
+						default: "page"
+						examples: ["after", "page"]
+					}
+				}
+				start_page: {
+					common:        false
+					description:   "The number of the first page."
+					relevant_when: "strategy = \"page\""
+					required:      false
+					type: uint: {
+						default: 1
+						unit:    null
+					}
+				}
+				strategy: {
+					description: "The pagination strategy."
+					required:    false
+					type: string: {
+						default: "none"
+						enum: {
+							none:        "Only the endpoint itself is requested."
+							cursor:      "The next page is requested with the cursor found in the response body, until the response has no cursor."
+							link_header: "The next page is the URL of the `next` relation of the `Link` header of the response."
+							page:        "The pages are requested by number, until a page has no events."
+						}
+					}
+				}
+			}
+		}
+		query: {
+			common: false
+			description: """
+				Custom parameters for the query string of the requests, which are added to the ones of the `endpoint`.
+				The values are templates rendered for each request, with `timestamp` being the time of the request
+				and `previous_timestamp` the time of the previous request.
+				"""
+			required: false
+			type: object: {
+				examples: [{"since": ["{{ previous_timestamp }}"], "date": ["%Y-%m-%d"]}]
+				options: {
+					"*": {
+						common:      false
+						description: "Any query key"
+						required:    false
+						type: array: {
+							default: null
+							items: type: string: {
+								examples: ["{{ previous_timestamp }}"]
+								syntax: "template"
+							}
+						}
+					}
+				}
+			}
+		}
+		records_path: {
+			common:      true
+			description: "The [JSONPath](\(urls.json_path)) of the records of the JSON responses, each of which is an event. The arrays selected are split into their elements. When set, the responses are parsed as JSON instead of being decoded by `framing` and `decoding`."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["$.data.items", "$.results[*].record"]
+			}
+		}
+		scrape_interval_secs: {
+			common:      true
+			description: "The interval between the requests of the endpoint, in seconds."
+			required:    false
+			type: uint: {
+				default: 15
+				unit:    "seconds"
+			}
+		}
+		auth: configuration._http_auth & {_args: {
+			password_example: "${HTTP_PASSWORD}"
+			username_example: "${HTTP_USERNAME}"
+		}}
+	}
+
+	output: logs: record: {
+		description: "A record of a response."
+		fields: {
+			message: {
+				description: "The record, when it isn't an object."
+				required:    false
+				type: string: {
+					examples: ["Hello world"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["http_client"]
+				}
+			}
+			timestamp: {
+				description: "The time the response was received, unless the record has a `timestamp` field."
+				required:    true
+				type: timestamp: {}
+			}
+			"*": {
+				description: "The fields of the records that are objects."
+				required:    false
+				type: "*": {}
+			}
+		}
+	}
+
+	how_it_works: {
+		pagination: {
+			title: "Pagination"
+			body: """
+				At each interval, the pages of the response are requested one after the other, up to
+				`max_pages`, following the `next` relation of the `Link` header, the cursor found in
+				the body at `pagination.cursor_path`, or increasing page numbers until a page has no
+				events. The requests stop at the first error, and start again from the first page at
+				the next interval.
+				"""
+		}
+
+		records: {
+			title: "Records"
+			body: """
+				With `records_path`, each value selected from the JSON response is an event: the
+				arrays are split into their elements, the objects become the fields of the event, and
+				the other values are its `message`.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
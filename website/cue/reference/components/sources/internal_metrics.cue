@@ -123,6 +123,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		aggregate_logs_flushes_total: {
+			description:       "The number of windows flushed by the aggregate_logs transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		api_started_total: {
 			description:       "The number of times the Vector GraphQL API has been started."
 			type:              "counter"
@@ -171,6 +177,41 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		enrichment_table_last_reload_timestamp_seconds: {
+			description:       "The time at which the data of the enrichment table was last loaded."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags: _internal_metrics_tags & {
+				table: {
+					description: "The name of the enrichment table."
+					required:    true
+				}
+			}
+		}
+		enrichment_table_reload_errors_total: {
+			description:       "The total number of errors reloading the data of the enrichment table."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _internal_metrics_tags & {
+				table: {
+					description: "The name of the enrichment table."
+					required:    true
+				}
+				error_type: _error_type
+				stage:      _stage
+			}
+		}
+		enrichment_table_rows: {
+			description:       "The number of rows loaded into the enrichment table."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags: _internal_metrics_tags & {
+				table: {
+					description: "The name of the enrichment table."
+					required:    true
+				}
+			}
+		}
 		quit_total: {
 			description:       "The total number of times the Vector instance has quit."
 			type:              "counter"
@@ -254,6 +295,45 @@ components: sources: internal_metrics: {
 				file: _file
 			}
 		}
+		chronicle_bytes_sent_total: {
+			description:       "The total number of bytes successfully sent to Chronicle, per log type."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				log_type: {
+					description: "The rendered Chronicle log type of the request."
+					required:    true
+				}
+			}
+		}
+		chronicle_events_sent_total: {
+			description:       "The total number of events successfully sent to Chronicle, per log type."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				log_type: {
+					description: "The rendered Chronicle log type of the request."
+					required:    true
+				}
+			}
+		}
+		chronicle_fallback_log_type_events_total: {
+			description:       "The total number of events sent with the fallback log type because their `log_type` template failed to render."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags
+		}
+		chronicle_requests_sent_total: {
+			description:       "The total number of requests successfully sent to Chronicle, per log type."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				log_type: {
+					description: "The rendered Chronicle log type of the request."
+					required:    true
+				}
+			}
+		}
 		collect_completed_total: {
 			description:       "The total number of metrics collections completed for this component."
 			type:              "counter"
@@ -334,6 +414,30 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		gcs_notification_ignored_total: {
+			description:       "The total number of times a Cloud Storage bucket notification was ignored (for an event that was not `OBJECT_FINALIZE`)."
+			type:              "counter"
+			default_namespace: "vector"
+
+			tags: _component_tags & {
+				event_type: {
+					description: "The type of the ignored notification, such as `OBJECT_DELETE`."
+					required:    true
+				}
+			}
+		}
+		gcp_token_refresh_attempts_total: {
+			description:       "The total number of attempts to refresh the GCP authentication token."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		gcp_token_refresh_failures_total: {
+			description:       "The total number of failed attempts to refresh the GCP authentication token."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		k8s_format_picker_edge_cases_total: {
 			description:       "The total number of edge cases encountered while picking format of the Kubernetes log message."
 			type:              "counter"
@@ -826,6 +930,25 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		limited_series_events_total: {
+			description: """
+				The total number of metric events of new series limited after hitting the
+				configured `series_limit`.
+				"""
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				action: {
+					description: "The action taken on the metric event."
+					required:    true
+					enum: {
+						drop_series: "The metric event was dropped."
+						overflow:    "The metric event was aggregated into the overflow series."
+						strip_tags:  "The tags of the metric event with new values were removed."
+					}
+				}
+			}
+		}
 		logging_driver_errors_total: {
 			description: """
 				The total number of logging driver errors encountered caused by not using either
@@ -853,6 +976,30 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		metrics_rollup_events_recorded_total: {
+			description:       "The number of events recorded by the metrics_rollup transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		metrics_rollup_failed_updates_total: {
+			description:       "The number of failed metric updates, `incremental` adds, encountered by the metrics_rollup transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		metrics_rollup_flushes_total: {
+			description:       "The number of intervals flushed by the metrics_rollup transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		metrics_rollup_series_flushed_total: {
+			description:       "The number of series flushed by the metrics_rollup transform, one per metric output."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		open_connections: {
 			description:       "The number of current open connections to Vector."
 			type:              "gauge"
@@ -865,6 +1012,17 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		pii_redactions_total: {
+			description:       "The number of PII values redacted by the redact_pii transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				detector: {
+					description: "The type of the detector that found the PII."
+					required:    true
+				}
+			}
+		}
 		processed_bytes_total: {
 			description:       "The number of bytes processed by the component."
 			type:              "counter"
@@ -956,12 +1114,33 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		rewritten_timestamp_events_total: {
+			description:       "The total number of out-of-order events whose timestamp was rewritten."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		send_errors_total: {
 			description:       "The total number of errors sending messages."
 			type:              "counter"
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		series_limit_reached_total: {
+			description: """
+				The total number of times a metric name reached the configured `series_limit`,
+				after which its new series are limited.
+				"""
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		sharded_stream_events_total: {
+			description:       "The total number of out-of-order events sent to a shard of their stream."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		splunk_pending_acks: {
 			description:       "The number of outstanding Splunk HEC indexer acknowledgement acks."
 			type:              "gauge"
@@ -1052,6 +1231,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		tail_sample_traces_evicted_total: {
+			description:       "The number of traces decided on early by the tail_sample transform because the maximum number of pending traces was reached."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		timestamp_parse_errors_total: {
 			description:       "The total number of errors encountered parsing [RFC 3339](\(urls.rfc_3339)) timestamps."
 			type:              "counter"
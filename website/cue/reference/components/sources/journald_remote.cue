@@ -0,0 +1,136 @@
+package metadata
+
+components: sources: journald_remote: {
+	_port: 19532
+
+	title: "Journald Remote"
+
+	description: """
+		Receives the journal entries uploaded by
+		[`systemd-journal-upload`](\(urls.journal_upload)) from remote hosts.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.journald
+
+				interface: socket: {
+					api: {
+						title: "Journal Export Format"
+						url:   urls.journal_export_format
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._source_acknowledgements
+		address: {
+			description: "The address to listen for uploads on. `systemd-journal-upload` sends the journal entries to port `19532` by default."
+			required:    true
+			type: string: {
+				examples: ["0.0.0.0:\(_port)"]
+			}
+		}
+		auth: sources.http.configuration.auth
+	}
+
+	output: logs: event: {
+		description: "A journal entry uploaded by a remote host."
+		fields: {
+			host: {
+				description: "The name of the host the entry was written on, from the `_HOSTNAME` field."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["my-host.local"]
+				}
+			}
+			message: {
+				description: "The message of the entry, from the `MESSAGE` field."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["Started Session 1 of user root."]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["journald_remote"]
+				}
+			}
+			timestamp: {
+				description: "The time the entry was written, from the `_SOURCE_REALTIME_TIMESTAMP` field, or the `__REALTIME_TIMESTAMP` field when it's missing."
+				required:    true
+				type: timestamp: {}
+			}
+			"*": {
+				common:      false
+				description: "Any field of the entry. The fields that appear several times in an entry are collected into an array."
+				required:    false
+				type: string: {
+					default: null
+					examples: ["/usr/sbin/sshd", "sshd.service"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		journal_upload: {
+			title: "Configuring `systemd-journal-upload`"
+			body: """
+				Point the `URL` option of `journal-upload.conf` on the remote hosts to the address
+				of the source, such as `http://vector.example.com:19532`. The entries are posted to
+				the `/upload` path, in the [journal export format](\(urls.journal_export_format)).
+				When acknowledgements are enabled, the uploads are only accepted once their entries
+				are delivered, and are rejected otherwise.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		http_bad_requests_total:              components.sources.internal_metrics.output.metrics.http_bad_requests_total
+	}
+}
@@ -39,6 +39,45 @@ components: sources: kafka: {
 
 	configuration: {
 		acknowledgements: configuration._source_acknowledgements
+		assignments: {
+			common:      false
+			description: "The partitions to read events from, bypassing the balancing of the partitions between the members of the consumer group. Each partition is read from its configured offset, regardless of the offsets committed for the consumer group. Can't be set along with `topics`."
+			required:    false
+			type: array: {
+				default: []
+				items: type: object: {
+					examples: [{topic: "logs", partition: 0, offset: "earliest"}, {topic: "logs", partition: 1, offset: {timestamp: 1660000000000}}]
+					options: {
+						offset: {
+							common:      true
+							description: "The offset the partition is read from: `earliest` or `latest`, or a table with either the `timestamp`, in milliseconds since the Unix epoch, of the first message to read or the `absolute` offset of that message."
+							required:    false
+							type: string: {
+								default: "latest"
+								enum: {
+									earliest: "The first message of the partition."
+									latest:   "The first message written to the partition once the source is started."
+								}
+							}
+						}
+						partition: {
+							description: "The partition to read events from."
+							required:    true
+							type: int: {
+								examples: [0, 1]
+							}
+						}
+						topic: {
+							description: "The topic of the partition."
+							required:    true
+							type: string: {
+								examples: ["logs"]
+							}
+						}
+					}
+				}
+			}
+		}
 		auto_offset_reset: {
 			common:      false
 			description: """
@@ -63,6 +102,31 @@ components: sources: kafka: {
 				unit: "milliseconds"
 			}
 		}
+		decoding_routes: {
+			common:      false
+			description: "Selects the deserializer of each message by the value of one of its headers, such as its content type, to read topics holding messages of mixed formats."
+			required:    false
+			type: object: {
+				examples: [{header: "content-type", routes: {"application/json": {codec: "json"}}}]
+				options: {
+					header: {
+						description: "The header whose value selects the deserializer of the message."
+						required:    true
+						type: string: {
+							examples: ["content-type"]
+						}
+					}
+					routes: {
+						description: "The deserializers of the messages, by the value of their header, with the same options as `decoding`. The messages without the header, or with a value not listed here, are decoded with `decoding`."
+						required:    true
+						type: object: {
+							examples: [{"application/json": {codec: "json"}, "text/plain": {codec: "bytes"}}]
+							options: {}
+						}
+					}
+				}
+			}
+		}
 		fetch_wait_max_ms: {
 			common:      false
 			description: "Maximum time the broker may wait to fill the response."
@@ -181,10 +245,13 @@ components: sources: kafka: {
 		}
 		socket_timeout_ms: components._kafka.configuration.socket_timeout_ms
 		topics: {
-			description: "The Kafka topics names to read events from. Regex is supported if the topic begins with `^`."
-			required:    true
-			type: array: items: type: string: {
-				examples: ["^(prefix1|prefix2)-.+", "topic-1", "topic-2"]
+			description: "The Kafka topics names to read events from. Regex is supported if the topic begins with `^`. Can't be set along with `assignments`."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["^(prefix1|prefix2)-.+", "topic-1", "topic-2"]
+				}
 			}
 		}
 	}
@@ -0,0 +1,222 @@
+package metadata
+
+components: sources: kubernetes_events: {
+	title: "Kubernetes Events"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.kubernetes
+
+				interface: socket: {
+					api: {
+						title: "Kubernetes API"
+						url:   urls.kubernetes_watch_api
+					}
+					direction: "outgoing"
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: [
+			"""
+				[Kubernetes](\(urls.kubernetes)) version `\(services.kubernetes.versions)` is required.
+				""",
+			"""
+				Vector must be allowed to `list` and `watch` the `events` resource, with a
+				`ClusterRole` when all the namespaces are watched, or with a `Role` in each of the
+				configured `namespaces` otherwise. See [RBAC](\(urls.kubernetes_rbac)).
+				""",
+		]
+		warnings: [
+			"""
+				A single Vector instance should run this source for a cluster, as each instance
+				reads all the events of the namespaces it watches.
+				""",
+		]
+		notices: []
+	}
+
+	installation: {
+		platform_name: "kubernetes"
+	}
+
+	configuration: {
+		data_dir: {
+			common:      false
+			description: "The directory used to persist the resource versions the watches are resumed from. By default, the [global `data_dir` option](\(urls.vector_configuration)/global-options#data_dir) is used. Please make sure the user Vector is running as has write permissions to this directory."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["/var/local/lib/vector/"]
+			}
+		}
+		field_selector: {
+			common:      true
+			description: "The field selector to filter the events with."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["type=Warning", "involvedObject.kind=Pod"]
+			}
+		}
+		kube_config_file: {
+			common:      false
+			description: "Optional path to a kubeconfig file readable by Vector. If not set, Vector will try to connect to Kubernetes using in-cluster configuration."
+			required:    false
+			type: string: default: null
+		}
+		label_selector: {
+			common:      false
+			description: "The label selector to filter the events with."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["app=web"]
+			}
+		}
+		namespaces: {
+			common:      true
+			description: "The namespaces to watch the events of. By default, the events of all the namespaces are watched."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["default", "kube-system"]
+				}
+			}
+		}
+		read_existing_events: {
+			common:      false
+			description: "Read the events already in the cluster when Vector starts for the first time, instead of only the events that happen afterwards."
+			required:    false
+			type: bool: default: false
+		}
+	}
+
+	output: logs: event: {
+		description: "A Kubernetes event, with the fields of the `Event` resource."
+		fields: {
+			count: {
+				description: "The number of times the event happened."
+				required:    false
+				common:      true
+				type: uint: {
+					default: null
+					examples: [5]
+					unit: null
+				}
+			}
+			involvedObject: {
+				description: "The object the event is about."
+				required:    true
+				type: object: {
+					examples: [{"kind": "Pod", "name": "web-0", "namespace": "default"}]
+					options: {}
+				}
+			}
+			message: {
+				description: "The description of the event."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["Back-off restarting failed container"]
+				}
+			}
+			metadata: {
+				description: "The metadata of the event, without its managed fields."
+				required:    true
+				type: object: {
+					examples: [{"name": "web-0.1700", "namespace": "default", "uid": "d5a4e5f6-1c2b-4b1a-8f0e-3c3e0c8b9a7d"}]
+					options: {}
+				}
+			}
+			reason: {
+				description: "The reason of the event."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["BackOff"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["kubernetes_events"]
+				}
+			}
+			timestamp: {
+				description: "The last time the event happened."
+				required:    true
+				type: timestamp: {}
+			}
+			type: {
+				description: "The type of the event."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["Normal", "Warning"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		resuming: {
+			title: "Resuming"
+			body: """
+				Vector stores the resource version of the last event read from each watch in its
+				data directory, and resumes the watches from it after a restart. The bookmarks sent
+				by the API server keep the resource version current when no events happen. When
+				the resource version is too old to resume from, the events are listed again, and
+				only the events that changed since they were last read are sent.
+				"""
+		}
+
+		deduplication: {
+			title: "Deduplication"
+			body: """
+				The events that happen repeatedly are updated in place by Kubernetes, with their
+				`count` increased. Each update is sent once, and the events that are listed again
+				without having changed are skipped.
+				"""
+		}
+
+		rbac: {
+			title: "RBAC"
+			body: """
+				When `namespaces` is empty, the events of the whole cluster are watched by a single
+				watch, which requires a `ClusterRole` allowing to `list` and `watch` the `events`.
+				When namespaces are configured, each of them is watched separately, and a `Role`
+				in each namespace is enough.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -185,6 +185,97 @@ components: sources: kubernetes_logs: {
 				}
 			}
 		}
+		owner_metadata: {
+			common:      false
+			description: "Whether or not to annotate the events with the names of the owners of their `Pod`, such as their `Deployment`, `StatefulSet` or `CronJob`. The `ReplicaSet`s, `Deployment`s and `Job`s of the cluster are watched to follow the chain of owners, which requires Vector to be allowed to list and watch them."
+			required:    false
+			type: bool: default: false
+		}
+		owner_annotation_fields: {
+			common:      false
+			description: "Configuration for how the events are annotated with the metadata of the owners of their `Pod`, when `owner_metadata` is enabled."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					cronjob_name: {
+						common:      false
+						description: "Event field for the name of the `CronJob` owning the `Job` of the `Pod`."
+						required:    false
+						type: string: {
+							default: "kubernetes.cronjob_name"
+						}
+					}
+					daemonset_name: {
+						common:      false
+						description: "Event field for the name of the `DaemonSet` owning the `Pod`."
+						required:    false
+						type: string: {
+							default: "kubernetes.daemonset_name"
+						}
+					}
+					deployment_annotations: {
+						common:      false
+						description: "Event field for Deployment annotations."
+						required:    false
+						type: string: {
+							default: "kubernetes.deployment_annotations"
+						}
+					}
+					deployment_labels: {
+						common:      false
+						description: "Event field for Deployment labels."
+						required:    false
+						type: string: {
+							default: "kubernetes.deployment_labels"
+						}
+					}
+					deployment_name: {
+						common:      false
+						description: "Event field for the name of the `Deployment` owning the `ReplicaSet` of the `Pod`."
+						required:    false
+						type: string: {
+							default: "kubernetes.deployment_name"
+						}
+					}
+					job_name: {
+						common:      false
+						description: "Event field for the name of the `Job` owning the `Pod`."
+						required:    false
+						type: string: {
+							default: "kubernetes.job_name"
+						}
+					}
+					replicaset_name: {
+						common:      false
+						description: "Event field for the name of the `ReplicaSet` owning the `Pod`."
+						required:    false
+						type: string: {
+							default: "kubernetes.replicaset_name"
+						}
+					}
+					statefulset_name: {
+						common:      false
+						description: "Event field for the name of the `StatefulSet` owning the `Pod`."
+						required:    false
+						type: string: {
+							default: "kubernetes.statefulset_name"
+						}
+					}
+				}
+			}
+		}
+		annotations_allowlist: {
+			common:      false
+			description: "A list of glob patterns of the annotations of the `Pod`s and `Deployment`s to annotate the events with. By default, all the annotations are added to the events."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["app.kubernetes.io/*", "example.com/team"]
+				}
+			}
+		}
 		auto_partial_merge: {
 			common:      false
 			description: "Automatically merge partial messages into a single event. Partial here is in respect to messages that were split by the Kubernetes Container Runtime log driver."
@@ -349,6 +440,60 @@ components: sources: kubernetes_logs: {
 					examples: ["coredns"]
 				}
 			}
+			"kubernetes.cronjob_name": {
+				description: "Name of the `CronJob` owning the `Job` of the Pod. Only when `owner_metadata` is enabled."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["backup"]
+				}
+			}
+			"kubernetes.daemonset_name": {
+				description: "Name of the `DaemonSet` owning the Pod. Only when `owner_metadata` is enabled."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["vector"]
+				}
+			}
+			"kubernetes.deployment_annotations": {
+				description: "Set of annotations attached to the `Deployment` owning the Pod. Only when `owner_metadata` is enabled."
+				required:    false
+				common:      false
+				type: object: {
+					examples: [{"myannotation": "myvalue"}]
+					options: {}
+				}
+			}
+			"kubernetes.deployment_labels": {
+				description: "Set of labels attached to the `Deployment` owning the Pod. Only when `owner_metadata` is enabled."
+				required:    false
+				common:      false
+				type: object: {
+					examples: [{"mylabel": "myvalue"}]
+					options: {}
+				}
+			}
+			"kubernetes.deployment_name": {
+				description: "Name of the `Deployment` owning the `ReplicaSet` of the Pod. Only when `owner_metadata` is enabled."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["coredns"]
+				}
+			}
+			"kubernetes.job_name": {
+				description: "Name of the `Job` owning the Pod. Only when `owner_metadata` is enabled."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["backup-27700000"]
+				}
+			}
 			"kubernetes.namespace_labels": {
 				description: "Set of labels attached to the Namespace."
 				required:    false
@@ -421,6 +566,24 @@ components: sources: kubernetes_logs: {
 					examples: ["minikube"]
 				}
 			}
+			"kubernetes.replicaset_name": {
+				description: "Name of the `ReplicaSet` owning the Pod. Only when `owner_metadata` is enabled."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["coredns-qwertyuiop"]
+				}
+			}
+			"kubernetes.statefulset_name": {
+				description: "Name of the `StatefulSet` owning the Pod. Only when `owner_metadata` is enabled."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["postgres"]
+				}
+			}
 			"kubernetes.pod_uid": {
 				description: "Pod uid."
 				required:    false
@@ -524,6 +687,28 @@ components: sources: kubernetes_logs: {
 				"""
 		}
 
+		owner_metadata: {
+			title: "Owner metadata"
+			body: """
+				When the `owner_metadata` option is enabled, Vector follows the owner references
+				of the `Pod`s to annotate the events with the names of their owners: the
+				`ReplicaSet` and `Deployment` of the `Pod`s of `Deployment`s, the `Job` and
+				`CronJob` of the `Pod`s of `CronJob`s, and the `StatefulSet` or `DaemonSet` of
+				their `Pod`s. These fields can be used to route the events by workload, rather
+				than by `Pod` labels.
+
+				Vector watches the `ReplicaSet`s, `Deployment`s and `Job`s of the whole cluster
+				for this purpose, and must be granted "list" and "watch" access to the
+				`/apis/apps/v1/replicasets`, `/apis/apps/v1/deployments`, and
+				`/apis/batch/v1/jobs` resources. The owners that Vector doesn't know of yet, such
+				as the `ReplicaSet` of a `Pod` that was just created, are skipped.
+
+				The `annotations_allowlist` option restricts the annotations of the `Pod`s and
+				`Deployment`s added to the events to the ones matching its glob patterns, to
+				avoid adding large annotations such as `kubectl.kubernetes.io/last-applied-configuration`.
+				"""
+		}
+
 		globbing: {
 			title: "Globbing"
 			body:  """
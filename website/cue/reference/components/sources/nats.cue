@@ -4,7 +4,7 @@ components: sources: nats: {
 	title: "NATS"
 
 	features: {
-		acknowledgements: false
+		acknowledgements: true
 		collect: {
 			checkpoint: enabled: false
 			from: components._nats.features.collect.from
@@ -32,6 +32,58 @@ components: sources: nats: {
 	}
 
 	configuration: components._nats.configuration & {
+		acknowledgements: configuration._source_acknowledgements
+		jetstream: {
+			common:      false
+			description: "Consumes a JetStream stream through a durable consumer, instead of subscribing to the subject. The consumer only receives the messages of the stream matching `subject`."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					ack_wait_secs: {
+						common:      false
+						description: "How long the server waits for a message to be acknowledged before delivering it again."
+						required:    false
+						type: uint: {
+							default: 30
+							unit:    "seconds"
+						}
+					}
+					batch_size: {
+						common:      false
+						description: "The maximum number of messages pulled from the consumer at once."
+						required:    false
+						type: uint: {
+							default: 100
+							unit:    null
+						}
+					}
+					durable_name: {
+						description: "The name of the durable consumer, which is created if it doesn't exist. The server keeps track of the messages acknowledged by the consumer, so that Vector resumes from the first unacknowledged message when it is restarted."
+						required:    true
+						type: string: {
+							examples: ["vector"]
+						}
+					}
+					max_deliver: {
+						common:      false
+						description: "The maximum number of times a message is delivered. By default, messages are delivered until they are acknowledged."
+						required:    false
+						type: int: {
+							default: null
+							examples: [5]
+						}
+					}
+					stream: {
+						description: "The name of the stream to consume."
+						required:    true
+						type: string: {
+							examples: ["logs"]
+						}
+					}
+				}
+			}
+		}
 		queue: {
 			common:      false
 			description: "NATS Queue Group to join"
@@ -67,5 +119,19 @@ components: sources: nats: {
 		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
 	}
 
-	how_it_works: components._nats.how_it_works
+	how_it_works: components._nats.how_it_works & {
+		jetstream: {
+			title: "JetStream"
+			body:  """
+				When `jetstream` is set, the messages of the stream are pulled from a durable
+				consumer, created with explicit acknowledgements if it doesn't exist. Each message
+				is acknowledged once its events are delivered when acknowledgements are enabled,
+				or once they are sent to the next components otherwise. The messages whose events
+				failed to be delivered are delivered again, unless they were rejected.
+
+				Messages that aren't acknowledged within `ack_wait_secs`, such as when Vector is
+				restarted, are delivered again, up to `max_deliver` times.
+				"""
+		}
+	}
 }
@@ -1,12 +1,13 @@
 package metadata
 
 components: sources: opentelemetry: {
-	_port: 4317
+	_grpc_port: 4317
+	_http_port: 4318
 
 	title: "OpenTelemetry"
 
 	description: """
-		Collect OpenTelemetry data over gRPC (currently, only logs are supported).
+		Collect OpenTelemetry logs, metrics, and traces over gRPC and HTTP, using the OpenTelemetry protocol (OTLP).
 		"""
 
 	classes: {
@@ -27,7 +28,7 @@ components: sources: opentelemetry: {
 
 				interface: socket: {
 					direction: "incoming"
-					port:      _port
+					port:      _grpc_port
 					protocols: ["tcp"]
 					ssl: "optional"
 				}
@@ -52,13 +53,48 @@ components: sources: opentelemetry: {
 
 	configuration: {
 		acknowledgements: configuration._source_acknowledgements
-		address: {
-			description: """
-				The gRPC address to listen for connections on. It _must_ include a port.
-				"""
-			required: true
-			type: string: {
-				examples: ["0.0.0.0:\(_port)"]
+		grpc: {
+			description: "Configuration options for the gRPC server."
+			required:    true
+			type: object: {
+				examples: []
+				options: {
+					address: {
+						description: """
+							The gRPC address to listen for connections on. It _must_ include a port.
+							"""
+						required: true
+						type: string: {
+							examples: ["0.0.0.0:\(_grpc_port)"]
+						}
+					}
+					tls: configuration._tls_accept & {_args: {
+						can_verify_certificate: true
+						enabled_default:        false
+					}}
+				}
+			}
+		}
+		http: {
+			description: "Configuration options for the HTTP server."
+			required:    true
+			type: object: {
+				examples: []
+				options: {
+					address: {
+						description: """
+							The HTTP address to listen for connections on. It _must_ include a port.
+							"""
+						required: true
+						type: string: {
+							examples: ["0.0.0.0:\(_http_port)"]
+						}
+					}
+					tls: configuration._tls_accept & {_args: {
+						can_verify_certificate: true
+						enabled_default:        false
+					}}
+				}
 			}
 		}
 	}
@@ -70,11 +106,23 @@ components: sources: opentelemetry: {
 				Received log events will go to this output stream. Use `<component_id>.logs` as an input to downstream transforms and sinks.
 				"""
 		},
+		{
+			name: "metrics"
+			description: """
+				Received metric events will go to this output stream. Use `<component_id>.metrics` as an input to downstream transforms and sinks.
+				"""
+		},
+		{
+			name: "traces"
+			description: """
+				Received trace events will go to this output stream. Use `<component_id>.traces` as an input to downstream transforms and sinks.
+				"""
+		},
 	]
 
 	output: {
 		logs: event: {
-			description: "An individual event from a batch of events received through a gRPC request sent by OpenTelemetry SDK"
+			description: "An individual event from a batch of events received through a gRPC or HTTP request sent by an OpenTelemetry SDK or collector."
 			fields: {
 				attributes: {
 					description: "Attributes that describe the specific event occurrence."
@@ -92,6 +140,14 @@ components: sources: opentelemetry: {
 						examples: [{"k1": "v1"}]
 					}
 				}
+				scope: {
+					description: "The instrumentation scope that emitted the event, with its `name`, `version`, and `attributes`."
+					required:    false
+					common:      true
+					type: object: {
+						examples: [{"name": "io.opentelemetry.contrib.mongodb", "version": "1.0.0"}]
+					}
+				}
 				message: {
 					description: "Contains the body of the log record."
 					required:    false
@@ -170,6 +226,152 @@ components: sources: opentelemetry: {
 				}
 			}
 		}
+		metrics: {
+			_extra_tags: {
+				"resources.*": {
+					description: "The attributes of the resource, prefixed with `resources.`."
+					required:    false
+					examples: ["service"]
+				}
+				"scope.*": {
+					description: "The name, version, and attributes of the instrumentation scope, prefixed with `scope.`."
+					required:    false
+					examples: ["io.opentelemetry.contrib.mongodb"]
+				}
+			}
+			counter: output._passthrough_counter & {
+				tags: _extra_tags
+			}
+			gauge: output._passthrough_gauge & {
+				tags: _extra_tags
+			}
+			histogram: output._passthrough_histogram & {
+				tags: _extra_tags
+			}
+			summary: output._passthrough_summary & {
+				tags: _extra_tags
+			}
+		}
+		traces: {
+			description: "A span received through a gRPC or HTTP request sent by an OpenTelemetry SDK or collector."
+			fields: {
+				trace_id: {
+					description: "The trace id of the span, hex encoded."
+					required:    true
+					type: string: {
+						examples: ["37e7518fe2e2fcaf22b41c2dac059221"]
+					}
+				}
+				span_id: {
+					description: "The id of the span, hex encoded."
+					required:    true
+					type: string: {
+						examples: ["05abe7510db73b88"]
+					}
+				}
+				parent_span_id: {
+					description: "The id of the parent span, hex encoded. Absent for root spans."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["05abe7510db73b88"]
+					}
+				}
+				name: {
+					description: "The name of the operation the span describes."
+					required:    true
+					type: string: {
+						examples: ["GET /users"]
+					}
+				}
+				kind: {
+					description: "The kind of the span, as the numerical value of the OpenTelemetry `SpanKind`."
+					required:    true
+					type: uint: {
+						unit: null
+						examples: [2]
+					}
+				}
+				resources: {
+					description: "Set of attributes that describe the resource."
+					required:    false
+					common:      true
+					type: object: {
+						examples: [{"k1": "v1"}]
+					}
+				}
+				scope: {
+					description: "The instrumentation scope that emitted the event, with its `name`, `version`, and `attributes`."
+					required:    false
+					common:      true
+					type: object: {
+						examples: [{"name": "io.opentelemetry.contrib.mongodb", "version": "1.0.0"}]
+					}
+				}
+				attributes: {
+					description: "Attributes that describe the span."
+					required:    false
+					common:      true
+					type: object: {
+						examples: [{"k1": "v1"}]
+					}
+				}
+				start_timestamp: {
+					description: "The UTC Datetime when the span started."
+					required:    false
+					common:      true
+					type: timestamp: {}
+				}
+				end_timestamp: {
+					description: "The UTC Datetime when the span ended."
+					required:    false
+					common:      true
+					type: timestamp: {}
+				}
+				events: {
+					description: "The events that occurred during the span, with their `name`, `timestamp`, and `attributes`."
+					required:    false
+					common:      false
+					type: array: items: type: object: options: {}
+				}
+				links: {
+					description: "The links to other spans, with their `trace_id`, `span_id`, `trace_state`, and `attributes`."
+					required:    false
+					common:      false
+					type: array: items: type: object: options: {}
+				}
+				status: {
+					description: "The status of the span, with its numerical `code` and optional `message`."
+					required:    false
+					common:      true
+					type: object: {
+						examples: [{"code": 2, "message": "connection refused"}]
+					}
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		protocols: {
+			title: "OTLP/gRPC and OTLP/HTTP"
+			body:  """
+				The gRPC server listens on the `grpc.address` and implements the logs, metrics, and trace services of
+				OTLP/gRPC. The HTTP server listens on the `http.address` and accepts binary protobuf encoded requests
+				(`Content-Type: application/x-protobuf`) on the `/v1/logs`, `/v1/metrics`, and `/v1/traces` paths.
+				Both servers accept gzip compressed requests.
+				"""
+		}
+		metrics: {
+			title: "Metrics"
+			body:  """
+				Each data point of the received metrics is converted into a metric event: gauges into gauges, monotonic sums
+				into counters and other sums into gauges, histograms and exponential histograms into aggregated histograms,
+				and summaries into aggregated summaries. Sums and histograms with delta temporality are incremental.
+				Data points flagged as having no recorded value are dropped.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
@@ -4,7 +4,7 @@ components: sources: redis: {
 	title: "Redis"
 
 	features: {
-		acknowledgements: false
+		acknowledgements: true
 		collect: {
 			checkpoint: enabled: false
 			tls: enabled:        false
@@ -58,6 +58,7 @@ components: sources: redis: {
 	}
 
 	configuration: {
+		acknowledgements: configuration._source_acknowledgements
 		url: {
 			description: "The Redis URL to connect to. The url _must_ take the form of `protocol://server:port/db` where the protocol can either be `redis` or `rediss` for connections secured via TLS."
 			groups: ["tcp"]
@@ -79,13 +80,14 @@ components: sources: redis: {
 		}
 		data_type: {
 			common:      false
-			description: "The Redis data type (`list` or `channel`) to use."
+			description: "The Redis data type (`list`, `channel` or `stream`) to use."
 			required:    false
 			type: string: {
 				default: "list"
 				enum: {
 					list:    "Use the Redis `list` data type."
 					channel: "Use the Redis `channel` data type."
+					stream:  "Use the Redis `stream` data type, reading it as a member of a consumer group."
 				}
 				syntax: "literal"
 			}
@@ -114,6 +116,91 @@ components: sources: redis: {
 				}
 			}
 		}
+		stream: {
+			common:      false
+			description: "Options for the Redis `stream` data type."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					group: {
+						common:      true
+						description: "The consumer group to read the entries of the stream as a member of. The group is created, along with the stream, if it doesn't exist."
+						required:    false
+						type: string: {
+							default: "vector"
+							syntax:  "literal"
+						}
+					}
+					consumer: {
+						common:      true
+						description: "The name of the consumer in the group. It must be unique to the Vector instance, and stable across its restarts, for the entries read before a restart to be read again."
+						required:    false
+						type: string: {
+							default: "vector"
+							syntax:  "literal"
+						}
+					}
+					start_id: {
+						common:      false
+						description: "The ID of the entry after which the consumer group reads the stream when it is created. `$` reads the entries added once the group is created, and `0` the whole stream."
+						required:    false
+						type: string: {
+							default: "$"
+							examples: ["0"]
+							syntax: "literal"
+						}
+					}
+					field: {
+						common:      false
+						description: "The field of the entries holding the message to decode."
+						required:    false
+						type: string: {
+							default: "message"
+							syntax:  "literal"
+						}
+					}
+					id_key: {
+						common:      false
+						description: "The log field name to use for the ID of the entry. If not set, the ID is not added to the log event."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["id"]
+							syntax: "literal"
+						}
+					}
+					count: {
+						common:      false
+						description: "The maximum number of entries read at once."
+						required:    false
+						type: uint: {
+							default: 100
+							unit:    null
+						}
+					}
+					block_ms: {
+						common:      false
+						description: "How long to wait for new entries."
+						required:    false
+						type: uint: {
+							default: 1000
+							unit:    "milliseconds"
+						}
+					}
+					min_idle_ms: {
+						common:      false
+						description: "How long an entry is pending before it is claimed by this consumer, so that the entries of the consumers that crashed, or whose events failed to be delivered, are read again."
+						required:    false
+						type: uint: {
+							default: 60000
+							unit:    "milliseconds"
+						}
+					}
+				}
+			}
+		}
 		redis_key: {
 			common:      false
 			description: "The log field name to use for the redis key. If set to an empty string or null, the key is not added to the log event."
@@ -154,6 +241,21 @@ components: sources: redis: {
 				API.
 				"""
 		}
+		consumer_groups: {
+			title: "Consumer groups"
+			body:  """
+				When `data_type` is `stream`, the entries of the stream are read with `XREADGROUP`
+				as a member of a consumer group, and acknowledged with `XACK` once their events are
+				delivered when acknowledgements are enabled, or once they are sent to the next
+				components otherwise. The entries whose events failed to be delivered are left
+				pending, unless they were rejected.
+
+				On startup, the entries left pending for the consumer are read first. The entries
+				pending for longer than `stream.min_idle_ms`, such as the ones of the consumers that
+				crashed, are claimed with `XAUTOCLAIM` and read again, which requires Redis 6.2 or
+				later.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
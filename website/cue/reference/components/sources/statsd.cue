@@ -102,6 +102,19 @@ components: sources: statsd: {
 				unit:    "concurrency"
 			}
 		}
+		proxy_protocol: {
+			common: false
+			description: """
+				Whether to expect each connection to start with a PROXY protocol header, as sent by
+				load balancers such as HAProxy or AWS Network Load Balancers. The address of the
+				original client from the header is then used as the peer address of the connection.
+				Both version 1 and 2 of the protocol are supported. Connections not starting with a
+				valid header are closed.
+				"""
+			relevant_when: "mode = `tcp`"
+			required:      false
+			type: bool: default: false
+		}
 
 	}
 
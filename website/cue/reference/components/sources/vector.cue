@@ -58,11 +58,73 @@ components: sources: vector: {
 			description: """
 				The HTTP address to listen for connections on. It _must_ include a port.
 				"""
-			required: true
+			relevant_when: "mode = `tcp`"
+			required:      true
 			type: string: {
 				examples: ["0.0.0.0:\(_port)"]
 			}
 		}
+		mode: {
+			common:      false
+			description: "The type of socket to listen on."
+			required:    false
+			warnings: ["With version `1`, acknowledgements are not exchanged over Unix sockets."]
+			type: string: {
+				default: "tcp"
+				enum: {
+					tcp:  "Listen on TCP, at `address`."
+					unix: "Listen on a Unix domain socket, in stream mode, at `path`. Only available on Unix platforms."
+				}
+			}
+		}
+		path: {
+			description:   "The unix socket path. *This should be an absolute path*."
+			relevant_when: "mode = `unix`"
+			required:      true
+			type: string: {
+				examples: ["/path/to/socket"]
+			}
+		}
+		socket_file_mode: {
+			common: false
+			description: """
+				Unix file mode bits to be applied to the unix socket file
+				as its designated file permissions.
+				Note that the file mode value can be specified in any numeric format
+				supported by your configuration language, but it is most intuitive to use an octal number.
+				"""
+			relevant_when: "mode = `unix`"
+			required:      false
+			type: uint: {
+				default: null
+				unit:    null
+				examples: [0o777, 0o600, 508]
+			}
+		}
+		compression: {
+			common: false
+			description: """
+				Whether to accept compressed event frames, as negotiated with each `vector` sink
+				connecting to this source. Only used by version `1`, version `2` always accepts gzip
+				and zstd compressed requests.
+				"""
+			relevant_when: "mode = `tcp`"
+			required:      false
+			type: bool: default: false
+		}
+		proxy_protocol: {
+			common: false
+			description: """
+				Whether to expect each connection to start with a PROXY protocol header, as sent by
+				load balancers such as HAProxy or AWS Network Load Balancers. The address of the
+				original client from the header is then used as the peer address of the connection.
+				Both version 1 and 2 of the protocol are supported. Connections not starting with a
+				valid header are closed.
+				"""
+			relevant_when: "mode = `tcp`"
+			required:      false
+			type: bool: default: false
+		}
 		shutdown_timeout_secs: {
 			common:      false
 			description: "The timeout before a connection is forcefully closed during shutdown."
@@ -76,7 +138,7 @@ components: sources: vector: {
 			description: "Source API version. Specifying this version ensures that Vector does not break backward compatibility."
 			common:      true
 			required:    false
-			warnings: ["Ensure you use the same version for both the source and sink."]
+			warnings: ["Ensure you use the same version for both the source and sink.", "With version `1`, acknowledgements are exchanged per event and must be enabled on both the sink and the source."]
 			type: string: {
 				enum: {
 					"1": "Vector source API version 1"
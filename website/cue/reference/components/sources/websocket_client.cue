@@ -0,0 +1,126 @@
+package metadata
+
+components: sources: websocket_client: {
+	title: "WebSocket Client"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["daemon", "aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: services.websocket
+
+				interface: socket: {
+					direction: "outgoing"
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		ping_interval_secs: {
+			common:      false
+			description: "The interval between the pings sent to the server to keep the connection alive. By default, no pings are sent."
+			required:    false
+			type: uint: {
+				default: null
+				examples: [30]
+				unit: "seconds"
+			}
+		}
+		subscribe_message: {
+			common:      true
+			description: "A text message sent to the server after each connection, such as the subscription request of the feed."
+			required:    false
+			type: string: {
+				default: null
+				examples: [#"{"op": "subscribe", "channel": "trades"}"#]
+			}
+		}
+		uri: {
+			description: "The WebSocket URI to connect to. TLS is used for `wss://` URIs."
+			required:    true
+			type: string: {
+				examples: ["wss://stream.example.com/feed"]
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: "An event decoded from a message of the server."
+		fields: {
+			message: {
+				description: "The message, or its frames when `framing` splits it."
+				required:    true
+				type: string: {
+					examples: [#"{"channel": "trades", "price": 42.1}"#]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["websocket_client"]
+				}
+			}
+			timestamp: {
+				description: "The time the message was received."
+				required:    true
+				type: timestamp: {}
+			}
+		}
+	}
+
+	how_it_works: {
+		reconnection: {
+			title: "Reconnection"
+			body: """
+				Both text and binary messages are decoded by `framing` and `decoding`. When the
+				connection is closed by the server or fails, the source connects again, with an
+				exponential backoff between failed attempts, and sends `subscribe_message` again.
+				Messages sent by the server while disconnected are lost.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		connection_established_total:         components.sources.internal_metrics.output.metrics.connection_established_total
+		connection_shutdown_total:            components.sources.internal_metrics.output.metrics.connection_shutdown_total
+	}
+}
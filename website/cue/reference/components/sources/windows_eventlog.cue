@@ -0,0 +1,339 @@
+package metadata
+
+components: sources: windows_eventlog: {
+	title: "Windows Event Log"
+
+	classes: {
+		commonly_used: true
+		delivery:      "at_least_once"
+		deployment_roles: ["daemon"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.windows_event_log
+
+				interface: ffi: {}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      false
+			"aarch64-unknown-linux-musl":     false
+			"armv7-unknown-linux-gnueabihf":  false
+			"armv7-unknown-linux-musleabihf": false
+			"x86_64-apple-darwin":            false
+			"x86_64-unknown-linux-gnu":       false
+			"x86_64-unknown-linux-musl":      false
+		}
+
+		requirements: [
+			"""
+				Reading the `Security` channel requires Vector to run as a user with the
+				"Manage auditing and security log" right, such as the `LocalSystem` account.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._source_acknowledgements
+		batch_size: {
+			common:      false
+			description: "The maximum number of events read from a channel at once."
+			required:    false
+			type: uint: {
+				default: 100
+				unit:    "events"
+			}
+		}
+		channels: {
+			description: "The channels to read the events of."
+			required:    true
+			type: array: items: type: string: {
+				examples: ["System", "Application", "Security", "Microsoft-Windows-Sysmon/Operational"]
+			}
+		}
+		data_dir: {
+			common:      false
+			description: "The directory used to persist the bookmarks of the channels. By default, the [global `data_dir` option](\(urls.vector_configuration)/global-options#data_dir) is used. Please make sure the user Vector is running as has write permissions to this directory."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["C:\\ProgramData\\vector"]
+			}
+		}
+		read_existing_events: {
+			common:      true
+			description: "Read the events already in the channels that don't have a bookmark yet, instead of only the events published after Vector starts."
+			required:    false
+			type: bool: default: false
+		}
+		render_format: {
+			common:      true
+			description: "The format the events are rendered to."
+			required:    false
+			type: string: {
+				default: "json"
+				enum: {
+					json: "The fields of the events are converted to the fields of the logs, and the message of the events is the message of the logs."
+					xml:  "The XML of the events is the message of the logs, and the message of the events is stored in the `rendered_message` field."
+				}
+			}
+		}
+		render_message: {
+			common:      false
+			description: "Render the message of the events with the message files of their publisher. The message isn't available for the events of the publishers that aren't registered on the host."
+			required:    false
+			type: bool: default: true
+		}
+		xpath_query: {
+			common:      true
+			description: "The [XPath query](\(urls.windows_event_log_queries)) selecting the events read from the channels. By default, all the events are read."
+			required:    false
+			type: string: {
+				default: "*"
+				examples: ["*[System[(Level=1 or Level=2)]]", "*[System[(EventID=4624 or EventID=4625)]]"]
+			}
+		}
+	}
+
+	output: logs: event: {
+		description: "An event read from a Windows Event Log channel."
+		fields: {
+			activity_id: {
+				description: "The activity identifier of the event."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["{f4a5e4c0-d5a9-0001-f6e4-a5f4a9d5d801}"]
+				}
+			}
+			channel: {
+				description: "The channel the event was read from."
+				required:    true
+				type: string: {
+					examples: ["Security"]
+				}
+			}
+			event_data: {
+				description: "The data of the event, by name. The unnamed data are numbered from `param1`. Only with the `json` render format."
+				required:    false
+				common:      true
+				type: object: {
+					examples: [{"SubjectUserSid": "S-1-5-18", "LogonType": "5"}]
+					options: {}
+				}
+			}
+			event_id: {
+				description: "The identifier of the event. Only with the `json` render format."
+				required:    false
+				common:      true
+				type: uint: {
+					default: null
+					examples: [4624]
+					unit: null
+				}
+			}
+			host: {
+				description: "The name of the computer the event was published on."
+				required:    true
+				type: string: {
+					examples: ["WIN-SERVER"]
+				}
+			}
+			keywords: {
+				description: "The keywords of the event, as an hexadecimal mask. Only with the `json` render format."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["0x8020000000000000"]
+				}
+			}
+			level: {
+				description: "The level of the event, from `1` for critical events to `5` for verbose ones. Only with the `json` render format."
+				required:    false
+				common:      true
+				type: uint: {
+					default: null
+					examples: [2]
+					unit: null
+				}
+			}
+			message: {
+				description: "With the `json` render format, the message of the event, when it could be rendered. With the `xml` render format, the XML of the event."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["An account was successfully logged on."]
+				}
+			}
+			opcode: {
+				description: "The opcode of the event. Only with the `json` render format."
+				required:    false
+				common:      false
+				type: uint: {
+					default: null
+					examples: [0]
+					unit: null
+				}
+			}
+			process_id: {
+				description: "The identifier of the process that published the event. Only with the `json` render format."
+				required:    false
+				common:      false
+				type: uint: {
+					default: null
+					examples: [636]
+					unit: null
+				}
+			}
+			provider_guid: {
+				description: "The GUID of the publisher of the event. Only with the `json` render format."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["{54849625-5478-4994-a5ba-3e3b0328c30d}"]
+				}
+			}
+			provider_name: {
+				description: "The name of the publisher of the event. Only with the `json` render format."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["Microsoft-Windows-Security-Auditing"]
+				}
+			}
+			record_id: {
+				description: "The number of the event in its channel. Only with the `json` render format."
+				required:    false
+				common:      false
+				type: uint: {
+					default: null
+					examples: [1234]
+					unit: null
+				}
+			}
+			rendered_message: {
+				description: "The message of the event, when it could be rendered. Only with the `xml` render format."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["An account was successfully logged on."]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["windows_eventlog"]
+				}
+			}
+			task: {
+				description: "The task of the event. Only with the `json` render format."
+				required:    false
+				common:      false
+				type: uint: {
+					default: null
+					examples: [12544]
+					unit: null
+				}
+			}
+			thread_id: {
+				description: "The identifier of the thread that published the event. Only with the `json` render format."
+				required:    false
+				common:      false
+				type: uint: {
+					default: null
+					examples: [700]
+					unit: null
+				}
+			}
+			timestamp: {
+				description: "The time the event was published."
+				required:    true
+				type: timestamp: {}
+			}
+			user_data: {
+				description: "The user data of the event, for the events that have one instead of event data. Only with the `json` render format."
+				required:    false
+				common:      false
+				type: object: {
+					examples: [{"SubjectUserName": "admin"}]
+					options: {}
+				}
+			}
+			user_id: {
+				description: "The security identifier of the user the event was published for. Only with the `json` render format."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["S-1-5-18"]
+				}
+			}
+			version: {
+				description: "The version of the event. Only with the `json` render format."
+				required:    false
+				common:      false
+				type: uint: {
+					default: null
+					examples: [2]
+					unit: null
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		bookmarks: {
+			title: "Bookmarks"
+			body: """
+				Vector stores a bookmark of the last event read from each channel in its data
+				directory, and reads the channels from their bookmark after a restart. When
+				acknowledgements are enabled, the bookmarks only advance once the events are
+				delivered, and a channel whose events are rejected stops being read until Vector
+				restarts. When the bookmarked event has been cleared from its channel, the channel
+				is read from its oldest event.
+				"""
+		}
+
+		filtering: {
+			title: "Filtering"
+			body: """
+				The `xpath_query` option selects the events read from the channels with the
+				[XPath 1.0 subset](\(urls.windows_event_log_queries)) supported by the Event Log
+				service, such as `*[System[(Level=1 or Level=2)]]` for the critical and error
+				events. The events are filtered by the Event Log service, before being read by
+				Vector.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
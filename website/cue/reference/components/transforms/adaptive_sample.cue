@@ -0,0 +1,115 @@
+package metadata
+
+components: transforms: adaptive_sample: {
+	title: "Adaptive Sample"
+
+	description: """
+		Samples events to forward a target number of events per second, adjusting the sampling rate of each key to
+		the traffic it receives.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		exclude: {
+			common: true
+			description: """
+				The set of logical conditions to exclude events from sampling.
+				"""
+			required: false
+			type: condition: {}
+		}
+		key_field: {
+			common: true
+			description: """
+				The name of the field whose values are sampled separately. The target is shared between the values, so
+				that rare values are forwarded in full, while frequent values are sampled at a higher rate. If left
+				unspecified, all events share a single sampling rate. Events without the field share the sampling rate
+				of an empty value.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["service", "host"]
+			}
+		}
+		sample_rate_field: {
+			common: false
+			description: """
+				The name of the field in which the sampling rate of the forwarded events is stored, expressed as `N`:
+				the event was forwarded out of every `N` events with the same key.
+				"""
+			required: false
+			type: string: {
+				default: "sample_rate"
+			}
+		}
+		target_events_per_sec: {
+			description: "The number of events per second that the transform aims to forward."
+			required:    true
+			type: float: {
+				examples: [100.0, 0.5]
+			}
+		}
+		window_secs: {
+			common: false
+			description: """
+				The duration of the window over which the events are counted before the sampling rates are adjusted.
+				"""
+			required: false
+			type: uint: {
+				default: 10
+				unit:    "seconds"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  true
+	}
+
+	telemetry: metrics: {
+		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
+	}
+
+	how_it_works: {
+		adaptive_rates: {
+			title: "Adaptive Sampling Rates"
+			body: """
+				The events of each key are counted over a window of `window_secs`. At the end of the window, the
+				`target_events_per_sec` is shared between the keys that received events: keys receiving fewer events
+				than an even share are forwarded in full, and the rest of their share is split between the other keys.
+				Each key then forwards one out of every `N` of its events during the next window, where `N` is the
+				smallest integer keeping the key within its share.
+
+				Keys are forwarded in full during the first window in which they are seen, and are forgotten after a
+				window without events.
+				"""
+		}
+		extrapolation: {
+			title: "Extrapolating Counts"
+			body: """
+				The forwarded events are annotated with their sampling rate in the `sample_rate_field`. Summing the
+				sampling rates of the forwarded events, instead of counting them, estimates the number of events
+				received before sampling.
+				"""
+		}
+	}
+}
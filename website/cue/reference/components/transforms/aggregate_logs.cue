@@ -0,0 +1,145 @@
+package metadata
+
+components: transforms: aggregate_logs: {
+	title: "Aggregate Logs"
+
+	description: """
+		Groups log events by a set of fields into tumbling or sliding time windows and
+		emits a single summarized event per group and window. This collapses noisy,
+		repeated logs before they reach expensive sinks.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		aggregate: {}
+	}
+
+	support: {
+		requirements: []
+		notices: []
+		warnings: []
+	}
+
+	configuration: {
+		group_by: {
+			common: true
+			description: """
+				An ordered list of fields by which to group events. Each group with matching values for the specified
+				fields is summarized independently. When no fields are specified, all the events of a window are
+				summarized in a single event.
+				"""
+			required: false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["service", "host", "level"]
+				}
+			}
+		}
+		max_samples: {
+			common:      false
+			description: "The maximum number of messages kept as samples of each window."
+			required:    false
+			type: uint: {
+				default: 5
+				unit:    null
+			}
+		}
+		numeric_fields: {
+			common: true
+			description: """
+				A list of numeric fields to summarize with their sum, minimum and maximum. The values that are missing
+				or not numeric are ignored.
+				"""
+			required: false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["duration", "bytes"]
+				}
+			}
+		}
+		slide_ms: {
+			common: false
+			description: """
+				The interval between the starts of two consecutive windows, in milliseconds. By default, windows are
+				tumbling and each event belongs to a single window. When lower than `window_ms`, windows are sliding and
+				overlap, so that each event is summarized in `window_ms / slide_ms` windows. `window_ms` must be a
+				multiple of `slide_ms`.
+				"""
+			required: false
+			type: uint: {
+				default: null
+				examples: [1000]
+				unit: "milliseconds"
+			}
+		}
+		window_ms: {
+			common:      true
+			description: "The length of the windows, in milliseconds."
+			required:    false
+			type: uint: {
+				default: 10000
+				unit:    "milliseconds"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	examples: [
+		{
+			title: "Summarize requests per service"
+			configuration: {
+				group_by: ["service"]
+				numeric_fields: ["duration"]
+				window_ms: 60000
+			}
+			input: [
+				{log: {message: "GET /users", service: "api", duration: 12}},
+				{log: {message: "GET /users", service: "api", duration: 30}},
+				{log: {message: "GET /orders", service: "api", duration: 18}},
+			]
+			output: [
+				{log: {
+					service: "api"
+					count:   3
+					duration: {sum: 60.0, min: 12.0, max: 30.0}
+					samples: ["GET /users", "GET /users", "GET /orders"]
+					window_start: "2021-07-12T07:58:00Z"
+					timestamp:    "2021-07-12T07:59:00Z"
+				}},
+			]
+		},
+	]
+
+	how_it_works: {
+		windows: {
+			title: "Windows"
+			body: """
+				Windows are based on the time at which the events are received by the transform. At the end of each
+				window, one event is emitted per group with events in the window. It contains the values of the
+				`group_by` fields, the number of events as `count`, the first messages as `samples`, the sum, minimum
+				and maximum of each of the `numeric_fields` at the path of the field, the start of the window as
+				`window_start`, and its end as the timestamp.
+
+				With sliding windows, a window ends every `slide_ms` and covers the last `window_ms`, so that a group
+				is emitted for as long as it has events in the window.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		aggregate_logs_flushes_total: components.sources.internal_metrics.output.metrics.aggregate_logs_flushes_total
+	}
+}
@@ -0,0 +1,143 @@
+package metadata
+
+components: transforms: cardinality_limit: {
+	title: "Cardinality Limit"
+
+	description: """
+		Limits the number of series, or distinct sets of tags, of each metric name, protecting against
+		accidental high cardinality usage that can commonly disrupt the stability of metrics storages.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		limit_exceeded_action: {
+			common: true
+			description: """
+				Controls what should happen when a metric event of a new series arrives after the metric name reached
+				the `series_limit`.
+				"""
+			required: false
+			type: string: {
+				default: "drop_series"
+				enum: {
+					drop_series: "Drop the metric event."
+					overflow:    "Replace the tags of the metric event with a single `overflow` tag set to `true`, so that all the new series of the metric name are aggregated into a single series."
+					strip_tags:  "Remove the tags whose value was never accepted for the metric name. The metric event is dropped if the remaining tags still make up a new series."
+				}
+			}
+		}
+		series_limit: {
+			common:      true
+			description: "How many distinct series, or sets of tags, to accept for any given metric name."
+			required:    false
+			type: uint: {
+				default: 1000
+				unit:    null
+			}
+		}
+	}
+
+	input: {
+		logs: false
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	examples: [
+		{
+			title: "Aggregate new series into an overflow series"
+			configuration: {
+				series_limit:          1
+				limit_exceeded_action: "overflow"
+			}
+			input: [
+				{metric: {
+					kind: "incremental"
+					name: "requests_total"
+					counter: {
+						value: 1.0
+					}
+					tags: {
+						path: "/"
+					}
+				}},
+				{metric: {
+					kind: "incremental"
+					name: "requests_total"
+					counter: {
+						value: 1.0
+					}
+					tags: {
+						path: "/users/1234"
+					}
+				}},
+			]
+			output: [
+				{metric: {
+					kind: "incremental"
+					name: "requests_total"
+					counter: {
+						value: 1.0
+					}
+					tags: {
+						path: "/"
+					}
+				}},
+				{metric: {
+					kind: "incremental"
+					name: "requests_total"
+					counter: {
+						value: 1.0
+					}
+					tags: {
+						overflow: "true"
+					}
+				}},
+			]
+		},
+	]
+
+	how_it_works: {
+		series_tracking: {
+			title: "Series Tracking"
+			body: """
+				The transform remembers the series of each metric name, identified by its namespace and name, in
+				the order in which they arrive. The first `series_limit` series are accepted, and pass through the
+				transform unchanged from then on. The metric events of any other series are limited according to the
+				`limit_exceeded_action`.
+
+				The accepted series are kept in memory until Vector restarts, and are not shared between instances
+				of Vector.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		events_discarded_total:      components.sources.internal_metrics.output.metrics.events_discarded_total
+		limited_series_events_total: components.sources.internal_metrics.output.metrics.limited_series_events_total
+		series_limit_reached_total:  components.sources.internal_metrics.output.metrics.series_limit_reached_total
+	}
+}
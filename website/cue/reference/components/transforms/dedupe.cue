@@ -40,9 +40,28 @@ components: transforms: dedupe: {
 							unit:    null
 						}
 					}
+					time_to_live_secs: {
+						common:      false
+						description: "The number of seconds after which a cached Event expires. An incoming Event matching an expired entry is not considered a duplicate and renews the entry. By default, entries only leave the cache when evicted by newer ones."
+						required:    false
+						type: uint: {
+							default: null
+							examples: [60, 3600]
+							unit: "seconds"
+						}
+					}
 				}
 			}
 		}
+		count_field: {
+			common:      false
+			description: "The name of the field added to the next Event passing through with the number of duplicate Events discarded since the previous one. The field is only added when duplicates were discarded."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["duplicates"]
+			}
+		}
 		fields: {
 			description: "Options controlling what fields to match against."
 			required:    true
@@ -93,6 +112,11 @@ components: transforms: dedupe: {
 				already in the cache that will put that event back to the head of
 				the cache and reset its place in line, making it once again last
 				entry in line to be evicted.
+
+				When `cache.time_to_live_secs` is set, entries also expire after
+				this number of seconds since they were inserted. An Event matching
+				an expired entry is passed through and renews the entry, so that
+				each distinct Event is passed through at most once per period.
 				"""
 		}
 
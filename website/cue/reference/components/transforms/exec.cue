@@ -0,0 +1,155 @@
+package metadata
+
+components: transforms: exec: {
+	title: "Exec"
+
+	description: """
+		Pipes events through an external command, which can modify, drop, or add events, and sends the events the
+		command writes back downstream.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		program: {
+			runtime: {
+				name:    "External command"
+				url:     urls.exec
+				version: null
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: [
+			"""
+				The command is trusted: it runs with the permissions of Vector, and its output is sent downstream as
+				is.
+				""",
+		]
+		notices: []
+	}
+
+	configuration: {
+		command: {
+			description: """
+				The command to be run, plus any arguments required.
+
+				The events are written to the standard input of the command, and the events it writes to its
+				standard output are sent downstream. Both are encoded as native JSON.
+				"""
+			required: true
+			type: array: items: type: string: {
+				examples: [["./transform.py"], ["jq", "--unbuffered", "-c", ".message |= ascii_upcase"]]
+			}
+		}
+		concurrency: {
+			common:      false
+			description: "The number of instances of the command processing events concurrently."
+			required:    false
+			type: uint: {
+				default: 1
+				unit:    null
+			}
+		}
+		framing: {
+			common:      false
+			description: "How the events are delimited on the standard input and output of the command."
+			required:    false
+			type: string: {
+				default: "newline_delimited"
+				enum: {
+					newline_delimited: "Each event is followed by a newline."
+					length_delimited:  "Each event is prefixed by its length, as a 4-byte big-endian integer."
+				}
+			}
+		}
+		max_batch_events: {
+			common:      false
+			description: "The maximum number of events written to the command at once."
+			required:    false
+			type: uint: {
+				default: 100
+				unit:    "events"
+			}
+		}
+		respawn_interval_secs: {
+			common:      false
+			description: "The amount of time that Vector will wait before restarting a command that exited or failed."
+			required:    false
+			type: uint: {
+				default: 5
+				unit:    "seconds"
+			}
+		}
+		timeout_secs: {
+			common: false
+			description: """
+				The time that the command has to accept a batch of events, or to exit once its standard input is
+				closed. If a batch isn't accepted in time, the command is killed and restarted, and the events of the
+				batch are discarded.
+				"""
+			required: false
+			type: uint: {
+				default: 30
+				unit:    "seconds"
+			}
+		}
+		working_directory: {
+			common:      false
+			description: "The directory in which to run the command."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["/var/lib/vector/scripts"]
+			}
+		}
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: true
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+	}
+
+	how_it_works: {
+		protocol: {
+			title: "Protocol"
+			body: """
+				Each event is encoded as a native JSON object, delimited according to `framing`, and written to the
+				standard input of the command. The command writes the events to send downstream to its standard
+				output, in the same format. It can write any number of events for each event it reads, so it can
+				drop, modify, or split events. The standard error of the command is inherited from Vector.
+
+				When Vector shuts down, the standard input of the command is closed, and the events the command
+				writes before exiting are still sent downstream.
+				"""
+		}
+		restarts: {
+			title: "Restarts"
+			body: """
+				A command that exits, or that doesn't accept a batch of events within `timeout_secs`, is restarted
+				after `respawn_interval_secs`. The events of a batch that couldn't be written to the command are
+				discarded, and tracked by the `component_discarded_events_total` metric.
+				"""
+		}
+	}
+}
@@ -25,100 +25,154 @@ components: transforms: log_to_metric: {
 	}
 
 	configuration: {
+		all_metrics: {
+			common:      false
+			description: """
+				Converts the log events back to the metrics they were generated from by the
+				[`metric_to_log` transform](/docs/reference/configuration/transforms/metric_to_log/), instead of
+				generating `metrics` from them. See [Round trip](#round-trip). `metrics` can't be set
+				along with this option.
+				"""
+			required:    false
+			type: bool: default: false
+		}
+		host_tag: {
+			common:        false
+			description:   "Name of the tag the host field of the log events is moved to, which is the `host_tag` of the `metric_to_log` transform. Defaults to the global `host_key` option."
+			relevant_when: "all_metrics = true"
+			required:      false
+			type: string: {
+				default: null
+				examples: ["host", "hostname"]
+			}
+		}
 		metrics: {
 			description: "A table of key/value pairs representing the keys to be added to the event."
-			required:    true
-			type: array: items: type: object: {
-				examples: []
-				options: {
-					field: {
-						description: "The log field to use as the metric."
-						required:    true
-						type: string: {
-							examples: ["duration", "parent.child"]
+			required:    false
+			common:      true
+			type: array: {
+				default: []
+				items: type: object: {
+					examples: []
+					options: {
+						field: {
+							description: "The log field to use as the metric."
+							required:    true
+							type: string: {
+								examples: ["duration", "parent.child"]
+							}
 						}
-					}
-					increment_by_value: {
-						description: """
-							If `true` the metric will be incremented by the `field` value.
-							If `false` the metric will be incremented by 1 regardless of the `field` value.
-							"""
-						required:      false
-						common:        false
-						relevant_when: #"type = "counter""#
-						type: bool: {
-							default: false
+						increment_by_value: {
+							description: """
+								If `true` the metric will be incremented by the `field` value.
+								If `false` the metric will be incremented by 1 regardless of the `field` value.
+								"""
+							required:      false
+							common:        false
+							relevant_when: #"type = "counter""#
+							type: bool: {
+								default: false
+							}
 						}
-					}
-					kind: {
-						description: """
-							The kind of the metric.
-							"""
-						required:      false
-						common:        false
-						relevant_when: #"type = "counter""#
-						type: string: {
-							enum: {
-								absolute:    "An absolute counter value."
-								incremental: "In incremental counter value."
+						kind: {
+							description: """
+								The kind of the metric.
+								"""
+							required:      false
+							common:        false
+							relevant_when: #"type = "counter""#
+							type: string: {
+								enum: {
+									absolute:    "An absolute counter value."
+									incremental: "In incremental counter value."
+								}
+								default: "incremental"
 							}
-							default: "incremental"
 						}
-					}
-					name: {
-						description: "The name of the metric. Defaults to `<field>_total` for `counter` and `<field>` for `gauge`."
-						required:    false
-						common:      true
-						type: string: {
-							default: null
-							examples: ["duration_total"]
-							syntax: "template"
+						name: {
+							description: "The name of the metric. Defaults to `<field>_total` for `counter` and `<field>` for `gauge`."
+							required:    false
+							common:      true
+							type: string: {
+								default: null
+								examples: ["duration_total"]
+								syntax: "template"
+							}
 						}
-					}
-					namespace: {
-						description: "The namespace of the metric."
-						required:    false
-						common:      true
-						type: string: {
-							default: null
-							examples: ["service"]
-							syntax: "template"
+						namespace: {
+							description: "The namespace of the metric."
+							required:    false
+							common:      true
+							type: string: {
+								default: null
+								examples: ["service"]
+								syntax: "template"
+							}
 						}
-					}
-					tags: {
-						description: "Key/value pairs representing [metric tags](\(urls.vector_metric)#tags)."
-						required:    false
-						common:      true
-						type: object: {
-							examples: [
-								{
-									host:   "${HOSTNAME}"
-									region: "us-east-1"
-									status: "{{status}}"
-								},
-							]
-							options: {
-								"*": {
-									description: """
-	                      Key/value pairs representing [metric tags](\(urls.vector_metric)#tags).
-	                      Environment variables and field interpolation is allowed.
-	                      """
-									required:    true
-									type: "*": {}
+						tags: {
+							description: "Key/value pairs representing [metric tags](\(urls.vector_metric)#tags)."
+							required:    false
+							common:      true
+							type: object: {
+								examples: [
+									{
+										host:   "${HOSTNAME}"
+										region: "us-east-1"
+										status: "{{status}}"
+									},
+								]
+								options: {
+									"*": {
+										description: """
+		                      Key/value pairs representing [metric tags](\(urls.vector_metric)#tags).
+		                      Environment variables and field interpolation is allowed.
+		                      """
+										required:    true
+										type: "*": {}
+									}
 								}
 							}
 						}
-					}
-					type: {
-						description: "The metric type."
-						required:    true
-						type: string: {
-							enum: {
-								counter:   "A [counter metric type](\(urls.vector_metric)#counter)."
-								gauge:     "A [gauge metric type](\(urls.vector_metric)#gauge)."
-								histogram: "A [distribution metric type](\(urls.vector_metric)#histogram) with histogram statistic."
-								set:       "A [set metric type](\(urls.vector_metric)#set)."
-								summary:   "A [distribution metric type](\(urls.vector_metric)#distribution) with summary statistic."
+						tags_expression: {
+							description: """
+								A [VRL](\(urls.vrl_reference)) expression returning an object of
+								[metric tags](\(urls.vector_metric)#tags), which are added to `tags`.
+								Its fields with a `null` value are ignored.
+								"""
+							required: false
+							common:   false
+							type: string: {
+								default: null
+								examples: [#"{"route": .route, "slow": to_float!(.duration_ms) > 1000}"#]
+								syntax: "remap_program"
+							}
+						}
+						type: {
+							description: "The metric type."
+							required:    true
+							type: string: {
+								enum: {
+									counter:   "A [counter metric type](\(urls.vector_metric)#counter)."
+									gauge:     "A [gauge metric type](\(urls.vector_metric)#gauge)."
+									histogram: "A [distribution metric type](\(urls.vector_metric)#histogram) with histogram statistic."
+									set:       "A [set metric type](\(urls.vector_metric)#set)."
+									summary:   "A [distribution metric type](\(urls.vector_metric)#distribution) with summary statistic."
+								}
+							}
+						}
+						value_expression: {
+							description: """
+								A [VRL](\(urls.vrl_reference)) expression computing the value of the metric,
+								instead of reading `field`, such as a duration parsed from a string. The
+								event doesn't need to have the `field`, which is still the default name of
+								the metric. A counter is incremented by the value of the expression.
+								"""
+							required: false
+							common:   false
+							type: string: {
+								default: null
+								examples: ["to_float!(.duration_ms) / 1000", #"parse_duration!(.latency, "s")"#]
+								syntax: "remap_program"
 							}
 						}
 					}
@@ -398,6 +452,20 @@ components: transforms: log_to_metric: {
 	]
 
 	how_it_works: {
+		round_trip: {
+			title: "Round trip"
+			body: """
+				The `metric_to_log` transform converts each metric to a log event whose fields are
+				the ones of the metric, such as `name`, `tags`, `kind`, and `counter.value` or
+				`aggregated_histogram.buckets`. With `all_metrics`, this transform converts such log
+				events back to the same metrics, including aggregated histograms and summaries, so
+				that metrics can go through log-only transports, such as Kafka with JSON encoding.
+				The infinite upper limits of histogram buckets are encoded as `"+Inf"` and `"-Inf"`.
+				The other usual spellings of infinities, such as `"inf"` or `"-Infinity"`, are also
+				accepted regardless of their case, whilst events with other strings as upper limits
+				are dropped.
+				"""
+		}
 		multiple_metrics: {
 			title: "Multiple Metrics"
 			body: """
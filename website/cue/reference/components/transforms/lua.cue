@@ -98,6 +98,22 @@ components: transforms: lua: {
 				}
 			}
 		}
+		require: {
+			common:      false
+			description: """
+				Where the [Lua `require` function](\(urls.lua_require)) can load modules from. Only available with
+				version `3`.
+				"""
+			required: false
+			type: string: {
+				default: "search_dirs"
+				enum: {
+					search_dirs: "Modules are only loaded from the `search_dirs`. C modules can't be loaded, and the `dofile` and `loadfile` functions are unavailable."
+					system:      "Modules are loaded from the `search_dirs`, then from the default paths of Lua. C modules can be loaded, as with version `2`."
+					disabled:    "Modules can't be loaded: the `require`, `dofile` and `loadfile` functions are unavailable."
+				}
+			}
+		}
 		search_dirs: {
 			common:      false
 			description: "A list of directories to search when loading a Lua file via the `require` function. If not specified, the modules are looked up in the directories of Vector's configs."
@@ -184,6 +200,7 @@ components: transforms: lua: {
 			type: string: enum: {
 				"1": "Lua transform API version 1"
 				"2": "Lua transform API version 2"
+				"3": "Lua transform API version 3, which also processes traces and restricts the `require` function"
 			}
 		}
 	}
@@ -198,7 +215,7 @@ components: transforms: lua: {
 			set:          true
 			summary:      true
 		}
-		traces: false
+		traces: true
 	}
 
 	examples: [
@@ -451,6 +468,23 @@ components: transforms: lua: {
 				absolute paths that will be searched when using the
 				[Lua `require` function](\(urls.lua_require)). If this option is not
 				set, the directories of the configuration files will be used instead.
+
+				With version `3`, modules are only loaded from these directories
+				unless `require` is set to `system`.
+				"""
+		}
+		version_3: {
+			title: "Version 3"
+			body:  """
+				Version `3` of the transform processes logs, metrics, and traces.
+				Each event is a table with a single `log`, `metric`, or `trace`
+				field, and the `emit` function of the hooks and timer handlers
+				accepts any of these tables, so that a transform can turn an
+				event of one type into events of another type. Traces are
+				represented like logs.
+
+				Its hooks and timers are configured as with version `2`, but the
+				modules that can be loaded are restricted by the `require` option.
 				"""
 		}
 	}
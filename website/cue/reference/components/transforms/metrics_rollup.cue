@@ -0,0 +1,147 @@
+package metadata
+
+components: transforms: metrics_rollup: {
+	title: "Metrics Rollup"
+
+	description: """
+		Rolls up metric events to a lower, fixed resolution, by aggregating the metric events of each series into
+		one metric event per interval. This cuts the volume of metric events, and the churn of series, sent to
+		expensive sinks.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		aggregate: {}
+	}
+
+	support: {
+		requirements: []
+		notices: []
+		warnings: []
+	}
+
+	configuration: {
+		allowed_lateness_secs: {
+			common: false
+			description: """
+				How long to wait after the end of an interval before its metrics are flushed. Metrics with a
+				timestamp in an interval that was already flushed are rolled up and flushed again, in a separate
+				metric event for the same interval.
+				"""
+			required: false
+			type: uint: {
+				default: 0
+				unit:    "seconds"
+			}
+		}
+		interval_secs: {
+			common: true
+			description: """
+				The resolution of the metrics output by the transform. The metrics of each series are rolled up into
+				one metric event per interval, whose timestamp is the start of the interval. Intervals are aligned on
+				the Unix epoch, so that a 60 seconds interval starts at the start of a minute.
+				"""
+			required: false
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
+	}
+
+	input: {
+		logs: false
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	examples: [
+		{
+			title: "Roll up counters to one minute"
+			configuration: {
+				interval_secs: 60
+			}
+			input: [
+				{
+					metric: {
+						kind:      "incremental"
+						name:      "requests_total"
+						timestamp: "2021-07-12T07:58:04Z"
+						counter: {
+							value: 1.0
+						}
+					}
+				},
+				{
+					metric: {
+						kind:      "incremental"
+						name:      "requests_total"
+						timestamp: "2021-07-12T07:58:44Z"
+						counter: {
+							value: 2.0
+						}
+					}
+				},
+			]
+			output: [
+				{
+					metric: {
+						kind:        "incremental"
+						name:        "requests_total"
+						timestamp:   "2021-07-12T07:58:00Z"
+						interval_ms: 60000
+						counter: {
+							value: 3.0
+						}
+					}
+				},
+			]
+		},
+	]
+
+	how_it_works: {
+		rollup_behavior: {
+			title: "Rollup Behavior"
+			body: """
+				Each metric event is assigned to the interval containing its timestamp, or the time at which it is
+				received if it has none. Within an interval, `incremental` metrics are added: counters are summed,
+				and the samples of distributions, the buckets of histograms, and the values of sets are merged. The
+				output metric has the start of the interval as its timestamp, and the interval as its `interval_ms`.
+				For `absolute` metrics, the value with the latest timestamp in the interval is kept.
+
+				An interval is flushed once it ended `allowed_lateness_secs` ago, according to the clock of the host,
+				and all intervals are flushed when Vector stops. Metrics with timestamps in the future are held
+				until their interval ends.
+				"""
+		}
+		aggregate_comparison: {
+			title: "Comparison With the Aggregate Transform"
+			body: """
+				The `aggregate` transform combines the metric events received during each flush period, whatever
+				their timestamps. The `metrics_rollup` transform groups metric events by the interval of their
+				timestamps instead, so that the output has a regular resolution that doesn't depend on when the
+				metric events are received.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		metrics_rollup_events_recorded_total: components.sources.internal_metrics.output.metrics.metrics_rollup_events_recorded_total
+		metrics_rollup_failed_updates_total:  components.sources.internal_metrics.output.metrics.metrics_rollup_failed_updates_total
+		metrics_rollup_flushes_total:         components.sources.internal_metrics.output.metrics.metrics_rollup_flushes_total
+		metrics_rollup_series_flushed_total:  components.sources.internal_metrics.output.metrics.metrics_rollup_series_flushed_total
+	}
+}
@@ -0,0 +1,138 @@
+package metadata
+
+components: transforms: redact_pii: {
+	title: "Redact PII"
+
+	description: """
+		Finds personally identifiable information (PII) in the string fields of log events, including
+		the nested ones, and masks, hashes, or removes it.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		sanitize: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		detectors: {
+			description: "The detectors applied to the string values of the events, in order."
+			required:    true
+			type: array: items: type: object: options: {
+				action: {
+					common:      true
+					description: "The action taken on the PII found by the detector."
+					required:    false
+					type: string: {
+						default: "mask"
+						enum: {
+							hash:   "Replaces the PII with the hex-encoded HMAC-SHA256 of the PII, keyed with `hash_key`. Hashing keeps equal values correlatable without revealing them."
+							mask:   "Replaces the PII with `mask`."
+							remove: "Removes the field holding the PII."
+						}
+					}
+				}
+				pattern: {
+					description:   "The regular expression matching the PII."
+					relevant_when: #"type = "regex""#
+					required:      true
+					type: string: {
+						examples: [#"user-\d+"#]
+					}
+				}
+				type: {
+					description: "The type of the detector."
+					required:    true
+					type: string: {
+						enum: {
+							credit_card: "Credit card numbers, with or without space or dash separators, passing the Luhn checksum."
+							email:       "Email addresses."
+							ip_address:  "IPv4 and IPv6 addresses."
+							regex:       "The matches of the regular expression in `pattern`."
+							uk_nino:     "UK national insurance numbers, such as `QQ 12 34 56 C`."
+							us_ssn:      "US social security numbers, in the `123-45-6789` form."
+						}
+					}
+				}
+			}
+		}
+		fields: {
+			common: true
+			description: """
+				The fields scanned for PII, including the fields nested in them. All the fields of the events
+				are scanned when empty.
+				"""
+			required: false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["message", "user"]
+				}
+			}
+		}
+		hash_key: {
+			common: false
+			description: """
+				The key of the HMAC-SHA256 replacing the PII found by the detectors with the `hash` action.
+				Required when a detector uses the `hash` action. Keeping the key secret prevents the hashes
+				from being reversed by hashing guessed values.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["${PII_HASH_KEY}"]
+			}
+		}
+		mask: {
+			common:      false
+			description: "The string replacing the PII found by the detectors with the `mask` action."
+			required:    false
+			type: string: {
+				default: "[REDACTED]"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	examples: [
+		{
+			title: "Mask emails and remove card numbers"
+
+			configuration: {
+				detectors: [
+					{type: "email"},
+					{type: "credit_card", action: "remove"},
+				]
+			}
+
+			input: log: {
+				message: "Password reset requested by jane.doe@example.com"
+				payment: card: "4111 1111 1111 1111"
+			}
+			output: log: {
+				message: "Password reset requested by [REDACTED]"
+				payment: {}
+			}
+		},
+	]
+
+	telemetry: metrics: {
+		pii_redactions_total: components.sources.internal_metrics.output.metrics.pii_redactions_total
+	}
+}
@@ -77,6 +77,28 @@ components: transforms: reduce: {
 				}
 			}
 		}
+		merge: {
+			common: false
+			description: """
+				A [Vector Remap Language](\(urls.vrl_reference)) (VRL) program which merges each event into the
+				combined event, instead of the `merge_strategies`.
+
+				The program is run for each event, with the combined event as the `.accumulator` field and the event
+				as the `.event` field. The `.accumulator` field, which is initially an empty object, must remain an
+				object and becomes the combined event. Events for which the program fails are discarded.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: [
+					"""
+						.accumulator.pages = push(array(.accumulator.pages) ?? [], .event.page)
+						.accumulator.duration = (int(.accumulator.duration) ?? 0) + int!(.event.duration)
+						""",
+				]
+				syntax: "remap_program"
+			}
+		}
 		merge_strategies: {
 			common: false
 			description: """
@@ -256,6 +278,8 @@ components: transforms: reduce: {
 	]
 
 	telemetry: metrics: {
-		stale_events_flushed_total: components.sources.internal_metrics.output.metrics.stale_events_flushed_total
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		stale_events_flushed_total:       components.sources.internal_metrics.output.metrics.stale_events_flushed_total
 	}
 }
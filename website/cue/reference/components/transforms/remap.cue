@@ -104,6 +104,22 @@ components: transforms: "remap": {
 				"""
 			type: bool: default: false
 		}
+		named_outputs: {
+			common:   false
+			required: false
+			description: """
+				The names of additional outputs that events can be sent to with the
+				[`route`](\(urls.vrl_functions)/#route) function. Events that aren't routed are
+				sent to the default output. The `dropped` output is reserved when
+				`reroute_dropped` is set.
+				"""
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["errors", "audit"]
+				}
+			}
+		}
 	}
 
 	input: {
@@ -210,6 +226,14 @@ components: transforms: "remap": {
 
 	outputs: [
 		components._default_output,
+		{
+			name: "<named_output>"
+			description: """
+				Each of the `named_outputs` can be referenced as an input by other components
+				with the name `<transform_name>.<named_output>`. Events are sent to the output
+				given to the `route` function, and to no other output.
+				"""
+		},
 		{
 			name: "dropped"
 			description: """
@@ -36,7 +36,7 @@ components: transforms: route: {
 				`_unmatched` is a reserved output name and cannot be used as a route name. `_default` is also reserved
 				for future use.
 				"""
-			required: true
+			required: false
 			type: object: {
 				options: {
 					"*": {
@@ -50,6 +50,52 @@ components: transforms: route: {
 				}
 			}
 		}
+		split: {
+			common: false
+			description: """
+				Splits the events between weighted outputs, for example to send a small share of the traffic to a
+				canary pipeline. Each event is sent to exactly one of the weighted outputs, in addition to the routes
+				whose condition it matches, and is never sent to the `_unmatched` output.
+				"""
+			required: false
+			type: object: options: {
+				key_field: {
+					common: false
+					description: """
+						The name of the field whose value is hashed to choose the output of the event, so that events
+						with the same value are consistently sent to the same output as long as the weights don't
+						change. For metrics, this is the name of a tag. If left unspecified, or if the event doesn't
+						have `key_field`, the output is chosen randomly.
+						"""
+					required: false
+					type: string: {
+						default: null
+						examples: ["user_id", "host"]
+					}
+				}
+				weights: {
+					description: """
+						A table of output names to their weights. Each event is sent to one of these outputs with a
+						probability proportional to its weight. Each output can be referenced as an input by other
+						components with the name `<transform_name>.<output_name>`.
+						"""
+					required: true
+					type: object: {
+						examples: [{primary: 95, canary: 5}]
+						options: {
+							"*": {
+								description: "The weight of the output, which must be greater than zero."
+								required:    true
+								type: uint: {
+									examples: [95, 5]
+									unit: null
+								}
+							}
+						}
+					}
+				}
+			}
+		}
 	}
 
 	input: {
@@ -119,5 +165,9 @@ components: transforms: route: {
 			name:        "<route_id>"
 			description: "Each route can be referenced as an input by other components with the name `<transform_name>.<route_id>`."
 		},
+		{
+			name:        "<output_name>"
+			description: "Each weighted output of `split` can be referenced as an input by other components with the name `<transform_name>.<output_name>`."
+		},
 	]
 }
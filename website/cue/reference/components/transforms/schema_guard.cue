@@ -0,0 +1,112 @@
+package metadata
+
+components: transforms: schema_guard: {
+	title: "Schema Guard"
+
+	description: """
+		Validates log events against a [JSON Schema](\(urls.json_schema)), forwarding the conforming
+		events and routing the violations, with their validation errors attached, to a separate output.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		route: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		errors_field: {
+			common:      false
+			description: "The name of the field of the invalid events holding their validation errors."
+			required:    false
+			type: string: {
+				default: "schema_errors"
+			}
+		}
+		max_errors: {
+			common:      false
+			description: "The maximum number of validation errors attached to an invalid event."
+			required:    false
+			type: uint: {
+				default: 10
+				unit:    null
+			}
+		}
+		schema: {
+			common: true
+			description: """
+				The JSON Schema the events must conform to, as a JSON document. Either `schema` or `schema_file`
+				must be set.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: [#"{"type": "object", "required": ["message", "level"]}"#]
+			}
+		}
+		schema_file: {
+			common: true
+			description: """
+				The path of a file holding the JSON Schema the events must conform to. Either `schema` or
+				`schema_file` must be set.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["/etc/vector/schemas/access_log.json"]
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	examples: [
+		{
+			title: "Missing required field"
+
+			configuration: {
+				schema: #"{"type": "object", "required": ["message", "level"]}"#
+			}
+
+			input: log: {
+				message: "Hello world"
+			}
+			output: log: {
+				message: "Hello world"
+				schema_errors: [#""level" is a required property"#]
+			}
+		},
+	]
+
+	outputs: [
+		components._default_output,
+		{
+			name: "invalid"
+			description: """
+				Events that fail validation are sent to the `invalid` output instead of the default one, with
+				their validation errors in the `errors_field` field, each prefixed with the path of the invalid
+				field. For a transform component named `foo`, this output can be accessed by specifying
+				`foo.invalid` as the input to another component.
+				"""
+		},
+	]
+
+	telemetry: metrics: {
+		component_errors_total: components.sources.internal_metrics.output.metrics.component_errors_total
+	}
+}
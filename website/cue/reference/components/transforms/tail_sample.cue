@@ -0,0 +1,168 @@
+package metadata
+
+components: transforms: tail_sample: {
+	title: "Tail Sample"
+
+	description: """
+		Buffers the spans of each trace until the trace completes or times out, then applies
+		policies, such as errors, latency, or probability, to keep or drop the whole trace.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		decision_wait_secs: {
+			common: true
+			description: """
+				The maximum time to wait for the spans of a trace after its first span. The policies are applied to
+				the trace once its root span is received, or after this time.
+				"""
+			required: false
+			type: float: {
+				default: 30.0
+				unit:    "seconds"
+			}
+		}
+		max_decisions: {
+			common:      false
+			description: "The number of decisions remembered for the spans received after the decision on their trace."
+			required:    false
+			type: uint: {
+				default: 100000
+				unit:    null
+			}
+		}
+		max_traces: {
+			common: false
+			description: """
+				The maximum number of traces buffered while waiting for their spans. When reached, the policies are
+				applied to the oldest trace early.
+				"""
+			required: false
+			type: uint: {
+				default: 10000
+				unit:    null
+			}
+		}
+		parent_id_field: {
+			common: false
+			description: """
+				The name of the field holding the ID of the parent span. Spans without parent ID, or with a null,
+				zero, or empty one, are the root spans of their trace.
+				"""
+			required: false
+			type: string: {
+				default: "parent_id"
+			}
+		}
+		policies: {
+			description: """
+				The policies deciding which traces are kept. A trace is kept when at least one of the policies
+				matches it, and dropped otherwise.
+				"""
+			required: true
+			type: array: items: type: object: options: {
+				condition: {
+					description:   "The condition matched against the spans."
+					relevant_when: #"type = "condition""#
+					required:      true
+					type: condition: {}
+				}
+				field: {
+					common: false
+					description: """
+						The name of the field of the spans holding the error, or their duration. Errors are `true`
+						booleans, non-zero integers, or non-empty strings.
+						"""
+					relevant_when: #"type = "error" or type = "latency""#
+					required:      false
+					type: string: {
+						default: null
+						examples: ["error", "duration"]
+					}
+				}
+				probability: {
+					description:   "The probability with which traces are kept, between `0.0` and `1.0`."
+					relevant_when: #"type = "probabilistic""#
+					required:      true
+					type: float: {
+						examples: [0.1]
+					}
+				}
+				threshold: {
+					description:   "The minimum duration of the spans, in the unit of `field`."
+					relevant_when: #"type = "latency""#
+					required:      true
+					type: float: {
+						examples: [500000000]
+					}
+				}
+				type: {
+					description: "The type of the policy."
+					required:    true
+					type: string: {
+						enum: {
+							condition:     "Keeps the traces with at least one span matching `condition`."
+							error:         "Keeps the traces with at least one span with an error in `field`, which defaults to `error`."
+							latency:       "Keeps the traces with at least one span lasting at least `threshold`, in `field`, which defaults to `duration`."
+							probabilistic: "Keeps a fraction of the traces, consistently across the instances of the transform."
+						}
+					}
+				}
+			}
+		}
+		trace_id_field: {
+			common: false
+			description: """
+				The name of the field holding the trace ID of the spans. Spans without trace ID are always
+				forwarded.
+				"""
+			required: false
+			type: string: {
+				default: "trace_id"
+			}
+		}
+	}
+
+	input: {
+		logs:    false
+		metrics: null
+		traces:  true
+	}
+
+	how_it_works: {
+		buffering: {
+			title: "Buffering"
+			body: """
+				The spans of each trace are buffered in memory until the root span of the trace is received, or
+				`decision_wait_secs` after its first span. The policies are then applied to all the buffered
+				spans, which are forwarded or dropped together. The spans received after the decision on their
+				trace follow it, as long as the decision is among the last `max_decisions` ones.
+
+				The buffer is bounded by `max_traces`. When it is full, the policies are applied to the oldest
+				trace early, which may split the trace if it has more spans to come. The spans that are buffered
+				when Vector stops are decided on before stopping.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		events_discarded_total:           components.sources.internal_metrics.output.metrics.events_discarded_total
+		tail_sample_traces_evicted_total: components.sources.internal_metrics.output.metrics.tail_sample_traces_evicted_total
+	}
+}
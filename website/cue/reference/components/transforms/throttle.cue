@@ -50,6 +50,40 @@ components: transforms: throttle: {
 				syntax: "template"
 			}
 		}
+		quotas: {
+			common: false
+			description: """
+				Per-key thresholds looked up from an [enrichment table](\(urls.enrichment_tables_concept)), such as
+				per-tenant quotas in a CSV file. Requires `key_field`. The `threshold` applies to the keys that aren't
+				in the table.
+				"""
+			required: false
+			type: object: options: {
+				key_column: {
+					common:      false
+					description: "The column of the table matched against the rendered `key_field` of the events."
+					required:    false
+					type: string: {
+						default: "key"
+					}
+				}
+				table: {
+					description: "The name of the enrichment table holding the thresholds."
+					required:    true
+					type: string: {
+						examples: ["tenant_quotas"]
+					}
+				}
+				threshold_column: {
+					common:      false
+					description: "The column of the table holding the number of events allowed per configured `window_secs` for the key."
+					required:    false
+					type: string: {
+						default: "threshold"
+					}
+				}
+			}
+		}
 		threshold: {
 			description: """
 				The number of events allowed for a given bucket per configured `window_secs`.
@@ -158,6 +192,15 @@ components: transforms: throttle: {
 						by the bucket's `key`.
 						"""
 				},
+				{
+					title: "Per-key Quotas"
+					body: """
+						With `quotas`, the threshold of each bucket is looked up from an enrichment table, by matching
+						the rendered `key_field` against the `quotas.key_column` column. Buckets without a row, or with a
+						threshold that isn't a positive integer, use `threshold`. When the enrichment table is reloaded,
+						the rate limiters of the buckets whose threshold changed are replaced.
+						"""
+				},
 			]
 		}
 	}
@@ -0,0 +1,132 @@
+package metadata
+
+components: transforms: trace_sampling: {
+	title: "Trace Sampling"
+
+	description: """
+		Samples traces with a consistent probability, keyed on their trace ID, so that all the events
+		of a kept trace are forwarded and trace volume is cut without breaking trace completeness.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		probability: {
+			description: """
+				The probability with which the events of a trace are forwarded, between `0.0` and `1.0`. For example,
+				`probability = 0.1` means that the events of 1 out of every 10 traces are forwarded and the rest are
+				dropped.
+				"""
+			required: true
+			type: float: {
+				examples: [0.1]
+			}
+		}
+		rules: {
+			common: false
+			description: """
+				An ordered list of rules overriding the probability of the events they match. The probability of the
+				first matching rule is used, and takes precedence over `services`.
+				"""
+			required: false
+			type: array: {
+				default: []
+				items: type: object: options: {
+					condition: {
+						description: "A logical condition matching the events to which the rule applies."
+						required:    true
+						type: condition: {}
+					}
+					probability: {
+						description: "The probability with which the events of the matching traces are forwarded, between `0.0` and `1.0`."
+						required:    true
+						type: float: {
+							examples: [1.0]
+						}
+					}
+				}
+			}
+		}
+		service_field: {
+			common:      false
+			description: "The name of the field holding the service of the events, used to look up `services`."
+			required:    false
+			type: string: {
+				default: "service"
+			}
+		}
+		services: {
+			common:      true
+			description: "A map of service names to the probabilities overriding `probability` for the events of the service."
+			required:    false
+			type: object: {
+				examples: [
+					{
+						checkout: 1.0
+						search:   0.01
+					},
+				]
+				options: {
+					"*": {
+						description: "The probability of the service."
+						required:    true
+						type: float: {}
+					}
+				}
+			}
+		}
+		trace_id_field: {
+			common: false
+			description: """
+				The name of the field holding the trace ID of the events. Events without trace ID are always
+				forwarded.
+				"""
+			required: false
+			type: string: {
+				default: "trace_id"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  true
+	}
+
+	how_it_works: {
+		consistent_sampling: {
+			title: "Consistent sampling"
+			body: """
+				The trace ID of each event is hashed, and the event is forwarded if the hash is in the
+				fraction of the hash space given by its probability. All the events of a trace, including logs
+				with the same trace ID, get the same decision, without the transform having to buffer them.
+
+				The probability of an event is the one of the first matching rule, or the one of its service,
+				or `probability`. Since the traces kept with a probability are also kept with any higher
+				probability, a trace kept for a service with a low probability is complete in the services
+				with a higher one. Rules should match attributes shared by all the events of a trace to keep
+				traces complete.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
+	}
+}
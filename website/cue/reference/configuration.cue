@@ -45,6 +45,8 @@ configuration: {
 
 				* [CSV](\(urls.csv)) files
 				* [MaxMind](\(urls.maxmind)) databases
+				* HTTP endpoints returning [JSON](\(urls.json)) records
+				* [Redis](\(urls.redis)) hashes and strings
 
 				For the lookup in the enrichment tables to be as performant as possible, the data is indexed according
 				to the fields that are used in the search. Note that indices can only be created for fields for which an
@@ -52,6 +54,11 @@ configuration: {
 				drops back to a sequential scan of the data. A sequential scan shouldn't impact performance
 				significantly provided that there are only a few possible rows returned by the exact matches in the
 				condition. We don't recommend using a condition that uses only date range searches.
+
+				The files of the CSV and MaxMind tables are only watched for changes when running Vector with
+				`--watch-config`, in which case a change to them triggers a reload of the configuration, which
+				reloads the tables whose files changed. Otherwise, the tables are only reloaded along with the
+				configuration, such as on `SIGHUP`, or at their `reload_interval_secs`.
 				"""
 			required:    false
 			type: object: options: {
@@ -136,6 +143,24 @@ configuration: {
 					}
 				}
 			}
+			type: object: options: {
+				reload_interval_secs: {
+					common:      false
+					description: """
+						The interval at which the files of the enrichment table are checked for changes. When the
+						files have changed, the table is reloaded and swapped in without reloading the configuration,
+						whilst lookups keep using the previously loaded data until the swap. If the reload fails, the
+						previously loaded data is kept.
+
+						By default, the table is only reloaded along with the configuration.
+						"""
+					required: false
+					type: uint: {
+						examples: [30]
+						unit: "seconds"
+					}
+				}
+			}
 			type: object: options: {
 				geoip: {
 					required:    true
@@ -159,6 +184,10 @@ configuration: {
 						The database file should be in the [MaxMind DB file format](\(urls.maxmind_db_file_format)).
 
 						This enrichment table only supports lookup with IP address.
+
+						The database is reloaded along with the configuration when the database file has
+						changed. When watching the configuration with `--watch-config`, changes to the
+						database file, such as updates by `geoipupdate`, also trigger the reload.
 						"""
 					type: object: options: {
 						path: {
@@ -186,6 +215,180 @@ configuration: {
 						}
 					}
 				}
+				http: {
+					required:    true
+					description: """
+						Configuration options for an HTTP endpoint that provides the records of the enrichment
+						table, such as the API of an asset inventory or of a user directory.
+
+						The endpoint is requested with the values of the fields searched for, which must all be
+						placeholders of the URL, and must respond with a JSON object or an array of JSON objects,
+						which are the records found. Records not found are signaled by a `404 Not Found` or `null`
+						response.
+
+						The responses are cached for the lookups of the same records. Failed requests are not
+						cached. The lookups block the transform until the response is received, so the timeout
+						and the cache should be set so that few lookups wait on the endpoint.
+						"""
+					type: object: options: {
+						url: {
+							description: """
+								The URL of the records, with the fields searched for as `{{ field }}` placeholders,
+								which are replaced with the URL-encoded values of the fields.
+								"""
+							required: true
+							type: string: {
+								examples: ["http://inventory.local/hosts/{{ host }}"]
+							}
+						}
+						headers: {
+							common:      false
+							description: "The headers of the requests."
+							required:    false
+							type: object: {
+								examples: [{"Accept": "application/json"}]
+								options: {}
+							}
+						}
+						timeout_secs: {
+							common:      false
+							description: "The timeout of the requests."
+							required:    false
+							type: uint: {
+								default: 5
+								unit:    "seconds"
+							}
+						}
+						cache: {
+							common:      false
+							description: "Configuration options for the cache of the responses."
+							required:    false
+							type: object: options: {
+								max_entries: {
+									common:      false
+									description: "The maximum number of responses cached."
+									required:    false
+									type: uint: {
+										default: 10000
+										unit:    null
+									}
+								}
+								ttl_secs: {
+									common:      false
+									description: "The time the records found are cached for."
+									required:    false
+									type: uint: {
+										default: 300
+										unit:    "seconds"
+									}
+								}
+								negative_ttl_secs: {
+									common:      false
+									description: "The time the absence of records is cached for."
+									required:    false
+									type: uint: {
+										default: 60
+										unit:    "seconds"
+									}
+								}
+							}
+						}
+					}
+				}
+				redis: {
+					required:    true
+					description: """
+						Configuration options for a [Redis](\(urls.redis)) server that holds the records of the
+						enrichment table, for enrichment data that changes too often to be reloaded from files.
+
+						The records are read from the key built with the values of the fields searched for, which
+						must all be placeholders of the key. Records not found are signaled by a missing key.
+
+						The records are cached for the lookups of the same keys. Failed lookups are not cached. The
+						lookups block the transform until the server responds, so the timeout and the cache should
+						be set so that few lookups wait on the server.
+						"""
+					type: object: options: {
+						url: {
+							description: "The URL of the Redis server."
+							required:    true
+							type: string: {
+								examples: ["redis://127.0.0.1:6379/0"]
+							}
+						}
+						key: {
+							description: "The key of the records, with the fields searched for as `{{ field }}` placeholders."
+							required:    true
+							type: string: {
+								examples: ["users:{{ user_id }}"]
+							}
+						}
+						data_type: {
+							common:      false
+							description: "The type of the values of the records."
+							required:    false
+							type: string: {
+								default: "hash"
+								enum: {
+									hash:   "Hashes, whose fields are the fields of the record, read with `HGETALL`."
+									string: "Strings, read with `GET`. JSON objects are the fields of the record, and other strings are its `value` field."
+								}
+							}
+						}
+						connections: {
+							common:      false
+							description: "The number of connections to the server, which the lookups are spread across."
+							required:    false
+							type: uint: {
+								default: 4
+								unit:    null
+							}
+						}
+						timeout_secs: {
+							common:      false
+							description: "The timeout of the lookups."
+							required:    false
+							type: uint: {
+								default: 5
+								unit:    "seconds"
+							}
+						}
+						cache: {
+							common:      false
+							description: "Configuration options for the cache of the records."
+							required:    false
+							type: object: options: {
+								max_entries: {
+									common:      false
+									description: "The maximum number of lookups cached."
+									required:    false
+									type: uint: {
+										default: 10000
+										unit:    null
+									}
+								}
+								ttl_secs: {
+									common:      false
+									description: "The time the records found are cached for."
+									required:    false
+									type: uint: {
+										default: 300
+										unit:    "seconds"
+									}
+								}
+								negative_ttl_secs: {
+									common:      false
+									description: "The time the absence of records is cached for."
+									required:    false
+									type: uint: {
+										default: 60
+										unit:    "seconds"
+									}
+								}
+							}
+						}
+					}
+				}
 			}
 		}
 
@@ -0,0 +1,42 @@
+package metadata
+
+remap: functions: get_state: {
+	category:    "Event"
+	description: """
+		Returns the value stored under the `key` by the `set_state` function, or `null` if there is
+		none or it has expired.
+		"""
+	notices: [
+		"""
+			The state is shared by all the events going through the transform. It is persisted in the
+			`data_dir`, when one is configured, so that it survives restarts. The state file is written in
+			the background at most once every second while the state changes, so the changes made in the
+			last second before Vector crashes are lost.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "key"
+			description: "The key of the value."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["any"]
+
+	examples: [
+		{
+			title: "Detect gaps in a sequence"
+			input: log: sequence: 42
+			source: #"""
+				last_sequence = get_state("last_sequence")
+				gap = last_sequence != null && .sequence != int!(last_sequence) + 1
+				set_state("last_sequence", .sequence)
+				gap
+				"""#
+			return: false
+		},
+	]
+}
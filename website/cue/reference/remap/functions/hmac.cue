@@ -0,0 +1,49 @@
+package metadata
+
+remap: functions: hmac: {
+	category:    "Cryptography"
+	description: """
+		Calculates the [HMAC](\(urls.hmac)) of the `value` with the `key`, as a hexadecimal string, to
+		sign the `value`.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to calculate the HMAC for."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "key"
+			description: "The secret key."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "algorithm"
+			description: "The hash algorithm to use."
+			enum: {
+				"SHA-224": "SHA-224 algorithm"
+				"SHA-256": "SHA-256 algorithm"
+				"SHA-384": "SHA-384 algorithm"
+				"SHA-512": "SHA-512 algorithm"
+			}
+			required: false
+			default:  "SHA-256"
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Calculate HMAC"
+			source: #"""
+				hmac("foo", "secret")
+				"""#
+			return: "773ba44693c7553d6ee20f61ea5d2757a9a4f4a44d2841ae4e95b52e4cd62db4"
+		},
+	]
+}
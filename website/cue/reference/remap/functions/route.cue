@@ -0,0 +1,39 @@
+package metadata
+
+remap: functions: route: {
+	category:    "Event"
+	description: """
+		Sends the event to the named `output` of the `remap` transform, instead of its default output.
+		"""
+	notices: [
+		"""
+			The `output` must be one of the `named_outputs` of the transform. Events routed more than
+			once are sent to the last `output` they were routed to.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "output"
+			description: "The name of the output."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`output` isn't one of the `named_outputs` of the transform",
+	]
+	return: types: ["null"]
+
+	examples: [
+		{
+			title: "Route errors to their own output"
+			source: #"""
+				if .level == "error" {
+					route("errors")
+				}
+				"""#
+			return: null
+		},
+	]
+}
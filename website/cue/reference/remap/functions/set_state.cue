@@ -0,0 +1,56 @@
+package metadata
+
+remap: functions: set_state: {
+	category:    "Event"
+	description: """
+		Stores the `value` under the `key`, to be returned by the `get_state` function when processing
+		later events.
+		"""
+	notices: [
+		"""
+			The state is shared by all the events going through the transform. It is persisted in the
+			`data_dir`, when one is configured, so that it survives restarts. The state file is written in
+			the background at most once every second while the state changes, so the changes made in the
+			last second before Vector crashes are lost.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "key"
+			description: "The key of the value."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "value"
+			description: "The value to store."
+			required:    true
+			type: ["any"]
+		},
+		{
+			name:        "ttl"
+			description: "The number of seconds after which the value expires. Values don't expire by default."
+			required:    false
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`ttl` isn't a positive number of seconds",
+	]
+	return: types: ["null"]
+
+	examples: [
+		{
+			title: "Count the events of each user"
+			input: log: user_id: "jane"
+			source: #"""
+				key = "count:" + string!(.user_id)
+				count = (int(get_state(key)) ?? 0) + 1
+				set_state(key, count, ttl: 86400)
+				count
+				"""#
+			return: 1
+		},
+	]
+}
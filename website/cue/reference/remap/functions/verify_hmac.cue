@@ -0,0 +1,67 @@
+package metadata
+
+remap: functions: verify_hmac: {
+	category:    "Cryptography"
+	description: """
+		Checks that the `signature` is the [HMAC](\(urls.hmac)) of the `value` with the `key`, as
+		calculated by the [`hmac`](#hmac) function.
+		"""
+	notices: [
+		"""
+			The signatures are compared in constant time, so that the comparison doesn't reveal how much of
+			an invalid signature is valid.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The signed string."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "key"
+			description: "The secret key."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "signature"
+			description: "The signature, as a hexadecimal string."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "algorithm"
+			description: "The hash algorithm to use."
+			enum: {
+				"SHA-224": "SHA-224 algorithm"
+				"SHA-256": "SHA-256 algorithm"
+				"SHA-384": "SHA-384 algorithm"
+				"SHA-512": "SHA-512 algorithm"
+			}
+			required: false
+			default:  "SHA-256"
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["boolean"]
+		rules: [
+			#"Returns `true` if `signature` is the HMAC of `value`."#,
+			#"Returns `false` otherwise, including if `signature` isn't a hexadecimal string."#,
+		]
+	}
+
+	examples: [
+		{
+			title: "Verify a signature"
+			source: #"""
+				verify_hmac("foo", "secret", "773ba44693c7553d6ee20f61ea5d2757a9a4f4a44d2841ae4e95b52e4cd62db4")
+				"""#
+			return: true
+		},
+	]
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: amqp: {
+	name:     "AMQP"
+	thing:    "an \(name) 0.9.1 broker"
+	url:      urls.amqp
+	versions: "0.9.1"
+
+	description: "[AMQP](\(urls.amqp)) is an open standard messaging protocol. Its 0.9.1 version is implemented by brokers such as [RabbitMQ](\(urls.rabbitmq))."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: aws_eventbridge: {
+	name:     "AWS EventBridge"
+	thing:    "an \(name) event bus"
+	url:      urls.aws_eventbridge
+	versions: null
+
+	description: "[Amazon EventBridge](\(urls.aws_eventbridge)) is a serverless event bus that routes events between AWS services, SaaS applications and your own applications, using rules matching the content of the events."
+}
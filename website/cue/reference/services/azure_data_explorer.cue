@@ -0,0 +1,10 @@
+package metadata
+
+services: azure_data_explorer: {
+	name:     "Azure Data Explorer"
+	thing:    "an \(name) cluster"
+	url:      urls.azure_data_explorer
+	versions: null
+
+	description: "[Azure Data Explorer](\(urls.azure_data_explorer)), also known as Kusto, is Microsoft's fully managed data analytics service for real-time analysis of large volumes of log and telemetry data."
+}
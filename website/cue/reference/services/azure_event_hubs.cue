@@ -0,0 +1,10 @@
+package metadata
+
+services: azure_event_hubs: {
+	name:     "Azure Event Hubs"
+	thing:    "an \(name) namespace"
+	url:      urls.azure_event_hubs
+	versions: null
+
+	description: "[Azure Event Hubs](\(urls.azure_event_hubs)) is Microsoft's fully managed event streaming platform. Event Hubs namespaces expose a [Kafka-compatible endpoint](\(urls.azure_event_hubs_kafka)) for the Standard tier and above."
+}
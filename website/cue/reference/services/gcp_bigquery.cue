@@ -0,0 +1,9 @@
+package metadata
+
+services: gcp_bigquery: {
+	name:        "GCP BigQuery"
+	thing:       "a \(name) table"
+	url:         urls.gcp_bigquery
+	versions:    null
+	description: "[Google BigQuery](\(urls.gcp_bigquery)) is a serverless, highly scalable data warehouse designed to run analytics over large datasets with SQL."
+}
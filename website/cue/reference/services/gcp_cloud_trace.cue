@@ -0,0 +1,9 @@
+package metadata
+
+services: gcp_cloud_trace: {
+	name:        "GCP Cloud Trace"
+	thing:       "a \(name) project"
+	url:         urls.gcp_cloud_trace
+	versions:    null
+	description: "[Google Cloud Trace](\(urls.gcp_cloud_trace)) is a distributed tracing system that collects latency data from applications and displays it in the Google Cloud console."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: mqtt: {
+	name:     "MQTT"
+	thing:    "an \(name) broker"
+	url:      urls.mqtt
+	versions: "3.1.1"
+
+	description: "[MQTT](\(urls.mqtt)) is a lightweight publish/subscribe messaging protocol for machine-to-machine and IoT communication, implemented by brokers such as Mosquitto, EMQX, and HiveMQ."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: postgresql: {
+	name:     "PostgreSQL"
+	thing:    "a \(name) database"
+	url:      urls.postgresql
+	versions: null
+
+	description: "[PostgreSQL](\(urls.postgresql)) is a powerful, open source object-relational database system, which [TimescaleDB](\(urls.timescaledb)) extends with hypertables for time series data."
+}
@@ -0,0 +1,8 @@
+package metadata
+
+services: tempo: {
+	name:     "Grafana Tempo"
+	thing:    "a \(name) database"
+	url:      urls.tempo
+	versions: null
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: windows_event_log: {
+	name:     "Windows Event Log"
+	thing:    "the \(name) channels"
+	url:      urls.windows_event_log
+	versions: null
+
+	description: "[Windows Event Log](\(urls.windows_event_log)) is the logging service of Windows, where the system, its services and the applications publish their events to channels such as `System`, `Application` and `Security`."
+}
@@ -1,6 +1,7 @@
 package metadata
 
 urls: {
+	amqp:                                       "https://www.amqp.org/"
 	azure_blob_storage:                         "https://azure.microsoft.com/en-us/services/storage/blobs/"
 	affine_type_system:                         "\(wikipedia)/wiki/Substructural_type_system#Affine_type_systems"
 	adaptive_request_concurrency_post:          "/blog/adaptive-request-concurrency/"
@@ -14,6 +15,7 @@ urls: {
 	apache_install:                             "\(apache)/docs/current/install.html"
 	apache_mod_status:                          "http://httpd.apache.org/docs/current/mod/mod_status.html"
 	apt:                                        "\(wikipedia)/wiki/APT_(software)"
+	arcsight_cef:                               "https://www.microfocus.com/documentation/arcsight/arcsight-smartconnectors/pdfdoc/common-event-format-v25/common-event-format-v25.pdf"
 	arm:                                        "\(wikipedia)/wiki/ARM_architecture"
 	aws_access_keys:                            "\(aws_docs)/IAM/latest/UserGuide/id_credentials_access-keys.html"
 	aws_arm_g2_announcement:                    "https://aws.amazon.com/about-aws/whats-new/2019/12/announcing-new-amazon-ec2-m6g-c6g-and-r6g-instances-powered-by-next-generation-arm-based-aws-graviton2-processors/"
@@ -47,6 +49,8 @@ urls: {
 	aws_elb:                                    "https://aws.amazon.com/elasticloadbalancing/"
 	aws_elb_access_format:                      "\(aws_docs)/elasticloadbalancing/latest/application/load-balancer-access-logs.html#access-log-entry-examples"
 	aws_elb_https:                              "\(aws_docs)/elasticloadbalancing/latest/classic/elb-create-https-ssl-load-balancer.html"
+	aws_eventbridge:                            "https://aws.amazon.com/eventbridge/"
+	aws_eventbridge_put_events:                 "\(aws_docs)/eventbridge/latest/APIReference/API_PutEvents.html"
 	aws_iam:                                    "\(aws_docs)/IAM/latest/UserGuide/introduction.html"
 	aws_iam_role:                               "\(aws_docs)/IAM/latest/UserGuide/id_roles.html"
 	aws_imds_v1_security_problems:              "https://aws.amazon.com/blogs/security/defense-in-depth-open-firewalls-reverse-proxies-ssrf-vulnerabilities-ec2-instance-metadata-service/"
@@ -59,6 +63,8 @@ urls: {
 	aws_kinesis_partition_key:                  "\(aws_docs)/kinesis/latest/APIReference/API_PutRecordsRequestEntry.html#Streams-Type-PutRecordsRequestEntry-PartitionKey"
 	aws_kinesis_streams:                        "https://aws.amazon.com/kinesis/data-streams/"
 	aws_kinesis_streams_api:                    "\(aws_docs)/kinesis/latest/APIReference/API_PutRecords.html"
+	aws_kinesis_streams_enhanced_fan_out:       "\(aws_docs)/streams/latest/dev/enhanced-consumers.html"
+	aws_kinesis_streams_kcl:                    "\(aws_docs)/streams/latest/dev/shared-throughput-kcl-consumers.html"
 	aws_kinesis_streams_service_limits:         "\(aws_docs)/streams/latest/dev/service-sizes-and-limits.html"
 	aws_kinesis_split_shards:                   "\(aws_docs)/streams/latest/dev/kinesis-using-sdk-java-resharding-split.html"
 	aws_regions:                                "\(aws_docs)/AmazonRDS/latest/UserGuide/Concepts.RegionsAndAvailabilityZones.html"
@@ -76,6 +82,7 @@ urls: {
 	aws_s3_sse:                                 "\(aws_docs)/AmazonS3/latest/dev/UsingServerSideEncryption.html"
 	aws_s3_storage_classes:                     "https://aws.amazon.com/s3/storage-classes/"
 	aws_s3_tags:                                "\(aws_docs)/AmazonS3/latest/user-guide/add-object-tags.html"
+	aws_security_lake:                          "https://aws.amazon.com/security-lake/"
 	aws_sqs:                                    "https://aws.amazon.com/sqs/"
 	aws_sqs_api:                                "\(aws_docs)/AWSSimpleQueueService/latest/APIReference/Welcome.html"
 	aws_sqs_create:                             "\(aws_docs)/AWSSimpleQueueService/latest/SQSDeveloperGuide/sqs-configure-create-queue.html"
@@ -85,8 +92,14 @@ urls: {
 	axiom_cloud:                                "https://cloud.axiom.co"
 	azure_blob:                                 "https://azure.microsoft.com/en-us/services/storage/blobs/"
 	azure_blob_endpoints:                       "https://docs.microsoft.com/en-us/rest/api/storageservices/blob-service-rest-api"
+	azure_data_explorer:                        "https://azure.microsoft.com/en-us/services/data-explorer/"
+	azure_data_explorer_queued_ingestion:       "https://docs.microsoft.com/en-us/azure/data-explorer/kusto/api/netfx/kusto-ingest-client-rest"
+	azure_event_hubs:                           "https://azure.microsoft.com/en-us/services/event-hubs/"
+	azure_event_hubs_kafka:                     "https://docs.microsoft.com/en-us/azure/event-hubs/event-hubs-for-kafka-ecosystem-overview"
 	azure_monitor:                              "https://azure.microsoft.com/en-us/services/monitor/"
 	azure_monitor_logs_endpoints:               "https://docs.microsoft.com/en-us/rest/api/monitor/"
+	azure_monitor_logs_ingestion_api:           "https://learn.microsoft.com/en-us/azure/azure-monitor/logs/logs-ingestion-api-overview"
+	azure_monitor_data_collection_rules:        "https://learn.microsoft.com/en-us/azure/azure-monitor/essentials/data-collection-rule-overview"
 	base64:                                     "\(wikipedia)/wiki/Base64"
 	base64_padding:                             "\(wikipedia)/wiki/Base64#Output_padding"
 	base64_standard:                            "https://tools.ietf.org/html/rfc4648#section-4"
@@ -104,6 +117,8 @@ urls: {
 	cloudsmith:                                 "https://cloudsmith.io/~timber/repos/vector/packages/"
 	cloudsmith_apt:                             "https://cloudsmith.io/~timber/repos/vector/setup/#formats-deb"
 	cloudsmith_yum:                             "https://cloudsmith.io/~timber/repos/vector/setup/#formats-rpm"
+	confluent_schema_registry:                  "https://docs.confluent.io/platform/current/schema-registry/index.html"
+	confluent_wire_format:                      "https://docs.confluent.io/platform/current/schema-registry/serdes-develop/index.html#wire-format"
 	console:                                    "\(wikipedia)/wiki/System_console"
 	conventional_commits:                       "https://www.conventionalcommits.org"
 	contributing:                               "\(vector_repo)/blob/master/CONTRIBUTING.md#setup"
@@ -162,14 +177,18 @@ urls: {
 	dot_format:                                 "https://graphviz.org/doc/info/lang.html"
 	dpkg:                                       "https://wiki.debian.org/dpkg"
 	dry_code:                                   "\(wikipedia)/wiki/Don%27t_repeat_yourself"
+	ebpf:                                       "https://ebpf.io/"
+	ebpf_btf:                                   "https://docs.kernel.org/bpf/btf.html"
 	cidr:                                       "\(wikipedia)/wiki/Classless_Inter-Domain_Routing"
 	elastic_beats:                              "https://www.elastic.co/beats/"
 	elasticsearch:                              "https://www.elastic.co/products/elasticsearch"
+	elasticsearch_api_keys:                     "https://www.elastic.co/guide/en/elasticsearch/reference/current/security-api-create-api-key.html"
 	elasticsearch_bulk:                         "https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-bulk.html"
 	elasticsearch_data_streams:                 "https://www.elastic.co/guide/en/elasticsearch/reference/current/data-streams.html"
 	elasticsearch_id_field:                     "https://www.elastic.co/guide/en/elasticsearch/reference/current/mapping-id-field.html"
 	elasticsearch_id_performance:               "https://www.elastic.co/guide/en/elasticsearch/reference/master/tune-for-indexing-speed.html#_use_auto_generated_ids"
 	elasticsearch_ignore_malformed:             "https://www.elastic.co/guide/en/elasticsearch/reference/current/ignore-malformed.html"
+	elasticsearch_service_tokens:               "https://www.elastic.co/guide/en/elasticsearch/reference/current/service-accounts.html#service-accounts-tokens"
 	encoding_charset_labels:                    "https://encoding.spec.whatwg.org/#concept-encoding-get"
 	encoding_standard:                          "https://encoding.spec.whatwg.org/"
 	endler_dev:                                 "https://endler.dev/"
@@ -194,7 +213,13 @@ urls: {
 	gcp_authentication_api_key:                 "\(gcp)/docs/authentication/api-keys"
 	gcp_authentication_server_to_server:        "\(gcp)/docs/authentication/production"
 	gcp_authentication_service_account:         "\(gcp)/docs/authentication/production#obtaining_and_providing_service_account_credentials_manually"
+	gcp_workload_identity_federation:           "\(gcp)/iam/docs/workload-identity-federation"
+	gcp_bigquery:                               "\(gcp)/bigquery"
+	gcp_bigquery_insert_all:                    "\(gcp)/bigquery/docs/reference/rest/v2/tabledata/insertAll"
+	gcp_bigquery_insert_id:                     "\(gcp)/bigquery/streaming-data-into-bigquery#dataconsistency"
 	gcp_cloud_storage:                          "\(gcp)/storage"
+	gcp_cloud_trace:                            "\(gcp)/trace"
+	gcp_cloud_trace_batch_write:                "\(gcp)/trace/docs/reference/v2/rest/v2/projects.traces/batchWrite"
 	gcp_chronicle:                              "https://chronicle.security"
 	gcp_folders:                                "\(gcp)/resource-manager/docs/creating-managing-folders"
 	gcp_pubsub:                                 "\(gcp)/pubsub/"
@@ -213,6 +238,9 @@ urls: {
 	gcs_predefined_acl:                         "\(gcp)/storage/docs/access-control/lists#predefined-acl"
 	gcs_storage_classes:                        "\(gcp)/storage/docs/storage-classes"
 	gcs_custom_metadata:                        "\(gcp)/storage/docs/metadata#custom-metadata"
+	gcs_csek:                                   "\(gcp)/storage/docs/encryption/customer-supplied-keys"
+	gcs_cmek:                                   "\(gcp)/storage/docs/encryption/customer-managed-keys"
+	gcs_pubsub_notifications:                   "\(gcp)/storage/docs/pubsub-notifications"
 	git:                                        "https://git-scm.com/"
 	github:                                     "https://github.com"
 	github_protected_branches:                  "https://help.github.com/en/github/administering-a-repository/about-protected-branches"
@@ -222,15 +250,19 @@ urls: {
 	graphql:                                    "https://graphql.org"
 	graphql_playground:                         "\(github)/graphql/graphql-playground"
 	graphviz:                                   "https://graphviz.org/"
+	graylog_gelf:                               "https://go2docs.graylog.org/current/getting_in_log_data/gelf.html"
+	graylog_gelf_chunking:                      "https://go2docs.graylog.org/current/getting_in_log_data/gelf.html#GELFviaUDP"
 	grok:                                       "https://grokdebug.herokuapp.com/"
 	grok_debugger:                              "https://grokdebug.herokuapp.com/"
 	grok_patterns:                              "\(github)/daschl/grok/tree/master/patterns"
+	grpc:                                       "https://grpc.io"
 	gzip:                                       "https://www.gzip.org/"
 	haproxy:                                    "https://www.haproxy.org/"
 	helm:                                       "https://helm.sh/"
 	heroku:                                     "https://www.heroku.com"
 	heroku_http_log_drain:                      "https://devcenter.heroku.com/articles/log-drains#https-drains"
 	heroku_start:                               "https://devcenter.heroku.com/start"
+	hmac:                                       "\(wikipedia)/wiki/HMAC"
 	homebrew:                                   "https://brew.sh/"
 	homebrew_services:                          "\(github)/Homebrew/homebrew-services"
 	honeycomb:                                  "https://honeycomb.io"
@@ -262,11 +294,16 @@ urls: {
 	iso_8601:                                   "\(wikipedia)/wiki/ISO_8601"
 	iso3166_2:                                  "\(wikipedia)/wiki/ISO_3166-2"
 	issue_1694:                                 "\(vector_repo)/issues/1694"
+	journal_export_format:                      "https://systemd.io/JOURNAL_EXPORT_FORMATS/#journal-export-format"
+	journal_upload:                             "https://www.freedesktop.org/software/systemd/man/systemd-journal-upload.service.html"
 	journalctl:                                 "https://www.freedesktop.org/software/systemd/man/journalctl.html"
 	journald:                                   "https://www.freedesktop.org/software/systemd/man/systemd-journald.service.html"
 	json:                                       "\(wikipedia)/wiki/JSON"
+	json_path:                                  "https://goessner.net/articles/JsonPath/"
+	json_schema:                                "https://json-schema.org/"
 	json_types:                                 "\(wikipedia)/wiki/JSON#Data_types_and_syntax"
 	jsonnet:                                    "https://jsonnet.org/"
+	jwt:                                        "https://datatracker.ietf.org/doc/html/rfc7519"
 	kafka:                                      "https://kafka.apache.org/"
 	kafka_partitioning_docs:                    "https://cwiki.apache.org/confluence/display/KAFKA/A+Guide+To+The+Kafka+Protocol#AGuideToTheKafkaProtocol-Partitioningandbootstrapping"
 	kafka_protocol:                             "https://kafka.apache.org/protocol"
@@ -338,7 +375,12 @@ urls: {
 	mongodb:                                    "https://www.mongodb.com"
 	mongodb_command_server_status:              "https://docs.mongodb.com/manual/reference/command/serverStatus/"
 	mongodb_connection_string_uri_format:       "https://docs.mongodb.com/manual/reference/connection-string/"
+	mqtt:                                       "https://mqtt.org/"
+	msgpack:                                    "https://msgpack.org/"
+	msgpack_timestamp:                          "\(github)/msgpack/msgpack/blob/master/spec.md#timestamp-extension-type"
 	musl_builder_docker_image:                  "\(vector_repo)/blob/master/scripts/ci-docker-images/builder-x86_64-unknown-linux-musl/Dockerfile"
+	mysql:                                      "https://www.mysql.com/"
+	mysql_connection_url:                       "https://docs.rs/mysql_async/latest/mysql_async/struct.Opts.html#url-parameters"
 	native_proto_schema:                        "\(vector_repo)/blob/master/lib/vector-core/proto/event.proto"
 	native_json_schema:                         "\(vector_repo)/blob/master/lib/codecs/tests/data/native_encoding/schema.cue"
 	nats:                                       "https://nats.io/"
@@ -360,9 +402,11 @@ urls: {
 	nix:                                        "https://nixos.org/nix/"
 	nixos:                                      "https://nixos.org/"
 	nixpkgs_9682:                               "\(github)/NixOS/nixpkgs/issues/9682"
+	ocsf:                                       "https://schema.ocsf.io/"
 	openssl:                                    "https://www.openssl.org/"
 	opentelemetry:                              "https://opentelemetry.io/"
 	order_of_ops:                               "\(wikipedia)/wiki/Order_of_operations"
+	parquet:                                    "https://parquet.apache.org/"
 	papertrail:                                 "https://www.papertrail.com/"
 	papertrail_syslog:                          "https://help.papertrailapp.com/kb/how-it-works/http-api/#submitting-log-messages"
 	perl_windows:                               "https://www.perl.org/get.html#win32"
@@ -377,6 +421,8 @@ urls: {
 	percent_encoding_www_form_urlencoded:       "https://url.spec.whatwg.org/#application-x-www-form-urlencoded-percent-encode-set"
 	posix_acls:                                 "https://www.usenix.org/legacy/publications/library/proceedings/usenix03/tech/freenix03/full_papers/gruenbacher/gruenbacher_html/main.html"
 	postgresql:                                 "https://www.postgresql.org/"
+	postgresql_connection_string:               "https://www.postgresql.org/docs/current/libpq-connect.html#LIBPQ-CONNSTRING"
+	postgresql_copy:                            "https://www.postgresql.org/docs/current/sql-copy.html"
 	postgresql_csvlog:                          "https://www.postgresql.org/docs/current/runtime-config-logging.html#RUNTIME-CONFIG-LOGGING-CSVLOG"
 	postgresql_matching:                        "https://www.postgresql.org/docs/current/functions-matching.html#FUNCTIONS-POSIX-REGEXP"
 	procfs:                                     "https://en.wikipedia.org/wiki/Procfs#:~:text=The%20proc%20filesystem%20(procfs)%20is,in%20the%20kernel%20than%20traditional"
@@ -397,6 +443,10 @@ urls: {
 	protobuf:                                   "https://developers.google.com/protocol-buffers"
 	pulsar:                                     "https://pulsar.apache.org/"
 	pulsar_protocol:                            "https://pulsar.apache.org/docs/en/develop-binary-protocol/"
+	questdb:                                    "https://questdb.io/"
+	questdb_ilp:                                "https://questdb.io/docs/reference/api/ilp/overview/"
+	questdb_types:                              "https://questdb.io/docs/reference/sql/datatypes/"
+	rabbitmq:                                   "https://www.rabbitmq.com/"
 	raspbian:                                   "https://www.raspbian.org/"
 	rdkafka:                                    "\(github)/edenhill/librdkafka"
 	regex:                                      "\(wikipedia)/wiki/Regular_expression"
@@ -479,7 +529,10 @@ urls: {
 	systemd_resolved:                           "https://wiki.archlinux.org/index.php/Systemd-resolved"
 	tcp:                                        "\(wikipedia)/wiki/Transmission_Control_Protocol"
 	team:                                       "/community#team"
+	tempo:                                      "https://grafana.com/oss/tempo/"
+	tempo_multitenancy:                         "https://grafana.com/docs/tempo/latest/operations/multitenancy/"
 	timber:                                     "https://timber.io"
+	timescaledb:                                "https://www.timescale.com/"
 	toml:                                       "\(github)/toml-lang/toml"
 	toml_array:                                 "\(github)/toml-lang/toml#array"
 	toml_table:                                 "\(github)/toml-lang/toml#table"
@@ -624,6 +677,8 @@ urls: {
 	websocket:                                  "\(wikipedia)/wiki/WebSocket"
 	wikipedia:                                  "https://en.wikipedia.org"
 	windows:                                    "https://www.microsoft.com/en-us/windows"
+	windows_event_log:                          "https://learn.microsoft.com/en-us/windows/win32/wes/windows-event-log"
+	windows_event_log_queries:                  "https://learn.microsoft.com/en-us/windows/win32/wes/consuming-events"
 	windows_installer:                          "\(wikipedia)/wiki/Windows_Installer"
 	windows_service:                            "https://docs.microsoft.com/en-us/powershell/module/microsoft.powershell.management/new-service"
 	woothee:                                    "https://github.com/woothee/woothee"